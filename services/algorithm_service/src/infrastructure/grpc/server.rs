@@ -6,6 +6,14 @@ use tracing::info;
 use super::service::AlgorithmServiceImpl;
 use crate::proto::effect::services::algorithm::algorithm_service_server::AlgorithmServiceServer;
 
+/// 接続済みのプールから gRPC サービスを組み立てる
+///
+/// 統合バイナリ（effect_monolith）は他のサービスと同じサーバーに登録する
+#[must_use]
+pub fn build_service(db_pool: sqlx::PgPool) -> AlgorithmServiceServer<AlgorithmServiceImpl> {
+    AlgorithmServiceServer::new(AlgorithmServiceImpl::new(db_pool))
+}
+
 /// gRPC サーバーを起動
 ///
 /// # Errors
@@ -21,8 +29,7 @@ pub async fn start(addr: SocketAddr) -> Result<(), Box<dyn std::error::Error>> {
     let db_pool = sqlx::PgPool::connect(&database_url).await?;
 
     // サービスの作成
-    let service = AlgorithmServiceImpl::new(db_pool);
-    let algorithm_server = AlgorithmServiceServer::new(service);
+    let algorithm_server = build_service(db_pool);
 
     // サーバーの起動
     Server::builder()
@@ -2,7 +2,8 @@
 
 use std::sync::Arc;
 
-use chrono::{Datelike, Timelike, Utc};
+use chrono::{Datelike, Timelike};
+use shared_kernel::{Clock, SystemClock, from_proto_timestamp, to_proto_timestamp};
 use tonic::{Request, Response, Status};
 
 use super::conversion::i32_to_u32;
@@ -62,6 +63,8 @@ pub struct AlgorithmServiceImpl {
     statistics_repo:     Arc<dyn super::super::repository::statistics::StatisticsRepository>,
     /// 戦略リポジトリ
     strategy_repo:       Arc<dyn super::super::repository::strategy::LearningStrategyRepository>,
+    /// スケジュールの基準にする時計
+    clock:               Arc<dyn Clock>,
 }
 
 impl AlgorithmServiceImpl {
@@ -80,10 +83,18 @@ impl AlgorithmServiceImpl {
             review_history_repo: Arc::new(ReviewHistoryRepo::new(db_pool.clone())),
             statistics_repo: Arc::new(StatisticsRepo::new(db_pool.clone())),
             strategy_repo: Arc::new(StrategyRepo::new(db_pool.clone())),
+            clock: Arc::new(SystemClock),
             db_pool,
         }
     }
 
+    /// スケジュールの基準にする時計を差し替える（テストで時刻を固定する）
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// SM-2 アルゴリズムで難易度から初期値を計算
     const fn calculate_initial_difficulty(user_level: i32, item_level: i32) -> u8 {
         // ユーザーレベルと項目レベルの差から難易度を計算
@@ -128,7 +139,7 @@ impl AlgorithmService for AlgorithmServiceImpl {
         let easy_factor = EasyFactor::initial();
         let repetition = Repetition::initial();
         let interval = Interval::first();
-        let current_time = Utc::now();
+        let current_time = self.clock.now();
 
         // SM-2 計算
         let result =
@@ -147,18 +158,8 @@ impl AlgorithmService for AlgorithmServiceImpl {
             interval_days:            result.interval.days(),
             mastery_level:            1, // Beginner
             retention_rate:           0.0,
-            next_review_date:         Some(prost_types::Timestamp {
-                seconds: result.next_review_date.timestamp(),
-                // timestamp_subsec_nanos() returns u32 (0..1_000_000_000), safe to cast to i32
-                #[allow(clippy::cast_possible_wrap)]
-                nanos: result.next_review_date.timestamp_subsec_nanos() as i32,
-            }),
-            last_reviewed_at:         Some(prost_types::Timestamp {
-                seconds: current_time.timestamp(),
-                // timestamp_subsec_nanos() returns u32 (0..1_000_000_000), safe to cast to i32
-                #[allow(clippy::cast_possible_wrap)]
-                nanos: current_time.timestamp_subsec_nanos() as i32,
-            }),
+            next_review_date:         Some(to_proto_timestamp(&result.next_review_date)),
+            last_reviewed_at:         Some(to_proto_timestamp(&current_time)),
             total_reviews:            0,
             correct_count:            0,
             incorrect_count:          0,
@@ -258,7 +259,7 @@ impl AlgorithmService for AlgorithmServiceImpl {
         )
         .map_err(|e| Status::invalid_argument(format!("Invalid interval: {e}")))?;
 
-        let current_time = Utc::now();
+        let current_time = self.clock.now();
 
         // SM-2 計算
         let result =
@@ -277,18 +278,8 @@ impl AlgorithmService for AlgorithmServiceImpl {
             interval_days:            result.interval.days(),
             mastery_level:            1,   // Beginner // TODO: 計算
             retention_rate:           0.0, // TODO: 計算
-            next_review_date:         Some(prost_types::Timestamp {
-                seconds: result.next_review_date.timestamp(),
-                // timestamp_subsec_nanos() returns u32 (0..1_000_000_000), safe to cast to i32
-                #[allow(clippy::cast_possible_wrap)]
-                nanos: result.next_review_date.timestamp_subsec_nanos() as i32,
-            }),
-            last_reviewed_at:         Some(prost_types::Timestamp {
-                seconds: current_time.timestamp(),
-                // timestamp_subsec_nanos() returns u32 (0..1_000_000_000), safe to cast to i32
-                #[allow(clippy::cast_possible_wrap)]
-                nanos: current_time.timestamp_subsec_nanos() as i32,
-            }),
+            next_review_date:         Some(to_proto_timestamp(&result.next_review_date)),
+            last_reviewed_at:         Some(to_proto_timestamp(&current_time)),
             total_reviews:            i32_to_u32(current_state.total_reviews + 1)?,
             correct_count:            i32_to_u32(current_state.correct_count)?
                 + u32::from(req.judgment >= 3),
@@ -434,8 +425,8 @@ impl AlgorithmService for AlgorithmServiceImpl {
         // 基準時刻を決定（指定がなければ現在時刻）
         let as_of = req
             .as_of
-            .and_then(|ts| chrono::DateTime::from_timestamp(ts.seconds, ts.nanos as u32))
-            .unwrap_or_else(Utc::now);
+            .and_then(|ts| from_proto_timestamp(&ts).ok())
+            .unwrap_or_else(|| self.clock.now());
 
         // Repository から due items を取得
         let db_items = self
@@ -467,26 +458,14 @@ impl AlgorithmService for AlgorithmServiceImpl {
                         interval_days:            i32_to_u32(item.interval_days)?,
                         mastery_level:            item.mastery_level,
                         retention_rate:           item.retention_rate,
-                        next_review_date:         item.next_review_date.map(|dt| {
-                            prost_types::Timestamp {
-                                seconds: dt.timestamp(),
-                                nanos:   {
-                                    #[allow(clippy::cast_possible_wrap)]
-                                    let value = dt.timestamp_subsec_nanos() as i32;
-                                    value
-                                },
-                            }
-                        }),
-                        last_reviewed_at:         item.last_reviewed_at.map(|dt| {
-                            prost_types::Timestamp {
-                                seconds: dt.timestamp(),
-                                nanos:   {
-                                    #[allow(clippy::cast_possible_wrap)]
-                                    let value = dt.timestamp_subsec_nanos() as i32;
-                                    value
-                                },
-                            }
-                        }),
+                        next_review_date:         item
+                            .next_review_date
+                            .as_ref()
+                            .map(to_proto_timestamp),
+                        last_reviewed_at:         item
+                            .last_reviewed_at
+                            .as_ref()
+                            .map(to_proto_timestamp),
                         total_reviews:            i32_to_u32(item.total_reviews)?,
                         correct_count:            i32_to_u32(item.correct_count)?,
                         incorrect_count:          i32_to_u32(item.incorrect_count)?,
@@ -670,7 +649,7 @@ impl AlgorithmService for AlgorithmServiceImpl {
 
         // 状態を更新
         current_state.easiness_factor = new_factor;
-        current_state.updated_at = Utc::now();
+        current_state.updated_at = self.clock.now();
         current_state.version += 1;
 
         // 新しい係数で次回復習日を再計算
@@ -693,7 +672,9 @@ impl AlgorithmService for AlgorithmServiceImpl {
             repetition,
             interval,
             easy_factor,
-            current_state.last_reviewed_at.unwrap_or_else(Utc::now),
+            current_state
+                .last_reviewed_at
+                .unwrap_or_else(|| self.clock.now()),
         );
 
         current_state.next_review_date = Some(result.next_review_date);
@@ -718,26 +699,14 @@ impl AlgorithmService for AlgorithmServiceImpl {
             interval_days:            i32_to_u32(current_state.interval_days)?,
             mastery_level:            current_state.mastery_level,
             retention_rate:           current_state.retention_rate,
-            next_review_date:         current_state.next_review_date.map(|dt| {
-                prost_types::Timestamp {
-                    seconds: dt.timestamp(),
-                    nanos:   {
-                        #[allow(clippy::cast_possible_wrap)]
-                        let value = dt.timestamp_subsec_nanos() as i32;
-                        value
-                    },
-                }
-            }),
-            last_reviewed_at:         current_state.last_reviewed_at.map(|dt| {
-                prost_types::Timestamp {
-                    seconds: dt.timestamp(),
-                    nanos:   {
-                        #[allow(clippy::cast_possible_wrap)]
-                        let value = dt.timestamp_subsec_nanos() as i32;
-                        value
-                    },
-                }
-            }),
+            next_review_date:         current_state
+                .next_review_date
+                .as_ref()
+                .map(to_proto_timestamp),
+            last_reviewed_at:         current_state
+                .last_reviewed_at
+                .as_ref()
+                .map(to_proto_timestamp),
             total_reviews:            i32_to_u32(current_state.total_reviews + 1)?,
             correct_count:            i32_to_u32(current_state.correct_count)?,
             incorrect_count:          i32_to_u32(current_state.incorrect_count)?,
@@ -1017,14 +986,8 @@ impl AlgorithmService for AlgorithmServiceImpl {
             adaptive_scheduling:   db_strategy.adaptive_scheduling,
             last_adjusted_at:      db_strategy
                 .last_adjusted_at
-                .map(|dt| prost_types::Timestamp {
-                    seconds: dt.timestamp(),
-                    nanos:   {
-                        #[allow(clippy::cast_possible_wrap)]
-                        let value = dt.timestamp_subsec_nanos() as i32;
-                        value
-                    },
-                }),
+                .as_ref()
+                .map(to_proto_timestamp),
         };
 
         let response = GetLearningStrategyResponse {
@@ -1075,7 +1038,7 @@ impl AlgorithmService for AlgorithmServiceImpl {
             db_strategy.learning_speed_factor = speed_factor;
         }
 
-        let current_time = Utc::now();
+        let current_time = self.clock.now();
         db_strategy.last_adjusted_at = Some(current_time);
         db_strategy.updated_at = current_time;
         db_strategy.version += 1;
@@ -1096,10 +1059,7 @@ impl AlgorithmService for AlgorithmServiceImpl {
             learning_speed_factor: db_strategy.learning_speed_factor,
             retention_priority:    db_strategy.retention_priority,
             adaptive_scheduling:   db_strategy.adaptive_scheduling,
-            last_adjusted_at:      Some(prost_types::Timestamp {
-                seconds: current_time.timestamp(),
-                nanos:   0,
-            }),
+            last_adjusted_at:      Some(to_proto_timestamp(&current_time)),
         };
 
         let response = AdjustStrategyResponse {
@@ -1137,14 +1097,7 @@ impl AlgorithmService for AlgorithmServiceImpl {
             .map(
                 |h| -> Result<crate::proto::effect::services::algorithm::ReviewHistory, Status> {
                     Ok(crate::proto::effect::services::algorithm::ReviewHistory {
-                        reviewed_at:      Some(prost_types::Timestamp {
-                            seconds: h.reviewed_at.timestamp(),
-                            nanos:   {
-                                #[allow(clippy::cast_possible_wrap)]
-                                let value = h.reviewed_at.timestamp_subsec_nanos() as i32;
-                                value
-                            },
-                        }),
+                        reviewed_at:      Some(to_proto_timestamp(&h.reviewed_at)),
                         judgment:         h.judgment,
                         response_time_ms: i32_to_u32(h.response_time_ms)?,
                         interval_days:    i32_to_u32(h.interval_days)?,
@@ -1170,26 +1123,14 @@ impl AlgorithmService for AlgorithmServiceImpl {
                 interval_days:            i32_to_u32(db_state.interval_days)?,
                 mastery_level:            db_state.mastery_level,
                 retention_rate:           db_state.retention_rate,
-                next_review_date:         db_state.next_review_date.map(|dt| {
-                    prost_types::Timestamp {
-                        seconds: dt.timestamp(),
-                        nanos:   {
-                            #[allow(clippy::cast_possible_wrap)]
-                            let value = dt.timestamp_subsec_nanos() as i32;
-                            value
-                        },
-                    }
-                }),
-                last_reviewed_at:         db_state.last_reviewed_at.map(|dt| {
-                    prost_types::Timestamp {
-                        seconds: dt.timestamp(),
-                        nanos:   {
-                            #[allow(clippy::cast_possible_wrap)]
-                            let value = dt.timestamp_subsec_nanos() as i32;
-                            value
-                        },
-                    }
-                }),
+                next_review_date:         db_state
+                    .next_review_date
+                    .as_ref()
+                    .map(to_proto_timestamp),
+                last_reviewed_at:         db_state
+                    .last_reviewed_at
+                    .as_ref()
+                    .map(to_proto_timestamp),
                 total_reviews:            i32_to_u32(db_state.total_reviews)?,
                 correct_count:            i32_to_u32(db_state.correct_count)?,
                 incorrect_count:          i32_to_u32(db_state.incorrect_count)?,
@@ -1232,7 +1173,7 @@ impl AlgorithmService for AlgorithmServiceImpl {
             .map_err(|e| Status::invalid_argument(format!("Invalid user_id: {e}")))?;
 
         // 分析期間の復習履歴を取得
-        let since = Utc::now() - chrono::Duration::days(i64::from(days_to_analyze));
+        let since = self.clock.now() - chrono::Duration::days(i64::from(days_to_analyze));
         let reviews = self
             .review_history_repo
             .get_reviews_since(user_uuid, since)
@@ -1371,7 +1312,7 @@ impl AlgorithmService for AlgorithmServiceImpl {
         // バーンアウトリスクを計算
         let recent_week_reviews = reviews
             .iter()
-            .filter(|r| r.reviewed_at > Utc::now() - chrono::Duration::days(7))
+            .filter(|r| r.reviewed_at > self.clock.now() - chrono::Duration::days(7))
             .count() as f32;
         let avg_weekly_reviews = reviews.len() as f32 / (days_to_analyze as f32 / 7.0);
 
@@ -1385,7 +1326,7 @@ impl AlgorithmService for AlgorithmServiceImpl {
         // 最近の正答率低下もリスク要因
         let recent_accuracy = reviews
             .iter()
-            .filter(|r| r.reviewed_at > Utc::now() - chrono::Duration::days(7))
+            .filter(|r| r.reviewed_at > self.clock.now() - chrono::Duration::days(7))
             .map(|r| if r.judgment >= 3 { 1.0 } else { 0.0 })
             .sum::<f32>()
             / recent_week_reviews.max(1.0);
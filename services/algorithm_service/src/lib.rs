@@ -22,3 +22,6 @@ pub mod infrastructure;
 
 /// Protocol Buffers 生成コード
 pub mod proto;
+
+/// 学習アルゴリズムのテーブルのマイグレーション
+pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
@@ -2,10 +2,7 @@
 
 use std::net::SocketAddr;
 
-use algorithm_service::{
-    infrastructure::grpc::service::AlgorithmServiceImpl,
-    proto::effect::services::algorithm::algorithm_service_server::AlgorithmServiceServer,
-};
+use algorithm_service::infrastructure::grpc::server::build_service;
 use tonic::transport::Server;
 use tracing::info;
 
@@ -30,8 +27,7 @@ pub async fn start(config: ServiceConfig) -> Result<(), Box<dyn std::error::Erro
     info!("Connected to database");
 
     // サービスの作成
-    let service = AlgorithmServiceImpl::new(db_pool);
-    let algorithm_server = AlgorithmServiceServer::new(service);
+    let algorithm_server = build_service(db_pool);
 
     // サーバーの起動
     Server::builder()
@@ -0,0 +1,17 @@
+-- イベントタイプごとの互換性モード
+-- 行がないイベントタイプはレジストリ全体の既定値（設定 registry.compatibility）を使う
+CREATE TABLE IF NOT EXISTS event_type_compatibility (
+    event_type VARCHAR(255) PRIMARY KEY,
+    mode VARCHAR(32) NOT NULL CHECK (
+        mode IN (
+            'NONE',
+            'BACKWARD',
+            'BACKWARD_TRANSITIVE',
+            'FORWARD',
+            'FORWARD_TRANSITIVE',
+            'FULL',
+            'FULL_TRANSITIVE'
+        )
+    ),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
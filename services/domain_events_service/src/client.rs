@@ -1,6 +1,9 @@
 //! Domain Events Service クライアントライブラリ
 //!
 //! 他のサービスから Domain Events Service に接続するためのクライアント
+//!
+//! `event-bus` feature を有効にすると、[`Client`] を
+//! `shared_event_bus::ValidatingEventBus` の検証に使える
 
 use tonic::transport::Channel;
 
@@ -14,6 +17,7 @@ use crate::grpc::proto::{
 };
 
 /// Domain Events クライアント
+#[derive(Clone)]
 pub struct Client {
     inner: DomainEventsServiceClient<Channel>,
 }
@@ -93,3 +97,34 @@ impl Client {
         Ok(response.into_inner().event_types)
     }
 }
+
+#[cfg(feature = "event-bus")]
+#[async_trait::async_trait]
+impl shared_event_bus::EventValidator for Client {
+    /// 最新のスキーマで検証する
+    async fn validate(
+        &self,
+        event_type: &str,
+        event: &[u8],
+    ) -> Result<(), shared_kernel::EventError> {
+        // validate_event は &mut self を取るので、チャネルを共有する複製で呼ぶ
+        let is_valid = self
+            .clone()
+            .validate_event(event_type.to_string(), event.to_vec(), None)
+            .await
+            .map_err(|e| {
+                shared_kernel::EventError::Bus(format!(
+                    "Failed to validate {event_type} against the schema registry: {}",
+                    e.message()
+                ))
+            })?;
+
+        if is_valid {
+            Ok(())
+        } else {
+            Err(shared_kernel::EventError::InvalidEvent(format!(
+                "{event_type} does not match its registered schema"
+            )))
+        }
+    }
+}
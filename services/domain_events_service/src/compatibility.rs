@@ -0,0 +1,817 @@
+//! スキーマの互換性チェック
+//!
+//! 登録するスキーマを、イベントタイプの互換性モードに従って以前のバージョンと
+//! 比較する。比較は JSON Schema のサブセット（`type`、`required`、`enum`、
+//! `properties`、`additionalProperties`、`items`、`$ref`）で行い、
+//! 「読み手のスキーマが書き手のスキーマで有効なドキュメントをすべて受け付けるか」
+//! を調べる。
+//!
+//! - BACKWARD: 新しいスキーマ（読み手）が以前のスキーマで書かれたイベントを読める
+//! - FORWARD: 以前のスキーマ（読み手）が新しいスキーマで書かれたイベントを読める
+//!   （古いコンシューマーを残したままプロデューサーを先にデプロイできる）
+//! - FULL: 両方向
+//!
+//! `_TRANSITIVE`
+//! 付きのモードは最新版だけでなく、以前のすべてのバージョンと比較する。
+
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+
+/// 互換性モード
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CompatibilityMode {
+    /// チェックしない
+    None,
+    /// 新しいスキーマが最新版で書かれたイベントを読める
+    #[default]
+    Backward,
+    /// 新しいスキーマが以前のすべてのバージョンで書かれたイベントを読める
+    BackwardTransitive,
+    /// 最新版のスキーマが新しいスキーマで書かれたイベントを読める
+    Forward,
+    /// 以前のすべてのバージョンが新しいスキーマで書かれたイベントを読める
+    ForwardTransitive,
+    /// BACKWARD と FORWARD の両方
+    Full,
+    /// `BACKWARD_TRANSITIVE` と `FORWARD_TRANSITIVE` の両方
+    FullTransitive,
+}
+
+impl CompatibilityMode {
+    /// すべてのモード
+    pub const ALL: [Self; 7] = [
+        Self::None,
+        Self::Backward,
+        Self::BackwardTransitive,
+        Self::Forward,
+        Self::ForwardTransitive,
+        Self::Full,
+        Self::FullTransitive,
+    ];
+
+    /// 新しいスキーマが以前のスキーマのイベントを読めることを求めるか
+    #[must_use]
+    pub const fn checks_backward(self) -> bool {
+        matches!(
+            self,
+            Self::Backward | Self::BackwardTransitive | Self::Full | Self::FullTransitive
+        )
+    }
+
+    /// 以前のスキーマが新しいスキーマのイベントを読めることを求めるか
+    #[must_use]
+    pub const fn checks_forward(self) -> bool {
+        matches!(
+            self,
+            Self::Forward | Self::ForwardTransitive | Self::Full | Self::FullTransitive
+        )
+    }
+
+    /// 以前のすべてのバージョンと比較するか
+    #[must_use]
+    pub const fn is_transitive(self) -> bool {
+        matches!(
+            self,
+            Self::BackwardTransitive | Self::ForwardTransitive | Self::FullTransitive
+        )
+    }
+
+    /// 設定ファイルや API で使う名前
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "NONE",
+            Self::Backward => "BACKWARD",
+            Self::BackwardTransitive => "BACKWARD_TRANSITIVE",
+            Self::Forward => "FORWARD",
+            Self::ForwardTransitive => "FORWARD_TRANSITIVE",
+            Self::Full => "FULL",
+            Self::FullTransitive => "FULL_TRANSITIVE",
+        }
+    }
+
+    /// `other` が求めるチェックをすべて含むか
+    #[must_use]
+    pub const fn is_at_least_as_strict_as(self, other: Self) -> bool {
+        (self.checks_backward() || !other.checks_backward())
+            && (self.checks_forward() || !other.checks_forward())
+            && (self.is_transitive() || !other.is_transitive())
+    }
+
+    /// モードの変更を検証する
+    ///
+    /// 厳しくする変更だけを受け付ける。緩める変更は `force` を指定した場合のみ
+    ///
+    /// # Errors
+    ///
+    /// - `CompatibilityError::ModeLoosened` - `force`
+    ///   なしでチェックを緩めようとした場合
+    pub const fn validate_change(self, to: Self, force: bool) -> Result<(), CompatibilityError> {
+        if force || to.is_at_least_as_strict_as(self) {
+            Ok(())
+        } else {
+            Err(CompatibilityError::ModeLoosened { from: self, to })
+        }
+    }
+}
+
+impl fmt::Display for CompatibilityMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CompatibilityMode {
+    type Err = CompatibilityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_ascii_uppercase();
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str() == normalized)
+            .ok_or_else(|| CompatibilityError::UnknownMode(s.to_string()))
+    }
+}
+
+/// 比較の方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// 新しいスキーマが読み手
+    Backward,
+    /// 以前のスキーマが読み手
+    Forward,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Backward => "backward",
+            Self::Forward => "forward",
+        })
+    }
+}
+
+/// 互換性の違反
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 比較した以前のバージョン
+    pub version:   i32,
+    /// 新しいスキーマを読み手・書き手のどちらとして比較したか
+    pub direction: Direction,
+    /// 違反箇所（読み手のスキーマ内の JSON Pointer）
+    pub path:      String,
+    /// 違反の内容
+    pub message:   String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(
+            f,
+            "[{} vs v{}] {}: {}",
+            self.direction, self.version, path, self.message
+        )
+    }
+}
+
+/// 互換性チェックのエラー
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CompatibilityError {
+    /// スキーマを JSON として解釈できない
+    #[error("Invalid schema definition (version {version:?}): {message}")]
+    InvalidSchema {
+        /// 以前のバージョン（None は新しいスキーマ）
+        version: Option<i32>,
+        /// 解析エラー
+        message: String,
+    },
+
+    /// 互換性モードに違反
+    #[error("Schema is not {mode} compatible: {}", format_violations(.violations))]
+    Incompatible {
+        /// イベントタイプの互換性モード
+        mode:       CompatibilityMode,
+        /// 違反の一覧
+        violations: Vec<Violation>,
+    },
+
+    /// 不明な互換性モード
+    #[error("Unknown compatibility mode: {0}")]
+    UnknownMode(String),
+
+    /// `force` なしでチェックを緩めようとした
+    #[error("Compatibility mode can only be tightened without force: {from} -> {to}")]
+    ModeLoosened {
+        /// 現在のモード
+        from: CompatibilityMode,
+        /// 変更後のモード
+        to:   CompatibilityMode,
+    },
+}
+
+fn format_violations(violations: &[Violation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// 新しいスキーマを以前のバージョンと比較する
+///
+/// `previous` は (バージョン, 定義) をバージョンの昇順で渡す。推移的でない
+/// モードでは最新版（末尾）とだけ比較する
+///
+/// # Errors
+///
+/// - `CompatibilityError::InvalidSchema` - スキーマを JSON
+///   として解釈できない場合
+/// - `CompatibilityError::Incompatible` - 互換性モードに違反する場合
+pub fn check_compatibility(
+    mode: CompatibilityMode,
+    definition: &str,
+    previous: &[(i32, String)],
+) -> Result<(), CompatibilityError> {
+    let new_schema = parse_schema(definition, None)?;
+    if mode == CompatibilityMode::None {
+        return Ok(());
+    }
+
+    let targets = if mode.is_transitive() {
+        previous
+    } else {
+        previous.last().map_or(&[][..], std::slice::from_ref)
+    };
+
+    let mut violations = Vec::new();
+    for (version, old_definition) in targets {
+        let old_schema = parse_schema(old_definition, Some(*version))?;
+        let old_label = format!("v{version}");
+
+        if mode.checks_backward() {
+            violations.extend(
+                reader_accepts(&new_schema, &old_schema, "new schema", &old_label)
+                    .into_iter()
+                    .map(|(path, message)| Violation {
+                        version: *version,
+                        direction: Direction::Backward,
+                        path,
+                        message,
+                    }),
+            );
+        }
+        if mode.checks_forward() {
+            violations.extend(
+                reader_accepts(&old_schema, &new_schema, &old_label, "new schema")
+                    .into_iter()
+                    .map(|(path, message)| Violation {
+                        version: *version,
+                        direction: Direction::Forward,
+                        path,
+                        message,
+                    }),
+            );
+        }
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(CompatibilityError::Incompatible { mode, violations })
+    }
+}
+
+fn parse_schema(definition: &str, version: Option<i32>) -> Result<JsonValue, CompatibilityError> {
+    serde_json::from_str(definition).map_err(|e| CompatibilityError::InvalidSchema {
+        version,
+        message: e.to_string(),
+    })
+}
+
+/// 読み手のスキーマが書き手のスキーマで有効なドキュメントをすべて受け付けるか調べ、
+/// 違反を (JSON Pointer, メッセージ) で返す
+#[must_use]
+pub fn reader_accepts(
+    reader: &JsonValue,
+    writer: &JsonValue,
+    reader_label: &str,
+    writer_label: &str,
+) -> Vec<(String, String)> {
+    let mut comparison = Comparison {
+        reader_label,
+        writer_label,
+        violations: Vec::new(),
+    };
+    comparison.compare(reader, writer, "");
+    comparison.violations
+}
+
+struct Comparison<'a> {
+    reader_label: &'a str,
+    writer_label: &'a str,
+    violations:   Vec<(String, String)>,
+}
+
+impl Comparison<'_> {
+    fn violation(&mut self, path: String, message: String) {
+        self.violations.push((path, message));
+    }
+
+    fn compare(&mut self, reader: &JsonValue, writer: &JsonValue, path: &str) {
+        let (reader_label, writer_label) = (self.reader_label, self.writer_label);
+        let empty = Map::new();
+
+        // 真偽値のスキーマ: true はすべてを、false は何も受け付けない
+        let reader = match reader {
+            JsonValue::Bool(true) => return,
+            JsonValue::Bool(false) => {
+                if *writer != JsonValue::Bool(false) {
+                    self.violation(
+                        path.to_string(),
+                        format!("{reader_label} rejects every value that {writer_label} allows"),
+                    );
+                }
+                return;
+            },
+            JsonValue::Object(reader) => reader,
+            _ => &empty,
+        };
+        let writer = match writer {
+            JsonValue::Bool(false) => return,
+            JsonValue::Object(writer) => writer,
+            _ => &empty,
+        };
+
+        // 参照先は解決せず、同じ参照かどうかだけを比較する
+        if reader.contains_key("$ref") || writer.contains_key("$ref") {
+            if reader.get("$ref") != writer.get("$ref") {
+                self.violation(
+                    pointer(path, "$ref"),
+                    format!(
+                        "{reader_label} references {} but {writer_label} references {}",
+                        describe(reader.get("$ref")),
+                        describe(writer.get("$ref"))
+                    ),
+                );
+            }
+            return;
+        }
+
+        self.compare_types(reader, writer, path);
+        self.compare_enums(reader, writer, path);
+        self.compare_required(reader, writer, path);
+        self.compare_properties(reader, writer, path);
+
+        match (reader.get("items"), writer.get("items")) {
+            (Some(reader_items), Some(writer_items)) => {
+                self.compare(reader_items, writer_items, &pointer(path, "items"));
+            },
+            (Some(reader_items), None) if *reader_items != JsonValue::Bool(true) => {
+                self.violation(
+                    pointer(path, "items"),
+                    format!(
+                        "{reader_label} constrains array items but {writer_label} allows any items"
+                    ),
+                );
+            },
+            _ => {},
+        }
+    }
+
+    fn compare_types(
+        &mut self,
+        reader: &Map<String, JsonValue>,
+        writer: &Map<String, JsonValue>,
+        path: &str,
+    ) {
+        let Some(reader_types) = types(reader) else {
+            return;
+        };
+        let (reader_label, writer_label) = (self.reader_label, self.writer_label);
+
+        match types(writer) {
+            None => self.violation(
+                pointer(path, "type"),
+                format!(
+                    "{reader_label} requires type {} but {writer_label} allows any type",
+                    join(&reader_types)
+                ),
+            ),
+            Some(writer_types) => {
+                let rejected: BTreeSet<&str> = writer_types
+                    .iter()
+                    .copied()
+                    .filter(|t| {
+                        !(reader_types.contains(t)
+                            || (*t == "integer" && reader_types.contains("number")))
+                    })
+                    .collect();
+                if !rejected.is_empty() {
+                    self.violation(
+                        pointer(path, "type"),
+                        format!(
+                            "{reader_label} does not accept type {} written by {writer_label} \
+                             (accepts {})",
+                            join(&rejected),
+                            join(&reader_types)
+                        ),
+                    );
+                }
+            },
+        }
+    }
+
+    fn compare_enums(
+        &mut self,
+        reader: &Map<String, JsonValue>,
+        writer: &Map<String, JsonValue>,
+        path: &str,
+    ) {
+        let Some(JsonValue::Array(reader_values)) = reader.get("enum") else {
+            return;
+        };
+        let (reader_label, writer_label) = (self.reader_label, self.writer_label);
+
+        match writer.get("enum") {
+            Some(JsonValue::Array(writer_values)) => {
+                let rejected: Vec<String> = writer_values
+                    .iter()
+                    .filter(|value| !reader_values.contains(value))
+                    .map(ToString::to_string)
+                    .collect();
+                if !rejected.is_empty() {
+                    self.violation(
+                        pointer(path, "enum"),
+                        format!(
+                            "{reader_label} does not accept enum values {} written by \
+                             {writer_label}",
+                            rejected.join(", ")
+                        ),
+                    );
+                }
+            },
+            _ => self.violation(
+                pointer(path, "enum"),
+                format!("{reader_label} restricts values to an enum but {writer_label} does not"),
+            ),
+        }
+    }
+
+    fn compare_required(
+        &mut self,
+        reader: &Map<String, JsonValue>,
+        writer: &Map<String, JsonValue>,
+        path: &str,
+    ) {
+        let writer_required = string_set(writer.get("required"));
+        let (reader_label, writer_label) = (self.reader_label, self.writer_label);
+
+        for field in string_set(reader.get("required")) {
+            if !writer_required.contains(field) {
+                self.violation(
+                    pointer(&pointer(path, "properties"), field),
+                    format!(
+                        "field `{field}` is required by {reader_label} but optional in \
+                         {writer_label}"
+                    ),
+                );
+            }
+        }
+    }
+
+    fn compare_properties(
+        &mut self,
+        reader: &Map<String, JsonValue>,
+        writer: &Map<String, JsonValue>,
+        path: &str,
+    ) {
+        let empty = Map::new();
+        let reader_properties = properties(reader).unwrap_or(&empty);
+        let reader_additional = reader.get("additionalProperties");
+        let (reader_label, writer_label) = (self.reader_label, self.writer_label);
+
+        // 読み手だけが定義するプロパティは、書き手のドキュメントに現れても
+        // 任意の値になりうるが、追加を許すスキーマ同士の慣習に従い違反としない
+        for (name, writer_property) in properties(writer).unwrap_or(&empty) {
+            let property_path = pointer(&pointer(path, "properties"), name);
+            match (reader_properties.get(name), reader_additional) {
+                (Some(reader_property), _) => {
+                    self.compare(reader_property, writer_property, &property_path);
+                },
+                (None, Some(JsonValue::Bool(false))) => self.violation(
+                    property_path,
+                    format!(
+                        "property `{name}` written by {writer_label} is not allowed by \
+                         {reader_label} (additionalProperties: false)"
+                    ),
+                ),
+                (None, Some(additional @ JsonValue::Object(_))) => {
+                    self.compare(
+                        additional,
+                        writer_property,
+                        &pointer(path, "additionalProperties"),
+                    );
+                },
+                (None, _) => {},
+            }
+        }
+
+        match (reader_additional, writer.get("additionalProperties")) {
+            (Some(JsonValue::Bool(false)), writer_additional)
+                if writer_additional != Some(&JsonValue::Bool(false)) =>
+            {
+                self.violation(
+                    pointer(path, "additionalProperties"),
+                    format!(
+                        "{reader_label} forbids additional properties that {writer_label} allows"
+                    ),
+                );
+            },
+            (Some(reader_additional @ JsonValue::Object(_)), Some(writer_additional)) => {
+                self.compare(
+                    reader_additional,
+                    writer_additional,
+                    &pointer(path, "additionalProperties"),
+                );
+            },
+            _ => {},
+        }
+    }
+}
+
+/// `type` を集合で返す（未指定は None）
+fn types(schema: &Map<String, JsonValue>) -> Option<BTreeSet<&str>> {
+    match schema.get("type")? {
+        JsonValue::String(t) => Some(BTreeSet::from([t.as_str()])),
+        JsonValue::Array(ts) => Some(ts.iter().filter_map(JsonValue::as_str).collect()),
+        _ => None,
+    }
+}
+
+fn properties(schema: &Map<String, JsonValue>) -> Option<&Map<String, JsonValue>> {
+    schema.get("properties")?.as_object()
+}
+
+fn string_set(value: Option<&JsonValue>) -> BTreeSet<&str> {
+    value
+        .and_then(JsonValue::as_array)
+        .map(|values| values.iter().filter_map(JsonValue::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn join(values: &BTreeSet<&str>) -> String {
+    values.iter().copied().collect::<Vec<_>>().join("|")
+}
+
+fn describe(value: Option<&JsonValue>) -> String {
+    value.map_or_else(|| "nothing".to_string(), ToString::to_string)
+}
+
+/// JSON Pointer に参照トークンを1つ追加する（RFC 6901 のエスケープ）
+fn pointer(base: &str, token: &str) -> String {
+    format!("{base}/{}", token.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema(value: &JsonValue) -> String {
+        value.to_string()
+    }
+
+    fn v1() -> JsonValue {
+        json!({
+            "type": "object",
+            "required": ["session_id", "user_id"],
+            "properties": {
+                "session_id": { "type": "string" },
+                "user_id": { "type": "string" },
+                "strategy": { "type": "string", "enum": ["new", "review", "mixed"] }
+            }
+        })
+    }
+
+    fn check(mode: CompatibilityMode, new: &JsonValue, previous: &[JsonValue]) -> Vec<Violation> {
+        let previous: Vec<(i32, String)> = previous
+            .iter()
+            .zip(1..)
+            .map(|(definition, version)| (version, schema(definition)))
+            .collect();
+        match check_compatibility(mode, &schema(new), &previous) {
+            Ok(()) => Vec::new(),
+            Err(CompatibilityError::Incompatible { violations, .. }) => violations,
+            Err(e) => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_backward_accepts_optional_field_and_rejects_new_required_field() {
+        let mut optional_added = v1();
+        optional_added["properties"]["item_count"] = json!({ "type": "integer" });
+        assert!(check(CompatibilityMode::Backward, &optional_added, &[v1()]).is_empty());
+
+        let mut required_added = optional_added;
+        required_added["required"] = json!(["session_id", "user_id", "item_count"]);
+        let violations = check(CompatibilityMode::Backward, &required_added, &[v1()]);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].direction, Direction::Backward);
+        assert_eq!(violations[0].path, "/properties/item_count");
+        assert_eq!(
+            violations[0].to_string(),
+            "[backward vs v1] /properties/item_count: field `item_count` is required by new \
+             schema but optional in v1"
+        );
+    }
+
+    #[test]
+    fn test_forward_rejects_dropping_a_field_old_readers_require() {
+        // 新しいスキーマに必須フィールドを加えても古い読み手は読める
+        let mut required_added = v1();
+        required_added["properties"]["item_count"] = json!({ "type": "integer" });
+        required_added["required"] = json!(["session_id", "user_id", "item_count"]);
+        assert!(check(CompatibilityMode::Forward, &required_added, &[v1()]).is_empty());
+
+        // 古い読み手が必須とするフィールドを任意にすると読めない
+        let mut relaxed = v1();
+        relaxed["required"] = json!(["session_id"]);
+        let violations = check(CompatibilityMode::Forward, &relaxed, &[v1()]);
+
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].direction, Direction::Forward);
+        assert_eq!(violations[0].path, "/properties/user_id");
+        assert!(
+            violations[0]
+                .message
+                .contains("required by v1 but optional in new schema")
+        );
+        // 逆方向（BACKWARD）では問題にならない
+        assert!(check(CompatibilityMode::Backward, &relaxed, &[v1()]).is_empty());
+    }
+
+    #[test]
+    fn test_full_checks_types_and_enums_in_both_directions() {
+        let mut widened = v1();
+        widened["properties"]["strategy"]["enum"] = json!(["new", "review", "mixed", "weak"]);
+
+        // 値を増やすと新しい読み手は読めるが、古い読み手は読めない
+        assert!(check(CompatibilityMode::Backward, &widened, &[v1()]).is_empty());
+        let violations = check(CompatibilityMode::Full, &widened, &[v1()]);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].direction, Direction::Forward);
+        assert_eq!(violations[0].path, "/properties/strategy/enum");
+        assert!(violations[0].message.contains(r#""weak""#));
+
+        let mut retyped = v1();
+        retyped["properties"]["user_id"] = json!({ "type": "integer" });
+        let paths: Vec<(Direction, String)> = check(CompatibilityMode::Full, &retyped, &[v1()])
+            .into_iter()
+            .map(|violation| (violation.direction, violation.path))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                (Direction::Backward, "/properties/user_id/type".to_string()),
+                (Direction::Forward, "/properties/user_id/type".to_string()),
+            ]
+        );
+
+        // integer は number に含まれる
+        let mut number = v1();
+        number["properties"]["score"] = json!({ "type": "number" });
+        let mut integer = v1();
+        integer["properties"]["score"] = json!({ "type": "integer" });
+        assert!(check(CompatibilityMode::Backward, &number, &[integer.clone()]).is_empty());
+        assert_eq!(
+            check(CompatibilityMode::Forward, &number, &[integer]).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_additional_properties_false_rejects_unknown_fields() {
+        let mut closed = v1();
+        closed["additionalProperties"] = json!(false);
+        let mut extended = closed.clone();
+        extended["properties"]["source"] = json!({ "type": "string" });
+
+        // 閉じた古い読み手は新しいフィールドを受け付けない
+        let violations = check(CompatibilityMode::Forward, &extended, &[closed.clone()]);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "/properties/source");
+        assert!(
+            violations[0]
+                .message
+                .contains("additionalProperties: false")
+        );
+
+        // 開いたスキーマを閉じると、以前のイベントの未知のフィールドを受け付けない
+        let violations = check(CompatibilityMode::Backward, &closed, &[v1()]);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "/additionalProperties");
+    }
+
+    #[test]
+    fn test_transitive_modes_check_every_prior_version() {
+        let v1 = v1();
+        let mut v2 = v1.clone();
+        v2["required"] = json!(["session_id"]);
+        // v3 は v2 とは互換だが、user_id を必須とする v1 の読み手は読めない
+        let mut v3 = v2.clone();
+        v3["properties"]["user_id"] = json!({ "type": "string", "format": "uuid" });
+
+        assert!(check(CompatibilityMode::Forward, &v3, &[v1.clone(), v2.clone()]).is_empty());
+
+        let violations = check(CompatibilityMode::ForwardTransitive, &v3, &[v1, v2]);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].version, 1);
+        assert_eq!(violations[0].path, "/properties/user_id");
+    }
+
+    #[test]
+    fn test_none_only_requires_valid_json_and_refs_are_compared_by_name() {
+        assert!(
+            check(
+                CompatibilityMode::None,
+                &json!({ "type": "string" }),
+                &[v1()]
+            )
+            .is_empty()
+        );
+        assert!(matches!(
+            check_compatibility(CompatibilityMode::None, "{", &[]),
+            Err(CompatibilityError::InvalidSchema { version: None, .. })
+        ));
+
+        let metadata = |reference: &str| json!({ "type": "object", "properties": { "metadata": { "$ref": reference } } });
+        assert!(
+            check(
+                CompatibilityMode::Full,
+                &metadata("#/definitions/EventMetadata"),
+                &[metadata("#/definitions/EventMetadata")]
+            )
+            .is_empty()
+        );
+        let violations = check(
+            CompatibilityMode::Backward,
+            &metadata("#/definitions/EventMetadataV2"),
+            &[metadata("#/definitions/EventMetadata")],
+        );
+        assert_eq!(violations[0].path, "/properties/metadata/$ref");
+    }
+
+    #[test]
+    fn test_mode_changes_can_only_tighten_without_force() {
+        use CompatibilityMode as M;
+
+        for (from, to) in [
+            (M::None, M::Backward),
+            (M::Backward, M::Full),
+            (M::Forward, M::ForwardTransitive),
+            (M::BackwardTransitive, M::FullTransitive),
+            (M::Full, M::Full),
+        ] {
+            assert!(from.validate_change(to, false).is_ok(), "{from} -> {to}");
+        }
+
+        for (from, to) in [
+            (M::Backward, M::None),
+            (M::Backward, M::Forward),
+            (M::Full, M::Forward),
+            (M::ForwardTransitive, M::Forward),
+            (M::FullTransitive, M::Full),
+        ] {
+            assert_eq!(
+                from.validate_change(to, false),
+                Err(CompatibilityError::ModeLoosened { from, to }),
+                "{from} -> {to}"
+            );
+            assert!(from.validate_change(to, true).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_parse_modes() {
+        assert_eq!(
+            "forward_transitive".parse::<CompatibilityMode>().unwrap(),
+            CompatibilityMode::ForwardTransitive
+        );
+        assert_eq!(
+            "FULL".parse::<CompatibilityMode>().unwrap(),
+            CompatibilityMode::Full
+        );
+        assert!("SIDEWAYS".parse::<CompatibilityMode>().is_err());
+        assert_eq!(CompatibilityMode::default(), CompatibilityMode::Backward);
+    }
+}
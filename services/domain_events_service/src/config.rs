@@ -2,6 +2,8 @@
 
 use serde::Deserialize;
 
+use crate::compatibility::CompatibilityMode;
+
 /// サービス設定
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -45,6 +47,10 @@ pub struct Registry {
     /// スキーマの最大バージョン数
     #[serde(default = "default_max_versions")]
     pub max_versions: usize,
+
+    /// イベントタイプごとに指定しない場合の互換性モード
+    #[serde(default)]
+    pub compatibility: CompatibilityMode,
 }
 
 impl Config {
@@ -69,6 +75,10 @@ impl Config {
             .set_default(
                 "registry.max_versions",
                 i64::try_from(default_max_versions()).unwrap_or(i64::MAX),
+            )?
+            .set_default(
+                "registry.compatibility",
+                CompatibilityMode::default().as_str(),
             )?;
 
         // 環境変数から設定を読み込む
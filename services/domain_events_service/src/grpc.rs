@@ -2,12 +2,14 @@
 
 use std::{net::SocketAddr, sync::Arc};
 
+use shared_kernel::to_proto_timestamp;
 use tonic::{Request, Response, Status, transport::Server};
 use tracing::info;
 
 use crate::{
+    compatibility::CompatibilityMode,
     config::Config,
-    registry::{Registry, SchemaInfo},
+    registry::{Registry, SchemaInfo, SchemaRegistryError},
     validator::{ValidationError, Validator},
 };
 
@@ -22,6 +24,8 @@ pub mod proto {
 
 use proto::{
     EventTypeInfo,
+    GetCompatibilityRequest,
+    GetCompatibilityResponse,
     GetSchemaRequest,
     GetSchemaResponse,
     GetSchemaVersionRequest,
@@ -31,6 +35,8 @@ use proto::{
     RegisterSchemaRequest,
     RegisterSchemaResponse,
     Schema,
+    SetCompatibilityRequest,
+    SetCompatibilityResponse,
     ValidateEventRequest,
     ValidateEventResponse,
     domain_events_service_server::{DomainEventsService, DomainEventsServiceServer},
@@ -97,7 +103,10 @@ impl DomainEventsService for DomainEventsServiceImpl {
             .registry
             .register_schema(&req.event_type, &req.schema_definition, &req.description)
             .await
-            .map_err(|e| Status::internal(format!("Failed to register schema: {e}")))?;
+            .map_err(|e| match e {
+                SchemaRegistryError::Compatibility(e) => Status::failed_precondition(e.to_string()),
+                e => Status::internal(format!("Failed to register schema: {e}")),
+            })?;
 
         Ok(Response::new(RegisterSchemaResponse {
             schema_id: schema_id.to_string(),
@@ -150,6 +159,48 @@ impl DomainEventsService for DomainEventsServiceImpl {
             available_versions,
         }))
     }
+
+    async fn get_compatibility(
+        &self,
+        request: Request<GetCompatibilityRequest>,
+    ) -> Result<Response<GetCompatibilityResponse>, Status> {
+        let req = request.into_inner();
+
+        let mode = self
+            .registry
+            .compatibility_mode(&req.event_type)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to get compatibility: {e}")))?;
+
+        Ok(Response::new(GetCompatibilityResponse {
+            mode: mode.to_string(),
+        }))
+    }
+
+    async fn set_compatibility(
+        &self,
+        request: Request<SetCompatibilityRequest>,
+    ) -> Result<Response<SetCompatibilityResponse>, Status> {
+        let req = request.into_inner();
+
+        let mode: CompatibilityMode = req
+            .mode
+            .parse()
+            .map_err(|e| Status::invalid_argument(format!("{e}")))?;
+        let previous = self
+            .registry
+            .set_compatibility_mode(&req.event_type, mode, req.force)
+            .await
+            .map_err(|e| match e {
+                SchemaRegistryError::Compatibility(e) => Status::failed_precondition(e.to_string()),
+                e => Status::internal(format!("Failed to set compatibility: {e}")),
+            })?;
+
+        Ok(Response::new(SetCompatibilityResponse {
+            previous_mode: previous.to_string(),
+            mode:          mode.to_string(),
+        }))
+    }
 }
 
 /// gRPC サーバーを起動
@@ -188,14 +239,8 @@ fn schema_info_to_proto(schema: SchemaInfo) -> Schema {
         version:     schema.version,
         definition:  schema.definition,
         description: schema.description,
-        created_at:  Some(prost_types::Timestamp {
-            seconds: schema.created_at.timestamp(),
-            nanos:   i32::try_from(schema.created_at.timestamp_subsec_nanos()).unwrap_or(0),
-        }),
-        updated_at:  Some(prost_types::Timestamp {
-            seconds: schema.updated_at.timestamp(),
-            nanos:   i32::try_from(schema.updated_at.timestamp_subsec_nanos()).unwrap_or(0),
-        }),
+        created_at:  Some(to_proto_timestamp(&schema.created_at)),
+        updated_at:  Some(to_proto_timestamp(&schema.updated_at)),
     }
 }
 
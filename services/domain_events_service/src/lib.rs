@@ -3,6 +3,7 @@
 //! イベントスキーマ管理とクライアントライブラリを提供
 
 pub mod client;
+pub mod compatibility;
 pub mod config;
 pub mod grpc;
 pub mod registry;
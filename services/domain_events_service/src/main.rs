@@ -49,6 +49,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let registry = registry::Registry::new(pool.clone(), config.registry.clone());
     info!("Schema registry initialized");
 
+    // 統合イベントのスキーマを登録
+    for schema in shared_integration_events::all_schemas() {
+        let version = i32::try_from(schema.version)?;
+        if registry
+            .ensure_schema(
+                &schema.registry_key(),
+                version,
+                schema.definition,
+                schema.description,
+            )
+            .await?
+        {
+            info!(
+                "Registered integration event schema {} v{}",
+                schema.registry_key(),
+                version
+            );
+        }
+    }
+
     // バリデーター初期化
     let validator = validator::Validator::new(registry.clone());
     info!("Event validator initialized");
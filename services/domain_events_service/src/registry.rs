@@ -7,7 +7,10 @@ use sqlx::PgPool;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use crate::config::Registry as RegistryConfig;
+use crate::{
+    compatibility::{CompatibilityError, CompatibilityMode, check_compatibility},
+    config::Registry as RegistryConfig,
+};
 
 /// スキーマ情報
 #[derive(Debug, Clone)]
@@ -158,10 +161,15 @@ impl Registry {
 
     /// スキーマを登録
     ///
+    /// イベントタイプの互換性モードに従って以前のバージョンと比較し、
+    /// 互換性がない場合は登録しない
+    ///
     /// # Errors
     ///
     /// - `SchemaRegistryError::MaxVersionsExceeded` -
     ///   最大バージョン数を超過した場合
+    /// - `SchemaRegistryError::Compatibility` -
+    ///   スキーマが不正、または互換性モードに違反する場合
     /// - `SchemaRegistryError::Database` - データベースエラーが発生した場合
     pub async fn register_schema(
         &self,
@@ -192,6 +200,20 @@ impl Registry {
             });
         }
 
+        let mode = self.compatibility_mode(event_type).await?;
+        let previous: Vec<(i32, String)> = sqlx::query_as(
+            r"
+            SELECT version, definition
+            FROM event_schemas
+            WHERE event_type = $1
+            ORDER BY version
+            ",
+        )
+        .bind(event_type)
+        .fetch_all(&self.pool)
+        .await?;
+        check_compatibility(mode, definition, &previous)?;
+
         let id = Uuid::new_v4();
         let now = Utc::now();
 
@@ -223,6 +245,116 @@ impl Registry {
         Ok((id, new_version))
     }
 
+    /// 指定バージョンのスキーマが未登録の場合のみ登録する
+    ///
+    /// 起動のたびに呼ばれても同じバージョンを重複登録しない。
+    /// 新規に登録した場合は `true` を返す
+    ///
+    /// # Errors
+    ///
+    /// - `SchemaRegistryError::Database` - データベースエラーが発生した場合
+    pub async fn ensure_schema(
+        &self,
+        event_type: &str,
+        version: i32,
+        definition: &str,
+        description: &str,
+    ) -> Result<bool, SchemaRegistryError> {
+        let now = Utc::now();
+        let result = sqlx::query(
+            r#"
+            INSERT INTO event_schemas (
+                id, event_type, version, definition, description,
+                created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (event_type, version) DO NOTHING
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(event_type)
+        .bind(version)
+        .bind(definition)
+        .bind(description)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        let inserted = result.rows_affected() > 0;
+        if inserted {
+            let mut cache = self.cache.write().await;
+            cache.clear();
+        }
+
+        Ok(inserted)
+    }
+
+    /// イベントタイプの互換性モードを取得
+    ///
+    /// イベントタイプごとに設定していない場合はレジストリ全体の既定値を返す
+    ///
+    /// # Errors
+    ///
+    /// - `SchemaRegistryError::Compatibility` -
+    ///   保存されたモードを解釈できない場合
+    /// - `SchemaRegistryError::Database` - データベースエラーが発生した場合
+    pub async fn compatibility_mode(
+        &self,
+        event_type: &str,
+    ) -> Result<CompatibilityMode, SchemaRegistryError> {
+        let mode: Option<String> = sqlx::query_scalar(
+            r"
+            SELECT mode
+            FROM event_type_compatibility
+            WHERE event_type = $1
+            ",
+        )
+        .bind(event_type)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match mode {
+            Some(mode) => Ok(mode.parse()?),
+            None => Ok(self.config.compatibility),
+        }
+    }
+
+    /// イベントタイプの互換性モードを変更し、変更前のモードを返す
+    ///
+    /// チェックを厳しくする変更だけを受け付ける。緩める場合は `force`
+    /// を指定する
+    ///
+    /// # Errors
+    ///
+    /// - `SchemaRegistryError::Compatibility` - `force`
+    ///   なしでチェックを緩めようとした場合
+    /// - `SchemaRegistryError::Database` - データベースエラーが発生した場合
+    pub async fn set_compatibility_mode(
+        &self,
+        event_type: &str,
+        mode: CompatibilityMode,
+        force: bool,
+    ) -> Result<CompatibilityMode, SchemaRegistryError> {
+        let current = self.compatibility_mode(event_type).await?;
+        current.validate_change(mode, force)?;
+
+        sqlx::query(
+            r"
+            INSERT INTO event_type_compatibility (event_type, mode, updated_at)
+            VALUES ($1, $2, now())
+            ON CONFLICT (event_type) DO UPDATE
+            SET mode = EXCLUDED.mode, updated_at = EXCLUDED.updated_at
+            ",
+        )
+        .bind(event_type)
+        .bind(mode.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(current)
+    }
+
     /// イベントタイプ一覧を取得
     ///
     /// # Errors
@@ -352,6 +484,10 @@ pub enum SchemaRegistryError {
         max_versions: usize,
     },
 
+    /// 互換性チェックまたは互換性モードの変更に失敗
+    #[error("Compatibility error: {0}")]
+    Compatibility(#[from] CompatibilityError),
+
     /// データベースエラー
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
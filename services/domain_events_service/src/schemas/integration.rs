@@ -0,0 +1,15 @@
+//! コンテキスト間の統合イベントのスキーマ定義
+//!
+//! 定義は `shared_integration_events` のカタログから取得する
+
+use std::collections::HashMap;
+
+/// 統合イベントのスキーマを取得
+#[must_use]
+#[allow(dead_code)]
+pub fn get_schemas() -> HashMap<String, String> {
+    shared_integration_events::all_schemas()
+        .into_iter()
+        .map(|schema| (schema.registry_key(), schema.definition.to_string()))
+        .collect()
+}
@@ -4,7 +4,9 @@
 
 pub mod ai;
 pub mod algorithm;
+pub mod integration;
 pub mod learning;
+pub mod progress;
 pub mod user;
 pub mod vocabulary;
 
@@ -28,8 +30,14 @@ pub fn initialize() -> HashMap<String, String> {
     // Algorithm Context のスキーマ
     schemas.extend(algorithm::get_schemas());
 
+    // Progress Context のスキーマ
+    schemas.extend(progress::get_schemas());
+
     // AI Context のスキーマ
     schemas.extend(ai::get_schemas());
 
+    // コンテキスト間の統合イベントのスキーマ
+    schemas.extend(integration::get_schemas());
+
     schemas
 }
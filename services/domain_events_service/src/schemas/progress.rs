@@ -0,0 +1,139 @@
+//! Progress Context のイベントスキーマ定義
+
+use std::collections::HashMap;
+
+/// Progress Context のスキーマを取得
+#[must_use]
+#[allow(dead_code, clippy::too_many_lines)]
+pub fn get_schemas() -> HashMap<String, String> {
+    let mut schemas = HashMap::new();
+
+    // StreakExtended イベント
+    schemas.insert(
+        "progress.StreakExtended".to_string(),
+        r##"{
+            "type": "object",
+            "required": ["user_id", "current_streak", "longest_streak", "study_date", "metadata"],
+            "properties": {
+                "user_id": {
+                    "type": "string",
+                    "format": "uuid"
+                },
+                "current_streak": {
+                    "type": "integer",
+                    "minimum": 1
+                },
+                "longest_streak": {
+                    "type": "integer",
+                    "minimum": 1
+                },
+                "study_date": {
+                    "type": "string",
+                    "format": "date"
+                },
+                "metadata": {
+                    "$ref": "#/definitions/EventMetadata"
+                }
+            }
+        }"##
+        .to_string(),
+    );
+
+    // StreakBroken イベント
+    schemas.insert(
+        "progress.StreakBroken".to_string(),
+        r##"{
+            "type": "object",
+            "required": ["user_id", "final_streak", "last_study_date", "metadata"],
+            "properties": {
+                "user_id": {
+                    "type": "string",
+                    "format": "uuid"
+                },
+                "final_streak": {
+                    "type": "integer",
+                    "minimum": 0
+                },
+                "last_study_date": {
+                    "type": "string",
+                    "format": "date"
+                },
+                "metadata": {
+                    "$ref": "#/definitions/EventMetadata"
+                }
+            }
+        }"##
+        .to_string(),
+    );
+
+    // AchievementUnlocked イベント
+    schemas.insert(
+        "progress.AchievementUnlocked".to_string(),
+        r##"{
+            "type": "object",
+            "required": ["user_id", "achievement_id", "name", "category", "metadata"],
+            "properties": {
+                "user_id": {
+                    "type": "string",
+                    "format": "uuid"
+                },
+                "achievement_id": {
+                    "type": "string",
+                    "minLength": 1
+                },
+                "name": {
+                    "type": "string",
+                    "minLength": 1
+                },
+                "category": {
+                    "type": "string",
+                    "enum": ["streak", "mastery", "volume"]
+                },
+                "metadata": {
+                    "$ref": "#/definitions/EventMetadata"
+                }
+            }
+        }"##
+        .to_string(),
+    );
+
+    // GoalProgressUpdated イベント
+    schemas.insert(
+        "progress.GoalProgressUpdated".to_string(),
+        r##"{
+            "type": "object",
+            "required": ["user_id", "goal_id", "metric", "current_value", "target_value", "achieved", "metadata"],
+            "properties": {
+                "user_id": {
+                    "type": "string",
+                    "format": "uuid"
+                },
+                "goal_id": {
+                    "type": "string",
+                    "format": "uuid"
+                },
+                "metric": {
+                    "type": "string",
+                    "enum": ["daily_items", "weekly_minutes", "items_mastered"]
+                },
+                "current_value": {
+                    "type": "integer",
+                    "minimum": 0
+                },
+                "target_value": {
+                    "type": "integer",
+                    "minimum": 1
+                },
+                "achieved": {
+                    "type": "boolean"
+                },
+                "metadata": {
+                    "$ref": "#/definitions/EventMetadata"
+                }
+            }
+        }"##
+        .to_string(),
+    );
+
+    schemas
+}
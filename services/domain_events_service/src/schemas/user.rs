@@ -116,5 +116,64 @@ pub fn get_schemas() -> HashMap<String, String> {
         .to_string(),
     );
 
+    // NotificationPreferencesUpdated イベント
+    schemas.insert(
+        "user.NotificationPreferencesUpdated".to_string(),
+        r##"{
+            "type": "object",
+            "required": ["user_id", "preferences", "metadata"],
+            "properties": {
+                "user_id": {
+                    "type": "string",
+                    "format": "uuid"
+                },
+                "preferences": {
+                    "type": "object",
+                    "properties": {
+                        "disabled_categories": {
+                            "type": "array",
+                            "items": {
+                                "type": "string",
+                                "enum": ["daily_summary", "goal_reminder", "review_reminder", "editorial", "weekly_report"]
+                            }
+                        },
+                        "preferred_channels": {
+                            "type": "array",
+                            "items": {
+                                "type": "string",
+                                "enum": ["email", "push", "in_app"]
+                            }
+                        },
+                        "quiet_hours": {
+                            "type": ["object", "null"],
+                            "required": ["start_minute", "end_minute"],
+                            "properties": {
+                                "start_minute": {
+                                    "type": "integer",
+                                    "minimum": 0,
+                                    "maximum": 1439
+                                },
+                                "end_minute": {
+                                    "type": "integer",
+                                    "minimum": 0,
+                                    "maximum": 1439
+                                }
+                            }
+                        },
+                        "utc_offset_minutes": {
+                            "type": "integer",
+                            "minimum": -720,
+                            "maximum": 840
+                        }
+                    }
+                },
+                "metadata": {
+                    "$ref": "#/definitions/EventMetadata"
+                }
+            }
+        }"##
+        .to_string(),
+    );
+
     schemas
 }
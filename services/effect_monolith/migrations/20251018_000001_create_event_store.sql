@@ -0,0 +1,27 @@
+-- 統合バイナリの語彙イベントストア（アウトボックスを兼ねる）
+-- Vocabulary Command Service の PostgresEventStore が書き込み、
+-- 語彙プロジェクションとイベントバスへのリレーが position 順に読み出す
+
+CREATE TABLE IF NOT EXISTS domain_events (
+    position BIGSERIAL PRIMARY KEY,
+    event_id UUID NOT NULL UNIQUE,
+    aggregate_id UUID NOT NULL,
+    aggregate_type VARCHAR(100) NOT NULL,
+    event_type VARCHAR(100) NOT NULL,
+    event_version INTEGER NOT NULL,
+    event_data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_domain_events_aggregate ON domain_events (aggregate_id, event_version);
+CREATE INDEX IF NOT EXISTS idx_domain_events_type ON domain_events (event_type, created_at);
+
+-- 集約のスナップショット
+CREATE TABLE IF NOT EXISTS snapshots (
+    aggregate_id UUID NOT NULL,
+    aggregate_type VARCHAR(100) NOT NULL,
+    data BYTEA NOT NULL,
+    version BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (aggregate_id, version)
+);
@@ -0,0 +1,261 @@
+//! サービスの組み立て
+//!
+//! 各サービスのライブラリのコンストラクタ（`build_service` / `build_router` /
+//! `build_processor`）に共有データベースのプールを渡し、gRPC サービスは
+//! 1つのルーターに、HTTP のルーターはサービスごとのパスの下にまとめる。
+//! gRPC と HTTP は同じポートで受ける。
+//!
+//! | サービス              | 公開先                                   |
+//! |-----------------------|------------------------------------------|
+//! | vocabulary_command    | gRPC `effect.services.vocabulary_command` |
+//! | algorithm             | gRPC `effect.services.algorithm`          |
+//! | vocabulary_query      | HTTP `/vocabulary/*`                     |
+//! | progress_query        | HTTP `/progress/*`（GraphQL）            |
+//! | vocabulary_projection | バックグラウンドタスク                   |
+//! | progress_projection   | バックグラウンドタスク                   |
+//! | user                  | マイグレーションのみ（サービスは未実装） |
+
+use std::{future::Future, sync::Arc};
+
+use axum::Router;
+use shared_event_bus::InMemoryEventBus;
+use tokio::{net::TcpListener, task::JoinHandle};
+use tonic::service::Routes;
+use tracing::{error, info};
+use vocabulary_projection_service::{
+    bootstrap::build_processor,
+    ports::inbound::EventProcessorUseCase,
+};
+
+use crate::{
+    config::Config,
+    database::{SharedDatabase, schema},
+    error::{Error, Result},
+    health::{self, ComponentStatus},
+    outbox::{OutboxEventSource, OutboxRelay},
+};
+
+/// 統合バイナリ自身のマイグレーション（語彙のイベントストア）
+pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
+
+/// 組み立て済みの統合バイナリ
+pub struct Monolith {
+    router: Router,
+    bus:    Arc<InMemoryEventBus>,
+    tasks:  Vec<JoinHandle<()>>,
+}
+
+impl Monolith {
+    /// マイグレーションを適用し、有効なサービスを組み立てる
+    ///
+    /// プロジェクションとアウトボックスのリレーはここで起動する
+    pub async fn build(config: &Config) -> Result<Self> {
+        let db = SharedDatabase::new(&config.database)?;
+        migrate_all(&db).await?;
+
+        let services = &config.services;
+        let event_store_pool = db
+            .pool(&[schema::EVENT_STORE], config.database.max_connections)
+            .await?;
+        let bus = Arc::new(InMemoryEventBus::new());
+        let mut tasks = Vec::new();
+
+        // gRPC サービス
+        let mut grpc = Routes::builder();
+        if services.vocabulary_command.enabled {
+            let overrides = &services.vocabulary_command;
+            grpc.add_service(
+                vocabulary_command_service::infrastructure::grpc::server::build_service(
+                    vocabulary_command_service::infrastructure::grpc::server::ServiceDeps {
+                        db_pool:          db
+                            .pool(
+                                &[schema::VOCABULARY_COMMAND],
+                                config.max_connections(overrides),
+                            )
+                            .await?,
+                        event_store_pool: event_store_pool.clone(),
+                    },
+                ),
+            );
+        }
+        if services.algorithm.enabled {
+            let pool = db
+                .pool(
+                    &[schema::ALGORITHM],
+                    config.max_connections(&services.algorithm),
+                )
+                .await?;
+            grpc.add_service(algorithm_service::infrastructure::grpc::server::build_service(pool));
+        }
+
+        // HTTP のルーター
+        let mut http = Router::new();
+        if services.vocabulary_query.enabled {
+            let query_config =
+                vocabulary_query_service::Config::from_env().map_err(service_error)?;
+            let pool = db
+                .pool(
+                    &[schema::VOCABULARY_READ],
+                    config.max_connections(&services.vocabulary_query),
+                )
+                .await?;
+            let router = vocabulary_query_service::build_router(&query_config, pool)
+                .await
+                .map_err(service_error)?;
+            http = http.nest("/vocabulary", router);
+        }
+        if services.progress_query.enabled {
+            let query_config =
+                progress_query_service::config::Config::from_env().map_err(service_error)?;
+            let pool = db
+                .pool(
+                    &[schema::PROGRESS_QUERY, schema::PROGRESS_READ],
+                    config.max_connections(&services.progress_query),
+                )
+                .await?;
+            http = http.nest(
+                "/progress",
+                progress_query_service::server::build_router(&query_config, pool)
+                    .map_err(service_error)?,
+            );
+        }
+
+        // プロジェクション
+        if services.vocabulary_projection.enabled {
+            let overrides = &services.vocabulary_projection;
+            let mut projection_config =
+                vocabulary_projection_service::config::Config::from_env().map_err(service_error)?;
+            if let Some(batch_size) = overrides.batch_size {
+                projection_config.event_store.batch_size = batch_size;
+            }
+            if let Some(poll_interval_ms) = overrides.poll_interval_ms {
+                projection_config.event_store.polling_interval_ms = poll_interval_ms;
+            }
+            let pool = db
+                .pool(
+                    &[schema::VOCABULARY_READ],
+                    config.max_connections(overrides),
+                )
+                .await?;
+            let processor = build_processor(
+                projection_config,
+                pool,
+                OutboxEventSource::new(event_store_pool.clone()),
+            );
+            tasks.push(tokio::spawn(async move {
+                if let Err(e) = processor.start_processing().await {
+                    error!("Vocabulary projection stopped: {}", e);
+                }
+            }));
+        }
+        if services.progress_projection.enabled {
+            let overrides = &services.progress_projection;
+            let mut projection_config =
+                progress_projection_service::config::Config::from_env().map_err(service_error)?;
+            if let Some(batch_size) = overrides.batch_size {
+                projection_config.processor.batch_size = batch_size;
+            }
+            if let Some(poll_interval_ms) = overrides.poll_interval_ms {
+                projection_config.processor.poll_interval_ms = poll_interval_ms;
+            }
+            let max_connections = config.max_connections(overrides);
+            let projection = progress_projection_service::processor::ProgressProjection::build(
+                &projection_config.processor,
+                db.pool(&[schema::PROGRESS_EVENT_STORE], max_connections)
+                    .await?,
+                db.pool(&[schema::PROGRESS_READ], max_connections).await?,
+            );
+            tasks.push(tokio::spawn(projection.run()));
+        }
+
+        // アウトボックスからイベントバスへのリレー
+        let relay = OutboxRelay::new(
+            OutboxEventSource::new(event_store_pool.clone()),
+            Arc::clone(&bus),
+            &config.outbox,
+        );
+        tasks.push(tokio::spawn(relay.run()));
+
+        let components = vec![
+            ComponentStatus::new("vocabulary_command", services.vocabulary_command.enabled),
+            ComponentStatus::new("vocabulary_query", services.vocabulary_query.enabled),
+            ComponentStatus::new(
+                "vocabulary_projection",
+                services.vocabulary_projection.enabled,
+            ),
+            ComponentStatus::new("algorithm", services.algorithm.enabled),
+            ComponentStatus::pending("user", services.user.enabled),
+            ComponentStatus::new("progress_projection", services.progress_projection.enabled),
+            ComponentStatus::new("progress_query", services.progress_query.enabled),
+        ];
+
+        let router = grpc
+            .routes()
+            .into_axum_router()
+            .merge(http)
+            .merge(health::router(event_store_pool, components));
+
+        Ok(Self { router, bus, tasks })
+    }
+
+    /// プロセス内のイベントバス
+    pub fn event_bus(&self) -> Arc<InMemoryEventBus> {
+        Arc::clone(&self.bus)
+    }
+
+    /// `shutdown` が完了するまで gRPC と HTTP を受け付ける
+    ///
+    /// 終了時にプロジェクションとリレーのタスクも止める
+    pub async fn serve<F>(self, listener: TcpListener, shutdown: F) -> Result<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        info!("Effect monolith listening on {}", listener.local_addr()?);
+
+        let result = axum::serve(listener, self.router)
+            .with_graceful_shutdown(shutdown)
+            .await;
+
+        for task in self.tasks {
+            task.abort();
+        }
+
+        Ok(result?)
+    }
+}
+
+/// 全サービスのマイグレーションをスキーマごとに適用する
+///
+/// 無効にしたサービスのスキーマも作っておき、後から有効にできるようにする
+async fn migrate_all(db: &SharedDatabase) -> Result<()> {
+    db.migrate(schema::EVENT_STORE, &MIGRATOR).await?;
+    db.migrate(
+        schema::VOCABULARY_COMMAND,
+        &vocabulary_command_service::MIGRATOR,
+    )
+    .await?;
+    db.migrate(
+        schema::VOCABULARY_READ,
+        &vocabulary_projection_service::MIGRATOR,
+    )
+    .await?;
+    db.migrate(schema::ALGORITHM, &algorithm_service::MIGRATOR)
+        .await?;
+    db.migrate(schema::USERS, &user_service::MIGRATOR).await?;
+    db.migrate(
+        schema::PROGRESS_EVENT_STORE,
+        &progress_command_service::MIGRATOR,
+    )
+    .await?;
+    db.migrate(
+        schema::PROGRESS_READ,
+        &progress_projection_service::MIGRATOR,
+    )
+    .await?;
+    db.migrate(schema::PROGRESS_QUERY, &progress_query_service::MIGRATOR)
+        .await
+}
+
+fn service_error(e: impl std::fmt::Display) -> Error {
+    Error::Service(e.to_string())
+}
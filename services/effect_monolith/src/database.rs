@@ -0,0 +1,116 @@
+//! 共有データベース
+//!
+//! 全サービスで1つのデータベースを使い、サービスごとにスキーマを分ける。
+//! 各サービスのクエリはスキーマを修飾しないテーブル名を使い、名前が衝突する
+//! テーブル（`projection_checkpoints` など）やマイグレーションのバージョンも
+//! あるため、接続ごとに `search_path` を設定したプールをサービスに渡す。
+//! マイグレーションの履歴（`_sqlx_migrations`）もスキーマごとに持つ。
+
+use std::str::FromStr;
+
+use sqlx::{
+    PgPool,
+    migrate::Migrator,
+    postgres::{PgConnectOptions, PgPoolOptions},
+};
+use tracing::info;
+
+use crate::{config::DatabaseConfig, error::Result};
+
+/// サービスごとのスキーマ
+pub mod schema {
+    /// 語彙コマンド側のイベントストア（アウトボックスを兼ねる）
+    pub const EVENT_STORE: &str = "event_store";
+    /// 語彙コマンド側のテーブル
+    pub const VOCABULARY_COMMAND: &str = "vocabulary_command";
+    /// 語彙の Read Model（プロジェクションが書き、クエリが読む）
+    pub const VOCABULARY_READ: &str = "vocabulary_read";
+    /// 学習アルゴリズム
+    pub const ALGORITHM: &str = "algorithm";
+    /// ユーザー
+    pub const USERS: &str = "users";
+    /// 進捗のイベントストア
+    pub const PROGRESS_EVENT_STORE: &str = "progress_event_store";
+    /// 進捗の Read Model（プロジェクションが書く）
+    pub const PROGRESS_READ: &str = "progress_read";
+    /// 進捗クエリのビュー
+    pub const PROGRESS_QUERY: &str = "progress_query";
+}
+
+/// 共有データベースへの接続設定
+#[derive(Debug, Clone)]
+pub struct SharedDatabase {
+    options: PgConnectOptions,
+}
+
+impl SharedDatabase {
+    pub fn new(config: &DatabaseConfig) -> Result<Self> {
+        Ok(Self {
+            options: PgConnectOptions::from_str(&config.url)?,
+        })
+    }
+
+    /// スキーマを作成し、サービスのマイグレーションを適用する
+    pub async fn migrate(&self, schema: &str, migrator: &Migrator) -> Result<()> {
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .connect_with(self.options_for(&[schema]))
+            .await?;
+
+        sqlx::query(&format!(
+            "CREATE SCHEMA IF NOT EXISTS {}",
+            quote_ident(schema)
+        ))
+        .execute(&pool)
+        .await?;
+        migrator.run(&pool).await?;
+        pool.close().await;
+
+        info!(schema, "Migrations applied");
+        Ok(())
+    }
+
+    /// `search_path` を設定したプールを作成する
+    ///
+    /// 先頭のスキーマに書き込み、後ろのスキーマは参照だけに使う想定
+    pub async fn pool(&self, search_path: &[&str], max_connections: u32) -> Result<PgPool> {
+        Ok(PgPoolOptions::new()
+            .max_connections(max_connections)
+            .connect_with(self.options_for(search_path))
+            .await?)
+    }
+
+    fn options_for(&self, search_path: &[&str]) -> PgConnectOptions {
+        self.options
+            .clone()
+            .options([("search_path", search_path_value(search_path))])
+    }
+}
+
+/// `search_path` の値
+fn search_path_value(schemas: &[&str]) -> String {
+    schemas
+        .iter()
+        .map(|schema| quote_ident(schema))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// 識別子を引用符で囲む
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_path_quotes_each_schema() {
+        assert_eq!(
+            search_path_value(&[schema::PROGRESS_QUERY, schema::PROGRESS_READ]),
+            r#""progress_query","progress_read""#
+        );
+        assert_eq!(quote_ident(r#"odd"name"#), r#""odd""name""#);
+    }
+}
@@ -0,0 +1,24 @@
+//! 統合バイナリのエラー型
+
+use thiserror::Error;
+
+/// 統合バイナリのエラー
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Configuration error: {0}")]
+    Config(#[from] config::ConfigError),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Migration error: {0}")]
+    Migration(#[from] sqlx::migrate::MigrateError),
+
+    #[error("Service error: {0}")]
+    Service(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,67 @@
+//! 統合バイナリのヘルスチェック
+//!
+//! 各サービスのヘルスチェックの代わりに、共有データベースへの接続と
+//! 組み込んだサービスの一覧を1つのエンドポイントで返す。
+
+use axum::{Json, Router, extract::State, http::StatusCode, routing::get};
+use serde::Serialize;
+use serde_json::json;
+use sqlx::PgPool;
+
+/// 組み込んだサービスの状態
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComponentStatus {
+    pub name:    &'static str,
+    pub enabled: bool,
+    /// サービス本体が未実装（マイグレーションだけ適用する）
+    pub pending: bool,
+}
+
+impl ComponentStatus {
+    pub const fn new(name: &'static str, enabled: bool) -> Self {
+        Self {
+            name,
+            enabled,
+            pending: false,
+        }
+    }
+
+    pub const fn pending(name: &'static str, enabled: bool) -> Self {
+        Self {
+            name,
+            enabled,
+            pending: true,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct HealthState {
+    pool:       PgPool,
+    components: Vec<ComponentStatus>,
+}
+
+/// `/health` のルーター
+pub fn router(pool: PgPool, components: Vec<ComponentStatus>) -> Router {
+    Router::new()
+        .route("/health", get(health_check))
+        .with_state(HealthState { pool, components })
+}
+
+async fn health_check(State(state): State<HealthState>) -> (StatusCode, Json<serde_json::Value>) {
+    let database = sqlx::query("SELECT 1").execute(&state.pool).await;
+    let (code, status, database) = match database {
+        Ok(_) => (StatusCode::OK, "healthy", "ok".to_string()),
+        Err(e) => (StatusCode::SERVICE_UNAVAILABLE, "unhealthy", e.to_string()),
+    };
+
+    (
+        code,
+        Json(json!({
+            "status": status,
+            "service": "effect_monolith",
+            "database": database,
+            "services": state.components,
+        })),
+    )
+}
@@ -0,0 +1,22 @@
+//! Effect Monolith
+//!
+//! 全サービスを1つのプロセスで動かす統合バイナリ（モジュラーモノリス）。
+//! セルフホスト向けに、データベースを1つにまとめ、サービス間のイベントは
+//! インメモリのイベントバスとアウトボックスで受け渡す。
+//!
+//! - config: 共通の設定とサービスごとの上書き
+//! - database: スキーマごとに `search_path` を分けた共有データベース
+//! - outbox: アウトボックスからの読み出しとイベントバスへのリレー
+//! - health: 統合されたヘルスチェック
+//! - app: サービスの組み立てとサーバーの起動
+
+pub mod app;
+pub mod config;
+pub mod database;
+pub mod error;
+pub mod health;
+pub mod outbox;
+
+pub use app::Monolith;
+pub use config::Config;
+pub use error::{Error, Result};
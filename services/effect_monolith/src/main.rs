@@ -0,0 +1,35 @@
+use std::net::SocketAddr;
+
+use effect_monolith::{Config, Error, Monolith, Result};
+use tokio::net::TcpListener;
+use tracing::info;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // ロギング初期化
+    tracing_subscriber::fmt::init();
+
+    info!("===========================================");
+    info!("Effect Monolith - 起動中");
+    info!("責務: 全サービスを1プロセスで提供（セルフホスト向け）");
+    info!("===========================================");
+
+    // 設定読み込み
+    let config = Config::load()?;
+    let addr: SocketAddr = format!("{}:{}", config.server.host, config.server.port)
+        .parse()
+        .map_err(|e| Error::Service(format!("Invalid server address: {}", e)))?;
+
+    // サービスを組み立てて起動
+    let monolith = Monolith::build(&config).await?;
+    let listener = TcpListener::bind(addr).await?;
+    monolith
+        .serve(listener, async {
+            let _ = tokio::signal::ctrl_c().await;
+            info!("Received shutdown signal");
+        })
+        .await?;
+
+    info!("Effect Monolith shutting down");
+    Ok(())
+}
@@ -0,0 +1,250 @@
+//! アウトボックスとイベントバスへのリレー
+//!
+//! コマンド側がイベントを書き込む `domain_events` テーブルを、統合バイナリの
+//! アウトボックスとして使う。
+//!
+//! - 語彙プロジェクションは [`OutboxEventSource`] から position 順に読み出す。
+//!   チェックポイントから読み直せるため、再起動してもイベントを取りこぼさない
+//! - [`OutboxRelay`] は新しく書き込まれたイベントを [`InMemoryEventBus`] の
+//!   [`DOMAIN_EVENTS_TOPIC`] に流し、プロセス内の購読者へすぐに届ける。
+//!   インメモリのバスは永続化しないため、起動時点より前のイベントは流さない
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared_event_bus::{InMemoryEventBus, OutgoingMessage};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+use vocabulary_projection_service::{
+    domain::events::StoredEvent,
+    error::Result,
+    ports::outbound::{AggregateEventSource, EventStream, EventSubscriber},
+};
+
+use crate::config::OutboxConfig;
+
+/// リレーがイベントを流すトピック
+pub const DOMAIN_EVENTS_TOPIC: &str = "vocabulary.domain_events";
+
+type EventRow = (i64, Uuid, Uuid, i32, String, String, DateTime<Utc>);
+
+/// アウトボックスからのイベント取得
+#[derive(Clone)]
+pub struct OutboxEventSource {
+    pool: PgPool,
+}
+
+impl OutboxEventSource {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 最後に書き込まれたイベントの位置（イベントがない場合は 0）
+    pub async fn latest_position(&self) -> Result<i64> {
+        let position =
+            sqlx::query_scalar::<_, i64>("SELECT COALESCE(MAX(position), 0) FROM domain_events")
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(position)
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for OutboxEventSource {
+    async fn fetch_events(
+        &self,
+        from_position: i64,
+        batch_size: usize,
+    ) -> Result<Vec<StoredEvent>> {
+        let rows = sqlx::query_as::<_, EventRow>(
+            r#"
+            SELECT position, event_id, aggregate_id, event_version, event_type,
+                   event_data::TEXT, created_at
+            FROM domain_events
+            WHERE position > $1
+            ORDER BY position
+            LIMIT $2
+            "#,
+        )
+        .bind(from_position)
+        .bind(i64::try_from(batch_size).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(stored_event).collect())
+    }
+
+    async fn subscribe(&self, _from_position: i64) -> Result<EventStream> {
+        // プロセッサーは fetch_events をポーリングする
+        Ok(EventStream {})
+    }
+}
+
+#[async_trait]
+impl AggregateEventSource for OutboxEventSource {
+    async fn fetch_aggregate_events(
+        &self,
+        aggregate_id: Uuid,
+        from_version: i64,
+        to_version: i64,
+    ) -> Result<Vec<StoredEvent>> {
+        let rows = sqlx::query_as::<_, EventRow>(
+            r#"
+            SELECT position, event_id, aggregate_id, event_version, event_type,
+                   event_data::TEXT, created_at
+            FROM domain_events
+            WHERE aggregate_id = $1 AND event_version BETWEEN $2 AND $3
+            ORDER BY event_version
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(from_version)
+        .bind(to_version)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(stored_event).collect())
+    }
+}
+
+fn stored_event(
+    (position, event_id, aggregate_id, event_version, event_type, event_data, created_at): EventRow,
+) -> StoredEvent {
+    StoredEvent {
+        position,
+        event_id,
+        aggregate_id,
+        aggregate_version: i64::from(event_version),
+        event_type,
+        event_data,
+        occurred_at: created_at,
+    }
+}
+
+/// イベントバスに流すイベント
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelayedEvent {
+    pub position:          i64,
+    pub event_id:          Uuid,
+    pub aggregate_id:      Uuid,
+    pub aggregate_version: i64,
+    pub event_type:        String,
+    pub event_data:        serde_json::Value,
+    pub occurred_at:       DateTime<Utc>,
+}
+
+impl RelayedEvent {
+    fn from_stored(event: StoredEvent) -> serde_json::Result<Self> {
+        Ok(Self {
+            position:          event.position,
+            event_id:          event.event_id,
+            aggregate_id:      event.aggregate_id,
+            aggregate_version: event.aggregate_version,
+            event_type:        event.event_type,
+            event_data:        serde_json::from_str(&event.event_data)?,
+            occurred_at:       event.occurred_at,
+        })
+    }
+}
+
+/// アウトボックスのイベントをイベントバスに流すリレー
+pub struct OutboxRelay {
+    source:        OutboxEventSource,
+    bus:           Arc<InMemoryEventBus>,
+    batch_size:    usize,
+    poll_interval: Duration,
+}
+
+impl OutboxRelay {
+    pub fn new(
+        source: OutboxEventSource,
+        bus: Arc<InMemoryEventBus>,
+        config: &OutboxConfig,
+    ) -> Self {
+        Self {
+            source,
+            bus,
+            batch_size: config.batch_size,
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+        }
+    }
+
+    /// リレーのループ（終了しない）
+    pub async fn run(self) {
+        let mut position = match self.source.latest_position().await {
+            Ok(position) => position,
+            Err(e) => {
+                error!(
+                    "Failed to read outbox position, relaying from the beginning: {}",
+                    e
+                );
+                0
+            },
+        };
+        info!("Relaying outbox events after position {}", position);
+
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+
+            match self.relay_batch(position).await {
+                Ok(Some(last)) => position = last,
+                Ok(None) => {},
+                Err(e) => error!("Outbox relay error: {}", e),
+            }
+        }
+    }
+
+    /// `after` より後のイベントを1バッチ分流し、最後に流した位置を返す
+    async fn relay_batch(&self, after: i64) -> Result<Option<i64>> {
+        let events = self.source.fetch_events(after, self.batch_size).await?;
+        let last = events.last().map(|event| event.position);
+
+        for event in events {
+            let relayed = RelayedEvent::from_stored(event)?;
+            self.bus.publish_message(OutgoingMessage {
+                topic: DOMAIN_EVENTS_TOPIC.to_string(),
+                data: serde_json::to_vec(&relayed)?,
+                ordering_key: Some(relayed.aggregate_id.to_string()),
+                ..Default::default()
+            });
+        }
+
+        Ok(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_relayed_event_carries_event_data_as_json() {
+        let stored = StoredEvent {
+            position:          7,
+            event_id:          Uuid::new_v4(),
+            aggregate_id:      Uuid::new_v4(),
+            aggregate_version: 1,
+            event_type:        "VocabularyItemCreated".to_string(),
+            event_data:        r#"{"type":"VocabularyItemCreated","spelling":"apple"}"#.to_string(),
+            occurred_at:       Utc::now(),
+        };
+
+        let relayed = RelayedEvent::from_stored(stored.clone()).unwrap();
+
+        assert_eq!(relayed.position, 7);
+        assert_eq!(relayed.aggregate_id, stored.aggregate_id);
+        assert_eq!(relayed.event_data["spelling"], json!("apple"));
+        let bytes = serde_json::to_vec(&relayed).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<RelayedEvent>(&bytes).unwrap(),
+            relayed
+        );
+    }
+}
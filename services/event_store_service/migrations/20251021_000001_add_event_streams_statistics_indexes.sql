@@ -0,0 +1,2 @@
+-- ストア統計の最長ストリームとスナップショット充足率の集計用インデックス
+CREATE INDEX IF NOT EXISTS idx_event_streams_version ON event_streams (version DESC);
@@ -0,0 +1,5 @@
+-- 購読で読み飛ばしを防ぐため、イベントを書き込んだトランザクションを記録する
+-- position はコミット順ではなく採番順なので、実行中のトランザクションより
+-- 後ろの位置は、そのトランザクションが終わるまで購読に流さない
+
+ALTER TABLE events ADD COLUMN IF NOT EXISTS transaction_id XID8 NOT NULL DEFAULT pg_current_xact_id();
@@ -0,0 +1,14 @@
+-- コンシューマーグループ（サーバーで管理する購読）
+-- リースを持つ1つのインスタンスがチェックポイントより後のイベントを読み、
+-- リースが切れると待機している他のインスタンスが引き継ぐ
+
+CREATE TABLE IF NOT EXISTS consumer_groups (
+    group_name TEXT PRIMARY KEY,
+    event_types TEXT[] NOT NULL DEFAULT '{}',
+    stream_types TEXT[] NOT NULL DEFAULT '{}',
+    checkpoint BIGINT NOT NULL DEFAULT 0,
+    leaseholder TEXT,
+    lease_expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,82 @@
+//! 管理 RPC の認可
+//!
+//! ストリームの削除などの管理 RPC は、`authorization: Bearer <JWT>` の
+//! `event_store:admin` 権限を持つロール（admin）だけが呼べる。JWT
+//! の署名鍵（`JWT_SECRET`）を設定していない場合は管理 RPC を無効にする。
+
+use shared_security::{Claims, Permission};
+
+/// 管理 RPC の認可のエラー
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum AdminAuthError {
+    #[error("Admin RPCs are disabled")]
+    Disabled,
+
+    #[error("Missing bearer token")]
+    MissingToken,
+
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+
+    #[error("{0} is not an admin")]
+    NotAdmin(String),
+}
+
+/// `authorization` ヘッダーの JWT を検証し、管理 RPC を呼べるクレームを返す
+pub fn authorize(
+    authorization: Option<&str>,
+    jwt_secret: Option<&str>,
+) -> Result<Claims, AdminAuthError> {
+    let secret = jwt_secret.ok_or(AdminAuthError::Disabled)?;
+    let token = authorization
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(AdminAuthError::MissingToken)?;
+
+    let claims = shared_security::validate_jwt(token, secret)
+        .map_err(|e| AdminAuthError::InvalidToken(e.to_string()))?;
+    if !claims.has(Permission::EventStoreAdmin) {
+        return Err(AdminAuthError::NotAdmin(claims.sub));
+    }
+    Ok(claims)
+}
+
+#[cfg(test)]
+mod tests {
+    use shared_security::Role;
+
+    use super::*;
+
+    const SECRET: &str = "test-secret";
+
+    fn bearer(role: Role) -> String {
+        let token = shared_security::generate_jwt("operator-1", role.as_str(), SECRET, 1).unwrap();
+        format!("Bearer {token}")
+    }
+
+    #[test]
+    fn test_only_admins_are_authorized() {
+        let admin = bearer(Role::Admin);
+        assert_eq!(
+            authorize(Some(&admin), Some(SECRET)).unwrap().sub,
+            "operator-1"
+        );
+
+        assert_eq!(
+            authorize(Some(&bearer(Role::User)), Some(SECRET)).unwrap_err(),
+            AdminAuthError::NotAdmin("operator-1".to_string())
+        );
+        assert_eq!(
+            authorize(None, Some(SECRET)).unwrap_err(),
+            AdminAuthError::MissingToken
+        );
+        assert!(matches!(
+            authorize(Some(&admin), Some("other-secret")),
+            Err(AdminAuthError::InvalidToken(_))
+        ));
+        // 署名鍵がなければ管理 RPC は無効
+        assert_eq!(
+            authorize(Some(&admin), None).unwrap_err(),
+            AdminAuthError::Disabled
+        );
+    }
+}
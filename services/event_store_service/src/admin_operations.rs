@@ -0,0 +1,335 @@
+//! 破壊的な管理 RPC の2段階の実行
+//!
+//! ストリームの削除は [`shared_admin_ops`] のプロトコルで実行する。
+//! ドライランで計画と確認トークンを返し、実行には確認トークンを必須にする。
+//! 実行の記録（[`AdminOperationExecuted`]）と確認トークンの使用は、
+//! このサービスの Event Store の専用のストリームに追記する。
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use async_trait::async_trait;
+use shared_admin_ops::{
+    ADMIN_AUDIT_AGGREGATE_TYPE,
+    ADMIN_AUDIT_STREAM_ID,
+    AdminOperation,
+    AdminOperationExecuted,
+    AdminOpsError,
+    AuditLog,
+    CONFIRMATION_TOKEN_AGGREGATE_TYPE,
+    ConfirmationTokenConsumed,
+    OperationPlan,
+};
+use uuid::Uuid;
+
+use crate::repository::{EventStoreError, PostgresEventStore};
+
+/// 削除するストリームの件数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamSummary {
+    pub events:    i64,
+    pub snapshots: i64,
+}
+
+/// ストリームの参照と削除
+#[async_trait]
+pub trait StreamCatalog: Send + Sync {
+    /// ストリームがなければ `None`
+    async fn summary(
+        &self,
+        stream_id: Uuid,
+        stream_type: &str,
+    ) -> Result<Option<StreamSummary>, EventStoreError>;
+
+    /// 削除したイベント数を返す
+    async fn delete(&self, stream_id: Uuid, stream_type: &str) -> Result<u64, EventStoreError>;
+}
+
+#[async_trait]
+impl StreamCatalog for PostgresEventStore {
+    async fn summary(
+        &self,
+        stream_id: Uuid,
+        stream_type: &str,
+    ) -> Result<Option<StreamSummary>, EventStoreError> {
+        let Some(stream) = self.stream_statistics(stream_id, stream_type).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(StreamSummary {
+            events:    stream.length,
+            snapshots: self.count_snapshots(stream_id, stream_type).await?,
+        }))
+    }
+
+    async fn delete(&self, stream_id: Uuid, stream_type: &str) -> Result<u64, EventStoreError> {
+        self.delete_stream(stream_id, stream_type).await
+    }
+}
+
+/// ストリームの削除（DeleteStream）
+pub struct DeleteStream {
+    catalog:     Arc<dyn StreamCatalog>,
+    stream_id:   Uuid,
+    stream_type: String,
+    deleted:     AtomicU64,
+}
+
+impl DeleteStream {
+    pub const OPERATION: &'static str = "stream.delete";
+
+    pub fn new(
+        catalog: Arc<dyn StreamCatalog>,
+        stream_id: Uuid,
+        stream_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            catalog,
+            stream_id,
+            stream_type: stream_type.into(),
+            deleted: AtomicU64::new(0),
+        }
+    }
+
+    /// 実行で削除したイベント数
+    pub fn deleted_events(&self) -> u64 {
+        self.deleted.load(Ordering::SeqCst)
+    }
+
+    fn target(&self) -> String {
+        format!("{}/{}", self.stream_type, self.stream_id)
+    }
+}
+
+#[async_trait]
+impl AdminOperation for DeleteStream {
+    async fn plan(&self) -> shared_admin_ops::Result<OperationPlan> {
+        let summary = self
+            .catalog
+            .summary(self.stream_id, &self.stream_type)
+            .await
+            .map_err(|e| AdminOpsError::Operation(e.to_string()))?
+            .ok_or_else(|| {
+                AdminOpsError::Operation(
+                    EventStoreError::StreamNotFound(self.stream_id).to_string(),
+                )
+            })?;
+
+        Ok(OperationPlan::new(Self::OPERATION, self.target())
+            .with_step(format!(
+                "delete {} events and {} snapshots of {}",
+                summary.events,
+                summary.snapshots,
+                self.target()
+            ))
+            .with_step("the stream cannot be restored")
+            .with_affected("events", summary.events)
+            .with_affected("snapshots", summary.snapshots))
+    }
+
+    async fn execute(&self, _plan: &OperationPlan) -> shared_admin_ops::Result<String> {
+        let deleted = self
+            .catalog
+            .delete(self.stream_id, &self.stream_type)
+            .await
+            .map_err(|e| AdminOpsError::Operation(e.to_string()))?;
+        self.deleted.store(deleted, Ordering::SeqCst);
+        Ok(format!("deleted {deleted} events of {}", self.target()))
+    }
+}
+
+/// このサービスの Event Store に監査ログを追記する
+pub struct RepositoryAuditLog {
+    repository: Arc<PostgresEventStore>,
+}
+
+impl RepositoryAuditLog {
+    pub fn new(repository: Arc<PostgresEventStore>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl AuditLog for RepositoryAuditLog {
+    async fn append(&self, event: &AdminOperationExecuted) -> shared_admin_ops::Result<()> {
+        let mut data = serde_json::to_value(event)?;
+        data["event_type"] = AdminOperationExecuted::EVENT_TYPE.into();
+
+        self.repository
+            .append_events(
+                ADMIN_AUDIT_STREAM_ID,
+                ADMIN_AUDIT_AGGREGATE_TYPE,
+                vec![data],
+                None,
+            )
+            .await
+            .map_err(audit_error)?;
+        Ok(())
+    }
+
+    async fn consume_token(
+        &self,
+        event: &ConfirmationTokenConsumed,
+    ) -> shared_admin_ops::Result<()> {
+        let token_stream_id =
+            Uuid::parse_str(&event.token_id).map_err(|_| AdminOpsError::InvalidToken)?;
+        let mut data = serde_json::to_value(event)?;
+        data["event_type"] = ConfirmationTokenConsumed::EVENT_TYPE.into();
+
+        // 空のストリーム（バージョン -1）への追記だけを許す
+        match self
+            .repository
+            .append_events(
+                token_stream_id,
+                CONFIRMATION_TOKEN_AGGREGATE_TYPE,
+                vec![data],
+                Some(-1),
+            )
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(EventStoreError::VersionConflict { .. }) => Err(AdminOpsError::TokenAlreadyUsed),
+            Err(e) => Err(audit_error(e)),
+        }
+    }
+}
+
+fn audit_error(e: EventStoreError) -> AdminOpsError {
+    AdminOpsError::Audit(shared_event_store::EventStoreError::Internal(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Mutex};
+
+    use chrono::{DateTime, Utc};
+    use shared_admin_ops::{
+        AdminOperationRunner,
+        ConfirmationSigner,
+        EventStoreAuditLog,
+        OperationOutcome,
+        Operator,
+    };
+    use shared_event_store::testing::InMemoryEventStore;
+    use shared_security::Claims;
+
+    use super::*;
+
+    /// (ストリーム ID, ストリームタイプ) ごとの件数
+    #[derive(Default)]
+    struct InMemoryStreamCatalog {
+        streams: Mutex<HashMap<(Uuid, String), StreamSummary>>,
+    }
+
+    #[async_trait]
+    impl StreamCatalog for InMemoryStreamCatalog {
+        async fn summary(
+            &self,
+            stream_id: Uuid,
+            stream_type: &str,
+        ) -> Result<Option<StreamSummary>, EventStoreError> {
+            Ok(self
+                .streams
+                .lock()
+                .unwrap()
+                .get(&(stream_id, stream_type.to_string()))
+                .copied())
+        }
+
+        async fn delete(&self, stream_id: Uuid, stream_type: &str) -> Result<u64, EventStoreError> {
+            self.streams
+                .lock()
+                .unwrap()
+                .remove(&(stream_id, stream_type.to_string()))
+                .map(|summary| summary.events as u64)
+                .ok_or(EventStoreError::StreamNotFound(stream_id))
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2025-10-01T12:00:00Z".parse().unwrap()
+    }
+
+    fn operator() -> Operator {
+        Operator::from_claims(&Claims {
+            sub:  "admin-1".to_string(),
+            exp:  0,
+            iat:  0,
+            role: "admin".to_string(),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_plans_the_deletion_and_execution_requires_an_unchanged_plan() {
+        let stream_id = Uuid::new_v4();
+        let catalog = Arc::new(InMemoryStreamCatalog::default());
+        catalog.streams.lock().unwrap().insert(
+            (stream_id, "VocabularyItem".to_string()),
+            StreamSummary {
+                events:    12,
+                snapshots: 2,
+            },
+        );
+        let audit = Arc::new(EventStoreAuditLog::new(Arc::new(InMemoryEventStore::new())));
+        let runner = AdminOperationRunner::new(ConfirmationSigner::new("secret"), audit.clone());
+        let operation = DeleteStream::new(catalog.clone(), stream_id, "VocabularyItem");
+
+        let dry_run = runner.dry_run(&operation, now()).await.unwrap();
+        assert_eq!(dry_run.plan.operation, "stream.delete");
+        assert_eq!(dry_run.plan.target, format!("VocabularyItem/{stream_id}"));
+        assert_eq!(dry_run.plan.affected["events"], 12);
+        assert_eq!(dry_run.plan.affected["snapshots"], 2);
+        // ドライランでは削除しない
+        assert_eq!(catalog.streams.lock().unwrap().len(), 1);
+
+        // トークン発行後にイベントが追加されたら実行しない
+        catalog
+            .streams
+            .lock()
+            .unwrap()
+            .get_mut(&(stream_id, "VocabularyItem".to_string()))
+            .unwrap()
+            .events = 13;
+        assert!(matches!(
+            runner
+                .execute(&operation, &dry_run.token.value, &operator(), now())
+                .await,
+            Err(AdminOpsError::PlanChanged { .. })
+        ));
+        assert_eq!(catalog.streams.lock().unwrap().len(), 1);
+
+        let dry_run = runner.dry_run(&operation, now()).await.unwrap();
+        let executed = runner
+            .execute(&operation, &dry_run.token.value, &operator(), now())
+            .await
+            .unwrap();
+
+        assert_eq!(operation.deleted_events(), 13);
+        assert!(catalog.streams.lock().unwrap().is_empty());
+        assert_eq!(executed.operator_id, "admin-1");
+        assert_eq!(
+            executed.outcome,
+            OperationOutcome::Succeeded {
+                summary: format!("deleted 13 events of VocabularyItem/{stream_id}"),
+            }
+        );
+        assert_eq!(audit.events().await.unwrap(), vec![executed]);
+    }
+
+    #[tokio::test]
+    async fn test_missing_stream_cannot_be_planned() {
+        let operation = DeleteStream::new(
+            Arc::new(InMemoryStreamCatalog::default()),
+            Uuid::new_v4(),
+            "VocabularyItem",
+        );
+
+        assert!(matches!(
+            operation.plan().await,
+            Err(AdminOpsError::Operation(message)) if message.starts_with("Stream not found")
+        ));
+    }
+}
@@ -14,18 +14,27 @@ pub struct Config {
     /// スナップショット設定
     pub snapshot: SnapshotConfig,
 
-    /// Event Bus (Pub/Sub) 設定
+    /// Event Bus 設定
     pub event_bus: EventBusConfig,
 
     /// Domain Events Service 設定
     pub domain_events: DomainEventsConfig,
+
+    /// ストア統計設定
+    pub statistics: StatisticsConfig,
+
+    /// 購読設定
+    pub subscription: SubscriptionConfig,
+
+    /// 管理 RPC 設定
+    pub admin: AdminConfig,
 }
 
 /// Event Bus 設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventBusConfig {
-    /// Google Cloud プロジェクト ID
-    pub project_id: String,
+    /// バックエンド（Pub/Sub、Kafka またはインメモリ）
+    pub backend: shared_event_bus::EventBusConfig,
 
     /// トピックのプレフィックス
     pub topic_prefix: String,
@@ -57,6 +66,48 @@ pub struct SnapshotConfig {
     pub retention_days: u32,
 }
 
+/// ストア統計設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatisticsConfig {
+    /// 統計を計算し直す間隔（キャッシュの TTL、秒）
+    pub refresh_interval_secs: u64,
+
+    /// 返す最長ストリームの数
+    pub top_streams: i64,
+
+    /// これ以下の行数のテーブルは正確に数える（超えると pg_stats から推定）
+    pub exact_count_row_limit: i64,
+
+    /// ストリーム長の標本の最大数
+    pub sample_rows: i64,
+}
+
+/// 購読設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionConfig {
+    /// 他のインスタンスが追記したイベントを確認する間隔（ミリ秒）
+    pub poll_interval_ms: u64,
+
+    /// 1回に読み込むイベント数
+    pub batch_size: i64,
+
+    /// コンシューマーグループのリースの期限（秒）。読み込みと ack で延長する
+    pub consumer_group_lease_secs: i64,
+}
+
+/// 管理 RPC 設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdminConfig {
+    /// admin ロールの JWT を検証する署名鍵（None の場合は管理 RPC
+    /// を無効にする）
+    #[serde(skip_serializing)]
+    pub jwt_secret:          Option<String>,
+    /// 確認トークンの署名鍵（None の場合はストリームの削除などの破壊的な
+    /// 管理 RPC を無効にする）
+    #[serde(skip_serializing)]
+    pub confirmation_secret: Option<String>,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -68,7 +119,10 @@ impl Default for Config {
                 retention_days: 30,
             },
             event_bus:     EventBusConfig {
-                project_id:      "effect-project".to_string(),
+                backend:         shared_event_bus::EventBusConfig::PubSub {
+                    project_id:     "effect-project".to_string(),
+                    auto_provision: false,
+                },
                 topic_prefix:    "effect".to_string(),
                 enable_ordering: true,
                 max_batch_size:  100,
@@ -77,6 +131,18 @@ impl Default for Config {
                 url:               "http://localhost:50053".to_string(),
                 enable_validation: true,
             },
+            statistics:    StatisticsConfig {
+                refresh_interval_secs: 60,
+                top_streams:           10,
+                exact_count_row_limit: 100_000,
+                sample_rows:           10_000,
+            },
+            subscription:  SubscriptionConfig {
+                poll_interval_ms:          500,
+                batch_size:                500,
+                consumer_group_lease_secs: 30,
+            },
+            admin:         AdminConfig::default(),
         }
     }
 }
@@ -99,8 +165,7 @@ pub fn load() -> Result<Config, Box<dyn std::error::Error>> {
                 .parse()?,
         },
         event_bus:     EventBusConfig {
-            project_id:      std::env::var("GCP_PROJECT_ID")
-                .unwrap_or_else(|_| Config::default().event_bus.project_id),
+            backend:         shared_event_bus::EventBusConfig::from_env()?,
             topic_prefix:    std::env::var("PUBSUB_TOPIC_PREFIX")
                 .unwrap_or_else(|_| Config::default().event_bus.topic_prefix),
             enable_ordering: std::env::var("PUBSUB_ENABLE_ORDERING")
@@ -119,6 +184,35 @@ pub fn load() -> Result<Config, Box<dyn std::error::Error>> {
                 .parse()
                 .unwrap_or(true),
         },
+        statistics:    StatisticsConfig {
+            refresh_interval_secs: std::env::var("STORE_STATISTICS_REFRESH_SECS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()?,
+            top_streams:           std::env::var("STORE_STATISTICS_TOP_STREAMS")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()?,
+            exact_count_row_limit: std::env::var("STORE_STATISTICS_EXACT_COUNT_ROW_LIMIT")
+                .unwrap_or_else(|_| "100000".to_string())
+                .parse()?,
+            sample_rows:           std::env::var("STORE_STATISTICS_SAMPLE_ROWS")
+                .unwrap_or_else(|_| "10000".to_string())
+                .parse()?,
+        },
+        subscription:  SubscriptionConfig {
+            poll_interval_ms:          std::env::var("SUBSCRIPTION_POLL_INTERVAL_MS")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse()?,
+            batch_size:                std::env::var("SUBSCRIPTION_BATCH_SIZE")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse()?,
+            consumer_group_lease_secs: std::env::var("CONSUMER_GROUP_LEASE_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()?,
+        },
+        admin:         AdminConfig {
+            jwt_secret:          std::env::var("JWT_SECRET").ok(),
+            confirmation_secret: std::env::var("ADMIN_CONFIRMATION_SECRET").ok(),
+        },
     };
 
     Ok(config)
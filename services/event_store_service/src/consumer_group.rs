@@ -0,0 +1,289 @@
+//! コンシューマーグループ（サーバーで管理する購読）
+//!
+//! 同じグループ名で読む射影サービスの複数のインスタンスのうち、リースを
+//! 持つ1つだけがイベントを受け取り、他のインスタンスは待機する。
+//!
+//! - 受け取ったイベントを処理したら ack でチェックポイントを進める
+//! - リースは読み込みと ack のたびに延び、持ち主が止まると期限で切れる
+//! - 切れたリースは待機しているインスタンスが引き継ぎ、チェックポイントから
+//!   読み直す（ack していないイベントはもう一度届く）
+//!
+//! 1つのインスタンスが位置の順に流すため、射影はイベントの順序を保てる。
+
+use std::{future::Future, sync::Arc};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    repository::{EventStoreError, StoredEvent},
+    subscription::{SubscriptionFilter, SubscriptionSource},
+};
+
+/// コンシューマーグループの状態
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerGroup {
+    pub name:             String,
+    /// 作成時に決めたイベントの絞り込み
+    pub filter:           SubscriptionFilter,
+    /// 処理済みの最後の位置
+    pub checkpoint:       i64,
+    /// リースを持っているインスタンス
+    pub leaseholder:      Option<String>,
+    pub lease_expires_at: Option<DateTime<Utc>>,
+}
+
+impl ConsumerGroup {
+    /// リースを取得または延長する
+    ///
+    /// 他のインスタンスのリースが切れていなければ失敗する
+    pub fn lease(
+        &mut self,
+        consumer_id: &str,
+        now: DateTime<Utc>,
+        ttl: Duration,
+    ) -> Result<DateTime<Utc>, ConsumerGroupError> {
+        if let (Some(leaseholder), Some(expires_at)) = (&self.leaseholder, self.lease_expires_at)
+            && leaseholder != consumer_id
+            && expires_at > now
+        {
+            return Err(ConsumerGroupError::Leased {
+                group: self.name.clone(),
+                leaseholder: leaseholder.clone(),
+                expires_at,
+            });
+        }
+
+        let expires_at = now + ttl;
+        self.leaseholder = Some(consumer_id.to_string());
+        self.lease_expires_at = Some(expires_at);
+        Ok(expires_at)
+    }
+
+    /// `position` までを処理済みにし、リースを延長する
+    ///
+    /// リースを持っていない（他のインスタンスに引き継がれた）場合は失敗する。
+    /// チェックポイントは戻さない
+    pub fn ack(
+        &mut self,
+        consumer_id: &str,
+        position: i64,
+        now: DateTime<Utc>,
+        ttl: Duration,
+    ) -> Result<DateTime<Utc>, ConsumerGroupError> {
+        if self.leaseholder.as_deref() != Some(consumer_id) {
+            return Err(ConsumerGroupError::LeaseLost {
+                group:       self.name.clone(),
+                consumer_id: consumer_id.to_string(),
+            });
+        }
+
+        self.checkpoint = self.checkpoint.max(position);
+        let expires_at = now + ttl;
+        self.lease_expires_at = Some(expires_at);
+        Ok(expires_at)
+    }
+
+    /// リースを手放す（持っていなければ何もしない）
+    pub fn release(&mut self, consumer_id: &str) {
+        if self.leaseholder.as_deref() == Some(consumer_id) {
+            self.leaseholder = None;
+            self.lease_expires_at = None;
+        }
+    }
+}
+
+/// コンシューマーグループのエラー
+#[derive(Debug, thiserror::Error)]
+pub enum ConsumerGroupError {
+    #[error("Consumer group {group} is leased by {leaseholder} until {expires_at}")]
+    Leased {
+        group:       String,
+        leaseholder: String,
+        expires_at:  DateTime<Utc>,
+    },
+
+    #[error("Consumer {consumer_id} does not hold the lease of consumer group {group}")]
+    LeaseLost {
+        group:       String,
+        consumer_id: String,
+    },
+
+    #[error("Consumer group {0} was created with a different filter")]
+    FilterMismatch(String),
+
+    #[error("Consumer group not found: {0}")]
+    NotFound(String),
+
+    #[error(transparent)]
+    Store(#[from] EventStoreError),
+}
+
+/// コンシューマーグループの保存先
+pub trait ConsumerGroupStore: Send + Sync {
+    /// グループを他のインスタンスと競合しないように読み、`f` で変更した
+    /// 状態を保存する
+    ///
+    /// グループがなければ `filter` で作成する（None の場合は
+    /// [`ConsumerGroupError::NotFound`]）。`f` が失敗した場合は保存しない
+    fn update<R, F>(
+        &self,
+        name: &str,
+        filter: Option<&SubscriptionFilter>,
+        f: F,
+    ) -> impl Future<Output = Result<R, ConsumerGroupError>> + Send
+    where
+        R: Send,
+        F: FnOnce(&mut ConsumerGroup) -> Result<R, ConsumerGroupError> + Send;
+}
+
+/// リースを持つインスタンスが読んだイベント
+#[derive(Debug, Clone)]
+pub struct LeasedEvents {
+    pub events:           Vec<StoredEvent>,
+    pub checkpoint:       i64,
+    pub lease_expires_at: DateTime<Utc>,
+}
+
+/// コンシューマーグループの読み込みと ack
+pub struct ConsumerGroups<S> {
+    store:     Arc<S>,
+    lease_ttl: Duration,
+}
+
+impl<S> ConsumerGroups<S>
+where
+    S: ConsumerGroupStore + SubscriptionSource,
+{
+    /// 新しいインスタンスを作成
+    pub fn new(store: Arc<S>, lease_ttl: Duration) -> Self {
+        Self { store, lease_ttl }
+    }
+
+    /// リースを取得してチェックポイントより後のイベントを最大 `limit` 件読む
+    ///
+    /// グループがなければ `filter` で作成する
+    pub async fn read(
+        &self,
+        name: &str,
+        consumer_id: &str,
+        filter: &SubscriptionFilter,
+        limit: i64,
+        now: DateTime<Utc>,
+    ) -> Result<LeasedEvents, ConsumerGroupError> {
+        let ttl = self.lease_ttl;
+        let (checkpoint, filter, lease_expires_at) = self
+            .store
+            .update(name, Some(filter), |group| {
+                if &group.filter != filter {
+                    return Err(ConsumerGroupError::FilterMismatch(group.name.clone()));
+                }
+                let expires_at = group.lease(consumer_id, now, ttl)?;
+                Ok((group.checkpoint, group.filter.clone(), expires_at))
+            })
+            .await?;
+
+        let events = self
+            .store
+            .read_all_after(checkpoint, &filter, limit)
+            .await?;
+        Ok(LeasedEvents {
+            events,
+            checkpoint,
+            lease_expires_at,
+        })
+    }
+
+    /// `position` までを処理済みにし、延長したリースの期限を返す
+    pub async fn ack(
+        &self,
+        name: &str,
+        consumer_id: &str,
+        position: i64,
+        now: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, ConsumerGroupError> {
+        let ttl = self.lease_ttl;
+        self.store
+            .update(name, None, |group| {
+                group.ack(consumer_id, position, now, ttl)
+            })
+            .await
+    }
+
+    /// リースを手放し、待機しているインスタンスにすぐ引き継ぐ
+    pub async fn leave(&self, name: &str, consumer_id: &str) -> Result<(), ConsumerGroupError> {
+        self.store
+            .update(name, None, |group| {
+                group.release(consumer_id);
+                Ok(())
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group() -> ConsumerGroup {
+        ConsumerGroup {
+            name:             "vocabulary_search".to_string(),
+            filter:           SubscriptionFilter::default(),
+            checkpoint:       0,
+            leaseholder:      None,
+            lease_expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_lease_fails_over_after_expiry() {
+        let mut group = group();
+        let ttl = Duration::seconds(30);
+        let now = Utc::now();
+
+        let expires_at = group.lease("search-1", now, ttl).unwrap();
+        assert!(matches!(
+            group.lease("search-2", now + Duration::seconds(10), ttl),
+            Err(ConsumerGroupError::Leased { leaseholder, .. }) if leaseholder == "search-1"
+        ));
+        // 持ち主は期限内に延長できる
+        assert!(
+            group
+                .lease("search-1", now + Duration::seconds(10), ttl)
+                .unwrap()
+                > expires_at
+        );
+
+        let later = now + Duration::seconds(60);
+        group.lease("search-2", later, ttl).unwrap();
+        assert_eq!(group.leaseholder.as_deref(), Some("search-2"));
+    }
+
+    #[test]
+    fn test_ack_advances_checkpoint_only_for_the_leaseholder() {
+        let mut group = group();
+        let ttl = Duration::seconds(30);
+        let now = Utc::now();
+        group.lease("search-1", now, ttl).unwrap();
+
+        group.ack("search-1", 42, now, ttl).unwrap();
+        group.ack("search-1", 40, now, ttl).unwrap();
+        assert_eq!(group.checkpoint, 42);
+
+        // 引き継がれた後の ack はチェックポイントを進めない
+        group
+            .lease("search-2", now + Duration::seconds(60), ttl)
+            .unwrap();
+        assert!(matches!(
+            group.ack("search-1", 50, now, ttl),
+            Err(ConsumerGroupError::LeaseLost { .. })
+        ));
+        assert_eq!(group.checkpoint, 42);
+
+        group.release("search-1");
+        assert_eq!(group.leaseholder.as_deref(), Some("search-2"));
+        group.release("search-2");
+        group
+            .lease("search-1", now + Duration::seconds(61), ttl)
+            .unwrap();
+    }
+}
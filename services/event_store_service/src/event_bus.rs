@@ -1,16 +1,13 @@
-//! Event Bus (Google Pub/Sub) 統合
+//! Event Bus 統合
 //!
-//! Event Store Service に統合された Event Bus 機能
+//! Event Store Service に統合された Event Bus
+//! 機能。バックエンド（Pub/Sub、Kafka、
+//! インメモリ）は `shared_event_bus::from_config` で選択する
 
-use std::{collections::HashMap, sync::Arc};
+use std::collections::HashMap;
 
-use google_cloud_googleapis::pubsub::v1::PubsubMessage;
-use google_cloud_pubsub::{
-    client::{Client, ClientConfig},
-    publisher::Publisher,
-};
 use serde_json::Value as JsonValue;
-use tokio::sync::RwLock;
+use shared_event_bus::{AnyEventBus, OutgoingMessage, Topic, TopicNames};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
@@ -19,8 +16,8 @@ use crate::config::EventBusConfig;
 /// Event Bus のエラー
 #[derive(Debug, thiserror::Error)]
 pub enum EventBusError {
-    #[error("Pub/Sub client error: {0}")]
-    PubSubClient(#[from] google_cloud_pubsub::client::Error),
+    #[error("Event bus backend error: {0}")]
+    Backend(#[from] shared_event_bus::EventBusError),
 
     #[error("Publisher error: {0}")]
     Publisher(String),
@@ -33,33 +30,33 @@ pub enum EventBusError {
     TopicNotFound(String),
 }
 
-/// Event Bus (Pub/Sub Publisher)
+/// Event Bus (Publisher)
 pub struct EventBus {
-    client:     Client,
-    publishers: Arc<RwLock<HashMap<String, Publisher>>>,
-    config:     EventBusConfig,
+    bus:    AnyEventBus,
+    config: EventBusConfig,
+    topics: TopicNames,
 }
 
 impl EventBus {
     /// 新しい Event Bus を作成
     pub async fn new(config: EventBusConfig) -> Result<Self, EventBusError> {
-        // Pub/Sub クライアントを作成
-        let client_config = ClientConfig {
-            project_id: Some(config.project_id.clone()),
-            ..Default::default()
-        };
-
-        let client = Client::new(client_config).await?;
+        let bus = shared_event_bus::from_config(&config.backend).await?;
 
-        info!("Event Bus initialized with project: {}", config.project_id);
+        info!("Event Bus initialized with backend: {}", bus.backend());
 
+        let topics = TopicNames::new(config.topic_prefix.clone());
         Ok(Self {
-            client,
-            publishers: Arc::new(RwLock::new(HashMap::new())),
+            bus,
             config,
+            topics,
         })
     }
 
+    /// バックエンド名
+    pub fn backend(&self) -> &'static str {
+        self.bus.backend()
+    }
+
     /// イベントを発行
     pub async fn publish_event(
         &self,
@@ -70,16 +67,13 @@ impl EventBus {
         // イベントタイプからトピックを決定
         let topic = self.get_topic_for_event(event_type);
 
-        // メッセージを作成（Publisher を取得する前に作成）
-        let message = self.create_message(event_type, aggregate_id, event_data)?;
-
-        // Publisher を取得または作成
-        let publisher = self.get_or_create_publisher(&topic).await?;
+        // メッセージを作成
+        let message = self.create_message(topic, event_type, aggregate_id, event_data)?;
 
         // 発行
-        let awaiter = publisher.publish(message).await;
-        let message_id = awaiter
-            .get()
+        let message_id = self
+            .bus
+            .publish_message(message)
             .await
             .map_err(|e| EventBusError::Publisher(e.to_string()))?;
 
@@ -118,63 +112,26 @@ impl EventBus {
 
     /// イベントタイプからトピック名を決定
     fn get_topic_for_event(&self, event_type: &str) -> String {
-        // イベントタイプの最初の部分（コンテキスト）を取得
-        let context = event_type.split('.').next().unwrap_or("unknown");
-
-        // コンテキストごとのトピックにマッピング
-        let topic_suffix = match context {
-            "vocabulary" => "vocabulary-events",
-            "learning" => "learning-events",
-            "user" => "user-events",
-            "algorithm" => "algorithm-events",
-            "ai" => "ai-events",
-            "progress" => "progress-events",
-            _ => {
-                warn!("Unknown event context: {}, using default topic", context);
-                "unknown-events"
-            },
-        };
-
-        format!("{}-{}", self.config.topic_prefix, topic_suffix)
+        // イベントタイプの最初の部分（コンテキスト）のトピックにマッピング
+        let topic = Topic::for_event_type(event_type).unwrap_or_else(|| {
+            warn!(
+                "Unknown event context in {}, using default topic",
+                event_type
+            );
+            Topic::Unknown
+        });
+
+        self.topics.name(topic)
     }
 
-    /// Publisher を取得または作成
-    async fn get_or_create_publisher(&self, topic: &str) -> Result<Publisher, EventBusError> {
-        // 読み取りロックで確認
-        {
-            let publishers = self.publishers.read().await;
-            if let Some(publisher) = publishers.get(topic) {
-                return Ok(publisher.clone());
-            }
-        }
-
-        // 書き込みロックで作成
-        let mut publishers = self.publishers.write().await;
-
-        // 再度確認（二重チェックロッキング）
-        if let Some(publisher) = publishers.get(topic) {
-            return Ok(publisher.clone());
-        }
-
-        // トピックの存在確認（オプション）
-        let topic_path = self.client.topic(topic);
-
-        // Publisher を作成
-        let publisher = topic_path.new_publisher(None);
-
-        info!("Created publisher for topic: {}", topic);
-        publishers.insert(topic.to_string(), publisher.clone());
-
-        Ok(publisher)
-    }
-
-    /// Pub/Sub メッセージを作成
+    /// 発行するメッセージを作成
     fn create_message(
         &self,
+        topic: String,
         event_type: &str,
         aggregate_id: &Uuid,
         event_data: JsonValue,
-    ) -> Result<PubsubMessage, EventBusError> {
+    ) -> Result<OutgoingMessage, EventBusError> {
         // イベントデータをシリアライズ
         let data = serde_json::to_vec(&event_data)?;
 
@@ -186,17 +143,15 @@ impl EventBus {
         attributes.insert("source".to_string(), "event_store_service".to_string());
 
         // Ordering Key を設定（同じ集約のイベントは順序保証）
-        let ordering_key = if self.config.enable_ordering {
-            aggregate_id.to_string()
-        } else {
-            String::new()
-        };
+        let ordering_key = self
+            .config
+            .enable_ordering
+            .then(|| aggregate_id.to_string());
 
-        Ok(PubsubMessage {
+        Ok(OutgoingMessage {
+            topic,
             data,
             attributes,
-            message_id: String::new(), // Pub/Sub が自動生成
-            publish_time: None,
             ordering_key,
         })
     }
@@ -206,12 +161,10 @@ impl EventBus {
     pub async fn shutdown(&self) {
         info!("Shutting down Event Bus...");
 
-        // すべての Publisher をシャットダウン
-        let mut publishers = self.publishers.write().await;
-        for (topic, mut publisher) in publishers.drain() {
-            publisher.shutdown().await;
-            info!("Publisher for topic {} shut down", topic);
-        }
+        // 保留中のメッセージを送り切る
+        self.bus
+            .shutdown(shared_event_bus::DEFAULT_SHUTDOWN_DEADLINE)
+            .await;
 
         info!("Event Bus shutdown complete");
     }
@@ -219,13 +172,12 @@ impl EventBus {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
 
     #[test]
     fn test_topic_mapping() {
-        // get_topic_for_event メソッドの動作を検証
-        // このテストはトピック名のマッピングロジックを確認します
-
-        // EventBus::get_topic_for_event の実装を参照して期待値を設定
+        // get_topic_for_event と同じく、コンテキストのトピックに接頭辞を付ける
+        let topics = TopicNames::new("effect");
         let test_cases = vec![
             ("vocabulary.ItemCreated", "effect-vocabulary-events"),
             ("learning.SessionStarted", "effect-learning-events"),
@@ -236,10 +188,52 @@ mod tests {
         ];
 
         for (event_type, expected_topic) in test_cases {
-            // ここではロジックのテストのみ（実際の EventBus インスタンス化は不要）
-            let context = event_type.split('.').next().unwrap_or("unknown");
-            let topic = format!("effect-{context}-events");
-            assert_eq!(topic, expected_topic, "Failed for event type: {event_type}");
+            let topic = Topic::for_event_type(event_type).unwrap_or(Topic::Unknown);
+            assert_eq!(
+                topics.name(topic),
+                expected_topic,
+                "Failed for event type: {event_type}"
+            );
         }
     }
+
+    #[tokio::test]
+    async fn test_publish_event_with_memory_backend() {
+        use std::sync::{Arc, Mutex};
+
+        use shared_kernel::EventBus as _;
+
+        let config = EventBusConfig {
+            backend: shared_event_bus::EventBusConfig::Memory,
+            ..crate::config::Config::default().event_bus
+        };
+        let event_bus = super::EventBus::new(config).await.unwrap();
+        assert_eq!(event_bus.backend(), "memory");
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        event_bus
+            .bus
+            .subscribe("effect-vocabulary-events", move |data: &[u8]| {
+                sink.lock().unwrap().push(data.to_vec());
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        event_bus
+            .publish_event(
+                "vocabulary.ItemCreated",
+                &Uuid::new_v4(),
+                serde_json::json!({ "spelling": "bank" }),
+            )
+            .await
+            .unwrap();
+        // 購読者への配信は非同期なので、処理し終えるまで待つ
+        event_bus.shutdown().await;
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0], br#"{"spelling":"bank"}"#.to_vec());
+    }
 }
@@ -1,12 +1,37 @@
 //! gRPC サーバー実装
 
-use std::{net::SocketAddr, sync::Arc};
-
-use tonic::{Request, Response, Status, transport::Server};
+use std::{net::SocketAddr, pin::Pin, sync::Arc};
+
+use futures::{Stream, StreamExt, stream};
+use shared_admin_ops::{
+    AdminOperationRunner,
+    AdminOpsError,
+    ConfirmationSigner,
+    OperationPlan,
+    Operator,
+};
+use shared_security::Claims;
+use tonic::{Request, Response, Status, Streaming, transport::Server};
 use tracing::info;
 use uuid::Uuid;
 
-use crate::{config::Config, event_bus::EventBus, repository::PostgresEventStore};
+use crate::{
+    admin::{self, AdminAuthError},
+    admin_operations::{DeleteStream, RepositoryAuditLog},
+    config::Config,
+    consumer_group::{ConsumerGroupError, ConsumerGroups},
+    event_bus::EventBus,
+    repository::{self, PostgresEventStore},
+    statistics::{self, PostgresStatisticsSource, StatisticsCache},
+    subscription::{
+        self,
+        AppendNotifier,
+        Cursor,
+        EventStream,
+        SubscriptionFilter,
+        SubscriptionSource,
+    },
+};
 
 // Protocol Buffers から生成されたコード
 #[allow(clippy::all)]
@@ -26,6 +51,15 @@ use proto::{
 pub struct EventStoreServiceImpl {
     repository:           Arc<PostgresEventStore>,
     event_bus:            Arc<EventBus>,
+    statistics:           Arc<StatisticsCache<PostgresStatisticsSource>>,
+    notifier:             Arc<AppendNotifier>,
+    /// 購読で1回に読み込むイベント数
+    subscription_batch:   i64,
+    consumer_groups:      ConsumerGroups<PostgresEventStore>,
+    /// 管理 RPC の JWT の署名鍵
+    admin_jwt_secret:     Option<String>,
+    /// 破壊的な管理 RPC の2段階の実行（確認トークンの署名鍵がなければ無効）
+    admin_operations:     Option<AdminOperationRunner>,
     #[allow(dead_code)]
     domain_events_client: Option<DomainEventsClient>,
 }
@@ -35,59 +69,122 @@ struct DomainEventsClient {
     // TODO: 実際の gRPC クライアント実装
 }
 
-#[tonic::async_trait]
-impl EventStoreService for EventStoreServiceImpl {
-    async fn append_events(
-        &self,
-        request: Request<AppendEventsRequest>,
-    ) -> Result<Response<AppendEventsResponse>, Status> {
-        let req = request.into_inner();
+/// 購読でクライアントに流すイベント通知
+type EventNotificationStream =
+    Pin<Box<dyn Stream<Item = Result<EventNotification, Status>> + Send>>;
+
+impl EventStoreServiceImpl {
+    /// 保存したイベントを Event Bus に発行する
+    async fn publish(&self, stream_id: Uuid, stream_type: &str, events: Vec<serde_json::Value>) {
+        for (i, event) in events.into_iter().enumerate() {
+            let event_type = format!("{}.Event{}", stream_type, i); // TODO: 実際のイベントタイプ
+            if let Err(e) = self
+                .event_bus
+                .publish_event(&event_type, &stream_id, event)
+                .await
+            {
+                // エラーをログに記録して続行（At-least-once 保証）
+                tracing::error!("Failed to publish event to Event Bus: {}", e);
+            }
+        }
+    }
+
+    /// 管理 RPC を呼んだ admin ロールのクレームを取り出す
+    fn authorize_admin<T>(&self, request: &Request<T>) -> Result<Claims, Status> {
+        let authorization = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok());
+        admin::authorize(authorization, self.admin_jwt_secret.as_deref()).map_err(|e| match e {
+            AdminAuthError::MissingToken | AdminAuthError::InvalidToken(_) => {
+                Status::unauthenticated(e.to_string())
+            },
+            AdminAuthError::Disabled | AdminAuthError::NotAdmin(_) => {
+                Status::permission_denied(e.to_string())
+            },
+        })
+    }
 
+    fn subscribe_events(&self, cursor: Cursor) -> EventStream {
+        subscription::subscribe(
+            self.repository.clone(),
+            self.notifier.clone(),
+            cursor,
+            self.subscription_batch,
+        )
+    }
+
+    /// ストリームの購読リクエストから読み込み位置を決める
+    async fn stream_cursor(&self, req: SubscribeRequest) -> Result<Cursor, Status> {
         let stream_id = Uuid::parse_str(&req.stream_id)
             .map_err(|e| Status::invalid_argument(format!("Invalid stream_id: {e}")))?;
 
-        let events: Vec<serde_json::Value> = req
-            .events
-            .into_iter()
-            .map(|e| {
-                if let Some(any_data) = e.data {
-                    // Any の value を JSON として扱う
-                    serde_json::from_slice(&any_data.value).unwrap_or(serde_json::json!({}))
-                } else {
-                    serde_json::json!({})
-                }
-            })
-            .collect();
+        Cursor::stream(
+            self.repository.as_ref(),
+            stream_id,
+            req.stream_type,
+            req.from_version,
+            req.include_existing,
+        )
+        .await
+        .map_err(|e| Status::internal(format!("Failed to subscribe: {e}")))
+    }
 
-        let expected_version = if req.expected_version < 0 {
+    /// 全イベントの購読リクエストから読み込み位置を決める
+    async fn all_cursor(&self, req: SubscribeAllRequest) -> Result<Cursor, Status> {
+        // 位置を指定した場合はその位置より後から再開する
+        let position = if req.position.is_empty() {
             None
         } else {
-            Some(req.expected_version)
+            Some(
+                req.position
+                    .parse::<i64>()
+                    .map_err(|e| Status::invalid_argument(format!("Invalid position: {e}")))?,
+            )
+        };
+
+        // 合わないイベントは読み込みの時点で除き、クライアントに送らない
+        let filter = SubscriptionFilter {
+            event_types:  req.event_types,
+            stream_types: req.aggregate_types,
         };
 
+        Cursor::all(
+            self.repository.as_ref(),
+            position,
+            filter,
+            req.include_existing,
+        )
+        .await
+        .map_err(|e| Status::internal(format!("Failed to subscribe: {e}")))
+    }
+}
+
+#[tonic::async_trait]
+impl EventStoreService for EventStoreServiceImpl {
+    async fn append_events(
+        &self,
+        request: Request<AppendEventsRequest>,
+    ) -> Result<Response<AppendEventsResponse>, Status> {
+        let req = request.into_inner();
+
+        let append = to_stream_append(req)?;
+        let events = append.events.clone();
+
         let version = self
             .repository
             .append_events(
-                stream_id,
-                &req.stream_type,
-                events.clone(),
-                expected_version,
+                append.stream_id,
+                &append.stream_type,
+                append.events,
+                append.expected_version,
             )
             .await
             .map_err(|e| Status::internal(format!("Failed to append events: {e}")))?;
+        self.notifier.notify();
 
-        // Event Bus に発行
-        for (i, event) in events.into_iter().enumerate() {
-            let event_type = format!("{}.Event{}", req.stream_type, i); // TODO: 実際のイベントタイプ
-            if let Err(e) = self
-                .event_bus
-                .publish_event(&event_type, &stream_id, event)
-                .await
-            {
-                // エラーをログに記録して続行（At-least-once 保証）
-                tracing::error!("Failed to publish event to Event Bus: {}", e);
-            }
-        }
+        self.publish(append.stream_id, &append.stream_type, events)
+            .await;
 
         Ok(Response::new(AppendEventsResponse {
             next_version: version,
@@ -95,6 +192,53 @@ impl EventStoreService for EventStoreServiceImpl {
         }))
     }
 
+    async fn append_batch(
+        &self,
+        request: Request<AppendBatchRequest>,
+    ) -> Result<Response<AppendBatchResponse>, Status> {
+        let req = request.into_inner();
+
+        let appends = req
+            .streams
+            .into_iter()
+            .map(to_stream_append)
+            .collect::<Result<Vec<_>, Status>>()?;
+
+        let versions = self
+            .repository
+            .append_batch(appends.clone())
+            .await
+            .map_err(|e| match &e {
+                // 競合したストリームを読み直して再試行できるよう、内部エラーと分ける
+                repository::EventStoreError::BatchAppend { source, .. }
+                    if matches!(
+                        source.as_ref(),
+                        repository::EventStoreError::VersionConflict { .. }
+                    ) =>
+                {
+                    Status::aborted(e.to_string())
+                },
+                _ => Status::internal(format!("Failed to append batch: {e}")),
+            })?;
+        self.notifier.notify();
+
+        // コミットした後にストリームごとに Event Bus に発行する
+        for append in appends {
+            self.publish(append.stream_id, &append.stream_type, append.events)
+                .await;
+        }
+
+        Ok(Response::new(AppendBatchResponse {
+            streams: versions
+                .into_iter()
+                .map(|version| AppendEventsResponse {
+                    next_version: version,
+                    event_ids:    vec![], // TODO: 実際の event_id を返す
+                })
+                .collect(),
+        }))
+    }
+
     async fn get_events(
         &self,
         request: Request<GetEventsRequest>,
@@ -116,44 +260,7 @@ impl EventStoreService for EventStoreServiceImpl {
             .await
             .map_err(|e| Status::internal(format!("Failed to get events: {e}")))?;
 
-        let proto_events = events
-            .into_iter()
-            .map(|e| {
-                use std::collections::HashMap;
-
-                use prost_types::Any;
-
-                // JSON を Any に変換
-                let data_bytes = e.data.to_string().into_bytes();
-                let any_data = Any {
-                    type_url: "type.googleapis.com/effect.event_store.Event".to_string(),
-                    value:    data_bytes,
-                };
-
-                // metadata を HashMap に変換
-                let mut metadata_map = HashMap::new();
-                if let Some(obj) = e.metadata.as_object() {
-                    for (k, v) in obj {
-                        metadata_map.insert(k.clone(), v.to_string());
-                    }
-                }
-
-                StoredEvent {
-                    event_id:    e.event_id.to_string(),
-                    stream_id:   e.stream_id.to_string(),
-                    stream_type: e.stream_type,
-                    version:     e.version,
-                    event_type:  e.event_type,
-                    data:        Some(any_data),
-                    metadata:    metadata_map,
-                    created_at:  Some(prost_types::Timestamp {
-                        seconds: e.created_at.timestamp(),
-                        nanos:   e.created_at.timestamp_subsec_nanos() as i32,
-                    }),
-                    position:    e.position.to_string(),
-                }
-            })
-            .collect();
+        let proto_events = events.into_iter().map(to_proto_event).collect();
 
         Ok(Response::new(GetEventsResponse {
             events:           proto_events,
@@ -162,6 +269,45 @@ impl EventStoreService for EventStoreServiceImpl {
         }))
     }
 
+    async fn read_all(
+        &self,
+        request: Request<ReadAllRequest>,
+    ) -> Result<Response<ReadAllResponse>, Status> {
+        let req = request.into_inner();
+
+        let from_position = if req.from_position.is_empty() {
+            1
+        } else {
+            req.from_position
+                .parse::<i64>()
+                .map_err(|e| Status::invalid_argument(format!("Invalid from_position: {e}")))?
+        };
+        // 1回の応答が大きくなりすぎないよう、購読のバッチサイズで抑える
+        let limit = if req.max_count > 0 {
+            i64::from(req.max_count).min(self.subscription_batch)
+        } else {
+            self.subscription_batch
+        };
+
+        // 購読と同じく、実行中のトランザクションより後ろのイベントは返さない
+        let events = self
+            .repository
+            .read_all_after(from_position - 1, &SubscriptionFilter::default(), limit)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to read events: {e}")))?;
+
+        let caught_up = (events.len() as i64) < limit;
+        let next_position = events
+            .last()
+            .map_or(from_position, |event| event.position + 1);
+
+        Ok(Response::new(ReadAllResponse {
+            events: events.into_iter().map(to_notification).collect(),
+            next_position: next_position.to_string(),
+            caught_up,
+        }))
+    }
+
     async fn get_snapshot(
         &self,
         request: Request<GetSnapshotRequest>,
@@ -200,10 +346,7 @@ impl EventStoreService for EventStoreServiceImpl {
                     stream_type: s.stream_type.clone(),
                     version:     s.version,
                     data:        Some(any_data),
-                    created_at:  Some(prost_types::Timestamp {
-                        seconds: s.created_at.timestamp(),
-                        nanos:   s.created_at.timestamp_subsec_nanos() as i32,
-                    }),
+                    created_at:  Some(to_timestamp(s.created_at)),
                 }
             }),
             found:    snapshot.is_some(),
@@ -236,24 +379,505 @@ impl EventStoreService for EventStoreServiceImpl {
         }))
     }
 
-    type SubscribeToStreamStream = tonic::codec::Streaming<EventNotification>;
+    type SubscribeToStreamStream = EventNotificationStream;
 
     async fn subscribe_to_stream(
         &self,
-        _request: Request<SubscribeRequest>,
+        request: Request<SubscribeRequest>,
     ) -> Result<Response<Self::SubscribeToStreamStream>, Status> {
-        // TODO: 実装
-        Err(Status::unimplemented("Not implemented"))
+        let cursor = self.stream_cursor(request.into_inner()).await?;
+
+        Ok(Response::new(to_notifications(
+            self.subscribe_events(cursor),
+        )))
     }
 
-    type SubscribeToAllStream = tonic::codec::Streaming<EventNotification>;
+    type SubscribeToAllStream = EventNotificationStream;
 
     async fn subscribe_to_all(
         &self,
-        _request: Request<SubscribeAllRequest>,
+        request: Request<SubscribeAllRequest>,
     ) -> Result<Response<Self::SubscribeToAllStream>, Status> {
-        // TODO: 実装
-        Err(Status::unimplemented("Not implemented"))
+        let cursor = self.all_cursor(request.into_inner()).await?;
+
+        Ok(Response::new(to_notifications(
+            self.subscribe_events(cursor),
+        )))
+    }
+
+    type SubscribeStream = EventNotificationStream;
+
+    async fn subscribe(
+        &self,
+        request: Request<Streaming<SubscribeControl>>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let mut controls = request.into_inner();
+
+        let first = controls
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("Missing subscription request"))?;
+        let initial_credits = first.credits;
+        let cursor = match first.start {
+            Some(subscribe_control::Start::Stream(req)) => self.stream_cursor(req).await?,
+            Some(subscribe_control::Start::All(req)) => self.all_cursor(req).await?,
+            None => {
+                return Err(Status::invalid_argument(
+                    "The first message must start a subscription",
+                ));
+            },
+        };
+
+        // 最初のメッセージの許可から数え、受信が途切れたら購読を終える
+        let credits = stream::iter([initial_credits]).chain(stream::unfold(
+            controls,
+            |mut controls| async move {
+                let control = controls.message().await.ok()??;
+                Some((control.credits, controls))
+            },
+        ));
+        let events = subscription::with_credits(self.subscribe_events(cursor), credits);
+
+        Ok(Response::new(to_notifications(events)))
+    }
+
+    async fn read_consumer_group(
+        &self,
+        request: Request<ReadConsumerGroupRequest>,
+    ) -> Result<Response<ReadConsumerGroupResponse>, Status> {
+        let req = request.into_inner();
+        validate_consumer(&req.group_name, &req.consumer_id)?;
+
+        let filter = SubscriptionFilter {
+            event_types:  req.event_types,
+            stream_types: req.aggregate_types,
+        };
+        let limit = if req.max_events > 0 {
+            i64::from(req.max_events)
+        } else {
+            self.subscription_batch
+        };
+
+        let leased = self
+            .consumer_groups
+            .read(
+                &req.group_name,
+                &req.consumer_id,
+                &filter,
+                limit,
+                chrono::Utc::now(),
+            )
+            .await;
+        match leased {
+            Ok(leased) => Ok(Response::new(ReadConsumerGroupResponse {
+                leased:           true,
+                leaseholder:      req.consumer_id,
+                lease_expires_at: Some(to_timestamp(leased.lease_expires_at)),
+                checkpoint:       leased.checkpoint.to_string(),
+                events:           leased.events.into_iter().map(to_notification).collect(),
+            })),
+            // 他のインスタンスがリースを持っている間は待機させる
+            Err(ConsumerGroupError::Leased {
+                leaseholder,
+                expires_at,
+                ..
+            }) => Ok(Response::new(ReadConsumerGroupResponse {
+                leased: false,
+                leaseholder,
+                lease_expires_at: Some(to_timestamp(expires_at)),
+                checkpoint: String::new(),
+                events: Vec::new(),
+            })),
+            Err(e) => Err(consumer_group_status(e)),
+        }
+    }
+
+    async fn ack_consumer_group(
+        &self,
+        request: Request<AckConsumerGroupRequest>,
+    ) -> Result<Response<AckConsumerGroupResponse>, Status> {
+        let req = request.into_inner();
+        validate_consumer(&req.group_name, &req.consumer_id)?;
+
+        let position = req
+            .position
+            .parse::<i64>()
+            .map_err(|e| Status::invalid_argument(format!("Invalid position: {e}")))?;
+
+        let expires_at = self
+            .consumer_groups
+            .ack(
+                &req.group_name,
+                &req.consumer_id,
+                position,
+                chrono::Utc::now(),
+            )
+            .await
+            .map_err(consumer_group_status)?;
+
+        Ok(Response::new(AckConsumerGroupResponse {
+            lease_expires_at: Some(to_timestamp(expires_at)),
+        }))
+    }
+
+    async fn leave_consumer_group(
+        &self,
+        request: Request<LeaveConsumerGroupRequest>,
+    ) -> Result<Response<LeaveConsumerGroupResponse>, Status> {
+        let req = request.into_inner();
+        validate_consumer(&req.group_name, &req.consumer_id)?;
+
+        self.consumer_groups
+            .leave(&req.group_name, &req.consumer_id)
+            .await
+            .map_err(consumer_group_status)?;
+
+        Ok(Response::new(LeaveConsumerGroupResponse {}))
+    }
+
+    async fn get_stream_statistics(
+        &self,
+        request: Request<GetStreamStatisticsRequest>,
+    ) -> Result<Response<GetStreamStatisticsResponse>, Status> {
+        self.authorize_admin(&request)?;
+        let req = request.into_inner();
+
+        let stream_id = Uuid::parse_str(&req.stream_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid stream_id: {e}")))?;
+
+        let stream = self
+            .repository
+            .stream_statistics(stream_id, &req.stream_type)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to get stream statistics: {e}")))?
+            .ok_or_else(|| Status::not_found(format!("Stream not found: {stream_id}")))?;
+
+        Ok(Response::new(GetStreamStatisticsResponse {
+            stream: Some(to_proto_stream_length(&stream)),
+        }))
+    }
+
+    async fn list_streams(
+        &self,
+        request: Request<ListStreamsRequest>,
+    ) -> Result<Response<ListStreamsResponse>, Status> {
+        self.authorize_admin(&request)?;
+        let req = request.into_inner();
+
+        // ページトークンは前のページの最後のストリーム（stream_id/stream_type）
+        let after = if req.page_token.is_empty() {
+            None
+        } else {
+            let (stream_id, stream_type) = req
+                .page_token
+                .split_once('/')
+                .ok_or_else(|| Status::invalid_argument("Invalid page_token"))?;
+            let stream_id = Uuid::parse_str(stream_id)
+                .map_err(|e| Status::invalid_argument(format!("Invalid page_token: {e}")))?;
+            Some((stream_id, stream_type.to_string()))
+        };
+        let limit = if req.page_size > 0 {
+            i64::from(req.page_size)
+        } else {
+            100
+        };
+
+        let streams = self
+            .repository
+            .list_streams(&req.aggregate_type, after, limit)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to list streams: {e}")))?;
+
+        let next_page_token = match streams.last() {
+            Some(last) if streams.len() as i64 == limit => {
+                format!("{}/{}", last.stream_id, last.stream_type)
+            },
+            _ => String::new(),
+        };
+
+        Ok(Response::new(ListStreamsResponse {
+            streams: streams.iter().map(to_proto_stream_length).collect(),
+            next_page_token,
+        }))
+    }
+
+    async fn delete_stream(
+        &self,
+        request: Request<DeleteStreamRequest>,
+    ) -> Result<Response<DeleteStreamResponse>, Status> {
+        let claims = self.authorize_admin(&request)?;
+        let runner = self.admin_operations.as_ref().ok_or_else(|| {
+            Status::permission_denied(
+                "Destructive admin RPCs are disabled (ADMIN_CONFIRMATION_SECRET is not set)",
+            )
+        })?;
+        let req = request.into_inner();
+
+        let stream_id = Uuid::parse_str(&req.stream_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid stream_id: {e}")))?;
+        let exists = self
+            .repository
+            .stream_statistics(stream_id, &req.stream_type)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to load stream: {e}")))?
+            .is_some();
+        if !exists {
+            return Err(Status::not_found(
+                repository::EventStoreError::StreamNotFound(stream_id).to_string(),
+            ));
+        }
+
+        let operation = DeleteStream::new(self.repository.clone(), stream_id, &req.stream_type);
+        if req.dry_run {
+            let dry_run = runner
+                .dry_run(&operation, chrono::Utc::now())
+                .await
+                .map_err(admin_operation_status)?;
+            return Ok(Response::new(DeleteStreamResponse {
+                deleted_events:     0,
+                plan:               Some(to_proto_plan(&dry_run.plan)?),
+                confirmation_token: dry_run.token.value,
+                token_expires_at:   Some(to_timestamp(dry_run.token.expires_at)),
+                audit_event_id:     String::new(),
+            }));
+        }
+
+        if req.confirmation_token.is_empty() {
+            return Err(Status::failed_precondition(
+                "confirmation_token is required; call with dry_run first to obtain one",
+            ));
+        }
+        let operator =
+            Operator::from_claims(&claims).map_err(|e| Status::permission_denied(e.to_string()))?;
+        let executed = runner
+            .execute(
+                &operation,
+                &req.confirmation_token,
+                &operator,
+                chrono::Utc::now(),
+            )
+            .await
+            .map_err(admin_operation_status)?;
+        tracing::warn!(
+            operator = %claims.sub,
+            %stream_id,
+            stream_type = %req.stream_type,
+            deleted_events = operation.deleted_events(),
+            audit_event_id = %executed.event_id,
+            "Deleted stream"
+        );
+
+        Ok(Response::new(DeleteStreamResponse {
+            deleted_events:     i64::try_from(operation.deleted_events()).unwrap_or(i64::MAX),
+            plan:               None,
+            confirmation_token: String::new(),
+            token_expires_at:   None,
+            audit_event_id:     executed.event_id.to_string(),
+        }))
+    }
+
+    async fn get_store_statistics(
+        &self,
+        _request: Request<GetStoreStatisticsRequest>,
+    ) -> Result<Response<GetStoreStatisticsResponse>, Status> {
+        // 重いクエリは実行せず、バックグラウンドで計算したキャッシュを返す
+        let cached = self
+            .statistics
+            .cached(chrono::Utc::now())
+            .ok_or_else(|| Status::unavailable("Store statistics have not been computed yet"))?;
+        let statistics = cached.statistics.as_ref();
+
+        Ok(Response::new(GetStoreStatisticsResponse {
+            aggregate_types: statistics
+                .aggregate_types
+                .iter()
+                .map(|a| AggregateTypeStatistics {
+                    aggregate_type:          a.aggregate_type.clone(),
+                    event_count:             a.event_count,
+                    stream_count:            a.stream_count,
+                    counts_estimated:        a.counts_estimated,
+                    p50_stream_length:       a.p50_stream_length,
+                    p99_stream_length:       a.p99_stream_length,
+                    stream_lengths_sampled:  a.stream_lengths_sampled,
+                    snapshot_coverage:       a.snapshot_coverage,
+                    archive_eligible_events: a.archive_eligible_events,
+                })
+                .collect(),
+            longest_streams: statistics
+                .longest_streams
+                .iter()
+                .map(to_proto_stream_length)
+                .collect(),
+            tables:          statistics
+                .tables
+                .iter()
+                .map(|t| TableHealth {
+                    table:          t.table.clone(),
+                    estimated_rows: t.estimated_rows,
+                    dead_rows:      t.dead_rows,
+                    table_bytes:    t.table_bytes,
+                    index_bytes:    t.index_bytes,
+                })
+                .collect(),
+            indexes:         statistics
+                .indexes
+                .iter()
+                .map(|i| IndexHealth {
+                    index:                 i.index.clone(),
+                    table:                 i.table.clone(),
+                    index_bytes:           i.index_bytes,
+                    estimated_bloat_bytes: i.estimated_bloat_bytes,
+                })
+                .collect(),
+            computed_at:     Some(to_timestamp(statistics.computed_at)),
+            stale:           cached.stale,
+        }))
+    }
+}
+
+/// イベント追加リクエストをストリームへの追記に変換する
+fn to_stream_append(req: AppendEventsRequest) -> Result<repository::StreamAppend, Status> {
+    let stream_id = Uuid::parse_str(&req.stream_id)
+        .map_err(|e| Status::invalid_argument(format!("Invalid stream_id: {e}")))?;
+
+    let events = req
+        .events
+        .into_iter()
+        .map(|e| {
+            if let Some(any_data) = e.data {
+                // Any の value を JSON として扱う
+                serde_json::from_slice(&any_data.value).unwrap_or(serde_json::json!({}))
+            } else {
+                serde_json::json!({})
+            }
+        })
+        .collect();
+
+    let expected_version = if req.expected_version < 0 {
+        None
+    } else {
+        Some(req.expected_version)
+    };
+
+    Ok(repository::StreamAppend {
+        stream_id,
+        stream_type: req.stream_type,
+        events,
+        expected_version,
+    })
+}
+
+/// 保存されたイベントを gRPC のメッセージに変換する
+fn to_proto_event(e: repository::StoredEvent) -> StoredEvent {
+    use std::collections::HashMap;
+
+    use prost_types::Any;
+
+    // JSON を Any に変換
+    let data_bytes = e.data.to_string().into_bytes();
+    let any_data = Any {
+        type_url: "type.googleapis.com/effect.event_store.Event".to_string(),
+        value:    data_bytes,
+    };
+
+    // metadata を HashMap に変換
+    let mut metadata_map = HashMap::new();
+    if let Some(obj) = e.metadata.as_object() {
+        for (k, v) in obj {
+            metadata_map.insert(k.clone(), v.to_string());
+        }
+    }
+
+    StoredEvent {
+        event_id:    e.event_id.to_string(),
+        stream_id:   e.stream_id.to_string(),
+        stream_type: e.stream_type,
+        version:     e.version,
+        event_type:  e.event_type,
+        data:        Some(any_data),
+        metadata:    metadata_map,
+        created_at:  Some(to_timestamp(e.created_at)),
+        position:    e.position.to_string(),
+    }
+}
+
+/// グループ名と読み手のインスタンス ID が指定されているか確認する
+fn validate_consumer(group_name: &str, consumer_id: &str) -> Result<(), Status> {
+    if group_name.is_empty() || consumer_id.is_empty() {
+        return Err(Status::invalid_argument(
+            "group_name and consumer_id are required",
+        ));
+    }
+    Ok(())
+}
+
+/// コンシューマーグループのエラーを gRPC のステータスに変換する
+fn consumer_group_status(error: ConsumerGroupError) -> Status {
+    match error {
+        ConsumerGroupError::Leased { .. } | ConsumerGroupError::LeaseLost { .. } => {
+            Status::failed_precondition(error.to_string())
+        },
+        ConsumerGroupError::FilterMismatch(_) => Status::invalid_argument(error.to_string()),
+        ConsumerGroupError::NotFound(_) => Status::not_found(error.to_string()),
+        ConsumerGroupError::Store(e) => {
+            Status::internal(format!("Failed to update consumer group: {e}"))
+        },
+    }
+}
+
+/// ストリームの長さを gRPC のメッセージに変換する
+fn to_proto_stream_length(s: &statistics::StreamLength) -> StreamLength {
+    StreamLength {
+        stream_id:        s.stream_id.to_string(),
+        stream_type:      s.stream_type.clone(),
+        aggregate_type:   s.aggregate_type.clone(),
+        length:           s.length,
+        last_appended_at: Some(to_timestamp(s.last_appended_at)),
+        last_version:     s.length - 1,
+    }
+}
+
+/// 購読するイベントの列をクライアントに流すイベント通知の列に変換する
+fn to_notifications(events: EventStream) -> EventNotificationStream {
+    Box::pin(events.map(|event| {
+        let event = event.map_err(|e| Status::internal(format!("Failed to read events: {e}")))?;
+        Ok(to_notification(event))
+    }))
+}
+
+/// 保存されたイベントを位置付きのイベント通知に変換する
+fn to_notification(event: repository::StoredEvent) -> EventNotification {
+    EventNotification {
+        position: event.position.to_string(),
+        event:    Some(to_proto_event(event)),
+    }
+}
+
+fn to_timestamp(time: chrono::DateTime<chrono::Utc>) -> prost_types::Timestamp {
+    shared_kernel::to_proto_timestamp(&time)
+}
+
+fn to_proto_plan(plan: &OperationPlan) -> Result<AdminOperationPlan, Status> {
+    Ok(AdminOperationPlan {
+        operation: plan.operation.clone(),
+        target:    plan.target.clone(),
+        steps:     plan.steps.clone(),
+        affected:  plan.affected.clone().into_iter().collect(),
+        plan_hash: plan.hash().map_err(admin_operation_status)?,
+    })
+}
+
+/// 管理操作のエラーを gRPC のステータスに変換する
+fn admin_operation_status(e: AdminOpsError) -> Status {
+    match e {
+        AdminOpsError::InvalidToken => Status::invalid_argument(e.to_string()),
+        AdminOpsError::TokenExpired
+        | AdminOpsError::TokenAlreadyUsed
+        | AdminOpsError::PlanChanged { .. } => Status::failed_precondition(e.to_string()),
+        AdminOpsError::Unauthorized(_) => Status::permission_denied(e.to_string()),
+        AdminOpsError::Operation(_) | AdminOpsError::Audit(_) | AdminOpsError::Serialization(_) => {
+            Status::internal(e.to_string())
+        },
     }
 }
 
@@ -262,6 +886,8 @@ pub async fn start_server(
     config: Config,
     repository: PostgresEventStore,
     event_bus: EventBus,
+    statistics: Arc<StatisticsCache<PostgresStatisticsSource>>,
+    notifier: Arc<AppendNotifier>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let addr: SocketAddr = format!("0.0.0.0:{}", config.port).parse()?;
 
@@ -273,9 +899,29 @@ pub async fn start_server(
         None
     };
 
+    // コンシューマーグループのリースは読み込みと ack のたびに延長する
+    let repository = Arc::new(repository);
+    let consumer_groups = ConsumerGroups::new(
+        repository.clone(),
+        chrono::Duration::seconds(config.subscription.consumer_group_lease_secs),
+    );
+    // 破壊的な管理 RPC の監査ログはこのストアの専用ストリームに追記する
+    let admin_operations = config.admin.confirmation_secret.clone().map(|secret| {
+        AdminOperationRunner::new(
+            ConfirmationSigner::new(secret),
+            Arc::new(RepositoryAuditLog::new(repository.clone())),
+        )
+    });
+
     let service = EventStoreServiceImpl {
-        repository: Arc::new(repository),
+        repository,
         event_bus: Arc::new(event_bus),
+        statistics,
+        notifier,
+        subscription_batch: config.subscription.batch_size,
+        consumer_groups,
+        admin_jwt_secret: config.admin.jwt_secret.clone(),
+        admin_operations,
         domain_events_client,
     };
 
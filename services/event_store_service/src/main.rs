@@ -1,11 +1,18 @@
 //! Event Store Service - イベントストアの中央管理サービス
 
+use std::{sync::Arc, time::Duration};
+
 use tracing::info;
 
+mod admin;
+mod admin_operations;
 mod config;
+mod consumer_group;
 mod event_bus;
 mod grpc;
 mod repository;
+mod statistics;
+mod subscription;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -35,10 +42,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Event Bus 初期化
     let event_bus = event_bus::EventBus::new(config.event_bus.clone()).await?;
-    info!("Event Bus (Pub/Sub) initialized");
+    info!("Event Bus ({}) initialized", event_bus.backend());
+
+    // ストア統計（重いクエリはバックグラウンドで定期的に実行する）
+    let statistics_cache = Arc::new(statistics::StatisticsCache::new(
+        statistics::PostgresStatisticsSource::new(
+            pool.clone(),
+            config.statistics.clone(),
+            config.snapshot.clone(),
+        ),
+        Duration::from_secs(config.statistics.refresh_interval_secs),
+    ));
+    statistics::spawn_refresher(statistics_cache.clone());
+
+    // 購読（このインスタンスの追記で待っている購読を起こす）
+    let notifier = Arc::new(subscription::AppendNotifier::new(Duration::from_millis(
+        config.subscription.poll_interval_ms,
+    )));
 
     // gRPC サーバー起動
-    grpc::start_server(config, repository, event_bus).await?;
+    grpc::start_server(config, repository, event_bus, statistics_cache, notifier).await?;
 
     Ok(())
 }
@@ -1,9 +1,32 @@
 //! Event Store リポジトリ実装
 
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
+use crate::{
+    consumer_group::{ConsumerGroup, ConsumerGroupError, ConsumerGroupStore},
+    statistics::StreamLength,
+    subscription::{SubscriptionFilter, SubscriptionSource},
+};
+
+/// イベントの列（event_id, stream_id, stream_type, version, event_type, data,
+/// metadata, created_at, position）
+type EventRow = (
+    Uuid,
+    Uuid,
+    String,
+    i64,
+    String,
+    serde_json::Value,
+    serde_json::Value,
+    DateTime<Utc>,
+    i64,
+);
+
+/// ストリームの列（stream_id, stream_type, aggregate_type, length, updated_at）
+type StreamRow = (Uuid, String, String, i64, DateTime<Utc>);
+
 /// PostgreSQL ベースの Event Store
 pub struct PostgresEventStore {
     pool: PgPool,
@@ -24,54 +47,44 @@ impl PostgresEventStore {
         expected_version: Option<i64>,
     ) -> Result<i64, EventStoreError> {
         let mut tx = self.pool.begin().await?;
+        let next_version =
+            append_in(&mut tx, stream_id, stream_type, events, expected_version).await?;
+        tx.commit().await?;
 
-        // 現在のバージョンを取得
-        let current_version: Option<i64> = sqlx::query_scalar(
-            "SELECT MAX(version) FROM events WHERE stream_id = $1 AND stream_type = $2",
-        )
-        .bind(stream_id)
-        .bind(stream_type)
-        .fetch_one(&mut *tx)
-        .await?;
-
-        let current_version = current_version.unwrap_or(-1);
-
-        // 楽観的ロックのチェック
-        if let Some(expected) = expected_version
-            && current_version != expected
-        {
-            return Err(EventStoreError::VersionConflict {
-                expected,
-                actual: current_version,
-            });
-        }
-
-        let mut next_version = current_version;
+        Ok(next_version)
+    }
 
-        // イベントを挿入
-        for event in events {
-            next_version += 1;
-            let event_id = Uuid::new_v4();
-            let metadata = serde_json::json!({});
+    /// 複数のストリームへのイベントを1つのトランザクションで保存
+    ///
+    /// どれかのストリームが失敗するとすべて取り消す。同じストリームを
+    /// 複数回含む場合は前の追記の後のバージョンで確認する。戻り値は
+    /// `appends` と同じ順のストリームごとの最新のバージョン
+    pub async fn append_batch(
+        &self,
+        appends: Vec<StreamAppend>,
+    ) -> Result<Vec<i64>, EventStoreError> {
+        let mut tx = self.pool.begin().await?;
 
-            sqlx::query(
-                "INSERT INTO events (event_id, stream_id, stream_type, version, event_type, data, metadata, created_at) 
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())"
+        let mut versions = Vec::with_capacity(appends.len());
+        for append in appends {
+            let version = append_in(
+                &mut tx,
+                append.stream_id,
+                &append.stream_type,
+                append.events,
+                append.expected_version,
             )
-            .bind(event_id)
-            .bind(stream_id)
-            .bind(stream_type)
-            .bind(next_version)
-            .bind("Event") // TODO: 実際のイベントタイプを使用
-            .bind(&event)
-            .bind(&metadata)
-            .execute(&mut *tx)
-            .await?;
+            .await
+            .map_err(|e| EventStoreError::BatchAppend {
+                stream_id: append.stream_id,
+                source:    Box::new(e),
+            })?;
+            versions.push(version);
         }
 
         tx.commit().await?;
 
-        Ok(next_version)
+        Ok(versions)
     }
 
     /// イベントを取得
@@ -83,20 +96,7 @@ impl PostgresEventStore {
         to_version: Option<i64>,
     ) -> Result<Vec<StoredEvent>, EventStoreError> {
         let query = if let Some(to) = to_version {
-            sqlx::query_as::<
-                _,
-                (
-                    Uuid,
-                    Uuid,
-                    String,
-                    i64,
-                    String,
-                    serde_json::Value,
-                    serde_json::Value,
-                    DateTime<Utc>,
-                    i64,
-                ),
-            >(
+            sqlx::query_as::<_, EventRow>(
                 "SELECT event_id, stream_id, stream_type, version, event_type, data, metadata, \
                  created_at, position 
                  FROM events 
@@ -108,20 +108,7 @@ impl PostgresEventStore {
             .bind(from_version)
             .bind(to)
         } else {
-            sqlx::query_as::<
-                _,
-                (
-                    Uuid,
-                    Uuid,
-                    String,
-                    i64,
-                    String,
-                    serde_json::Value,
-                    serde_json::Value,
-                    DateTime<Utc>,
-                    i64,
-                ),
-            >(
+            sqlx::query_as::<_, EventRow>(
                 "SELECT event_id, stream_id, stream_type, version, event_type, data, metadata, \
                  created_at, position 
                  FROM events 
@@ -135,22 +122,108 @@ impl PostgresEventStore {
 
         let rows = query.fetch_all(&self.pool).await?;
 
-        let events = rows
-            .into_iter()
-            .map(|row| StoredEvent {
-                event_id:    row.0,
-                stream_id:   row.1,
-                stream_type: row.2,
-                version:     row.3,
-                event_type:  row.4,
-                data:        row.5,
-                metadata:    row.6,
-                created_at:  row.7,
-                position:    row.8,
-            })
-            .collect();
-
-        Ok(events)
+        Ok(rows.into_iter().map(stored_event).collect())
+    }
+
+    /// ストリームの長さと最後に追加された日時を取得
+    pub async fn stream_statistics(
+        &self,
+        stream_id: Uuid,
+        stream_type: &str,
+    ) -> Result<Option<StreamLength>, EventStoreError> {
+        let row = sqlx::query_as::<_, StreamRow>(
+            "SELECT stream_id, stream_type, aggregate_type, version + 1, updated_at
+             FROM event_streams
+             WHERE stream_id = $1 AND stream_type = $2",
+        )
+        .bind(stream_id)
+        .bind(stream_type)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(stream_length))
+    }
+
+    /// 集約タイプのストリームを (stream_id, stream_type) の順に最大 `limit`
+    /// 件取得
+    ///
+    /// `after` を指定するとそのストリームより後から取得する
+    pub async fn list_streams(
+        &self,
+        aggregate_type: &str,
+        after: Option<(Uuid, String)>,
+        limit: i64,
+    ) -> Result<Vec<StreamLength>, EventStoreError> {
+        let (after_id, after_type) = after.unzip();
+        let rows = sqlx::query_as::<_, StreamRow>(
+            "SELECT stream_id, stream_type, aggregate_type, version + 1, updated_at
+             FROM event_streams
+             WHERE aggregate_type = $1
+               AND ($2::uuid IS NULL OR (stream_id, stream_type) > ($2, $3))
+             ORDER BY stream_id, stream_type
+             LIMIT $4",
+        )
+        .bind(aggregate_type)
+        .bind(after_id)
+        .bind(after_type)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(stream_length).collect())
+    }
+
+    /// ストリームのスナップショットの件数を取得
+    pub async fn count_snapshots(
+        &self,
+        stream_id: Uuid,
+        stream_type: &str,
+    ) -> Result<i64, EventStoreError> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM snapshots WHERE stream_id = $1 AND stream_type = $2",
+        )
+        .bind(stream_id)
+        .bind(stream_type)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// ストリームのイベントとスナップショットを削除し、削除したイベント数を返す
+    pub async fn delete_stream(
+        &self,
+        stream_id: Uuid,
+        stream_type: &str,
+    ) -> Result<u64, EventStoreError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM snapshots WHERE stream_id = $1 AND stream_type = $2")
+            .bind(stream_id)
+            .bind(stream_type)
+            .execute(&mut *tx)
+            .await?;
+
+        let deleted = sqlx::query("DELETE FROM events WHERE stream_id = $1 AND stream_type = $2")
+            .bind(stream_id)
+            .bind(stream_type)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        let stream =
+            sqlx::query("DELETE FROM event_streams WHERE stream_id = $1 AND stream_type = $2")
+                .bind(stream_id)
+                .bind(stream_type)
+                .execute(&mut *tx)
+                .await?;
+        if stream.rows_affected() == 0 && deleted == 0 {
+            return Err(EventStoreError::StreamNotFound(stream_id));
+        }
+
+        tx.commit().await?;
+
+        Ok(deleted)
     }
 
     /// スナップショットを保存
@@ -224,6 +297,247 @@ impl PostgresEventStore {
     }
 }
 
+impl SubscriptionSource for PostgresEventStore {
+    async fn read_all_after(
+        &self,
+        position: i64,
+        filter: &SubscriptionFilter,
+        limit: i64,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        // 実行中のトランザクションが書き込んだイベントより後ろは返さないため、
+        // 位置の小さいイベントが後からコミットされても読み飛ばさない
+        let rows = sqlx::query_as::<_, EventRow>(
+            "SELECT event_id, stream_id, stream_type, version, event_type, data, metadata, \
+             created_at, position
+             FROM events
+             WHERE position > $1
+               AND transaction_id < pg_snapshot_xmin(pg_current_snapshot())
+               AND (cardinality($2::text[]) = 0 OR event_type = ANY($2))
+               AND (cardinality($3::text[]) = 0 OR stream_type = ANY($3))
+             ORDER BY position
+             LIMIT $4",
+        )
+        .bind(position)
+        .bind(&filter.event_types)
+        .bind(&filter.stream_types)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(stored_event).collect())
+    }
+
+    async fn read_stream_after(
+        &self,
+        stream_id: Uuid,
+        stream_type: &str,
+        version: i64,
+        limit: i64,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let rows = sqlx::query_as::<_, EventRow>(
+            "SELECT event_id, stream_id, stream_type, version, event_type, data, metadata, \
+             created_at, position
+             FROM events
+             WHERE stream_id = $1 AND stream_type = $2 AND version > $3
+             ORDER BY version
+             LIMIT $4",
+        )
+        .bind(stream_id)
+        .bind(stream_type)
+        .bind(version)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(stored_event).collect())
+    }
+
+    async fn head_position(&self) -> Result<i64, EventStoreError> {
+        let position: Option<i64> = sqlx::query_scalar(
+            "SELECT MAX(position) FROM events
+             WHERE transaction_id < pg_snapshot_xmin(pg_current_snapshot())",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(position.unwrap_or(0))
+    }
+
+    async fn stream_version(
+        &self,
+        stream_id: Uuid,
+        stream_type: &str,
+    ) -> Result<i64, EventStoreError> {
+        let version: Option<i64> = sqlx::query_scalar(
+            "SELECT MAX(version) FROM events WHERE stream_id = $1 AND stream_type = $2",
+        )
+        .bind(stream_id)
+        .bind(stream_type)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(version.unwrap_or(-1))
+    }
+}
+
+impl ConsumerGroupStore for PostgresEventStore {
+    async fn update<R, F>(
+        &self,
+        name: &str,
+        filter: Option<&SubscriptionFilter>,
+        f: F,
+    ) -> Result<R, ConsumerGroupError>
+    where
+        R: Send,
+        F: FnOnce(&mut ConsumerGroup) -> Result<R, ConsumerGroupError> + Send,
+    {
+        let mut tx = self.pool.begin().await.map_err(EventStoreError::from)?;
+
+        if let Some(filter) = filter {
+            sqlx::query(
+                "INSERT INTO consumer_groups (group_name, event_types, stream_types)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (group_name) DO NOTHING",
+            )
+            .bind(name)
+            .bind(&filter.event_types)
+            .bind(&filter.stream_types)
+            .execute(&mut *tx)
+            .await
+            .map_err(EventStoreError::from)?;
+        }
+
+        // 行ロックで他のインスタンスのリースの取得と ack を待たせる
+        let row = sqlx::query_as::<
+            _,
+            (
+                Vec<String>,
+                Vec<String>,
+                i64,
+                Option<String>,
+                Option<DateTime<Utc>>,
+            ),
+        >(
+            "SELECT event_types, stream_types, checkpoint, leaseholder, lease_expires_at
+             FROM consumer_groups
+             WHERE group_name = $1
+             FOR UPDATE",
+        )
+        .bind(name)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(EventStoreError::from)?
+        .ok_or_else(|| ConsumerGroupError::NotFound(name.to_string()))?;
+
+        let mut group = ConsumerGroup {
+            name:             name.to_string(),
+            filter:           SubscriptionFilter {
+                event_types:  row.0,
+                stream_types: row.1,
+            },
+            checkpoint:       row.2,
+            leaseholder:      row.3,
+            lease_expires_at: row.4,
+        };
+        let result = f(&mut group)?;
+
+        sqlx::query(
+            "UPDATE consumer_groups
+             SET checkpoint = $2, leaseholder = $3, lease_expires_at = $4, updated_at = NOW()
+             WHERE group_name = $1",
+        )
+        .bind(name)
+        .bind(group.checkpoint)
+        .bind(&group.leaseholder)
+        .bind(group.lease_expires_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(EventStoreError::from)?;
+
+        tx.commit().await.map_err(EventStoreError::from)?;
+        Ok(result)
+    }
+}
+
+/// トランザクションの中でストリームにイベントを保存し、最新のバージョンを返す
+async fn append_in(
+    tx: &mut Transaction<'_, Postgres>,
+    stream_id: Uuid,
+    stream_type: &str,
+    events: Vec<serde_json::Value>,
+    expected_version: Option<i64>,
+) -> Result<i64, EventStoreError> {
+    // 現在のバージョンを取得
+    let current_version: Option<i64> = sqlx::query_scalar(
+        "SELECT MAX(version) FROM events WHERE stream_id = $1 AND stream_type = $2",
+    )
+    .bind(stream_id)
+    .bind(stream_type)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    let current_version = current_version.unwrap_or(-1);
+
+    // 楽観的ロックのチェック
+    if let Some(expected) = expected_version
+        && current_version != expected
+    {
+        return Err(EventStoreError::VersionConflict {
+            expected,
+            actual: current_version,
+        });
+    }
+
+    let mut next_version = current_version;
+
+    // イベントを挿入
+    for event in events {
+        next_version += 1;
+        let event_id = Uuid::new_v4();
+        let metadata = serde_json::json!({});
+
+        sqlx::query(
+            "INSERT INTO events (event_id, stream_id, stream_type, version, event_type, data, metadata, created_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())"
+        )
+        .bind(event_id)
+        .bind(stream_id)
+        .bind(stream_type)
+        .bind(next_version)
+        .bind("Event") // TODO: 実際のイベントタイプを使用
+        .bind(&event)
+        .bind(&metadata)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(next_version)
+}
+
+fn stream_length(row: StreamRow) -> StreamLength {
+    StreamLength {
+        stream_id:        row.0,
+        stream_type:      row.1,
+        aggregate_type:   row.2,
+        length:           row.3,
+        last_appended_at: row.4,
+    }
+}
+
+fn stored_event(row: EventRow) -> StoredEvent {
+    StoredEvent {
+        event_id:    row.0,
+        stream_id:   row.1,
+        stream_type: row.2,
+        version:     row.3,
+        event_type:  row.4,
+        data:        row.5,
+        metadata:    row.6,
+        created_at:  row.7,
+        position:    row.8,
+    }
+}
+
 /// 保存されたイベント
 #[derive(Debug, Clone)]
 pub struct StoredEvent {
@@ -238,6 +552,16 @@ pub struct StoredEvent {
     pub position:    i64,
 }
 
+/// 一括保存でのストリームへの追記
+#[derive(Debug, Clone)]
+pub struct StreamAppend {
+    pub stream_id:        Uuid,
+    pub stream_type:      String,
+    pub events:           Vec<serde_json::Value>,
+    /// 期待するバージョン（None の場合は確認しない）
+    pub expected_version: Option<i64>,
+}
+
 /// スナップショット
 #[derive(Debug, Clone)]
 pub struct Snapshot {
@@ -256,9 +580,15 @@ pub enum EventStoreError {
     VersionConflict { expected: i64, actual: i64 },
 
     #[error("Stream not found: {0}")]
-    #[allow(dead_code)]
     StreamNotFound(Uuid),
 
+    #[error("Failed to append to stream {stream_id}: {source}")]
+    BatchAppend {
+        stream_id: Uuid,
+        #[source]
+        source:    Box<EventStoreError>,
+    },
+
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
 
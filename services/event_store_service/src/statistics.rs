@@ -0,0 +1,808 @@
+//! イベントストアの統計とテーブルの健全性
+//!
+//! 集約タイプごとのイベント数とストリーム長、スナップショットの充足率、
+//! アーカイブ可能なイベント数、テーブルとインデックスの肥大化を計算する。
+//!
+//! - 行数の多いテーブルは pg_class / pg_stats の統計情報と標本から推定し、
+//!   少ないテーブルは正確に数える
+//! - 重いクエリはバックグラウンドの更新タスクだけが実行し、RPC は
+//!   [`StatisticsCache::cached`] でキャッシュした結果を返す
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::{Arc, PoisonError, RwLock},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    config::{SnapshotConfig, StatisticsConfig},
+    repository::EventStoreError,
+};
+
+/// 統計の対象テーブル
+const TABLES: [&str; 3] = ["events", "event_streams", "snapshots"];
+
+/// Most Common Values に含まれない集約タイプをまとめる名前
+pub const OTHER_AGGREGATE_TYPES: &str = "(other)";
+
+/// B-tree インデックスのタプルごとのオーバーヘッド（タプルヘッダーとラインポインタ）
+const INDEX_TUPLE_OVERHEAD: f64 = 12.0;
+
+/// B-tree インデックスの既定の fillfactor
+const INDEX_FILL_FACTOR: f64 = 0.9;
+
+/// イベントストア全体の統計
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreStatistics {
+    /// 集約タイプごとの統計（イベント数の多い順）
+    pub aggregate_types: Vec<AggregateTypeStatistics>,
+    /// 最も長いストリーム
+    pub longest_streams: Vec<StreamLength>,
+    pub tables:          Vec<TableHealth>,
+    pub indexes:         Vec<IndexHealth>,
+    pub computed_at:     DateTime<Utc>,
+}
+
+/// 集約タイプごとの統計
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregateTypeStatistics {
+    pub aggregate_type:          String,
+    pub event_count:             i64,
+    pub stream_count:            i64,
+    /// 件数が pg_stats からの推定値か
+    pub counts_estimated:        bool,
+    pub p50_stream_length:       i64,
+    pub p99_stream_length:       i64,
+    /// ストリーム長が標本からの推定値か
+    pub stream_lengths_sampled:  bool,
+    /// スナップショット閾値以上のストリームのうちスナップショットを持つ割合
+    /// （対象のストリームがなければ 1.0）
+    pub snapshot_coverage:       f64,
+    /// 保持期間を過ぎたスナップショットに置き換えられるイベント数
+    pub archive_eligible_events: i64,
+}
+
+/// ストリームの長さ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamLength {
+    pub stream_id:        Uuid,
+    pub stream_type:      String,
+    pub aggregate_type:   String,
+    pub length:           i64,
+    pub last_appended_at: DateTime<Utc>,
+}
+
+/// テーブルの健全性
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableHealth {
+    pub table:          String,
+    /// pg_class.reltuples による推定行数
+    pub estimated_rows: i64,
+    pub dead_rows:      i64,
+    pub table_bytes:    i64,
+    pub index_bytes:    i64,
+}
+
+/// インデックスの健全性
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexHealth {
+    pub index:                 String,
+    pub table:                 String,
+    pub index_bytes:           i64,
+    /// 行数とキー幅から見積もった適正サイズを超える分
+    pub estimated_bloat_bytes: i64,
+}
+
+/// 集約タイプごとの件数
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeCounts {
+    pub counts:    BTreeMap<String, i64>,
+    pub estimated: bool,
+}
+
+/// スナップショットの充足状況
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnapshotCoverage {
+    /// スナップショット閾値以上のストリーム数
+    pub eligible: i64,
+    /// そのうちスナップショットを持つストリーム数
+    pub covered:  i64,
+}
+
+/// 集約タイプごとの統計の材料
+#[derive(Debug, Clone, Default)]
+pub struct AggregateInputs {
+    pub event_counts:     TypeCounts,
+    pub stream_counts:    TypeCounts,
+    /// (集約タイプ, ストリーム長) の標本
+    pub stream_lengths:   Vec<(String, i64)>,
+    pub lengths_sampled:  bool,
+    pub coverage:         HashMap<String, SnapshotCoverage>,
+    pub archive_eligible: HashMap<String, i64>,
+}
+
+/// 昇順に並んだ値の百分位数（nearest-rank 法）
+pub fn percentile(sorted: &[i64], percent: f64) -> i64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((percent / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// pg_stats の Most Common Values と推定行数から件数を推定する
+///
+/// MCV に含まれない残りは [`OTHER_AGGREGATE_TYPES`] にまとめる
+pub fn estimate_counts_from_mcv(total_rows: i64, values: &[String], freqs: &[f32]) -> TypeCounts {
+    let total = total_rows.max(0) as f64;
+    let mut counts = BTreeMap::new();
+    let mut covered = 0.0;
+
+    for (value, freq) in values.iter().zip(freqs) {
+        let freq = f64::from(*freq);
+        covered += freq;
+        counts.insert(value.clone(), (total * freq).round() as i64);
+    }
+
+    let other = (total * (1.0 - covered)).round() as i64;
+    if other > 0 {
+        counts.insert(OTHER_AGGREGATE_TYPES.to_string(), other);
+    }
+
+    TypeCounts {
+        counts,
+        estimated: true,
+    }
+}
+
+/// インデックスの肥大化を推定する
+///
+/// 行数 ×（キー幅 + タプルのオーバーヘッド）を fillfactor で割った大きさを
+/// 適正サイズとし、実サイズとの差を返す。部分インデックスや可変長のキーでは
+/// 誤差が大きいため、傾向の把握に使う
+pub fn estimate_index_bloat(index_bytes: i64, rows: i64, key_width: i64, block_size: i64) -> i64 {
+    if block_size <= 0 {
+        return 0;
+    }
+    let (rows, key_width, block_size) = (
+        rows.max(0) as f64,
+        key_width.max(0) as f64,
+        block_size as f64,
+    );
+    let tuples_per_page = ((block_size * INDEX_FILL_FACTOR) / (key_width + INDEX_TUPLE_OVERHEAD))
+        .floor()
+        .max(1.0);
+    // メタページの分を加える
+    let expected_pages = (rows / tuples_per_page).ceil() + 1.0;
+    let expected_bytes = (expected_pages * block_size) as i64;
+    (index_bytes - expected_bytes).max(0)
+}
+
+/// 集約タイプごとの統計をまとめる（イベント数の多い順）
+pub fn build_aggregate_statistics(inputs: AggregateInputs) -> Vec<AggregateTypeStatistics> {
+    let mut lengths: HashMap<String, Vec<i64>> = HashMap::new();
+    for (aggregate_type, length) in inputs.stream_lengths {
+        lengths.entry(aggregate_type).or_default().push(length);
+    }
+    for values in lengths.values_mut() {
+        values.sort_unstable();
+    }
+
+    let mut aggregate_types: Vec<String> = inputs
+        .event_counts
+        .counts
+        .keys()
+        .chain(inputs.stream_counts.counts.keys())
+        .cloned()
+        .collect();
+    aggregate_types.sort();
+    aggregate_types.dedup();
+
+    let mut statistics: Vec<AggregateTypeStatistics> = aggregate_types
+        .into_iter()
+        .map(|aggregate_type| {
+            let sorted = lengths.get(&aggregate_type).map_or(&[][..], Vec::as_slice);
+            let coverage = inputs
+                .coverage
+                .get(&aggregate_type)
+                .copied()
+                .unwrap_or_default();
+            let snapshot_coverage = if coverage.eligible == 0 {
+                1.0
+            } else {
+                coverage.covered as f64 / coverage.eligible as f64
+            };
+
+            AggregateTypeStatistics {
+                event_count: inputs
+                    .event_counts
+                    .counts
+                    .get(&aggregate_type)
+                    .copied()
+                    .unwrap_or(0),
+                stream_count: inputs
+                    .stream_counts
+                    .counts
+                    .get(&aggregate_type)
+                    .copied()
+                    .unwrap_or(0),
+                counts_estimated: inputs.event_counts.estimated || inputs.stream_counts.estimated,
+                p50_stream_length: percentile(sorted, 50.0),
+                p99_stream_length: percentile(sorted, 99.0),
+                stream_lengths_sampled: inputs.lengths_sampled,
+                snapshot_coverage,
+                archive_eligible_events: inputs
+                    .archive_eligible
+                    .get(&aggregate_type)
+                    .copied()
+                    .unwrap_or(0),
+                aggregate_type,
+            }
+        })
+        .collect();
+
+    statistics.sort_by(|a, b| {
+        b.event_count
+            .cmp(&a.event_count)
+            .then_with(|| a.aggregate_type.cmp(&b.aggregate_type))
+    });
+    statistics
+}
+
+/// 統計を計算する（重いクエリを実行する）
+pub trait StatisticsSource: Send + Sync {
+    fn collect(&self) -> impl Future<Output = Result<StoreStatistics, EventStoreError>> + Send;
+}
+
+/// PostgreSQL の統計情報から計算する
+pub struct PostgresStatisticsSource {
+    pool:     PgPool,
+    config:   StatisticsConfig,
+    snapshot: SnapshotConfig,
+}
+
+impl PostgresStatisticsSource {
+    /// 新しいインスタンスを作成
+    pub fn new(pool: PgPool, config: StatisticsConfig, snapshot: SnapshotConfig) -> Self {
+        Self {
+            pool,
+            config,
+            snapshot,
+        }
+    }
+
+    async fn table_health(&self) -> Result<Vec<TableHealth>, EventStoreError> {
+        let rows = sqlx::query_as::<_, (String, i64, i64, i64, i64)>(
+            "SELECT c.relname::TEXT, GREATEST(c.reltuples, 0)::BIGINT, COALESCE(s.n_dead_tup, \
+             0)::BIGINT, pg_table_size(c.oid), pg_indexes_size(c.oid)
+             FROM pg_class c
+             LEFT JOIN pg_stat_user_tables s ON s.relid = c.oid
+             WHERE c.relname = ANY($1) AND c.relkind = 'r' AND pg_table_is_visible(c.oid)
+             ORDER BY c.relname",
+        )
+        .bind(&TABLES[..])
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TableHealth {
+                table:          row.0,
+                estimated_rows: row.1,
+                dead_rows:      row.2,
+                table_bytes:    row.3,
+                index_bytes:    row.4,
+            })
+            .collect())
+    }
+
+    async fn index_health(&self) -> Result<Vec<IndexHealth>, EventStoreError> {
+        let rows = sqlx::query_as::<_, (String, String, i64, i64, i64, i64)>(
+            "SELECT i.relname::TEXT, t.relname::TEXT, pg_relation_size(i.oid), \
+             GREATEST(t.reltuples, 0)::BIGINT,
+                    COALESCE((SELECT SUM(st.avg_width)
+                              FROM pg_attribute a
+                              JOIN pg_stats st ON st.tablename = t.relname AND st.attname = \
+             a.attname
+                              WHERE a.attrelid = t.oid AND a.attnum = ANY(x.indkey::INT2[])), \
+             0)::BIGINT,
+                    current_setting('block_size')::BIGINT
+             FROM pg_index x
+             JOIN pg_class i ON i.oid = x.indexrelid
+             JOIN pg_class t ON t.oid = x.indrelid
+             WHERE t.relname = ANY($1) AND pg_table_is_visible(t.oid)
+             ORDER BY t.relname, i.relname",
+        )
+        .bind(&TABLES[..])
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| IndexHealth {
+                estimated_bloat_bytes: estimate_index_bloat(row.2, row.3, row.4, row.5),
+                index:                 row.0,
+                table:                 row.1,
+                index_bytes:           row.2,
+            })
+            .collect())
+    }
+
+    /// 集約タイプごとの行数（行数が上限以下なら正確に数え、超えれば pg_stats
+    /// から推定）
+    async fn count_by_aggregate_type(
+        &self,
+        table: &'static str,
+        estimated_rows: i64,
+    ) -> Result<TypeCounts, EventStoreError> {
+        if estimated_rows <= self.config.exact_count_row_limit {
+            let rows = sqlx::query_as::<_, (String, i64)>(&format!(
+                "SELECT aggregate_type, COUNT(*)::BIGINT FROM {table} GROUP BY aggregate_type"
+            ))
+            .fetch_all(&self.pool)
+            .await?;
+            return Ok(TypeCounts {
+                counts:    rows.into_iter().collect(),
+                estimated: false,
+            });
+        }
+
+        let mcv = sqlx::query_as::<_, (Option<Vec<String>>, Option<Vec<f32>>)>(
+            "SELECT most_common_vals::TEXT::TEXT[], most_common_freqs
+             FROM pg_stats
+             WHERE tablename = $1 AND attname = 'aggregate_type'
+             LIMIT 1",
+        )
+        .bind(table)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let (values, freqs) = mcv.unwrap_or_default();
+        Ok(estimate_counts_from_mcv(
+            estimated_rows,
+            &values.unwrap_or_default(),
+            &freqs.unwrap_or_default(),
+        ))
+    }
+
+    /// ストリーム長の標本（ストリーム数が標本数を超える場合は TABLESAMPLE
+    /// で抜き出す）
+    async fn stream_lengths(
+        &self,
+        estimated_streams: i64,
+    ) -> Result<(Vec<(String, i64)>, bool), EventStoreError> {
+        let sample_rows = self.config.sample_rows;
+        if estimated_streams <= sample_rows {
+            let rows = sqlx::query_as::<_, (String, i64)>(
+                "SELECT aggregate_type, version + 1 FROM event_streams",
+            )
+            .fetch_all(&self.pool)
+            .await?;
+            return Ok((rows, false));
+        }
+
+        let percent = (sample_rows as f64 / estimated_streams as f64 * 100.0).min(100.0);
+        let rows = sqlx::query_as::<_, (String, i64)>(
+            "SELECT aggregate_type, version + 1 FROM event_streams TABLESAMPLE SYSTEM ($1) LIMIT \
+             $2",
+        )
+        .bind(percent)
+        .bind(sample_rows)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok((rows, true))
+    }
+
+    async fn snapshot_coverage(
+        &self,
+    ) -> Result<HashMap<String, SnapshotCoverage>, EventStoreError> {
+        let rows = sqlx::query_as::<_, (String, i64, i64)>(
+            "SELECT es.aggregate_type, COUNT(*)::BIGINT,
+                    COUNT(*) FILTER (WHERE EXISTS (
+                        SELECT 1 FROM snapshots s
+                        WHERE s.stream_id = es.stream_id AND s.stream_type = es.stream_type
+                    ))::BIGINT
+             FROM event_streams es
+             WHERE es.version >= $1 - 1
+             GROUP BY es.aggregate_type",
+        )
+        .bind(i64::from(self.snapshot.threshold))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(aggregate_type, eligible, covered)| {
+                (aggregate_type, SnapshotCoverage { eligible, covered })
+            })
+            .collect())
+    }
+
+    async fn archive_eligible(&self) -> Result<HashMap<String, i64>, EventStoreError> {
+        let rows = sqlx::query_as::<_, (String, i64)>(
+            "SELECT es.aggregate_type, COALESCE(SUM(latest.version + 1), 0)::BIGINT
+             FROM (
+                 SELECT DISTINCT ON (stream_id, stream_type) stream_id, stream_type, version, \
+             created_at
+                 FROM snapshots
+                 ORDER BY stream_id, stream_type, version DESC
+             ) latest
+             JOIN event_streams es USING (stream_id, stream_type)
+             WHERE latest.created_at < NOW() - MAKE_INTERVAL(days => $1)
+             GROUP BY es.aggregate_type",
+        )
+        .bind(i32::try_from(self.snapshot.retention_days).unwrap_or(i32::MAX))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    async fn longest_streams(&self) -> Result<Vec<StreamLength>, EventStoreError> {
+        let rows = sqlx::query_as::<_, (Uuid, String, String, i64, DateTime<Utc>)>(
+            "SELECT stream_id, stream_type, aggregate_type, version + 1, updated_at
+             FROM event_streams
+             ORDER BY version DESC
+             LIMIT $1",
+        )
+        .bind(self.config.top_streams)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| StreamLength {
+                stream_id:        row.0,
+                stream_type:      row.1,
+                aggregate_type:   row.2,
+                length:           row.3,
+                last_appended_at: row.4,
+            })
+            .collect())
+    }
+}
+
+impl StatisticsSource for PostgresStatisticsSource {
+    async fn collect(&self) -> Result<StoreStatistics, EventStoreError> {
+        let tables = self.table_health().await?;
+        let estimated_rows = |table: &str| {
+            tables
+                .iter()
+                .find(|health| health.table == table)
+                .map_or(0, |health| health.estimated_rows)
+        };
+        let estimated_events = estimated_rows("events");
+        let estimated_streams = estimated_rows("event_streams");
+
+        let (stream_lengths, lengths_sampled) = self.stream_lengths(estimated_streams).await?;
+        let inputs = AggregateInputs {
+            event_counts: self
+                .count_by_aggregate_type("events", estimated_events)
+                .await?,
+            stream_counts: self
+                .count_by_aggregate_type("event_streams", estimated_streams)
+                .await?,
+            stream_lengths,
+            lengths_sampled,
+            coverage: self.snapshot_coverage().await?,
+            archive_eligible: self.archive_eligible().await?,
+        };
+
+        Ok(StoreStatistics {
+            aggregate_types: build_aggregate_statistics(inputs),
+            longest_streams: self.longest_streams().await?,
+            indexes: self.index_health().await?,
+            tables,
+            computed_at: Utc::now(),
+        })
+    }
+}
+
+/// キャッシュした統計
+#[derive(Debug, Clone)]
+pub struct CachedStatistics {
+    pub statistics: Arc<StoreStatistics>,
+    /// 更新が TTL の2倍以上途絶えている
+    pub stale:      bool,
+}
+
+/// 統計のキャッシュ
+///
+/// [`refresh`](Self::refresh) だけが [`StatisticsSource`] を呼び出す
+pub struct StatisticsCache<S> {
+    source:  S,
+    ttl:     Duration,
+    current: RwLock<Option<Arc<StoreStatistics>>>,
+}
+
+impl<S: StatisticsSource> StatisticsCache<S> {
+    /// 新しいインスタンスを作成
+    pub fn new(source: S, ttl: Duration) -> Self {
+        Self {
+            source,
+            ttl,
+            current: RwLock::new(None),
+        }
+    }
+
+    /// キャッシュした統計（まだ計算していなければ None）
+    pub fn cached(&self, now: DateTime<Utc>) -> Option<CachedStatistics> {
+        let statistics = self
+            .current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()?;
+        let stale = chrono::Duration::from_std(self.ttl * 2)
+            .is_ok_and(|limit| now - statistics.computed_at > limit);
+        Some(CachedStatistics { statistics, stale })
+    }
+
+    /// 統計を計算し直してキャッシュを置き換える
+    pub async fn refresh(&self) -> Result<Arc<StoreStatistics>, EventStoreError> {
+        let statistics = Arc::new(self.source.collect().await?);
+        record_metrics(&statistics);
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Some(statistics.clone());
+        Ok(statistics)
+    }
+}
+
+/// TTL ごとに統計を更新するバックグラウンドタスクを起動する
+pub fn spawn_refresher<S>(cache: Arc<StatisticsCache<S>>) -> JoinHandle<()>
+where
+    S: StatisticsSource + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(cache.ttl);
+        loop {
+            interval.tick().await;
+            match cache.refresh().await {
+                Ok(statistics) => info!(
+                    "Store statistics refreshed: {} aggregate types, {} tables",
+                    statistics.aggregate_types.len(),
+                    statistics.tables.len()
+                ),
+                Err(e) => warn!("Failed to refresh store statistics: {}", e),
+            }
+        }
+    })
+}
+
+/// 主要な値をメトリクスとして記録する
+fn record_metrics(statistics: &StoreStatistics) {
+    for aggregate in &statistics.aggregate_types {
+        let prefix = format!("event_store.{}", aggregate.aggregate_type);
+        shared_telemetry::record_metric!(
+            format!("{prefix}.events").as_str(),
+            aggregate.event_count
+        );
+        shared_telemetry::record_metric!(
+            format!("{prefix}.streams").as_str(),
+            aggregate.stream_count
+        );
+        shared_telemetry::record_metric!(
+            format!("{prefix}.stream_length.p99").as_str(),
+            aggregate.p99_stream_length
+        );
+        shared_telemetry::record_metric!(
+            format!("{prefix}.snapshot_coverage").as_str(),
+            aggregate.snapshot_coverage
+        );
+        shared_telemetry::record_metric!(
+            format!("{prefix}.archive_eligible_events").as_str(),
+            aggregate.archive_eligible_events
+        );
+    }
+    for table in &statistics.tables {
+        let prefix = format!("event_store.table.{}", table.table);
+        shared_telemetry::record_metric!(format!("{prefix}.rows").as_str(), table.estimated_rows);
+        shared_telemetry::record_metric!(format!("{prefix}.dead_rows").as_str(), table.dead_rows);
+        shared_telemetry::record_metric!(format!("{prefix}.bytes").as_str(), table.table_bytes);
+    }
+    for index in &statistics.indexes {
+        shared_telemetry::record_metric!(
+            format!("event_store.index.{}.bloat_bytes", index.index).as_str(),
+            index.estimated_bloat_bytes
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// 既知の分布: VocabularyItem は長さ 1〜200 が各 20 本、User は長さ 5 が
+    /// 1000 本
+    fn seeded_streams() -> Vec<(String, i64)> {
+        let mut streams = Vec::new();
+        for length in 1..=200 {
+            for _ in 0..20 {
+                streams.push(("VocabularyItem".to_string(), length));
+            }
+        }
+        for _ in 0..1000 {
+            streams.push(("User".to_string(), 5));
+        }
+        streams
+    }
+
+    /// 決定的な標本（`step` 本ごとに 1 本）
+    fn sample(streams: &[(String, i64)], step: usize) -> Vec<(String, i64)> {
+        streams.iter().step_by(step).cloned().collect()
+    }
+
+    #[test]
+    fn test_percentiles_from_a_sample_stay_within_tolerance() {
+        let streams = seeded_streams();
+
+        let mut lengths: Vec<i64> = streams
+            .iter()
+            .filter(|(aggregate_type, _)| aggregate_type == "VocabularyItem")
+            .map(|(_, length)| *length)
+            .collect();
+        lengths.sort_unstable();
+        assert_eq!(percentile(&lengths, 50.0), 100);
+        assert_eq!(percentile(&lengths, 99.0), 198);
+
+        let mut sampled_lengths: Vec<i64> = sample(&streams, 7)
+            .into_iter()
+            .filter(|(aggregate_type, _)| aggregate_type == "VocabularyItem")
+            .map(|(_, length)| length)
+            .collect();
+        sampled_lengths.sort_unstable();
+        assert!((percentile(&sampled_lengths, 50.0) - 100).abs() <= 5);
+        assert!((percentile(&sampled_lengths, 99.0) - 198).abs() <= 5);
+        assert_eq!(percentile(&[], 50.0), 0);
+    }
+
+    #[test]
+    fn test_aggregate_statistics_from_seeded_distribution() {
+        let streams = seeded_streams();
+        // VocabularyItem: 20 × (1 + … + 200) = 402000 件, User: 5000 件
+        let event_counts = estimate_counts_from_mcv(
+            407_000,
+            &["VocabularyItem".to_string(), "User".to_string()],
+            &[402_000.0 / 407_000.0, 5_000.0 / 407_000.0],
+        );
+        let stream_counts = TypeCounts {
+            counts:    [
+                ("VocabularyItem".to_string(), 4000),
+                ("User".to_string(), 1000),
+            ]
+            .into_iter()
+            .collect(),
+            estimated: false,
+        };
+
+        let statistics = build_aggregate_statistics(AggregateInputs {
+            event_counts,
+            stream_counts,
+            stream_lengths: sample(&streams, 7),
+            lengths_sampled: true,
+            coverage: [(
+                "VocabularyItem".to_string(),
+                SnapshotCoverage {
+                    eligible: 2020,
+                    covered:  1515,
+                },
+            )]
+            .into_iter()
+            .collect(),
+            archive_eligible: [("VocabularyItem".to_string(), 150_000)]
+                .into_iter()
+                .collect(),
+        });
+
+        assert_eq!(statistics.len(), 2);
+        let item = &statistics[0];
+        assert_eq!(item.aggregate_type, "VocabularyItem");
+        // f32 の頻度からの推定なので 0.1% の誤差を許容する
+        assert!((item.event_count - 402_000).abs() <= 402);
+        assert_eq!(item.stream_count, 4000);
+        assert!(item.counts_estimated && item.stream_lengths_sampled);
+        assert!((item.p50_stream_length - 100).abs() <= 5);
+        assert!((item.p99_stream_length - 198).abs() <= 5);
+        assert!((item.snapshot_coverage - 0.75).abs() < 1e-9);
+        assert_eq!(item.archive_eligible_events, 150_000);
+
+        let user = &statistics[1];
+        assert_eq!(user.aggregate_type, "User");
+        assert!((user.event_count - 5_000).abs() <= 5);
+        assert_eq!((user.p50_stream_length, user.p99_stream_length), (5, 5));
+        assert!((user.snapshot_coverage - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_mcv_estimate_keeps_the_remainder_as_other() {
+        let counts = estimate_counts_from_mcv(1000, &["A".to_string()], &[0.6]);
+        assert!(counts.estimated);
+        assert_eq!(counts.counts["A"], 600);
+        assert_eq!(counts.counts[OTHER_AGGREGATE_TYPES], 400);
+
+        let empty = estimate_counts_from_mcv(0, &[], &[]);
+        assert!(empty.counts.is_empty());
+    }
+
+    #[test]
+    fn test_index_bloat_estimate() {
+        // 100 万行、キー幅 16 バイト: 1 ページ 263 タプル → 3803 ページ + メタページ
+        let expected = 3804 * 8192;
+        assert_eq!(estimate_index_bloat(expected, 1_000_000, 16, 8192), 0);
+        assert_eq!(
+            estimate_index_bloat(expected * 3, 1_000_000, 16, 8192),
+            expected * 2
+        );
+        assert_eq!(estimate_index_bloat(8192, 0, 16, 8192), 0);
+    }
+
+    struct CountingSource {
+        calls: AtomicUsize,
+    }
+
+    impl StatisticsSource for CountingSource {
+        async fn collect(&self) -> Result<StoreStatistics, EventStoreError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(StoreStatistics {
+                aggregate_types: Vec::new(),
+                longest_streams: Vec::new(),
+                tables:          Vec::new(),
+                indexes:         Vec::new(),
+                computed_at:     Utc::now(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_reads_never_run_the_heavy_queries() {
+        let cache = StatisticsCache::new(
+            CountingSource {
+                calls: AtomicUsize::new(0),
+            },
+            Duration::from_secs(60),
+        );
+
+        // 初回の更新までは何も返さず、計算もしない
+        assert!(cache.cached(Utc::now()).is_none());
+        assert_eq!(cache.source.calls.load(Ordering::SeqCst), 0);
+
+        let refreshed = cache.refresh().await.unwrap();
+        assert_eq!(cache.source.calls.load(Ordering::SeqCst), 1);
+
+        for _ in 0..100 {
+            let cached = cache.cached(Utc::now()).unwrap();
+            assert!(Arc::ptr_eq(&cached.statistics, &refreshed));
+            assert!(!cached.stale);
+        }
+        assert_eq!(cache.source.calls.load(Ordering::SeqCst), 1);
+
+        // 更新が TTL の2倍以上途絶えると stale になる
+        let later = refreshed.computed_at + chrono::Duration::seconds(121);
+        assert!(cache.cached(later).unwrap().stale);
+        assert_eq!(cache.source.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_refresher_recomputes_every_ttl() {
+        let cache = Arc::new(StatisticsCache::new(
+            CountingSource {
+                calls: AtomicUsize::new(0),
+            },
+            Duration::from_millis(20),
+        ));
+        let handle = spawn_refresher(cache.clone());
+
+        // 起動直後に1回、その後は TTL ごと
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        handle.abort();
+        assert!(cache.source.calls.load(Ordering::SeqCst) >= 2);
+        assert!(cache.cached(Utc::now()).is_some());
+    }
+}
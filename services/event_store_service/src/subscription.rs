@@ -0,0 +1,461 @@
+//! イベントの購読（Server Streaming）
+//!
+//! 購読はイベントを位置（ストリームの購読ではバージョン）の順に流し、
+//! 既存のイベントを読み終えた後は新しく追記されたイベントを待つ。
+//!
+//! - このインスタンスの追記は [`AppendNotifier::notify`] で待っている購読を
+//!   すぐに起こす
+//! - 他のインスタンスの追記は `poll_interval` ごとの確認で拾う
+//!
+//! [`with_credits`] はクライアントが許可した件数だけイベントを流し、
+//! 読み込みの遅いクライアントの分のイベントをサーバーに溜め込まない。
+
+use std::{future::Future, pin::pin, sync::Arc, time::Duration};
+
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use tokio::sync::Notify;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::repository::{EventStoreError, StoredEvent};
+
+/// 購読するイベントの列
+pub type EventStream = BoxStream<'static, Result<StoredEvent, EventStoreError>>;
+
+/// 購読が読むイベント
+pub trait SubscriptionSource: Send + Sync {
+    /// `position` より後のコミット済みイベントのうち `filter`
+    /// に合うものを位置の順に最大 `limit` 件
+    fn read_all_after(
+        &self,
+        position: i64,
+        filter: &SubscriptionFilter,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<StoredEvent>, EventStoreError>> + Send;
+
+    /// ストリームの `version` より後のイベントをバージョンの順に最大 `limit` 件
+    fn read_stream_after(
+        &self,
+        stream_id: Uuid,
+        stream_type: &str,
+        version: i64,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<StoredEvent>, EventStoreError>> + Send;
+
+    /// コミット済みの最後のイベントの位置（イベントがなければ 0）
+    fn head_position(&self) -> impl Future<Output = Result<i64, EventStoreError>> + Send;
+
+    /// ストリームの現在のバージョン（イベントがなければ -1）
+    fn stream_version(
+        &self,
+        stream_id: Uuid,
+        stream_type: &str,
+    ) -> impl Future<Output = Result<i64, EventStoreError>> + Send;
+}
+
+/// 全イベントの購読で流すイベントの絞り込み
+///
+/// 空の条件は絞り込まない。読み込みの時点で絞り込むため、合わない
+/// イベントはクライアントに送らない
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubscriptionFilter {
+    pub event_types:  Vec<String>,
+    /// ストリームタイプ（集約タイプ）
+    pub stream_types: Vec<String>,
+}
+
+impl SubscriptionFilter {
+    /// イベントが条件に合うか（PostgreSQL では同じ条件を SQL で絞り込む）
+    #[cfg(test)]
+    pub fn matches(&self, event: &StoredEvent) -> bool {
+        (self.event_types.is_empty() || self.event_types.contains(&event.event_type))
+            && (self.stream_types.is_empty() || self.stream_types.contains(&event.stream_type))
+    }
+}
+
+/// 追記を待っている購読に知らせる
+pub struct AppendNotifier {
+    notify:        Notify,
+    poll_interval: Duration,
+}
+
+impl AppendNotifier {
+    /// 新しいインスタンスを作成
+    pub fn new(poll_interval: Duration) -> Self {
+        Self {
+            notify: Notify::new(),
+            poll_interval,
+        }
+    }
+
+    /// 追記をコミットした後に呼ぶ
+    pub fn notify(&self) {
+        self.notify.notify_waiters();
+    }
+}
+
+/// 購読の読み込み位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cursor {
+    /// 全イベントの `position` より後の `filter` に合うイベント
+    All {
+        position: i64,
+        filter:   SubscriptionFilter,
+    },
+    /// ストリームの `version` より後
+    Stream {
+        stream_id:   Uuid,
+        stream_type: String,
+        version:     i64,
+    },
+}
+
+impl Cursor {
+    /// 全イベントの購読の開始位置
+    ///
+    /// `position` が None の場合は、既存のイベントを含めるなら最初から、
+    /// 含めないなら今の末尾から読む
+    pub async fn all<S: SubscriptionSource>(
+        source: &S,
+        position: Option<i64>,
+        filter: SubscriptionFilter,
+        include_existing: bool,
+    ) -> Result<Self, EventStoreError> {
+        let position = match position {
+            Some(position) => position,
+            None if include_existing => 0,
+            None => source.head_position().await?,
+        };
+        Ok(Self::All { position, filter })
+    }
+
+    /// ストリームの購読の開始位置
+    ///
+    /// 既存のイベントを含めるなら `from_version` 以降を、含めないなら
+    /// 今のバージョンより後を読む
+    pub async fn stream<S: SubscriptionSource>(
+        source: &S,
+        stream_id: Uuid,
+        stream_type: String,
+        from_version: i64,
+        include_existing: bool,
+    ) -> Result<Self, EventStoreError> {
+        let version = if include_existing {
+            from_version - 1
+        } else {
+            source.stream_version(stream_id, &stream_type).await?
+        };
+        Ok(Self::Stream {
+            stream_id,
+            stream_type,
+            version,
+        })
+    }
+
+    async fn read<S: SubscriptionSource>(
+        &self,
+        source: &S,
+        limit: i64,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        match self {
+            Self::All { position, filter } => source.read_all_after(*position, filter, limit).await,
+            Self::Stream {
+                stream_id,
+                stream_type,
+                version,
+            } => {
+                source
+                    .read_stream_after(*stream_id, stream_type, *version, limit)
+                    .await
+            },
+        }
+    }
+
+    fn advance(&mut self, event: &StoredEvent) {
+        match self {
+            Self::All { position, .. } => *position = event.position,
+            Self::Stream { version, .. } => *version = event.version,
+        }
+    }
+}
+
+/// `cursor` から購読する
+///
+/// 読み込みに失敗した場合はエラーを流し、`poll_interval` 後に同じ位置から
+/// 読み直す（購読を終えるかはクライアントが決める）
+pub fn subscribe<S>(
+    source: Arc<S>,
+    notifier: Arc<AppendNotifier>,
+    cursor: Cursor,
+    batch_size: i64,
+) -> EventStream
+where
+    S: SubscriptionSource + 'static,
+{
+    let state = (source, notifier, cursor, Vec::new().into_iter());
+    stream::unfold(
+        state,
+        move |(source, notifier, mut cursor, mut buffered)| async move {
+            loop {
+                if let Some(event) = buffered.next() {
+                    cursor.advance(&event);
+                    return Some((Ok(event), (source, notifier, cursor, buffered)));
+                }
+
+                let read = {
+                    // 読み込みの前から通知を受け付け、読んだ後の追記を取りこぼさない
+                    let mut notified = pin!(notifier.notify.notified());
+                    notified.as_mut().enable();
+                    let read = cursor.read(source.as_ref(), batch_size).await;
+                    if matches!(&read, Ok(events) if events.is_empty()) {
+                        let _ = tokio::time::timeout(notifier.poll_interval, notified).await;
+                    }
+                    read
+                };
+                match read {
+                    Ok(events) => buffered = events.into_iter(),
+                    Err(e) => {
+                        warn!("Failed to read events for subscription: {}", e);
+                        tokio::time::sleep(notifier.poll_interval).await;
+                        return Some((Err(e), (source, notifier, cursor, buffered)));
+                    },
+                }
+            }
+        },
+    )
+    .boxed()
+}
+
+/// クライアントが `credits` で許可した件数だけ `events` を流す
+///
+/// 許可が尽きると `events` を読まずに次の許可を待つ。イベントを待つ間も
+/// 許可を受け取って積み増す。許可の列が終わる（クライアントが送信を
+/// 閉じる）と購読を終える
+pub fn with_credits<C>(events: EventStream, credits: C) -> EventStream
+where
+    C: Stream<Item = u32> + Send + 'static,
+{
+    let state = (events, credits.boxed(), 0_u64);
+    stream::unfold(
+        state,
+        |(mut events, mut credits, mut available)| async move {
+            loop {
+                if available == 0 {
+                    available += u64::from(credits.next().await?);
+                    continue;
+                }
+                tokio::select! {
+                    credit = credits.next() => available += u64::from(credit?),
+                    event = events.next() => {
+                        return Some((event?, (events, credits, available - 1)));
+                    },
+                }
+            }
+        },
+    )
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use chrono::Utc;
+
+    use super::*;
+
+    /// 追記されたイベントをメモリに持つ
+    #[derive(Default)]
+    struct MemorySource {
+        events: Mutex<Vec<StoredEvent>>,
+    }
+
+    impl MemorySource {
+        fn append(&self, stream_id: Uuid, stream_type: &str) {
+            self.append_event(stream_id, stream_type, "ItemUpdated");
+        }
+
+        fn append_event(&self, stream_id: Uuid, stream_type: &str, event_type: &str) {
+            let mut events = self.events.lock().unwrap();
+            let version = events
+                .iter()
+                .filter(|event| event.stream_id == stream_id)
+                .count() as i64;
+            let position = events.len() as i64 + 1;
+            events.push(StoredEvent {
+                event_id: Uuid::new_v4(),
+                stream_id,
+                stream_type: stream_type.to_string(),
+                version,
+                event_type: event_type.to_string(),
+                data: serde_json::json!({}),
+                metadata: serde_json::json!({}),
+                created_at: Utc::now(),
+                position,
+            });
+        }
+
+        fn select(&self, limit: i64, predicate: impl Fn(&StoredEvent) -> bool) -> Vec<StoredEvent> {
+            self.events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|event| predicate(event))
+                .take(limit as usize)
+                .cloned()
+                .collect()
+        }
+    }
+
+    impl SubscriptionSource for MemorySource {
+        async fn read_all_after(
+            &self,
+            position: i64,
+            filter: &SubscriptionFilter,
+            limit: i64,
+        ) -> Result<Vec<StoredEvent>, EventStoreError> {
+            Ok(self.select(limit, |event| {
+                event.position > position && filter.matches(event)
+            }))
+        }
+
+        async fn read_stream_after(
+            &self,
+            stream_id: Uuid,
+            _stream_type: &str,
+            version: i64,
+            limit: i64,
+        ) -> Result<Vec<StoredEvent>, EventStoreError> {
+            Ok(self.select(limit, |event| {
+                event.stream_id == stream_id && event.version > version
+            }))
+        }
+
+        async fn head_position(&self) -> Result<i64, EventStoreError> {
+            Ok(self.events.lock().unwrap().len() as i64)
+        }
+
+        async fn stream_version(
+            &self,
+            stream_id: Uuid,
+            _stream_type: &str,
+        ) -> Result<i64, EventStoreError> {
+            Ok(self
+                .select(i64::MAX, |event| event.stream_id == stream_id)
+                .len() as i64
+                - 1)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delivers_existing_then_appended_events() {
+        let source = Arc::new(MemorySource::default());
+        // 通知がなければ届かないよう、確認の間隔を長くする
+        let notifier = Arc::new(AppendNotifier::new(Duration::from_secs(60)));
+        let item_id = Uuid::new_v4();
+        source.append(item_id, "VocabularyItem");
+
+        let cursor = Cursor::all(source.as_ref(), None, SubscriptionFilter::default(), true)
+            .await
+            .unwrap();
+        let mut events = subscribe(source.clone(), notifier.clone(), cursor, 10);
+        assert_eq!(events.next().await.unwrap().unwrap().position, 1);
+
+        let next = tokio::spawn(async move { events.next().await });
+        tokio::task::yield_now().await;
+        source.append(item_id, "VocabularyItem");
+        notifier.notify();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), next)
+            .await
+            .expect("appended event should be delivered after the notification")
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.position, 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_subscription_without_existing_starts_at_the_end() {
+        let source = Arc::new(MemorySource::default());
+        let notifier = Arc::new(AppendNotifier::new(Duration::from_millis(10)));
+        let (item_id, other_id) = (Uuid::new_v4(), Uuid::new_v4());
+        source.append(item_id, "VocabularyItem");
+        source.append(item_id, "VocabularyItem");
+
+        let cursor = Cursor::stream(
+            source.as_ref(),
+            item_id,
+            "VocabularyItem".to_string(),
+            0,
+            false,
+        )
+        .await
+        .unwrap();
+        let mut events = subscribe(source.clone(), notifier, cursor, 10);
+        source.append(other_id, "VocabularyItem");
+        source.append(item_id, "VocabularyItem");
+
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!((event.stream_id, event.version), (item_id, 2));
+    }
+
+    #[tokio::test]
+    async fn test_all_subscription_sends_only_matching_events() {
+        let source = Arc::new(MemorySource::default());
+        let notifier = Arc::new(AppendNotifier::new(Duration::from_millis(10)));
+        let (item_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        source.append_event(item_id, "VocabularyItem", "ItemCreated");
+        source.append_event(user_id, "User", "UserCreated");
+        source.append_event(item_id, "VocabularyItem", "ItemDeleted");
+        source.append_event(item_id, "VocabularyItem", "ItemUpdated");
+
+        let filter = SubscriptionFilter {
+            event_types:  vec!["ItemCreated".to_string(), "ItemUpdated".to_string()],
+            stream_types: vec!["VocabularyItem".to_string()],
+        };
+        let cursor = Cursor::all(source.as_ref(), None, filter, true)
+            .await
+            .unwrap();
+        let events: Vec<_> = subscribe(source.clone(), notifier, cursor, 10)
+            .take(2)
+            .map(|event| event.unwrap().position)
+            .collect()
+            .await;
+
+        assert_eq!(events, vec![1, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_sends_at_most_the_granted_credits() {
+        let source = Arc::new(MemorySource::default());
+        let notifier = Arc::new(AppendNotifier::new(Duration::from_millis(10)));
+        let item_id = Uuid::new_v4();
+        for _ in 0..5 {
+            source.append(item_id, "VocabularyItem");
+        }
+
+        let (grant, credits) = futures::channel::mpsc::unbounded();
+        let cursor = Cursor::all(source.as_ref(), None, SubscriptionFilter::default(), true)
+            .await
+            .unwrap();
+        let mut events = with_credits(subscribe(source.clone(), notifier, cursor, 10), credits);
+
+        grant.unbounded_send(2).unwrap();
+        assert_eq!(events.next().await.unwrap().unwrap().position, 1);
+        assert_eq!(events.next().await.unwrap().unwrap().position, 2);
+        // 許可が尽きたら次の許可まで送らない
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), events.next())
+                .await
+                .is_err()
+        );
+
+        grant.unbounded_send(1).unwrap();
+        assert_eq!(events.next().await.unwrap().unwrap().position, 3);
+
+        // クライアントが送信を閉じたら購読を終える
+        drop(grant);
+        assert!(events.next().await.is_none());
+    }
+}
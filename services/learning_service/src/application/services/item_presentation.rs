@@ -0,0 +1,154 @@
+//! 出題項目の準備
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::{
+    domain::{item_selection::SelectedItem, presentation::PresentedItem},
+    ports::outbound::{ItemSourceError, VocabularyItemSource},
+};
+
+/// 選定済みの項目から `ItemPresented` で提示する内容を用意する
+pub struct ItemPresenter<S> {
+    source: S,
+}
+
+impl<S: VocabularyItemSource> ItemPresenter<S> {
+    pub const fn new(source: S) -> Self {
+        Self { source }
+    }
+
+    /// セッションの項目を一度の一括取得で集め、選定順に並べる
+    ///
+    /// 選定後に削除されたなどで取得できなかった項目は除く
+    ///
+    /// # Errors
+    ///
+    /// 取得元から項目を取得できない場合はエラーを返す
+    pub async fn prepare(
+        &self,
+        selected: &[SelectedItem],
+    ) -> Result<Vec<PresentedItem>, ItemSourceError> {
+        if selected.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let item_ids: Vec<Uuid> = selected.iter().map(|item| item.item_id).collect();
+        let mut fetched: HashMap<Uuid, PresentedItem> = self
+            .source
+            .fetch_items(&item_ids)
+            .await?
+            .into_iter()
+            .map(|item| (item.item_id, item))
+            .collect();
+
+        Ok(item_ids
+            .iter()
+            .filter_map(|item_id| fetched.remove(item_id))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeSource {
+        items: Vec<PresentedItem>,
+        calls: Mutex<Vec<Vec<Uuid>>>,
+    }
+
+    #[async_trait]
+    impl VocabularyItemSource for FakeSource {
+        async fn fetch_items(
+            &self,
+            item_ids: &[Uuid],
+        ) -> Result<Vec<PresentedItem>, ItemSourceError> {
+            self.calls.lock().unwrap().push(item_ids.to_vec());
+            // 取得元は順序を保証しない
+            Ok(self
+                .items
+                .iter()
+                .rev()
+                .filter(|item| item_ids.contains(&item.item_id))
+                .cloned()
+                .collect())
+        }
+    }
+
+    fn selected(item_id: Uuid) -> SelectedItem {
+        SelectedItem {
+            item_id,
+            priority_score: 1.0,
+            reason: "new item",
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prepare_carries_learning_context_in_session_order() {
+        let ids = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        // 出題用の JSON（Vocabulary Query Service のレスポンス）から読み込む
+        let items: Vec<PresentedItem> = serde_json::from_value(serde_json::json!([
+            {
+                "item_id": ids[0],
+                "entry_id": Uuid::new_v4(),
+                "spelling": "leverage",
+                "disambiguation": "to use something to maximum advantage",
+                "part_of_speech": "verb",
+                "cefr_level": "C1",
+                "register": "formal",
+                "domain": "business",
+                "usage_notes": "Often criticised as jargon; prefer \"use\" in plain writing",
+                "example_count": 2
+            },
+            {
+                "item_id": ids[1],
+                "spelling": "gonna",
+                "register": "slang"
+            }
+        ]))
+        .unwrap();
+        let presenter = ItemPresenter::new(FakeSource {
+            items,
+            ..Default::default()
+        });
+
+        let prepared = presenter.prepare(&ids.map(selected)).await.unwrap();
+
+        // 取得できなかった項目は除き、選定順に並ぶ
+        assert_eq!(
+            prepared.iter().map(|item| item.item_id).collect::<Vec<_>>(),
+            vec![ids[0], ids[1]]
+        );
+        assert_eq!(prepared[0].register.as_deref(), Some("formal"));
+        assert_eq!(prepared[0].domain.as_deref(), Some("business"));
+        assert_eq!(prepared[0].cefr_level.as_deref(), Some("C1"));
+        assert_eq!(prepared[1].register.as_deref(), Some("slang"));
+        assert_eq!(
+            prepared[0].usage_notes.as_deref(),
+            Some("Often criticised as jargon; prefer \"use\" in plain writing")
+        );
+        assert_eq!(prepared[1].domain, None);
+        assert_eq!(prepared[1].usage_notes, None);
+
+        // 一括取得は一度だけ
+        assert_eq!(
+            presenter.source.calls.lock().unwrap().as_slice(),
+            &[ids.to_vec()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prepare_without_items_skips_fetch() {
+        let presenter = ItemPresenter::new(FakeSource::default());
+
+        assert!(presenter.prepare(&[]).await.unwrap().is_empty());
+        assert!(presenter.source.calls.lock().unwrap().is_empty());
+    }
+}
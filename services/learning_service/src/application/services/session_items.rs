@@ -0,0 +1,567 @@
+//! セッションの項目選定とプレビュー
+//!
+//! `GetSessionPreview` と `StartSession` は同じ選定処理を使う。プレビューは
+//! イベントを発行せず、選定結果を短時間キャッシュして選定トークンを返す。
+//! 有効期限内にトークンを付けて `StartSession` を呼ぶと、プレビューと同じ項目で
+//! セッションを開始する。キャッシュは `ItemReviewed` やコレクションの変更で
+//! 無効にする（無効にしたトークンでは選定し直す）。
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        events::SessionStarted,
+        item_selection::{LearningContextFilter, SelectedItem, SelectionStrategy},
+        session_preview::{CategoryCounts, DurationEstimate, SessionPreview},
+    },
+    ports::outbound::{
+        ItemCandidateSource,
+        LearningEventPublisher,
+        ResponseTimeSource,
+        SessionPortError,
+    },
+};
+
+/// プレビューの選定結果を保持する時間（秒）
+pub const PREVIEW_CACHE_TTL_SECONDS: i64 = 120;
+
+/// 戦略を指定しない場合の選定戦略
+pub const DEFAULT_SELECTION_STRATEGY: SelectionStrategy = SelectionStrategy::Mixed;
+
+/// 項目数を指定しない場合の項目数
+pub const DEFAULT_SESSION_ITEM_COUNT: usize = 20;
+
+/// プレビューのキャッシュを無効にするイベント
+///
+/// 復習で復習予定が変わるほか、コレクションの変更で候補そのものが変わる
+pub const PREVIEW_INVALIDATING_EVENTS: [&str; 5] = [
+    "ItemReviewed",
+    "CollectionItemAdded",
+    "CollectionItemRemoved",
+    "CollectionMemberJoined",
+    "CollectionMemberLeft",
+];
+
+/// セッションの準備のエラー
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SessionSelectionError {
+    #[error(transparent)]
+    Port(#[from] SessionPortError),
+}
+
+/// 選定の条件（`SessionConfig` に対応）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionRequest {
+    pub user_id:    Uuid,
+    pub strategy:   SelectionStrategy,
+    pub item_count: usize,
+    pub filter:     LearningContextFilter,
+}
+
+impl SessionRequest {
+    /// 既定の戦略と項目数で作る
+    #[must_use]
+    pub fn new(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            strategy: DEFAULT_SELECTION_STRATEGY,
+            item_count: DEFAULT_SESSION_ITEM_COUNT,
+            filter: LearningContextFilter::default(),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_strategy(mut self, strategy: SelectionStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_item_count(mut self, item_count: usize) -> Self {
+        self.item_count = item_count;
+        self
+    }
+
+    #[must_use]
+    pub fn with_filter(mut self, filter: LearningContextFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+}
+
+/// 開始したセッション
+#[derive(Debug, Clone, PartialEq)]
+pub struct StartedSession {
+    pub session_id:     Uuid,
+    pub selected_items: Vec<SelectedItem>,
+    /// プレビューの選定結果をそのまま使ったか
+    pub from_preview:   bool,
+}
+
+/// キャッシュしたプレビュー
+struct CachedSelection {
+    request:  SessionRequest,
+    selected: Vec<SelectedItem>,
+    preview:  SessionPreview,
+}
+
+/// セッションの項目選定（プレビューと開始）
+pub struct SessionSelectionService<C, R, P> {
+    candidates:     C,
+    response_times: R,
+    publisher:      P,
+    ttl:            Duration,
+    /// 選定トークンごとのプレビュー
+    cache:          Mutex<HashMap<Uuid, CachedSelection>>,
+}
+
+impl<C, R, P> SessionSelectionService<C, R, P>
+where
+    C: ItemCandidateSource,
+    R: ResponseTimeSource,
+    P: LearningEventPublisher,
+{
+    pub fn new(candidates: C, response_times: R, publisher: P) -> Self {
+        Self {
+            candidates,
+            response_times,
+            publisher,
+            ttl: Duration::seconds(PREVIEW_CACHE_TTL_SECONDS),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// プレビューを保持する時間を変更
+    #[must_use]
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// 次のセッションのプレビューを返す（イベントは発行しない）
+    ///
+    /// 同じ条件のプレビューが有効期限内にあれば、選定し直さずにそれを返す
+    ///
+    /// # Errors
+    ///
+    /// 選定候補を取得できない場合はエラーを返す
+    pub async fn get_session_preview(
+        &self,
+        request: &SessionRequest,
+        now: DateTime<Utc>,
+    ) -> Result<SessionPreview, SessionSelectionError> {
+        {
+            let mut cache = self.cache.lock().await;
+            cache.retain(|_, cached| now < cached.preview.expires_at);
+            if let Some(cached) = cache.values().find(|cached| cached.request == *request) {
+                return Ok(cached.preview.clone());
+            }
+        }
+
+        let selected = self.select(request, now).await?;
+        let history = self
+            .response_times
+            .response_time_stats(request.user_id)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Falling back to the global average response time: {}", e);
+                None
+            });
+        let preview = SessionPreview {
+            user_id:         request.user_id,
+            item_ids:        selected.iter().map(|item| item.item_id).collect(),
+            counts:          CategoryCounts::from_selected(&selected),
+            duration:        DurationEstimate::for_items(selected.len(), history),
+            selection_token: Uuid::new_v4(),
+            expires_at:      now + self.ttl,
+        };
+
+        self.cache.lock().await.insert(
+            preview.selection_token,
+            CachedSelection {
+                request: request.clone(),
+                selected,
+                preview: preview.clone(),
+            },
+        );
+        Ok(preview)
+    }
+
+    /// セッションを開始し、`SessionStarted` を発行する
+    ///
+    /// 有効な選定トークンが同じ条件のプレビューのものであれば、プレビューと
+    /// 同じ項目を使う。期限切れ・無効化済み・条件違いのトークンでは選定し直す
+    ///
+    /// # Errors
+    ///
+    /// 選定候補を取得できない場合、イベントを発行できない場合はエラーを返す
+    pub async fn start_session(
+        &self,
+        request: &SessionRequest,
+        selection_token: Option<Uuid>,
+        now: DateTime<Utc>,
+    ) -> Result<StartedSession, SessionSelectionError> {
+        let cached = match selection_token {
+            Some(token) => self.cache.lock().await.remove(&token),
+            None => None,
+        }
+        .filter(|cached| cached.request == *request && now < cached.preview.expires_at);
+
+        let (selected_items, from_preview) = match cached {
+            Some(cached) => (cached.selected, true),
+            None => (self.select(request, now).await?, false),
+        };
+
+        let session_id = Uuid::new_v4();
+        self.publisher
+            .publish_session_started(&SessionStarted {
+                session_id,
+                user_id: request.user_id,
+                strategy: request.strategy,
+                requested_count: request.item_count,
+                selected_items: selected_items.clone(),
+                started_at: now,
+            })
+            .await?;
+
+        Ok(StartedSession {
+            session_id,
+            selected_items,
+            from_preview,
+        })
+    }
+
+    /// イベントに応じてプレビューを無効にする
+    ///
+    /// ユーザーが分かる場合はそのユーザーの分だけを、分からない場合
+    /// （コレクション全体の変更など）はすべてを無効にする
+    pub async fn invalidate_on_event(&self, event_type: &str, user_id: Option<Uuid>) {
+        if !PREVIEW_INVALIDATING_EVENTS.contains(&event_type) {
+            return;
+        }
+
+        let mut cache = self.cache.lock().await;
+        match user_id {
+            Some(user_id) => cache.retain(|_, cached| cached.request.user_id != user_id),
+            None => cache.clear(),
+        }
+    }
+
+    /// `StartSession` と共通の選定処理
+    async fn select(
+        &self,
+        request: &SessionRequest,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<SelectedItem>, SessionSelectionError> {
+        let candidates = self.candidates.fetch_candidates(request.user_id).await?;
+        Ok(request
+            .strategy
+            .select(&candidates, &request.filter, request.item_count, now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex as StdMutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::domain::{
+        item_selection::ItemCandidate,
+        session_preview::{
+            DurationSource,
+            GLOBAL_AVERAGE_SECONDS_PER_ITEM,
+            MIN_RESPONSE_TIME_SAMPLES,
+            ResponseTimeStats,
+        },
+    };
+
+    #[derive(Default)]
+    struct FakeCandidates {
+        candidates: StdMutex<Vec<ItemCandidate>>,
+        calls:      StdMutex<usize>,
+    }
+
+    #[async_trait]
+    impl ItemCandidateSource for &FakeCandidates {
+        async fn fetch_candidates(
+            &self,
+            _user_id: Uuid,
+        ) -> Result<Vec<ItemCandidate>, SessionPortError> {
+            *self.calls.lock().unwrap() += 1;
+            Ok(self.candidates.lock().unwrap().clone())
+        }
+    }
+
+    struct FakeResponseTimes(Result<Option<ResponseTimeStats>, SessionPortError>);
+
+    #[async_trait]
+    impl ResponseTimeSource for FakeResponseTimes {
+        async fn response_time_stats(
+            &self,
+            _user_id: Uuid,
+        ) -> Result<Option<ResponseTimeStats>, SessionPortError> {
+            self.0.clone()
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingPublisher {
+        events: StdMutex<Vec<SessionStarted>>,
+    }
+
+    #[async_trait]
+    impl LearningEventPublisher for &RecordingPublisher {
+        async fn publish_session_started(
+            &self,
+            event: &SessionStarted,
+        ) -> Result<(), SessionPortError> {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2025-10-01T09:00:00Z".parse().unwrap()
+    }
+
+    fn due(days_ago: i64, accuracy: f32) -> ItemCandidate {
+        ItemCandidate {
+            item_id:        Uuid::new_v4(),
+            register:       None,
+            domain:         None,
+            next_review_at: Some(now() - Duration::days(days_ago)),
+            accuracy:       Some(accuracy),
+        }
+    }
+
+    fn new_item() -> ItemCandidate {
+        ItemCandidate {
+            next_review_at: None,
+            accuracy: None,
+            ..due(0, 0.0)
+        }
+    }
+
+    fn weak(accuracy: f32) -> ItemCandidate {
+        ItemCandidate {
+            next_review_at: Some(now() + Duration::days(3)),
+            ..due(0, accuracy)
+        }
+    }
+
+    fn history() -> FakeResponseTimes {
+        FakeResponseTimes(Ok(Some(ResponseTimeStats {
+            average_ms: 15_000,
+            samples:    MIN_RESPONSE_TIME_SAMPLES,
+        })))
+    }
+
+    #[tokio::test]
+    async fn test_preview_is_read_only_and_matches_start_selection() {
+        let candidates = FakeCandidates::default();
+        *candidates.candidates.lock().unwrap() =
+            vec![due(2, 0.9), due(1, 0.5), new_item(), weak(0.1)];
+        let publisher = RecordingPublisher::default();
+        let service = SessionSelectionService::new(&candidates, history(), &publisher);
+        let request = SessionRequest::new(Uuid::new_v4()).with_item_count(10);
+
+        let preview = service.get_session_preview(&request, now()).await.unwrap();
+
+        assert_eq!(preview.item_ids.len(), 4);
+        assert_eq!(preview.counts.due, 2);
+        assert_eq!(preview.counts.new, 1);
+        assert_eq!(preview.counts.weak_area, 1);
+        assert_eq!(preview.duration.seconds, 60);
+        assert_eq!(preview.duration.source, DurationSource::History);
+        // プレビューではイベントを発行しない
+        assert!(publisher.events.lock().unwrap().is_empty());
+
+        // 開始するときと同じ選定処理を使う
+        let started = service.start_session(&request, None, now()).await.unwrap();
+        assert!(!started.from_preview);
+        assert_eq!(
+            started
+                .selected_items
+                .iter()
+                .map(|item| item.item_id)
+                .collect::<Vec<_>>(),
+            preview.item_ids
+        );
+        assert_eq!(publisher.events.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_selection_token_keeps_previewed_items() {
+        let candidates = FakeCandidates::default();
+        *candidates.candidates.lock().unwrap() = vec![due(3, 0.8), due(1, 0.8), new_item()];
+        let publisher = RecordingPublisher::default();
+        let service = SessionSelectionService::new(&candidates, history(), &publisher);
+        let request = SessionRequest::new(Uuid::new_v4())
+            .with_strategy(SelectionStrategy::DueForReview)
+            .with_item_count(2);
+
+        let preview = service.get_session_preview(&request, now()).await.unwrap();
+        // 同じ条件のプレビューはキャッシュから返す
+        let again = service
+            .get_session_preview(&request, now() + Duration::seconds(30))
+            .await
+            .unwrap();
+        assert_eq!(again, preview);
+        assert_eq!(*candidates.calls.lock().unwrap(), 1);
+
+        // プレビューの後で候補が変わっても、トークンがあれば同じ項目で開始する
+        candidates
+            .candidates
+            .lock()
+            .unwrap()
+            .insert(0, due(10, 0.8));
+        let started = service
+            .start_session(
+                &request,
+                Some(preview.selection_token),
+                now() + Duration::seconds(60),
+            )
+            .await
+            .unwrap();
+        assert!(started.from_preview);
+        assert_eq!(
+            started
+                .selected_items
+                .iter()
+                .map(|item| item.item_id)
+                .collect::<Vec<_>>(),
+            preview.item_ids
+        );
+        assert_eq!(
+            publisher.events.lock().unwrap()[0].selected_items,
+            started.selected_items
+        );
+
+        // トークンは1回限り
+        let restarted = service
+            .start_session(
+                &request,
+                Some(preview.selection_token),
+                now() + Duration::seconds(60),
+            )
+            .await
+            .unwrap();
+        assert!(!restarted.from_preview);
+        assert_ne!(restarted.selected_items[0].item_id, preview.item_ids[0]);
+    }
+
+    #[tokio::test]
+    async fn test_expired_or_mismatched_token_reselects() {
+        let candidates = FakeCandidates::default();
+        *candidates.candidates.lock().unwrap() = vec![due(1, 0.5), new_item()];
+        let publisher = RecordingPublisher::default();
+        let service = SessionSelectionService::new(&candidates, history(), &publisher);
+        let request = SessionRequest::new(Uuid::new_v4());
+
+        let preview = service.get_session_preview(&request, now()).await.unwrap();
+        let started = service
+            .start_session(&request, Some(preview.selection_token), preview.expires_at)
+            .await
+            .unwrap();
+        assert!(!started.from_preview);
+
+        let preview = service.get_session_preview(&request, now()).await.unwrap();
+        let other = request.clone().with_item_count(1);
+        let started = service
+            .start_session(&other, Some(preview.selection_token), now())
+            .await
+            .unwrap();
+        assert!(!started.from_preview);
+        assert_eq!(started.selected_items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_review_and_collection_changes_invalidate_preview() {
+        let candidates = FakeCandidates::default();
+        *candidates.candidates.lock().unwrap() = vec![due(1, 0.5), new_item()];
+        let publisher = RecordingPublisher::default();
+        let service = SessionSelectionService::new(&candidates, history(), &publisher);
+        let user = SessionRequest::new(Uuid::new_v4());
+        let other_user = SessionRequest::new(Uuid::new_v4());
+
+        let preview = service.get_session_preview(&user, now()).await.unwrap();
+        let other_preview = service
+            .get_session_preview(&other_user, now())
+            .await
+            .unwrap();
+
+        // 関係のないイベントでは無効にしない
+        service
+            .invalidate_on_event("ItemPresented", Some(user.user_id))
+            .await;
+        assert_eq!(
+            service.get_session_preview(&user, now()).await.unwrap(),
+            preview
+        );
+
+        service
+            .invalidate_on_event("ItemReviewed", Some(user.user_id))
+            .await;
+        let refreshed = service.get_session_preview(&user, now()).await.unwrap();
+        assert_ne!(refreshed.selection_token, preview.selection_token);
+        assert_eq!(
+            service
+                .get_session_preview(&other_user, now())
+                .await
+                .unwrap(),
+            other_preview
+        );
+        // 無効にしたトークンでは選定し直す
+        let started = service
+            .start_session(&user, Some(preview.selection_token), now())
+            .await
+            .unwrap();
+        assert!(!started.from_preview);
+
+        service
+            .invalidate_on_event("CollectionItemAdded", None)
+            .await;
+        assert_ne!(
+            service
+                .get_session_preview(&other_user, now())
+                .await
+                .unwrap()
+                .selection_token,
+            other_preview.selection_token
+        );
+    }
+
+    #[tokio::test]
+    async fn test_duration_falls_back_to_global_average() {
+        let candidates = FakeCandidates::default();
+        *candidates.candidates.lock().unwrap() = vec![due(1, 0.5), new_item(), new_item()];
+        let publisher = RecordingPublisher::default();
+
+        for response_times in [
+            FakeResponseTimes(Ok(None)),
+            FakeResponseTimes(Err(SessionPortError::Unavailable(
+                "progress read model".to_string(),
+            ))),
+        ] {
+            let service = SessionSelectionService::new(&candidates, response_times, &publisher);
+            let preview = service
+                .get_session_preview(&SessionRequest::new(Uuid::new_v4()), now())
+                .await
+                .unwrap();
+
+            assert_eq!(preview.duration.source, DurationSource::GlobalAverage);
+            assert_eq!(
+                preview.duration.seconds,
+                3 * GLOBAL_AVERAGE_SECONDS_PER_ITEM
+            );
+        }
+    }
+}
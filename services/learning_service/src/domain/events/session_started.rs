@@ -0,0 +1,19 @@
+//! セッション開始イベント
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::item_selection::{SelectedItem, SelectionStrategy};
+
+/// セッションを開始し、項目を選定した
+///
+/// 発行先で `SessionStarted` と `ItemsSelected` に変換する
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionStarted {
+    pub session_id:      Uuid,
+    pub user_id:         Uuid,
+    pub strategy:        SelectionStrategy,
+    pub requested_count: usize,
+    pub selected_items:  Vec<SelectedItem>,
+    pub started_at:      DateTime<Utc>,
+}
@@ -0,0 +1,324 @@
+//! 項目選定
+//!
+//! セッション開始時に、学習コンテキスト（使用域・専門分野）の絞り込みを
+//! 適用してから選定戦略の優先度順に項目を選ぶ。絞り込みはすべての戦略で
+//! 共通に適用するため、どの戦略でも条件に合わない項目は選ばれない。
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use shared_vocabulary_context::domain::{Domain, Register};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// 項目選定のエラー
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SelectionError {
+    #[error("Invalid session filter: {0}")]
+    InvalidFilter(String),
+}
+
+/// セッション単位の学習コンテキストの絞り込み
+///
+/// `StartSession` の `exclude_registers` と `only_domains` に対応する
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LearningContextFilter {
+    exclude_registers: HashSet<Register>,
+    only_domains:      HashSet<Domain>,
+}
+
+impl LearningContextFilter {
+    /// リクエストの文字列から作る
+    ///
+    /// 値は語彙コンテキストと同じ規則で正規化する（空のリストは絞り込まない）
+    ///
+    /// # Errors
+    ///
+    /// 使用域・専門分野として解釈できない値がある場合はエラーを返す
+    pub fn parse(
+        exclude_registers: &[String],
+        only_domains: &[String],
+    ) -> Result<Self, SelectionError> {
+        let exclude_registers = exclude_registers
+            .iter()
+            .map(|value| value.parse::<Register>())
+            .collect::<Result<_, _>>()
+            .map_err(SelectionError::InvalidFilter)?;
+        let only_domains = only_domains
+            .iter()
+            .map(|value| value.parse::<Domain>())
+            .collect::<Result<_, _>>()
+            .map_err(SelectionError::InvalidFilter)?;
+
+        Ok(Self {
+            exclude_registers,
+            only_domains,
+        })
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.exclude_registers.is_empty() && self.only_domains.is_empty()
+    }
+
+    /// 項目が出題対象か
+    ///
+    /// 使用域が未設定の項目は除外しない。専門分野を指定した場合は、
+    /// 専門分野が一致する項目だけを対象にする（未設定の項目は対象外）
+    #[must_use]
+    pub fn allows(&self, candidate: &ItemCandidate) -> bool {
+        let register_allowed = candidate
+            .register
+            .is_none_or(|register| !self.exclude_registers.contains(&register));
+        let domain_allowed = self.only_domains.is_empty()
+            || candidate
+                .domain
+                .as_ref()
+                .is_some_and(|domain| self.only_domains.contains(domain));
+
+        register_allowed && domain_allowed
+    }
+}
+
+/// 選定候補の項目
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemCandidate {
+    pub item_id:        Uuid,
+    pub register:       Option<Register>,
+    pub domain:         Option<Domain>,
+    /// 次回の復習予定日時（未学習の項目は None）
+    pub next_review_at: Option<DateTime<Utc>>,
+    /// これまでの正答率（0.0〜1.0、未学習の項目は None）
+    pub accuracy:       Option<f32>,
+}
+
+impl ItemCandidate {
+    const fn is_new(&self) -> bool {
+        self.next_review_at.is_none()
+    }
+
+    fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_review_at.is_some_and(|at| at <= now)
+    }
+
+    fn weakness(&self) -> f32 {
+        self.accuracy
+            .map_or(0.0, |accuracy| 1.0 - accuracy.clamp(0.0, 1.0))
+    }
+}
+
+/// 項目選定戦略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionStrategy {
+    /// 新規項目優先
+    NewItemsFirst,
+    /// 復習期限到来優先
+    DueForReview,
+    /// 苦手項目優先
+    WeakItemsFirst,
+    /// 混合
+    Mixed,
+}
+
+/// 選定された項目
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectedItem {
+    pub item_id:        Uuid,
+    pub priority_score: f32,
+    pub reason:         &'static str,
+}
+
+/// 選定された項目の区分（プレビューの内訳）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemCategory {
+    /// 復習（期限前の先取りを含む）
+    Due,
+    /// 新規項目
+    New,
+    /// 苦手項目
+    WeakArea,
+}
+
+impl SelectedItem {
+    /// 選定理由から区分を決める
+    #[must_use]
+    pub fn category(&self) -> ItemCategory {
+        match self.reason {
+            "new item" => ItemCategory::New,
+            "weak item" => ItemCategory::WeakArea,
+            _ => ItemCategory::Due,
+        }
+    }
+}
+
+impl SelectionStrategy {
+    /// 絞り込みに合う候補から優先度の高い順に最大 `count` 件を選ぶ
+    ///
+    /// 優先度が同じ場合は復習予定日時が早い順、次に候補の順に並べる
+    #[must_use]
+    pub fn select(
+        self,
+        candidates: &[ItemCandidate],
+        filter: &LearningContextFilter,
+        count: usize,
+        now: DateTime<Utc>,
+    ) -> Vec<SelectedItem> {
+        let mut scored: Vec<(&ItemCandidate, f32, &'static str)> = candidates
+            .iter()
+            .filter(|candidate| filter.allows(candidate))
+            .map(|candidate| {
+                let (score, reason) = self.score(candidate, now);
+                (candidate, score, reason)
+            })
+            .collect();
+
+        scored.sort_by(|(a, a_score, _), (b, b_score, _)| {
+            b_score
+                .total_cmp(a_score)
+                .then_with(|| a.next_review_at.cmp(&b.next_review_at))
+        });
+
+        scored
+            .into_iter()
+            .take(count)
+            .map(|(candidate, priority_score, reason)| SelectedItem {
+                item_id: candidate.item_id,
+                priority_score,
+                reason,
+            })
+            .collect()
+    }
+
+    fn score(self, candidate: &ItemCandidate, now: DateTime<Utc>) -> (f32, &'static str) {
+        let is_new = candidate.is_new();
+        let is_due = candidate.is_due(now);
+
+        match self {
+            Self::NewItemsFirst if is_new => (1.0, "new item"),
+            Self::NewItemsFirst if is_due => (0.5, "due for review"),
+            Self::DueForReview if is_due => (1.0, "due for review"),
+            Self::DueForReview if is_new => (0.3, "new item"),
+            Self::WeakItemsFirst if is_new => (0.2, "new item"),
+            Self::WeakItemsFirst => (candidate.weakness(), "weak item"),
+            Self::Mixed if is_due => (0.4f32.mul_add(candidate.weakness(), 0.6), "due for review"),
+            Self::Mixed if is_new => (0.5, "new item"),
+            Self::Mixed => (0.4 * candidate.weakness(), "weak item"),
+            Self::NewItemsFirst | Self::DueForReview => (0.1, "review ahead"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn candidate(register: Option<Register>, domain: Option<Domain>) -> ItemCandidate {
+        ItemCandidate {
+            item_id: Uuid::new_v4(),
+            register,
+            domain,
+            next_review_at: None,
+            accuracy: None,
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_filter_is_enforced_by_every_strategy() {
+        let now = Utc::now();
+        let slang = candidate(Some(Register::Slang), Some(Domain::Business));
+        let formal = ItemCandidate {
+            next_review_at: Some(now - Duration::days(1)),
+            accuracy: Some(0.4),
+            ..candidate(Some(Register::Formal), Some(Domain::Business))
+        };
+        let untagged = candidate(None, Some(Domain::Business));
+        let medical = candidate(Some(Register::Neutral), Some(Domain::Medical));
+        let no_domain = candidate(Some(Register::Neutral), None);
+        let candidates = vec![slang, formal.clone(), untagged.clone(), medical, no_domain];
+
+        let filter =
+            LearningContextFilter::parse(&strings(&["Slang"]), &strings(&["business"])).unwrap();
+
+        for strategy in [
+            SelectionStrategy::NewItemsFirst,
+            SelectionStrategy::DueForReview,
+            SelectionStrategy::WeakItemsFirst,
+            SelectionStrategy::Mixed,
+        ] {
+            let selected: HashSet<Uuid> = strategy
+                .select(&candidates, &filter, 10, now)
+                .into_iter()
+                .map(|item| item.item_id)
+                .collect();
+            assert_eq!(
+                selected,
+                HashSet::from([formal.item_id, untagged.item_id]),
+                "{strategy:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_filter_allows_everything() {
+        let filter = LearningContextFilter::parse(&[], &[]).unwrap();
+        assert!(filter.is_empty());
+        assert!(filter.allows(&candidate(Some(Register::Slang), None)));
+        assert!(filter.allows(&candidate(None, None)));
+    }
+
+    #[test]
+    fn test_invalid_filter_values_are_rejected() {
+        assert!(matches!(
+            LearningContextFilter::parse(&strings(&["street"]), &[]),
+            Err(SelectionError::InvalidFilter(_))
+        ));
+        assert!(matches!(
+            LearningContextFilter::parse(&[], &strings(&[" "])),
+            Err(SelectionError::InvalidFilter(_))
+        ));
+    }
+
+    #[test]
+    fn test_strategy_ordering_and_count() {
+        let now = Utc::now();
+        let new_item = candidate(None, None);
+        let overdue = ItemCandidate {
+            next_review_at: Some(now - Duration::days(3)),
+            accuracy: Some(0.9),
+            ..candidate(None, None)
+        };
+        let due_today = ItemCandidate {
+            next_review_at: Some(now - Duration::hours(1)),
+            accuracy: Some(0.2),
+            ..candidate(None, None)
+        };
+        let candidates = vec![new_item.clone(), due_today.clone(), overdue.clone()];
+        let filter = LearningContextFilter::default();
+        let ids = |strategy: SelectionStrategy, count| -> Vec<Uuid> {
+            strategy
+                .select(&candidates, &filter, count, now)
+                .into_iter()
+                .map(|item| item.item_id)
+                .collect()
+        };
+
+        assert_eq!(
+            ids(SelectionStrategy::NewItemsFirst, 1),
+            vec![new_item.item_id]
+        );
+        assert_eq!(
+            ids(SelectionStrategy::DueForReview, 2),
+            vec![overdue.item_id, due_today.item_id]
+        );
+        assert_eq!(
+            ids(SelectionStrategy::WeakItemsFirst, 1),
+            vec![due_today.item_id]
+        );
+    }
+}
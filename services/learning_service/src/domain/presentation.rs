@@ -0,0 +1,22 @@
+//! 出題時に提示する項目
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// `ItemPresented` で提示する項目の内容
+///
+/// Vocabulary Query Service の一括取得（`POST /items/batch`）の結果から作る。
+/// 使用域と専門分野は学習時のヒントとしてそのまま表示する。
+/// 使用上の注意は出題時には隠し、解答とともに見せる（`HiddenContent.
+/// usage_notes`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresentedItem {
+    pub item_id:        Uuid,
+    pub spelling:       String,
+    pub disambiguation: Option<String>,
+    pub part_of_speech: Option<String>,
+    pub cefr_level:     Option<String>,
+    pub register:       Option<String>,
+    pub domain:         Option<String>,
+    pub usage_notes:    Option<String>,
+}
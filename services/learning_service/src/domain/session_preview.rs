@@ -0,0 +1,170 @@
+//! 次のセッションのプレビュー
+//!
+//! セッションを開始せずに、選定される項目の内訳と所要時間の目安を示す
+//! （ホーム画面の「次のセッション: 復習 12 件、新規 4 件、約 8 分」）。
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::item_selection::{ItemCategory, SelectedItem};
+
+/// 履歴がない場合に使う1項目あたりの所要時間（秒、全ユーザーの平均）
+pub const GLOBAL_AVERAGE_SECONDS_PER_ITEM: u32 = 30;
+
+/// ユーザーの履歴を所要時間の推定に使うのに必要な回答数
+pub const MIN_RESPONSE_TIME_SAMPLES: u32 = 20;
+
+/// ユーザーの1項目あたりの反応時間（Progress の Read Model）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResponseTimeStats {
+    /// 提示から判定までの平均（ミリ秒）
+    pub average_ms: u32,
+    pub samples:    u32,
+}
+
+/// 区分ごとの項目数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CategoryCounts {
+    pub due:       u32,
+    pub new:       u32,
+    pub weak_area: u32,
+}
+
+impl CategoryCounts {
+    #[must_use]
+    pub fn from_selected(selected: &[SelectedItem]) -> Self {
+        selected.iter().fold(Self::default(), |mut counts, item| {
+            match item.category() {
+                ItemCategory::Due => counts.due += 1,
+                ItemCategory::New => counts.new += 1,
+                ItemCategory::WeakArea => counts.weak_area += 1,
+            }
+            counts
+        })
+    }
+}
+
+/// 所要時間の推定に使った値
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationSource {
+    /// ユーザーの反応時間の履歴
+    History,
+    /// 全ユーザーの平均（履歴が足りない、または取得できない場合）
+    GlobalAverage,
+}
+
+/// セッションの所要時間の目安
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DurationEstimate {
+    pub seconds: u32,
+    pub source:  DurationSource,
+}
+
+impl DurationEstimate {
+    /// 項目数と反応時間の履歴から推定する
+    #[must_use]
+    pub fn for_items(item_count: usize, history: Option<ResponseTimeStats>) -> Self {
+        let (seconds_per_item, source) = match history {
+            Some(stats) if stats.samples >= MIN_RESPONSE_TIME_SAMPLES => (
+                f64::from(stats.average_ms) / 1000.0,
+                DurationSource::History,
+            ),
+            _ => (
+                f64::from(GLOBAL_AVERAGE_SECONDS_PER_ITEM),
+                DurationSource::GlobalAverage,
+            ),
+        };
+
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let seconds = (item_count as f64 * seconds_per_item).round() as u32;
+        Self { seconds, source }
+    }
+
+    /// 分単位に切り上げた目安
+    #[must_use]
+    pub const fn minutes(self) -> u32 {
+        self.seconds.div_ceil(60)
+    }
+}
+
+/// 次のセッションのプレビュー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionPreview {
+    pub user_id:         Uuid,
+    /// 選定順
+    pub item_ids:        Vec<Uuid>,
+    pub counts:          CategoryCounts,
+    pub duration:        DurationEstimate,
+    /// `StartSession` に渡すと同じ項目でセッションを開始する
+    pub selection_token: Uuid,
+    /// 選定トークンの有効期限
+    pub expires_at:      DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selected(reason: &'static str) -> SelectedItem {
+        SelectedItem {
+            item_id: Uuid::new_v4(),
+            priority_score: 1.0,
+            reason,
+        }
+    }
+
+    #[test]
+    fn test_counts_by_category() {
+        let items = [
+            selected("due for review"),
+            selected("review ahead"),
+            selected("new item"),
+            selected("weak item"),
+            selected("due for review"),
+        ];
+
+        assert_eq!(
+            CategoryCounts::from_selected(&items),
+            CategoryCounts {
+                due:       3,
+                new:       1,
+                weak_area: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_duration_uses_history_only_with_enough_samples() {
+        let history = ResponseTimeStats {
+            average_ms: 12_500,
+            samples:    MIN_RESPONSE_TIME_SAMPLES,
+        };
+        let estimate = DurationEstimate::for_items(16, Some(history));
+        assert_eq!(
+            estimate,
+            DurationEstimate {
+                seconds: 200,
+                source:  DurationSource::History,
+            }
+        );
+        assert_eq!(estimate.minutes(), 4);
+
+        let sparse = ResponseTimeStats {
+            samples: MIN_RESPONSE_TIME_SAMPLES - 1,
+            ..history
+        };
+        for history in [Some(sparse), None] {
+            assert_eq!(
+                DurationEstimate::for_items(16, history),
+                DurationEstimate {
+                    seconds: 16 * GLOBAL_AVERAGE_SECONDS_PER_ITEM,
+                    source:  DurationSource::GlobalAverage,
+                }
+            );
+        }
+    }
+}
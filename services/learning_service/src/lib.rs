@@ -9,12 +9,20 @@
 pub mod domain {
     //! ドメイン層
 
+    pub mod item_selection;
+    pub mod presentation;
+    pub mod session_preview;
+
     pub mod aggregates {
         //! 集約
     }
 
     pub mod events {
         //! ドメインイベント
+
+        mod session_started;
+
+        pub use session_started::SessionStarted;
     }
 
     pub mod commands {
@@ -39,6 +47,9 @@ pub mod application {
 
     pub mod services {
         //! アプリケーションサービス
+
+        pub mod item_presentation;
+        pub mod session_items;
     }
 }
 
@@ -61,7 +72,5 @@ pub mod ports {
         //! インバウンドポート
     }
 
-    pub mod outbound {
-        //! アウトバウンドポート
-    }
+    pub mod outbound;
 }
@@ -0,0 +1,64 @@
+//! アウトバウンドポート
+
+use async_trait::async_trait;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::domain::{
+    events::SessionStarted,
+    item_selection::ItemCandidate,
+    presentation::PresentedItem,
+    session_preview::ResponseTimeStats,
+};
+
+/// 語彙項目の取得エラー
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ItemSourceError {
+    #[error("Vocabulary item source unavailable: {0}")]
+    Unavailable(String),
+    #[error("Invalid response from vocabulary item source: {0}")]
+    InvalidResponse(String),
+}
+
+/// 出題する語彙項目の取得元（Vocabulary Query Service）
+#[async_trait]
+pub trait VocabularyItemSource: Send + Sync {
+    /// 項目をまとめて取得する
+    ///
+    /// 存在しない項目は結果に含まれない。結果の順序は問わない
+    async fn fetch_items(&self, item_ids: &[Uuid]) -> Result<Vec<PresentedItem>, ItemSourceError>;
+}
+
+/// セッションの準備に使う取得元・発行先のエラー
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SessionPortError {
+    #[error("Learning data source unavailable: {0}")]
+    Unavailable(String),
+    #[error("Failed to publish learning event: {0}")]
+    PublishFailed(String),
+}
+
+/// 選定候補の取得元（Algorithm Service の学習状態）
+#[async_trait]
+pub trait ItemCandidateSource: Send + Sync {
+    /// ユーザーの学習対象の項目を、復習予定と正答率付きで取得する
+    async fn fetch_candidates(&self, user_id: Uuid)
+    -> Result<Vec<ItemCandidate>, SessionPortError>;
+}
+
+/// 反応時間の取得元（Progress の Read Model）
+#[async_trait]
+pub trait ResponseTimeSource: Send + Sync {
+    /// 回答の履歴がない場合は None
+    async fn response_time_stats(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<ResponseTimeStats>, SessionPortError>;
+}
+
+/// 学習イベントの発行先
+#[async_trait]
+pub trait LearningEventPublisher: Send + Sync {
+    async fn publish_session_started(&self, event: &SessionStarted)
+    -> Result<(), SessionPortError>;
+}
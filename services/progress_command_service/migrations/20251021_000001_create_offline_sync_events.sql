@@ -0,0 +1,17 @@
+-- オフライン同期で適用したイベントの記録
+-- 再送されたバッチの重複排除と、端末で記録した元の日時の保持に使う
+
+CREATE TABLE IF NOT EXISTS offline_sync_events (
+    client_event_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    device_id VARCHAR(255) NOT NULL,
+    event_type VARCHAR(100) NOT NULL,
+    client_timestamp TIMESTAMPTZ NOT NULL,   -- 端末の時計で記録した日時
+    server_timestamp TIMESTAMPTZ NOT NULL,   -- サーバーの時刻に置き換えた日時
+    clock_offset_ms BIGINT NOT NULL,         -- 置き換えに使った補正量
+    applied_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, client_event_id)
+);
+
+CREATE INDEX idx_offline_sync_events_user_server_timestamp
+    ON offline_sync_events (user_id, server_timestamp);
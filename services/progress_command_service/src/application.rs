@@ -1,7 +1,11 @@
 //! アプリケーション層
 
+pub mod collections;
 pub mod command_handler;
+pub mod offline_sync;
 pub mod services;
 
+pub use collections::*;
 pub use command_handler::*;
+pub use offline_sync::*;
 pub use services::*;
@@ -0,0 +1,171 @@
+//! 共有コレクションの構成とメンバーの変更
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    domain::{Collection, CollectionCommand, CollectionEvent},
+    error::Result,
+    ports::outbound::CollectionEventStorePort,
+};
+
+/// 共有コレクションのコマンドサービス
+pub struct CollectionService {
+    store: Arc<dyn CollectionEventStorePort>,
+}
+
+impl CollectionService {
+    pub fn new(store: Arc<dyn CollectionEventStorePort>) -> Self {
+        Self { store }
+    }
+
+    /// コレクションへのコマンドを処理し、記録したイベントを返す
+    ///
+    /// 構成が変わらない場合は何も記録せず `None` を返す
+    pub async fn execute(
+        &self,
+        collection_id: Uuid,
+        actor: Uuid,
+        command: CollectionCommand,
+        now: DateTime<Utc>,
+    ) -> Result<Option<CollectionEvent>> {
+        let events = self.store.get_collection_events(collection_id).await?;
+        let collection = Collection::from_events(collection_id, &events);
+
+        let Some(event) = collection.decide(actor, &command, now)? else {
+            return Ok(None);
+        };
+        self.store.save_collection_event(&event).await?;
+        info!(
+            collection_id = %collection_id,
+            user_id = %actor,
+            event_type = event.event_type(),
+            "Recorded collection event"
+        );
+
+        Ok(Some(event))
+    }
+
+    /// メンバー別統計を他のメンバーに公開するかを変更する
+    pub async fn set_stats_visibility(
+        &self,
+        user_id: Uuid,
+        visible: bool,
+        now: DateTime<Utc>,
+    ) -> Result<CollectionEvent> {
+        let event = CollectionEvent::CollectionStatsVisibilityChanged {
+            user_id,
+            visible,
+            timestamp: now,
+        };
+        self.store.save_collection_event(&event).await?;
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::{domain::collection_stream_id, error::Error};
+
+    /// (ストリーム ID, イベント) を保存順に保持する
+    #[derive(Default)]
+    struct InMemoryCollectionStore {
+        events: Mutex<Vec<(String, CollectionEvent)>>,
+    }
+
+    #[async_trait]
+    impl CollectionEventStorePort for InMemoryCollectionStore {
+        async fn save_collection_event(&self, event: &CollectionEvent) -> Result<i64> {
+            let mut events = self.events.lock().unwrap();
+            events.push((event.stream_id(), event.clone()));
+            Ok(events.len() as i64)
+        }
+
+        async fn get_collection_events(&self, collection_id: Uuid) -> Result<Vec<CollectionEvent>> {
+            let stream_id = collection_stream_id(collection_id);
+            Ok(self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(id, _)| *id == stream_id)
+                .map(|(_, event)| event.clone())
+                .collect())
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2025-10-01T12:00:00Z".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_commands_are_validated_against_the_stored_collection() {
+        let store = Arc::new(InMemoryCollectionStore::default());
+        let service = CollectionService::new(store.clone());
+        let (collection_id, member, outsider, item) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let add = CollectionCommand::AddItem {
+            vocabulary_item_id: item,
+        };
+
+        assert!(matches!(
+            service
+                .execute(collection_id, outsider, add.clone(), now())
+                .await,
+            Err(Error::Collection(_))
+        ));
+
+        service
+            .execute(collection_id, member, CollectionCommand::Join, now())
+            .await
+            .unwrap();
+        let added = service
+            .execute(collection_id, member, add.clone(), now())
+            .await
+            .unwrap();
+        assert_eq!(
+            added,
+            Some(CollectionEvent::CollectionItemAdded {
+                collection_id,
+                vocabulary_item_id: item,
+                added_by: member,
+                timestamp: now(),
+            })
+        );
+        // 再送は記録しない
+        assert_eq!(
+            service
+                .execute(collection_id, member, add, now())
+                .await
+                .unwrap(),
+            None
+        );
+        service
+            .set_stats_visibility(member, false, now())
+            .await
+            .unwrap();
+
+        let events = store.events.lock().unwrap();
+        let types: Vec<_> = events.iter().map(|(_, event)| event.event_type()).collect();
+        assert_eq!(
+            types,
+            vec![
+                "CollectionMemberJoined",
+                "CollectionItemAdded",
+                "CollectionStatsVisibilityChanged"
+            ]
+        );
+    }
+}
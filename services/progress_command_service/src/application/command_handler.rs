@@ -3,7 +3,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::{
@@ -77,10 +77,14 @@ impl ProgressCommandHandler {
 #[async_trait]
 impl CommandHandler for ProgressCommandHandler {
     async fn handle(&self, command: ProgressCommand) -> Result<()> {
+        self.handle_at(command, Utc::now()).await
+    }
+
+    async fn handle_at(&self, command: ProgressCommand, occurred_at: DateTime<Utc>) -> Result<()> {
         let event = match command {
             ProgressCommand::StartLearning { user_id } => ProgressEvent::LearningStarted {
                 user_id,
-                timestamp: Utc::now(),
+                timestamp: occurred_at,
             },
             ProgressCommand::RecordItemCompletion {
                 user_id,
@@ -92,7 +96,7 @@ impl CommandHandler for ProgressCommandHandler {
                 vocabulary_item_id,
                 accuracy,
                 time_spent,
-                timestamp: Utc::now(),
+                timestamp: occurred_at,
             },
             ProgressCommand::RecordSessionCompletion {
                 user_id,
@@ -104,7 +108,7 @@ impl CommandHandler for ProgressCommandHandler {
                 items_count,
                 correct_count,
                 duration,
-                timestamp: Utc::now(),
+                timestamp: occurred_at,
             },
             ProgressCommand::UpdateStreak {
                 user_id,
@@ -112,7 +116,7 @@ impl CommandHandler for ProgressCommandHandler {
             } => ProgressEvent::StreakUpdated {
                 user_id,
                 new_streak,
-                timestamp: Utc::now(),
+                timestamp: occurred_at,
             },
             ProgressCommand::UnlockAchievement {
                 user_id,
@@ -120,11 +124,11 @@ impl CommandHandler for ProgressCommandHandler {
             } => ProgressEvent::AchievementUnlocked {
                 user_id,
                 achievement_id,
-                timestamp: Utc::now(),
+                timestamp: occurred_at,
             },
             ProgressCommand::CompleteDailyGoal { user_id } => ProgressEvent::DailyGoalCompleted {
                 user_id,
-                timestamp: Utc::now(),
+                timestamp: occurred_at,
             },
             ProgressCommand::ResetDaily { user_id } => {
                 let mut progress = self.load_aggregate(user_id).await?;
@@ -147,14 +151,7 @@ impl CommandHandler for ProgressCommandHandler {
         };
 
         // ユーザーIDを取得
-        let user_id = match &event {
-            ProgressEvent::LearningStarted { user_id, .. }
-            | ProgressEvent::ItemCompleted { user_id, .. }
-            | ProgressEvent::SessionCompleted { user_id, .. }
-            | ProgressEvent::StreakUpdated { user_id, .. }
-            | ProgressEvent::AchievementUnlocked { user_id, .. }
-            | ProgressEvent::DailyGoalCompleted { user_id, .. } => *user_id,
-        };
+        let user_id = event.user_id();
 
         // イベントを保存・発行
         self.save_event(user_id, event).await?;
@@ -0,0 +1,769 @@
+//! オフラインセッションの同期
+//!
+//! 端末がオフラインで記録したセッションを検証してサーバーの時刻に置き換え、
+//! オンラインの学習と同じコマンドとして処理する。集約・イベントストア・
+//! イベント発行の経路は共通のため、後続のプロジェクションはオンラインで
+//! 学習した場合と同じように更新される。
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Duration, Utc};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        AppliedOfflineEvent,
+        DEFAULT_OFFLINE_WINDOW_DAYS,
+        OfflineEvent,
+        OfflineEventKind,
+        OfflineEventOutcome,
+        OfflineEventReport,
+        OfflineSessionBatch,
+        OfflineSyncReport,
+        RejectionReason,
+        SessionInterval,
+        session_intervals,
+        validate_session,
+    },
+    error::Result,
+    ports::{inbound::CommandHandler, outbound::*},
+};
+
+/// オフラインセッションの同期サービス
+pub struct OfflineSyncService {
+    command_handler: Arc<dyn CommandHandler>,
+    event_store:     Arc<dyn EventStorePort>,
+    ledger:          Arc<dyn OfflineSyncLedgerPort>,
+    offline_window:  Duration,
+}
+
+impl OfflineSyncService {
+    pub fn new(
+        command_handler: Arc<dyn CommandHandler>,
+        event_store: Arc<dyn EventStorePort>,
+        ledger: Arc<dyn OfflineSyncLedgerPort>,
+    ) -> Self {
+        Self {
+            command_handler,
+            event_store,
+            ledger,
+            offline_window: Duration::days(DEFAULT_OFFLINE_WINDOW_DAYS),
+        }
+    }
+
+    /// 受け付けるセッションの古さの上限を変更する
+    pub fn with_offline_window(mut self, offline_window: Duration) -> Self {
+        self.offline_window = offline_window;
+        self
+    }
+
+    /// オフラインで記録したセッションを取り込む
+    ///
+    /// バッチ全体の不整合（手順、時刻、オンラインのセッションとの重なり）は
+    /// 未適用のイベントをすべて拒否し、アイテム完了の内容の不備とバッチ内の
+    /// ID の重複はそのイベントだけを拒否する。適用済みのイベントは再送されても
+    /// 適用し直さない。
+    ///
+    /// イベントを保存した後に適用を記録するため、その間に失敗した場合だけは
+    /// 再送でイベントが重複しうる
+    pub async fn submit(
+        &self,
+        batch: OfflineSessionBatch,
+        received_at: DateTime<Utc>,
+    ) -> Result<OfflineSyncReport> {
+        let mut outcomes: Vec<Option<OfflineEventOutcome>> = vec![None; batch.events.len()];
+
+        // バッチ内で ID が重複するイベントはセッションから除く
+        let mut seen = HashSet::new();
+        let mut session: Vec<usize> = Vec::new();
+        for (index, event) in batch.events.iter().enumerate() {
+            if seen.insert(event.client_event_id) {
+                session.push(index);
+            } else {
+                outcomes[index] = Some(OfflineEventOutcome::Rejected(
+                    RejectionReason::DuplicateInBatch,
+                ));
+            }
+        }
+
+        let client_event_ids: Vec<Uuid> = session
+            .iter()
+            .map(|&index| batch.events[index].client_event_id)
+            .collect();
+        let applied: HashMap<Uuid, AppliedOfflineEvent> = self
+            .ledger
+            .find_applied(batch.user_id, &client_event_ids)
+            .await?
+            .into_iter()
+            .map(|event| (event.client_event_id, event))
+            .collect();
+        for &index in &session {
+            if applied.contains_key(&batch.events[index].client_event_id) {
+                outcomes[index] = Some(OfflineEventOutcome::Deduplicated);
+            }
+        }
+
+        // 再送では最初の送信と同じ補正量を使い、適用済みのイベントとの間隔を保つ
+        let clock_offset = applied.values().next().map_or_else(
+            || received_at - batch.sent_at,
+            |event| Duration::milliseconds(event.clock_offset_ms),
+        );
+        let pending: Vec<usize> = session
+            .iter()
+            .copied()
+            .filter(|&index| outcomes[index].is_none())
+            .collect();
+
+        if !pending.is_empty() {
+            let events: Vec<&OfflineEvent> =
+                session.iter().map(|&index| &batch.events[index]).collect();
+            let checked = match validate_session(&events, batch.sent_at) {
+                Ok(()) if applied.is_empty() => {
+                    self.check_placement(&batch, &events, clock_offset, received_at)
+                        .await?
+                },
+                // 一部適用済みのセッションは最初の送信で検証を通っている
+                Ok(()) => Ok(()),
+                Err(reason) => Err(reason),
+            };
+
+            match checked {
+                Ok(()) => {
+                    self.apply(&batch, &pending, clock_offset, &mut outcomes)
+                        .await?
+                },
+                Err(reason) => {
+                    warn!(
+                        user_id = %batch.user_id,
+                        device_id = %batch.device_id,
+                        "Rejected offline session: {}",
+                        reason
+                    );
+                    for &index in &pending {
+                        outcomes[index] = Some(OfflineEventOutcome::Rejected(reason.clone()));
+                    }
+                },
+            }
+        }
+
+        let report = OfflineSyncReport {
+            user_id: batch.user_id,
+            device_id: batch.device_id,
+            clock_offset,
+            events: batch
+                .events
+                .iter()
+                .zip(outcomes)
+                .map(|(event, outcome)| OfflineEventReport {
+                    client_event_id: event.client_event_id,
+                    outcome:         outcome.unwrap_or(OfflineEventOutcome::Deduplicated),
+                })
+                .collect(),
+        };
+        info!(
+            user_id = %report.user_id,
+            device_id = %report.device_id,
+            applied = report.applied_count(),
+            deduplicated = report.deduplicated_count(),
+            rejected = report.rejected_count(),
+            "Offline session synced"
+        );
+        Ok(report)
+    }
+
+    /// サーバーの時刻に置き換えたセッションが受け付け期間内で、
+    /// オンラインのセッションと重ならないことを確認する
+    async fn check_placement(
+        &self,
+        batch: &OfflineSessionBatch,
+        events: &[&OfflineEvent],
+        clock_offset: Duration,
+        received_at: DateTime<Utc>,
+    ) -> Result<std::result::Result<(), RejectionReason>> {
+        let (Some(first), Some(last)) = (events.first(), events.last()) else {
+            return Ok(Err(RejectionReason::EmptyBatch));
+        };
+        let offline = SessionInterval {
+            started_at: first.recorded_at + clock_offset,
+            ended_at:   last.recorded_at + clock_offset,
+        };
+        if offline.started_at < received_at - self.offline_window {
+            return Ok(Err(RejectionReason::OutsideOfflineWindow));
+        }
+
+        // オフライン同期で適用済みのセッションはオンラインのセッションとみなさない
+        let synced_starts: HashSet<DateTime<Utc>> = self
+            .ledger
+            .applied_since(batch.user_id, offline.started_at - self.offline_window)
+            .await?
+            .into_iter()
+            .filter(|event| event.event_type == OfflineEventKind::SessionStarted.name())
+            .map(|event| event.server_timestamp)
+            .collect();
+        let stream_id = format!("progress-{}", batch.user_id);
+        let online = session_intervals(&self.event_store.get_events(stream_id).await?);
+
+        Ok(
+            match online.into_iter().find(|interval| {
+                !synced_starts.contains(&interval.started_at) && interval.overlaps(&offline)
+            }) {
+                Some(interval) => Err(RejectionReason::OverlapsOnlineSession {
+                    started_at: interval.started_at,
+                    ended_at:   interval.ended_at,
+                }),
+                None => Ok(()),
+            },
+        )
+    }
+
+    /// 未適用のイベントを記録順に通常のコマンドとして処理する
+    async fn apply(
+        &self,
+        batch: &OfflineSessionBatch,
+        pending: &[usize],
+        clock_offset: Duration,
+        outcomes: &mut [Option<OfflineEventOutcome>],
+    ) -> Result<()> {
+        for &index in pending {
+            let event = &batch.events[index];
+            if let Err(reason) = event.kind.validate() {
+                outcomes[index] = Some(OfflineEventOutcome::Rejected(reason));
+                continue;
+            }
+
+            let server_timestamp = event.recorded_at + clock_offset;
+            self.command_handler
+                .handle_at(event.kind.to_command(batch.user_id), server_timestamp)
+                .await?;
+            self.ledger
+                .record_applied(&AppliedOfflineEvent {
+                    client_event_id: event.client_event_id,
+                    user_id: batch.user_id,
+                    device_id: batch.device_id.clone(),
+                    event_type: event.kind.name().to_string(),
+                    client_timestamp: event.recorded_at,
+                    server_timestamp,
+                    clock_offset_ms: clock_offset.num_milliseconds(),
+                })
+                .await?;
+            outcomes[index] = Some(OfflineEventOutcome::Applied { server_timestamp });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+    use shared_event_store::EventStore;
+
+    use super::*;
+    use crate::{
+        application::{ProgressCommandHandler, ProgressQueryService},
+        domain::{Progress, ProgressCommand, ProgressEvent},
+        ports::inbound::ProgressQueryPort,
+    };
+
+    /// 共有のインメモリ Event Store に `progress-{user_id}`
+    /// のストリームを保存する
+    #[derive(Default)]
+    struct InMemoryEventStore(shared_event_store::testing::InMemoryEventStore);
+
+    const AGGREGATE_TYPE: &str = "progress";
+
+    fn aggregate_id(stream_id: &str) -> Uuid {
+        stream_id.trim_start_matches("progress-").parse().unwrap()
+    }
+
+    #[async_trait]
+    impl EventStorePort for InMemoryEventStore {
+        async fn save_event(&self, stream_id: String, event: ProgressEvent) -> Result<i64> {
+            let aggregate_id = aggregate_id(&stream_id);
+            self.0
+                .save_events(
+                    aggregate_id,
+                    AGGREGATE_TYPE,
+                    vec![serde_json::to_value(&event)?],
+                    None,
+                )
+                .await
+                .unwrap();
+            Ok(i64::from(self.0.current_version(aggregate_id)))
+        }
+
+        async fn get_events(&self, stream_id: String) -> Result<Vec<ProgressEvent>> {
+            self.get_events_from(stream_id, 0).await
+        }
+
+        async fn get_events_from(
+            &self,
+            stream_id: String,
+            from_version: i64,
+        ) -> Result<Vec<ProgressEvent>> {
+            let from_version = u32::try_from(from_version).unwrap();
+            self.0
+                .load_events(aggregate_id(&stream_id), AGGREGATE_TYPE, Some(from_version))
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|event| Ok(serde_json::from_value(event.event_data)?))
+                .collect()
+        }
+    }
+
+    #[derive(Default)]
+    struct InMemorySnapshotStore {
+        snapshots: Mutex<HashMap<Uuid, Progress>>,
+    }
+
+    #[async_trait]
+    impl SnapshotStorePort for InMemorySnapshotStore {
+        async fn save_snapshot(&self, aggregate_id: Uuid, snapshot: Progress) -> Result<()> {
+            self.snapshots
+                .lock()
+                .unwrap()
+                .insert(aggregate_id, snapshot);
+            Ok(())
+        }
+
+        async fn get_latest_snapshot(&self, aggregate_id: Uuid) -> Result<Option<Progress>> {
+            Ok(self.snapshots.lock().unwrap().get(&aggregate_id).cloned())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingPublisher {
+        published: Mutex<Vec<ProgressEvent>>,
+    }
+
+    #[async_trait]
+    impl EventPublisherPort for RecordingPublisher {
+        async fn publish(&self, event: ProgressEvent) -> Result<()> {
+            self.published.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct InMemoryLedger {
+        applied: Mutex<Vec<AppliedOfflineEvent>>,
+    }
+
+    #[async_trait]
+    impl OfflineSyncLedgerPort for InMemoryLedger {
+        async fn find_applied(
+            &self,
+            user_id: Uuid,
+            client_event_ids: &[Uuid],
+        ) -> Result<Vec<AppliedOfflineEvent>> {
+            Ok(self
+                .applied
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| e.user_id == user_id && client_event_ids.contains(&e.client_event_id))
+                .cloned()
+                .collect())
+        }
+
+        async fn applied_since(
+            &self,
+            user_id: Uuid,
+            since: DateTime<Utc>,
+        ) -> Result<Vec<AppliedOfflineEvent>> {
+            Ok(self
+                .applied
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| e.user_id == user_id && e.server_timestamp >= since)
+                .cloned()
+                .collect())
+        }
+
+        async fn record_applied(&self, event: &AppliedOfflineEvent) -> Result<()> {
+            self.applied.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    struct Fixture {
+        user_id:     Uuid,
+        event_store: Arc<InMemoryEventStore>,
+        snapshots:   Arc<InMemorySnapshotStore>,
+        publisher:   Arc<RecordingPublisher>,
+        ledger:      Arc<InMemoryLedger>,
+        handler:     Arc<ProgressCommandHandler>,
+        service:     OfflineSyncService,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let event_store = Arc::new(InMemoryEventStore::default());
+            let snapshots = Arc::new(InMemorySnapshotStore::default());
+            let publisher = Arc::new(RecordingPublisher::default());
+            let ledger = Arc::new(InMemoryLedger::default());
+            let handler = Arc::new(ProgressCommandHandler::new(
+                event_store.clone(),
+                snapshots.clone(),
+                publisher.clone(),
+            ));
+            let service =
+                OfflineSyncService::new(handler.clone(), event_store.clone(), ledger.clone());
+            Self {
+                user_id: Uuid::new_v4(),
+                event_store,
+                snapshots,
+                publisher,
+                ledger,
+                handler,
+                service,
+            }
+        }
+
+        fn stored_events(&self) -> Vec<ProgressEvent> {
+            self.event_store
+                .0
+                .events()
+                .iter()
+                .filter(|event| event.aggregate_id == self.user_id)
+                .map(|event| serde_json::from_value(event.event_data.clone()).unwrap())
+                .collect()
+        }
+
+        /// 端末の時計で `start` から1分ごとに記録した、2アイテムのセッション
+        fn batch(&self, start: DateTime<Utc>, sent_at: DateTime<Utc>) -> OfflineSessionBatch {
+            let minutes = |n: i64| start + Duration::minutes(n);
+            let events = vec![
+                (minutes(0), OfflineEventKind::SessionStarted),
+                (
+                    minutes(1),
+                    OfflineEventKind::ItemCompleted {
+                        vocabulary_item_id: Uuid::from_u128(1),
+                        accuracy:           1.0,
+                        time_spent:         40,
+                    },
+                ),
+                (
+                    minutes(2),
+                    OfflineEventKind::ItemCompleted {
+                        vocabulary_item_id: Uuid::from_u128(2),
+                        accuracy:           0.5,
+                        time_spent:         50,
+                    },
+                ),
+                (
+                    minutes(3),
+                    OfflineEventKind::SessionCompleted {
+                        items_count:   2,
+                        correct_count: 1,
+                        duration:      180,
+                    },
+                ),
+            ];
+            OfflineSessionBatch {
+                user_id: self.user_id,
+                device_id: "ios-device-1".to_string(),
+                sent_at,
+                events: events
+                    .into_iter()
+                    .map(|(recorded_at, kind)| OfflineEvent {
+                        client_event_id: Uuid::new_v4(),
+                        recorded_at,
+                        kind,
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    fn server_now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 10, 21, 12, 0, 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_timestamps_are_rebased_onto_server_time() {
+        let fixture = Fixture::new();
+        // 端末の時計は2時間遅れている。端末で 07:00 に学習し、09:30 に送信した
+        let device_start = Utc.with_ymd_and_hms(2025, 10, 21, 7, 0, 0).unwrap();
+        let batch = fixture.batch(device_start, server_now() - Duration::hours(2));
+
+        let report = fixture
+            .service
+            .submit(batch.clone(), server_now())
+            .await
+            .unwrap();
+
+        assert_eq!(report.clock_offset, Duration::hours(2));
+        assert_eq!(report.applied_count(), 4);
+        for (event, reported) in batch.events.iter().zip(&report.events) {
+            assert_eq!(
+                reported.outcome,
+                OfflineEventOutcome::Applied {
+                    server_timestamp: event.recorded_at + Duration::hours(2),
+                }
+            );
+        }
+
+        // 保存したイベントは順序と間隔を保ち、端末の日時は記録に残る
+        let timestamps: Vec<_> = fixture
+            .stored_events()
+            .iter()
+            .map(ProgressEvent::timestamp)
+            .collect();
+        let expected_start = device_start + Duration::hours(2);
+        assert_eq!(
+            timestamps,
+            (0..4)
+                .map(|n| expected_start + Duration::minutes(n))
+                .collect::<Vec<_>>()
+        );
+        let ledger = fixture.ledger.applied.lock().unwrap();
+        assert_eq!(ledger.len(), 4);
+        assert_eq!(ledger[0].client_timestamp, device_start);
+        assert_eq!(ledger[0].server_timestamp, expected_start);
+        assert_eq!(ledger[0].device_id, "ios-device-1");
+    }
+
+    #[tokio::test]
+    async fn test_retried_upload_is_deduplicated() {
+        let fixture = Fixture::new();
+        let batch = fixture.batch(
+            server_now() - Duration::hours(3),
+            server_now() - Duration::minutes(1),
+        );
+
+        let first = fixture
+            .service
+            .submit(batch.clone(), server_now())
+            .await
+            .unwrap();
+        assert_eq!(first.applied_count(), 4);
+
+        // 応答を受け取れなかった端末が、時計が変わった後で再送する
+        let mut retry = batch.clone();
+        retry.sent_at += Duration::minutes(30);
+        let second = fixture
+            .service
+            .submit(retry, server_now() + Duration::minutes(10))
+            .await
+            .unwrap();
+
+        assert_eq!(second.deduplicated_count(), 4);
+        assert_eq!(second.applied_count(), 0);
+        assert_eq!(second.clock_offset, first.clock_offset);
+        assert_eq!(fixture.stored_events().len(), 4);
+        assert_eq!(fixture.publisher.published.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_partially_applied_session_is_completed_with_the_original_offset() {
+        let fixture = Fixture::new();
+        let batch = fixture.batch(
+            server_now() - Duration::hours(3),
+            server_now() - Duration::minutes(5),
+        );
+
+        // 最初の送信は2件目まで適用したところで途切れた
+        let mut partial = batch.clone();
+        partial.events.truncate(2);
+        let first = fixture.service.submit(partial, server_now()).await.unwrap();
+        assert!(first.events.iter().all(|e| matches!(
+            e.outcome,
+            OfflineEventOutcome::Rejected(RejectionReason::ProtocolOrder(_))
+        )));
+
+        // 手順どおりでないバッチは適用しないため、適用済みの状態を直接作る
+        let offset = Duration::minutes(5);
+        for event in &batch.events[..2] {
+            fixture
+                .handler
+                .handle_at(
+                    event.kind.to_command(fixture.user_id),
+                    event.recorded_at + offset,
+                )
+                .await
+                .unwrap();
+            fixture
+                .ledger
+                .record_applied(&AppliedOfflineEvent {
+                    client_event_id:  event.client_event_id,
+                    user_id:          fixture.user_id,
+                    device_id:        batch.device_id.clone(),
+                    event_type:       event.kind.name().to_string(),
+                    client_timestamp: event.recorded_at,
+                    server_timestamp: event.recorded_at + offset,
+                    clock_offset_ms:  offset.num_milliseconds(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let mut retry = batch.clone();
+        retry.sent_at = server_now();
+        let report = fixture
+            .service
+            .submit(retry, server_now() + Duration::hours(1))
+            .await
+            .unwrap();
+
+        assert_eq!(report.deduplicated_count(), 2);
+        assert_eq!(report.applied_count(), 2);
+        assert_eq!(report.clock_offset, offset);
+        assert_eq!(
+            report.events[3].outcome,
+            OfflineEventOutcome::Applied {
+                server_timestamp: batch.events[3].recorded_at + offset,
+            }
+        );
+        assert_eq!(fixture.stored_events().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_batch_overlapping_an_online_session_is_rejected() {
+        let fixture = Fixture::new();
+        let online_start = server_now() - Duration::hours(2);
+        fixture
+            .handler
+            .handle_at(
+                ProgressCommand::StartLearning {
+                    user_id: fixture.user_id,
+                },
+                online_start,
+            )
+            .await
+            .unwrap();
+        fixture
+            .handler
+            .handle_at(
+                ProgressCommand::RecordSessionCompletion {
+                    user_id:       fixture.user_id,
+                    items_count:   0,
+                    correct_count: 0,
+                    duration:      600,
+                },
+                online_start + Duration::minutes(10),
+            )
+            .await
+            .unwrap();
+
+        // サーバーの時刻で online_start + 5分から始まるオフラインセッション
+        let batch = fixture.batch(online_start + Duration::minutes(5), server_now());
+        let report = fixture.service.submit(batch, server_now()).await.unwrap();
+
+        assert_eq!(report.rejected_count(), 4);
+        assert!(report.events.iter().all(|e| e.outcome
+            == OfflineEventOutcome::Rejected(RejectionReason::OverlapsOnlineSession {
+                started_at: online_start,
+                ended_at:   online_start + Duration::minutes(10),
+            })));
+        assert_eq!(fixture.stored_events().len(), 2);
+        assert!(fixture.ledger.applied.lock().unwrap().is_empty());
+
+        // 重ならない時間帯なら受け付ける
+        let batch = fixture.batch(online_start + Duration::minutes(11), server_now());
+        let report = fixture.service.submit(batch, server_now()).await.unwrap();
+        assert_eq!(report.applied_count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_items_and_stale_sessions_are_rejected() {
+        let fixture = Fixture::new();
+        let mut batch = fixture.batch(server_now() - Duration::hours(1), server_now());
+        batch.events[2].kind = OfflineEventKind::ItemCompleted {
+            vocabulary_item_id: Uuid::from_u128(2),
+            accuracy:           3.0,
+            time_spent:         50,
+        };
+        let duplicate = batch.events[1].clone();
+        batch.events.insert(2, duplicate);
+
+        let report = fixture.service.submit(batch, server_now()).await.unwrap();
+        let codes: Vec<Option<&str>> = report
+            .events
+            .iter()
+            .map(|e| match &e.outcome {
+                OfflineEventOutcome::Rejected(reason) => Some(reason.code()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                None,
+                None,
+                Some("duplicate_in_batch"),
+                Some("invalid_payload"),
+                None
+            ]
+        );
+        assert_eq!(fixture.stored_events().len(), 3);
+
+        let stale = fixture.batch(server_now() - Duration::days(15), server_now());
+        let report = fixture.service.submit(stale, server_now()).await.unwrap();
+        assert!(
+            report.events.iter().all(|e| e.outcome
+                == OfflineEventOutcome::Rejected(RejectionReason::OutsideOfflineWindow))
+        );
+        assert_eq!(fixture.stored_events().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_synced_session_updates_progress_like_a_live_session() {
+        let fixture = Fixture::new();
+        let batch = fixture.batch(server_now() - Duration::hours(5), server_now());
+        fixture.service.submit(batch, server_now()).await.unwrap();
+
+        let progress =
+            ProgressQueryService::new(fixture.event_store.clone(), fixture.snapshots.clone())
+                .get_user_progress(fixture.user_id)
+                .await
+                .unwrap();
+        assert_eq!(progress.daily_stats.session_count, 1);
+        assert_eq!(progress.total_stats.total_sessions, 1);
+        assert_eq!(progress.vocabulary.item_progress.len(), 2);
+        assert_eq!(
+            progress.vocabulary.item_progress[&Uuid::from_u128(1)].correct_count,
+            1
+        );
+        assert_eq!(
+            progress.vocabulary.item_progress[&Uuid::from_u128(2)].correct_count,
+            0
+        );
+        assert_eq!(
+            progress.updated_at,
+            server_now() - Duration::hours(5) + Duration::minutes(3)
+        );
+
+        // 発行したイベントはオンラインの場合と同じ種類と順序
+        let published: Vec<&'static str> = fixture
+            .publisher
+            .published
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|event| match event {
+                ProgressEvent::LearningStarted { .. } => "LearningStarted",
+                ProgressEvent::ItemCompleted { .. } => "ItemCompleted",
+                ProgressEvent::SessionCompleted { .. } => "SessionCompleted",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(
+            published,
+            vec![
+                "LearningStarted",
+                "ItemCompleted",
+                "ItemCompleted",
+                "SessionCompleted"
+            ]
+        );
+    }
+}
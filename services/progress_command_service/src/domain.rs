@@ -1,11 +1,15 @@
 //! Progress ドメイン層
 
 pub mod aggregates;
+pub mod collection;
 pub mod commands;
 pub mod events;
+pub mod offline_session;
 pub mod value_objects;
 
 pub use aggregates::Progress;
+pub use collection::*;
 pub use commands::*;
 pub use events::*;
+pub use offline_session::*;
 pub use value_objects::*;
@@ -0,0 +1,332 @@
+//! 共有コレクションの構成とメンバー
+//!
+//! 共有コレクションのアイテムとメンバーの変更、メンバー別統計の公開設定を
+//! イベントとして記録する。Progress Projection Service はこのイベントと
+//! 学習イベントを突き合わせ、`(コレクション, メンバー)` の組ごとの統計を
+//! 計算し直す。
+//!
+//! アイテムの追加・削除はメンバーだけができる。コレクションは最初の
+//! メンバーが参加した時点で作られる。
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// コレクションのイベント
+///
+/// ペイロードのフィールド名は Progress Projection Service が読み取る
+/// 名前（`collection_id`、`vocabulary_item_id`、`user_id`、
+/// `visible`）に合わせる
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum CollectionEvent {
+    /// アイテムを追加した
+    CollectionItemAdded {
+        collection_id:      Uuid,
+        vocabulary_item_id: Uuid,
+        added_by:           Uuid,
+        timestamp:          DateTime<Utc>,
+    },
+
+    /// アイテムを削除した
+    CollectionItemRemoved {
+        collection_id:      Uuid,
+        vocabulary_item_id: Uuid,
+        removed_by:         Uuid,
+        timestamp:          DateTime<Utc>,
+    },
+
+    /// メンバーが参加した
+    CollectionMemberJoined {
+        collection_id: Uuid,
+        user_id:       Uuid,
+        timestamp:     DateTime<Utc>,
+    },
+
+    /// メンバーが抜けた
+    CollectionMemberLeft {
+        collection_id: Uuid,
+        user_id:       Uuid,
+        timestamp:     DateTime<Utc>,
+    },
+
+    /// メンバー別統計を他のメンバーに公開するかを変更した（全コレクション共通）
+    CollectionStatsVisibilityChanged {
+        user_id:   Uuid,
+        visible:   bool,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl CollectionEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::CollectionItemAdded { .. } => "CollectionItemAdded",
+            Self::CollectionItemRemoved { .. } => "CollectionItemRemoved",
+            Self::CollectionMemberJoined { .. } => "CollectionMemberJoined",
+            Self::CollectionMemberLeft { .. } => "CollectionMemberLeft",
+            Self::CollectionStatsVisibilityChanged { .. } => "CollectionStatsVisibilityChanged",
+        }
+    }
+
+    /// イベントを保存するストリーム
+    ///
+    /// 構成とメンバーはコレクションごと、公開設定はユーザーごとのストリームに
+    /// 保存する（ユーザーの進捗のストリームには混ぜない）
+    pub fn stream_id(&self) -> String {
+        match self {
+            Self::CollectionItemAdded { collection_id, .. }
+            | Self::CollectionItemRemoved { collection_id, .. }
+            | Self::CollectionMemberJoined { collection_id, .. }
+            | Self::CollectionMemberLeft { collection_id, .. } => {
+                collection_stream_id(*collection_id)
+            },
+            Self::CollectionStatsVisibilityChanged { user_id, .. } => {
+                format!("collection-stats-visibility-{user_id}")
+            },
+        }
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            Self::CollectionItemAdded { timestamp, .. }
+            | Self::CollectionItemRemoved { timestamp, .. }
+            | Self::CollectionMemberJoined { timestamp, .. }
+            | Self::CollectionMemberLeft { timestamp, .. }
+            | Self::CollectionStatsVisibilityChanged { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+/// コレクションのストリーム ID
+pub fn collection_stream_id(collection_id: Uuid) -> String {
+    format!("collection-{collection_id}")
+}
+
+/// コレクションへのコマンド（操作するユーザーは別に渡す）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CollectionCommand {
+    AddItem { vocabulary_item_id: Uuid },
+    RemoveItem { vocabulary_item_id: Uuid },
+    Join,
+    Leave,
+}
+
+/// コレクションへのコマンドを受け付けなかった理由
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CollectionError {
+    #[error("{user_id} is not a member of collection {collection_id}")]
+    NotMember {
+        collection_id: Uuid,
+        user_id:       Uuid,
+    },
+}
+
+/// コレクションの現在の構成（イベントから復元する）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collection {
+    collection_id: Uuid,
+    members:       HashSet<Uuid>,
+    items:         HashSet<Uuid>,
+}
+
+impl Collection {
+    pub fn new(collection_id: Uuid) -> Self {
+        Self {
+            collection_id,
+            members: HashSet::new(),
+            items: HashSet::new(),
+        }
+    }
+
+    /// 保存済みのイベントから復元する
+    pub fn from_events(collection_id: Uuid, events: &[CollectionEvent]) -> Self {
+        let mut collection = Self::new(collection_id);
+        for event in events {
+            collection.apply(event);
+        }
+        collection
+    }
+
+    pub fn apply(&mut self, event: &CollectionEvent) {
+        match event {
+            CollectionEvent::CollectionItemAdded {
+                vocabulary_item_id, ..
+            } => {
+                self.items.insert(*vocabulary_item_id);
+            },
+            CollectionEvent::CollectionItemRemoved {
+                vocabulary_item_id, ..
+            } => {
+                self.items.remove(vocabulary_item_id);
+            },
+            CollectionEvent::CollectionMemberJoined { user_id, .. } => {
+                self.members.insert(*user_id);
+            },
+            CollectionEvent::CollectionMemberLeft { user_id, .. } => {
+                self.members.remove(user_id);
+            },
+            CollectionEvent::CollectionStatsVisibilityChanged { .. } => {},
+        }
+    }
+
+    pub fn is_member(&self, user_id: Uuid) -> bool {
+        self.members.contains(&user_id)
+    }
+
+    /// コマンドを検証し、記録するイベントを返す
+    ///
+    /// 構成が変わらない場合（参加済みのメンバーの参加、追加済みのアイテムの
+    /// 追加など）は `None` を返し、再送しても同じ結果になる
+    pub fn decide(
+        &self,
+        actor: Uuid,
+        command: &CollectionCommand,
+        now: DateTime<Utc>,
+    ) -> Result<Option<CollectionEvent>, CollectionError> {
+        let collection_id = self.collection_id;
+        let not_member = || CollectionError::NotMember {
+            collection_id,
+            user_id: actor,
+        };
+
+        let event = match command {
+            CollectionCommand::Join => {
+                (!self.is_member(actor)).then_some(CollectionEvent::CollectionMemberJoined {
+                    collection_id,
+                    user_id: actor,
+                    timestamp: now,
+                })
+            },
+            CollectionCommand::Leave => {
+                if !self.is_member(actor) {
+                    return Err(not_member());
+                }
+                Some(CollectionEvent::CollectionMemberLeft {
+                    collection_id,
+                    user_id: actor,
+                    timestamp: now,
+                })
+            },
+            CollectionCommand::AddItem { vocabulary_item_id } => {
+                if !self.is_member(actor) {
+                    return Err(not_member());
+                }
+                (!self.items.contains(vocabulary_item_id)).then_some(
+                    CollectionEvent::CollectionItemAdded {
+                        collection_id,
+                        vocabulary_item_id: *vocabulary_item_id,
+                        added_by: actor,
+                        timestamp: now,
+                    },
+                )
+            },
+            CollectionCommand::RemoveItem { vocabulary_item_id } => {
+                if !self.is_member(actor) {
+                    return Err(not_member());
+                }
+                self.items.contains(vocabulary_item_id).then_some(
+                    CollectionEvent::CollectionItemRemoved {
+                        collection_id,
+                        vocabulary_item_id: *vocabulary_item_id,
+                        removed_by: actor,
+                        timestamp: now,
+                    },
+                )
+            },
+        };
+
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        "2025-10-01T12:00:00Z".parse().unwrap()
+    }
+
+    fn apply(collection: &mut Collection, actor: Uuid, command: CollectionCommand) {
+        let event = collection.decide(actor, &command, now()).unwrap().unwrap();
+        collection.apply(&event);
+    }
+
+    #[test]
+    fn test_only_members_change_items_and_repeats_are_no_ops() {
+        let (member, outsider, item) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut collection = Collection::new(Uuid::new_v4());
+        let add = CollectionCommand::AddItem {
+            vocabulary_item_id: item,
+        };
+
+        assert!(matches!(
+            collection.decide(outsider, &add, now()),
+            Err(CollectionError::NotMember { user_id, .. }) if user_id == outsider
+        ));
+
+        apply(&mut collection, member, CollectionCommand::Join);
+        assert_eq!(
+            collection.decide(member, &CollectionCommand::Join, now()),
+            Ok(None)
+        );
+        apply(&mut collection, member, add.clone());
+        assert_eq!(collection.decide(member, &add, now()), Ok(None));
+
+        apply(
+            &mut collection,
+            member,
+            CollectionCommand::RemoveItem {
+                vocabulary_item_id: item,
+            },
+        );
+        apply(&mut collection, member, CollectionCommand::Leave);
+        assert!(
+            collection
+                .decide(member, &CollectionCommand::Leave, now())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_events_carry_the_fields_the_projection_reads() {
+        let (collection_id, user_id, item) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let joined = CollectionEvent::CollectionMemberJoined {
+            collection_id,
+            user_id,
+            timestamp: now(),
+        };
+        let added = CollectionEvent::CollectionItemAdded {
+            collection_id,
+            vocabulary_item_id: item,
+            added_by: user_id,
+            timestamp: now(),
+        };
+        let visibility = CollectionEvent::CollectionStatsVisibilityChanged {
+            user_id,
+            visible: false,
+            timestamp: now(),
+        };
+
+        let joined_json = serde_json::to_value(&joined).unwrap();
+        assert_eq!(joined_json["type"], joined.event_type());
+        assert_eq!(joined_json["collection_id"], collection_id.to_string());
+        assert_eq!(joined_json["user_id"], user_id.to_string());
+        assert_eq!(
+            serde_json::to_value(&added).unwrap()["vocabulary_item_id"],
+            item.to_string()
+        );
+        assert_eq!(serde_json::to_value(&visibility).unwrap()["visible"], false);
+
+        assert_eq!(joined.stream_id(), format!("collection-{collection_id}"));
+        assert_eq!(added.stream_id(), joined.stream_id());
+        assert_eq!(
+            visibility.stream_id(),
+            format!("collection-stats-visibility-{user_id}")
+        );
+    }
+}
@@ -52,3 +52,29 @@ pub enum ProgressEvent {
         timestamp: DateTime<Utc>,
     },
 }
+
+impl ProgressEvent {
+    /// イベントのユーザー ID
+    pub fn user_id(&self) -> Uuid {
+        match self {
+            Self::LearningStarted { user_id, .. }
+            | Self::ItemCompleted { user_id, .. }
+            | Self::SessionCompleted { user_id, .. }
+            | Self::StreakUpdated { user_id, .. }
+            | Self::AchievementUnlocked { user_id, .. }
+            | Self::DailyGoalCompleted { user_id, .. } => *user_id,
+        }
+    }
+
+    /// イベントの発生日時
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            Self::LearningStarted { timestamp, .. }
+            | Self::ItemCompleted { timestamp, .. }
+            | Self::SessionCompleted { timestamp, .. }
+            | Self::StreakUpdated { timestamp, .. }
+            | Self::AchievementUnlocked { timestamp, .. }
+            | Self::DailyGoalCompleted { timestamp, .. } => *timestamp,
+        }
+    }
+}
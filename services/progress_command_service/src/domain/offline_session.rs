@@ -0,0 +1,511 @@
+//! オフラインで記録した学習セッション
+//!
+//! モバイルアプリがオフラインで記録したセッションのイベントをまとめて受け取り、
+//! セッションの手順どおりに並んでいるか、時刻がもっともらしいかを検証する。
+//! 端末の時計はずれている可能性があるため、送信時刻と受信時刻の差で
+//! サーバーの時刻に置き換える（イベントの間隔と順序は変わらない）。
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{commands::ProgressCommand, events::ProgressEvent};
+
+/// オフラインで記録したセッションを受け付ける期間の既定値（日）
+pub const DEFAULT_OFFLINE_WINDOW_DAYS: i64 = 14;
+
+/// オフラインで記録したイベントの内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum OfflineEventKind {
+    /// セッション開始
+    SessionStarted,
+
+    /// アイテム完了
+    ItemCompleted {
+        vocabulary_item_id: Uuid,
+        accuracy:           f32,
+        time_spent:         i32,
+    },
+
+    /// セッション完了
+    SessionCompleted {
+        items_count:   i32,
+        correct_count: i32,
+        duration:      i32,
+    },
+}
+
+impl OfflineEventKind {
+    /// イベント名
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SessionStarted => "SessionStarted",
+            Self::ItemCompleted { .. } => "ItemCompleted",
+            Self::SessionCompleted { .. } => "SessionCompleted",
+        }
+    }
+
+    /// オンラインで記録した場合と同じコマンド
+    pub fn to_command(&self, user_id: Uuid) -> ProgressCommand {
+        match *self {
+            Self::SessionStarted => ProgressCommand::StartLearning { user_id },
+            Self::ItemCompleted {
+                vocabulary_item_id,
+                accuracy,
+                time_spent,
+            } => ProgressCommand::RecordItemCompletion {
+                user_id,
+                vocabulary_item_id,
+                accuracy,
+                time_spent,
+            },
+            Self::SessionCompleted {
+                items_count,
+                correct_count,
+                duration,
+            } => ProgressCommand::RecordSessionCompletion {
+                user_id,
+                items_count,
+                correct_count,
+                duration,
+            },
+        }
+    }
+
+    /// 内容の妥当性を検証する
+    pub fn validate(&self) -> Result<(), RejectionReason> {
+        match *self {
+            Self::SessionStarted => Ok(()),
+            Self::ItemCompleted {
+                accuracy,
+                time_spent,
+                ..
+            } => {
+                if !(0.0..=1.0).contains(&accuracy) {
+                    return Err(RejectionReason::InvalidPayload(format!(
+                        "accuracy must be between 0 and 1: {accuracy}"
+                    )));
+                }
+                if time_spent < 0 {
+                    return Err(RejectionReason::InvalidPayload(format!(
+                        "time_spent must not be negative: {time_spent}"
+                    )));
+                }
+                Ok(())
+            },
+            Self::SessionCompleted {
+                items_count,
+                correct_count,
+                duration,
+            } => {
+                if items_count < 0 || correct_count < 0 || duration < 0 {
+                    return Err(RejectionReason::InvalidPayload(
+                        "session counts and duration must not be negative".to_string(),
+                    ));
+                }
+                if correct_count > items_count {
+                    return Err(RejectionReason::InvalidPayload(format!(
+                        "correct_count {correct_count} exceeds items_count {items_count}"
+                    )));
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// オフラインで記録したイベント
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfflineEvent {
+    /// 端末で生成したイベント ID（再送の重複排除に使う）
+    pub client_event_id: Uuid,
+    /// 端末の時計で記録した日時
+    pub recorded_at:     DateTime<Utc>,
+    pub kind:            OfflineEventKind,
+}
+
+/// オフラインで記録した1セッション分のイベント
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfflineSessionBatch {
+    pub user_id:   Uuid,
+    pub device_id: String,
+    /// 端末の時計で送信した日時
+    pub sent_at:   DateTime<Utc>,
+    /// 記録した順のイベント
+    pub events:    Vec<OfflineEvent>,
+}
+
+/// イベントを受け付けなかった理由
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RejectionReason {
+    #[error("Batch contains no events")]
+    EmptyBatch,
+
+    #[error("Events are not in session order: {0}")]
+    ProtocolOrder(String),
+
+    #[error("Event was recorded before the previous event")]
+    TimestampOutOfOrder,
+
+    #[error("Event was recorded after the batch was sent")]
+    RecordedAfterSend,
+
+    #[error("Session is older than the offline window")]
+    OutsideOfflineWindow,
+
+    #[error("Session overlaps an online session from {started_at} to {ended_at}")]
+    OverlapsOnlineSession {
+        started_at: DateTime<Utc>,
+        ended_at:   DateTime<Utc>,
+    },
+
+    #[error("Invalid event payload: {0}")]
+    InvalidPayload(String),
+
+    #[error("Client event id appears more than once in the batch")]
+    DuplicateInBatch,
+}
+
+impl RejectionReason {
+    /// クライアントが分岐に使うコード
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::EmptyBatch => "empty_batch",
+            Self::ProtocolOrder(_) => "protocol_order",
+            Self::TimestampOutOfOrder => "timestamp_out_of_order",
+            Self::RecordedAfterSend => "recorded_after_send",
+            Self::OutsideOfflineWindow => "outside_offline_window",
+            Self::OverlapsOnlineSession { .. } => "overlaps_online_session",
+            Self::InvalidPayload(_) => "invalid_payload",
+            Self::DuplicateInBatch => "duplicate_in_batch",
+        }
+    }
+}
+
+/// イベントごとの取り込み結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OfflineEventOutcome {
+    /// 適用した（サーバーの時刻に置き換えた発生日時）
+    Applied { server_timestamp: DateTime<Utc> },
+    /// 以前の送信で適用済み
+    Deduplicated,
+    Rejected(RejectionReason),
+}
+
+/// イベントごとの取り込み結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfflineEventReport {
+    pub client_event_id: Uuid,
+    pub outcome:         OfflineEventOutcome,
+}
+
+/// バッチの取り込み結果（送信されたイベントの順）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfflineSyncReport {
+    pub user_id:      Uuid,
+    pub device_id:    String,
+    /// 端末の時計からサーバーの時刻への補正量
+    pub clock_offset: Duration,
+    pub events:       Vec<OfflineEventReport>,
+}
+
+impl OfflineSyncReport {
+    pub fn applied_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, OfflineEventOutcome::Applied { .. }))
+    }
+
+    pub fn deduplicated_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, OfflineEventOutcome::Deduplicated))
+    }
+
+    pub fn rejected_count(&self) -> usize {
+        self.count(|outcome| matches!(outcome, OfflineEventOutcome::Rejected(_)))
+    }
+
+    fn count(&self, predicate: impl Fn(&OfflineEventOutcome) -> bool) -> usize {
+        self.events
+            .iter()
+            .filter(|event| predicate(&event.outcome))
+            .count()
+    }
+}
+
+/// オフライン同期で適用したイベントの記録
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedOfflineEvent {
+    pub client_event_id:  Uuid,
+    pub user_id:          Uuid,
+    pub device_id:        String,
+    pub event_type:       String,
+    /// 端末の時計で記録した日時
+    pub client_timestamp: DateTime<Utc>,
+    /// サーバーの時刻に置き換えた日時
+    pub server_timestamp: DateTime<Utc>,
+    /// 置き換えに使った補正量（ミリ秒）
+    pub clock_offset_ms:  i64,
+}
+
+/// セッションの区間
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionInterval {
+    pub started_at: DateTime<Utc>,
+    pub ended_at:   DateTime<Utc>,
+}
+
+impl SessionInterval {
+    /// 区間が重なるか（端点の一致も重なりとみなす）
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.started_at <= other.ended_at && other.started_at <= self.ended_at
+    }
+}
+
+/// 進捗イベントのストリームから各セッションの区間を求める
+///
+/// 学習開始から次の学習開始またはセッション完了までを1セッションとし、
+/// 完了していないセッションは最後のアイテム完了までとする
+pub fn session_intervals(events: &[ProgressEvent]) -> Vec<SessionInterval> {
+    let mut intervals = Vec::new();
+    let mut current: Option<SessionInterval> = None;
+
+    for event in events {
+        match event {
+            ProgressEvent::LearningStarted { timestamp, .. } => {
+                intervals.extend(current.take());
+                current = Some(SessionInterval {
+                    started_at: *timestamp,
+                    ended_at:   *timestamp,
+                });
+            },
+            ProgressEvent::ItemCompleted { timestamp, .. } => {
+                if let Some(interval) = current.as_mut() {
+                    interval.ended_at = interval.ended_at.max(*timestamp);
+                }
+            },
+            ProgressEvent::SessionCompleted { timestamp, .. } => {
+                if let Some(mut interval) = current.take() {
+                    interval.ended_at = interval.ended_at.max(*timestamp);
+                    intervals.push(interval);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    intervals.extend(current);
+    intervals
+}
+
+/// セッション全体の整合性を検証する
+///
+/// 開始 → アイテム完了（0件以上）→ 完了の順に並び、端末の時計で時刻が
+/// 減らず、送信より後に記録されたイベントがないこと。アイテム完了の内容は
+/// イベントごとに検証するため、ここでは見ない
+pub fn validate_session(
+    events: &[&OfflineEvent],
+    sent_at: DateTime<Utc>,
+) -> Result<(), RejectionReason> {
+    let (Some(first), Some(last)) = (events.first(), events.last()) else {
+        return Err(RejectionReason::EmptyBatch);
+    };
+
+    if first.kind != OfflineEventKind::SessionStarted {
+        return Err(RejectionReason::ProtocolOrder(format!(
+            "first event must be SessionStarted, got {}",
+            first.kind.name()
+        )));
+    }
+    if events.len() < 2 || !matches!(last.kind, OfflineEventKind::SessionCompleted { .. }) {
+        return Err(RejectionReason::ProtocolOrder(format!(
+            "last event must be SessionCompleted, got {}",
+            last.kind.name()
+        )));
+    }
+    if let Some(event) = events[1..events.len() - 1]
+        .iter()
+        .find(|event| !matches!(event.kind, OfflineEventKind::ItemCompleted { .. }))
+    {
+        return Err(RejectionReason::ProtocolOrder(format!(
+            "only ItemCompleted may appear inside a session, got {}",
+            event.kind.name()
+        )));
+    }
+    last.kind.validate()?;
+
+    if events
+        .windows(2)
+        .any(|pair| pair[1].recorded_at < pair[0].recorded_at)
+    {
+        return Err(RejectionReason::TimestampOutOfOrder);
+    }
+    if last.recorded_at > sent_at {
+        return Err(RejectionReason::RecordedAfterSend);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 10, 20, 8, minute, 0).unwrap()
+    }
+
+    fn event(minute: u32, kind: OfflineEventKind) -> OfflineEvent {
+        OfflineEvent {
+            client_event_id: Uuid::new_v4(),
+            recorded_at: at(minute),
+            kind,
+        }
+    }
+
+    fn item(accuracy: f32) -> OfflineEventKind {
+        OfflineEventKind::ItemCompleted {
+            vocabulary_item_id: Uuid::new_v4(),
+            accuracy,
+            time_spent: 30,
+        }
+    }
+
+    fn completed() -> OfflineEventKind {
+        OfflineEventKind::SessionCompleted {
+            items_count:   1,
+            correct_count: 1,
+            duration:      120,
+        }
+    }
+
+    #[test]
+    fn test_session_must_follow_protocol_order() {
+        let started = event(0, OfflineEventKind::SessionStarted);
+        let answered = event(1, item(1.0));
+        let finished = event(2, completed());
+
+        assert_eq!(
+            validate_session(&[&started, &answered, &finished], at(3)),
+            Ok(())
+        );
+        assert_eq!(validate_session(&[], at(3)), Err(RejectionReason::EmptyBatch));
+        assert!(matches!(
+            validate_session(&[&answered, &finished], at(3)),
+            Err(RejectionReason::ProtocolOrder(_))
+        ));
+        assert!(matches!(
+            validate_session(&[&started, &answered], at(3)),
+            Err(RejectionReason::ProtocolOrder(_))
+        ));
+        assert!(matches!(
+            validate_session(&[&started, &started, &finished], at(3)),
+            Err(RejectionReason::ProtocolOrder(_))
+        ));
+    }
+
+    #[test]
+    fn test_session_timestamps_must_be_plausible() {
+        let started = event(5, OfflineEventKind::SessionStarted);
+        let answered = event(4, item(1.0));
+        let finished = event(6, completed());
+        assert_eq!(
+            validate_session(&[&started, &answered, &finished], at(10)),
+            Err(RejectionReason::TimestampOutOfOrder)
+        );
+
+        let answered = event(5, item(1.0));
+        assert_eq!(
+            validate_session(&[&started, &answered, &finished], at(5)),
+            Err(RejectionReason::RecordedAfterSend)
+        );
+    }
+
+    #[test]
+    fn test_payload_validation() {
+        assert!(item(0.5).validate().is_ok());
+        assert!(matches!(
+            item(1.5).validate(),
+            Err(RejectionReason::InvalidPayload(_))
+        ));
+        assert!(matches!(
+            item(f32::NAN).validate(),
+            Err(RejectionReason::InvalidPayload(_))
+        ));
+        assert!(matches!(
+            OfflineEventKind::SessionCompleted {
+                items_count:   1,
+                correct_count: 2,
+                duration:      10,
+            }
+            .validate(),
+            Err(RejectionReason::InvalidPayload(_))
+        ));
+    }
+
+    #[test]
+    fn test_session_intervals_from_progress_events() {
+        let user_id = Uuid::new_v4();
+        let events = vec![
+            ProgressEvent::LearningStarted {
+                user_id,
+                timestamp: at(0),
+            },
+            ProgressEvent::ItemCompleted {
+                user_id,
+                vocabulary_item_id: Uuid::new_v4(),
+                accuracy: 1.0,
+                time_spent: 10,
+                timestamp: at(3),
+            },
+            ProgressEvent::SessionCompleted {
+                user_id,
+                items_count: 1,
+                correct_count: 1,
+                duration: 300,
+                timestamp: at(5),
+            },
+            ProgressEvent::StreakUpdated {
+                user_id,
+                new_streak: 2,
+                timestamp: at(6),
+            },
+            // 完了していないセッション
+            ProgressEvent::LearningStarted {
+                user_id,
+                timestamp: at(20),
+            },
+            ProgressEvent::ItemCompleted {
+                user_id,
+                vocabulary_item_id: Uuid::new_v4(),
+                accuracy: 0.5,
+                time_spent: 10,
+                timestamp: at(22),
+            },
+        ];
+
+        let intervals = session_intervals(&events);
+        assert_eq!(
+            intervals,
+            vec![
+                SessionInterval {
+                    started_at: at(0),
+                    ended_at:   at(5),
+                },
+                SessionInterval {
+                    started_at: at(20),
+                    ended_at:   at(22),
+                },
+            ]
+        );
+        assert!(intervals[0].overlaps(&SessionInterval {
+            started_at: at(5),
+            ended_at:   at(8),
+        }));
+        assert!(!intervals[0].overlaps(&SessionInterval {
+            started_at: at(6),
+            ended_at:   at(8),
+        }));
+    }
+}
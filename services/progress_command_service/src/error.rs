@@ -17,6 +17,9 @@ pub enum Error {
 
     #[error("Parse error: {0}")]
     Parse(String),
+
+    #[error("Collection command rejected: {0}")]
+    Collection(#[from] crate::domain::CollectionError),
 }
 
 impl From<std::num::ParseIntError> for Error {
@@ -2,8 +2,10 @@
 
 pub mod event_publisher;
 pub mod event_store;
+pub mod offline_sync_ledger;
 pub mod snapshot;
 
 pub use event_publisher::*;
 pub use event_store::*;
+pub use offline_sync_ledger::*;
 pub use snapshot::*;
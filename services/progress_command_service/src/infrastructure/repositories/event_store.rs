@@ -2,11 +2,12 @@
 
 use async_trait::async_trait;
 use sqlx::PgPool;
+use uuid::Uuid;
 
 use crate::{
-    domain::events::ProgressEvent,
+    domain::{CollectionEvent, collection_stream_id, events::ProgressEvent},
     error::{Error, Result},
-    ports::outbound::EventStorePort,
+    ports::outbound::{CollectionEventStorePort, EventStorePort},
 };
 
 /// PostgreSQL イベントストア
@@ -48,7 +49,7 @@ impl EventStorePort for PostgresEventStore {
             event_type,
             event_data,
             stream_id,
-            chrono::Utc::now()
+            event.timestamp()
         )
         .fetch_one(&self.pool)
         .await
@@ -110,3 +111,55 @@ impl EventStorePort for PostgresEventStore {
         Ok(events)
     }
 }
+
+#[async_trait]
+impl CollectionEventStorePort for PostgresEventStore {
+    async fn save_collection_event(&self, event: &CollectionEvent) -> Result<i64> {
+        let event_data =
+            serde_json::to_value(event).map_err(|e| Error::Serialization(e.to_string()))?;
+        let stream_id = event.stream_id();
+
+        let event_version = sqlx::query_scalar!(
+            r#"
+            INSERT INTO events (event_id, stream_id, event_type, event_data, event_version, occurred_at)
+            VALUES ($1, $2, $3, $4,
+                (SELECT COALESCE(MAX(event_version), 0) + 1 FROM events WHERE stream_id = $5),
+                $6)
+            RETURNING event_version
+            "#,
+            Uuid::new_v4(),
+            stream_id.clone(),
+            event.event_type(),
+            event_data,
+            stream_id,
+            event.timestamp(),
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(event_version)
+    }
+
+    async fn get_collection_events(&self, collection_id: Uuid) -> Result<Vec<CollectionEvent>> {
+        let records = sqlx::query_scalar!(
+            r#"
+            SELECT event_data
+            FROM events
+            WHERE stream_id = $1
+            ORDER BY event_version
+            "#,
+            collection_stream_id(collection_id),
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        records
+            .into_iter()
+            .map(|data| {
+                serde_json::from_value(data).map_err(|e| Error::Serialization(e.to_string()))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,108 @@
+//! オフライン同期の適用記録の実装
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    domain::AppliedOfflineEvent,
+    error::{Error, Result},
+    ports::outbound::OfflineSyncLedgerPort,
+};
+
+/// PostgreSQL のオフライン同期の適用記録
+pub struct PostgresOfflineSyncLedger {
+    pool: PgPool,
+}
+
+impl PostgresOfflineSyncLedger {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn from_row(row: &PgRow) -> Result<AppliedOfflineEvent> {
+    Ok(AppliedOfflineEvent {
+        client_event_id:  row.try_get("client_event_id")?,
+        user_id:          row.try_get("user_id")?,
+        device_id:        row.try_get("device_id")?,
+        event_type:       row.try_get("event_type")?,
+        client_timestamp: row.try_get("client_timestamp")?,
+        server_timestamp: row.try_get("server_timestamp")?,
+        clock_offset_ms:  row.try_get("clock_offset_ms")?,
+    })
+}
+
+#[async_trait]
+impl OfflineSyncLedgerPort for PostgresOfflineSyncLedger {
+    async fn find_applied(
+        &self,
+        user_id: Uuid,
+        client_event_ids: &[Uuid],
+    ) -> Result<Vec<AppliedOfflineEvent>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT client_event_id, user_id, device_id, event_type,
+                   client_timestamp, server_timestamp, clock_offset_ms
+            FROM offline_sync_events
+            WHERE user_id = $1 AND client_event_id = ANY($2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(client_event_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        rows.iter().map(from_row).collect()
+    }
+
+    async fn applied_since(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AppliedOfflineEvent>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT client_event_id, user_id, device_id, event_type,
+                   client_timestamp, server_timestamp, clock_offset_ms
+            FROM offline_sync_events
+            WHERE user_id = $1 AND server_timestamp >= $2
+            ORDER BY server_timestamp
+            "#,
+        )
+        .bind(user_id)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        rows.iter().map(from_row).collect()
+    }
+
+    async fn record_applied(&self, event: &AppliedOfflineEvent) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO offline_sync_events (
+                client_event_id, user_id, device_id, event_type,
+                client_timestamp, server_timestamp, clock_offset_ms
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (user_id, client_event_id) DO NOTHING
+            "#,
+        )
+        .bind(event.client_event_id)
+        .bind(event.user_id)
+        .bind(&event.device_id)
+        .bind(&event.event_type)
+        .bind(event.client_timestamp)
+        .bind(event.server_timestamp)
+        .bind(event.clock_offset_ms)
+        .execute(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(())
+    }
+}
@@ -1,11 +1,6 @@
+use progress_command_service::{config, error::Result, server};
 use tracing::info;
 
-mod config;
-mod error;
-mod server;
-
-use error::Result;
-
 #[tokio::main]
 async fn main() -> Result<()> {
     // ロギング初期化
@@ -14,7 +9,6 @@ async fn main() -> Result<()> {
     info!("===========================================");
     info!("Progress Command Service - 起動中");
     info!("責務: イベント受信と Event Store への永続化");
-    info!("状態: 未実装（設計済み）");
     info!("===========================================");
     info!("");
     info!("純粋な CQRS/Event Sourcing の Write 側");
@@ -22,6 +16,8 @@ async fn main() -> Result<()> {
     info!("- Event Store への永続化");
     info!("- イベント順序保証");
     info!("- Pub/Sub へのイベント配信");
+    info!("- オフラインセッションの同期（POST /offline-sessions）");
+    info!("- 共有コレクションの構成とメンバーの記録（/collections）");
     info!("");
     info!("詳細: docs/tactical/contexts/progress/");
     info!("===========================================");
@@ -1,6 +1,7 @@
 //! インバウンドポート
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::{domain::commands::ProgressCommand, error::Result};
@@ -9,6 +10,9 @@ use crate::{domain::commands::ProgressCommand, error::Result};
 #[async_trait]
 pub trait CommandHandler: Send + Sync {
     async fn handle(&self, command: ProgressCommand) -> Result<()>;
+
+    /// 発生日時を指定してコマンドを処理する（オフラインで記録したイベントの取り込み用）
+    async fn handle_at(&self, command: ProgressCommand, occurred_at: DateTime<Utc>) -> Result<()>;
 }
 
 /// 進捗照会ポート
@@ -1,11 +1,12 @@
 //! アウトバウンドポート
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
-    domain::{Progress, events::ProgressEvent},
+    domain::{AppliedOfflineEvent, CollectionEvent, Progress, events::ProgressEvent},
     error::Result,
 };
 
@@ -21,6 +22,14 @@ pub trait EventStorePort: Send + Sync {
     ) -> Result<Vec<ProgressEvent>>;
 }
 
+/// 共有コレクションのイベントの保存先（Progress
+/// のイベントストアの別ストリーム）
+#[async_trait]
+pub trait CollectionEventStorePort: Send + Sync {
+    async fn save_collection_event(&self, event: &CollectionEvent) -> Result<i64>;
+    async fn get_collection_events(&self, collection_id: Uuid) -> Result<Vec<CollectionEvent>>;
+}
+
 /// スナップショットストアポート
 #[async_trait]
 pub trait SnapshotStorePort: Send + Sync {
@@ -34,6 +43,26 @@ pub trait EventPublisherPort: Send + Sync {
     async fn publish(&self, event: ProgressEvent) -> Result<()>;
 }
 
+/// オフライン同期で適用したイベントの記録先
+#[async_trait]
+pub trait OfflineSyncLedgerPort: Send + Sync {
+    /// 指定したクライアントイベント ID のうち適用済みのもの
+    async fn find_applied(
+        &self,
+        user_id: Uuid,
+        client_event_ids: &[Uuid],
+    ) -> Result<Vec<AppliedOfflineEvent>>;
+
+    /// サーバーの時刻で `since` 以降に発生したことにした適用済みのイベント
+    async fn applied_since(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AppliedOfflineEvent>>;
+
+    async fn record_applied(&self, event: &AppliedOfflineEvent) -> Result<()>;
+}
+
 /// イベントメタデータ
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventMetadata {
@@ -1,16 +1,72 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 
-use axum::{Json, Router, routing::get};
+use axum::{
+    Json,
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use tracing::info;
+use sqlx::postgres::PgPoolOptions;
+use tracing::{error, info};
+use uuid::Uuid;
 
-use crate::config::Config;
+use crate::{
+    application::{CollectionService, OfflineSyncService, ProgressCommandHandler},
+    config::Config,
+    domain::{CollectionCommand, OfflineEventOutcome, OfflineSessionBatch, OfflineSyncReport},
+    error::Error,
+    infrastructure::{
+        PostgresEventStore,
+        PostgresOfflineSyncLedger,
+        PostgresSnapshotStore,
+        RedisEventPublisher,
+    },
+};
+
+/// リクエストしたユーザーを識別するヘッダー（API Gateway が付与する）
+const USER_ID_HEADER: &str = "x-user-id";
+
+#[derive(Clone)]
+struct AppState {
+    offline_sync: Arc<OfflineSyncService>,
+    collections:  Arc<CollectionService>,
+}
 
 pub async fn run(config: Config) -> crate::error::Result<()> {
-    // ルーター構築
-    let app = Router::new()
-        .route("/health", get(health_check))
-        .route("/", get(index));
+    // Event Store への接続
+    let pool = PgPoolOptions::new().connect(&config.database.url).await?;
+    crate::MIGRATOR
+        .run(&pool)
+        .await
+        .map_err(|e| Error::Database(e.into()))?;
+
+    // イベント配信用の Redis 接続
+    let redis = redis::Client::open(config.redis.url.as_str())
+        .map_err(|e| Error::PubSub(e.to_string()))?
+        .get_connection_manager()
+        .await
+        .map_err(|e| Error::PubSub(e.to_string()))?;
+
+    let event_store = Arc::new(PostgresEventStore::new(pool.clone()));
+    let command_handler = Arc::new(ProgressCommandHandler::new(
+        event_store.clone(),
+        Arc::new(PostgresSnapshotStore::new(pool.clone())),
+        Arc::new(RedisEventPublisher::new(redis)),
+    ));
+    let offline_sync = OfflineSyncService::new(
+        command_handler,
+        event_store.clone(),
+        Arc::new(PostgresOfflineSyncLedger::new(pool)),
+    )
+    .with_offline_window(Duration::days(config.offline_sync.window_days));
+    let collections = CollectionService::new(event_store);
+
+    let app = build_router(Arc::new(offline_sync), Arc::new(collections));
 
     // サーバーアドレス
     let addr = SocketAddr::from(([0, 0, 0, 0], config.server.port));
@@ -23,11 +79,266 @@ pub async fn run(config: Config) -> crate::error::Result<()> {
     Ok(())
 }
 
+/// コマンドサービスからルーターを組み立てる
+pub fn build_router(
+    offline_sync: Arc<OfflineSyncService>,
+    collections: Arc<CollectionService>,
+) -> Router {
+    Router::new()
+        .route("/offline-sessions", post(submit_offline_session))
+        .route(
+            "/collections/{collection_id}/members",
+            post(join_collection).delete(leave_collection),
+        )
+        .route(
+            "/collections/{collection_id}/items",
+            post(add_collection_item),
+        )
+        .route(
+            "/collections/{collection_id}/items/{vocabulary_item_id}",
+            delete(remove_collection_item),
+        )
+        .route("/collection-stats/visibility", put(set_stats_visibility))
+        .route("/health", get(health_check))
+        .route("/", get(index))
+        .with_state(AppState {
+            offline_sync,
+            collections,
+        })
+}
+
+/// API Gateway が付与したリクエストのユーザー
+fn requesting_user(headers: &HeaderMap) -> Option<Uuid> {
+    headers
+        .get(USER_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+/// オフラインで記録したセッションを取り込む（SubmitOfflineSession）
+///
+/// 取り込めなかったイベントがあってもバッチは処理済みとして 200 を返し、
+/// イベントごとの結果をレスポンスに含める
+async fn submit_offline_session(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(batch): Json<OfflineSessionBatch>,
+) -> Response {
+    if requesting_user(&headers) != Some(batch.user_id) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": "Offline sessions can only be submitted by their owner" })),
+        )
+            .into_response();
+    }
+
+    match state.offline_sync.submit(batch, Utc::now()).await {
+        Ok(report) => (StatusCode::OK, Json(OfflineSyncResponse::from(&report))).into_response(),
+        Err(e) => {
+            error!("Failed to submit offline session: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to submit offline session" })),
+            )
+                .into_response()
+        },
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AddCollectionItemBody {
+    vocabulary_item_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatsVisibilityBody {
+    visible: bool,
+}
+
+/// 共有コレクションに参加する
+async fn join_collection(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(collection_id): Path<Uuid>,
+) -> Response {
+    execute_collection_command(&state, &headers, collection_id, CollectionCommand::Join).await
+}
+
+/// 共有コレクションから抜ける
+async fn leave_collection(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(collection_id): Path<Uuid>,
+) -> Response {
+    execute_collection_command(&state, &headers, collection_id, CollectionCommand::Leave).await
+}
+
+/// 共有コレクションにアイテムを追加する（メンバーのみ）
+async fn add_collection_item(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(collection_id): Path<Uuid>,
+    Json(body): Json<AddCollectionItemBody>,
+) -> Response {
+    let command = CollectionCommand::AddItem {
+        vocabulary_item_id: body.vocabulary_item_id,
+    };
+    execute_collection_command(&state, &headers, collection_id, command).await
+}
+
+/// 共有コレクションからアイテムを削除する（メンバーのみ）
+async fn remove_collection_item(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((collection_id, vocabulary_item_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    let command = CollectionCommand::RemoveItem { vocabulary_item_id };
+    execute_collection_command(&state, &headers, collection_id, command).await
+}
+
+/// 共有コレクションのコマンドを実行し、記録したイベントを返す
+///
+/// 構成が変わらなかった場合は `event` を `null` にする
+async fn execute_collection_command(
+    state: &AppState,
+    headers: &HeaderMap,
+    collection_id: Uuid,
+    command: CollectionCommand,
+) -> Response {
+    let Some(user_id) = requesting_user(headers) else {
+        return missing_user().into_response();
+    };
+
+    match state
+        .collections
+        .execute(collection_id, user_id, command, Utc::now())
+        .await
+    {
+        Ok(event) => (StatusCode::OK, Json(json!({ "event": event }))).into_response(),
+        Err(Error::Collection(e)) => (
+            StatusCode::FORBIDDEN,
+            Json(json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+        Err(e) => {
+            error!("Failed to execute collection command: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to execute collection command" })),
+            )
+                .into_response()
+        },
+    }
+}
+
+/// 共有コレクションのメンバー別統計を他のメンバーに公開するかを変更する
+async fn set_stats_visibility(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<StatsVisibilityBody>,
+) -> Response {
+    let Some(user_id) = requesting_user(&headers) else {
+        return missing_user().into_response();
+    };
+
+    match state
+        .collections
+        .set_stats_visibility(user_id, body.visible, Utc::now())
+        .await
+    {
+        Ok(event) => (StatusCode::OK, Json(json!({ "event": event }))).into_response(),
+        Err(e) => {
+            error!("Failed to change collection stats visibility: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to change collection stats visibility" })),
+            )
+                .into_response()
+        },
+    }
+}
+
+fn missing_user() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({ "error": format!("Missing {USER_ID_HEADER} header") })),
+    )
+}
+
+/// バッチの取り込み結果のレスポンス
+#[derive(Debug, Serialize)]
+struct OfflineSyncResponse {
+    user_id:         Uuid,
+    device_id:       String,
+    /// 端末の時計からサーバーの時刻への補正量（ミリ秒）
+    clock_offset_ms: i64,
+    applied:         usize,
+    deduplicated:    usize,
+    rejected:        usize,
+    events:          Vec<OfflineEventResponse>,
+}
+
+/// イベントごとの取り込み結果のレスポンス
+#[derive(Debug, Serialize)]
+struct OfflineEventResponse {
+    client_event_id:  Uuid,
+    /// `applied`・`deduplicated`・`rejected` のいずれか
+    status:           &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    server_timestamp: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rejection:        Option<RejectionResponse>,
+}
+
+#[derive(Debug, Serialize)]
+struct RejectionResponse {
+    code:    &'static str,
+    message: String,
+}
+
+impl From<&OfflineSyncReport> for OfflineSyncResponse {
+    fn from(report: &OfflineSyncReport) -> Self {
+        Self {
+            user_id:         report.user_id,
+            device_id:       report.device_id.clone(),
+            clock_offset_ms: report.clock_offset.num_milliseconds(),
+            applied:         report.applied_count(),
+            deduplicated:    report.deduplicated_count(),
+            rejected:        report.rejected_count(),
+            events:          report
+                .events
+                .iter()
+                .map(|event| {
+                    let (status, server_timestamp, rejection) = match &event.outcome {
+                        OfflineEventOutcome::Applied { server_timestamp } => {
+                            ("applied", Some(*server_timestamp), None)
+                        },
+                        OfflineEventOutcome::Deduplicated => ("deduplicated", None, None),
+                        OfflineEventOutcome::Rejected(reason) => (
+                            "rejected",
+                            None,
+                            Some(RejectionResponse {
+                                code:    reason.code(),
+                                message: reason.to_string(),
+                            }),
+                        ),
+                    };
+                    OfflineEventResponse {
+                        client_event_id: event.client_event_id,
+                        status,
+                        server_timestamp,
+                        rejection,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
 async fn health_check() -> Json<serde_json::Value> {
     Json(json!({
         "status": "healthy",
-        "service": "progress_command_service",
-        "implementation": "pending"
+        "service": "progress_command_service"
     }))
 }
 
@@ -35,9 +346,80 @@ async fn index() -> Json<serde_json::Value> {
     Json(json!({
         "service": "Progress Command Service",
         "version": "0.1.0",
-        "status": "未実装",
         "responsibility": "イベント受信と永続化",
         "description": "純粋な CQRS/Event Sourcing の Write 側",
+        "endpoints": {
+            "offline_sessions": "POST /offline-sessions",
+            "collection_members": "POST|DELETE /collections/{collection_id}/members",
+            "collection_items": "POST /collections/{collection_id}/items, DELETE /collections/{collection_id}/items/{vocabulary_item_id}",
+            "collection_stats_visibility": "PUT /collection-stats/visibility"
+        },
         "documentation": "docs/tactical/contexts/progress/architecture.md"
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::domain::{OfflineEventReport, RejectionReason};
+
+    #[test]
+    fn test_response_reports_each_event_outcome() {
+        let server_timestamp = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let (applied, deduplicated, rejected) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let report = OfflineSyncReport {
+            user_id:      Uuid::new_v4(),
+            device_id:    "device-1".to_string(),
+            clock_offset: Duration::seconds(-90),
+            events:       vec![
+                OfflineEventReport {
+                    client_event_id: applied,
+                    outcome:         OfflineEventOutcome::Applied { server_timestamp },
+                },
+                OfflineEventReport {
+                    client_event_id: deduplicated,
+                    outcome:         OfflineEventOutcome::Deduplicated,
+                },
+                OfflineEventReport {
+                    client_event_id: rejected,
+                    outcome:         OfflineEventOutcome::Rejected(
+                        RejectionReason::DuplicateInBatch,
+                    ),
+                },
+            ],
+        };
+
+        let response = serde_json::to_value(OfflineSyncResponse::from(&report)).unwrap();
+
+        assert_eq!(response["clock_offset_ms"], -90_000);
+        assert_eq!(
+            (
+                &response["applied"],
+                &response["deduplicated"],
+                &response["rejected"]
+            ),
+            (&json!(1), &json!(1), &json!(1))
+        );
+        assert_eq!(
+            response["events"],
+            json!([
+                {
+                    "client_event_id": applied,
+                    "status": "applied",
+                    "server_timestamp": server_timestamp,
+                },
+                { "client_event_id": deduplicated, "status": "deduplicated" },
+                {
+                    "client_event_id": rejected,
+                    "status": "rejected",
+                    "rejection": {
+                        "code": "duplicate_in_batch",
+                        "message": "Client event id appears more than once in the batch",
+                    },
+                },
+            ])
+        );
+    }
+}
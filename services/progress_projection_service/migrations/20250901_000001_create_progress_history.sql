@@ -0,0 +1,29 @@
+-- 進捗統計の履歴（as-of クエリ用）
+-- 各行はその日の終わり時点の値。90日より古い行は週ごとに最後の行だけを残す
+
+-- 習熟度ごとの語彙アイテム数の履歴
+CREATE TABLE IF NOT EXISTS item_mastery_history (
+    user_id UUID NOT NULL,
+    snapshot_date DATE NOT NULL,
+    beginner_count INTEGER NOT NULL DEFAULT 0,
+    learning_count INTEGER NOT NULL DEFAULT 0,
+    familiar_count INTEGER NOT NULL DEFAULT 0,
+    proficient_count INTEGER NOT NULL DEFAULT 0,
+    mastered_count INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, snapshot_date)
+);
+
+-- ストリークの履歴
+CREATE TABLE IF NOT EXISTS streak_history (
+    user_id UUID NOT NULL,
+    snapshot_date DATE NOT NULL,
+    current_streak_days INTEGER NOT NULL DEFAULT 0,
+    longest_streak_days INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, snapshot_date)
+);
+
+-- 間引き対象の検索用
+CREATE INDEX idx_item_mastery_history_date ON item_mastery_history (snapshot_date);
+CREATE INDEX idx_streak_history_date ON streak_history (snapshot_date);
@@ -0,0 +1,7 @@
+-- 語彙アイテムを初めて習得（Mastered）した日時
+-- 週次レポートの「その週に新しく習得した語彙」の抽出に使う
+ALTER TABLE vocabulary_item_progress ADD COLUMN IF NOT EXISTS mastered_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_vocabulary_item_progress_mastered
+    ON vocabulary_item_progress (user_id, mastered_at)
+    WHERE mastered_at IS NOT NULL;
@@ -0,0 +1,41 @@
+-- 共有コレクションのメンバー別学習統計
+
+-- コレクションに含まれる語彙アイテム
+CREATE TABLE IF NOT EXISTS collection_items (
+    collection_id UUID NOT NULL,
+    vocabulary_item_id UUID NOT NULL,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (collection_id, vocabulary_item_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_collection_items_item
+    ON collection_items (vocabulary_item_id);
+
+-- コレクションのメンバー
+CREATE TABLE IF NOT EXISTS collection_members (
+    collection_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    joined_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (collection_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_collection_members_user
+    ON collection_members (user_id);
+
+-- 統計を他のメンバーに公開しないユーザー（本人には常に表示する）
+CREATE TABLE IF NOT EXISTS collection_stats_privacy (
+    user_id UUID PRIMARY KEY,
+    hidden BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- (コレクション, メンバー) ごとの統計
+CREATE TABLE IF NOT EXISTS collection_member_stats (
+    collection_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    items_studied INTEGER NOT NULL DEFAULT 0,
+    items_mastered INTEGER NOT NULL DEFAULT 0,
+    last_activity_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (collection_id, user_id)
+);
@@ -0,0 +1,19 @@
+-- 学習する時間帯の推定と復習リマインダー
+
+-- セッションの開始時刻の分布（UTC の週の中の15分枠、月曜 0:00 = 0）
+CREATE TABLE IF NOT EXISTS study_time_histogram (
+    user_id UUID NOT NULL,
+    quarter_of_week INTEGER NOT NULL CHECK (quarter_of_week >= 0 AND quarter_of_week < 672),
+    sessions INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, quarter_of_week)
+);
+
+-- 要求済みの復習リマインダー（ユーザーのローカルの日付ごとに1件）
+CREATE TABLE IF NOT EXISTS review_reminders (
+    user_id UUID NOT NULL,
+    reminder_date DATE NOT NULL,
+    scheduled_for TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, reminder_date)
+);
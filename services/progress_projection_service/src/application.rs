@@ -1,9 +1,19 @@
 //! アプリケーション層
 
+pub mod collection_stats;
 pub mod event_handlers;
+pub mod history_compaction;
+pub mod projection;
 pub mod projection_manager;
-pub mod services;
+pub mod review_reminder;
+pub mod study_time;
+pub mod weekly_report;
 
+pub use collection_stats::*;
 pub use event_handlers::*;
+pub use history_compaction::*;
+pub use projection::*;
 pub use projection_manager::*;
-pub use services::*;
+pub use review_reminder::*;
+pub use study_time::*;
+pub use weekly_report::*;
@@ -0,0 +1,538 @@
+//! 共有コレクションのメンバー別統計のプロジェクション
+//!
+//! コレクションの構成・メンバーのイベントと学習イベントを突き合わせ、
+//! `(コレクション, メンバー)` の組ごとに統計を保持する。
+//! 統計は語彙アイテム進捗の Read Model から組単位で計算し直すため、
+//! 学習イベントは [`ProgressEventHandler`](super::ProgressEventHandler)
+//! が進捗を更新した後に渡すこと。
+//!
+//! コレクションのイベントは Progress Command Service が進捗と同じ
+//! Event Store に記録する。ペイロード:
+//!
+//! - `CollectionItemAdded` / `CollectionItemRemoved`: `collection_id`,
+//!   `vocabulary_item_id`
+//! - `CollectionMemberJoined` / `CollectionMemberLeft`: `collection_id`,
+//!   `user_id`
+//! - `CollectionStatsVisibilityChanged`: `user_id`, `visible`
+
+use std::{collections::HashSet, sync::Arc};
+
+use serde::de::DeserializeOwned;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::{
+    domain::compute_member_stats,
+    error::{Error, Result},
+    ports::outbound::{CollectionStatsRepository, Event},
+};
+
+/// 共有コレクションの統計プロジェクション
+pub struct CollectionStatsProjector {
+    repository: Arc<dyn CollectionStatsRepository>,
+}
+
+impl CollectionStatsProjector {
+    pub fn new(repository: Arc<dyn CollectionStatsRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// イベントを処理
+    pub async fn handle_event(&self, event: &Event) -> Result<()> {
+        match event.event_type.as_str() {
+            "CollectionItemAdded" => {
+                let collection_id = field(event, "collection_id")?;
+                let item_id = field(event, "vocabulary_item_id")?;
+                self.repository
+                    .add_collection_item(collection_id, item_id)
+                    .await?;
+                self.rebuild_for_item(collection_id, item_id).await
+            },
+            "CollectionItemRemoved" => {
+                let collection_id = field(event, "collection_id")?;
+                let item_id = field(event, "vocabulary_item_id")?;
+                self.repository
+                    .remove_collection_item(collection_id, item_id)
+                    .await?;
+                self.rebuild_for_item(collection_id, item_id).await
+            },
+            "CollectionMemberJoined" => {
+                let collection_id = field(event, "collection_id")?;
+                let user_id = field(event, "user_id")?;
+                self.repository
+                    .add_member(collection_id, user_id, event.occurred_at)
+                    .await?;
+                // 参加前の学習も統計に含める
+                self.rebuild_pairs(collection_id, &[user_id]).await
+            },
+            "CollectionMemberLeft" => {
+                self.repository
+                    .remove_member(field(event, "collection_id")?, field(event, "user_id")?)
+                    .await
+            },
+            "CollectionStatsVisibilityChanged" => {
+                self.repository
+                    .set_stats_visibility(field(event, "user_id")?, field(event, "visible")?)
+                    .await
+            },
+            "ItemCompleted" => {
+                let user_id = field(event, "user_id")?;
+                let item_id = field(event, "vocabulary_item_id")?;
+                let collections = self
+                    .repository
+                    .get_member_collections_with_item(user_id, item_id)
+                    .await?;
+                for collection_id in collections {
+                    self.rebuild_pairs(collection_id, &[user_id]).await?;
+                }
+                Ok(())
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// 指定した組の統計を計算し直す
+    ///
+    /// コレクションの構成が変わった場合や、Read Model を部分的に
+    /// 修復する場合に、影響のある組だけを対象に使う
+    pub async fn rebuild_pairs(&self, collection_id: Uuid, user_ids: &[Uuid]) -> Result<()> {
+        let item_ids = self.repository.get_collection_items(collection_id).await?;
+        let items: HashSet<Uuid> = item_ids.iter().copied().collect();
+
+        for &user_id in user_ids {
+            let progress = self
+                .repository
+                .get_item_progress_for_items(user_id, &item_ids)
+                .await?;
+            let stats = compute_member_stats(collection_id, user_id, &items, &progress);
+            self.repository.save_member_stats(&stats).await?;
+        }
+
+        debug!(
+            collection_id = %collection_id,
+            pairs = user_ids.len(),
+            "Rebuilt collection member stats"
+        );
+        Ok(())
+    }
+
+    /// アイテムの追加・削除で統計が変わるメンバー（そのアイテムを学習したメンバー）だけを計算し直す
+    async fn rebuild_for_item(&self, collection_id: Uuid, item_id: Uuid) -> Result<()> {
+        let members = self
+            .repository
+            .get_members_with_item_progress(collection_id, item_id)
+            .await?;
+        self.rebuild_pairs(collection_id, &members).await
+    }
+}
+
+/// イベントデータのフィールドを読み取る
+pub(super) fn field<T: DeserializeOwned>(event: &Event, name: &str) -> Result<T> {
+    let value = event.event_data.get(name).ok_or_else(|| {
+        Error::Parse(format!(
+            "{} event {} is missing field {name}",
+            event.event_type, event.event_id
+        ))
+    })?;
+    Ok(serde_json::from_value(value.clone())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeSet, HashMap},
+        sync::Mutex,
+    };
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use serde_json::json;
+    use shared_progress_context::collection::CollectionMemberStats;
+
+    use super::*;
+    use crate::domain::VocabularyItemProgress;
+
+    #[derive(Default)]
+    struct State {
+        items:    HashMap<Uuid, BTreeSet<Uuid>>,
+        members:  HashMap<Uuid, BTreeSet<Uuid>>,
+        hidden:   HashSet<Uuid>,
+        progress: Vec<VocabularyItemProgress>,
+        stats:    HashMap<(Uuid, Uuid), CollectionMemberStats>,
+        /// 統計を保存した組（再計算の対象の確認用）
+        saved:    Vec<(Uuid, Uuid)>,
+    }
+
+    #[derive(Default)]
+    struct InMemoryCollections {
+        state: Mutex<State>,
+    }
+
+    impl InMemoryCollections {
+        fn with<R>(&self, f: impl FnOnce(&mut State) -> R) -> R {
+            f(&mut self.state.lock().unwrap())
+        }
+
+        fn stats(&self, collection_id: Uuid, user_id: Uuid) -> Option<CollectionMemberStats> {
+            self.with(|state| state.stats.get(&(collection_id, user_id)).cloned())
+        }
+
+        fn take_saved(&self) -> Vec<(Uuid, Uuid)> {
+            self.with(|state| std::mem::take(&mut state.saved))
+        }
+    }
+
+    #[async_trait]
+    impl CollectionStatsRepository for InMemoryCollections {
+        async fn add_collection_item(&self, collection_id: Uuid, item_id: Uuid) -> Result<()> {
+            self.with(|state| {
+                state
+                    .items
+                    .entry(collection_id)
+                    .or_default()
+                    .insert(item_id);
+            });
+            Ok(())
+        }
+
+        async fn remove_collection_item(&self, collection_id: Uuid, item_id: Uuid) -> Result<()> {
+            self.with(|state| {
+                state
+                    .items
+                    .entry(collection_id)
+                    .or_default()
+                    .remove(&item_id);
+            });
+            Ok(())
+        }
+
+        async fn get_collection_items(&self, collection_id: Uuid) -> Result<Vec<Uuid>> {
+            Ok(self.with(|state| {
+                state
+                    .items
+                    .get(&collection_id)
+                    .map(|items| items.iter().copied().collect())
+                    .unwrap_or_default()
+            }))
+        }
+
+        async fn add_member(
+            &self,
+            collection_id: Uuid,
+            user_id: Uuid,
+            _joined_at: DateTime<Utc>,
+        ) -> Result<()> {
+            self.with(|state| {
+                state
+                    .members
+                    .entry(collection_id)
+                    .or_default()
+                    .insert(user_id);
+            });
+            Ok(())
+        }
+
+        async fn remove_member(&self, collection_id: Uuid, user_id: Uuid) -> Result<()> {
+            self.with(|state| {
+                state
+                    .members
+                    .entry(collection_id)
+                    .or_default()
+                    .remove(&user_id);
+                state.stats.remove(&(collection_id, user_id));
+            });
+            Ok(())
+        }
+
+        async fn set_stats_visibility(&self, user_id: Uuid, visible: bool) -> Result<()> {
+            self.with(|state| {
+                if visible {
+                    state.hidden.remove(&user_id);
+                } else {
+                    state.hidden.insert(user_id);
+                }
+            });
+            Ok(())
+        }
+
+        async fn get_member_collections_with_item(
+            &self,
+            user_id: Uuid,
+            item_id: Uuid,
+        ) -> Result<Vec<Uuid>> {
+            Ok(self.with(|state| {
+                state
+                    .members
+                    .iter()
+                    .filter(|(collection_id, members)| {
+                        members.contains(&user_id)
+                            && state
+                                .items
+                                .get(collection_id)
+                                .is_some_and(|items| items.contains(&item_id))
+                    })
+                    .map(|(collection_id, _)| *collection_id)
+                    .collect()
+            }))
+        }
+
+        async fn get_members_with_item_progress(
+            &self,
+            collection_id: Uuid,
+            item_id: Uuid,
+        ) -> Result<Vec<Uuid>> {
+            Ok(self.with(|state| {
+                state
+                    .members
+                    .get(&collection_id)
+                    .into_iter()
+                    .flatten()
+                    .filter(|user_id| {
+                        state.progress.iter().any(|progress| {
+                            progress.user_id == **user_id && progress.vocabulary_item_id == item_id
+                        })
+                    })
+                    .copied()
+                    .collect()
+            }))
+        }
+
+        async fn get_item_progress_for_items(
+            &self,
+            user_id: Uuid,
+            item_ids: &[Uuid],
+        ) -> Result<Vec<VocabularyItemProgress>> {
+            Ok(self.with(|state| {
+                state
+                    .progress
+                    .iter()
+                    .filter(|progress| {
+                        progress.user_id == user_id
+                            && item_ids.contains(&progress.vocabulary_item_id)
+                    })
+                    .cloned()
+                    .collect()
+            }))
+        }
+
+        async fn save_member_stats(&self, stats: &CollectionMemberStats) -> Result<()> {
+            self.with(|state| {
+                let key = (stats.collection_id, stats.user_id);
+                state.saved.push(key);
+                state.stats.insert(key, stats.clone());
+            });
+            Ok(())
+        }
+    }
+
+    fn event(event_type: &str, data: serde_json::Value) -> Event {
+        Event {
+            event_id:      Uuid::new_v4(),
+            stream_id:     "collection".to_string(),
+            event_type:    event_type.to_string(),
+            event_data:    data,
+            event_version: 1,
+            position:      1,
+            occurred_at:   "2025-01-10T12:00:00Z".parse().unwrap(),
+        }
+    }
+
+    fn item_event(event_type: &str, collection_id: Uuid, item_id: Uuid) -> Event {
+        event(
+            event_type,
+            json!({ "collection_id": collection_id, "vocabulary_item_id": item_id }),
+        )
+    }
+
+    fn member_event(event_type: &str, collection_id: Uuid, user_id: Uuid) -> Event {
+        event(
+            event_type,
+            json!({ "collection_id": collection_id, "user_id": user_id }),
+        )
+    }
+
+    fn progress(
+        user_id: Uuid,
+        item_id: Uuid,
+        mastery_level: i32,
+        at: &str,
+    ) -> VocabularyItemProgress {
+        VocabularyItemProgress {
+            user_id,
+            vocabulary_item_id: item_id,
+            attempts_count: 10,
+            correct_count: 10,
+            last_attempt_date: at.parse().unwrap(),
+            last_accuracy: 1.0,
+            average_accuracy: 1.0,
+            mastery_level,
+            time_spent_seconds: 60,
+            mastered_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_item_completed_updates_only_collections_of_the_member() {
+        let repository = Arc::new(InMemoryCollections::default());
+        let projector = CollectionStatsProjector::new(repository.clone());
+        let (shared, other, unjoined) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let user = Uuid::new_v4();
+        let item = Uuid::new_v4();
+
+        for collection in [shared, other, unjoined] {
+            projector
+                .handle_event(&item_event("CollectionItemAdded", collection, item))
+                .await
+                .unwrap();
+        }
+        for collection in [shared, other] {
+            projector
+                .handle_event(&member_event("CollectionMemberJoined", collection, user))
+                .await
+                .unwrap();
+        }
+        repository.take_saved();
+
+        // 進捗は ProgressEventHandler が先に更新している
+        repository.with(|state| {
+            state
+                .progress
+                .push(progress(user, item, 4, "2025-01-10T12:00:00Z"))
+        });
+        projector
+            .handle_event(&event(
+                "ItemCompleted",
+                json!({
+                    "user_id": user,
+                    "vocabulary_item_id": item,
+                    "accuracy": 1.0,
+                    "time_spent": 30,
+                }),
+            ))
+            .await
+            .unwrap();
+
+        let mut saved = repository.take_saved();
+        saved.sort();
+        let mut expected = vec![(shared, user), (other, user)];
+        expected.sort();
+        assert_eq!(saved, expected);
+        assert_eq!(repository.stats(shared, user).unwrap().items_mastered, 1);
+        assert!(repository.stats(unjoined, user).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_item_added_or_removed_after_study_recomputes_affected_pairs() {
+        let repository = Arc::new(InMemoryCollections::default());
+        let projector = CollectionStatsProjector::new(repository.clone());
+        let collection = Uuid::new_v4();
+        let (studied, idle) = (Uuid::new_v4(), Uuid::new_v4());
+        let (first, later) = (Uuid::new_v4(), Uuid::new_v4());
+
+        projector
+            .handle_event(&item_event("CollectionItemAdded", collection, first))
+            .await
+            .unwrap();
+        for user in [studied, idle] {
+            projector
+                .handle_event(&member_event("CollectionMemberJoined", collection, user))
+                .await
+                .unwrap();
+        }
+        // コレクションに追加される前に学習していた
+        repository.with(|state| {
+            state.progress.extend([
+                progress(studied, first, 2, "2025-01-05T10:00:00Z"),
+                progress(studied, later, 4, "2025-01-08T10:00:00Z"),
+            ])
+        });
+        projector
+            .rebuild_pairs(collection, &[studied])
+            .await
+            .unwrap();
+        repository.take_saved();
+
+        projector
+            .handle_event(&item_event("CollectionItemAdded", collection, later))
+            .await
+            .unwrap();
+
+        // 追加したアイテムを学習していないメンバーは計算し直さない
+        assert_eq!(repository.take_saved(), vec![(collection, studied)]);
+        assert_eq!(
+            repository.stats(collection, studied).unwrap(),
+            CollectionMemberStats {
+                collection_id:    collection,
+                user_id:          studied,
+                items_studied:    2,
+                items_mastered:   1,
+                last_activity_at: Some("2025-01-08T10:00:00Z".parse().unwrap()),
+            }
+        );
+
+        projector
+            .handle_event(&item_event("CollectionItemRemoved", collection, later))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repository.stats(collection, studied).unwrap(),
+            CollectionMemberStats {
+                collection_id:    collection,
+                user_id:          studied,
+                items_studied:    1,
+                items_mastered:   0,
+                last_activity_at: Some("2025-01-05T10:00:00Z".parse().unwrap()),
+            }
+        );
+        assert_eq!(
+            repository.stats(collection, idle).unwrap(),
+            CollectionMemberStats::empty(collection, idle)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_member_leaving_removes_stats_and_visibility_is_recorded() {
+        let repository = Arc::new(InMemoryCollections::default());
+        let projector = CollectionStatsProjector::new(repository.clone());
+        let collection = Uuid::new_v4();
+        let user = Uuid::new_v4();
+
+        projector
+            .handle_event(&member_event("CollectionMemberJoined", collection, user))
+            .await
+            .unwrap();
+        projector
+            .handle_event(&event(
+                "CollectionStatsVisibilityChanged",
+                json!({ "user_id": user, "visible": false }),
+            ))
+            .await
+            .unwrap();
+        projector
+            .handle_event(&member_event("CollectionMemberLeft", collection, user))
+            .await
+            .unwrap();
+
+        assert!(repository.stats(collection, user).is_none());
+        assert!(repository.with(|state| state.hidden.contains(&user)));
+    }
+
+    #[tokio::test]
+    async fn test_missing_field_is_rejected() {
+        let projector = CollectionStatsProjector::new(Arc::new(InMemoryCollections::default()));
+
+        let result = projector
+            .handle_event(&event(
+                "CollectionItemAdded",
+                json!({ "collection_id": Uuid::new_v4() }),
+            ))
+            .await;
+
+        assert!(
+            matches!(result, Err(Error::Parse(message)) if message.contains("vocabulary_item_id"))
+        );
+    }
+}
@@ -2,9 +2,10 @@
 
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use uuid::Uuid;
 
+use super::{CollectionStatsProjector, StudyTimeProjector};
 use crate::{
     domain::*,
     error::Result,
@@ -13,25 +14,52 @@ use crate::{
 
 /// Progress イベントハンドラー
 pub struct ProgressEventHandler {
-    repository: Arc<dyn ReadModelRepository>,
+    repository:       Arc<dyn ReadModelRepository>,
+    collection_stats: Option<Arc<CollectionStatsProjector>>,
+    study_time:       Option<Arc<StudyTimeProjector>>,
 }
 
 impl ProgressEventHandler {
     pub fn new(repository: Arc<dyn ReadModelRepository>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            collection_stats: None,
+            study_time: None,
+        }
+    }
+
+    /// 共有コレクションの統計も更新する
+    pub fn with_collection_stats(mut self, projector: Arc<CollectionStatsProjector>) -> Self {
+        self.collection_stats = Some(projector);
+        self
+    }
+
+    /// 学習する時間帯も集計する
+    pub fn with_study_time(mut self, projector: Arc<StudyTimeProjector>) -> Self {
+        self.study_time = Some(projector);
+        self
     }
 
     /// イベントを処理
     pub async fn handle_event(&self, event: &Event) -> Result<()> {
         match event.event_type.as_str() {
-            "LearningStarted" => self.handle_learning_started(event).await,
-            "ItemCompleted" => self.handle_item_completed(event).await,
-            "SessionCompleted" => self.handle_session_completed(event).await,
-            "StreakUpdated" => self.handle_streak_updated(event).await,
-            "AchievementUnlocked" => self.handle_achievement_unlocked(event).await,
-            "DailyGoalCompleted" => self.handle_daily_goal_completed(event).await,
-            _ => Ok(()), // 未知のイベントタイプは無視
+            "LearningStarted" => self.handle_learning_started(event).await?,
+            "ItemCompleted" => self.handle_item_completed(event).await?,
+            "SessionCompleted" => self.handle_session_completed(event).await?,
+            "StreakUpdated" => self.handle_streak_updated(event).await?,
+            "AchievementUnlocked" => self.handle_achievement_unlocked(event).await?,
+            "DailyGoalCompleted" => self.handle_daily_goal_completed(event).await?,
+            _ => {}, // 未知のイベントタイプは無視
+        }
+
+        // 語彙アイテム進捗を更新した後に集計する
+        if let Some(collection_stats) = &self.collection_stats {
+            collection_stats.handle_event(event).await?;
+        }
+        if let Some(study_time) = &self.study_time {
+            study_time.handle_event(event).await?;
         }
+        Ok(())
     }
 
     async fn handle_learning_started(&self, event: &Event) -> Result<()> {
@@ -75,23 +103,27 @@ impl ProgressEventHandler {
             serde_json::from_value(event.event_data.get("time_spent").unwrap().clone()).unwrap();
 
         // 語彙アイテム進捗を更新
-        let mut item_progress = self
+        let existing = self
             .repository
             .get_vocabulary_item_progress(user_id, vocabulary_item_id)
-            .await?
-            .unwrap_or_else(|| VocabularyItemProgress {
-                user_id,
-                vocabulary_item_id,
-                attempts_count: 0,
-                correct_count: 0,
-                last_attempt_date: event.occurred_at,
-                last_accuracy: 0.0,
-                average_accuracy: 0.0,
-                mastery_level: 0,
-                time_spent_seconds: 0,
-                created_at: Utc::now(),
-                updated_at: Utc::now(),
-            });
+            .await?;
+        let previous_level = existing
+            .as_ref()
+            .and_then(|progress| MasteryLevel::from_i32(progress.mastery_level));
+        let mut item_progress = existing.unwrap_or_else(|| VocabularyItemProgress {
+            user_id,
+            vocabulary_item_id,
+            attempts_count: 0,
+            correct_count: 0,
+            last_attempt_date: event.occurred_at,
+            last_accuracy: 0.0,
+            average_accuracy: 0.0,
+            mastery_level: 0,
+            time_spent_seconds: 0,
+            mastered_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        });
 
         item_progress.attempts_count += 1;
         if accuracy >= 0.8 {
@@ -103,16 +135,25 @@ impl ProgressEventHandler {
             (item_progress.average_accuracy * (item_progress.attempts_count - 1) as f32 + accuracy)
                 / item_progress.attempts_count as f32;
         item_progress.time_spent_seconds += time_spent;
-        item_progress.mastery_level = MasteryLevel::from_accuracy(
+        let mastery_level = MasteryLevel::from_accuracy(
             item_progress.average_accuracy,
             item_progress.attempts_count,
-        ) as i32;
+        );
+        item_progress.mastery_level = mastery_level as i32;
+        if mastery_level == MasteryLevel::Mastered && item_progress.mastered_at.is_none() {
+            item_progress.mastered_at = Some(event.occurred_at);
+        }
         item_progress.updated_at = Utc::now();
 
         self.repository
             .save_vocabulary_item_progress(&item_progress)
             .await?;
 
+        // 習熟度の内訳の履歴を更新
+        let date = event.occurred_at.date_naive();
+        self.record_item_mastery_history(user_id, date, previous_level, mastery_level)
+            .await?;
+
         // ユーザー進捗も更新
         if let Some(mut user_progress) = self.repository.get_user_progress(user_id).await? {
             user_progress.total_items_learned += 1;
@@ -125,7 +166,6 @@ impl ProgressEventHandler {
         }
 
         // 日次進捗を更新
-        let date = event.occurred_at.date_naive();
         let mut daily_progress = self
             .repository
             .get_daily_progress(user_id, date)
@@ -192,6 +232,16 @@ impl ProgressEventHandler {
             }
             progress.updated_at = Utc::now();
             self.repository.save_user_progress(&progress).await?;
+
+            self.repository
+                .save_streak_history(&StreakHistory {
+                    user_id,
+                    snapshot_date: event.occurred_at.date_naive(),
+                    current_streak_days: progress.current_streak_days,
+                    longest_streak_days: progress.longest_streak_days,
+                    updated_at: Utc::now(),
+                })
+                .await?;
         }
 
         Ok(())
@@ -242,4 +292,32 @@ impl ProgressEventHandler {
 
         Ok(())
     }
+
+    /// 習熟度の内訳の履歴行を更新する
+    ///
+    /// その日の行がなければ直前の行の内訳を引き継いで追記する
+    async fn record_item_mastery_history(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+        from: Option<MasteryLevel>,
+        to: MasteryLevel,
+    ) -> Result<()> {
+        let mut breakdown = self
+            .repository
+            .get_item_mastery_history_as_of(user_id, date)
+            .await?
+            .map(|history| history.breakdown)
+            .unwrap_or_default();
+        breakdown.record_transition(from, to);
+
+        self.repository
+            .save_item_mastery_history(&ItemMasteryHistory {
+                user_id,
+                snapshot_date: date,
+                breakdown,
+                updated_at: Utc::now(),
+            })
+            .await
+    }
 }
@@ -0,0 +1,50 @@
+//! 履歴の間引き
+
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use tracing::info;
+
+use crate::{
+    domain::{CompactionPolicy, HistoryKind},
+    error::Result,
+    ports::outbound::ReadModelRepository,
+};
+
+/// 保持期間を過ぎた履歴行を週単位に間引くサービス
+pub struct HistoryCompactionService {
+    repository: Arc<dyn ReadModelRepository>,
+    policy:     CompactionPolicy,
+}
+
+impl HistoryCompactionService {
+    pub fn new(repository: Arc<dyn ReadModelRepository>, policy: CompactionPolicy) -> Self {
+        Self { repository, policy }
+    }
+
+    /// すべての履歴を間引き、削除した行数を返す
+    pub async fn compact(&self, today: NaiveDate) -> Result<u64> {
+        let cutoff = self.policy.cutoff(today);
+        let mut removed = 0;
+
+        for kind in HistoryKind::ALL {
+            for user_id in self
+                .repository
+                .get_history_users_before(kind, cutoff)
+                .await?
+            {
+                let dates = self.repository.get_history_dates(kind, user_id).await?;
+                let targets = self.policy.dates_to_remove(&dates, today);
+                if !targets.is_empty() {
+                    removed += self
+                        .repository
+                        .delete_history(kind, user_id, &targets)
+                        .await?;
+                }
+            }
+        }
+
+        info!("Compacted {} history rows older than {}", removed, cutoff);
+        Ok(removed)
+    }
+}
@@ -0,0 +1,41 @@
+//! 進捗のプロジェクション
+//!
+//! イベントの読み込みとチェックポイントの保存は `shared_cqrs` の
+//! [`ProjectionRunner`](shared_cqrs::ProjectionRunner) に任せ、ここでは
+//! イベントを [`ProgressEventHandler`] に渡すだけにする。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shared_cqrs::{CqrsError, PositionedEvent, Projection};
+
+use super::ProgressEventHandler;
+use crate::ports::outbound::Event;
+
+/// チェックポイントの名前
+pub const PROGRESS_PROJECTION_NAME: &str = "progress_projection";
+
+/// 進捗の Read Model を更新するプロジェクション
+pub struct ProgressProjector {
+    handler: Arc<ProgressEventHandler>,
+}
+
+impl ProgressProjector {
+    pub fn new(handler: Arc<ProgressEventHandler>) -> Self {
+        Self { handler }
+    }
+}
+
+#[async_trait]
+impl Projection for ProgressProjector {
+    fn name(&self) -> &str {
+        PROGRESS_PROJECTION_NAME
+    }
+
+    async fn apply(&self, event: &PositionedEvent) -> shared_cqrs::Result<()> {
+        self.handler
+            .handle_event(&Event::from(event))
+            .await
+            .map_err(CqrsError::handler)
+    }
+}
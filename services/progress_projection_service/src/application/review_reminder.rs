@@ -0,0 +1,442 @@
+//! 毎日の復習リマインダーのスケジュール
+//!
+//! 復習期限の来たアイテムがあるユーザーに、1日1回リマインダーを要求する。
+//! 送る時刻はローカルの今日のうちまだ始まっていない、学習することの多い
+//! 時間帯の開始時刻にする。履歴の少ないユーザーや今日の時間帯が過ぎた
+//! ユーザーは既定の時刻にする。
+//!
+//! 通知設定で復習リマインダーを無効にしたユーザーには要求せず、おやすみ時間に
+//! かかる場合は時間帯の終わりまで延期する。同じ `(ユーザー, ローカルの日付)`
+//! のリマインダーは一度だけ要求する。
+
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveTime, Utc};
+use shared_integration_events::{
+    Delivery,
+    NotificationCategory,
+    NotificationChannel,
+    NotificationPreferenceSource,
+    NotificationRequested,
+    OwningContext,
+};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::StudyTimeService;
+use crate::{
+    domain::{ReminderSlot, select_reminder_slot},
+    error::Result,
+    ports::outbound::{DueReviewCounter, NotificationPublisher},
+};
+
+/// 通知テンプレート名
+pub const REVIEW_REMINDER_TEMPLATE: &str = "review_reminder";
+
+/// 履歴の少ないユーザーに送る時刻（ローカル時刻）
+pub const DEFAULT_REVIEW_REMINDER_TIME: NaiveTime = NaiveTime::from_hms_opt(19, 0, 0).unwrap();
+
+/// スケジュールの結果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReminderOutcome {
+    /// リマインダーを要求した（おやすみ時間による延期を反映した配信時刻）
+    Scheduled {
+        deliver_at: DateTime<Utc>,
+        slot:       ReminderSlot,
+    },
+    /// 今日のリマインダーは要求済み
+    AlreadyScheduled,
+    /// 復習期限の来たアイテムがない
+    NothingDue,
+    /// 復習リマインダーの通知カテゴリが無効
+    Disabled,
+}
+
+/// 復習リマインダーのスケジューラー
+pub struct ReviewReminderScheduler<S> {
+    study_time:   StudyTimeService<S>,
+    due_counter:  Arc<dyn DueReviewCounter>,
+    publisher:    Arc<dyn NotificationPublisher>,
+    default_time: NaiveTime,
+}
+
+impl<S: NotificationPreferenceSource> ReviewReminderScheduler<S> {
+    pub fn new(
+        study_time: StudyTimeService<S>,
+        due_counter: Arc<dyn DueReviewCounter>,
+        publisher: Arc<dyn NotificationPublisher>,
+    ) -> Self {
+        Self {
+            study_time,
+            due_counter,
+            publisher,
+            default_time: DEFAULT_REVIEW_REMINDER_TIME,
+        }
+    }
+
+    /// 履歴の少ないユーザーに送る時刻を変更
+    pub fn with_default_time(mut self, default_time: NaiveTime) -> Self {
+        self.default_time = default_time;
+        self
+    }
+
+    /// セッションを開始したことのあるユーザーのリマインダーを要求し、件数を返す
+    ///
+    /// 定期的に呼び出す。要求済みのユーザーは飛ばすため、同じ日に何度
+    /// 呼び出してもよい
+    pub async fn run_due(&self, now: DateTime<Utc>) -> Result<usize> {
+        let users = self.study_time.repository().get_study_time_users().await?;
+
+        let mut scheduled = 0;
+        for user_id in users {
+            match self.schedule(user_id, now).await {
+                Ok(ReminderOutcome::Scheduled { .. }) => scheduled += 1,
+                Ok(_) => {},
+                Err(e) => error!("Failed to schedule review reminder for {}: {}", user_id, e),
+            }
+        }
+
+        if scheduled > 0 {
+            info!("Scheduled {} review reminders", scheduled);
+        }
+        Ok(scheduled)
+    }
+
+    /// ユーザーの今日のリマインダーを要求する
+    pub async fn schedule(&self, user_id: Uuid, now: DateTime<Utc>) -> Result<ReminderOutcome> {
+        let preferences = self
+            .study_time
+            .preference_source()
+            .get_preferences(&user_id.to_string())
+            .await?;
+        if !preferences.is_enabled(NotificationCategory::ReviewReminder) {
+            return Ok(ReminderOutcome::Disabled);
+        }
+
+        let due_count = self.due_counter.count_due(user_id, now).await?;
+        if due_count == 0 {
+            return Ok(ReminderOutcome::NothingDue);
+        }
+
+        let windows = self.study_time.windows_for(user_id, &preferences).await?;
+        let offset = preferences.offset();
+        let slot = select_reminder_slot(&windows.windows, now, offset, self.default_time);
+        let local_date = now.with_timezone(&offset).date_naive();
+
+        let mut request = NotificationRequested::new(
+            user_id.to_string(),
+            NotificationCategory::ReviewReminder,
+            NotificationChannel::Push,
+            REVIEW_REMINDER_TEMPLATE,
+            OwningContext::Progress,
+        )
+        .with_param("due_count", due_count.to_string())
+        .with_param("date", local_date.to_string());
+        request.not_before = Some(slot.at());
+
+        let request = match preferences.apply(request, now) {
+            Delivery::Send(request) | Delivery::Deferred(request) => request,
+            Delivery::Suppressed => return Ok(ReminderOutcome::Disabled),
+        };
+        let deliver_at = request.not_before.unwrap_or(now);
+
+        if !self
+            .study_time
+            .repository()
+            .record_review_reminder(user_id, local_date, deliver_at)
+            .await?
+        {
+            return Ok(ReminderOutcome::AlreadyScheduled);
+        }
+
+        self.publisher.publish(&request).await?;
+        Ok(ReminderOutcome::Scheduled { deliver_at, slot })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::Mutex,
+    };
+
+    use async_trait::async_trait;
+    use chrono::NaiveDate;
+    use shared_integration_events::{NotificationPreferences, QuietHours};
+
+    use super::*;
+    use crate::{domain::utc_quarter_of_week, ports::outbound::StudyTimeRepository};
+
+    #[derive(Default)]
+    struct InMemoryStudyTime {
+        quarters:  Mutex<HashMap<Uuid, HashMap<u32, u32>>>,
+        reminders: Mutex<HashSet<(Uuid, NaiveDate)>>,
+    }
+
+    #[async_trait]
+    impl StudyTimeRepository for InMemoryStudyTime {
+        async fn record_session_start(&self, user_id: Uuid, quarter_of_week: u32) -> Result<()> {
+            *self
+                .quarters
+                .lock()
+                .unwrap()
+                .entry(user_id)
+                .or_default()
+                .entry(quarter_of_week)
+                .or_default() += 1;
+            Ok(())
+        }
+
+        async fn get_study_time_quarters(&self, user_id: Uuid) -> Result<Vec<(u32, u32)>> {
+            Ok(self
+                .quarters
+                .lock()
+                .unwrap()
+                .get(&user_id)
+                .map(|quarters| quarters.iter().map(|(&q, &n)| (q, n)).collect())
+                .unwrap_or_default())
+        }
+
+        async fn get_study_time_users(&self) -> Result<Vec<Uuid>> {
+            Ok(self.quarters.lock().unwrap().keys().copied().collect())
+        }
+
+        async fn record_review_reminder(
+            &self,
+            user_id: Uuid,
+            date: NaiveDate,
+            _scheduled_for: DateTime<Utc>,
+        ) -> Result<bool> {
+            Ok(self.reminders.lock().unwrap().insert((user_id, date)))
+        }
+    }
+
+    struct FixedDueCounter(u32);
+
+    #[async_trait]
+    impl DueReviewCounter for FixedDueCounter {
+        async fn count_due(&self, _user_id: Uuid, _at: DateTime<Utc>) -> Result<u32> {
+            Ok(self.0)
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingPublisher {
+        published: Mutex<Vec<NotificationRequested>>,
+    }
+
+    #[async_trait]
+    impl NotificationPublisher for RecordingPublisher {
+        async fn publish(&self, request: &NotificationRequested) -> Result<()> {
+            self.published.lock().unwrap().push(request.clone());
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct FakePreferences {
+        preferences: HashMap<String, NotificationPreferences>,
+    }
+
+    #[async_trait]
+    impl NotificationPreferenceSource for FakePreferences {
+        async fn get_preferences(
+            &self,
+            user_id: &str,
+        ) -> shared_integration_events::Result<NotificationPreferences> {
+            Ok(self
+                .preferences
+                .get(user_id)
+                .cloned()
+                .unwrap_or_else(|| NotificationPreferences::default_for(user_id)))
+        }
+    }
+
+    struct Fixture {
+        study_time: Arc<InMemoryStudyTime>,
+        publisher:  Arc<RecordingPublisher>,
+        scheduler:  ReviewReminderScheduler<FakePreferences>,
+    }
+
+    fn fixture(due_count: u32, preferences: FakePreferences) -> Fixture {
+        let study_time = Arc::new(InMemoryStudyTime::default());
+        let publisher = Arc::new(RecordingPublisher::default());
+        let scheduler = ReviewReminderScheduler::new(
+            StudyTimeService::new(study_time.clone(), preferences),
+            Arc::new(FixedDueCounter(due_count)),
+            publisher.clone(),
+        );
+        Fixture {
+            study_time,
+            publisher,
+            scheduler,
+        }
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    async fn study(f: &Fixture, user_id: Uuid, start: &str, sessions: u32) {
+        for _ in 0..sessions {
+            f.study_time
+                .record_session_start(user_id, utc_quarter_of_week(at(start)))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reminder_is_placed_at_preferred_window_once_per_day() {
+        let user_id = Uuid::new_v4();
+        let f = fixture(4, FakePreferences::default());
+        // 月曜 07:00 と 20:00 に学習することが多い
+        study(&f, user_id, "2025-01-06T07:05:00Z", 6).await;
+        study(&f, user_id, "2025-01-06T20:40:00Z", 4).await;
+
+        let outcome = f
+            .scheduler
+            .schedule(user_id, at("2025-01-13T09:00:00Z"))
+            .await
+            .unwrap();
+        let ReminderOutcome::Scheduled { deliver_at, slot } = outcome else {
+            panic!("unexpected outcome: {outcome:?}");
+        };
+        assert_eq!(deliver_at, at("2025-01-13T20:00:00Z"));
+        assert!(matches!(slot, ReminderSlot::Window { window, .. } if window.hour_of_week == 20));
+
+        let published = f.publisher.published.lock().unwrap().clone();
+        assert_eq!(published.len(), 1);
+        assert_eq!(
+            published[0].category,
+            Some(NotificationCategory::ReviewReminder)
+        );
+        assert_eq!(published[0].params["due_count"], "4");
+        assert_eq!(published[0].params["date"], "2025-01-13");
+        assert_eq!(published[0].not_before, Some(at("2025-01-13T20:00:00Z")));
+
+        // 同じ日に再度呼び出しても要求しない
+        assert_eq!(
+            f.scheduler
+                .schedule(user_id, at("2025-01-13T10:00:00Z"))
+                .await
+                .unwrap(),
+            ReminderOutcome::AlreadyScheduled
+        );
+        assert_eq!(f.publisher.published.lock().unwrap().len(), 1);
+
+        // 翌日は要求する
+        assert!(matches!(
+            f.scheduler
+                .schedule(user_id, at("2025-01-14T09:00:00Z"))
+                .await
+                .unwrap(),
+            ReminderOutcome::Scheduled { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_low_sample_user_gets_default_time_in_local_time_zone() {
+        let user_id = Uuid::new_v4();
+        let preferences = FakePreferences {
+            preferences: HashMap::from([(
+                user_id.to_string(),
+                NotificationPreferences {
+                    utc_offset_minutes: 9 * 60,
+                    ..NotificationPreferences::default_for(user_id.to_string())
+                },
+            )]),
+        };
+        let f = fixture(2, preferences);
+        study(&f, user_id, "2025-01-06T11:00:00Z", 3).await;
+
+        let windows = f
+            .scheduler
+            .study_time
+            .get_preferred_study_windows(user_id)
+            .await
+            .unwrap();
+        assert_eq!(windows.total_sessions, 3);
+        assert!(windows.windows.is_empty());
+
+        // 東京の 19:00
+        let outcome = f
+            .scheduler
+            .schedule(user_id, at("2025-01-13T01:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(
+            outcome,
+            ReminderOutcome::Scheduled {
+                deliver_at: at("2025-01-13T10:00:00Z"),
+                slot:       ReminderSlot::Default {
+                    at: at("2025-01-13T10:00:00Z"),
+                },
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quiet_hours_defer_the_reminder() {
+        let user_id = Uuid::new_v4();
+        let preferences = FakePreferences {
+            preferences: HashMap::from([(
+                user_id.to_string(),
+                NotificationPreferences {
+                    quiet_hours: QuietHours::from_minutes(18 * 60, 20 * 60 + 30),
+                    ..NotificationPreferences::default_for(user_id.to_string())
+                },
+            )]),
+        };
+        let f = fixture(1, preferences);
+
+        let outcome = f
+            .scheduler
+            .schedule(user_id, at("2025-01-13T09:00:00Z"))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            outcome,
+            ReminderOutcome::Scheduled { deliver_at, .. } if deliver_at == at("2025-01-13T20:30:00Z")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_no_reminder_without_due_items_or_when_disabled() {
+        let user_id = Uuid::new_v4();
+        let f = fixture(0, FakePreferences::default());
+        study(&f, user_id, "2025-01-06T07:00:00Z", 1).await;
+
+        assert_eq!(
+            f.scheduler
+                .schedule(user_id, at("2025-01-13T09:00:00Z"))
+                .await
+                .unwrap(),
+            ReminderOutcome::NothingDue
+        );
+
+        let disabled = FakePreferences {
+            preferences: HashMap::from([(
+                user_id.to_string(),
+                NotificationPreferences {
+                    disabled_categories: [NotificationCategory::ReviewReminder].into(),
+                    ..NotificationPreferences::default_for(user_id.to_string())
+                },
+            )]),
+        };
+        let f = fixture(3, disabled);
+        study(&f, user_id, "2025-01-06T07:00:00Z", 1).await;
+
+        assert_eq!(
+            f.scheduler
+                .run_due(at("2025-01-13T09:00:00Z"))
+                .await
+                .unwrap(),
+            0
+        );
+        assert!(f.publisher.published.lock().unwrap().is_empty());
+        assert!(f.study_time.reminders.lock().unwrap().is_empty());
+    }
+}
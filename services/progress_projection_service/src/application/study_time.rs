@@ -0,0 +1,100 @@
+//! 学習する時間帯のプロジェクションと取得
+//!
+//! `LearningStarted`（セッションの開始）を UTC の15分枠ごとに数え、
+//! 取得するときにユーザーのプロフィールのタイムゾーンで集計し直す。
+
+use std::sync::Arc;
+
+use shared_integration_events::{NotificationPreferenceSource, NotificationPreferences};
+use uuid::Uuid;
+
+use super::collection_stats::field;
+use crate::{
+    domain::{MIN_STUDY_TIME_SAMPLES, PreferredStudyWindows, utc_quarter_of_week},
+    error::Result,
+    ports::outbound::{Event, StudyTimeRepository},
+};
+
+/// 学習する時間帯のプロジェクション
+pub struct StudyTimeProjector {
+    repository: Arc<dyn StudyTimeRepository>,
+}
+
+impl StudyTimeProjector {
+    pub fn new(repository: Arc<dyn StudyTimeRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// イベントを処理
+    pub async fn handle_event(&self, event: &Event) -> Result<()> {
+        if event.event_type != "LearningStarted" {
+            return Ok(());
+        }
+
+        self.repository
+            .record_session_start(
+                field(event, "user_id")?,
+                utc_quarter_of_week(event.occurred_at),
+            )
+            .await
+    }
+}
+
+/// 学習する時間帯の取得（内部 API）
+pub struct StudyTimeService<S> {
+    repository:  Arc<dyn StudyTimeRepository>,
+    preferences: S,
+    min_samples: u32,
+}
+
+impl<S: NotificationPreferenceSource> StudyTimeService<S> {
+    pub fn new(repository: Arc<dyn StudyTimeRepository>, preferences: S) -> Self {
+        Self {
+            repository,
+            preferences,
+            min_samples: MIN_STUDY_TIME_SAMPLES,
+        }
+    }
+
+    /// 時間帯を推定するのに必要なセッション数を変更
+    pub fn with_min_samples(mut self, min_samples: u32) -> Self {
+        self.min_samples = min_samples;
+        self
+    }
+
+    pub fn repository(&self) -> &Arc<dyn StudyTimeRepository> {
+        &self.repository
+    }
+
+    pub fn preference_source(&self) -> &S {
+        &self.preferences
+    }
+
+    /// ユーザーのローカル時刻で学習することの多い時間帯を返す
+    pub async fn get_preferred_study_windows(
+        &self,
+        user_id: Uuid,
+    ) -> Result<PreferredStudyWindows> {
+        let preferences = self
+            .preferences
+            .get_preferences(&user_id.to_string())
+            .await?;
+        self.windows_for(user_id, &preferences).await
+    }
+
+    /// 取得済みの通知設定のタイムゾーンで時間帯を返す
+    pub async fn windows_for(
+        &self,
+        user_id: Uuid,
+        preferences: &NotificationPreferences,
+    ) -> Result<PreferredStudyWindows> {
+        let quarters = self.repository.get_study_time_quarters(user_id).await?;
+
+        Ok(PreferredStudyWindows::from_utc_quarters(
+            user_id,
+            &quarters,
+            preferences.offset(),
+            self.min_samples,
+        ))
+    }
+}
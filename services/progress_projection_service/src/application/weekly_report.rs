@@ -0,0 +1,553 @@
+//! 週次レポートの生成
+//!
+//! ユーザーのローカル時刻で週が終わった（月曜日になった）時点で、前の週の
+//! レポートを生成して BLOB ストアに保存する。週次レポートの通知カテゴリを
+//! 無効にしたユーザーのレポートは生成しない。
+//!
+//! 同じ週の再生成は内容のハッシュが変わった場合だけ上書きする。
+//! 通知は初めて保存したときだけ要求し、更新では要求しない。
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Days, NaiveTime, Utc};
+use shared_integration_events::{
+    Delivery,
+    NotificationCategory,
+    NotificationChannel,
+    NotificationGate,
+    NotificationPreferenceSource,
+    NotificationPreferences,
+    NotificationRequested,
+    OwningContext,
+};
+use shared_progress_context::report::{IsoWeek, WeeklyReportDocument, weekly_report_key};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    domain::{TOP_MASTERED_WORDS, build_weekly_report, is_study_day, top_mastered_items},
+    error::Result,
+    ports::outbound::{BlobStore, NotificationPublisher, VocabularyLookup, WeeklyReportSource},
+};
+
+/// 通知テンプレート名
+pub const WEEKLY_REPORT_TEMPLATE: &str = "weekly_report";
+
+/// レポート生成の結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportOutcome {
+    /// 新しく保存した
+    Created,
+    /// 元データが変わったため上書きした
+    Updated,
+    /// 保存済みのレポートと内容が同じ
+    Unchanged,
+    /// その週に学習していない
+    NoActivity,
+    /// 週次レポートの通知カテゴリが無効
+    Disabled,
+}
+
+/// 週次レポートの生成サービス
+pub struct WeeklyReportGenerator<S> {
+    source:     Arc<dyn WeeklyReportSource>,
+    vocabulary: Arc<dyn VocabularyLookup>,
+    blobs:      Arc<dyn BlobStore>,
+    publisher:  Arc<dyn NotificationPublisher>,
+    gate:       NotificationGate<S>,
+}
+
+impl<S: NotificationPreferenceSource> WeeklyReportGenerator<S> {
+    pub fn new(
+        source: Arc<dyn WeeklyReportSource>,
+        vocabulary: Arc<dyn VocabularyLookup>,
+        blobs: Arc<dyn BlobStore>,
+        publisher: Arc<dyn NotificationPublisher>,
+        gate: NotificationGate<S>,
+    ) -> Self {
+        Self {
+            source,
+            vocabulary,
+            blobs,
+            publisher,
+            gate,
+        }
+    }
+
+    /// 週が終わったユーザーのレポートを生成し、保存した件数を返す
+    ///
+    /// 定期的に呼び出す。保存済みで内容が変わらないレポートは書き込まないため、
+    /// 同じ週の間に何度呼び出してもよい
+    pub async fn run_due(&self, now: DateTime<Utc>) -> Result<usize> {
+        // タイムゾーンによっては UTC の今週がすでに終わっているユーザーもいるため、
+        // 前の週と今週に学習したユーザーを対象にする
+        let current = IsoWeek::containing(now.date_naive());
+        let previous = current.previous();
+        let users = self
+            .source
+            .get_active_users(previous.first_day(), current.last_day())
+            .await?;
+
+        let mut written = 0;
+        for user_id in users {
+            match self.generate_due(user_id, now).await {
+                Ok(ReportOutcome::Created | ReportOutcome::Updated) => written += 1,
+                Ok(_) => {},
+                Err(e) => error!("Failed to generate weekly report for {}: {}", user_id, e),
+            }
+        }
+
+        if written > 0 {
+            info!("Wrote {} weekly reports", written);
+        }
+        Ok(written)
+    }
+
+    /// ユーザーのローカル時刻で直前に終わった週のレポートを生成する
+    pub async fn generate_due(&self, user_id: Uuid, now: DateTime<Utc>) -> Result<ReportOutcome> {
+        let preferences = self.preferences(user_id).await?;
+        let local_today = now.with_timezone(&preferences.offset()).date_naive();
+        let week = IsoWeek::containing(local_today).previous();
+
+        self.generate_with(user_id, week, now, &preferences).await
+    }
+
+    /// 指定した週のレポートを生成する
+    pub async fn generate(
+        &self,
+        user_id: Uuid,
+        week: IsoWeek,
+        now: DateTime<Utc>,
+    ) -> Result<ReportOutcome> {
+        let preferences = self.preferences(user_id).await?;
+        self.generate_with(user_id, week, now, &preferences).await
+    }
+
+    async fn preferences(&self, user_id: Uuid) -> Result<NotificationPreferences> {
+        Ok(self
+            .gate
+            .source()
+            .get_preferences(&user_id.to_string())
+            .await?)
+    }
+
+    async fn generate_with(
+        &self,
+        user_id: Uuid,
+        week: IsoWeek,
+        now: DateTime<Utc>,
+        preferences: &NotificationPreferences,
+    ) -> Result<ReportOutcome> {
+        if !preferences.is_enabled(NotificationCategory::WeeklyReport) {
+            return Ok(ReportOutcome::Disabled);
+        }
+
+        let days = self
+            .source
+            .get_daily_progress_between(user_id, week.first_day(), week.last_day())
+            .await?;
+        if !days.iter().any(is_study_day) {
+            return Ok(ReportOutcome::NoActivity);
+        }
+
+        let from = week.first_day().and_time(NaiveTime::MIN).and_utc();
+        let to = week
+            .last_day()
+            .checked_add_days(Days::new(1))
+            .map_or(DateTime::<Utc>::MAX_UTC, |date| {
+                date.and_time(NaiveTime::MIN).and_utc()
+            });
+        let mastered = self
+            .source
+            .get_items_mastered_between(user_id, from, to)
+            .await?;
+        let item_ids: Vec<Uuid> = top_mastered_items(&mastered, TOP_MASTERED_WORDS)
+            .into_iter()
+            .map(|item| item.vocabulary_item_id)
+            .collect();
+        let definitions = if item_ids.is_empty() {
+            Vec::new()
+        } else {
+            self.vocabulary.lookup_words(&item_ids).await?
+        };
+
+        let report = build_weekly_report(user_id, week, &days, &mastered, &definitions);
+        let key = weekly_report_key(user_id, week);
+
+        let existing = self.blobs.get(&key).await?;
+        if let Some(bytes) = &existing {
+            match WeeklyReportDocument::from_bytes(bytes) {
+                Ok(document) if document.content_hash == report.content_hash()? => {
+                    return Ok(ReportOutcome::Unchanged);
+                },
+                Ok(_) => {},
+                Err(e) => warn!("Overwriting unreadable weekly report {}: {}", key, e),
+            }
+        }
+
+        let document = WeeklyReportDocument::new(report, now)?;
+        self.blobs.put(&key, &document.to_bytes()?).await?;
+
+        if existing.is_some() {
+            return Ok(ReportOutcome::Updated);
+        }
+
+        self.notify(user_id, week, &key, now).await?;
+        Ok(ReportOutcome::Created)
+    }
+
+    async fn notify(
+        &self,
+        user_id: Uuid,
+        week: IsoWeek,
+        key: &str,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let request = NotificationRequested::new(
+            user_id.to_string(),
+            NotificationCategory::WeeklyReport,
+            NotificationChannel::InApp,
+            WEEKLY_REPORT_TEMPLATE,
+            OwningContext::Progress,
+        )
+        .with_param("week", week.to_string())
+        .with_param("report_key", key);
+
+        match self.gate.prepare(request, now).await? {
+            Delivery::Send(request) | Delivery::Deferred(request) => {
+                self.publisher.publish(&request).await
+            },
+            Delivery::Suppressed => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Mutex};
+
+    use async_trait::async_trait;
+    use chrono::NaiveDate;
+    use shared_blob_store::InMemoryBlobStore;
+
+    use super::*;
+    use crate::domain::{DailyProgress, VocabularyItemProgress, WordDefinition};
+
+    #[derive(Default)]
+    struct FakeSource {
+        days:     Mutex<Vec<DailyProgress>>,
+        mastered: Vec<VocabularyItemProgress>,
+    }
+
+    #[async_trait]
+    impl WeeklyReportSource for FakeSource {
+        async fn get_active_users(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Uuid>> {
+            let mut users: Vec<Uuid> = self
+                .days
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|day| from <= day.date && day.date <= to)
+                .map(|day| day.user_id)
+                .collect();
+            users.dedup();
+            Ok(users)
+        }
+
+        async fn get_daily_progress_between(
+            &self,
+            user_id: Uuid,
+            from: NaiveDate,
+            to: NaiveDate,
+        ) -> Result<Vec<DailyProgress>> {
+            Ok(self
+                .days
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|day| day.user_id == user_id && from <= day.date && day.date <= to)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_items_mastered_between(
+            &self,
+            user_id: Uuid,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+        ) -> Result<Vec<VocabularyItemProgress>> {
+            Ok(self
+                .mastered
+                .iter()
+                .filter(|item| {
+                    item.user_id == user_id
+                        && item.mastered_at.is_some_and(|at| from <= at && at < to)
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    struct FakeVocabulary;
+
+    #[async_trait]
+    impl VocabularyLookup for FakeVocabulary {
+        async fn lookup_words(&self, item_ids: &[Uuid]) -> Result<Vec<WordDefinition>> {
+            Ok(item_ids
+                .iter()
+                .map(|&item_id| WordDefinition {
+                    item_id,
+                    spelling: "serendipity".to_string(),
+                    definition: Some("a happy accident".to_string()),
+                })
+                .collect())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingPublisher {
+        published: Mutex<Vec<NotificationRequested>>,
+    }
+
+    #[async_trait]
+    impl NotificationPublisher for RecordingPublisher {
+        async fn publish(&self, request: &NotificationRequested) -> Result<()> {
+            self.published.lock().unwrap().push(request.clone());
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct FakePreferences {
+        preferences: HashMap<String, NotificationPreferences>,
+    }
+
+    #[async_trait]
+    impl NotificationPreferenceSource for FakePreferences {
+        async fn get_preferences(
+            &self,
+            user_id: &str,
+        ) -> shared_integration_events::Result<NotificationPreferences> {
+            Ok(self
+                .preferences
+                .get(user_id)
+                .cloned()
+                .unwrap_or_else(|| NotificationPreferences::default_for(user_id)))
+        }
+    }
+
+    struct Fixture {
+        source:    Arc<FakeSource>,
+        blobs:     Arc<InMemoryBlobStore>,
+        publisher: Arc<RecordingPublisher>,
+        generator: WeeklyReportGenerator<FakePreferences>,
+    }
+
+    fn fixture(source: FakeSource, preferences: FakePreferences) -> Fixture {
+        let source = Arc::new(source);
+        let blobs = Arc::new(InMemoryBlobStore::new());
+        let publisher = Arc::new(RecordingPublisher::default());
+        let generator = WeeklyReportGenerator::new(
+            source.clone(),
+            Arc::new(FakeVocabulary),
+            blobs.clone(),
+            publisher.clone(),
+            NotificationGate::new(preferences),
+        );
+        Fixture {
+            source,
+            blobs,
+            publisher,
+            generator,
+        }
+    }
+
+    fn day(user_id: Uuid, date: &str, minutes: i32) -> DailyProgress {
+        DailyProgress {
+            user_id,
+            date: date.parse().unwrap(),
+            items_learned: 3,
+            items_reviewed: 2,
+            items_mastered: 0,
+            correct_answers: 4,
+            total_answers: 5,
+            study_minutes: minutes,
+            sessions_count: 1,
+            goal_completed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn mastered(user_id: Uuid, at: &str) -> VocabularyItemProgress {
+        VocabularyItemProgress {
+            user_id,
+            vocabulary_item_id: Uuid::new_v4(),
+            attempts_count: 5,
+            correct_count: 5,
+            last_attempt_date: at.parse().unwrap(),
+            last_accuracy: 1.0,
+            average_accuracy: 0.95,
+            mastery_level: 4,
+            time_spent_seconds: 60,
+            mastered_at: Some(at.parse().unwrap()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    async fn stored(
+        blobs: &InMemoryBlobStore,
+        user_id: Uuid,
+        week: IsoWeek,
+    ) -> WeeklyReportDocument {
+        let bytes = blobs
+            .get(&weekly_report_key(user_id, week))
+            .await
+            .unwrap()
+            .unwrap();
+        WeeklyReportDocument::from_bytes(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_regeneration_is_idempotent() {
+        let user_id = Uuid::new_v4();
+        let week: IsoWeek = "2025-W02".parse().unwrap();
+        let source = FakeSource {
+            days:     Mutex::new(vec![day(user_id, "2025-01-07", 25)]),
+            mastered: vec![mastered(user_id, "2025-01-07T10:00:00Z")],
+        };
+        let f = fixture(source, FakePreferences::default());
+
+        let first = f
+            .generator
+            .generate(user_id, week, at("2025-01-13T01:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(first, ReportOutcome::Created);
+
+        let document = stored(&f.blobs, user_id, week).await;
+        assert_eq!(document.report.stats.study_minutes, 25);
+        assert_eq!(
+            document.report.new_mastered_words[0].spelling,
+            "serendipity"
+        );
+
+        let published = f.publisher.published.lock().unwrap().clone();
+        assert_eq!(published.len(), 1);
+        assert_eq!(
+            published[0].category,
+            Some(NotificationCategory::WeeklyReport)
+        );
+        assert_eq!(published[0].params["week"], "2025-W02");
+        assert_eq!(
+            published[0].params["report_key"],
+            weekly_report_key(user_id, week)
+        );
+
+        // 元データが同じなら上書きも通知もしない
+        let second = f
+            .generator
+            .generate(user_id, week, at("2025-01-13T02:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(second, ReportOutcome::Unchanged);
+        assert_eq!(stored(&f.blobs, user_id, week).await, document);
+        assert_eq!(f.publisher.published.lock().unwrap().len(), 1);
+
+        // 元データが変わったら上書きするが、通知は再度要求しない
+        f.source
+            .days
+            .lock()
+            .unwrap()
+            .push(day(user_id, "2025-01-12", 10));
+        let third = f
+            .generator
+            .generate(user_id, week, at("2025-01-13T03:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(third, ReportOutcome::Updated);
+
+        let updated = stored(&f.blobs, user_id, week).await;
+        assert_eq!(updated.report.stats.study_minutes, 35);
+        assert_ne!(updated.content_hash, document.content_hash);
+        assert_eq!(updated.generated_at, at("2025-01-13T03:00:00Z"));
+        assert_eq!(f.publisher.published.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_category_is_not_generated() {
+        let user_id = Uuid::new_v4();
+        let week: IsoWeek = "2025-W02".parse().unwrap();
+        let source = FakeSource {
+            days: Mutex::new(vec![day(user_id, "2025-01-07", 25)]),
+            ..Default::default()
+        };
+        let preferences = FakePreferences {
+            preferences: HashMap::from([(
+                user_id.to_string(),
+                NotificationPreferences {
+                    disabled_categories: [NotificationCategory::WeeklyReport].into(),
+                    ..NotificationPreferences::default_for(user_id.to_string())
+                },
+            )]),
+        };
+        let f = fixture(source, preferences);
+
+        let outcome = f
+            .generator
+            .generate(user_id, week, Utc::now())
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, ReportOutcome::Disabled);
+        assert!(f.blobs.list("reports/").await.unwrap().is_empty());
+        assert!(f.publisher.published.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_due_week_follows_user_time_zone() {
+        let tokyo = Uuid::new_v4();
+        let london = Uuid::new_v4();
+        let source = FakeSource {
+            days: Mutex::new(vec![
+                day(tokyo, "2025-01-08", 20),
+                day(london, "2025-01-08", 20),
+            ]),
+            ..Default::default()
+        };
+        let preferences = FakePreferences {
+            preferences: HashMap::from([(
+                tokyo.to_string(),
+                NotificationPreferences {
+                    utc_offset_minutes: 9 * 60,
+                    ..NotificationPreferences::default_for(tokyo.to_string())
+                },
+            )]),
+        };
+        let f = fixture(source, preferences);
+
+        // UTC では日曜日 16:00（2025-W02 の途中）、東京では月曜日 01:00
+        let now = at("2025-01-12T16:00:00Z");
+        assert_eq!(f.generator.run_due(now).await.unwrap(), 1);
+
+        let week: IsoWeek = "2025-W02".parse().unwrap();
+        assert_eq!(stored(&f.blobs, tokyo, week).await.report.week, week);
+        // ロンドンのユーザーの直前の週は 2025-W01 で、学習していない
+        assert_eq!(
+            f.generator.generate_due(london, now).await.unwrap(),
+            ReportOutcome::NoActivity
+        );
+        assert!(
+            f.blobs
+                .get(&weekly_report_key(london, week))
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
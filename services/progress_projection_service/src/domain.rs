@@ -1,7 +1,15 @@
 //! Progress Projection ドメイン層
 
+pub mod collection;
+pub mod history;
 pub mod models;
+pub mod report;
+pub mod study_time;
 pub mod value_objects;
 
+pub use collection::*;
+pub use history::*;
 pub use models::*;
+pub use report::*;
+pub use study_time::*;
 pub use value_objects::*;
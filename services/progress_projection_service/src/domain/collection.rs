@@ -0,0 +1,119 @@
+//! 共有コレクションのメンバー別統計の集計
+//!
+//! メンバーの語彙アイテム進捗のうち、コレクションに含まれるアイテムだけを
+//! 集計する。コレクションへのアイテムの追加・削除は学習の後にも起きるため、
+//! 統計は差分ではなく現在の構成から毎回計算し直す。
+
+use std::collections::HashSet;
+
+use shared_progress_context::collection::CollectionMemberStats;
+use uuid::Uuid;
+
+use super::{models::VocabularyItemProgress, value_objects::MasteryLevel};
+
+/// コレクションのアイテムとメンバーの進捗を突き合わせて統計を計算する
+///
+/// 他のユーザーの進捗とコレクション外のアイテムの進捗は無視する
+pub fn compute_member_stats(
+    collection_id: Uuid,
+    user_id: Uuid,
+    collection_items: &HashSet<Uuid>,
+    progress: &[VocabularyItemProgress],
+) -> CollectionMemberStats {
+    let studied: Vec<&VocabularyItemProgress> = progress
+        .iter()
+        .filter(|item| {
+            item.user_id == user_id
+                && item.attempts_count > 0
+                && collection_items.contains(&item.vocabulary_item_id)
+        })
+        .collect();
+
+    CollectionMemberStats {
+        collection_id,
+        user_id,
+        items_studied: i32::try_from(studied.len()).unwrap_or(i32::MAX),
+        items_mastered: i32::try_from(
+            studied
+                .iter()
+                .filter(|item| item.mastery_level >= MasteryLevel::Mastered as i32)
+                .count(),
+        )
+        .unwrap_or(i32::MAX),
+        last_activity_at: studied.iter().map(|item| item.last_attempt_date).max(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+
+    fn progress(
+        user_id: Uuid,
+        item_id: Uuid,
+        mastery_level: i32,
+        last_attempt: &str,
+    ) -> VocabularyItemProgress {
+        VocabularyItemProgress {
+            user_id,
+            vocabulary_item_id: item_id,
+            attempts_count: 3,
+            correct_count: 3,
+            last_attempt_date: last_attempt.parse::<DateTime<Utc>>().unwrap(),
+            last_accuracy: 1.0,
+            average_accuracy: 1.0,
+            mastery_level,
+            time_spent_seconds: 60,
+            mastered_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_joins_only_collection_items_of_the_member() {
+        let collection_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let other_user = Uuid::new_v4();
+        let in_collection: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let outside = Uuid::new_v4();
+        let items: HashSet<Uuid> = in_collection.iter().copied().collect();
+
+        let stats = compute_member_stats(
+            collection_id,
+            user_id,
+            &items,
+            &[
+                progress(user_id, in_collection[0], 4, "2025-01-05T10:00:00Z"),
+                progress(user_id, in_collection[1], 2, "2025-01-03T10:00:00Z"),
+                // コレクション外のアイテムは最新でも数えない
+                progress(user_id, outside, 4, "2025-01-09T10:00:00Z"),
+                // 他のメンバーの進捗は数えない
+                progress(other_user, in_collection[2], 4, "2025-01-08T10:00:00Z"),
+            ],
+        );
+
+        assert_eq!(
+            stats,
+            CollectionMemberStats {
+                collection_id,
+                user_id,
+                items_studied: 2,
+                items_mastered: 1,
+                last_activity_at: Some("2025-01-05T10:00:00Z".parse().unwrap()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_member_without_progress_has_empty_stats() {
+        let collection_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        let stats = compute_member_stats(collection_id, user_id, &HashSet::new(), &[]);
+
+        assert_eq!(stats, CollectionMemberStats::empty(collection_id, user_id));
+    }
+}
@@ -0,0 +1,331 @@
+//! 進捗統計の履歴
+//!
+//! ItemMastery と Streaks は現在値だけでなく、日付ごとの時点の値を履歴行として
+//! 追記する。履歴行はその日の終わり時点の状態を表し、同じ日の更新は同じ行に
+//! 上書きする。古い行は `CompactionPolicy` に従って週単位に間引く。
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::value_objects::MasteryLevel;
+
+/// 履歴の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HistoryKind {
+    ItemMastery,
+    Streak,
+}
+
+impl HistoryKind {
+    pub const ALL: [Self; 2] = [Self::ItemMastery, Self::Streak];
+
+    /// 履歴行を保持するテーブル名
+    pub fn table_name(self) -> &'static str {
+        match self {
+            Self::ItemMastery => "item_mastery_history",
+            Self::Streak => "streak_history",
+        }
+    }
+}
+
+/// 習熟度ごとの語彙アイテム数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MasteryBreakdown {
+    pub beginner:   i32,
+    pub learning:   i32,
+    pub familiar:   i32,
+    pub proficient: i32,
+    pub mastered:   i32,
+}
+
+impl MasteryBreakdown {
+    fn count_mut(&mut self, level: MasteryLevel) -> &mut i32 {
+        match level {
+            MasteryLevel::Beginner => &mut self.beginner,
+            MasteryLevel::Learning => &mut self.learning,
+            MasteryLevel::Familiar => &mut self.familiar,
+            MasteryLevel::Proficient => &mut self.proficient,
+            MasteryLevel::Mastered => &mut self.mastered,
+        }
+    }
+
+    /// 1アイテムの習熟度の変化を反映する
+    ///
+    /// `from` が None の場合は新しく学習を始めたアイテムとして数える
+    pub fn record_transition(&mut self, from: Option<MasteryLevel>, to: MasteryLevel) {
+        if let Some(from) = from {
+            let count = self.count_mut(from);
+            *count = (*count - 1).max(0);
+        }
+        *self.count_mut(to) += 1;
+    }
+
+    pub fn total(&self) -> i32 {
+        self.beginner + self.learning + self.familiar + self.proficient + self.mastered
+    }
+}
+
+/// ItemMastery の履歴行
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemMasteryHistory {
+    pub user_id:       Uuid,
+    pub snapshot_date: NaiveDate,
+    pub breakdown:     MasteryBreakdown,
+    pub updated_at:    DateTime<Utc>,
+}
+
+/// Streaks の履歴行
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreakHistory {
+    pub user_id:             Uuid,
+    pub snapshot_date:       NaiveDate,
+    pub current_streak_days: i32,
+    pub longest_streak_days: i32,
+    pub updated_at:          DateTime<Utc>,
+}
+
+/// 指定日時点の値
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsOf<T> {
+    Available(T),
+    /// 記録を開始する前の日付
+    NotAvailable,
+}
+
+impl<T> AsOf<T> {
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Available(value) => Some(value),
+            Self::NotAvailable => None,
+        }
+    }
+}
+
+/// 日付順に並んだ履歴から指定日時点の値を求める
+///
+/// 指定日以前で最も新しい行を返す。間引かれた期間では週の最後の行が
+/// 使われるため、指定日より後の状態を返すことはない
+pub fn resolve_as_of<T>(
+    rows: impl IntoIterator<Item = (NaiveDate, T)>,
+    as_of: NaiveDate,
+) -> AsOf<T> {
+    rows.into_iter()
+        .take_while(|(date, _)| *date <= as_of)
+        .last()
+        .map_or(AsOf::NotAvailable, |(_, value)| AsOf::Available(value))
+}
+
+/// 履歴の間引き方針
+///
+/// 直近 `daily_retention_days` 日は日次の行をすべて残し、それより古い行は
+/// ISO 週ごとに最後の行だけを残す。記録開始を判定できるよう最初の行は常に残す
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionPolicy {
+    pub daily_retention_days: i64,
+}
+
+impl Default for CompactionPolicy {
+    fn default() -> Self {
+        Self {
+            daily_retention_days: 90,
+        }
+    }
+}
+
+impl CompactionPolicy {
+    /// これより前の行が週単位に間引かれる
+    pub fn cutoff(&self, today: NaiveDate) -> NaiveDate {
+        today - Duration::days(self.daily_retention_days)
+    }
+
+    /// 1ユーザーの履歴の日付（昇順）のうち削除する日付を返す
+    pub fn dates_to_remove(&self, dates: &[NaiveDate], today: NaiveDate) -> Vec<NaiveDate> {
+        let cutoff = self.cutoff(today);
+
+        dates
+            .iter()
+            .enumerate()
+            .filter(|&(index, date)| {
+                let is_first = index == 0;
+                let is_last_of_week = dates
+                    .get(index + 1)
+                    .is_none_or(|next| next.iso_week() != date.iso_week());
+                *date < cutoff && !is_first && !is_last_of_week
+            })
+            .map(|(_, date)| *date)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    /// `start` から `days` 日分の日次の行（値は通し番号）
+    fn daily_rows(start: NaiveDate, days: i64) -> Vec<(NaiveDate, i64)> {
+        (0..days)
+            .map(|offset| (start + Duration::days(offset), offset))
+            .collect()
+    }
+
+    fn compact(rows: &[(NaiveDate, i64)], today: NaiveDate) -> Vec<(NaiveDate, i64)> {
+        let dates: Vec<_> = rows.iter().map(|(date, _)| *date).collect();
+        let removed = CompactionPolicy::default().dates_to_remove(&dates, today);
+        rows.iter()
+            .filter(|(date, _)| !removed.contains(date))
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_replay_records_end_of_day_breakdown_per_date() {
+        // (日付, 以前の習熟度, 新しい習熟度) の順に再生する
+        let events = [
+            ("2025-06-02", None, MasteryLevel::Learning),
+            ("2025-06-02", None, MasteryLevel::Learning),
+            (
+                "2025-06-02",
+                Some(MasteryLevel::Learning),
+                MasteryLevel::Familiar,
+            ),
+            (
+                "2025-06-04",
+                Some(MasteryLevel::Familiar),
+                MasteryLevel::Proficient,
+            ),
+            ("2025-06-04", None, MasteryLevel::Learning),
+        ];
+
+        let mut rows: Vec<(NaiveDate, MasteryBreakdown)> = Vec::new();
+        for (day, from, to) in events {
+            let day = date(day);
+            // 同じ日の行があれば上書きし、なければ直前の行を引き継いで追記する
+            let mut breakdown = resolve_as_of(rows.iter().copied(), day)
+                .into_option()
+                .unwrap_or_default();
+            breakdown.record_transition(from, to);
+            match rows.last_mut() {
+                Some((last, value)) if *last == day => *value = breakdown,
+                _ => rows.push((day, breakdown)),
+            }
+        }
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0].1,
+            MasteryBreakdown {
+                learning: 1,
+                familiar: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            rows[1].1,
+            MasteryBreakdown {
+                learning: 2,
+                proficient: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(rows[1].1.total(), 3);
+        assert_eq!(
+            resolve_as_of(rows.iter().copied(), date("2025-06-03")),
+            AsOf::Available(rows[0].1)
+        );
+    }
+
+    #[test]
+    fn test_compaction_keeps_daily_rows_within_retention_and_weekly_before() {
+        // 2025-01-06 は月曜日
+        let rows = daily_rows(date("2025-01-06"), 200);
+        let today = date("2025-07-24");
+        let cutoff = CompactionPolicy::default().cutoff(today);
+        let compacted = compact(&rows, today);
+
+        // 保持期間内はすべて残る
+        assert!(
+            rows.iter()
+                .filter(|(date, _)| *date >= cutoff)
+                .all(|row| compacted.contains(row))
+        );
+        // それより前は最初の行と各週の日曜日だけが残る
+        let older: Vec<_> = compacted
+            .iter()
+            .filter(|(date, _)| *date < cutoff)
+            .collect();
+        assert_eq!(older[0].0, date("2025-01-06"));
+        assert!(
+            older[1..]
+                .iter()
+                .all(|(date, _)| date.weekday() == chrono::Weekday::Sun)
+        );
+
+        // 間引きは冪等
+        assert_eq!(compact(&compacted, today), compacted);
+    }
+
+    #[test]
+    fn test_compaction_keeps_last_row_of_sparse_weeks() {
+        let dates = [
+            date("2025-01-07"), // 火（最初の行）
+            date("2025-01-08"), // 水（週の最後の行）
+            date("2025-01-14"), // 火
+            date("2025-01-16"), // 木（週の最後の行）
+            date("2025-01-22"), // 水（週に1行だけ）
+        ];
+        let removed = CompactionPolicy::default().dates_to_remove(&dates, date("2025-07-01"));
+
+        assert_eq!(removed, vec![date("2025-01-14")]);
+    }
+
+    #[test]
+    fn test_as_of_resolution_across_granularities() {
+        let rows = daily_rows(date("2025-01-06"), 200);
+        let today = date("2025-07-24");
+        let compacted = compact(&rows, today);
+
+        // 日次の期間は指定日の行そのもの
+        let recent = date("2025-07-01");
+        assert_eq!(
+            resolve_as_of(compacted.iter().copied(), recent),
+            resolve_as_of(rows.iter().copied(), recent)
+        );
+
+        // 週次の期間は指定日以前の直近の週末の行（未来の状態は返さない）
+        let wednesday = date("2025-02-12");
+        assert_eq!(
+            resolve_as_of(compacted.iter().copied(), wednesday),
+            resolve_as_of(rows.iter().copied(), date("2025-02-09"))
+        );
+        let sunday = date("2025-02-16");
+        assert_eq!(
+            resolve_as_of(compacted.iter().copied(), sunday),
+            resolve_as_of(rows.iter().copied(), sunday)
+        );
+    }
+
+    #[test]
+    fn test_as_of_before_tracking_began_is_not_available() {
+        let rows = daily_rows(date("2025-01-08"), 200);
+        let compacted = compact(&rows, date("2025-07-24"));
+
+        assert_eq!(
+            resolve_as_of(compacted.iter().copied(), date("2025-01-07")),
+            AsOf::NotAvailable
+        );
+        // 記録開始日は間引かれても値を返す
+        assert_eq!(
+            resolve_as_of(compacted.iter().copied(), date("2025-01-08")),
+            AsOf::Available(0)
+        );
+        assert_eq!(
+            resolve_as_of(compacted.iter().copied(), date("2025-01-09")),
+            AsOf::Available(0)
+        );
+    }
+}
@@ -20,7 +20,7 @@ pub struct UserProgress {
 }
 
 /// 日次進捗リードモデル
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct DailyProgress {
     pub user_id:         Uuid,
     pub date:            chrono::NaiveDate,
@@ -54,7 +54,7 @@ pub struct WeeklyProgress {
 }
 
 /// 語彙アイテム進捗リードモデル
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct VocabularyItemProgress {
     pub user_id:            Uuid,
     pub vocabulary_item_id: Uuid,
@@ -65,6 +65,8 @@ pub struct VocabularyItemProgress {
     pub average_accuracy:   f32,
     pub mastery_level:      i32,
     pub time_spent_seconds: i32,
+    /// 初めて習得（Mastered）した日時
+    pub mastered_at:        Option<DateTime<Utc>>,
     pub created_at:         DateTime<Utc>,
     pub updated_at:         DateTime<Utc>,
 }
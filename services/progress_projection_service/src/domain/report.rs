@@ -0,0 +1,335 @@
+//! 週次レポートの組み立て
+//!
+//! 日次進捗のロールアップと、その週に初めて習得した語彙アイテムから
+//! [`WeeklyReport`] を作る。習得した語彙は正答率の高い順に上位だけを載せ、
+//! 見出し語と定義は Vocabulary の Read Model から引く。
+
+use std::collections::HashMap;
+
+use chrono::{Days, NaiveDate};
+use shared_progress_context::report::{
+    BestDay,
+    IsoWeek,
+    MasteredWord,
+    WeeklyHighlights,
+    WeeklyReport,
+    WeeklyStats,
+};
+use uuid::Uuid;
+
+use super::models::{DailyProgress, VocabularyItemProgress};
+
+/// レポートに載せる新規習得語彙の数
+pub const TOP_MASTERED_WORDS: usize = 5;
+
+/// 語彙アイテムの見出し語と定義
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordDefinition {
+    pub item_id:    Uuid,
+    pub spelling:   String,
+    pub definition: Option<String>,
+}
+
+/// レポートに載せる新規習得語彙を選ぶ（正答率の高い順、同率は習得の早い順）
+pub fn top_mastered_items(
+    mastered: &[VocabularyItemProgress],
+    limit: usize,
+) -> Vec<&VocabularyItemProgress> {
+    let mut items: Vec<_> = mastered
+        .iter()
+        .filter(|item| item.mastered_at.is_some())
+        .collect();
+    items.sort_by(|a, b| {
+        b.average_accuracy
+            .total_cmp(&a.average_accuracy)
+            .then_with(|| a.mastered_at.cmp(&b.mastered_at))
+            .then_with(|| a.vocabulary_item_id.cmp(&b.vocabulary_item_id))
+    });
+    items.truncate(limit);
+    items
+}
+
+/// 週次レポートを組み立てる
+///
+/// 週に含まれない日の進捗は無視する。`mastered` はその週に初めて習得した
+/// アイテムで、定義が見つからないアイテムはレポートに載せない
+pub fn build_weekly_report(
+    user_id: Uuid,
+    week: IsoWeek,
+    days: &[DailyProgress],
+    mastered: &[VocabularyItemProgress],
+    definitions: &[WordDefinition],
+) -> WeeklyReport {
+    let mut days: Vec<&DailyProgress> = days
+        .iter()
+        .filter(|day| day.user_id == user_id && week.contains(day.date))
+        .collect();
+    days.sort_by_key(|day| day.date);
+
+    let studied: Vec<&DailyProgress> = days
+        .iter()
+        .copied()
+        .filter(|day| is_study_day(day))
+        .collect();
+
+    let correct: i32 = days.iter().map(|day| day.correct_answers).sum();
+    let total: i32 = days.iter().map(|day| day.total_answers).sum();
+    let mastered_in_week: Vec<VocabularyItemProgress> = mastered
+        .iter()
+        .filter(|item| {
+            item.mastered_at
+                .is_some_and(|at| week.contains(at.date_naive()))
+        })
+        .cloned()
+        .collect();
+
+    let stats = WeeklyStats {
+        items_learned:   days.iter().map(|day| day.items_learned).sum(),
+        items_reviewed:  days.iter().map(|day| day.items_reviewed).sum(),
+        items_mastered:  i32::try_from(mastered_in_week.len()).unwrap_or(i32::MAX),
+        study_minutes:   days.iter().map(|day| day.study_minutes).sum(),
+        study_days:      i32::try_from(studied.len()).unwrap_or(i32::MAX),
+        sessions:        days.iter().map(|day| day.sessions_count).sum(),
+        goals_completed: i32::try_from(days.iter().filter(|day| day.goal_completed).count())
+            .unwrap_or(i32::MAX),
+        accuracy:        (total > 0).then(|| correct as f32 / total as f32),
+    };
+
+    let best_day = studied
+        .iter()
+        .max_by(|a, b| {
+            a.study_minutes
+                .cmp(&b.study_minutes)
+                .then_with(|| a.items_learned.cmp(&b.items_learned))
+                // 同じ値の場合は早い日を選ぶ
+                .then_with(|| b.date.cmp(&a.date))
+        })
+        .map(|day| BestDay {
+            date:          day.date,
+            study_minutes: day.study_minutes,
+            items_learned: day.items_learned,
+        });
+
+    let definitions: HashMap<Uuid, &WordDefinition> = definitions
+        .iter()
+        .map(|word| (word.item_id, word))
+        .collect();
+    let new_mastered_words = top_mastered_items(&mastered_in_week, TOP_MASTERED_WORDS)
+        .into_iter()
+        .filter_map(|item| {
+            let word = definitions.get(&item.vocabulary_item_id)?;
+            Some(MasteredWord {
+                item_id:     item.vocabulary_item_id,
+                spelling:    word.spelling.clone(),
+                definition:  word.definition.clone(),
+                mastered_at: item.mastered_at?,
+            })
+        })
+        .collect();
+
+    WeeklyReport {
+        user_id,
+        week,
+        period_start: week.first_day(),
+        period_end: week.last_day(),
+        stats,
+        highlights: WeeklyHighlights {
+            best_day,
+            longest_run_days: longest_run(studied.iter().map(|day| day.date)),
+        },
+        new_mastered_words,
+    }
+}
+
+/// 学習した日か（回答・学習・セッションのいずれかがある日）
+pub fn is_study_day(day: &DailyProgress) -> bool {
+    day.total_answers > 0 || day.items_learned > 0 || day.sessions_count > 0
+}
+
+/// 昇順の日付のうち、連続する日の最長の長さ
+fn longest_run(dates: impl Iterator<Item = NaiveDate>) -> i32 {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous: Option<NaiveDate> = None;
+
+    for date in dates {
+        current = match previous.and_then(|p| p.checked_add_days(Days::new(1))) {
+            Some(next) if next == date => current + 1,
+            _ => 1,
+        };
+        longest = longest.max(current);
+        previous = Some(date);
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn day(
+        user_id: Uuid,
+        on: &str,
+        minutes: i32,
+        learned: i32,
+        correct: i32,
+        total: i32,
+    ) -> DailyProgress {
+        DailyProgress {
+            user_id,
+            date: date(on),
+            items_learned: learned,
+            items_reviewed: 0,
+            items_mastered: 0,
+            correct_answers: correct,
+            total_answers: total,
+            study_minutes: minutes,
+            sessions_count: 1,
+            goal_completed: minutes >= 20,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn mastered(user_id: Uuid, accuracy: f32, mastered_at: &str) -> VocabularyItemProgress {
+        VocabularyItemProgress {
+            user_id,
+            vocabulary_item_id: Uuid::new_v4(),
+            attempts_count: 6,
+            correct_count: 6,
+            last_attempt_date: at(mastered_at),
+            last_accuracy: accuracy,
+            average_accuracy: accuracy,
+            mastery_level: 4,
+            time_spent_seconds: 120,
+            mastered_at: Some(at(mastered_at)),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn definition(item: &VocabularyItemProgress, spelling: &str) -> WordDefinition {
+        WordDefinition {
+            item_id:    item.vocabulary_item_id,
+            spelling:   spelling.to_string(),
+            definition: Some(format!("definition of {spelling}")),
+        }
+    }
+
+    #[test]
+    fn test_assembles_report_from_rollups() {
+        let user_id = Uuid::new_v4();
+        let week: IsoWeek = "2025-W02".parse().unwrap();
+        let days = vec![
+            day(user_id, "2025-01-06", 15, 5, 8, 10),
+            day(user_id, "2025-01-07", 30, 7, 9, 10),
+            day(user_id, "2025-01-09", 30, 4, 5, 10),
+            // 翌週の月曜日は含まない
+            day(user_id, "2025-01-13", 60, 9, 10, 10),
+        ];
+        let words: Vec<_> = [0.85, 0.99, 0.9, 0.8, 0.95, 0.88]
+            .iter()
+            .enumerate()
+            .map(|(i, &accuracy)| {
+                mastered(
+                    user_id,
+                    accuracy,
+                    &format!("2025-01-0{}T12:00:00Z", 6 + i % 3),
+                )
+            })
+            .collect();
+        let definitions: Vec<_> = words
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != 4)
+            .map(|(i, item)| definition(item, &format!("word{i}")))
+            .collect();
+
+        let report = build_weekly_report(user_id, week, &days, &words, &definitions);
+
+        assert_eq!(report.period_start, date("2025-01-06"));
+        assert_eq!(report.period_end, date("2025-01-12"));
+        assert_eq!(
+            report.stats,
+            WeeklyStats {
+                items_learned:   16,
+                items_reviewed:  0,
+                items_mastered:  6,
+                study_minutes:   75,
+                study_days:      3,
+                sessions:        3,
+                goals_completed: 2,
+                accuracy:        Some(22.0 / 30.0),
+            }
+        );
+        assert_eq!(
+            report.highlights,
+            WeeklyHighlights {
+                best_day:         Some(BestDay {
+                    date:          date("2025-01-07"),
+                    study_minutes: 30,
+                    items_learned: 7,
+                }),
+                longest_run_days: 2,
+            }
+        );
+        // 上位5件のうち、定義が見つからない word4 は載せない
+        let spellings: Vec<&str> = report
+            .new_mastered_words
+            .iter()
+            .map(|word| word.spelling.as_str())
+            .collect();
+        assert_eq!(spellings, vec!["word1", "word2", "word5", "word0"]);
+        assert_eq!(
+            report.new_mastered_words[0].definition.as_deref(),
+            Some("definition of word1")
+        );
+    }
+
+    #[test]
+    fn test_week_boundary_spans_new_year() {
+        let user_id = Uuid::new_v4();
+        // 2020-W53 は 2020-12-28（月）から 2021-01-03（日）まで
+        let week: IsoWeek = "2020-W53".parse().unwrap();
+        let days = vec![
+            day(user_id, "2020-12-27", 10, 1, 1, 1),
+            day(user_id, "2020-12-31", 10, 1, 1, 1),
+            day(user_id, "2021-01-01", 10, 1, 1, 1),
+            day(user_id, "2021-01-03", 10, 1, 1, 1),
+            day(user_id, "2021-01-04", 10, 1, 1, 1),
+        ];
+        let words = vec![
+            mastered(user_id, 0.9, "2021-01-03T23:59:59Z"),
+            mastered(user_id, 0.9, "2021-01-04T00:00:00Z"),
+        ];
+
+        let report = build_weekly_report(user_id, week, &days, &words, &[]);
+
+        assert_eq!(report.stats.study_days, 3);
+        assert_eq!(report.stats.items_mastered, 1);
+        assert_eq!(report.highlights.longest_run_days, 2);
+        assert_eq!(
+            report.highlights.best_day.map(|best| best.date),
+            Some(date("2020-12-31"))
+        );
+    }
+
+    #[test]
+    fn test_week_without_answers_has_no_accuracy() {
+        let report =
+            build_weekly_report(Uuid::new_v4(), "2025-W02".parse().unwrap(), &[], &[], &[]);
+
+        assert_eq!(report.stats, WeeklyStats::default());
+        assert_eq!(report.highlights, WeeklyHighlights::default());
+    }
+}
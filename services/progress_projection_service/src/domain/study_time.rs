@@ -0,0 +1,141 @@
+//! 復習リマインダーを送る時刻の選択
+//!
+//! 学習する時間帯のモデルは Progress Query Service と共有する
+//! （[`shared_progress_context::study_time`]）。
+
+use chrono::{DateTime, FixedOffset, NaiveTime, Utc};
+pub use shared_progress_context::study_time::*;
+
+/// 復習リマインダーを送る時刻を決めた根拠
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReminderSlot {
+    /// 学習することの多い時間帯の開始時刻
+    Window {
+        at:     DateTime<Utc>,
+        window: StudyWindow,
+    },
+    /// 既定の時刻（履歴が少ない、または今日の時間帯がもう過ぎた）
+    Default { at: DateTime<Utc> },
+}
+
+impl ReminderSlot {
+    pub fn at(&self) -> DateTime<Utc> {
+        match self {
+            Self::Window { at, .. } | Self::Default { at } => *at,
+        }
+    }
+}
+
+/// ローカルの今日のうち、まだ始まっていない時間帯で最も確信度の高いものを選ぶ
+///
+/// 該当する時間帯がない場合は既定の時刻にし、既定の時刻も過ぎていれば `now`
+/// にする
+pub fn select_reminder_slot(
+    windows: &[StudyWindow],
+    now: DateTime<Utc>,
+    offset: FixedOffset,
+    default_time: NaiveTime,
+) -> ReminderSlot {
+    let today = now.with_timezone(&offset).date_naive();
+
+    let upcoming = windows
+        .iter()
+        .filter_map(|window| Some((window.start_on(today, offset)?, *window)))
+        .filter(|(at, _)| *at >= now)
+        .max_by(|(_, a), (_, b)| {
+            a.confidence
+                .total_cmp(&b.confidence)
+                .then(b.hour_of_week.cmp(&a.hour_of_week))
+        });
+    if let Some((at, window)) = upcoming {
+        return ReminderSlot::Window { at, window };
+    }
+
+    let at = local_time_on(today, default_time, offset)
+        .filter(|at| *at >= now)
+        .unwrap_or(now);
+    ReminderSlot::Default { at }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn offset_minutes(minutes: i32) -> FixedOffset {
+        FixedOffset::east_opt(minutes * 60).unwrap()
+    }
+
+    fn window(hour_of_week: u32, confidence: f64) -> StudyWindow {
+        StudyWindow {
+            hour_of_week,
+            sessions: 1,
+            confidence,
+        }
+    }
+
+    #[test]
+    fn test_slot_picks_best_upcoming_window_today() {
+        let default_time = NaiveTime::from_hms_opt(19, 0, 0).unwrap();
+        // 2025-01-06 は月曜日
+        let windows = [
+            window(7, 0.5),
+            window(20, 0.3),
+            window(21, 0.2),
+            window(24 + 20, 0.9),
+        ];
+
+        // 月曜 10:00: 朝の時間帯は過ぎているため、今日の残りで最も確信度の高い 20 時
+        let slot = select_reminder_slot(
+            &windows,
+            utc("2025-01-06T10:00:00Z"),
+            offset_minutes(0),
+            default_time,
+        );
+        assert_eq!(
+            slot,
+            ReminderSlot::Window {
+                at:     utc("2025-01-06T20:00:00Z"),
+                window: windows[1],
+            }
+        );
+
+        // 月曜 06:00: 朝の時間帯のほうが確信度が高い
+        let slot = select_reminder_slot(
+            &windows,
+            utc("2025-01-06T06:00:00Z"),
+            offset_minutes(0),
+            default_time,
+        );
+        assert_eq!(slot.at(), utc("2025-01-06T07:00:00Z"));
+
+        // 月曜 21:30: 今日の時間帯も既定の時刻も過ぎているため今すぐ
+        let now = utc("2025-01-06T21:30:00Z");
+        let slot = select_reminder_slot(&windows, now, offset_minutes(0), default_time);
+        assert_eq!(slot, ReminderSlot::Default { at: now });
+    }
+
+    #[test]
+    fn test_slot_follows_local_day_and_falls_back_to_default_time() {
+        let default_time = NaiveTime::from_hms_opt(19, 0, 0).unwrap();
+        let tokyo = offset_minutes(9 * 60);
+        // 東京の火曜 08:00 の時間帯
+        let windows = [window(24 + 8, 0.6)];
+
+        // UTC 月曜 22:00 = 東京の火曜 07:00 → 東京 08:00（UTC 23:00）
+        let slot = select_reminder_slot(&windows, utc("2025-01-06T22:00:00Z"), tokyo, default_time);
+        assert_eq!(slot.at(), utc("2025-01-06T23:00:00Z"));
+
+        // 履歴が少ないユーザーは東京の 19:00（UTC 10:00）
+        let slot = select_reminder_slot(&[], utc("2025-01-06T22:00:00Z"), tokyo, default_time);
+        assert_eq!(
+            slot,
+            ReminderSlot::Default {
+                at: utc("2025-01-07T10:00:00Z"),
+            }
+        );
+    }
+}
@@ -40,6 +40,18 @@ impl MasteryLevel {
             _ => Self::Beginner,
         }
     }
+
+    /// Read Model に保存した数値から復元する
+    pub fn from_i32(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Beginner),
+            1 => Some(Self::Learning),
+            2 => Some(Self::Familiar),
+            3 => Some(Self::Proficient),
+            4 => Some(Self::Mastered),
+            _ => None,
+        }
+    }
 }
 
 /// ストリーク状態
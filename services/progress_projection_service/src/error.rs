@@ -17,6 +17,15 @@ pub enum Error {
 
     #[error("Parse error: {0}")]
     Parse(String),
+
+    #[error("Blob store error: {0}")]
+    BlobStore(#[from] shared_blob_store::BlobStoreError),
+
+    #[error("Report error: {0}")]
+    Report(#[from] shared_progress_context::report::ReportError),
+
+    #[error("Notification error: {0}")]
+    Notification(String),
 }
 
 impl From<std::num::ParseIntError> for Error {
@@ -37,6 +46,12 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<shared_integration_events::IntegrationEventError> for Error {
+    fn from(err: shared_integration_events::IntegrationEventError) -> Self {
+        Error::Notification(err.to_string())
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 // 後方互換性のため
@@ -1,5 +1,7 @@
 //! インフラストラクチャ層
 
+pub mod notification_publisher;
 pub mod repositories;
 
+pub use notification_publisher::*;
 pub use repositories::*;
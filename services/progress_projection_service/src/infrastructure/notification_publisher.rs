@@ -0,0 +1,118 @@
+//! EventBus への通知リクエストの発行
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shared_integration_events::{IntegrationEnvelope, NotificationRequested, TopicRegistry};
+use shared_kernel::EventBus;
+
+use crate::{
+    error::{Error, Result},
+    ports::outbound::NotificationPublisher,
+};
+
+/// 通知リクエストを統合イベントの封筒に入れて EventBus に発行する
+///
+/// 発行先のトピックは [`TopicRegistry::with_integration_events`] の
+/// ルーティングに従う。同じユーザーの通知は発行順に配信する
+pub struct EventBusNotificationPublisher<B> {
+    bus:    Arc<B>,
+    topics: TopicRegistry,
+}
+
+impl<B: EventBus> EventBusNotificationPublisher<B> {
+    pub fn new(bus: Arc<B>) -> Self {
+        Self {
+            bus,
+            topics: TopicRegistry::with_integration_events(),
+        }
+    }
+}
+
+#[async_trait]
+impl<B: EventBus> NotificationPublisher for EventBusNotificationPublisher<B> {
+    async fn publish(&self, request: &NotificationRequested) -> Result<()> {
+        let envelope = IntegrationEnvelope::wrap(request)?;
+        let topic = self.topics.route(&envelope)?;
+
+        self.bus
+            .publish_ordered(topic, &envelope.to_bytes()?, &envelope.aggregate_id)
+            .await
+            .map_err(|e| Error::Notification(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use shared_integration_events::{NotificationCategory, NotificationChannel, OwningContext};
+    use shared_kernel::EventError;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct RecordingBus {
+        published: Mutex<Vec<(String, Vec<u8>, String)>>,
+    }
+
+    #[async_trait]
+    impl EventBus for RecordingBus {
+        async fn publish(&self, topic: &str, event: &[u8]) -> std::result::Result<(), EventError> {
+            self.publish_ordered(topic, event, "").await
+        }
+
+        async fn publish_ordered(
+            &self,
+            topic: &str,
+            event: &[u8],
+            ordering_key: &str,
+        ) -> std::result::Result<(), EventError> {
+            self.published.lock().unwrap().push((
+                topic.to_string(),
+                event.to_vec(),
+                ordering_key.to_string(),
+            ));
+            Ok(())
+        }
+
+        async fn subscribe<F>(
+            &self,
+            _topic: &str,
+            _handler: F,
+        ) -> std::result::Result<(), EventError>
+        where
+            F: Fn(&[u8]) -> std::result::Result<(), EventError> + Send + Sync + 'static,
+        {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_routes_the_envelope_to_the_notification_topic() {
+        let bus = Arc::new(RecordingBus::default());
+        let publisher = EventBusNotificationPublisher::new(bus.clone());
+        let request = NotificationRequested::new(
+            "user-1",
+            NotificationCategory::WeeklyReport,
+            NotificationChannel::Email,
+            "weekly_report",
+            OwningContext::Progress,
+        );
+
+        publisher.publish(&request).await.unwrap();
+
+        let published = bus.published.lock().unwrap();
+        assert_eq!(published.len(), 1);
+        let (topic, bytes, ordering_key) = &published[0];
+        assert_eq!(topic, "platform-notifications");
+        assert_eq!(
+            IntegrationEnvelope::decode::<NotificationRequested>(bytes).unwrap(),
+            request
+        );
+        assert_eq!(
+            ordering_key,
+            &IntegrationEnvelope::wrap(&request).unwrap().aggregate_id
+        );
+    }
+}
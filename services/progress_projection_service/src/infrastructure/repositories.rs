@@ -1,9 +1,13 @@
 //! リポジトリ実装
 
+pub mod due_review_counter;
 pub mod event_store_reader;
 pub mod projection_state_store;
 pub mod read_model_repository;
+pub mod vocabulary_lookup;
 
+pub use due_review_counter::*;
 pub use event_store_reader::*;
 pub use projection_state_store::*;
 pub use read_model_repository::*;
+pub use vocabulary_lookup::*;
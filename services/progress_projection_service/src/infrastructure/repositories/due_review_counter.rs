@@ -0,0 +1,44 @@
+//! Algorithm Service からの復習対象の件数の取得
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    error::{Error, Result},
+    ports::outbound::DueReviewCounter,
+};
+
+/// Algorithm Service の学習状態（`learning_item_states`）を読む
+///
+/// GetDueItems の `total_due_count`
+/// と同じく、次の復習日時が来ているアイテムを数える
+pub struct PostgresDueReviewCounter {
+    pool: PgPool,
+}
+
+impl PostgresDueReviewCounter {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DueReviewCounter for PostgresDueReviewCounter {
+    async fn count_due(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<u32> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM learning_item_states
+            WHERE user_id = $1 AND next_review_date <= $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(u32::try_from(count).unwrap_or(u32::MAX))
+    }
+}
@@ -1,6 +1,7 @@
 //! イベントストア読み取り実装
 
 use async_trait::async_trait;
+use shared_cqrs::{CqrsError, EventFeed, PositionedEvent};
 use sqlx::PgPool;
 
 use crate::{
@@ -97,3 +98,20 @@ impl EventStoreReader for PostgresEventStoreReader {
         Ok(events)
     }
 }
+
+/// プロジェクションの実行基盤（`shared_cqrs`）が位置順に読み込む
+#[async_trait]
+impl EventFeed for PostgresEventStoreReader {
+    async fn read_after(
+        &self,
+        position: i64,
+        limit: usize,
+    ) -> shared_cqrs::Result<Vec<PositionedEvent>> {
+        let events = self
+            .read_events(position, limit)
+            .await
+            .map_err(CqrsError::handler)?;
+
+        Ok(events.into_iter().map(PositionedEvent::from).collect())
+    }
+}
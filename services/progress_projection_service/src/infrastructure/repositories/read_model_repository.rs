@@ -1,14 +1,20 @@
 //! Read Model リポジトリ実装
 
 use async_trait::async_trait;
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
+use shared_progress_context::collection::CollectionMemberStats;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::{
     domain::*,
     error::{Error, Result},
-    ports::outbound::ReadModelRepository,
+    ports::outbound::{
+        CollectionStatsRepository,
+        ReadModelRepository,
+        StudyTimeRepository,
+        WeeklyReportSource,
+    },
 };
 
 /// PostgreSQL Read Model リポジトリ
@@ -239,9 +245,9 @@ impl ReadModelRepository for PostgresReadModelRepository {
             INSERT INTO vocabulary_item_progress (
                 user_id, vocabulary_item_id, attempts_count, correct_count,
                 last_attempt_date, last_accuracy, average_accuracy, mastery_level,
-                time_spent_seconds, created_at, updated_at
+                time_spent_seconds, mastered_at, created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             ON CONFLICT (user_id, vocabulary_item_id)
             DO UPDATE SET
                 attempts_count = EXCLUDED.attempts_count,
@@ -251,6 +257,7 @@ impl ReadModelRepository for PostgresReadModelRepository {
                 average_accuracy = EXCLUDED.average_accuracy,
                 mastery_level = EXCLUDED.mastery_level,
                 time_spent_seconds = EXCLUDED.time_spent_seconds,
+                mastered_at = COALESCE(vocabulary_item_progress.mastered_at, EXCLUDED.mastered_at),
                 updated_at = EXCLUDED.updated_at
             "#,
             progress.user_id,
@@ -262,6 +269,7 @@ impl ReadModelRepository for PostgresReadModelRepository {
             progress.average_accuracy,
             progress.mastery_level,
             progress.time_spent_seconds,
+            progress.mastered_at,
             progress.created_at,
             progress.updated_at
         )
@@ -279,7 +287,10 @@ impl ReadModelRepository for PostgresReadModelRepository {
     ) -> Result<Option<VocabularyItemProgress>> {
         let record = sqlx::query!(
             r#"
-            SELECT * FROM vocabulary_item_progress 
+            SELECT user_id, vocabulary_item_id, attempts_count, correct_count,
+                   last_attempt_date, last_accuracy, average_accuracy, mastery_level,
+                   time_spent_seconds, mastered_at, created_at, updated_at
+            FROM vocabulary_item_progress
             WHERE user_id = $1 AND vocabulary_item_id = $2
             "#,
             user_id,
@@ -299,6 +310,7 @@ impl ReadModelRepository for PostgresReadModelRepository {
             average_accuracy:   r.average_accuracy,
             mastery_level:      r.mastery_level,
             time_spent_seconds: r.time_spent_seconds,
+            mastered_at:        r.mastered_at,
             created_at:         r.created_at,
             updated_at:         r.updated_at,
         }))
@@ -359,4 +371,502 @@ impl ReadModelRepository for PostgresReadModelRepository {
             })
             .collect())
     }
+
+    async fn save_item_mastery_history(&self, history: &ItemMasteryHistory) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO item_mastery_history (
+                user_id, snapshot_date, beginner_count, learning_count,
+                familiar_count, proficient_count, mastered_count, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (user_id, snapshot_date)
+            DO UPDATE SET
+                beginner_count = EXCLUDED.beginner_count,
+                learning_count = EXCLUDED.learning_count,
+                familiar_count = EXCLUDED.familiar_count,
+                proficient_count = EXCLUDED.proficient_count,
+                mastered_count = EXCLUDED.mastered_count,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(history.user_id)
+        .bind(history.snapshot_date)
+        .bind(history.breakdown.beginner)
+        .bind(history.breakdown.learning)
+        .bind(history.breakdown.familiar)
+        .bind(history.breakdown.proficient)
+        .bind(history.breakdown.mastered)
+        .bind(history.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(())
+    }
+
+    async fn get_item_mastery_history_as_of(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<Option<ItemMasteryHistory>> {
+        let record =
+            sqlx::query_as::<_, (Uuid, NaiveDate, i32, i32, i32, i32, i32, DateTime<Utc>)>(
+                r#"
+            SELECT user_id, snapshot_date, beginner_count, learning_count,
+                   familiar_count, proficient_count, mastered_count, updated_at
+            FROM item_mastery_history
+            WHERE user_id = $1 AND snapshot_date <= $2
+            ORDER BY snapshot_date DESC
+            LIMIT 1
+            "#,
+            )
+            .bind(user_id)
+            .bind(date)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(Error::Database)?;
+
+        Ok(record.map(
+            |(
+                user_id,
+                snapshot_date,
+                beginner,
+                learning,
+                familiar,
+                proficient,
+                mastered,
+                updated_at,
+            )| {
+                ItemMasteryHistory {
+                    user_id,
+                    snapshot_date,
+                    breakdown: MasteryBreakdown {
+                        beginner,
+                        learning,
+                        familiar,
+                        proficient,
+                        mastered,
+                    },
+                    updated_at,
+                }
+            },
+        ))
+    }
+
+    async fn save_streak_history(&self, history: &StreakHistory) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO streak_history (
+                user_id, snapshot_date, current_streak_days, longest_streak_days, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id, snapshot_date)
+            DO UPDATE SET
+                current_streak_days = EXCLUDED.current_streak_days,
+                longest_streak_days = EXCLUDED.longest_streak_days,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(history.user_id)
+        .bind(history.snapshot_date)
+        .bind(history.current_streak_days)
+        .bind(history.longest_streak_days)
+        .bind(history.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(())
+    }
+
+    async fn get_history_users_before(
+        &self,
+        kind: HistoryKind,
+        before: NaiveDate,
+    ) -> Result<Vec<Uuid>> {
+        let query = format!(
+            "SELECT DISTINCT user_id FROM {} WHERE snapshot_date < $1",
+            kind.table_name()
+        );
+        sqlx::query_scalar(&query)
+            .bind(before)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(Error::Database)
+    }
+
+    async fn get_history_dates(&self, kind: HistoryKind, user_id: Uuid) -> Result<Vec<NaiveDate>> {
+        let query = format!(
+            "SELECT snapshot_date FROM {} WHERE user_id = $1 ORDER BY snapshot_date",
+            kind.table_name()
+        );
+        sqlx::query_scalar(&query)
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(Error::Database)
+    }
+
+    async fn delete_history(
+        &self,
+        kind: HistoryKind,
+        user_id: Uuid,
+        dates: &[NaiveDate],
+    ) -> Result<u64> {
+        let query = format!(
+            "DELETE FROM {} WHERE user_id = $1 AND snapshot_date = ANY($2)",
+            kind.table_name()
+        );
+        let result = sqlx::query(&query)
+            .bind(user_id)
+            .bind(dates)
+            .execute(&self.pool)
+            .await
+            .map_err(Error::Database)?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl WeeklyReportSource for PostgresReadModelRepository {
+    async fn get_active_users(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Uuid>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT user_id FROM daily_progress
+            WHERE date BETWEEN $1 AND $2
+            ORDER BY user_id
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)
+    }
+
+    async fn get_daily_progress_between(
+        &self,
+        user_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DailyProgress>> {
+        sqlx::query_as::<_, DailyProgress>(
+            r#"
+            SELECT user_id, date, items_learned, items_reviewed, items_mastered,
+                   correct_answers, total_answers, study_minutes, sessions_count,
+                   goal_completed, created_at, updated_at
+            FROM daily_progress
+            WHERE user_id = $1 AND date BETWEEN $2 AND $3
+            ORDER BY date
+            "#,
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)
+    }
+
+    async fn get_items_mastered_between(
+        &self,
+        user_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<VocabularyItemProgress>> {
+        sqlx::query_as::<_, VocabularyItemProgress>(
+            r#"
+            SELECT user_id, vocabulary_item_id, attempts_count, correct_count,
+                   last_attempt_date, last_accuracy, average_accuracy, mastery_level,
+                   time_spent_seconds, mastered_at, created_at, updated_at
+            FROM vocabulary_item_progress
+            WHERE user_id = $1 AND mastered_at >= $2 AND mastered_at < $3
+            ORDER BY mastered_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)
+    }
+}
+
+#[async_trait]
+impl CollectionStatsRepository for PostgresReadModelRepository {
+    async fn add_collection_item(&self, collection_id: Uuid, item_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO collection_items (collection_id, vocabulary_item_id)
+            VALUES ($1, $2)
+            ON CONFLICT (collection_id, vocabulary_item_id) DO NOTHING
+            "#,
+        )
+        .bind(collection_id)
+        .bind(item_id)
+        .execute(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(())
+    }
+
+    async fn remove_collection_item(&self, collection_id: Uuid, item_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM collection_items
+            WHERE collection_id = $1 AND vocabulary_item_id = $2
+            "#,
+        )
+        .bind(collection_id)
+        .bind(item_id)
+        .execute(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(())
+    }
+
+    async fn get_collection_items(&self, collection_id: Uuid) -> Result<Vec<Uuid>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT vocabulary_item_id FROM collection_items
+            WHERE collection_id = $1
+            "#,
+        )
+        .bind(collection_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)
+    }
+
+    async fn add_member(
+        &self,
+        collection_id: Uuid,
+        user_id: Uuid,
+        joined_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO collection_members (collection_id, user_id, joined_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (collection_id, user_id) DO NOTHING
+            "#,
+        )
+        .bind(collection_id)
+        .bind(user_id)
+        .bind(joined_at)
+        .execute(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(())
+    }
+
+    async fn remove_member(&self, collection_id: Uuid, user_id: Uuid) -> Result<()> {
+        let mut tx = self.pool.begin().await.map_err(Error::Database)?;
+
+        for table in ["collection_members", "collection_member_stats"] {
+            sqlx::query(&format!(
+                "DELETE FROM {table} WHERE collection_id = $1 AND user_id = $2"
+            ))
+            .bind(collection_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(Error::Database)?;
+        }
+
+        tx.commit().await.map_err(Error::Database)
+    }
+
+    async fn set_stats_visibility(&self, user_id: Uuid, visible: bool) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO collection_stats_privacy (user_id, hidden, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (user_id)
+            DO UPDATE SET hidden = EXCLUDED.hidden, updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(!visible)
+        .execute(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(())
+    }
+
+    async fn get_member_collections_with_item(
+        &self,
+        user_id: Uuid,
+        item_id: Uuid,
+    ) -> Result<Vec<Uuid>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT m.collection_id
+            FROM collection_members m
+            JOIN collection_items i ON i.collection_id = m.collection_id
+            WHERE m.user_id = $1 AND i.vocabulary_item_id = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(item_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)
+    }
+
+    async fn get_members_with_item_progress(
+        &self,
+        collection_id: Uuid,
+        item_id: Uuid,
+    ) -> Result<Vec<Uuid>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT m.user_id
+            FROM collection_members m
+            JOIN vocabulary_item_progress p
+              ON p.user_id = m.user_id AND p.vocabulary_item_id = $2
+            WHERE m.collection_id = $1
+            "#,
+        )
+        .bind(collection_id)
+        .bind(item_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)
+    }
+
+    async fn get_item_progress_for_items(
+        &self,
+        user_id: Uuid,
+        item_ids: &[Uuid],
+    ) -> Result<Vec<VocabularyItemProgress>> {
+        sqlx::query_as::<_, VocabularyItemProgress>(
+            r#"
+            SELECT user_id, vocabulary_item_id, attempts_count, correct_count,
+                   last_attempt_date, last_accuracy, average_accuracy, mastery_level,
+                   time_spent_seconds, mastered_at, created_at, updated_at
+            FROM vocabulary_item_progress
+            WHERE user_id = $1 AND vocabulary_item_id = ANY($2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(item_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)
+    }
+
+    async fn save_member_stats(&self, stats: &CollectionMemberStats) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO collection_member_stats (
+                collection_id, user_id, items_studied, items_mastered,
+                last_activity_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, NOW())
+            ON CONFLICT (collection_id, user_id)
+            DO UPDATE SET
+                items_studied = EXCLUDED.items_studied,
+                items_mastered = EXCLUDED.items_mastered,
+                last_activity_at = EXCLUDED.last_activity_at,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(stats.collection_id)
+        .bind(stats.user_id)
+        .bind(stats.items_studied)
+        .bind(stats.items_mastered)
+        .bind(stats.last_activity_at)
+        .execute(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StudyTimeRepository for PostgresReadModelRepository {
+    async fn record_session_start(&self, user_id: Uuid, quarter_of_week: u32) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO study_time_histogram (user_id, quarter_of_week, sessions, updated_at)
+            VALUES ($1, $2, 1, NOW())
+            ON CONFLICT (user_id, quarter_of_week)
+            DO UPDATE SET
+                sessions = study_time_histogram.sessions + 1,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(i32::try_from(quarter_of_week).map_err(|e| Error::Parse(e.to_string()))?)
+        .execute(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(())
+    }
+
+    async fn get_study_time_quarters(&self, user_id: Uuid) -> Result<Vec<(u32, u32)>> {
+        let rows = sqlx::query_as::<_, (i32, i32)>(
+            r#"
+            SELECT quarter_of_week, sessions FROM study_time_histogram
+            WHERE user_id = $1
+            ORDER BY quarter_of_week
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(quarter, sessions)| {
+                Some((u32::try_from(quarter).ok()?, u32::try_from(sessions).ok()?))
+            })
+            .collect())
+    }
+
+    async fn get_study_time_users(&self) -> Result<Vec<Uuid>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT user_id FROM study_time_histogram
+            ORDER BY user_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)
+    }
+
+    async fn record_review_reminder(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+        scheduled_for: DateTime<Utc>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO review_reminders (user_id, reminder_date, scheduled_for)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, reminder_date) DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .bind(date)
+        .bind(scheduled_for)
+        .execute(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(result.rows_affected() == 1)
+    }
 }
@@ -0,0 +1,48 @@
+//! Vocabulary Read Model からの語彙の参照
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    domain::WordDefinition,
+    error::{Error, Result},
+    ports::outbound::VocabularyLookup,
+};
+
+/// Vocabulary Projection Service の Read Model（`vocabulary_items_read`）を読む
+pub struct PostgresVocabularyLookup {
+    pool: PgPool,
+}
+
+impl PostgresVocabularyLookup {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl VocabularyLookup for PostgresVocabularyLookup {
+    async fn lookup_words(&self, item_ids: &[Uuid]) -> Result<Vec<WordDefinition>> {
+        let rows = sqlx::query_as::<_, (Uuid, String, Option<String>)>(
+            r#"
+            SELECT item_id, spelling, definition
+            FROM vocabulary_items_read
+            WHERE item_id = ANY($1) AND NOT is_deleted
+            "#,
+        )
+        .bind(item_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(Error::Database)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(item_id, spelling, definition)| WordDefinition {
+                item_id,
+                spelling,
+                definition,
+            })
+            .collect())
+    }
+}
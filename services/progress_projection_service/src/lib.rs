@@ -9,3 +9,6 @@ pub mod error;
 pub mod infrastructure;
 pub mod ports;
 pub mod processor;
+
+/// Read Model のマイグレーション
+pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
@@ -4,16 +4,6 @@ use async_trait::async_trait;
 
 use crate::error::Result;
 
-/// イベントプロセッサーポート
-#[async_trait]
-pub trait EventProcessor: Send + Sync {
-    /// イベントを処理
-    async fn process_events(&self) -> Result<()>;
-
-    /// 特定のプロジェクションを再構築
-    async fn rebuild_projection(&self, projection_name: &str) -> Result<()>;
-}
-
 /// プロジェクション管理ポート
 #[async_trait]
 pub trait ProjectionManager: Send + Sync {
@@ -2,6 +2,11 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDate, Utc};
+pub use shared_blob_store::BlobStore;
+use shared_cqrs::PositionedEvent;
+use shared_event_store::StoredEvent;
+use shared_integration_events::NotificationRequested;
+use shared_progress_context::collection::CollectionMemberStats;
 use uuid::Uuid;
 
 use crate::{domain::*, error::Result};
@@ -50,23 +55,153 @@ pub trait ReadModelRepository: Send + Sync {
     // アチーブメント
     async fn save_achievement(&self, achievement: &Achievement) -> Result<()>;
     async fn get_user_achievements(&self, user_id: Uuid) -> Result<Vec<Achievement>>;
+
+    // 履歴（日付ごとの時点の値）
+    async fn save_item_mastery_history(&self, history: &ItemMasteryHistory) -> Result<()>;
+    async fn get_item_mastery_history_as_of(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<Option<ItemMasteryHistory>>;
+    async fn save_streak_history(&self, history: &StreakHistory) -> Result<()>;
+
+    /// 指定日より前の履歴行を持つユーザー
+    async fn get_history_users_before(
+        &self,
+        kind: HistoryKind,
+        before: NaiveDate,
+    ) -> Result<Vec<Uuid>>;
+    /// ユーザーの履歴行の日付（昇順）
+    async fn get_history_dates(&self, kind: HistoryKind, user_id: Uuid) -> Result<Vec<NaiveDate>>;
+    async fn delete_history(
+        &self,
+        kind: HistoryKind,
+        user_id: Uuid,
+        dates: &[NaiveDate],
+    ) -> Result<u64>;
 }
 
-/// プロジェクション状態ストアポート
+/// 週次レポートの元データの取得ポート
 #[async_trait]
-pub trait ProjectionStateStore: Send + Sync {
-    /// プロジェクション状態を保存
-    async fn save_state(&self, state: &ProjectionState) -> Result<()>;
+pub trait WeeklyReportSource: Send + Sync {
+    /// 期間内（両端の日を含む）に日次進捗があるユーザー
+    async fn get_active_users(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Uuid>>;
+
+    /// 期間内（両端の日を含む）の日次進捗（日付の昇順）
+    async fn get_daily_progress_between(
+        &self,
+        user_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<DailyProgress>>;
+
+    /// `from` 以降 `to` より前に初めて習得した語彙アイテムの進捗
+    async fn get_items_mastered_between(
+        &self,
+        user_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<VocabularyItemProgress>>;
+}
+
+/// 語彙の見出し語と定義の取得ポート（Vocabulary の Read Model）
+#[async_trait]
+pub trait VocabularyLookup: Send + Sync {
+    /// 見つかったアイテムだけを返す（削除済みのアイテムは含めない）
+    async fn lookup_words(&self, item_ids: &[Uuid]) -> Result<Vec<WordDefinition>>;
+}
+
+/// 通知リクエストの発行ポート
+#[async_trait]
+pub trait NotificationPublisher: Send + Sync {
+    async fn publish(&self, request: &NotificationRequested) -> Result<()>;
+}
+
+/// 学習する時間帯の Read Model ポート
+#[async_trait]
+pub trait StudyTimeRepository: Send + Sync {
+    /// UTC の15分枠のセッション数を1つ増やす
+    async fn record_session_start(&self, user_id: Uuid, quarter_of_week: u32) -> Result<()>;
+
+    /// UTC の15分枠ごとの `(枠, セッション数)`
+    async fn get_study_time_quarters(&self, user_id: Uuid) -> Result<Vec<(u32, u32)>>;
+
+    /// セッションを開始したことのあるユーザー
+    async fn get_study_time_users(&self) -> Result<Vec<Uuid>>;
+
+    /// 復習リマインダーの送信を記録する
+    ///
+    /// 同じ `(ユーザー, ローカルの日付)` がすでに記録されていれば false を返す
+    async fn record_review_reminder(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+        scheduled_for: DateTime<Utc>,
+    ) -> Result<bool>;
+}
+
+/// 復習対象の件数の取得ポート（Algorithm Service の GetDueItems の
+/// `total_due_count`）
+#[async_trait]
+pub trait DueReviewCounter: Send + Sync {
+    /// `at` の時点で復習期限が来ているアイテムの数
+    async fn count_due(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<u32>;
+}
 
-    /// プロジェクション状態を取得
-    async fn get_state(&self, projection_name: &str) -> Result<Option<ProjectionState>>;
+/// 共有コレクションの統計の Read Model ポート
+#[async_trait]
+pub trait CollectionStatsRepository: Send + Sync {
+    // コレクションの構成
+    async fn add_collection_item(&self, collection_id: Uuid, item_id: Uuid) -> Result<()>;
+    async fn remove_collection_item(&self, collection_id: Uuid, item_id: Uuid) -> Result<()>;
+    async fn get_collection_items(&self, collection_id: Uuid) -> Result<Vec<Uuid>>;
 
+    // メンバー
+    async fn add_member(
+        &self,
+        collection_id: Uuid,
+        user_id: Uuid,
+        joined_at: DateTime<Utc>,
+    ) -> Result<()>;
+    /// メンバーと、そのメンバーの統計を削除する
+    async fn remove_member(&self, collection_id: Uuid, user_id: Uuid) -> Result<()>;
+    /// 他のメンバーに統計を公開するか（ユーザー単位の設定）
+    async fn set_stats_visibility(&self, user_id: Uuid, visible: bool) -> Result<()>;
+
+    /// ユーザーがメンバーで、アイテムを含むコレクション
+    async fn get_member_collections_with_item(
+        &self,
+        user_id: Uuid,
+        item_id: Uuid,
+    ) -> Result<Vec<Uuid>>;
+    /// アイテムを学習したことのあるコレクションのメンバー
+    async fn get_members_with_item_progress(
+        &self,
+        collection_id: Uuid,
+        item_id: Uuid,
+    ) -> Result<Vec<Uuid>>;
+
+    // 統計
+    async fn get_item_progress_for_items(
+        &self,
+        user_id: Uuid,
+        item_ids: &[Uuid],
+    ) -> Result<Vec<VocabularyItemProgress>>;
+    async fn save_member_stats(&self, stats: &CollectionMemberStats) -> Result<()>;
+}
+
+/// プロジェクション状態ストアポート
+///
+/// チェックポイントは `shared_cqrs::PostgresCheckpointStore` が同じテーブルに
+/// 書き込む。このポートは状態の一覧だけを読む
+#[async_trait]
+pub trait ProjectionStateStore: Send + Sync {
     /// すべてのプロジェクション状態を取得
     async fn get_all_states(&self) -> Result<Vec<ProjectionState>>;
 }
 
 /// イベント
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Event {
     pub event_id:      Uuid,
     pub stream_id:     String,
@@ -76,3 +211,95 @@ pub struct Event {
     pub position:      i64,
     pub occurred_at:   DateTime<Utc>,
 }
+
+/// ストリーム ID（`{種類}-{UUID}`）を集約の種類と ID に分ける
+fn split_stream_id(stream_id: &str) -> Option<(&str, Uuid)> {
+    let (aggregate_type, id) = stream_id.split_at_checked(stream_id.len().checked_sub(36)?)?;
+    Some((aggregate_type.strip_suffix('-')?, id.parse().ok()?))
+}
+
+/// プロジェクションの実行基盤（`shared_cqrs`）に渡す形に変換する
+///
+/// ストリーム ID は集約の種類と ID に分けて持たせ、[`Event::from`] で元に戻す
+impl From<Event> for PositionedEvent {
+    fn from(event: Event) -> Self {
+        let (aggregate_type, aggregate_id) = split_stream_id(&event.stream_id)
+            .map_or((event.stream_id.clone(), Uuid::nil()), |(kind, id)| {
+                (kind.to_string(), id)
+            });
+
+        Self {
+            position: event.position,
+            event:    StoredEvent {
+                event_id: event.event_id,
+                tenant_id: None,
+                aggregate_id,
+                aggregate_type,
+                event_type: event.event_type,
+                event_version: u32::try_from(event.event_version).unwrap_or(u32::MAX),
+                event_data: event.event_data,
+                metadata: None,
+                occurred_at: event.occurred_at,
+                created_at: event.occurred_at,
+            },
+        }
+    }
+}
+
+impl From<&PositionedEvent> for Event {
+    fn from(positioned: &PositionedEvent) -> Self {
+        let event = &positioned.event;
+        let stream_id = if event.aggregate_id.is_nil() {
+            event.aggregate_type.clone()
+        } else {
+            format!("{}-{}", event.aggregate_type, event.aggregate_id)
+        };
+
+        Self {
+            event_id: event.event_id,
+            stream_id,
+            event_type: event.event_type.clone(),
+            event_data: event.event_data.clone(),
+            event_version: i64::from(event.event_version),
+            position: positioned.position,
+            occurred_at: event.occurred_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn event(stream_id: &str) -> Event {
+        Event {
+            event_id:      Uuid::new_v4(),
+            stream_id:     stream_id.to_string(),
+            event_type:    "ItemCompleted".to_string(),
+            event_data:    json!({ "user_id": Uuid::new_v4() }),
+            event_version: 3,
+            position:      3,
+            occurred_at:   "2025-10-01T12:00:00Z".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_event_round_trips_through_the_projection_runner_format() {
+        let user_id = Uuid::new_v4();
+        for stream_id in [
+            format!("progress-{user_id}"),
+            format!("collection-stats-visibility-{user_id}"),
+            "legacy".to_string(),
+        ] {
+            let original = event(&stream_id);
+            let positioned = PositionedEvent::from(original.clone());
+            assert_eq!(Event::from(&positioned), original);
+        }
+
+        let positioned = PositionedEvent::from(event(&format!("progress-{user_id}")));
+        assert_eq!(positioned.event.aggregate_type, "progress");
+        assert_eq!(positioned.event.aggregate_id, user_id);
+    }
+}
@@ -1,20 +1,50 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
+use chrono::Utc;
+use shared_blob_store::FileSystemBlobStore;
+use shared_cqrs::{ErrorPolicy, PostgresCheckpointStore, ProjectionRunner};
+use shared_integration_events::{
+    CachedPreferenceSource,
+    NotificationGate,
+    NotificationPreferenceSource,
+};
+use shared_user_context::UserServicePreferenceSource;
 use sqlx::PgPool;
 use tokio::time;
 use tracing::{error, info};
 
 use crate::{
-    application::{EventProcessorService, ProgressEventHandler},
-    config::Config,
-    infrastructure::repositories::{
-        PostgresEventStoreReader,
-        PostgresProjectionStateStore,
-        PostgresReadModelRepository,
+    application::{
+        CollectionStatsProjector,
+        HistoryCompactionService,
+        ProgressEventHandler,
+        ProgressProjector,
+        ReviewReminderScheduler,
+        StudyTimeProjector,
+        StudyTimeService,
+        WeeklyReportGenerator,
     },
-    ports::inbound::EventProcessor,
+    config::{Config, NotificationsConfig, ProcessorConfig},
+    domain::CompactionPolicy,
+    error::Error,
+    infrastructure::{
+        EventBusNotificationPublisher,
+        repositories::{
+            PostgresDueReviewCounter,
+            PostgresEventStoreReader,
+            PostgresReadModelRepository,
+            PostgresVocabularyLookup,
+        },
+    },
+    ports::outbound::NotificationPublisher,
 };
 
+/// 通知設定の取得元（User Service、キャッシュ付き）
+pub type PreferenceSource = Arc<dyn NotificationPreferenceSource>;
+
 pub async fn run(config: Config) -> crate::error::Result<()> {
     info!("イベントプロセッサー開始");
     info!("バッチサイズ: {}", config.processor.batch_size);
@@ -24,33 +54,180 @@ pub async fn run(config: Config) -> crate::error::Result<()> {
     let event_store_pool = PgPool::connect(&config.database.event_store_url).await?;
     let read_model_pool = PgPool::connect(&config.database.read_model_url).await?;
 
-    // リポジトリを作成
-    let event_store_reader = Arc::new(PostgresEventStoreReader::new(event_store_pool.clone()));
-    let state_store = Arc::new(PostgresProjectionStateStore::new(read_model_pool.clone()));
-    let read_model_repository = Arc::new(PostgresReadModelRepository::new(read_model_pool.clone()));
+    let notifications =
+        build_notification_jobs(&config.notifications, read_model_pool.clone()).await?;
+
+    ProgressProjection::build(&config.processor, event_store_pool, read_model_pool)
+        .with_notifications(notifications)
+        .run()
+        .await;
+
+    Ok(())
+}
+
+/// 通知を要求する定期ジョブ
+pub struct NotificationJobs {
+    pub weekly_reports:           WeeklyReportGenerator<PreferenceSource>,
+    pub weekly_report_interval:   Duration,
+    pub review_reminders:         ReviewReminderScheduler<PreferenceSource>,
+    pub review_reminder_interval: Duration,
+}
+
+/// 週次レポートと復習リマインダーのジョブを組み立てる
+///
+/// 通知リクエストは設定のイベントバスに発行し、通知設定は User Service から
+/// 取得する（両方のジョブで同じキャッシュを使う）
+pub async fn build_notification_jobs(
+    config: &NotificationsConfig,
+    read_model_pool: PgPool,
+) -> crate::error::Result<NotificationJobs> {
+    let vocabulary_pool = PgPool::connect(&config.vocabulary_read_model_url).await?;
+    let algorithm_pool = PgPool::connect(&config.algorithm_database_url).await?;
+    let bus = shared_event_bus::from_config(&config.event_bus)
+        .await
+        .map_err(|e| Error::Config(e.to_string()))?;
+    info!("通知の発行先: {}", bus.backend());
 
-    // イベントハンドラーを作成
-    let event_handler = Arc::new(ProgressEventHandler::new(read_model_repository));
+    let user_service = UserServicePreferenceSource::connect_lazy(config.user_service_url.clone())
+        .map_err(|e| Error::Config(format!("Invalid USER_SERVICE_URL: {e}")))?;
+    let preferences: PreferenceSource = Arc::new(CachedPreferenceSource::new(
+        user_service,
+        Duration::from_secs(config.preference_cache_ttl_secs),
+    ));
+    let publisher: Arc<dyn NotificationPublisher> =
+        Arc::new(EventBusNotificationPublisher::new(Arc::new(bus)));
+    let read_model_repository = Arc::new(PostgresReadModelRepository::new(read_model_pool));
 
-    // イベントプロセッサーを作成
-    let processor = EventProcessorService::new(
-        event_store_reader,
-        state_store,
-        event_handler,
-        config.processor.batch_size,
+    let weekly_reports = WeeklyReportGenerator::new(
+        read_model_repository.clone(),
+        Arc::new(PostgresVocabularyLookup::new(vocabulary_pool)),
+        Arc::new(FileSystemBlobStore::new(&config.report_store_dir)),
+        publisher.clone(),
+        NotificationGate::new(preferences.clone()),
     );
+    let review_reminders = ReviewReminderScheduler::new(
+        StudyTimeService::new(read_model_repository, preferences),
+        Arc::new(PostgresDueReviewCounter::new(algorithm_pool)),
+        publisher,
+    );
+
+    Ok(NotificationJobs {
+        weekly_reports,
+        weekly_report_interval: Duration::from_secs(config.weekly_report_interval_secs),
+        review_reminders,
+        review_reminder_interval: Duration::from_secs(config.review_reminder_interval_secs),
+    })
+}
+
+/// 組み立て済みのプロジェクション
+///
+/// 単独のサービスとしても、
+/// 統合バイナリ（effect_monolith）の中でも同じ構成で動かす
+pub struct ProgressProjection {
+    runner:        ProjectionRunner<ProgressProjector>,
+    compaction:    HistoryCompactionService,
+    poll_interval: Duration,
+    notifications: Option<NotificationJobs>,
+}
 
-    // イベント処理ループ
-    let mut interval = time::interval(Duration::from_millis(config.processor.poll_interval_ms));
+impl ProgressProjection {
+    /// 接続済みのプールからプロジェクションを組み立てる
+    pub fn build(
+        config: &ProcessorConfig,
+        event_store_pool: PgPool,
+        read_model_pool: PgPool,
+    ) -> Self {
+        // リポジトリを作成
+        let event_store_reader = Arc::new(PostgresEventStoreReader::new(event_store_pool));
+        let checkpoints = Arc::new(PostgresCheckpointStore::new(read_model_pool.clone()));
+        let read_model_repository = Arc::new(PostgresReadModelRepository::new(read_model_pool));
 
-    loop {
-        interval.tick().await;
+        // イベントハンドラーを作成
+        let collection_stats =
+            Arc::new(CollectionStatsProjector::new(read_model_repository.clone()));
+        let study_time = Arc::new(StudyTimeProjector::new(read_model_repository.clone()));
+        let event_handler = Arc::new(
+            ProgressEventHandler::new(read_model_repository.clone())
+                .with_collection_stats(collection_stats)
+                .with_study_time(study_time),
+        );
 
-        match processor.process_events().await {
-            Ok(_) => {},
-            Err(e) => {
-                error!("イベント処理エラー: {}", e);
+        // 履歴の間引きサービスを作成
+        let compaction = HistoryCompactionService::new(
+            read_model_repository,
+            CompactionPolicy {
+                daily_retention_days: config.history_daily_retention_days,
             },
+        );
+
+        // 処理できなかったイベントは記録して読み飛ばす
+        let runner = ProjectionRunner::new(
+            Arc::new(ProgressProjector::new(event_handler)),
+            event_store_reader,
+            checkpoints,
+        )
+        .with_batch_size(config.batch_size)
+        .with_error_policy(ErrorPolicy::Skip);
+
+        Self {
+            runner,
+            compaction,
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+            notifications: None,
         }
     }
+
+    /// 週次レポートの生成と復習リマインダーのスケジュールを有効にする
+    pub fn with_notifications(mut self, notifications: NotificationJobs) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// イベント処理ループ（終了しない）
+    pub async fn run(self) {
+        let mut interval = time::interval(self.poll_interval);
+        let mut last_compacted_on = None;
+        let mut last_reported_at: Option<Instant> = None;
+        let mut last_reminded_at: Option<Instant> = None;
+
+        loop {
+            interval.tick().await;
+
+            // 履歴の間引きは1日1回
+            let today = Utc::now().date_naive();
+            if last_compacted_on != Some(today) {
+                match self.compaction.compact(today).await {
+                    Ok(_) => last_compacted_on = Some(today),
+                    Err(e) => error!("履歴の間引きエラー: {}", e),
+                }
+            }
+
+            if let Some(jobs) = &self.notifications {
+                // 週が終わったユーザーの週次レポート（内容が変わらないレポートは書き込まない）
+                if is_due(last_reported_at, jobs.weekly_report_interval) {
+                    if let Err(e) = jobs.weekly_reports.run_due(Utc::now()).await {
+                        error!("週次レポートの生成エラー: {}", e);
+                    }
+                    last_reported_at = Some(Instant::now());
+                }
+
+                // 復習リマインダー（要求済みのユーザーは飛ばす）
+                if is_due(last_reminded_at, jobs.review_reminder_interval) {
+                    if let Err(e) = jobs.review_reminders.run_due(Utc::now()).await {
+                        error!("復習リマインダーのスケジュールエラー: {}", e);
+                    }
+                    last_reminded_at = Some(Instant::now());
+                }
+            }
+
+            if let Err(e) = self.runner.catch_up().await {
+                error!("イベント処理エラー: {}", e);
+            }
+        }
+    }
+}
+
+/// 前回の実行から `interval` が経過したか（未実行なら実行する）
+fn is_due(last_run: Option<Instant>, interval: Duration) -> bool {
+    last_run.is_none_or(|at| at.elapsed() >= interval)
 }
@@ -0,0 +1,111 @@
+//! 共有コレクションのメンバー別統計の読み出し
+//!
+//! Progress Projection Service が `(コレクション, メンバー)` ごとに集計した
+//! 統計を、メンバー向けのランキングとして返す。統計を非公開にしたユーザーは
+//! 他のメンバーのランキングには載せず、本人のランキングにだけ載せる。
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shared_progress_context::collection::{CollectionMemberStats, RankedMember, rank_members};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::Result;
+
+/// メンバーの統計と公開設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberStatsRow {
+    pub stats:  CollectionMemberStats,
+    /// 他のメンバーに統計を公開しない
+    pub hidden: bool,
+}
+
+/// 共有コレクションの統計の読み取りポート
+#[async_trait]
+pub trait CollectionStatsRepository: Send + Sync {
+    async fn is_member(&self, collection_id: Uuid, user_id: Uuid) -> Result<bool>;
+
+    /// 現在のメンバー全員の統計
+    async fn member_stats(&self, collection_id: Uuid) -> Result<Vec<MemberStatsRow>>;
+}
+
+/// `requester` から見たランキング
+///
+/// 非公開のメンバーは本人以外には表示しない。順位は表示するメンバーの中で付ける
+pub fn leaderboard_for(requester: Uuid, rows: Vec<MemberStatsRow>) -> Vec<RankedMember> {
+    rank_members(
+        rows.into_iter()
+            .filter(|row| !row.hidden || row.stats.user_id == requester)
+            .map(|row| row.stats)
+            .collect(),
+    )
+}
+
+/// PostgreSQL 実装
+pub struct PostgresCollectionStatsRepository {
+    pool: PgPool,
+}
+
+impl PostgresCollectionStatsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CollectionStatsRepository for PostgresCollectionStatsRepository {
+    async fn is_member(&self, collection_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let member = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM collection_members
+                WHERE collection_id = $1 AND user_id = $2
+            )
+            "#,
+        )
+        .bind(collection_id)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(member)
+    }
+
+    async fn member_stats(&self, collection_id: Uuid) -> Result<Vec<MemberStatsRow>> {
+        let rows = sqlx::query_as::<_, (Uuid, i32, i32, Option<DateTime<Utc>>, bool)>(
+            r#"
+            SELECT m.user_id,
+                   COALESCE(s.items_studied, 0),
+                   COALESCE(s.items_mastered, 0),
+                   s.last_activity_at,
+                   COALESCE(p.hidden, FALSE)
+            FROM collection_members m
+            LEFT JOIN collection_member_stats s
+              ON s.collection_id = m.collection_id AND s.user_id = m.user_id
+            LEFT JOIN collection_stats_privacy p ON p.user_id = m.user_id
+            WHERE m.collection_id = $1
+            "#,
+        )
+        .bind(collection_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(user_id, items_studied, items_mastered, last_activity_at, hidden)| {
+                    MemberStatsRow {
+                        stats: CollectionMemberStats {
+                            collection_id,
+                            user_id,
+                            items_studied,
+                            items_mastered,
+                            last_activity_at,
+                        },
+                        hidden,
+                    }
+                },
+            )
+            .collect())
+    }
+}
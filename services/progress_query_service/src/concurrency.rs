@@ -0,0 +1,141 @@
+//! ユーザーごとの同時実行クエリ数の制限
+//!
+//! 実行中のクエリが上限に達したユーザーのクエリは、少数だけ待ち行列に
+//! 入れて順番を待たせ、それを超えたものは即座に拒否する。
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use thiserror::Error;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// 待ち行列も満杯のため拒否した
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Too many concurrent queries for user {user_id}")]
+pub struct ConcurrencyLimitExceeded {
+    pub user_id: String,
+}
+
+struct UserSlots {
+    semaphore: Arc<Semaphore>,
+    waiting:   AtomicUsize,
+}
+
+/// ユーザーごとの同時実行数リミッター
+pub struct UserConcurrencyLimiter {
+    max_concurrent: usize,
+    max_queued:     usize,
+    users:          Mutex<HashMap<String, Arc<UserSlots>>>,
+}
+
+/// クエリの実行枠。ドロップすると解放される
+pub struct QueryPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl UserConcurrencyLimiter {
+    pub fn new(max_concurrent: usize, max_queued: usize) -> Self {
+        Self {
+            max_concurrent: max_concurrent.max(1),
+            max_queued,
+            users: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 実行枠を取得する。空きがなければ待ち行列の上限まで待つ
+    pub async fn acquire(&self, user_id: &str) -> Result<QueryPermit, ConcurrencyLimitExceeded> {
+        let slots = self.slots(user_id);
+
+        if let Ok(permit) = slots.semaphore.clone().try_acquire_owned() {
+            return Ok(QueryPermit { _permit: permit });
+        }
+
+        let queued = slots.waiting.fetch_add(1, Ordering::AcqRel);
+        if queued >= self.max_queued {
+            slots.waiting.fetch_sub(1, Ordering::AcqRel);
+            return Err(ConcurrencyLimitExceeded {
+                user_id: user_id.to_string(),
+            });
+        }
+
+        let permit = slots.semaphore.clone().acquire_owned().await;
+        slots.waiting.fetch_sub(1, Ordering::AcqRel);
+
+        // セマフォは閉じないため取得に失敗することはない
+        let permit = permit.expect("query semaphore is never closed");
+        Ok(QueryPermit { _permit: permit })
+    }
+
+    fn slots(&self, user_id: &str) -> Arc<UserSlots> {
+        let mut users = self.users.lock().unwrap();
+
+        // 実行中・待機中のクエリがないユーザーのエントリを掃除する
+        users.retain(|_, slots| {
+            Arc::strong_count(slots) > 1
+                || slots.semaphore.available_permits() < self.max_concurrent
+        });
+
+        users
+            .entry(user_id.to_string())
+            .or_insert_with(|| {
+                Arc::new(UserSlots {
+                    semaphore: Arc::new(Semaphore::new(self.max_concurrent)),
+                    waiting:   AtomicUsize::new(0),
+                })
+            })
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_queues_up_to_bound_then_rejects() {
+        let limiter = Arc::new(UserConcurrencyLimiter::new(1, 1));
+
+        let first = limiter.acquire("user-1").await.unwrap();
+
+        // 2件目は待ち行列に入る
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire("user-1").await.map(|_| ()) }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        // 3件目は待ち行列も満杯のため拒否される
+        assert_eq!(
+            limiter.acquire("user-1").await.err(),
+            Some(ConcurrencyLimitExceeded {
+                user_id: "user-1".to_string(),
+            })
+        );
+
+        // 1件目が終わると2件目が実行される
+        drop(first);
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_limits_are_per_user() {
+        let limiter = UserConcurrencyLimiter::new(1, 0);
+
+        let _first = limiter.acquire("user-1").await.unwrap();
+        assert!(limiter.acquire("user-1").await.is_err());
+        assert!(limiter.acquire("user-2").await.is_ok());
+    }
+}
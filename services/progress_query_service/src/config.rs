@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 pub struct Config {
     pub server:   ServerConfig,
     pub database: DatabaseConfig,
+    pub query:    QueryLimitsConfig,
+    pub reports:  ReportsConfig,
+    pub users:    UserServiceConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +21,32 @@ pub struct DatabaseConfig {
     pub max_connections: u32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryLimitsConfig {
+    /// クエリのネストの深さの上限
+    pub max_depth:               usize,
+    /// 1クエリの見積もりコストの上限
+    pub max_query_cost:          u64,
+    /// ユーザーごとの同時実行クエリ数
+    pub max_concurrent_per_user: usize,
+    /// 同時実行数の上限に達したときに待たせるクエリ数
+    pub max_queued_per_user:     usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportsConfig {
+    /// 週次レポートを保存する BLOB ストアのルートディレクトリ
+    pub store_dir: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserServiceConfig {
+    /// User Service（タイムゾーンを含む通知設定の取得元）の URL
+    pub url: String,
+    /// 通知設定をキャッシュする秒数
+    pub preference_cache_ttl_secs: u64,
+}
+
 impl Config {
     pub fn from_env() -> crate::error::Result<Self> {
         Ok(Config {
@@ -36,6 +65,31 @@ impl Config {
                     .unwrap_or_else(|_| "10".to_string())
                     .parse()?,
             },
+            query:    QueryLimitsConfig {
+                max_depth:               std::env::var("GRAPHQL_MAX_DEPTH")
+                    .unwrap_or_else(|_| "10".to_string())
+                    .parse()?,
+                max_query_cost:          std::env::var("GRAPHQL_MAX_QUERY_COST")
+                    .unwrap_or_else(|_| "5000".to_string())
+                    .parse()?,
+                max_concurrent_per_user: std::env::var("GRAPHQL_MAX_CONCURRENT_PER_USER")
+                    .unwrap_or_else(|_| "2".to_string())
+                    .parse()?,
+                max_queued_per_user:     std::env::var("GRAPHQL_MAX_QUEUED_PER_USER")
+                    .unwrap_or_else(|_| "4".to_string())
+                    .parse()?,
+            },
+            reports:  ReportsConfig {
+                store_dir: std::env::var("REPORT_STORE_DIR")
+                    .unwrap_or_else(|_| "./data/reports".to_string()),
+            },
+            users:    UserServiceConfig {
+                url: std::env::var("USER_SERVICE_URL")
+                    .unwrap_or_else(|_| "http://localhost:50051".to_string()),
+                preference_cache_ttl_secs: std::env::var("PREFERENCE_CACHE_TTL_SECS")
+                    .unwrap_or_else(|_| "300".to_string())
+                    .parse()?,
+            },
         })
     }
 }
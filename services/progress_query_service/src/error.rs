@@ -17,6 +17,15 @@ pub enum ServiceError {
 
     #[error("Parse error: {0}")]
     Parse(String),
+
+    #[error("Blob store error: {0}")]
+    BlobStore(#[from] shared_blob_store::BlobStoreError),
+
+    #[error("Report error: {0}")]
+    Report(#[from] shared_progress_context::report::ReportError),
+
+    #[error("Notification preferences error: {0}")]
+    Preferences(#[from] shared_integration_events::IntegrationEventError),
 }
 
 impl From<std::num::ParseIntError> for ServiceError {
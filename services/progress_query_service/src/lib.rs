@@ -3,8 +3,28 @@
 // 純粋な CQRS/Event Sourcing の Read 側
 // GraphQL API による読み取りモデルの提供
 //
+// - schema: GraphQL スキーマ定義とフィールドのコスト注釈
+// - query_cost: クエリコストの見積もりと予算の適用
+// - concurrency: ユーザーごとの同時実行数の制限
+// - repositories: Read Model からのデータアクセス（現在値と指定日時点の履歴）
+// - reports: BLOB ストアに保存された週次レポートの読み出し
+// - collections: 共有コレクションのメンバー別統計の読み出し
+// - study_time: 学習する時間帯の読み出し（ユーザーのタイムゾーンで集計）
+//
 // 実装予定のモジュール：
-// - schema: GraphQL スキーマ定義
 // - resolvers: GraphQL リゾルバー実装
 // - loaders: DataLoader による効率的なデータ取得
-// - repositories: Read Model からのデータアクセス
+
+pub mod collections;
+pub mod concurrency;
+pub mod config;
+pub mod error;
+pub mod query_cost;
+pub mod reports;
+pub mod repositories;
+pub mod schema;
+pub mod server;
+pub mod study_time;
+
+/// Read Model（ビュー）のマイグレーション
+pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
@@ -1,11 +1,6 @@
+use progress_query_service::{config, error::Result, server};
 use tracing::info;
 
-mod config;
-mod error;
-mod server;
-
-use error::Result;
-
 #[tokio::main]
 async fn main() -> Result<()> {
     // ロギング初期化
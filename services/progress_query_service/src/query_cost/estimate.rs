@@ -0,0 +1,274 @@
+//! 実行前のクエリコスト見積もり
+//!
+//! フィールドのコストに子の選択のコストを加算する。リストフィールドでは
+//! 子の選択のコストを要求されたページサイズ倍にする。
+
+use std::collections::{HashMap, HashSet};
+
+use async_graphql::{
+    Name,
+    Positioned,
+    Value,
+    Variables,
+    parser::types::{
+        ExecutableDocument,
+        FragmentDefinition,
+        OperationType,
+        Selection,
+        SelectionSet,
+    },
+};
+
+use super::model::{CostModel, FieldKind};
+
+/// 見積もり結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostEstimate {
+    pub cost: u64,
+    /// 最もコストの大きい選択の経路（例: `recentSessions.items`）
+    pub path: Vec<String>,
+}
+
+impl CostEstimate {
+    pub fn path_string(&self) -> String {
+        self.path.join(".")
+    }
+}
+
+/// ドキュメント内のクエリ操作のうち、最もコストの大きいものを見積もる
+///
+/// 実行される操作はこの時点では確定していないため、安全側に倒して最大値を採る
+pub fn estimate(
+    model: &CostModel,
+    document: &ExecutableDocument,
+    variables: &Variables,
+) -> CostEstimate {
+    let estimator = Estimator {
+        model,
+        fragments: &document.fragments,
+        variables,
+    };
+
+    document
+        .operations
+        .iter()
+        .filter(|(_, operation)| operation.node.ty == OperationType::Query)
+        .map(|(_, operation)| {
+            estimator.selection_set(
+                model.query_type(),
+                &operation.node.selection_set,
+                &mut HashSet::new(),
+            )
+        })
+        .max_by_key(|estimate| estimate.cost)
+        .unwrap_or(CostEstimate {
+            cost: 0,
+            path: Vec::new(),
+        })
+}
+
+struct Estimator<'a> {
+    model:     &'a CostModel,
+    fragments: &'a HashMap<Name, Positioned<FragmentDefinition>>,
+    variables: &'a Variables,
+}
+
+impl Estimator<'_> {
+    fn selection_set(
+        &self,
+        type_name: &str,
+        selection_set: &Positioned<SelectionSet>,
+        visiting: &mut HashSet<Name>,
+    ) -> CostEstimate {
+        let mut total = 0u64;
+        let mut heaviest = CostEstimate {
+            cost: 0,
+            path: Vec::new(),
+        };
+
+        for selection in &selection_set.node.items {
+            let estimate = match &selection.node {
+                Selection::Field(field) => {
+                    let field = &field.node;
+                    let name = field.name.node.as_str();
+                    // イントロスペクションはコストの対象外
+                    if name.starts_with("__") {
+                        continue;
+                    }
+                    let key = field.response_key().node.to_string();
+
+                    // 未定義のフィールドは後段のバリデーションで拒否される
+                    let Some(entry) = self.model.field(type_name, name) else {
+                        continue;
+                    };
+
+                    let (factor, child) = match entry.kind {
+                        FieldKind::Scalar => (0, None),
+                        FieldKind::Object(item_type) => (1, Some(item_type)),
+                        FieldKind::List {
+                            item_type,
+                            page_size_arg,
+                            default_page_size,
+                            max_page_size,
+                        } => {
+                            let page_size = field
+                                .get_argument(page_size_arg)
+                                .and_then(|value| {
+                                    // 変数を展開して引数の値を得る
+                                    value
+                                        .node
+                                        .clone()
+                                        .into_const_with(|name| {
+                                            self.variables.get(&name).cloned().ok_or(())
+                                        })
+                                        .ok()
+                                })
+                                .and_then(|value| page_size(&value))
+                                .unwrap_or(default_page_size)
+                                .min(max_page_size);
+                            (page_size, Some(item_type))
+                        },
+                    };
+
+                    let mut path = vec![key];
+                    let mut cost = entry.cost;
+                    if let Some(child) = child {
+                        let sub = self.selection_set(child, &field.selection_set, visiting);
+                        cost = cost.saturating_add(factor.saturating_mul(sub.cost));
+                        path.extend(sub.path);
+                    }
+                    CostEstimate { cost, path }
+                },
+                Selection::FragmentSpread(spread) => {
+                    let name = &spread.node.fragment_name.node;
+                    let Some(fragment) = self.fragments.get(name) else {
+                        continue;
+                    };
+                    // 循環するフラグメントは後段のバリデーションで拒否される
+                    if !visiting.insert(name.clone()) {
+                        continue;
+                    }
+                    let estimate = self.selection_set(
+                        fragment.node.type_condition.node.on.node.as_str(),
+                        &fragment.node.selection_set,
+                        visiting,
+                    );
+                    visiting.remove(name);
+                    estimate
+                },
+                Selection::InlineFragment(fragment) => {
+                    let type_name = fragment
+                        .node
+                        .type_condition
+                        .as_ref()
+                        .map_or(type_name, |condition| condition.node.on.node.as_str());
+                    self.selection_set(type_name, &fragment.node.selection_set, visiting)
+                },
+            };
+
+            total = total.saturating_add(estimate.cost);
+            if estimate.cost > heaviest.cost {
+                heaviest = estimate;
+            }
+        }
+
+        CostEstimate {
+            cost: total,
+            path: heaviest.path,
+        }
+    }
+}
+
+/// 引数の値をページサイズとして読み取る
+fn page_size(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number
+            .as_u64()
+            .or_else(|| number.as_i64().map(|n| n.max(0) as u64)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::parser::parse_query;
+    use serde_json::json;
+
+    use super::*;
+    use crate::query_cost::model::FieldCost;
+
+    fn model() -> CostModel {
+        CostModel::new(
+            "QueryRoot",
+            &[
+                FieldCost::scalar("QueryRoot", "apiVersion", 1),
+                FieldCost::list("QueryRoot", "recentSessions", 5, "Session", "first", 10, 50),
+                FieldCost::scalar("Session", "id", 0),
+                FieldCost::list("Session", "items", 2, "SessionItem", "first", 20, 100),
+                FieldCost::scalar("SessionItem", "id", 0),
+                FieldCost::object("SessionItem", "session", 3, "Session"),
+            ],
+        )
+    }
+
+    fn estimate_query(query: &str, variables: serde_json::Value) -> CostEstimate {
+        let document = parse_query(query).unwrap();
+        estimate(&model(), &document, &Variables::from_json(variables))
+    }
+
+    #[test]
+    fn test_list_fields_multiply_by_page_size() {
+        // recentSessions: 5 + 3 × (id: 0 + items: 2 + 4 × id: 0) = 5 + 3 × 2
+        let estimate = estimate_query(
+            "{ recentSessions(first: 3) { id items(first: 4) { id } } }",
+            json!({}),
+        );
+        assert_eq!(estimate.cost, 11);
+        assert_eq!(estimate.path_string(), "recentSessions.items");
+    }
+
+    #[test]
+    fn test_page_size_defaults_clamps_and_reads_variables() {
+        // 省略時は既定値（10 × 20）
+        let estimate = estimate_query("{ recentSessions { items { session { id } } } }", json!({}));
+        assert_eq!(estimate.cost, 5 + 10 * (2 + 20 * 3));
+
+        // 上限を超える指定は上限に丸める
+        let estimate = estimate_query(
+            "query($n: Int) { recentSessions(first: $n) { id } }",
+            json!({ "n": 1000 }),
+        );
+        assert_eq!(estimate.cost, 5);
+
+        let estimate = estimate_query(
+            "query($n: Int) { recentSessions(first: 2) { items(first: $n) { session { id } } } }",
+            json!({ "n": 1000 }),
+        );
+        assert_eq!(estimate.cost, 5 + 2 * (2 + 100 * 3));
+    }
+
+    #[test]
+    fn test_fragments_are_expanded_and_cycles_terminate() {
+        let estimate = estimate_query(
+            r#"
+            { recentSessions(first: 2) { ...S } apiVersion }
+            fragment S on Session { items(first: 3) { ... on SessionItem { session { ...S } } } }
+            "#,
+            json!({}),
+        );
+        // 循環した2回目の展開は数えない: 1 + 5 + 2 × (2 + 3 × 3)
+        assert_eq!(estimate.cost, 1 + 5 + 2 * (2 + 3 * 3));
+        assert_eq!(estimate.path_string(), "recentSessions.items.session");
+    }
+
+    #[test]
+    fn test_aliases_are_used_in_path_and_introspection_is_free() {
+        let estimate = estimate_query(
+            "{ __typename a: recentSessions(first: 1) { id } b: recentSessions(first: 1) { \
+             items(first: 1) { id } } }",
+            json!({}),
+        );
+        assert_eq!(estimate.cost, 5 + 5 + 2);
+        assert_eq!(estimate.path_string(), "b.items");
+    }
+}
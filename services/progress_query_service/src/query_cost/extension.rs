@@ -0,0 +1,354 @@
+//! コスト予算の適用と実行コストの計測を行う GraphQL 拡張
+
+use std::{
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+
+use async_graphql::{
+    Context,
+    ErrorExtensionValues,
+    QueryPathSegment,
+    Request,
+    Response,
+    ServerError,
+    ServerResult,
+    Value,
+    Variables,
+    extensions::{
+        Extension,
+        ExtensionContext,
+        ExtensionFactory,
+        NextParseQuery,
+        NextPrepareRequest,
+        NextRequest,
+        NextResolve,
+        ResolveInfo,
+    },
+    parser::types::ExecutableDocument,
+};
+use tracing::{info, warn};
+
+use super::{
+    estimate::{CostEstimate, estimate},
+    model::CostModel,
+};
+
+/// 予算超過時のエラーコード
+pub const QUERY_COST_EXCEEDED: &str = "QUERY_COST_EXCEEDED";
+
+/// 1リクエストの実行コスト
+#[derive(Debug, Default)]
+pub struct QueryCostTracker {
+    resolver_calls:  AtomicU64,
+    resolver_micros: AtomicU64,
+    rows_fetched:    AtomicU64,
+    actual_cost:     AtomicU64,
+}
+
+impl QueryCostTracker {
+    /// リゾルバーの呼び出し回数
+    pub fn resolver_calls(&self) -> u64 {
+        self.resolver_calls.load(Ordering::Relaxed)
+    }
+
+    /// リゾルバーの実行時間の合計（マイクロ秒）
+    pub fn resolver_micros(&self) -> u64 {
+        self.resolver_micros.load(Ordering::Relaxed)
+    }
+
+    /// Read Model から取得した行数
+    pub fn rows_fetched(&self) -> u64 {
+        self.rows_fetched.load(Ordering::Relaxed)
+    }
+
+    /// 実際に解決されたフィールドのコストの合計
+    pub fn actual_cost(&self) -> u64 {
+        self.actual_cost.load(Ordering::Relaxed)
+    }
+
+    pub fn add_rows(&self, rows: u64) {
+        self.rows_fetched.fetch_add(rows, Ordering::Relaxed);
+    }
+}
+
+/// リゾルバーから Read Model の取得行数を記録する
+pub fn record_rows(ctx: &Context<'_>, rows: u64) {
+    if let Some(tracker) = ctx.data_opt::<Arc<QueryCostTracker>>() {
+        tracker.add_rows(rows);
+    }
+}
+
+/// クエリコストの予算を適用する拡張
+///
+/// 見積もりが予算を超えるクエリは実行前に拒否し、実行したクエリは
+/// 見積もりと実測をログに出してコストモデルの調整に使う
+pub struct QueryCostAnalysis {
+    model:  Arc<CostModel>,
+    budget: u64,
+}
+
+impl QueryCostAnalysis {
+    pub fn new(model: CostModel, budget: u64) -> Self {
+        Self {
+            model: Arc::new(model),
+            budget,
+        }
+    }
+}
+
+impl ExtensionFactory for QueryCostAnalysis {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(QueryCostExtension {
+            model:    self.model.clone(),
+            budget:   self.budget,
+            estimate: Mutex::new(None),
+            tracker:  Arc::new(QueryCostTracker::default()),
+        })
+    }
+}
+
+struct QueryCostExtension {
+    model:    Arc<CostModel>,
+    budget:   u64,
+    estimate: Mutex<Option<CostEstimate>>,
+    tracker:  Arc<QueryCostTracker>,
+}
+
+#[async_trait::async_trait]
+impl Extension for QueryCostExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let started = Instant::now();
+        let response = next.run(ctx).await;
+
+        if let Some(estimate) = self.estimate.lock().unwrap().as_ref() {
+            info!(
+                estimated_cost = estimate.cost,
+                actual_cost = self.tracker.actual_cost(),
+                resolver_calls = self.tracker.resolver_calls(),
+                resolver_ms = self.tracker.resolver_micros() / 1000,
+                rows_fetched = self.tracker.rows_fetched(),
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                "GraphQL query cost"
+            );
+        }
+        response
+    }
+
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        next.run(ctx, request.data(self.tracker.clone())).await
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+
+        let estimate = estimate(&self.model, &document, variables);
+        if estimate.cost > self.budget {
+            warn!(
+                estimated_cost = estimate.cost,
+                budget = self.budget,
+                path = %estimate.path_string(),
+                "Rejecting GraphQL query over cost budget"
+            );
+            return Err(cost_exceeded(&estimate, self.budget));
+        }
+
+        *self.estimate.lock().unwrap() = Some(estimate);
+        Ok(document)
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        // リストの要素はフィールドとしては解決済みのため数えない
+        if matches!(info.path_node.segment, QueryPathSegment::Index(_)) {
+            return next.run(ctx, info).await;
+        }
+
+        let cost = self
+            .model
+            .field(info.parent_type, info.name)
+            .map_or(0, |entry| entry.cost);
+
+        let started = Instant::now();
+        let result = next.run(ctx, info).await;
+
+        self.tracker.resolver_calls.fetch_add(1, Ordering::Relaxed);
+        self.tracker
+            .resolver_micros
+            .fetch_add(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+        self.tracker.actual_cost.fetch_add(cost, Ordering::Relaxed);
+        result
+    }
+}
+
+fn cost_exceeded(estimate: &CostEstimate, budget: u64) -> ServerError {
+    let path = estimate.path_string();
+    let mut error = ServerError::new(
+        format!(
+            "Query cost {} exceeds the budget of {} (most expensive path: {})",
+            estimate.cost, budget, path
+        ),
+        None,
+    );
+
+    let mut extensions = ErrorExtensionValues::default();
+    extensions.set("code", QUERY_COST_EXCEEDED);
+    extensions.set("estimatedCost", estimate.cost);
+    extensions.set("budget", budget);
+    extensions.set("path", path);
+    error.extensions = Some(extensions);
+    error
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema, SimpleObject};
+
+    use super::*;
+    use crate::query_cost::model::FieldCost;
+
+    #[derive(SimpleObject)]
+    struct Session {
+        id:    u32,
+        items: Vec<SessionItem>,
+    }
+
+    #[derive(SimpleObject)]
+    struct SessionItem {
+        id: u32,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn recent_sessions(&self, ctx: &Context<'_>, first: Option<u32>) -> Vec<Session> {
+            let sessions: Vec<Session> = (0..first.unwrap_or(10))
+                .map(|id| Session {
+                    id,
+                    items: vec![SessionItem { id: 0 }, SessionItem { id: 1 }],
+                })
+                .collect();
+            record_rows(ctx, sessions.len() as u64);
+            sessions
+        }
+    }
+
+    fn schema(budget: u64) -> Schema<Query, EmptyMutation, EmptySubscription> {
+        let model = CostModel::new(
+            "Query",
+            &[
+                FieldCost::list("Query", "recentSessions", 5, "Session", "first", 10, 50),
+                FieldCost::scalar("Session", "id", 0),
+                FieldCost::list("Session", "items", 2, "SessionItem", "first", 20, 100),
+                FieldCost::scalar("SessionItem", "id", 1),
+            ],
+        );
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(QueryCostAnalysis::new(model, budget))
+            .finish()
+    }
+
+    #[tokio::test]
+    async fn test_query_over_budget_is_rejected_with_path_and_cost() {
+        // 5 + 50 × (2 + 20 × 1) = 1105
+        let response = schema(1000)
+            .execute("{ recentSessions(first: 80) { id items { id } } }")
+            .await;
+
+        assert_eq!(response.errors.len(), 1);
+        let error = &response.errors[0];
+        assert!(error.message.contains("recentSessions.items"));
+        assert!(error.message.contains("1105"));
+
+        let extensions = error.extensions.as_ref().unwrap();
+        assert_eq!(
+            extensions.get("code"),
+            Some(&Value::from(QUERY_COST_EXCEEDED))
+        );
+        assert_eq!(extensions.get("estimatedCost"), Some(&Value::from(1105u64)));
+        assert_eq!(extensions.get("budget"), Some(&Value::from(1000u64)));
+        assert_eq!(
+            extensions.get("path"),
+            Some(&Value::from("recentSessions.items.id"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_within_budget_is_executed_and_tracked() {
+        let response = schema(1000)
+            .execute("{ recentSessions(first: 2) { id items { id } } }")
+            .await;
+
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({
+                "recentSessions": [
+                    { "id": 0, "items": [{ "id": 0 }, { "id": 1 }] },
+                    { "id": 1, "items": [{ "id": 0 }, { "id": 1 }] },
+                ]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tracker_records_actual_cost_and_rows() {
+        let tracker = Arc::new(QueryCostTracker::default());
+        let extension = QueryCostExtension {
+            model:    Arc::new(CostModel::new(
+                "Query",
+                &[
+                    FieldCost::list("Query", "recentSessions", 5, "Session", "first", 10, 50),
+                    FieldCost::scalar("Session", "id", 0),
+                    FieldCost::list("Session", "items", 2, "SessionItem", "first", 20, 100),
+                    FieldCost::scalar("SessionItem", "id", 1),
+                ],
+            )),
+            budget:   1000,
+            estimate: Mutex::new(None),
+            tracker:  tracker.clone(),
+        };
+        let extension: Arc<dyn Extension> = Arc::new(extension);
+        let factory = FixedExtension(extension);
+
+        let response = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(factory)
+            .finish()
+            .execute("{ recentSessions(first: 2) { id items { id } } }")
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        // recentSessions 1回 + Session.id 2回 + items 2回 + SessionItem.id 4回
+        assert_eq!(tracker.resolver_calls(), 9);
+        assert_eq!(tracker.actual_cost(), 5 + 2 * 2 + 4);
+        assert_eq!(tracker.rows_fetched(), 2);
+    }
+
+    struct FixedExtension(Arc<dyn Extension>);
+
+    impl ExtensionFactory for FixedExtension {
+        fn create(&self) -> Arc<dyn Extension> {
+            self.0.clone()
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! GraphQL クエリのコスト計算
+//!
+//! - model: フィールドごとのコスト注釈
+//! - estimate: 実行前のコスト見積もり
+//! - extension: 予算の適用と実行コストの計測
+
+pub mod estimate;
+pub mod extension;
+pub mod model;
+
+pub use estimate::{CostEstimate, estimate};
+pub use extension::{QUERY_COST_EXCEEDED, QueryCostAnalysis, QueryCostTracker, record_rows};
+pub use model::{CostModel, FieldCost, FieldKind};
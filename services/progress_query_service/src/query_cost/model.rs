@@ -0,0 +1,143 @@
+//! フィールドごとのコスト定義
+
+use std::collections::{BTreeSet, HashMap};
+
+use async_graphql::parser::{
+    parse_schema,
+    types::{TypeKind, TypeSystemDefinition},
+};
+
+/// フィールドの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// スカラー値（子の選択を持たない）
+    Scalar,
+    /// 単一のオブジェクト
+    Object(&'static str),
+    /// オブジェクトのリスト（子の選択のコストはページサイズ倍になる）
+    List {
+        item_type:         &'static str,
+        /// ページサイズを指定する引数名
+        page_size_arg:     &'static str,
+        default_page_size: u64,
+        max_page_size:     u64,
+    },
+}
+
+/// フィールドのコスト注釈
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldCost {
+    pub type_name: &'static str,
+    pub field:     &'static str,
+    /// フィールド1回の解決にかかるコスト
+    pub cost:      u64,
+    pub kind:      FieldKind,
+}
+
+impl FieldCost {
+    pub const fn scalar(type_name: &'static str, field: &'static str, cost: u64) -> Self {
+        Self {
+            type_name,
+            field,
+            cost,
+            kind: FieldKind::Scalar,
+        }
+    }
+
+    pub const fn object(
+        type_name: &'static str,
+        field: &'static str,
+        cost: u64,
+        item_type: &'static str,
+    ) -> Self {
+        Self {
+            type_name,
+            field,
+            cost,
+            kind: FieldKind::Object(item_type),
+        }
+    }
+
+    pub const fn list(
+        type_name: &'static str,
+        field: &'static str,
+        cost: u64,
+        item_type: &'static str,
+        page_size_arg: &'static str,
+        default_page_size: u64,
+        max_page_size: u64,
+    ) -> Self {
+        Self {
+            type_name,
+            field,
+            cost,
+            kind: FieldKind::List {
+                item_type,
+                page_size_arg,
+                default_page_size,
+                max_page_size,
+            },
+        }
+    }
+}
+
+/// スキーマ全体のコストモデル
+#[derive(Debug, Clone)]
+pub struct CostModel {
+    query_type: &'static str,
+    /// 型名 → フィールド名 → コスト
+    fields:     HashMap<&'static str, HashMap<&'static str, FieldCost>>,
+}
+
+impl CostModel {
+    pub fn new(query_type: &'static str, annotations: &[FieldCost]) -> Self {
+        let mut fields: HashMap<_, HashMap<_, _>> = HashMap::new();
+        for cost in annotations {
+            fields
+                .entry(cost.type_name)
+                .or_default()
+                .insert(cost.field, *cost);
+        }
+        Self { query_type, fields }
+    }
+
+    /// Query ルートの型名
+    pub fn query_type(&self) -> &'static str {
+        self.query_type
+    }
+
+    pub fn field(&self, type_name: &str, field: &str) -> Option<&FieldCost> {
+        self.fields.get(type_name)?.get(field)
+    }
+
+    /// SDL のオブジェクト型のフィールドのうち、コスト注釈のないものを返す
+    ///
+    /// 戻り値は `Type.field` 形式で、スキーマにフィールドを追加した際に
+    /// 注釈の付け忘れをテストで検出するために使う
+    pub fn missing_annotations(&self, sdl: &str) -> Result<Vec<String>, String> {
+        let document = parse_schema(sdl).map_err(|e| e.to_string())?;
+
+        let mut missing = BTreeSet::new();
+        for definition in document.definitions {
+            let TypeSystemDefinition::Type(ty) = definition else {
+                continue;
+            };
+            let type_name = ty.node.name.node.as_str();
+            let TypeKind::Object(object) = &ty.node.kind else {
+                continue;
+            };
+            if type_name.starts_with("__") {
+                continue;
+            }
+
+            for field in &object.fields {
+                let field_name = field.node.name.node.as_str();
+                if self.field(type_name, field_name).is_none() {
+                    missing.insert(format!("{type_name}.{field_name}"));
+                }
+            }
+        }
+
+        Ok(missing.into_iter().collect())
+    }
+}
@@ -0,0 +1,51 @@
+//! 週次レポートの読み出し
+//!
+//! Progress Projection Service が BLOB ストアに保存した週次レポート
+//! ドキュメントを、同じキーの規則で読み出す。
+
+use std::sync::Arc;
+
+use shared_blob_store::BlobStore;
+use shared_progress_context::report::{
+    IsoWeek,
+    WeeklyReportDocument,
+    week_of_report_key,
+    weekly_report_key,
+    weekly_report_prefix,
+};
+use uuid::Uuid;
+
+use crate::error::Result;
+
+/// 週次レポートの保存先
+#[derive(Clone)]
+pub struct WeeklyReportStore {
+    blobs: Arc<dyn BlobStore>,
+}
+
+impl WeeklyReportStore {
+    pub fn new(blobs: Arc<dyn BlobStore>) -> Self {
+        Self { blobs }
+    }
+
+    /// 指定した週のレポート（生成されていなければ None）
+    pub async fn get(&self, user_id: Uuid, week: IsoWeek) -> Result<Option<WeeklyReportDocument>> {
+        match self.blobs.get(&weekly_report_key(user_id, week)).await? {
+            Some(bytes) => Ok(Some(WeeklyReportDocument::from_bytes(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// レポートが生成済みの週（新しい順）
+    pub async fn available_weeks(&self, user_id: Uuid) -> Result<Vec<IsoWeek>> {
+        let mut weeks: Vec<IsoWeek> = self
+            .blobs
+            .list(&weekly_report_prefix(user_id))
+            .await?
+            .iter()
+            .filter_map(|key| week_of_report_key(key))
+            .collect();
+        weeks.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(weeks)
+    }
+}
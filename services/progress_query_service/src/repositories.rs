@@ -0,0 +1,186 @@
+//! Read Model からのデータアクセス
+//!
+//! 現在値は Progress Projection の Read Model から、指定日時点の値は
+//! 履歴テーブル（`item_mastery_history`, `streak_history`）から取得する。
+//! 履歴は指定日以前で最も新しい行を使い、行がなければ記録開始前とみなす。
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::Result;
+
+/// 習熟度ごとの語彙アイテム数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MasteryBreakdown {
+    pub beginner:   i32,
+    pub learning:   i32,
+    pub familiar:   i32,
+    pub proficient: i32,
+    pub mastered:   i32,
+}
+
+impl MasteryBreakdown {
+    /// Read Model の `mastery_level`（0 = Beginner 〜 4 =
+    /// Mastered）ごとの件数から作る
+    fn from_level_counts(counts: impl IntoIterator<Item = (i32, i64)>) -> Self {
+        let mut breakdown = Self::default();
+        for (level, count) in counts {
+            let count = count as i32;
+            match level {
+                0 => breakdown.beginner += count,
+                1 => breakdown.learning += count,
+                2 => breakdown.familiar += count,
+                3 => breakdown.proficient += count,
+                _ => breakdown.mastered += count,
+            }
+        }
+        breakdown
+    }
+}
+
+/// ストリーク
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreakStats {
+    pub current_days: i32,
+    pub longest_days: i32,
+}
+
+/// 進捗統計の読み取りポート
+///
+/// いずれも記録がなければ None を返す
+#[async_trait]
+pub trait ProgressStatsRepository: Send + Sync {
+    async fn current_mastery_breakdown(&self, user_id: Uuid) -> Result<Option<MasteryBreakdown>>;
+    async fn mastery_breakdown_as_of(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<Option<MasteryBreakdown>>;
+    async fn current_streak(&self, user_id: Uuid) -> Result<Option<StreakStats>>;
+    async fn streak_as_of(&self, user_id: Uuid, date: NaiveDate) -> Result<Option<StreakStats>>;
+}
+
+/// PostgreSQL 実装
+pub struct PostgresProgressStatsRepository {
+    pool: PgPool,
+}
+
+impl PostgresProgressStatsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ProgressStatsRepository for PostgresProgressStatsRepository {
+    async fn current_mastery_breakdown(&self, user_id: Uuid) -> Result<Option<MasteryBreakdown>> {
+        let counts = sqlx::query_as::<_, (i32, i64)>(
+            r#"
+            SELECT mastery_level, COUNT(*)
+            FROM vocabulary_item_progress
+            WHERE user_id = $1
+            GROUP BY mastery_level
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        if counts.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(MasteryBreakdown::from_level_counts(counts)))
+    }
+
+    async fn mastery_breakdown_as_of(
+        &self,
+        user_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<Option<MasteryBreakdown>> {
+        let record = sqlx::query_as::<_, (i32, i32, i32, i32, i32)>(
+            r#"
+            SELECT beginner_count, learning_count, familiar_count,
+                   proficient_count, mastered_count
+            FROM item_mastery_history
+            WHERE user_id = $1 AND snapshot_date <= $2
+            ORDER BY snapshot_date DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record.map(
+            |(beginner, learning, familiar, proficient, mastered)| MasteryBreakdown {
+                beginner,
+                learning,
+                familiar,
+                proficient,
+                mastered,
+            },
+        ))
+    }
+
+    async fn current_streak(&self, user_id: Uuid) -> Result<Option<StreakStats>> {
+        let record = sqlx::query_as::<_, (i32, i32)>(
+            r#"
+            SELECT current_streak_days, longest_streak_days
+            FROM user_progress
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record.map(|(current_days, longest_days)| StreakStats {
+            current_days,
+            longest_days,
+        }))
+    }
+
+    async fn streak_as_of(&self, user_id: Uuid, date: NaiveDate) -> Result<Option<StreakStats>> {
+        let record = sqlx::query_as::<_, (i32, i32)>(
+            r#"
+            SELECT current_streak_days, longest_streak_days
+            FROM streak_history
+            WHERE user_id = $1 AND snapshot_date <= $2
+            ORDER BY snapshot_date DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record.map(|(current_days, longest_days)| StreakStats {
+            current_days,
+            longest_days,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakdown_from_level_counts() {
+        let breakdown = MasteryBreakdown::from_level_counts([(1, 3), (4, 2), (0, 1)]);
+
+        assert_eq!(
+            breakdown,
+            MasteryBreakdown {
+                beginner: 1,
+                learning: 3,
+                mastered: 2,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,1165 @@
+//! GraphQL スキーマ定義
+//!
+//! フィールドを追加したら `COST_ANNOTATIONS` にもコストを定義すること。
+//! 注釈のないフィールドはテストで検出される。
+
+use std::sync::Arc;
+
+use async_graphql::{
+    Context,
+    EmptyMutation,
+    EmptySubscription,
+    ErrorExtensions,
+    ID,
+    Object,
+    Schema,
+    SimpleObject,
+};
+use chrono::NaiveDate;
+use shared_progress_context::{
+    collection::RankedMember,
+    report::{IsoWeek, MasteredWord, WeeklyReportDocument},
+    study_time::{PreferredStudyWindows, StudyWindow},
+};
+use uuid::Uuid;
+
+use crate::{
+    collections::{CollectionStatsRepository, leaderboard_for},
+    config::QueryLimitsConfig,
+    query_cost::{CostModel, FieldCost, QueryCostAnalysis, record_rows},
+    reports::WeeklyReportStore,
+    repositories::{MasteryBreakdown, ProgressStatsRepository, StreakStats},
+    study_time::StudyWindowsReader,
+};
+
+/// `availableReports` の1ページの上限（約1年分）
+const MAX_REPORTS_PAGE: i32 = 53;
+
+/// `collectionStats` の1ページの上限
+const MAX_LEADERBOARD_PAGE: i32 = 100;
+
+/// リクエストしたユーザー
+///
+/// API Gateway が付与する `x-user-id` ヘッダーから、リクエストごとに設定する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestingUser(pub Option<Uuid>);
+
+pub type ProgressSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+// GraphQL Query Root
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn api_version(&self) -> &str {
+        "0.1.0"
+    }
+
+    async fn service_status(&self) -> &str {
+        "Progress Query Service - 未実装"
+    }
+
+    /// 習熟度ごとの語彙アイテム数
+    ///
+    /// `asOf`（YYYY-MM-DD）を指定するとその日の終わり時点の値を返す。
+    /// 90日より前は週ごとの値になり、指定日以前の直近の週末時点を返す
+    async fn mastery_breakdown(
+        &self,
+        ctx: &Context<'_>,
+        user_id: ID,
+        as_of: Option<String>,
+    ) -> async_graphql::Result<MasteryBreakdownSnapshot> {
+        let user_id = parse_user_id(&user_id)?;
+        let as_of = as_of.as_deref().map(parse_date).transpose()?;
+        let repository = ctx.data::<Arc<dyn ProgressStatsRepository>>()?;
+
+        let breakdown = match as_of {
+            Some(date) => repository.mastery_breakdown_as_of(user_id, date).await?,
+            None => repository.current_mastery_breakdown(user_id).await?,
+        };
+        record_rows(ctx, 1);
+
+        Ok(MasteryBreakdownSnapshot::new(as_of, breakdown))
+    }
+
+    /// ストリーク
+    ///
+    /// `asOf` の扱いは `masteryBreakdown` と同じ
+    async fn streak(
+        &self,
+        ctx: &Context<'_>,
+        user_id: ID,
+        as_of: Option<String>,
+    ) -> async_graphql::Result<StreakSnapshot> {
+        let user_id = parse_user_id(&user_id)?;
+        let as_of = as_of.as_deref().map(parse_date).transpose()?;
+        let repository = ctx.data::<Arc<dyn ProgressStatsRepository>>()?;
+
+        let streak = match as_of {
+            Some(date) => repository.streak_as_of(user_id, date).await?,
+            None => repository.current_streak(user_id).await?,
+        };
+        record_rows(ctx, 1);
+
+        Ok(StreakSnapshot::new(as_of, streak))
+    }
+
+    /// 週次レポート（`week` は ISO 週 YYYY-Www）
+    ///
+    /// 本人のレポートだけを返す。まだ生成されていない週は null
+    async fn weekly_report(
+        &self,
+        ctx: &Context<'_>,
+        user_id: ID,
+        week: String,
+    ) -> async_graphql::Result<Option<WeeklyReportView>> {
+        let user_id = parse_user_id(&user_id)?;
+        authorize_owner(ctx, user_id, "Reports are only available to their owner")?;
+        let week = week.parse::<IsoWeek>()?;
+        let store = ctx.data::<WeeklyReportStore>()?;
+
+        let document = store.get(user_id, week).await?;
+        record_rows(ctx, 1);
+
+        Ok(document.map(WeeklyReportView::new))
+    }
+
+    /// レポートが生成済みの週（新しい順）
+    ///
+    /// 本人のレポートだけを返す
+    async fn available_reports(
+        &self,
+        ctx: &Context<'_>,
+        user_id: ID,
+        #[graphql(default = 10)] first: i32,
+    ) -> async_graphql::Result<Vec<ReportSummary>> {
+        let user_id = parse_user_id(&user_id)?;
+        authorize_owner(ctx, user_id, "Reports are only available to their owner")?;
+        let store = ctx.data::<WeeklyReportStore>()?;
+
+        let first = usize::try_from(first.clamp(0, MAX_REPORTS_PAGE)).unwrap_or_default();
+        let reports: Vec<ReportSummary> = store
+            .available_weeks(user_id)
+            .await?
+            .into_iter()
+            .take(first)
+            .map(ReportSummary::new)
+            .collect();
+        record_rows(ctx, reports.len() as u64);
+
+        Ok(reports)
+    }
+
+    /// 共有コレクションのメンバー別統計（習得数の多い順）
+    ///
+    /// コレクションのメンバーだけが参照できる。統計を非公開にしたメンバーは
+    /// 本人のリクエストにだけ含める
+    async fn collection_stats(
+        &self,
+        ctx: &Context<'_>,
+        collection_id: ID,
+        #[graphql(default = 20)] first: i32,
+    ) -> async_graphql::Result<Vec<CollectionMemberView>> {
+        let collection_id = collection_id
+            .parse::<Uuid>()
+            .map_err(|_| format!("Invalid collectionId: {}", collection_id.as_str()))?;
+        let repository = ctx.data::<Arc<dyn CollectionStatsRepository>>()?;
+        let requester = authorize_member(ctx, repository.as_ref(), collection_id).await?;
+
+        let first = usize::try_from(first.clamp(0, MAX_LEADERBOARD_PAGE)).unwrap_or_default();
+        let rows = repository.member_stats(collection_id).await?;
+        record_rows(ctx, rows.len() as u64);
+
+        Ok(leaderboard_for(requester, rows)
+            .into_iter()
+            .take(first)
+            .map(CollectionMemberView::new)
+            .collect())
+    }
+
+    /// 学習することの多い時間帯（GetPreferredStudyWindows）
+    ///
+    /// 本人だけが参照できる。
+    /// 時間帯はプロフィールのタイムゾーンのローカル時刻で、
+    /// セッションが少ないうちは空を返す
+    async fn preferred_study_windows(
+        &self,
+        ctx: &Context<'_>,
+        user_id: ID,
+    ) -> async_graphql::Result<PreferredStudyWindowsView> {
+        let user_id = parse_user_id(&user_id)?;
+        authorize_owner(
+            ctx,
+            user_id,
+            "Study windows are only available to their owner",
+        )?;
+        let reader = ctx.data::<StudyWindowsReader>()?;
+
+        let windows = reader.get(user_id).await?;
+        record_rows(ctx, 1);
+
+        Ok(PreferredStudyWindowsView::new(windows))
+    }
+}
+
+/// 習熟度の内訳
+///
+/// 記録開始前の日付では `notAvailable` が true になり、件数は null を返す
+#[derive(Debug, SimpleObject)]
+pub struct MasteryBreakdownSnapshot {
+    /// 基準日（現在値の場合は null）
+    as_of:         Option<String>,
+    not_available: bool,
+    beginner:      Option<i32>,
+    learning:      Option<i32>,
+    familiar:      Option<i32>,
+    proficient:    Option<i32>,
+    mastered:      Option<i32>,
+}
+
+impl MasteryBreakdownSnapshot {
+    fn new(as_of: Option<NaiveDate>, breakdown: Option<MasteryBreakdown>) -> Self {
+        Self {
+            as_of:         as_of.map(|date| date.to_string()),
+            not_available: breakdown.is_none(),
+            beginner:      breakdown.map(|b| b.beginner),
+            learning:      breakdown.map(|b| b.learning),
+            familiar:      breakdown.map(|b| b.familiar),
+            proficient:    breakdown.map(|b| b.proficient),
+            mastered:      breakdown.map(|b| b.mastered),
+        }
+    }
+}
+
+/// ストリーク
+///
+/// 記録開始前の日付では `notAvailable` が true になり、日数は null を返す
+#[derive(Debug, SimpleObject)]
+pub struct StreakSnapshot {
+    /// 基準日（現在値の場合は null）
+    as_of:         Option<String>,
+    not_available: bool,
+    current_days:  Option<i32>,
+    longest_days:  Option<i32>,
+}
+
+impl StreakSnapshot {
+    fn new(as_of: Option<NaiveDate>, streak: Option<StreakStats>) -> Self {
+        Self {
+            as_of:         as_of.map(|date| date.to_string()),
+            not_available: streak.is_none(),
+            current_days:  streak.map(|s| s.current_days),
+            longest_days:  streak.map(|s| s.longest_days),
+        }
+    }
+}
+
+/// 週次レポート
+#[derive(Debug, SimpleObject)]
+pub struct WeeklyReportView {
+    /// ISO 週（YYYY-Www）
+    week:               String,
+    period_start:       String,
+    period_end:         String,
+    generated_at:       String,
+    items_learned:      i32,
+    items_reviewed:     i32,
+    items_mastered:     i32,
+    study_minutes:      i32,
+    study_days:         i32,
+    sessions:           i32,
+    goals_completed:    i32,
+    /// 正答率（回答がない週は null）
+    accuracy:           Option<f32>,
+    /// 学習時間が最も長かった日
+    best_day:           Option<String>,
+    best_day_minutes:   Option<i32>,
+    longest_run_days:   i32,
+    new_mastered_words: Vec<MasteredWordView>,
+}
+
+impl WeeklyReportView {
+    fn new(document: WeeklyReportDocument) -> Self {
+        let report = document.report;
+        let stats = report.stats;
+        let best_day = report.highlights.best_day;
+
+        Self {
+            week:               report.week.to_string(),
+            period_start:       report.period_start.to_string(),
+            period_end:         report.period_end.to_string(),
+            generated_at:       document.generated_at.to_rfc3339(),
+            items_learned:      stats.items_learned,
+            items_reviewed:     stats.items_reviewed,
+            items_mastered:     stats.items_mastered,
+            study_minutes:      stats.study_minutes,
+            study_days:         stats.study_days,
+            sessions:           stats.sessions,
+            goals_completed:    stats.goals_completed,
+            accuracy:           stats.accuracy,
+            best_day:           best_day.as_ref().map(|day| day.date.to_string()),
+            best_day_minutes:   best_day.as_ref().map(|day| day.study_minutes),
+            longest_run_days:   report.highlights.longest_run_days,
+            new_mastered_words: report
+                .new_mastered_words
+                .into_iter()
+                .map(MasteredWordView::new)
+                .collect(),
+        }
+    }
+}
+
+/// その週に新しく習得した語彙
+#[derive(Debug, SimpleObject)]
+pub struct MasteredWordView {
+    item_id:     ID,
+    spelling:    String,
+    definition:  Option<String>,
+    mastered_at: String,
+}
+
+impl MasteredWordView {
+    fn new(word: MasteredWord) -> Self {
+        Self {
+            item_id:     ID(word.item_id.to_string()),
+            spelling:    word.spelling,
+            definition:  word.definition,
+            mastered_at: word.mastered_at.to_rfc3339(),
+        }
+    }
+}
+
+/// 生成済みのレポート
+#[derive(Debug, SimpleObject)]
+pub struct ReportSummary {
+    /// ISO 週（YYYY-Www）
+    week:         String,
+    period_start: String,
+    period_end:   String,
+}
+
+impl ReportSummary {
+    fn new(week: IsoWeek) -> Self {
+        Self {
+            week:         week.to_string(),
+            period_start: week.first_day().to_string(),
+            period_end:   week.last_day().to_string(),
+        }
+    }
+}
+
+/// コレクションのメンバーのランキング上の1行
+#[derive(Debug, SimpleObject)]
+pub struct CollectionMemberView {
+    /// 1 から始まる順位
+    rank:             i32,
+    user_id:          ID,
+    items_studied:    i32,
+    items_mastered:   i32,
+    /// コレクションの語彙アイテムを最後に学習した日時（未学習は null）
+    last_activity_at: Option<String>,
+}
+
+impl CollectionMemberView {
+    fn new(member: RankedMember) -> Self {
+        Self {
+            rank:             i32::try_from(member.rank).unwrap_or(i32::MAX),
+            user_id:          ID(member.stats.user_id.to_string()),
+            items_studied:    member.stats.items_studied,
+            items_mastered:   member.stats.items_mastered,
+            last_activity_at: member.stats.last_activity_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+/// 学習することの多い時間帯
+#[derive(Debug, SimpleObject)]
+pub struct PreferredStudyWindowsView {
+    /// 時間帯を集計したタイムゾーン（UTC からのオフセット、分）
+    utc_offset_minutes: i32,
+    total_sessions:     i32,
+    /// 確信度の高い順
+    windows:            Vec<StudyWindowView>,
+}
+
+impl PreferredStudyWindowsView {
+    fn new(windows: PreferredStudyWindows) -> Self {
+        Self {
+            utc_offset_minutes: windows.utc_offset_minutes,
+            total_sessions:     i32::try_from(windows.total_sessions).unwrap_or(i32::MAX),
+            windows:            windows
+                .windows
+                .into_iter()
+                .map(StudyWindowView::new)
+                .collect(),
+        }
+    }
+}
+
+/// 1時間の時間帯（ローカル時刻）
+#[derive(Debug, SimpleObject)]
+pub struct StudyWindowView {
+    /// 曜日（月曜 = 0）
+    weekday:    i32,
+    /// 時間帯が始まる時
+    hour:       i32,
+    sessions:   i32,
+    /// 0.0〜1.0
+    confidence: f64,
+}
+
+impl StudyWindowView {
+    fn new(window: StudyWindow) -> Self {
+        Self {
+            weekday:    i32::try_from(window.weekday_from_monday()).unwrap_or_default(),
+            hour:       i32::try_from(window.hour()).unwrap_or_default(),
+            sessions:   i32::try_from(window.sessions).unwrap_or(i32::MAX),
+            confidence: window.confidence,
+        }
+    }
+}
+
+/// リクエストしたユーザー本人のデータかを確認する
+fn authorize_owner(ctx: &Context<'_>, user_id: Uuid, message: &str) -> async_graphql::Result<()> {
+    match ctx.data_opt::<RequestingUser>() {
+        Some(RequestingUser(Some(requester))) if *requester == user_id => Ok(()),
+        _ => Err(forbidden(message)),
+    }
+}
+
+/// リクエストしたユーザーがコレクションのメンバーかを確認し、そのユーザーを返す
+async fn authorize_member(
+    ctx: &Context<'_>,
+    repository: &dyn CollectionStatsRepository,
+    collection_id: Uuid,
+) -> async_graphql::Result<Uuid> {
+    let message = "Collection stats are only available to its members";
+    let Some(RequestingUser(Some(requester))) = ctx.data_opt::<RequestingUser>() else {
+        return Err(forbidden(message));
+    };
+    if !repository.is_member(collection_id, *requester).await? {
+        return Err(forbidden(message));
+    }
+    Ok(*requester)
+}
+
+fn forbidden(message: &str) -> async_graphql::Error {
+    async_graphql::Error::new(message).extend_with(|_, e| e.set("code", "FORBIDDEN"))
+}
+
+fn parse_user_id(user_id: &ID) -> async_graphql::Result<Uuid> {
+    user_id
+        .parse::<Uuid>()
+        .map_err(|_| format!("Invalid userId: {}", user_id.as_str()).into())
+}
+
+fn parse_date(value: &str) -> async_graphql::Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid asOf date: {value} (expected YYYY-MM-DD)").into())
+}
+
+/// フィールドごとのコスト注釈
+///
+/// Read Model への問い合わせを伴うフィールドは 1 以上、リストフィールドは
+/// `FieldCost::list` でページサイズ引数と上限を指定する
+pub const COST_ANNOTATIONS: &[FieldCost] = &[
+    FieldCost::scalar("QueryRoot", "apiVersion", 0),
+    FieldCost::scalar("QueryRoot", "serviceStatus", 0),
+    FieldCost::object(
+        "QueryRoot",
+        "masteryBreakdown",
+        1,
+        "MasteryBreakdownSnapshot",
+    ),
+    FieldCost::object("QueryRoot", "streak", 1, "StreakSnapshot"),
+    FieldCost::scalar("MasteryBreakdownSnapshot", "asOf", 0),
+    FieldCost::scalar("MasteryBreakdownSnapshot", "notAvailable", 0),
+    FieldCost::scalar("MasteryBreakdownSnapshot", "beginner", 0),
+    FieldCost::scalar("MasteryBreakdownSnapshot", "learning", 0),
+    FieldCost::scalar("MasteryBreakdownSnapshot", "familiar", 0),
+    FieldCost::scalar("MasteryBreakdownSnapshot", "proficient", 0),
+    FieldCost::scalar("MasteryBreakdownSnapshot", "mastered", 0),
+    FieldCost::scalar("StreakSnapshot", "asOf", 0),
+    FieldCost::scalar("StreakSnapshot", "notAvailable", 0),
+    FieldCost::scalar("StreakSnapshot", "currentDays", 0),
+    FieldCost::scalar("StreakSnapshot", "longestDays", 0),
+    FieldCost::object("QueryRoot", "weeklyReport", 1, "WeeklyReportView"),
+    FieldCost::list(
+        "QueryRoot",
+        "availableReports",
+        1,
+        "ReportSummary",
+        "first",
+        10,
+        MAX_REPORTS_PAGE as u64,
+    ),
+    FieldCost::scalar("WeeklyReportView", "week", 0),
+    FieldCost::scalar("WeeklyReportView", "periodStart", 0),
+    FieldCost::scalar("WeeklyReportView", "periodEnd", 0),
+    FieldCost::scalar("WeeklyReportView", "generatedAt", 0),
+    FieldCost::scalar("WeeklyReportView", "itemsLearned", 0),
+    FieldCost::scalar("WeeklyReportView", "itemsReviewed", 0),
+    FieldCost::scalar("WeeklyReportView", "itemsMastered", 0),
+    FieldCost::scalar("WeeklyReportView", "studyMinutes", 0),
+    FieldCost::scalar("WeeklyReportView", "studyDays", 0),
+    FieldCost::scalar("WeeklyReportView", "sessions", 0),
+    FieldCost::scalar("WeeklyReportView", "goalsCompleted", 0),
+    FieldCost::scalar("WeeklyReportView", "accuracy", 0),
+    FieldCost::scalar("WeeklyReportView", "bestDay", 0),
+    FieldCost::scalar("WeeklyReportView", "bestDayMinutes", 0),
+    FieldCost::scalar("WeeklyReportView", "longestRunDays", 0),
+    // ドキュメントに載る語彙は最大5件
+    FieldCost::list(
+        "WeeklyReportView",
+        "newMasteredWords",
+        0,
+        "MasteredWordView",
+        "first",
+        5,
+        5,
+    ),
+    FieldCost::scalar("MasteredWordView", "itemId", 0),
+    FieldCost::scalar("MasteredWordView", "spelling", 0),
+    FieldCost::scalar("MasteredWordView", "definition", 0),
+    FieldCost::scalar("MasteredWordView", "masteredAt", 0),
+    FieldCost::scalar("ReportSummary", "week", 0),
+    FieldCost::scalar("ReportSummary", "periodStart", 0),
+    FieldCost::scalar("ReportSummary", "periodEnd", 0),
+    FieldCost::list(
+        "QueryRoot",
+        "collectionStats",
+        2,
+        "CollectionMemberView",
+        "first",
+        20,
+        MAX_LEADERBOARD_PAGE as u64,
+    ),
+    FieldCost::scalar("CollectionMemberView", "rank", 0),
+    FieldCost::scalar("CollectionMemberView", "userId", 0),
+    FieldCost::scalar("CollectionMemberView", "itemsStudied", 0),
+    FieldCost::scalar("CollectionMemberView", "itemsMastered", 0),
+    FieldCost::scalar("CollectionMemberView", "lastActivityAt", 0),
+    FieldCost::object(
+        "QueryRoot",
+        "preferredStudyWindows",
+        1,
+        "PreferredStudyWindowsView",
+    ),
+    FieldCost::scalar("PreferredStudyWindowsView", "utcOffsetMinutes", 0),
+    FieldCost::scalar("PreferredStudyWindowsView", "totalSessions", 0),
+    // 時間帯は最大3件
+    FieldCost::list(
+        "PreferredStudyWindowsView",
+        "windows",
+        0,
+        "StudyWindowView",
+        "first",
+        3,
+        3,
+    ),
+    FieldCost::scalar("StudyWindowView", "weekday", 0),
+    FieldCost::scalar("StudyWindowView", "hour", 0),
+    FieldCost::scalar("StudyWindowView", "sessions", 0),
+    FieldCost::scalar("StudyWindowView", "confidence", 0),
+];
+
+pub fn cost_model() -> CostModel {
+    CostModel::new("QueryRoot", COST_ANNOTATIONS)
+}
+
+/// クエリ制限を適用したスキーマを構築する
+pub fn build_schema(
+    limits: &QueryLimitsConfig,
+    repository: Arc<dyn ProgressStatsRepository>,
+    reports: WeeklyReportStore,
+    collections: Arc<dyn CollectionStatsRepository>,
+    study_windows: StudyWindowsReader,
+) -> ProgressSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(repository)
+        .data(reports)
+        .data(collections)
+        .data(study_windows)
+        .limit_depth(limits.max_depth)
+        .extension(QueryCostAnalysis::new(cost_model(), limits.max_query_cost))
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use async_graphql::Request;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use serde_json::json;
+    use shared_blob_store::{BlobStore, InMemoryBlobStore};
+    use shared_integration_events::{NotificationPreferenceSource, NotificationPreferences};
+    use shared_progress_context::{
+        collection::CollectionMemberStats,
+        report::{WeeklyHighlights, WeeklyReport, WeeklyStats, weekly_report_key},
+    };
+
+    use super::*;
+    use crate::{collections::MemberStatsRow, error::Result, study_time::StudyTimeRepository};
+
+    /// 日付ごとの履歴を持つインメモリのリポジトリ
+    struct InMemoryStats {
+        mastery: BTreeMap<NaiveDate, MasteryBreakdown>,
+        streaks: BTreeMap<NaiveDate, StreakStats>,
+    }
+
+    #[async_trait]
+    impl ProgressStatsRepository for InMemoryStats {
+        async fn current_mastery_breakdown(&self, _: Uuid) -> Result<Option<MasteryBreakdown>> {
+            Ok(self.mastery.values().last().copied())
+        }
+
+        async fn mastery_breakdown_as_of(
+            &self,
+            _: Uuid,
+            date: NaiveDate,
+        ) -> Result<Option<MasteryBreakdown>> {
+            Ok(self.mastery.range(..=date).last().map(|(_, value)| *value))
+        }
+
+        async fn current_streak(&self, _: Uuid) -> Result<Option<StreakStats>> {
+            Ok(self.streaks.values().last().copied())
+        }
+
+        async fn streak_as_of(&self, _: Uuid, date: NaiveDate) -> Result<Option<StreakStats>> {
+            Ok(self.streaks.range(..=date).last().map(|(_, value)| *value))
+        }
+    }
+
+    /// コレクションごとのメンバーの統計を持つインメモリのリポジトリ
+    #[derive(Default)]
+    struct InMemoryCollections {
+        members: BTreeMap<Uuid, Vec<MemberStatsRow>>,
+    }
+
+    #[async_trait]
+    impl CollectionStatsRepository for InMemoryCollections {
+        async fn is_member(&self, collection_id: Uuid, user_id: Uuid) -> Result<bool> {
+            Ok(self
+                .members
+                .get(&collection_id)
+                .is_some_and(|rows| rows.iter().any(|row| row.stats.user_id == user_id)))
+        }
+
+        async fn member_stats(&self, collection_id: Uuid) -> Result<Vec<MemberStatsRow>> {
+            Ok(self
+                .members
+                .get(&collection_id)
+                .cloned()
+                .unwrap_or_default())
+        }
+    }
+
+    /// 全ユーザーで同じ15分枠ごとのセッション数を返すリポジトリ
+    struct InMemoryStudyTime {
+        quarters: Vec<(u32, u32)>,
+    }
+
+    #[async_trait]
+    impl StudyTimeRepository for InMemoryStudyTime {
+        async fn study_time_quarters(&self, _: Uuid) -> Result<Vec<(u32, u32)>> {
+            Ok(self.quarters.clone())
+        }
+    }
+
+    /// 全ユーザーが東京（+09:00）のプロフィール
+    struct TokyoPreferences;
+
+    #[async_trait]
+    impl NotificationPreferenceSource for TokyoPreferences {
+        async fn get_preferences(
+            &self,
+            user_id: &str,
+        ) -> shared_integration_events::Result<NotificationPreferences> {
+            Ok(NotificationPreferences {
+                utc_offset_minutes: 9 * 60,
+                ..NotificationPreferences::default_for(user_id)
+            })
+        }
+    }
+
+    fn study_windows(quarters: Vec<(u32, u32)>) -> StudyWindowsReader {
+        StudyWindowsReader::new(
+            Arc::new(InMemoryStudyTime { quarters }),
+            Arc::new(TokyoPreferences),
+        )
+    }
+
+    fn stats_schema() -> ProgressSchema {
+        let date = |value: &str| value.parse::<NaiveDate>().unwrap();
+        let repository = InMemoryStats {
+            mastery: BTreeMap::from([
+                (
+                    date("2025-01-08"),
+                    MasteryBreakdown {
+                        learning: 2,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    date("2025-01-12"),
+                    MasteryBreakdown {
+                        learning: 1,
+                        familiar: 3,
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            streaks: BTreeMap::from([(
+                date("2025-01-08"),
+                StreakStats {
+                    current_days: 1,
+                    longest_days: 4,
+                },
+            )]),
+        };
+
+        build_schema(
+            &QueryLimitsConfig {
+                max_depth:               10,
+                max_query_cost:          100,
+                max_concurrent_per_user: 1,
+                max_queued_per_user:     0,
+            },
+            Arc::new(repository),
+            WeeklyReportStore::new(Arc::new(InMemoryBlobStore::new())),
+            Arc::new(InMemoryCollections::default()),
+            study_windows(Vec::new()),
+        )
+    }
+
+    async fn query(schema: &ProgressSchema, query: &str) -> serde_json::Value {
+        let response = schema.execute(query).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_as_of_resolves_latest_snapshot_on_or_before_date() {
+        let schema = stats_schema();
+        let user = "7f1c3a56-1d7e-4b7a-9a53-0c8d2b1e9f10";
+
+        let data = query(
+            &schema,
+            &format!(
+                r#"{{
+                    mid: masteryBreakdown(userId: "{user}", asOf: "2025-01-10") {{
+                        asOf notAvailable learning familiar
+                    }}
+                    current: masteryBreakdown(userId: "{user}") {{ asOf learning familiar }}
+                    streak(userId: "{user}", asOf: "2025-02-01") {{ currentDays longestDays }}
+                }}"#
+            ),
+        )
+        .await;
+
+        assert_eq!(
+            data,
+            json!({
+                "mid": { "asOf": "2025-01-10", "notAvailable": false, "learning": 2, "familiar": 0 },
+                "current": { "asOf": null, "learning": 1, "familiar": 3 },
+                "streak": { "currentDays": 1, "longestDays": 4 },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_as_of_before_tracking_began_is_not_available() {
+        let schema = stats_schema();
+        let user = "7f1c3a56-1d7e-4b7a-9a53-0c8d2b1e9f10";
+
+        let data = query(
+            &schema,
+            &format!(
+                r#"{{
+                    masteryBreakdown(userId: "{user}", asOf: "2025-01-07") {{
+                        notAvailable learning mastered
+                    }}
+                    streak(userId: "{user}", asOf: "2025-01-07") {{ notAvailable currentDays }}
+                }}"#
+            ),
+        )
+        .await;
+
+        assert_eq!(
+            data,
+            json!({
+                "masteryBreakdown": { "notAvailable": true, "learning": null, "mastered": null },
+                "streak": { "notAvailable": true, "currentDays": null },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_as_of_date_is_rejected() {
+        let response = stats_schema()
+            .execute(
+                r#"{ streak(userId: "7f1c3a56-1d7e-4b7a-9a53-0c8d2b1e9f10", asOf: "01/07/2025") { notAvailable } }"#,
+            )
+            .await;
+
+        assert_eq!(
+            response.errors[0].message,
+            "Invalid asOf date: 01/07/2025 (expected YYYY-MM-DD)"
+        );
+    }
+
+    const OWNER: &str = "7f1c3a56-1d7e-4b7a-9a53-0c8d2b1e9f10";
+
+    async fn reports_schema() -> ProgressSchema {
+        let owner: Uuid = OWNER.parse().unwrap();
+        let blobs = InMemoryBlobStore::new();
+        for (week, minutes) in [("2025-W01", 30), ("2025-W03", 45), ("2024-W52", 10)] {
+            let week: IsoWeek = week.parse().unwrap();
+            let report = WeeklyReport {
+                user_id: owner,
+                week,
+                period_start: week.first_day(),
+                period_end: week.last_day(),
+                stats: WeeklyStats {
+                    study_minutes: minutes,
+                    study_days: 2,
+                    accuracy: Some(0.5),
+                    ..Default::default()
+                },
+                highlights: WeeklyHighlights::default(),
+                new_mastered_words: vec![MasteredWord {
+                    item_id:     Uuid::nil(),
+                    spelling:    "serendipity".to_string(),
+                    definition:  Some("a happy accident".to_string()),
+                    mastered_at: "2025-01-02T09:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+                }],
+            };
+            let generated_at = "2025-01-20T00:00:00Z".parse().unwrap();
+            let document = WeeklyReportDocument::new(report, generated_at).unwrap();
+            blobs
+                .put(
+                    &weekly_report_key(owner, week),
+                    &document.to_bytes().unwrap(),
+                )
+                .await
+                .unwrap();
+        }
+
+        build_schema(
+            &QueryLimitsConfig {
+                max_depth:               10,
+                max_query_cost:          100,
+                max_concurrent_per_user: 1,
+                max_queued_per_user:     0,
+            },
+            Arc::new(InMemoryStats {
+                mastery: BTreeMap::new(),
+                streaks: BTreeMap::new(),
+            }),
+            WeeklyReportStore::new(Arc::new(blobs)),
+            Arc::new(InMemoryCollections::default()),
+            study_windows(Vec::new()),
+        )
+    }
+
+    fn as_user(query: &str, user: Option<&str>) -> Request {
+        Request::new(query).data(RequestingUser(user.map(|id| id.parse().unwrap())))
+    }
+
+    #[tokio::test]
+    async fn test_owner_reads_weekly_report_and_available_weeks() {
+        let schema = reports_schema().await;
+
+        let response = schema
+            .execute(as_user(
+                &format!(
+                    r#"{{
+                        weeklyReport(userId: "{OWNER}", week: "2025-W01") {{
+                            week periodStart periodEnd studyMinutes accuracy
+                            newMasteredWords {{ spelling definition }}
+                        }}
+                        missing: weeklyReport(userId: "{OWNER}", week: "2025-W02") {{ week }}
+                        availableReports(userId: "{OWNER}", first: 2) {{ week periodStart }}
+                    }}"#
+                ),
+                Some(OWNER),
+            ))
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            json!({
+                "weeklyReport": {
+                    "week": "2025-W01",
+                    "periodStart": "2024-12-30",
+                    "periodEnd": "2025-01-05",
+                    "studyMinutes": 30,
+                    "accuracy": 0.5,
+                    "newMasteredWords": [
+                        { "spelling": "serendipity", "definition": "a happy accident" }
+                    ],
+                },
+                "missing": null,
+                "availableReports": [
+                    { "week": "2025-W03", "periodStart": "2025-01-13" },
+                    { "week": "2025-W01", "periodStart": "2024-12-30" },
+                ],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reports_are_only_visible_to_their_owner() {
+        let schema = reports_schema().await;
+        let other = "0b3c6f1e-52a4-4f0c-8a0e-2d7a9c4b5e61";
+
+        for requester in [Some(other), None] {
+            for query in [
+                format!(r#"{{ weeklyReport(userId: "{OWNER}", week: "2025-W01") {{ week }} }}"#),
+                format!(r#"{{ availableReports(userId: "{OWNER}") {{ week }} }}"#),
+            ] {
+                let response = schema.execute(as_user(&query, requester)).await;
+
+                assert_eq!(
+                    response.errors[0].message,
+                    "Reports are only available to their owner"
+                );
+                assert_eq!(
+                    response.errors[0]
+                        .extensions
+                        .as_ref()
+                        .and_then(|extensions| extensions.get("code")),
+                    Some(&async_graphql::Value::from("FORBIDDEN"))
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_week_is_rejected() {
+        let response = reports_schema()
+            .await
+            .execute(as_user(
+                &format!(r#"{{ weeklyReport(userId: "{OWNER}", week: "2021-W53") {{ week }} }}"#),
+                Some(OWNER),
+            ))
+            .await;
+
+        assert_eq!(
+            response.errors[0].message,
+            "Invalid ISO week: 2021-W53 (expected YYYY-Www)"
+        );
+    }
+
+    const COLLECTION: &str = "5a0f7c2e-9b1d-4e3a-8c6f-1d2e3f4a5b6c";
+    const PRIVATE_MEMBER: &str = "00000000-0000-0000-0000-000000000003";
+
+    fn collections_schema() -> ProgressSchema {
+        let collection_id: Uuid = COLLECTION.parse().unwrap();
+        let row = |user: u128, mastered: i32, last: Option<&str>, hidden: bool| MemberStatsRow {
+            stats: CollectionMemberStats {
+                collection_id,
+                user_id: Uuid::from_u128(user),
+                items_studied: mastered + 2,
+                items_mastered: mastered,
+                last_activity_at: last.map(|at| at.parse().unwrap()),
+            },
+            hidden,
+        };
+
+        build_schema(
+            &QueryLimitsConfig {
+                max_depth:               10,
+                max_query_cost:          500,
+                max_concurrent_per_user: 1,
+                max_queued_per_user:     0,
+            },
+            Arc::new(InMemoryStats {
+                mastery: BTreeMap::new(),
+                streaks: BTreeMap::new(),
+            }),
+            WeeklyReportStore::new(Arc::new(InMemoryBlobStore::new())),
+            Arc::new(InMemoryCollections {
+                members: BTreeMap::from([(
+                    collection_id,
+                    vec![
+                        row(1, 4, Some("2025-01-05T10:00:00Z"), false),
+                        row(2, 4, Some("2025-01-06T10:00:00Z"), false),
+                        // 最も習得数が多いが統計を非公開にしている
+                        row(3, 9, Some("2025-01-07T10:00:00Z"), true),
+                        row(4, 0, None, false),
+                    ],
+                )]),
+            }),
+            study_windows(Vec::new()),
+        )
+    }
+
+    async fn leaderboard(requester: &str) -> serde_json::Value {
+        let response = collections_schema()
+            .execute(as_user(
+                &format!(
+                    r#"{{ collectionStats(collectionId: "{COLLECTION}") {{
+                        rank userId itemsMastered lastActivityAt
+                    }} }}"#
+                ),
+                Some(requester),
+            ))
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_members_see_leaderboard_without_private_members() {
+        let data = leaderboard("00000000-0000-0000-0000-000000000001").await;
+
+        assert_eq!(
+            data,
+            json!({
+                "collectionStats": [
+                    {
+                        "rank": 1,
+                        "userId": "00000000-0000-0000-0000-000000000002",
+                        "itemsMastered": 4,
+                        "lastActivityAt": "2025-01-06T10:00:00+00:00",
+                    },
+                    {
+                        "rank": 2,
+                        "userId": "00000000-0000-0000-0000-000000000001",
+                        "itemsMastered": 4,
+                        "lastActivityAt": "2025-01-05T10:00:00+00:00",
+                    },
+                    {
+                        "rank": 3,
+                        "userId": "00000000-0000-0000-0000-000000000004",
+                        "itemsMastered": 0,
+                        "lastActivityAt": null,
+                    },
+                ],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_private_member_still_sees_own_stats() {
+        let data = leaderboard(PRIVATE_MEMBER).await;
+
+        let ranks: Vec<(&str, i64)> = data["collectionStats"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| {
+                (
+                    row["userId"].as_str().unwrap(),
+                    row["rank"].as_i64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            ranks,
+            vec![
+                (PRIVATE_MEMBER, 1),
+                ("00000000-0000-0000-0000-000000000002", 2),
+                ("00000000-0000-0000-0000-000000000001", 3),
+                ("00000000-0000-0000-0000-000000000004", 4),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collection_stats_are_only_visible_to_members() {
+        let schema = collections_schema();
+        let query = format!(r#"{{ collectionStats(collectionId: "{COLLECTION}") {{ rank }} }}"#);
+
+        for requester in [Some(OWNER), None] {
+            let response = schema.execute(as_user(&query, requester)).await;
+
+            assert_eq!(
+                response.errors[0].message,
+                "Collection stats are only available to its members"
+            );
+            assert_eq!(
+                response.errors[0]
+                    .extensions
+                    .as_ref()
+                    .and_then(|extensions| extensions.get("code")),
+                Some(&async_graphql::Value::from("FORBIDDEN"))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preferred_study_windows_use_profile_time_zone() {
+        // UTC 月曜 10:00〜10:15 に10セッション（東京の月曜 19 時台）
+        let schema = build_schema(
+            &QueryLimitsConfig {
+                max_depth:               10,
+                max_query_cost:          100,
+                max_concurrent_per_user: 1,
+                max_queued_per_user:     0,
+            },
+            Arc::new(InMemoryStats {
+                mastery: BTreeMap::new(),
+                streaks: BTreeMap::new(),
+            }),
+            WeeklyReportStore::new(Arc::new(InMemoryBlobStore::new())),
+            Arc::new(InMemoryCollections::default()),
+            study_windows(vec![(10 * 4, 10)]),
+        );
+        let query = format!(
+            r#"{{ preferredStudyWindows(userId: "{OWNER}") {{
+                utcOffsetMinutes totalSessions windows {{ weekday hour sessions confidence }}
+            }} }}"#
+        );
+
+        let response = schema.execute(as_user(&query, Some(OWNER))).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            json!({
+                "preferredStudyWindows": {
+                    "utcOffsetMinutes": 540,
+                    "totalSessions": 10,
+                    "windows": [{ "weekday": 0, "hour": 19, "sessions": 10, "confidence": 1.0 }],
+                },
+            })
+        );
+
+        let response = schema.execute(as_user(&query, Some(PRIVATE_MEMBER))).await;
+        assert_eq!(
+            response.errors[0].message,
+            "Study windows are only available to their owner"
+        );
+    }
+
+    #[test]
+    fn test_every_field_has_cost_annotation() {
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription).finish();
+
+        let missing = cost_model().missing_annotations(&schema.sdl()).unwrap();
+        assert!(
+            missing.is_empty(),
+            "Fields without cost annotation (add them to COST_ANNOTATIONS): {missing:?}"
+        );
+    }
+
+    #[test]
+    fn test_missing_annotation_is_detected() {
+        let annotations: Vec<_> = COST_ANNOTATIONS
+            .iter()
+            .filter(|cost| cost.field != "serviceStatus")
+            .copied()
+            .collect();
+        let model = CostModel::new("QueryRoot", &annotations);
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription).finish();
+
+        assert_eq!(
+            model.missing_annotations(&schema.sdl()).unwrap(),
+            vec!["QueryRoot.serviceStatus".to_string()]
+        );
+    }
+}
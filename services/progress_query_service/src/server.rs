@@ -1,39 +1,50 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
-use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
-use async_graphql_axum::GraphQL;
-use axum::{Json, Router, routing::get};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{
+    Json,
+    Router,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+};
 use serde_json::json;
-use tracing::info;
+use shared_blob_store::FileSystemBlobStore;
+use shared_integration_events::CachedPreferenceSource;
+use shared_user_context::UserServicePreferenceSource;
+use sqlx::{PgPool, postgres::PgPoolOptions};
+use tracing::{info, warn};
+use uuid::Uuid;
 
-use crate::config::Config;
+use crate::{
+    collections::PostgresCollectionStatsRepository,
+    concurrency::UserConcurrencyLimiter,
+    config::Config,
+    error::ServiceError,
+    reports::WeeklyReportStore,
+    repositories::PostgresProgressStatsRepository,
+    schema::{ProgressSchema, RequestingUser, build_schema},
+    study_time::{PostgresStudyTimeRepository, StudyWindowsReader},
+};
 
-// GraphQL Query Root
-pub struct QueryRoot;
+/// リクエストしたユーザーを識別するヘッダー（API Gateway が付与する）
+const USER_ID_HEADER: &str = "x-user-id";
 
-#[Object]
-impl QueryRoot {
-    async fn api_version(&self) -> &str {
-        "0.1.0"
-    }
-
-    async fn service_status(&self) -> &str {
-        "Progress Query Service - 未実装"
-    }
+#[derive(Clone)]
+struct AppState {
+    schema:  ProgressSchema,
+    limiter: Arc<UserConcurrencyLimiter>,
 }
 
 pub async fn run(config: Config) -> crate::error::Result<()> {
-    // GraphQL スキーマ構築
-    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription).finish();
-
-    // ルーター構築
-    let app = Router::new()
-        .route(
-            "/graphql",
-            get(graphql_playground).post_service(GraphQL::new(schema)),
-        )
-        .route("/health", get(health_check))
-        .route("/", get(index));
+    // Read Model への接続
+    let pool = PgPoolOptions::new()
+        .max_connections(config.database.max_connections)
+        .connect(&config.database.url)
+        .await?;
+
+    let app = build_router(&config, pool)?;
 
     // サーバーアドレス
     let addr = SocketAddr::from(([0, 0, 0, 0], config.server.port));
@@ -46,6 +57,77 @@ pub async fn run(config: Config) -> crate::error::Result<()> {
     Ok(())
 }
 
+/// Read Model のプールからルーターを組み立てる
+///
+/// 統合バイナリ（effect_monolith）はこのルーターをパスの下にネストして使う
+pub fn build_router(config: &Config, pool: PgPool) -> crate::error::Result<Router> {
+    // タイムゾーンは User Service の通知設定から取得する
+    let user_service = UserServicePreferenceSource::connect_lazy(config.users.url.clone())
+        .map_err(|e| ServiceError::Config(format!("Invalid USER_SERVICE_URL: {e}")))?;
+    let preferences = Arc::new(CachedPreferenceSource::new(
+        user_service,
+        Duration::from_secs(config.users.preference_cache_ttl_secs),
+    ));
+
+    // GraphQL スキーマ構築
+    let schema = build_schema(
+        &config.query,
+        Arc::new(PostgresProgressStatsRepository::new(pool.clone())),
+        WeeklyReportStore::new(Arc::new(FileSystemBlobStore::new(
+            &config.reports.store_dir,
+        ))),
+        Arc::new(PostgresCollectionStatsRepository::new(pool.clone())),
+        StudyWindowsReader::new(
+            Arc::new(PostgresStudyTimeRepository::new(pool)),
+            preferences,
+        ),
+    );
+    let limiter = Arc::new(UserConcurrencyLimiter::new(
+        config.query.max_concurrent_per_user,
+        config.query.max_queued_per_user,
+    ));
+
+    Ok(Router::new()
+        .route("/graphql", get(graphql_playground).post(graphql_handler))
+        .route("/health", get(health_check))
+        .route("/", get(index))
+        .with_state(AppState { schema, limiter }))
+}
+
+async fn graphql_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    request: GraphQLRequest,
+) -> Response {
+    let user_id = headers
+        .get(USER_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("anonymous");
+
+    // 実行枠はレスポンスを返すまで保持する
+    let _permit = match state.limiter.acquire(user_id).await {
+        Ok(permit) => permit,
+        Err(e) => {
+            warn!("{}", e);
+            return (
+                StatusCode::TOO_MANY_REQUESTS,
+                Json(json!({
+                    "errors": [{
+                        "message": e.to_string(),
+                        "extensions": { "code": "TOO_MANY_CONCURRENT_QUERIES" }
+                    }]
+                })),
+            )
+                .into_response();
+        },
+    };
+
+    let request = request
+        .into_inner()
+        .data(RequestingUser(user_id.parse::<Uuid>().ok()));
+    GraphQLResponse::from(state.schema.execute(request).await).into_response()
+}
+
 async fn graphql_playground() -> impl axum::response::IntoResponse {
     axum::response::Html(async_graphql::http::playground_source(
         async_graphql::http::GraphQLPlaygroundConfig::new("/graphql"),
@@ -0,0 +1,92 @@
+//! 学習する時間帯の読み出し（GetPreferredStudyWindows）
+//!
+//! Progress Projection Service が UTC の15分枠ごとに数えたセッション数を、
+//! ユーザーの通知設定のタイムゾーンで集計し直して返す。時間帯の推定は
+//! 復習リマインダーと同じモデル（[`shared_progress_context::study_time`]）を使う。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shared_integration_events::NotificationPreferenceSource;
+use shared_progress_context::study_time::{MIN_STUDY_TIME_SAMPLES, PreferredStudyWindows};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::Result;
+
+/// 学習する時間帯の読み取りポート
+#[async_trait]
+pub trait StudyTimeRepository: Send + Sync {
+    /// UTC の15分枠ごとの `(枠, セッション数)`
+    async fn study_time_quarters(&self, user_id: Uuid) -> Result<Vec<(u32, u32)>>;
+}
+
+/// 学習する時間帯の取得
+#[derive(Clone)]
+pub struct StudyWindowsReader {
+    repository:  Arc<dyn StudyTimeRepository>,
+    preferences: Arc<dyn NotificationPreferenceSource>,
+}
+
+impl StudyWindowsReader {
+    pub fn new(
+        repository: Arc<dyn StudyTimeRepository>,
+        preferences: Arc<dyn NotificationPreferenceSource>,
+    ) -> Self {
+        Self {
+            repository,
+            preferences,
+        }
+    }
+
+    /// ユーザーのローカル時刻で学習することの多い時間帯
+    pub async fn get(&self, user_id: Uuid) -> Result<PreferredStudyWindows> {
+        let preferences = self
+            .preferences
+            .get_preferences(&user_id.to_string())
+            .await?;
+        let quarters = self.repository.study_time_quarters(user_id).await?;
+
+        Ok(PreferredStudyWindows::from_utc_quarters(
+            user_id,
+            &quarters,
+            preferences.offset(),
+            MIN_STUDY_TIME_SAMPLES,
+        ))
+    }
+}
+
+/// PostgreSQL 実装
+pub struct PostgresStudyTimeRepository {
+    pool: PgPool,
+}
+
+impl PostgresStudyTimeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StudyTimeRepository for PostgresStudyTimeRepository {
+    async fn study_time_quarters(&self, user_id: Uuid) -> Result<Vec<(u32, u32)>> {
+        let rows = sqlx::query_as::<_, (i32, i32)>(
+            r#"
+            SELECT quarter_of_week, sessions
+            FROM study_time_histogram
+            WHERE user_id = $1
+            ORDER BY quarter_of_week
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(quarter, sessions)| {
+                Some((u32::try_from(quarter).ok()?, u32::try_from(sessions).ok()?))
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,39 @@
+-- Notification preferences read model
+-- NotificationPreferencesUpdated イベントから投影され、
+-- 内部 RPC GetNotificationPreferences で参照される
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users (id) ON DELETE CASCADE,
+
+    -- 無効化されたカテゴリ（daily_summary, goal_reminder, review_reminder, editorial）
+    disabled_categories TEXT [] NOT NULL DEFAULT '{}',
+
+    -- 希望する配信チャネル（優先順）
+    preferred_channels TEXT [] NOT NULL DEFAULT '{}',
+
+    -- おやすみ時間（ローカル時刻、0:00 からの分）
+    quiet_start_minute INTEGER,
+    quiet_end_minute INTEGER,
+
+    -- プロフィールのタイムゾーン（UTC からのオフセット、分）
+    utc_offset_minutes INTEGER NOT NULL DEFAULT 0,
+
+    -- 投影した最後のイベントのバージョン
+    version BIGINT NOT NULL DEFAULT 1,
+
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_quiet_hours_pair CHECK (
+        (quiet_start_minute IS NULL) = (quiet_end_minute IS NULL)
+    ),
+    CONSTRAINT chk_quiet_hours_range CHECK (
+        quiet_start_minute IS NULL
+        OR (quiet_start_minute BETWEEN 0 AND 1439 AND quiet_end_minute BETWEEN 0 AND 1439)
+    )
+);
+
+COMMENT ON TABLE notification_preferences IS 'Notification preferences read model';
+COMMENT ON COLUMN notification_preferences.disabled_categories IS 'Disabled notification categories';
+COMMENT ON COLUMN notification_preferences.preferred_channels IS 'Preferred delivery channels in priority order';
+COMMENT ON COLUMN notification_preferences.quiet_start_minute IS 'Quiet hours start (minutes from local midnight)';
+COMMENT ON COLUMN notification_preferences.quiet_end_minute IS 'Quiet hours end, exclusive (minutes from local midnight)';
+COMMENT ON COLUMN notification_preferences.utc_offset_minutes IS 'Profile timezone as UTC offset in minutes';
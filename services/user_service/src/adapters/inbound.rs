@@ -0,0 +1,3 @@
+//! 入力アダプター
+
+pub mod grpc;
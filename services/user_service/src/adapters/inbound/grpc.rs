@@ -0,0 +1,390 @@
+//! User Service の gRPC 実装
+//!
+//! 現時点では通知設定の RPC（UpdateNotificationPreferences /
+//! GetNotificationPreferences）のみ実装している。ユーザー集約の RPC は
+//! `UNIMPLEMENTED` を返す。
+
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+use crate::{
+    application::{Error, NotificationPreferencesService},
+    domain::{self, QuietHours, UpdateNotificationPreferences},
+};
+
+// Proto から生成されたコード
+#[allow(clippy::all)]
+#[allow(clippy::pedantic)]
+#[allow(clippy::nursery)]
+#[allow(clippy::restriction)]
+#[allow(warnings)]
+pub mod proto {
+    pub mod effect {
+        pub mod common {
+            tonic::include_proto!("effect.common");
+        }
+        pub mod services {
+            pub mod user {
+                tonic::include_proto!("effect.services.user");
+            }
+        }
+    }
+
+    // 再エクスポート
+    pub use effect::services::user::*;
+}
+
+use proto::{
+    ChangeRoleRequest,
+    ChangeRoleResponse,
+    CreateUserRequest,
+    CreateUserResponse,
+    DeleteUserRequest,
+    DeleteUserResponse,
+    GetNotificationPreferencesRequest,
+    GetNotificationPreferencesResponse,
+    GetUserByEmailRequest,
+    GetUserByEmailResponse,
+    GetUserRequest,
+    GetUserResponse,
+    SetLearningGoalRequest,
+    SetLearningGoalResponse,
+    UpdateNotificationPreferencesRequest,
+    UpdateNotificationPreferencesResponse,
+    UpdateProfileRequest,
+    UpdateProfileResponse,
+    user_service_server::UserService,
+};
+
+impl From<Error> for Status {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Validation(msg) => Self::invalid_argument(msg),
+            Error::UserNotFound(msg) => Self::not_found(msg),
+            Error::Conflict(msg) => Self::aborted(msg),
+            e => Self::internal(e.to_string()),
+        }
+    }
+}
+
+/// ユーザー集約が未実装の RPC の応答
+fn user_aggregate_unimplemented<T>() -> Result<Response<T>, Status> {
+    Err(Status::unimplemented(
+        "The user aggregate is not implemented in this service yet",
+    ))
+}
+
+fn parse_user_id(user_id: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(user_id).map_err(|e| Status::invalid_argument(format!("Invalid user_id: {e}")))
+}
+
+fn category_from_proto(value: i32) -> Result<domain::NotificationCategory, Status> {
+    use proto::NotificationCategory as Proto;
+
+    match Proto::try_from(value) {
+        Ok(Proto::DailySummary) => Ok(domain::NotificationCategory::DailySummary),
+        Ok(Proto::GoalReminder) => Ok(domain::NotificationCategory::GoalReminder),
+        Ok(Proto::ReviewReminder) => Ok(domain::NotificationCategory::ReviewReminder),
+        Ok(Proto::Editorial) => Ok(domain::NotificationCategory::Editorial),
+        Ok(Proto::WeeklyReport) => Ok(domain::NotificationCategory::WeeklyReport),
+        Ok(Proto::Unspecified) | Err(_) => Err(Status::invalid_argument(format!(
+            "Invalid notification category: {value}"
+        ))),
+    }
+}
+
+const fn category_to_proto(category: domain::NotificationCategory) -> proto::NotificationCategory {
+    use proto::NotificationCategory as Proto;
+
+    match category {
+        domain::NotificationCategory::DailySummary => Proto::DailySummary,
+        domain::NotificationCategory::GoalReminder => Proto::GoalReminder,
+        domain::NotificationCategory::ReviewReminder => Proto::ReviewReminder,
+        domain::NotificationCategory::Editorial => Proto::Editorial,
+        domain::NotificationCategory::WeeklyReport => Proto::WeeklyReport,
+    }
+}
+
+fn channel_from_proto(value: i32) -> Result<domain::NotificationChannel, Status> {
+    use proto::NotificationChannel as Proto;
+
+    match Proto::try_from(value) {
+        Ok(Proto::Email) => Ok(domain::NotificationChannel::Email),
+        Ok(Proto::Push) => Ok(domain::NotificationChannel::Push),
+        Ok(Proto::InApp) => Ok(domain::NotificationChannel::InApp),
+        Ok(Proto::Unspecified) | Err(_) => Err(Status::invalid_argument(format!(
+            "Invalid notification channel: {value}"
+        ))),
+    }
+}
+
+const fn channel_to_proto(channel: domain::NotificationChannel) -> proto::NotificationChannel {
+    use proto::NotificationChannel as Proto;
+
+    match channel {
+        domain::NotificationChannel::Email => Proto::Email,
+        domain::NotificationChannel::Push => Proto::Push,
+        domain::NotificationChannel::InApp => Proto::InApp,
+    }
+}
+
+fn minutes_of(time: chrono::NaiveTime) -> u32 {
+    use chrono::Timelike;
+
+    time.hour() * 60 + time.minute()
+}
+
+fn preferences_to_proto(
+    preferences: domain::NotificationPreferences,
+) -> proto::NotificationPreferences {
+    proto::NotificationPreferences {
+        user_id:             preferences.user_id,
+        disabled_categories: preferences
+            .disabled_categories
+            .into_iter()
+            .map(|category| category_to_proto(category) as i32)
+            .collect(),
+        preferred_channels:  preferences
+            .preferred_channels
+            .into_iter()
+            .map(|channel| channel_to_proto(channel) as i32)
+            .collect(),
+        quiet_hours:         preferences.quiet_hours.map(|quiet| proto::QuietHours {
+            start_minute: minutes_of(quiet.start),
+            end_minute:   minutes_of(quiet.end),
+        }),
+        utc_offset_minutes:  preferences.utc_offset_minutes,
+        updated_at:          None,
+    }
+}
+
+impl TryFrom<UpdateNotificationPreferencesRequest> for UpdateNotificationPreferences {
+    type Error = Status;
+
+    fn try_from(request: UpdateNotificationPreferencesRequest) -> Result<Self, Status> {
+        let quiet_hours = request
+            .quiet_hours
+            .map(|quiet| {
+                QuietHours::from_minutes(quiet.start_minute, quiet.end_minute).ok_or_else(|| {
+                    Status::invalid_argument("Quiet hours must be within 0-1439 minutes")
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            user_id: parse_user_id(&request.user_id)?,
+            disabled_categories: request
+                .disabled_categories
+                .into_iter()
+                .map(category_from_proto)
+                .collect::<Result<_, _>>()?,
+            preferred_channels: request
+                .preferred_channels
+                .into_iter()
+                .map(channel_from_proto)
+                .collect::<Result<_, _>>()?,
+            quiet_hours,
+            utc_offset_minutes: request.utc_offset_minutes,
+        })
+    }
+}
+
+/// User Service の gRPC 実装
+pub struct UserServiceImpl {
+    preferences: Arc<NotificationPreferencesService>,
+}
+
+impl UserServiceImpl {
+    /// 新しいインスタンスを作成
+    #[must_use]
+    pub const fn new(preferences: Arc<NotificationPreferencesService>) -> Self {
+        Self { preferences }
+    }
+}
+
+#[tonic::async_trait]
+impl UserService for UserServiceImpl {
+    async fn create_user(
+        &self,
+        _request: Request<CreateUserRequest>,
+    ) -> Result<Response<CreateUserResponse>, Status> {
+        user_aggregate_unimplemented()
+    }
+
+    async fn get_user(
+        &self,
+        _request: Request<GetUserRequest>,
+    ) -> Result<Response<GetUserResponse>, Status> {
+        user_aggregate_unimplemented()
+    }
+
+    async fn get_user_by_email(
+        &self,
+        _request: Request<GetUserByEmailRequest>,
+    ) -> Result<Response<GetUserByEmailResponse>, Status> {
+        user_aggregate_unimplemented()
+    }
+
+    async fn update_profile(
+        &self,
+        _request: Request<UpdateProfileRequest>,
+    ) -> Result<Response<UpdateProfileResponse>, Status> {
+        user_aggregate_unimplemented()
+    }
+
+    async fn set_learning_goal(
+        &self,
+        _request: Request<SetLearningGoalRequest>,
+    ) -> Result<Response<SetLearningGoalResponse>, Status> {
+        user_aggregate_unimplemented()
+    }
+
+    async fn change_role(
+        &self,
+        _request: Request<ChangeRoleRequest>,
+    ) -> Result<Response<ChangeRoleResponse>, Status> {
+        user_aggregate_unimplemented()
+    }
+
+    async fn delete_user(
+        &self,
+        _request: Request<DeleteUserRequest>,
+    ) -> Result<Response<DeleteUserResponse>, Status> {
+        user_aggregate_unimplemented()
+    }
+
+    async fn update_notification_preferences(
+        &self,
+        request: Request<UpdateNotificationPreferencesRequest>,
+    ) -> Result<Response<UpdateNotificationPreferencesResponse>, Status> {
+        let command = UpdateNotificationPreferences::try_from(request.into_inner())?;
+        let preferences = self.preferences.update(command).await?;
+
+        Ok(Response::new(UpdateNotificationPreferencesResponse {
+            preferences: Some(preferences_to_proto(preferences)),
+        }))
+    }
+
+    async fn get_notification_preferences(
+        &self,
+        request: Request<GetNotificationPreferencesRequest>,
+    ) -> Result<Response<GetNotificationPreferencesResponse>, Status> {
+        let user_id = parse_user_id(&request.into_inner().user_id)?;
+        let preferences = self.preferences.get(user_id).await?;
+
+        Ok(Response::new(GetNotificationPreferencesResponse {
+            preferences: Some(preferences_to_proto(preferences)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shared_event_store::testing::InMemoryEventStore;
+    use tonic::Code;
+
+    use super::*;
+    use crate::application::test_support::InMemoryReadModel;
+
+    fn service(user_id: Uuid) -> UserServiceImpl {
+        UserServiceImpl::new(Arc::new(NotificationPreferencesService::new(
+            Arc::new(InMemoryEventStore::new()),
+            Arc::new(InMemoryReadModel::with_user(user_id)),
+        )))
+    }
+
+    fn update_request(user_id: Uuid) -> UpdateNotificationPreferencesRequest {
+        UpdateNotificationPreferencesRequest {
+            user_id:             user_id.to_string(),
+            disabled_categories: vec![proto::NotificationCategory::Editorial as i32],
+            preferred_channels:  vec![proto::NotificationChannel::Push as i32],
+            quiet_hours:         Some(proto::QuietHours {
+                start_minute: 22 * 60,
+                end_minute:   7 * 60,
+            }),
+            utc_offset_minutes:  9 * 60,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_returns_what_update_stored() {
+        let user_id = Uuid::new_v4();
+        let service = service(user_id);
+        let get = || {
+            service.get_notification_preferences(Request::new(GetNotificationPreferencesRequest {
+                user_id: user_id.to_string(),
+            }))
+        };
+
+        let defaults = get().await.unwrap().into_inner().preferences.unwrap();
+        assert!(defaults.disabled_categories.is_empty());
+        assert_eq!(defaults.quiet_hours, None);
+
+        let updated = service
+            .update_notification_preferences(Request::new(update_request(user_id)))
+            .await
+            .unwrap()
+            .into_inner()
+            .preferences;
+
+        assert_eq!(get().await.unwrap().into_inner().preferences, updated);
+        let updated = updated.unwrap();
+        assert_eq!(
+            updated.disabled_categories,
+            vec![proto::NotificationCategory::Editorial as i32]
+        );
+        assert_eq!(
+            updated.quiet_hours,
+            Some(proto::QuietHours {
+                start_minute: 22 * 60,
+                end_minute:   7 * 60,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_requests_and_unknown_users_map_to_status_codes() {
+        let user_id = Uuid::new_v4();
+        let service = service(user_id);
+        let update = |request| service.update_notification_preferences(Request::new(request));
+
+        let unspecified = UpdateNotificationPreferencesRequest {
+            disabled_categories: vec![proto::NotificationCategory::Unspecified as i32],
+            ..update_request(user_id)
+        };
+        assert_eq!(
+            update(unspecified).await.unwrap_err().code(),
+            Code::InvalidArgument
+        );
+
+        let quiet_hours = UpdateNotificationPreferencesRequest {
+            quiet_hours: Some(proto::QuietHours {
+                start_minute: 1440,
+                end_minute:   0,
+            }),
+            ..update_request(user_id)
+        };
+        assert_eq!(
+            update(quiet_hours).await.unwrap_err().code(),
+            Code::InvalidArgument
+        );
+
+        assert_eq!(
+            update(update_request(Uuid::new_v4()))
+                .await
+                .unwrap_err()
+                .code(),
+            Code::NotFound
+        );
+        assert_eq!(
+            service
+                .get_user(Request::new(GetUserRequest::default()))
+                .await
+                .unwrap_err()
+                .code(),
+            Code::Unimplemented
+        );
+    }
+}
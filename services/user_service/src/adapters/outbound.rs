@@ -0,0 +1,3 @@
+//! 出力アダプター
+
+pub mod notification_preferences;
@@ -2,7 +2,7 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use shared_kernel::UserId;
+use shared_kernel::{Email, UserId};
 use shared_repository::{
     Entity,
     Error as RepoError,
@@ -29,7 +29,6 @@ use crate::{
         aggregates::user::User,
         value_objects::{
             account_status::AccountStatus,
-            email::Email,
             user_profile::UserProfile,
             user_role::UserRole,
         },
@@ -329,13 +328,17 @@ impl UserRepository for PostgresUserRepository {
     }
 
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, Self::Error> {
+        // 保存時と同じく正規化したアドレスで探す。形式が正しくなければ該当なし
+        let Ok(email) = Email::new(email) else {
+            return Ok(None);
+        };
         let query = r"
             SELECT * FROM users 
             WHERE email = $1 AND deleted_at IS NULL
         ";
 
         sqlx::query(query)
-            .bind(email)
+            .bind(email.as_str())
             .fetch_optional(&self.pool)
             .await
             .map_err(RepoError::from_sqlx)?
@@ -394,6 +397,8 @@ mod tests {
         // Test find_by_email
         let found = repo.find_by_email("test@example.com").await.unwrap();
         assert!(found.is_some());
+        let found = repo.find_by_email(" Test@Example.COM ").await.unwrap();
+        assert!(found.is_some());
 
         // Test soft delete
         repo.soft_delete(&user_id).await.unwrap();
@@ -3,7 +3,10 @@
 //! ユースケースとアプリケーションロジックを含む
 
 pub mod errors;
-pub mod use_cases;
+pub mod notification_preferences;
+
+#[cfg(test)]
+pub(crate) mod test_support;
 
 pub use errors::ApplicationError as Error;
-pub use use_cases::UseCaseImpl;
+pub use notification_preferences::NotificationPreferencesService;
@@ -0,0 +1,52 @@
+//! アプリケーション層のエラー
+
+use shared_event_store::EventStoreError;
+use thiserror::Error;
+
+use crate::domain::PreferencesError;
+
+/// アプリケーション層のエラー
+#[derive(Debug, Error)]
+pub enum ApplicationError {
+    /// 入力の検証エラー
+    #[error("Validation error: {0}")]
+    Validation(String),
+
+    /// ユーザーが見つからない
+    #[error("User not found: {0}")]
+    UserNotFound(String),
+
+    /// 同時に更新されたため保存できなかった
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// Event Store のエラー
+    #[error("Event store error: {0}")]
+    EventStore(String),
+
+    /// データベースのエラー
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    /// シリアライズのエラー
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+impl From<PreferencesError> for ApplicationError {
+    fn from(err: PreferencesError) -> Self {
+        Self::Validation(err.to_string())
+    }
+}
+
+impl From<EventStoreError> for ApplicationError {
+    fn from(err: EventStoreError) -> Self {
+        match err {
+            EventStoreError::VersionConflict { .. } => Self::Conflict(err.to_string()),
+            err => Self::EventStore(err.to_string()),
+        }
+    }
+}
+
+/// アプリケーション層の Result
+pub type Result<T> = std::result::Result<T, ApplicationError>;
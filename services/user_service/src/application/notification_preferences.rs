@@ -0,0 +1,268 @@
+//! 通知設定のユースケース
+//!
+//! 更新はユーザー集約のストリームに `NotificationPreferencesUpdated` を追記し、
+//! 追記したイベントを [`NotificationPreferencesProjection`] で Read Model に
+//! 投影する。取得は Read Model だけを読む（内部 RPC
+//! `GetNotificationPreferences` 用）。
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use shared_event_store::{
+    EventStore,
+    StoredEvent,
+    retry::{DEFAULT_MAX_ATTEMPTS, with_optimistic_retry},
+};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::{
+    application::errors::{ApplicationError, Result},
+    domain::{
+        NotificationPreferences,
+        USER_AGGREGATE_TYPE,
+        UpdateNotificationPreferences,
+        UserEvent,
+    },
+    ports::outbound::NotificationPreferencesReadModel,
+};
+
+/// 通知設定の Read Model への投影
+pub struct NotificationPreferencesProjection {
+    read_model: Arc<dyn NotificationPreferencesReadModel>,
+}
+
+impl NotificationPreferencesProjection {
+    /// 新しいインスタンスを作成
+    pub fn new(read_model: Arc<dyn NotificationPreferencesReadModel>) -> Self {
+        Self { read_model }
+    }
+
+    /// ユーザー集約のイベントを適用する（通知設定以外のイベントは読み飛ばす）
+    ///
+    /// # Errors
+    ///
+    /// * Read Model の更新に失敗した場合
+    pub async fn apply(&self, event: &StoredEvent) -> Result<()> {
+        if event.aggregate_type != USER_AGGREGATE_TYPE {
+            return Ok(());
+        }
+        match serde_json::from_value::<UserEvent>(event.event_data.clone()) {
+            Ok(UserEvent::NotificationPreferencesUpdated {
+                preferences,
+                updated_at,
+            }) => {
+                self.read_model
+                    .upsert(&preferences, event.event_version, updated_at)
+                    .await
+            },
+            Err(e) => {
+                debug!(
+                    event_type = %event.event_type,
+                    error = %e,
+                    "Skipping user event without notification preferences"
+                );
+                Ok(())
+            },
+        }
+    }
+}
+
+/// 通知設定の更新と取得
+pub struct NotificationPreferencesService {
+    event_store: Arc<dyn EventStore>,
+    read_model:  Arc<dyn NotificationPreferencesReadModel>,
+    projection:  NotificationPreferencesProjection,
+}
+
+impl NotificationPreferencesService {
+    /// 新しいインスタンスを作成
+    pub fn new(
+        event_store: Arc<dyn EventStore>,
+        read_model: Arc<dyn NotificationPreferencesReadModel>,
+    ) -> Self {
+        Self {
+            event_store,
+            projection: NotificationPreferencesProjection::new(Arc::clone(&read_model)),
+            read_model,
+        }
+    }
+
+    /// 通知設定を置き換え、保存した設定を返す
+    ///
+    /// # Errors
+    ///
+    /// * 設定が不正な場合、ユーザーが存在しない場合
+    /// * 競合の再試行が尽きた場合、Event Store・Read Model の操作に失敗した場合
+    pub async fn update(
+        &self,
+        command: UpdateNotificationPreferences,
+    ) -> Result<NotificationPreferences> {
+        let user_id = command.user_id;
+        let preferences = command.into_preferences()?;
+        if !self.read_model.user_exists(user_id).await? {
+            return Err(ApplicationError::UserNotFound(user_id.to_string()));
+        }
+
+        let event = serde_json::to_value(UserEvent::NotificationPreferencesUpdated {
+            preferences: preferences.clone(),
+            updated_at:  Utc::now(),
+        })?;
+        let store = self.event_store.as_ref();
+        let version = with_optimistic_retry(
+            store,
+            user_id,
+            USER_AGGREGATE_TYPE,
+            DEFAULT_MAX_ATTEMPTS,
+            |version| {
+                let event = event.clone();
+                async move {
+                    store
+                        .save_events(user_id, USER_AGGREGATE_TYPE, vec![event], Some(version))
+                        .await
+                        .map(|()| version)
+                }
+            },
+        )
+        .await?;
+
+        for stored in store
+            .load_events(user_id, USER_AGGREGATE_TYPE, Some(version))
+            .await?
+        {
+            self.projection.apply(&stored).await?;
+        }
+
+        Ok(preferences)
+    }
+
+    /// 通知設定を取得する（未設定のユーザーは既定値）
+    ///
+    /// # Errors
+    ///
+    /// * Read Model の読み込みに失敗した場合
+    pub async fn get(&self, user_id: Uuid) -> Result<NotificationPreferences> {
+        Ok(self
+            .read_model
+            .find(user_id)
+            .await?
+            .unwrap_or_else(|| NotificationPreferences::default_for(user_id.to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use shared_event_store::testing::InMemoryEventStore;
+
+    use super::*;
+    use crate::{
+        application::test_support::InMemoryReadModel,
+        domain::{NotificationCategory, NotificationChannel, QuietHours},
+    };
+
+    fn service(
+        store: &Arc<InMemoryEventStore>,
+        read_model: &Arc<InMemoryReadModel>,
+    ) -> NotificationPreferencesService {
+        NotificationPreferencesService::new(
+            Arc::<InMemoryEventStore>::clone(store),
+            Arc::<InMemoryReadModel>::clone(read_model),
+        )
+    }
+
+    fn command(user_id: Uuid, disabled: &[NotificationCategory]) -> UpdateNotificationPreferences {
+        UpdateNotificationPreferences {
+            user_id,
+            disabled_categories: disabled.iter().copied().collect::<BTreeSet<_>>(),
+            preferred_channels: vec![NotificationChannel::Email],
+            quiet_hours: QuietHours::from_minutes(23 * 60, 6 * 60),
+            utc_offset_minutes: 9 * 60,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_updated_preferences_round_trip_through_the_read_model() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let user_id = Uuid::new_v4();
+        let read_model = Arc::new(InMemoryReadModel::with_user(user_id));
+        let service = service(&store, &read_model);
+
+        assert_eq!(
+            service.get(user_id).await.unwrap(),
+            NotificationPreferences::default_for(user_id.to_string())
+        );
+
+        service
+            .update(command(user_id, &[NotificationCategory::DailySummary]))
+            .await
+            .unwrap();
+        let updated = service
+            .update(command(user_id, &[NotificationCategory::Editorial]))
+            .await
+            .unwrap();
+
+        assert_eq!(service.get(user_id).await.unwrap(), updated);
+        assert_eq!(store.current_version(user_id), 2);
+        assert_eq!(read_model.preferences.lock().unwrap()[&user_id].0, 2);
+        assert_eq!(
+            store.events()[1].event_type,
+            "NotificationPreferencesUpdated"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_users_and_invalid_preferences() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let read_model = Arc::new(InMemoryReadModel::default());
+        let service = service(&store, &read_model);
+
+        assert!(matches!(
+            service.update(command(Uuid::new_v4(), &[])).await,
+            Err(ApplicationError::UserNotFound(_))
+        ));
+
+        let user_id = Uuid::new_v4();
+        read_model.users.lock().unwrap().push(user_id);
+        let invalid = UpdateNotificationPreferences {
+            utc_offset_minutes: 15 * 60,
+            ..command(user_id, &[])
+        };
+        assert!(matches!(
+            service.update(invalid).await,
+            Err(ApplicationError::Validation(_))
+        ));
+        assert!(store.events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replaying_the_stream_keeps_the_latest_preferences() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let user_id = Uuid::new_v4();
+        let read_model = Arc::new(InMemoryReadModel::with_user(user_id));
+        let service = service(&store, &read_model);
+        service
+            .update(command(user_id, &[NotificationCategory::DailySummary]))
+            .await
+            .unwrap();
+        let latest = service
+            .update(command(user_id, &[NotificationCategory::GoalReminder]))
+            .await
+            .unwrap();
+
+        let projection =
+            NotificationPreferencesProjection::new(Arc::<InMemoryReadModel>::clone(&read_model));
+        for event in store
+            .load_events(user_id, USER_AGGREGATE_TYPE, None)
+            .await
+            .unwrap()
+        {
+            projection.apply(&event).await.unwrap();
+        }
+
+        assert_eq!(service.get(user_id).await.unwrap(), latest);
+    }
+}
@@ -0,0 +1,63 @@
+//! アプリケーション層テスト用のインメモリ実装
+
+use std::{collections::HashMap, sync::Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    application::errors::Result,
+    domain::NotificationPreferences,
+    ports::outbound::NotificationPreferencesReadModel,
+};
+
+/// 登録済みのユーザーと、投影したバージョンを持つ Read Model
+#[derive(Default)]
+pub struct InMemoryReadModel {
+    pub users:       Mutex<Vec<Uuid>>,
+    pub preferences: Mutex<HashMap<Uuid, (u32, NotificationPreferences)>>,
+}
+
+impl InMemoryReadModel {
+    /// ユーザーを登録した Read Model
+    pub fn with_user(user_id: Uuid) -> Self {
+        let read_model = Self::default();
+        read_model.users.lock().unwrap().push(user_id);
+        read_model
+    }
+}
+
+#[async_trait]
+impl NotificationPreferencesReadModel for InMemoryReadModel {
+    async fn user_exists(&self, user_id: Uuid) -> Result<bool> {
+        Ok(self.users.lock().unwrap().contains(&user_id))
+    }
+
+    async fn find(&self, user_id: Uuid) -> Result<Option<NotificationPreferences>> {
+        Ok(self
+            .preferences
+            .lock()
+            .unwrap()
+            .get(&user_id)
+            .map(|(_, preferences)| preferences.clone()))
+    }
+
+    async fn upsert(
+        &self,
+        preferences: &NotificationPreferences,
+        version: u32,
+        _updated_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let user_id = preferences.user_id.parse().unwrap();
+        let mut stored = self.preferences.lock().unwrap();
+        if stored
+            .get(&user_id)
+            .is_none_or(|(current, _)| *current < version)
+        {
+            stored.insert(user_id, (version, preferences.clone()));
+        }
+        drop(stored);
+        Ok(())
+    }
+}
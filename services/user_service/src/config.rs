@@ -5,6 +5,7 @@ use thiserror::Error;
 use validator::{Validate, ValidationError, ValidationErrors};
 
 /// 設定エラー
+#[allow(clippy::module_name_repetitions)]
 #[derive(Error, Debug)]
 pub enum ConfigError {
     /// 設定の読み込みエラー
@@ -19,18 +20,21 @@ pub enum ConfigError {
 }
 
 /// ユーザーサービスの設定
-#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct Config {
     /// サーバー設定
     #[validate(nested)]
-    pub server:   Server,
-    /// データベース設定
+    pub server:      Server,
+    /// データベース設定（Read Model）
+    #[validate(nested)]
+    pub database:    Database,
+    /// Event Store のデータベース設定
     #[validate(nested)]
-    pub database: Database,
+    pub event_store: Database,
     /// 認証設定
-    pub auth:     Auth,
+    pub auth:        Auth,
     /// イベントバス設定
-    pub event:    Event,
+    pub event:       Event,
 }
 
 /// ポート番号のバリデーション
@@ -105,10 +109,11 @@ pub struct MockToken {
 }
 
 /// イベントバス設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Event {
     /// インメモリイベントバス（開発環境用）
+    #[default]
     #[serde(rename = "memory")]
     Memory,
     /// Google Pub/Sub
@@ -139,17 +144,26 @@ impl Default for Database {
     }
 }
 
-impl Default for Auth {
+impl Default for Config {
     fn default() -> Self {
-        Self::Mock {
-            predefined_tokens: None,
+        Self {
+            server:      Server::default(),
+            database:    Database::default(),
+            event_store: Database {
+                url:             String::from("postgres://localhost/effect_event_store"),
+                max_connections: 10,
+            },
+            auth:        Auth::default(),
+            event:       Event::default(),
         }
     }
 }
 
-impl Default for Event {
+impl Default for Auth {
     fn default() -> Self {
-        Self::Memory
+        Self::Mock {
+            predefined_tokens: None,
+        }
     }
 }
 
@@ -167,6 +181,8 @@ impl Config {
             .set_default("server.port", 50051)?
             .set_default("database.url", "postgres://localhost/effect_user")?
             .set_default("database.max_connections", 10)?
+            .set_default("event_store.url", "postgres://localhost/effect_event_store")?
+            .set_default("event_store.max_connections", 10)?
             .set_default("auth.type", "mock")?
             .set_default("event.type", "memory")?
             // 設定ファイルから読み込み（オプション）
@@ -208,6 +224,7 @@ impl Config {
             .set_default("server.host", "0.0.0.0")?
             .set_default("server.port", 50051)?
             .set_default("database.max_connections", 10)?
+            .set_default("event_store.max_connections", 10)?
             .set_default("auth.type", "mock")?
             .set_default("event.type", "memory")?
             // 環境変数から読み込み（USER_SERVICE_ プレフィックス）
@@ -238,21 +255,25 @@ impl Config {
     #[must_use]
     pub fn for_test() -> Self {
         Self {
-            server:   Server {
+            server:      Server {
                 host: String::from("127.0.0.1"),
                 port: 50051,
             },
-            database: Database {
+            database:    Database {
                 url:             String::from("postgres://localhost/effect_user_test"),
                 max_connections: 5,
             },
-            auth:     Auth::Mock {
+            event_store: Database {
+                url:             String::from("postgres://localhost/effect_event_store_test"),
+                max_connections: 5,
+            },
+            auth:        Auth::Mock {
                 predefined_tokens: Some(vec![MockToken {
                     token:   String::from("test-token"),
                     user_id: String::from("test-user"),
                 }]),
             },
-            event:    Event::Memory,
+            event:       Event::Memory,
         }
     }
 }
@@ -425,6 +446,10 @@ mod tests {
             .unwrap()
             .set_default("database.max_connections", 5)
             .unwrap()
+            .set_default("event_store.url", "postgres://localhost/events")
+            .unwrap()
+            .set_default("event_store.max_connections", 5)
+            .unwrap()
             .set_default("auth.type", "mock")
             .unwrap()
             .set_default("event.type", "memory")
@@ -1,7 +1,5 @@
 //! ドメイン層
 
-pub mod aggregates;
-pub mod commands;
-pub mod events;
-pub mod services;
-pub mod value_objects;
+pub mod notification_preferences;
+
+pub use notification_preferences::*;
@@ -0,0 +1,160 @@
+//! 通知設定
+//!
+//! 通知設定はユーザー集約のストリームに `NotificationPreferencesUpdated`
+//! として記録し、Read Model（`notification_preferences`）に投影する。
+//! 設定の中身と適用のルールは通知を発行する各サービスと共有するため、
+//! `shared_integration_events` の [`NotificationPreferences`] を使う。
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+pub use shared_integration_events::{
+    NotificationCategory,
+    NotificationChannel,
+    NotificationPreferences,
+    QuietHours,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// ユーザー集約のストリームの集約タイプ
+pub const USER_AGGREGATE_TYPE: &str = "user";
+
+/// UTC からのオフセットの上限（分、UTC+14:00）
+pub const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+/// 通知設定の検証エラー
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PreferencesError {
+    /// UTC からのオフセットが範囲外
+    #[error("UTC offset must be within ±{MAX_UTC_OFFSET_MINUTES} minutes: {0}")]
+    UtcOffsetOutOfRange(i32),
+
+    /// 同じチャネルが複数回指定された
+    #[error("Preferred channel is listed more than once: {0:?}")]
+    DuplicateChannel(NotificationChannel),
+}
+
+/// 通知設定を更新するコマンド（設定全体を置き換える）
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateNotificationPreferences {
+    /// 対象のユーザー
+    pub user_id:             Uuid,
+    /// 無効化するカテゴリ
+    pub disabled_categories: BTreeSet<NotificationCategory>,
+    /// 希望する配信チャネル（優先順）
+    pub preferred_channels:  Vec<NotificationChannel>,
+    /// None の場合はおやすみ時間を解除する
+    pub quiet_hours:         Option<QuietHours>,
+    /// プロフィールのタイムゾーン（UTC からのオフセット、分）
+    pub utc_offset_minutes:  i32,
+}
+
+impl UpdateNotificationPreferences {
+    /// 検証して、保存する通知設定にする
+    ///
+    /// # Errors
+    ///
+    /// * オフセットが範囲外の場合、チャネルが重複している場合
+    pub fn into_preferences(self) -> Result<NotificationPreferences, PreferencesError> {
+        if self.utc_offset_minutes.abs() > MAX_UTC_OFFSET_MINUTES {
+            return Err(PreferencesError::UtcOffsetOutOfRange(
+                self.utc_offset_minutes,
+            ));
+        }
+        for (index, channel) in self.preferred_channels.iter().enumerate() {
+            if self.preferred_channels[..index].contains(channel) {
+                return Err(PreferencesError::DuplicateChannel(*channel));
+            }
+        }
+
+        Ok(NotificationPreferences {
+            user_id:             self.user_id.to_string(),
+            disabled_categories: self.disabled_categories,
+            preferred_channels:  self.preferred_channels,
+            quiet_hours:         self.quiet_hours,
+            utc_offset_minutes:  self.utc_offset_minutes,
+        })
+    }
+}
+
+/// ユーザー集約のイベント（Event Store に保存する JSON）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event_type")]
+pub enum UserEvent {
+    /// 通知設定が更新された
+    NotificationPreferencesUpdated {
+        /// 更新後の設定
+        preferences: NotificationPreferences,
+        /// 更新日時
+        updated_at:  DateTime<Utc>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+
+    use super::*;
+
+    fn command() -> UpdateNotificationPreferences {
+        UpdateNotificationPreferences {
+            user_id:             Uuid::from_u128(1),
+            disabled_categories: BTreeSet::from([NotificationCategory::Editorial]),
+            preferred_channels:  vec![NotificationChannel::Push, NotificationChannel::Email],
+            quiet_hours:         QuietHours::from_minutes(22 * 60, 7 * 60),
+            utc_offset_minutes:  9 * 60,
+        }
+    }
+
+    #[test]
+    fn test_command_becomes_preferences_of_the_user() {
+        let preferences = command().into_preferences().unwrap();
+
+        assert_eq!(preferences.user_id, Uuid::from_u128(1).to_string());
+        assert!(!preferences.is_enabled(NotificationCategory::Editorial));
+        assert_eq!(
+            preferences.quiet_hours.unwrap().start,
+            NaiveTime::from_hms_opt(22, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_offset_and_duplicate_channels() {
+        let offset = UpdateNotificationPreferences {
+            utc_offset_minutes: -(MAX_UTC_OFFSET_MINUTES + 1),
+            ..command()
+        };
+        assert_eq!(
+            offset.into_preferences(),
+            Err(PreferencesError::UtcOffsetOutOfRange(-841))
+        );
+
+        let duplicate = UpdateNotificationPreferences {
+            preferred_channels: vec![NotificationChannel::Push, NotificationChannel::Push],
+            ..command()
+        };
+        assert_eq!(
+            duplicate.into_preferences(),
+            Err(PreferencesError::DuplicateChannel(
+                NotificationChannel::Push
+            ))
+        );
+    }
+
+    #[test]
+    fn test_event_data_carries_the_event_type() {
+        let event = UserEvent::NotificationPreferencesUpdated {
+            preferences: command().into_preferences().unwrap(),
+            updated_at:  Utc::now(),
+        };
+
+        let data = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(data["event_type"], "NotificationPreferencesUpdated");
+        assert_eq!(data["preferences"]["disabled_categories"][0], "editorial");
+        assert_eq!(serde_json::from_value::<UserEvent>(data).unwrap(), event);
+    }
+}
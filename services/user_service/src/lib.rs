@@ -1,3 +1,14 @@
 //! User Service ライブラリ
 //!
-//! TODO: CQRS + Event Sourcing パターンで実装予定
+//! 現時点では通知設定（更新・取得）のみを実装している。ユーザー集約は
+//! CQRS + Event Sourcing パターンで実装予定
+
+pub mod adapters;
+pub mod application;
+pub mod config;
+pub mod domain;
+pub mod ports;
+pub mod server;
+
+/// ユーザーテーブルのマイグレーション
+pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
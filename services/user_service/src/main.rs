@@ -1,7 +1,13 @@
 //! User Service - ユーザー管理マイクロサービス
 //!
-//! TODO: CQRS + Event Sourcing パターンで実装予定
+//! 現時点では通知設定の RPC のみを提供する
 
-fn main() {
-    println!("User Service - 実装予定");
+use user_service::{config::Config, server};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt::init();
+
+    let config = Config::load()?;
+    server::start(config).await
 }
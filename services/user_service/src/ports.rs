@@ -1,4 +1,3 @@
 //! ポート定義（ヘキサゴナルアーキテクチャのインターフェース）
 
-pub mod inbound;
 pub mod outbound;
@@ -0,0 +1,27 @@
+//! 出力ポート
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{application::errors::Result, domain::NotificationPreferences};
+
+/// 通知設定の Read Model
+#[async_trait]
+pub trait NotificationPreferencesReadModel: Send + Sync {
+    /// ユーザーが存在するか（削除済みのユーザーは存在しない）
+    async fn user_exists(&self, user_id: Uuid) -> Result<bool>;
+
+    /// 保存済みの通知設定（未設定のユーザーは None）
+    async fn find(&self, user_id: Uuid) -> Result<Option<NotificationPreferences>>;
+
+    /// イベントのバージョンで通知設定を書き込む
+    ///
+    /// 保存済みのバージョン以下のイベントは無視する（再投影しても戻らない）
+    async fn upsert(
+        &self,
+        preferences: &NotificationPreferences,
+        version: u32,
+        updated_at: DateTime<Utc>,
+    ) -> Result<()>;
+}
@@ -1,20 +1,62 @@
 //! gRPC サーバー実装
 
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 
+use shared_event_store::postgres::PostgresEventStore;
+use sqlx::{PgPool, postgres::PgPoolOptions};
+use tonic::transport::Server;
 use tracing::info;
 
-use crate::config::ServiceConfig;
+use crate::{
+    MIGRATOR,
+    adapters::{
+        inbound::grpc::{UserServiceImpl, proto::user_service_server::UserServiceServer},
+        outbound::notification_preferences::PostgresNotificationPreferences,
+    },
+    application::NotificationPreferencesService,
+    config::{Config, Database},
+};
+
+async fn connect(database: &Database) -> Result<PgPool, sqlx::Error> {
+    PgPoolOptions::new()
+        .max_connections(database.max_connections)
+        .connect(&database.url)
+        .await
+}
+
+/// Read Model と Event Store の接続から gRPC サービスを組み立てる
+#[must_use]
+pub fn build_service(read_model_pool: PgPool, event_store_pool: PgPool) -> UserServiceImpl {
+    let preferences = NotificationPreferencesService::new(
+        Arc::new(PostgresEventStore::new(event_store_pool)),
+        Arc::new(PostgresNotificationPreferences::new(read_model_pool)),
+    );
+    UserServiceImpl::new(Arc::new(preferences))
+}
 
 /// gRPC サーバーを起動
-pub async fn start(config: ServiceConfig) -> Result<(), Box<dyn std::error::Error>> {
-    let addr: SocketAddr = format!("0.0.0.0:{}", config.port).parse()?;
+///
+/// # Errors
+///
+/// * データベースへの接続、マイグレーション、サーバーの起動に失敗した場合
+pub async fn start(config: Config) -> Result<(), Box<dyn std::error::Error>> {
+    let addr: SocketAddr = format!("{}:{}", config.server.host, config.server.port).parse()?;
+
+    let read_model_pool = connect(&config.database).await?;
+    MIGRATOR.run(&read_model_pool).await?;
+    let event_store_pool = connect(&config.event_store).await?;
 
     info!("User Service listening on {}", addr);
 
-    // TODO: gRPC サーバーの実装
-    // 現時点では、シグナルを待つだけ
-    tokio::signal::ctrl_c().await?;
+    Server::builder()
+        .add_service(UserServiceServer::new(build_service(
+            read_model_pool,
+            event_store_pool,
+        )))
+        .serve_with_shutdown(addr, async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
 
     info!("User Service shutting down...");
 
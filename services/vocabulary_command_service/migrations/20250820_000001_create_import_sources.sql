@@ -0,0 +1,18 @@
+-- 外部辞書ソース（Wiktionary, WordNet など）のレジストリ
+-- ライセンス遵守のため、インポートデータの出典を追跡する
+
+CREATE TABLE IF NOT EXISTS import_sources (
+    source_id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL UNIQUE,
+    license VARCHAR(255) NOT NULL,
+    attribution_text TEXT NOT NULL DEFAULT '',
+    url TEXT,
+    requires_attribution BOOLEAN NOT NULL DEFAULT FALSE,
+    import_examples BOOLEAN NOT NULL DEFAULT TRUE,
+    examples_require_review BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- スペリングキー（正規化したスペリング）による重複判定用インデックス
+CREATE INDEX idx_vocabulary_entries_spelling_key
+ON vocabulary_entries (LOWER(REGEXP_REPLACE(BTRIM(spelling), '\s+', ' ', 'g')));
@@ -0,0 +1,7 @@
+-- 語彙項目の学習コンテキスト（使用域・専門分野）
+-- 値は shared_vocabulary_context の Register / Domain の正規化された名前
+
+ALTER TABLE vocabulary_items
+    ADD COLUMN IF NOT EXISTS register VARCHAR(50)
+        CHECK (register IN ('formal', 'neutral', 'informal', 'slang', 'technical')),
+    ADD COLUMN IF NOT EXISTS domain VARCHAR(50);
@@ -0,0 +1,5 @@
+-- 語彙項目の使用上の注意（学習時に解答とともに提示する）
+-- 値は shared_vocabulary_context の UsageNotes（前後の空白を除いた 500 文字以内）
+
+ALTER TABLE vocabulary_items
+    ADD COLUMN IF NOT EXISTS usage_notes TEXT;
@@ -1,15 +1,21 @@
+use uuid::Uuid;
+
 use crate::{
     domain::{
         CreateVocabularyItem,
         Disambiguation,
+        Domain,
         DomainEvent,
         EntryId,
         EventMetadata,
+        ItemId,
+        Register,
         Spelling,
+        UsageNotes,
         VocabularyItem,
         VocabularyItemCreated,
     },
-    error::Result,
+    error::{Error, Result},
     ports::{
         event_store::EventStore,
         repositories::{VocabularyEntryRepository, VocabularyItemRepository},
@@ -48,6 +54,39 @@ where
             Spelling::new(command.spelling.clone()).map_err(crate::error::Error::Validation)?;
         let disambiguation = Disambiguation::new(command.disambiguation.clone())
             .map_err(crate::error::Error::Validation)?;
+        let register = command
+            .register
+            .as_deref()
+            .map(str::parse::<Register>)
+            .transpose()
+            .map_err(crate::error::Error::Validation)?;
+        let domain = command
+            .domain
+            .as_deref()
+            .map(str::parse::<Domain>)
+            .transpose()
+            .map_err(crate::error::Error::Validation)?;
+        let usage_notes = command
+            .usage_notes
+            .as_deref()
+            .map(str::parse::<UsageNotes>)
+            .transpose()
+            .map_err(crate::error::Error::Validation)?;
+
+        // クライアントが生成した ID の検証（同じ内容の再送なら既存の項目を返す）
+        if let Some(client_id) = command.client_id
+            && let Some(existing) = self
+                .check_client_id(
+                    client_id,
+                    &command,
+                    register.as_ref(),
+                    domain.as_ref(),
+                    usage_notes.as_ref(),
+                )
+                .await?
+        {
+            return Ok(existing);
+        }
 
         // エントリの取得または作成
         use crate::domain::VocabularyEntry;
@@ -81,7 +120,12 @@ where
         };
 
         // 集約の生成
-        let item = VocabularyItem::create(entry.entry_id, spelling, disambiguation);
+        let mut item = VocabularyItem::create(entry.entry_id, spelling, disambiguation)
+            .with_learning_context(register, domain)
+            .with_usage_notes(usage_notes);
+        if let Some(client_id) = command.client_id {
+            item = item.with_item_id(ItemId::from_uuid(client_id));
+        }
 
         // リポジトリに保存
         self.item_repository.save(&item).await?;
@@ -93,11 +137,78 @@ where
             entry_id:       *entry.entry_id.as_uuid(),
             spelling:       command.spelling,
             disambiguation: command.disambiguation,
+            provenance:     None,
+            register:       item.register.map(|r| r.as_str().to_string()),
+            domain:         item.domain.as_ref().map(|d| d.as_str().to_string()),
+            usage_notes:    item.usage_notes.as_ref().map(|n| n.as_str().to_string()),
+            created_by:     command.issued_by,
         });
         self.event_store.append_event(event).await?;
 
         Ok(item)
     }
+
+    /// クライアントが生成した ID を検証する
+    ///
+    /// クライアント指定の ID は認証済みユーザーが自分の下書きを作る場合だけ
+    /// 受け付ける（インポートや AI フローでは常にサーバーが生成する）。
+    /// ID が使用済みの場合、同じユーザーによる同じ内容の作成なら再送として
+    /// 既存の項目を返し、内容が異なれば競合とする。他の作成者の項目であれば
+    /// 既存の状態の要約を付けて拒否する
+    async fn check_client_id(
+        &self,
+        client_id: Uuid,
+        command: &CreateVocabularyItem,
+        register: Option<&Register>,
+        domain: Option<&Domain>,
+        usage_notes: Option<&UsageNotes>,
+    ) -> Result<Option<VocabularyItem>> {
+        if command.issued_by.is_none() {
+            return Err(Error::PermissionDenied(
+                "client_id is only accepted for drafts of authenticated users".to_string(),
+            ));
+        }
+        if client_id.is_nil() {
+            return Err(Error::Validation("client_id must not be nil".to_string()));
+        }
+
+        let Some(existing) = self
+            .item_repository
+            .find_by_id(&ItemId::from_uuid(client_id))
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let created = self
+            .event_store
+            .get_events_by_aggregate_id(client_id)
+            .await?
+            .into_iter()
+            .find_map(|event| match event {
+                DomainEvent::VocabularyItemCreated(created) => Some(created),
+                _ => None,
+            });
+
+        match created {
+            Some(created) if created.created_by == command.issued_by => {
+                let same_payload = created.spelling == command.spelling
+                    && created.disambiguation == command.disambiguation
+                    && created.register.as_deref() == register.map(Register::as_str)
+                    && created.domain.as_deref() == domain.map(Domain::as_str)
+                    && created.usage_notes.as_deref() == usage_notes.map(UsageNotes::as_str)
+                    && (command.entry_id.is_nil() || created.entry_id == command.entry_id);
+                if same_payload {
+                    Ok(Some(existing))
+                } else {
+                    Err(Error::Conflict(format!(
+                        "client_id {client_id} was already used with a different payload"
+                    )))
+                }
+            },
+            _ => Err(Error::AlreadyExists(Box::new(existing.summary()))),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -127,6 +238,11 @@ mod tests {
             entry_id,
             spelling: "apple".to_string(),
             disambiguation: Some("fruit".to_string()),
+            register: None,
+            domain: None,
+            usage_notes: None,
+            client_id: None,
+            issued_by: None,
         };
 
         // リポジトリのモック設定
@@ -179,6 +295,11 @@ mod tests {
             entry_id,
             spelling: "apple".to_string(),
             disambiguation: None,
+            register: None,
+            domain: None,
+            usage_notes: None,
+            client_id: None,
+            issued_by: None,
         };
 
         // エントリが見つからない
@@ -216,6 +337,11 @@ mod tests {
             entry_id,
             spelling: "".to_string(), // 空のスペリングは無効
             disambiguation: None,
+            register: None,
+            domain: None,
+            usage_notes: None,
+            client_id: None,
+            issued_by: None,
         };
 
         // スペリングバリデーションで失敗するため、exists は呼ばれない
@@ -248,6 +374,11 @@ mod tests {
             entry_id,
             spelling: "run".to_string(),
             disambiguation: Some("  ".to_string()), // 空白のみは None として扱われる
+            register: None,
+            domain: None,
+            usage_notes: None,
+            client_id: None,
+            issued_by: None,
         };
 
         mock_entry_repo
@@ -294,4 +425,235 @@ mod tests {
         let item = result.unwrap();
         assert!(item.disambiguation.is_none());
     }
+
+    #[tokio::test]
+    async fn test_create_vocabulary_item_with_learning_context() {
+        // Arrange
+        let mut mock_entry_repo = MockEntryRepository::new();
+        let mut mock_item_repo = MockItemRepository::new();
+        let mut mock_event_store = MockEventStore::new();
+
+        let entry_id = Uuid::new_v4();
+        let command = CreateVocabularyItem {
+            entry_id,
+            spelling: "diagnosis".to_string(),
+            disambiguation: None,
+            register: Some("Formal".to_string()),
+            domain: Some("medical".to_string()),
+            usage_notes: Some("  Countable; plural \"diagnoses\" ".to_string()),
+            client_id: None,
+            issued_by: None,
+        };
+
+        mock_entry_repo.expect_exists().returning(|_| Ok(true));
+        let mut entry = VocabularyEntry::create(Spelling::new("diagnosis".to_string()).unwrap());
+        entry.entry_id = EntryId::from_uuid(entry_id);
+        mock_entry_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(entry.clone())));
+
+        mock_item_repo.expect_save().times(1).returning(|item| {
+            assert_eq!(item.register, Some(Register::Formal));
+            assert_eq!(item.domain, Some(Domain::Medical));
+            Ok(())
+        });
+
+        mock_event_store
+            .expect_append_event()
+            .times(1)
+            .returning(|event| {
+                // イベントには正規化された値が載る
+                match event {
+                    DomainEvent::VocabularyItemCreated(e) => {
+                        assert_eq!(e.register.as_deref(), Some("formal"));
+                        assert_eq!(e.domain.as_deref(), Some("medical"));
+                        assert_eq!(
+                            e.usage_notes.as_deref(),
+                            Some("Countable; plural \"diagnoses\"")
+                        );
+                    },
+                    _ => panic!("Expected VocabularyItemCreated"),
+                }
+                Ok(())
+            });
+
+        let handler =
+            CreateVocabularyItemHandler::new(mock_entry_repo, mock_item_repo, mock_event_store);
+
+        // Act
+        let item = handler.handle(command).await.unwrap();
+
+        // Assert
+        assert_eq!(item.register, Some(Register::Formal));
+        assert_eq!(item.domain, Some(Domain::Medical));
+    }
+
+    #[tokio::test]
+    async fn test_create_vocabulary_item_invalid_register() {
+        // Arrange
+        let handler = CreateVocabularyItemHandler::new(
+            MockEntryRepository::new(),
+            MockItemRepository::new(),
+            MockEventStore::new(),
+        );
+
+        let command = CreateVocabularyItem {
+            entry_id:       Uuid::new_v4(),
+            spelling:       "ain't".to_string(),
+            disambiguation: None,
+            register:       Some("street".to_string()),
+            domain:         None,
+            usage_notes:    None,
+            client_id:      None,
+            issued_by:      None,
+        };
+
+        // Act
+        let result = handler.handle(command).await;
+
+        // Assert
+        match result.unwrap_err() {
+            crate::error::Error::Validation(msg) => {
+                assert!(msg.contains("Invalid Register"));
+            },
+            _ => panic!("Expected Validation error"),
+        }
+    }
+
+    mod client_ids {
+        use super::*;
+        use crate::{
+            application::commands::test_helpers::fakes::{
+                InMemoryEntryRepository,
+                InMemoryItemRepository,
+                RecordingEventStore,
+            },
+            domain::VocabularyStatus,
+            error::Error,
+        };
+
+        type Handler = CreateVocabularyItemHandler<
+            InMemoryEntryRepository,
+            InMemoryItemRepository,
+            RecordingEventStore,
+        >;
+
+        fn handler() -> (Handler, RecordingEventStore) {
+            let event_store = RecordingEventStore::default();
+            let handler = CreateVocabularyItemHandler::new(
+                InMemoryEntryRepository::default(),
+                InMemoryItemRepository::default(),
+                event_store.clone(),
+            );
+            (handler, event_store)
+        }
+
+        fn command(client_id: Uuid, issued_by: Option<Uuid>) -> CreateVocabularyItem {
+            CreateVocabularyItem {
+                entry_id: Uuid::nil(),
+                spelling: "bank".to_string(),
+                disambiguation: Some("river side".to_string()),
+                register: None,
+                domain: None,
+                usage_notes: None,
+                client_id: Some(client_id),
+                issued_by,
+            }
+        }
+
+        #[tokio::test]
+        async fn test_client_id_becomes_item_id() {
+            let (handler, event_store) = handler();
+            let client_id = Uuid::new_v4();
+            let user_id = Uuid::new_v4();
+
+            let item = handler
+                .handle(command(client_id, Some(user_id)))
+                .await
+                .unwrap();
+
+            assert_eq!(*item.item_id.as_uuid(), client_id);
+            assert_eq!(item.status, VocabularyStatus::Draft);
+            match &event_store.recorded()[..] {
+                [DomainEvent::VocabularyItemCreated(created)] => {
+                    assert_eq!(created.item_id, client_id);
+                    assert_eq!(created.metadata.aggregate_id, client_id);
+                    assert_eq!(created.created_by, Some(user_id));
+                },
+                events => panic!("Expected one VocabularyItemCreated, got {events:?}"),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_taken_client_id_is_rejected_with_state_summary() {
+            let (handler, _) = handler();
+            let client_id = Uuid::new_v4();
+            handler
+                .handle(command(client_id, Some(Uuid::new_v4())))
+                .await
+                .unwrap();
+
+            // 別のユーザーが同じ ID で作成しようとした
+            let result = handler
+                .handle(command(client_id, Some(Uuid::new_v4())))
+                .await;
+
+            match result {
+                Err(Error::AlreadyExists(summary)) => {
+                    assert_eq!(summary.item_id, client_id);
+                    assert_eq!(summary.spelling, "bank");
+                    assert_eq!(summary.disambiguation.as_deref(), Some("river side"));
+                    assert_eq!(summary.status, VocabularyStatus::Draft);
+                    assert_eq!(summary.version, 1);
+                },
+                other => panic!("Expected AlreadyExists, got {other:?}"),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_retry_with_same_payload_replays_and_different_payload_conflicts() {
+            let (handler, event_store) = handler();
+            let client_id = Uuid::new_v4();
+            let user_id = Some(Uuid::new_v4());
+
+            let first = handler.handle(command(client_id, user_id)).await.unwrap();
+            let replayed = handler.handle(command(client_id, user_id)).await.unwrap();
+
+            // 再送は成功し、イベントは追加されない
+            assert_eq!(replayed.item_id, first.item_id);
+            assert_eq!(replayed.version, first.version);
+            assert_eq!(event_store.recorded().len(), 1);
+
+            let changed = CreateVocabularyItem {
+                disambiguation: Some("financial institution".to_string()),
+                ..command(client_id, user_id)
+            };
+            assert!(matches!(
+                handler.handle(changed).await,
+                Err(Error::Conflict(_))
+            ));
+            assert_eq!(event_store.recorded().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_client_id_requires_authenticated_user() {
+            let (handler, event_store) = handler();
+
+            let result = handler.handle(command(Uuid::new_v4(), None)).await;
+
+            assert!(matches!(result, Err(Error::PermissionDenied(_))));
+            assert!(event_store.recorded().is_empty());
+
+            // ID を指定しなければ認証なしでもサーバーが ID を生成する
+            let item = handler
+                .handle(CreateVocabularyItem {
+                    client_id: None,
+                    ..command(Uuid::new_v4(), None)
+                })
+                .await
+                .unwrap();
+            assert_eq!(event_store.recorded().len(), 1);
+            assert!(!item.item_id.as_uuid().is_nil());
+        }
+    }
 }
@@ -0,0 +1,600 @@
+use serde::Serialize;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        Disambiguation,
+        DomainEvent,
+        EventMetadata,
+        ExampleAdded,
+        FieldPolicy,
+        IMPORT_TARGET_LANGUAGE,
+        ImportExternalSource,
+        ImportRecord,
+        ImportSource,
+        ItemId,
+        RegisterImportSource,
+        Spelling,
+        SpellingKey,
+        VocabularyEntry,
+        VocabularyEntryCreated,
+        VocabularyItem,
+        VocabularyItemCreated,
+    },
+    error::{Error, Result},
+    ports::{
+        event_store::EventStore,
+        repositories::{
+            ImportSourceRepository,
+            VocabularyEntryRepository,
+            VocabularyItemRepository,
+        },
+    },
+};
+
+/// インポートレポート
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ImportReport {
+    /// 新しいエントリとして作成された行数
+    pub created:  u32,
+    /// 既存エントリにマージされた行数
+    pub merged:   u32,
+    /// 取り込むものがなくスキップされた行数
+    pub skipped:  u32,
+    /// 失敗した行数
+    pub failed:   u32,
+    /// 失敗した行の詳細
+    pub failures: Vec<ImportFailure>,
+}
+
+/// 失敗した行の詳細
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportFailure {
+    /// 行番号（1始まり）
+    pub line:   usize,
+    pub reason: String,
+}
+
+/// 1行のインポート結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ImportOutcome {
+    Created,
+    Merged,
+    Skipped(String),
+}
+
+impl ImportReport {
+    fn record(&mut self, line: usize, outcome: Result<ImportOutcome>) {
+        match outcome {
+            Ok(ImportOutcome::Created) => self.created += 1,
+            Ok(ImportOutcome::Merged) => self.merged += 1,
+            Ok(ImportOutcome::Skipped(reason)) => {
+                info!(line, reason = %reason, "Import row skipped");
+                self.skipped += 1;
+            },
+            Err(e) => {
+                warn!(line, error = %e, "Import row failed");
+                self.failed += 1;
+                self.failures.push(ImportFailure {
+                    line,
+                    reason: e.to_string(),
+                });
+            },
+        }
+    }
+}
+
+/// ストリーミングで受け取った JSONL を行単位に分割する
+///
+/// チャンクの境界が行の途中に来ても、次のチャンクと結合してから行を返す
+#[derive(Debug, Default)]
+pub struct JsonlLineSplitter {
+    pending: String,
+}
+
+impl JsonlLineSplitter {
+    /// チャンクを追加し、完結した行を返す
+    pub fn push(&mut self, chunk: &str) -> Vec<String> {
+        self.pending.push_str(chunk);
+
+        let mut lines = Vec::new();
+        while let Some(pos) = self.pending.find('\n') {
+            let line: String = self.pending.drain(..=pos).collect();
+            lines.push(line.trim_end_matches(['\r', '\n']).to_string());
+        }
+        lines
+    }
+
+    /// 末尾に残った改行なしの行を返す
+    pub fn finish(self) -> Option<String> {
+        let rest = self.pending.trim_end_matches('\r');
+        (!rest.is_empty()).then(|| rest.to_string())
+    }
+}
+
+/// ImportExternalSource コマンドハンドラー
+///
+/// 外部辞書の JSONL を CreateEntry/CreateItem 相当のイベントに変換し、
+/// 出典情報を付与して保存する
+pub struct ImportExternalSourceHandler<ER, IR, ES, SR>
+where
+    ER: VocabularyEntryRepository,
+    IR: VocabularyItemRepository,
+    ES: EventStore,
+    SR: ImportSourceRepository,
+{
+    entry_repository:  ER,
+    item_repository:   IR,
+    event_store:       ES,
+    source_repository: SR,
+}
+
+impl<ER, IR, ES, SR> ImportExternalSourceHandler<ER, IR, ES, SR>
+where
+    ER: VocabularyEntryRepository,
+    IR: VocabularyItemRepository,
+    ES: EventStore,
+    SR: ImportSourceRepository,
+{
+    pub fn new(
+        entry_repository: ER,
+        item_repository: IR,
+        event_store: ES,
+        source_repository: SR,
+    ) -> Self {
+        Self {
+            entry_repository,
+            item_repository,
+            event_store,
+            source_repository,
+        }
+    }
+
+    /// 外部ソースを登録
+    pub async fn register_source(&self, command: RegisterImportSource) -> Result<ImportSource> {
+        let source = ImportSource::register(
+            command.name,
+            command.license,
+            command.attribution_text,
+            command.url,
+            command.requires_attribution,
+            FieldPolicy {
+                import_examples:         command.import_examples,
+                examples_require_review: command.examples_require_review,
+            },
+        )?;
+
+        if self
+            .source_repository
+            .find_by_name(&source.name)
+            .await?
+            .is_some()
+        {
+            return Err(Error::Conflict(format!(
+                "Import source already registered: {}",
+                source.name
+            )));
+        }
+
+        self.source_repository.save(&source).await?;
+        Ok(source)
+    }
+
+    /// 登録済みのソースを取得
+    pub async fn load_source(&self, source_id: Uuid) -> Result<ImportSource> {
+        self.source_repository
+            .find_by_id(source_id)
+            .await?
+            .ok_or_else(|| Error::NotFound(format!("Import source not found: {}", source_id)))
+    }
+
+    /// JSONL 全体をインポート
+    pub async fn handle(&self, command: ImportExternalSource) -> Result<ImportReport> {
+        let source = self.load_source(command.source_id).await?;
+
+        let mut report = ImportReport::default();
+        for (index, line) in command.content.lines().enumerate() {
+            self.import_line(&source, command.imported_by, index + 1, line, &mut report)
+                .await;
+        }
+
+        info!(
+            source = %source.name,
+            created = report.created,
+            merged = report.merged,
+            skipped = report.skipped,
+            failed = report.failed,
+            "External source import finished"
+        );
+        Ok(report)
+    }
+
+    /// 1行をインポートしてレポートに記録
+    ///
+    /// 空行は無視する。行単位の失敗はレポートに記録し、処理は継続する
+    pub async fn import_line(
+        &self,
+        source: &ImportSource,
+        imported_by: Uuid,
+        line_number: usize,
+        line: &str,
+        report: &mut ImportReport,
+    ) {
+        if line.trim().is_empty() {
+            return;
+        }
+
+        let outcome = match serde_json::from_str::<ImportRecord>(line) {
+            Ok(record) => self.import_record(source, imported_by, record).await,
+            Err(e) => Err(Error::Validation(format!("Malformed import row: {}", e))),
+        };
+        report.record(line_number, outcome);
+    }
+
+    async fn import_record(
+        &self,
+        source: &ImportSource,
+        imported_by: Uuid,
+        record: ImportRecord,
+    ) -> Result<ImportOutcome> {
+        if !record.language.eq_ignore_ascii_case(IMPORT_TARGET_LANGUAGE) {
+            return Ok(ImportOutcome::Skipped(format!(
+                "Unsupported language: {}",
+                record.language
+            )));
+        }
+
+        let spelling = Spelling::new(record.spelling.clone()).map_err(Error::Validation)?;
+        let disambiguations = if record.senses.is_empty() {
+            vec![Disambiguation::new(None).map_err(Error::Validation)?]
+        } else {
+            record
+                .senses
+                .iter()
+                .map(|sense| Disambiguation::new(sense.gloss.clone()))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(Error::Validation)?
+        };
+        if let Some(example) = record
+            .examples
+            .iter()
+            .find(|e| e.sense.unwrap_or(0) >= disambiguations.len())
+        {
+            return Err(Error::Validation(format!(
+                "Example refers to unknown sense: {}",
+                example.sense.unwrap_or(0)
+            )));
+        }
+
+        let provenance = source.provenance(record.external_id.clone());
+
+        // スペリングキーで既存エントリと重複判定
+        let key = SpellingKey::from(&spelling);
+        let (entry, entry_created) = match self.entry_repository.find_by_spelling_key(&key).await? {
+            Some(existing) => (existing, false),
+            None => {
+                let entry = VocabularyEntry::create(spelling);
+                self.entry_repository.save(&entry).await?;
+
+                let event = DomainEvent::VocabularyEntryCreated(VocabularyEntryCreated {
+                    metadata:   EventMetadata::new(
+                        *entry.entry_id.as_uuid(),
+                        entry.version.value(),
+                    ),
+                    entry_id:   *entry.entry_id.as_uuid(),
+                    spelling:   entry.spelling.value().to_string(),
+                    provenance: Some(provenance.clone()),
+                });
+                self.event_store.append_event(event).await?;
+                (entry, true)
+            },
+        };
+
+        let mut existing_items = if entry_created {
+            Vec::new()
+        } else {
+            self.item_repository
+                .find_by_entry_id(&entry.entry_id)
+                .await?
+        };
+
+        // 語義ごとにアイテムを作成（既存の語義は再利用し、新規作成分のみ記録）
+        let mut sense_items: Vec<(ItemId, i64, bool)> = Vec::with_capacity(disambiguations.len());
+        for disambiguation in disambiguations {
+            if let Some(existing) = existing_items
+                .iter()
+                .find(|i| !i.is_deleted && i.disambiguation == disambiguation)
+            {
+                sense_items.push((existing.item_id, existing.version.value(), false));
+                continue;
+            }
+
+            let item =
+                VocabularyItem::create(entry.entry_id, entry.spelling.clone(), disambiguation);
+            self.item_repository.save(&item).await?;
+
+            let event = DomainEvent::VocabularyItemCreated(VocabularyItemCreated {
+                metadata:       EventMetadata::new(*item.item_id.as_uuid(), item.version.value()),
+                item_id:        *item.item_id.as_uuid(),
+                entry_id:       *entry.entry_id.as_uuid(),
+                spelling:       item.spelling.value().to_string(),
+                disambiguation: item.disambiguation.as_option().map(|s| s.to_string()),
+                provenance:     Some(provenance.clone()),
+                register:       None,
+                domain:         None,
+                usage_notes:    None,
+                created_by:     None,
+            });
+            self.event_store.append_event(event).await?;
+
+            sense_items.push((item.item_id, item.version.value(), true));
+            existing_items.push(item);
+        }
+
+        // 例文は今回作成したアイテムにのみ追加する（再インポート時の重複を防ぐ）
+        if source.field_policy.import_examples {
+            for example in &record.examples {
+                let (item_id, version, is_new) = sense_items[example.sense.unwrap_or(0)];
+                let text = example.text.trim();
+                if !is_new || text.is_empty() {
+                    continue;
+                }
+
+                let event = DomainEvent::ExampleAdded(ExampleAdded {
+                    metadata:        EventMetadata::new(*item_id.as_uuid(), version),
+                    item_id:         *item_id.as_uuid(),
+                    example:         text.to_string(),
+                    translation:     example.translation.clone(),
+                    added_by:        imported_by,
+                    provenance:      Some(provenance.clone()),
+                    requires_review: source.field_policy.examples_require_review,
+                });
+                self.event_store.append_event(event).await?;
+            }
+        }
+
+        let items_created = sense_items.iter().any(|(_, _, is_new)| *is_new);
+        Ok(if entry_created {
+            ImportOutcome::Created
+        } else if items_created {
+            ImportOutcome::Merged
+        } else {
+            ImportOutcome::Skipped("All senses already exist".to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::commands::test_helpers::{
+        fakes::{InMemoryEntryRepository, InMemoryItemRepository, RecordingEventStore},
+        mocks::MockImportSourceRepository,
+    };
+
+    const FIXTURE: &str = include_str!("../../../tests/fixtures/external_source_import.jsonl");
+
+    fn source(policy: FieldPolicy) -> ImportSource {
+        ImportSource::register(
+            "wiktionary".to_string(),
+            "CC BY-SA 4.0".to_string(),
+            "From Wiktionary, the free dictionary".to_string(),
+            Some("https://en.wiktionary.org".to_string()),
+            true,
+            policy,
+        )
+        .unwrap()
+    }
+
+    struct Fixture {
+        entries: InMemoryEntryRepository,
+        items:   InMemoryItemRepository,
+        events:  RecordingEventStore,
+        handler: ImportExternalSourceHandler<
+            InMemoryEntryRepository,
+            InMemoryItemRepository,
+            RecordingEventStore,
+            MockImportSourceRepository,
+        >,
+    }
+
+    fn fixture(source: ImportSource) -> Fixture {
+        let entries = InMemoryEntryRepository::default();
+        let items = InMemoryItemRepository::default();
+        let events = RecordingEventStore::default();
+
+        let mut sources = MockImportSourceRepository::new();
+        sources
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(source.clone())));
+
+        let handler = ImportExternalSourceHandler::new(
+            entries.clone(),
+            items.clone(),
+            events.clone(),
+            sources,
+        );
+        Fixture {
+            entries,
+            items,
+            events,
+            handler,
+        }
+    }
+
+    fn command(content: &str) -> ImportExternalSource {
+        ImportExternalSource {
+            source_id:   Uuid::new_v4(),
+            imported_by: Uuid::new_v4(),
+            content:     content.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_tags_events_with_provenance() {
+        let source = source(FieldPolicy::default());
+        let f = fixture(source.clone());
+
+        let line = r#"{"spelling":"apple","language":"en","external_id":"wikt-1","senses":[{"gloss":"fruit"}],"examples":[{"text":"An apple a day."}]}"#;
+        let report = f.handler.handle(command(line)).await.unwrap();
+        assert_eq!(report.created, 1);
+
+        let events = f.events.recorded();
+        assert_eq!(events.len(), 3);
+        for event in &events {
+            let provenance = match event {
+                DomainEvent::VocabularyEntryCreated(e) => e.provenance.clone(),
+                DomainEvent::VocabularyItemCreated(e) => e.provenance.clone(),
+                DomainEvent::ExampleAdded(e) => e.provenance.clone(),
+                other => panic!("Unexpected event: {}", other.event_type()),
+            }
+            .expect("provenance should be set");
+            assert_eq!(provenance.source_id, source.source_id);
+            assert_eq!(provenance.license, "CC BY-SA 4.0");
+            assert_eq!(
+                provenance.attribution.as_deref(),
+                Some("From Wiktionary, the free dictionary")
+            );
+            assert_eq!(provenance.external_ref.as_deref(), Some("wikt-1"));
+        }
+
+        // シリアライズ後も出典が保持される
+        let json = serde_json::to_value(&events[1]).unwrap();
+        assert_eq!(json["provenance"]["source_name"], "wiktionary");
+    }
+
+    #[tokio::test]
+    async fn test_examples_require_review_per_source_policy() {
+        let line = r#"{"spelling":"run","language":"en","examples":[{"text":"I run daily."}]}"#;
+
+        let f = fixture(source(FieldPolicy {
+            import_examples:         true,
+            examples_require_review: true,
+        }));
+        f.handler.handle(command(line)).await.unwrap();
+        let reviewed: Vec<bool> = f
+            .events
+            .recorded()
+            .iter()
+            .filter_map(|e| match e {
+                DomainEvent::ExampleAdded(e) => Some(e.requires_review),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(reviewed, vec![true]);
+
+        let f = fixture(source(FieldPolicy::default()));
+        f.handler.handle(command(line)).await.unwrap();
+        assert!(f.events.recorded().iter().any(|e| matches!(
+            e,
+            DomainEvent::ExampleAdded(ExampleAdded {
+                requires_review: false,
+                ..
+            })
+        )));
+
+        // 例文を取り込まないソース
+        let f = fixture(source(FieldPolicy {
+            import_examples:         false,
+            examples_require_review: false,
+        }));
+        f.handler.handle(command(line)).await.unwrap();
+        assert!(
+            !f.events
+                .recorded()
+                .iter()
+                .any(|e| matches!(e, DomainEvent::ExampleAdded(_)))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dedup_merges_into_existing_entry_by_spelling_key() {
+        let f = fixture(source(FieldPolicy::default()));
+        let existing = VocabularyEntry::create(Spelling::new("Bank".to_string()).unwrap());
+        f.entries.save(&existing).await.unwrap();
+        let existing_item = VocabularyItem::create(
+            existing.entry_id,
+            existing.spelling.clone(),
+            Disambiguation::new(Some("finance".to_string())).unwrap(),
+        );
+        f.items.save(&existing_item).await.unwrap();
+
+        let content = [
+            r#"{"spelling":" bank ","language":"en","senses":[{"gloss":"finance"},{"gloss":"river"}]}"#,
+            r#"{"spelling":"BANK","language":"en","senses":[{"gloss":"river"}]}"#,
+        ]
+        .join("\n");
+        let report = f.handler.handle(command(&content)).await.unwrap();
+
+        assert_eq!(report.merged, 1);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.created, 0);
+
+        // エントリは増えず、新しい語義だけが既存エントリに追加される
+        assert_eq!(f.entries.entries.lock().unwrap().len(), 1);
+        let items = f.items.find_by_entry_id(&existing.entry_id).await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(
+            items
+                .iter()
+                .any(|i| i.disambiguation.as_option() == Some("river"))
+        );
+        assert!(
+            !f.events
+                .recorded()
+                .iter()
+                .any(|e| matches!(e, DomainEvent::VocabularyEntryCreated(_)))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_report_accuracy_on_fixture_file() {
+        let f = fixture(source(FieldPolicy::default()));
+        let report = f.handler.handle(command(FIXTURE)).await.unwrap();
+
+        assert_eq!(report.created, 3);
+        assert_eq!(report.merged, 1);
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.failed, 3);
+        let failed_lines: Vec<usize> = report.failures.iter().map(|f| f.line).collect();
+        assert_eq!(failed_lines, vec![6, 7, 9]);
+    }
+
+    #[tokio::test]
+    async fn test_register_source_rejects_duplicate_names() {
+        let mut sources = MockImportSourceRepository::new();
+        sources
+            .expect_find_by_name()
+            .returning(|_| Ok(Some(source(FieldPolicy::default()))));
+        let handler = ImportExternalSourceHandler::new(
+            InMemoryEntryRepository::default(),
+            InMemoryItemRepository::default(),
+            RecordingEventStore::default(),
+            sources,
+        );
+
+        let result = handler
+            .register_source(RegisterImportSource {
+                name:                    "wiktionary".to_string(),
+                license:                 "CC BY-SA 4.0".to_string(),
+                attribution_text:        "From Wiktionary".to_string(),
+                url:                     None,
+                requires_attribution:    true,
+                import_examples:         true,
+                examples_require_review: false,
+            })
+            .await;
+        assert!(matches!(result, Err(Error::Conflict(_))));
+    }
+
+    #[test]
+    fn test_jsonl_splitter_handles_chunk_boundaries() {
+        let mut splitter = JsonlLineSplitter::default();
+        assert!(splitter.push("{\"a\":").is_empty());
+        assert_eq!(splitter.push("1}\r\n{\"b\"").as_slice(), ["{\"a\":1}"]);
+        assert_eq!(splitter.push(":2}\n").as_slice(), ["{\"b\":2}"]);
+        assert!(splitter.push("{\"c\":3}").is_empty());
+        assert_eq!(splitter.finish().as_deref(), Some("{\"c\":3}"));
+    }
+}
@@ -5,11 +5,15 @@ pub mod mocks {
     use uuid::Uuid;
 
     use crate::{
-        domain::{DomainEvent, EntryId, ItemId, VocabularyEntry, VocabularyItem},
+        domain::{DomainEvent, EntryId, ImportSource, ItemId, VocabularyEntry, VocabularyItem},
         error::Result,
         ports::{
             event_store::{AggregateSnapshot, EventStore},
-            repositories::{VocabularyEntryRepository, VocabularyItemRepository},
+            repositories::{
+                ImportSourceRepository,
+                VocabularyEntryRepository,
+                VocabularyItemRepository,
+            },
         },
     };
 
@@ -23,6 +27,7 @@ pub mod mocks {
             async fn exists(&self, entry_id: &EntryId) -> Result<bool>;
             async fn save(&self, entry: &VocabularyEntry) -> Result<()>;
             async fn find_by_spelling(&self, spelling: &crate::domain::Spelling) -> Result<Option<VocabularyEntry>>;
+            async fn find_by_spelling_key(&self, key: &crate::domain::SpellingKey) -> Result<Option<VocabularyEntry>>;
         }
     }
 
@@ -54,4 +59,204 @@ pub mod mocks {
             async fn save_snapshot(&self, snapshot: AggregateSnapshot) -> Result<()>;
         }
     }
+
+    // ImportSourceRepository のモック
+    mock! {
+        pub ImportSourceRepository {}
+
+        #[async_trait]
+        impl ImportSourceRepository for ImportSourceRepository {
+            async fn find_by_id(&self, source_id: Uuid) -> Result<Option<ImportSource>>;
+            async fn find_by_name(&self, name: &str) -> Result<Option<ImportSource>>;
+            async fn save(&self, source: &ImportSource) -> Result<()>;
+        }
+    }
+}
+
+/// 状態を持つインメモリ実装（複数コマンドにまたがる振る舞いの検証用）
+#[cfg(test)]
+pub mod fakes {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use uuid::Uuid;
+
+    use crate::{
+        domain::{
+            DomainEvent,
+            EntryId,
+            ItemId,
+            Spelling,
+            SpellingKey,
+            VocabularyEntry,
+            VocabularyItem,
+        },
+        error::Result,
+        ports::{
+            event_store::{AggregateSnapshot, EventStore},
+            repositories::{VocabularyEntryRepository, VocabularyItemRepository},
+        },
+    };
+
+    /// インメモリの VocabularyEntryRepository
+    #[derive(Clone, Default)]
+    pub struct InMemoryEntryRepository {
+        pub entries: Arc<Mutex<Vec<VocabularyEntry>>>,
+    }
+
+    #[async_trait]
+    impl VocabularyEntryRepository for InMemoryEntryRepository {
+        async fn find_by_id(&self, entry_id: &EntryId) -> Result<Option<VocabularyEntry>> {
+            let entries = self.entries.lock().unwrap();
+            Ok(entries.iter().find(|e| &e.entry_id == entry_id).cloned())
+        }
+
+        async fn exists(&self, entry_id: &EntryId) -> Result<bool> {
+            Ok(self.find_by_id(entry_id).await?.is_some())
+        }
+
+        async fn save(&self, entry: &VocabularyEntry) -> Result<()> {
+            let mut entries = self.entries.lock().unwrap();
+            entries.retain(|e| e.entry_id != entry.entry_id);
+            entries.push(entry.clone());
+            Ok(())
+        }
+
+        async fn find_by_spelling(&self, spelling: &Spelling) -> Result<Option<VocabularyEntry>> {
+            let entries = self.entries.lock().unwrap();
+            Ok(entries.iter().find(|e| &e.spelling == spelling).cloned())
+        }
+
+        async fn find_by_spelling_key(&self, key: &SpellingKey) -> Result<Option<VocabularyEntry>> {
+            let entries = self.entries.lock().unwrap();
+            Ok(entries
+                .iter()
+                .find(|e| &SpellingKey::from(&e.spelling) == key)
+                .cloned())
+        }
+    }
+
+    /// インメモリの VocabularyItemRepository
+    #[derive(Clone, Default)]
+    pub struct InMemoryItemRepository {
+        pub items: Arc<Mutex<Vec<VocabularyItem>>>,
+    }
+
+    #[async_trait]
+    impl VocabularyItemRepository for InMemoryItemRepository {
+        async fn find_by_id(&self, item_id: &ItemId) -> Result<Option<VocabularyItem>> {
+            let items = self.items.lock().unwrap();
+            Ok(items.iter().find(|i| &i.item_id == item_id).cloned())
+        }
+
+        async fn save(&self, item: &VocabularyItem) -> Result<()> {
+            let mut items = self.items.lock().unwrap();
+            items.retain(|i| i.item_id != item.item_id);
+            items.push(item.clone());
+            Ok(())
+        }
+
+        async fn find_by_entry_id(&self, entry_id: &EntryId) -> Result<Vec<VocabularyItem>> {
+            let items = self.items.lock().unwrap();
+            Ok(items
+                .iter()
+                .filter(|i| &i.entry_id == entry_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_primary_by_entry_id(
+            &self,
+            entry_id: &EntryId,
+        ) -> Result<Option<VocabularyItem>> {
+            let items = self.items.lock().unwrap();
+            Ok(items
+                .iter()
+                .find(|i| &i.entry_id == entry_id && i.is_primary)
+                .cloned())
+        }
+    }
+
+    /// 追加されたイベントを記録する EventStore
+    #[derive(Clone, Default)]
+    pub struct RecordingEventStore {
+        pub events: Arc<Mutex<Vec<DomainEvent>>>,
+    }
+
+    impl RecordingEventStore {
+        pub fn recorded(&self) -> Vec<DomainEvent> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl EventStore for RecordingEventStore {
+        async fn append_event(&self, event: DomainEvent) -> Result<()> {
+            self.events.lock().unwrap().push(event);
+            Ok(())
+        }
+
+        async fn get_events_by_aggregate_id(&self, aggregate_id: Uuid) -> Result<Vec<DomainEvent>> {
+            let events = self.events.lock().unwrap();
+            Ok(events
+                .iter()
+                .filter(|e| e.metadata().aggregate_id == aggregate_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_events_since_version(
+            &self,
+            aggregate_id: Uuid,
+            version: i64,
+        ) -> Result<Vec<DomainEvent>> {
+            let events = self.events.lock().unwrap();
+            Ok(events
+                .iter()
+                .filter(|e| {
+                    e.metadata().aggregate_id == aggregate_id && e.metadata().version >= version
+                })
+                .cloned()
+                .collect())
+        }
+
+        async fn get_events_by_type(
+            &self,
+            event_type: &str,
+            limit: Option<usize>,
+        ) -> Result<Vec<DomainEvent>> {
+            let events = self.events.lock().unwrap();
+            Ok(events
+                .iter()
+                .filter(|e| e.event_type() == event_type)
+                .take(limit.unwrap_or(usize::MAX))
+                .cloned()
+                .collect())
+        }
+
+        async fn get_events_in_range(
+            &self,
+            start: DateTime<Utc>,
+            end: DateTime<Utc>,
+        ) -> Result<Vec<DomainEvent>> {
+            let events = self.events.lock().unwrap();
+            Ok(events
+                .iter()
+                .filter(|e| e.metadata().occurred_at >= start && e.metadata().occurred_at <= end)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_latest_snapshot(
+            &self,
+            _aggregate_id: Uuid,
+        ) -> Result<Option<AggregateSnapshot>> {
+            Ok(None)
+        }
+
+        async fn save_snapshot(&self, _snapshot: AggregateSnapshot) -> Result<()> {
+            Ok(())
+        }
+    }
 }
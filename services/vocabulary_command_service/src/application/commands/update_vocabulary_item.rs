@@ -1,12 +1,18 @@
 use crate::{
     domain::{
         Disambiguation,
+        Domain,
         DomainEvent,
         EventMetadata,
+        ItemField,
         ItemId,
+        Register,
         UpdateVocabularyItem,
+        UpdateVocabularyItemField,
+        UsageNotes,
         VocabularyItem,
         VocabularyItemDisambiguationUpdated,
+        VocabularyItemFieldUpdated,
     },
     error::Result,
     ports::{event_store::EventStore, repositories::VocabularyItemRepository},
@@ -35,20 +41,7 @@ where
     }
 
     pub async fn handle(&self, command: UpdateVocabularyItem) -> Result<VocabularyItem> {
-        // アイテムの取得
-        let item_id = ItemId::from_uuid(command.item_id);
-        let mut item = self.repository.find_by_id(&item_id).await?.ok_or_else(|| {
-            crate::error::Error::NotFound(format!("Item not found: {}", command.item_id))
-        })?;
-
-        // バージョンチェック（楽観的ロック）
-        if item.version.value() != command.version {
-            return Err(crate::error::Error::Conflict(format!(
-                "Version mismatch. Current: {}, Expected: {}",
-                item.version.value(),
-                command.version
-            )));
-        }
+        let mut item = self.load(command.item_id, command.version).await?;
 
         // 値オブジェクトの生成
         let new_disambiguation = Disambiguation::new(command.disambiguation.clone())
@@ -75,6 +68,84 @@ where
 
         Ok(item)
     }
+
+    /// 使用域・専門分野・使用上の注意など個別フィールドの更新
+    pub async fn handle_field_update(
+        &self,
+        command: UpdateVocabularyItemField,
+    ) -> Result<VocabularyItem> {
+        let mut item = self.load(command.item_id, command.version).await?;
+
+        // 値オブジェクトで検証し、正規化した値をイベントに記録する
+        let value = command.value.as_deref();
+        let (old_value, new_value) = match command.field {
+            ItemField::Register => {
+                let register = value
+                    .map(str::parse::<Register>)
+                    .transpose()
+                    .map_err(crate::error::Error::Validation)?;
+                let old_value = item.register.map(|r| r.as_str().to_string());
+                item.update_register(register);
+                (old_value, register.map(|r| r.as_str().to_string()))
+            },
+            ItemField::Domain => {
+                let domain = value
+                    .map(str::parse::<Domain>)
+                    .transpose()
+                    .map_err(crate::error::Error::Validation)?;
+                let old_value = item.domain.as_ref().map(|d| d.as_str().to_string());
+                let new_value = domain.as_ref().map(|d| d.as_str().to_string());
+                item.update_domain(domain);
+                (old_value, new_value)
+            },
+            ItemField::UsageNotes => {
+                let usage_notes = value
+                    .map(str::parse::<UsageNotes>)
+                    .transpose()
+                    .map_err(crate::error::Error::Validation)?;
+                let old_value = item.usage_notes.as_ref().map(|n| n.as_str().to_string());
+                let new_value = usage_notes.as_ref().map(|n| n.as_str().to_string());
+                item.update_usage_notes(usage_notes);
+                (old_value, new_value)
+            },
+        };
+
+        // リポジトリに保存
+        self.repository.save(&item).await?;
+
+        // イベントの生成と保存
+        let event = DomainEvent::VocabularyItemFieldUpdated(VocabularyItemFieldUpdated {
+            metadata: EventMetadata::new(*item.item_id.as_uuid(), item.version.value()),
+            item_id: *item.item_id.as_uuid(),
+            field_path: command.field.as_str().to_string(),
+            old_value,
+            new_value,
+        });
+        self.event_store.append_event(event).await?;
+
+        Ok(item)
+    }
+
+    /// アイテムを取得し、バージョンを検証する
+    async fn load(&self, item_id: uuid::Uuid, version: i64) -> Result<VocabularyItem> {
+        // アイテムの取得
+        let item = self
+            .repository
+            .find_by_id(&ItemId::from_uuid(item_id))
+            .await?
+            .ok_or_else(|| crate::error::Error::NotFound(format!("Item not found: {}", item_id)))?;
+
+        // バージョンチェック（楽観的ロック）
+        if item.version.value() != version {
+            return Err(crate::error::Error::Conflict(format!(
+                "Version mismatch. Current: {}, Expected: {}",
+                item.version.value(),
+                version
+            )));
+        }
+
+        Ok(item)
+    }
 }
 
 #[cfg(test)]
@@ -306,4 +377,179 @@ mod tests {
         let updated_item = result.unwrap();
         assert!(updated_item.disambiguation.is_none());
     }
+
+    #[tokio::test]
+    async fn test_update_register_of_published_item() {
+        // Arrange
+        let mut mock_repo = MockItemRepository::new();
+        let mut mock_event_store = MockEventStore::new();
+
+        let mut item = create_test_item().with_learning_context(Some(Register::Neutral), None);
+        item.publish().unwrap(); // 学習用のヒントは公開後も更新できる
+        let item_id = *item.item_id.as_uuid();
+        let version = item.version.value();
+
+        let command = UpdateVocabularyItemField {
+            item_id,
+            field: ItemField::Register,
+            value: Some("SLANG".to_string()),
+            version,
+        };
+
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(item.clone())));
+
+        mock_repo.expect_save().times(1).returning(|item| {
+            assert_eq!(item.register, Some(Register::Slang));
+            assert_eq!(item.version.value(), 3);
+            Ok(())
+        });
+
+        mock_event_store
+            .expect_append_event()
+            .times(1)
+            .returning(|event| {
+                match event {
+                    DomainEvent::VocabularyItemFieldUpdated(e) => {
+                        assert_eq!(e.field_path, "register");
+                        assert_eq!(e.old_value.as_deref(), Some("neutral"));
+                        assert_eq!(e.new_value.as_deref(), Some("slang"));
+                        assert_eq!(e.metadata.version, 3);
+                    },
+                    _ => panic!("Expected VocabularyItemFieldUpdated"),
+                }
+                Ok(())
+            });
+
+        let handler = UpdateVocabularyItemHandler::new(mock_repo, mock_event_store);
+
+        // Act
+        let updated_item = handler.handle_field_update(command).await.unwrap();
+
+        // Assert
+        assert_eq!(updated_item.register, Some(Register::Slang));
+    }
+
+    #[tokio::test]
+    async fn test_clear_domain() {
+        // Arrange
+        let mut mock_repo = MockItemRepository::new();
+        let mut mock_event_store = MockEventStore::new();
+
+        let item = create_test_item().with_learning_context(None, Some(Domain::Legal));
+        let item_id = *item.item_id.as_uuid();
+
+        let command = UpdateVocabularyItemField {
+            item_id,
+            field: ItemField::Domain,
+            value: None, // 解除
+            version: 1,
+        };
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(item.clone())));
+        mock_repo.expect_save().times(1).returning(|_| Ok(()));
+        mock_event_store
+            .expect_append_event()
+            .times(1)
+            .returning(|event| {
+                if let DomainEvent::VocabularyItemFieldUpdated(e) = event {
+                    assert_eq!(e.field_path, "domain");
+                    assert_eq!(e.old_value.as_deref(), Some("legal"));
+                    assert_eq!(e.new_value, None);
+                }
+                Ok(())
+            });
+
+        let handler = UpdateVocabularyItemHandler::new(mock_repo, mock_event_store);
+
+        // Act
+        let updated_item = handler.handle_field_update(command).await.unwrap();
+
+        // Assert
+        assert_eq!(updated_item.domain, None);
+    }
+
+    #[tokio::test]
+    async fn test_update_usage_notes() {
+        // Arrange
+        let mut mock_repo = MockItemRepository::new();
+        let mut mock_event_store = MockEventStore::new();
+
+        let item = create_test_item();
+        let item_id = *item.item_id.as_uuid();
+
+        let command = UpdateVocabularyItemField {
+            item_id,
+            field: ItemField::UsageNotes,
+            value: Some(" Often used with \"of\" ".to_string()),
+            version: 1,
+        };
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(item.clone())));
+        mock_repo.expect_save().times(1).returning(|_| Ok(()));
+        mock_event_store
+            .expect_append_event()
+            .times(1)
+            .returning(|event| {
+                match event {
+                    DomainEvent::VocabularyItemFieldUpdated(e) => {
+                        assert_eq!(e.field_path, "usage_notes");
+                        assert_eq!(e.old_value, None);
+                        // 前後の空白を除いた値を記録する
+                        assert_eq!(e.new_value.as_deref(), Some("Often used with \"of\""));
+                    },
+                    _ => panic!("Expected VocabularyItemFieldUpdated"),
+                }
+                Ok(())
+            });
+
+        let handler = UpdateVocabularyItemHandler::new(mock_repo, mock_event_store);
+
+        // Act
+        let updated_item = handler.handle_field_update(command).await.unwrap();
+
+        // Assert
+        assert_eq!(
+            updated_item.usage_notes.as_ref().map(UsageNotes::as_str),
+            Some("Often used with \"of\"")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_register_with_invalid_value() {
+        // Arrange
+        let mut mock_repo = MockItemRepository::new();
+        let mock_event_store = MockEventStore::new();
+
+        let item = create_test_item();
+        let item_id = *item.item_id.as_uuid();
+
+        let command = UpdateVocabularyItemField {
+            item_id,
+            field: ItemField::Register,
+            value: Some("shouting".to_string()),
+            version: 1,
+        };
+
+        mock_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(item.clone())));
+
+        let handler = UpdateVocabularyItemHandler::new(mock_repo, mock_event_store);
+
+        // Act
+        let result = handler.handle_field_update(command).await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::Validation(msg)) if msg.contains("Invalid Register")
+        ));
+    }
 }
@@ -1,8 +1,22 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::{
-    domain::value_objects::{Disambiguation, EntryId, ItemId, Spelling, Version, VocabularyStatus},
+    domain::{
+        events::DomainEvent,
+        value_objects::{
+            Disambiguation,
+            Domain,
+            EntryId,
+            ItemId,
+            Register,
+            Spelling,
+            UsageNotes,
+            Version,
+            VocabularyStatus,
+        },
+    },
     error::{Error, Result},
 };
 
@@ -44,6 +58,15 @@ pub struct VocabularyItem {
     pub entry_id:       EntryId,
     pub spelling:       Spelling,
     pub disambiguation: Disambiguation,
+    /// 使用域（学習時に提示するヒント）
+    #[serde(default)]
+    pub register:       Option<Register>,
+    /// 専門分野（学習時に提示するヒント）
+    #[serde(default)]
+    pub domain:         Option<Domain>,
+    /// 使用上の注意（学習時に解答とともに提示する）
+    #[serde(default)]
+    pub usage_notes:    Option<UsageNotes>,
     pub is_primary:     bool,
     pub status:         VocabularyStatus,
     pub is_deleted:     bool,
@@ -52,6 +75,21 @@ pub struct VocabularyItem {
     pub version:        Version,
 }
 
+/// 語彙項目の状態の要約
+///
+/// クライアントが指定した ID がすでに使われている場合に、既存の項目と
+/// 突き合わせられるよう返す
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemStateSummary {
+    pub item_id:        Uuid,
+    pub entry_id:       Uuid,
+    pub spelling:       String,
+    pub disambiguation: Option<String>,
+    pub status:         VocabularyStatus,
+    pub is_deleted:     bool,
+    pub version:        i64,
+}
+
 impl VocabularyItem {
     /// 新しい語彙項目を作成
     pub fn create(entry_id: EntryId, spelling: Spelling, disambiguation: Disambiguation) -> Self {
@@ -61,6 +99,9 @@ impl VocabularyItem {
             entry_id,
             spelling,
             disambiguation,
+            register: None,
+            domain: None,
+            usage_notes: None,
             is_primary: false,
             status: VocabularyStatus::Draft,
             is_deleted: false,
@@ -70,6 +111,42 @@ impl VocabularyItem {
         }
     }
 
+    /// 使用域と専門分野を設定した状態で返す（作成時用）
+    pub fn with_learning_context(
+        mut self,
+        register: Option<Register>,
+        domain: Option<Domain>,
+    ) -> Self {
+        self.register = register;
+        self.domain = domain;
+        self
+    }
+
+    /// 使用上の注意を設定した状態で返す（作成時用）
+    pub fn with_usage_notes(mut self, usage_notes: Option<UsageNotes>) -> Self {
+        self.usage_notes = usage_notes;
+        self
+    }
+
+    /// 項目 ID を指定した状態で返す（クライアントが生成した ID での作成時用）
+    pub fn with_item_id(mut self, item_id: ItemId) -> Self {
+        self.item_id = item_id;
+        self
+    }
+
+    /// 現在の状態の要約
+    pub fn summary(&self) -> ItemStateSummary {
+        ItemStateSummary {
+            item_id:        *self.item_id.as_uuid(),
+            entry_id:       *self.entry_id.as_uuid(),
+            spelling:       self.spelling.as_str().to_string(),
+            disambiguation: self.disambiguation.as_option().map(str::to_string),
+            status:         self.status,
+            is_deleted:     self.is_deleted,
+            version:        self.version.value(),
+        }
+    }
+
     /// 主要項目として設定
     pub fn set_as_primary(&mut self) -> Result<()> {
         if self.status != VocabularyStatus::Published {
@@ -154,6 +231,33 @@ impl VocabularyItem {
         Ok(())
     }
 
+    /// 使用域を更新
+    ///
+    /// 語の意味を変えない学習用のヒントなので、公開済みでも更新できる
+    pub fn update_register(&mut self, register: Option<Register>) {
+        self.register = register;
+        self.updated_at = Utc::now();
+        self.version = self.version.increment();
+    }
+
+    /// 専門分野を更新
+    ///
+    /// 使用域と同様に公開済みでも更新できる
+    pub fn update_domain(&mut self, domain: Option<Domain>) {
+        self.domain = domain;
+        self.updated_at = Utc::now();
+        self.version = self.version.increment();
+    }
+
+    /// 使用上の注意を更新
+    ///
+    /// 使用域と同様に公開済みでも更新できる
+    pub fn update_usage_notes(&mut self, usage_notes: Option<UsageNotes>) {
+        self.usage_notes = usage_notes;
+        self.updated_at = Utc::now();
+        self.version = self.version.increment();
+    }
+
     /// アイテムを削除（ソフトデリート）
     pub fn mark_as_deleted(&mut self) -> Result<()> {
         if self.is_deleted {
@@ -164,6 +268,79 @@ impl VocabularyItem {
         self.version = self.version.increment();
         Ok(())
     }
+
+    /// イベントを1件畳み込んだ状態を返す
+    ///
+    /// 副作用のない純粋関数で、状態の再構築やデバッグ用の畳み込みに使う。
+    /// 語彙項目に適用できないイベント（見出し語のイベントや、作成前の
+    /// 更新イベント）は `None` を返す
+    pub fn apply_event(state: Option<&Self>, event: &DomainEvent) -> Option<Self> {
+        let metadata = event.metadata();
+
+        let mut item = match (state, event) {
+            (_, DomainEvent::VocabularyItemCreated(e)) => Self {
+                item_id:        ItemId::from_uuid(e.item_id),
+                entry_id:       EntryId::from_uuid(e.entry_id),
+                spelling:       Spelling::new(e.spelling.clone()).ok()?,
+                disambiguation: Disambiguation::new(e.disambiguation.clone()).ok()?,
+                register:       e.register.as_deref().map(str::parse).transpose().ok()?,
+                domain:         e.domain.as_deref().map(str::parse).transpose().ok()?,
+                usage_notes:    e.usage_notes.as_deref().map(str::parse).transpose().ok()?,
+                is_primary:     false,
+                status:         VocabularyStatus::Draft,
+                is_deleted:     false,
+                created_at:     metadata.occurred_at,
+                updated_at:     metadata.occurred_at,
+                version:        Version::initial(),
+            },
+            (None, _) => return None,
+            (
+                Some(_),
+                DomainEvent::VocabularyEntryCreated(_)
+                | DomainEvent::VocabularyEntrySpellingUpdated(_),
+            ) => return None,
+            (Some(current), event) => {
+                let mut item = current.clone();
+                match event {
+                    DomainEvent::VocabularyItemDisambiguationUpdated(e) => {
+                        item.disambiguation =
+                            Disambiguation::new(e.new_disambiguation.clone()).ok()?;
+                    },
+                    DomainEvent::VocabularyItemFieldUpdated(e) => {
+                        let value = e.new_value.as_deref();
+                        match e.field_path.as_str() {
+                            "register" => item.register = value.map(str::parse).transpose().ok()?,
+                            "domain" => item.domain = value.map(str::parse).transpose().ok()?,
+                            "usage_notes" => {
+                                item.usage_notes = value.map(str::parse).transpose().ok()?
+                            },
+                            _ => return None,
+                        }
+                    },
+                    DomainEvent::VocabularyItemPublished(_) => {
+                        item.status = VocabularyStatus::Published;
+                    },
+                    DomainEvent::AIEnrichmentRequested(_) => {
+                        item.status = VocabularyStatus::PendingAI;
+                    },
+                    DomainEvent::AIEnrichmentCompleted(_) => {
+                        item.status = VocabularyStatus::Draft;
+                    },
+                    DomainEvent::PrimaryItemSet(_) => item.is_primary = true,
+                    DomainEvent::PrimaryItemUnset(_) => item.is_primary = false,
+                    DomainEvent::VocabularyItemDeleted(_) => item.is_deleted = true,
+                    _ => {},
+                }
+                item
+            },
+        };
+
+        item.updated_at = metadata.occurred_at;
+        if let Ok(version) = Version::new(metadata.version) {
+            item.version = version;
+        }
+        Some(item)
+    }
 }
 
 #[cfg(test)]
@@ -251,4 +428,105 @@ mod tests {
         // 完了後は公開可能
         assert!(item.publish().is_ok());
     }
+
+    #[test]
+    fn test_apply_event_replays_item_lifecycle() {
+        use uuid::Uuid;
+
+        use crate::domain::events::{
+            EventMetadata,
+            PrimaryItemSet,
+            VocabularyEntryCreated,
+            VocabularyItemCreated,
+            VocabularyItemFieldUpdated,
+            VocabularyItemPublished,
+        };
+
+        let item_id = Uuid::new_v4();
+        let entry_id = Uuid::new_v4();
+
+        // 作成前の更新イベントは適用できない
+        let published = DomainEvent::VocabularyItemPublished(VocabularyItemPublished {
+            metadata: EventMetadata::new(item_id, 2),
+            item_id,
+            entry_id,
+        });
+        assert!(VocabularyItem::apply_event(None, &published).is_none());
+
+        let created = DomainEvent::VocabularyItemCreated(VocabularyItemCreated {
+            metadata: EventMetadata::new(item_id, 1),
+            item_id,
+            entry_id,
+            spelling: "bank".to_string(),
+            disambiguation: Some("financial institution".to_string()),
+            provenance: None,
+            register: Some("neutral".to_string()),
+            domain: Some("business".to_string()),
+            usage_notes: None,
+            created_by: None,
+        });
+        let item = VocabularyItem::apply_event(None, &created).unwrap();
+        assert_eq!(item.status, VocabularyStatus::Draft);
+        assert_eq!(item.version.value(), 1);
+        assert_eq!(item.register, Some(Register::Neutral));
+        assert_eq!(item.domain, Some(Domain::Business));
+
+        let item = VocabularyItem::apply_event(Some(&item), &published).unwrap();
+        assert_eq!(item.status, VocabularyStatus::Published);
+        assert_eq!(item.version.value(), 2);
+
+        let primary = DomainEvent::PrimaryItemSet(PrimaryItemSet {
+            metadata: EventMetadata::new(item_id, 3),
+            entry_id,
+            item_id,
+            previous_primary_item_id: None,
+        });
+        let item = VocabularyItem::apply_event(Some(&item), &primary).unwrap();
+        assert!(item.is_primary);
+
+        // 公開後も使用域は更新でき、解除もできる
+        let register_updated =
+            DomainEvent::VocabularyItemFieldUpdated(VocabularyItemFieldUpdated {
+                metadata: EventMetadata::new(item_id, 4),
+                item_id,
+                field_path: "register".to_string(),
+                old_value: Some("neutral".to_string()),
+                new_value: Some("informal".to_string()),
+            });
+        let item = VocabularyItem::apply_event(Some(&item), &register_updated).unwrap();
+        assert_eq!(item.register, Some(Register::Informal));
+        assert_eq!(item.version.value(), 4);
+
+        let domain_cleared = DomainEvent::VocabularyItemFieldUpdated(VocabularyItemFieldUpdated {
+            metadata: EventMetadata::new(item_id, 5),
+            item_id,
+            field_path: "domain".to_string(),
+            old_value: Some("business".to_string()),
+            new_value: None,
+        });
+        let item = VocabularyItem::apply_event(Some(&item), &domain_cleared).unwrap();
+        assert_eq!(item.domain, None);
+
+        let notes_updated = DomainEvent::VocabularyItemFieldUpdated(VocabularyItemFieldUpdated {
+            metadata: EventMetadata::new(item_id, 6),
+            item_id,
+            field_path: "usage_notes".to_string(),
+            old_value: None,
+            new_value: Some("Usually followed by \"account\" in this sense".to_string()),
+        });
+        let item = VocabularyItem::apply_event(Some(&item), &notes_updated).unwrap();
+        assert_eq!(
+            item.usage_notes.as_ref().map(UsageNotes::as_str),
+            Some("Usually followed by \"account\" in this sense")
+        );
+
+        // 見出し語のイベントは語彙項目には適用しない
+        let entry_created = DomainEvent::VocabularyEntryCreated(VocabularyEntryCreated {
+            metadata: EventMetadata::new(entry_id, 1),
+            entry_id,
+            spelling: "bank".to_string(),
+            provenance: None,
+        });
+        assert!(VocabularyItem::apply_event(Some(&item), &entry_created).is_none());
+    }
 }
@@ -13,6 +13,22 @@ pub struct CreateVocabularyItem {
     pub entry_id:       Uuid,
     pub spelling:       String,
     pub disambiguation: Option<String>,
+    /// 使用域（formal / informal / slang など）
+    #[serde(default)]
+    pub register:       Option<String>,
+    /// 専門分野（medical / legal / general など）
+    #[serde(default)]
+    pub domain:         Option<String>,
+    /// 使用上の注意（解答とともに学習者に提示する）
+    #[serde(default)]
+    pub usage_notes:    Option<String>,
+    /// クライアントが生成した項目 ID（楽観的 UI
+    /// 用、認証済みユーザーの下書きのみ）
+    #[serde(default)]
+    pub client_id:      Option<Uuid>,
+    /// コマンドを発行した認証済みユーザー（インポートや AI フローでは None）
+    #[serde(default)]
+    pub issued_by:      Option<Uuid>,
 }
 
 /// VocabularyItem を更新するコマンド
@@ -23,6 +39,50 @@ pub struct UpdateVocabularyItem {
     pub version:        i64,
 }
 
+/// VocabularyItem の個別フィールドを更新するコマンド
+///
+/// `value` が None の場合はフィールドの値を解除する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateVocabularyItemField {
+    pub item_id: Uuid,
+    pub field:   ItemField,
+    pub value:   Option<String>,
+    pub version: i64,
+}
+
+/// 個別に更新できる VocabularyItem のフィールド
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemField {
+    Register,
+    Domain,
+    UsageNotes,
+}
+
+impl ItemField {
+    /// イベントの `field_path` に記録する名前
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Register => "register",
+            Self::Domain => "domain",
+            Self::UsageNotes => "usage_notes",
+        }
+    }
+}
+
+impl std::str::FromStr for ItemField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "register" => Ok(Self::Register),
+            "domain" => Ok(Self::Domain),
+            "usage_notes" => Ok(Self::UsageNotes),
+            _ => Err(format!("Invalid ItemField: {}", s)),
+        }
+    }
+}
+
 /// VocabularyItem を公開するコマンド
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublishVocabularyItem {
@@ -90,3 +150,23 @@ pub struct AddExample {
     pub added_by:    Uuid,
     pub version:     i64,
 }
+
+/// 外部辞書ソースを登録するコマンド
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterImportSource {
+    pub name:                    String,
+    pub license:                 String,
+    pub attribution_text:        String,
+    pub url:                     Option<String>,
+    pub requires_attribution:    bool,
+    pub import_examples:         bool,
+    pub examples_require_review: bool,
+}
+
+/// 外部辞書ソースから JSONL をインポートするコマンド
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportExternalSource {
+    pub source_id:   Uuid,
+    pub imported_by: Uuid,
+    pub content:     String,
+}
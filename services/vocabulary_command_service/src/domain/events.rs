@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::domain::commands::EnrichedData;
+use crate::domain::{commands::EnrichedData, import_sources::Provenance};
 
 /// イベントの基本メタデータ
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,9 +27,12 @@ impl EventMetadata {
 /// VocabularyEntry が作成された
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VocabularyEntryCreated {
-    pub metadata: EventMetadata,
-    pub entry_id: Uuid,
-    pub spelling: String,
+    pub metadata:   EventMetadata,
+    pub entry_id:   Uuid,
+    pub spelling:   String,
+    /// 外部ソースからインポートされた場合の出典
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
 }
 
 /// VocabularyEntry のスペリングが更新された
@@ -49,6 +52,21 @@ pub struct VocabularyItemCreated {
     pub entry_id:       Uuid,
     pub spelling:       String,
     pub disambiguation: Option<String>,
+    /// 外部ソースからインポートされた場合の出典
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance:     Option<Provenance>,
+    /// 使用域（`Register` の正規化された名前）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub register:       Option<String>,
+    /// 専門分野（`Domain` の正規化された名前）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain:         Option<String>,
+    /// 使用上の注意（前後の空白を除いた `UsageNotes`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_notes:    Option<String>,
+    /// 作成したユーザー（インポートや AI フローでは None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by:     Option<Uuid>,
 }
 
 /// VocabularyItem の曖昧性解消が更新された
@@ -60,6 +78,19 @@ pub struct VocabularyItemDisambiguationUpdated {
     pub new_disambiguation: Option<String>,
 }
 
+/// VocabularyItem の個別フィールドが更新された
+///
+/// 値は正規化された文字列で記録し、None は値の解除を表す
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VocabularyItemFieldUpdated {
+    pub metadata:   EventMetadata,
+    pub item_id:    Uuid,
+    /// 更新されたフィールド（`register`、`domain` または `usage_notes`）
+    pub field_path: String,
+    pub old_value:  Option<String>,
+    pub new_value:  Option<String>,
+}
+
 /// VocabularyItem が公開された
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VocabularyItemPublished {
@@ -114,11 +145,17 @@ pub struct VocabularyItemDeleted {
 /// VocabularyItem に例文が追加された
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExampleAdded {
-    pub metadata:    EventMetadata,
-    pub item_id:     Uuid,
-    pub example:     String,
-    pub translation: Option<String>,
-    pub added_by:    Uuid,
+    pub metadata:        EventMetadata,
+    pub item_id:         Uuid,
+    pub example:         String,
+    pub translation:     Option<String>,
+    pub added_by:        Uuid,
+    /// 外部ソースからインポートされた場合の出典
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance:      Option<Provenance>,
+    /// 公開前にレビューが必要かどうか（ソースのフィールドポリシーによる）
+    #[serde(default)]
+    pub requires_review: bool,
 }
 
 /// すべてのドメインイベントをまとめる列挙型
@@ -129,6 +166,7 @@ pub enum DomainEvent {
     VocabularyEntrySpellingUpdated(VocabularyEntrySpellingUpdated),
     VocabularyItemCreated(VocabularyItemCreated),
     VocabularyItemDisambiguationUpdated(VocabularyItemDisambiguationUpdated),
+    VocabularyItemFieldUpdated(VocabularyItemFieldUpdated),
     VocabularyItemPublished(VocabularyItemPublished),
     VocabularyItemDeleted(VocabularyItemDeleted),
     ExampleAdded(ExampleAdded),
@@ -146,6 +184,7 @@ impl DomainEvent {
             DomainEvent::VocabularyEntrySpellingUpdated(e) => &e.metadata,
             DomainEvent::VocabularyItemCreated(e) => &e.metadata,
             DomainEvent::VocabularyItemDisambiguationUpdated(e) => &e.metadata,
+            DomainEvent::VocabularyItemFieldUpdated(e) => &e.metadata,
             DomainEvent::VocabularyItemPublished(e) => &e.metadata,
             DomainEvent::VocabularyItemDeleted(e) => &e.metadata,
             DomainEvent::ExampleAdded(e) => &e.metadata,
@@ -165,6 +204,7 @@ impl DomainEvent {
             DomainEvent::VocabularyItemDisambiguationUpdated(_) => {
                 "VocabularyItemDisambiguationUpdated"
             },
+            DomainEvent::VocabularyItemFieldUpdated(_) => "VocabularyItemFieldUpdated",
             DomainEvent::VocabularyItemPublished(_) => "VocabularyItemPublished",
             DomainEvent::VocabularyItemDeleted(_) => "VocabularyItemDeleted",
             DomainEvent::ExampleAdded(_) => "ExampleAdded",
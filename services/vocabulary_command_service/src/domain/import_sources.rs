@@ -0,0 +1,210 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+/// インポート対象として受け付ける言語
+pub const IMPORT_TARGET_LANGUAGE: &str = "en";
+
+/// 外部辞書ソース（Wiktionary, WordNet など）
+///
+/// ライセンス遵守のため、インポートされたデータはすべて
+/// 登録済みのソースに紐づけて出典を追跡する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSource {
+    pub source_id:            Uuid,
+    pub name:                 String,
+    pub license:              String,
+    pub attribution_text:     String,
+    pub url:                  Option<String>,
+    pub requires_attribution: bool,
+    pub field_policy:         FieldPolicy,
+    pub created_at:           DateTime<Utc>,
+}
+
+impl ImportSource {
+    /// 新しいソースを登録
+    pub fn register(
+        name: String,
+        license: String,
+        attribution_text: String,
+        url: Option<String>,
+        requires_attribution: bool,
+        field_policy: FieldPolicy,
+    ) -> Result<Self> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(Error::Validation("Source name cannot be empty".to_string()));
+        }
+
+        let license = license.trim().to_string();
+        if license.is_empty() {
+            return Err(Error::Validation(
+                "Source license cannot be empty".to_string(),
+            ));
+        }
+
+        let attribution_text = attribution_text.trim().to_string();
+        if requires_attribution && attribution_text.is_empty() {
+            return Err(Error::Validation(
+                "Attribution text is required when the license requires attribution".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            source_id: Uuid::new_v4(),
+            name,
+            license,
+            attribution_text,
+            url: url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty()),
+            requires_attribution,
+            field_policy,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// このソースから取り込んだデータに付与する出典情報を生成
+    pub fn provenance(&self, external_ref: Option<String>) -> Provenance {
+        Provenance {
+            source_id: self.source_id,
+            source_name: self.name.clone(),
+            license: self.license.clone(),
+            attribution: self
+                .requires_attribution
+                .then(|| self.attribution_text.clone()),
+            external_ref,
+        }
+    }
+}
+
+/// ソースごとのフィールドポリシー
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldPolicy {
+    /// 例文を取り込むかどうか（ライセンス上再配布できない場合は false）
+    pub import_examples:         bool,
+    /// 取り込んだ例文の公開にレビューが必要かどうか
+    pub examples_require_review: bool,
+}
+
+impl Default for FieldPolicy {
+    fn default() -> Self {
+        Self {
+            import_examples:         true,
+            examples_require_review: false,
+        }
+    }
+}
+
+/// 出典情報（イベントと Read Model に付与される）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub source_id:    Uuid,
+    pub source_name:  String,
+    pub license:      String,
+    /// 帰属表示が必要な場合のみ設定される
+    pub attribution:  Option<String>,
+    /// ソース側の識別子（Wiktionary のページ ID など）
+    pub external_ref: Option<String>,
+}
+
+/// 正規化済み JSONL の1行
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImportRecord {
+    pub spelling:    String,
+    pub language:    String,
+    #[serde(default)]
+    pub senses:      Vec<ImportSense>,
+    #[serde(default)]
+    pub examples:    Vec<ImportExample>,
+    #[serde(default)]
+    pub external_id: Option<String>,
+}
+
+/// インポートされる語義
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ImportSense {
+    /// 語義の区別（Disambiguation にマップされる）
+    #[serde(default)]
+    pub gloss: Option<String>,
+}
+
+/// インポートされる例文
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImportExample {
+    pub text:        String,
+    #[serde(default)]
+    pub translation: Option<String>,
+    /// 対応する語義のインデックス（省略時は最初の語義）
+    #[serde(default)]
+    pub sense:       Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wiktionary(requires_attribution: bool) -> ImportSource {
+        ImportSource::register(
+            "wiktionary".to_string(),
+            "CC BY-SA 4.0".to_string(),
+            "From Wiktionary, the free dictionary".to_string(),
+            Some("https://en.wiktionary.org".to_string()),
+            requires_attribution,
+            FieldPolicy::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_register_source_validation() {
+        assert!(
+            ImportSource::register(
+                " ".to_string(),
+                "CC0".to_string(),
+                String::new(),
+                None,
+                false,
+                FieldPolicy::default(),
+            )
+            .is_err()
+        );
+
+        // 帰属表示が必要なのにテキストが空
+        assert!(
+            ImportSource::register(
+                "wordnet".to_string(),
+                "WordNet License".to_string(),
+                "  ".to_string(),
+                None,
+                true,
+                FieldPolicy::default(),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_provenance_includes_attribution_only_when_required() {
+        let source = wiktionary(true);
+        let provenance = source.provenance(Some("page-42".to_string()));
+        assert_eq!(provenance.source_name, "wiktionary");
+        assert_eq!(
+            provenance.attribution.as_deref(),
+            Some("From Wiktionary, the free dictionary")
+        );
+        assert_eq!(provenance.external_ref.as_deref(), Some("page-42"));
+
+        let source = wiktionary(false);
+        assert!(source.provenance(None).attribution.is_none());
+    }
+
+    #[test]
+    fn test_import_record_defaults() {
+        let record: ImportRecord =
+            serde_json::from_str(r#"{"spelling":"apple","language":"en"}"#).unwrap();
+        assert!(record.senses.is_empty());
+        assert!(record.examples.is_empty());
+        assert!(record.external_id.is_none());
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
+// 使用域・専門分野・使用上の注意は Vocabulary Context 共通の値オブジェクトを使う
+pub use shared_vocabulary_context::domain::{Domain, Register, UsageNotes};
 use uuid::Uuid;
 
 /// エントリID（見出し語ID）
@@ -94,6 +96,37 @@ impl fmt::Display for Spelling {
     }
 }
 
+/// スペリングキー（重複判定用に正規化したスペリング）
+///
+/// 前後の空白を除去し、連続する空白を1つにまとめ、小文字化する
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SpellingKey(String);
+
+impl SpellingKey {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&Spelling> for SpellingKey {
+    fn from(spelling: &Spelling) -> Self {
+        Self(
+            spelling
+                .as_str()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase(),
+        )
+    }
+}
+
+impl fmt::Display for SpellingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// 曖昧性解消（意味の区別）
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Disambiguation(Option<String>);
@@ -217,6 +250,14 @@ mod tests {
         assert!(Spelling::new("a".repeat(256)).is_err());
     }
 
+    #[test]
+    fn test_spelling_key_normalization() {
+        let a = SpellingKey::from(&Spelling::new("Ice  Cream".to_string()).unwrap());
+        let b = SpellingKey::from(&Spelling::new(" ice cream ".to_string()).unwrap());
+        assert_eq!(a, b);
+        assert_eq!(a.as_str(), "ice cream");
+    }
+
     #[test]
     fn test_disambiguation() {
         // None の場合
@@ -232,6 +273,26 @@ mod tests {
         assert_eq!(d.as_option(), Some("fruit"));
     }
 
+    #[test]
+    fn test_learning_context_values() {
+        assert_eq!(" Slang ".parse::<Register>().unwrap(), Register::Slang);
+        assert!("general".parse::<Register>().is_err());
+
+        assert_eq!("business".parse::<Domain>().unwrap(), Domain::Business);
+        // 既定の分野以外は Other として受け付ける
+        let food = "Food".parse::<Domain>().unwrap();
+        assert_eq!(food, Domain::Other("food".to_string()));
+        assert_eq!(food.as_str(), "food");
+        assert!("  ".parse::<Domain>().is_err());
+
+        let notes = "  Formal; avoid in speech ".parse::<UsageNotes>().unwrap();
+        assert_eq!(notes.as_str(), "Formal; avoid in speech");
+        assert!(" ".parse::<UsageNotes>().is_err());
+        assert!("a".repeat(UsageNotes::MAX_CHARS + 1).parse::<UsageNotes>().is_err());
+        // 文字数で数えるため、日本語でも上限まで書ける
+        assert!("あ".repeat(UsageNotes::MAX_CHARS).parse::<UsageNotes>().is_ok());
+    }
+
     #[test]
     fn test_version() {
         let v = Version::initial();
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::domain::ItemStateSummary;
+
 /// Vocabulary Command Service のエラー型
 #[derive(Error, Debug)]
 pub enum Error {
@@ -27,6 +29,14 @@ pub enum Error {
     #[error("Conflict error: {0}")]
     Conflict(String),
 
+    /// 指定された ID の集約がすでに存在する（既存の状態の要約を持つ）
+    #[error("Already exists: {}", .0.item_id)]
+    AlreadyExists(Box<ItemStateSummary>),
+
+    /// 権限がない
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
     /// リソースが見つからない
     #[error("Not found: {0}")]
     NotFound(String),
@@ -58,6 +68,16 @@ impl From<Error> for tonic::Status {
             Error::Validation(msg) => tonic::Status::invalid_argument(msg),
             Error::NotFound(msg) => tonic::Status::not_found(msg),
             Error::Conflict(msg) => tonic::Status::aborted(msg),
+            Error::AlreadyExists(summary) => {
+                // 既存の状態の要約を JSON で詳細に載せる
+                let details = serde_json::to_vec(&summary).unwrap_or_default();
+                tonic::Status::with_details(
+                    tonic::Code::AlreadyExists,
+                    format!("Already exists: {}", summary.item_id),
+                    details.into(),
+                )
+            },
+            Error::PermissionDenied(msg) => tonic::Status::permission_denied(msg),
             Error::Domain(msg) => tonic::Status::failed_precondition(msg),
             _ => tonic::Status::internal(err.to_string()),
         }
@@ -349,6 +349,11 @@ mod tests {
             entry_id:       *entry_id.as_uuid(),
             spelling:       "test".to_string(),
             disambiguation: Some("test meaning".to_string()),
+            provenance:     None,
+            register:       None,
+            domain:         None,
+            usage_notes:    None,
+            created_by:     None,
         });
 
         // イベントを保存
@@ -8,6 +8,7 @@ use crate::{
     application::commands::{
         CreateVocabularyItemHandler,
         DeleteVocabularyItemHandler,
+        ImportExternalSourceHandler,
         UpdateVocabularyItemHandler,
     },
     config::Config,
@@ -18,24 +19,45 @@ use crate::{
             VocabularyCommandServiceImpl,
             proto::vocabulary_command_service_server::VocabularyCommandServiceServer,
         },
-        repositories::{PostgresVocabularyEntryRepository, PostgresVocabularyItemRepository},
+        repositories::{
+            PostgresImportSourceRepository,
+            PostgresVocabularyEntryRepository,
+            PostgresVocabularyItemRepository,
+        },
     },
 };
 
-/// gRPC サーバーを起動
-pub async fn start_grpc_server(config: Config) -> Result<()> {
-    // データベース接続プールを作成
-    let db_pool = PgPool::connect(&config.database.url)
-        .await
-        .map_err(crate::error::Error::Database)?;
+/// 組み立て済みの gRPC サービス
+pub type CommandServiceServer = VocabularyCommandServiceServer<
+    VocabularyCommandServiceImpl<
+        PostgresVocabularyEntryRepository,
+        PostgresVocabularyItemRepository,
+        PostgresEventStore,
+        PostgresImportSourceRepository,
+    >,
+>;
 
-    let event_store_pool = PgPool::connect(&config.event_store.url)
-        .await
-        .map_err(crate::error::Error::Database)?;
+/// サービスの依存関係
+pub struct ServiceDeps {
+    /// コマンド側テーブルのプール
+    pub db_pool:          PgPool,
+    /// イベントストアのプール
+    pub event_store_pool: PgPool,
+}
+
+/// 接続済みのプールから gRPC サービスを組み立てる
+///
+/// 統合バイナリ（effect_monolith）は他のサービスと同じサーバーに登録する
+pub fn build_service(deps: ServiceDeps) -> CommandServiceServer {
+    let ServiceDeps {
+        db_pool,
+        event_store_pool,
+    } = deps;
 
     // リポジトリとイベントストアを初期化
     let entry_repo = PostgresVocabularyEntryRepository::new(db_pool.clone());
     let item_repo = PostgresVocabularyItemRepository::new(db_pool.clone());
+    let source_repo = PostgresImportSourceRepository::new(db_pool);
     let event_store = PostgresEventStore::new(event_store_pool);
 
     // コマンドハンドラーを初期化
@@ -51,14 +73,42 @@ pub async fn start_grpc_server(config: Config) -> Result<()> {
     ));
 
     let delete_handler = Arc::new(DeleteVocabularyItemHandler::new(
+        entry_repo.clone(),
+        item_repo.clone(),
+        event_store.clone(),
+    ));
+
+    let import_handler = Arc::new(ImportExternalSourceHandler::new(
         entry_repo,
         item_repo,
         event_store,
+        source_repo,
     ));
 
     // gRPC サービスを作成
-    let grpc_service =
-        VocabularyCommandServiceImpl::new(create_handler, update_handler, delete_handler);
+    VocabularyCommandServiceServer::new(VocabularyCommandServiceImpl::new(
+        create_handler,
+        update_handler,
+        delete_handler,
+        import_handler,
+    ))
+}
+
+/// gRPC サーバーを起動
+pub async fn start_grpc_server(config: Config) -> Result<()> {
+    // データベース接続プールを作成
+    let db_pool = PgPool::connect(&config.database.url)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+    let event_store_pool = PgPool::connect(&config.event_store.url)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+    let service = build_service(ServiceDeps {
+        db_pool,
+        event_store_pool,
+    });
 
     // gRPC サーバーアドレス
     let addr: SocketAddr = format!("{}:{}", config.server.host, config.server.port)
@@ -69,7 +119,7 @@ pub async fn start_grpc_server(config: Config) -> Result<()> {
 
     // gRPC サーバーを起動
     Server::builder()
-        .add_service(VocabularyCommandServiceServer::new(grpc_service))
+        .add_service(service)
         .serve(addr)
         .await
         .map_err(|e| crate::error::Error::Internal(format!("gRPC server error: {}", e)))?;
@@ -1,20 +1,26 @@
 use std::sync::Arc;
 
-use tonic::{Request, Response, Status};
+use tonic::{Request, Response, Status, Streaming};
 use uuid::Uuid;
 
 use crate::{
     application::commands::{
         CreateVocabularyItemHandler,
         DeleteVocabularyItemHandler,
+        ImportExternalSourceHandler,
+        ImportReport,
         UpdateVocabularyItemHandler,
+        import_external_source::JsonlLineSplitter,
     },
     domain::{
         CreateVocabularyItem,
         DeleteVocabularyItem,
         Disambiguation,
+        ItemField,
         ItemId,
+        RegisterImportSource,
         UpdateVocabularyItem,
+        UpdateVocabularyItemField,
     },
     error::Error,
 };
@@ -43,6 +49,11 @@ use proto::{
     CreateVocabularyItemResponse,
     DeleteVocabularyItemRequest,
     DeleteVocabularyItemResponse,
+    ImportExternalSourceRequest,
+    ImportExternalSourceResponse,
+    ImportFailure,
+    RegisterImportSourceRequest,
+    RegisterImportSourceResponse,
     RequestAiEnrichmentRequest,
     RequestAiEnrichmentResponse,
     UpdateVocabularyItemRequest,
@@ -51,42 +62,67 @@ use proto::{
 };
 
 /// Vocabulary Command Service の gRPC 実装
-pub struct VocabularyCommandServiceImpl<ER, IR, ES>
+pub struct VocabularyCommandServiceImpl<ER, IR, ES, SR>
 where
     ER: crate::ports::repositories::VocabularyEntryRepository + Send + Sync,
     IR: crate::ports::repositories::VocabularyItemRepository + Send + Sync,
     ES: crate::ports::event_store::EventStore + Send + Sync,
+    SR: crate::ports::repositories::ImportSourceRepository + Send + Sync,
 {
     create_handler: Arc<CreateVocabularyItemHandler<ER, IR, ES>>,
     update_handler: Arc<UpdateVocabularyItemHandler<IR, ES>>,
     delete_handler: Arc<DeleteVocabularyItemHandler<ER, IR, ES>>,
+    import_handler: Arc<ImportExternalSourceHandler<ER, IR, ES, SR>>,
 }
 
-impl<ER, IR, ES> VocabularyCommandServiceImpl<ER, IR, ES>
+impl<ER, IR, ES, SR> VocabularyCommandServiceImpl<ER, IR, ES, SR>
 where
     ER: crate::ports::repositories::VocabularyEntryRepository + Send + Sync,
     IR: crate::ports::repositories::VocabularyItemRepository + Send + Sync,
     ES: crate::ports::event_store::EventStore + Send + Sync,
+    SR: crate::ports::repositories::ImportSourceRepository + Send + Sync,
 {
     pub fn new(
         create_handler: Arc<CreateVocabularyItemHandler<ER, IR, ES>>,
         update_handler: Arc<UpdateVocabularyItemHandler<IR, ES>>,
         delete_handler: Arc<DeleteVocabularyItemHandler<ER, IR, ES>>,
+        import_handler: Arc<ImportExternalSourceHandler<ER, IR, ES, SR>>,
     ) -> Self {
         Self {
             create_handler,
             update_handler,
             delete_handler,
+            import_handler,
+        }
+    }
+}
+
+impl From<ImportReport> for ImportExternalSourceResponse {
+    fn from(report: ImportReport) -> Self {
+        Self {
+            created:  report.created,
+            merged:   report.merged,
+            skipped:  report.skipped,
+            failed:   report.failed,
+            failures: report
+                .failures
+                .into_iter()
+                .map(|f| ImportFailure {
+                    line:   f.line as u32,
+                    reason: f.reason,
+                })
+                .collect(),
         }
     }
 }
 
 #[tonic::async_trait]
-impl<ER, IR, ES> VocabularyCommandService for VocabularyCommandServiceImpl<ER, IR, ES>
+impl<ER, IR, ES, SR> VocabularyCommandService for VocabularyCommandServiceImpl<ER, IR, ES, SR>
 where
     ER: crate::ports::repositories::VocabularyEntryRepository + Send + Sync + 'static,
     IR: crate::ports::repositories::VocabularyItemRepository + Send + Sync + 'static,
     ES: crate::ports::event_store::EventStore + Send + Sync + 'static,
+    SR: crate::ports::repositories::ImportSourceRepository + Send + Sync + 'static,
 {
     async fn create_vocabulary_item(
         &self,
@@ -94,15 +130,35 @@ where
     ) -> Result<Response<CreateVocabularyItemResponse>, Status> {
         let req = request.into_inner();
 
+        let client_id = if req.client_id.is_empty() {
+            None
+        } else {
+            Some(
+                Uuid::parse_str(&req.client_id)
+                    .map_err(|e| Status::invalid_argument(format!("Invalid client_id: {}", e)))?,
+            )
+        };
+        // 発行者が特定できない場合は認証されていないものとして扱う
+        let issued_by = req
+            .metadata
+            .as_ref()
+            .and_then(|metadata| Uuid::parse_str(&metadata.issued_by).ok());
+
         // コマンドを作成（entry_id は nil UUID にして、ハンドラーで自動生成してもらう）
         let command = CreateVocabularyItem {
-            entry_id:       Uuid::nil(), // nil の場合、ハンドラー内で自動的にエントリー作成
-            spelling:       req.word.clone(),
+            entry_id: Uuid::nil(), // nil の場合、ハンドラー内で自動的にエントリー作成
+            spelling: req.word.clone(),
             disambiguation: if req.definitions.is_empty() {
                 None
             } else {
                 Some(req.definitions[0].clone())
             },
+            // 空文字列は未指定として扱う
+            register: Some(req.register).filter(|s| !s.is_empty()),
+            domain: Some(req.domain).filter(|s| !s.is_empty()),
+            usage_notes: Some(req.usage_notes).filter(|s| !s.is_empty()),
+            client_id,
+            issued_by,
         };
 
         // ハンドラーを実行
@@ -111,9 +167,8 @@ where
             .handle(command)
             .await
             .map_err(|e| match e {
-                Error::Validation(msg) => Status::invalid_argument(msg),
                 Error::Conflict(msg) => Status::already_exists(msg),
-                _ => Status::internal(e.to_string()),
+                e => Status::from(e),
             })?;
 
         Ok(Response::new(CreateVocabularyItemResponse {
@@ -133,46 +188,70 @@ where
                 .map_err(|e| Status::invalid_argument(format!("Invalid item_id: {}", e)))?,
         );
 
-        // updates から disambiguation の更新を取得
+        // updates から disambiguation と個別フィールドの更新を取得
         let mut new_disambiguation = None;
+        let mut field_updates = Vec::new();
+        // JSON 形式の値をパース
+        let parse_value = |value_json: &str| -> Result<Option<String>, Status> {
+            serde_json::from_str(value_json)
+                .map_err(|e| Status::invalid_argument(format!("Invalid JSON value: {}", e)))
+        };
         for update in req.updates {
             if update.field_name == "disambiguation" {
-                // JSON 形式の値をパース
-                let value: Option<String> = serde_json::from_str(&update.value_json)
-                    .map_err(|e| Status::invalid_argument(format!("Invalid JSON value: {}", e)))?;
+                let value = parse_value(&update.value_json)?;
                 new_disambiguation = Some(Disambiguation::new(value).map_err(|e| {
                     Status::invalid_argument(format!("Invalid disambiguation: {}", e))
                 })?);
+            } else if let Ok(field) = update.field_name.parse::<ItemField>() {
+                field_updates.push((field, parse_value(&update.value_json)?));
             }
         }
 
-        if new_disambiguation.is_none() {
+        if new_disambiguation.is_none() && field_updates.is_empty() {
             return Err(Status::invalid_argument("No valid updates provided"));
         }
 
-        let command = UpdateVocabularyItem {
-            item_id:        *item_id.as_uuid(),
-            disambiguation: new_disambiguation
-                .unwrap()
-                .as_option()
-                .map(|s| s.to_string()),
-            version:        req.expected_version as i64,
+        let to_status = |e: Error| match e {
+            Error::NotFound(msg) => Status::not_found(msg),
+            Error::Conflict(msg) => Status::aborted(msg),
+            Error::Validation(msg) => Status::invalid_argument(msg),
+            _ => Status::internal(e.to_string()),
         };
 
-        // ハンドラーを実行
-        let item = self
-            .update_handler
-            .handle(command)
-            .await
-            .map_err(|e| match e {
-                Error::NotFound(msg) => Status::not_found(msg),
-                Error::Conflict(msg) => Status::aborted(msg),
-                Error::Validation(msg) => Status::invalid_argument(msg),
-                _ => Status::internal(e.to_string()),
-            })?;
+        // 更新ごとにバージョンが進むため、前の更新の結果のバージョンを引き継ぐ
+        let mut version = req.expected_version as i64;
+
+        if let Some(disambiguation) = new_disambiguation {
+            let command = UpdateVocabularyItem {
+                item_id: *item_id.as_uuid(),
+                disambiguation: disambiguation.as_option().map(|s| s.to_string()),
+                version,
+            };
+            let item = self
+                .update_handler
+                .handle(command)
+                .await
+                .map_err(to_status)?;
+            version = item.version.value();
+        }
+
+        for (field, value) in field_updates {
+            let command = UpdateVocabularyItemField {
+                item_id: *item_id.as_uuid(),
+                field,
+                value,
+                version,
+            };
+            let item = self
+                .update_handler
+                .handle_field_update(command)
+                .await
+                .map_err(to_status)?;
+            version = item.version.value();
+        }
 
         Ok(Response::new(UpdateVocabularyItemResponse {
-            new_version: item.version.value() as u32,
+            new_version: version as u32,
         }))
     }
 
@@ -225,4 +304,91 @@ where
             "Request AI enrichment is not implemented yet",
         ))
     }
+
+    async fn register_import_source(
+        &self,
+        request: Request<RegisterImportSourceRequest>,
+    ) -> Result<Response<RegisterImportSourceResponse>, Status> {
+        let req = request.into_inner();
+
+        let command = RegisterImportSource {
+            name:                    req.name,
+            license:                 req.license,
+            attribution_text:        req.attribution_text,
+            url:                     req.url,
+            requires_attribution:    req.requires_attribution,
+            import_examples:         req.import_examples,
+            examples_require_review: req.examples_require_review,
+        };
+
+        let source = self
+            .import_handler
+            .register_source(command)
+            .await
+            .map_err(|e| match e {
+                Error::Validation(msg) => Status::invalid_argument(msg),
+                Error::Conflict(msg) => Status::already_exists(msg),
+                _ => Status::internal(e.to_string()),
+            })?;
+
+        Ok(Response::new(RegisterImportSourceResponse {
+            source_id: source.source_id.to_string(),
+        }))
+    }
+
+    async fn import_external_source(
+        &self,
+        request: Request<Streaming<ImportExternalSourceRequest>>,
+    ) -> Result<Response<ImportExternalSourceResponse>, Status> {
+        let mut stream = request.into_inner();
+
+        // 最初のメッセージからソースとユーザーを特定
+        let first = stream
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("Empty import stream"))?;
+        let metadata = first
+            .metadata
+            .ok_or_else(|| Status::invalid_argument("metadata is required"))?;
+        let imported_by = Uuid::parse_str(&metadata.issued_by)
+            .map_err(|e| Status::invalid_argument(format!("Invalid issued_by: {}", e)))?;
+        let source_id = Uuid::parse_str(&first.source_id)
+            .map_err(|e| Status::invalid_argument(format!("Invalid source_id: {}", e)))?;
+
+        let source = self
+            .import_handler
+            .load_source(source_id)
+            .await
+            .map_err(|e| match e {
+                Error::NotFound(msg) => Status::not_found(msg),
+                _ => Status::internal(e.to_string()),
+            })?;
+
+        // チャンクを行単位に分割しながら逐次インポート
+        let mut report = ImportReport::default();
+        let mut splitter = JsonlLineSplitter::default();
+        let mut line_number = 0;
+        let mut chunk = first.chunk;
+        loop {
+            for line in splitter.push(&chunk) {
+                line_number += 1;
+                self.import_handler
+                    .import_line(&source, imported_by, line_number, &line, &mut report)
+                    .await;
+            }
+
+            match stream.message().await? {
+                Some(next) => chunk = next.chunk,
+                None => break,
+            }
+        }
+        if let Some(line) = splitter.finish() {
+            line_number += 1;
+            self.import_handler
+                .import_line(&source, imported_by, line_number, &line, &mut report)
+                .await;
+        }
+
+        Ok(Response::new(report.into()))
+    }
 }
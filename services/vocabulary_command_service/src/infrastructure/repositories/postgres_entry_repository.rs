@@ -4,7 +4,7 @@ use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
 use crate::{
-    domain::{EntryId, Spelling, Version, VocabularyEntry},
+    domain::{EntryId, Spelling, SpellingKey, Version, VocabularyEntry},
     error::{Error, Result},
     ports::repositories::VocabularyEntryRepository,
 };
@@ -142,6 +142,43 @@ impl VocabularyEntryRepository for PostgresVocabularyEntryRepository {
             None => Ok(None),
         }
     }
+
+    async fn find_by_spelling_key(&self, key: &SpellingKey) -> Result<Option<VocabularyEntry>> {
+        let row = sqlx::query(
+            r#"
+            SELECT 
+                entry_id,
+                spelling,
+                version,
+                created_at,
+                updated_at
+            FROM vocabulary_entries
+            WHERE LOWER(REGEXP_REPLACE(BTRIM(spelling), '\s+', ' ', 'g')) = $1
+            ORDER BY created_at
+            LIMIT 1
+            "#,
+        )
+        .bind(key.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::DatabaseString(e.to_string()))?;
+
+        match row {
+            Some(row) => {
+                let entry = VocabularyEntry {
+                    entry_id:   EntryId::from_uuid(row.get::<Uuid, _>("entry_id")),
+                    spelling:   Spelling::new(row.get::<String, _>("spelling"))
+                        .map_err(Error::Validation)?,
+                    version:    Version::new(row.get::<i64, _>("version"))
+                        .map_err(Error::Validation)?,
+                    created_at: row.get::<DateTime<Utc>, _>("created_at"),
+                    updated_at: row.get::<DateTime<Utc>, _>("updated_at"),
+                };
+                Ok(Some(entry))
+            },
+            None => Ok(None),
+        }
+    }
 }
 
 #[cfg(test)]
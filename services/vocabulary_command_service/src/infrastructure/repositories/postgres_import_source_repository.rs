@@ -0,0 +1,131 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use crate::{
+    domain::{FieldPolicy, ImportSource},
+    error::{Error, Result},
+    ports::repositories::ImportSourceRepository,
+};
+
+/// PostgreSQL 実装の ImportSourceRepository
+#[derive(Clone)]
+pub struct PostgresImportSourceRepository {
+    pool: PgPool,
+}
+
+impl PostgresImportSourceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: &PgRow) -> ImportSource {
+        ImportSource {
+            source_id:            row.get::<Uuid, _>("source_id"),
+            name:                 row.get("name"),
+            license:              row.get("license"),
+            attribution_text:     row.get("attribution_text"),
+            url:                  row.get::<Option<String>, _>("url"),
+            requires_attribution: row.get::<bool, _>("requires_attribution"),
+            field_policy:         FieldPolicy {
+                import_examples:         row.get::<bool, _>("import_examples"),
+                examples_require_review: row.get::<bool, _>("examples_require_review"),
+            },
+            created_at:           row.get::<DateTime<Utc>, _>("created_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl ImportSourceRepository for PostgresImportSourceRepository {
+    async fn find_by_id(&self, source_id: Uuid) -> Result<Option<ImportSource>> {
+        let row = sqlx::query(
+            r#"
+            SELECT 
+                source_id,
+                name,
+                license,
+                attribution_text,
+                url,
+                requires_attribution,
+                import_examples,
+                examples_require_review,
+                created_at
+            FROM import_sources
+            WHERE source_id = $1
+            "#,
+        )
+        .bind(source_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::DatabaseString(e.to_string()))?;
+
+        Ok(row.as_ref().map(Self::map_row))
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<ImportSource>> {
+        let row = sqlx::query(
+            r#"
+            SELECT 
+                source_id,
+                name,
+                license,
+                attribution_text,
+                url,
+                requires_attribution,
+                import_examples,
+                examples_require_review,
+                created_at
+            FROM import_sources
+            WHERE name = $1
+            "#,
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Error::DatabaseString(e.to_string()))?;
+
+        Ok(row.as_ref().map(Self::map_row))
+    }
+
+    async fn save(&self, source: &ImportSource) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO import_sources (
+                source_id,
+                name,
+                license,
+                attribution_text,
+                url,
+                requires_attribution,
+                import_examples,
+                examples_require_review,
+                created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (source_id)
+            DO UPDATE SET
+                license = EXCLUDED.license,
+                attribution_text = EXCLUDED.attribution_text,
+                url = EXCLUDED.url,
+                requires_attribution = EXCLUDED.requires_attribution,
+                import_examples = EXCLUDED.import_examples,
+                examples_require_review = EXCLUDED.examples_require_review
+            "#,
+        )
+        .bind(source.source_id)
+        .bind(&source.name)
+        .bind(&source.license)
+        .bind(&source.attribution_text)
+        .bind(&source.url)
+        .bind(source.requires_attribution)
+        .bind(source.field_policy.import_examples)
+        .bind(source.field_policy.examples_require_review)
+        .bind(source.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Error::DatabaseString(e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use sqlx::{PgPool, Row};
+use sqlx::{PgPool, Row, postgres::PgRow};
 use uuid::Uuid;
 
 use crate::{
@@ -9,6 +9,7 @@ use crate::{
         EntryId,
         ItemId,
         Spelling,
+        UsageNotes,
         Version,
         VocabularyItem,
         VocabularyStatus,
@@ -29,6 +30,18 @@ impl PostgresVocabularyItemRepository {
     }
 }
 
+/// 使用域・専門分野・使用上の注意の列を値オブジェクトに変換する
+fn parse_column<T>(row: &PgRow, column: &str) -> Result<Option<T>>
+where
+    T: std::str::FromStr<Err = String>,
+{
+    row.get::<Option<String>, _>(column)
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(Error::DatabaseString)
+}
+
 #[async_trait]
 impl VocabularyItemRepository for PostgresVocabularyItemRepository {
     async fn find_by_id(&self, item_id: &ItemId) -> Result<Option<VocabularyItem>> {
@@ -39,6 +52,9 @@ impl VocabularyItemRepository for PostgresVocabularyItemRepository {
                 entry_id,
                 spelling,
                 disambiguation,
+                register,
+                domain,
+                usage_notes,
                 is_primary,
                 status,
                 is_deleted,
@@ -65,6 +81,9 @@ impl VocabularyItemRepository for PostgresVocabularyItemRepository {
                         row.get::<Option<String>, _>("disambiguation"),
                     )
                     .map_err(Error::Validation)?,
+                    register:       parse_column(&row, "register")?,
+                    domain:         parse_column(&row, "domain")?,
+                    usage_notes:    parse_column(&row, "usage_notes")?,
                     is_primary:     row.get::<bool, _>("is_primary"),
                     status:         match row.get::<String, _>("status").as_str() {
                         "draft" => VocabularyStatus::Draft,
@@ -98,29 +117,38 @@ impl VocabularyItemRepository for PostgresVocabularyItemRepository {
                 entry_id,
                 spelling,
                 disambiguation,
+                register,
+                domain,
+                usage_notes,
                 is_primary,
                 status,
                 is_deleted,
                 created_at,
                 updated_at,
                 version
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             ON CONFLICT (item_id) 
             DO UPDATE SET
                 spelling = EXCLUDED.spelling,
                 disambiguation = EXCLUDED.disambiguation,
+                register = EXCLUDED.register,
+                domain = EXCLUDED.domain,
+                usage_notes = EXCLUDED.usage_notes,
                 is_primary = EXCLUDED.is_primary,
                 status = EXCLUDED.status,
                 is_deleted = EXCLUDED.is_deleted,
                 updated_at = EXCLUDED.updated_at,
                 version = EXCLUDED.version
-            WHERE vocabulary_items.version = $10 - 1
+            WHERE vocabulary_items.version = $13 - 1
             "#,
         )
         .bind(item.item_id.as_uuid())
         .bind(item.entry_id.as_uuid())
         .bind(item.spelling.value())
         .bind(item.disambiguation.as_option())
+        .bind(item.register.map(|r| r.as_str().to_string()))
+        .bind(item.domain.as_ref().map(|d| d.as_str().to_string()))
+        .bind(item.usage_notes.as_ref().map(UsageNotes::as_str))
         .bind(item.is_primary)
         .bind(item.status.as_str())
         .bind(item.is_deleted)
@@ -150,6 +178,9 @@ impl VocabularyItemRepository for PostgresVocabularyItemRepository {
                 entry_id,
                 spelling,
                 disambiguation,
+                register,
+                domain,
+                usage_notes,
                 is_primary,
                 status,
                 is_deleted,
@@ -175,6 +206,9 @@ impl VocabularyItemRepository for PostgresVocabularyItemRepository {
                     .map_err(Error::Validation)?,
                 disambiguation: Disambiguation::new(row.get::<Option<String>, _>("disambiguation"))
                     .map_err(Error::Validation)?,
+                register:       parse_column(&row, "register")?,
+                domain:         parse_column(&row, "domain")?,
+                usage_notes:    parse_column(&row, "usage_notes")?,
                 is_primary:     row.get::<bool, _>("is_primary"),
                 status:         match row.get::<String, _>("status").as_str() {
                     "draft" => VocabularyStatus::Draft,
@@ -296,6 +330,9 @@ impl VocabularyItemRepository for PostgresVocabularyItemRepository {
                 entry_id,
                 spelling,
                 disambiguation,
+                register,
+                domain,
+                usage_notes,
                 is_primary,
                 status,
                 is_deleted,
@@ -323,6 +360,9 @@ impl VocabularyItemRepository for PostgresVocabularyItemRepository {
                         row.get::<Option<String>, _>("disambiguation"),
                     )
                     .map_err(Error::Validation)?,
+                    register:       parse_column(&row, "register")?,
+                    domain:         parse_column(&row, "domain")?,
+                    usage_notes:    parse_column(&row, "usage_notes")?,
                     is_primary:     row.get::<bool, _>("is_primary"),
                     status:         match row.get::<String, _>("status").as_str() {
                         "draft" => VocabularyStatus::Draft,
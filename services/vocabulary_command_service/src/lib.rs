@@ -6,17 +6,22 @@
 pub mod config;
 pub mod error;
 
+/// コマンド側テーブルのマイグレーション
+pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
+
 // ドメイン層
 pub mod domain {
     pub mod aggregates;
     pub mod commands;
     pub mod events;
+    pub mod import_sources;
     pub mod value_objects;
 
     // 再エクスポート
     pub use aggregates::*;
     pub use commands::*;
     pub use events::*;
+    pub use import_sources::*;
     pub use value_objects::*;
 }
 
@@ -34,6 +39,7 @@ pub mod application {
     pub mod commands {
         pub mod create_vocabulary_item;
         pub mod delete_vocabulary_item;
+        pub mod import_external_source;
         pub mod update_vocabulary_item;
 
         #[cfg(test)]
@@ -41,6 +47,7 @@ pub mod application {
 
         pub use create_vocabulary_item::CreateVocabularyItemHandler;
         pub use delete_vocabulary_item::DeleteVocabularyItemHandler;
+        pub use import_external_source::{ImportExternalSourceHandler, ImportReport};
         pub use update_vocabulary_item::UpdateVocabularyItemHandler;
     }
 }
@@ -49,9 +56,11 @@ pub mod application {
 pub mod infrastructure {
     pub mod repositories {
         pub mod postgres_entry_repository;
+        pub mod postgres_import_source_repository;
         pub mod postgres_item_repository;
 
         pub use postgres_entry_repository::PostgresVocabularyEntryRepository;
+        pub use postgres_import_source_repository::PostgresImportSourceRepository;
         pub use postgres_item_repository::PostgresVocabularyItemRepository;
     }
 
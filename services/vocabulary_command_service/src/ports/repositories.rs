@@ -1,7 +1,8 @@
 use async_trait::async_trait;
+use uuid::Uuid;
 
 use crate::{
-    domain::{EntryId, ItemId, VocabularyEntry, VocabularyItem},
+    domain::{EntryId, ImportSource, ItemId, SpellingKey, VocabularyEntry, VocabularyItem},
     error::Result,
 };
 
@@ -22,6 +23,9 @@ pub trait VocabularyEntryRepository: Send + Sync {
         &self,
         spelling: &crate::domain::Spelling,
     ) -> Result<Option<VocabularyEntry>>;
+
+    /// 正規化したスペリングキーでエントリを検索（重複判定用）
+    async fn find_by_spelling_key(&self, key: &SpellingKey) -> Result<Option<VocabularyEntry>>;
 }
 
 /// VocabularyItem のリポジトリトレイト
@@ -40,6 +44,19 @@ pub trait VocabularyItemRepository: Send + Sync {
     async fn find_primary_by_entry_id(&self, entry_id: &EntryId) -> Result<Option<VocabularyItem>>;
 }
 
+/// 外部辞書ソースのリポジトリトレイト
+#[async_trait]
+pub trait ImportSourceRepository: Send + Sync {
+    /// ID でソースを検索
+    async fn find_by_id(&self, source_id: Uuid) -> Result<Option<ImportSource>>;
+
+    /// 名前でソースを検索
+    async fn find_by_name(&self, name: &str) -> Result<Option<ImportSource>>;
+
+    /// ソースを保存
+    async fn save(&self, source: &ImportSource) -> Result<()>;
+}
+
 /// 統合リポジトリトレイト（トランザクション管理用）
 #[async_trait]
 pub trait VocabularyRepository: VocabularyEntryRepository + VocabularyItemRepository {
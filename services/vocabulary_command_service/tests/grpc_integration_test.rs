@@ -52,8 +52,10 @@ async fn test_vocabulary_item_lifecycle() -> Result<(), Box<dyn std::error::Erro
         word:           "apple".to_string(),
         definitions:    vec!["A round fruit with red or green skin".to_string()],
         part_of_speech: "noun".to_string(),
-        register:       "general".to_string(),
+        register:       "neutral".to_string(),
         domain:         "food".to_string(),
+        client_id:      String::new(),
+        usage_notes:    String::new(),
     });
 
     let create_response = client.create_vocabulary_item(create_request).await?;
@@ -115,8 +117,10 @@ async fn test_duplicate_word_handling() -> Result<(), Box<dyn std::error::Error>
         word:           word.clone(),
         definitions:    vec!["First definition".to_string()],
         part_of_speech: "noun".to_string(),
-        register:       "general".to_string(),
+        register:       "neutral".to_string(),
         domain:         "test".to_string(),
+        client_id:      String::new(),
+        usage_notes:    String::new(),
     });
 
     let first_response = client.create_vocabulary_item(create_request).await?;
@@ -129,8 +133,10 @@ async fn test_duplicate_word_handling() -> Result<(), Box<dyn std::error::Error>
         word:           word.clone(),
         definitions:    vec!["Second definition".to_string()],
         part_of_speech: "verb".to_string(),
-        register:       "general".to_string(),
+        register:       "neutral".to_string(),
         domain:         "test".to_string(),
+        client_id:      String::new(),
+        usage_notes:    String::new(),
     });
 
     let second_response = client.create_vocabulary_item(create_request2).await?;
@@ -168,8 +174,10 @@ async fn test_optimistic_locking() -> Result<(), Box<dyn std::error::Error>> {
         word,
         definitions: vec!["Test definition".to_string()],
         part_of_speech: "noun".to_string(),
-        register: "general".to_string(),
+        register: "neutral".to_string(),
         domain: "test".to_string(),
+        client_id: String::new(),
+        usage_notes: String::new(),
     });
 
     let create_response = client.create_vocabulary_item(create_request).await?;
@@ -0,0 +1,51 @@
+-- 集約ごとのイベント順序制御
+-- EventBus 経由では同一集約のイベントが順不同で届くことがあるため、
+-- 適用済みバージョンを追跡し、欠番のあるイベントは欠番が埋まるまで保留する
+
+CREATE TABLE IF NOT EXISTS projection_aggregate_versions (
+    projection_name VARCHAR(255) NOT NULL,
+    aggregate_id UUID NOT NULL,
+    last_applied_version BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (projection_name, aggregate_id)
+);
+
+-- 欠番待ちで保留中のイベント
+CREATE TABLE IF NOT EXISTS projection_pending_events (
+    projection_name VARCHAR(255) NOT NULL,
+    aggregate_id UUID NOT NULL,
+    aggregate_version BIGINT NOT NULL,
+    position BIGINT NOT NULL,
+    event_id UUID NOT NULL,
+    event_type VARCHAR(255) NOT NULL,
+    event_data TEXT NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    buffered_at TIMESTAMPTZ NOT NULL,
+
+    PRIMARY KEY (projection_name, aggregate_id, aggregate_version)
+);
+
+CREATE INDEX idx_projection_pending_events_buffered_at
+ON projection_pending_events (projection_name, buffered_at);
+
+-- 既存の Read Model から適用済みバージョンを引き継ぐ
+-- （導入前に投影済みの集約を欠番扱いにしないため）
+INSERT INTO projection_aggregate_versions (projection_name, aggregate_id, last_applied_version)
+SELECT
+    'vocabulary_projection',
+    aggregate_id,
+    MAX(last_event_version)
+FROM (
+    SELECT
+        entry_id AS aggregate_id,
+        last_event_version
+    FROM vocabulary_entries_read
+    UNION ALL
+    SELECT
+        item_id AS aggregate_id,
+        last_event_version
+    FROM vocabulary_items_read
+) AS applied
+GROUP BY aggregate_id
+ON CONFLICT (projection_name, aggregate_id) DO NOTHING;
@@ -0,0 +1,9 @@
+-- 共有コレクションに含まれる語彙アイテム
+-- CollectionItemAdded / CollectionItemRemoved から Projection Service が更新する
+
+CREATE TABLE IF NOT EXISTS vocabulary_collection_items_read (
+    collection_id UUID NOT NULL,
+    item_id UUID NOT NULL,
+    added_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (collection_id, item_id)
+);
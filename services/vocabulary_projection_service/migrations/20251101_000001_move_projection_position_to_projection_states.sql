@@ -0,0 +1,27 @@
+-- 処理済みの位置を shared_cqrs の PostgresCheckpointStore のテーブルに移す
+-- projection_state にはエラーの状態だけを残す
+
+CREATE TABLE IF NOT EXISTS projection_states (
+    projection_name VARCHAR(100) PRIMARY KEY,
+    last_position BIGINT NOT NULL DEFAULT 0,
+    last_event_id UUID,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- 再起動後に続きから処理できるよう、これまでの位置を引き継ぐ
+INSERT INTO projection_states (projection_name, last_position, last_event_id, updated_at)
+SELECT
+    projection_name,
+    last_processed_position,
+    last_processed_event_id,
+    COALESCE(last_processed_at, updated_at)
+FROM projection_state
+ON CONFLICT (projection_name) DO NOTHING;
+
+-- チェックポイントの履歴は保存しなくなった
+DROP TABLE IF EXISTS projection_checkpoints;
+
+ALTER TABLE projection_state
+    DROP COLUMN last_processed_position,
+    DROP COLUMN last_processed_event_id,
+    DROP COLUMN last_processed_at;
@@ -0,0 +1,5 @@
+-- 語彙項目の使用上の注意
+-- 学習セッションで解答とともに提示する
+
+ALTER TABLE vocabulary_items_read
+    ADD COLUMN IF NOT EXISTS usage_notes TEXT;
@@ -9,6 +9,9 @@ use crate::{
     domain::{
         events::{EnrichedData, StoredEvent},
         projections::{
+            LearningContextField,
+            ProvenanceProjection,
+            ProvenanceTarget,
             VocabularyEntryProjection,
             VocabularyExampleProjection,
             VocabularyItemProjection,
@@ -42,11 +45,14 @@ impl<R: ReadModelRepository> EventHandler<R> {
         match event.event_type.as_str() {
             "VocabularyEntryCreated" => self.handle_entry_created(tx, event).await,
             "VocabularyItemCreated" => self.handle_item_created(tx, event).await,
+            "VocabularyItemFieldUpdated" => self.handle_item_field_updated(tx, event).await,
             "VocabularyItemPublished" => self.handle_item_published(tx, event).await,
             "VocabularyItemDeleted" => self.handle_item_deleted(tx, event).await,
             "ExampleAdded" => self.handle_example_added(tx, event).await,
             "AIEnrichmentCompleted" => self.handle_ai_enrichment(tx, event).await,
             "PrimaryItemSet" => self.handle_primary_item_set(tx, event).await,
+            "CollectionItemAdded" => self.handle_collection_item_added(tx, event).await,
+            "CollectionItemRemoved" => self.handle_collection_item_removed(tx, event).await,
             _ => {
                 warn!("Unknown event type: {}", event.event_type);
                 Ok(())
@@ -81,34 +87,47 @@ impl<R: ReadModelRepository> EventHandler<R> {
     ) -> Result<()> {
         let data: JsonValue = serde_json::from_str(&event.event_data)?;
 
-        let item = VocabularyItemProjection {
-            item_id:            data["item_id"]
-                .as_str()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or_else(Uuid::new_v4),
-            entry_id:           data["entry_id"]
-                .as_str()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(event.aggregate_id),
-            spelling:           data["spelling"].as_str().unwrap_or("").to_string(),
-            disambiguation:     data["disambiguation"].as_str().map(String::from),
-            part_of_speech:     None,
-            definition:         None,
-            ipa_pronunciation:  None,
-            cefr_level:         None,
-            frequency_rank:     None,
-            is_published:       false,
-            is_deleted:         false,
-            example_count:      0,
-            created_at:         event.occurred_at,
-            updated_at:         event.occurred_at,
-            last_event_version: event.aggregate_version,
-        };
+        let item = item_projection(event, &data);
 
         self.repository.save_item(tx, &item).await?;
+
+        // 外部ソースからのインポートであれば出典を記録
+        if let Some(provenance) =
+            self.extract_provenance(&data, item.item_id, item.item_id, ProvenanceTarget::Item)
+        {
+            self.repository.save_provenance(tx, &provenance).await?;
+        }
+
         self.repository.update_item_count(tx, item.entry_id).await
     }
 
+    async fn handle_item_field_updated(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        event: &StoredEvent,
+    ) -> Result<()> {
+        let data: JsonValue = serde_json::from_str(&event.event_data)?;
+        let item_id = self.extract_uuid(&data, "item_id")?;
+
+        match learning_context_change(&data) {
+            Some((field, value)) => {
+                self.repository
+                    .update_item_learning_context(
+                        tx,
+                        item_id,
+                        field,
+                        value,
+                        event.aggregate_version,
+                    )
+                    .await
+            },
+            None => {
+                debug!("Field {:?} is not projected", data["field_path"]);
+                Ok(())
+            },
+        }
+    }
+
     async fn handle_item_published(
         &self,
         tx: &mut Transaction<'_, Postgres>,
@@ -164,6 +183,16 @@ impl<R: ReadModelRepository> EventHandler<R> {
         };
 
         self.repository.add_example(tx, &example).await?;
+
+        if let Some(provenance) = self.extract_provenance(
+            &data,
+            example.example_id,
+            item_id,
+            ProvenanceTarget::Example,
+        ) {
+            self.repository.save_provenance(tx, &provenance).await?;
+        }
+
         self.repository.increment_example_count(tx, item_id).await
     }
 
@@ -204,6 +233,59 @@ impl<R: ReadModelRepository> EventHandler<R> {
             .await
     }
 
+    async fn handle_collection_item_added(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        event: &StoredEvent,
+    ) -> Result<()> {
+        let data: JsonValue = serde_json::from_str(&event.event_data)?;
+        let collection_id = self.extract_uuid(&data, "collection_id")?;
+        let item_id = self.extract_uuid(&data, "vocabulary_item_id")?;
+
+        self.repository
+            .add_collection_item(tx, collection_id, item_id, event.occurred_at)
+            .await
+    }
+
+    async fn handle_collection_item_removed(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        event: &StoredEvent,
+    ) -> Result<()> {
+        let data: JsonValue = serde_json::from_str(&event.event_data)?;
+        let collection_id = self.extract_uuid(&data, "collection_id")?;
+        let item_id = self.extract_uuid(&data, "vocabulary_item_id")?;
+
+        self.repository
+            .remove_collection_item(tx, collection_id, item_id)
+            .await
+    }
+
+    /// イベントデータから出典を取り出す（インポート以外のイベントでは None）
+    fn extract_provenance(
+        &self,
+        data: &JsonValue,
+        target_id: Uuid,
+        item_id: Uuid,
+        target_kind: ProvenanceTarget,
+    ) -> Option<ProvenanceProjection> {
+        let provenance = data["provenance"].as_object()?;
+
+        Some(ProvenanceProjection {
+            target_id,
+            item_id,
+            target_kind,
+            source_name: provenance.get("source_name")?.as_str()?.to_string(),
+            license: provenance.get("license")?.as_str()?.to_string(),
+            attribution: provenance
+                .get("attribution")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            requires_review: target_kind == ProvenanceTarget::Example
+                && data["requires_review"].as_bool().unwrap_or(false),
+        })
+    }
+
     fn extract_uuid(&self, data: &JsonValue, field: &str) -> Result<Uuid> {
         data[field]
             .as_str()
@@ -213,3 +295,150 @@ impl<R: ReadModelRepository> EventHandler<R> {
             })
     }
 }
+
+/// VocabularyItemCreated のデータから Item の Read Model を作る
+fn item_projection(event: &StoredEvent, data: &JsonValue) -> VocabularyItemProjection {
+    VocabularyItemProjection {
+        item_id:            data["item_id"]
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(Uuid::new_v4),
+        entry_id:           data["entry_id"]
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(event.aggregate_id),
+        spelling:           data["spelling"].as_str().unwrap_or("").to_string(),
+        disambiguation:     data["disambiguation"].as_str().map(String::from),
+        register:           data["register"].as_str().map(String::from),
+        domain:             data["domain"].as_str().map(String::from),
+        usage_notes:        data["usage_notes"].as_str().map(String::from),
+        part_of_speech:     None,
+        definition:         None,
+        ipa_pronunciation:  None,
+        cefr_level:         None,
+        frequency_rank:     None,
+        is_published:       false,
+        is_deleted:         false,
+        example_count:      0,
+        created_at:         event.occurred_at,
+        updated_at:         event.occurred_at,
+        last_event_version: event.aggregate_version,
+    }
+}
+
+/// VocabularyItemFieldUpdated のデータから学習コンテキストの変更を取り出す
+///
+/// 投影しないフィールドの更新では None を返す
+fn learning_context_change(data: &JsonValue) -> Option<(LearningContextField, Option<String>)> {
+    let field = LearningContextField::from_field_path(data["field_path"].as_str()?)?;
+    Some((field, data["new_value"].as_str().map(String::from)))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use super::*;
+
+    fn stored_event(event_type: &str, version: i64, data: &JsonValue) -> StoredEvent {
+        StoredEvent {
+            position:          version,
+            event_id:          Uuid::new_v4(),
+            aggregate_id:      Uuid::new_v4(),
+            aggregate_version: version,
+            event_type:        event_type.to_string(),
+            event_data:        data.to_string(),
+            occurred_at:       Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_item_created_projects_learning_context() {
+        let item_id = Uuid::new_v4();
+        let data = json!({
+            "type": "VocabularyItemCreated",
+            "item_id": item_id,
+            "entry_id": Uuid::new_v4(),
+            "spelling": "tort",
+            "disambiguation": null,
+            "register": "formal",
+            "domain": "legal",
+            "usage_notes": "Used in civil law; not a criminal offence",
+        });
+        let event = stored_event("VocabularyItemCreated", 1, &data);
+
+        let item = item_projection(&event, &data);
+
+        assert_eq!(item.item_id, item_id);
+        assert_eq!(item.register.as_deref(), Some("formal"));
+        assert_eq!(item.domain.as_deref(), Some("legal"));
+        assert_eq!(
+            item.usage_notes.as_deref(),
+            Some("Used in civil law; not a criminal offence")
+        );
+        assert_eq!(item.last_event_version, 1);
+    }
+
+    #[test]
+    fn test_item_created_without_learning_context() {
+        // 学習コンテキスト導入前のイベントにはフィールドがない
+        let data = json!({
+            "item_id": Uuid::new_v4(),
+            "entry_id": Uuid::new_v4(),
+            "spelling": "bank",
+            "disambiguation": "financial institution",
+        });
+        let event = stored_event("VocabularyItemCreated", 1, &data);
+
+        let item = item_projection(&event, &data);
+
+        assert_eq!(item.register, None);
+        assert_eq!(item.domain, None);
+        assert_eq!(item.usage_notes, None);
+    }
+
+    #[test]
+    fn test_field_updated_extracts_learning_context_change() {
+        let data = json!({
+            "item_id": Uuid::new_v4(),
+            "field_path": "register",
+            "old_value": "neutral",
+            "new_value": "slang",
+        });
+        assert_eq!(
+            learning_context_change(&data),
+            Some((LearningContextField::Register, Some("slang".to_string())))
+        );
+
+        // 値の解除
+        let data = json!({
+            "item_id": Uuid::new_v4(),
+            "field_path": "domain",
+            "old_value": "medical",
+            "new_value": null,
+        });
+        assert_eq!(
+            learning_context_change(&data),
+            Some((LearningContextField::Domain, None))
+        );
+
+        let data = json!({
+            "item_id": Uuid::new_v4(),
+            "field_path": "usage_notes",
+            "old_value": null,
+            "new_value": "Usually plural",
+        });
+        assert_eq!(
+            learning_context_change(&data),
+            Some((
+                LearningContextField::UsageNotes,
+                Some("Usually plural".to_string())
+            ))
+        );
+
+        // 投影しないフィールド
+        let data = json!({ "field_path": "pronunciation", "new_value": "/bæŋk/" });
+        assert_eq!(learning_context_change(&data), None);
+    }
+}
@@ -0,0 +1,314 @@
+//! 集約ごとの順序保証付きのイベント適用
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use chrono::{Duration, Utc};
+use sqlx::{Postgres, Transaction};
+use tracing::{debug, warn};
+
+use crate::{
+    domain::{
+        events::StoredEvent,
+        ordering::{Admission, AggregateCursor},
+    },
+    error::{ProjectionError, Result},
+    ports::outbound::{AggregateEventSource, EventOrderingRepository},
+};
+
+/// 順序制御のメトリクス
+#[derive(Debug, Default)]
+pub struct OrderingMetrics {
+    buffered:      AtomicU64,
+    gap_filled:    AtomicU64,
+    expired_holds: AtomicU64,
+}
+
+impl OrderingMetrics {
+    /// 欠番待ちで保留したイベント数
+    pub fn buffered(&self) -> u64 {
+        self.buffered.load(Ordering::Relaxed)
+    }
+
+    /// Event Store から取得して欠番を埋めたイベント数
+    pub fn gap_filled(&self) -> u64 {
+        self.gap_filled.load(Ordering::Relaxed)
+    }
+
+    /// 保留期限切れになった回数
+    pub fn expired_holds(&self) -> u64 {
+        self.expired_holds.load(Ordering::Relaxed)
+    }
+}
+
+/// 欠番を Event Store から取得して埋め、適用可能になったイベントを返す
+///
+/// 欠番をすべて埋められない場合はカーソルを変更せずにエラーを返す
+pub async fn fill_gap<S>(
+    source: &S,
+    cursor: &mut AggregateCursor,
+    metrics: &OrderingMetrics,
+) -> Result<Vec<StoredEvent>>
+where
+    S: AggregateEventSource + ?Sized,
+{
+    let Some((from, to)) = cursor.missing_range() else {
+        return Ok(cursor.fill(Vec::new()));
+    };
+
+    let fetched = source
+        .fetch_aggregate_events(cursor.aggregate_id, from, to)
+        .await?;
+
+    let covered = (from..=to).all(|version| {
+        fetched
+            .iter()
+            .any(|e| e.aggregate_id == cursor.aggregate_id && e.aggregate_version == version)
+    });
+    if !covered {
+        return Err(ProjectionError::EventProcessing(format!(
+            "Could not fill gap for aggregate {}: versions {}..={} are not available",
+            cursor.aggregate_id, from, to
+        )));
+    }
+
+    metrics
+        .gap_filled
+        .fetch_add((to - from + 1) as u64, Ordering::Relaxed);
+    Ok(cursor.fill(fetched))
+}
+
+/// 集約ごとにバージョン順でイベントを適用するためのシーケンサー
+pub struct EventSequencer<O, S>
+where
+    O: EventOrderingRepository,
+    S: AggregateEventSource,
+{
+    projection_name: String,
+    repository:      O,
+    source:          Arc<S>,
+    max_hold:        Duration,
+    metrics:         OrderingMetrics,
+}
+
+impl<O, S> EventSequencer<O, S>
+where
+    O: EventOrderingRepository,
+    S: AggregateEventSource,
+{
+    pub fn new(
+        projection_name: String,
+        repository: O,
+        source: Arc<S>,
+        max_hold: std::time::Duration,
+    ) -> Self {
+        Self {
+            projection_name,
+            repository,
+            source,
+            max_hold: Duration::from_std(max_hold).unwrap_or(Duration::MAX),
+            metrics: OrderingMetrics::default(),
+        }
+    }
+
+    pub fn metrics(&self) -> &OrderingMetrics {
+        &self.metrics
+    }
+
+    /// イベントを受け入れ、今すぐ適用すべきイベントをバージョン順に返す
+    pub async fn admit(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        event: &StoredEvent,
+    ) -> Result<Vec<StoredEvent>> {
+        let mut cursor = self
+            .repository
+            .load_cursor(tx, &self.projection_name, event.aggregate_id)
+            .await?;
+
+        match cursor.admit(event.clone(), Utc::now()) {
+            Admission::Apply(events) => {
+                self.repository
+                    .advance(
+                        tx,
+                        &self.projection_name,
+                        cursor.aggregate_id,
+                        cursor.last_applied_version,
+                    )
+                    .await?;
+                Ok(events)
+            },
+            Admission::Buffered => {
+                debug!(
+                    "Buffering event {} (aggregate {} v{}, last applied v{})",
+                    event.event_id,
+                    event.aggregate_id,
+                    event.aggregate_version,
+                    cursor.last_applied_version
+                );
+                if let Some(pending) = cursor
+                    .pending
+                    .iter()
+                    .find(|p| p.event.aggregate_version == event.aggregate_version)
+                {
+                    self.repository
+                        .buffer_event(tx, &self.projection_name, pending)
+                        .await?;
+                }
+                self.metrics.buffered.fetch_add(1, Ordering::Relaxed);
+                Ok(Vec::new())
+            },
+            Admission::Duplicate => {
+                debug!(
+                    "Skipping already applied event {} (aggregate {} v{})",
+                    event.event_id, event.aggregate_id, event.aggregate_version
+                );
+                Ok(Vec::new())
+            },
+        }
+    }
+
+    /// 保留期限を過ぎた集約の欠番を補完し、適用すべきイベントを返す
+    ///
+    /// 補完できなかった集約は保留したまま次回に再試行する
+    pub async fn release_expired(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+    ) -> Result<Vec<StoredEvent>> {
+        let buffered_before = Utc::now() - self.max_hold;
+        let aggregate_ids = self
+            .repository
+            .find_expired_aggregates(&self.projection_name, buffered_before)
+            .await?;
+
+        let mut ready = Vec::new();
+        for aggregate_id in aggregate_ids {
+            self.metrics.expired_holds.fetch_add(1, Ordering::Relaxed);
+
+            let mut cursor = self
+                .repository
+                .load_cursor(tx, &self.projection_name, aggregate_id)
+                .await?;
+
+            match fill_gap(self.source.as_ref(), &mut cursor, &self.metrics).await {
+                Ok(events) => {
+                    self.repository
+                        .advance(
+                            tx,
+                            &self.projection_name,
+                            aggregate_id,
+                            cursor.last_applied_version,
+                        )
+                        .await?;
+                    ready.extend(events);
+                },
+                Err(e) => warn!("Hold expired but gap fill failed: {}", e),
+            }
+        }
+
+        Ok(ready)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn event(aggregate_id: Uuid, version: i64) -> StoredEvent {
+        StoredEvent {
+            position: version,
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            aggregate_version: version,
+            event_type: "VocabularyItemPublished".to_string(),
+            event_data: "{}".to_string(),
+            occurred_at: Utc::now(),
+        }
+    }
+
+    /// 保持しているイベントを範囲で返すスタブの Event Store
+    #[derive(Default)]
+    struct StubEventSource {
+        events: Vec<StoredEvent>,
+        calls:  Mutex<Vec<(Uuid, i64, i64)>>,
+    }
+
+    #[async_trait]
+    impl AggregateEventSource for StubEventSource {
+        async fn fetch_aggregate_events(
+            &self,
+            aggregate_id: Uuid,
+            from_version: i64,
+            to_version: i64,
+        ) -> Result<Vec<StoredEvent>> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((aggregate_id, from_version, to_version));
+            Ok(self
+                .events
+                .iter()
+                .filter(|e| {
+                    e.aggregate_id == aggregate_id
+                        && (from_version..=to_version).contains(&e.aggregate_version)
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fill_gap_fetches_missing_versions_from_event_store() {
+        let id = Uuid::new_v4();
+        let source = StubEventSource {
+            events: (1..=6).map(|v| event(id, v)).collect(),
+            ..Default::default()
+        };
+        let metrics = OrderingMetrics::default();
+
+        let mut cursor = AggregateCursor::new(id, 2, Vec::new());
+        cursor.admit(event(id, 5), Utc::now());
+        cursor.admit(event(id, 6), Utc::now());
+
+        let ready = fill_gap(&source, &mut cursor, &metrics).await.unwrap();
+
+        assert_eq!(*source.calls.lock().unwrap(), vec![(id, 3, 4)]);
+        assert_eq!(
+            ready
+                .iter()
+                .map(|e| e.aggregate_version)
+                .collect::<Vec<_>>(),
+            vec![3, 4, 5, 6]
+        );
+        assert_eq!(cursor.last_applied_version, 6);
+        assert!(cursor.pending.is_empty());
+        assert_eq!(metrics.gap_filled(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fill_gap_keeps_buffer_when_event_store_is_missing_versions() {
+        let id = Uuid::new_v4();
+        let source = StubEventSource {
+            events: vec![event(id, 3)],
+            ..Default::default()
+        };
+        let metrics = OrderingMetrics::default();
+
+        let mut cursor = AggregateCursor::new(id, 2, Vec::new());
+        cursor.admit(event(id, 5), Utc::now());
+
+        let result = fill_gap(&source, &mut cursor, &metrics).await;
+
+        assert!(matches!(result, Err(ProjectionError::EventProcessing(_))));
+        assert_eq!(cursor.last_applied_version, 2);
+        assert_eq!(cursor.pending.len(), 1);
+        assert_eq!(metrics.gap_filled(), 0);
+    }
+}
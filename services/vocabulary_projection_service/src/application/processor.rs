@@ -1,56 +1,96 @@
 //! イベント処理サービス
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
+use shared_cqrs::{CheckpointStore, ErrorPolicy, ProjectionRunner};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info};
 
 use crate::{
-    application::event_handlers::EventHandler,
+    application::{
+        ordering::EventSequencer,
+        projection::{SubscriberFeed, VocabularyProjector},
+    },
     config::Config,
-    domain::projections::{ProjectionCheckpoint, ProjectionState},
-    error::Result,
+    domain::projections::ProjectionState,
+    error::{ProjectionError, Result},
     ports::{
         inbound::{EventProcessorUseCase, ProcessorStatus},
-        outbound::{EventSubscriber, ProjectionStateRepository, ReadModelRepository},
+        outbound::{
+            AggregateEventSource,
+            EventOrderingRepository,
+            EventSubscriber,
+            ProjectionStateRepository,
+            ReadModelRepository,
+        },
     },
 };
 
+/// 失敗したイベントを再試行するまでの間隔
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
 /// イベントプロセッサー
-pub struct EventProcessor<E, R, P>
+pub struct EventProcessor<E, R, P, O>
 where
-    E: EventSubscriber,
+    E: EventSubscriber + AggregateEventSource,
     R: ReadModelRepository,
     P: ProjectionStateRepository,
+    O: EventOrderingRepository,
 {
     config:           Config,
-    event_subscriber: Arc<E>,
-    event_handler:    Arc<EventHandler<R>>,
+    projection:       Arc<VocabularyProjector<R, O, E>>,
+    runner:           ProjectionRunner<VocabularyProjector<R, O, E>>,
+    sequencer:        Arc<EventSequencer<O, E>>,
+    checkpoints:      Arc<dyn CheckpointStore>,
     state_repository: Arc<P>,
-    read_repository:  Arc<R>,
     is_running:       Arc<RwLock<bool>>,
 }
 
-impl<E, R, P> EventProcessor<E, R, P>
+impl<E, R, P, O> EventProcessor<E, R, P, O>
 where
-    E: EventSubscriber,
-    R: ReadModelRepository + Clone,
+    E: EventSubscriber + AggregateEventSource + 'static,
+    R: ReadModelRepository + Clone + 'static,
     P: ProjectionStateRepository,
+    O: EventOrderingRepository + 'static,
 {
     pub fn new(
         config: Config,
         event_subscriber: E,
         read_repository: R,
         state_repository: P,
+        ordering_repository: O,
+        checkpoints: Arc<dyn CheckpointStore>,
     ) -> Self {
-        let event_handler = EventHandler::new(read_repository.clone());
+        let event_subscriber = Arc::new(event_subscriber);
+        let sequencer = Arc::new(EventSequencer::new(
+            config.projection.name.clone(),
+            ordering_repository,
+            event_subscriber.clone(),
+            Duration::from_millis(config.projection.max_hold_ms),
+        ));
+        let projection = Arc::new(VocabularyProjector::new(
+            config.projection.name.clone(),
+            Arc::new(read_repository),
+            sequencer.clone(),
+        ));
+        let runner = ProjectionRunner::new(
+            projection.clone(),
+            Arc::new(SubscriberFeed::new(event_subscriber)),
+            checkpoints.clone(),
+        )
+        .with_batch_size(config.event_store.batch_size)
+        .with_error_policy(ErrorPolicy::Retry {
+            max_attempts: config.projection.error_retry_limit,
+            delay:        RETRY_DELAY,
+        });
 
         Self {
             config,
-            event_subscriber: Arc::new(event_subscriber),
-            event_handler: Arc::new(event_handler),
+            projection,
+            runner,
+            sequencer,
+            checkpoints,
             state_repository: Arc::new(state_repository),
-            read_repository: Arc::new(read_repository),
             is_running: Arc::new(RwLock::new(false)),
         }
     }
@@ -65,11 +105,11 @@ where
         // 実行フラグを設定
         *self.is_running.write().await = true;
 
-        // プロジェクション状態を取得または初期化
-        let mut state = self.get_or_init_state().await?;
+        let position = self.last_processed_position().await?;
+        info!("Resuming projection from position {}", position);
 
         while *self.is_running.read().await {
-            match self.process_batch(&mut state).await {
+            match self.process_batch().await {
                 Ok(events_processed) => {
                     if events_processed > 0 {
                         debug!("Processed {} events", events_processed);
@@ -77,7 +117,6 @@ where
                 },
                 Err(e) => {
                     error!("Error processing events: {}", e);
-                    state.record_error(e.to_string());
                     self.state_repository
                         .record_error(&self.config.projection.name, &e.to_string())
                         .await
@@ -99,83 +138,39 @@ where
         Ok(())
     }
 
-    async fn get_or_init_state(&self) -> Result<ProjectionState> {
-        match self
-            .state_repository
-            .get_state(&self.config.projection.name)
-            .await?
-        {
-            Some(state) => {
-                info!(
-                    "Resuming projection from position {}",
-                    state.last_processed_position
-                );
-                Ok(state)
-            },
-            None => {
-                info!("Initializing new projection");
-                let state = ProjectionState::new(self.config.projection.name.clone());
-
-                let mut tx = self.read_repository.begin_transaction().await?;
-                self.state_repository.save_state(&mut tx, &state).await?;
-                tx.commit().await?;
+    async fn last_processed_position(&self) -> Result<i64> {
+        let checkpoint = self
+            .checkpoints
+            .load(&self.config.projection.name)
+            .await
+            .map_err(|e| ProjectionError::Checkpoint(e.to_string()))?;
 
-                Ok(state)
-            },
-        }
+        Ok(checkpoint.map_or(0, |checkpoint| checkpoint.position))
     }
 
-    async fn process_batch(&self, state: &mut ProjectionState) -> Result<usize> {
-        let events = self
-            .event_subscriber
-            .fetch_events(
-                state.last_processed_position,
-                self.config.event_store.batch_size,
-            )
-            .await?;
-
-        if events.is_empty() {
-            return Ok(0);
-        }
-
-        let mut tx = self.read_repository.begin_transaction().await?;
-        let mut events_processed = 0;
-
-        for event in &events {
-            self.event_handler.handle_event(&mut tx, event).await?;
-            events_processed += 1;
-
-            // チェックポイント間隔に達したら保存
-            if events_processed % self.config.projection.checkpoint_interval == 0 {
-                let checkpoint = ProjectionCheckpoint::new(
-                    self.config.projection.name.clone(),
-                    event.position,
-                    Some(event.event_id),
-                    events_processed as i32,
-                );
-                self.state_repository
-                    .save_checkpoint(&mut tx, &checkpoint)
-                    .await?;
-            }
-        }
-
-        // 最後のイベント情報で状態を更新
-        if let Some(last_event) = events.last() {
-            state.update_position(last_event.position, Some(last_event.event_id));
-            self.state_repository.save_state(&mut tx, state).await?;
+    /// 保留期限を過ぎた集約を補完してから、新しいイベントがなくなるまで処理する
+    ///
+    /// 失敗した場合は失敗したイベントの手前までを確定し、次回はそこから再開する
+    async fn process_batch(&self) -> Result<usize> {
+        self.projection.release_expired().await?;
+
+        match self.runner.catch_up().await {
+            Ok(events_processed) => Ok(events_processed),
+            Err(e) => {
+                self.projection.discard().await;
+                Err(ProjectionError::EventProcessing(e.to_string()))
+            },
         }
-
-        tx.commit().await?;
-        Ok(events_processed)
     }
 }
 
 #[async_trait::async_trait]
-impl<E, R, P> EventProcessorUseCase for EventProcessor<E, R, P>
+impl<E, R, P, O> EventProcessorUseCase for EventProcessor<E, R, P, O>
 where
-    E: EventSubscriber,
-    R: ReadModelRepository + Clone,
+    E: EventSubscriber + AggregateEventSource + 'static,
+    R: ReadModelRepository + Clone + 'static,
     P: ProjectionStateRepository,
+    O: EventOrderingRepository + 'static,
 {
     async fn start_processing(&self) -> Result<()> {
         self.process_events().await
@@ -193,12 +188,16 @@ where
             .get_state(&self.config.projection.name)
             .await?
             .unwrap_or_else(|| ProjectionState::new(self.config.projection.name.clone()));
+        let metrics = self.sequencer.metrics();
 
         Ok(ProcessorStatus {
             is_running:              *self.is_running.read().await,
-            last_processed_position: state.last_processed_position,
+            last_processed_position: self.last_processed_position().await?,
             events_processed_total:  0, // TODO: 実装
             error_count:             state.error_count as u32,
+            buffered_events_total:   metrics.buffered(),
+            gap_filled_events_total: metrics.gap_filled(),
+            expired_holds_total:     metrics.expired_holds(),
         })
     }
 }
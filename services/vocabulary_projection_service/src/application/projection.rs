@@ -0,0 +1,164 @@
+//! 語彙の Read Model を更新するプロジェクション
+//!
+//! イベントの読み込みとチェックポイントの保存は `shared_cqrs` の
+//! [`ProjectionRunner`](shared_cqrs::ProjectionRunner) に任せる。
+//!
+//! バッチのイベントは1つのトランザクションで適用し、[`Projection::flush`]
+//! でコミットする。イベントごとにセーブポイントを置くため、失敗したイベントの
+//! 書き込みだけを取り消し、それより前のイベントはチェックポイントとともに
+//! 確定する。チェックポイントの保存前に停止した場合は同じイベントを読み直すが、
+//! 適用済みのバージョンは [`EventSequencer`] が読み飛ばす。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shared_cqrs::{CqrsError, EventFeed, PositionedEvent, Projection};
+use sqlx::{Acquire, Postgres, Transaction};
+use tokio::sync::Mutex;
+
+use crate::{
+    application::{event_handlers::EventHandler, ordering::EventSequencer},
+    domain::events::StoredEvent,
+    error::Result,
+    ports::outbound::{
+        AggregateEventSource,
+        EventOrderingRepository,
+        EventSubscriber,
+        ReadModelRepository,
+    },
+};
+
+/// 語彙のプロジェクション
+pub struct VocabularyProjector<R, O, S>
+where
+    R: ReadModelRepository,
+    O: EventOrderingRepository,
+    S: AggregateEventSource,
+{
+    name:            String,
+    event_handler:   EventHandler<R>,
+    sequencer:       Arc<EventSequencer<O, S>>,
+    read_repository: Arc<R>,
+    /// 適用中のバッチのトランザクション
+    batch:           Mutex<Option<Transaction<'static, Postgres>>>,
+}
+
+impl<R, O, S> VocabularyProjector<R, O, S>
+where
+    R: ReadModelRepository + Clone,
+    O: EventOrderingRepository,
+    S: AggregateEventSource,
+{
+    pub fn new(
+        name: String,
+        read_repository: Arc<R>,
+        sequencer: Arc<EventSequencer<O, S>>,
+    ) -> Self {
+        Self {
+            name,
+            event_handler: EventHandler::new(read_repository.as_ref().clone()),
+            sequencer,
+            read_repository,
+            batch: Mutex::new(None),
+        }
+    }
+
+    /// 保留期限を過ぎた集約の欠番を Event Store から補完して適用する
+    pub async fn release_expired(&self) -> Result<()> {
+        let mut tx = self.read_repository.begin_transaction().await?;
+        for ready in self.sequencer.release_expired(&mut tx).await? {
+            self.event_handler.handle_event(&mut tx, &ready).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// 確定していないバッチの書き込みを取り消す（処理を止めたときに呼ぶ）
+    pub async fn discard(&self) {
+        self.batch.lock().await.take();
+    }
+
+    /// イベントを集約のバージョン順に適用する（欠番がある場合は保留される）
+    async fn admit(&self, tx: &mut Transaction<'_, Postgres>, event: &StoredEvent) -> Result<()> {
+        for ready in self.sequencer.admit(tx, event).await? {
+            self.event_handler.handle_event(tx, &ready).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<R, O, S> Projection for VocabularyProjector<R, O, S>
+where
+    R: ReadModelRepository + Clone,
+    O: EventOrderingRepository,
+    S: AggregateEventSource,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn apply(&self, event: &PositionedEvent) -> shared_cqrs::Result<()> {
+        let event = StoredEvent::from_positioned(event);
+        let mut batch = self.batch.lock().await;
+        if batch.is_none() {
+            *batch = Some(
+                self.read_repository
+                    .begin_transaction()
+                    .await
+                    .map_err(CqrsError::handler)?,
+            );
+        }
+        let Some(tx) = batch.as_mut() else {
+            return Err(CqrsError::Internal(
+                "batch transaction is not open".to_string(),
+            ));
+        };
+
+        // 失敗した場合はこのイベントの書き込みだけを取り消す
+        let mut savepoint = tx.begin().await?;
+        self.admit(&mut savepoint, &event)
+            .await
+            .map_err(CqrsError::handler)?;
+        savepoint.commit().await?;
+        Ok(())
+    }
+
+    async fn flush(&self) -> shared_cqrs::Result<()> {
+        if let Some(tx) = self.batch.lock().await.take() {
+            tx.commit().await?;
+        }
+        Ok(())
+    }
+}
+
+/// [`EventSubscriber`] から位置順に読み込む
+pub struct SubscriberFeed<E: EventSubscriber> {
+    subscriber: Arc<E>,
+}
+
+impl<E: EventSubscriber> SubscriberFeed<E> {
+    pub fn new(subscriber: Arc<E>) -> Self {
+        Self { subscriber }
+    }
+}
+
+#[async_trait]
+impl<E: EventSubscriber> EventFeed for SubscriberFeed<E> {
+    async fn read_after(
+        &self,
+        position: i64,
+        limit: usize,
+    ) -> shared_cqrs::Result<Vec<PositionedEvent>> {
+        self.subscriber
+            .fetch_events(position, limit)
+            .await
+            .and_then(|events| {
+                events
+                    .into_iter()
+                    .map(StoredEvent::into_positioned)
+                    .collect()
+            })
+            .map_err(CqrsError::handler)
+    }
+}
@@ -0,0 +1,52 @@
+//! サービスの組み立て
+//!
+//! イベントの取得元だけを差し替えられるようにし、単独のサービスとしても
+//! 統合バイナリ（effect_monolith）の中でも同じ構成で動かす。
+
+use std::sync::Arc;
+
+use shared_cqrs::PostgresCheckpointStore;
+use sqlx::PgPool;
+
+use crate::{
+    application::processor::EventProcessor,
+    config::Config,
+    infrastructure::repositories::{
+        postgres_event_ordering::PostgresEventOrderingRepository,
+        postgres_projection_state::PostgresProjectionStateRepository,
+        postgres_read_model::PostgresReadModelRepository,
+    },
+    ports::outbound::{AggregateEventSource, EventSubscriber},
+};
+
+/// PostgreSQL の Read Model に投影するイベントプロセッサー
+pub type PostgresEventProcessor<E> = EventProcessor<
+    E,
+    PostgresReadModelRepository,
+    PostgresProjectionStateRepository,
+    PostgresEventOrderingRepository,
+>;
+
+/// Read Model のプールとイベントの取得元からプロセッサーを組み立てる
+pub fn build_processor<E>(
+    config: Config,
+    pool: PgPool,
+    event_source: E,
+) -> PostgresEventProcessor<E>
+where
+    E: EventSubscriber + AggregateEventSource + 'static,
+{
+    let read_repository = PostgresReadModelRepository::new(pool.clone());
+    let state_repository = PostgresProjectionStateRepository::new(pool.clone());
+    let ordering_repository = PostgresEventOrderingRepository::new(pool.clone());
+    let checkpoints = Arc::new(PostgresCheckpointStore::new(pool));
+
+    EventProcessor::new(
+        config,
+        event_source,
+        read_repository,
+        state_repository,
+        ordering_repository,
+        checkpoints,
+    )
+}
@@ -26,9 +26,12 @@ pub struct EventStoreConfig {
 /// プロジェクション設定
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectionConfig {
-    pub name:                String,
-    pub checkpoint_interval: usize,
-    pub error_retry_limit:   u32,
+    pub name:              String,
+    /// 適用に失敗したイベントの試行回数（超えたら処理を止め、
+    /// 次の間隔で再開する）
+    pub error_retry_limit: u32,
+    /// 欠番待ちの保留時間の上限（超えたら Event Store から補完）
+    pub max_hold_ms:       u64,
 }
 
 impl Config {
@@ -56,9 +59,12 @@ impl Config {
                     .unwrap_or(1000),
             },
             projection:  ProjectionConfig {
-                name:                "vocabulary_projection".to_string(),
-                checkpoint_interval: 100,
-                error_retry_limit:   3,
+                name:              "vocabulary_projection".to_string(),
+                error_retry_limit: 3,
+                max_hold_ms:       std::env::var("PROJECTION_MAX_HOLD_MS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(30_000),
             },
         })
     }
@@ -2,8 +2,11 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shared_cqrs::PositionedEvent;
 use uuid::Uuid;
 
+use crate::error::Result;
+
 /// Event Store から取得したイベント
 #[derive(Debug, Clone)]
 pub struct StoredEvent {
@@ -16,6 +19,44 @@ pub struct StoredEvent {
     pub occurred_at:       DateTime<Utc>,
 }
 
+/// プロジェクションの実行基盤（`shared_cqrs`）に渡すときの集約の種類
+const AGGREGATE_TYPE: &str = "vocabulary";
+
+impl StoredEvent {
+    /// プロジェクションの実行基盤（`shared_cqrs`）に渡す形に変換する
+    pub fn into_positioned(self) -> Result<PositionedEvent> {
+        Ok(PositionedEvent {
+            position: self.position,
+            event:    shared_event_store::StoredEvent {
+                event_id:       self.event_id,
+                tenant_id:      None,
+                aggregate_id:   self.aggregate_id,
+                aggregate_type: AGGREGATE_TYPE.to_string(),
+                event_type:     self.event_type,
+                event_version:  u32::try_from(self.aggregate_version).unwrap_or(u32::MAX),
+                event_data:     serde_json::from_str(&self.event_data)?,
+                metadata:       None,
+                occurred_at:    self.occurred_at,
+                created_at:     self.occurred_at,
+            },
+        })
+    }
+
+    /// [`into_positioned`](Self::into_positioned) で変換したイベントを元に戻す
+    pub fn from_positioned(positioned: &PositionedEvent) -> Self {
+        let event = &positioned.event;
+        Self {
+            position:          positioned.position,
+            event_id:          event.event_id,
+            aggregate_id:      event.aggregate_id,
+            aggregate_version: i64::from(event.event_version),
+            event_type:        event.event_type.clone(),
+            event_data:        event.event_data.to_string(),
+            occurred_at:       event.occurred_at,
+        }
+    }
+}
+
 /// イベントのメタデータ
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventMetadata {
@@ -34,3 +75,49 @@ pub struct EnrichedData {
     pub cefr_level:        Option<String>,
     pub frequency_rank:    Option<i32>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_round_trips_through_the_projection_runner_format() {
+        let event = StoredEvent {
+            position:          42,
+            event_id:          Uuid::new_v4(),
+            aggregate_id:      Uuid::new_v4(),
+            aggregate_version: 3,
+            event_type:        "VocabularyItemPublished".to_string(),
+            event_data:        r#"{"item_id":"abc","version":3}"#.to_string(),
+            occurred_at:       "2025-10-01T12:00:00Z".parse().unwrap(),
+        };
+
+        let positioned = event.clone().into_positioned().unwrap();
+        assert_eq!(positioned.position, 42);
+        assert_eq!(positioned.event.event_data["version"], 3);
+
+        let restored = StoredEvent::from_positioned(&positioned);
+        assert_eq!(restored.event_id, event.event_id);
+        assert_eq!(restored.aggregate_id, event.aggregate_id);
+        assert_eq!(restored.aggregate_version, 3);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&restored.event_data).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&event.event_data).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_malformed_event_data_is_rejected() {
+        let event = StoredEvent {
+            position:          1,
+            event_id:          Uuid::new_v4(),
+            aggregate_id:      Uuid::new_v4(),
+            aggregate_version: 1,
+            event_type:        "VocabularyEntryCreated".to_string(),
+            event_data:        "not json".to_string(),
+            occurred_at:       "2025-10-01T12:00:00Z".parse().unwrap(),
+        };
+
+        assert!(event.into_positioned().is_err());
+    }
+}
@@ -0,0 +1,240 @@
+//! 集約ごとのイベント順序制御
+//!
+//! EventBus 経由では再配信により v5 が v4 より先に届くことがある。
+//! 集約ごとに適用済みバージョンを追跡し、欠番のあるイベントは保留して、
+//! 欠番が埋まった時点で順番に適用する。
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::domain::events::StoredEvent;
+
+/// 欠番待ちで保留されたイベント
+#[derive(Debug, Clone)]
+pub struct PendingEvent {
+    pub event:       StoredEvent,
+    pub buffered_at: DateTime<Utc>,
+}
+
+/// イベントの受け入れ結果
+#[derive(Debug)]
+pub enum Admission {
+    /// 順番通りに届いた。続けて適用可能になった保留イベントを含め、
+    /// バージョン順に適用する
+    Apply(Vec<StoredEvent>),
+    /// 欠番があるため保留した
+    Buffered,
+    /// 適用済みまたは保留済み（再配信）のため無視する
+    Duplicate,
+}
+
+/// 集約ごとの適用状況
+#[derive(Debug, Clone)]
+pub struct AggregateCursor {
+    pub aggregate_id:         Uuid,
+    pub last_applied_version: i64,
+    /// バージョン順に並んだ保留イベント
+    pub pending:              Vec<PendingEvent>,
+}
+
+impl AggregateCursor {
+    pub fn new(
+        aggregate_id: Uuid,
+        last_applied_version: i64,
+        mut pending: Vec<PendingEvent>,
+    ) -> Self {
+        pending.sort_by_key(|p| p.event.aggregate_version);
+        Self {
+            aggregate_id,
+            last_applied_version,
+            pending,
+        }
+    }
+
+    /// イベントを受け入れ、適用するか保留するかを決める
+    pub fn admit(&mut self, event: StoredEvent, now: DateTime<Utc>) -> Admission {
+        let version = event.aggregate_version;
+        if version <= self.last_applied_version || self.is_pending(version) {
+            return Admission::Duplicate;
+        }
+
+        if version == self.last_applied_version + 1 {
+            self.last_applied_version = version;
+            let mut ready = vec![event];
+            ready.extend(self.drain_ready());
+            return Admission::Apply(ready);
+        }
+
+        let index = self
+            .pending
+            .partition_point(|p| p.event.aggregate_version < version);
+        self.pending.insert(
+            index,
+            PendingEvent {
+                event,
+                buffered_at: now,
+            },
+        );
+        Admission::Buffered
+    }
+
+    /// 最も古い保留イベントが保留期限を過ぎているかどうか
+    pub fn hold_expired(&self, now: DateTime<Utc>, max_hold: Duration) -> bool {
+        self.pending
+            .iter()
+            .map(|p| p.buffered_at)
+            .min()
+            .is_some_and(|oldest| oldest + max_hold <= now)
+    }
+
+    /// 欠けているバージョンの範囲（両端を含む）
+    pub fn missing_range(&self) -> Option<(i64, i64)> {
+        let next = self.pending.first()?.event.aggregate_version;
+        (next > self.last_applied_version + 1).then_some((self.last_applied_version + 1, next - 1))
+    }
+
+    /// 取得したイベントで欠番を埋め、適用可能になったイベントを返す
+    ///
+    /// 取得したイベントが連続していない場合は、連続している部分までを適用する
+    pub fn fill(&mut self, mut fetched: Vec<StoredEvent>) -> Vec<StoredEvent> {
+        fetched.sort_by_key(|e| e.aggregate_version);
+
+        let mut ready = Vec::new();
+        for event in fetched {
+            if event.aggregate_version == self.last_applied_version + 1 {
+                self.last_applied_version = event.aggregate_version;
+                ready.push(event);
+            }
+        }
+        ready.extend(self.drain_ready());
+        ready
+    }
+
+    fn is_pending(&self, version: i64) -> bool {
+        self.pending
+            .iter()
+            .any(|p| p.event.aggregate_version == version)
+    }
+
+    /// 先頭から連続している保留イベントを取り出す
+    fn drain_ready(&mut self) -> Vec<StoredEvent> {
+        let count = self
+            .pending
+            .iter()
+            .zip(self.last_applied_version + 1..)
+            .take_while(|(p, expected)| p.event.aggregate_version == *expected)
+            .count();
+
+        let ready: Vec<StoredEvent> = self.pending.drain(..count).map(|p| p.event).collect();
+        if let Some(last) = ready.last() {
+            self.last_applied_version = last.aggregate_version;
+        }
+        ready
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(aggregate_id: Uuid, version: i64) -> StoredEvent {
+        StoredEvent {
+            position: version,
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            aggregate_version: version,
+            event_type: "VocabularyItemPublished".to_string(),
+            event_data: "{}".to_string(),
+            occurred_at: Utc::now(),
+        }
+    }
+
+    fn versions(events: &[StoredEvent]) -> Vec<i64> {
+        events.iter().map(|e| e.aggregate_version).collect()
+    }
+
+    #[test]
+    fn test_in_order_events_pass_through() {
+        let id = Uuid::new_v4();
+        let mut cursor = AggregateCursor::new(id, 0, Vec::new());
+
+        for version in 1..=3 {
+            match cursor.admit(event(id, version), Utc::now()) {
+                Admission::Apply(events) => assert_eq!(versions(&events), vec![version]),
+                other => panic!("unexpected admission: {other:?}"),
+            }
+        }
+        assert_eq!(cursor.last_applied_version, 3);
+        assert!(cursor.pending.is_empty());
+    }
+
+    #[test]
+    fn test_out_of_order_events_are_buffered_and_released_in_order() {
+        let id = Uuid::new_v4();
+        let mut cursor = AggregateCursor::new(id, 3, Vec::new());
+
+        assert!(matches!(
+            cursor.admit(event(id, 6), Utc::now()),
+            Admission::Buffered
+        ));
+        assert!(matches!(
+            cursor.admit(event(id, 5), Utc::now()),
+            Admission::Buffered
+        ));
+        assert_eq!(cursor.missing_range(), Some((4, 4)));
+
+        match cursor.admit(event(id, 4), Utc::now()) {
+            Admission::Apply(events) => assert_eq!(versions(&events), vec![4, 5, 6]),
+            other => panic!("unexpected admission: {other:?}"),
+        }
+        assert_eq!(cursor.last_applied_version, 6);
+        assert!(cursor.pending.is_empty());
+    }
+
+    #[test]
+    fn test_redelivered_events_are_ignored() {
+        let id = Uuid::new_v4();
+        let mut cursor = AggregateCursor::new(id, 2, Vec::new());
+
+        assert!(matches!(
+            cursor.admit(event(id, 2), Utc::now()),
+            Admission::Duplicate
+        ));
+        assert!(matches!(
+            cursor.admit(event(id, 4), Utc::now()),
+            Admission::Buffered
+        ));
+        assert!(matches!(
+            cursor.admit(event(id, 4), Utc::now()),
+            Admission::Duplicate
+        ));
+        assert_eq!(cursor.pending.len(), 1);
+    }
+
+    #[test]
+    fn test_hold_expires_after_max_hold() {
+        let id = Uuid::new_v4();
+        let buffered_at = Utc::now();
+        let mut cursor = AggregateCursor::new(id, 1, Vec::new());
+        cursor.admit(event(id, 3), buffered_at);
+
+        let max_hold = Duration::seconds(30);
+        assert!(!cursor.hold_expired(buffered_at + Duration::seconds(29), max_hold));
+        assert!(cursor.hold_expired(buffered_at + Duration::seconds(30), max_hold));
+        assert!(!AggregateCursor::new(id, 1, Vec::new()).hold_expired(buffered_at, max_hold));
+    }
+
+    #[test]
+    fn test_fill_applies_fetched_events_before_pending() {
+        let id = Uuid::new_v4();
+        let mut cursor = AggregateCursor::new(id, 1, Vec::new());
+        cursor.admit(event(id, 5), Utc::now());
+        assert_eq!(cursor.missing_range(), Some((2, 4)));
+
+        // 取得結果の順序は問わず、既に適用済みのバージョンは無視する
+        let ready = cursor.fill(vec![event(id, 3), event(id, 1), event(id, 4), event(id, 2)]);
+        assert_eq!(versions(&ready), vec![2, 3, 4, 5]);
+        assert_eq!(cursor.last_applied_version, 5);
+        assert!(cursor.missing_range().is_none());
+    }
+}
@@ -22,6 +22,12 @@ pub struct VocabularyItemProjection {
     pub entry_id:           Uuid,
     pub spelling:           String,
     pub disambiguation:     Option<String>,
+    /// 使用域（formal / informal / slang など）
+    pub register:           Option<String>,
+    /// 専門分野（medical / legal / general など）
+    pub domain:             Option<String>,
+    /// 使用上の注意（学習時に解答とともに提示する）
+    pub usage_notes:        Option<String>,
     pub part_of_speech:     Option<String>,
     pub definition:         Option<String>,
     pub ipa_pronunciation:  Option<String>,
@@ -46,68 +52,73 @@ pub struct VocabularyExampleProjection {
     pub created_at:  DateTime<Utc>,
 }
 
-/// プロジェクション状態
+/// 外部ソースからインポートされたデータの出典 Read Model
 #[derive(Debug, Clone)]
-pub struct ProjectionState {
-    pub projection_name:         String,
-    pub last_processed_position: i64,
-    pub last_processed_event_id: Option<Uuid>,
-    pub last_processed_at:       Option<DateTime<Utc>>,
-    pub error_count:             i32,
-    pub last_error:              Option<String>,
-    pub last_error_at:           Option<DateTime<Utc>>,
+pub struct ProvenanceProjection {
+    /// 出典の対象（Item ID または例文 ID）
+    pub target_id:       Uuid,
+    pub item_id:         Uuid,
+    pub target_kind:     ProvenanceTarget,
+    pub source_name:     String,
+    pub license:         String,
+    pub attribution:     Option<String>,
+    /// 公開前にレビューが必要かどうか（例文のみ）
+    pub requires_review: bool,
 }
 
-impl ProjectionState {
-    pub fn new(name: String) -> Self {
-        Self {
-            projection_name:         name,
-            last_processed_position: 0,
-            last_processed_event_id: None,
-            last_processed_at:       None,
-            error_count:             0,
-            last_error:              None,
-            last_error_at:           None,
+/// 出典の対象種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvenanceTarget {
+    Item,
+    Example,
+}
+
+impl ProvenanceTarget {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Item => "item",
+            Self::Example => "example",
         }
     }
+}
 
-    pub fn update_position(&mut self, position: i64, event_id: Option<Uuid>) {
-        self.last_processed_position = position;
-        self.last_processed_event_id = event_id;
-        self.last_processed_at = Some(Utc::now());
-        self.error_count = 0; // 成功時はエラーカウントをリセット
-    }
+/// 学習コンテキストとして投影する VocabularyItem のフィールド
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LearningContextField {
+    Register,
+    Domain,
+    UsageNotes,
+}
 
-    pub fn record_error(&mut self, error: String) {
-        self.error_count += 1;
-        self.last_error = Some(error);
-        self.last_error_at = Some(Utc::now());
+impl LearningContextField {
+    /// `VocabularyItemFieldUpdated` の `field_path` から判定する
+    pub fn from_field_path(field_path: &str) -> Option<Self> {
+        match field_path {
+            "register" => Some(Self::Register),
+            "domain" => Some(Self::Domain),
+            "usage_notes" => Some(Self::UsageNotes),
+            _ => None,
+        }
     }
 }
 
-/// チェックポイント
+/// プロジェクションのエラー状態
 #[derive(Debug, Clone)]
-pub struct ProjectionCheckpoint {
-    pub projection_name:  String,
-    pub position:         i64,
-    pub event_id:         Option<Uuid>,
-    pub events_processed: i32,
-    pub created_at:       DateTime<Utc>,
+pub struct ProjectionState {
+    pub projection_name: String,
+    /// 失敗した回数の累計
+    pub error_count:     i32,
+    pub last_error:      Option<String>,
+    pub last_error_at:   Option<DateTime<Utc>>,
 }
 
-impl ProjectionCheckpoint {
-    pub fn new(
-        projection_name: String,
-        position: i64,
-        event_id: Option<Uuid>,
-        events_processed: i32,
-    ) -> Self {
+impl ProjectionState {
+    pub fn new(name: String) -> Self {
         Self {
-            projection_name,
-            position,
-            event_id,
-            events_processed,
-            created_at: Utc::now(),
+            projection_name: name,
+            error_count:     0,
+            last_error:      None,
+            last_error_at:   None,
         }
     }
 }
@@ -1,11 +1,12 @@
 //! Event Store サブスクライバー実装
 
 use async_trait::async_trait;
+use uuid::Uuid;
 
 use crate::{
     domain::events::StoredEvent,
     error::Result,
-    ports::outbound::{EventStream, EventSubscriber},
+    ports::outbound::{AggregateEventSource, EventStream, EventSubscriber},
 };
 
 /// Event Store サブスクライバー（モック実装）
@@ -38,3 +39,17 @@ impl EventSubscriber for EventStoreSubscriber {
         Ok(EventStream {})
     }
 }
+
+#[async_trait]
+impl AggregateEventSource for EventStoreSubscriber {
+    async fn fetch_aggregate_events(
+        &self,
+        _aggregate_id: Uuid,
+        _from_version: i64,
+        _to_version: i64,
+    ) -> Result<Vec<StoredEvent>> {
+        // TODO: Event Store Service の GetEvents（集約・バージョン範囲指定）を呼び出す
+        // 現在は空のベクタを返す（欠番は補完されず保留が継続する）
+        Ok(vec![])
+    }
+}
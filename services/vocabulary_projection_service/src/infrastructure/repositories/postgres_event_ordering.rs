@@ -0,0 +1,180 @@
+//! PostgreSQL イベント順序制御リポジトリ実装
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        events::StoredEvent,
+        ordering::{AggregateCursor, PendingEvent},
+    },
+    error::Result,
+    ports::outbound::EventOrderingRepository,
+};
+
+/// PostgreSQL イベント順序制御リポジトリ
+pub struct PostgresEventOrderingRepository {
+    pool: PgPool,
+}
+
+impl PostgresEventOrderingRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EventOrderingRepository for PostgresEventOrderingRepository {
+    async fn load_cursor(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        projection_name: &str,
+        aggregate_id: Uuid,
+    ) -> Result<AggregateCursor> {
+        let last_applied_version: Option<i64> = sqlx::query_scalar(
+            r#"
+            SELECT last_applied_version
+            FROM projection_aggregate_versions
+            WHERE projection_name = $1 AND aggregate_id = $2
+            FOR UPDATE
+            "#,
+        )
+        .bind(projection_name)
+        .bind(aggregate_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT position, event_id, aggregate_version, event_type,
+                   event_data, occurred_at, buffered_at
+            FROM projection_pending_events
+            WHERE projection_name = $1 AND aggregate_id = $2
+            ORDER BY aggregate_version
+            "#,
+        )
+        .bind(projection_name)
+        .bind(aggregate_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        let pending = rows
+            .into_iter()
+            .map(|row| PendingEvent {
+                event:       StoredEvent {
+                    position: row.get("position"),
+                    event_id: row.get("event_id"),
+                    aggregate_id,
+                    aggregate_version: row.get("aggregate_version"),
+                    event_type: row.get("event_type"),
+                    event_data: row.get("event_data"),
+                    occurred_at: row.get("occurred_at"),
+                },
+                buffered_at: row.get("buffered_at"),
+            })
+            .collect();
+
+        Ok(AggregateCursor::new(
+            aggregate_id,
+            last_applied_version.unwrap_or(0),
+            pending,
+        ))
+    }
+
+    async fn advance(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        projection_name: &str,
+        aggregate_id: Uuid,
+        version: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO projection_aggregate_versions (
+                projection_name, aggregate_id, last_applied_version, updated_at
+            )
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (projection_name, aggregate_id) DO UPDATE SET
+                last_applied_version = EXCLUDED.last_applied_version,
+                updated_at = NOW()
+            WHERE projection_aggregate_versions.last_applied_version < EXCLUDED.last_applied_version
+            "#,
+        )
+        .bind(projection_name)
+        .bind(aggregate_id)
+        .bind(version)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM projection_pending_events
+            WHERE projection_name = $1 AND aggregate_id = $2 AND aggregate_version <= $3
+            "#,
+        )
+        .bind(projection_name)
+        .bind(aggregate_id)
+        .bind(version)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn buffer_event(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        projection_name: &str,
+        pending: &PendingEvent,
+    ) -> Result<()> {
+        let event = &pending.event;
+        sqlx::query(
+            r#"
+            INSERT INTO projection_pending_events (
+                projection_name, aggregate_id, aggregate_version, position,
+                event_id, event_type, event_data, occurred_at, buffered_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (projection_name, aggregate_id, aggregate_version) DO NOTHING
+            "#,
+        )
+        .bind(projection_name)
+        .bind(event.aggregate_id)
+        .bind(event.aggregate_version)
+        .bind(event.position)
+        .bind(event.event_id)
+        .bind(&event.event_type)
+        .bind(&event.event_data)
+        .bind(event.occurred_at)
+        .bind(pending.buffered_at)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_expired_aggregates(
+        &self,
+        projection_name: &str,
+        buffered_before: DateTime<Utc>,
+    ) -> Result<Vec<Uuid>> {
+        let aggregate_ids = sqlx::query_scalar(
+            r#"
+            SELECT aggregate_id
+            FROM projection_pending_events
+            WHERE projection_name = $1
+            GROUP BY aggregate_id
+            HAVING MIN(buffered_at) <= $2
+            ORDER BY MIN(buffered_at)
+            "#,
+        )
+        .bind(projection_name)
+        .bind(buffered_before)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(aggregate_ids)
+    }
+}
@@ -1,11 +1,14 @@
 //! PostgreSQL Read Model リポジトリ実装
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
 use crate::{
     domain::projections::{
+        LearningContextField,
+        ProvenanceProjection,
         VocabularyEntryProjection,
         VocabularyExampleProjection,
         VocabularyItemProjection,
@@ -70,16 +73,19 @@ impl ReadModelRepository for PostgresReadModelRepository {
         sqlx::query!(
             r#"
             INSERT INTO vocabulary_items_read (
-                item_id, entry_id, spelling, disambiguation,
+                item_id, entry_id, spelling, disambiguation, register, domain, usage_notes,
                 part_of_speech, definition, ipa_pronunciation,
                 cefr_level, frequency_rank, is_published, is_deleted,
                 example_count, created_at, updated_at, last_event_version
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
             ON CONFLICT (item_id) DO UPDATE SET
                 entry_id = EXCLUDED.entry_id,
                 spelling = EXCLUDED.spelling,
                 disambiguation = EXCLUDED.disambiguation,
+                register = EXCLUDED.register,
+                domain = EXCLUDED.domain,
+                usage_notes = EXCLUDED.usage_notes,
                 part_of_speech = EXCLUDED.part_of_speech,
                 definition = EXCLUDED.definition,
                 ipa_pronunciation = EXCLUDED.ipa_pronunciation,
@@ -96,6 +102,9 @@ impl ReadModelRepository for PostgresReadModelRepository {
             item.entry_id,
             item.spelling,
             item.disambiguation,
+            item.register,
+            item.domain,
+            item.usage_notes,
             item.part_of_speech,
             item.definition,
             item.ipa_pronunciation,
@@ -139,6 +148,34 @@ impl ReadModelRepository for PostgresReadModelRepository {
         Ok(())
     }
 
+    async fn save_provenance(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        provenance: &ProvenanceProjection,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO vocabulary_provenance_read (
+                target_id, item_id, target_kind, source_name,
+                license, attribution, requires_review
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (target_id) DO NOTHING
+            "#,
+        )
+        .bind(provenance.target_id)
+        .bind(provenance.item_id)
+        .bind(provenance.target_kind.as_str())
+        .bind(&provenance.source_name)
+        .bind(&provenance.license)
+        .bind(&provenance.attribution)
+        .bind(provenance.requires_review)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
     async fn update_item_published(
         &self,
         tx: &mut Transaction<'_, Postgres>,
@@ -222,6 +259,68 @@ impl ReadModelRepository for PostgresReadModelRepository {
         Ok(())
     }
 
+    async fn update_item_learning_context(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        item_id: Uuid,
+        field: LearningContextField,
+        value: Option<String>,
+        version: i64,
+    ) -> Result<()> {
+        match field {
+            LearningContextField::Register => {
+                sqlx::query!(
+                    r#"
+                    UPDATE vocabulary_items_read
+                    SET register = $2,
+                        updated_at = NOW(),
+                        last_event_version = $3
+                    WHERE item_id = $1 AND last_event_version < $3
+                    "#,
+                    item_id,
+                    value,
+                    version
+                )
+                .execute(&mut **tx)
+                .await?;
+            },
+            LearningContextField::Domain => {
+                sqlx::query!(
+                    r#"
+                    UPDATE vocabulary_items_read
+                    SET domain = $2,
+                        updated_at = NOW(),
+                        last_event_version = $3
+                    WHERE item_id = $1 AND last_event_version < $3
+                    "#,
+                    item_id,
+                    value,
+                    version
+                )
+                .execute(&mut **tx)
+                .await?;
+            },
+            LearningContextField::UsageNotes => {
+                sqlx::query!(
+                    r#"
+                    UPDATE vocabulary_items_read
+                    SET usage_notes = $2,
+                        updated_at = NOW(),
+                        last_event_version = $3
+                    WHERE item_id = $1 AND last_event_version < $3
+                    "#,
+                    item_id,
+                    value,
+                    version
+                )
+                .execute(&mut **tx)
+                .await?;
+            },
+        }
+
+        Ok(())
+    }
+
     async fn update_entry_primary_item(
         &self,
         tx: &mut Transaction<'_, Postgres>,
@@ -291,7 +390,50 @@ impl ReadModelRepository for PostgresReadModelRepository {
         Ok(())
     }
 
-    async fn begin_transaction(&self) -> Result<Transaction<'_, Postgres>> {
+    async fn add_collection_item(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        collection_id: Uuid,
+        item_id: Uuid,
+        added_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO vocabulary_collection_items_read (collection_id, item_id, added_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (collection_id, item_id) DO NOTHING
+            "#,
+        )
+        .bind(collection_id)
+        .bind(item_id)
+        .bind(added_at)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn remove_collection_item(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        collection_id: Uuid,
+        item_id: Uuid,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM vocabulary_collection_items_read
+            WHERE collection_id = $1 AND item_id = $2
+            "#,
+        )
+        .bind(collection_id)
+        .bind(item_id)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn begin_transaction(&self) -> Result<Transaction<'static, Postgres>> {
         Ok(self.pool.begin().await?)
     }
 }
@@ -3,12 +3,17 @@
 //! Event Store からイベントを購読し、Read Model を構築するサービス
 //! ヘキサゴナルアーキテクチャを採用
 
+pub mod bootstrap;
 pub mod config;
 pub mod error;
 
+/// Read Model のマイグレーション
+pub static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("./migrations");
+
 // ドメイン層
 pub mod domain {
     pub mod events;
+    pub mod ordering;
     pub mod projections;
 }
 
@@ -21,12 +26,15 @@ pub mod ports {
 // アプリケーション層（ユースケース）
 pub mod application {
     pub mod event_handlers;
+    pub mod ordering;
     pub mod processor;
+    pub mod projection;
 }
 
 // インフラストラクチャ層（技術的実装）
 pub mod infrastructure {
     pub mod repositories {
+        pub mod postgres_event_ordering;
         pub mod postgres_projection_state;
         pub mod postgres_read_model;
     }
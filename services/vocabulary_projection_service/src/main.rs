@@ -2,15 +2,10 @@ use anyhow::Result;
 use sqlx::postgres::PgPoolOptions;
 use tracing::{error, info};
 use vocabulary_projection_service::{
-    application::processor::EventProcessor,
+    MIGRATOR,
+    bootstrap::build_processor,
     config::Config,
-    infrastructure::{
-        adapters::event_store_subscriber::EventStoreSubscriber,
-        repositories::{
-            postgres_projection_state::PostgresProjectionStateRepository,
-            postgres_read_model::PostgresReadModelRepository,
-        },
-    },
+    infrastructure::adapters::event_store_subscriber::EventStoreSubscriber,
     ports::inbound::EventProcessorUseCase,
 };
 
@@ -38,18 +33,13 @@ async fn main() -> Result<()> {
     info!("Database connection established");
 
     // マイグレーションを実行
-    sqlx::migrate!("./migrations").run(&pool).await?;
+    MIGRATOR.run(&pool).await?;
 
     info!("Database migrations completed");
 
-    // インフラストラクチャ層の実装を作成
+    // イベントの取得元を作成し、プロセッサーを組み立てる
     let event_subscriber = EventStoreSubscriber::new(config.event_store.url.clone());
-    let read_repository = PostgresReadModelRepository::new(pool.clone());
-    let state_repository = PostgresProjectionStateRepository::new(pool);
-
-    // アプリケーション層のサービスを作成
-    let processor =
-        EventProcessor::new(config, event_subscriber, read_repository, state_repository);
+    let processor = build_processor(config, pool, event_subscriber);
 
     // イベント処理ループを開始
     info!("Starting event processing loop");
@@ -24,4 +24,10 @@ pub struct ProcessorStatus {
     pub last_processed_position: i64,
     pub events_processed_total:  u64,
     pub error_count:             u32,
+    /// 欠番待ちで保留したイベント数
+    pub buffered_events_total:   u64,
+    /// Event Store から取得して欠番を埋めたイベント数
+    pub gap_filled_events_total: u64,
+    /// 保留期限切れになった回数
+    pub expired_holds_total:     u64,
 }
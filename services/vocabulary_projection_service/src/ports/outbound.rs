@@ -1,15 +1,18 @@
 //! 出力ポート（外部システムとのインターフェース）
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
 use crate::{
     domain::{
         events::StoredEvent,
+        ordering::{AggregateCursor, PendingEvent},
         projections::{
-            ProjectionCheckpoint,
+            LearningContextField,
             ProjectionState,
+            ProvenanceProjection,
             VocabularyEntryProjection,
             VocabularyExampleProjection,
             VocabularyItemProjection,
@@ -38,6 +41,18 @@ pub trait EventSubscriber: Send + Sync {
     async fn subscribe(&self, from_position: i64) -> Result<EventStream>;
 }
 
+/// 集約単位のイベント取得（欠番の補完用）
+#[async_trait]
+pub trait AggregateEventSource: Send + Sync {
+    /// 集約の指定バージョン範囲（両端を含む）のイベントを取得
+    async fn fetch_aggregate_events(
+        &self,
+        aggregate_id: Uuid,
+        from_version: i64,
+        to_version: i64,
+    ) -> Result<Vec<StoredEvent>>;
+}
+
 /// イベントストリーム
 pub struct EventStream {
     // 実装詳細は infrastructure 層で定義
@@ -67,6 +82,13 @@ pub trait ReadModelRepository: Send + Sync {
         example: &VocabularyExampleProjection,
     ) -> Result<()>;
 
+    /// インポートされたデータの出典を保存
+    async fn save_provenance(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        provenance: &ProvenanceProjection,
+    ) -> Result<()>;
+
     /// Item の公開状態を更新
     async fn update_item_published(
         &self,
@@ -94,6 +116,16 @@ pub trait ReadModelRepository: Send + Sync {
         version: i64,
     ) -> Result<()>;
 
+    /// 使用域・専門分野・使用上の注意のいずれかを更新（None は値の解除）
+    async fn update_item_learning_context(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        item_id: Uuid,
+        field: LearningContextField,
+        value: Option<String>,
+        version: i64,
+    ) -> Result<()>;
+
     /// Entry の主要項目を設定
     async fn update_entry_primary_item(
         &self,
@@ -117,30 +149,71 @@ pub trait ReadModelRepository: Send + Sync {
         item_id: Uuid,
     ) -> Result<()>;
 
-    /// トランザクションを開始
-    async fn begin_transaction(&self) -> Result<Transaction<'_, Postgres>>;
+    /// コレクションにアイテムを追加（追加済みの場合は何もしない）
+    async fn add_collection_item(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        collection_id: Uuid,
+        item_id: Uuid,
+        added_at: DateTime<Utc>,
+    ) -> Result<()>;
+
+    /// コレクションからアイテムを削除
+    async fn remove_collection_item(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        collection_id: Uuid,
+        item_id: Uuid,
+    ) -> Result<()>;
+
+    /// トランザクションを開始（バッチの間プロジェクションが保持する）
+    async fn begin_transaction(&self) -> Result<Transaction<'static, Postgres>>;
 }
 
-/// プロジェクション状態リポジトリ
+/// プロジェクションのエラー状態リポジトリ
+///
+/// 処理済みの位置は `shared_cqrs::PostgresCheckpointStore` が保存する
 #[async_trait]
 pub trait ProjectionStateRepository: Send + Sync {
     /// プロジェクション状態を取得
     async fn get_state(&self, name: &str) -> Result<Option<ProjectionState>>;
 
-    /// プロジェクション状態を保存
-    async fn save_state(
+    /// エラーを記録
+    async fn record_error(&self, name: &str, error: &str) -> Result<()>;
+}
+
+/// 集約ごとの適用済みバージョンと保留イベントのリポジトリ
+#[async_trait]
+pub trait EventOrderingRepository: Send + Sync {
+    /// 集約の適用状況を取得（未登録の場合はバージョン 0）
+    async fn load_cursor(
         &self,
         tx: &mut Transaction<'_, Postgres>,
-        state: &ProjectionState,
-    ) -> Result<()>;
+        projection_name: &str,
+        aggregate_id: Uuid,
+    ) -> Result<AggregateCursor>;
 
-    /// エラーを記録
-    async fn record_error(&self, name: &str, error: &str) -> Result<()>;
+    /// 適用済みバージョンを進め、適用済みになった保留イベントを削除
+    async fn advance(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        projection_name: &str,
+        aggregate_id: Uuid,
+        version: i64,
+    ) -> Result<()>;
 
-    /// チェックポイントを保存
-    async fn save_checkpoint(
+    /// イベントを保留
+    async fn buffer_event(
         &self,
         tx: &mut Transaction<'_, Postgres>,
-        checkpoint: &ProjectionCheckpoint,
+        projection_name: &str,
+        pending: &PendingEvent,
     ) -> Result<()>;
+
+    /// 指定時刻より前から保留されているイベントを持つ集約を取得
+    async fn find_expired_aggregates(
+        &self,
+        projection_name: &str,
+        buffered_before: DateTime<Utc>,
+    ) -> Result<Vec<Uuid>>;
 }
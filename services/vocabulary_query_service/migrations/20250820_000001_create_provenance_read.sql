@@ -0,0 +1,14 @@
+-- 外部ソースからインポートされたデータの出典
+-- Projection Service によって更新される
+
+CREATE TABLE IF NOT EXISTS vocabulary_provenance_read (
+    target_id UUID PRIMARY KEY,          -- Item ID または例文 ID
+    item_id UUID NOT NULL,
+    target_kind VARCHAR(20) NOT NULL,    -- 'item' | 'example'
+    source_name VARCHAR(255) NOT NULL,
+    license VARCHAR(255) NOT NULL,
+    attribution TEXT,
+    requires_review BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX idx_provenance_item_id ON vocabulary_provenance_read (item_id);
@@ -0,0 +1,13 @@
+-- アクセス数の記録（キャッシュウォームアップの対象選定に使用）
+-- 書き込み増幅を抑えるため、クエリサービス側でサンプリングして加算する
+
+ALTER TABLE vocabulary_items_read
+    ADD COLUMN IF NOT EXISTS access_count BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_items_access_count
+    ON vocabulary_items_read (access_count DESC)
+    WHERE NOT is_deleted;
+
+CREATE INDEX IF NOT EXISTS idx_items_published_updated_at
+    ON vocabulary_items_read (updated_at DESC)
+    WHERE is_published AND NOT is_deleted;
@@ -0,0 +1,9 @@
+-- 語彙項目の学習コンテキスト（使用域・専門分野）
+-- プロジェクションサービスの Read Model と同じ列を持たせる
+
+ALTER TABLE vocabulary_items_read
+    ADD COLUMN IF NOT EXISTS register VARCHAR(50),
+    ADD COLUMN IF NOT EXISTS domain VARCHAR(50);
+
+CREATE INDEX IF NOT EXISTS idx_vocabulary_items_register ON vocabulary_items_read (register) WHERE NOT is_deleted;
+CREATE INDEX IF NOT EXISTS idx_vocabulary_items_domain ON vocabulary_items_read (domain) WHERE NOT is_deleted;
@@ -0,0 +1,56 @@
+//! アイテムのアクセス数トラッキング
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// サンプリング付きのアクセストラッカー
+///
+/// 読み取りのたびに書き込むと Read Model への書き込みが増幅するため、
+/// N 回に1回だけ重み N で記録する
+#[derive(Debug)]
+pub struct AccessTracker {
+    sample_rate: u64,
+    counter:     AtomicU64,
+}
+
+impl AccessTracker {
+    /// `sample_rate` が 0 の場合は 1（毎回記録）として扱う
+    pub fn new(sample_rate: u64) -> Self {
+        Self {
+            sample_rate: sample_rate.max(1),
+            counter:     AtomicU64::new(0),
+        }
+    }
+
+    /// アクセスを1回記録し、書き込むべき場合は加算する重みを返す
+    pub fn sample(&self) -> Option<i64> {
+        let n = self.counter.fetch_add(1, Ordering::Relaxed) + 1;
+        n.is_multiple_of(self.sample_rate).then_some(self.sample_rate as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_records_every_nth_access_with_weight() {
+        let tracker = AccessTracker::new(4);
+        let samples: Vec<Option<i64>> = (0..8).map(|_| tracker.sample()).collect();
+
+        assert_eq!(
+            samples,
+            vec![None, None, None, Some(4), None, None, None, Some(4)]
+        );
+        // 記録された重みの合計は実際のアクセス数と一致する
+        assert_eq!(samples.iter().flatten().sum::<i64>(), 8);
+    }
+
+    #[test]
+    fn test_sample_rate_one_records_every_access() {
+        let tracker = AccessTracker::new(1);
+        assert!((0..5).all(|_| tracker.sample() == Some(1)));
+
+        let tracker = AccessTracker::new(0);
+        assert_eq!(tracker.sample(), Some(1));
+    }
+}
@@ -0,0 +1,106 @@
+//! 起動時のキャッシュウォームアップ
+
+use std::{collections::HashSet, time::Duration};
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        health_check_service::ReadinessGate,
+        vocabulary_query_service::VocabularyQueryService,
+    },
+    config::WarmupConfig,
+    ports::outbound::{CacheRepository, ReadModelRepository},
+};
+
+/// ウォームアップの計画
+#[derive(Debug, Clone)]
+pub struct WarmupPlan {
+    pub top_accessed:       i64,
+    pub recently_published: i64,
+    pub time_budget:        Duration,
+}
+
+impl From<&WarmupConfig> for WarmupPlan {
+    fn from(config: &WarmupConfig) -> Self {
+        Self {
+            top_accessed:       config.top_accessed,
+            recently_published: config.recently_published,
+            time_budget:        Duration::from_millis(config.time_budget_ms),
+        }
+    }
+}
+
+/// ウォームアップの結果
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WarmupReport {
+    /// ウォームアップ対象として選ばれたアイテム数
+    pub selected:   usize,
+    /// キャッシュに読み込んだアイテム数
+    pub warmed:     usize,
+    /// 読み込みに失敗した（または既に存在しない）アイテム数
+    pub failed:     usize,
+    /// 時間内にすべて読み込めたかどうか（false の場合は部分的なウォームアップ）
+    pub completed:  bool,
+    pub elapsed_ms: u64,
+}
+
+/// アクセス上位と最近公開されたアイテムから、
+/// 重複を除いたウォームアップ対象を選ぶ
+///
+/// アクセス上位を優先し、時間切れの場合も重要なものから読み込まれるようにする
+pub fn select_warm_set(most_accessed: Vec<Uuid>, recently_published: Vec<Uuid>) -> Vec<Uuid> {
+    let mut seen = HashSet::new();
+    most_accessed
+        .into_iter()
+        .chain(recently_published)
+        .filter(|id| seen.insert(*id))
+        .collect()
+}
+
+/// ウォームアップを実行し、終了後（時間切れを含む）に Ready にする
+pub async fn warm_up_then_mark_ready<R, C>(
+    service: &VocabularyQueryService<R, C>,
+    plan: &WarmupPlan,
+    readiness: &ReadinessGate,
+) -> WarmupReport
+where
+    R: ReadModelRepository,
+    C: CacheRepository,
+{
+    let report = service.warm_up(plan).await;
+    readiness.mark_ready(Some(report.clone()));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_warm_set_prioritizes_accessed_and_dedups() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let c = Uuid::new_v4();
+
+        let selected = select_warm_set(vec![a, b], vec![c, a]);
+        assert_eq!(selected, vec![a, b, c]);
+
+        assert!(select_warm_set(Vec::new(), Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_plan_from_config() {
+        let plan = WarmupPlan::from(&WarmupConfig {
+            enabled:            true,
+            top_accessed:       50,
+            recently_published: 10,
+            time_budget_ms:     1500,
+            access_sample_rate: 10,
+        });
+        assert_eq!(plan.top_accessed, 50);
+        assert_eq!(plan.recently_published, 10);
+        assert_eq!(plan.time_budget, Duration::from_millis(1500));
+    }
+}
@@ -1,9 +1,16 @@
 //! ヘルスチェックサービス
 
+use std::sync::{
+    Arc,
+    Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
 use async_trait::async_trait;
 use tracing::{error, info};
 
 use crate::{
+    application::cache_warmup::WarmupReport,
     error::Result,
     ports::{
         inbound::{DatabaseStatus, HealthCheckUseCase, HealthStatus},
@@ -11,20 +18,49 @@ use crate::{
     },
 };
 
+/// 起動処理（キャッシュウォームアップ）の完了を表すゲート
+#[derive(Debug, Clone, Default)]
+pub struct ReadinessGate {
+    ready:  Arc<AtomicBool>,
+    warmup: Arc<Mutex<Option<WarmupReport>>>,
+}
+
+impl ReadinessGate {
+    /// Ready にする（ウォームアップを行った場合はその結果を記録）
+    pub fn mark_ready(&self, warmup: Option<WarmupReport>) {
+        if let Ok(mut report) = self.warmup.lock() {
+            *report = warmup;
+        }
+        self.ready.store(true, Ordering::Release);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    pub fn warmup_report(&self) -> Option<WarmupReport> {
+        self.warmup.lock().ok().and_then(|report| report.clone())
+    }
+}
+
 /// ヘルスチェックサービス
 pub struct HealthCheckService<R>
 where
     R: ReadModelRepository,
 {
     repository: R,
+    readiness:  ReadinessGate,
 }
 
 impl<R> HealthCheckService<R>
 where
     R: ReadModelRepository,
 {
-    pub fn new(repository: R) -> Self {
-        Self { repository }
+    pub fn new(repository: R, readiness: ReadinessGate) -> Self {
+        Self {
+            repository,
+            readiness,
+        }
     }
 }
 
@@ -49,6 +85,8 @@ where
 
         Ok(HealthStatus {
             is_healthy,
+            is_ready: self.readiness.is_ready(),
+            warmup: self.readiness.warmup_report(),
             database_status,
             message: if is_healthy {
                 None
@@ -58,3 +96,30 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{application::test_support::InMemoryReadModel, ports::inbound::HealthCheckUseCase};
+
+    #[tokio::test]
+    async fn test_not_ready_until_gate_is_marked() {
+        let readiness = ReadinessGate::default();
+        let service = HealthCheckService::new(InMemoryReadModel::default(), readiness.clone());
+
+        let status = service.check_health().await.unwrap();
+        assert!(status.is_healthy);
+        assert!(!status.is_ready);
+
+        readiness.mark_ready(Some(WarmupReport {
+            selected: 3,
+            warmed: 2,
+            completed: false,
+            ..Default::default()
+        }));
+
+        let status = service.check_health().await.unwrap();
+        assert!(status.is_ready);
+        assert_eq!(status.warmup.map(|w| w.warmed), Some(2));
+    }
+}
@@ -1,7 +1,22 @@
 //! アプリケーション層
 
+pub mod access_tracker;
+pub mod cache_warmup;
 pub mod health_check_service;
+pub mod study_sheet_service;
 pub mod vocabulary_query_service;
 
-pub use health_check_service::HealthCheckService;
+#[cfg(test)]
+pub mod test_support;
+
+pub use access_tracker::AccessTracker;
+pub use cache_warmup::{WarmupPlan, WarmupReport};
+pub use health_check_service::{HealthCheckService, ReadinessGate};
+pub use study_sheet_service::{
+    GeneratedStudySheet,
+    StudySheetDelivery,
+    StudySheetLinkSigner,
+    StudySheetRequest,
+    StudySheetService,
+};
 pub use vocabulary_query_service::VocabularyQueryService;
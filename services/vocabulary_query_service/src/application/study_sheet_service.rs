@@ -0,0 +1,478 @@
+//! 学習シートの生成
+//!
+//! コレクションのアイテムを Read Model から集めて PDF に描画し、
+//! そのまま返すか BLOB ストアに保存して署名付き URL を返す。
+//! 描画は件数の上限と時間の上限で制限する。
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use shared_blob_store::BlobStore;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{
+    application::vocabulary_query_service::MAX_BATCH_ITEMS,
+    domain::{StudySheet, StudySheetLayout},
+    error::{QueryError, Result},
+    ports::{
+        inbound::VocabularyQueryUseCase,
+        outbound::{CollectionRepository, StudySheetRenderer},
+    },
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 1枚のシートに載せられるアイテム数の既定の上限
+pub const DEFAULT_MAX_STUDY_SHEET_ITEMS: usize = 300;
+/// 描画時間の既定の上限
+pub const DEFAULT_RENDER_TIMEOUT: Duration = Duration::from_secs(10);
+/// 署名付き URL の既定の有効期間（秒）
+pub const DEFAULT_LINK_TTL_SECONDS: i64 = 3600;
+
+/// 学習シートの受け取り方
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StudySheetDelivery {
+    /// PDF をそのまま返す
+    #[default]
+    Inline,
+    /// BLOB ストアに保存して署名付き URL を返す
+    Link,
+}
+
+/// 学習シートの生成リクエスト
+#[derive(Debug, Clone)]
+pub struct StudySheetRequest {
+    pub collection_id:    Uuid,
+    pub layout:           StudySheetLayout,
+    pub include_examples: bool,
+    pub delivery:         StudySheetDelivery,
+}
+
+/// 保存した学習シートの署名付き URL
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SignedLink {
+    pub key:        String,
+    pub url:        String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// 生成した学習シート
+#[derive(Debug, Clone)]
+pub enum GeneratedStudySheet {
+    Pdf {
+        pdf:        Vec<u8>,
+        page_count: usize,
+    },
+    Link {
+        link:       SignedLink,
+        page_count: usize,
+    },
+}
+
+/// 保存した学習シートの URL への署名
+///
+/// URL は `{base_url}/study-sheets/{キー}?expires={UNIX 秒}&signature={署名}`
+/// の形式で、署名はキーと有効期限に対する HMAC-SHA256
+#[derive(Clone)]
+pub struct StudySheetLinkSigner {
+    secret:   Vec<u8>,
+    base_url: String,
+    ttl:      chrono::Duration,
+}
+
+impl StudySheetLinkSigner {
+    pub fn new(secret: impl Into<Vec<u8>>, base_url: impl Into<String>) -> Self {
+        Self {
+            secret:   secret.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            ttl:      chrono::Duration::seconds(DEFAULT_LINK_TTL_SECONDS),
+        }
+    }
+
+    /// 有効期間を変更
+    pub fn with_ttl(mut self, ttl: chrono::Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// キーに対する署名付き URL を発行する
+    pub fn sign(&self, key: &str, now: DateTime<Utc>) -> SignedLink {
+        let expires = (now + self.ttl).timestamp();
+        let signature = hex::encode(self.mac(key, expires).finalize().into_bytes());
+
+        SignedLink {
+            key:        key.to_string(),
+            url:        format!(
+                "{}/study-sheets/{key}?expires={expires}&signature={signature}",
+                self.base_url
+            ),
+            expires_at: DateTime::from_timestamp(expires, 0).unwrap_or(now + self.ttl),
+        }
+    }
+
+    /// 署名と有効期限を検証する
+    pub fn verify(&self, key: &str, expires: i64, signature: &str, now: DateTime<Utc>) -> bool {
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+
+        now.timestamp() < expires && self.mac(key, expires).verify_slice(&signature).is_ok()
+    }
+
+    fn mac(&self, key: &str, expires: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(format!("{key}\n{expires}").as_bytes());
+        mac
+    }
+}
+
+/// 学習シートの BLOB ストアのキー
+pub fn study_sheet_key(collection_id: Uuid, sheet_id: Uuid) -> String {
+    format!("{collection_id}/{sheet_id}.pdf")
+}
+
+/// 学習シートの生成サービス
+pub struct StudySheetService {
+    query:          Arc<dyn VocabularyQueryUseCase>,
+    collections:    Arc<dyn CollectionRepository>,
+    renderer:       Arc<dyn StudySheetRenderer>,
+    storage:        Option<(Arc<dyn BlobStore>, StudySheetLinkSigner)>,
+    max_items:      usize,
+    render_timeout: Duration,
+}
+
+impl StudySheetService {
+    pub fn new(
+        query: Arc<dyn VocabularyQueryUseCase>,
+        collections: Arc<dyn CollectionRepository>,
+        renderer: Arc<dyn StudySheetRenderer>,
+    ) -> Self {
+        Self {
+            query,
+            collections,
+            renderer,
+            storage: None,
+            max_items: DEFAULT_MAX_STUDY_SHEET_ITEMS,
+            render_timeout: DEFAULT_RENDER_TIMEOUT,
+        }
+    }
+
+    /// BLOB ストアへの保存と署名付き URL を有効にする
+    pub fn with_storage(mut self, blobs: Arc<dyn BlobStore>, signer: StudySheetLinkSigner) -> Self {
+        self.storage = Some((blobs, signer));
+        self
+    }
+
+    /// アイテム数と描画時間の上限を変更
+    pub fn with_limits(mut self, max_items: usize, render_timeout: Duration) -> Self {
+        self.max_items = max_items;
+        self.render_timeout = render_timeout;
+        self
+    }
+
+    /// 保存した学習シートの署名付き URL の検証
+    pub fn link_signer(&self) -> Option<&StudySheetLinkSigner> {
+        self.storage.as_ref().map(|(_, signer)| signer)
+    }
+
+    /// 保存した学習シートを取得する（署名の検証は呼び出し側で行う）
+    pub async fn stored_sheet(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match &self.storage {
+            Some((blobs, _)) => Ok(blobs.get(key).await?),
+            None => Ok(None),
+        }
+    }
+
+    /// コレクションの学習シートを生成する
+    pub async fn generate(
+        &self,
+        request: &StudySheetRequest,
+        now: DateTime<Utc>,
+    ) -> Result<GeneratedStudySheet> {
+        let item_ids = self
+            .collections
+            .find_collection_item_ids(request.collection_id)
+            .await?;
+        if item_ids.is_empty() {
+            return Err(QueryError::NotFound(format!(
+                "Collection {} has no items",
+                request.collection_id
+            )));
+        }
+        if item_ids.len() > self.max_items {
+            return Err(QueryError::InvalidInput(format!(
+                "Collection has {} items; study sheets are limited to {}",
+                item_ids.len(),
+                self.max_items
+            )));
+        }
+        if request.delivery == StudySheetDelivery::Link && self.storage.is_none() {
+            return Err(QueryError::Configuration(
+                "Study sheet storage is not configured".to_string(),
+            ));
+        }
+
+        let mut items = Vec::with_capacity(item_ids.len());
+        for chunk in item_ids.chunks(MAX_BATCH_ITEMS) {
+            items.extend(
+                self.query
+                    .get_items_by_ids(chunk)
+                    .await?
+                    .into_iter()
+                    .filter(|item| !item.is_deleted),
+            );
+        }
+
+        let sheet = StudySheet {
+            title: "Vocabulary study sheet".to_string(),
+            layout: request.layout,
+            include_examples: request.include_examples,
+            items,
+            generated_at: now,
+        };
+        let renderer = self.renderer.clone();
+        let deadline = Instant::now() + self.render_timeout;
+        let rendered = tokio::task::spawn_blocking(move || renderer.render(&sheet, deadline))
+            .await
+            .map_err(|e| QueryError::Internal(format!("Study sheet rendering failed: {e}")))??;
+        if rendered.substituted_chars > 0 {
+            warn!(
+                "Study sheet for collection {} replaced {} characters the fonts cannot display",
+                request.collection_id, rendered.substituted_chars
+            );
+        }
+
+        match (&self.storage, request.delivery) {
+            (Some((blobs, signer)), StudySheetDelivery::Link) => {
+                let key = study_sheet_key(request.collection_id, Uuid::new_v4());
+                blobs.put(&key, &rendered.pdf).await?;
+                Ok(GeneratedStudySheet::Link {
+                    link:       signer.sign(&key, now),
+                    page_count: rendered.page_count,
+                })
+            },
+            _ => Ok(GeneratedStudySheet::Pdf {
+                pdf:        rendered.pdf,
+                page_count: rendered.page_count,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{
+            Mutex,
+            atomic::{AtomicUsize, Ordering},
+        },
+    };
+
+    use async_trait::async_trait;
+    use shared_blob_store::InMemoryBlobStore;
+
+    use super::*;
+    use crate::{
+        application::{
+            VocabularyQueryService,
+            test_support::{InMemoryCache, InMemoryReadModel, item},
+        },
+        domain::RenderedStudySheet,
+        infrastructure::PdfStudySheetRenderer,
+    };
+
+    #[derive(Default)]
+    struct InMemoryCollections {
+        items: Mutex<HashMap<Uuid, Vec<Uuid>>>,
+    }
+
+    #[async_trait]
+    impl CollectionRepository for InMemoryCollections {
+        async fn find_collection_item_ids(&self, collection_id: Uuid) -> Result<Vec<Uuid>> {
+            Ok(self
+                .items
+                .lock()
+                .unwrap()
+                .get(&collection_id)
+                .cloned()
+                .unwrap_or_default())
+        }
+    }
+
+    /// 描画の呼び出し回数を数える
+    #[derive(Default)]
+    struct CountingRenderer {
+        calls: AtomicUsize,
+    }
+
+    impl StudySheetRenderer for CountingRenderer {
+        fn render(&self, sheet: &StudySheet, deadline: Instant) -> Result<RenderedStudySheet> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            PdfStudySheetRenderer.render(sheet, deadline)
+        }
+    }
+
+    struct Fixture {
+        collection_id: Uuid,
+        renderer:      Arc<CountingRenderer>,
+        blobs:         Arc<InMemoryBlobStore>,
+        service:       StudySheetService,
+    }
+
+    fn fixture(item_count: usize) -> Fixture {
+        let read_model = InMemoryReadModel::default();
+        let item_ids: Vec<Uuid> = (0..item_count)
+            .map(|index| read_model.insert(item(&format!("word{index}"))))
+            .collect();
+        let collection_id = Uuid::new_v4();
+        let collections = InMemoryCollections::default();
+        collections
+            .items
+            .lock()
+            .unwrap()
+            .insert(collection_id, item_ids);
+
+        let renderer = Arc::new(CountingRenderer::default());
+        let blobs = Arc::new(InMemoryBlobStore::new());
+        let service = StudySheetService::new(
+            Arc::new(VocabularyQueryService::<_, InMemoryCache>::new(
+                read_model, None,
+            )),
+            Arc::new(collections),
+            renderer.clone(),
+        )
+        .with_storage(
+            blobs.clone(),
+            StudySheetLinkSigner::new("secret", "https://effect.example/vocabulary/"),
+        );
+
+        Fixture {
+            collection_id,
+            renderer,
+            blobs,
+            service,
+        }
+    }
+
+    fn request(collection_id: Uuid, delivery: StudySheetDelivery) -> StudySheetRequest {
+        StudySheetRequest {
+            collection_id,
+            layout: StudySheetLayout::FlashcardGrid,
+            include_examples: false,
+            delivery,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2025-10-20T09:00:00Z".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_inline_sheet_covers_items_beyond_one_batch() {
+        let Fixture {
+            collection_id,
+            service,
+            ..
+        } = fixture(MAX_BATCH_ITEMS + 20);
+
+        let sheet = service
+            .generate(&request(collection_id, StudySheetDelivery::Inline), now())
+            .await
+            .unwrap();
+
+        let GeneratedStudySheet::Pdf { pdf, page_count } = sheet else {
+            panic!("expected inline PDF");
+        };
+        assert_eq!(page_count, 15);
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(String::from_utf8_lossy(&pdf).contains("(word119) Tj"));
+    }
+
+    #[tokio::test]
+    async fn test_collections_over_the_item_cap_are_rejected_before_rendering() {
+        let Fixture {
+            collection_id,
+            renderer,
+            service,
+            ..
+        } = fixture(11);
+        let service = service.with_limits(10, DEFAULT_RENDER_TIMEOUT);
+
+        let result = service
+            .generate(&request(collection_id, StudySheetDelivery::Inline), now())
+            .await;
+
+        assert!(matches!(result, Err(QueryError::InvalidInput(_))));
+        assert_eq!(renderer.calls.load(Ordering::SeqCst), 0);
+
+        let unknown = service
+            .generate(&request(Uuid::new_v4(), StudySheetDelivery::Inline), now())
+            .await;
+        assert!(matches!(unknown, Err(QueryError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_render_timeout_is_reported() {
+        let Fixture {
+            collection_id,
+            service,
+            ..
+        } = fixture(3);
+        let service = service.with_limits(10, Duration::ZERO);
+
+        let result = service
+            .generate(&request(collection_id, StudySheetDelivery::Inline), now())
+            .await;
+
+        assert!(matches!(result, Err(QueryError::Timeout(_))));
+    }
+
+    #[tokio::test]
+    async fn test_link_delivery_stores_the_pdf_behind_a_signed_url() {
+        let Fixture {
+            collection_id,
+            blobs,
+            service,
+            ..
+        } = fixture(3);
+
+        let sheet = service
+            .generate(&request(collection_id, StudySheetDelivery::Link), now())
+            .await
+            .unwrap();
+
+        let GeneratedStudySheet::Link { link, page_count } = sheet else {
+            panic!("expected signed link");
+        };
+        assert_eq!(page_count, 1);
+        assert!(link.key.starts_with(&format!("{collection_id}/")));
+        assert!(link.url.starts_with(&format!(
+            "https://effect.example/vocabulary/study-sheets/{}?expires=",
+            link.key
+        )));
+        assert_eq!(link.expires_at, now() + chrono::Duration::hours(1));
+        assert!(blobs.get(&link.key).await.unwrap().is_some());
+
+        let signer = service.link_signer().unwrap();
+        let signature = link.url.rsplit_once("signature=").unwrap().1;
+        let expires = link.expires_at.timestamp();
+        assert!(signer.verify(&link.key, expires, signature, now()));
+        assert!(!signer.verify(&link.key, expires + 60, signature, now()));
+        assert!(!signer.verify("other/key.pdf", expires, signature, now()));
+        assert!(!signer.verify(&link.key, expires, signature, link.expires_at));
+        assert_eq!(
+            service.stored_sheet(&link.key).await.unwrap(),
+            blobs.get(&link.key).await.unwrap()
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! アプリケーション層テスト用のインメモリ実装
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        Attribution,
+        Cursor,
+        PageInfo,
+        PageSize,
+        PagedResult,
+        SortOptions,
+        VocabularyEntry,
+        VocabularyExample,
+        VocabularyFilter,
+        VocabularyItem,
+        VocabularyStatistics,
+    },
+    error::Result,
+    ports::outbound::{CacheRepository, ReadModelRepository},
+};
+
+/// テスト用のアイテムを生成
+pub fn item(spelling: &str) -> VocabularyItem {
+    VocabularyItem {
+        item_id:           Uuid::new_v4(),
+        entry_id:          Uuid::new_v4(),
+        spelling:          spelling.to_string(),
+        disambiguation:    None,
+        register:          None,
+        domain:            None,
+        usage_notes:       None,
+        part_of_speech:    None,
+        definition:        None,
+        ipa_pronunciation: None,
+        cefr_level:        None,
+        frequency_rank:    None,
+        is_published:      true,
+        is_deleted:        false,
+        example_count:     0,
+        examples:          Vec::new(),
+        attribution:       None,
+        created_at:        Utc::now(),
+        updated_at:        Utc::now(),
+    }
+}
+
+fn empty_page<T>() -> PagedResult<T> {
+    PagedResult {
+        items:     Vec::new(),
+        page_info: PageInfo {
+            has_next_page:     false,
+            has_previous_page: false,
+            start_cursor:      None,
+            end_cursor:        None,
+            total_count:       Some(0),
+        },
+    }
+}
+
+/// インメモリの Read Model
+#[derive(Clone, Default)]
+pub struct InMemoryReadModel {
+    pub items:              Arc<Mutex<HashMap<Uuid, VocabularyItem>>>,
+    pub access_counts:      Arc<Mutex<HashMap<Uuid, i64>>>,
+    pub most_accessed:      Arc<Mutex<Vec<Uuid>>>,
+    pub recently_published: Arc<Mutex<Vec<Uuid>>>,
+    /// find_item_by_id の呼び出し回数
+    pub item_reads:         Arc<AtomicUsize>,
+    /// find_item_by_id の応答遅延
+    pub item_read_delay:    Option<Duration>,
+}
+
+impl InMemoryReadModel {
+    pub fn insert(&self, item: VocabularyItem) -> Uuid {
+        let item_id = item.item_id;
+        self.items.lock().unwrap().insert(item_id, item);
+        item_id
+    }
+
+    pub fn item_reads(&self) -> usize {
+        self.item_reads.load(Ordering::SeqCst)
+    }
+
+    pub fn access_count(&self, item_id: Uuid) -> i64 {
+        self.access_counts
+            .lock()
+            .unwrap()
+            .get(&item_id)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+#[async_trait]
+impl ReadModelRepository for InMemoryReadModel {
+    async fn find_entry_by_id(&self, _entry_id: Uuid) -> Result<Option<VocabularyEntry>> {
+        Ok(None)
+    }
+
+    async fn find_entry_by_spelling(&self, _spelling: &str) -> Result<Option<VocabularyEntry>> {
+        Ok(None)
+    }
+
+    async fn find_entries(
+        &self,
+        _filter: Option<VocabularyFilter>,
+        _sort: Option<SortOptions>,
+        _cursor: Option<Cursor>,
+        _limit: PageSize,
+    ) -> Result<PagedResult<VocabularyEntry>> {
+        Ok(empty_page())
+    }
+
+    async fn find_item_by_id(&self, item_id: Uuid) -> Result<Option<VocabularyItem>> {
+        self.item_reads.fetch_add(1, Ordering::SeqCst);
+        if let Some(delay) = self.item_read_delay {
+            tokio::time::sleep(delay).await;
+        }
+        Ok(self.items.lock().unwrap().get(&item_id).cloned())
+    }
+
+    async fn find_items_by_ids(&self, item_ids: &[Uuid]) -> Result<Vec<VocabularyItem>> {
+        self.item_reads.fetch_add(1, Ordering::SeqCst);
+        let items = self.items.lock().unwrap();
+        Ok(item_ids
+            .iter()
+            .filter_map(|id| items.get(id).cloned())
+            .collect())
+    }
+
+    async fn find_items_by_entry_id(
+        &self,
+        entry_id: Uuid,
+        _include_deleted: bool,
+    ) -> Result<Vec<VocabularyItem>> {
+        Ok(self
+            .items
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|i| i.entry_id == entry_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_items(
+        &self,
+        _filter: Option<VocabularyFilter>,
+        _sort: Option<SortOptions>,
+        _cursor: Option<Cursor>,
+        _limit: PageSize,
+    ) -> Result<PagedResult<VocabularyItem>> {
+        Ok(empty_page())
+    }
+
+    async fn find_examples_by_item_id(&self, _item_id: Uuid) -> Result<Vec<VocabularyExample>> {
+        Ok(Vec::new())
+    }
+
+    async fn find_item_attribution(&self, _item_id: Uuid) -> Result<Option<Attribution>> {
+        Ok(None)
+    }
+
+    async fn search_items(
+        &self,
+        _search_term: &str,
+        _filter: Option<VocabularyFilter>,
+        _cursor: Option<Cursor>,
+        _limit: PageSize,
+    ) -> Result<PagedResult<VocabularyItem>> {
+        Ok(empty_page())
+    }
+
+    async fn get_statistics(&self) -> Result<VocabularyStatistics> {
+        Ok(VocabularyStatistics {
+            total_entries:   0,
+            total_items:     self.items.lock().unwrap().len() as i64,
+            total_examples:  0,
+            published_items: 0,
+            items_by_pos:    HashMap::new(),
+            items_by_cefr:   HashMap::new(),
+        })
+    }
+
+    async fn record_item_access(&self, item_id: Uuid, count: i64) -> Result<()> {
+        *self
+            .access_counts
+            .lock()
+            .unwrap()
+            .entry(item_id)
+            .or_default() += count;
+        Ok(())
+    }
+
+    async fn find_most_accessed_item_ids(&self, limit: i64) -> Result<Vec<Uuid>> {
+        let ids = self.most_accessed.lock().unwrap();
+        Ok(ids.iter().take(limit as usize).copied().collect())
+    }
+
+    async fn find_recently_published_item_ids(&self, limit: i64) -> Result<Vec<Uuid>> {
+        let ids = self.recently_published.lock().unwrap();
+        Ok(ids.iter().take(limit as usize).copied().collect())
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// インメモリのキャッシュ（TTL は無視する）
+#[derive(Clone, Default)]
+pub struct InMemoryCache {
+    pub entries: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+}
+
+impl InMemoryCache {
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.lock().unwrap().contains_key(key)
+    }
+}
+
+#[async_trait]
+impl CacheRepository for InMemoryCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        Ok(self.entries.lock().unwrap().get(key).cloned())
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, _ttl_seconds: u64) -> Result<()> {
+        self.entries.lock().unwrap().insert(key.to_string(), value);
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.entries.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn delete_pattern(&self, pattern: &str) -> Result<()> {
+        let prefix = pattern.trim_end_matches('*');
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|key, _| !key.starts_with(prefix));
+        Ok(())
+    }
+}
@@ -1,10 +1,15 @@
 //! 語彙クエリサービスの実装
 
 use async_trait::async_trait;
-use tracing::{debug, error, info};
+use tokio::time::{Instant, timeout_at};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::{
+    application::{
+        access_tracker::AccessTracker,
+        cache_warmup::{WarmupPlan, WarmupReport, select_warm_set},
+    },
     domain::{
         Cursor,
         PageSize,
@@ -23,14 +28,18 @@ use crate::{
     },
 };
 
+/// 一度にまとめて取得できるアイテム数の上限
+pub const MAX_BATCH_ITEMS: usize = 100;
+
 /// 語彙クエリサービス
 pub struct VocabularyQueryService<R, C>
 where
     R: ReadModelRepository,
     C: CacheRepository,
 {
-    repository: R,
-    cache:      Option<C>,
+    repository:     R,
+    cache:          Option<C>,
+    access_tracker: Option<AccessTracker>,
 }
 
 impl<R, C> VocabularyQueryService<R, C>
@@ -39,7 +48,114 @@ where
     C: CacheRepository,
 {
     pub fn new(repository: R, cache: Option<C>) -> Self {
-        Self { repository, cache }
+        Self {
+            repository,
+            cache,
+            access_tracker: None,
+        }
+    }
+
+    /// アイテムのアクセス数トラッキングを有効にする
+    pub fn with_access_tracking(mut self, tracker: AccessTracker) -> Self {
+        self.access_tracker = Some(tracker);
+        self
+    }
+
+    /// アイテムを例文・帰属表示とともに Read Model から読み込む
+    async fn load_item(&self, item_id: Uuid) -> Result<Option<VocabularyItem>> {
+        let mut item = self.repository.find_item_by_id(item_id).await?;
+
+        if let Some(ref mut item) = item {
+            item.examples = self.repository.find_examples_by_item_id(item_id).await?;
+            item.attribution = self.repository.find_item_attribution(item_id).await?;
+        }
+
+        Ok(item)
+    }
+
+    /// アクセスを記録（サンプリングされた場合のみ書き込む）
+    async fn track_item_access(&self, item_id: Uuid) {
+        let Some(weight) = self.access_tracker.as_ref().and_then(|t| t.sample()) else {
+            return;
+        };
+
+        if let Err(e) = self.repository.record_item_access(item_id, weight).await {
+            warn!("Failed to record item access: {}", e);
+        }
+    }
+
+    /// アイテムをキャッシュに読み込む（アクセス数には含めない）
+    ///
+    /// アイテムが存在しない場合は false を返す
+    pub async fn warm_item(&self, item_id: Uuid) -> Result<bool> {
+        match self.load_item(item_id).await? {
+            Some(item) => {
+                let cache_key = self.cache_key("item", &item_id.to_string());
+                self.save_to_cache(&cache_key, &item, 300).await;
+                Ok(true)
+            },
+            None => Ok(false),
+        }
+    }
+
+    /// よく読まれるアイテムをキャッシュに読み込む
+    ///
+    /// 時間予算を超えた時点で打ち切り、それまでの結果を返す
+    pub async fn warm_up(&self, plan: &WarmupPlan) -> WarmupReport {
+        let started = Instant::now();
+        let deadline = started + plan.time_budget;
+        let mut report = WarmupReport::default();
+
+        let candidates = timeout_at(deadline, async {
+            let most_accessed = self
+                .repository
+                .find_most_accessed_item_ids(plan.top_accessed)
+                .await?;
+            let recently_published = self
+                .repository
+                .find_recently_published_item_ids(plan.recently_published)
+                .await?;
+            Ok::<_, QueryError>(select_warm_set(most_accessed, recently_published))
+        })
+        .await;
+
+        let item_ids = match candidates {
+            Ok(Ok(ids)) => ids,
+            Ok(Err(e)) => {
+                error!("Failed to select warm-up items: {}", e);
+                report.elapsed_ms = started.elapsed().as_millis() as u64;
+                return report;
+            },
+            Err(_) => {
+                warn!("Cache warm-up timed out while selecting items");
+                report.elapsed_ms = started.elapsed().as_millis() as u64;
+                return report;
+            },
+        };
+
+        report.selected = item_ids.len();
+        report.completed = true;
+        for item_id in item_ids {
+            match timeout_at(deadline, self.warm_item(item_id)).await {
+                Ok(Ok(true)) => report.warmed += 1,
+                Ok(Ok(false)) => report.failed += 1,
+                Ok(Err(e)) => {
+                    warn!("Failed to warm item {}: {}", item_id, e);
+                    report.failed += 1;
+                },
+                Err(_) => {
+                    report.completed = false;
+                    break;
+                },
+            }
+        }
+        report.elapsed_ms = started.elapsed().as_millis() as u64;
+
+        info!(
+            "Cache warm-up finished: {}/{} items warmed in {}ms (completed: {})",
+            report.warmed, report.selected, report.elapsed_ms, report.completed
+        );
+        report
     }
 
     /// キャッシュキーを生成
@@ -154,20 +270,71 @@ where
         let cache_key = self.cache_key("item", &item_id.to_string());
 
         if let Some(item) = self.try_get_from_cache(&cache_key).await {
+            self.track_item_access(item_id).await;
             return Ok(Some(item));
         }
 
-        let mut item = self.repository.find_item_by_id(item_id).await?;
-
         // 例文も取得
-        if let Some(ref mut item) = item {
-            item.examples = self.repository.find_examples_by_item_id(item_id).await?;
+        let item = self.load_item(item_id).await?;
+
+        if let Some(ref item) = item {
             self.save_to_cache(&cache_key, item, 300).await;
+            self.track_item_access(item_id).await;
         }
 
         Ok(item)
     }
 
+    async fn get_items_by_ids(&self, item_ids: &[Uuid]) -> Result<Vec<VocabularyItem>> {
+        if item_ids.len() > MAX_BATCH_ITEMS {
+            return Err(QueryError::InvalidInput(format!(
+                "Too many item ids: {} (max {})",
+                item_ids.len(),
+                MAX_BATCH_ITEMS
+            )));
+        }
+
+        // キャッシュにあるものはそのまま使い、残りを Read Model から一括で読む
+        let mut found = std::collections::HashMap::new();
+        let mut misses = Vec::new();
+        for &item_id in item_ids {
+            if found.contains_key(&item_id) || misses.contains(&item_id) {
+                continue;
+            }
+            let cache_key = self.cache_key("item", &item_id.to_string());
+            match self.try_get_from_cache::<VocabularyItem>(&cache_key).await {
+                Some(item) => {
+                    found.insert(item_id, item);
+                },
+                None => misses.push(item_id),
+            }
+        }
+
+        if !misses.is_empty() {
+            for mut item in self.repository.find_items_by_ids(&misses).await? {
+                item.examples = self
+                    .repository
+                    .find_examples_by_item_id(item.item_id)
+                    .await?;
+                item.attribution = self.repository.find_item_attribution(item.item_id).await?;
+
+                let cache_key = self.cache_key("item", &item.item_id.to_string());
+                self.save_to_cache(&cache_key, &item, 300).await;
+                found.insert(item.item_id, item);
+            }
+        }
+
+        let mut items = Vec::with_capacity(found.len());
+        for item_id in item_ids {
+            if let Some(item) = found.remove(item_id) {
+                self.track_item_access(*item_id).await;
+                items.push(item);
+            }
+        }
+
+        Ok(items)
+    }
+
     async fn list_items_by_entry(
         &self,
         entry_id: Uuid,
@@ -178,12 +345,13 @@ where
             .find_items_by_entry_id(entry_id, include_deleted)
             .await?;
 
-        // 各アイテムの例文を取得
+        // 各アイテムの例文と帰属表示を取得
         for item in &mut items {
             item.examples = self
                 .repository
                 .find_examples_by_item_id(item.item_id)
                 .await?;
+            item.attribution = self.repository.find_item_attribution(item.item_id).await?;
         }
 
         Ok(items)
@@ -236,3 +404,161 @@ where
         Ok(stats)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::application::{
+        cache_warmup::warm_up_then_mark_ready,
+        health_check_service::ReadinessGate,
+        test_support::{InMemoryCache, InMemoryReadModel, item},
+    };
+
+    fn plan(time_budget: Duration) -> WarmupPlan {
+        WarmupPlan {
+            top_accessed: 10,
+            recently_published: 10,
+            time_budget,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_item_reads_are_tracked_with_sampling() {
+        let repository = InMemoryReadModel::default();
+        let item_id = repository.insert(item("apple"));
+        let service =
+            VocabularyQueryService::new(repository.clone(), Some(InMemoryCache::default()))
+                .with_access_tracking(AccessTracker::new(3));
+
+        for _ in 0..7 {
+            service.get_item_by_id(item_id).await.unwrap();
+        }
+
+        // 7 回のうちサンプリングされた 2 回分が重み 3 で記録される
+        assert_eq!(repository.access_count(item_id), 6);
+    }
+
+    #[tokio::test]
+    async fn test_batch_returns_items_in_requested_order_with_learning_context() {
+        let repository = InMemoryReadModel::default();
+        let slang = repository.insert(VocabularyItem {
+            register: Some("slang".to_string()),
+            ..item("gonna")
+        });
+        let legal = repository.insert(VocabularyItem {
+            register: Some("formal".to_string()),
+            domain: Some("legal".to_string()),
+            usage_notes: Some("Civil wrong; not a crime".to_string()),
+            ..item("tort")
+        });
+        let cache = InMemoryCache::default();
+        let service = VocabularyQueryService::new(repository.clone(), Some(cache.clone()));
+
+        let missing = Uuid::new_v4();
+        let items = service
+            .get_items_by_ids(&[legal, missing, slang, legal])
+            .await
+            .unwrap();
+
+        // 存在しない ID は含まず、重複は一度だけ返す
+        let ids: Vec<Uuid> = items.iter().map(|i| i.item_id).collect();
+        assert_eq!(ids, vec![legal, slang]);
+        assert_eq!(items[0].domain.as_deref(), Some("legal"));
+        assert_eq!(
+            items[0].usage_notes.as_deref(),
+            Some("Civil wrong; not a crime")
+        );
+        assert_eq!(items[1].register.as_deref(), Some("slang"));
+
+        // 取得したアイテムはキャッシュされ、次回は Read Model を参照しない
+        assert!(cache.contains(&format!("vocabulary:item:{}", slang)));
+        let reads = repository.item_reads();
+        service.get_items_by_ids(&[slang, legal]).await.unwrap();
+        assert_eq!(repository.item_reads(), reads);
+    }
+
+    #[tokio::test]
+    async fn test_batch_rejects_too_many_ids() {
+        let service = VocabularyQueryService::new(
+            InMemoryReadModel::default(),
+            None::<InMemoryCache>,
+        );
+        let ids: Vec<Uuid> = (0..=MAX_BATCH_ITEMS).map(|_| Uuid::new_v4()).collect();
+
+        let result = service.get_items_by_ids(&ids).await;
+
+        assert!(matches!(result, Err(QueryError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_warmed_item_is_served_from_cache() {
+        let repository = InMemoryReadModel::default();
+        let popular = repository.insert(item("apple"));
+        let recent = repository.insert(item("banana"));
+        repository.most_accessed.lock().unwrap().push(popular);
+        repository.recently_published.lock().unwrap().push(recent);
+
+        let cache = InMemoryCache::default();
+        let service = VocabularyQueryService::new(repository.clone(), Some(cache.clone()));
+        let readiness = ReadinessGate::default();
+
+        let report =
+            warm_up_then_mark_ready(&service, &plan(Duration::from_secs(5)), &readiness).await;
+        assert_eq!(report.selected, 2);
+        assert_eq!(report.warmed, 2);
+        assert!(report.completed);
+        assert!(readiness.is_ready());
+        assert!(cache.contains(&format!("vocabulary:item:{}", popular)));
+
+        // 起動直後の読み取りは Read Model を参照せずキャッシュから返る
+        let reads_after_warmup = repository.item_reads();
+        let served = service.get_item_by_id(popular).await.unwrap().unwrap();
+        assert_eq!(served.spelling, "apple");
+        assert_eq!(repository.item_reads(), reads_after_warmup);
+
+        // ウォームアップ自体はアクセス数に含めない
+        assert_eq!(repository.access_count(popular), 0);
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_respects_time_budget() {
+        let repository = InMemoryReadModel {
+            item_read_delay: Some(Duration::from_millis(100)),
+            ..Default::default()
+        };
+        for spelling in ["a", "b", "c", "d", "e"] {
+            let item_id = repository.insert(item(spelling));
+            repository.most_accessed.lock().unwrap().push(item_id);
+        }
+
+        let service = VocabularyQueryService::new(repository, Some(InMemoryCache::default()));
+        let readiness = ReadinessGate::default();
+        assert!(!readiness.is_ready());
+
+        let started = std::time::Instant::now();
+        let report =
+            warm_up_then_mark_ready(&service, &plan(Duration::from_millis(250)), &readiness).await;
+
+        // 予算を超えて Ready が遅れることはなく、部分的な結果が報告される
+        assert!(started.elapsed() < Duration::from_millis(450));
+        assert!(readiness.is_ready());
+        assert_eq!(report.selected, 5);
+        assert!(report.warmed < 5);
+        assert!(!report.completed);
+        assert_eq!(readiness.warmup_report(), Some(report));
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_without_candidates_completes() {
+        let service = VocabularyQueryService::new(
+            InMemoryReadModel::default(),
+            Some(InMemoryCache::default()),
+        );
+
+        let report = service.warm_up(&plan(Duration::from_secs(1))).await;
+        assert_eq!(report.selected, 0);
+        assert!(report.completed);
+    }
+}
@@ -1,7 +1,9 @@
 //! ドメイン層
 
 pub mod models;
+pub mod study_sheet;
 pub mod value_objects;
 
 pub use models::*;
+pub use study_sheet::*;
 pub use value_objects::*;
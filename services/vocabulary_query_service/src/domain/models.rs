@@ -50,6 +50,15 @@ pub struct VocabularyItem {
     pub entry_id:          Uuid,
     pub spelling:          String,
     pub disambiguation:    Option<String>,
+    /// 使用域（formal / informal / slang など）
+    #[serde(default)]
+    pub register:          Option<String>,
+    /// 専門分野（medical / legal / general など）
+    #[serde(default)]
+    pub domain:            Option<String>,
+    /// 使用上の注意（学習時に解答とともに提示する）
+    #[serde(default)]
+    pub usage_notes:       Option<String>,
     pub part_of_speech:    Option<String>,
     pub definition:        Option<String>,
     pub ipa_pronunciation: Option<String>,
@@ -60,10 +69,42 @@ pub struct VocabularyItem {
     pub example_count:     i32,
     #[serde(default)]
     pub examples:          Vec<VocabularyExample>,
+    /// 外部ソースからインポートされた場合の帰属表示
+    #[serde(default)]
+    pub attribution:       Option<Attribution>,
     pub created_at:        DateTime<Utc>,
     pub updated_at:        DateTime<Utc>,
 }
 
+/// 外部ソースの帰属表示（ライセンス遵守のため表示が必要）
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Attribution {
+    pub source_name: String,
+    pub license:     String,
+    pub attribution: Option<String>,
+}
+
+impl Attribution {
+    /// 表示しなくてよいライセンス（パブリックドメイン相当）
+    const NOTICE_FREE_LICENSES: &'static [&'static str] =
+        &["CC0", "CC0-1.0", "PUBLIC-DOMAIN", "PUBLIC DOMAIN", "PD"];
+
+    /// 配布物（印刷物など）に帰属表示が必要かどうか
+    pub fn requires_notice(&self) -> bool {
+        !Self::NOTICE_FREE_LICENSES
+            .iter()
+            .any(|license| self.license.trim().eq_ignore_ascii_case(license))
+    }
+
+    /// 表示する帰属表示の文言
+    pub fn notice(&self) -> String {
+        match &self.attribution {
+            Some(text) if !text.trim().is_empty() => format!("{} ({})", text.trim(), self.license),
+            _ => format!("{} ({})", self.source_name, self.license),
+        }
+    }
+}
+
 /// 語彙項目の DB レコード
 #[derive(Debug, Clone, FromRow)]
 pub struct VocabularyItemRow {
@@ -71,6 +112,9 @@ pub struct VocabularyItemRow {
     pub entry_id:          Uuid,
     pub spelling:          String,
     pub disambiguation:    Option<String>,
+    pub register:          Option<String>,
+    pub domain:            Option<String>,
+    pub usage_notes:       Option<String>,
     pub part_of_speech:    Option<String>,
     pub definition:        Option<String>,
     pub ipa_pronunciation: Option<String>,
@@ -90,6 +134,9 @@ impl From<VocabularyItemRow> for VocabularyItem {
             entry_id:          row.entry_id,
             spelling:          row.spelling,
             disambiguation:    row.disambiguation,
+            register:          row.register,
+            domain:            row.domain,
+            usage_notes:       row.usage_notes,
             part_of_speech:    row.part_of_speech,
             definition:        row.definition,
             ipa_pronunciation: row.ipa_pronunciation,
@@ -99,6 +146,7 @@ impl From<VocabularyItemRow> for VocabularyItem {
             is_deleted:        row.is_deleted,
             example_count:     row.example_count,
             examples:          Vec::new(),
+            attribution:       None,
             created_at:        row.created_at,
             updated_at:        row.updated_at,
         }
@@ -0,0 +1,73 @@
+//! 印刷用の学習シート
+//!
+//! コレクションの語彙アイテムを印刷して学習するためのシート。
+//! レイアウトは単語リスト（2段組み）と、折り線付きの単語カード
+//! （左半分に単語、右半分に意味）の2種類。
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::models::VocabularyItem;
+use crate::error::QueryError;
+
+/// 学習シートのレイアウト
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StudySheetLayout {
+    /// 2段組みの単語リスト
+    #[default]
+    VocabularyList,
+    /// 縦の折り線で単語と意味を隠せる単語カード
+    FlashcardGrid,
+}
+
+impl StudySheetLayout {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::VocabularyList => "vocabulary_list",
+            Self::FlashcardGrid => "flashcard_grid",
+        }
+    }
+}
+
+impl fmt::Display for StudySheetLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for StudySheetLayout {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "vocabulary_list" | "list" => Ok(Self::VocabularyList),
+            "flashcard_grid" | "flashcards" => Ok(Self::FlashcardGrid),
+            _ => Err(QueryError::InvalidInput(format!(
+                "Unknown study sheet layout: {s}"
+            ))),
+        }
+    }
+}
+
+/// 描画する学習シートの内容
+#[derive(Debug, Clone)]
+pub struct StudySheet {
+    pub title:            String,
+    pub layout:           StudySheetLayout,
+    pub include_examples: bool,
+    /// コレクションへの追加順
+    pub items:            Vec<VocabularyItem>,
+    pub generated_at:     DateTime<Utc>,
+}
+
+/// 描画した学習シート
+#[derive(Debug, Clone)]
+pub struct RenderedStudySheet {
+    pub pdf:               Vec<u8>,
+    pub page_count:        usize,
+    /// フォントで表示できず `?` に置き換えた文字数
+    pub substituted_chars: usize,
+}
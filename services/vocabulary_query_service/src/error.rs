@@ -21,6 +21,12 @@ pub enum QueryError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    #[error("Blob store error: {0}")]
+    BlobStore(#[from] shared_blob_store::BlobStoreError),
+
     #[error("Configuration error: {0}")]
     Configuration(String),
 
@@ -1,5 +1,7 @@
 //! インフラストラクチャ層
 
+pub mod pdf;
 pub mod repositories;
 
+pub use pdf::PdfStudySheetRenderer;
 pub use repositories::*;
@@ -0,0 +1,234 @@
+//! PDF ドキュメントの書き出し
+//!
+//! PDF 1.4 のオブジェクト、相互参照表、トレーラーを直接書き出す。
+//! フォントは [`text`](super::text) の方針どおり埋め込まない。
+
+use std::fmt::Write as _;
+
+use super::text::{FontResource, Style, runs, win_ansi_byte};
+
+/// A4 の幅（ポイント）
+pub const PAGE_WIDTH: f32 = 595.0;
+/// A4 の高さ（ポイント）
+pub const PAGE_HEIGHT: f32 = 842.0;
+
+/// Adobe-Japan1 の CID フォント（非埋め込み、ビューアが代替フォントで表示する）
+const WIDE_FONT_NAME: &str = "KozGoPr6N-Medium";
+
+/// 線の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineStyle {
+    Solid,
+    /// 切り取り線
+    Cut,
+    /// 折り線
+    Fold,
+}
+
+impl LineStyle {
+    fn dash(self) -> &'static str {
+        match self {
+            Self::Solid => "[] 0 d",
+            Self::Cut => "[2 2] 0 d",
+            Self::Fold => "[6 3] 0 d",
+        }
+    }
+}
+
+/// 1ページの描画内容（コンテンツストリーム）
+#[derive(Debug, Default)]
+pub struct Canvas {
+    content:     String,
+    substituted: usize,
+}
+
+impl Canvas {
+    /// `(x, y)` をベースラインの左端として1行のテキストを描く
+    pub fn text(&mut self, x: f32, y: f32, style: Style, size: f32, text: &str) {
+        let (runs, substituted) = runs(text, style);
+        self.substituted += substituted;
+
+        let _ = write!(self.content, "BT {x:.2} {y:.2} Td ");
+        for run in runs {
+            let _ = write!(self.content, "/{} {size:.1} Tf ", run.font.name());
+            match run.font {
+                FontResource::Latin(_) => {
+                    self.content.push('(');
+                    for c in run.text.chars() {
+                        push_latin_byte(&mut self.content, c);
+                    }
+                    self.content.push_str(") Tj ");
+                },
+                FontResource::Wide => {
+                    self.content.push('<');
+                    for unit in run.text.encode_utf16() {
+                        let _ = write!(self.content, "{unit:04X}");
+                    }
+                    self.content.push_str("> Tj ");
+                },
+            }
+        }
+        self.content.push_str("ET\n");
+    }
+
+    /// 線を描く
+    pub fn line(&mut self, from: (f32, f32), to: (f32, f32), style: LineStyle) {
+        let _ = writeln!(
+            self.content,
+            "q 0.5 w 0.6 G {} {:.2} {:.2} m {:.2} {:.2} l S Q",
+            style.dash(),
+            from.0,
+            from.1,
+            to.0,
+            to.1
+        );
+    }
+
+    /// 表示できず置き換えた文字数
+    pub fn substituted(&self) -> usize {
+        self.substituted
+    }
+}
+
+/// WinAnsiEncoding の1文字を PDF のリテラル文字列として書く
+fn push_latin_byte(out: &mut String, c: char) {
+    match win_ansi_byte(c) {
+        Some(b'(') => out.push_str("\\("),
+        Some(b')') => out.push_str("\\)"),
+        Some(b'\\') => out.push_str("\\\\"),
+        Some(byte @ 0x20..=0x7e) => out.push(char::from(byte)),
+        Some(byte) => {
+            let _ = write!(out, "\\{byte:03o}");
+        },
+        None => out.push('?'),
+    }
+}
+
+/// 文書情報の文字列（ASCII 以外を含む場合は UTF-16BE）
+fn text_string(text: &str) -> String {
+    if text.chars().all(|c| (' '..='~').contains(&c)) {
+        let mut out = String::from("(");
+        for c in text.chars() {
+            push_latin_byte(&mut out, c);
+        }
+        out.push(')');
+        out
+    } else {
+        let mut out = String::from("<FEFF");
+        for unit in text.encode_utf16() {
+            let _ = write!(out, "{unit:04X}");
+        }
+        out.push('>');
+        out
+    }
+}
+
+/// ページを PDF ドキュメントとして書き出す
+pub fn write_pdf(title: &str, pages: &[Canvas]) -> Vec<u8> {
+    // 1: Catalog, 2: Pages, 3〜5: Helvetica 系, 6〜8: CID フォント, 9: Info,
+    // 10 以降: ページごとに (Page, Contents)
+    const FIRST_PAGE_ID: usize = 10;
+
+    let page_ids: Vec<usize> = (0..pages.len())
+        .map(|index| FIRST_PAGE_ID + index * 2)
+        .collect();
+    let kids: Vec<String> = page_ids.iter().map(|id| format!("{id} 0 R")).collect();
+
+    let mut objects: Vec<String> = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        ),
+        latin_font("Helvetica"),
+        latin_font("Helvetica-Bold"),
+        latin_font("Helvetica-Oblique"),
+        format!(
+            "<< /Type /Font /Subtype /Type0 /BaseFont /{WIDE_FONT_NAME} /Encoding /UniJIS-UTF16-H \
+             /DescendantFonts [7 0 R] >>"
+        ),
+        format!(
+            "<< /Type /Font /Subtype /CIDFontType0 /BaseFont /{WIDE_FONT_NAME} /CIDSystemInfo << \
+             /Registry (Adobe) /Ordering (Japan1) /Supplement 6 >> /FontDescriptor 8 0 R /DW 1000 \
+             >>"
+        ),
+        format!(
+            "<< /Type /FontDescriptor /FontName /{WIDE_FONT_NAME} /Flags 4 /FontBBox [-538 -374 \
+             1254 1418] /ItalicAngle 0 /Ascent 880 /Descent -120 /CapHeight 763 /StemV 116 >>"
+        ),
+        format!(
+            "<< /Title {} /Producer (effect vocabulary_query_service) >>",
+            text_string(title)
+        ),
+    ];
+
+    for (page, page_id) in pages.iter().zip(&page_ids) {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] /Resources \
+             << /Font << /F1 3 0 R /F2 4 0 R /F3 5 0 R /F4 6 0 R >> >> /Contents {} 0 R >>",
+            page_id + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            page.content.len(),
+            page.content
+        ));
+    }
+
+    let mut pdf: Vec<u8> = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{object}\nendobj\n", index + 1).as_bytes());
+    }
+
+    let xref_offset = pdf.len();
+    let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(trailer, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        trailer,
+        "trailer\n<< /Size {} /Root 1 0 R /Info 9 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+        objects.len() + 1
+    );
+    pdf.extend_from_slice(trailer.as_bytes());
+    pdf
+}
+
+fn latin_font(base_font: &str) -> String {
+    format!("<< /Type /Font /Subtype /Type1 /BaseFont /{base_font} /Encoding /WinAnsiEncoding >>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writes_objects_with_matching_xref_offsets() {
+        let mut page = Canvas::default();
+        page.text(40.0, 800.0, Style::Bold, 12.0, "(café) 学校");
+        page.line((0.0, 0.0), (10.0, 10.0), LineStyle::Fold);
+
+        let pdf = write_pdf("語彙シート", &[page, Canvas::default()]);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.contains("/Count 2"));
+        assert!(text.contains("/F2 12.0 Tf (\\(caf\\351\\) ) Tj /F4 12.0 Tf <5B666821> Tj"));
+        assert!(text.contains("[6 3] 0 d"));
+        assert!(text.contains("/Title <FEFF8A9E5F5930B730FC30C8>"));
+
+        // 相互参照表のオフセットがそれぞれのオブジェクトの先頭を指す
+        let xref_start = text.rfind("\nxref\n").unwrap() + 1;
+        for (index, entry) in text[xref_start..].lines().skip(3).take(13).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(
+                pdf[offset..].starts_with(format!("{} 0 obj", index + 1).as_bytes()),
+                "object {}",
+                index + 1
+            );
+        }
+    }
+}
@@ -0,0 +1,646 @@
+//! 学習シートの PDF 描画
+//!
+//! - 単語リスト: 2段組みで、アイテムごとに単語・発音・品詞・意味・
+//!   例文を流し込む
+//! - 単語カード: 1ページ 8 行の表で、左半分に単語、右半分に意味を置く。
+//!   中央の折り線で折ると意味を隠して練習でき、行の間の切り取り線で
+//!   カードに切り分けられる
+//!
+//! 帰属表示が必要なアイテムを載せたページには、フッターに出典を表示する。
+
+pub mod document;
+pub mod text;
+
+use std::{collections::BTreeSet, time::Instant};
+
+use document::{Canvas, LineStyle, PAGE_HEIGHT, PAGE_WIDTH, write_pdf};
+use text::{Style, is_wide, measure, truncate_lines, win_ansi_byte, wrap};
+
+use crate::{
+    domain::{RenderedStudySheet, StudySheet, StudySheetLayout, VocabularyItem},
+    error::{QueryError, Result},
+    ports::outbound::StudySheetRenderer,
+};
+
+const MARGIN: f32 = 40.0;
+/// 本文の上端（ヘッダーの下）
+const BODY_TOP: f32 = PAGE_HEIGHT - MARGIN - 30.0;
+/// 本文の下端（帰属表示とページ番号の上）
+const BODY_BOTTOM: f32 = 72.0;
+const LINE_SPACING: f32 = 1.35;
+
+/// 単語リストの段の間隔
+const COLUMN_GAP: f32 = 20.0;
+/// 単語リストのアイテムの間隔
+const ITEM_GAP: f32 = 10.0;
+/// 1アイテムに載せる例文の数
+const EXAMPLES_PER_ITEM: usize = 2;
+
+/// 単語カードの1ページあたりの行数
+pub const FLASHCARD_ROWS_PER_PAGE: usize = 8;
+const CARD_PADDING: f32 = 10.0;
+
+/// フッターの帰属表示の最大行数
+const MAX_NOTICE_LINES: usize = 3;
+const NOTICE_SIZE: f32 = 7.0;
+
+/// 描画中のページ
+#[derive(Default)]
+struct Page {
+    canvas:  Canvas,
+    /// このページに載せたアイテムの帰属表示
+    notices: BTreeSet<String>,
+}
+
+impl Page {
+    fn add_notice(&mut self, item: &VocabularyItem) {
+        if let Some(attribution) = &item.attribution
+            && attribution.requires_notice()
+        {
+            self.notices.insert(attribution.notice());
+        }
+    }
+}
+
+/// 1行分のテキスト
+struct Line {
+    style:  Style,
+    size:   f32,
+    indent: f32,
+    text:   String,
+}
+
+impl Line {
+    fn height(&self) -> f32 {
+        self.size * LINE_SPACING
+    }
+}
+
+/// 折り返した段落を行にする
+fn paragraph(text: &str, style: Style, size: f32, indent: f32, width: f32) -> Vec<Line> {
+    wrap(text, style, size, width - indent)
+        .into_iter()
+        .map(|text| Line {
+            style,
+            size,
+            indent,
+            text,
+        })
+        .collect()
+}
+
+/// すべての文字を標準フォントで表示できるかどうか
+fn is_representable(text: &str) -> bool {
+    text.chars()
+        .all(|c| win_ansi_byte(c).is_some() || is_wide(c) || c.is_whitespace())
+}
+
+/// 見出し（綴りと語義の区別）
+fn heading(item: &VocabularyItem) -> String {
+    match &item.disambiguation {
+        Some(disambiguation) => format!("{} ({disambiguation})", item.spelling),
+        None => item.spelling.clone(),
+    }
+}
+
+/// 発音・品詞・CEFR レベルの1行
+///
+/// 発音記号は標準フォントで表示できない記号が多いため、`?` だらけに
+/// ならないよう表示できる場合だけ載せる
+fn details(item: &VocabularyItem) -> Option<String> {
+    let details: Vec<&str> = [
+        item.ipa_pronunciation
+            .as_deref()
+            .filter(|ipa| is_representable(ipa)),
+        item.part_of_speech.as_deref(),
+        item.cefr_level.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    (!details.is_empty()).then(|| details.join("  ·  "))
+}
+
+fn check_deadline(deadline: Instant) -> Result<()> {
+    if Instant::now() >= deadline {
+        return Err(QueryError::Timeout(
+            "study sheet rendering took too long".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// PDF の学習シート描画
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PdfStudySheetRenderer;
+
+impl StudySheetRenderer for PdfStudySheetRenderer {
+    fn render(&self, sheet: &StudySheet, deadline: Instant) -> Result<RenderedStudySheet> {
+        let mut pages = match sheet.layout {
+            StudySheetLayout::VocabularyList => vocabulary_list(sheet, deadline)?,
+            StudySheetLayout::FlashcardGrid => flashcard_grid(sheet, deadline)?,
+        };
+
+        let page_count = pages.len();
+        for (index, page) in pages.iter_mut().enumerate() {
+            draw_header(page, sheet);
+            draw_footer(page, index + 1, page_count);
+        }
+
+        let canvases: Vec<Canvas> = pages.into_iter().map(|page| page.canvas).collect();
+        Ok(RenderedStudySheet {
+            substituted_chars: canvases.iter().map(Canvas::substituted).sum(),
+            pdf: write_pdf(&sheet.title, &canvases),
+            page_count,
+        })
+    }
+}
+
+fn draw_header(page: &mut Page, sheet: &StudySheet) {
+    let width = PAGE_WIDTH - MARGIN * 2.0;
+    let summary = format!(
+        "{} items  ·  {}",
+        sheet.items.len(),
+        sheet.generated_at.format("%Y-%m-%d")
+    );
+    let summary_width = measure(&summary, Style::Regular, 8.0);
+
+    let title = truncate_lines(
+        wrap(
+            &sheet.title,
+            Style::Bold,
+            14.0,
+            width - summary_width - 10.0,
+        ),
+        1,
+        Style::Bold,
+        14.0,
+        width - summary_width - 10.0,
+    );
+    let baseline = PAGE_HEIGHT - MARGIN - 14.0;
+    if let Some(title) = title.first() {
+        page.canvas.text(MARGIN, baseline, Style::Bold, 14.0, title);
+    }
+    page.canvas.text(
+        PAGE_WIDTH - MARGIN - summary_width,
+        baseline,
+        Style::Regular,
+        8.0,
+        &summary,
+    );
+    page.canvas.line(
+        (MARGIN, baseline - 8.0),
+        (PAGE_WIDTH - MARGIN, baseline - 8.0),
+        LineStyle::Solid,
+    );
+}
+
+fn draw_footer(page: &mut Page, number: usize, page_count: usize) {
+    let width = PAGE_WIDTH - MARGIN * 2.0;
+
+    if !page.notices.is_empty() {
+        let notices: Vec<&str> = page.notices.iter().map(String::as_str).collect();
+        let lines = truncate_lines(
+            wrap(
+                &format!("Sources: {}", notices.join("; ")),
+                Style::Regular,
+                NOTICE_SIZE,
+                width,
+            ),
+            MAX_NOTICE_LINES,
+            Style::Regular,
+            NOTICE_SIZE,
+            width,
+        );
+        let mut baseline = BODY_BOTTOM - NOTICE_SIZE - 4.0;
+        for line in lines {
+            page.canvas
+                .text(MARGIN, baseline, Style::Regular, NOTICE_SIZE, &line);
+            baseline -= NOTICE_SIZE * LINE_SPACING;
+        }
+    }
+
+    let number = format!("{number} / {page_count}");
+    page.canvas.text(
+        (PAGE_WIDTH - measure(&number, Style::Regular, 8.0)) / 2.0,
+        MARGIN - 16.0,
+        Style::Regular,
+        8.0,
+        &number,
+    );
+}
+
+/// 単語リストの流し込み位置
+struct Flow {
+    pages:  Vec<Page>,
+    page:   Page,
+    column: usize,
+    /// 次の行の上端
+    cursor: f32,
+}
+
+impl Flow {
+    fn new() -> Self {
+        Self {
+            pages:  Vec::new(),
+            page:   Page::default(),
+            column: 0,
+            cursor: BODY_TOP,
+        }
+    }
+
+    fn next_column(&mut self) {
+        self.column += 1;
+        if self.column == 2 {
+            self.pages.push(std::mem::take(&mut self.page));
+            self.column = 0;
+        }
+        self.cursor = BODY_TOP;
+    }
+
+    fn finish(mut self) -> Vec<Page> {
+        self.pages.push(self.page);
+        self.pages
+    }
+}
+
+/// 2段組みの単語リスト
+fn vocabulary_list(sheet: &StudySheet, deadline: Instant) -> Result<Vec<Page>> {
+    let column_width = (PAGE_WIDTH - MARGIN * 2.0 - COLUMN_GAP) / 2.0;
+    let mut flow = Flow::new();
+
+    for item in &sheet.items {
+        check_deadline(deadline)?;
+
+        let mut lines = paragraph(&heading(item), Style::Bold, 11.0, 0.0, column_width);
+        if let Some(details) = details(item) {
+            lines.extend(paragraph(&details, Style::Regular, 8.0, 0.0, column_width));
+        }
+        match &item.definition {
+            Some(definition) => {
+                lines.extend(paragraph(
+                    definition,
+                    Style::Regular,
+                    9.0,
+                    0.0,
+                    column_width,
+                ));
+            },
+            None => lines.extend(paragraph(
+                "(no definition)",
+                Style::Oblique,
+                9.0,
+                0.0,
+                column_width,
+            )),
+        }
+        if sheet.include_examples {
+            for example in item.examples.iter().take(EXAMPLES_PER_ITEM) {
+                lines.extend(paragraph(
+                    &format!("• {}", example.example),
+                    Style::Oblique,
+                    8.5,
+                    8.0,
+                    column_width,
+                ));
+                if let Some(translation) = &example.translation {
+                    lines.extend(paragraph(
+                        translation,
+                        Style::Regular,
+                        8.0,
+                        16.0,
+                        column_width,
+                    ));
+                }
+            }
+        }
+
+        // 段の途中から始めてはみ出す場合は次の段に送る（1段より長いアイテムは分割する）
+        let block_height: f32 = lines.iter().map(Line::height).sum();
+        if flow.cursor - block_height < BODY_BOTTOM && flow.cursor < BODY_TOP {
+            flow.next_column();
+        }
+
+        flow.page.add_notice(item);
+        for line in lines {
+            if flow.cursor - line.height() < BODY_BOTTOM {
+                flow.next_column();
+                flow.page.add_notice(item);
+            }
+            let x = MARGIN + (column_width + COLUMN_GAP) * flow.column as f32 + line.indent;
+            flow.page.canvas.text(
+                x,
+                flow.cursor - line.size,
+                line.style,
+                line.size,
+                &line.text,
+            );
+            flow.cursor -= line.height();
+        }
+        flow.cursor -= ITEM_GAP;
+    }
+
+    Ok(flow.finish())
+}
+
+/// 折り線付きの単語カード
+fn flashcard_grid(sheet: &StudySheet, deadline: Instant) -> Result<Vec<Page>> {
+    let row_height = (BODY_TOP - BODY_BOTTOM) / FLASHCARD_ROWS_PER_PAGE as f32;
+    let fold_x = PAGE_WIDTH / 2.0;
+    let cell_width = fold_x - MARGIN - CARD_PADDING * 2.0;
+    let cell_height = row_height - CARD_PADDING * 2.0;
+
+    let mut pages = Vec::new();
+    for chunk in sheet.items.chunks(FLASHCARD_ROWS_PER_PAGE) {
+        let mut page = Page::default();
+
+        // 切り取り線と折り線
+        for row in 0..=chunk.len() {
+            let y = BODY_TOP - row_height * row as f32;
+            page.canvas
+                .line((MARGIN, y), (PAGE_WIDTH - MARGIN, y), LineStyle::Cut);
+        }
+        let grid_bottom = BODY_TOP - row_height * chunk.len() as f32;
+        page.canvas
+            .line((fold_x, BODY_TOP), (fold_x, grid_bottom), LineStyle::Fold);
+        page.canvas
+            .text(fold_x + 3.0, BODY_TOP + 3.0, Style::Oblique, 6.0, "fold");
+
+        for (row, item) in chunk.iter().enumerate() {
+            check_deadline(deadline)?;
+            page.add_notice(item);
+
+            let top = BODY_TOP - row_height * row as f32 - CARD_PADDING;
+
+            // 表面: 単語と発音・品詞
+            let mut front = fit(
+                paragraph(&heading(item), Style::Bold, 13.0, 0.0, cell_width),
+                2,
+                cell_width,
+            );
+            if let Some(details) = details(item) {
+                front.extend(fit(
+                    paragraph(&details, Style::Regular, 8.0, 0.0, cell_width),
+                    1,
+                    cell_width,
+                ));
+            }
+            draw_cell(
+                &mut page.canvas,
+                MARGIN + CARD_PADDING,
+                top,
+                cell_height,
+                cell_width,
+                front,
+            );
+
+            // 裏面: 意味と例文
+            let mut back = paragraph(
+                item.definition.as_deref().unwrap_or("(no definition)"),
+                Style::Regular,
+                9.0,
+                0.0,
+                cell_width,
+            );
+            if sheet.include_examples
+                && let Some(example) = item.examples.first()
+            {
+                back.extend(paragraph(
+                    &format!("• {}", example.example),
+                    Style::Oblique,
+                    8.0,
+                    0.0,
+                    cell_width,
+                ));
+            }
+            draw_cell(
+                &mut page.canvas,
+                fold_x + CARD_PADDING,
+                top,
+                cell_height,
+                cell_width,
+                back,
+            );
+        }
+
+        pages.push(page);
+    }
+
+    if pages.is_empty() {
+        pages.push(Page::default());
+    }
+    Ok(pages)
+}
+
+/// 行の末尾を `…` にして幅に収める
+fn ellipsize(line: &mut Line, width: f32) {
+    while !line.text.is_empty()
+        && measure(&format!("{}…", line.text), line.style, line.size) > width - line.indent
+    {
+        line.text.pop();
+    }
+    line.text.push('…');
+}
+
+/// 行数を制限する（切り詰めた場合は最後の行を `…` で終える）
+fn fit(mut lines: Vec<Line>, max_lines: usize, width: f32) -> Vec<Line> {
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        if let Some(last) = lines.last_mut() {
+            ellipsize(last, width);
+        }
+    }
+    lines
+}
+
+/// セルの高さに収まる行だけを上から描く（収まらない行がある場合は最後の行を
+/// `…` で終える）
+fn draw_cell(canvas: &mut Canvas, x: f32, top: f32, height: f32, width: f32, lines: Vec<Line>) {
+    let mut used = 0.0;
+    let fitting = lines
+        .iter()
+        .take_while(|line| {
+            used += line.height();
+            used <= height
+        })
+        .count();
+    let lines = fit(lines, fitting, width);
+
+    let mut cursor = top;
+    for line in lines {
+        canvas.text(
+            x + line.indent,
+            cursor - line.size,
+            line.style,
+            line.size,
+            &line.text,
+        );
+        cursor -= line.height();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::{
+        application::test_support::item,
+        domain::{Attribution, VocabularyExample},
+    };
+
+    fn word(index: usize) -> VocabularyItem {
+        VocabularyItem {
+            definition: Some(format!("meaning number {index}")),
+            ..item(&format!("word{index}"))
+        }
+    }
+
+    fn sheet(layout: StudySheetLayout, items: Vec<VocabularyItem>) -> StudySheet {
+        StudySheet {
+            title: "Study sheet".to_string(),
+            layout,
+            include_examples: true,
+            items,
+            generated_at: Utc::now(),
+        }
+    }
+
+    fn render(sheet: &StudySheet) -> RenderedStudySheet {
+        PdfStudySheetRenderer
+            .render(sheet, Instant::now() + Duration::from_secs(10))
+            .unwrap()
+    }
+
+    fn pdf_text(rendered: &RenderedStudySheet) -> String {
+        String::from_utf8_lossy(&rendered.pdf).into_owned()
+    }
+
+    fn attributed(mut item: VocabularyItem, source: &str, license: &str) -> VocabularyItem {
+        item.attribution = Some(Attribution {
+            source_name: source.to_string(),
+            license:     license.to_string(),
+            attribution: None,
+        });
+        item
+    }
+
+    #[test]
+    fn test_vocabulary_list_flows_two_columns_per_page() {
+        // 見出し・意味の2行のアイテムは1段に 19 件、1ページに 38 件入る
+        let rendered = render(&sheet(
+            StudySheetLayout::VocabularyList,
+            (0..80).map(word).collect(),
+        ));
+
+        assert_eq!(rendered.page_count, 3);
+        let text = pdf_text(&rendered);
+        assert!(text.contains("/Count 3"));
+        assert_eq!(text.matches("/Type /Page ").count(), 3);
+        assert!(text.contains("(1 / 3) Tj"));
+        assert!(text.contains("(word79) Tj"));
+        assert_eq!(rendered.substituted_chars, 0);
+    }
+
+    #[test]
+    fn test_layout_switches_between_list_and_fold_line_flashcards() {
+        let items: Vec<VocabularyItem> = (0..17).map(word).collect();
+
+        let list = render(&sheet(StudySheetLayout::VocabularyList, items.clone()));
+        let cards = render(&sheet(StudySheetLayout::FlashcardGrid, items));
+
+        assert_eq!(list.page_count, 1);
+        assert_eq!(cards.page_count, 3);
+        assert!(!pdf_text(&list).contains("[6 3] 0 d"));
+        let cards = pdf_text(&cards);
+        // 3ページそれぞれに折り線、行の間に切り取り線（8 + 8 + 1 行）
+        assert_eq!(cards.matches("[6 3] 0 d").count(), 3);
+        assert_eq!(cards.matches("[2 2] 0 d").count(), 9 + 9 + 2);
+    }
+
+    #[test]
+    fn test_examples_are_included_only_when_requested() {
+        let mut with_example = word(0);
+        with_example.examples.push(VocabularyExample {
+            example_id:  Uuid::new_v4(),
+            item_id:     with_example.item_id,
+            example:     "She clarified her point.".to_string(),
+            translation: Some("彼女は要点を明確にした。".to_string()),
+            added_by:    None,
+            created_at:  Utc::now(),
+        });
+
+        let mut sheet = sheet(StudySheetLayout::VocabularyList, vec![with_example]);
+        let included = pdf_text(&render(&sheet));
+        sheet.include_examples = false;
+        let excluded = pdf_text(&render(&sheet));
+
+        assert!(included.contains("She clarified her point."));
+        assert!(included.contains("/F4 8.0 Tf <5F7C5973306F8981"));
+        assert!(!excluded.contains("She clarified her point."));
+    }
+
+    #[test]
+    fn test_attribution_footer_only_on_pages_with_attributed_items() {
+        let mut items: Vec<VocabularyItem> = (0..80).map(word).collect();
+        items[79] = attributed(word(79), "Wiktionary", "CC BY-SA 4.0");
+        items[0] = attributed(word(0), "Public Domain Dictionary", "CC0");
+
+        let text = pdf_text(&render(&sheet(StudySheetLayout::VocabularyList, items)));
+
+        assert_eq!(
+            text.matches("(Sources: Wiktionary \\(CC BY-SA 4.0\\)) Tj")
+                .count(),
+            1
+        );
+        // パブリックドメインの出典は表示しない
+        assert!(!text.contains("Public Domain Dictionary"));
+        let last_page = text.rfind("(3 / 3) Tj").unwrap();
+        let notice = text.find("(Sources: Wiktionary").unwrap();
+        assert!(text[..notice].contains("(2 / 3) Tj") && notice < last_page);
+    }
+
+    #[test]
+    fn test_non_latin_text_is_encoded_or_substituted_not_garbled() {
+        let japanese = VocabularyItem {
+            definition: Some("物事をより詳しく説明して明らかにすること".repeat(3)),
+            ipa_pronunciation: Some("/ˈklærɪfaɪ/".to_string()),
+            part_of_speech: Some("verb".to_string()),
+            ..item("clarify")
+        };
+        let korean = VocabularyItem {
+            definition: Some("분명히 하다".to_string()),
+            ..item("clarify")
+        };
+
+        let rendered = render(&sheet(
+            StudySheetLayout::VocabularyList,
+            vec![japanese, korean],
+        ));
+        let text = pdf_text(&rendered);
+
+        // 日本語は CID フォントの UTF-16 で出力し、段の幅で折り返す
+        assert!(text.contains("/F4 9.0 Tf <7269"));
+        assert!(text.matches("/F4 9.0 Tf").count() > 1);
+        // 表示できない発音記号は載せず、品詞だけを表示する
+        assert!(text.contains("(verb) Tj"));
+        assert!(!text.contains("kl?r"));
+        // ハングルは置き換えて数える
+        assert!(text.contains("(??? ??) Tj"));
+        assert_eq!(rendered.substituted_chars, 5);
+    }
+
+    #[test]
+    fn test_rendering_stops_at_the_deadline() {
+        let result = PdfStudySheetRenderer.render(
+            &sheet(StudySheetLayout::FlashcardGrid, vec![word(0)]),
+            Instant::now(),
+        );
+
+        assert!(matches!(result, Err(QueryError::Timeout(_))));
+    }
+}
@@ -0,0 +1,339 @@
+//! フォントの選択、幅の計測、折り返し
+//!
+//! フォントは埋め込まず、PDF ビューアが必ず持つ標準フォントを使う。
+//!
+//! - ラテン文字（WinAnsiEncoding で表せる文字）: Helvetica 系
+//! - 日本語・中国語の漢字、かな、全角記号、ギリシャ文字、キリル文字:
+//!   Adobe-Japan1 の CID フォント（全角幅で計測する）
+//! - どちらでも表せない文字（IPA 記号、ハングルなど）: `?` に置き換えて数える
+//!
+//! 文字化けしたバイト列を出力しないことと、表示幅を計測して枠内で
+//! 折り返すことを優先し、合字やカーニングなどの組版は行わない。
+
+/// ラテン文字の書体
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Regular,
+    Bold,
+    Oblique,
+}
+
+/// PDF のフォントリソース
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontResource {
+    Latin(Style),
+    /// Adobe-Japan1 の CID フォント
+    Wide,
+}
+
+impl FontResource {
+    /// ページのリソース辞書での名前
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Latin(Style::Regular) => "F1",
+            Self::Latin(Style::Bold) => "F2",
+            Self::Latin(Style::Oblique) => "F3",
+            Self::Wide => "F4",
+        }
+    }
+}
+
+/// 表示できない文字の代わりに出力する文字
+pub const SUBSTITUTE: char = '?';
+
+/// Helvetica の ASCII（0x20〜0x7E）の文字幅（1000 分率）
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // 0x20
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0x30
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // 0x40
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // 0x50
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // 0x60
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // 0x70
+];
+
+/// Helvetica-Bold の ASCII（0x20〜0x7E）の文字幅（1000 分率）
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, // 0x20
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, // 0x30
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, // 0x40
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556, // 0x50
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, // 0x60
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584, // 0x70
+];
+
+/// ASCII 以外のラテン文字の幅（アクセント付き文字の多くはこの幅）
+const LATIN_FALLBACK_WIDTH: u16 = 611;
+
+/// 全角文字の幅
+const WIDE_WIDTH: u16 = 1000;
+
+/// WinAnsiEncoding（Windows-1252）でのバイト
+pub fn win_ansi_byte(c: char) -> Option<u8> {
+    match c {
+        ' '..='~' => Some(c as u8),
+        '\u{a0}'..='\u{ff}' => u8::try_from(u32::from(c)).ok(),
+        '€' => Some(0x80),
+        '‚' => Some(0x82),
+        '„' => Some(0x84),
+        '…' => Some(0x85),
+        '‘' => Some(0x91),
+        '’' => Some(0x92),
+        '“' => Some(0x93),
+        '”' => Some(0x94),
+        '•' => Some(0x95),
+        '–' => Some(0x96),
+        '—' => Some(0x97),
+        '™' => Some(0x99),
+        _ => None,
+    }
+}
+
+/// Adobe-Japan1 の CID フォントで表示する文字
+pub fn is_wide(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0370}'..='\u{03ff}' // ギリシャ文字
+            | '\u{0400}'..='\u{04ff}' // キリル文字
+            | '\u{3000}'..='\u{30ff}' // 全角記号、ひらがな、カタカナ
+            | '\u{31f0}'..='\u{31ff}' // カタカナ拡張
+            | '\u{3400}'..='\u{4dbf}' // CJK 統合漢字拡張 A
+            | '\u{4e00}'..='\u{9fff}' // CJK 統合漢字
+            | '\u{f900}'..='\u{faff}' // CJK 互換漢字
+            | '\u{ff01}'..='\u{ff9f}' // 全角英数字、半角カタカナ
+    )
+}
+
+/// 文字を出力するフォントと、置き換えが必要かどうか
+fn classify(c: char, style: Style) -> (FontResource, char, bool) {
+    if win_ansi_byte(c).is_some() {
+        (FontResource::Latin(style), c, false)
+    } else if is_wide(c) {
+        (FontResource::Wide, c, false)
+    } else if c.is_whitespace() {
+        (FontResource::Latin(style), ' ', false)
+    } else {
+        (FontResource::Latin(style), SUBSTITUTE, true)
+    }
+}
+
+fn char_width(c: char, style: Style) -> u16 {
+    match classify(c, style) {
+        (FontResource::Wide, ..) => WIDE_WIDTH,
+        (FontResource::Latin(style), c, _) => {
+            let table = match style {
+                Style::Bold => &HELVETICA_BOLD_WIDTHS,
+                Style::Regular | Style::Oblique => &HELVETICA_WIDTHS,
+            };
+            (c as usize)
+                .checked_sub(0x20)
+                .and_then(|index| table.get(index))
+                .copied()
+                .unwrap_or(LATIN_FALLBACK_WIDTH)
+        },
+    }
+}
+
+/// 文字列の表示幅（ポイント）
+pub fn measure(text: &str, style: Style, size: f32) -> f32 {
+    let units: u32 = text.chars().map(|c| u32::from(char_width(c, style))).sum();
+    #[allow(clippy::cast_precision_loss)]
+    let units = units as f32;
+    units * size / 1000.0
+}
+
+/// 同じフォントで出力する連続した文字
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub font: FontResource,
+    pub text: String,
+}
+
+/// 文字列をフォントごとに分け、置き換えた文字数とともに返す
+pub fn runs(text: &str, style: Style) -> (Vec<Run>, usize) {
+    let mut runs: Vec<Run> = Vec::new();
+    let mut substituted = 0;
+
+    for c in text.chars() {
+        let (font, c, replaced) = classify(c, style);
+        if replaced {
+            substituted += 1;
+        }
+        match runs.last_mut() {
+            Some(run) if run.font == font => run.text.push(c),
+            _ => runs.push(Run {
+                font,
+                text: c.to_string(),
+            }),
+        }
+    }
+
+    (runs, substituted)
+}
+
+/// 折り返しの単位に分ける
+///
+/// 全角文字はどこでも改行できるため1文字ずつ、それ以外は連続する文字を
+/// まとめる。空白で区切った単語ごとに返す
+fn words(text: &str) -> Vec<Vec<String>> {
+    text.split_whitespace()
+        .map(|word| {
+            let mut pieces: Vec<String> = Vec::new();
+            let mut previous_wide = true;
+            for c in word.chars() {
+                let wide = is_wide(c);
+                match pieces.last_mut() {
+                    Some(piece) if !wide && !previous_wide => piece.push(c),
+                    _ => pieces.push(c.to_string()),
+                }
+                previous_wide = wide;
+            }
+            pieces
+        })
+        .collect()
+}
+
+/// `max_width` に収まるように折り返す
+///
+/// 単語の途中では改行しない。1単語で `max_width` を超える場合だけ
+/// 文字の途中で改行する
+pub fn wrap(text: &str, style: Style, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in words(text) {
+        for (index, piece) in word.iter().enumerate() {
+            let separator = if index == 0 && !line.is_empty() {
+                " "
+            } else {
+                ""
+            };
+            let candidate = format!("{line}{separator}{piece}");
+            if measure(&candidate, style, size) <= max_width {
+                line = candidate;
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            for c in piece.chars() {
+                line.push(c);
+                if measure(&line, style, size) > max_width && line.chars().count() > 1 {
+                    line.pop();
+                    lines.push(std::mem::replace(&mut line, c.to_string()));
+                }
+            }
+        }
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// 折り返した行を `max_lines` 行までにし、切り詰めた場合は末尾に `…` を付ける
+pub fn truncate_lines(
+    mut lines: Vec<String>,
+    max_lines: usize,
+    style: Style,
+    size: f32,
+    max_width: f32,
+) -> Vec<String> {
+    if lines.len() <= max_lines {
+        return lines;
+    }
+
+    lines.truncate(max_lines);
+    if let Some(last) = lines.last_mut() {
+        while !last.is_empty() && measure(&format!("{last}…"), style, size) > max_width {
+            last.pop();
+        }
+        last.push('…');
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measures_with_helvetica_metrics_and_full_width_cjk() {
+        assert!((measure("Hello", Style::Regular, 10.0) - 22.78).abs() < 0.01);
+        assert!(measure("Hello", Style::Bold, 10.0) > measure("Hello", Style::Regular, 10.0));
+        assert!((measure("日本語", Style::Regular, 10.0) - 30.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_splits_runs_by_font_and_substitutes_unsupported_characters() {
+        let (runs, substituted) = runs("café 学校 ˈskuːl", Style::Regular);
+
+        assert_eq!(
+            runs,
+            vec![
+                Run {
+                    font: FontResource::Latin(Style::Regular),
+                    text: "café ".to_string(),
+                },
+                Run {
+                    font: FontResource::Wide,
+                    text: "学校".to_string(),
+                },
+                Run {
+                    font: FontResource::Latin(Style::Regular),
+                    text: " ?sku?l".to_string(),
+                },
+            ]
+        );
+        assert_eq!(substituted, 2);
+    }
+
+    #[test]
+    fn test_wraps_latin_at_spaces_and_cjk_anywhere() {
+        let latin = wrap(
+            "to make something clear by describing it in more detail",
+            Style::Regular,
+            9.0,
+            100.0,
+        );
+        assert!(latin.len() > 1);
+        for line in &latin {
+            assert!(measure(line, Style::Regular, 9.0) <= 100.0, "{line}");
+            assert!(!line.starts_with(' ') && !line.ends_with(' '));
+        }
+        assert_eq!(
+            latin.join(" "),
+            "to make something clear by describing it in more detail"
+        );
+
+        let japanese = wrap(
+            "物事をより詳しく説明して明らかにすること",
+            Style::Regular,
+            10.0,
+            55.0,
+        );
+        assert_eq!(japanese[0], "物事をより");
+        assert!(japanese.iter().all(|line| line.chars().count() <= 5));
+        assert_eq!(
+            japanese.concat(),
+            "物事をより詳しく説明して明らかにすること"
+        );
+    }
+
+    #[test]
+    fn test_breaks_words_longer_than_the_line_and_truncates_with_ellipsis() {
+        let lines = wrap("pneumonoultramicroscopic", Style::Bold, 12.0, 60.0);
+        assert!(lines.len() > 1);
+        assert!(
+            lines
+                .iter()
+                .all(|line| measure(line, Style::Bold, 12.0) <= 60.0)
+        );
+
+        let truncated = truncate_lines(lines, 1, Style::Bold, 12.0, 60.0);
+        assert_eq!(truncated.len(), 1);
+        assert!(truncated[0].ends_with('…'));
+        assert!(measure(&truncated[0], Style::Bold, 12.0) <= 60.0);
+    }
+}
@@ -1,5 +1,7 @@
 //! リポジトリ実装
 
 pub mod postgres_read_model;
+pub mod shared_cache;
 
 pub use postgres_read_model::PostgresReadModelRepository;
+pub use shared_cache::SharedCacheRepository;
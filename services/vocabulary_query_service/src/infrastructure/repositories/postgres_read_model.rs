@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use crate::{
     domain::{
+        Attribution,
         Cursor,
         PageInfo,
         PageSize,
@@ -23,7 +24,7 @@ use crate::{
         VocabularyStatistics,
     },
     error::{QueryError, Result},
-    ports::outbound::ReadModelRepository,
+    ports::outbound::{CollectionRepository, ReadModelRepository},
 };
 
 /// PostgreSQL Read Model リポジトリ
@@ -245,6 +246,9 @@ impl ReadModelRepository for PostgresReadModelRepository {
                 entry_id,
                 spelling,
                 disambiguation,
+                register,
+                domain,
+                usage_notes,
                 part_of_speech,
                 definition,
                 ipa_pronunciation,
@@ -266,6 +270,42 @@ impl ReadModelRepository for PostgresReadModelRepository {
         Ok(row.map(VocabularyItem::from))
     }
 
+    async fn find_items_by_ids(&self, item_ids: &[Uuid]) -> Result<Vec<VocabularyItem>> {
+        let items = sqlx::query_as!(
+            VocabularyItemRow,
+            r#"
+            SELECT 
+                item_id,
+                entry_id,
+                spelling,
+                disambiguation,
+                register,
+                domain,
+                usage_notes,
+                part_of_speech,
+                definition,
+                ipa_pronunciation,
+                cefr_level,
+                frequency_rank,
+                is_published,
+                is_deleted,
+                example_count,
+                created_at,
+                updated_at
+            FROM vocabulary_items_read
+            WHERE item_id = ANY($1)
+            "#,
+            item_ids
+        )
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(VocabularyItem::from)
+        .collect();
+
+        Ok(items)
+    }
+
     async fn find_items_by_entry_id(
         &self,
         entry_id: Uuid,
@@ -280,6 +320,9 @@ impl ReadModelRepository for PostgresReadModelRepository {
                     entry_id,
                     spelling,
                     disambiguation,
+                    register,
+                    domain,
+                    usage_notes,
                     part_of_speech,
                     definition,
                     ipa_pronunciation,
@@ -310,6 +353,9 @@ impl ReadModelRepository for PostgresReadModelRepository {
                     entry_id,
                     spelling,
                     disambiguation,
+                    register,
+                    domain,
+                    usage_notes,
                     part_of_speech,
                     definition,
                     ipa_pronunciation,
@@ -359,28 +405,44 @@ impl ReadModelRepository for PostgresReadModelRepository {
     }
 
     async fn find_examples_by_item_id(&self, item_id: Uuid) -> Result<Vec<VocabularyExample>> {
-        let examples = sqlx::query_as!(
-            VocabularyExample,
+        let examples = sqlx::query_as::<_, VocabularyExample>(
             r#"
-            SELECT 
-                example_id,
-                item_id,
-                example,
-                translation,
-                added_by,
-                created_at
-            FROM vocabulary_examples_read
-            WHERE item_id = $1
-            ORDER BY created_at
+            SELECT
+                e.example_id,
+                e.item_id,
+                e.example,
+                e.translation,
+                e.added_by,
+                e.created_at
+            FROM vocabulary_examples_read e
+            LEFT JOIN vocabulary_provenance_read p ON p.target_id = e.example_id
+            WHERE e.item_id = $1
+              AND NOT COALESCE(p.requires_review, FALSE)
+            ORDER BY e.created_at
             "#,
-            item_id
         )
+        .bind(item_id)
         .fetch_all(&self.pool)
         .await?;
 
         Ok(examples)
     }
 
+    async fn find_item_attribution(&self, item_id: Uuid) -> Result<Option<Attribution>> {
+        let attribution = sqlx::query_as::<_, Attribution>(
+            r#"
+            SELECT source_name, license, attribution
+            FROM vocabulary_provenance_read
+            WHERE target_id = $1 AND target_kind = 'item'
+            "#,
+        )
+        .bind(item_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(attribution)
+    }
+
     async fn search_items(
         &self,
         search_term: &str,
@@ -402,6 +464,9 @@ impl ReadModelRepository for PostgresReadModelRepository {
                 entry_id,
                 spelling,
                 disambiguation,
+                register,
+                domain,
+                usage_notes,
                 part_of_speech,
                 definition,
                 ipa_pronunciation,
@@ -470,6 +535,56 @@ impl ReadModelRepository for PostgresReadModelRepository {
         })
     }
 
+    async fn record_item_access(&self, item_id: Uuid, count: i64) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE vocabulary_items_read
+            SET access_count = access_count + $2
+            WHERE item_id = $1
+            "#,
+        )
+        .bind(item_id)
+        .bind(count)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_most_accessed_item_ids(&self, limit: i64) -> Result<Vec<Uuid>> {
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT item_id
+            FROM vocabulary_items_read
+            WHERE NOT is_deleted AND access_count > 0
+            ORDER BY access_count DESC, item_id
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    async fn find_recently_published_item_ids(&self, limit: i64) -> Result<Vec<Uuid>> {
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT item_id
+            FROM vocabulary_items_read
+            WHERE is_published AND NOT is_deleted
+            ORDER BY updated_at DESC, item_id
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
     async fn health_check(&self) -> Result<()> {
         sqlx::query("SELECT 1")
             .fetch_one(&self.pool)
@@ -481,3 +596,22 @@ impl ReadModelRepository for PostgresReadModelRepository {
         Ok(())
     }
 }
+
+#[async_trait]
+impl CollectionRepository for PostgresReadModelRepository {
+    async fn find_collection_item_ids(&self, collection_id: Uuid) -> Result<Vec<Uuid>> {
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT item_id
+            FROM vocabulary_collection_items_read
+            WHERE collection_id = $1
+            ORDER BY added_at, item_id
+            "#,
+        )
+        .bind(collection_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+}
@@ -0,0 +1,68 @@
+//! 共有キャッシュ（shared_cache）によるキャッシュリポジトリ実装
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shared_cache::Cache;
+
+use crate::{
+    error::{QueryError, Result},
+    ports::outbound::CacheRepository,
+};
+
+/// 共有キャッシュリポジトリ
+///
+/// バックエンド（Redis、インメモリ）は `shared_cache::from_config` で選択する
+#[derive(Clone)]
+pub struct SharedCacheRepository {
+    cache: Arc<dyn Cache>,
+}
+
+impl SharedCacheRepository {
+    pub fn new(cache: Arc<dyn Cache>) -> Self {
+        Self { cache }
+    }
+
+    /// 設定からキャッシュを構築する
+    pub async fn from_config(config: &shared_cache::CacheConfig) -> Result<Self> {
+        let cache = shared_cache::from_config(config)
+            .await
+            .map_err(|e| match e {
+                shared_cache::Error::Configuration(_)
+                | shared_cache::Error::BackendNotCompiled { .. } => {
+                    QueryError::Configuration(e.to_string())
+                },
+                e => QueryError::Cache(e.to_string()),
+            })?;
+        Ok(Self::new(cache))
+    }
+}
+
+fn cache_error(e: shared_cache::Error) -> QueryError {
+    QueryError::Cache(e.to_string())
+}
+
+#[async_trait]
+impl CacheRepository for SharedCacheRepository {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        self.cache.get(key).await.map_err(cache_error)
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl_seconds: u64) -> Result<()> {
+        self.cache
+            .set(key, value, ttl_seconds)
+            .await
+            .map_err(cache_error)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.cache.delete(key).await.map_err(cache_error)
+    }
+
+    async fn delete_pattern(&self, pattern: &str) -> Result<()> {
+        self.cache
+            .delete_pattern(pattern)
+            .await
+            .map_err(cache_error)
+    }
+}
@@ -10,4 +10,4 @@ pub mod server;
 
 pub use config::Config;
 pub use error::{QueryError, Result};
-pub use server::run;
+pub use server::{build_router, run};
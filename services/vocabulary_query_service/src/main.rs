@@ -1,8 +1,6 @@
 use anyhow::Result;
 use tracing::info;
-
-mod config;
-mod server;
+use vocabulary_query_service::{Config, run};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -17,17 +15,17 @@ async fn main() -> Result<()> {
     info!("");
     info!("CQRS + Event Sourcing の Read 側を担当");
     info!("- Read Model からの語彙情報取得");
-    info!("- Redis キャッシュによる高速化");
+    info!("- キャッシュ（Redis またはインメモリ）による高速化");
     info!("- GraphQL への応答");
     info!("");
     info!("詳細: docs/tactical/contexts/vocabulary/");
     info!("===========================================");
 
     // 設定読み込み
-    let config = config::Config::from_env()?;
+    let config = Config::from_env()?;
 
     // サーバー起動
-    server::run(config).await?;
+    run(config).await?;
 
     Ok(())
 }
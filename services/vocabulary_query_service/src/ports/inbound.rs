@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use uuid::Uuid;
 
 use crate::{
+    application::cache_warmup::WarmupReport,
     domain::{
         Cursor,
         PageSize,
@@ -39,6 +40,11 @@ pub trait VocabularyQueryUseCase: Send + Sync {
     /// アイテムを ID で取得
     async fn get_item_by_id(&self, item_id: Uuid) -> Result<Option<VocabularyItem>>;
 
+    /// 複数のアイテムを ID でまとめて取得
+    ///
+    /// 結果は指定した順に並び、存在しない ID は含まない
+    async fn get_items_by_ids(&self, item_ids: &[Uuid]) -> Result<Vec<VocabularyItem>>;
+
     /// エントリのアイテム一覧を取得
     async fn list_items_by_entry(
         &self,
@@ -79,6 +85,10 @@ pub trait HealthCheckUseCase: Send + Sync {
 #[derive(Debug, Clone)]
 pub struct HealthStatus {
     pub is_healthy:      bool,
+    /// 起動時のウォームアップが終わり、トラフィックを受け付けられるかどうか
+    pub is_ready:        bool,
+    /// 起動時のウォームアップ結果（実施した場合のみ）
+    pub warmup:          Option<WarmupReport>,
     pub database_status: DatabaseStatus,
     pub message:         Option<String>,
 }
@@ -1,14 +1,19 @@
 //! 出力ポート（外部システムインターフェース）
 
+use std::time::Instant;
+
 use async_trait::async_trait;
 use uuid::Uuid;
 
 use crate::{
     domain::{
+        Attribution,
         Cursor,
         PageSize,
         PagedResult,
+        RenderedStudySheet,
         SortOptions,
+        StudySheet,
         VocabularyEntry,
         VocabularyExample,
         VocabularyFilter,
@@ -39,6 +44,10 @@ pub trait ReadModelRepository: Send + Sync {
     /// アイテムを ID で取得
     async fn find_item_by_id(&self, item_id: Uuid) -> Result<Option<VocabularyItem>>;
 
+    /// 複数のアイテムを ID でまとめて取得（存在しない ID
+    /// は含まない、順序は不定）
+    async fn find_items_by_ids(&self, item_ids: &[Uuid]) -> Result<Vec<VocabularyItem>>;
+
     /// エントリのアイテムを取得
     async fn find_items_by_entry_id(
         &self,
@@ -55,9 +64,12 @@ pub trait ReadModelRepository: Send + Sync {
         limit: PageSize,
     ) -> Result<PagedResult<VocabularyItem>>;
 
-    /// アイテムの例文を取得
+    /// アイテムの例文を取得（レビュー待ちのインポート例文は除く）
     async fn find_examples_by_item_id(&self, item_id: Uuid) -> Result<Vec<VocabularyExample>>;
 
+    /// アイテムの帰属表示を取得
+    async fn find_item_attribution(&self, item_id: Uuid) -> Result<Option<Attribution>>;
+
     /// 全文検索
     async fn search_items(
         &self,
@@ -70,6 +82,15 @@ pub trait ReadModelRepository: Send + Sync {
     /// 統計情報を取得
     async fn get_statistics(&self) -> Result<VocabularyStatistics>;
 
+    /// アイテムのアクセス数を加算
+    async fn record_item_access(&self, item_id: Uuid, count: i64) -> Result<()>;
+
+    /// アクセス数の多いアイテムの ID を取得
+    async fn find_most_accessed_item_ids(&self, limit: i64) -> Result<Vec<Uuid>>;
+
+    /// 最近公開されたアイテムの ID を取得
+    async fn find_recently_published_item_ids(&self, limit: i64) -> Result<Vec<Uuid>>;
+
     /// データベース接続をチェック
     async fn health_check(&self) -> Result<()>;
 }
@@ -89,3 +110,18 @@ pub trait CacheRepository: Send + Sync {
     /// パターンに一致するキーを削除
     async fn delete_pattern(&self, pattern: &str) -> Result<()>;
 }
+
+/// 共有コレクションの Read Model
+#[async_trait]
+pub trait CollectionRepository: Send + Sync {
+    /// コレクションのアイテム ID を追加順に取得（存在しない場合は空）
+    async fn find_collection_item_ids(&self, collection_id: Uuid) -> Result<Vec<Uuid>>;
+}
+
+/// 学習シートの描画
+pub trait StudySheetRenderer: Send + Sync {
+    /// シートを描画する
+    ///
+    /// `deadline` を過ぎた場合は途中でやめて `QueryError::Timeout` を返す
+    fn render(&self, sheet: &StudySheet, deadline: Instant) -> Result<RenderedStudySheet>;
+}
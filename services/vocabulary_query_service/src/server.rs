@@ -1,16 +1,80 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
-use axum::{Json, Router, routing::get};
+use axum::{
+    Json,
+    Router,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use chrono::Utc;
+use serde::Deserialize;
 use serde_json::json;
-use tracing::info;
+use shared_blob_store::FileSystemBlobStore;
+use sqlx::PgPool;
+use tracing::{error, info, warn};
+use uuid::Uuid;
 
-use crate::config::Config;
+use crate::{
+    application::{
+        AccessTracker,
+        GeneratedStudySheet,
+        HealthCheckService,
+        ReadinessGate,
+        StudySheetDelivery,
+        StudySheetLinkSigner,
+        StudySheetRequest,
+        StudySheetService,
+        VocabularyQueryService,
+        WarmupPlan,
+        cache_warmup::warm_up_then_mark_ready,
+    },
+    config::Config,
+    domain::StudySheetLayout,
+    error::QueryError,
+    infrastructure::{PdfStudySheetRenderer, PostgresReadModelRepository, SharedCacheRepository},
+    ports::inbound::{HealthCheckUseCase, VocabularyQueryUseCase},
+};
+
+type HealthService = Arc<HealthCheckService<PostgresReadModelRepository>>;
+type QueryService = Arc<VocabularyQueryService<PostgresReadModelRepository, SharedCacheRepository>>;
+
+/// ルーターの共有状態
+#[derive(Clone)]
+struct AppState {
+    health:       HealthService,
+    query:        QueryService,
+    study_sheets: Arc<StudySheetService>,
+}
+
+/// アイテム一括取得のリクエスト
+#[derive(Debug, Deserialize)]
+struct BatchItemsRequest {
+    item_ids: Vec<Uuid>,
+}
+
+/// 学習シートの生成リクエスト
+#[derive(Debug, Deserialize)]
+struct StudySheetBody {
+    #[serde(default)]
+    layout:           StudySheetLayout,
+    #[serde(default)]
+    include_examples: bool,
+    #[serde(default)]
+    delivery:         StudySheetDelivery,
+}
+
+/// 署名付き URL のクエリパラメータ
+#[derive(Debug, Deserialize)]
+struct SignedLinkParams {
+    expires:   i64,
+    signature: String,
+}
 
 pub async fn run(config: Config) -> anyhow::Result<()> {
-    // ルーター構築
-    let app = Router::new()
-        .route("/health", get(health_check))
-        .route("/", get(index));
+    let pool = PgPool::connect_lazy(&config.database.url)?;
+    let app = build_router(&config, pool).await?;
 
     // サーバーアドレス
     let addr = SocketAddr::from(([0, 0, 0, 0], config.server.port));
@@ -23,12 +87,226 @@ pub async fn run(config: Config) -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn health_check() -> Json<serde_json::Value> {
-    Json(json!({
-        "status": "healthy",
-        "service": "vocabulary_query_service",
-        "implementation": "pending"
-    }))
+/// Read Model のプールからルーターを組み立てる
+///
+/// キャッシュの接続とウォームアップの開始もここで行う。統合バイナリ
+/// （effect_monolith）はこのルーターをパスの下にネストして使う
+pub async fn build_router(config: &Config, pool: PgPool) -> anyhow::Result<Router> {
+    // Read Model とキャッシュを初期化
+    let repository = PostgresReadModelRepository::new(pool);
+    let cache = match &config.cache {
+        Some(cache) => {
+            info!("Cache backend: {}", cache.backend());
+            Some(SharedCacheRepository::from_config(cache).await?)
+        },
+        None => None,
+    };
+    let has_cache = cache.is_some();
+
+    let query_service = Arc::new(
+        VocabularyQueryService::new(repository.clone(), cache)
+            .with_access_tracking(AccessTracker::new(config.warmup.access_sample_rate)),
+    );
+
+    // ウォームアップが終わるまで Ready にしない（時間予算を超えたら途中で Ready
+    // にする）
+    let readiness = ReadinessGate::default();
+    if config.warmup.enabled && has_cache {
+        let plan = WarmupPlan::from(&config.warmup);
+        let service = query_service.clone();
+        let readiness = readiness.clone();
+        tokio::spawn(async move {
+            warm_up_then_mark_ready(&service, &plan, &readiness).await;
+        });
+    } else {
+        if config.warmup.enabled {
+            warn!("Cache warm-up is enabled but no cache is configured; skipping");
+        }
+        readiness.mark_ready(None);
+    }
+
+    let study_sheets = {
+        let study = &config.study_sheets;
+        let service = StudySheetService::new(
+            query_service.clone(),
+            Arc::new(repository.clone()),
+            Arc::new(PdfStudySheetRenderer),
+        )
+        .with_limits(
+            study.max_items,
+            Duration::from_millis(study.render_timeout_ms),
+        );
+        match (&study.store_dir, &study.link_secret) {
+            (Some(store_dir), Some(secret)) => service.with_storage(
+                Arc::new(FileSystemBlobStore::new(store_dir)),
+                StudySheetLinkSigner::new(secret.as_str(), study.public_base_url.as_str())
+                    .with_ttl(chrono::Duration::seconds(study.link_ttl_seconds)),
+            ),
+            _ => service,
+        }
+    };
+
+    let health_service: HealthService = Arc::new(HealthCheckService::new(repository, readiness));
+
+    Ok(Router::new()
+        .route("/health", get(health_check))
+        .route("/ready", get(readiness_check))
+        .route("/items/batch", post(batch_items))
+        .route(
+            "/collections/{collection_id}/study-sheet",
+            post(generate_study_sheet),
+        )
+        .route(
+            "/study-sheets/{collection_id}/{file}",
+            get(download_study_sheet),
+        )
+        .route("/", get(index))
+        .with_state(AppState {
+            health:       health_service,
+            query:        query_service,
+            study_sheets: Arc::new(study_sheets),
+        }))
+}
+
+async fn health_check(State(state): State<AppState>) -> Json<serde_json::Value> {
+    match state.health.check_health().await {
+        Ok(status) => {
+            let state = if status.is_healthy {
+                "healthy"
+            } else {
+                "unhealthy"
+            };
+            Json(json!({
+                "status": state,
+                "ready": status.is_ready,
+                "warmup": status.warmup,
+                "service": "vocabulary_query_service",
+                "message": status.message,
+            }))
+        },
+        Err(e) => Json(json!({
+            "status": "unhealthy",
+            "service": "vocabulary_query_service",
+            "message": e.to_string(),
+        })),
+    }
+}
+
+async fn readiness_check(State(state): State<AppState>) -> (StatusCode, Json<serde_json::Value>) {
+    match state.health.check_health().await {
+        Ok(status) if status.is_ready => (
+            StatusCode::OK,
+            Json(json!({ "ready": true, "warmup": status.warmup })),
+        ),
+        _ => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "ready": false })),
+        ),
+    }
+}
+
+/// 複数のアイテムをまとめて取得（学習セッションの出題用）
+async fn batch_items(
+    State(state): State<AppState>,
+    Json(request): Json<BatchItemsRequest>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    match state.query.get_items_by_ids(&request.item_ids).await {
+        Ok(items) => (StatusCode::OK, Json(json!({ "items": items }))),
+        Err(QueryError::InvalidInput(message)) => {
+            (StatusCode::BAD_REQUEST, Json(json!({ "error": message })))
+        },
+        Err(e) => {
+            error!("Failed to fetch items: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to fetch items" })),
+            )
+        },
+    }
+}
+
+/// コレクションの学習シート（印刷用 PDF）を生成
+async fn generate_study_sheet(
+    State(state): State<AppState>,
+    Path(collection_id): Path<Uuid>,
+    Json(body): Json<StudySheetBody>,
+) -> Response {
+    let request = StudySheetRequest {
+        collection_id,
+        layout: body.layout,
+        include_examples: body.include_examples,
+        delivery: body.delivery,
+    };
+
+    match state.study_sheets.generate(&request, Utc::now()).await {
+        Ok(GeneratedStudySheet::Pdf { pdf, page_count }) => (
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, "application/pdf".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"study-sheet-{collection_id}.pdf\""),
+                ),
+                (
+                    header::HeaderName::from_static("x-page-count"),
+                    page_count.to_string(),
+                ),
+            ],
+            pdf,
+        )
+            .into_response(),
+        Ok(GeneratedStudySheet::Link { link, page_count }) => (
+            StatusCode::OK,
+            Json(json!({
+                "url": link.url,
+                "expires_at": link.expires_at,
+                "page_count": page_count,
+            })),
+        )
+            .into_response(),
+        Err(e) => {
+            let status = match &e {
+                QueryError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+                QueryError::NotFound(_) => StatusCode::NOT_FOUND,
+                QueryError::Timeout(_) => StatusCode::SERVICE_UNAVAILABLE,
+                QueryError::Configuration(_) => StatusCode::NOT_IMPLEMENTED,
+                _ => {
+                    error!("Failed to generate study sheet: {}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                },
+            };
+            (status, Json(json!({ "error": e.to_string() }))).into_response()
+        },
+    }
+}
+
+/// 署名付き URL で保存済みの学習シートを返す
+async fn download_study_sheet(
+    State(state): State<AppState>,
+    Path((collection_id, file)): Path<(Uuid, String)>,
+    Query(params): Query<SignedLinkParams>,
+) -> Response {
+    let key = format!("{collection_id}/{file}");
+    let Some(signer) = state.study_sheets.link_signer() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    if !signer.verify(&key, params.expires, &params.signature, Utc::now()) {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    match state.study_sheets.stored_sheet(&key).await {
+        Ok(Some(pdf)) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "application/pdf")],
+            pdf,
+        )
+            .into_response(),
+        Ok(None) => StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            error!("Failed to read study sheet {}: {}", key, e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        },
+    }
 }
 
 async fn index() -> Json<serde_json::Value> {
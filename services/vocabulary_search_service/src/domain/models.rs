@@ -1,7 +1,10 @@
 //! 検索ドメインモデル
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
 use uuid::Uuid;
 
 /// 検索結果
@@ -16,7 +19,7 @@ pub struct SearchResult<T> {
 }
 
 /// 語彙検索アイテム
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct VocabularySearchItem {
     pub item_id:           Uuid,
     pub entry_id:          Uuid,
@@ -26,6 +29,12 @@ pub struct VocabularySearchItem {
     pub definition:        Option<String>,
     pub ipa_pronunciation: Option<String>,
     pub cefr_level:        Option<String>,
+    /// 使用域（formal / informal / slang など）
+    #[serde(default)]
+    pub register:          Option<String>,
+    /// 専門分野（business / medical / general など）
+    #[serde(default)]
+    pub domain:            Option<String>,
     pub frequency_rank:    Option<i32>,
     pub example_count:     i32,
     pub score:             f32, // 検索スコア
@@ -53,6 +62,8 @@ pub struct SearchFilter {
     pub has_definition: Option<bool>,
     pub has_examples:   Option<bool>,
     pub is_published:   Option<bool>,
+    pub register:       Option<Vec<String>>,
+    pub domain:         Option<Vec<String>>,
 }
 
 /// ソートオプション
@@ -105,12 +116,54 @@ pub struct FacetValue {
     pub count: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SearchFacets {
     pub part_of_speech: Vec<FacetValue>,
     pub cefr_level:     Vec<FacetValue>,
     pub has_definition: Vec<FacetValue>,
     pub has_examples:   Vec<FacetValue>,
+    pub register:       Vec<FacetValue>,
+    pub domain:         Vec<FacetValue>,
+}
+
+impl SearchFacets {
+    /// 集計できる属性
+    pub const ATTRIBUTES: [&'static str; 6] = [
+        "part_of_speech",
+        "cefr_level",
+        "has_definition",
+        "has_examples",
+        "register",
+        "domain",
+    ];
+
+    /// (属性, 値, 件数) の集計結果から作る
+    ///
+    /// 値は件数の多い順（同数は値の昇順）に並べる。未知の属性は無視する
+    pub fn from_counts(counts: impl IntoIterator<Item = (String, String, usize)>) -> Self {
+        let mut grouped: BTreeMap<String, Vec<FacetValue>> = BTreeMap::new();
+        for (attribute, value, count) in counts {
+            grouped
+                .entry(attribute)
+                .or_default()
+                .push(FacetValue { value, count });
+        }
+
+        let mut take = |attribute: &str| {
+            let mut values = grouped.remove(attribute).unwrap_or_default();
+            values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+            values
+        };
+
+        Self {
+            part_of_speech: take("part_of_speech"),
+            cefr_level:     take("cefr_level"),
+            has_definition: take("has_definition"),
+            has_examples:   take("has_examples"),
+            register:       take("register"),
+            domain:         take("domain"),
+        }
+    }
 }
 
 /// インデックス統計
@@ -193,3 +246,35 @@ impl Default for TypoTolerance {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(facets: &[FacetValue]) -> Vec<(&str, usize)> {
+        facets
+            .iter()
+            .map(|facet| (facet.value.as_str(), facet.count))
+            .collect()
+    }
+
+    #[test]
+    fn test_facets_from_counts_include_register_and_domain() {
+        let facets = SearchFacets::from_counts([
+            ("register".to_string(), "formal".to_string(), 3),
+            ("register".to_string(), "slang".to_string(), 5),
+            ("register".to_string(), "neutral".to_string(), 3),
+            ("domain".to_string(), "business".to_string(), 4),
+            ("cefr_level".to_string(), "B2".to_string(), 7),
+            ("unknown".to_string(), "x".to_string(), 1),
+        ]);
+
+        assert_eq!(
+            counts(&facets.register),
+            vec![("slang", 5), ("formal", 3), ("neutral", 3)]
+        );
+        assert_eq!(counts(&facets.domain), vec![("business", 4)]);
+        assert_eq!(counts(&facets.cefr_level), vec![("B2", 7)]);
+        assert!(facets.part_of_speech.is_empty());
+    }
+}
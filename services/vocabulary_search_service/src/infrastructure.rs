@@ -1,3 +1,4 @@
 //! インフラストラクチャ層
 
 pub mod repositories;
+pub mod search_engine;
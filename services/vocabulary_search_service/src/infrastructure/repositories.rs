@@ -1,9 +1,15 @@
 //! リポジトリ実装
 
+#[cfg(feature = "meilisearch")]
 pub mod meilisearch;
 pub mod postgres_data_source;
+#[cfg(feature = "postgres-fts")]
+pub mod postgres_fts;
 pub mod redis_search_log;
 
+#[cfg(feature = "meilisearch")]
 pub use meilisearch::MeilisearchRepository;
 pub use postgres_data_source::PostgresDataSourceRepository;
+#[cfg(feature = "postgres-fts")]
+pub use postgres_fts::PostgresFtsRepository;
 pub use redis_search_log::RedisSearchLogRepository;
@@ -73,6 +73,8 @@ impl MeilisearchRepository {
                 "is_published".to_string(),
                 "has_definition".to_string(),
                 "has_examples".to_string(),
+                "register".to_string(),
+                "domain".to_string(),
             ])
             .with_sortable_attributes(vec![
                 "spelling".to_string(),
@@ -134,6 +136,18 @@ impl SearchEngineRepository for MeilisearchRepository {
                 filters.push(format!("({})", cefr_filter));
             }
 
+            for (attribute, values) in [("register", &filter.register), ("domain", &filter.domain)]
+            {
+                if let Some(values) = values {
+                    let any_of = values
+                        .iter()
+                        .map(|v| format!("{} = \"{}\"", attribute, v))
+                        .collect::<Vec<_>>()
+                        .join(" OR ");
+                    filters.push(format!("({})", any_of));
+                }
+            }
+
             if let Some(is_published) = filter.is_published {
                 filters.push(format!("is_published = {}", is_published));
             }
@@ -238,8 +252,15 @@ impl SearchEngineRepository for MeilisearchRepository {
             query: query.query.clone(),
         };
 
-        // TODO: ファセットの処理
-        Ok((search_result, None))
+        let facets = results.facet_distribution.map(|distribution| {
+            SearchFacets::from_counts(distribution.into_iter().flat_map(|(attribute, values)| {
+                values
+                    .into_iter()
+                    .map(move |(value, count)| (attribute.clone(), value, count))
+            }))
+        });
+
+        Ok((search_result, facets))
     }
 
     async fn suggest(&self, prefix: &str, limit: usize) -> Result<Vec<AutocompleteItem>> {
@@ -291,6 +312,8 @@ impl SearchEngineRepository for MeilisearchRepository {
             "definition": document.definition,
             "ipa_pronunciation": document.ipa_pronunciation,
             "cefr_level": document.cefr_level,
+            "register": document.register,
+            "domain": document.domain,
             "frequency_rank": document.frequency_rank,
             "example_count": document.example_count,
             "has_definition": document.definition.is_some(),
@@ -327,6 +350,8 @@ impl SearchEngineRepository for MeilisearchRepository {
                     "definition": doc.definition,
                     "ipa_pronunciation": doc.ipa_pronunciation,
                     "cefr_level": doc.cefr_level,
+                    "register": doc.register,
+                    "domain": doc.domain,
                     "frequency_rank": doc.frequency_rank,
                     "example_count": doc.example_count,
                     "has_definition": doc.definition.is_some(),
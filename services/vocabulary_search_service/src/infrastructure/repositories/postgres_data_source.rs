@@ -24,7 +24,7 @@ impl DataSourceRepository for PostgresDataSourceRepository {
         let items = sqlx::query_as!(
             VocabularySearchItem,
             r#"
-            SELECT 
+            SELECT
                 item_id,
                 entry_id,
                 spelling,
@@ -33,9 +33,11 @@ impl DataSourceRepository for PostgresDataSourceRepository {
                 definition,
                 ipa_pronunciation,
                 cefr_level,
+                register,
+                domain,
                 frequency_rank,
                 example_count,
-                0.0::float4 as "score!: f32",
+                0.0::float4 AS "score!: f32",
                 created_at,
                 updated_at
             FROM vocabulary_items_read
@@ -54,7 +56,7 @@ impl DataSourceRepository for PostgresDataSourceRepository {
         let items = sqlx::query_as!(
             VocabularySearchItem,
             r#"
-            SELECT 
+            SELECT
                 item_id,
                 entry_id,
                 spelling,
@@ -63,9 +65,11 @@ impl DataSourceRepository for PostgresDataSourceRepository {
                 definition,
                 ipa_pronunciation,
                 cefr_level,
+                register,
+                domain,
                 frequency_rank,
                 example_count,
-                0.0::float4 as "score!: f32",
+                0.0::float4 AS "score!: f32",
                 created_at,
                 updated_at
             FROM vocabulary_items_read
@@ -86,7 +90,7 @@ impl DataSourceRepository for PostgresDataSourceRepository {
         let item = sqlx::query_as!(
             VocabularySearchItem,
             r#"
-            SELECT 
+            SELECT
                 item_id,
                 entry_id,
                 spelling,
@@ -95,9 +99,11 @@ impl DataSourceRepository for PostgresDataSourceRepository {
                 definition,
                 ipa_pronunciation,
                 cefr_level,
+                register,
+                domain,
                 frequency_rank,
                 example_count,
-                0.0::float4 as "score!: f32",
+                0.0::float4 AS "score!: f32",
                 created_at,
                 updated_at
             FROM vocabulary_items_read
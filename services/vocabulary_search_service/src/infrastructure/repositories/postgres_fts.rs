@@ -0,0 +1,362 @@
+//! PostgreSQL 全文検索リポジトリ実装
+//!
+//! 射影サービスが書き込む `vocabulary_items_read` を直接検索する。
+//! インデックスは読み取りモデルそのものなので、インデックス操作は何もしない。
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{
+    PgPool,
+    Postgres,
+    Row,
+    postgres::{PgArguments, PgRow},
+    query::Query,
+};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        AutocompleteItem,
+        IndexSettings,
+        IndexStatistics,
+        ProximityPrecision,
+        SearchFacets,
+        SearchFilter,
+        SearchQuery,
+        SearchResult,
+        SortBy,
+        SortOrder,
+        VocabularySearchItem,
+    },
+    error::{Result, SearchError},
+    ports::outbound::SearchEngineRepository,
+};
+
+/// 検索対象の文書（`to_tsvector` の入力）
+const DOCUMENT: &str = "coalesce(spelling, '') || ' ' || coalesce(disambiguation, '') || ' ' || \
+                        coalesce(definition, '')";
+
+/// PostgreSQL 全文検索リポジトリ
+pub struct PostgresFtsRepository {
+    pool: PgPool,
+}
+
+impl PostgresFtsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 検索条件に合う項目を属性の値ごとに数える
+    ///
+    /// 集計できない属性の指定は無視する
+    async fn count_facets(
+        &self,
+        text: &str,
+        filter: &SearchFilter,
+        requested: &[String],
+    ) -> Result<SearchFacets> {
+        let attributes: Vec<&str> = SearchFacets::ATTRIBUTES
+            .into_iter()
+            .filter(|attribute| requested.iter().any(|r| r == attribute))
+            .collect();
+        if attributes.is_empty() {
+            return Ok(SearchFacets::default());
+        }
+
+        let sql = format!(
+            r#"
+            SELECT facets.attribute, facets.value, COUNT(*) AS count
+            FROM vocabulary_items_read
+            CROSS JOIN LATERAL (VALUES
+                ('part_of_speech', part_of_speech),
+                ('cefr_level', cefr_level),
+                ('has_definition', (definition IS NOT NULL)::text),
+                ('has_examples', (example_count > 0)::text),
+                ('register', register),
+                ('domain', domain)
+            ) AS facets(attribute, value)
+            WHERE {}
+                AND facets.attribute = ANY($11)
+                AND facets.value IS NOT NULL
+            GROUP BY facets.attribute, facets.value
+            "#,
+            filter_clause()
+        );
+
+        let rows = bind_filter(&sql, text, filter)
+            .bind(&attributes)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let counts = rows
+            .iter()
+            .map(|row| {
+                Ok((
+                    row.try_get::<String, _>("attribute")?,
+                    row.try_get::<String, _>("value")?,
+                    row.try_get::<i64, _>("count")? as usize,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SearchFacets::from_counts(counts))
+    }
+
+    fn item_from_row(row: &PgRow) -> Result<VocabularySearchItem> {
+        Ok(VocabularySearchItem {
+            item_id:           row.try_get("item_id")?,
+            entry_id:          row.try_get("entry_id")?,
+            spelling:          row.try_get("spelling")?,
+            disambiguation:    row.try_get("disambiguation")?,
+            part_of_speech:    row.try_get("part_of_speech")?,
+            definition:        row.try_get("definition")?,
+            ipa_pronunciation: row.try_get("ipa_pronunciation")?,
+            cefr_level:        row.try_get("cefr_level")?,
+            register:          row.try_get("register")?,
+            domain:            row.try_get("domain")?,
+            frequency_rank:    row.try_get("frequency_rank")?,
+            example_count:     row.try_get("example_count")?,
+            score:             row.try_get("score")?,
+            created_at:        row.try_get("created_at")?,
+            updated_at:        row.try_get("updated_at")?,
+        })
+    }
+}
+
+/// 検索条件の WHERE 句（$1〜$10 は [`bind_filter`] で束縛する）
+fn filter_clause() -> String {
+    format!(
+        r#"NOT is_deleted
+                AND ($1 = '' OR to_tsvector('simple', {DOCUMENT}) @@ plainto_tsquery('simple', $1))
+                AND ($2::text[] IS NULL OR part_of_speech = ANY($2))
+                AND ($3::text[] IS NULL OR cefr_level = ANY($3))
+                AND ($4::int4 IS NULL OR frequency_rank >= $4)
+                AND ($5::int4 IS NULL OR frequency_rank <= $5)
+                AND ($6::bool IS NULL OR (definition IS NOT NULL) = $6)
+                AND ($7::bool IS NULL OR (example_count > 0) = $7)
+                AND is_published = coalesce($8, true)
+                AND ($9::text[] IS NULL OR register = ANY($9))
+                AND ($10::text[] IS NULL OR domain = ANY($10))"#
+    )
+}
+
+fn bind_filter<'q>(
+    sql: &'q str,
+    text: &'q str,
+    filter: &'q SearchFilter,
+) -> Query<'q, Postgres, PgArguments> {
+    sqlx::query(sql)
+        .bind(text)
+        .bind(&filter.part_of_speech)
+        .bind(&filter.cefr_level)
+        .bind(filter.min_frequency)
+        .bind(filter.max_frequency)
+        .bind(filter.has_definition)
+        .bind(filter.has_examples)
+        .bind(filter.is_published)
+        .bind(&filter.register)
+        .bind(&filter.domain)
+}
+
+/// ORDER BY 句（列名は固定の候補から選ぶ）
+fn order_clause(sort_by: Option<&SortBy>, sort_order: Option<&SortOrder>) -> String {
+    let column = match sort_by.unwrap_or(&SortBy::Relevance) {
+        SortBy::Relevance => return "score DESC, spelling ASC".to_string(),
+        SortBy::Spelling => "spelling",
+        SortBy::FrequencyRank => "frequency_rank",
+        SortBy::CefrLevel => "cefr_level",
+        SortBy::ExampleCount => "example_count",
+        SortBy::CreatedAt => "created_at",
+        SortBy::UpdatedAt => "updated_at",
+    };
+    let direction = match sort_order.unwrap_or(&SortOrder::default()) {
+        SortOrder::Ascending => "ASC",
+        SortOrder::Descending => "DESC",
+    };
+    format!("{column} {direction} NULLS LAST, item_id")
+}
+
+#[async_trait]
+impl SearchEngineRepository for PostgresFtsRepository {
+    async fn search(
+        &self,
+        query: &SearchQuery,
+    ) -> Result<(SearchResult<VocabularySearchItem>, Option<SearchFacets>)> {
+        let page = query.page.unwrap_or(1).max(1);
+        let per_page = query.per_page.unwrap_or(20);
+        let filter = query.filter.clone().unwrap_or_default();
+
+        let sql = format!(
+            r#"
+            SELECT
+                item_id, entry_id, spelling, disambiguation, part_of_speech, definition,
+                ipa_pronunciation, cefr_level, register, domain, frequency_rank, example_count,
+                ts_rank(to_tsvector('simple', {DOCUMENT}), plainto_tsquery('simple', $1))::float4
+                    AS score,
+                created_at, updated_at,
+                COUNT(*) OVER () AS total_results
+            FROM vocabulary_items_read
+            WHERE {}
+            ORDER BY {}
+            LIMIT $11 OFFSET $12
+            "#,
+            filter_clause(),
+            order_clause(query.sort_by.as_ref(), query.sort_order.as_ref())
+        );
+
+        let start_time = std::time::Instant::now();
+        let rows = bind_filter(&sql, &query.query, &filter)
+            .bind(i64::from(per_page))
+            .bind(i64::from((page - 1) * per_page))
+            .fetch_all(&self.pool)
+            .await?;
+        let processing_time = start_time.elapsed().as_millis() as u64;
+
+        let total_results = match rows.first() {
+            Some(row) => row.try_get::<i64, _>("total_results")? as usize,
+            None => 0,
+        };
+        let items = rows
+            .iter()
+            .map(Self::item_from_row)
+            .collect::<Result<Vec<_>>>()?;
+
+        let search_result = SearchResult {
+            items,
+            total_results,
+            total_pages: total_results.div_ceil(per_page.max(1) as usize),
+            current_page: page as usize,
+            processing_time,
+            query: query.query.clone(),
+        };
+
+        let facets = match query.facets.as_deref() {
+            Some(requested) if !requested.is_empty() => {
+                Some(self.count_facets(&query.query, &filter, requested).await?)
+            },
+            _ => None,
+        };
+
+        Ok((search_result, facets))
+    }
+
+    async fn suggest(&self, prefix: &str, limit: usize) -> Result<Vec<AutocompleteItem>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT ON (spelling) spelling, frequency_rank
+            FROM vocabulary_items_read
+            WHERE NOT is_deleted
+                AND is_published
+                AND starts_with(lower(spelling), lower($1))
+            ORDER BY spelling, frequency_rank NULLS LAST
+            LIMIT $2
+            "#,
+        )
+        .bind(prefix)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let spelling: String = row.try_get("spelling")?;
+                Ok(AutocompleteItem {
+                    display: spelling.clone(),
+                    spelling,
+                    category: "word".to_string(),
+                    frequency_rank: row.try_get("frequency_rank")?,
+                    score: 1.0,
+                })
+            })
+            .collect()
+    }
+
+    async fn find_similar(
+        &self,
+        _item_id: Uuid,
+        _limit: usize,
+    ) -> Result<Vec<VocabularySearchItem>> {
+        // TODO: 類似検索の実装（pg_trgm など）
+        Ok(Vec::new())
+    }
+
+    async fn index_document(&self, _document: &VocabularySearchItem) -> Result<()> {
+        Ok(())
+    }
+
+    async fn batch_index(&self, _documents: &[VocabularySearchItem]) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_document(&self, _item_id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn clear_index(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn get_statistics(&self) -> Result<IndexStatistics> {
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM vocabulary_items_read WHERE NOT is_deleted AND is_published",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(IndexStatistics {
+            total_documents:   total as usize,
+            indexed_documents: total as usize,
+            deleted_documents: 0,
+            primary_key:       "item_id".to_string(),
+            index_size:        0,
+            last_updated:      Utc::now(),
+            is_indexing:       false,
+        })
+    }
+
+    async fn get_settings(&self) -> Result<IndexSettings> {
+        Ok(IndexSettings {
+            searchable_attributes: vec![
+                "spelling".to_string(),
+                "definition".to_string(),
+                "disambiguation".to_string(),
+            ],
+            filterable_attributes: vec![
+                "part_of_speech".to_string(),
+                "cefr_level".to_string(),
+                "frequency_rank".to_string(),
+                "is_published".to_string(),
+                "has_definition".to_string(),
+                "has_examples".to_string(),
+                "register".to_string(),
+                "domain".to_string(),
+            ],
+            sortable_attributes:   vec![
+                "spelling".to_string(),
+                "frequency_rank".to_string(),
+                "cefr_level".to_string(),
+                "example_count".to_string(),
+                "created_at".to_string(),
+                "updated_at".to_string(),
+            ],
+            displayed_attributes:  vec!["*".to_string()],
+            ranking_rules:         vec!["ts_rank".to_string()],
+            stop_words:            Vec::new(),
+            synonyms:              Vec::new(),
+            distinct_attribute:    None,
+            proximity_precision:   ProximityPrecision::ByWord,
+            typo_tolerance:        Default::default(),
+        })
+    }
+
+    async fn update_settings(&self, _settings: &IndexSettings) -> Result<()> {
+        Err(SearchError::Configuration(
+            "Index settings are fixed for the postgres-fts search backend".to_string(),
+        ))
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+}
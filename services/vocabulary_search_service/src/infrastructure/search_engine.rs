@@ -0,0 +1,111 @@
+//! 設定からの検索エンジン構築
+
+use std::sync::Arc;
+
+#[cfg(feature = "postgres-fts")]
+use sqlx::postgres::PgPoolOptions;
+
+#[cfg(feature = "meilisearch")]
+use super::repositories::MeilisearchRepository;
+#[cfg(feature = "postgres-fts")]
+use super::repositories::PostgresFtsRepository;
+#[cfg(any(feature = "meilisearch", feature = "postgres-fts"))]
+use crate::config::SearchBackend;
+use crate::{
+    config::Config,
+    error::{Result, SearchError},
+    ports::outbound::SearchEngineRepository,
+};
+
+/// Meilisearch のインデックス名
+pub const INDEX_NAME: &str = "vocabulary_items";
+
+/// 設定された検索エンジンを構築する
+///
+/// バックエンドが feature で組み込まれていない場合は設定エラーを返す
+#[cfg_attr(
+    not(any(feature = "meilisearch", feature = "postgres-fts")),
+    allow(unused_variables)
+)]
+pub async fn search_engine_from_config(config: &Config) -> Result<Arc<dyn SearchEngineRepository>> {
+    match config.search {
+        #[cfg(feature = "meilisearch")]
+        SearchBackend::Meilisearch => Ok(Arc::new(MeilisearchRepository::new(
+            config.meilisearch.url.clone(),
+            config.meilisearch.api_key.clone(),
+            INDEX_NAME.to_string(),
+        ))),
+        #[cfg(feature = "postgres-fts")]
+        SearchBackend::PostgresFts => {
+            let pool = PgPoolOptions::new()
+                .max_connections(5)
+                .connect_lazy(&config.database.url)?;
+            Ok(Arc::new(PostgresFtsRepository::new(pool)))
+        },
+        #[allow(unreachable_patterns)]
+        backend => Err(SearchError::Configuration(format!(
+            "Search backend '{0}' is not compiled in (enable the `{0}` feature of \
+             vocabulary_search_service)",
+            backend.name()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SearchBackend;
+
+    fn config(search: SearchBackend) -> Config {
+        let mut config = Config::from_env().unwrap();
+        config.search = search;
+        config
+    }
+
+    #[cfg(feature = "meilisearch")]
+    #[tokio::test]
+    async fn test_builds_meilisearch_backend() {
+        assert!(
+            search_engine_from_config(&config(SearchBackend::Meilisearch))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[cfg(feature = "postgres-fts")]
+    #[tokio::test]
+    async fn test_builds_postgres_fts_backend() {
+        // 接続は最初のクエリまで遅延する
+        assert!(
+            search_engine_from_config(&config(SearchBackend::PostgresFts))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[cfg(not(feature = "meilisearch"))]
+    #[tokio::test]
+    async fn test_meilisearch_backend_not_compiled_is_reported() {
+        let error = search_engine_from_config(&config(SearchBackend::Meilisearch))
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "Configuration error: Search backend 'meilisearch' is not compiled in (enable the \
+             `meilisearch` feature of vocabulary_search_service)"
+        );
+    }
+
+    #[cfg(not(feature = "postgres-fts"))]
+    #[tokio::test]
+    async fn test_postgres_fts_backend_not_compiled_is_reported() {
+        let error = search_engine_from_config(&config(SearchBackend::PostgresFts))
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, SearchError::Configuration(_)));
+    }
+}
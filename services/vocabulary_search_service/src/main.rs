@@ -1,8 +1,6 @@
 use anyhow::Result;
 use tracing::info;
-
-mod config;
-mod server;
+use vocabulary_search_service::{Config, run};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -12,23 +10,14 @@ async fn main() -> Result<()> {
     info!("===========================================");
     info!("Vocabulary Search Service - 起動中");
     info!("責務: 全文検索と自動補完");
-    info!("状態: 未実装（設計済み）");
-    info!("===========================================");
-    info!("");
-    info!("Meilisearch を活用した高度な検索機能");
-    info!("- Typo 許容検索");
-    info!("- 部分一致検索");
-    info!("- フィルタリング（status, CEFR レベル等）");
-    info!("- 高速な自動補完");
-    info!("");
-    info!("詳細: docs/tactical/contexts/vocabulary/");
     info!("===========================================");
 
     // 設定読み込み
-    let config = config::Config::from_env()?;
+    let config = Config::from_env()?;
+    info!("Search backend: {}", config.search.name());
 
     // サーバー起動
-    server::run(config).await?;
+    run(config).await?;
 
     Ok(())
 }
@@ -1,16 +1,34 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 
-use axum::{Json, Router, routing::get};
+use axum::{Json, Router, extract::State, routing::get};
 use serde_json::json;
 use tracing::info;
 
-use crate::config::Config;
+use crate::{
+    config::Config,
+    infrastructure::search_engine::search_engine_from_config,
+    ports::outbound::SearchEngineRepository,
+};
+
+#[derive(Clone)]
+struct AppState {
+    backend:       &'static str,
+    search_engine: Arc<dyn SearchEngineRepository>,
+}
 
 pub async fn run(config: Config) -> anyhow::Result<()> {
+    // 検索エンジン構築（組み込まれていないバックエンドはここでエラーになる）
+    let search_engine = search_engine_from_config(&config).await?;
+    let state = AppState {
+        backend: config.search.name(),
+        search_engine,
+    };
+
     // ルーター構築
     let app = Router::new()
         .route("/health", get(health_check))
-        .route("/", get(index));
+        .route("/", get(index))
+        .with_state(state);
 
     // サーバーアドレス
     let addr = SocketAddr::from(([0, 0, 0, 0], config.server.port));
@@ -23,11 +41,17 @@ pub async fn run(config: Config) -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn health_check() -> Json<serde_json::Value> {
+async fn health_check(State(state): State<AppState>) -> Json<serde_json::Value> {
+    let search_engine = match state.search_engine.health_check().await {
+        Ok(()) => "connected".to_string(),
+        Err(e) => format!("error: {e}"),
+    };
+
     Json(json!({
         "status": "healthy",
         "service": "vocabulary_search_service",
-        "implementation": "pending"
+        "search_backend": state.backend,
+        "search_engine": search_engine
     }))
 }
 
@@ -35,9 +59,8 @@ async fn index() -> Json<serde_json::Value> {
     Json(json!({
         "service": "Vocabulary Search Service",
         "version": "0.1.0",
-        "status": "未実装",
         "responsibility": "全文検索と自動補完",
-        "description": "Meilisearch を活用した高度な検索機能",
+        "description": "Meilisearch または PostgreSQL 全文検索による検索機能",
         "documentation": "docs/tactical/contexts/vocabulary/architecture.md"
     }))
 }
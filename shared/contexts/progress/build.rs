@@ -0,0 +1,52 @@
+//! progress context パッケージのビルドスクリプト
+//!
+//! Progress Context 固有のイベントを protobuf から生成します。
+
+#[path = "../../kernel/build_support/proto_events.rs"]
+mod proto_events;
+
+use std::{env, path::PathBuf};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let proto_root = "../../../protos".to_string();
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    let descriptor_set = out_dir.join("progress_events_descriptor.bin");
+
+    // prost_build::Config を作成して protoc のパスを設定
+    let mut prost_config = ::prost_build::Config::new();
+    prost_config.protoc_executable(protobuf_src::protoc());
+
+    // 生成コードの clippy 警告を抑制
+    prost_config
+        .type_attribute(".", "#[allow(clippy::all)]")
+        .type_attribute(".", "#[allow(dead_code)]")
+        .type_attribute(".", "#[allow(missing_docs)]")
+        // 外部クレートとして shared_kernel の型を使用
+        .extern_path(".effect.common", "::shared_kernel::proto::effect::common");
+
+    // tonic_prost_build の設定
+    let builder = tonic_prost_build::configure()
+        .build_server(false)
+        .build_client(false)
+        .file_descriptor_set_path(&descriptor_set);
+
+    // compile_with_config を使用
+    builder.compile_with_config(
+        prost_config,
+        &[
+            // Progress イベント定義
+            &format!("{proto_root}/events/progress_events.proto"),
+        ],
+        &[&proto_root],
+    )?;
+
+    // イベントのアクセサとテスト用のビルダーを生成
+    proto_events::generate(
+        &descriptor_set,
+        &out_dir,
+        &["effect.events.progress"],
+        &[(".effect.common", "::shared_kernel::proto::effect::common")],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! 共有コレクションのメンバー別学習統計
+//!
+//! Progress Projection Service がコレクションとメンバーの組ごとに集計し、
+//! Progress Query Service がメンバー向けのランキングとして返す。
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// コレクション内でのメンバーの学習統計
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionMemberStats {
+    pub collection_id:    Uuid,
+    pub user_id:          Uuid,
+    /// コレクションの語彙アイテムのうち学習したアイテム数
+    pub items_studied:    i32,
+    /// コレクションの語彙アイテムのうち習得（Mastered）したアイテム数
+    pub items_mastered:   i32,
+    /// コレクションの語彙アイテムを最後に学習した日時
+    pub last_activity_at: Option<DateTime<Utc>>,
+}
+
+impl CollectionMemberStats {
+    /// まだ学習していないメンバーの統計
+    pub fn empty(collection_id: Uuid, user_id: Uuid) -> Self {
+        Self {
+            collection_id,
+            user_id,
+            items_studied: 0,
+            items_mastered: 0,
+            last_activity_at: None,
+        }
+    }
+}
+
+/// 順位付きのメンバー統計
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedMember {
+    /// 1 から始まる順位
+    pub rank:  u32,
+    pub stats: CollectionMemberStats,
+}
+
+/// 習得数の多い順に順位を付ける
+///
+/// 同数の場合は最後の学習が新しいメンバーを上位にし、学習していない
+/// メンバーは最後に並べる。それでも並ばない場合はユーザー ID 順
+pub fn rank_members(mut members: Vec<CollectionMemberStats>) -> Vec<RankedMember> {
+    members.sort_by(|a, b| {
+        b.items_mastered
+            .cmp(&a.items_mastered)
+            .then_with(|| b.last_activity_at.cmp(&a.last_activity_at))
+            .then_with(|| a.user_id.cmp(&b.user_id))
+    });
+
+    members
+        .into_iter()
+        .zip(1..)
+        .map(|(stats, rank)| RankedMember { rank, stats })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(user: u128, mastered: i32, last_activity_at: Option<&str>) -> CollectionMemberStats {
+        CollectionMemberStats {
+            collection_id:    Uuid::nil(),
+            user_id:          Uuid::from_u128(user),
+            items_studied:    mastered + 1,
+            items_mastered:   mastered,
+            last_activity_at: last_activity_at.map(|at| at.parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_ranks_by_mastered_then_recency() {
+        let ranked = rank_members(vec![
+            member(1, 3, Some("2025-01-05T10:00:00Z")),
+            member(2, 5, Some("2025-01-01T10:00:00Z")),
+            member(3, 3, Some("2025-01-06T09:00:00Z")),
+            member(4, 0, None),
+            member(5, 0, Some("2025-01-02T00:00:00Z")),
+            member(6, 3, Some("2025-01-06T09:00:00Z")),
+        ]);
+
+        let order: Vec<(u32, u128)> = ranked
+            .iter()
+            .map(|member| (member.rank, member.stats.user_id.as_u128()))
+            .collect();
+        assert_eq!(order, vec![(1, 2), (2, 3), (3, 6), (4, 1), (5, 5), (6, 4)]);
+    }
+}
@@ -0,0 +1,113 @@
+//! Progress Context のドメインイベント
+//!
+//! 連続学習日数・実績・目標の進捗など、学習イベントから導いた進捗の
+//! イベントを定義します。Proto ファイルから生成されたコードを使用し、
+//! 必要に応じて拡張トレイトを実装します。
+
+use prost::Message as _;
+use shared_kernel::{CloudEventCodec, CloudEventData, DomainEvent, EventError, HasMetadata};
+
+// Proto 生成コードを含める
+pub mod proto {
+    include!(concat!(env!("OUT_DIR"), "/effect.events.progress.rs"));
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/effect.events.progress.proto_events.rs"
+    ));
+}
+
+// Proto 型を再エクスポート
+pub use proto::*;
+
+/// テスト用のイベントのビルダー
+///
+/// ```ignore
+/// let event = StreakExtendedBuilder::default()
+///     .current_streak(7)
+///     .build_event();
+/// ```
+#[cfg(any(test, feature = "test-util"))]
+pub mod builders {
+    use super::*;
+
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/effect.events.progress.builders.rs"
+    ));
+}
+
+// DomainEvent トレイトの実装（Proto 生成型用）
+impl DomainEvent for ProgressEvent {
+    fn event_type(&self) -> &str {
+        match &self.event {
+            Some(progress_event::Event::StreakExtended(_)) => "StreakExtended",
+            Some(progress_event::Event::StreakBroken(_)) => "StreakBroken",
+            Some(progress_event::Event::AchievementUnlocked(_)) => "AchievementUnlocked",
+            Some(progress_event::Event::GoalProgressUpdated(_)) => "GoalProgressUpdated",
+            None => "ProgressEventUnknown",
+        }
+    }
+
+    fn full_event_type(&self) -> &str {
+        self.qualified_event_type().unwrap_or("progress.Unknown")
+    }
+
+    // metadata() は実装せず、Proto のメタデータから直接取り出す
+    fn event_id(&self) -> &str {
+        HasMetadata::metadata(self).map_or("", |metadata| metadata.event_id.as_str())
+    }
+
+    fn aggregate_id(&self) -> &str {
+        HasMetadata::aggregate_id(self).unwrap_or_default()
+    }
+
+    fn correlation_id(&self) -> &str {
+        HasMetadata::metadata(self)
+            .and_then(|metadata| metadata.correlation_id.as_deref())
+            .unwrap_or_else(|| DomainEvent::event_id(self))
+    }
+
+    fn occurred_at(&self) -> chrono::DateTime<chrono::Utc> {
+        HasMetadata::occurred_at(self).unwrap_or_default()
+    }
+}
+
+// CloudEvents の data は Protobuf のバイナリ
+impl CloudEventCodec for ProgressEvent {
+    const DATA_CONTENT_TYPE: &'static str = "application/protobuf";
+
+    fn encode_data(&self) -> Result<CloudEventData, EventError> {
+        Ok(CloudEventData::Binary(self.encode_to_vec()))
+    }
+
+    fn decode_data(data: &CloudEventData) -> Result<Self, EventError> {
+        match data {
+            CloudEventData::Binary(bytes) => Self::decode(bytes.as_slice())
+                .map_err(|e| EventError::Deserialization(e.to_string())),
+            CloudEventData::Json(_) => Err(EventError::InvalidEvent(
+                "ProgressEvent must be carried as protobuf data".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_events_round_trip_as_cloudevents() {
+        let event = builders::AchievementUnlockedBuilder::default()
+            .achievement_id("streak_30")
+            .name("30日連続")
+            .category(AchievementCategory::Streak)
+            .build_event();
+
+        assert_eq!(event.event_type(), "AchievementUnlocked");
+        assert_eq!(event.full_event_type(), "progress.AchievementUnlocked");
+
+        let cloudevent = event.to_cloudevent().unwrap();
+        assert_eq!(cloudevent.source, "/effect/progress");
+        assert_eq!(ProgressEvent::from_cloudevent(&cloudevent).unwrap(), event);
+    }
+}
@@ -1 +1,11 @@
 //! progress Context 共有ライブラリ
+
+pub mod collection;
+pub mod events;
+pub mod report;
+pub mod study_time;
+
+pub use collection::{CollectionMemberStats, RankedMember, rank_members};
+pub use events::*;
+pub use report::{IsoWeek, WeeklyReport, WeeklyReportDocument};
+pub use study_time::{PreferredStudyWindows, StudyWindow};
@@ -0,0 +1,351 @@
+//! 週次レポート
+//!
+//! Progress Projection Service が ISO 週ごとに生成し、BLOB ストアに
+//! バージョン付きの JSON ドキュメントとして保存する。Progress Query Service は
+//! 同じキーで読み出して GraphQL API から返す。
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc, Weekday};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// 週次レポートドキュメントのスキーマバージョン
+pub const WEEKLY_REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// 週次レポートのエラー
+#[derive(Debug, Error)]
+pub enum ReportError {
+    #[error("Invalid ISO week: {0} (expected YYYY-Www)")]
+    InvalidWeek(String),
+
+    #[error("Unsupported weekly report schema version {version} (supported up to {supported})")]
+    UnsupportedVersion { version: u32, supported: u32 },
+
+    #[error("Report serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// ISO 8601 の週（月曜始まり、その年の最初の木曜日を含む週が第1週）
+///
+/// 年をまたぐ週は木曜日が属する年の週になる（例: 2021-01-03 は 2020-W53）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IsoWeek {
+    year: i32,
+    week: u32,
+}
+
+impl IsoWeek {
+    /// 年と週番号から作る（その年に存在しない週番号は None）
+    pub fn new(year: i32, week: u32) -> Option<Self> {
+        NaiveDate::from_isoywd_opt(year, week, Weekday::Mon).map(|_| Self { year, week })
+    }
+
+    /// 日付を含む週
+    pub fn containing(date: NaiveDate) -> Self {
+        let iso = date.iso_week();
+        Self {
+            year: iso.year(),
+            week: iso.week(),
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn week(&self) -> u32 {
+        self.week
+    }
+
+    /// 週の初日（月曜日）
+    pub fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_isoywd_opt(self.year, self.week, Weekday::Mon).unwrap_or(NaiveDate::MIN)
+    }
+
+    /// 週の最終日（日曜日）
+    pub fn last_day(&self) -> NaiveDate {
+        NaiveDate::from_isoywd_opt(self.year, self.week, Weekday::Sun).unwrap_or(NaiveDate::MAX)
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        Self::containing(date) == *self
+    }
+
+    /// 前の週
+    pub fn previous(&self) -> Self {
+        self.first_day()
+            .checked_sub_days(Days::new(7))
+            .map_or(*self, Self::containing)
+    }
+}
+
+impl fmt::Display for IsoWeek {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-W{:02}", self.year, self.week)
+    }
+}
+
+impl FromStr for IsoWeek {
+    type Err = ReportError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || ReportError::InvalidWeek(value.to_string());
+        let (year, week) = value.split_once("-W").ok_or_else(invalid)?;
+        if year.len() != 4 || week.len() != 2 {
+            return Err(invalid());
+        }
+
+        let year = year.parse().map_err(|_| invalid())?;
+        let week = week.parse().map_err(|_| invalid())?;
+        Self::new(year, week).ok_or_else(invalid)
+    }
+}
+
+impl Serialize for IsoWeek {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for IsoWeek {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// 週次レポートの保存先キー
+///
+/// ユーザーと週の組に対して常に同じキーになるため、再生成は同じ
+/// ドキュメントを上書きする
+pub fn weekly_report_key(user_id: Uuid, week: IsoWeek) -> String {
+    format!("{}{week}.json", weekly_report_prefix(user_id))
+}
+
+/// ユーザーの週次レポートのキーに共通するプレフィックス
+pub fn weekly_report_prefix(user_id: Uuid) -> String {
+    format!("reports/weekly/{user_id}/")
+}
+
+/// キーから週を取り出す（週次レポートのキーでない場合は None）
+pub fn week_of_report_key(key: &str) -> Option<IsoWeek> {
+    let (_, file) = key.strip_prefix("reports/weekly/")?.split_once('/')?;
+    file.strip_suffix(".json")?.parse().ok()
+}
+
+/// 週次レポート
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyReport {
+    pub user_id:            Uuid,
+    pub week:               IsoWeek,
+    pub period_start:       NaiveDate,
+    pub period_end:         NaiveDate,
+    pub stats:              WeeklyStats,
+    pub highlights:         WeeklyHighlights,
+    /// その週に新しく習得した語彙（上位のみ）
+    pub new_mastered_words: Vec<MasteredWord>,
+}
+
+impl WeeklyReport {
+    /// 内容のハッシュ（SHA-256 の16進表記）
+    ///
+    /// 生成日時を含まないため、元データが同じなら同じ値になる
+    pub fn content_hash(&self) -> Result<String, ReportError> {
+        let bytes = serde_json::to_vec(self)?;
+        Ok(hex::encode(Sha256::digest(&bytes)))
+    }
+}
+
+/// 週の集計
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyStats {
+    pub items_learned:   i32,
+    pub items_reviewed:  i32,
+    pub items_mastered:  i32,
+    pub study_minutes:   i32,
+    pub study_days:      i32,
+    pub sessions:        i32,
+    pub goals_completed: i32,
+    /// 正答率（0.0〜1.0、回答がない週は None）
+    pub accuracy:        Option<f32>,
+}
+
+/// 週のハイライト
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyHighlights {
+    /// 学習時間が最も長かった日
+    pub best_day:         Option<BestDay>,
+    /// 週の中で連続して学習した最長の日数
+    pub longest_run_days: i32,
+}
+
+/// 学習時間が最も長かった日
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BestDay {
+    pub date:          NaiveDate,
+    pub study_minutes: i32,
+    pub items_learned: i32,
+}
+
+/// 新しく習得した語彙
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MasteredWord {
+    pub item_id:     Uuid,
+    pub spelling:    String,
+    pub definition:  Option<String>,
+    pub mastered_at: DateTime<Utc>,
+}
+
+/// 保存する週次レポートドキュメント
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyReportDocument {
+    pub schema_version: u32,
+    /// `report` の内容のハッシュ（再生成時の変更検出に使う）
+    pub content_hash:   String,
+    pub generated_at:   DateTime<Utc>,
+    pub report:         WeeklyReport,
+}
+
+impl WeeklyReportDocument {
+    pub fn new(report: WeeklyReport, generated_at: DateTime<Utc>) -> Result<Self, ReportError> {
+        Ok(Self {
+            schema_version: WEEKLY_REPORT_SCHEMA_VERSION,
+            content_hash: report.content_hash()?,
+            generated_at,
+            report,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ReportError> {
+        Ok(serde_json::to_vec_pretty(self)?)
+    }
+
+    /// 保存されたドキュメントを読み込む
+    ///
+    /// 読み手が知らない新しいバージョンのドキュメントは拒否する
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReportError> {
+        #[derive(Deserialize)]
+        struct Header {
+            schema_version: u32,
+        }
+
+        let header: Header = serde_json::from_slice(bytes)?;
+        if header.schema_version > WEEKLY_REPORT_SCHEMA_VERSION {
+            return Err(ReportError::UnsupportedVersion {
+                version:   header.schema_version,
+                supported: WEEKLY_REPORT_SCHEMA_VERSION,
+            });
+        }
+
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn week(value: &str) -> IsoWeek {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_iso_week_boundaries_across_years() {
+        // 2020 年は 53 週あり、2021-01-03（日）までが 2020-W53
+        assert_eq!(IsoWeek::containing(date("2020-12-31")), week("2020-W53"));
+        assert_eq!(IsoWeek::containing(date("2021-01-03")), week("2020-W53"));
+        assert_eq!(IsoWeek::containing(date("2021-01-04")), week("2021-W01"));
+        // 2024-12-30（月）は 2025-W01
+        assert_eq!(IsoWeek::containing(date("2024-12-29")), week("2024-W52"));
+        assert_eq!(IsoWeek::containing(date("2024-12-30")), week("2025-W01"));
+
+        let w53 = week("2020-W53");
+        assert_eq!(w53.first_day(), date("2020-12-28"));
+        assert_eq!(w53.last_day(), date("2021-01-03"));
+        assert!(w53.contains(date("2021-01-03")));
+        assert!(!w53.contains(date("2021-01-04")));
+
+        assert_eq!(week("2021-W01").previous(), w53);
+        assert_eq!(week("2025-W01").previous(), week("2024-W52"));
+    }
+
+    #[test]
+    fn test_iso_week_parsing() {
+        assert_eq!(week("2025-W07").to_string(), "2025-W07");
+        assert_eq!(week("2025-W07").week(), 7);
+
+        for invalid in [
+            "2021-W53", "2025-W00", "2025-W7", "25-W07", "2025-07", "2025-Wxx",
+        ] {
+            assert!(
+                matches!(invalid.parse::<IsoWeek>(), Err(ReportError::InvalidWeek(_))),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_report_key_round_trip() {
+        let user_id = Uuid::new_v4();
+        let key = weekly_report_key(user_id, week("2020-W53"));
+
+        assert_eq!(key, format!("reports/weekly/{user_id}/2020-W53.json"));
+        assert!(key.starts_with(&weekly_report_prefix(user_id)));
+        assert_eq!(week_of_report_key(&key), Some(week("2020-W53")));
+        assert_eq!(week_of_report_key("reports/weekly/x/notes.json"), None);
+    }
+
+    fn sample_report() -> WeeklyReport {
+        let week = week("2025-W02");
+        WeeklyReport {
+            user_id: Uuid::new_v4(),
+            week,
+            period_start: week.first_day(),
+            period_end: week.last_day(),
+            stats: WeeklyStats {
+                items_learned: 12,
+                study_days: 2,
+                accuracy: Some(0.75),
+                ..Default::default()
+            },
+            highlights: WeeklyHighlights::default(),
+            new_mastered_words: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_document_round_trip_and_hash() {
+        let report = sample_report();
+        let document = WeeklyReportDocument::new(report.clone(), Utc::now()).unwrap();
+
+        let decoded = WeeklyReportDocument::from_bytes(&document.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded, document);
+        assert_eq!(decoded.schema_version, WEEKLY_REPORT_SCHEMA_VERSION);
+
+        // 生成日時はハッシュに影響せず、内容の変更は影響する
+        let regenerated = WeeklyReportDocument::new(report.clone(), Utc::now()).unwrap();
+        assert_eq!(regenerated.content_hash, document.content_hash);
+
+        let mut changed = report;
+        changed.stats.items_learned += 1;
+        assert_ne!(changed.content_hash().unwrap(), document.content_hash);
+    }
+
+    #[test]
+    fn test_newer_document_version_is_rejected() {
+        let mut document = WeeklyReportDocument::new(sample_report(), Utc::now()).unwrap();
+        document.schema_version = WEEKLY_REPORT_SCHEMA_VERSION + 1;
+
+        assert!(matches!(
+            WeeklyReportDocument::from_bytes(&document.to_bytes().unwrap()),
+            Err(ReportError::UnsupportedVersion { version: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,241 @@
+//! 学習する時間帯のモデル
+//!
+//! Progress Projection Service が集計し、復習リマインダーの時刻を決めるのに
+//! 使う。Progress Query Service も同じモデルで時間帯を返す。
+//!
+//! セッションの開始時刻を、UTC の「週の中の15分枠」（月曜 0:00 起点、672 枠）
+//! ごとに数えておく。読み出すときにユーザーのタイムゾーンで「週の中の時」
+//! （168 枠）に集計し直すため、タイムゾーンを変更したユーザーも新しい
+//! ローカル時刻で扱える。15分単位で持つのは、30分・45分ずれの
+//! タイムゾーンでも正確に変換するため。
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 1週間の15分枠の数
+pub const QUARTERS_PER_WEEK: u32 = 7 * 24 * 4;
+
+/// 1週間の時間枠の数
+pub const HOURS_PER_WEEK: u32 = 7 * 24;
+
+/// 学習する時間帯を推定するのに必要なセッション数
+pub const MIN_STUDY_TIME_SAMPLES: u32 = 10;
+
+/// 返す時間帯の数
+pub const PREFERRED_WINDOW_LIMIT: usize = 3;
+
+/// 時刻が属する UTC の15分枠
+pub fn utc_quarter_of_week(at: DateTime<Utc>) -> u32 {
+    let hour_of_week = at.weekday().num_days_from_monday() * 24 + at.hour();
+    hour_of_week * 4 + at.minute() / 15
+}
+
+/// ユーザーのローカル時刻で集計した、週の中の時ごとのセッション数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StudyTimeHistogram {
+    hours: [u32; HOURS_PER_WEEK as usize],
+}
+
+impl StudyTimeHistogram {
+    /// UTC の15分枠ごとの `(枠, セッション数)` からローカル時刻で集計する
+    ///
+    /// 15分の倍数でないオフセットは15分単位に切り捨てる
+    pub fn from_utc_quarters(quarters: &[(u32, u32)], offset: FixedOffset) -> Self {
+        let shift = i64::from(offset.local_minus_utc().div_euclid(15 * 60));
+        let mut hours = [0; HOURS_PER_WEEK as usize];
+
+        for &(quarter, sessions) in quarters {
+            if quarter >= QUARTERS_PER_WEEK {
+                continue;
+            }
+            let local = (i64::from(quarter) + shift).rem_euclid(i64::from(QUARTERS_PER_WEEK));
+            hours[(local / 4) as usize] += sessions;
+        }
+
+        Self { hours }
+    }
+
+    /// セッションの総数
+    pub fn total(&self) -> u32 {
+        self.hours.iter().sum()
+    }
+
+    /// ローカル時刻の週の中の時（月曜 0 時 = 0）のセッション数
+    pub fn sessions_at(&self, hour_of_week: u32) -> u32 {
+        self.hours.get(hour_of_week as usize).copied().unwrap_or(0)
+    }
+
+    /// セッションの多い時間帯を上位 `limit` 件まで返す
+    ///
+    /// セッションが `min_samples` に満たない場合は推定できないため空を返す。
+    /// 確信度はその時間帯に始めたセッションの割合
+    pub fn preferred_windows(&self, limit: usize, min_samples: u32) -> Vec<StudyWindow> {
+        let total = self.total();
+        if total == 0 || total < min_samples {
+            return Vec::new();
+        }
+
+        let mut windows: Vec<StudyWindow> = (0..HOURS_PER_WEEK)
+            .filter(|&hour| self.sessions_at(hour) > 0)
+            .map(|hour| {
+                let sessions = self.sessions_at(hour);
+                StudyWindow {
+                    hour_of_week: hour,
+                    sessions,
+                    confidence: f64::from(sessions) / f64::from(total),
+                }
+            })
+            .collect();
+        windows.sort_by(|a, b| {
+            b.sessions
+                .cmp(&a.sessions)
+                .then(a.hour_of_week.cmp(&b.hour_of_week))
+        });
+        windows.truncate(limit);
+        windows
+    }
+}
+
+/// 学習することの多い1時間の時間帯（ローカル時刻）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StudyWindow {
+    /// 週の中の時（月曜 0 時 = 0）
+    pub hour_of_week: u32,
+    pub sessions:     u32,
+    /// 0.0〜1.0
+    pub confidence:   f64,
+}
+
+impl StudyWindow {
+    /// 曜日（月曜 = 0）
+    pub fn weekday_from_monday(&self) -> u32 {
+        self.hour_of_week / 24
+    }
+
+    /// 時間帯が始まる時刻（ローカル時刻の時）
+    pub fn hour(&self) -> u32 {
+        self.hour_of_week % 24
+    }
+
+    /// ローカルの日付 `date` にこの時間帯が始まる時刻（UTC）
+    ///
+    /// 曜日が合わない日は None
+    pub fn start_on(&self, date: NaiveDate, offset: FixedOffset) -> Option<DateTime<Utc>> {
+        if date.weekday().num_days_from_monday() != self.weekday_from_monday() {
+            return None;
+        }
+        local_time_on(date, NaiveTime::from_hms_opt(self.hour(), 0, 0)?, offset)
+    }
+}
+
+/// ユーザーの学習する時間帯（GetPreferredStudyWindows の応答）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreferredStudyWindows {
+    pub user_id:            Uuid,
+    /// 時間帯を集計したタイムゾーン（UTC からのオフセット、分）
+    pub utc_offset_minutes: i32,
+    pub total_sessions:     u32,
+    /// 確信度の高い順。セッションが足りない場合は空
+    pub windows:            Vec<StudyWindow>,
+}
+
+impl PreferredStudyWindows {
+    /// UTC の15分枠ごとの `(枠, セッション数)` から、`offset` のローカル時刻で
+    /// 時間帯を推定する
+    pub fn from_utc_quarters(
+        user_id: Uuid,
+        quarters: &[(u32, u32)],
+        offset: FixedOffset,
+        min_samples: u32,
+    ) -> Self {
+        let histogram = StudyTimeHistogram::from_utc_quarters(quarters, offset);
+
+        Self {
+            user_id,
+            utc_offset_minutes: offset.local_minus_utc() / 60,
+            total_sessions: histogram.total(),
+            windows: histogram.preferred_windows(PREFERRED_WINDOW_LIMIT, min_samples),
+        }
+    }
+}
+
+/// ローカルの日付と時刻を UTC にする（存在しない時刻は None）
+pub fn local_time_on(
+    date: NaiveDate,
+    time: NaiveTime,
+    offset: FixedOffset,
+) -> Option<DateTime<Utc>> {
+    offset
+        .from_local_datetime(&date.and_time(time))
+        .single()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn offset_minutes(minutes: i32) -> FixedOffset {
+        FixedOffset::east_opt(minutes * 60).unwrap()
+    }
+
+    fn quarters(starts: &[&str]) -> Vec<(u32, u32)> {
+        let mut counts = std::collections::BTreeMap::new();
+        for start in starts {
+            *counts.entry(utc_quarter_of_week(utc(start))).or_insert(0) += 1;
+        }
+        counts.into_iter().collect()
+    }
+
+    #[test]
+    fn test_histogram_uses_local_hour_of_week() {
+        // UTC 日曜 23:30 は東京（+09:00）の月曜 08:30、ニューヨーク（-05:00）の日曜
+        // 18:30
+        let rows = quarters(&["2025-01-05T23:30:00Z"]);
+        assert_eq!(rows, vec![(6 * 96 + 23 * 4 + 2, 1)]);
+
+        let tokyo = StudyTimeHistogram::from_utc_quarters(&rows, offset_minutes(9 * 60));
+        assert_eq!(tokyo.sessions_at(8), 1);
+        assert_eq!(tokyo.total(), 1);
+
+        let new_york = StudyTimeHistogram::from_utc_quarters(&rows, offset_minutes(-5 * 60));
+        assert_eq!(new_york.sessions_at(6 * 24 + 18), 1);
+
+        // インド（+05:30）では UTC 日曜 18:45 が月曜 00:15 になる
+        let india = StudyTimeHistogram::from_utc_quarters(
+            &quarters(&["2025-01-05T18:45:00Z", "2025-01-05T18:15:00Z"]),
+            offset_minutes(5 * 60 + 30),
+        );
+        assert_eq!(india.sessions_at(0), 1);
+        assert_eq!(india.sessions_at(6 * 24 + 23), 1);
+    }
+
+    #[test]
+    fn test_windows_require_minimum_samples() {
+        let starts = ["2025-01-06T19:10:00Z"; 9];
+        let histogram =
+            StudyTimeHistogram::from_utc_quarters(&quarters(&starts), offset_minutes(0));
+        assert!(
+            histogram
+                .preferred_windows(PREFERRED_WINDOW_LIMIT, MIN_STUDY_TIME_SAMPLES)
+                .is_empty()
+        );
+
+        let mut starts = starts.to_vec();
+        starts.push("2025-01-07T07:00:00Z");
+        let histogram =
+            StudyTimeHistogram::from_utc_quarters(&quarters(&starts), offset_minutes(0));
+        let windows = histogram.preferred_windows(PREFERRED_WINDOW_LIMIT, MIN_STUDY_TIME_SAMPLES);
+
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].hour_of_week, 19);
+        assert_eq!(windows[0].sessions, 9);
+        assert!((windows[0].confidence - 0.9).abs() < f64::EPSILON);
+        assert_eq!(windows[1].hour_of_week, 24 + 7);
+    }
+}
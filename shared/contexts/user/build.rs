@@ -2,8 +2,15 @@
 //!
 //! User Context 固有のイベントを protobuf から生成します。
 
+#[path = "../../kernel/build_support/proto_events.rs"]
+mod proto_events;
+
+use std::{env, path::PathBuf};
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let proto_root = "../../../protos".to_string();
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    let descriptor_set = out_dir.join("user_events_descriptor.bin");
 
     // User イベント定義のコンパイル
     {
@@ -23,7 +30,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         // tonic_prost_build の設定
         let builder = tonic_prost_build::configure()
             .build_server(false)
-            .build_client(false);
+            .build_client(false)
+            .file_descriptor_set_path(&descriptor_set);
 
         // compile_with_config を使用
         builder.compile_with_config(
@@ -31,6 +39,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             &[&format!("{proto_root}/events/user_events.proto")],
             &[&proto_root],
         )?;
+
+        // イベントのアクセサとテスト用のビルダーを生成
+        proto_events::generate(
+            &descriptor_set,
+            &out_dir,
+            &["effect.events.user"],
+            &[
+                (".effect.common", "::shared_kernel::proto::effect::common"),
+                (".effect.services.user", "crate::proto"),
+            ],
+        )?;
     }
 
     // サービス定義を別設定でコンパイル
@@ -46,10 +65,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .type_attribute(".", "#[allow(missing_docs)]")
             .extern_path(".effect.common", "::shared_kernel::proto::effect::common");
 
-        // tonic_prost_build の設定
+        // tonic_prost_build の設定（通知設定の取得にクライアントを使う）
         let builder = tonic_prost_build::configure()
             .build_server(false)
-            .build_client(false);
+            .build_client(true);
 
         // compile_with_config を使用
         builder.compile_with_config(
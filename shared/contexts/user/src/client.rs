@@ -0,0 +1,140 @@
+//! User Service クライアント
+//!
+//! 通知を発行するサービスが、User Service の内部 RPC
+//! GetNotificationPreferences から通知設定を取得するためのクライアント。
+//! 通知のたびに問い合わせないよう、通常は
+//! [`shared_integration_events::CachedPreferenceSource`] で包んで使う
+
+use async_trait::async_trait;
+use shared_integration_events::{
+    IntegrationEventError,
+    NotificationCategory,
+    NotificationChannel,
+    NotificationPreferenceSource,
+    NotificationPreferences,
+    QuietHours,
+    Result,
+};
+use tonic::transport::{Channel, Endpoint};
+
+use crate::proto::{
+    self,
+    GetNotificationPreferencesRequest,
+    user_service_client::UserServiceClient,
+};
+
+/// User Service から通知設定を取得する取得元
+#[derive(Clone)]
+pub struct UserServicePreferenceSource {
+    client: UserServiceClient<Channel>,
+}
+
+impl UserServicePreferenceSource {
+    /// 接続を遅延させてクライアントを作成（最初の問い合わせで接続する）
+    ///
+    /// # Errors
+    ///
+    /// - `tonic::transport::Error` - URL が不正な場合
+    pub fn connect_lazy(
+        url: impl Into<String>,
+    ) -> std::result::Result<Self, tonic::transport::Error> {
+        let channel = Endpoint::from_shared(url.into())?.connect_lazy();
+        Ok(Self {
+            client: UserServiceClient::new(channel),
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationPreferenceSource for UserServicePreferenceSource {
+    async fn get_preferences(&self, user_id: &str) -> Result<NotificationPreferences> {
+        let response = self
+            .client
+            .clone()
+            .get_notification_preferences(GetNotificationPreferencesRequest {
+                user_id: user_id.to_string(),
+            })
+            .await
+            .map_err(|status| IntegrationEventError::PreferenceLookup(status.to_string()))?;
+
+        Ok(response.into_inner().preferences.map_or_else(
+            || NotificationPreferences::default_for(user_id),
+            preferences_from_proto,
+        ))
+    }
+}
+
+/// proto の通知設定を変換する
+///
+/// このクライアントが知らないカテゴリ・チャネル（新しい User Service が
+/// 追加した値）は読み飛ばす
+pub fn preferences_from_proto(
+    preferences: proto::NotificationPreferences,
+) -> NotificationPreferences {
+    let disabled_categories = preferences
+        .disabled_categories()
+        .filter_map(|category| match category {
+            proto::NotificationCategory::DailySummary => Some(NotificationCategory::DailySummary),
+            proto::NotificationCategory::GoalReminder => Some(NotificationCategory::GoalReminder),
+            proto::NotificationCategory::ReviewReminder => {
+                Some(NotificationCategory::ReviewReminder)
+            },
+            proto::NotificationCategory::Editorial => Some(NotificationCategory::Editorial),
+            proto::NotificationCategory::WeeklyReport => Some(NotificationCategory::WeeklyReport),
+            proto::NotificationCategory::Unspecified => None,
+        })
+        .collect();
+    let preferred_channels = preferences
+        .preferred_channels()
+        .filter_map(|channel| match channel {
+            proto::NotificationChannel::Email => Some(NotificationChannel::Email),
+            proto::NotificationChannel::Push => Some(NotificationChannel::Push),
+            proto::NotificationChannel::InApp => Some(NotificationChannel::InApp),
+            proto::NotificationChannel::Unspecified => None,
+        })
+        .collect();
+
+    NotificationPreferences {
+        disabled_categories,
+        preferred_channels,
+        quiet_hours: preferences
+            .quiet_hours
+            .and_then(|quiet| QuietHours::from_minutes(quiet.start_minute, quiet.end_minute)),
+        utc_offset_minutes: preferences.utc_offset_minutes,
+        user_id: preferences.user_id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preferences_from_proto_skips_unknown_values() {
+        let preferences = preferences_from_proto(proto::NotificationPreferences {
+            user_id:             "user-1".to_string(),
+            disabled_categories: vec![proto::NotificationCategory::WeeklyReport as i32, 99],
+            preferred_channels:  vec![
+                proto::NotificationChannel::Unspecified as i32,
+                proto::NotificationChannel::InApp as i32,
+            ],
+            quiet_hours:         Some(proto::QuietHours {
+                start_minute: 22 * 60,
+                end_minute:   7 * 60,
+            }),
+            utc_offset_minutes:  9 * 60,
+            updated_at:          None,
+        });
+
+        assert!(!preferences.is_enabled(NotificationCategory::WeeklyReport));
+        assert_eq!(preferences.disabled_categories.len(), 1);
+        assert_eq!(
+            preferences.preferred_channels,
+            vec![NotificationChannel::InApp]
+        );
+        assert_eq!(
+            preferences.quiet_hours,
+            QuietHours::from_minutes(22 * 60, 7 * 60)
+        );
+    }
+}
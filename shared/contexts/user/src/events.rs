@@ -4,17 +4,43 @@
 //! Proto ファイルから生成されたコードを使用し、
 //! 必要に応じて拡張トレイトを実装します。
 
-use shared_kernel::{DomainEvent, EventMetadata, IntegrationEvent};
+use prost::Message as _;
+use shared_kernel::{
+    CloudEventCodec,
+    CloudEventData,
+    DomainEvent,
+    EventError,
+    HasMetadata,
+    IntegrationEvent,
+};
 
 // Proto 生成コードを含める
 pub mod proto {
     include!(concat!(env!("OUT_DIR"), "/effect.events.user.rs"));
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/effect.events.user.proto_events.rs"
+    ));
     include!(concat!(env!("OUT_DIR"), "/effect.services.user.rs"));
 }
 
 // Proto 型を再エクスポート
 pub use proto::*;
 
+/// テスト用のイベントのビルダー
+///
+/// ```ignore
+/// let event = UserSignedUpBuilder::default()
+///     .email("user@example.com")
+///     .build_event();
+/// ```
+#[cfg(any(test, feature = "test-util"))]
+pub mod builders {
+    use super::*;
+
+    include!(concat!(env!("OUT_DIR"), "/effect.events.user.builders.rs"));
+}
+
 // DomainEvent トレイトの実装（Proto 生成型用）
 impl DomainEvent for UserEvent {
     fn event_type(&self) -> &str {
@@ -27,14 +53,53 @@ impl DomainEvent for UserEvent {
             Some(user_event::Event::UserSignedIn(_)) => "UserSignedIn",
             Some(user_event::Event::UserSignedOut(_)) => "UserSignedOut",
             Some(user_event::Event::SessionRefreshed(_)) => "UserSessionRefreshed",
+            Some(user_event::Event::NotificationPreferencesUpdated(_)) => {
+                "UserNotificationPreferencesUpdated"
+            },
             None => "UserEventUnknown",
         }
     }
 
-    fn metadata(&self) -> &EventMetadata {
-        // Proto の EventMetadata を shared_kernel の EventMetadata に変換する必要がある
-        // 一時的にパニックを返す（後で実装）
-        todo!("Convert proto EventMetadata to shared_kernel EventMetadata")
+    fn full_event_type(&self) -> &str {
+        self.qualified_event_type().unwrap_or("user.Unknown")
+    }
+
+    // metadata() は実装せず、Proto のメタデータから直接取り出す
+    fn event_id(&self) -> &str {
+        HasMetadata::metadata(self).map_or("", |metadata| metadata.event_id.as_str())
+    }
+
+    fn aggregate_id(&self) -> &str {
+        HasMetadata::aggregate_id(self).unwrap_or_default()
+    }
+
+    fn correlation_id(&self) -> &str {
+        HasMetadata::metadata(self)
+            .and_then(|metadata| metadata.correlation_id.as_deref())
+            .unwrap_or_else(|| DomainEvent::event_id(self))
+    }
+
+    fn occurred_at(&self) -> chrono::DateTime<chrono::Utc> {
+        HasMetadata::occurred_at(self).unwrap_or_default()
+    }
+}
+
+// CloudEvents の data は Protobuf のバイナリ
+impl CloudEventCodec for UserEvent {
+    const DATA_CONTENT_TYPE: &'static str = "application/protobuf";
+
+    fn encode_data(&self) -> Result<CloudEventData, EventError> {
+        Ok(CloudEventData::Binary(self.encode_to_vec()))
+    }
+
+    fn decode_data(data: &CloudEventData) -> Result<Self, EventError> {
+        match data {
+            CloudEventData::Binary(bytes) => Self::decode(bytes.as_slice())
+                .map_err(|e| EventError::Deserialization(e.to_string())),
+            CloudEventData::Json(_) => Err(EventError::InvalidEvent(
+                "UserEvent must be carried as protobuf data".to_string(),
+            )),
+        }
     }
 }
 
@@ -3,7 +3,9 @@
 //! User Context 内の各マイクロサービス間で共有される
 //! ドメインモデル、イベント、コマンド、クエリを定義
 
+pub mod client;
 pub mod events;
 
 // Re-export commonly used items
+pub use client::UserServicePreferenceSource;
 pub use events::*;
@@ -2,8 +2,15 @@
 //!
 //! Vocabulary Context 固有のイベントを protobuf から生成します。
 
+#[path = "../../kernel/build_support/proto_events.rs"]
+mod proto_events;
+
+use std::{env, path::PathBuf};
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let proto_root = "../../../protos".to_string();
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    let descriptor_set = out_dir.join("vocabulary_events_descriptor.bin");
 
     // prost_build::Config を作成して protoc のパスを設定
     let mut prost_config = ::prost_build::Config::new();
@@ -20,7 +27,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // tonic_prost_build の設定
     let builder = tonic_prost_build::configure()
         .build_server(false)
-        .build_client(false);
+        .build_client(false)
+        .file_descriptor_set_path(&descriptor_set);
 
     // compile_with_config を使用
     builder.compile_with_config(
@@ -32,5 +40,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         &[&proto_root],
     )?;
 
+    // イベントのアクセサとテスト用のビルダーを生成
+    proto_events::generate(
+        &descriptor_set,
+        &out_dir,
+        &["effect.events.vocabulary"],
+        &[(".effect.common", "::shared_kernel::proto::effect::common")],
+    )?;
+
     Ok(())
 }
@@ -1,5 +1,7 @@
 //! Vocabulary Context 固有の値オブジェクト
 
+use std::{fmt, str::FromStr};
+
 use serde::{Deserialize, Serialize};
 
 /// 定義
@@ -68,3 +70,120 @@ pub enum Domain {
     Literary,
     Other(String),
 }
+
+impl Register {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Formal => "formal",
+            Self::Neutral => "neutral",
+            Self::Informal => "informal",
+            Self::Slang => "slang",
+            Self::Technical => "technical",
+        }
+    }
+}
+
+impl FromStr for Register {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "formal" => Ok(Self::Formal),
+            "neutral" => Ok(Self::Neutral),
+            "informal" => Ok(Self::Informal),
+            "slang" => Ok(Self::Slang),
+            "technical" => Ok(Self::Technical),
+            _ => Err(format!("Invalid Register: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Domain {
+    /// 既定の分野以外は `Other` の値をそのまま返す
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::General => "general",
+            Self::Academic => "academic",
+            Self::Business => "business",
+            Self::Medical => "medical",
+            Self::Legal => "legal",
+            Self::Technical => "technical",
+            Self::Scientific => "scientific",
+            Self::Literary => "literary",
+            Self::Other(value) => value,
+        }
+    }
+}
+
+impl FromStr for Domain {
+    type Err = String;
+
+    /// 既定の分野名に一致しない値は `Other` として受け付ける
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase();
+        match normalized.as_str() {
+            "" => Err("Domain cannot be empty".to_string()),
+            "general" => Ok(Self::General),
+            "academic" => Ok(Self::Academic),
+            "business" => Ok(Self::Business),
+            "medical" => Ok(Self::Medical),
+            "legal" => Ok(Self::Legal),
+            "technical" => Ok(Self::Technical),
+            "scientific" => Ok(Self::Scientific),
+            "literary" => Ok(Self::Literary),
+            _ if normalized.len() > 50 => Err("Domain cannot exceed 50 characters".to_string()),
+            _ => Ok(Self::Other(normalized)),
+        }
+    }
+}
+
+impl fmt::Display for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// 使用上の注意（学習時に解答とともに提示する）
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UsageNotes(String);
+
+impl UsageNotes {
+    /// 最大文字数
+    pub const MAX_CHARS: usize = 500;
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for UsageNotes {
+    type Err = String;
+
+    /// 前後の空白を除いて受け付ける
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        if trimmed.is_empty() {
+            Err("UsageNotes cannot be empty".to_string())
+        } else if trimmed.chars().count() > Self::MAX_CHARS {
+            Err(format!(
+                "UsageNotes cannot exceed {} characters",
+                Self::MAX_CHARS
+            ))
+        } else {
+            Ok(Self(trimmed.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for UsageNotes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
@@ -4,16 +4,43 @@
 //! Proto ファイルから生成されたコードを使用し、
 //! 必要に応じて拡張トレイトを実装します。
 
-use shared_kernel::{DomainEvent, EventMetadata, IntegrationEvent};
+use prost::Message as _;
+use shared_kernel::{
+    CloudEventCodec,
+    CloudEventData,
+    DomainEvent,
+    EventError,
+    HasMetadata,
+    IntegrationEvent,
+};
 
 // Proto 生成コードを含める
 pub mod proto {
     include!(concat!(env!("OUT_DIR"), "/effect.events.vocabulary.rs"));
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/effect.events.vocabulary.proto_events.rs"
+    ));
 }
 
 // Proto 型を再エクスポート
 pub use proto::*;
 
+/// テスト用のイベントのビルダー
+///
+/// ```ignore
+/// let event = ItemPublishedBuilder::default().item_id("item-1").build_event();
+/// ```
+#[cfg(any(test, feature = "test-util"))]
+pub mod builders {
+    use super::*;
+
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/effect.events.vocabulary.builders.rs"
+    ));
+}
+
 // DomainEvent トレイトの実装（Proto 生成型用）
 impl DomainEvent for VocabularyEvent {
     fn event_type(&self) -> &str {
@@ -34,10 +61,46 @@ impl DomainEvent for VocabularyEvent {
         }
     }
 
-    fn metadata(&self) -> &EventMetadata {
-        // Proto の EventMetadata を shared_kernel の EventMetadata に変換する必要がある
-        // 一時的にパニックを返す（後で実装）
-        todo!("Convert proto EventMetadata to shared_kernel EventMetadata")
+    fn full_event_type(&self) -> &str {
+        self.qualified_event_type().unwrap_or("vocabulary.Unknown")
+    }
+
+    // metadata() は実装せず、Proto のメタデータから直接取り出す
+    fn event_id(&self) -> &str {
+        HasMetadata::metadata(self).map_or("", |metadata| metadata.event_id.as_str())
+    }
+
+    fn aggregate_id(&self) -> &str {
+        HasMetadata::aggregate_id(self).unwrap_or_default()
+    }
+
+    fn correlation_id(&self) -> &str {
+        HasMetadata::metadata(self)
+            .and_then(|metadata| metadata.correlation_id.as_deref())
+            .unwrap_or_else(|| DomainEvent::event_id(self))
+    }
+
+    fn occurred_at(&self) -> chrono::DateTime<chrono::Utc> {
+        HasMetadata::occurred_at(self).unwrap_or_default()
+    }
+}
+
+// CloudEvents の data は Protobuf のバイナリ
+impl CloudEventCodec for VocabularyEvent {
+    const DATA_CONTENT_TYPE: &'static str = "application/protobuf";
+
+    fn encode_data(&self) -> Result<CloudEventData, EventError> {
+        Ok(CloudEventData::Binary(self.encode_to_vec()))
+    }
+
+    fn decode_data(data: &CloudEventData) -> Result<Self, EventError> {
+        match data {
+            CloudEventData::Binary(bytes) => Self::decode(bytes.as_slice())
+                .map_err(|e| EventError::Deserialization(e.to_string())),
+            CloudEventData::Json(_) => Err(EventError::InvalidEvent(
+                "VocabularyEvent must be carried as protobuf data".to_string(),
+            )),
+        }
     }
 }
 
@@ -106,3 +169,93 @@ impl IntegrationEvent for VocabularyIntegrationEvent {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::create_event_metadata;
+
+    #[test]
+    fn test_generated_metadata_accessors() {
+        let item_id = Uuid::new_v4();
+        let occurred_at = Utc.with_ymd_and_hms(2025, 4, 1, 9, 0, 0).unwrap();
+        let event = VocabularyEvent {
+            event: Some(vocabulary_event::Event::ItemPublished(ItemPublished {
+                metadata: Some(create_event_metadata(
+                    item_id,
+                    "VocabularyItem",
+                    occurred_at,
+                )),
+                item_id:  item_id.to_string(),
+            })),
+        };
+
+        assert_eq!(
+            HasMetadata::aggregate_id(&event),
+            Some(item_id.to_string().as_str())
+        );
+        assert_eq!(HasMetadata::occurred_at(&event), Some(occurred_at));
+        assert_eq!(DomainEvent::aggregate_id(&event), item_id.to_string());
+        assert_eq!(DomainEvent::occurred_at(&event), occurred_at);
+        assert_eq!(event.correlation_id(), DomainEvent::event_id(&event));
+        assert!(HasMetadata::metadata(&VocabularyEvent { event: None }).is_none());
+    }
+
+    #[test]
+    fn test_cloudevent_round_trip_with_protobuf_data() {
+        let item_id = Uuid::new_v4();
+        let event = VocabularyEvent {
+            event: Some(vocabulary_event::Event::ItemPublished(ItemPublished {
+                metadata: Some(create_event_metadata(item_id, "VocabularyItem", Utc::now())),
+                item_id:  item_id.to_string(),
+            })),
+        };
+
+        let cloudevent = event.to_cloudevent().unwrap();
+        assert_eq!(cloudevent.event_type, "vocabulary.ItemPublished");
+        assert_eq!(cloudevent.source, "/effect/vocabulary");
+        assert_eq!(cloudevent.subject, Some(item_id.to_string()));
+
+        let (headers, body) = cloudevent.to_binary().unwrap();
+        let received = shared_kernel::CloudEvent::from_binary(&headers, &body).unwrap();
+        assert_eq!(VocabularyEvent::from_cloudevent(&received).unwrap(), event);
+    }
+
+    #[test]
+    fn test_builders_fill_metadata_and_wrap_the_event() {
+        let event = builders::UpdateConflictedBuilder::default()
+            .conflicting_fields(["definitions", "synonyms"])
+            .build_event();
+
+        assert_eq!(event.full_event_type(), "vocabulary.UpdateConflicted");
+        assert!(HasMetadata::aggregate_id(&event).is_some());
+        let Some(vocabulary_event::Event::UpdateConflicted(conflicted)) = event.event else {
+            panic!("expected UpdateConflicted");
+        };
+        assert_eq!(conflicted.conflicting_fields, ["definitions", "synonyms"]);
+
+        let published = builders::ItemPublishedBuilder::default()
+            .item_id("item-1")
+            .build();
+        assert_eq!(published.item_id, "item-1");
+    }
+
+    #[test]
+    fn test_full_event_type_is_qualified_with_the_context() {
+        let event = VocabularyEvent {
+            event: Some(vocabulary_event::Event::ItemPublished(ItemPublished {
+                metadata: None,
+                item_id:  Uuid::new_v4().to_string(),
+            })),
+        };
+
+        assert_eq!(event.full_event_type(), "vocabulary.ItemPublished");
+        assert_eq!(
+            VocabularyEvent { event: None }.full_event_type(),
+            "vocabulary.Unknown"
+        );
+    }
+}
@@ -26,10 +26,7 @@ pub fn create_event_metadata(
     shared_kernel::proto::effect::common::EventMetadata {
         event_id:          Uuid::new_v4().to_string(),
         aggregate_id:      aggregate_id.to_string(),
-        occurred_at:       Some(prost_types::Timestamp {
-            seconds: occurred_at.timestamp(),
-            nanos:   occurred_at.timestamp_subsec_nanos() as i32,
-        }),
+        occurred_at:       Some(shared_kernel::to_proto_timestamp(&occurred_at)),
         version:           0,
         caused_by_user_id: None,
         correlation_id:    None,
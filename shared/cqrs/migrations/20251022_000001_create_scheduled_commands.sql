@@ -0,0 +1,21 @@
+-- 予約したコマンド（shared_cqrs の PostgresScheduleStore が使う）
+-- schedule_key で取り消し・置き換えを行い、locked_until で複数のディスパッチャーの同時実行を防ぐ
+
+CREATE TABLE IF NOT EXISTS scheduled_commands (
+    schedule_id UUID PRIMARY KEY,
+    schedule_key VARCHAR(255) NOT NULL UNIQUE,
+    command_name VARCHAR(255) NOT NULL,
+    payload JSONB NOT NULL,
+    correlation_id VARCHAR(255),
+    due_at TIMESTAMPTZ NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',  -- pending / failed（諦めた予約）
+    attempts INTEGER NOT NULL DEFAULT 0,
+    locked_until TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_scheduled_commands_pending_due_at
+    ON scheduled_commands (due_at)
+    WHERE status = 'pending';
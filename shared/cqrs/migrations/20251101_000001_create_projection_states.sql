@@ -0,0 +1,9 @@
+-- プロジェクションのチェックポイント（shared_cqrs の PostgresCheckpointStore が使う）
+-- プロジェクション名ごとに、処理済みの最後の位置とイベントを保持する
+
+CREATE TABLE IF NOT EXISTS projection_states (
+    projection_name VARCHAR(100) PRIMARY KEY,
+    last_position BIGINT NOT NULL DEFAULT 0,
+    last_event_id UUID,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,229 @@
+//! コマンドの認可
+//!
+//! [`AuthorizationMiddleware`] はコマンドごとに必要な権限を登録し、
+//! [`CommandContext`](crate::CommandContext) の拡張値に入った [`Claims`]
+//! のロールが権限を持つか確かめる。権限を登録していないコマンドはそのまま通す。
+//!
+//! ```ignore
+//! let bus = CommandBus::new()
+//!     .register::<ChangeUserRole, _>(handler)
+//!     .with_middleware(
+//!         AuthorizationMiddleware::new().requires::<ChangeUserRole>(Permission::UserManageRoles),
+//!     );
+//!
+//! bus.dispatch_with(command, CommandContext::new().with_extension(claims)).await?;
+//! ```
+
+use std::{any::Any, collections::HashMap};
+
+use async_trait::async_trait;
+use shared_security::{Claims, Permission};
+use tracing::warn;
+
+use crate::{
+    command::{Command, CommandEnvelope, Middleware, Next},
+    error::{CqrsError, Result},
+};
+
+/// コマンドごとに必要な権限を確認する
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Default)]
+pub struct AuthorizationMiddleware {
+    required_permissions: HashMap<&'static str, Vec<Permission>>,
+}
+
+impl AuthorizationMiddleware {
+    /// 必要な権限が登録されていない状態で作る
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// コマンドの実行に必要な権限を追加する
+    /// （複数追加した場合はすべてを持っている必要がある）
+    #[must_use]
+    pub fn requires<C: Command>(mut self, permission: Permission) -> Self {
+        self.required_permissions
+            .entry(C::NAME)
+            .or_default()
+            .push(permission);
+        self
+    }
+
+    fn authorize(&self, envelope: &CommandEnvelope) -> Result<()> {
+        let command = envelope.name();
+        let Some(permissions) = self.required_permissions.get(command) else {
+            return Ok(());
+        };
+
+        let claims = envelope
+            .context()
+            .extensions
+            .get::<Claims>()
+            .ok_or(CqrsError::Unauthenticated(command))?;
+        let Some(missing) = permissions
+            .iter()
+            .find(|permission| !claims.has(**permission))
+        else {
+            return Ok(());
+        };
+
+        warn!(
+            command,
+            subject = %claims.sub,
+            role = %claims.role,
+            permission = %missing,
+            "Command rejected: missing required permission"
+        );
+        Err(CqrsError::Forbidden {
+            command,
+            subject: claims.sub.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl Middleware for AuthorizationMiddleware {
+    async fn handle(
+        &self,
+        envelope: CommandEnvelope,
+        next: Next<'_>,
+    ) -> Result<Box<dyn Any + Send>> {
+        self.authorize(&envelope)?;
+        next.run(envelope).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use shared_security::Role;
+
+    use super::*;
+    use crate::{
+        command::{CommandBus, CommandHandler},
+        context::CommandContext,
+    };
+
+    struct ChangeUserRole;
+
+    impl Command for ChangeUserRole {
+        type Output = ();
+
+        const NAME: &'static str = "ChangeUserRole";
+    }
+
+    struct PublishVocabularyItem;
+
+    impl Command for PublishVocabularyItem {
+        type Output = ();
+
+        const NAME: &'static str = "PublishVocabularyItem";
+    }
+
+    struct RecordAnswer;
+
+    impl Command for RecordAnswer {
+        type Output = ();
+
+        const NAME: &'static str = "RecordAnswer";
+    }
+
+    struct Accept;
+
+    #[async_trait]
+    impl<C: Command<Output = ()>> CommandHandler<C> for Accept {
+        async fn handle(&self, _command: C, _context: &CommandContext) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn bus() -> CommandBus {
+        let handler = Arc::new(Accept);
+        CommandBus::new()
+            .register::<ChangeUserRole, _>(Arc::clone(&handler))
+            .register::<PublishVocabularyItem, _>(Arc::clone(&handler))
+            .register::<RecordAnswer, _>(handler)
+            .with_middleware(
+                AuthorizationMiddleware::new()
+                    .requires::<ChangeUserRole>(Permission::UserManageRoles)
+                    .requires::<PublishVocabularyItem>(Permission::VocabularyRead)
+                    .requires::<PublishVocabularyItem>(Permission::VocabularyPublish),
+            )
+    }
+
+    fn as_role(role: Role) -> CommandContext {
+        CommandContext::new().with_extension(Claims {
+            sub:  format!("{role}-1"),
+            exp:  0,
+            iat:  0,
+            role: role.as_str().to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_checks_required_permissions_per_command() {
+        let bus = bus();
+
+        bus.dispatch_with(ChangeUserRole, as_role(Role::Admin))
+            .await
+            .unwrap();
+        bus.dispatch_with(PublishVocabularyItem, as_role(Role::Moderator))
+            .await
+            .unwrap();
+        assert!(matches!(
+            bus.dispatch_with(ChangeUserRole, as_role(Role::Moderator))
+                .await,
+            Err(CqrsError::Forbidden {
+                command: "ChangeUserRole",
+                ..
+            })
+        ));
+        assert!(matches!(
+            bus.dispatch_with(PublishVocabularyItem, as_role(Role::User))
+                .await,
+            Err(CqrsError::Forbidden { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_role_only_claims_keep_the_access_of_the_role_based_rules() {
+        // ロールで登録していたころ（ChangeUserRole は admin、PublishVocabularyItem は
+        // admin か moderator）と同じ結果になることを、ロールだけのクレームで確かめる
+        let bus = bus();
+        let cases = [
+            (Role::Admin, true, true),
+            (Role::Moderator, false, true),
+            (Role::User, false, false),
+        ];
+
+        for (role, can_change_role, can_publish) in cases {
+            assert_eq!(
+                bus.dispatch_with(ChangeUserRole, as_role(role))
+                    .await
+                    .is_ok(),
+                can_change_role,
+                "{role} ChangeUserRole"
+            );
+            assert_eq!(
+                bus.dispatch_with(PublishVocabularyItem, as_role(role))
+                    .await
+                    .is_ok(),
+                can_publish,
+                "{role} PublishVocabularyItem"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_requires_claims_only_for_protected_commands() {
+        let bus = bus();
+
+        bus.dispatch(RecordAnswer).await.unwrap();
+        assert!(matches!(
+            bus.dispatch(ChangeUserRole).await,
+            Err(CqrsError::Unauthenticated("ChangeUserRole"))
+        ));
+    }
+}
@@ -0,0 +1,222 @@
+//! クエリ結果のキャッシュ
+//!
+//! [`CachingQueryHandler`] はハンドラーを包み、結果を JSON にして
+//! [`Cache`] に保存する。キャッシュキーは
+//! `query:{クエリ名}:{クエリごとのキー}`。
+//!
+//! キャッシュは補助的なもので、読み書きに失敗した場合は警告を出して
+//! ハンドラーの結果をそのまま返す。
+
+use std::{marker::PhantomData, sync::Arc};
+
+use async_trait::async_trait;
+use serde::{Serialize, de::DeserializeOwned};
+use shared_cache::Cache;
+use tracing::{debug, warn};
+
+use crate::{
+    error::Result,
+    query::{Query, QueryHandler},
+};
+
+/// キャッシュの既定の有効期限（秒）
+pub const DEFAULT_QUERY_TTL_SECONDS: u64 = 60;
+
+/// 結果をキャッシュできるクエリ
+pub trait CacheableQuery: Query {
+    /// クエリの条件を表すキー（同じ結果になるクエリは同じキーを返す）
+    fn cache_key(&self) -> String;
+}
+
+/// クエリ結果のキャッシュキー
+#[must_use]
+pub fn query_cache_key<Q: CacheableQuery>(query: &Q) -> String {
+    format!("query:{}:{}", Q::NAME, query.cache_key())
+}
+
+/// 結果をキャッシュするハンドラーのデコレーター
+#[allow(clippy::module_name_repetitions)]
+pub struct CachingQueryHandler<Q, H: ?Sized> {
+    inner:       Arc<H>,
+    cache:       Arc<dyn Cache>,
+    ttl_seconds: u64,
+    _query:      PhantomData<fn() -> Q>,
+}
+
+impl<Q, H> CachingQueryHandler<Q, H>
+where
+    Q: CacheableQuery,
+    H: QueryHandler<Q> + ?Sized,
+{
+    /// 既定の有効期限でハンドラーを包む
+    pub fn new(inner: Arc<H>, cache: Arc<dyn Cache>) -> Self {
+        Self {
+            inner,
+            cache,
+            ttl_seconds: DEFAULT_QUERY_TTL_SECONDS,
+            _query: PhantomData,
+        }
+    }
+
+    /// 有効期限を変更する
+    #[must_use]
+    pub const fn with_ttl_seconds(mut self, ttl_seconds: u64) -> Self {
+        self.ttl_seconds = ttl_seconds;
+        self
+    }
+
+    /// クエリの結果をキャッシュから削除する
+    pub async fn invalidate(&self, query: &Q) {
+        let key = query_cache_key(query);
+        if let Err(e) = self.cache.delete(&key).await {
+            warn!(key = %key, error = %e, "Failed to invalidate cached query result");
+        }
+    }
+
+    /// このクエリの結果をすべてキャッシュから削除する
+    pub async fn invalidate_all(&self) {
+        let pattern = format!("query:{}:*", Q::NAME);
+        if let Err(e) = self.cache.delete_pattern(&pattern).await {
+            warn!(pattern = %pattern, error = %e, "Failed to invalidate cached query results");
+        }
+    }
+
+    async fn read(&self, key: &str) -> Option<Q::Output>
+    where
+        Q::Output: DeserializeOwned,
+    {
+        let bytes = match self.cache.get(key).await {
+            Ok(bytes) => bytes?,
+            Err(e) => {
+                warn!(key = %key, error = %e, "Failed to read cached query result");
+                return None;
+            },
+        };
+        match serde_json::from_slice(&bytes) {
+            Ok(output) => Some(output),
+            Err(e) => {
+                warn!(key = %key, error = %e, "Discarding undecodable cached query result");
+                None
+            },
+        }
+    }
+
+    async fn write(&self, key: &str, output: &Q::Output)
+    where
+        Q::Output: Serialize,
+    {
+        let bytes = match serde_json::to_vec(output) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!(key = %key, error = %e, "Failed to encode query result for cache");
+                return;
+            },
+        };
+        if let Err(e) = self.cache.set(key, bytes, self.ttl_seconds).await {
+            warn!(key = %key, error = %e, "Failed to write cached query result");
+        }
+    }
+}
+
+#[async_trait]
+impl<Q, H> QueryHandler<Q> for CachingQueryHandler<Q, H>
+where
+    Q: CacheableQuery,
+    Q::Output: Serialize + DeserializeOwned,
+    H: QueryHandler<Q> + ?Sized,
+{
+    async fn handle(&self, query: Q) -> Result<Q::Output> {
+        let key = query_cache_key(&query);
+        if let Some(output) = self.read(&key).await {
+            debug!(key = %key, "Query result served from cache");
+            return Ok(output);
+        }
+
+        let output = self.inner.handle(query).await?;
+        self.write(&key, &output).await;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use shared_cache::MemoryCache;
+
+    use super::*;
+    use crate::{error::CqrsError, query::QueryBus};
+
+    struct WordsByLevel {
+        level: &'static str,
+    }
+
+    impl Query for WordsByLevel {
+        type Output = Vec<String>;
+
+        const NAME: &'static str = "WordsByLevel";
+    }
+
+    impl CacheableQuery for WordsByLevel {
+        fn cache_key(&self) -> String {
+            self.level.to_string()
+        }
+    }
+
+    #[derive(Default)]
+    struct CountingHandler {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl QueryHandler<WordsByLevel> for CountingHandler {
+        async fn handle(&self, query: WordsByLevel) -> Result<Vec<String>> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if query.level == "broken" {
+                return Err(CqrsError::Internal("read model unavailable".to_string()));
+            }
+            Ok(vec![format!("{}-{call}", query.level)])
+        }
+    }
+
+    fn words(level: &'static str) -> WordsByLevel {
+        WordsByLevel { level }
+    }
+
+    #[tokio::test]
+    async fn test_caches_results_per_query_key() {
+        let inner = Arc::new(CountingHandler::default());
+        let cache: Arc<dyn Cache> = Arc::new(MemoryCache::new());
+        let bus = QueryBus::new().register::<WordsByLevel, _>(Arc::new(CachingQueryHandler::new(
+            Arc::clone(&inner),
+            Arc::clone(&cache),
+        )));
+
+        assert_eq!(bus.dispatch(words("b1")).await.unwrap(), vec!["b1-0"]);
+        assert_eq!(bus.dispatch(words("b1")).await.unwrap(), vec!["b1-0"]);
+        assert_eq!(bus.dispatch(words("c1")).await.unwrap(), vec!["c1-1"]);
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        assert!(cache.get("query:WordsByLevel:b1").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_cached_and_invalidation_forces_reload() {
+        let inner = Arc::new(CountingHandler::default());
+        let handler = CachingQueryHandler::new(Arc::clone(&inner), Arc::new(MemoryCache::new()))
+            .with_ttl_seconds(300);
+
+        assert!(handler.handle(words("broken")).await.is_err());
+        assert!(handler.handle(words("broken")).await.is_err());
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+
+        handler.handle(words("a1")).await.unwrap();
+        handler.handle(words("a2")).await.unwrap();
+        handler.invalidate(&words("a1")).await;
+        assert_eq!(handler.handle(words("a1")).await.unwrap(), vec!["a1-4"]);
+        assert_eq!(handler.handle(words("a2")).await.unwrap(), vec!["a2-3"]);
+
+        handler.invalidate_all().await;
+        assert_eq!(handler.handle(words("a2")).await.unwrap(), vec!["a2-5"]);
+    }
+}
@@ -0,0 +1,431 @@
+//! コマンドとコマンドバス
+//!
+//! コマンドは型ごとに1つのハンドラーへ振り分ける。ハンドラーの前後に
+//! [`Middleware`] を連鎖させ、先に追加したミドルウェアほど外側で実行する。
+//!
+//! ```text
+//! dispatch → Middleware 1 → Middleware 2 → ... → CommandHandler
+//! ```
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    marker::PhantomData,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    context::CommandContext,
+    error::{CqrsError, Result},
+    validation::ValidationErrors,
+};
+
+/// コマンド
+pub trait Command: Send + 'static {
+    /// ログやエラーに出すコマンド名
+    const NAME: &'static str;
+
+    /// ハンドラーが返す値
+    type Output: Send + 'static;
+
+    /// コマンドの内容を検証する（バスがハンドラーの直前に呼ぶ）
+    ///
+    /// 既定では何も検証しない。
+    /// リポジトリなどに問い合わせる検証はハンドラーで行う
+    ///
+    /// # Errors
+    ///
+    /// 違反が1つ以上ある場合
+    fn validate(&self) -> std::result::Result<(), ValidationErrors> {
+        Ok(())
+    }
+}
+
+/// コマンドハンドラー
+#[async_trait]
+pub trait CommandHandler<C: Command>: Send + Sync {
+    /// コマンドを処理する
+    async fn handle(&self, command: C, context: &CommandContext) -> Result<C::Output>;
+}
+
+/// ミドルウェアに渡す、型を消したコマンド
+pub struct CommandEnvelope {
+    name:    &'static str,
+    command: Box<dyn Any + Send>,
+    context: CommandContext,
+}
+
+impl CommandEnvelope {
+    fn new<C: Command>(command: C, context: CommandContext) -> Self {
+        Self {
+            name: C::NAME,
+            command: Box::new(command),
+            context,
+        }
+    }
+
+    /// コマンド名
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// 型を指定してコマンドを取り出す（型が異なる場合は None）
+    #[must_use]
+    pub fn command<C: Command>(&self) -> Option<&C> {
+        self.command.downcast_ref()
+    }
+
+    /// 実行コンテキスト
+    #[must_use]
+    pub const fn context(&self) -> &CommandContext {
+        &self.context
+    }
+
+    /// 実行コンテキスト（後続に値を渡す場合）
+    pub const fn context_mut(&mut self) -> &mut CommandContext {
+        &mut self.context
+    }
+}
+
+/// コマンドの前後に挟む処理
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// `next.run(envelope)` で後続を実行する。呼ばずにエラーを返すと
+    /// ハンドラーは実行されない
+    async fn handle(
+        &self,
+        envelope: CommandEnvelope,
+        next: Next<'_>,
+    ) -> Result<Box<dyn Any + Send>>;
+}
+
+/// 後続のミドルウェアとハンドラー
+pub struct Next<'a> {
+    middlewares: &'a [Arc<dyn Middleware>],
+    handler:     &'a dyn ErasedHandler,
+}
+
+impl Next<'_> {
+    /// 後続を実行する
+    ///
+    /// # Errors
+    ///
+    /// 後続のミドルウェアが拒否した場合、またはハンドラーが失敗した場合
+    pub async fn run(self, envelope: CommandEnvelope) -> Result<Box<dyn Any + Send>> {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => {
+                middleware
+                    .handle(
+                        envelope,
+                        Next {
+                            middlewares: rest,
+                            handler:     self.handler,
+                        },
+                    )
+                    .await
+            },
+            None => self.handler.handle(envelope).await,
+        }
+    }
+}
+
+/// 型を消したハンドラー
+#[async_trait]
+trait ErasedHandler: Send + Sync {
+    async fn handle(&self, envelope: CommandEnvelope) -> Result<Box<dyn Any + Send>>;
+}
+
+struct Registered<C, H: ?Sized> {
+    handler:  Arc<H>,
+    _command: PhantomData<fn() -> C>,
+}
+
+#[async_trait]
+impl<C, H> ErasedHandler for Registered<C, H>
+where
+    C: Command,
+    H: CommandHandler<C> + ?Sized + 'static,
+{
+    async fn handle(&self, envelope: CommandEnvelope) -> Result<Box<dyn Any + Send>> {
+        let CommandEnvelope {
+            command, context, ..
+        } = envelope;
+        let command = command
+            .downcast::<C>()
+            .map_err(|_| CqrsError::Internal(format!("Command type mismatch for {}", C::NAME)))?;
+        command.validate()?;
+        let output = self.handler.handle(*command, &context).await?;
+        Ok(Box::new(output))
+    }
+}
+
+/// コマンドバス
+#[derive(Default)]
+pub struct CommandBus {
+    handlers:    HashMap<TypeId, Arc<dyn ErasedHandler>>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+impl CommandBus {
+    /// ハンドラーもミドルウェアもないバスを作る
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// コマンドのハンドラーを登録する（登録済みの場合は置き換える）
+    ///
+    /// 実行時はハンドラーの直前で [`Command::validate`] を呼ぶ
+    #[must_use]
+    pub fn register<C, H>(mut self, handler: Arc<H>) -> Self
+    where
+        C: Command,
+        H: CommandHandler<C> + ?Sized + 'static,
+    {
+        self.handlers.insert(
+            TypeId::of::<C>(),
+            Arc::new(Registered::<C, H> {
+                handler,
+                _command: PhantomData,
+            }),
+        );
+        self
+    }
+
+    /// ミドルウェアを追加する（先に追加したものほど外側で実行する）
+    #[must_use]
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// コマンドのハンドラーが登録されているか
+    #[must_use]
+    pub fn handles<C: Command>(&self) -> bool {
+        self.handlers.contains_key(&TypeId::of::<C>())
+    }
+
+    /// 新しいコンテキストでコマンドを実行する
+    ///
+    /// # Errors
+    ///
+    /// ハンドラーが登録されていない場合、ミドルウェアが拒否した場合、
+    /// 検証に失敗した場合、またはハンドラーが失敗した場合
+    pub async fn dispatch<C: Command>(&self, command: C) -> Result<C::Output> {
+        self.dispatch_with(command, CommandContext::new()).await
+    }
+
+    /// コンテキストを指定してコマンドを実行する
+    ///
+    /// # Errors
+    ///
+    /// ハンドラーが登録されていない場合、ミドルウェアが拒否した場合、
+    /// 検証に失敗した場合、またはハンドラーが失敗した場合
+    pub async fn dispatch_with<C: Command>(
+        &self,
+        command: C,
+        context: CommandContext,
+    ) -> Result<C::Output> {
+        let handler = self
+            .handlers
+            .get(&TypeId::of::<C>())
+            .ok_or(CqrsError::HandlerNotRegistered(C::NAME))?;
+
+        let output = Next {
+            middlewares: &self.middlewares,
+            handler:     handler.as_ref(),
+        }
+        .run(CommandEnvelope::new(command, context))
+        .await?;
+
+        output
+            .downcast::<C::Output>()
+            .map(|output| *output)
+            .map_err(|_| CqrsError::Internal(format!("Output type mismatch for {}", C::NAME)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    struct RegisterWord {
+        spelling: String,
+    }
+
+    impl Command for RegisterWord {
+        type Output = usize;
+
+        const NAME: &'static str = "RegisterWord";
+
+        fn validate(&self) -> std::result::Result<(), ValidationErrors> {
+            let mut errors = ValidationErrors::new();
+            if self.spelling.trim().is_empty() {
+                errors.add("spelling", "must not be empty");
+            }
+            if self.spelling.len() > 20 {
+                errors.add("spelling", "must be at most 20 characters");
+            }
+            errors.into_result()
+        }
+    }
+
+    struct DeleteWord;
+
+    impl Command for DeleteWord {
+        type Output = ();
+
+        const NAME: &'static str = "DeleteWord";
+    }
+
+    #[derive(Default)]
+    struct WordHandler {
+        registered: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl CommandHandler<RegisterWord> for WordHandler {
+        async fn handle(&self, command: RegisterWord, context: &CommandContext) -> Result<usize> {
+            let mut registered = self.registered.lock().unwrap();
+            let prefix = context
+                .extensions
+                .get::<&'static str>()
+                .copied()
+                .unwrap_or("");
+            registered.push(format!("{prefix}{}", command.spelling));
+            Ok(registered.len())
+        }
+    }
+
+    /// 実行順を記録し、`reject` が一致するコマンドを拒否する
+    struct Recording {
+        label:  &'static str,
+        log:    Arc<Mutex<Vec<String>>>,
+        reject: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl Middleware for Recording {
+        async fn handle(
+            &self,
+            envelope: CommandEnvelope,
+            next: Next<'_>,
+        ) -> Result<Box<dyn Any + Send>> {
+            if let Some(spelling) = self.reject
+                && envelope
+                    .command::<RegisterWord>()
+                    .is_some_and(|command| command.spelling == spelling)
+            {
+                self.log
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} rejected", self.label));
+                return Err(CqrsError::Internal("rejected".to_string()));
+            }
+
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} before", self.label));
+            let result = next.run(envelope).await;
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} after", self.label));
+            result
+        }
+    }
+
+    fn word(spelling: &str) -> RegisterWord {
+        RegisterWord {
+            spelling: spelling.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatches_to_registered_handler_with_context() {
+        let handler = Arc::new(WordHandler::default());
+        let bus = CommandBus::new().register::<RegisterWord, _>(Arc::clone(&handler));
+
+        assert_eq!(bus.dispatch(word("apple")).await.unwrap(), 1);
+        let context = CommandContext::new().with_extension("admin:");
+        assert_eq!(bus.dispatch_with(word("pear"), context).await.unwrap(), 2);
+
+        assert_eq!(
+            *handler.registered.lock().unwrap(),
+            vec!["apple".to_string(), "admin:pear".to_string()]
+        );
+        assert!(bus.handles::<RegisterWord>());
+        assert!(!bus.handles::<DeleteWord>());
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_command_is_an_error() {
+        let bus = CommandBus::new();
+
+        let result = bus.dispatch(DeleteWord).await;
+
+        assert!(matches!(
+            result,
+            Err(CqrsError::HandlerNotRegistered("DeleteWord"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_middlewares_run_in_order_and_can_short_circuit() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(WordHandler::default());
+        let bus = CommandBus::new()
+            .register::<RegisterWord, _>(Arc::clone(&handler))
+            .with_middleware(Recording {
+                label:  "outer",
+                log:    Arc::clone(&log),
+                reject: None,
+            })
+            .with_middleware(Recording {
+                label:  "inner",
+                log:    Arc::clone(&log),
+                reject: Some("forbidden"),
+            });
+
+        bus.dispatch(word("apple")).await.unwrap();
+        let result = bus.dispatch(word("forbidden")).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "outer before",
+                "inner before",
+                "inner after",
+                "outer after",
+                "outer before",
+                "inner rejected",
+                "outer after",
+            ]
+        );
+        assert_eq!(handler.registered.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_commands_are_validated_before_the_handler() {
+        let handler = Arc::new(WordHandler::default());
+        let bus = CommandBus::new().register::<RegisterWord, _>(Arc::clone(&handler));
+
+        let result = bus.dispatch(word(" ")).await;
+
+        assert!(matches!(
+            result,
+            Err(CqrsError::Validation(errors)) if errors.violations()[0].field == "spelling"
+        ));
+        assert!(handler.registered.lock().unwrap().is_empty());
+        assert_eq!(bus.dispatch(word("apple")).await.unwrap(), 1);
+    }
+}
@@ -0,0 +1,82 @@
+//! コマンドに付随する情報
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+};
+
+use uuid::Uuid;
+
+/// 型をキーにした任意の値（認証情報など、ミドルウェアとハンドラーで共有する値）
+#[derive(Default)]
+pub struct Extensions {
+    values: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// 値を追加する（同じ型の値があれば置き換える）
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) {
+        self.values.insert(TypeId::of::<T>(), Box::new(value));
+    }
+
+    /// 型を指定して値を取り出す
+    #[must_use]
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
+
+/// コマンドの実行コンテキスト
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct CommandContext {
+    /// コマンドごとに発行する ID
+    pub command_id:     Uuid,
+    /// 呼び出し元から引き継ぐ相関 ID
+    pub correlation_id: Option<String>,
+    /// ミドルウェアとハンドラーで共有する値
+    pub extensions:     Extensions,
+}
+
+impl Default for CommandContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandContext {
+    /// 新しい ID でコンテキストを作る
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            command_id:     Uuid::new_v4(),
+            correlation_id: None,
+            extensions:     Extensions::default(),
+        }
+    }
+
+    /// 相関 ID を設定する
+    #[must_use]
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// 共有する値を追加する
+    #[must_use]
+    pub fn with_extension<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
+        self
+    }
+}
@@ -0,0 +1,76 @@
+//! コマンド・クエリの処理のエラー
+
+use shared_event_store::EventStoreError;
+use thiserror::Error;
+
+use crate::validation::ValidationErrors;
+
+/// コマンド・クエリの処理のエラー
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Error)]
+pub enum CqrsError {
+    /// ハンドラーが登録されていない
+    #[error("No handler registered for {0}")]
+    HandlerNotRegistered(&'static str),
+
+    /// コマンドの検証に失敗した（ハンドラーは実行されていない）
+    #[error("Validation failed: {0}")]
+    Validation(#[from] ValidationErrors),
+
+    /// 認可が必要なコマンドに認証情報がない
+    #[error("Authentication required for {0}")]
+    Unauthenticated(&'static str),
+
+    /// コマンドに必要なロールを持っていない
+    #[error("{subject} is not allowed to execute {command}")]
+    Forbidden {
+        /// コマンド名
+        command: &'static str,
+        /// 実行しようとしたユーザー
+        subject: String,
+    },
+
+    /// イベントストアの読み書きに失敗した
+    #[error("Event store error: {0}")]
+    EventStore(#[from] EventStoreError),
+
+    /// ハンドラーが返したエラー（元のエラーは `source` から取り出せる）
+    #[error("Handler failed: {0}")]
+    Handler(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// 予約やチェックポイントなどの保存先の読み書きに失敗した
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    /// バスの内部の不整合
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl CqrsError {
+    /// サービス固有のエラーをハンドラーのエラーとして包む
+    pub fn handler(error: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self::Handler(Box::new(error))
+    }
+
+    /// 楽観的ロックの競合か（ハンドラーのエラーとして包まれている場合も含む）
+    #[must_use]
+    pub fn is_version_conflict(&self) -> bool {
+        let error = match self {
+            Self::EventStore(error) => Some(error),
+            Self::Handler(error) => error.downcast_ref::<EventStoreError>(),
+            _ => None,
+        };
+        matches!(error, Some(EventStoreError::VersionConflict { .. }))
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<sqlx::Error> for CqrsError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::Storage(error.to_string())
+    }
+}
+
+/// コマンド・クエリの処理の結果
+pub type Result<T> = std::result::Result<T, CqrsError>;
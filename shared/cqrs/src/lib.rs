@@ -0,0 +1,111 @@
+//! CQRS - コマンド・クエリの処理基盤
+//!
+//! 各サービスのコマンド側が共通で使う部品を提供する。
+//!
+//! - [`CommandBus`] は型付きのコマンドを登録したハンドラーに振り分け、
+//!   ログやトレースなどの横断的な処理を [`Middleware`] の連鎖で挟む
+//!   登録したコマンドはすべてハンドラーの前に [`Command::validate`] で検証する
+//! - [`AuthorizationMiddleware`] はコマンドごとに必要な権限を JWT
+//!   のクレームのロールと照合する
+//! - [`RetryOnConflict`] で包んだハンドラーは、同時書き込みによる
+//!   楽観的ロックの競合を集約の読み込みからやり直して吸収する
+//! - [`CommandScheduler`] はコマンドを予約し、[`ScheduledCommandDispatcher`]
+//!   が期限の来たものを実行する（`postgres` feature でデータベースに保存する）
+//! - [`ProcessTimeouts`] はプロセスマネージャーのタイムアウトと
+//!   リマインダーをコマンドの予約として永続化する
+//! - [`OutboxRelay`] はイベントと同じトランザクションで書き込んだ
+//!   アウトボックスの行をイベントバスへ発行する
+//! - [`QueryBus`] は型付きのクエリを登録したハンドラーに振り分け、
+//!   [`CachingQueryHandler`] で包んだハンドラーは結果を共有キャッシュに保存する
+//! - [`EventSourcedRepository`] はスナップショットとイベントから集約ルートを
+//!   復元し、新しいイベントを楽観的ロックで保存する（[`SnapshotPolicy`]
+//!   を指定するとスナップショットも自動で保存する）。読み込み時のイベントの
+//!   変換は `shared_event_store` の [`UpcasterRegistry`] で指定する
+//! - [`ProjectionRunner`] は [`Projection`] にイベントを順に適用し、
+//!   処理済みの位置を [`CheckpointStore`] に保存する（`postgres` feature で
+//!   データベースに保存する）
+//! - `testing` feature の [`AggregateTestFixture`] は集約ルートの振る舞いを
+//!   Given/When/Then で検証する
+
+pub mod authorization;
+pub mod caching;
+pub mod command;
+pub mod context;
+pub mod error;
+pub mod middleware;
+pub mod outbox;
+pub mod process_manager;
+pub mod projection;
+pub mod query;
+pub mod repository;
+pub mod retry;
+pub mod scheduling;
+pub mod snapshot;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod validation;
+
+pub use authorization::AuthorizationMiddleware;
+pub use caching::{
+    CacheableQuery,
+    CachingQueryHandler,
+    DEFAULT_QUERY_TTL_SECONDS,
+    query_cache_key,
+};
+pub use command::{Command, CommandBus, CommandEnvelope, CommandHandler, Middleware, Next};
+pub use context::{CommandContext, Extensions};
+pub use error::{CqrsError, Result};
+pub use middleware::{LoggingMiddleware, TracingMiddleware};
+#[cfg(feature = "postgres")]
+pub use outbox::PostgresOutboxStore;
+pub use outbox::{
+    DEFAULT_OUTBOX_BATCH_SIZE,
+    OutboxMessage,
+    OutboxPublisher,
+    OutboxRelay,
+    OutboxStore,
+    TopicResolver,
+    default_outbox_topic,
+};
+pub use process_manager::ProcessTimeouts;
+#[cfg(feature = "postgres")]
+pub use projection::PostgresCheckpointStore;
+pub use projection::{
+    Checkpoint,
+    CheckpointStore,
+    DEFAULT_PROJECTION_BATCH_SIZE,
+    ErrorPolicy,
+    EventFeed,
+    EventStoreFeed,
+    InMemoryCheckpointStore,
+    PositionedEvent,
+    Projection,
+    ProjectionRunner,
+};
+pub use query::{Query, QueryBus, QueryHandler};
+pub use repository::EventSourcedRepository;
+pub use retry::{
+    DEFAULT_BASE_DELAY,
+    DEFAULT_MAX_ATTEMPTS,
+    DEFAULT_MAX_DELAY,
+    RetryOnConflict,
+    RetryPolicy,
+};
+#[cfg(feature = "postgres")]
+pub use scheduling::PostgresScheduleStore;
+pub use scheduling::{
+    CommandScheduler,
+    DEFAULT_SCHEDULE_BATCH_SIZE,
+    DEFAULT_SCHEDULE_MAX_ATTEMPTS,
+    DEFAULT_SCHEDULE_RETRY_DELAY,
+    InMemoryScheduleStore,
+    SCHEDULE_LEASE,
+    ScheduleStore,
+    ScheduledCommand,
+    ScheduledCommandDispatcher,
+};
+pub use shared_event_store::UpcasterRegistry;
+pub use snapshot::{SnapshotMark, SnapshotPolicy};
+#[cfg(any(test, feature = "testing"))]
+pub use testing::{AggregateTestFixture, AggregateTestResult};
+pub use validation::{FieldViolation, ValidationErrors};
@@ -0,0 +1,105 @@
+//! 共通のミドルウェア
+
+use std::{any::Any, time::Instant};
+
+use async_trait::async_trait;
+use tracing::{Instrument, info, info_span, warn};
+
+use crate::{
+    command::{CommandEnvelope, Middleware, Next},
+    error::Result,
+};
+
+/// コマンドの結果と処理時間をログに出す
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoggingMiddleware;
+
+#[async_trait]
+impl Middleware for LoggingMiddleware {
+    async fn handle(
+        &self,
+        envelope: CommandEnvelope,
+        next: Next<'_>,
+    ) -> Result<Box<dyn Any + Send>> {
+        let command = envelope.name();
+        let command_id = envelope.context().command_id;
+        let started = Instant::now();
+
+        let result = next.run(envelope).await;
+
+        let elapsed_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        match &result {
+            Ok(_) => info!(command, %command_id, elapsed_ms, "Command handled"),
+            Err(e) => warn!(command, %command_id, elapsed_ms, error = %e, "Command failed"),
+        }
+        result
+    }
+}
+
+/// 後続の処理をコマンドごとのスパンで囲む
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingMiddleware;
+
+#[async_trait]
+impl Middleware for TracingMiddleware {
+    async fn handle(
+        &self,
+        envelope: CommandEnvelope,
+        next: Next<'_>,
+    ) -> Result<Box<dyn Any + Send>> {
+        let context = envelope.context();
+        let span = info_span!(
+            "command",
+            command = envelope.name(),
+            command_id = %context.command_id,
+            correlation_id = context.correlation_id.as_deref().unwrap_or_default(),
+        );
+        next.run(envelope).instrument(span).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        command::{Command, CommandBus, CommandHandler},
+        context::CommandContext,
+        error::CqrsError,
+    };
+
+    struct Divide(i32, i32);
+
+    impl Command for Divide {
+        type Output = i32;
+
+        const NAME: &'static str = "Divide";
+    }
+
+    struct Calculator;
+
+    #[async_trait]
+    impl CommandHandler<Divide> for Calculator {
+        async fn handle(&self, Divide(a, b): Divide, _context: &CommandContext) -> Result<i32> {
+            a.checked_div(b)
+                .ok_or_else(|| CqrsError::Internal("division by zero".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_logging_and_tracing_pass_results_through() {
+        let bus = CommandBus::new()
+            .register::<Divide, _>(Arc::new(Calculator))
+            .with_middleware(TracingMiddleware)
+            .with_middleware(LoggingMiddleware);
+
+        assert_eq!(bus.dispatch(Divide(10, 2)).await.unwrap(), 5);
+        assert!(matches!(
+            bus.dispatch(Divide(1, 0)).await,
+            Err(CqrsError::Internal(_))
+        ));
+    }
+}
@@ -0,0 +1,362 @@
+//! トランザクショナル・アウトボックスのリレー
+//!
+//! `PostgresEventStore::with_outbox` はイベントの追記と同じトランザクションで
+//! `event_outbox` に行を書き込む。[`OutboxRelay`] は未発行の行を
+//! 書き込み順に読み出してイベントバスへ発行し、発行済みとして記録する。
+//!
+//! ```text
+//! EventSourcedRepository::save → events + event_outbox（1トランザクション）
+//!   → OutboxRelay::relay_once → OutboxPublisher（AnyEventBus）→ 発行済みに更新
+//! ```
+//!
+//! 発行してから記録するまでの間に落ちた場合は再起動後にもう一度発行するため、
+//! 配信は at-least-once になる（購読側は `event_id` で重複を除く）。
+
+#![allow(clippy::module_name_repetitions)]
+
+#[cfg(feature = "postgres")]
+mod postgres;
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresOutboxStore;
+use shared_event_bus::{AnyEventBus, OutgoingMessage};
+use tracing::{debug, error, warn};
+use uuid::Uuid;
+
+use crate::error::{CqrsError, Result};
+
+/// 既定の1回に発行する件数
+pub const DEFAULT_OUTBOX_BATCH_SIZE: usize = 100;
+
+/// 発行待ちのイベント
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxMessage {
+    /// アウトボックス内の連番（書き込み順）
+    pub outbox_id:      i64,
+    /// イベント ID
+    pub event_id:       Uuid,
+    /// 集約 ID
+    pub aggregate_id:   Uuid,
+    /// 集約の種類
+    pub aggregate_type: String,
+    /// イベント種別
+    pub event_type:     String,
+    /// 集約内のバージョン
+    pub event_version:  u32,
+    /// イベントの JSON
+    pub payload:        serde_json::Value,
+    /// 発生日時
+    pub occurred_at:    DateTime<Utc>,
+    /// これまでに発行に失敗した回数
+    pub attempts:       u32,
+}
+
+/// アウトボックスの読み書き
+#[async_trait]
+pub trait OutboxStore: Send + Sync {
+    /// 未発行のイベントを書き込み順に最大 `limit` 件返す
+    async fn fetch_pending(&self, limit: usize) -> Result<Vec<OutboxMessage>>;
+
+    /// 発行済みとして記録する
+    async fn mark_published(&self, outbox_id: i64, published_at: DateTime<Utc>) -> Result<()>;
+
+    /// 発行の失敗を記録する
+    async fn record_failure(&self, outbox_id: i64, error: &str) -> Result<()>;
+}
+
+/// アウトボックスのイベントを発行する先
+#[async_trait]
+pub trait OutboxPublisher: Send + Sync {
+    /// イベントをトピックに発行する
+    async fn publish(&self, topic: &str, message: &OutboxMessage) -> Result<()>;
+}
+
+#[async_trait]
+impl OutboxPublisher for AnyEventBus {
+    async fn publish(&self, topic: &str, message: &OutboxMessage) -> Result<()> {
+        let data = serde_json::to_vec(&message.payload).map_err(|e| {
+            CqrsError::Internal(format!("Failed to encode event {}: {e}", message.event_id))
+        })?;
+        let attributes = HashMap::from([
+            ("event_id".to_string(), message.event_id.to_string()),
+            ("event_type".to_string(), message.event_type.clone()),
+            ("aggregate_id".to_string(), message.aggregate_id.to_string()),
+            ("aggregate_type".to_string(), message.aggregate_type.clone()),
+            (
+                "event_version".to_string(),
+                message.event_version.to_string(),
+            ),
+        ]);
+
+        self.publish_message(OutgoingMessage {
+            topic: topic.to_string(),
+            data,
+            attributes,
+            ordering_key: Some(message.aggregate_id.to_string()),
+        })
+        .await
+        .map(drop)
+        .map_err(CqrsError::handler)
+    }
+}
+
+/// 発行先のトピックを決める関数
+pub type TopicResolver = Arc<dyn Fn(&OutboxMessage) -> String + Send + Sync>;
+
+/// 既定のトピック（`{集約の種類}-events`）
+#[must_use]
+pub fn default_outbox_topic(message: &OutboxMessage) -> String {
+    format!("{}-events", message.aggregate_type)
+}
+
+/// アウトボックスのイベントをイベントバスへ発行する
+pub struct OutboxRelay {
+    store:      Arc<dyn OutboxStore>,
+    publisher:  Arc<dyn OutboxPublisher>,
+    topic:      TopicResolver,
+    batch_size: usize,
+}
+
+impl OutboxRelay {
+    /// 既定のトピックとバッチサイズで作る
+    #[must_use]
+    pub fn new(store: Arc<dyn OutboxStore>, publisher: Arc<dyn OutboxPublisher>) -> Self {
+        Self {
+            store,
+            publisher,
+            topic: Arc::new(default_outbox_topic),
+            batch_size: DEFAULT_OUTBOX_BATCH_SIZE,
+        }
+    }
+
+    /// 発行先のトピックの決め方を変更する
+    #[must_use]
+    pub fn with_topic(
+        mut self,
+        topic: impl Fn(&OutboxMessage) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.topic = Arc::new(topic);
+        self
+    }
+
+    /// 1回に発行する件数を変更する
+    #[must_use]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// 未発行のイベントを1バッチ分発行し、発行した件数を返す
+    ///
+    /// 発行に失敗した場合は、後続のイベントが先に届かないようそこで止める
+    ///
+    /// # Errors
+    ///
+    /// アウトボックスの読み書きに失敗した場合、
+    /// またはイベントの発行に失敗した場合
+    pub async fn relay_once(&self) -> Result<usize> {
+        let pending = self.store.fetch_pending(self.batch_size).await?;
+
+        let mut published = 0;
+        for message in &pending {
+            let topic = (self.topic)(message);
+            if let Err(e) = self.publisher.publish(&topic, message).await {
+                warn!(
+                    outbox_id = message.outbox_id,
+                    event_id = %message.event_id,
+                    topic = %topic,
+                    attempts = message.attempts + 1,
+                    error = %e,
+                    "Failed to publish outbox event"
+                );
+                self.store
+                    .record_failure(message.outbox_id, &e.to_string())
+                    .await?;
+                return Err(e);
+            }
+            self.store
+                .mark_published(message.outbox_id, Utc::now())
+                .await?;
+            published += 1;
+        }
+
+        if published > 0 {
+            debug!(published, "Relayed outbox events");
+        }
+        Ok(published)
+    }
+
+    /// 一定間隔で未発行のイベントを発行し続ける（終了しない）
+    pub async fn run(&self, poll_interval: Duration) {
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            interval.tick().await;
+            loop {
+                match self.relay_once().await {
+                    Ok(published) if published == self.batch_size => {},
+                    Ok(_) => break,
+                    Err(e) => {
+                        error!(error = %e, "Outbox relay failed");
+                        break;
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use serde_json::json;
+    use shared_event_bus::{DEFAULT_SHUTDOWN_DEADLINE, InMemoryEventBus};
+    use shared_kernel::EventBus;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct VecOutbox {
+        messages:  Mutex<Vec<OutboxMessage>>,
+        published: Mutex<Vec<i64>>,
+    }
+
+    #[async_trait]
+    impl OutboxStore for VecOutbox {
+        async fn fetch_pending(&self, limit: usize) -> Result<Vec<OutboxMessage>> {
+            let published = self.published.lock().unwrap().clone();
+            Ok(self
+                .messages
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|message| !published.contains(&message.outbox_id))
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+
+        async fn mark_published(&self, outbox_id: i64, _published_at: DateTime<Utc>) -> Result<()> {
+            self.published.lock().unwrap().push(outbox_id);
+            Ok(())
+        }
+
+        async fn record_failure(&self, outbox_id: i64, _error: &str) -> Result<()> {
+            let mut messages = self.messages.lock().unwrap();
+            if let Some(message) = messages
+                .iter_mut()
+                .find(|message| message.outbox_id == outbox_id)
+            {
+                message.attempts += 1;
+            }
+            drop(messages);
+            Ok(())
+        }
+    }
+
+    /// `unavailable` の間は失敗する発行先
+    #[derive(Default)]
+    struct FlakyPublisher {
+        unavailable: Mutex<bool>,
+        sent:        Mutex<Vec<(String, Uuid)>>,
+    }
+
+    #[async_trait]
+    impl OutboxPublisher for FlakyPublisher {
+        async fn publish(&self, topic: &str, message: &OutboxMessage) -> Result<()> {
+            if *self.unavailable.lock().unwrap() {
+                return Err(CqrsError::Internal("broker unavailable".to_string()));
+            }
+            self.sent
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), message.event_id));
+            Ok(())
+        }
+    }
+
+    fn message(outbox_id: i64, aggregate_type: &str) -> OutboxMessage {
+        OutboxMessage {
+            outbox_id,
+            event_id: Uuid::new_v4(),
+            aggregate_id: Uuid::new_v4(),
+            aggregate_type: aggregate_type.to_string(),
+            event_type: "ItemCreated".to_string(),
+            event_version: 1,
+            payload: json!({ "event_type": "ItemCreated", "spelling": "apple" }),
+            occurred_at: Utc::now(),
+            attempts: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_relays_in_order_and_resumes_after_failure() {
+        let store = Arc::new(VecOutbox::default());
+        store.messages.lock().unwrap().extend([
+            message(1, "vocabulary"),
+            message(2, "learning"),
+            message(3, "vocabulary"),
+        ]);
+        let publisher = Arc::new(FlakyPublisher::default());
+        let relay = OutboxRelay::new(
+            Arc::clone(&store) as Arc<dyn OutboxStore>,
+            Arc::clone(&publisher) as Arc<dyn OutboxPublisher>,
+        )
+        .with_batch_size(2);
+
+        *publisher.unavailable.lock().unwrap() = true;
+        assert!(relay.relay_once().await.is_err());
+        assert_eq!(store.messages.lock().unwrap()[0].attempts, 1);
+
+        *publisher.unavailable.lock().unwrap() = false;
+        assert_eq!(relay.relay_once().await.unwrap(), 2);
+        assert_eq!(relay.relay_once().await.unwrap(), 1);
+        assert_eq!(relay.relay_once().await.unwrap(), 0);
+
+        let topics: Vec<String> = publisher
+            .sent
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(topic, _)| topic.clone())
+            .collect();
+        assert_eq!(
+            topics,
+            vec!["vocabulary-events", "learning-events", "vocabulary-events"]
+        );
+        assert_eq!(*store.published.lock().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_publishes_payload_to_the_event_bus() {
+        let bus = InMemoryEventBus::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&received);
+        bus.subscribe("vocabulary.domain_events", move |data: &[u8]| {
+            sink.lock().unwrap().push(data.to_vec());
+            Ok(())
+        })
+        .await
+        .unwrap();
+        let store = Arc::new(VecOutbox::default());
+        store
+            .messages
+            .lock()
+            .unwrap()
+            .push(message(1, "vocabulary"));
+        let bus = Arc::new(AnyEventBus::Memory(bus));
+        let relay = OutboxRelay::new(store, Arc::<AnyEventBus>::clone(&bus))
+            .with_topic(|message| format!("{}.domain_events", message.aggregate_type));
+
+        assert_eq!(relay.relay_once().await.unwrap(), 1);
+        bus.shutdown(DEFAULT_SHUTDOWN_DEADLINE).await;
+
+        let data = received.lock().unwrap()[0].clone();
+        let payload: serde_json::Value = serde_json::from_slice(&data).unwrap();
+        assert_eq!(payload["spelling"], "apple");
+    }
+}
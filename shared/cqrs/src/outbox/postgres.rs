@@ -0,0 +1,85 @@
+//! `event_outbox` テーブルの読み書き
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+
+use super::{OutboxMessage, OutboxStore};
+use crate::error::Result;
+
+/// `event_outbox` テーブルのアウトボックス
+///
+/// テーブルは `shared_event_store` のマイグレーションで作る。
+/// リレーは1つのプロセスで動かす前提で、行のロックは取らない
+pub struct PostgresOutboxStore {
+    pool: PgPool,
+}
+
+impl PostgresOutboxStore {
+    /// 接続プールを指定して作る
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn outbox_message(row: &PgRow) -> Result<OutboxMessage> {
+    Ok(OutboxMessage {
+        outbox_id:      row.try_get("outbox_id")?,
+        event_id:       row.try_get("event_id")?,
+        aggregate_id:   row.try_get("aggregate_id")?,
+        aggregate_type: row.try_get("aggregate_type")?,
+        event_type:     row.try_get("event_type")?,
+        event_version:  row
+            .try_get::<i32, _>("event_version")?
+            .try_into()
+            .unwrap_or_default(),
+        payload:        row.try_get("payload")?,
+        occurred_at:    row.try_get("occurred_at")?,
+        attempts:       row
+            .try_get::<i32, _>("attempts")?
+            .try_into()
+            .unwrap_or_default(),
+    })
+}
+
+#[async_trait]
+impl OutboxStore for PostgresOutboxStore {
+    async fn fetch_pending(&self, limit: usize) -> Result<Vec<OutboxMessage>> {
+        let rows = sqlx::query(
+            r"
+            SELECT outbox_id, event_id, aggregate_id, aggregate_type, event_type,
+                   event_version, payload, occurred_at, attempts
+            FROM event_outbox
+            WHERE published_at IS NULL
+            ORDER BY outbox_id
+            LIMIT $1
+            ",
+        )
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(outbox_message).collect()
+    }
+
+    async fn mark_published(&self, outbox_id: i64, published_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query("UPDATE event_outbox SET published_at = $2 WHERE outbox_id = $1")
+            .bind(outbox_id)
+            .bind(published_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn record_failure(&self, outbox_id: i64, error: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE event_outbox SET attempts = attempts + 1, last_error = $2 WHERE outbox_id = $1",
+        )
+        .bind(outbox_id)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+//! プロセスマネージャー（サーガ）のタイムアウトとリマインダー
+//!
+//! 「5 分以内に `AiGenerationCompleted` が届かなければタスクを再試行する」
+//! のような期限は、[`ProcessTimeouts`] で補償コマンドを予約しておき、
+//! 待っていたイベントが届いたら取り消す。予約は [`CommandScheduler`] の
+//! 保存先に永続化されるため、サービスを再起動しても失われない。
+//!
+//! ```ignore
+//! let timeouts = ProcessTimeouts::new(scheduler, "ai_generation");
+//!
+//! // TaskCreated を受けたとき
+//! timeouts
+//!     .set_timeout(task_id, "completion", Duration::from_mins(5), RetryAiTask { task_id }, &context)
+//!     .await?;
+//!
+//! // AiGenerationCompleted を受けたとき
+//! timeouts.clear_timeout(task_id, "completion").await?;
+//! ```
+//!
+//! 期限のコマンドは [`ScheduledCommandDispatcher`](crate::ScheduledCommandDispatcher)
+//! で実行するので、ディスパッチャーに `route` で登録しておく。
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::time::Duration;
+
+use chrono::{TimeDelta, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    command::Command,
+    context::CommandContext,
+    error::{CqrsError, Result},
+    scheduling::CommandScheduler,
+};
+
+/// プロセスごとのタイムアウトとリマインダー
+#[derive(Clone)]
+pub struct ProcessTimeouts {
+    scheduler:    CommandScheduler,
+    process_type: &'static str,
+}
+
+impl ProcessTimeouts {
+    /// プロセスの種類（予約のキーの接頭辞）を指定して作る
+    #[must_use]
+    pub const fn new(scheduler: CommandScheduler, process_type: &'static str) -> Self {
+        Self {
+            scheduler,
+            process_type,
+        }
+    }
+
+    /// `after` 後に `command` を実行するタイムアウトを設定する
+    ///
+    /// 同じプロセスの同じ名前のタイムアウトは置き換える
+    ///
+    /// # Errors
+    ///
+    /// 予約の保存に失敗した場合
+    pub async fn set_timeout<C>(
+        &self,
+        process_id: Uuid,
+        name: &str,
+        after: Duration,
+        command: C,
+        context: &CommandContext,
+    ) -> Result<Uuid>
+    where
+        C: Command + Serialize,
+    {
+        let key = self.key("timeout", process_id, name);
+        self.schedule(key, after, command, context).await
+    }
+
+    /// タイムアウトを取り消す（取り消した場合は true）
+    ///
+    /// # Errors
+    ///
+    /// 保存先の更新に失敗した場合
+    pub async fn clear_timeout(&self, process_id: Uuid, name: &str) -> Result<bool> {
+        self.scheduler
+            .cancel(&self.key("timeout", process_id, name))
+            .await
+    }
+
+    /// `after` 後に `command` を実行するリマインダーを設定する
+    ///
+    /// タイムアウトと違い、待っているイベントが届いても取り消さない前提の通知
+    /// （例: 学習の中断から 1 日後の再開の案内）。繰り返す場合は、
+    /// リマインダーのコマンドのハンドラーから次のリマインダーを設定する
+    ///
+    /// # Errors
+    ///
+    /// 予約の保存に失敗した場合
+    pub async fn set_reminder<C>(
+        &self,
+        process_id: Uuid,
+        name: &str,
+        after: Duration,
+        command: C,
+        context: &CommandContext,
+    ) -> Result<Uuid>
+    where
+        C: Command + Serialize,
+    {
+        let key = self.key("reminder", process_id, name);
+        self.schedule(key, after, command, context).await
+    }
+
+    /// リマインダーを取り消す（取り消した場合は true）
+    ///
+    /// # Errors
+    ///
+    /// 保存先の更新に失敗した場合
+    pub async fn clear_reminder(&self, process_id: Uuid, name: &str) -> Result<bool> {
+        self.scheduler
+            .cancel(&self.key("reminder", process_id, name))
+            .await
+    }
+
+    async fn schedule<C>(
+        &self,
+        key: String,
+        after: Duration,
+        command: C,
+        context: &CommandContext,
+    ) -> Result<Uuid>
+    where
+        C: Command + Serialize,
+    {
+        let after = TimeDelta::from_std(after)
+            .map_err(|e| CqrsError::Internal(format!("Invalid delay for {key}: {e}")))?;
+        self.scheduler
+            .schedule(key, command, Utc::now() + after, context)
+            .await
+    }
+
+    /// 予約のキー（`{プロセスの種類}:{プロセス ID}:{timeout|reminder}:{名前}`）
+    fn key(&self, kind: &str, process_id: Uuid, name: &str) -> String {
+        format!("{}:{process_id}:{kind}:{name}", self.process_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::{
+        command::{CommandBus, CommandHandler},
+        scheduling::{InMemoryScheduleStore, ScheduleStore, ScheduledCommandDispatcher},
+    };
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct RetryAiTask {
+        task_id: Uuid,
+    }
+
+    impl Command for RetryAiTask {
+        type Output = ();
+
+        const NAME: &'static str = "RetryAiTask";
+    }
+
+    #[derive(Default)]
+    struct RetryHandler {
+        retried: Mutex<Vec<Uuid>>,
+    }
+
+    #[async_trait]
+    impl CommandHandler<RetryAiTask> for RetryHandler {
+        async fn handle(&self, command: RetryAiTask, _context: &CommandContext) -> Result<()> {
+            self.retried.lock().unwrap().push(command.task_id);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_fire_unless_cleared() {
+        let store = Arc::new(InMemoryScheduleStore::new());
+        let handler = Arc::new(RetryHandler::default());
+        let bus = Arc::new(CommandBus::new().register::<RetryAiTask, _>(Arc::clone(&handler)));
+        let dispatcher =
+            ScheduledCommandDispatcher::new(Arc::clone(&store) as Arc<dyn ScheduleStore>, bus)
+                .route::<RetryAiTask>();
+        let timeouts = ProcessTimeouts::new(
+            CommandScheduler::new(Arc::clone(&store) as Arc<dyn ScheduleStore>),
+            "ai_generation",
+        );
+        let context = CommandContext::new();
+        let (completed, stalled) = (Uuid::new_v4(), Uuid::new_v4());
+
+        for task_id in [completed, stalled] {
+            timeouts
+                .set_timeout(
+                    task_id,
+                    "completion",
+                    Duration::from_mins(5),
+                    RetryAiTask { task_id },
+                    &context,
+                )
+                .await
+                .unwrap();
+        }
+        timeouts
+            .set_reminder(
+                completed,
+                "completion",
+                Duration::from_hours(1),
+                RetryAiTask { task_id: completed },
+                &context,
+            )
+            .await
+            .unwrap();
+        // AiGenerationCompleted が届いた
+        assert!(
+            timeouts
+                .clear_timeout(completed, "completion")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !timeouts
+                .clear_timeout(completed, "completion")
+                .await
+                .unwrap()
+        );
+
+        assert_eq!(dispatcher.run_due(Utc::now()).await.unwrap(), 0);
+        assert_eq!(
+            dispatcher
+                .run_due(Utc::now() + TimeDelta::minutes(6))
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(*handler.retried.lock().unwrap(), vec![stalled]);
+
+        let pending = store.pending().await;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(
+            pending[0].key,
+            format!("ai_generation:{completed}:reminder:completion")
+        );
+    }
+}
@@ -0,0 +1,481 @@
+//! プロジェクションの実行基盤
+//!
+//! [`ProjectionRunner`] はイベントを位置順に読み込み、[`Projection`] に適用して
+//! 処理済みの位置を [`CheckpointStore`] に保存する。
+//!
+//! ```text
+//! チェックポイント読み込み → EventFeed から batch_size 件 → Projection::apply
+//!   → Projection::flush → チェックポイント保存
+//! ```
+//!
+//! 適用に失敗したイベントの扱いは [`ErrorPolicy`] で選ぶ。チェックポイントは
+//! 最後に成功したイベントの位置で保存するため、停止した場合は次の実行で
+//! 失敗したイベントから再開する。
+//!
+//! イベントストアから読む場合は [`EventStoreFeed`] を使う
+//! （`EventStore::load_all_events` で全体を位置順に読み進める）。
+//! チェックポイントは `postgres` feature の `PostgresCheckpointStore` で
+//! Read Model と同じデータベースに保存できる。
+
+#![allow(clippy::module_name_repetitions)]
+
+#[cfg(feature = "postgres")]
+mod postgres;
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresCheckpointStore;
+pub use shared_event_store::PositionedEvent;
+use shared_event_store::{EventFilter, EventStore};
+use tokio::sync::RwLock;
+use tracing::{debug, error, warn};
+use uuid::Uuid;
+
+use crate::error::Result;
+
+/// 既定のバッチサイズ
+pub const DEFAULT_PROJECTION_BATCH_SIZE: usize = 100;
+
+/// プロジェクションが処理済みの位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// プロジェクション名
+    pub projection_name: String,
+    /// 処理済みの最後の位置
+    pub position:        i64,
+    /// 処理済みの最後のイベント
+    pub last_event_id:   Option<Uuid>,
+    /// 更新日時
+    pub updated_at:      DateTime<Utc>,
+}
+
+/// 位置順にイベントを読み込む
+#[async_trait]
+pub trait EventFeed: Send + Sync {
+    /// `position` より後のイベントを最大 `limit` 件、位置の昇順で返す
+    async fn read_after(&self, position: i64, limit: usize) -> Result<Vec<PositionedEvent>>;
+}
+
+/// チェックポイントの保存先
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// チェックポイントを読み込む（未処理の場合は None）
+    async fn load(&self, projection_name: &str) -> Result<Option<Checkpoint>>;
+
+    /// チェックポイントを保存する
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<()>;
+}
+
+/// 読み取りモデルを更新するプロジェクション
+#[async_trait]
+pub trait Projection: Send + Sync {
+    /// チェックポイントの名前（プロジェクションごとに一意）
+    fn name(&self) -> &str;
+
+    /// 処理するイベント種別か（それ以外は読み飛ばしてチェックポイントだけ進める）
+    fn handles(&self, _event_type: &str) -> bool {
+        true
+    }
+
+    /// イベントを適用する
+    async fn apply(&self, event: &PositionedEvent) -> Result<()>;
+
+    /// バッチの終わり、チェックポイントを保存する前に呼ぶ
+    /// （書き込みをまとめるプロジェクションはここで反映する）
+    async fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 適用に失敗したイベントの扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// 失敗したイベントの手前で止め、エラーを返す
+    #[default]
+    Halt,
+    /// 警告を出して次のイベントへ進む
+    Skip,
+    /// 間隔を空けて再試行し、それでも失敗した場合は止める
+    Retry {
+        /// 最大試行回数（最初の1回を含む）
+        max_attempts: u32,
+        /// 再試行までの間隔
+        delay:        Duration,
+    },
+}
+
+/// プロジェクションを実行する
+pub struct ProjectionRunner<P: ?Sized> {
+    projection:   Arc<P>,
+    feed:         Arc<dyn EventFeed>,
+    checkpoints:  Arc<dyn CheckpointStore>,
+    batch_size:   usize,
+    error_policy: ErrorPolicy,
+}
+
+impl<P: Projection + ?Sized> ProjectionRunner<P> {
+    /// 既定のバッチサイズと [`ErrorPolicy::Halt`] で作る
+    pub fn new(
+        projection: Arc<P>,
+        feed: Arc<dyn EventFeed>,
+        checkpoints: Arc<dyn CheckpointStore>,
+    ) -> Self {
+        Self {
+            projection,
+            feed,
+            checkpoints,
+            batch_size: DEFAULT_PROJECTION_BATCH_SIZE,
+            error_policy: ErrorPolicy::default(),
+        }
+    }
+
+    /// バッチサイズを変更する（0 は 1 として扱う）
+    #[must_use]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// 失敗したイベントの扱いを変更する
+    #[must_use]
+    pub const fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// 1バッチ分のイベントを処理し、処理した件数を返す
+    ///
+    /// # Errors
+    ///
+    /// イベントやチェックポイントの読み書きに失敗した場合、
+    /// または [`ErrorPolicy`] に従って適用の失敗で止めた場合
+    pub async fn run_once(&self) -> Result<usize> {
+        let name = self.projection.name();
+        let position = self
+            .checkpoints
+            .load(name)
+            .await?
+            .map_or(0, |checkpoint| checkpoint.position);
+        let events = self.feed.read_after(position, self.batch_size).await?;
+
+        let mut last = None;
+        let mut failure = None;
+        for event in &events {
+            if self.projection.handles(&event.event.event_type)
+                && let Err(e) = self.apply(event).await
+            {
+                failure = Some(e);
+                break;
+            }
+            last = Some(event);
+        }
+
+        if let Some(event) = last {
+            self.projection.flush().await?;
+            self.checkpoints
+                .save(&Checkpoint {
+                    projection_name: name.to_string(),
+                    position:        event.position,
+                    last_event_id:   Some(event.event.event_id),
+                    updated_at:      Utc::now(),
+                })
+                .await?;
+        }
+
+        if let Some(e) = failure {
+            return Err(e);
+        }
+        let processed = events.len();
+        if processed > 0 {
+            debug!(projection = name, processed, "Projected events");
+        }
+        Ok(processed)
+    }
+
+    /// 新しいイベントがなくなるまでバッチを繰り返し、処理した件数を返す
+    ///
+    /// # Errors
+    ///
+    /// [`run_once`](Self::run_once) が失敗した場合
+    pub async fn catch_up(&self) -> Result<usize> {
+        let mut total = 0;
+        loop {
+            let processed = self.run_once().await?;
+            total += processed;
+            if processed < self.batch_size {
+                return Ok(total);
+            }
+        }
+    }
+
+    /// 一定間隔でイベントを処理し続ける（終了しない）
+    ///
+    /// 失敗した場合はエラーを記録し、次の間隔で同じ位置から再開する
+    pub async fn run(&self, poll_interval: Duration) {
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.catch_up().await {
+                error!(projection = self.projection.name(), error = %e, "Projection failed");
+            }
+        }
+    }
+
+    async fn apply(&self, event: &PositionedEvent) -> Result<()> {
+        let max_attempts = match self.error_policy {
+            ErrorPolicy::Retry { max_attempts, .. } => max_attempts.max(1),
+            ErrorPolicy::Halt | ErrorPolicy::Skip => 1,
+        };
+
+        let mut attempt = 1;
+        loop {
+            let Err(e) = self.projection.apply(event).await else {
+                return Ok(());
+            };
+            warn!(
+                projection = self.projection.name(),
+                position = event.position,
+                event_type = %event.event.event_type,
+                attempt,
+                error = %e,
+                "Failed to project event"
+            );
+
+            match self.error_policy {
+                ErrorPolicy::Skip => return Ok(()),
+                ErrorPolicy::Retry { delay, .. } if attempt < max_attempts => {
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                },
+                ErrorPolicy::Halt | ErrorPolicy::Retry { .. } => return Err(e),
+            }
+        }
+    }
+}
+
+/// メモリ上のチェックポイントストア（テストや単一プロセスでの実行向け）
+#[derive(Debug, Default)]
+pub struct InMemoryCheckpointStore {
+    checkpoints: RwLock<HashMap<String, Checkpoint>>,
+}
+
+impl InMemoryCheckpointStore {
+    /// 空のストアを作る
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn load(&self, projection_name: &str) -> Result<Option<Checkpoint>> {
+        Ok(self.checkpoints.read().await.get(projection_name).cloned())
+    }
+
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<()> {
+        self.checkpoints
+            .write()
+            .await
+            .insert(checkpoint.projection_name.clone(), checkpoint.clone());
+        Ok(())
+    }
+}
+
+/// イベントストア全体を位置順に読む [`EventFeed`]
+pub struct EventStoreFeed {
+    store:  Arc<dyn EventStore>,
+    filter: EventFilter,
+}
+
+impl EventStoreFeed {
+    /// 全イベントを読む
+    #[must_use]
+    pub fn new(store: Arc<dyn EventStore>) -> Self {
+        Self {
+            store,
+            filter: EventFilter::all(),
+        }
+    }
+
+    /// 読むイベントを絞り込む（プロジェクションが扱わないイベントを読まずに済む）
+    #[must_use]
+    pub fn with_filter(mut self, filter: EventFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+}
+
+#[async_trait]
+impl EventFeed for EventStoreFeed {
+    async fn read_after(&self, position: i64, limit: usize) -> Result<Vec<PositionedEvent>> {
+        Ok(self
+            .store
+            .load_all_events(position, limit, &self.filter)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use serde_json::json;
+    use shared_event_store::StoredEvent;
+
+    use super::*;
+    use crate::error::CqrsError;
+
+    struct VecFeed(Vec<PositionedEvent>);
+
+    #[async_trait]
+    impl EventFeed for VecFeed {
+        async fn read_after(&self, position: i64, limit: usize) -> Result<Vec<PositionedEvent>> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|event| event.position > position)
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+    }
+
+    /// 語彙の登録数を数え、`poison` の綴りで失敗する
+    #[derive(Default)]
+    struct WordCount {
+        applied: Mutex<Vec<String>>,
+        flushes: Mutex<usize>,
+        poison:  Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl Projection for WordCount {
+        fn name(&self) -> &'static str {
+            "word_count"
+        }
+
+        fn handles(&self, event_type: &str) -> bool {
+            event_type == "ItemCreated"
+        }
+
+        async fn apply(&self, event: &PositionedEvent) -> Result<()> {
+            let spelling = event.event.event_data["spelling"]
+                .as_str()
+                .unwrap()
+                .to_string();
+            if self.poison == Some(spelling.as_str()) {
+                return Err(CqrsError::Internal(format!("cannot project {spelling}")));
+            }
+            self.applied.lock().unwrap().push(spelling);
+            Ok(())
+        }
+
+        async fn flush(&self) -> Result<()> {
+            *self.flushes.lock().unwrap() += 1;
+            Ok(())
+        }
+    }
+
+    fn event(position: i64, event_type: &str, spelling: &str) -> PositionedEvent {
+        PositionedEvent {
+            position,
+            event: StoredEvent {
+                event_id:       Uuid::new_v4(),
+                tenant_id:      None,
+                aggregate_id:   Uuid::new_v4(),
+                aggregate_type: "vocabulary_item".to_string(),
+                event_type:     event_type.to_string(),
+                event_version:  1,
+                event_data:     json!({ "spelling": spelling }),
+                metadata:       None,
+                occurred_at:    Utc::now(),
+                created_at:     Utc::now(),
+            },
+        }
+    }
+
+    fn feed() -> Arc<VecFeed> {
+        Arc::new(VecFeed(vec![
+            event(1, "ItemCreated", "apple"),
+            event(2, "ItemUpdated", "apple"),
+            event(3, "ItemCreated", "banana"),
+            event(4, "ItemCreated", "cherry"),
+            event(5, "ItemCreated", "damson"),
+        ]))
+    }
+
+    fn runner(
+        projection: &Arc<WordCount>,
+        checkpoints: &Arc<InMemoryCheckpointStore>,
+    ) -> ProjectionRunner<WordCount> {
+        ProjectionRunner::new(
+            Arc::clone(projection),
+            feed(),
+            Arc::clone(checkpoints) as Arc<dyn CheckpointStore>,
+        )
+        .with_batch_size(2)
+    }
+
+    #[tokio::test]
+    async fn test_catches_up_in_batches_and_saves_checkpoints() {
+        let projection = Arc::new(WordCount::default());
+        let checkpoints = Arc::new(InMemoryCheckpointStore::new());
+        let runner = runner(&projection, &checkpoints);
+
+        assert_eq!(runner.run_once().await.unwrap(), 2);
+        assert_eq!(runner.catch_up().await.unwrap(), 3);
+        assert_eq!(runner.run_once().await.unwrap(), 0);
+
+        assert_eq!(
+            *projection.applied.lock().unwrap(),
+            vec!["apple", "banana", "cherry", "damson"]
+        );
+        assert_eq!(*projection.flushes.lock().unwrap(), 3);
+        let checkpoint = checkpoints.load("word_count").await.unwrap().unwrap();
+        assert_eq!(checkpoint.position, 5);
+    }
+
+    #[tokio::test]
+    async fn test_halt_keeps_the_checkpoint_before_the_failed_event() {
+        let projection = Arc::new(WordCount {
+            poison: Some("banana"),
+            ..WordCount::default()
+        });
+        let checkpoints = Arc::new(InMemoryCheckpointStore::new());
+        let runner = runner(&projection, &checkpoints).with_batch_size(10);
+
+        assert!(runner.run_once().await.is_err());
+        assert!(runner.run_once().await.is_err());
+
+        let checkpoint = checkpoints.load("word_count").await.unwrap().unwrap();
+        assert_eq!(checkpoint.position, 2);
+        assert_eq!(*projection.applied.lock().unwrap(), vec!["apple"]);
+    }
+
+    #[tokio::test]
+    async fn test_skip_and_retry_policies() {
+        let projection = Arc::new(WordCount {
+            poison: Some("banana"),
+            ..WordCount::default()
+        });
+        let checkpoints = Arc::new(InMemoryCheckpointStore::new());
+
+        let retrying = runner(&projection, &checkpoints).with_error_policy(ErrorPolicy::Retry {
+            max_attempts: 2,
+            delay:        Duration::ZERO,
+        });
+        assert!(retrying.catch_up().await.is_err());
+
+        let skipping = runner(&projection, &checkpoints).with_error_policy(ErrorPolicy::Skip);
+        assert_eq!(skipping.catch_up().await.unwrap(), 3);
+
+        assert_eq!(
+            *projection.applied.lock().unwrap(),
+            vec!["apple", "cherry", "damson"]
+        );
+    }
+}
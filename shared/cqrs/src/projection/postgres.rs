@@ -0,0 +1,69 @@
+//! `projection_states` テーブルを使うチェックポイントの保存先
+
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+
+use super::{Checkpoint, CheckpointStore};
+use crate::error::Result;
+
+/// `projection_states` テーブルにチェックポイントを保存する
+///
+/// テーブルは `migrations/` のマイグレーションで作る（Read Model と同じ
+/// データベースに置き、プロジェクション名ごとに1行を持つ）
+pub struct PostgresCheckpointStore {
+    pool: PgPool,
+}
+
+impl PostgresCheckpointStore {
+    /// 接続プールを指定して作る
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for PostgresCheckpointStore {
+    async fn load(&self, projection_name: &str) -> Result<Option<Checkpoint>> {
+        let row = sqlx::query(
+            r"
+            SELECT projection_name, last_position, last_event_id, updated_at
+            FROM projection_states
+            WHERE projection_name = $1
+            ",
+        )
+        .bind(projection_name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(Checkpoint {
+                projection_name: row.try_get("projection_name")?,
+                position:        row.try_get("last_position")?,
+                last_event_id:   row.try_get("last_event_id")?,
+                updated_at:      row.try_get("updated_at")?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<()> {
+        sqlx::query(
+            r"
+            INSERT INTO projection_states (projection_name, last_position, last_event_id, updated_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (projection_name) DO UPDATE SET
+                last_position = EXCLUDED.last_position,
+                last_event_id = EXCLUDED.last_event_id,
+                updated_at = EXCLUDED.updated_at
+            ",
+        )
+        .bind(&checkpoint.projection_name)
+        .bind(checkpoint.position)
+        .bind(checkpoint.last_event_id)
+        .bind(checkpoint.updated_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+//! クエリとクエリバス
+//!
+//! クエリは型ごとに1つのハンドラーへ振り分ける。結果のキャッシュなどは
+//! ハンドラーを包むデコレーター（[`CachingQueryHandler`](crate::CachingQueryHandler)
+//! など）として登録する。
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    marker::PhantomData,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+
+use crate::error::{CqrsError, Result};
+
+/// クエリ
+pub trait Query: Send + Sync + 'static {
+    /// ログやエラーに出すクエリ名（キャッシュキーの接頭辞にも使う）
+    const NAME: &'static str;
+
+    /// ハンドラーが返す値
+    type Output: Send + Sync + 'static;
+}
+
+/// クエリハンドラー
+#[async_trait]
+pub trait QueryHandler<Q: Query>: Send + Sync {
+    /// クエリを処理する（状態は変更しない）
+    async fn handle(&self, query: Q) -> Result<Q::Output>;
+}
+
+/// 型を消したハンドラー
+#[async_trait]
+trait ErasedQueryHandler: Send + Sync {
+    async fn handle(&self, query: Box<dyn Any + Send>) -> Result<Box<dyn Any + Send>>;
+}
+
+struct Registered<Q, H: ?Sized> {
+    handler: Arc<H>,
+    _query:  PhantomData<fn() -> Q>,
+}
+
+#[async_trait]
+impl<Q, H> ErasedQueryHandler for Registered<Q, H>
+where
+    Q: Query,
+    H: QueryHandler<Q> + ?Sized + 'static,
+{
+    async fn handle(&self, query: Box<dyn Any + Send>) -> Result<Box<dyn Any + Send>> {
+        let query = query
+            .downcast::<Q>()
+            .map_err(|_| CqrsError::Internal(format!("Query type mismatch for {}", Q::NAME)))?;
+        let output = self.handler.handle(*query).await?;
+        Ok(Box::new(output))
+    }
+}
+
+/// クエリバス
+#[derive(Default)]
+pub struct QueryBus {
+    handlers: HashMap<TypeId, Arc<dyn ErasedQueryHandler>>,
+}
+
+impl QueryBus {
+    /// ハンドラーのないバスを作る
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// クエリのハンドラーを登録する（登録済みの場合は置き換える）
+    #[must_use]
+    pub fn register<Q, H>(mut self, handler: Arc<H>) -> Self
+    where
+        Q: Query,
+        H: QueryHandler<Q> + ?Sized + 'static,
+    {
+        self.handlers.insert(
+            TypeId::of::<Q>(),
+            Arc::new(Registered::<Q, H> {
+                handler,
+                _query: PhantomData,
+            }),
+        );
+        self
+    }
+
+    /// クエリのハンドラーが登録されているか
+    #[must_use]
+    pub fn handles<Q: Query>(&self) -> bool {
+        self.handlers.contains_key(&TypeId::of::<Q>())
+    }
+
+    /// クエリを実行する
+    ///
+    /// # Errors
+    ///
+    /// ハンドラーが登録されていない場合、またはハンドラーが失敗した場合
+    pub async fn dispatch<Q: Query>(&self, query: Q) -> Result<Q::Output> {
+        let handler = self
+            .handlers
+            .get(&TypeId::of::<Q>())
+            .ok_or(CqrsError::HandlerNotRegistered(Q::NAME))?;
+
+        let output = handler.handle(Box::new(query)).await?;
+        output
+            .downcast::<Q::Output>()
+            .map(|output| *output)
+            .map_err(|_| CqrsError::Internal(format!("Output type mismatch for {}", Q::NAME)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct CountWords {
+        prefix: String,
+    }
+
+    impl Query for CountWords {
+        type Output = usize;
+
+        const NAME: &'static str = "CountWords";
+    }
+
+    struct ListTags;
+
+    impl Query for ListTags {
+        type Output = Vec<String>;
+
+        const NAME: &'static str = "ListTags";
+    }
+
+    struct Dictionary(Vec<&'static str>);
+
+    #[async_trait]
+    impl QueryHandler<CountWords> for Dictionary {
+        async fn handle(&self, query: CountWords) -> Result<usize> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|word| word.starts_with(&query.prefix))
+                .count())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatches_to_registered_handler() {
+        let bus = QueryBus::new()
+            .register::<CountWords, _>(Arc::new(Dictionary(vec!["apple", "apricot", "pear"])));
+
+        let count = bus
+            .dispatch(CountWords {
+                prefix: "ap".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(count, 2);
+        assert!(bus.handles::<CountWords>());
+        assert!(matches!(
+            bus.dispatch(ListTags).await,
+            Err(CqrsError::HandlerNotRegistered("ListTags"))
+        ));
+    }
+}
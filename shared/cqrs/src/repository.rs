@@ -0,0 +1,432 @@
+//! イベントソーシングの集約リポジトリ
+//!
+//! [`EventSourcedRepository`] は `shared_event_store` の
+//! [`AggregateRepository`] にスナップショットを加えたもので、
+//! [`AggregateRoot`] を次の順で読み込む。
+//!
+//! 1. 最新のスナップショットがあれば、その状態とバージョンから始める
+//! 2. スナップショットより後のイベントを読み込み、[`UpcasterRegistry`]
+//!    で現在の形式に変換する
+//! 3. 変換したイベントをデシリアライズして順に適用する
+//!
+//! 保存は読み込んだ時点のバージョンを期待バージョンとする楽観的ロックで行う。
+//! イベントは `event_type` フィールドを含む JSON にシリアライズされる必要がある
+//! （`#[serde(tag = "event_type")]` など）。
+//!
+//! イベントストアを `PostgresEventStore::with_outbox` で作ると、保存した
+//! イベントは同じトランザクションでアウトボックスにも書き込まれ、
+//! [`OutboxRelay`](crate::OutboxRelay) がイベントバスへ発行する。
+//!
+//! [`SnapshotPolicy`] を指定すると、保存の後に条件を満たせば
+//! スナップショットも保存する。
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use shared_event_store::{
+    AggregateRepository,
+    AggregateRoot,
+    EventSourced,
+    EventStore,
+    EventStoreError,
+    UpcasterRegistry,
+    VersionedAggregate,
+};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::snapshot::{SnapshotMark, SnapshotPolicy};
+
+/// 集約ルートのリポジトリ
+pub struct EventSourcedRepository<A> {
+    store:      Arc<dyn EventStore>,
+    aggregates: AggregateRepository<A>,
+    snapshots:  SnapshotPolicy,
+}
+
+impl<A: AggregateRoot> EventSourcedRepository<A> {
+    /// 変換なしのリポジトリを作る
+    pub fn new(store: Arc<dyn EventStore>) -> Self {
+        Self {
+            aggregates: AggregateRepository::new(Arc::clone(&store)),
+            store,
+            snapshots: SnapshotPolicy::Never,
+        }
+    }
+
+    /// スナップショットを自動で取る条件を指定する
+    #[must_use]
+    pub const fn with_snapshot_policy(mut self, policy: SnapshotPolicy) -> Self {
+        self.snapshots = policy;
+        self
+    }
+
+    /// 読み込み時にイベントを現在の形式に変換する
+    #[must_use]
+    pub fn with_upcasters(mut self, upcasters: UpcasterRegistry) -> Self {
+        self.aggregates = self.aggregates.with_upcasters(upcasters);
+        self
+    }
+
+    /// 集約を読み込む（イベントがない場合は初期状態とバージョン 0）
+    ///
+    /// # Errors
+    ///
+    /// イベントストアの読み込み、
+    /// イベントの変換またはデシリアライズに失敗した場合
+    pub async fn load(&self, aggregate_id: Uuid) -> Result<EventSourced<A>, EventStoreError> {
+        let VersionedAggregate { state, version } = match self.load_snapshot(aggregate_id).await? {
+            Some(snapshot) => self.aggregates.load_after(aggregate_id, snapshot).await?,
+            None => self.aggregates.load(aggregate_id).await?,
+        };
+        Ok(EventSourced::restore(state, version))
+    }
+
+    /// 未コミットのイベントを保存する
+    ///
+    /// 保存に失敗した場合、未コミットのイベントはそのまま残る。
+    /// イベントの保存後にスナップショットの保存に失敗しても、警告を出すだけで
+    /// 成功として扱う
+    ///
+    /// # Errors
+    ///
+    /// 読み込んだ後に別の書き込みがあった場合は
+    /// [`EventStoreError::VersionConflict`]
+    pub async fn save(
+        &self,
+        aggregate_id: Uuid,
+        aggregate: &mut EventSourced<A>,
+    ) -> Result<(), EventStoreError> {
+        let previous_version = aggregate.committed_version();
+        if !aggregate.has_uncommitted_events() {
+            return Ok(());
+        }
+        self.aggregates.commit(aggregate_id, aggregate).await?;
+
+        if let Err(e) = self
+            .snapshot_if_due(aggregate_id, aggregate, previous_version)
+            .await
+        {
+            warn!(
+                aggregate_id = %aggregate_id,
+                aggregate_type = A::AGGREGATE_TYPE,
+                version = aggregate.committed_version(),
+                error = %e,
+                "Failed to save snapshot"
+            );
+        }
+        Ok(())
+    }
+
+    /// 保存済みの状態をスナップショットとして保存する
+    ///
+    /// # Errors
+    ///
+    /// 未コミットのイベントがある場合、
+    /// またはスナップショットの保存に失敗した場合
+    pub async fn save_snapshot(
+        &self,
+        aggregate_id: Uuid,
+        aggregate: &EventSourced<A>,
+    ) -> Result<(), EventStoreError> {
+        if aggregate.has_uncommitted_events() {
+            return Err(EventStoreError::Internal(
+                "Cannot snapshot an aggregate with uncommitted events".to_string(),
+            ));
+        }
+
+        let data = serde_json::to_value(aggregate.state())?;
+        self.store
+            .save_snapshot(
+                aggregate_id,
+                A::AGGREGATE_TYPE,
+                aggregate.committed_version(),
+                data,
+            )
+            .await
+    }
+
+    /// ポリシーの条件を満たしていればスナップショットを保存する
+    async fn snapshot_if_due(
+        &self,
+        aggregate_id: Uuid,
+        aggregate: &EventSourced<A>,
+        previous_version: u32,
+    ) -> Result<(), EventStoreError> {
+        if self.snapshots == SnapshotPolicy::Never {
+            return Ok(());
+        }
+
+        let data = serde_json::to_value(aggregate.state())?;
+        let last_snapshot = if self.snapshots.needs_last_snapshot() {
+            self.store
+                .load_snapshot(aggregate_id, A::AGGREGATE_TYPE)
+                .await?
+                .map(|snapshot| SnapshotMark {
+                    version:  snapshot.aggregate_version,
+                    taken_at: snapshot.created_at,
+                })
+        } else {
+            None
+        };
+        let state_size = data.to_string().len();
+        if !self.snapshots.should_snapshot(
+            previous_version,
+            aggregate.committed_version(),
+            state_size,
+            last_snapshot.as_ref(),
+            Utc::now(),
+        ) {
+            return Ok(());
+        }
+
+        self.store
+            .save_snapshot(
+                aggregate_id,
+                A::AGGREGATE_TYPE,
+                aggregate.committed_version(),
+                data,
+            )
+            .await
+    }
+
+    /// スナップショットの状態とバージョン（ない、または読めない場合は None）
+    async fn load_snapshot(
+        &self,
+        aggregate_id: Uuid,
+    ) -> Result<Option<VersionedAggregate<A>>, EventStoreError> {
+        let Some(snapshot) = self
+            .store
+            .load_snapshot(aggregate_id, A::AGGREGATE_TYPE)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        match A::deserialize(&snapshot.aggregate_data) {
+            Ok(state) => Ok(Some(VersionedAggregate {
+                state,
+                version: snapshot.aggregate_version,
+            })),
+            Err(e) => {
+                // 集約の形式が変わった後の古いスナップショットは使わず、全イベントから復元する
+                warn!(
+                    aggregate_id = %aggregate_id,
+                    aggregate_type = A::AGGREGATE_TYPE,
+                    version = snapshot.aggregate_version,
+                    error = %e,
+                    "Ignoring snapshot that no longer deserializes"
+                );
+                Ok(None)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use shared_event_store::{RenameField, testing::InMemoryEventStore};
+
+    use super::*;
+
+    #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+    struct Entry {
+        spelling:    String,
+        definitions: Vec<String>,
+    }
+
+    enum EntryCommand {
+        Create(String),
+        AddDefinition(String),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "event_type")]
+    enum EntryEvent {
+        Created { spelling: String },
+        DefinitionAdded { text: String },
+    }
+
+    impl AggregateRoot for Entry {
+        type Command = EntryCommand;
+        type Error = String;
+        type Event = EntryEvent;
+
+        const AGGREGATE_TYPE: &'static str = "vocabulary_entry";
+
+        fn handle(&self, command: EntryCommand) -> Result<Vec<EntryEvent>, String> {
+            match command {
+                EntryCommand::Create(spelling) => Ok(vec![EntryEvent::Created { spelling }]),
+                EntryCommand::AddDefinition(text) if self.definitions.contains(&text) => {
+                    Err(format!("duplicate definition: {text}"))
+                },
+                EntryCommand::AddDefinition(text) => Ok(vec![EntryEvent::DefinitionAdded { text }]),
+            }
+        }
+
+        fn apply(&mut self, event: &EntryEvent) {
+            match event {
+                EntryEvent::Created { spelling } => self.spelling.clone_from(spelling),
+                EntryEvent::DefinitionAdded { text } => self.definitions.push(text.clone()),
+            }
+        }
+    }
+
+    /// v1 の `DefinitionAdded` は本文を `definition` に持っていた
+    fn upcasters() -> UpcasterRegistry {
+        UpcasterRegistry::new().register(Arc::new(RenameField::new(
+            "DefinitionAdded",
+            1,
+            "definition",
+            "text",
+        )))
+    }
+
+    async fn snapshot_version(store: &InMemoryEventStore, id: Uuid) -> Option<u32> {
+        store
+            .load_snapshot(id, Entry::AGGREGATE_TYPE)
+            .await
+            .unwrap()
+            .map(|snapshot| snapshot.aggregate_version)
+    }
+
+    fn repository(store: &Arc<InMemoryEventStore>) -> EventSourcedRepository<Entry> {
+        EventSourcedRepository::new(Arc::clone(store) as Arc<dyn EventStore>)
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load_with_optimistic_concurrency() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let repository = repository(&store);
+        let id = Uuid::new_v4();
+
+        let mut entry = repository.load(id).await.unwrap();
+        entry
+            .execute(EntryCommand::Create("serendipity".to_string()))
+            .unwrap();
+        entry
+            .execute(EntryCommand::AddDefinition("a happy accident".to_string()))
+            .unwrap();
+        let mut stale = repository.load(id).await.unwrap();
+        repository.save(id, &mut entry).await.unwrap();
+
+        let loaded = repository.load(id).await.unwrap();
+        assert_eq!(loaded.committed_version(), 2);
+        assert_eq!(loaded.state(), entry.state());
+
+        stale
+            .execute(EntryCommand::Create("other".to_string()))
+            .unwrap();
+        assert!(matches!(
+            repository.save(id, &mut stale).await,
+            Err(EventStoreError::VersionConflict {
+                expected: 0,
+                actual:   2,
+            })
+        ));
+        assert!(stale.has_uncommitted_events());
+    }
+
+    #[tokio::test]
+    async fn test_loads_from_snapshot_and_only_reads_later_events() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let repository = repository(&store);
+        let id = Uuid::new_v4();
+
+        let mut entry = repository.load(id).await.unwrap();
+        entry
+            .execute(EntryCommand::Create("ephemeral".to_string()))
+            .unwrap();
+        entry
+            .execute(EntryCommand::AddDefinition("short-lived".to_string()))
+            .unwrap();
+        repository.save(id, &mut entry).await.unwrap();
+        repository.save_snapshot(id, &entry).await.unwrap();
+        entry
+            .execute(EntryCommand::AddDefinition("lasting a day".to_string()))
+            .unwrap();
+        assert!(repository.save_snapshot(id, &entry).await.is_err());
+        repository.save(id, &mut entry).await.unwrap();
+
+        let loaded = repository.load(id).await.unwrap();
+
+        assert_eq!(store.reads().last(), Some(&Some(2)));
+        assert_eq!(loaded.committed_version(), 3);
+        assert_eq!(
+            loaded.state().definitions,
+            vec!["short-lived", "lasting a day"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_snapshots_automatically_according_to_the_policy() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let repository = repository(&store).with_snapshot_policy(SnapshotPolicy::EveryEvents(3));
+        let id = Uuid::new_v4();
+
+        let mut entry = repository.load(id).await.unwrap();
+        entry
+            .execute(EntryCommand::Create("petrichor".to_string()))
+            .unwrap();
+        entry
+            .execute(EntryCommand::AddDefinition("the smell of rain".to_string()))
+            .unwrap();
+        repository.save(id, &mut entry).await.unwrap();
+        assert_eq!(snapshot_version(&store, id).await, None);
+
+        for text in ["earthy scent", "after a dry spell"] {
+            entry
+                .execute(EntryCommand::AddDefinition(text.to_string()))
+                .unwrap();
+            repository.save(id, &mut entry).await.unwrap();
+        }
+        assert_eq!(snapshot_version(&store, id).await, Some(3));
+
+        let repository = repository
+            .with_snapshot_policy(SnapshotPolicy::Interval(std::time::Duration::from_hours(1)));
+        entry
+            .execute(EntryCommand::AddDefinition("petrichor oil".to_string()))
+            .unwrap();
+        repository.save(id, &mut entry).await.unwrap();
+        assert_eq!(snapshot_version(&store, id).await, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_upcasts_old_events_and_ignores_undecodable_snapshots() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let id = Uuid::new_v4();
+        store
+            .save_events(
+                id,
+                Entry::AGGREGATE_TYPE,
+                vec![
+                    json!({ "event_type": "Created", "spelling": "lexicon" }),
+                    json!({ "event_type": "DefinitionAdded", "definition": "a vocabulary" }),
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+        store
+            .save_snapshot(id, Entry::AGGREGATE_TYPE, 1, json!({ "word": "lexicon" }))
+            .await
+            .unwrap();
+
+        assert!(repository(&store).load(id).await.is_err());
+
+        let loaded = repository(&store)
+            .with_upcasters(upcasters())
+            .load(id)
+            .await
+            .unwrap();
+
+        assert_eq!(store.reads().last(), Some(&None));
+        assert_eq!(loaded.committed_version(), 2);
+        assert_eq!(loaded.state().spelling, "lexicon");
+        assert_eq!(loaded.state().definitions, vec!["a vocabulary"]);
+    }
+}
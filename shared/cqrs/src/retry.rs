@@ -0,0 +1,276 @@
+//! 楽観的ロックの競合時の再試行
+//!
+//! [`RetryOnConflict`] はハンドラーを包み、ハンドラーが
+//! [`EventStoreError::VersionConflict`](shared_event_store::EventStoreError::VersionConflict)
+//! で失敗した場合にコマンドを最初からやり直す。ハンドラーは集約の読み込みから
+//! 保存までを1回の `handle` で行うため、やり直すと最新の状態でコマンドを
+//! 処理し直すことになる。
+//!
+//! 同じ項目への同時書き込みで再試行がそろって再び競合しないよう、待ち時間は
+//! 指数バックオフの上限までの範囲でランダムに選ぶ（full jitter）。
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::{
+    hash::{BuildHasher, RandomState},
+    marker::PhantomData,
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use crate::{
+    command::{Command, CommandHandler},
+    context::CommandContext,
+    error::Result,
+};
+
+/// 既定の最大試行回数（最初の1回を含む）
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// 既定の待ち時間の基準値
+pub const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(20);
+
+/// 既定の待ち時間の上限
+pub const DEFAULT_MAX_DELAY: Duration = Duration::from_millis(500);
+
+/// 再試行の回数と待ち時間
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay:   Duration,
+    max_delay:    Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            base_delay:   DEFAULT_BASE_DELAY,
+            max_delay:    DEFAULT_MAX_DELAY,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 最大試行回数を指定する（0 は 1 として扱う）
+    #[must_use]
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// 再試行しない
+    #[must_use]
+    pub fn never() -> Self {
+        Self::new(1)
+    }
+
+    /// 待ち時間の基準値を変更する
+    #[must_use]
+    pub const fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// 待ち時間の上限を変更する
+    #[must_use]
+    pub const fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// 最大試行回数（最初の1回を含む）
+    #[must_use]
+    pub const fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// `attempt` 回目の失敗の後に待つ時間
+    ///
+    /// `基準値 × 2^(attempt - 1)`（上限で切り詰める）以下のランダムな時間
+    #[must_use]
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let ceiling = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+        let ceiling_nanos = u64::try_from(ceiling.as_nanos()).unwrap_or(u64::MAX);
+        if ceiling_nanos == 0 {
+            return Duration::ZERO;
+        }
+        // 乱数のためだけに依存を増やさないよう、ハッシュのランダムなシードを使う
+        let random = RandomState::new().hash_one(attempt);
+        Duration::from_nanos(random % (ceiling_nanos + 1))
+    }
+}
+
+/// 競合したコマンドを再試行するハンドラーのデコレーター
+pub struct RetryOnConflict<C, H: ?Sized> {
+    inner:    Arc<H>,
+    policy:   RetryPolicy,
+    _command: PhantomData<fn() -> C>,
+}
+
+impl<C, H> RetryOnConflict<C, H>
+where
+    C: Command + Clone,
+    H: CommandHandler<C> + ?Sized,
+{
+    /// 既定のポリシーでハンドラーを包む
+    pub fn new(inner: Arc<H>) -> Self {
+        Self {
+            inner,
+            policy: RetryPolicy::default(),
+            _command: PhantomData,
+        }
+    }
+
+    /// ポリシーを変更する
+    #[must_use]
+    pub const fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+#[async_trait]
+impl<C, H> CommandHandler<C> for RetryOnConflict<C, H>
+where
+    C: Command + Clone,
+    H: CommandHandler<C> + ?Sized,
+{
+    async fn handle(&self, command: C, context: &CommandContext) -> Result<C::Output> {
+        let mut attempt = 1;
+        loop {
+            match self.inner.handle(command.clone(), context).await {
+                Err(e) if e.is_version_conflict() && attempt < self.policy.max_attempts => {
+                    let delay = self.policy.backoff(attempt);
+                    warn!(
+                        command = C::NAME,
+                        command_id = %context.command_id,
+                        attempt,
+                        delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                        error = %e,
+                        "Retrying command after version conflict"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                },
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use shared_event_store::EventStoreError;
+
+    use super::*;
+    use crate::{command::CommandBus, error::CqrsError};
+
+    #[derive(Clone)]
+    struct AddDefinition;
+
+    impl Command for AddDefinition {
+        type Output = u32;
+
+        const NAME: &'static str = "AddDefinition";
+    }
+
+    /// 最初の `conflicts` 回は競合し、その後は成功する
+    struct ConflictingHandler {
+        conflicts: u32,
+        calls:     AtomicU32,
+        error:     fn(u32) -> CqrsError,
+    }
+
+    impl ConflictingHandler {
+        fn new(conflicts: u32) -> Self {
+            Self {
+                conflicts,
+                calls: AtomicU32::new(0),
+                error: |actual| {
+                    EventStoreError::VersionConflict {
+                        expected: 0,
+                        actual,
+                    }
+                    .into()
+                },
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CommandHandler<AddDefinition> for ConflictingHandler {
+        async fn handle(&self, _command: AddDefinition, _context: &CommandContext) -> Result<u32> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call <= self.conflicts {
+                return Err((self.error)(call));
+            }
+            Ok(call)
+        }
+    }
+
+    fn policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::new(max_attempts).with_base_delay(Duration::from_millis(1))
+    }
+
+    #[tokio::test]
+    async fn test_retries_version_conflicts_until_success() {
+        let inner = Arc::new(ConflictingHandler::new(2));
+        let bus = CommandBus::new().register::<AddDefinition, _>(Arc::new(
+            RetryOnConflict::new(Arc::clone(&inner)).with_policy(policy(3)),
+        ));
+
+        assert_eq!(bus.dispatch(AddDefinition).await.unwrap(), 3);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let inner = Arc::new(ConflictingHandler::new(5));
+        let handler = RetryOnConflict::new(Arc::clone(&inner)).with_policy(policy(2));
+
+        let result = handler.handle(AddDefinition, &CommandContext::new()).await;
+
+        assert!(result.is_err_and(|e| e.is_version_conflict()));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_other_errors_are_not_retried() {
+        let inner = Arc::new(ConflictingHandler {
+            error: |_| CqrsError::handler(EventStoreError::Internal("down".to_string())),
+            ..ConflictingHandler::new(1)
+        });
+        let handler = RetryOnConflict::new(Arc::clone(&inner)).with_policy(policy(3));
+
+        let result = handler.handle(AddDefinition, &CommandContext::new()).await;
+
+        assert!(result.is_err_and(|e| !e.is_version_conflict()));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_backoff_stays_within_the_exponential_ceiling() {
+        let policy = RetryPolicy::default()
+            .with_base_delay(Duration::from_millis(10))
+            .with_max_delay(Duration::from_millis(25));
+
+        for _ in 0..100 {
+            assert!(policy.backoff(1) <= Duration::from_millis(10));
+            assert!(policy.backoff(2) <= Duration::from_millis(20));
+            assert!(policy.backoff(5) <= Duration::from_millis(25));
+        }
+        assert_eq!(RetryPolicy::new(0).max_attempts(), 1);
+    }
+}
@@ -0,0 +1,525 @@
+//! 予約したコマンドの遅延実行
+//!
+//! サーガなどは [`CommandScheduler`] で「30 分後にセッションを放棄する」の
+//! ようなコマンドを予約し、状況が変わったらキーを指定して取り消す
+//! （同じキーで予約し直すと実行時刻を置き換える）。
+//!
+//! 予約は [`ScheduleStore`] に永続化し、[`ScheduledCommandDispatcher`] が
+//! 期限の来たものを取り出して [`CommandBus`] で実行する。
+//!
+//! ```text
+//! CommandScheduler::schedule → ScheduleStore（scheduled_commands）
+//!   → ScheduledCommandDispatcher::run_due → CommandBus::dispatch_with
+//! ```
+//!
+//! 取り出した予約は一定時間ロックするため、複数のプロセスで
+//! ディスパッチャーを動かしても同じ予約を同時に実行しない。
+
+#![allow(clippy::module_name_repetitions)]
+
+#[cfg(feature = "postgres")]
+mod postgres;
+
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresScheduleStore;
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::sync::Mutex;
+use tracing::{debug, error, warn};
+use uuid::Uuid;
+
+use crate::{
+    command::{Command, CommandBus},
+    context::CommandContext,
+    error::{CqrsError, Result},
+};
+
+/// 既定の1回に取り出す予約の数
+pub const DEFAULT_SCHEDULE_BATCH_SIZE: usize = 50;
+
+/// 既定の最大試行回数
+pub const DEFAULT_SCHEDULE_MAX_ATTEMPTS: u32 = 5;
+
+/// 既定の失敗から再試行までの間隔
+pub const DEFAULT_SCHEDULE_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// 取り出した予約をロックしておく時間
+pub const SCHEDULE_LEASE: Duration = Duration::from_mins(1);
+
+/// 予約したコマンド
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledCommand {
+    /// 予約 ID
+    pub id:             Uuid,
+    /// 取り消しや置き換えに使うキー（例: `abandon-session:{session_id}`）
+    pub key:            String,
+    /// コマンド名（[`Command::NAME`]）
+    pub command_name:   String,
+    /// コマンドの JSON
+    pub payload:        serde_json::Value,
+    /// 予約時の相関 ID
+    pub correlation_id: Option<String>,
+    /// 実行予定日時
+    pub due_at:         DateTime<Utc>,
+    /// これまでに取り出した回数
+    pub attempts:       u32,
+}
+
+/// 予約の保存先
+#[async_trait]
+pub trait ScheduleStore: Send + Sync {
+    /// 予約を保存する（同じキーの予約があれば置き換える）
+    async fn schedule(&self, command: ScheduledCommand) -> Result<()>;
+
+    /// キーを指定して予約を取り消す（取り消した場合は true）
+    async fn cancel(&self, key: &str) -> Result<bool>;
+
+    /// 期限の来た予約を実行予定日時の順に取り出し、`lease_until` までロックする
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ScheduledCommand>>;
+
+    /// 実行した予約を削除する
+    async fn complete(&self, id: Uuid) -> Result<()>;
+
+    /// 実行に失敗した予約を記録する（`retry_at` が None の場合は諦める）
+    async fn fail(&self, id: Uuid, error: &str, retry_at: Option<DateTime<Utc>>) -> Result<()>;
+}
+
+/// コマンドを予約する
+#[derive(Clone)]
+pub struct CommandScheduler {
+    store: Arc<dyn ScheduleStore>,
+}
+
+impl CommandScheduler {
+    /// 保存先を指定して作る
+    #[must_use]
+    pub fn new(store: Arc<dyn ScheduleStore>) -> Self {
+        Self { store }
+    }
+
+    /// `due_at` にコマンドを実行するよう予約する（同じキーの予約は置き換える）
+    ///
+    /// # Errors
+    ///
+    /// コマンドをシリアライズできない場合、または保存に失敗した場合
+    pub async fn schedule<C>(
+        &self,
+        key: impl Into<String>,
+        command: C,
+        due_at: DateTime<Utc>,
+        context: &CommandContext,
+    ) -> Result<Uuid>
+    where
+        C: Command + Serialize,
+    {
+        let payload = serde_json::to_value(&command)
+            .map_err(|e| CqrsError::Internal(format!("Failed to serialize {}: {e}", C::NAME)))?;
+        let id = Uuid::new_v4();
+        self.store
+            .schedule(ScheduledCommand {
+                id,
+                key: key.into(),
+                command_name: C::NAME.to_string(),
+                payload,
+                correlation_id: context.correlation_id.clone(),
+                due_at,
+                attempts: 0,
+            })
+            .await?;
+        Ok(id)
+    }
+
+    /// キーを指定して予約を取り消す（取り消した場合は true）
+    ///
+    /// # Errors
+    ///
+    /// 保存先の更新に失敗した場合
+    pub async fn cancel(&self, key: &str) -> Result<bool> {
+        self.store.cancel(key).await
+    }
+}
+
+type DispatchFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// JSON からコマンドを復元して実行する関数
+type Route = Box<
+    dyn for<'a> Fn(&'a CommandBus, serde_json::Value, CommandContext) -> DispatchFuture<'a>
+        + Send
+        + Sync,
+>;
+
+/// 期限の来た予約を実行する
+pub struct ScheduledCommandDispatcher {
+    store:        Arc<dyn ScheduleStore>,
+    bus:          Arc<CommandBus>,
+    routes:       HashMap<&'static str, Route>,
+    batch_size:   usize,
+    max_attempts: u32,
+    retry_delay:  Duration,
+}
+
+impl ScheduledCommandDispatcher {
+    /// 保存先と実行に使うバスを指定して作る
+    #[must_use]
+    pub fn new(store: Arc<dyn ScheduleStore>, bus: Arc<CommandBus>) -> Self {
+        Self {
+            store,
+            bus,
+            routes: HashMap::new(),
+            batch_size: DEFAULT_SCHEDULE_BATCH_SIZE,
+            max_attempts: DEFAULT_SCHEDULE_MAX_ATTEMPTS,
+            retry_delay: DEFAULT_SCHEDULE_RETRY_DELAY,
+        }
+    }
+
+    /// 予約できるコマンドを登録する
+    #[must_use]
+    pub fn route<C>(mut self) -> Self
+    where
+        C: Command + DeserializeOwned,
+    {
+        self.routes.insert(
+            C::NAME,
+            Box::new(|bus, payload, context| {
+                Box::pin(async move {
+                    let command: C = serde_json::from_value(payload).map_err(|e| {
+                        CqrsError::Internal(format!("Failed to deserialize {}: {e}", C::NAME))
+                    })?;
+                    bus.dispatch_with(command, context).await.map(drop)
+                })
+            }),
+        );
+        self
+    }
+
+    /// 1回に取り出す予約の数を変更する
+    #[must_use]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// 失敗した予約の最大試行回数と再試行までの間隔を変更する
+    #[must_use]
+    pub fn with_retry(mut self, max_attempts: u32, retry_delay: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// `now` までに期限の来た予約を実行し、成功した数を返す
+    ///
+    /// # Errors
+    ///
+    /// 保存先の読み書きに失敗した場合（コマンドの失敗は予約に記録して続ける）
+    pub async fn run_due(&self, now: DateTime<Utc>) -> Result<usize> {
+        let lease_until = now + SCHEDULE_LEASE;
+        let due = self
+            .store
+            .claim_due(now, lease_until, self.batch_size)
+            .await?;
+
+        let mut succeeded = 0;
+        for scheduled in due {
+            match self.dispatch(&scheduled).await {
+                Ok(()) => {
+                    self.store.complete(scheduled.id).await?;
+                    succeeded += 1;
+                },
+                Err(e) => {
+                    let retry_at =
+                        (scheduled.attempts < self.max_attempts).then(|| now + self.retry_delay);
+                    warn!(
+                        schedule_id = %scheduled.id,
+                        key = %scheduled.key,
+                        command = %scheduled.command_name,
+                        attempts = scheduled.attempts,
+                        gave_up = retry_at.is_none(),
+                        error = %e,
+                        "Scheduled command failed"
+                    );
+                    self.store
+                        .fail(scheduled.id, &e.to_string(), retry_at)
+                        .await?;
+                },
+            }
+        }
+        if succeeded > 0 {
+            debug!(succeeded, "Dispatched scheduled commands");
+        }
+        Ok(succeeded)
+    }
+
+    /// 一定間隔で期限の来た予約を実行し続ける（終了しない）
+    pub async fn run(&self, poll_interval: Duration) {
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.run_due(Utc::now()).await {
+                error!(error = %e, "Failed to dispatch scheduled commands");
+            }
+        }
+    }
+
+    async fn dispatch(&self, scheduled: &ScheduledCommand) -> Result<()> {
+        let route = self
+            .routes
+            .get(scheduled.command_name.as_str())
+            .ok_or_else(|| {
+                CqrsError::Internal(format!(
+                    "No route registered for scheduled command {}",
+                    scheduled.command_name
+                ))
+            })?;
+
+        let mut context = CommandContext::new();
+        context.correlation_id.clone_from(&scheduled.correlation_id);
+        route(&self.bus, scheduled.payload.clone(), context).await
+    }
+}
+
+/// メモリ上の予約の保存先（テストや単一プロセスでの実行向け、
+/// 諦めた予約は残さない）
+#[derive(Debug, Default)]
+pub struct InMemoryScheduleStore {
+    entries: Mutex<Vec<(ScheduledCommand, Option<DateTime<Utc>>)>>,
+}
+
+impl InMemoryScheduleStore {
+    /// 空の保存先を作る
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 実行待ちの予約
+    pub async fn pending(&self) -> Vec<ScheduledCommand> {
+        self.entries
+            .lock()
+            .await
+            .iter()
+            .map(|(command, _)| command.clone())
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ScheduleStore for InMemoryScheduleStore {
+    async fn schedule(&self, command: ScheduledCommand) -> Result<()> {
+        let mut entries = self.entries.lock().await;
+        entries.retain(|(existing, _)| existing.key != command.key);
+        entries.push((command, None));
+        drop(entries);
+        Ok(())
+    }
+
+    async fn cancel(&self, key: &str) -> Result<bool> {
+        let mut entries = self.entries.lock().await;
+        let before = entries.len();
+        entries.retain(|(existing, _)| existing.key != key);
+        Ok(entries.len() < before)
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ScheduledCommand>> {
+        let mut entries = self.entries.lock().await;
+        entries.sort_by_key(|(command, _)| command.due_at);
+        let claimed = entries
+            .iter_mut()
+            .filter(|(command, locked_until)| {
+                command.due_at <= now && locked_until.is_none_or(|until| until < now)
+            })
+            .take(limit)
+            .map(|(command, locked_until)| {
+                command.attempts += 1;
+                *locked_until = Some(lease_until);
+                command.clone()
+            })
+            .collect();
+        drop(entries);
+        Ok(claimed)
+    }
+
+    async fn complete(&self, id: Uuid) -> Result<()> {
+        self.entries
+            .lock()
+            .await
+            .retain(|(command, _)| command.id != id);
+        Ok(())
+    }
+
+    async fn fail(&self, id: Uuid, _error: &str, retry_at: Option<DateTime<Utc>>) -> Result<()> {
+        let mut entries = self.entries.lock().await;
+        match retry_at {
+            Some(retry_at) => {
+                if let Some((command, locked_until)) =
+                    entries.iter_mut().find(|(command, _)| command.id == id)
+                {
+                    command.due_at = retry_at;
+                    *locked_until = None;
+                }
+            },
+            None => entries.retain(|(command, _)| command.id != id),
+        }
+        drop(entries);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex as StdMutex;
+
+    use chrono::TimeDelta;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::command::CommandHandler;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct AbandonSession {
+        session_id: Uuid,
+    }
+
+    impl Command for AbandonSession {
+        type Output = ();
+
+        const NAME: &'static str = "AbandonSession";
+    }
+
+    /// 放棄したセッションを記録し、`failing` のセッションでは失敗する
+    #[derive(Default)]
+    struct SessionHandler {
+        abandoned: StdMutex<Vec<(Uuid, Option<String>)>>,
+        failing:   Option<Uuid>,
+    }
+
+    #[async_trait]
+    impl CommandHandler<AbandonSession> for SessionHandler {
+        async fn handle(&self, command: AbandonSession, context: &CommandContext) -> Result<()> {
+            if self.failing == Some(command.session_id) {
+                return Err(CqrsError::Internal("session locked".to_string()));
+            }
+            self.abandoned
+                .lock()
+                .unwrap()
+                .push((command.session_id, context.correlation_id.clone()));
+            Ok(())
+        }
+    }
+
+    fn setup(
+        handler: &Arc<SessionHandler>,
+    ) -> (
+        Arc<InMemoryScheduleStore>,
+        CommandScheduler,
+        ScheduledCommandDispatcher,
+    ) {
+        let store = Arc::new(InMemoryScheduleStore::new());
+        let bus = Arc::new(CommandBus::new().register::<AbandonSession, _>(Arc::clone(handler)));
+        let scheduler = CommandScheduler::new(Arc::clone(&store) as Arc<dyn ScheduleStore>);
+        let dispatcher =
+            ScheduledCommandDispatcher::new(Arc::clone(&store) as Arc<dyn ScheduleStore>, bus)
+                .route::<AbandonSession>()
+                .with_retry(2, Duration::from_secs(10));
+        (store, scheduler, dispatcher)
+    }
+
+    #[tokio::test]
+    async fn test_dispatches_commands_when_due_and_honours_rescheduling() {
+        let handler = Arc::new(SessionHandler::default());
+        let (store, scheduler, dispatcher) = setup(&handler);
+        let now = Utc::now();
+        let (active, idle) = (Uuid::new_v4(), Uuid::new_v4());
+        let context = CommandContext::new().with_correlation_id("saga-1");
+
+        for session_id in [active, idle] {
+            scheduler
+                .schedule(
+                    format!("abandon-session:{session_id}"),
+                    AbandonSession { session_id },
+                    now + TimeDelta::minutes(30),
+                    &context,
+                )
+                .await
+                .unwrap();
+        }
+        // 活動があったセッションは期限を延ばす
+        scheduler
+            .schedule(
+                format!("abandon-session:{active}"),
+                AbandonSession { session_id: active },
+                now + TimeDelta::minutes(50),
+                &context,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(dispatcher.run_due(now).await.unwrap(), 0);
+        assert_eq!(
+            dispatcher
+                .run_due(now + TimeDelta::minutes(31))
+                .await
+                .unwrap(),
+            1
+        );
+
+        assert_eq!(
+            *handler.abandoned.lock().unwrap(),
+            vec![(idle, Some("saga-1".to_string()))]
+        );
+        assert_eq!(store.pending().await.len(), 1);
+        assert!(
+            scheduler
+                .cancel(&format!("abandon-session:{active}"))
+                .await
+                .unwrap()
+        );
+        assert!(store.pending().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_commands_are_retried_then_given_up() {
+        let failing = Uuid::new_v4();
+        let handler = Arc::new(SessionHandler {
+            failing: Some(failing),
+            ..SessionHandler::default()
+        });
+        let (store, scheduler, dispatcher) = setup(&handler);
+        let now = Utc::now();
+        scheduler
+            .schedule(
+                "abandon",
+                AbandonSession {
+                    session_id: failing,
+                },
+                now,
+                &CommandContext::new(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(dispatcher.run_due(now).await.unwrap(), 0);
+        let pending = store.pending().await;
+        assert_eq!(pending[0].due_at, now + TimeDelta::seconds(10));
+        assert_eq!(pending[0].attempts, 1);
+
+        dispatcher
+            .run_due(now + TimeDelta::seconds(10))
+            .await
+            .unwrap();
+        assert!(store.pending().await.is_empty());
+    }
+}
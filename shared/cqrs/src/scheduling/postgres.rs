@@ -0,0 +1,153 @@
+//! `scheduled_commands` テーブルを使う予約の保存先
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row, postgres::PgRow};
+use uuid::Uuid;
+
+use super::{ScheduleStore, ScheduledCommand};
+use crate::error::Result;
+
+/// `scheduled_commands` テーブルに予約を保存する
+///
+/// テーブルは `migrations/` のマイグレーションで作る。諦めた予約は
+/// `status = 'failed'` として最後のエラーとともに残す
+pub struct PostgresScheduleStore {
+    pool: PgPool,
+}
+
+impl PostgresScheduleStore {
+    /// 接続プールを指定して作る
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn scheduled_command(row: &PgRow) -> Result<ScheduledCommand> {
+    Ok(ScheduledCommand {
+        id:             row.try_get("schedule_id")?,
+        key:            row.try_get("schedule_key")?,
+        command_name:   row.try_get("command_name")?,
+        payload:        row.try_get("payload")?,
+        correlation_id: row.try_get("correlation_id")?,
+        due_at:         row.try_get("due_at")?,
+        attempts:       row
+            .try_get::<i32, _>("attempts")?
+            .try_into()
+            .unwrap_or_default(),
+    })
+}
+
+#[async_trait]
+impl ScheduleStore for PostgresScheduleStore {
+    async fn schedule(&self, command: ScheduledCommand) -> Result<()> {
+        sqlx::query(
+            r"
+            INSERT INTO scheduled_commands (
+                schedule_id, schedule_key, command_name, payload, correlation_id, due_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (schedule_key) DO UPDATE SET
+                schedule_id = EXCLUDED.schedule_id,
+                command_name = EXCLUDED.command_name,
+                payload = EXCLUDED.payload,
+                correlation_id = EXCLUDED.correlation_id,
+                due_at = EXCLUDED.due_at,
+                status = 'pending',
+                attempts = 0,
+                locked_until = NULL,
+                last_error = NULL,
+                updated_at = NOW()
+            ",
+        )
+        .bind(command.id)
+        .bind(&command.key)
+        .bind(&command.command_name)
+        .bind(&command.payload)
+        .bind(&command.correlation_id)
+        .bind(command.due_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn cancel(&self, key: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "DELETE FROM scheduled_commands WHERE schedule_key = $1 AND status = 'pending'",
+        )
+        .bind(key)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ScheduledCommand>> {
+        // 他のディスパッチャーがロック中の行は読み飛ばす
+        let rows = sqlx::query(
+            r"
+            WITH due AS (
+                SELECT schedule_id
+                FROM scheduled_commands
+                WHERE status = 'pending'
+                  AND due_at <= $1
+                  AND (locked_until IS NULL OR locked_until < $1)
+                ORDER BY due_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE scheduled_commands AS s
+            SET locked_until = $2, attempts = s.attempts + 1, updated_at = NOW()
+            FROM due
+            WHERE s.schedule_id = due.schedule_id
+            RETURNING s.schedule_id, s.schedule_key, s.command_name, s.payload,
+                      s.correlation_id, s.due_at, s.attempts
+            ",
+        )
+        .bind(now)
+        .bind(lease_until)
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut claimed = rows
+            .iter()
+            .map(scheduled_command)
+            .collect::<Result<Vec<_>>>()?;
+        claimed.sort_by_key(|command| command.due_at);
+        Ok(claimed)
+    }
+
+    async fn complete(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM scheduled_commands WHERE schedule_id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn fail(&self, id: Uuid, error: &str, retry_at: Option<DateTime<Utc>>) -> Result<()> {
+        sqlx::query(
+            r"
+            UPDATE scheduled_commands
+            SET status = CASE WHEN $3::timestamptz IS NULL THEN 'failed' ELSE 'pending' END,
+                due_at = COALESCE($3, due_at),
+                locked_until = NULL,
+                last_error = $2,
+                updated_at = NOW()
+            WHERE schedule_id = $1
+            ",
+        )
+        .bind(id)
+        .bind(error)
+        .bind(retry_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+//! スナップショットを取るタイミング
+//!
+//! [`EventSourcedRepository`](crate::EventSourcedRepository) は保存のたびに
+//! [`SnapshotPolicy`] を評価し、条件を満たせば保存後の状態をスナップショットに
+//! する。ハンドラーはスナップショットのことを意識しなくてよい。
+//!
+//! ```ignore
+//! let repository = EventSourcedRepository::<VocabularyEntry>::new(store)
+//!     .with_snapshot_policy(SnapshotPolicy::EveryEvents(50));
+//! ```
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// スナップショットを取る条件
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SnapshotPolicy {
+    /// 自動では取らない（`save_snapshot` を明示的に呼ぶ）
+    #[default]
+    Never,
+    /// バージョンが N の倍数をまたいだとき
+    EveryEvents(u32),
+    /// スナップショットがないか、前回から一定時間たったとき
+    ///
+    /// 判定のために保存のたびに最新のスナップショットを読み込む
+    Interval(Duration),
+    /// シリアライズした状態が指定バイト数以上のとき
+    ///
+    /// 小さな集約はイベントからの復元で十分なため、大きな集約だけ取る
+    StateSize(usize),
+}
+
+/// 最後に取ったスナップショット
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotMark {
+    /// スナップショットのバージョン
+    pub version:  u32,
+    /// 取った日時
+    pub taken_at: DateTime<Utc>,
+}
+
+impl SnapshotPolicy {
+    /// 判定に最後のスナップショットが必要か
+    #[must_use]
+    pub const fn needs_last_snapshot(&self) -> bool {
+        matches!(self, Self::Interval(_))
+    }
+
+    /// バージョンを `previous_version` から `version` に進めた後に取るか
+    ///
+    /// `state_size` はシリアライズした状態のバイト数。`last_snapshot` は
+    /// [`needs_last_snapshot`](Self::needs_last_snapshot) の場合だけ渡す
+    #[must_use]
+    pub fn should_snapshot(
+        &self,
+        previous_version: u32,
+        version: u32,
+        state_size: usize,
+        last_snapshot: Option<&SnapshotMark>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        if version <= previous_version {
+            return false;
+        }
+        match *self {
+            Self::Never => false,
+            Self::EveryEvents(0) => true,
+            Self::EveryEvents(every) => previous_version / every != version / every,
+            Self::Interval(interval) => last_snapshot.is_none_or(|mark| {
+                mark.version < version
+                    && now - mark.taken_at
+                        >= TimeDelta::from_std(interval).unwrap_or(TimeDelta::MAX)
+            }),
+            Self::StateSize(min_bytes) => state_size >= min_bytes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_events_snapshots_when_crossing_a_multiple() {
+        let policy = SnapshotPolicy::EveryEvents(10);
+        let now = Utc::now();
+
+        assert!(!policy.should_snapshot(0, 9, 0, None, now));
+        assert!(policy.should_snapshot(9, 10, 0, None, now));
+        assert!(policy.should_snapshot(8, 12, 0, None, now));
+        assert!(!policy.should_snapshot(10, 19, 0, None, now));
+        assert!(!SnapshotPolicy::Never.should_snapshot(0, 100, 0, None, now));
+    }
+
+    #[test]
+    fn test_interval_and_state_size() {
+        let policy = SnapshotPolicy::Interval(Duration::from_mins(5));
+        let now = Utc::now();
+        let mark = |minutes_ago| SnapshotMark {
+            version:  3,
+            taken_at: now - TimeDelta::minutes(minutes_ago),
+        };
+
+        assert!(policy.needs_last_snapshot());
+        assert!(policy.should_snapshot(0, 1, 0, None, now));
+        assert!(!policy.should_snapshot(3, 4, 0, Some(&mark(1)), now));
+        assert!(policy.should_snapshot(3, 4, 0, Some(&mark(6)), now));
+
+        let policy = SnapshotPolicy::StateSize(1024);
+        assert!(!policy.should_snapshot(0, 1, 512, None, now));
+        assert!(policy.should_snapshot(0, 1, 2048, None, now));
+        assert!(!policy.should_snapshot(1, 1, 2048, None, now));
+    }
+}
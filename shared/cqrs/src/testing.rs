@@ -0,0 +1,237 @@
+//! 集約ルートのテスト用 DSL（`testing` feature）
+//!
+//! データベースを使わず、過去のイベント（given）とコマンド（when）から
+//! 発生するイベントやエラー（then）を検証する。
+//!
+//! ```ignore
+//! AggregateTestFixture::<Entry>::given([EntryEvent::Created { .. }])
+//!     .when(EntryCommand::AddDefinition(..))
+//!     .then([EntryEvent::DefinitionAdded { .. }]);
+//! ```
+
+// 検証の失敗はテストの失敗として panic で伝える。then 系は検証を続けて
+// 書けるよう Self を返すが、最後の戻り値は捨ててよい
+#![allow(
+    clippy::module_name_repetitions,
+    clippy::panic,
+    clippy::return_self_not_must_use
+)]
+
+use std::fmt::Debug;
+
+use shared_event_store::{AggregateRoot, EventSourced};
+
+/// 集約ルートのテストの前提（過去のイベント）
+pub struct AggregateTestFixture<A: AggregateRoot> {
+    aggregate: EventSourced<A>,
+}
+
+impl<A: AggregateRoot> AggregateTestFixture<A> {
+    /// 過去のイベントを適用した状態から始める
+    pub fn given(events: impl IntoIterator<Item = A::Event>) -> Self {
+        let events: Vec<A::Event> = events.into_iter().collect();
+        Self {
+            aggregate: EventSourced::from_history(&events),
+        }
+    }
+
+    /// イベントがない初期状態から始める
+    #[must_use]
+    pub fn given_no_prior_activity() -> Self {
+        Self {
+            aggregate: EventSourced::default(),
+        }
+    }
+
+    /// コマンドを処理する
+    #[must_use]
+    pub fn when(mut self, command: A::Command) -> AggregateTestResult<A> {
+        let result = self
+            .aggregate
+            .execute(command)
+            .map(|_| ())
+            .map(|()| self.aggregate.take_uncommitted());
+        AggregateTestResult {
+            aggregate: self.aggregate,
+            result,
+        }
+    }
+}
+
+/// コマンドを処理した結果
+pub struct AggregateTestResult<A: AggregateRoot> {
+    aggregate: EventSourced<A>,
+    result:    Result<Vec<A::Event>, A::Error>,
+}
+
+impl<A> AggregateTestResult<A>
+where
+    A: AggregateRoot,
+    A::Event: PartialEq + Debug,
+    A::Error: Debug,
+{
+    /// 発生したイベントが `expected` と一致することを確かめる
+    ///
+    /// # Panics
+    ///
+    /// コマンドが拒否された場合、またはイベントが一致しない場合
+    pub fn then(self, expected: impl IntoIterator<Item = A::Event>) -> Self {
+        let expected: Vec<A::Event> = expected.into_iter().collect();
+        match &self.result {
+            Ok(events) => assert_eq!(events, &expected, "unexpected events"),
+            Err(error) => {
+                panic!("expected events {expected:?}, but the command was rejected: {error:?}")
+            },
+        }
+        self
+    }
+
+    /// イベントが発生しないことを確かめる
+    ///
+    /// # Panics
+    ///
+    /// コマンドが拒否された場合、またはイベントが発生した場合
+    pub fn then_no_events(self) -> Self {
+        self.then([])
+    }
+
+    /// コマンドが `expected` で拒否されることを確かめる
+    ///
+    /// # Panics
+    ///
+    /// コマンドが受け付けられた場合、またはエラーが一致しない場合
+    pub fn then_error(self, expected: &A::Error) -> Self
+    where
+        A::Error: PartialEq,
+    {
+        self.then_error_matches(|error| error == expected)
+    }
+
+    /// コマンドが拒否され、エラーが条件を満たすことを確かめる
+    ///
+    /// # Panics
+    ///
+    /// コマンドが受け付けられた場合、またはエラーが条件を満たさない場合
+    pub fn then_error_matches(self, predicate: impl FnOnce(&A::Error) -> bool) -> Self {
+        match &self.result {
+            Ok(events) => {
+                panic!("expected the command to be rejected, but it raised {events:?}")
+            },
+            Err(error) => assert!(predicate(error), "unexpected error: {error:?}"),
+        }
+        self
+    }
+
+    /// イベントを適用した後の状態を検証する
+    pub fn then_state(self, check: impl FnOnce(&A)) -> Self {
+        check(self.aggregate.state());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    /// 定員のある学習グループ
+    #[derive(Debug, Default, Clone, Serialize, Deserialize)]
+    struct StudyGroup {
+        members:  Vec<String>,
+        capacity: usize,
+    }
+
+    enum GroupCommand {
+        Create { capacity: usize },
+        Join(String),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "event_type")]
+    enum GroupEvent {
+        Created { capacity: usize },
+        Joined { member: String },
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum GroupError {
+        Full,
+        AlreadyMember,
+    }
+
+    impl AggregateRoot for StudyGroup {
+        type Command = GroupCommand;
+        type Error = GroupError;
+        type Event = GroupEvent;
+
+        const AGGREGATE_TYPE: &'static str = "study_group";
+
+        fn handle(&self, command: GroupCommand) -> Result<Vec<GroupEvent>, GroupError> {
+            match command {
+                GroupCommand::Create { capacity } => Ok(vec![GroupEvent::Created { capacity }]),
+                GroupCommand::Join(member) if self.members.contains(&member) => {
+                    Err(GroupError::AlreadyMember)
+                },
+                GroupCommand::Join(_) if self.members.len() >= self.capacity => {
+                    Err(GroupError::Full)
+                },
+                GroupCommand::Join(member) => Ok(vec![GroupEvent::Joined { member }]),
+            }
+        }
+
+        fn apply(&mut self, event: &GroupEvent) {
+            match event {
+                GroupEvent::Created { capacity } => self.capacity = *capacity,
+                GroupEvent::Joined { member } => self.members.push(member.clone()),
+            }
+        }
+    }
+
+    fn joined(member: &str) -> GroupEvent {
+        GroupEvent::Joined {
+            member: member.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_given_when_then_checks_events_and_state() {
+        AggregateTestFixture::<StudyGroup>::given_no_prior_activity()
+            .when(GroupCommand::Create { capacity: 2 })
+            .then([GroupEvent::Created { capacity: 2 }]);
+
+        AggregateTestFixture::<StudyGroup>::given([GroupEvent::Created { capacity: 2 }])
+            .when(GroupCommand::Join("aiko".to_string()))
+            .then([joined("aiko")])
+            .then_state(|group| assert_eq!(group.members, vec!["aiko"]));
+    }
+
+    #[test]
+    fn test_then_error_checks_rejections() {
+        let history = [GroupEvent::Created { capacity: 1 }, joined("aiko")];
+
+        AggregateTestFixture::<StudyGroup>::given(history.clone())
+            .when(GroupCommand::Join("ben".to_string()))
+            .then_error(&GroupError::Full);
+        AggregateTestFixture::<StudyGroup>::given(history)
+            .when(GroupCommand::Join("aiko".to_string()))
+            .then_error_matches(|error| matches!(error, GroupError::AlreadyMember))
+            .then_state(|group| assert_eq!(group.members.len(), 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected events")]
+    fn test_then_fails_on_different_events() {
+        AggregateTestFixture::<StudyGroup>::given([GroupEvent::Created { capacity: 2 }])
+            .when(GroupCommand::Join("aiko".to_string()))
+            .then([joined("ben")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected the command to be rejected")]
+    fn test_then_error_fails_when_command_is_accepted() {
+        AggregateTestFixture::<StudyGroup>::given([GroupEvent::Created { capacity: 2 }])
+            .when(GroupCommand::Join("aiko".to_string()))
+            .then_error(&GroupError::Full);
+    }
+}
@@ -0,0 +1,201 @@
+//! コマンドの検証
+//!
+//! コマンドは [`Command::validate`](crate::Command::validate)
+//! で内容を検証する。 [`CommandBus`](crate::CommandBus)
+//! は登録したすべてのコマンドをハンドラーの 直前で検証し、違反があれば
+//! [`CqrsError::Validation`](crate::CqrsError::Validation) を返す。
+//!
+//! `grpc` feature では違反の一覧を `InvalidArgument` の [`tonic::Status`] に
+//! 変換し、フィールドごとの違反を `google.rpc.BadRequest` の詳細として付ける。
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::fmt;
+
+use serde::Serialize;
+
+/// フィールド単位の違反
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldViolation {
+    /// 違反したフィールド（ネストしたフィールドは `definitions[0].text`
+    /// のように書く）
+    pub field:       String,
+    /// 違反の内容
+    pub description: String,
+}
+
+/// コマンドの検証エラー（違反の一覧）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ValidationErrors {
+    violations: Vec<FieldViolation>,
+}
+
+impl ValidationErrors {
+    /// 違反のない一覧を作る
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 違反を追加する
+    pub fn add(&mut self, field: impl Into<String>, description: impl Into<String>) {
+        self.violations.push(FieldViolation {
+            field:       field.into(),
+            description: description.into(),
+        });
+    }
+
+    /// 違反を追加する（ビルダー形式）
+    #[must_use]
+    pub fn with(mut self, field: impl Into<String>, description: impl Into<String>) -> Self {
+        self.add(field, description);
+        self
+    }
+
+    /// 違反がないか
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// 違反の一覧
+    #[must_use]
+    pub fn violations(&self) -> &[FieldViolation] {
+        &self.violations
+    }
+
+    /// 違反がなければ `Ok(())`、あればエラーとして返す
+    ///
+    /// # Errors
+    ///
+    /// 違反が1つ以上ある場合
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let violations = self
+            .violations
+            .iter()
+            .map(|violation| format!("{}: {}", violation.field, violation.description))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{violations}")
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+#[cfg(feature = "grpc")]
+mod grpc {
+    //! `google.rpc.BadRequest` への変換
+    //!
+    //! tonic-types を使わずに済むよう、必要なメッセージだけを定義する。
+
+    use prost::Message;
+
+    use super::ValidationErrors;
+
+    const BAD_REQUEST_TYPE_URL: &str = "type.googleapis.com/google.rpc.BadRequest";
+
+    /// `google.rpc.Status`
+    #[derive(Clone, PartialEq, Message)]
+    struct RpcStatus {
+        #[prost(int32, tag = "1")]
+        code:    i32,
+        #[prost(string, tag = "2")]
+        message: String,
+        #[prost(message, repeated, tag = "3")]
+        details: Vec<prost_types::Any>,
+    }
+
+    /// `google.rpc.BadRequest`
+    #[derive(Clone, PartialEq, Message)]
+    struct BadRequest {
+        #[prost(message, repeated, tag = "1")]
+        field_violations: Vec<FieldViolation>,
+    }
+
+    /// `google.rpc.BadRequest.FieldViolation`
+    #[derive(Clone, PartialEq, Message)]
+    struct FieldViolation {
+        #[prost(string, tag = "1")]
+        field:       String,
+        #[prost(string, tag = "2")]
+        description: String,
+    }
+
+    impl From<ValidationErrors> for tonic::Status {
+        fn from(errors: ValidationErrors) -> Self {
+            let message = format!("Invalid argument: {errors}");
+            let bad_request = BadRequest {
+                field_violations: errors
+                    .violations
+                    .into_iter()
+                    .map(|violation| FieldViolation {
+                        field:       violation.field,
+                        description: violation.description,
+                    })
+                    .collect(),
+            };
+            let status = RpcStatus {
+                code:    tonic::Code::InvalidArgument as i32,
+                message: message.clone(),
+                details: vec![prost_types::Any {
+                    type_url: BAD_REQUEST_TYPE_URL.to_string(),
+                    value:    bad_request.encode_to_vec(),
+                }],
+            };
+            Self::with_details(
+                tonic::Code::InvalidArgument,
+                message,
+                status.encode_to_vec().into(),
+            )
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_converts_to_invalid_argument_with_bad_request_details() {
+            let errors = ValidationErrors::new().with("spelling", "must not be empty");
+
+            let status = tonic::Status::from(errors);
+
+            assert_eq!(status.code(), tonic::Code::InvalidArgument);
+            let details = RpcStatus::decode(status.details()).unwrap();
+            assert_eq!(details.details[0].type_url, BAD_REQUEST_TYPE_URL);
+            let bad_request = BadRequest::decode(details.details[0].value.as_slice()).unwrap();
+            assert_eq!(bad_request.field_violations[0].field, "spelling");
+            assert_eq!(
+                bad_request.field_violations[0].description,
+                "must not be empty"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collects_violations() {
+        let mut errors = ValidationErrors::new();
+        assert!(errors.clone().into_result().is_ok());
+
+        errors.add("spelling", "must not be empty");
+        let errors = errors.with("definitions[0].text", "is too long");
+
+        assert_eq!(errors.violations().len(), 2);
+        assert_eq!(
+            errors.to_string(),
+            "spelling: must not be empty, definitions[0].text: is too long"
+        );
+        assert!(errors.into_result().is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//! 設定からのキャッシュ構築
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "memory")]
+use crate::memory::MemoryCache;
+#[cfg(feature = "redis")]
+use crate::redis_cache::RedisCache;
+use crate::{Cache, Error, Result};
+
+/// キャッシュ設定
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum CacheConfig {
+    /// インメモリキャッシュ（開発環境用）
+    #[serde(rename = "memory")]
+    Memory,
+    /// Redis
+    #[serde(rename = "redis")]
+    Redis {
+        /// 接続 URL
+        url: String,
+    },
+}
+
+impl CacheConfig {
+    /// 環境変数から読み込む
+    ///
+    /// `CACHE_BACKEND`（`redis`、`memory`、`none`）を参照する。未指定の場合は
+    /// `REDIS_URL` があれば Redis、なければキャッシュなし（`None`）とする
+    ///
+    /// # Errors
+    ///
+    /// 未知のバックエンド名が指定された場合や、Redis の URL がない場合は
+    /// エラーを返す
+    pub fn from_env() -> Result<Option<Self>> {
+        let redis_url = std::env::var("REDIS_URL").ok();
+        match std::env::var("CACHE_BACKEND") {
+            Ok(backend) => Self::parse(&backend, redis_url),
+            Err(_) => Ok(redis_url.map(|url| Self::Redis { url })),
+        }
+    }
+
+    /// バックエンド名から設定を作る
+    ///
+    /// # Errors
+    ///
+    /// 未知のバックエンド名が指定された場合や、Redis の URL がない場合は
+    /// エラーを返す
+    pub fn parse(backend: &str, redis_url: Option<String>) -> Result<Option<Self>> {
+        match backend {
+            "none" => Ok(None),
+            "memory" => Ok(Some(Self::Memory)),
+            "redis" => redis_url
+                .map(|url| Some(Self::Redis { url }))
+                .ok_or_else(|| {
+                    Error::Configuration("REDIS_URL is required for the redis cache backend".into())
+                }),
+            other => Err(Error::Configuration(format!(
+                "Unknown cache backend: {other} (expected redis, memory or none)"
+            ))),
+        }
+    }
+
+    /// バックエンド名
+    #[must_use]
+    pub const fn backend(&self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Redis { .. } => "redis",
+        }
+    }
+}
+
+/// 設定からキャッシュを構築する
+///
+/// # Errors
+///
+/// バックエンドが feature で無効化されている場合は
+/// [`Error::BackendNotCompiled`]、接続に失敗した場合は [`Error::Connection`]
+/// を返す
+pub async fn from_config(config: &CacheConfig) -> Result<Arc<dyn Cache>> {
+    match config {
+        #[cfg(feature = "memory")]
+        CacheConfig::Memory => Ok(Arc::new(MemoryCache::new())),
+        #[cfg(feature = "redis")]
+        CacheConfig::Redis { url } => Ok(Arc::new(RedisCache::connect(url).await?)),
+        #[allow(unreachable_patterns)]
+        other => Err(Error::BackendNotCompiled {
+            backend: other.backend(),
+            feature: other.backend(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_backend_names() {
+        assert_eq!(CacheConfig::parse("none", None).unwrap(), None);
+        assert_eq!(
+            CacheConfig::parse("memory", None).unwrap(),
+            Some(CacheConfig::Memory)
+        );
+        assert_eq!(
+            CacheConfig::parse("redis", Some("redis://localhost".to_string())).unwrap(),
+            Some(CacheConfig::Redis {
+                url: "redis://localhost".to_string(),
+            })
+        );
+        assert!(matches!(
+            CacheConfig::parse("redis", None),
+            Err(Error::Configuration(_))
+        ));
+        assert!(matches!(
+            CacheConfig::parse("memcached", None),
+            Err(Error::Configuration(_))
+        ));
+    }
+
+    #[cfg(feature = "memory")]
+    #[tokio::test]
+    async fn test_from_config_builds_memory_cache() {
+        let cache = from_config(&CacheConfig::Memory).await.unwrap();
+
+        cache.set("key", b"value".to_vec(), 60).await.unwrap();
+        assert_eq!(cache.get("key").await.unwrap(), Some(b"value".to_vec()));
+    }
+
+    #[cfg(not(feature = "memory"))]
+    #[tokio::test]
+    async fn test_memory_backend_not_compiled_is_reported() {
+        assert!(matches!(
+            from_config(&CacheConfig::Memory).await,
+            Err(Error::BackendNotCompiled {
+                backend: "memory",
+                ..
+            })
+        ));
+    }
+
+    #[cfg(not(feature = "redis"))]
+    #[tokio::test]
+    async fn test_redis_backend_not_compiled_is_reported() {
+        let config = CacheConfig::Redis {
+            url: "redis://localhost".to_string(),
+        };
+
+        let error = from_config(&config).await.err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Cache backend 'redis' is not compiled in (enable the `redis` feature of shared_cache)"
+        );
+    }
+}
@@ -1,18 +1,76 @@
 //! Cache - キャッシュ実装
 //!
-//! キャッシュ機能を提供するクレート。
-//! 現在は基本的な構造のみ定義しており、Redis
-//! クライアントの実装を予定しています。
+//! キー・バイト列のキャッシュを [`Cache`] トレイトで抽象化するクレート。
+//!
+//! バックエンドは cargo feature でコンパイル時に選択します。
+//!
+//! - `redis`: Redis（[`RedisCache`]）
+//! - `memory`: インメモリ（[`MemoryCache`]）
+//!
+//! 実行時は [`CacheConfig`] から [`from_config`] で構築します。
 
+use async_trait::async_trait;
 use thiserror::Error;
 
-/// キャッシュクライアント
-pub struct Client;
+pub mod factory;
+#[cfg(feature = "memory")]
+pub mod memory;
+#[cfg(feature = "redis")]
+pub mod redis_cache;
+
+pub use factory::{CacheConfig, from_config};
+#[cfg(feature = "memory")]
+pub use memory::MemoryCache;
+#[cfg(feature = "redis")]
+pub use redis_cache::RedisCache;
 
 /// キャッシュエラー
 #[derive(Debug, Error)]
 pub enum Error {
+    /// 接続エラー
+    #[error("Connection error: {0}")]
+    Connection(String),
+
+    /// バックエンドの操作エラー
+    #[error("Backend error: {0}")]
+    Backend(String),
+
+    /// 設定エラー
+    #[error("Configuration error: {0}")]
+    Configuration(String),
+
+    /// 設定されたバックエンドが feature で無効化されている
+    #[error(
+        "Cache backend '{backend}' is not compiled in (enable the `{feature}` feature of \
+         shared_cache)"
+    )]
+    BackendNotCompiled {
+        /// バックエンド名
+        backend: &'static str,
+        /// 有効にすべき feature 名
+        feature: &'static str,
+    },
+
     /// 内部エラー
     #[error("Internal error: {0}")]
     Internal(String),
 }
+
+/// キャッシュの Result 型
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// キャッシュの共通インターフェース
+#[async_trait]
+pub trait Cache: Send + Sync {
+    /// キャッシュから取得
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// 有効期限付きでキャッシュに保存
+    async fn set(&self, key: &str, value: Vec<u8>, ttl_seconds: u64) -> Result<()>;
+
+    /// キャッシュから削除
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    /// パターン（`*` と `?` のグロブ）に一致するキーを削除
+    async fn delete_pattern(&self, pattern: &str) -> Result<()>;
+}
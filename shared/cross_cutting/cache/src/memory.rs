@@ -0,0 +1,134 @@
+//! インメモリの [`Cache`] 実装
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+use crate::{Cache, Result};
+
+struct Entry {
+    value:      Vec<u8>,
+    expires_at: Instant,
+}
+
+/// インメモリキャッシュ
+///
+/// 期限切れのエントリは読み取り時に削除する。ローカル開発とテスト用
+#[allow(clippy::module_name_repetitions)]
+#[derive(Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl MemoryCache {
+    /// 新しい [`MemoryCache`] インスタンスを作成
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[async_trait]
+impl Cache for MemoryCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut entries = self.entries();
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Ok(Some(entry.value.clone())),
+            Some(_) => {
+                entries.remove(key);
+                drop(entries);
+                Ok(None)
+            },
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl_seconds: u64) -> Result<()> {
+        let expires_at = Instant::now() + Duration::from_secs(ttl_seconds);
+        self.entries()
+            .insert(key.to_string(), Entry { value, expires_at });
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.entries().remove(key);
+        Ok(())
+    }
+
+    async fn delete_pattern(&self, pattern: &str) -> Result<()> {
+        self.entries().retain(|key, _| !glob_match(pattern, key));
+        Ok(())
+    }
+}
+
+/// Redis の KEYS と同じく `*`（任意の文字列）と `?`（任意の1文字）を扱う
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            },
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            },
+            _ => match backtrack {
+                // 直前の `*` に1文字多く吸収させてやり直す
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                },
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("vocab:item:*", "vocab:item:42"));
+        assert!(glob_match("vocab:*:42", "vocab:item:42"));
+        assert!(glob_match("vocab:item:?", "vocab:item:4"));
+        assert!(!glob_match("vocab:item:?", "vocab:item:42"));
+        assert!(!glob_match("vocab:entry:*", "vocab:item:42"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[tokio::test]
+    async fn test_delete_pattern_and_expiry() {
+        let cache = MemoryCache::new();
+        cache.set("item:1", b"a".to_vec(), 60).await.unwrap();
+        cache.set("item:2", b"b".to_vec(), 60).await.unwrap();
+        cache.set("entry:1", b"c".to_vec(), 60).await.unwrap();
+        cache.set("expired", b"d".to_vec(), 0).await.unwrap();
+
+        cache.delete_pattern("item:*").await.unwrap();
+
+        assert_eq!(cache.get("item:1").await.unwrap(), None);
+        assert_eq!(cache.get("item:2").await.unwrap(), None);
+        assert_eq!(cache.get("entry:1").await.unwrap(), Some(b"c".to_vec()));
+        assert_eq!(cache.get("expired").await.unwrap(), None);
+    }
+}
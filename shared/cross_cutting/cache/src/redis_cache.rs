@@ -0,0 +1,67 @@
+//! Redis による [`Cache`] 実装
+
+use async_trait::async_trait;
+use redis::{AsyncCommands, aio::ConnectionManager};
+
+use crate::{Cache, Error, Result};
+
+/// Redis キャッシュ
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone)]
+pub struct RedisCache {
+    conn: ConnectionManager,
+}
+
+impl RedisCache {
+    /// Redis に接続する
+    ///
+    /// # Errors
+    ///
+    /// URL が不正な場合や接続に失敗した場合はエラーを返す
+    pub async fn connect(redis_url: &str) -> Result<Self> {
+        let client = redis::Client::open(redis_url)
+            .map_err(|e| Error::Configuration(format!("Invalid Redis URL: {e}")))?;
+        let conn = ConnectionManager::new(client)
+            .await
+            .map_err(|e| Error::Connection(e.to_string()))?;
+        Ok(Self { conn })
+    }
+}
+
+#[async_trait]
+impl Cache for RedisCache {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut conn = self.conn.clone();
+        conn.get(key)
+            .await
+            .map_err(|e| Error::Backend(e.to_string()))
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>, ttl_seconds: u64) -> Result<()> {
+        let mut conn = self.conn.clone();
+        conn.set_ex(key, value, ttl_seconds)
+            .await
+            .map_err(|e| Error::Backend(e.to_string()))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let mut conn = self.conn.clone();
+        conn.del(key)
+            .await
+            .map_err(|e| Error::Backend(e.to_string()))
+    }
+
+    async fn delete_pattern(&self, pattern: &str) -> Result<()> {
+        let mut conn = self.conn.clone();
+        let keys: Vec<String> = conn
+            .keys(pattern)
+            .await
+            .map_err(|e| Error::Backend(e.to_string()))?;
+        if keys.is_empty() {
+            return Ok(());
+        }
+        conn.del(keys)
+            .await
+            .map_err(|e| Error::Backend(e.to_string()))
+    }
+}
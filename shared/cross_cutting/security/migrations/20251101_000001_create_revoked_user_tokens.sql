@@ -0,0 +1,12 @@
+-- 失効させたユーザーのトークン（shared_security の PostgresRevocationStore が使う）
+-- revoked_at（UNIX 秒）以前に発行したトークンを拒否する。expires_at を過ぎた行は不要なので消してよい
+
+CREATE TABLE IF NOT EXISTS revoked_user_tokens (
+    user_id VARCHAR(255) PRIMARY KEY,
+    revoked_at BIGINT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_revoked_user_tokens_expires_at
+    ON revoked_user_tokens (expires_at);
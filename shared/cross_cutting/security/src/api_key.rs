@@ -0,0 +1,272 @@
+//! API キー
+//!
+//! CLI のインポーターや分析のエクスポーターなど、ユーザーの JWT を持たない
+//! 機械のクライアントは API キーで gRPC API を呼びます。キーは
+//! `ek_{prefix}_{secret}` の形で、発行時に一度だけ平文を返します。
+//! 保存するのは検索に使う `prefix` と `secret` の SHA-256 ハッシュだけで、
+//! 検証ではハッシュを定数時間で比較します。`secret` は十分に長い乱数のため、
+//! パスワードのような遅いハッシュは使いません。
+//!
+//! キーには [`Permission`] をスコープとして与え、有効期限と失効を確かめます。
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+use async_trait::async_trait;
+use rand::{Rng, distributions::Alphanumeric};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::{Permission, SecurityError};
+
+/// API キーの先頭に付ける識別子
+const KEY_TAG: &str = "ek";
+/// 検索に使う部分の長さ
+const PREFIX_LEN: usize = 8;
+/// 秘密の部分の長さ（英数字 40 文字で約 238 ビット）
+const SECRET_LEN: usize = 40;
+
+/// 保存する API キーの情報（秘密の部分はハッシュのみ）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKey {
+    /// 検索に使う部分（平文のキーの `ek_` の後ろ）
+    pub prefix:      String,
+    /// 秘密の部分の SHA-256（16 進）
+    pub secret_hash: String,
+    /// 用途の説明（例: `anki-importer`）
+    pub name:        String,
+    /// 許可する操作
+    pub scopes:      Vec<Permission>,
+    /// 発行した時刻（UNIX 秒）
+    pub created_at:  u64,
+    /// 有効期限（UNIX 秒）。None なら期限なし
+    pub expires_at:  Option<u64>,
+    /// 失効させた時刻（UNIX 秒）
+    pub revoked_at:  Option<u64>,
+}
+
+impl ApiKey {
+    /// スコープに権限が含まれるか
+    pub fn has(&self, permission: Permission) -> bool {
+        self.scopes.contains(&permission)
+    }
+
+    /// `now`（UNIX 秒）の時点で使えるか
+    pub fn is_active(&self, now: u64) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+
+    /// 秘密の部分がこのキーのものか（定数時間で比較する）
+    fn matches_secret(&self, secret: &str) -> bool {
+        hash_secret(secret)
+            .as_bytes()
+            .ct_eq(self.secret_hash.as_bytes())
+            .into()
+    }
+}
+
+/// 発行した API キー
+#[derive(Clone)]
+pub struct GeneratedApiKey {
+    /// 保存する情報
+    pub key:       ApiKey,
+    /// 平文のキー（クライアントに一度だけ渡し、保存しない）
+    pub plaintext: String,
+}
+
+impl std::fmt::Debug for GeneratedApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // 平文のキーはログに出さない
+        f.debug_struct("GeneratedApiKey")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+fn hash_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+fn random_alphanumeric(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+/// API キーを発行
+///
+/// `now` と `expires_at` は UNIX 秒
+pub fn generate_api_key(
+    name: impl Into<String>,
+    scopes: Vec<Permission>,
+    now: u64,
+    expires_at: Option<u64>,
+) -> GeneratedApiKey {
+    let prefix = random_alphanumeric(PREFIX_LEN);
+    let secret = random_alphanumeric(SECRET_LEN);
+    GeneratedApiKey {
+        plaintext: format!("{KEY_TAG}_{prefix}_{secret}"),
+        key:       ApiKey {
+            prefix,
+            secret_hash: hash_secret(&secret),
+            name: name.into(),
+            scopes,
+            created_at: now,
+            expires_at,
+            revoked_at: None,
+        },
+    }
+}
+
+/// 平文のキーを検索に使う部分と秘密の部分に分ける
+fn split_key(plaintext: &str) -> Option<(&str, &str)> {
+    let rest = plaintext.strip_prefix(KEY_TAG)?.strip_prefix('_')?;
+    let (prefix, secret) = rest.split_once('_')?;
+    (prefix.len() == PREFIX_LEN && secret.len() == SECRET_LEN).then_some((prefix, secret))
+}
+
+/// API キーの保存先
+#[async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// 発行したキーを保存する（同じ prefix のキーがあればエラー）
+    async fn insert(&self, key: &ApiKey) -> Result<(), SecurityError>;
+
+    /// prefix でキーを探す
+    async fn find_by_prefix(&self, prefix: &str) -> Result<Option<ApiKey>, SecurityError>;
+
+    /// キーを失効させる（見つからなければ false）
+    async fn revoke(&self, prefix: &str, revoked_at: u64) -> Result<bool, SecurityError>;
+}
+
+/// 平文の API キーを検証し、保存したキーの情報を返す
+///
+/// キーが見つからない・秘密が違う・失効や期限切れのいずれの場合も、
+/// 手がかりを与えないよう同じ [`SecurityError::InvalidApiKey`] を返します。
+///
+/// # Errors
+///
+/// キーが使えない場合や、保存先を読めない場合はエラーを返します
+pub async fn validate_api_key(
+    plaintext: &str,
+    store: &dyn ApiKeyStore,
+    now: u64,
+) -> Result<ApiKey, SecurityError> {
+    let (prefix, secret) = split_key(plaintext).ok_or(SecurityError::InvalidApiKey)?;
+    let key = store
+        .find_by_prefix(prefix)
+        .await?
+        .ok_or(SecurityError::InvalidApiKey)?;
+    if !key.matches_secret(secret) || !key.is_active(now) {
+        return Err(SecurityError::InvalidApiKey);
+    }
+    Ok(key)
+}
+
+/// メモリ上に API キーを保存する（ローカル開発・テスト用）
+#[derive(Debug, Default)]
+pub struct InMemoryApiKeyStore {
+    keys: Mutex<HashMap<String, ApiKey>>,
+}
+
+impl InMemoryApiKeyStore {
+    /// 空の保存先を作成
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, ApiKey>> {
+        self.keys.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for InMemoryApiKeyStore {
+    async fn insert(&self, key: &ApiKey) -> Result<(), SecurityError> {
+        let mut keys = self.lock();
+        if keys.contains_key(&key.prefix) {
+            return Err(SecurityError::ApiKeyStoreError(format!(
+                "API key prefix {} already exists",
+                key.prefix
+            )));
+        }
+        keys.insert(key.prefix.clone(), key.clone());
+        Ok(())
+    }
+
+    async fn find_by_prefix(&self, prefix: &str) -> Result<Option<ApiKey>, SecurityError> {
+        Ok(self.lock().get(prefix).cloned())
+    }
+
+    async fn revoke(&self, prefix: &str, revoked_at: u64) -> Result<bool, SecurityError> {
+        Ok(self
+            .lock()
+            .get_mut(prefix)
+            .map(|key| key.revoked_at = Some(revoked_at))
+            .is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_760_000_000;
+
+    #[tokio::test]
+    async fn test_api_keys_are_verified_against_hashed_secrets() {
+        let store = InMemoryApiKeyStore::new();
+        let generated = generate_api_key(
+            "anki-importer",
+            vec![Permission::VocabularyWrite],
+            NOW,
+            Some(NOW + 3600),
+        );
+        // 秘密の部分は保存しない
+        let secret = generated.plaintext.rsplit('_').next().unwrap();
+        assert!(
+            !serde_json::to_string(&generated.key)
+                .unwrap()
+                .contains(secret)
+        );
+        store.insert(&generated.key).await.unwrap();
+
+        let key = validate_api_key(&generated.plaintext, &store, NOW)
+            .await
+            .unwrap();
+        assert_eq!(key.name, "anki-importer");
+        assert!(key.has(Permission::VocabularyWrite));
+        assert!(!key.has(Permission::VocabularyPublish));
+
+        // 秘密の違うキー・形式の違うキー・期限切れのキーは使えない
+        let mut tampered = generated.plaintext.clone();
+        tampered.pop();
+        tampered.push(if generated.plaintext.ends_with('a') {
+            'b'
+        } else {
+            'a'
+        });
+        for (plaintext, now) in [
+            (tampered.as_str(), NOW),
+            ("not-an-api-key", NOW),
+            (generated.plaintext.as_str(), NOW + 3600),
+        ] {
+            assert!(matches!(
+                validate_api_key(plaintext, &store, now).await,
+                Err(SecurityError::InvalidApiKey)
+            ));
+        }
+
+        // 失効させたキーは使えない
+        assert!(store.revoke(&generated.key.prefix, NOW).await.unwrap());
+        assert!(matches!(
+            validate_api_key(&generated.plaintext, &store, NOW).await,
+            Err(SecurityError::InvalidApiKey)
+        ));
+    }
+}
@@ -0,0 +1,390 @@
+//! 個人情報のフィールド暗号化
+//!
+//! リポジトリはメールアドレスや表示名などの列を [`FieldCipher`] で
+//! AES-256-GCM により暗号化して保存し、読み込み時に復号します。暗号文は
+//! `pii:{key_id}:{hex(nonce || ciphertext)}` の形で、どの鍵で暗号化したかを
+//! 含むため、鍵を入れ替えても古い鍵の暗号文を読めます（新しい暗号化には
+//! 常に有効な鍵を使い、[`FieldCipher::needs_reencryption`] で古い鍵の
+//! 暗号文を見つけて暗号化し直します）。
+//!
+//! 暗号化には行と列を表す文脈（例: `users.email:{user_id}`）を認証データとして
+//! 含めるため、暗号文を別の行や列に写しても復号できません。
+//!
+//! データ鍵は平文で保存せず、KMS などの鍵暗号化鍵（[`KeyWrapper`]）で
+//! 暗号化した [`WrappedDataKey`] として保存します（エンベロープ暗号化）。
+//!
+//! ```ignore
+//! let (_, wrapped) = generate_data_key(&kms, "2025-11").await?;
+//! // wrapped を保存する
+//! let cipher = FieldCipher::from_wrapped_keys(&kms, "2025-11", &stored_keys).await?;
+//! let email = cipher.encrypt(user.email.as_str(), format!("users.email:{user_id}").as_bytes())?;
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+use aes_gcm::{
+    Aes256Gcm,
+    Key,
+    Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::SecurityError;
+
+/// 暗号化したフィールドの接頭辞
+const ENCRYPTED_PREFIX: &str = "pii";
+
+/// AES-GCM のナンスの長さ
+const NONCE_LEN: usize = 12;
+
+/// データ鍵（AES-256）
+#[derive(Clone)]
+pub struct EncryptionKey([u8; 32]);
+
+impl EncryptionKey {
+    /// 新しい鍵を作る
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(Aes256Gcm::generate_key(OsRng).into())
+    }
+
+    /// バイト列から戻す
+    ///
+    /// # Errors
+    ///
+    /// 長さが 32 バイトでない場合はエラーを返します
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SecurityError> {
+        let key = bytes.try_into().map_err(|_| {
+            SecurityError::EncryptionError(format!(
+                "Invalid encryption key length: {}",
+                bytes.len()
+            ))
+        })?;
+        Ok(Self(key))
+    }
+
+    /// 鍵のバイト列
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.0))
+    }
+
+    /// `nonce || ciphertext` を返す
+    fn seal(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, SecurityError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            )
+            .map_err(|e| SecurityError::EncryptionError(e.to_string()))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend(ciphertext);
+        Ok(sealed)
+    }
+
+    fn open(&self, sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>, SecurityError> {
+        if sealed.len() < NONCE_LEN {
+            return Err(SecurityError::DecryptionError(
+                "Encrypted value is too short".to_string(),
+            ));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        self.cipher()
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|e| SecurityError::DecryptionError(e.to_string()))
+    }
+}
+
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EncryptionKey(..)")
+    }
+}
+
+/// 鍵 ID で鍵を選んでフィールドを暗号化・復号する
+#[derive(Debug, Clone)]
+pub struct FieldCipher {
+    active_key_id: String,
+    keys:          HashMap<String, EncryptionKey>,
+}
+
+impl FieldCipher {
+    /// `key` で暗号化する（`key_id` に `:` は使えない）
+    ///
+    /// # Errors
+    ///
+    /// 鍵 ID が空か `:` を含む場合はエラーを返します
+    pub fn new(key_id: impl Into<String>, key: EncryptionKey) -> Result<Self, SecurityError> {
+        let key_id = validate_key_id(key_id.into())?;
+        Ok(Self {
+            keys:          HashMap::from([(key_id.clone(), key)]),
+            active_key_id: key_id,
+        })
+    }
+
+    /// 復号にだけ使う古い鍵を追加する
+    ///
+    /// # Errors
+    ///
+    /// 鍵 ID が空か `:` を含む場合はエラーを返します
+    pub fn with_retired_key(
+        mut self,
+        key_id: impl Into<String>,
+        key: EncryptionKey,
+    ) -> Result<Self, SecurityError> {
+        let key_id = validate_key_id(key_id.into())?;
+        self.keys.entry(key_id).or_insert(key);
+        Ok(self)
+    }
+
+    /// 保存したデータ鍵を `wrapper` で復号して作る
+    ///
+    /// `active_key_id` の鍵で暗号化し、ほかの鍵は復号にだけ使う
+    ///
+    /// # Errors
+    ///
+    /// データ鍵を復号できない場合や、`active_key_id` の鍵がない場合は
+    /// エラーを返します
+    pub async fn from_wrapped_keys(
+        wrapper: &dyn KeyWrapper,
+        active_key_id: &str,
+        wrapped_keys: &[WrappedDataKey],
+    ) -> Result<Self, SecurityError> {
+        let mut keys = HashMap::with_capacity(wrapped_keys.len());
+        for wrapped in wrapped_keys {
+            let key = wrapper.unwrap_key(wrapped).await?;
+            keys.insert(validate_key_id(wrapped.key_id.clone())?, key);
+        }
+        if !keys.contains_key(active_key_id) {
+            return Err(SecurityError::EncryptionError(format!(
+                "Unknown active key id: {active_key_id}"
+            )));
+        }
+        Ok(Self {
+            active_key_id: active_key_id.to_string(),
+            keys,
+        })
+    }
+
+    /// 有効な鍵で暗号化する
+    ///
+    /// `context` には行と列を表す値（例: `users.email:{user_id}`）を渡す
+    ///
+    /// # Errors
+    ///
+    /// 暗号化に失敗した場合はエラーを返します
+    pub fn encrypt(&self, plaintext: &str, context: &[u8]) -> Result<String, SecurityError> {
+        let sealed = self.keys[&self.active_key_id].seal(plaintext.as_bytes(), context)?;
+        Ok(format!(
+            "{ENCRYPTED_PREFIX}:{}:{}",
+            self.active_key_id,
+            hex::encode(sealed)
+        ))
+    }
+
+    /// 暗号化したときと同じ `context` で復号する
+    ///
+    /// # Errors
+    ///
+    /// 暗号文の形式が不正な場合、鍵 ID の鍵がない場合、`context` が違うなど
+    /// 認証に失敗した場合はエラーを返します
+    pub fn decrypt(&self, encrypted: &str, context: &[u8]) -> Result<String, SecurityError> {
+        let (key_id, sealed) = split_encrypted(encrypted).ok_or_else(|| {
+            SecurityError::DecryptionError("Value is not an encrypted field".to_string())
+        })?;
+        let key = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| SecurityError::DecryptionError(format!("Unknown key id: {key_id}")))?;
+        let sealed =
+            hex::decode(sealed).map_err(|e| SecurityError::DecryptionError(e.to_string()))?;
+        let plaintext = key.open(&sealed, context)?;
+        String::from_utf8(plaintext).map_err(|e| SecurityError::DecryptionError(e.to_string()))
+    }
+
+    /// 有効な鍵以外で暗号化されているか（暗号化し直す対象か）
+    #[must_use]
+    pub fn needs_reencryption(&self, encrypted: &str) -> bool {
+        key_id_of(encrypted).is_some_and(|key_id| key_id != self.active_key_id)
+    }
+}
+
+/// 暗号文の鍵 ID（暗号化したフィールドでなければ None）
+#[must_use]
+pub fn key_id_of(encrypted: &str) -> Option<&str> {
+    split_encrypted(encrypted).map(|(key_id, _)| key_id)
+}
+
+fn split_encrypted(encrypted: &str) -> Option<(&str, &str)> {
+    let rest = encrypted
+        .strip_prefix(ENCRYPTED_PREFIX)?
+        .strip_prefix(':')?;
+    rest.split_once(':')
+        .filter(|(key_id, sealed)| !key_id.is_empty() && !sealed.is_empty())
+}
+
+fn validate_key_id(key_id: String) -> Result<String, SecurityError> {
+    if key_id.is_empty() || key_id.contains(':') {
+        return Err(SecurityError::EncryptionError(format!(
+            "Invalid key id: {key_id:?}"
+        )));
+    }
+    Ok(key_id)
+}
+
+/// 鍵暗号化鍵で暗号化したデータ鍵（保存する形）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WrappedDataKey {
+    /// データ鍵の ID（暗号文に含める ID）
+    pub key_id:      String,
+    /// 暗号化に使った鍵暗号化鍵の ID
+    pub wrapper_id:  String,
+    /// 暗号化したデータ鍵
+    pub wrapped_key: Vec<u8>,
+}
+
+/// データ鍵を暗号化する鍵暗号化鍵（KMS など）
+#[async_trait]
+pub trait KeyWrapper: Send + Sync {
+    /// 鍵暗号化鍵の ID
+    fn wrapper_id(&self) -> &str;
+
+    /// データ鍵を暗号化する
+    async fn wrap_key(
+        &self,
+        key_id: &str,
+        key: &EncryptionKey,
+    ) -> Result<WrappedDataKey, SecurityError>;
+
+    /// データ鍵を復号する
+    async fn unwrap_key(&self, wrapped: &WrappedDataKey) -> Result<EncryptionKey, SecurityError>;
+}
+
+/// 新しいデータ鍵を作り、`wrapper` で暗号化したものと一緒に返す
+///
+/// # Errors
+///
+/// データ鍵を暗号化できない場合はエラーを返します
+pub async fn generate_data_key(
+    wrapper: &dyn KeyWrapper,
+    key_id: &str,
+) -> Result<(EncryptionKey, WrappedDataKey), SecurityError> {
+    let key = EncryptionKey::generate();
+    let wrapped = wrapper.wrap_key(key_id, &key).await?;
+    Ok((key, wrapped))
+}
+
+/// 手元の鍵でデータ鍵を暗号化する（ローカル開発・テスト用）
+#[derive(Debug, Clone)]
+pub struct LocalKeyWrapper {
+    wrapper_id: String,
+    key:        EncryptionKey,
+}
+
+impl LocalKeyWrapper {
+    /// `key` でデータ鍵を暗号化する
+    #[must_use]
+    pub fn new(wrapper_id: impl Into<String>, key: EncryptionKey) -> Self {
+        Self {
+            wrapper_id: wrapper_id.into(),
+            key,
+        }
+    }
+}
+
+#[async_trait]
+impl KeyWrapper for LocalKeyWrapper {
+    fn wrapper_id(&self) -> &str {
+        &self.wrapper_id
+    }
+
+    async fn wrap_key(
+        &self,
+        key_id: &str,
+        key: &EncryptionKey,
+    ) -> Result<WrappedDataKey, SecurityError> {
+        // データ鍵の ID を認証に含め、別の ID の鍵と取り違えないようにする
+        let wrapped_key = self.key.seal(key.as_bytes(), key_id.as_bytes())?;
+        Ok(WrappedDataKey {
+            key_id: key_id.to_string(),
+            wrapper_id: self.wrapper_id.clone(),
+            wrapped_key,
+        })
+    }
+
+    async fn unwrap_key(&self, wrapped: &WrappedDataKey) -> Result<EncryptionKey, SecurityError> {
+        if wrapped.wrapper_id != self.wrapper_id {
+            return Err(SecurityError::DecryptionError(format!(
+                "Data key {} was wrapped by {}",
+                wrapped.key_id, wrapped.wrapper_id
+            )));
+        }
+        let key = self
+            .key
+            .open(&wrapped.wrapped_key, wrapped.key_id.as_bytes())?;
+        EncryptionKey::from_bytes(&key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_encrypts_fields_with_wrapped_data_keys_and_rotates() {
+        let kms = LocalKeyWrapper::new("local", EncryptionKey::generate());
+        let (_, old) = generate_data_key(&kms, "2025-10").await.unwrap();
+        let cipher = FieldCipher::from_wrapped_keys(&kms, "2025-10", std::slice::from_ref(&old))
+            .await
+            .unwrap();
+
+        let context = b"users.email:user-1";
+        let encrypted = cipher.encrypt("learner@example.com", context).unwrap();
+        assert!(encrypted.starts_with("pii:2025-10:"));
+        assert!(!encrypted.contains("learner"));
+        assert_eq!(
+            cipher.decrypt(&encrypted, context).unwrap(),
+            "learner@example.com"
+        );
+        // 別の行に写した暗号文は復号できない
+        assert!(matches!(
+            cipher.decrypt(&encrypted, b"users.email:user-2"),
+            Err(SecurityError::DecryptionError(_))
+        ));
+
+        // 鍵を入れ替えても古い暗号文を読め、暗号化し直す対象とわかる
+        let (_, new) = generate_data_key(&kms, "2025-11").await.unwrap();
+        let rotated = FieldCipher::from_wrapped_keys(&kms, "2025-11", &[old, new])
+            .await
+            .unwrap();
+        assert_eq!(
+            rotated.decrypt(&encrypted, context).unwrap(),
+            "learner@example.com"
+        );
+        assert!(rotated.needs_reencryption(&encrypted));
+        let reencrypted = rotated.encrypt("learner@example.com", context).unwrap();
+        assert_eq!(key_id_of(&reencrypted), Some("2025-11"));
+        assert!(!rotated.needs_reencryption(&reencrypted));
+
+        // 平文のままの値は暗号化したフィールドとして扱わない
+        assert!(!rotated.needs_reencryption("learner@example.com"));
+    }
+}
@@ -0,0 +1,295 @@
+//! gRPC サービスの認証
+//!
+//! `authorization: Bearer <JWT>` のトークンを検証し、[`Claims`] をリクエストの
+//! extensions に入れます。ハンドラーは [`authenticated_claims`]
+//! で取り出します。
+//!
+//! ```ignore
+//! let validator = Arc::new(SharedSecretValidator::new(jwt_secret));
+//! Server::builder()
+//!     .layer(AuthLayer::new(validator).with_public_prefix("/grpc.health.v1.Health/"))
+//!     .add_service(service)
+//!     .serve(addr)
+//!     .await?;
+//! ```
+//!
+//! JWKS や失効の確認など非同期の検証には [`AuthLayer`] を使います。
+//! 共有の署名鍵だけで検証するサービスは、同期の [`AuthInterceptor`] を
+//! `with_interceptor` に渡しても構いません。
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use tonic::{Request, Status, metadata::MetadataMap, service::Interceptor};
+use tower::{Layer, Service};
+
+use crate::{Claims, SecurityError, validate_jwt, validator::TokenValidator};
+
+impl From<SecurityError> for Status {
+    fn from(error: SecurityError) -> Self {
+        match error {
+            SecurityError::JwtValidationError(_)
+            | SecurityError::InvalidToken
+            | SecurityError::TokenRevoked
+            | SecurityError::InvalidApiKey
+            | SecurityError::InvalidTotpCode => Self::unauthenticated(error.to_string()),
+            // 鍵や失効の記録を取得できないのは一時的な障害
+            SecurityError::JwksFetchError(_)
+            | SecurityError::RevocationStoreError(_)
+            | SecurityError::ApiKeyStoreError(_) => Self::unavailable(error.to_string()),
+            SecurityError::HashingError(_)
+            | SecurityError::VerificationError
+            | SecurityError::JwtGenerationError(_)
+            | SecurityError::InvalidTotpSecret(_)
+            | SecurityError::EncryptionError(_)
+            | SecurityError::DecryptionError(_) => Self::internal(error.to_string()),
+        }
+    }
+}
+
+/// `authorization` ヘッダーの Bearer トークン
+fn bearer_token(authorization: Option<&str>) -> Result<&str, Status> {
+    authorization
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| Status::unauthenticated("Missing bearer token"))
+}
+
+/// 認証したリクエストのクレームを取り出す
+///
+/// # Errors
+///
+/// [`AuthLayer`] か [`AuthInterceptor`] を通っていないリクエストでは
+/// `Unauthenticated` を返します
+pub fn authenticated_claims<T>(request: &Request<T>) -> Result<&Claims, Status> {
+    request
+        .extensions()
+        .get::<Claims>()
+        .ok_or_else(|| Status::unauthenticated("Request is not authenticated"))
+}
+
+/// 共有の署名鍵でトークンを検証する interceptor
+#[derive(Clone)]
+pub struct AuthInterceptor {
+    secret: Arc<str>,
+}
+
+impl AuthInterceptor {
+    /// `secret` で検証する
+    #[must_use]
+    pub fn new(secret: impl Into<Arc<str>>) -> Self {
+        Self {
+            secret: secret.into(),
+        }
+    }
+
+    fn authenticate(&self, metadata: &MetadataMap) -> Result<Claims, Status> {
+        let authorization = metadata
+            .get("authorization")
+            .and_then(|value| value.to_str().ok());
+        let token = bearer_token(authorization)?;
+        validate_jwt(token, &self.secret).map_err(Status::from)
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let claims = self.authenticate(request.metadata())?;
+        request.extensions_mut().insert(claims);
+        Ok(request)
+    }
+}
+
+/// [`TokenValidator`] でトークンを検証する tower の layer
+#[derive(Clone)]
+pub struct AuthLayer {
+    validator:       Arc<dyn TokenValidator>,
+    public_prefixes: Arc<[String]>,
+}
+
+impl AuthLayer {
+    /// `validator` で検証する
+    #[must_use]
+    pub fn new(validator: Arc<dyn TokenValidator>) -> Self {
+        Self {
+            validator,
+            public_prefixes: Arc::new([]),
+        }
+    }
+
+    /// パスが `prefix` で始まるメソッドは認証せずに通す
+    /// （例: `/grpc.health.v1.Health/`）
+    #[must_use]
+    pub fn with_public_prefix(mut self, prefix: impl Into<String>) -> Self {
+        let mut prefixes = self.public_prefixes.to_vec();
+        prefixes.push(prefix.into());
+        self.public_prefixes = prefixes.into();
+        self
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = AuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// [`AuthLayer`] のサービス
+#[derive(Clone)]
+pub struct AuthService<S> {
+    inner: S,
+    layer: AuthLayer,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for AuthService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    ReqBody: Send + 'static,
+    ResBody: Default,
+{
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+    type Response = S::Response;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<ReqBody>) -> Self::Future {
+        // poll_ready を済ませたサービスで呼ぶため、複製と入れ替える
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let validator = self.layer.validator.clone();
+        let is_public = self
+            .layer
+            .public_prefixes
+            .iter()
+            .any(|prefix| request.uri().path().starts_with(prefix.as_str()));
+
+        Box::pin(async move {
+            if !is_public {
+                let authorization = request
+                    .headers()
+                    .get(http::header::AUTHORIZATION)
+                    .and_then(|value| value.to_str().ok());
+                let claims = match bearer_token(authorization) {
+                    Ok(token) => validator.validate(token).await.map_err(Status::from),
+                    Err(status) => Err(status),
+                };
+                match claims {
+                    Ok(claims) => {
+                        request.extensions_mut().insert(claims);
+                    },
+                    Err(status) => return Ok(status.into_http()),
+                }
+            }
+            inner.call(request).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use tower::{ServiceExt as _, service_fn};
+
+    use super::*;
+    use crate::{Role, generate_jwt, validator::SharedSecretValidator};
+
+    const SECRET: &str = "test_secret";
+
+    /// クレームのユーザー ID を `x-user-id` で返すサービス
+    async fn echo_user(request: http::Request<()>) -> Result<http::Response<String>, Infallible> {
+        let user = request
+            .extensions()
+            .get::<Claims>()
+            .map(|claims| claims.sub.clone())
+            .unwrap_or_default();
+        Ok(http::Response::builder()
+            .header("x-user-id", user)
+            .body(String::new())
+            .unwrap())
+    }
+
+    fn request(path: &str, authorization: Option<&str>) -> http::Request<()> {
+        let mut builder = http::Request::builder().uri(path);
+        if let Some(authorization) = authorization {
+            builder = builder.header("authorization", authorization);
+        }
+        builder.body(()).unwrap()
+    }
+
+    fn grpc_status(response: &http::Response<String>) -> Option<&str> {
+        response
+            .headers()
+            .get("grpc-status")
+            .and_then(|value| value.to_str().ok())
+    }
+
+    #[tokio::test]
+    async fn test_auth_layer_injects_claims_or_rejects() {
+        let layer = AuthLayer::new(Arc::new(SharedSecretValidator::new(SECRET)))
+            .with_public_prefix("/grpc.health.v1.Health/");
+        let service = layer.layer(service_fn(echo_user));
+        let token = generate_jwt("user123", Role::User.as_str(), SECRET, 1).unwrap();
+
+        let response = service
+            .clone()
+            .oneshot(request(
+                "/effect.services.Vocabulary/Get",
+                Some(&format!("Bearer {token}")),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["x-user-id"], "user123");
+
+        // トークンがない・不正なら Unauthenticated（16）を返し、サービスを呼ばない
+        for authorization in [None, Some("Bearer invalid")] {
+            let response = service
+                .clone()
+                .oneshot(request("/effect.services.Vocabulary/Get", authorization))
+                .await
+                .unwrap();
+            assert_eq!(grpc_status(&response), Some("16"));
+            assert!(response.headers().get("x-user-id").is_none());
+        }
+
+        // 公開したメソッドは認証しない
+        let response = service
+            .oneshot(request("/grpc.health.v1.Health/Check", None))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["x-user-id"], "");
+    }
+
+    #[test]
+    fn test_auth_interceptor_injects_claims() {
+        let mut interceptor = AuthInterceptor::new(SECRET);
+        let token = generate_jwt("user123", Role::Admin.as_str(), SECRET, 1).unwrap();
+
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert("authorization", format!("Bearer {token}").parse().unwrap());
+        let request = interceptor.call(request).unwrap();
+        assert!(
+            authenticated_claims(&request)
+                .unwrap()
+                .has_role(Role::Admin)
+        );
+
+        let status = interceptor.call(Request::new(())).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+    }
+}
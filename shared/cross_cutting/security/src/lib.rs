@@ -2,6 +2,16 @@
 //!
 //! 認証、暗号化、トークン生成など
 
+pub mod api_key;
+pub mod encryption;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod jwks;
+pub mod permission;
+pub mod revocation;
+pub mod totp;
+pub mod validator;
+
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use argon2::{
@@ -9,6 +19,7 @@ use argon2::{
     password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
 };
 use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+pub use permission::Permission;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -27,6 +38,33 @@ pub enum SecurityError {
     #[error("JWT validation failed: {0}")]
     JwtValidationError(String),
 
+    #[error("JWKS fetch failed: {0}")]
+    JwksFetchError(String),
+
+    #[error("Token has been revoked")]
+    TokenRevoked,
+
+    #[error("Revocation store error: {0}")]
+    RevocationStoreError(String),
+
+    #[error("Invalid API key")]
+    InvalidApiKey,
+
+    #[error("API key store error: {0}")]
+    ApiKeyStoreError(String),
+
+    #[error("Invalid TOTP secret: {0}")]
+    InvalidTotpSecret(String),
+
+    #[error("Invalid TOTP code")]
+    InvalidTotpCode,
+
+    #[error("Encryption failed: {0}")]
+    EncryptionError(String),
+
+    #[error("Decryption failed: {0}")]
+    DecryptionError(String),
+
     #[error("Invalid token")]
     InvalidToken,
 }
@@ -37,7 +75,57 @@ pub struct Claims {
     pub sub:  String, // Subject (user ID)
     pub exp:  u64,    // Expiration time
     pub iat:  u64,    // Issued at
-    pub role: String, // User role
+    #[serde(default = "default_role")]
+    pub role: String, // User role（Firebase のトークンにはないため既定は user）
+}
+
+fn default_role() -> String {
+    Role::User.as_str().to_string()
+}
+
+impl Claims {
+    /// ロールを持っているか
+    pub fn has_role(&self, role: Role) -> bool {
+        self.role == role.as_str()
+    }
+
+    /// クレームのロール（知らないロールなら None）
+    pub fn role(&self) -> Option<Role> {
+        [Role::User, Role::Moderator, Role::Admin]
+            .into_iter()
+            .find(|role| self.has_role(*role))
+    }
+
+    /// ロールが権限を持っているか（知らないロールは権限を持たない）
+    pub fn has(&self, permission: Permission) -> bool {
+        self.role().is_some_and(|role| role.grants(permission))
+    }
+}
+
+/// ユーザーロール（`Claims::role` に入る値）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    User,
+    Moderator,
+    Admin,
+}
+
+impl Role {
+    /// `Claims::role` に入る文字列
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Moderator => "moderator",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// パスワードをハッシュ化
@@ -125,4 +213,15 @@ mod tests {
         assert_eq!(claims.sub, user_id);
         assert_eq!(claims.role, role);
     }
+
+    #[test]
+    fn test_claims_role_check() {
+        let token = generate_jwt("user123", Role::Moderator.as_str(), "test_secret", 1).unwrap();
+        let claims = validate_jwt(&token, "test_secret").unwrap();
+
+        assert!(claims.has_role(Role::Moderator));
+        assert!(!claims.has_role(Role::Admin));
+        assert!(claims.has(Permission::VocabularyPublish));
+        assert!(!claims.has(Permission::UserManageRoles));
+    }
 }
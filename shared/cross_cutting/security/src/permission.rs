@@ -0,0 +1,126 @@
+//! 権限とロールの対応
+//!
+//! エンドポイントやコマンドはロールの文字列ではなく [`Permission`] を要求し、
+//! [`crate::Claims::has`] でクレームのロールが権限を持つかを確かめます。
+//! ロールに与える権限は [`Role::permissions`] にまとめて定義します。
+
+use serde::{Deserialize, Serialize};
+
+use crate::Role;
+
+/// 操作の権限（`{リソース}:{操作}`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Permission {
+    /// 語彙を読む
+    #[serde(rename = "vocabulary:read")]
+    VocabularyRead,
+    /// 語彙を作成・編集する
+    #[serde(rename = "vocabulary:write")]
+    VocabularyWrite,
+    /// 語彙を公開する（モデレーション）
+    #[serde(rename = "vocabulary:publish")]
+    VocabularyPublish,
+    /// 学習セッションを記録する
+    #[serde(rename = "learning:write")]
+    LearningWrite,
+    /// 学習の進捗を読む
+    #[serde(rename = "progress:read")]
+    ProgressRead,
+    /// ユーザーのロールを変更する
+    #[serde(rename = "user:manage_roles")]
+    UserManageRoles,
+    /// イベントストアの管理 RPC を呼ぶ
+    #[serde(rename = "event_store:admin")]
+    EventStoreAdmin,
+    /// 取り消せない管理操作を実行する
+    #[serde(rename = "admin:operations")]
+    AdminOperations,
+}
+
+impl Permission {
+    /// すべての権限
+    pub const ALL: [Self; 8] = [
+        Self::VocabularyRead,
+        Self::VocabularyWrite,
+        Self::VocabularyPublish,
+        Self::LearningWrite,
+        Self::ProgressRead,
+        Self::UserManageRoles,
+        Self::EventStoreAdmin,
+        Self::AdminOperations,
+    ];
+
+    /// 権限の文字列（例: `vocabulary:publish`）
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::VocabularyRead => "vocabulary:read",
+            Self::VocabularyWrite => "vocabulary:write",
+            Self::VocabularyPublish => "vocabulary:publish",
+            Self::LearningWrite => "learning:write",
+            Self::ProgressRead => "progress:read",
+            Self::UserManageRoles => "user:manage_roles",
+            Self::EventStoreAdmin => "event_store:admin",
+            Self::AdminOperations => "admin:operations",
+        }
+    }
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 学習者の権限
+const USER_PERMISSIONS: &[Permission] = &[
+    Permission::VocabularyRead,
+    Permission::VocabularyWrite,
+    Permission::LearningWrite,
+    Permission::ProgressRead,
+];
+
+/// モデレーターの権限（学習者の権限と語彙の公開）
+const MODERATOR_PERMISSIONS: &[Permission] = &[
+    Permission::VocabularyRead,
+    Permission::VocabularyWrite,
+    Permission::VocabularyPublish,
+    Permission::LearningWrite,
+    Permission::ProgressRead,
+];
+
+impl Role {
+    /// ロールに与える権限
+    pub const fn permissions(self) -> &'static [Permission] {
+        match self {
+            Self::User => USER_PERMISSIONS,
+            Self::Moderator => MODERATOR_PERMISSIONS,
+            Self::Admin => &Permission::ALL,
+        }
+    }
+
+    /// ロールが権限を持つか
+    pub fn grants(self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_grant_cumulative_permissions() {
+        assert!(!Role::User.grants(Permission::VocabularyPublish));
+        assert!(Role::Moderator.grants(Permission::VocabularyPublish));
+        assert!(!Role::Moderator.grants(Permission::UserManageRoles));
+        assert!(Permission::ALL.iter().all(|p| Role::Admin.grants(*p)));
+
+        // 下位のロールの権限は上位のロールも持つ
+        assert!(USER_PERMISSIONS.iter().all(|p| Role::Moderator.grants(*p)));
+
+        assert_eq!(
+            serde_json::to_string(&Permission::VocabularyPublish).unwrap(),
+            "\"vocabulary:publish\""
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! トークンの失効
+//!
+//! JWT は有効期限まで検証を通るため、サインアウト（`UserSignedOut`）や
+//! 退会（`UserDeleted`）の後も発行済みのトークンを使えてしまいます。
+//! イベントを受けたら [`RevocationStore::revoke_user`] でユーザーの失効時刻を
+//! 記録し、検証では [`validate_jwt_with_revocation`] を使うことで、失効時刻
+//! より前に発行された（`iat` が失効時刻より小さい）トークンを拒否します。
+//!
+//! `iat` は秒単位のため、失効と同じ秒に発行したトークンは受け入れます。
+//! 失効の直後に再発行したトークンを拒否しないためで、失効した側の
+//! トークンもその秒の間に発行されていれば使えることになります。
+//!
+//! 失効の記録は、その時点で発行済みのトークンがすべて期限切れになれば
+//! 不要になるため、保存先はトークンの最長の有効期間が過ぎたら消してかまいません。
+
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "redis")]
+mod redis;
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+#[cfg(feature = "postgres")]
+pub use self::postgres::PostgresRevocationStore;
+#[cfg(feature = "redis")]
+pub use self::redis::RedisRevocationStore;
+use crate::{Claims, SecurityError, validate_jwt};
+
+/// 失効の記録の保存先
+#[async_trait]
+pub trait RevocationStore: Send + Sync {
+    /// `user_id` に `revoked_at`（UNIX 秒）より前に発行したトークンを失効させる
+    ///
+    /// 記録は `ttl` が過ぎたら消してよい。すでにより新しい失効時刻が
+    /// 記録されている場合はそちらを残す
+    async fn revoke_user(
+        &self,
+        user_id: &str,
+        revoked_at: u64,
+        ttl: Duration,
+    ) -> Result<(), SecurityError>;
+
+    /// `user_id` の失効時刻（UNIX 秒）
+    async fn revoked_at(&self, user_id: &str) -> Result<Option<u64>, SecurityError>;
+}
+
+/// クレームのトークンが失効していないか確かめる
+///
+/// [`crate::jwks::JwksValidator`] で検証したクレームにも使えます。
+///
+/// # Errors
+///
+/// トークンが失効している場合や、失効の記録を読めない場合はエラーを返します
+pub async fn ensure_not_revoked(
+    claims: &Claims,
+    store: &dyn RevocationStore,
+) -> Result<(), SecurityError> {
+    match store.revoked_at(&claims.sub).await? {
+        // 失効と同じ秒に発行したトークンは、失効後に再発行したものとみなす
+        Some(revoked_at) if claims.iat < revoked_at => Err(SecurityError::TokenRevoked),
+        _ => Ok(()),
+    }
+}
+
+/// JWT トークンを検証し、失効していないか確かめる
+///
+/// # Errors
+///
+/// トークンが不正な場合や失効している場合、失効の記録を読めない場合は
+/// エラーを返します
+pub async fn validate_jwt_with_revocation(
+    token: &str,
+    secret: &str,
+    store: &dyn RevocationStore,
+) -> Result<Claims, SecurityError> {
+    let claims = validate_jwt(token, secret)?;
+    ensure_not_revoked(&claims, store).await?;
+    Ok(claims)
+}
+
+/// メモリ上に失効を記録する（ローカル開発・テスト用）
+#[derive(Debug, Default)]
+pub struct InMemoryRevocationStore {
+    /// ユーザー ID → (失効時刻, 記録を消す時刻)
+    revocations: Mutex<HashMap<String, (u64, Instant)>>,
+}
+
+impl InMemoryRevocationStore {
+    /// 空の保存先を作成
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RevocationStore for InMemoryRevocationStore {
+    async fn revoke_user(
+        &self,
+        user_id: &str,
+        revoked_at: u64,
+        ttl: Duration,
+    ) -> Result<(), SecurityError> {
+        let expires_at = Instant::now() + ttl;
+        let mut revocations = self
+            .revocations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let entry = revocations
+            .entry(user_id.to_string())
+            .or_insert((revoked_at, expires_at));
+        if revoked_at >= entry.0 {
+            *entry = (revoked_at, expires_at);
+        }
+        Ok(())
+    }
+
+    async fn revoked_at(&self, user_id: &str) -> Result<Option<u64>, SecurityError> {
+        let mut revocations = self
+            .revocations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match revocations.get(user_id) {
+            Some(&(_, expires_at)) if expires_at <= Instant::now() => {
+                revocations.remove(user_id);
+                Ok(None)
+            },
+            Some(&(revoked_at, _)) => Ok(Some(revoked_at)),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_jwt;
+
+    #[tokio::test]
+    async fn test_revoked_users_tokens_are_rejected_until_reissued() {
+        let store = InMemoryRevocationStore::new();
+        let token = generate_jwt("user123", "user", "test_secret", 1).unwrap();
+        let claims = validate_jwt_with_revocation(&token, "test_secret", &store)
+            .await
+            .unwrap();
+
+        // サインアウトした時刻より前に発行したトークンは使えない
+        store
+            .revoke_user("user123", claims.iat + 1, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        assert!(matches!(
+            validate_jwt_with_revocation(&token, "test_secret", &store).await,
+            Err(SecurityError::TokenRevoked)
+        ));
+
+        // 古い失効時刻で上書きしても、新しい失効時刻が残る
+        store
+            .revoke_user("user123", claims.iat - 60, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.revoked_at("user123").await.unwrap(),
+            Some(claims.iat + 1)
+        );
+
+        // サインアウト後に発行したトークンは使える
+        let reissued = Claims {
+            iat: claims.iat + 1,
+            ..claims
+        };
+        ensure_not_revoked(&reissued, &store).await.unwrap();
+
+        // 記録の期限が過ぎたら消える
+        store
+            .revoke_user("user456", claims.iat, Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(store.revoked_at("user456").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_token_issued_in_the_revocation_second_is_accepted() {
+        let store = InMemoryRevocationStore::new();
+        let token = generate_jwt("user123", "user", "test_secret", 1).unwrap();
+        let claims = validate_jwt(&token, "test_secret").unwrap();
+        store
+            .revoke_user("user123", claims.iat, Duration::from_secs(3600))
+            .await
+            .unwrap();
+
+        // 失効と同じ秒に再発行したトークンは使える
+        ensure_not_revoked(&claims, &store).await.unwrap();
+
+        // 1 秒前に発行したトークンは使えない
+        let previous = Claims {
+            iat: claims.iat - 1,
+            ..claims
+        };
+        assert!(matches!(
+            ensure_not_revoked(&previous, &store).await,
+            Err(SecurityError::TokenRevoked)
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+//! `revoked_user_tokens` テーブルを使う失効の保存先
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use super::RevocationStore;
+use crate::SecurityError;
+
+/// `revoked_user_tokens` テーブルに失効時刻を保存する
+///
+/// テーブルは `migrations/` のマイグレーションで作る。期限の過ぎた行は
+/// 読み取りでは無視し、[`PostgresRevocationStore::purge_expired`] で消す
+#[allow(clippy::module_name_repetitions)]
+pub struct PostgresRevocationStore {
+    pool: PgPool,
+}
+
+impl PostgresRevocationStore {
+    /// 接続プールを指定して作る
+    #[must_use]
+    pub const fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 期限の過ぎた記録を消し、消した件数を返す
+    ///
+    /// # Errors
+    ///
+    /// データベースの操作に失敗した場合はエラーを返す
+    pub async fn purge_expired(&self) -> Result<u64, SecurityError> {
+        sqlx::query("DELETE FROM revoked_user_tokens WHERE expires_at <= NOW()")
+            .execute(&self.pool)
+            .await
+            .map(|result| result.rows_affected())
+            .map_err(store_error)
+    }
+}
+
+fn store_error(error: sqlx::Error) -> SecurityError {
+    SecurityError::RevocationStoreError(error.to_string())
+}
+
+#[async_trait]
+impl RevocationStore for PostgresRevocationStore {
+    async fn revoke_user(
+        &self,
+        user_id: &str,
+        revoked_at: u64,
+        ttl: Duration,
+    ) -> Result<(), SecurityError> {
+        let revoked_at = i64::try_from(revoked_at)
+            .map_err(|e| SecurityError::RevocationStoreError(e.to_string()))?;
+        let ttl_seconds = i64::try_from(ttl.as_secs()).unwrap_or(i64::MAX);
+        sqlx::query(
+            r"
+            INSERT INTO revoked_user_tokens (user_id, revoked_at, expires_at)
+            VALUES ($1, $2, NOW() + $3 * INTERVAL '1 second')
+            ON CONFLICT (user_id) DO UPDATE SET
+                revoked_at = EXCLUDED.revoked_at,
+                expires_at = EXCLUDED.expires_at
+            WHERE revoked_user_tokens.revoked_at <= EXCLUDED.revoked_at
+                OR revoked_user_tokens.expires_at <= NOW()
+            ",
+        )
+        .bind(user_id)
+        .bind(revoked_at)
+        .bind(ttl_seconds)
+        .execute(&self.pool)
+        .await
+        .map_err(store_error)?;
+        Ok(())
+    }
+
+    async fn revoked_at(&self, user_id: &str) -> Result<Option<u64>, SecurityError> {
+        let revoked_at: Option<i64> = sqlx::query_scalar(
+            "SELECT revoked_at FROM revoked_user_tokens WHERE user_id = $1 AND expires_at > NOW()",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(store_error)?;
+        Ok(revoked_at.and_then(|at| u64::try_from(at).ok()))
+    }
+}
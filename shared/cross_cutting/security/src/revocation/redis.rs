@@ -0,0 +1,79 @@
+//! Redis による [`RevocationStore`] 実装
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use redis::{AsyncCommands, aio::ConnectionManager};
+
+use super::RevocationStore;
+use crate::SecurityError;
+
+/// 失効時刻を `revoked_user:{user_id}` に TTL 付きで保存する
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone)]
+pub struct RedisRevocationStore {
+    conn: ConnectionManager,
+}
+
+impl RedisRevocationStore {
+    /// Redis に接続する
+    ///
+    /// # Errors
+    ///
+    /// URL が不正な場合や接続に失敗した場合はエラーを返す
+    pub async fn connect(redis_url: &str) -> Result<Self, SecurityError> {
+        let client = redis::Client::open(redis_url)
+            .map_err(|e| SecurityError::RevocationStoreError(format!("Invalid Redis URL: {e}")))?;
+        let conn = ConnectionManager::new(client)
+            .await
+            .map_err(|e| SecurityError::RevocationStoreError(e.to_string()))?;
+        Ok(Self { conn })
+    }
+
+    /// 接続を指定して作る
+    #[must_use]
+    pub const fn new(conn: ConnectionManager) -> Self {
+        Self { conn }
+    }
+
+    fn key(user_id: &str) -> String {
+        format!("revoked_user:{user_id}")
+    }
+}
+
+#[async_trait]
+impl RevocationStore for RedisRevocationStore {
+    async fn revoke_user(
+        &self,
+        user_id: &str,
+        revoked_at: u64,
+        ttl: Duration,
+    ) -> Result<(), SecurityError> {
+        // より新しい失効時刻があれば残す（比較と書き込みを1回で行う）
+        let script = redis::Script::new(
+            r"
+            local current = tonumber(redis.call('GET', KEYS[1]))
+            if current == nil or current <= tonumber(ARGV[1]) then
+                redis.call('SET', KEYS[1], ARGV[1], 'EX', ARGV[2])
+            end
+            return 1
+            ",
+        );
+        let mut conn = self.conn.clone();
+        script
+            .key(Self::key(user_id))
+            .arg(revoked_at)
+            .arg(ttl.as_secs().max(1))
+            .invoke_async::<i64>(&mut conn)
+            .await
+            .map(|_| ())
+            .map_err(|e| SecurityError::RevocationStoreError(e.to_string()))
+    }
+
+    async fn revoked_at(&self, user_id: &str) -> Result<Option<u64>, SecurityError> {
+        let mut conn = self.conn.clone();
+        conn.get(Self::key(user_id))
+            .await
+            .map_err(|e| SecurityError::RevocationStoreError(e.to_string()))
+    }
+}
@@ -0,0 +1,217 @@
+//! TOTP による2段階認証（RFC 6238）
+//!
+//! 管理者アカウントの2段階認証に使います。[`TotpSecret::generate`] で作った
+//! 秘密鍵を [`provisioning_uri`] の QR コードで認証アプリに登録してもらい、
+//! サインインのたびに [`verify_totp`] でコードを確かめます。
+//!
+//! 認証アプリとの互換性のため、HMAC-SHA1・6桁・30秒の既定の設定に固定します。
+//! 端末の時計のずれを許すため前後 [`TOTP_ALLOWED_DRIFT_STEPS`] 区間のコードも
+//! 受け付けますが、同じコードを2度使えないよう、最後に受け付けた区間以前の
+//! コードは拒否します。
+
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use rand::RngCore;
+use sha1::Sha1;
+use subtle::ConstantTimeEq;
+
+use crate::SecurityError;
+
+/// コードの桁数
+pub const TOTP_DIGITS: u32 = 6;
+/// コードが切り替わる間隔（秒）
+pub const TOTP_PERIOD_SECS: u64 = 30;
+/// 前後に許す区間の数（時計のずれ）
+pub const TOTP_ALLOWED_DRIFT_STEPS: u64 = 1;
+/// 秘密鍵の長さ（RFC 4226 の推奨する 160 ビット）
+const SECRET_LEN: usize = 20;
+
+/// TOTP の秘密鍵
+#[derive(Clone, PartialEq, Eq)]
+pub struct TotpSecret(Vec<u8>);
+
+impl TotpSecret {
+    /// ランダムな秘密鍵を作成
+    #[must_use]
+    pub fn generate() -> Self {
+        let mut bytes = vec![0; SECRET_LEN];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    /// Base32 の文字列から読み込む（認証アプリと同じく空白と大小文字は無視する）
+    ///
+    /// # Errors
+    ///
+    /// Base32 として読めない場合はエラーを返します
+    pub fn from_base32(encoded: &str) -> Result<Self, SecurityError> {
+        let normalized: String = encoded
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '=')
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        BASE32_NOPAD
+            .decode(normalized.as_bytes())
+            .map(Self)
+            .map_err(|e| SecurityError::InvalidTotpSecret(e.to_string()))
+    }
+
+    /// Base32 の文字列（保存や手入力に使う）
+    #[must_use]
+    pub fn to_base32(&self) -> String {
+        BASE32_NOPAD.encode(&self.0)
+    }
+}
+
+impl std::fmt::Debug for TotpSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // 秘密鍵はログに出さない
+        f.debug_tuple("TotpSecret").finish_non_exhaustive()
+    }
+}
+
+/// 認証アプリに登録する `otpauth://` の URI（QR コードにして表示する）
+///
+/// `issuer` はアプリに表示するサービス名、`account_name` はメールアドレスなど
+#[must_use]
+pub fn provisioning_uri(secret: &TotpSecret, issuer: &str, account_name: &str) -> String {
+    let issuer = utf8_percent_encode(issuer, NON_ALPHANUMERIC).to_string();
+    let account_name = utf8_percent_encode(account_name, NON_ALPHANUMERIC);
+    format!(
+        "otpauth://totp/{issuer}:{account_name}?secret={secret}&issuer={issuer}&algorithm=SHA1&\
+         digits={TOTP_DIGITS}&period={TOTP_PERIOD_SECS}",
+        secret = secret.to_base32(),
+    )
+}
+
+/// 区間 `step` のコード
+fn code_at_step(secret: &TotpSecret, step: u64) -> Result<String, SecurityError> {
+    let mut mac = Hmac::<Sha1>::new_from_slice(&secret.0)
+        .map_err(|e| SecurityError::InvalidTotpSecret(e.to_string()))?;
+    mac.update(&step.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    // RFC 4226 の dynamic truncation
+    let offset = usize::from(digest[digest.len() - 1] & 0x0f);
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    Ok(format!(
+        "{:0width$}",
+        binary % 10_u32.pow(TOTP_DIGITS),
+        width = TOTP_DIGITS as usize
+    ))
+}
+
+/// 時刻 `unix_time`（UNIX 秒）のコード
+///
+/// # Errors
+///
+/// 秘密鍵を HMAC の鍵にできない場合は [`SecurityError::InvalidTotpSecret`]
+/// を返します
+pub fn generate_totp(secret: &TotpSecret, unix_time: u64) -> Result<String, SecurityError> {
+    code_at_step(secret, unix_time / TOTP_PERIOD_SECS)
+}
+
+/// コードを検証し、受け付けた区間を返す
+///
+/// 返した区間はユーザーごとに保存し、次の検証で `last_used_step` に渡すことで
+/// 同じコードの再利用を防ぎます。
+///
+/// # Errors
+///
+/// 時計のずれを許しても一致しない場合や、`last_used_step` 以前の区間の
+/// コードの場合は [`SecurityError::InvalidTotpCode`] を、秘密鍵を HMAC の鍵に
+/// できない場合は [`SecurityError::InvalidTotpSecret`] を返します
+pub fn verify_totp(
+    secret: &TotpSecret,
+    code: &str,
+    unix_time: u64,
+    last_used_step: Option<u64>,
+) -> Result<u64, SecurityError> {
+    let code = code.trim();
+    let current = unix_time / TOTP_PERIOD_SECS;
+    let earliest = current.saturating_sub(TOTP_ALLOWED_DRIFT_STEPS);
+    let latest = current + TOTP_ALLOWED_DRIFT_STEPS;
+
+    // 一致した区間で打ち切らず、すべての区間を比べる
+    let mut matched = None;
+    for step in earliest..=latest {
+        let is_match: bool = code_at_step(secret, step)?
+            .as_bytes()
+            .ct_eq(code.as_bytes())
+            .into();
+        if is_match && last_used_step.is_none_or(|last| step > last) {
+            matched = Some(step);
+        }
+    }
+    matched.ok_or(SecurityError::InvalidTotpCode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 6238 の付録 B の SHA1 の鍵
+    fn rfc_secret() -> TotpSecret {
+        TotpSecret(b"12345678901234567890".to_vec())
+    }
+
+    #[test]
+    fn test_generates_rfc_6238_codes() {
+        // 付録 B の 8 桁のコードの下 6 桁
+        assert_eq!(generate_totp(&rfc_secret(), 59).unwrap(), "287082");
+        assert_eq!(
+            generate_totp(&rfc_secret(), 1_111_111_109).unwrap(),
+            "081804"
+        );
+        assert_eq!(
+            generate_totp(&rfc_secret(), 2_000_000_000).unwrap(),
+            "279037"
+        );
+
+        let secret = TotpSecret::generate();
+        let encoded = secret.to_base32();
+        assert_eq!(
+            TotpSecret::from_base32(&encoded.to_lowercase()).unwrap(),
+            secret
+        );
+        assert_eq!(
+            provisioning_uri(&rfc_secret(), "Effect", "admin@example.com"),
+            "otpauth://totp/Effect:admin%40example%2Ecom?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&\
+             issuer=Effect&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[test]
+    fn test_verification_tolerates_drift_and_rejects_reuse() {
+        let secret = rfc_secret();
+        let now = 1_111_111_109;
+        let step = now / TOTP_PERIOD_SECS;
+
+        // 1区間前後のずれは受け付ける
+        let previous = generate_totp(&secret, now - TOTP_PERIOD_SECS).unwrap();
+        assert_eq!(
+            verify_totp(&secret, &previous, now, None).unwrap(),
+            step - 1
+        );
+        let next = generate_totp(&secret, now + TOTP_PERIOD_SECS).unwrap();
+        assert_eq!(verify_totp(&secret, &next, now, None).unwrap(), step + 1);
+
+        // 2区間以上のずれは受け付けない
+        let stale = generate_totp(&secret, now - 2 * TOTP_PERIOD_SECS).unwrap();
+        assert!(verify_totp(&secret, &stale, now, None).is_err());
+
+        // 一度使った区間のコードは使えない
+        let current = generate_totp(&secret, now).unwrap();
+        let used = verify_totp(&secret, &current, now, None).unwrap();
+        assert!(matches!(
+            verify_totp(&secret, &current, now, Some(used)),
+            Err(SecurityError::InvalidTotpCode)
+        ));
+    }
+}
@@ -0,0 +1,87 @@
+//! トークンの検証器
+//!
+//! gRPC の認証（[`crate::grpc`]）などは検証の方法を知らずに
+//! [`TokenValidator`] でトークンをクレームにします。共有の署名鍵（HS256）なら
+//! [`SharedSecretValidator`]、Firebase などの公開鍵なら
+//! [`crate::jwks::JwksValidator`] を使い、失効も確かめる場合は
+//! [`RevocationCheckingValidator`] で包みます。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    Claims,
+    SecurityError,
+    jwks::JwksValidator,
+    revocation::{RevocationStore, ensure_not_revoked},
+    validate_jwt,
+};
+
+/// トークンを検証してクレームを返す
+#[async_trait]
+pub trait TokenValidator: Send + Sync {
+    /// `token` を検証してクレームを返す
+    async fn validate(&self, token: &str) -> Result<Claims, SecurityError>;
+}
+
+/// 共有の署名鍵で検証する（HS256）
+#[derive(Clone)]
+pub struct SharedSecretValidator {
+    secret: Arc<str>,
+}
+
+impl SharedSecretValidator {
+    /// `secret` で検証する
+    #[must_use]
+    pub fn new(secret: impl Into<Arc<str>>) -> Self {
+        Self {
+            secret: secret.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for SharedSecretValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // 署名鍵はログに出さない
+        f.debug_struct("SharedSecretValidator")
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl TokenValidator for SharedSecretValidator {
+    async fn validate(&self, token: &str) -> Result<Claims, SecurityError> {
+        validate_jwt(token, &self.secret)
+    }
+}
+
+#[async_trait]
+impl TokenValidator for JwksValidator {
+    async fn validate(&self, token: &str) -> Result<Claims, SecurityError> {
+        Self::validate(self, token).await
+    }
+}
+
+/// 検証したトークンが失効していないかも確かめる
+pub struct RevocationCheckingValidator<V> {
+    inner: V,
+    store: Arc<dyn RevocationStore>,
+}
+
+impl<V> RevocationCheckingValidator<V> {
+    /// `inner` で検証したトークンの失効を `store` で確かめる
+    #[must_use]
+    pub fn new(inner: V, store: Arc<dyn RevocationStore>) -> Self {
+        Self { inner, store }
+    }
+}
+
+#[async_trait]
+impl<V: TokenValidator> TokenValidator for RevocationCheckingValidator<V> {
+    async fn validate(&self, token: &str) -> Result<Claims, SecurityError> {
+        let claims = self.inner.validate(token).await?;
+        ensure_not_revoked(&claims, self.store.as_ref()).await?;
+        Ok(claims)
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! 全マイクロサービスで共通のテレメトリ設定
 
+pub mod metrics;
+
 use opentelemetry::{KeyValue, trace::TracerProvider as _};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::{Resource, trace::Tracer};
@@ -27,8 +29,24 @@ pub fn init_telemetry(
 
         let provider = opentelemetry_sdk::trace::TracerProvider::builder()
             .with_batch_exporter(exporter, runtime::Tokio)
-            .with_resource(resource)
+            .with_resource(resource.clone())
             .build();
+
+        // メトリクスも同じエンドポイントへ定期的にエクスポートする
+        let metric_exporter = opentelemetry_otlp::MetricExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()?;
+        let reader =
+            opentelemetry_sdk::metrics::PeriodicReader::builder(metric_exporter, runtime::Tokio)
+                .build();
+        opentelemetry::global::set_meter_provider(
+            opentelemetry_sdk::metrics::SdkMeterProvider::builder()
+                .with_reader(reader)
+                .with_resource(resource)
+                .build(),
+        );
+
         provider.tracer(service_name.to_string())
     } else {
         // ローカル開発用のトレーサー
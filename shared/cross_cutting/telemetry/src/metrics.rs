@@ -0,0 +1,24 @@
+//! メトリクス
+//!
+//! 各クレートは [`meter`]
+//! で自分の名前のメーターを取得し、計器を作って記録する。
+//! [`init_telemetry`](crate::init_telemetry) で OTLP のエンドポイントを指定した
+//! 場合は定期的にエクスポートし、指定しない場合（ローカル開発・テスト）は
+//! 記録しても何もしない。
+//!
+//! ```ignore
+//! let conflicts = shared_telemetry::metrics::meter("shared_event_store")
+//!     .u64_counter("event_store.append.conflicts")
+//!     .build();
+//! conflicts.add(1, &[KeyValue::new("aggregate_type", "vocabulary_item")]);
+//! ```
+
+pub use opentelemetry::{
+    KeyValue,
+    metrics::{Counter, Gauge, Histogram, Meter},
+};
+
+/// 名前（計測するクレート名）を指定してメーターを取得
+pub fn meter(name: &'static str) -> Meter {
+    opentelemetry::global::meter(name)
+}
@@ -0,0 +1,142 @@
+//! 管理操作の監査ログ
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared_event_store::{EventStore, EventStoreError};
+use uuid::Uuid;
+
+use crate::error::{AdminOpsError, Result};
+
+/// 監査用ストリームの集約 ID（全操作で1本のストリームを使う）
+pub const ADMIN_AUDIT_STREAM_ID: Uuid = Uuid::from_u128(0x0000_0000_0000_4000_8000_ad01_0000_0001);
+
+/// 監査用ストリームの集約タイプ
+pub const ADMIN_AUDIT_AGGREGATE_TYPE: &str = "AdminOperation";
+
+/// 確認トークンの使用を記録するストリームの集約タイプ
+///
+/// トークン ID をそのまま集約 ID にし、トークンごとに1本のストリームを使う
+pub const CONFIRMATION_TOKEN_AGGREGATE_TYPE: &str = "AdminConfirmationToken";
+
+/// 操作の結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum OperationOutcome {
+    Succeeded { summary: String },
+    Failed { error: String },
+}
+
+/// 管理操作を実行した
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminOperationExecuted {
+    pub event_id:      Uuid,
+    pub occurred_at:   DateTime<Utc>,
+    /// 認証コンテキストの操作者
+    pub operator_id:   String,
+    pub operator_role: String,
+    pub operation:     String,
+    pub target:        String,
+    pub plan_hash:     String,
+    /// 使用した確認トークンの ID（再利用の検出に使う）
+    pub token_id:      String,
+    pub outcome:       OperationOutcome,
+}
+
+impl AdminOperationExecuted {
+    pub const EVENT_TYPE: &'static str = "AdminOperationExecuted";
+}
+
+/// 確認トークンを使った（操作の実行前に記録する）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfirmationTokenConsumed {
+    pub event_id:    Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub token_id:    String,
+    pub operator_id: String,
+}
+
+impl ConfirmationTokenConsumed {
+    pub const EVENT_TYPE: &'static str = "ConfirmationTokenConsumed";
+}
+
+/// 監査ログの書き込み先
+#[async_trait]
+pub trait AuditLog: Send + Sync {
+    async fn append(&self, event: &AdminOperationExecuted) -> Result<()>;
+
+    /// 確認トークンを使用済みにする
+    ///
+    /// 同じトークンで同時に呼ばれても成功するのは1回だけで、ほかは
+    /// [`AdminOpsError::TokenAlreadyUsed`] を返す
+    async fn consume_token(&self, event: &ConfirmationTokenConsumed) -> Result<()>;
+}
+
+/// Event Store の監査用ストリームに追記する
+pub struct EventStoreAuditLog {
+    store: Arc<dyn EventStore>,
+}
+
+impl EventStoreAuditLog {
+    pub fn new(store: Arc<dyn EventStore>) -> Self {
+        Self { store }
+    }
+
+    /// 監査用ストリームのイベント（古い順）
+    pub async fn events(&self) -> Result<Vec<AdminOperationExecuted>> {
+        let stored = self
+            .store
+            .load_events(ADMIN_AUDIT_STREAM_ID, ADMIN_AUDIT_AGGREGATE_TYPE, None)
+            .await?;
+
+        stored
+            .into_iter()
+            .filter(|event| event.event_type == AdminOperationExecuted::EVENT_TYPE)
+            .map(|event| Ok(serde_json::from_value(event.event_data)?))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl AuditLog for EventStoreAuditLog {
+    async fn append(&self, event: &AdminOperationExecuted) -> Result<()> {
+        // Event Store はイベントデータの event_type と occurred_at を使う
+        let mut data = serde_json::to_value(event)?;
+        data["event_type"] = AdminOperationExecuted::EVENT_TYPE.into();
+
+        self.store
+            .save_events(
+                ADMIN_AUDIT_STREAM_ID,
+                ADMIN_AUDIT_AGGREGATE_TYPE,
+                vec![data],
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn consume_token(&self, event: &ConfirmationTokenConsumed) -> Result<()> {
+        let token_stream_id =
+            Uuid::parse_str(&event.token_id).map_err(|_| AdminOpsError::InvalidToken)?;
+        let mut data = serde_json::to_value(event)?;
+        data["event_type"] = ConfirmationTokenConsumed::EVENT_TYPE.into();
+
+        // 空のストリームへの追記だけを許し、2回目以降は競合として拒否する
+        match self
+            .store
+            .save_events(
+                token_stream_id,
+                CONFIRMATION_TOKEN_AGGREGATE_TYPE,
+                vec![data],
+                Some(0),
+            )
+            .await
+        {
+            Ok(()) => Ok(()),
+            Err(EventStoreError::VersionConflict { .. }) => Err(AdminOpsError::TokenAlreadyUsed),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
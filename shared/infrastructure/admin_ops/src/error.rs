@@ -0,0 +1,38 @@
+use shared_event_store::EventStoreError;
+use thiserror::Error;
+
+/// 管理操作のエラー
+#[derive(Debug, Error)]
+pub enum AdminOpsError {
+    /// 形式が不正、または署名が一致しない
+    #[error("Invalid confirmation token")]
+    InvalidToken,
+
+    #[error("Confirmation token expired")]
+    TokenExpired,
+
+    #[error("Confirmation token has already been used")]
+    TokenAlreadyUsed,
+
+    /// トークン発行後に計画が変わった（ドライランからやり直す）
+    #[error(
+        "Operation plan changed since the token was issued (issued for {issued}, now {current})"
+    )]
+    PlanChanged { issued: String, current: String },
+
+    /// 管理者ではない
+    #[error("Operator is not authorized: {0}")]
+    Unauthorized(String),
+
+    /// 操作自体の失敗
+    #[error("Operation failed: {0}")]
+    Operation(String),
+
+    #[error("Audit log error: {0}")]
+    Audit(#[from] EventStoreError),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, AdminOpsError>;
@@ -0,0 +1,42 @@
+//! 破壊的な管理操作の共通プロトコル
+//!
+//! 再構築や削除など取り消せない管理操作は、次の2段階で実行する。
+//!
+//! 1. ドライラン: 操作の計画（[`OperationPlan`]）を作り、計画のハッシュに対する
+//!    確認トークン（HMAC、有効期限付き、1回限り）を発行する
+//! 2. 実行: 確認トークンを必ず受け取り、計画を作り直してトークン発行時から
+//!    変わっていないことを確かめてから実行する
+//!
+//! 確認トークンは実行の前に Event Store のトークンごとのストリームへ
+//! [`ConfirmationTokenConsumed`] を追記して使用済みにし、同じトークンでの
+//! 同時の実行は1つだけを通す。
+//!
+//! 実行した操作は成否にかかわらず [`AdminOperationExecuted`] を Event Store の
+//! 監査用ストリームに追記する。
+//!
+//! このプロトコルで実行する操作は次のとおり。破壊的な管理操作を追加する
+//! 場合は [`AdminOperation`] を実装し、直接実行する経路を作らない。
+//!
+//! - `effect-admin snapshots rebuild` と `effect-admin projection rebuild`
+//! - Event Store Service の `DeleteStream` RPC
+
+pub mod audit;
+pub mod error;
+pub mod operation;
+pub mod plan;
+pub mod token;
+
+pub use audit::{
+    ADMIN_AUDIT_AGGREGATE_TYPE,
+    ADMIN_AUDIT_STREAM_ID,
+    AdminOperationExecuted,
+    AuditLog,
+    CONFIRMATION_TOKEN_AGGREGATE_TYPE,
+    ConfirmationTokenConsumed,
+    EventStoreAuditLog,
+    OperationOutcome,
+};
+pub use error::{AdminOpsError, Result};
+pub use operation::{ADMIN_ROLE, AdminOperation, AdminOperationRunner, DryRun, Operator};
+pub use plan::OperationPlan;
+pub use token::{ConfirmationSigner, ConfirmationToken, DEFAULT_TOKEN_TTL_SECONDS};
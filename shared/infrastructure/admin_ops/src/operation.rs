@@ -0,0 +1,364 @@
+//! 管理操作と2段階の実行
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared_security::{Claims, Permission};
+use uuid::Uuid;
+
+use crate::{
+    audit::{AdminOperationExecuted, AuditLog, ConfirmationTokenConsumed, OperationOutcome},
+    error::{AdminOpsError, Result},
+    plan::OperationPlan,
+    token::{ConfirmationSigner, ConfirmationToken},
+};
+
+/// 管理操作を実行できるロール
+pub const ADMIN_ROLE: &str = "admin";
+
+/// 操作者（認証コンテキストから取り出す）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Operator {
+    pub id:   String,
+    pub role: String,
+}
+
+impl Operator {
+    /// 検証済みの JWT クレームから操作者を取り出す
+    /// （`admin:operations` 権限を持つロールのみ）
+    pub fn from_claims(claims: &Claims) -> Result<Self> {
+        if !claims.has(Permission::AdminOperations) {
+            return Err(AdminOpsError::Unauthorized(format!(
+                "{} has role {} without {}",
+                claims.sub,
+                claims.role,
+                Permission::AdminOperations
+            )));
+        }
+
+        Ok(Self {
+            id:   claims.sub.clone(),
+            role: claims.role.clone(),
+        })
+    }
+}
+
+/// 取り消せない管理操作
+#[async_trait]
+pub trait AdminOperation: Send + Sync {
+    /// 現在の状態から計画を作る（状態は変更しない）
+    async fn plan(&self) -> Result<OperationPlan>;
+
+    /// 計画を実行し、実行結果の要約を返す
+    async fn execute(&self, plan: &OperationPlan) -> Result<String>;
+}
+
+/// ドライランの結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DryRun {
+    pub plan:  OperationPlan,
+    pub token: ConfirmationToken,
+}
+
+/// 確認トークンを検証して管理操作を実行する
+pub struct AdminOperationRunner {
+    signer: ConfirmationSigner,
+    audit:  Arc<dyn AuditLog>,
+}
+
+impl AdminOperationRunner {
+    pub fn new(signer: ConfirmationSigner, audit: Arc<dyn AuditLog>) -> Self {
+        Self { signer, audit }
+    }
+
+    /// 計画を作り、確認トークンを発行する
+    pub async fn dry_run(
+        &self,
+        operation: &dyn AdminOperation,
+        now: DateTime<Utc>,
+    ) -> Result<DryRun> {
+        let plan = operation.plan().await?;
+        let token = self.signer.issue(&plan.hash()?, now);
+        Ok(DryRun { plan, token })
+    }
+
+    /// 確認トークンを検証して操作を実行し、監査ログに記録する
+    ///
+    /// 計画を作り直し、トークン発行時の計画と異なる場合は実行しない。
+    /// トークンは実行の前に使用済みにするため、操作が失敗した場合も
+    /// 同じトークンでは再実行できない（ドライランからやり直す）。
+    /// 操作が失敗した場合も監査ログに記録してからエラーを返す
+    pub async fn execute(
+        &self,
+        operation: &dyn AdminOperation,
+        token: &str,
+        operator: &Operator,
+        now: DateTime<Utc>,
+    ) -> Result<AdminOperationExecuted> {
+        let plan = operation.plan().await?;
+        let plan_hash = plan.hash()?;
+        let token = self.signer.verify(token, &plan_hash, now)?;
+        self.audit
+            .consume_token(&ConfirmationTokenConsumed {
+                event_id:    Uuid::new_v4(),
+                occurred_at: now,
+                token_id:    token.token_id.clone(),
+                operator_id: operator.id.clone(),
+            })
+            .await?;
+
+        let result = operation.execute(&plan).await;
+        let event = AdminOperationExecuted {
+            event_id: Uuid::new_v4(),
+            occurred_at: now,
+            operator_id: operator.id.clone(),
+            operator_role: operator.role.clone(),
+            operation: plan.operation.clone(),
+            target: plan.target.clone(),
+            plan_hash,
+            token_id: token.token_id,
+            outcome: match &result {
+                Ok(summary) => OperationOutcome::Succeeded {
+                    summary: summary.clone(),
+                },
+                Err(e) => OperationOutcome::Failed {
+                    error: e.to_string(),
+                },
+            },
+        };
+        self.audit.append(&event).await?;
+
+        result.map(|_| event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use chrono::Duration;
+    use shared_event_store::{EventStore, testing::InMemoryEventStore};
+
+    use super::*;
+    use crate::audit::{ADMIN_AUDIT_STREAM_ID, EventStoreAuditLog};
+
+    /// 件数を消去する操作
+    struct PurgeRows {
+        rows:       Mutex<i64>,
+        fail:       bool,
+        executions: AtomicUsize,
+    }
+
+    impl PurgeRows {
+        fn new(rows: i64) -> Self {
+            Self {
+                rows:       Mutex::new(rows),
+                fail:       false,
+                executions: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AdminOperation for PurgeRows {
+        async fn plan(&self) -> Result<OperationPlan> {
+            Ok(OperationPlan::new("rows.purge", "test_table")
+                .with_step("delete all rows")
+                .with_affected("rows", *self.rows.lock().unwrap()))
+        }
+
+        async fn execute(&self, plan: &OperationPlan) -> Result<String> {
+            self.executions.fetch_add(1, Ordering::SeqCst);
+            // 同時に呼ばれた実行が途中で入れ替わるようにする
+            tokio::task::yield_now().await;
+            if self.fail {
+                return Err(AdminOpsError::Operation("disk full".to_string()));
+            }
+            *self.rows.lock().unwrap() = 0;
+            Ok(format!("deleted {} rows", plan.affected["rows"]))
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2025-10-01T12:00:00Z".parse().unwrap()
+    }
+
+    fn operator() -> Operator {
+        Operator::from_claims(&Claims {
+            sub:  "admin-1".to_string(),
+            exp:  0,
+            iat:  0,
+            role: ADMIN_ROLE.to_string(),
+        })
+        .unwrap()
+    }
+
+    struct Fixture {
+        store:  Arc<InMemoryEventStore>,
+        audit:  Arc<EventStoreAuditLog>,
+        runner: AdminOperationRunner,
+    }
+
+    fn fixture() -> Fixture {
+        let store = Arc::new(InMemoryEventStore::new());
+        let audit = Arc::new(EventStoreAuditLog::new(store.clone()));
+        let runner = AdminOperationRunner::new(ConfirmationSigner::new("secret"), audit.clone());
+        Fixture {
+            store,
+            audit,
+            runner,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execution_appends_audit_event_and_token_is_single_use() {
+        let Fixture {
+            store,
+            audit,
+            runner,
+        } = fixture();
+        let operation = PurgeRows::new(42);
+
+        let dry_run = runner.dry_run(&operation, now()).await.unwrap();
+        assert_eq!(dry_run.plan.affected["rows"], 42);
+        // ドライランでは何も変更しない
+        assert_eq!(*operation.rows.lock().unwrap(), 42);
+
+        // 行数が変わると計画も変わるため、消去前のトークンで確認する
+        let plan_hash = dry_run.plan.hash().unwrap();
+        let executed = runner
+            .execute(&operation, &dry_run.token.value, &operator(), now())
+            .await
+            .unwrap();
+
+        let events = audit.events().await.unwrap();
+        assert_eq!(events, vec![executed.clone()]);
+        assert_eq!(executed.operator_id, "admin-1");
+        assert_eq!(executed.operator_role, ADMIN_ROLE);
+        assert_eq!(executed.operation, "rows.purge");
+        assert_eq!(executed.target, "test_table");
+        assert_eq!(executed.plan_hash, plan_hash);
+        assert_eq!(executed.token_id, dry_run.token.token_id);
+        assert_eq!(
+            executed.outcome,
+            OperationOutcome::Succeeded {
+                summary: "deleted 42 rows".to_string(),
+            }
+        );
+        assert_eq!(
+            store
+                .load_events(ADMIN_AUDIT_STREAM_ID, "AdminOperation", None)
+                .await
+                .unwrap()[0]
+                .event_type,
+            "AdminOperationExecuted"
+        );
+
+        // 同じ計画に戻しても、使ったトークンは受け付けない
+        *operation.rows.lock().unwrap() = 42;
+        assert!(matches!(
+            runner
+                .execute(&operation, &dry_run.token.value, &operator(), now())
+                .await,
+            Err(AdminOpsError::TokenAlreadyUsed)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_executions_with_one_token_run_once() {
+        let Fixture { audit, runner, .. } = fixture();
+        let operation = PurgeRows::new(42);
+        let dry_run = runner.dry_run(&operation, now()).await.unwrap();
+        let operator = operator();
+
+        let (first, second) = tokio::join!(
+            runner.execute(&operation, &dry_run.token.value, &operator, now()),
+            runner.execute(&operation, &dry_run.token.value, &operator, now()),
+        );
+
+        let results = [first, second];
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(
+            results
+                .iter()
+                .any(|result| matches!(result, Err(AdminOpsError::TokenAlreadyUsed)))
+        );
+        assert_eq!(operation.executions.load(Ordering::SeqCst), 1);
+        assert_eq!(audit.events().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_plan_drift_and_expiry_are_rejected_without_executing() {
+        let Fixture { audit, runner, .. } = fixture();
+        let operation = PurgeRows::new(10);
+        let dry_run = runner.dry_run(&operation, now()).await.unwrap();
+
+        *operation.rows.lock().unwrap() = 11;
+        assert!(matches!(
+            runner
+                .execute(&operation, &dry_run.token.value, &operator(), now())
+                .await,
+            Err(AdminOpsError::PlanChanged { .. })
+        ));
+
+        *operation.rows.lock().unwrap() = 10;
+        assert!(matches!(
+            runner
+                .execute(
+                    &operation,
+                    &dry_run.token.value,
+                    &operator(),
+                    now() + Duration::minutes(10)
+                )
+                .await,
+            Err(AdminOpsError::TokenExpired)
+        ));
+
+        assert_eq!(*operation.rows.lock().unwrap(), 10);
+        assert!(audit.events().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_operation_is_audited() {
+        let Fixture { audit, runner, .. } = fixture();
+        let operation = PurgeRows {
+            fail: true,
+            ..PurgeRows::new(3)
+        };
+        let dry_run = runner.dry_run(&operation, now()).await.unwrap();
+
+        let result = runner
+            .execute(&operation, &dry_run.token.value, &operator(), now())
+            .await;
+
+        assert!(matches!(result, Err(AdminOpsError::Operation(_))));
+        let events = audit.events().await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].outcome,
+            OperationOutcome::Failed {
+                error: "Operation failed: disk full".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_non_admin_operator_is_rejected() {
+        let claims = Claims {
+            sub:  "user-1".to_string(),
+            exp:  0,
+            iat:  0,
+            role: "user".to_string(),
+        };
+
+        assert!(matches!(
+            Operator::from_claims(&claims),
+            Err(AdminOpsError::Unauthorized(_))
+        ));
+    }
+}
@@ -0,0 +1,75 @@
+//! 操作の計画
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::Result;
+
+/// ドライランで返す計画
+///
+/// 実行時に作り直した計画とハッシュで比較するため、対象の状態
+/// （件数やチェックポイントの位置など）を `affected` に含める
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationPlan {
+    /// 操作名（例: `projection.rebuild`）
+    pub operation: String,
+    /// 対象（プロジェクション名、集約タイプなど）
+    pub target:    String,
+    /// 実行する内容（人が読む説明）
+    pub steps:     Vec<String>,
+    /// 影響を受ける件数など
+    pub affected:  BTreeMap<String, i64>,
+}
+
+impl OperationPlan {
+    pub fn new(operation: impl Into<String>, target: impl Into<String>) -> Self {
+        Self {
+            operation: operation.into(),
+            target:    target.into(),
+            steps:     Vec::new(),
+            affected:  BTreeMap::new(),
+        }
+    }
+
+    pub fn with_step(mut self, step: impl Into<String>) -> Self {
+        self.steps.push(step.into());
+        self
+    }
+
+    pub fn with_affected(mut self, key: impl Into<String>, count: i64) -> Self {
+        self.affected.insert(key.into(), count);
+        self
+    }
+
+    /// 計画のハッシュ（SHA-256、16進）
+    ///
+    /// フィールドの順序と `BTreeMap` のキー順で JSON が決まるため、
+    /// 同じ計画からは常に同じハッシュになる
+    pub fn hash(&self) -> Result<String> {
+        Ok(hex::encode(Sha256::digest(serde_json::to_vec(self)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_is_stable_and_covers_affected_counts() {
+        let plan = OperationPlan::new("snapshots.rebuild", "vocabulary_item")
+            .with_affected("snapshots", 10)
+            .with_affected("aggregates", 4);
+        let same = OperationPlan::new("snapshots.rebuild", "vocabulary_item")
+            .with_affected("aggregates", 4)
+            .with_affected("snapshots", 10);
+
+        assert_eq!(plan.hash().unwrap(), same.hash().unwrap());
+        assert_eq!(plan.hash().unwrap().len(), 64);
+        assert_ne!(
+            plan.hash().unwrap(),
+            plan.clone().with_affected("snapshots", 11).hash().unwrap()
+        );
+    }
+}
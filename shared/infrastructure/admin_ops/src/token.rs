@@ -0,0 +1,170 @@
+//! 確認トークン
+//!
+//! トークンは `{トークン ID}.{有効期限（UNIX 秒）}.{計画のハッシュ}.{署名}`
+//! の形式で、 署名は前の3つに対する HMAC-SHA256。計画のハッシュを含めるため、
+//! 改ざんされたトークンと計画が変わった場合を区別して返せる。
+
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::error::{AdminOpsError, Result};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 確認トークンの既定の有効期間（秒）
+pub const DEFAULT_TOKEN_TTL_SECONDS: i64 = 300;
+
+/// 発行した確認トークン
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfirmationToken {
+    /// 1回限りの利用を確認するための ID
+    pub token_id:   String,
+    pub plan_hash:  String,
+    pub expires_at: DateTime<Utc>,
+    /// 実行時に渡す文字列
+    pub value:      String,
+}
+
+/// 確認トークンの発行と検証
+pub struct ConfirmationSigner {
+    secret: Vec<u8>,
+    ttl:    Duration,
+}
+
+impl ConfirmationSigner {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: secret.into(),
+            ttl:    Duration::seconds(DEFAULT_TOKEN_TTL_SECONDS),
+        }
+    }
+
+    /// 有効期間を変更
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// 計画のハッシュに対するトークンを発行する
+    pub fn issue(&self, plan_hash: &str, now: DateTime<Utc>) -> ConfirmationToken {
+        let token_id = Uuid::new_v4().simple().to_string();
+        let expires_at = now + self.ttl;
+        let payload = format!("{token_id}.{}.{plan_hash}", expires_at.timestamp());
+        let signature = hex::encode(self.mac(&payload).finalize().into_bytes());
+
+        ConfirmationToken {
+            value: format!("{payload}.{signature}"),
+            token_id,
+            plan_hash: plan_hash.to_string(),
+            expires_at: DateTime::from_timestamp(expires_at.timestamp(), 0).unwrap_or(expires_at),
+        }
+    }
+
+    /// トークンを検証し、現在の計画のハッシュと一致することを確かめる
+    pub fn verify(
+        &self,
+        value: &str,
+        current_plan_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<ConfirmationToken> {
+        let (payload, signature) = value.rsplit_once('.').ok_or(AdminOpsError::InvalidToken)?;
+        let signature = hex::decode(signature).map_err(|_| AdminOpsError::InvalidToken)?;
+        self.mac(payload)
+            .verify_slice(&signature)
+            .map_err(|_| AdminOpsError::InvalidToken)?;
+
+        let mut parts = payload.splitn(3, '.');
+        let (Some(token_id), Some(expires_at), Some(plan_hash)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(AdminOpsError::InvalidToken);
+        };
+        let expires_at = expires_at
+            .parse::<i64>()
+            .ok()
+            .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+            .ok_or(AdminOpsError::InvalidToken)?;
+
+        if now >= expires_at {
+            return Err(AdminOpsError::TokenExpired);
+        }
+        if plan_hash != current_plan_hash {
+            return Err(AdminOpsError::PlanChanged {
+                issued:  plan_hash.to_string(),
+                current: current_plan_hash.to_string(),
+            });
+        }
+
+        Ok(ConfirmationToken {
+            token_id: token_id.to_string(),
+            plan_hash: plan_hash.to_string(),
+            expires_at,
+            value: value.to_string(),
+        })
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        "2025-10-01T12:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_token_round_trip_until_expiry() {
+        let signer = ConfirmationSigner::new("secret");
+        let token = signer.issue("abc123", now());
+
+        let verified = signer
+            .verify(&token.value, "abc123", now() + Duration::seconds(299))
+            .unwrap();
+        assert_eq!(verified.token_id, token.token_id);
+        assert_eq!(verified.expires_at, token.expires_at);
+
+        assert!(matches!(
+            signer.verify(&token.value, "abc123", now() + Duration::seconds(300)),
+            Err(AdminOpsError::TokenExpired)
+        ));
+    }
+
+    #[test]
+    fn test_plan_drift_is_distinguished_from_tampering() {
+        let signer = ConfirmationSigner::new("secret");
+        let token = signer.issue("abc123", now());
+
+        assert!(matches!(
+            signer.verify(&token.value, "def456", now()),
+            Err(AdminOpsError::PlanChanged { issued, current })
+                if issued == "abc123" && current == "def456"
+        ));
+
+        // 計画のハッシュを書き換えると署名が合わない
+        let tampered = token.value.replace(".abc123.", ".def456.");
+        assert!(matches!(
+            signer.verify(&tampered, "def456", now()),
+            Err(AdminOpsError::InvalidToken)
+        ));
+        // 別の鍵で発行したトークンも受け付けない
+        let other = ConfirmationSigner::new("other").issue("abc123", now());
+        assert!(matches!(
+            signer.verify(&other.value, "abc123", now()),
+            Err(AdminOpsError::InvalidToken)
+        ));
+        assert!(matches!(
+            signer.verify("garbage", "abc123", now()),
+            Err(AdminOpsError::InvalidToken)
+        ));
+    }
+}
@@ -0,0 +1,156 @@
+//! ファイルシステムの BLOB ストア
+//!
+//! キーをルートディレクトリからの相対パスとして保存する。書き込みは
+//! 一時ファイルに書いてから置き換えるため、読み手が書きかけの
+//! ドキュメントを読むことはない。
+
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use tokio::fs;
+
+use crate::{BlobStore, Result, validate_key};
+
+/// 書きかけのファイルに付ける拡張子（列挙の対象外）
+const TEMP_SUFFIX: &str = ".tmp";
+
+/// ファイルシステムの BLOB ストア
+#[derive(Debug, Clone)]
+pub struct FileSystemBlobStore {
+    root: PathBuf,
+}
+
+impl FileSystemBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+
+    /// `dir` 以下のファイルのキーを集める
+    async fn collect_keys(&self, dir: PathBuf, keys: &mut Vec<String>) -> Result<()> {
+        let mut pending = vec![dir];
+        while let Some(dir) = pending.pop() {
+            let mut entries = match fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if entry.file_type().await?.is_dir() {
+                    pending.push(path);
+                } else if let Some(key) = self.key_of(&path)
+                    && !key.ends_with(TEMP_SUFFIX)
+                {
+                    keys.push(key);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn key_of(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        let segments: Option<Vec<&str>> = relative
+            .components()
+            .map(|component| component.as_os_str().to_str())
+            .collect();
+        Some(segments?.join("/"))
+    }
+}
+
+#[async_trait]
+impl BlobStore for FileSystemBlobStore {
+    async fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let mut temp = path.clone().into_os_string();
+        temp.push(TEMP_SUFFIX);
+        fs::write(&temp, data).await?;
+        fs::rename(&temp, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match fs::read(self.path(key)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        // 前方一致の対象になりうるのは、プレフィックスの最後の `/` までの
+        // ディレクトリ以下だけ
+        let dir = match prefix.rsplit_once('/') {
+            Some((dir, _)) => self.path(dir)?,
+            None => self.root.clone(),
+        };
+
+        let mut keys = Vec::new();
+        self.collect_keys(dir, &mut keys).await?;
+        keys.retain(|key| key.starts_with(prefix));
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempRoot(PathBuf);
+
+    impl Drop for TempRoot {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn temp_root() -> TempRoot {
+        TempRoot(std::env::temp_dir().join(format!("blob-store-{}", uuid::Uuid::new_v4())))
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_and_overwrite() {
+        let root = temp_root();
+        let store = FileSystemBlobStore::new(&root.0);
+
+        assert_eq!(store.get("reports/u/2025-W01.json").await.unwrap(), None);
+        store.put("reports/u/2025-W01.json", b"v1").await.unwrap();
+        store.put("reports/u/2025-W01.json", b"v2").await.unwrap();
+
+        assert_eq!(
+            store.get("reports/u/2025-W01.json").await.unwrap(),
+            Some(b"v2".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_by_prefix() {
+        let root = temp_root();
+        let store = FileSystemBlobStore::new(&root.0);
+        store.put("reports/u/2025-W02.json", b"").await.unwrap();
+        store.put("reports/u/2025-W01.json", b"").await.unwrap();
+        store.put("reports/v/2025-W01.json", b"").await.unwrap();
+        store.put("reports/u/nested/note.json", b"").await.unwrap();
+
+        assert_eq!(
+            store.list("reports/u/2025-").await.unwrap(),
+            vec!["reports/u/2025-W01.json", "reports/u/2025-W02.json"]
+        );
+        assert_eq!(store.list("reports/u/").await.unwrap().len(), 3);
+        assert!(store.list("reports/w/").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+//! 生成済みドキュメントの保存先
+//!
+//! レポートなど、サービスが生成して後から読み出すドキュメントを
+//! キー単位で保存する。キーは `/` 区切りの相対パスで、
+//! [`BlobStore::list`] は前方一致で列挙する。
+
+pub mod filesystem;
+pub mod memory;
+
+use async_trait::async_trait;
+pub use filesystem::FileSystemBlobStore;
+pub use memory::InMemoryBlobStore;
+use thiserror::Error;
+
+/// BLOB ストアのエラー
+#[derive(Debug, Error)]
+pub enum BlobStoreError {
+    /// 不正なキー
+    #[error("Invalid blob key: {0}")]
+    InvalidKey(String),
+
+    /// 読み書きのエラー
+    #[error("Blob store IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, BlobStoreError>;
+
+/// キー単位でドキュメントを保存するストア
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// ドキュメントを保存する（同じキーは上書き）
+    async fn put(&self, key: &str, data: &[u8]) -> Result<()>;
+
+    /// ドキュメントを取得する（存在しない場合は None）
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// `prefix` で始まるキーを昇順で列挙する
+    async fn list(&self, prefix: &str) -> Result<Vec<String>>;
+}
+
+/// キーを検証する
+///
+/// 空のセグメント、`.` と `..`、先頭の `/` を含むキーは
+/// 保存先の外を指しうるため拒否する
+pub fn validate_key(key: &str) -> Result<()> {
+    let valid = !key.is_empty()
+        && key
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+
+    if valid {
+        Ok(())
+    } else {
+        Err(BlobStoreError::InvalidKey(key.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_key() {
+        assert!(validate_key("reports/weekly/user/2025-W01.json").is_ok());
+
+        for key in [
+            "",
+            "/absolute",
+            "reports//weekly",
+            "reports/../secret",
+            "./report",
+            "a/",
+        ] {
+            assert!(
+                matches!(validate_key(key), Err(BlobStoreError::InvalidKey(_))),
+                "{key}"
+            );
+        }
+    }
+}
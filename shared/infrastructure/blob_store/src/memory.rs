@@ -0,0 +1,76 @@
+//! インメモリの BLOB ストア（ローカル開発・テスト用）
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::{BlobStore, Result, validate_key};
+
+/// インメモリの BLOB ストア
+#[derive(Debug, Default)]
+pub struct InMemoryBlobStore {
+    blobs: RwLock<BTreeMap<String, Vec<u8>>>,
+}
+
+impl InMemoryBlobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl BlobStore for InMemoryBlobStore {
+    async fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        validate_key(key)?;
+        self.blobs
+            .write()
+            .await
+            .insert(key.to_string(), data.to_vec());
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        validate_key(key)?;
+        Ok(self.blobs.read().await.get(key).cloned())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        Ok(self
+            .blobs
+            .read()
+            .await
+            .range(prefix.to_string()..)
+            .map(|(key, _)| key)
+            .take_while(|key| key.starts_with(prefix))
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_put_get_and_list_by_prefix() {
+        let store = InMemoryBlobStore::new();
+        store.put("reports/a/2025-W02.json", b"two").await.unwrap();
+        store.put("reports/a/2025-W01.json", b"one").await.unwrap();
+        store
+            .put("reports/b/2025-W01.json", b"other")
+            .await
+            .unwrap();
+        store.put("reports/a/2025-W01.json", b"uno").await.unwrap();
+
+        assert_eq!(
+            store.get("reports/a/2025-W01.json").await.unwrap(),
+            Some(b"uno".to_vec())
+        );
+        assert_eq!(store.get("reports/a/2025-W03.json").await.unwrap(), None);
+        assert_eq!(
+            store.list("reports/a/").await.unwrap(),
+            vec!["reports/a/2025-W01.json", "reports/a/2025-W02.json"]
+        );
+    }
+}
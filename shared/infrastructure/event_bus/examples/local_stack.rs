@@ -0,0 +1,68 @@
+//! インメモリのバックエンドだけでイベントバスとキャッシュを動かす例
+//!
+//! 外部サービスなしで、イベントの発行と購読、キャッシュへの保存と読み出しを
+//! 一通り確認する。
+//!
+//! ```sh
+//! cargo run -p shared_event_bus --example local_stack --no-default-features --features memory
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use shared_cache::CacheConfig;
+use shared_event_bus::{
+    DEFAULT_SHUTDOWN_DEADLINE,
+    EventBusConfig,
+    OutgoingMessage,
+    Topic,
+    TopicNames,
+};
+use shared_kernel::EventBus;
+
+/// 受信したイベントをキャッシュに書き込み、読み戻した内容を返す
+pub async fn run() -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let bus = shared_event_bus::from_config(&EventBusConfig::Memory).await?;
+    let cache = shared_cache::from_config(&CacheConfig::Memory).await?;
+    let topic = TopicNames::default().name(Topic::Vocabulary);
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&received);
+    bus.subscribe(&topic, move |data: &[u8]| {
+        sink.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(data.to_vec());
+        Ok(())
+    })
+    .await?;
+
+    bus.publish_message(OutgoingMessage {
+        topic,
+        data: br#"{"event_type":"VocabularyItemCreated"}"#.to_vec(),
+        ordering_key: Some("vocabulary-item-1".to_string()),
+        ..Default::default()
+    })
+    .await?;
+    // 購読者への配信は非同期なので、処理し終えるまで待つ
+    bus.shutdown(DEFAULT_SHUTDOWN_DEADLINE).await;
+
+    let events = std::mem::take(&mut *received.lock().unwrap_or_else(|e| e.into_inner()));
+    for event in &events {
+        cache
+            .set("vocabulary:last_event", event.clone(), 60)
+            .await?;
+    }
+
+    let cached = cache
+        .get("vocabulary:last_event")
+        .await?
+        .unwrap_or_default();
+    Ok(cached)
+}
+
+#[cfg_attr(test, allow(dead_code))]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cached = run().await?;
+    println!("cached: {}", String::from_utf8_lossy(&cached));
+    Ok(())
+}
@@ -0,0 +1,310 @@
+//! デッドレター（処理できないメッセージの退避）
+//!
+//! [`DeadLetterEventBus`] で包んだバスの購読では、ハンドラーが
+//! [`DeadLetterPolicy::max_delivery_attempts`] 回続けて失敗したメッセージを
+//! デッドレタートピック（`{topic}-dead-letter`）に送り、元のトピックでは
+//! 処理済みとして扱う。不正なイベントが1件あっても射影が止まらない。
+//! 再試行しても変わらないエラー（[`is_retryable`] が false）は1回目で
+//! デッドレターにする。
+//!
+//! デッドレターになったメッセージは [`DeadLetterEventBus::dead_letters`] で
+//! 一覧し、原因を直した後で [`DeadLetterEventBus::redrive`] で元のトピックに
+//! 発行し直せる。
+
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shared_kernel::{EventBus, EventError};
+use tokio::sync::mpsc;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use crate::{EventBusError, retry::is_retryable};
+
+/// 既定の最大配信回数
+const DEFAULT_MAX_DELIVERY_ATTEMPTS: u32 = 5;
+
+/// デッドレターの方針
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeadLetterPolicy {
+    /// デッドレターにするまでにハンドラーを呼ぶ回数（1 以上）
+    pub max_delivery_attempts: u32,
+}
+
+impl Default for DeadLetterPolicy {
+    fn default() -> Self {
+        Self {
+            max_delivery_attempts: DEFAULT_MAX_DELIVERY_ATTEMPTS,
+        }
+    }
+}
+
+impl DeadLetterPolicy {
+    /// 環境変数から読み込む
+    ///
+    /// `EVENT_BUS_MAX_DELIVERY_ATTEMPTS`（既定は 5）を参照する
+    ///
+    /// # Errors
+    ///
+    /// 1 以上の整数でない場合はエラーを返す
+    pub fn from_env() -> Result<Self, EventBusError> {
+        let Ok(value) = std::env::var("EVENT_BUS_MAX_DELIVERY_ATTEMPTS") else {
+            return Ok(Self::default());
+        };
+
+        match value.parse() {
+            Ok(max_delivery_attempts) if max_delivery_attempts > 0 => Ok(Self {
+                max_delivery_attempts,
+            }),
+            _ => Err(EventBusError::Configuration(format!(
+                "EVENT_BUS_MAX_DELIVERY_ATTEMPTS must be a positive integer: {value}"
+            ))),
+        }
+    }
+}
+
+/// トピックのデッドレタートピック名
+pub fn dead_letter_topic(topic: &str) -> String {
+    format!("{topic}-dead-letter")
+}
+
+/// デッドレターになったメッセージ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetter {
+    pub id:               Uuid,
+    /// 元のトピック
+    pub topic:            String,
+    pub data:             Vec<u8>,
+    /// 最後の配信で返されたエラー
+    pub error:            String,
+    pub attempts:         u32,
+    pub dead_lettered_at: DateTime<Utc>,
+}
+
+/// 購読にデッドレターを加えるイベントバス
+pub struct DeadLetterEventBus<B> {
+    inner:        Arc<B>,
+    policy:       DeadLetterPolicy,
+    dead_letters: Arc<RwLock<Vec<DeadLetter>>>,
+}
+
+impl<B> DeadLetterEventBus<B>
+where
+    B: EventBus + 'static,
+{
+    /// `inner` を包んだインスタンスを作成
+    pub fn new(inner: B, policy: DeadLetterPolicy) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            policy,
+            dead_letters: Arc::default(),
+        }
+    }
+
+    /// 包んでいるイベントバス
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// デッドレターになったメッセージを古い順に返す
+    ///
+    /// `topic` を指定した場合はそのトピックのものだけを返す
+    pub fn dead_letters(&self, topic: Option<&str>) -> Vec<DeadLetter> {
+        self.dead_letters
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|dead_letter| topic.is_none_or(|topic| dead_letter.topic == topic))
+            .cloned()
+            .collect()
+    }
+
+    /// デッドレターを元のトピックに発行し直し、一覧から外す
+    ///
+    /// 元のトピックのすべての購読者にもう一度届く
+    ///
+    /// # Errors
+    ///
+    /// デッドレターが見つからない場合や発行に失敗した場合はエラーを返す
+    /// （発行に失敗したデッドレターは一覧に残る）
+    pub async fn redrive(&self, id: Uuid) -> Result<(), EventError> {
+        let dead_letter = self
+            .dead_letters(None)
+            .into_iter()
+            .find(|dead_letter| dead_letter.id == id)
+            .ok_or_else(|| EventError::Bus(format!("Dead letter not found: {id}")))?;
+
+        self.inner
+            .publish(&dead_letter.topic, &dead_letter.data)
+            .await?;
+        self.dead_letters
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|dead_letter| dead_letter.id != id);
+        Ok(())
+    }
+
+    /// トピックのデッドレターをすべて発行し直し、件数を返す
+    ///
+    /// # Errors
+    ///
+    /// 発行に失敗した場合はそこで止めてエラーを返す
+    pub async fn redrive_all(&self, topic: &str) -> Result<usize, EventError> {
+        let dead_letters = self.dead_letters(Some(topic));
+        for dead_letter in &dead_letters {
+            self.redrive(dead_letter.id).await?;
+        }
+        Ok(dead_letters.len())
+    }
+}
+
+#[async_trait]
+impl<B> EventBus for DeadLetterEventBus<B>
+where
+    B: EventBus + 'static,
+{
+    async fn publish(&self, topic: &str, event: &[u8]) -> Result<(), EventError> {
+        self.inner.publish(topic, event).await
+    }
+
+    async fn publish_ordered(
+        &self,
+        topic: &str,
+        event: &[u8],
+        ordering_key: &str,
+    ) -> Result<(), EventError> {
+        self.inner.publish_ordered(topic, event, ordering_key).await
+    }
+
+    async fn publish_batch(&self, topic: &str, events: &[&[u8]]) -> Result<(), EventError> {
+        self.inner.publish_batch(topic, events).await
+    }
+
+    async fn subscribe<F>(&self, topic: &str, handler: F) -> Result<(), EventError>
+    where
+        F: Fn(&[u8]) -> Result<(), EventError> + Send + Sync + 'static,
+    {
+        // ハンドラーは同期なので、デッドレタートピックへの発行は別のタスクで行う
+        let (sender, mut receiver) = mpsc::unbounded_channel::<DeadLetter>();
+        let inner = Arc::clone(&self.inner);
+        tokio::spawn(async move {
+            while let Some(dead_letter) = receiver.recv().await {
+                let topic = dead_letter_topic(&dead_letter.topic);
+                if let Err(e) = inner.publish(&topic, &dead_letter.data).await {
+                    error!(
+                        "Failed to publish dead letter {} to {}: {}",
+                        dead_letter.id, topic, e
+                    );
+                }
+            }
+        });
+
+        let dead_letters = Arc::clone(&self.dead_letters);
+        let max_delivery_attempts = self.policy.max_delivery_attempts.max(1);
+        let topic_name = topic.to_string();
+        self.inner
+            .subscribe(topic, move |data| {
+                let mut attempts = 0;
+                let error = loop {
+                    attempts += 1;
+                    match handler(data) {
+                        Ok(()) => return Ok(()),
+                        Err(e) if attempts >= max_delivery_attempts || !is_retryable(&e) => {
+                            break e.to_string();
+                        },
+                        Err(_) => {},
+                    }
+                };
+
+                let dead_letter = DeadLetter {
+                    id: Uuid::new_v4(),
+                    topic: topic_name.clone(),
+                    data: data.to_vec(),
+                    error,
+                    attempts,
+                    dead_lettered_at: Utc::now(),
+                };
+                warn!(
+                    "Message on topic {} failed {} times and was dead-lettered as {}: {}",
+                    topic_name, attempts, dead_letter.id, dead_letter.error
+                );
+                dead_letters
+                    .write()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(dead_letter.clone());
+                let _ = sender.send(dead_letter);
+                // 元のトピックでは処理済みにし、後続のメッセージを止めない
+                Ok(())
+            })
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use std::sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+    };
+
+    use super::*;
+    use crate::InMemoryEventBus;
+
+    #[tokio::test]
+    async fn test_poison_message_is_dead_lettered_and_redriven() {
+        let bus = DeadLetterEventBus::new(
+            InMemoryEventBus::new(),
+            DeadLetterPolicy {
+                max_delivery_attempts: 3,
+            },
+        );
+        let fixed = Arc::new(AtomicBool::new(false));
+        let calls = Arc::new(AtomicU32::new(0));
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let (is_fixed, counter, sink) = (
+            Arc::clone(&fixed),
+            Arc::clone(&calls),
+            Arc::clone(&received),
+        );
+        bus.subscribe("vocabulary-events", move |data| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            if data == b"malformed" && !is_fixed.load(Ordering::SeqCst) {
+                return Err(EventError::Handler("missing field".to_string()));
+            }
+            sink.lock().unwrap().push(data.to_vec());
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        bus.publish("vocabulary-events", b"malformed")
+            .await
+            .unwrap();
+        bus.publish("vocabulary-events", b"valid").await.unwrap();
+        bus.inner().flush().await;
+
+        // 不正なメッセージは後続を止めずにデッドレターになる
+        assert_eq!(*received.lock().unwrap(), vec![b"valid".to_vec()]);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        let dead_letters = bus.dead_letters(Some("vocabulary-events"));
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].data, b"malformed".to_vec());
+        assert_eq!(dead_letters[0].attempts, 3);
+        assert!(dead_letters[0].error.contains("missing field"));
+        assert!(bus.dead_letters(Some("learning-events")).is_empty());
+
+        // 原因を直した後で発行し直す
+        fixed.store(true, Ordering::SeqCst);
+        assert_eq!(bus.redrive_all("vocabulary-events").await.unwrap(), 1);
+        bus.inner().flush().await;
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![b"valid".to_vec(), b"malformed".to_vec()]
+        );
+        assert!(bus.dead_letters(None).is_empty());
+        assert!(bus.redrive(dead_letters[0].id).await.is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! 購読側の重複排除
+//!
+//! Pub/Sub などの配信は at-least-once のため、同じイベントが複数回届くことが
+//! ある。[`Deduplicator`] を設定したバックエンドは、ハンドラーが成功した
+//! イベントの `event_id` を共有キャッシュ（Redis など）に TTL 付きで記録し、
+//! 記録済みのイベントはハンドラーを呼ばずに処理済みとして扱う。
+//!
+//! - 記録はコンシューマー（サービス）ごとに分けるため、同じトピックを購読する
+//!   別のサービスの処理には影響しない
+//! - ハンドラーが失敗したイベントは記録しないので、再配信で処理し直す
+//! - `event_id` を含まないメッセージは重複排除せずに処理する
+
+use std::{future::Future, sync::Arc, time::Duration};
+
+use shared_cache::Cache;
+use shared_kernel::EventError;
+use tracing::{debug, warn};
+
+/// 処理済みのイベントを記録する
+pub struct Deduplicator {
+    cache:    Arc<dyn Cache>,
+    consumer: String,
+    ttl:      Duration,
+}
+
+impl Deduplicator {
+    /// コンシューマー名と記録を残す期間を指定して作る
+    pub fn new(cache: Arc<dyn Cache>, consumer: impl Into<String>, ttl: Duration) -> Self {
+        Self {
+            cache,
+            consumer: consumer.into(),
+            ttl,
+        }
+    }
+
+    /// 記録済みでなければ `handling` でイベントを処理し、成功したら記録する
+    ///
+    /// キャッシュの読み書きに失敗した場合は重複排除せずに処理する
+    ///
+    /// # Errors
+    ///
+    /// `handling` が失敗した場合はそのエラーを返す
+    pub async fn run<Fut>(&self, topic: &str, data: &[u8], handling: Fut) -> Result<(), EventError>
+    where
+        Fut: Future<Output = Result<(), EventError>>,
+    {
+        let Some(event_id) = event_id(data) else {
+            return handling.await;
+        };
+        let key = format!("event_bus:processed:{}:{topic}:{event_id}", self.consumer);
+
+        match self.cache.get(&key).await {
+            Ok(Some(_)) => {
+                debug!("Skipping duplicate event {} on topic {}", event_id, topic);
+                return Ok(());
+            },
+            Ok(None) => {},
+            Err(e) => warn!("Failed to check processed event {}: {}", event_id, e),
+        }
+
+        handling.await?;
+
+        if let Err(e) = self.cache.set(&key, Vec::new(), self.ttl.as_secs()).await {
+            warn!("Failed to record processed event {}: {}", event_id, e);
+        }
+        Ok(())
+    }
+}
+
+/// 重複排除が設定されていればそれを通して `handling` を実行する
+#[cfg_attr(
+    not(any(feature = "pubsub", feature = "kafka", feature = "memory")),
+    allow(dead_code)
+)]
+pub(crate) async fn handle<Fut>(
+    deduplicator: Option<&Deduplicator>,
+    topic: &str,
+    data: &[u8],
+    handling: Fut,
+) -> Result<(), EventError>
+where
+    Fut: Future<Output = Result<(), EventError>>,
+{
+    match deduplicator {
+        Some(deduplicator) => deduplicator.run(topic, data, handling).await,
+        None => handling.await,
+    }
+}
+
+/// イベントの JSON から `event_id`（トップレベルか
+/// `metadata.event_id`）を取り出す
+fn event_id(data: &[u8]) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_slice(data).ok()?;
+    let id = value
+        .get("event_id")
+        .or_else(|| value.get("metadata")?.get("event_id"))?;
+    id.as_str().map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use shared_cache::{CacheConfig, from_config};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_each_event_is_processed_once_per_consumer() {
+        let cache = from_config(&CacheConfig::Memory).await.unwrap();
+        let projection = Deduplicator::new(
+            Arc::clone(&cache),
+            "vocabulary_projection",
+            Duration::from_secs(60),
+        );
+        let search = Deduplicator::new(cache, "vocabulary_search", Duration::from_secs(60));
+        let calls = AtomicU32::new(0);
+        let handle = |result: Result<(), EventError>| {
+            let calls = &calls;
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                result
+            }
+        };
+
+        let event = br#"{"metadata":{"event_id":"event-1"},"spelling":"apple"}"#;
+        // 失敗した処理は記録せず、再配信で処理し直す
+        let failed = Err(EventError::Handler("unavailable".to_string()));
+        assert!(
+            projection
+                .run("vocabulary-events", event, handle(failed))
+                .await
+                .is_err()
+        );
+        for _ in 0..3 {
+            projection
+                .run("vocabulary-events", event, handle(Ok(())))
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.swap(0, Ordering::SeqCst), 2);
+
+        // 別のコンシューマーは独立して処理する
+        search
+            .run("vocabulary-events", event, handle(Ok(())))
+            .await
+            .unwrap();
+        // event_id のないメッセージは毎回処理する
+        for _ in 0..2 {
+            projection
+                .run("vocabulary-events", b"{}", handle(Ok(())))
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
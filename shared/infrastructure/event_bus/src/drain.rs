@@ -0,0 +1,97 @@
+//! 購読の停止（グレースフルドレイン）
+//!
+//! デプロイ時の SIGTERM で購読を止めるとき、処理中のメッセージを途中で
+//! 捨てると射影の更新が失われ、確認応答の前に止まると再配信で二重に適用
+//! される。各バックエンドは [`Drain`] を通して次の順に停止する。
+//!
+//! 1. 新しいメッセージの受信をやめる（受信済みで未処理のものは否定応答する）
+//! 2. 処理中のハンドラーが終わるのを期限まで待つ
+//! 3. 期限を過ぎても終わらない処理は結果を待たずに否定応答し、再配信に回す
+
+use std::{future::Future, time::Duration};
+
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+use tracing::{info, warn};
+
+/// 購読の停止を待つ既定の期限
+///
+/// Cloud Run は SIGTERM から 10 秒で強制終了するため、否定応答を送る余裕を残す
+pub const DEFAULT_SHUTDOWN_DEADLINE: Duration = Duration::from_secs(8);
+
+/// 期限切れの後、否定応答を送り終えるのを待つ時間
+const NACK_GRACE: Duration = Duration::from_secs(1);
+
+/// 購読タスクの停止を管理する
+#[derive(Clone, Default)]
+pub(crate) struct Drain {
+    /// 受信をやめる合図
+    stopping: CancellationToken,
+    /// 処理中のハンドラーを待つ期限が過ぎた合図
+    expired:  CancellationToken,
+    /// 購読タスク
+    tasks:    TaskTracker,
+}
+
+#[cfg_attr(
+    not(any(feature = "pubsub", feature = "kafka", feature = "memory")),
+    allow(dead_code)
+)]
+impl Drain {
+    /// 購読タスクを起動する
+    pub(crate) fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks.spawn(task);
+    }
+
+    /// 停止が始まっているか
+    #[cfg_attr(not(feature = "pubsub"), allow(dead_code))]
+    pub(crate) fn is_stopping(&self) -> bool {
+        self.stopping.is_cancelled()
+    }
+
+    /// 停止が始まるまで待つ（受信と競わせる）
+    pub(crate) async fn stopping(&self) {
+        self.stopping.cancelled().await;
+    }
+
+    /// メッセージの処理を期限まで待ち、期限を過ぎたら None を返す
+    pub(crate) async fn run<Fut>(&self, handling: Fut) -> Option<Fut::Output>
+    where
+        Fut: Future,
+    {
+        tokio::select! {
+            biased;
+            output = handling => Some(output),
+            () = self.expired.cancelled() => None,
+        }
+    }
+
+    /// 受信をやめ、処理中のハンドラーが終わるのを `deadline` まで待つ
+    pub(crate) async fn shutdown(&self, deadline: Duration) {
+        self.stopping.cancel();
+        self.tasks.close();
+
+        if tokio::time::timeout(deadline, self.tasks.wait())
+            .await
+            .is_ok()
+        {
+            info!("All subscriptions drained");
+            return;
+        }
+
+        warn!(
+            "{} subscriptions did not drain within {:?}, nacking in-flight messages",
+            self.tasks.len(),
+            deadline
+        );
+        self.expired.cancel();
+        if tokio::time::timeout(NACK_GRACE, self.tasks.wait())
+            .await
+            .is_err()
+        {
+            warn!("{} subscriptions are still running", self.tasks.len());
+        }
+    }
+}
@@ -0,0 +1,465 @@
+//! 設定からのイベントバス構築
+//!
+//! サービスはバックエンドの型を直接扱わず、[`EventBusConfig`] から
+//! [`from_config`] で [`AnyEventBus`] を構築する。設定されたバックエンドが
+//! feature で無効化されている場合は実行時に
+//! [`EventBusError::BackendNotCompiled`] を返す。
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use shared_kernel::{EventBus, EventError};
+
+#[cfg(feature = "kafka")]
+use crate::kafka::KafkaEventBus;
+#[cfg(feature = "memory")]
+use crate::memory::InMemoryEventBus;
+#[cfg(feature = "pubsub")]
+use crate::pubsub::PubSubEventBus;
+use crate::{Deduplicator, EventBusError, RetryPolicy, SubscribeOptions};
+
+/// イベントバス設定
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum EventBusConfig {
+    /// インメモリイベントバス（開発環境用）
+    #[serde(rename = "memory")]
+    Memory,
+    /// Google Pub/Sub
+    #[serde(rename = "pubsub")]
+    PubSub {
+        /// プロジェクト ID
+        project_id:     String,
+        /// 存在しないトピックを自動で作成するか（開発環境用）
+        #[serde(default)]
+        auto_provision: bool,
+    },
+    /// Apache Kafka（オンプレミス環境用）
+    #[serde(rename = "kafka")]
+    Kafka {
+        /// ブローカーのアドレス（カンマ区切り）
+        brokers:  String,
+        /// コンシューマーグループ ID の接頭辞
+        group_id: String,
+    },
+}
+
+impl EventBusConfig {
+    /// 環境変数から読み込む
+    ///
+    /// `EVENT_BUS_BACKEND`（`pubsub`、`kafka` または `memory`、既定は
+    /// `pubsub`）と、バックエンドに応じて `GCP_PROJECT_ID` /
+    /// `PUBSUB_AUTO_PROVISION` または `KAFKA_BROKERS` / `KAFKA_GROUP_ID` を
+    /// 参照する。`PUBSUB_AUTO_PROVISION` の既定は、エミュレータ
+    /// （`PUBSUB_EMULATOR_HOST`）を使う場合に `true`
+    ///
+    /// # Errors
+    ///
+    /// 未知のバックエンド名や不正な値が指定された場合はエラーを返す
+    pub fn from_env() -> Result<Self, EventBusError> {
+        let backend = std::env::var("EVENT_BUS_BACKEND").unwrap_or_else(|_| "pubsub".to_string());
+        Self::parse(&backend, |name| std::env::var(name).ok())
+    }
+
+    /// バックエンド名から設定を作る
+    ///
+    /// バックエンドの設定値は `var` で名前（環境変数名）から引き、未設定なら
+    /// 既定値を使う
+    ///
+    /// # Errors
+    ///
+    /// 未知のバックエンド名や不正な値が指定された場合はエラーを返す
+    pub fn parse(
+        backend: &str,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, EventBusError> {
+        let var_or = |name: &str, default: &str| var(name).unwrap_or_else(|| default.to_string());
+        match backend {
+            "memory" => Ok(Self::Memory),
+            "pubsub" => {
+                let auto_provision = match var("PUBSUB_AUTO_PROVISION") {
+                    Some(value) => value.parse().map_err(|_| {
+                        EventBusError::Configuration(format!(
+                            "PUBSUB_AUTO_PROVISION must be true or false: {value}"
+                        ))
+                    })?,
+                    None => var("PUBSUB_EMULATOR_HOST").is_some(),
+                };
+                Ok(Self::PubSub {
+                    project_id: var_or("GCP_PROJECT_ID", "effect-project"),
+                    auto_provision,
+                })
+            },
+            "kafka" => Ok(Self::Kafka {
+                brokers:  var_or("KAFKA_BROKERS", "localhost:9092"),
+                group_id: var_or("KAFKA_GROUP_ID", "effect"),
+            }),
+            other => Err(EventBusError::Configuration(format!(
+                "Unknown event bus backend: {other} (expected pubsub, kafka or memory)"
+            ))),
+        }
+    }
+
+    /// バックエンド名
+    pub fn backend(&self) -> &'static str {
+        match *self {
+            Self::Memory => "memory",
+            Self::PubSub { .. } => "pubsub",
+            Self::Kafka { .. } => "kafka",
+        }
+    }
+}
+
+/// 属性付きで発行するメッセージ
+///
+/// `topic` はバックエンド上のトピック名をそのまま指定する
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutgoingMessage {
+    pub topic:        String,
+    pub data:         Vec<u8>,
+    pub attributes:   HashMap<String, String>,
+    /// 同じキーのメッセージは発行順に配信される
+    pub ordering_key: Option<String>,
+}
+
+/// イベントの JSON から集約 ID を取り出す（既定の順序キー）
+///
+/// トップレベルの `aggregate_id` か `metadata.aggregate_id` を使う
+#[cfg_attr(not(any(feature = "pubsub", feature = "kafka")), allow(dead_code))]
+pub(crate) fn aggregate_id(event: &[u8]) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_slice(event).ok()?;
+    let id = value
+        .get("aggregate_id")
+        .or_else(|| value.get("metadata")?.get("aggregate_id"))?;
+    id.as_str().map(ToString::to_string)
+}
+
+/// 設定から構築したイベントバス
+///
+/// 有効な feature のバックエンドだけがバリアントとして存在する
+pub enum AnyEventBus {
+    #[cfg(feature = "pubsub")]
+    PubSub(PubSubEventBus),
+    #[cfg(feature = "kafka")]
+    Kafka(KafkaEventBus),
+    #[cfg(feature = "memory")]
+    Memory(InMemoryEventBus),
+}
+
+#[cfg_attr(
+    not(any(feature = "pubsub", feature = "kafka", feature = "memory")),
+    allow(unused_variables)
+)]
+impl AnyEventBus {
+    /// バックエンド名
+    pub fn backend(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "pubsub")]
+            Self::PubSub(_) => "pubsub",
+            #[cfg(feature = "kafka")]
+            Self::Kafka(_) => "kafka",
+            #[cfg(feature = "memory")]
+            Self::Memory(_) => "memory",
+        }
+    }
+
+    /// ハンドラーの再試行の方針を設定する
+    #[must_use]
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+        match self {
+            #[cfg(feature = "pubsub")]
+            Self::PubSub(bus) => Self::PubSub(bus.with_retry_policy(retry_policy)),
+            #[cfg(feature = "kafka")]
+            Self::Kafka(bus) => Self::Kafka(bus.with_retry_policy(retry_policy)),
+            #[cfg(feature = "memory")]
+            Self::Memory(bus) => Self::Memory(bus.with_retry_policy(retry_policy)),
+        }
+    }
+
+    /// 処理済みのイベントを記録する重複排除を設定する
+    #[must_use]
+    pub fn with_deduplicator(self, deduplicator: Arc<Deduplicator>) -> Self {
+        match self {
+            #[cfg(feature = "pubsub")]
+            Self::PubSub(bus) => Self::PubSub(bus.with_deduplicator(deduplicator)),
+            #[cfg(feature = "kafka")]
+            Self::Kafka(bus) => Self::Kafka(bus.with_deduplicator(deduplicator)),
+            #[cfg(feature = "memory")]
+            Self::Memory(bus) => Self::Memory(bus.with_deduplicator(deduplicator)),
+        }
+    }
+
+    /// 属性付きのメッセージを発行し、メッセージ ID を返す
+    ///
+    /// # Errors
+    ///
+    /// 発行に失敗した場合はエラーを返す
+    pub async fn publish_message(&self, message: OutgoingMessage) -> Result<String, EventError> {
+        match *self {
+            #[cfg(feature = "pubsub")]
+            Self::PubSub(ref bus) => bus.publish_message(message).await,
+            #[cfg(feature = "kafka")]
+            Self::Kafka(ref bus) => bus.publish_message(message).await,
+            #[cfg(feature = "memory")]
+            Self::Memory(ref bus) => Ok(bus.publish_message(message)),
+        }
+    }
+
+    /// 購読を止め、保留中のメッセージを送り切ってから停止する
+    ///
+    /// 受信をやめ、処理中のハンドラーを `deadline` まで待つ。それまでに
+    /// 終わらなかったメッセージは否定応答して再配信に回す。インメモリの
+    /// 場合は再配信できないため、発行済みのメッセージの処理を期限まで待つ
+    pub async fn shutdown(&self, deadline: Duration) {
+        match *self {
+            #[cfg(feature = "pubsub")]
+            Self::PubSub(ref bus) => bus.shutdown(deadline).await,
+            #[cfg(feature = "kafka")]
+            Self::Kafka(ref bus) => bus.shutdown(deadline).await,
+            #[cfg(feature = "memory")]
+            Self::Memory(ref bus) => bus.shutdown(deadline).await,
+        }
+    }
+
+    /// 設定を指定してイベントを購読
+    ///
+    /// # Errors
+    ///
+    /// 購読の開始に失敗した場合はエラーを返す
+    pub async fn subscribe_with_options<F>(
+        &self,
+        topic: &str,
+        options: SubscribeOptions,
+        handler: F,
+    ) -> Result<(), EventError>
+    where
+        F: Fn(&[u8]) -> Result<(), EventError> + Send + Sync + 'static,
+    {
+        match *self {
+            #[cfg(feature = "pubsub")]
+            Self::PubSub(ref bus) => bus.subscribe_with_options(topic, options, handler).await,
+            #[cfg(feature = "kafka")]
+            Self::Kafka(ref bus) => bus.subscribe_with_options(topic, options, handler).await,
+            #[cfg(feature = "memory")]
+            Self::Memory(ref bus) => bus.subscribe_with_options(topic, options, handler).await,
+        }
+    }
+}
+
+#[cfg_attr(
+    not(any(feature = "pubsub", feature = "kafka", feature = "memory")),
+    allow(unused_variables)
+)]
+#[async_trait]
+impl EventBus for AnyEventBus {
+    async fn publish(&self, topic: &str, event: &[u8]) -> Result<(), EventError> {
+        match *self {
+            #[cfg(feature = "pubsub")]
+            Self::PubSub(ref bus) => bus.publish(topic, event).await,
+            #[cfg(feature = "kafka")]
+            Self::Kafka(ref bus) => bus.publish(topic, event).await,
+            #[cfg(feature = "memory")]
+            Self::Memory(ref bus) => bus.publish(topic, event).await,
+        }
+    }
+
+    async fn publish_ordered(
+        &self,
+        topic: &str,
+        event: &[u8],
+        ordering_key: &str,
+    ) -> Result<(), EventError> {
+        match *self {
+            #[cfg(feature = "pubsub")]
+            Self::PubSub(ref bus) => bus.publish_ordered(topic, event, ordering_key).await,
+            #[cfg(feature = "kafka")]
+            Self::Kafka(ref bus) => bus.publish_ordered(topic, event, ordering_key).await,
+            #[cfg(feature = "memory")]
+            Self::Memory(ref bus) => bus.publish_ordered(topic, event, ordering_key).await,
+        }
+    }
+
+    async fn publish_batch(&self, topic: &str, events: &[&[u8]]) -> Result<(), EventError> {
+        match *self {
+            #[cfg(feature = "pubsub")]
+            Self::PubSub(ref bus) => bus.publish_batch(topic, events).await,
+            #[cfg(feature = "kafka")]
+            Self::Kafka(ref bus) => bus.publish_batch(topic, events).await,
+            #[cfg(feature = "memory")]
+            Self::Memory(ref bus) => bus.publish_batch(topic, events).await,
+        }
+    }
+
+    async fn subscribe<F>(&self, topic: &str, handler: F) -> Result<(), EventError>
+    where
+        F: Fn(&[u8]) -> Result<(), EventError> + Send + Sync + 'static,
+    {
+        match *self {
+            #[cfg(feature = "pubsub")]
+            Self::PubSub(ref bus) => bus.subscribe(topic, handler).await,
+            #[cfg(feature = "kafka")]
+            Self::Kafka(ref bus) => bus.subscribe(topic, handler).await,
+            #[cfg(feature = "memory")]
+            Self::Memory(ref bus) => bus.subscribe(topic, handler).await,
+        }
+    }
+}
+
+/// 設定からイベントバスを構築する
+///
+/// # Errors
+///
+/// バックエンドが feature で無効化されている場合は
+/// [`EventBusError::BackendNotCompiled`]、接続に失敗した場合は
+/// [`EventBusError::Connection`] を返す
+pub async fn from_config(config: &EventBusConfig) -> Result<AnyEventBus, EventBusError> {
+    match config {
+        #[cfg(feature = "memory")]
+        EventBusConfig::Memory => Ok(AnyEventBus::Memory(InMemoryEventBus::new())),
+        #[cfg(feature = "pubsub")]
+        EventBusConfig::PubSub {
+            project_id,
+            auto_provision,
+        } => PubSubEventBus::new(project_id.clone())
+            .await
+            .map(|bus| AnyEventBus::PubSub(bus.with_auto_provision(*auto_provision)))
+            .map_err(|e| EventBusError::Connection(e.to_string())),
+        #[cfg(feature = "kafka")]
+        EventBusConfig::Kafka { brokers, group_id } => KafkaEventBus::new(brokers, group_id)
+            .map(AnyEventBus::Kafka)
+            .map_err(|e| EventBusError::Connection(e.to_string())),
+        #[allow(unreachable_patterns)]
+        other => Err(EventBusError::BackendNotCompiled {
+            backend: other.backend(),
+            feature: other.backend(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_backend_names() {
+        let var = |name: &str| (name == "GCP_PROJECT_ID").then(|| "effect-dev".to_string());
+
+        assert_eq!(
+            EventBusConfig::parse("memory", var).unwrap(),
+            EventBusConfig::Memory
+        );
+        assert_eq!(
+            EventBusConfig::parse("pubsub", var).unwrap(),
+            EventBusConfig::PubSub {
+                project_id:     "effect-dev".to_string(),
+                auto_provision: false,
+            }
+        );
+        // 未設定の値は既定値になる
+        assert_eq!(
+            EventBusConfig::parse("kafka", var).unwrap(),
+            EventBusConfig::Kafka {
+                brokers:  "localhost:9092".to_string(),
+                group_id: "effect".to_string(),
+            }
+        );
+        assert!(matches!(
+            EventBusConfig::parse("rabbitmq", var),
+            Err(EventBusError::Configuration(_))
+        ));
+    }
+
+    #[test]
+    fn test_pubsub_emulator_enables_auto_provisioning() {
+        let auto_provision = |vars: &[(&str, &str)]| -> Result<bool, EventBusError> {
+            let config = EventBusConfig::parse("pubsub", |name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| (*value).to_string())
+            })?;
+            match config {
+                EventBusConfig::PubSub { auto_provision, .. } => Ok(auto_provision),
+                _ => unreachable!(),
+            }
+        };
+
+        assert!(auto_provision(&[("PUBSUB_EMULATOR_HOST", "localhost:8085")]).unwrap());
+        assert!(
+            !auto_provision(&[
+                ("PUBSUB_EMULATOR_HOST", "localhost:8085"),
+                ("PUBSUB_AUTO_PROVISION", "false"),
+            ])
+            .unwrap()
+        );
+        assert!(auto_provision(&[("PUBSUB_AUTO_PROVISION", "true")]).unwrap());
+        assert!(auto_provision(&[("PUBSUB_AUTO_PROVISION", "yes")]).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_id_is_the_default_ordering_key() {
+        assert_eq!(
+            aggregate_id(br#"{"aggregate_id":"item-1","data":{}}"#).as_deref(),
+            Some("item-1")
+        );
+        assert_eq!(
+            aggregate_id(br#"{"metadata":{"aggregate_id":"item-2"}}"#).as_deref(),
+            Some("item-2")
+        );
+        assert_eq!(aggregate_id(br#"{"data":{}}"#), None);
+        assert_eq!(aggregate_id(b"not json"), None);
+    }
+
+    #[cfg(feature = "memory")]
+    #[tokio::test]
+    async fn test_from_config_builds_memory_bus() {
+        let bus = from_config(&EventBusConfig::Memory).await.unwrap();
+        assert_eq!(bus.backend(), "memory");
+    }
+
+    #[cfg(not(feature = "memory"))]
+    #[tokio::test]
+    async fn test_memory_backend_not_compiled_is_reported() {
+        let result = from_config(&EventBusConfig::Memory).await;
+        assert!(matches!(
+            result,
+            Err(EventBusError::BackendNotCompiled {
+                backend: "memory",
+                ..
+            })
+        ));
+    }
+
+    #[cfg(not(feature = "pubsub"))]
+    #[tokio::test]
+    async fn test_pubsub_backend_not_compiled_is_reported() {
+        let config = EventBusConfig::PubSub {
+            project_id:     "effect-dev".to_string(),
+            auto_provision: false,
+        };
+
+        let error = from_config(&config).await.err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Event bus backend 'pubsub' is not compiled in (enable the `pubsub` feature of \
+             shared_event_bus)"
+        );
+    }
+
+    #[cfg(not(feature = "kafka"))]
+    #[tokio::test]
+    async fn test_kafka_backend_not_compiled_is_reported() {
+        let config = EventBusConfig::Kafka {
+            brokers:  "localhost:9092".to_string(),
+            group_id: "effect".to_string(),
+        };
+
+        assert!(matches!(
+            from_config(&config).await,
+            Err(EventBusError::BackendNotCompiled {
+                backend: "kafka",
+                feature: "kafka",
+            })
+        ));
+    }
+}
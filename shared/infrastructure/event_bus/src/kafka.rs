@@ -0,0 +1,403 @@
+//! Apache Kafka による [`EventBus`] 実装
+//!
+//! Pub/Sub を使えないオンプレミス環境向けのバックエンド。
+//!
+//! - メッセージのキーは集約 ID で、同じ集約のイベントは同じパーティションに
+//!   入り発行順に配信される
+//! - 購読はトピックごとのコンシューマーグループ（`{group_id}-{topic}`）に
+//!   対応し、同じサービスの複数のインスタンスはパーティションを分け合う
+//! - オフセットはハンドラーが成功した後にコミットし、[`RetryPolicy`] に
+//!   従って呼び直しても失敗したメッセージは同じオフセットに戻して読み直す
+//! - [`SubscribeOptions::max_in_flight`] を 2 以上にするとパーティション内の
+//!   メッセージも並行に処理し、終わった順にコミットする
+//! - 停止時は受信をやめ、処理中のハンドラーを期限まで待ってから
+//!   コンシューマーグループを抜ける
+//! - 読み直しで同じイベントを二度処理しないよう [`Deduplicator`] を設定できる
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use rdkafka::{
+    ClientConfig,
+    Message,
+    Offset,
+    TopicPartitionList,
+    consumer::{CommitMode, Consumer, StreamConsumer},
+    error::KafkaResult,
+    message::{Header, Headers, OwnedHeaders, OwnedMessage},
+    producer::{FutureProducer, FutureRecord, Producer},
+    util::Timeout,
+};
+use shared_kernel::{EventBus, EventError};
+use tracing::{Instrument, error, info, warn};
+
+use crate::{
+    Deduplicator,
+    OutgoingMessage,
+    RetryPolicy,
+    SubscribeOptions,
+    dedup,
+    drain::Drain,
+    factory::aggregate_id,
+    metrics,
+    trace_context,
+};
+
+/// 発行の完了を待つ時間
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 失敗したメッセージを読み直すまでの時間
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Apache Kafka ベースのイベントバス実装
+pub struct KafkaEventBus {
+    brokers:      String,
+    group_id:     String,
+    producer:     FutureProducer,
+    retry_policy: RetryPolicy,
+    deduplicator: Option<Arc<Deduplicator>>,
+    drain:        Drain,
+}
+
+impl KafkaEventBus {
+    /// 新しい [`KafkaEventBus`] インスタンスを作成
+    ///
+    /// # Arguments
+    ///
+    /// * `brokers` - ブローカーのアドレス（カンマ区切り）
+    /// * `group_id` - コンシューマーグループ ID の接頭辞
+    ///
+    /// # Errors
+    ///
+    /// プロデューサーの作成に失敗した場合はエラーを返す
+    pub fn new(brokers: &str, group_id: &str) -> Result<Self, EventError> {
+        // 再送で重複や順序の入れ替わりが起きないよう冪等プロデューサーにする
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("enable.idempotence", "true")
+            // まとめて発行されたメッセージを1回のリクエストに詰める
+            .set("linger.ms", "20")
+            .create()
+            .map_err(|e| EventError::Publish(format!("Failed to create Kafka producer: {e}")))?;
+
+        Ok(Self {
+            brokers: brokers.to_string(),
+            group_id: group_id.to_string(),
+            producer,
+            retry_policy: RetryPolicy::default(),
+            deduplicator: None,
+            drain: Drain::default(),
+        })
+    }
+
+    /// ハンドラーの再試行の方針を設定する
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// 処理済みのイベントを記録する重複排除を設定する
+    #[must_use]
+    pub fn with_deduplicator(mut self, deduplicator: Arc<Deduplicator>) -> Self {
+        self.deduplicator = Some(deduplicator);
+        self
+    }
+
+    /// 属性付きのメッセージをトピック名そのままに発行し、
+    /// `{partition}-{offset}` をメッセージ ID として返す
+    ///
+    /// 順序キーをメッセージのキーにし、属性はヘッダーにする
+    ///
+    /// # Errors
+    ///
+    /// 発行に失敗した場合はエラーを返す
+    pub async fn publish_message(&self, message: OutgoingMessage) -> Result<String, EventError> {
+        let headers = to_headers(message.attributes);
+        let sending = self.send(
+            &message.topic,
+            message.ordering_key.as_deref(),
+            &message.data,
+            headers,
+        );
+        metrics::timed_publish("kafka", &message.topic, 1, sending).await
+    }
+
+    /// 購読を止めてから、送信中のメッセージを送り切る
+    ///
+    /// 受信をやめ、処理中のハンドラーを `deadline` まで待つ。それまでに
+    /// 終わらなかったメッセージはオフセットをコミットせず、読み直しに回す
+    pub async fn shutdown(&self, deadline: Duration) {
+        self.drain.shutdown(deadline).await;
+
+        if let Err(e) = self.producer.flush(Timeout::After(SEND_TIMEOUT)) {
+            error!("Failed to flush Kafka producer: {}", e);
+        }
+        info!("Kafka producer flushed");
+    }
+
+    /// 設定を指定し、トピックのコンシューマーグループに参加してイベントを購読
+    ///
+    /// 並行に処理する場合、オフセットはメッセージごとに処理が終わった順に
+    /// コミットする
+    ///
+    /// # Errors
+    ///
+    /// コンシューマーの作成やトピックの購読に失敗した場合はエラーを返す
+    pub async fn subscribe_with_options<F>(
+        &self,
+        topic: &str,
+        options: SubscribeOptions,
+        handler: F,
+    ) -> Result<(), EventError>
+    where
+        F: Fn(&[u8]) -> Result<(), EventError> + Send + Sync + 'static,
+    {
+        let topic_name = Self::get_topic_name(topic);
+        let group_id = self.consumer_group_id(topic);
+
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &self.brokers)
+            .set("group.id", &group_id)
+            .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", "earliest")
+            .create()
+            .map_err(|e| EventError::Handler(format!("Failed to create Kafka consumer: {e}")))?;
+        consumer
+            .subscribe(&[&topic_name])
+            .map_err(|e| EventError::Handler(format!("Failed to subscribe to topic: {e}")))?;
+
+        let consumer = Arc::new(consumer);
+        let handler = Arc::new(handler);
+        let retry_policy = self.retry_policy;
+        let deduplicator = self.deduplicator.clone();
+        let limit = options.in_flight_limit();
+        let topic_clone = topic.to_string();
+
+        let drain = self.drain.clone();
+
+        // メッセージの受信を開始（停止すると受信をやめ、
+        // コンシューマーグループを抜ける）
+        self.drain.spawn(async move {
+            loop {
+                // 処理中のメッセージが上限に達している間は受信しない
+                let permit = tokio::select! {
+                    permit = Arc::clone(&limit).acquire_owned() => match permit {
+                        Ok(permit) => permit,
+                        Err(_) => break,
+                    },
+                    () = drain.stopping() => break,
+                };
+                let received = tokio::select! {
+                    received = consumer.recv() => received.map(|message| message.detach()),
+                    () = drain.stopping() => break,
+                };
+                let message = match received {
+                    Ok(message) => message,
+                    Err(e) => {
+                        error!("Error receiving messages: {}", e);
+                        tokio::select! {
+                            () = tokio::time::sleep(RETRY_DELAY) => continue,
+                            () = drain.stopping() => break,
+                        }
+                    },
+                };
+
+                let consumer = Arc::clone(&consumer);
+                let handler = Arc::clone(&handler);
+                let deduplicator = deduplicator.clone();
+                let topic_clone = topic_clone.clone();
+                let task_drain = drain.clone();
+                drain.spawn(async move {
+                    // イベントを発行側のトレースの中で処理
+                    let span = trace_context::consumer_span(&topic_clone, &attributes(&message));
+                    let payload = message.payload().unwrap_or_default();
+                    let handling = retry_policy.run(handler.as_ref(), payload);
+                    let handling =
+                        dedup::handle(deduplicator.as_deref(), &topic_clone, payload, handling);
+                    let handling = metrics::timed_handling("kafka", &topic_clone, handling);
+                    if let Some(published_at) = timestamp(&message) {
+                        metrics::record_lag("kafka", &topic_clone, published_at);
+                    }
+                    match task_drain.run(handling.instrument(span)).await {
+                        Some(Ok(())) => {
+                            if let Err(e) = commit(&consumer, &message) {
+                                error!("Failed to commit offset: {}", e);
+                            }
+                        },
+                        Some(Err(e)) => {
+                            error!("Error handling event: {}", e);
+                            // コミットせずに同じオフセットへ戻し、後で読み直す
+                            tokio::select! {
+                                () = tokio::time::sleep(RETRY_DELAY) => {},
+                                () = task_drain.stopping() => return,
+                            }
+                            if let Err(e) = consumer.seek(
+                                message.topic(),
+                                message.partition(),
+                                Offset::Offset(message.offset()),
+                                Timeout::After(SEND_TIMEOUT),
+                            ) {
+                                error!("Failed to seek back to the failed message: {}", e);
+                            }
+                        },
+                        None => {
+                            // コミットしないので、グループの他のメンバーが読み直す
+                            warn!(
+                                "Handler did not finish before shutdown, leaving offset \
+                                 uncommitted"
+                            );
+                        },
+                    }
+                    drop(permit);
+                });
+            }
+        });
+
+        info!(
+            "Started subscription to {} in consumer group {}",
+            topic_name, group_id
+        );
+        Ok(())
+    }
+
+    /// トピック名からイベントタイプを取得
+    fn get_topic_name(topic: &str) -> String {
+        format!("effect-{topic}")
+    }
+
+    /// 購読するトピックのコンシューマーグループ ID
+    fn consumer_group_id(&self, topic: &str) -> String {
+        format!("{}-{}", self.group_id, topic)
+    }
+
+    /// `effect-{topic}` にキーを付けて発行する
+    async fn publish_with_key(
+        &self,
+        topic: &str,
+        event: &[u8],
+        key: Option<&str>,
+    ) -> Result<(), EventError> {
+        let topic_name = Self::get_topic_name(topic);
+        let headers = to_headers(HashMap::from([
+            ("topic".to_string(), topic.to_string()),
+            ("timestamp".to_string(), chrono::Utc::now().to_rfc3339()),
+        ]));
+
+        let sending = self.send(&topic_name, key, event, headers);
+        metrics::timed_publish("kafka", topic, 1, sending).await?;
+
+        info!("Published event to topic {}", topic_name);
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        topic: &str,
+        key: Option<&str>,
+        payload: &[u8],
+        headers: OwnedHeaders,
+    ) -> Result<String, EventError> {
+        let mut record = FutureRecord::to(topic).payload(payload).headers(headers);
+        if let Some(key) = key {
+            record = record.key(key);
+        }
+
+        let (partition, offset) = self
+            .producer
+            .send(record, Timeout::After(SEND_TIMEOUT))
+            .await
+            .map_err(|(e, _)| EventError::Publish(format!("Failed to publish message: {e}")))?;
+        Ok(format!("{partition}-{offset}"))
+    }
+}
+
+/// 属性に現在のトレースコンテキストを加えてヘッダーにする
+fn to_headers(mut attributes: HashMap<String, String>) -> OwnedHeaders {
+    trace_context::inject(&mut attributes);
+    attributes
+        .iter()
+        .fold(OwnedHeaders::new(), |headers, (key, value)| {
+            headers.insert(Header {
+                key,
+                value: Some(value.as_str()),
+            })
+        })
+}
+
+/// 処理し終えたメッセージの次のオフセットをコミットする
+fn commit(consumer: &StreamConsumer, message: &OwnedMessage) -> KafkaResult<()> {
+    let mut offsets = TopicPartitionList::new();
+    offsets.add_partition_offset(
+        message.topic(),
+        message.partition(),
+        Offset::Offset(message.offset() + 1),
+    )?;
+    consumer.commit(&offsets, CommitMode::Async)
+}
+
+/// メッセージのタイムスタンプ（発行時刻）
+fn timestamp(message: &OwnedMessage) -> Option<SystemTime> {
+    let millis = u64::try_from(message.timestamp().to_millis()?).ok()?;
+    UNIX_EPOCH.checked_add(Duration::from_millis(millis))
+}
+
+/// 受信したメッセージのヘッダーを属性にする（UTF-8 でない値は除く）
+fn attributes(message: &impl Message) -> HashMap<String, String> {
+    message
+        .headers()
+        .map(|headers| {
+            headers
+                .iter()
+                .filter_map(|header| {
+                    let value = std::str::from_utf8(header.value?).ok()?;
+                    Some((header.key.to_string(), value.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[async_trait]
+impl EventBus for KafkaEventBus {
+    /// イベントを集約 ID をキーにしてトピックに発行
+    async fn publish(&self, topic: &str, event: &[u8]) -> Result<(), EventError> {
+        self.publish_with_key(topic, event, aggregate_id(event).as_deref())
+            .await
+    }
+
+    /// イベントを順序キーをキーにしてトピックに発行
+    async fn publish_ordered(
+        &self,
+        topic: &str,
+        event: &[u8],
+        ordering_key: &str,
+    ) -> Result<(), EventError> {
+        self.publish_with_key(topic, event, Some(ordering_key))
+            .await
+    }
+
+    /// 複数のイベントを並行して送り、プロデューサーにまとめて発行させる
+    async fn publish_batch(&self, topic: &str, events: &[&[u8]]) -> Result<(), EventError> {
+        futures::future::try_join_all(
+            events
+                .iter()
+                .map(|event| self.publish_with_key(topic, event, aggregate_id(event).as_deref())),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// トピックのコンシューマーグループに参加してイベントを購読
+    async fn subscribe<F>(&self, topic: &str, handler: F) -> Result<(), EventError>
+    where
+        F: Fn(&[u8]) -> Result<(), EventError> + Send + Sync + 'static,
+    {
+        self.subscribe_with_options(topic, SubscribeOptions::default(), handler)
+            .await
+    }
+}
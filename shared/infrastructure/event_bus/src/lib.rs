@@ -2,6 +2,34 @@
 //!
 //! このモジュールは [`EventBus`] トレイトの異なるメッセージングシステム向けの
 //! 実装を提供します。
+//!
+//! バックエンドは cargo feature でコンパイル時に選択します。
+//!
+//! - `pubsub`: Google Pub/Sub（[`PubSubEventBus`]）
+//! - `kafka`: Apache Kafka（[`KafkaEventBus`]、オンプレミス環境用）
+//! - `memory`: インメモリ（[`InMemoryEventBus`]）
+//!
+//! 実行時は [`EventBusConfig`] から [`from_config`] で構築します。
+//! 発行先は [`Topic`] で指定し、環境ごとの接頭辞は [`TopicNames`] が付けます。
+//! 処理できないメッセージは [`DeadLetterEventBus`] で包むとデッドレター
+//! トピックに退避できます。ハンドラーの失敗は各バックエンドが
+//! [`RetryPolicy`] に従って再試行し、同時に処理する数は購読ごとに
+//! [`SubscribeOptions`] で抑えます。発行時のトレースコンテキストは
+//! メッセージの属性で購読側に引き継ぎます（[`trace_context`]）。
+//! 配信は at-least-once のため、同じイベントを二度処理してはならない購読者は
+//! [`Deduplicator`] で処理済みの `event_id` を記録します。
+//! [`ValidatingEventBus`] で包むと、登録されたスキーマに合わないイベントを
+//! 発行前に拒否できます。
+//! 発行と処理の時間、失敗の回数、購読の遅れは shared_telemetry の
+//! メトリクス（`event_bus.*`）として記録します。
+//! 停止時は [`AnyEventBus::shutdown`] が受信をやめ、処理中のハンドラーを期限
+//! まで待ってから止めます（[`drain`]）。
+//!
+//! イベントストアへの追記と同じトランザクションで書いたアウトボックスの
+//! 発行（トランザクショナル・アウトボックス）は、イベントストアと
+//! PostgreSQL に依存するため `shared_cqrs::OutboxRelay` が担います。
+//! [`AnyEventBus`]
+//! はその発行先（`shared_cqrs::OutboxPublisher`）として使えます。
 
 //! Event Bus 共通インターフェース
 //!
@@ -13,7 +41,22 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub mod dead_letter;
+pub mod dedup;
+pub mod drain;
+pub mod factory;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "memory")]
+pub mod memory;
+mod metrics;
+#[cfg(feature = "pubsub")]
 pub mod pubsub;
+pub mod retry;
+pub mod subscription;
+pub mod topic;
+pub mod trace_context;
+pub mod validation;
 
 /// Event Bus のエラー型
 #[derive(Debug, Error)]
@@ -30,6 +73,18 @@ pub enum EventBusError {
     #[error("Serialization error: {0}")]
     Serialization(String),
 
+    #[error("Configuration error: {0}")]
+    Configuration(String),
+
+    #[error(
+        "Event bus backend '{backend}' is not compiled in (enable the `{feature}` feature of \
+         shared_event_bus)"
+    )]
+    BackendNotCompiled {
+        backend: &'static str,
+        feature: &'static str,
+    },
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -46,8 +101,35 @@ pub trait Event: Serialize + for<'de> Deserialize<'de> + Send + Sync {
 /// Event Bus の共通インターフェース
 #[async_trait]
 pub trait EventBus: Send + Sync {
-    /// イベントを発行
-    async fn publish<E: Event>(&self, topic: &str, event: &E) -> Result<(), EventBusError>;
+    /// イベントを集約 ID を順序キーにして発行
+    async fn publish<E: Event>(&self, topic: &str, event: &E) -> Result<(), EventBusError> {
+        self.publish_ordered(topic, event, event.aggregate_id())
+            .await
+    }
+
+    /// 順序キーを指定してイベントを発行
+    ///
+    /// 同じ順序キーのイベントは発行順に配信される
+    async fn publish_ordered<E: Event>(
+        &self,
+        topic: &str,
+        event: &E,
+        ordering_key: &str,
+    ) -> Result<(), EventBusError>;
+
+    /// 複数のイベントを同じトピックにまとめて発行
+    ///
+    /// 既定の実装は1件ずつ [`EventBus::publish`] で発行する
+    async fn publish_batch<E: Event>(
+        &self,
+        topic: &str,
+        events: &[E],
+    ) -> Result<(), EventBusError> {
+        for event in events {
+            self.publish(topic, event).await?;
+        }
+        Ok(())
+    }
 
     /// イベントを購読
     async fn subscribe<E, F>(
@@ -65,4 +147,17 @@ pub trait EventBus: Send + Sync {
 }
 
 // Re-export
+pub use dead_letter::{DeadLetter, DeadLetterEventBus, DeadLetterPolicy};
+pub use dedup::Deduplicator;
+pub use drain::DEFAULT_SHUTDOWN_DEADLINE;
+pub use factory::{AnyEventBus, EventBusConfig, OutgoingMessage, from_config};
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaEventBus;
+#[cfg(feature = "memory")]
+pub use memory::InMemoryEventBus;
+#[cfg(feature = "pubsub")]
 pub use pubsub::PubSubEventBus;
+pub use retry::RetryPolicy;
+pub use subscription::SubscribeOptions;
+pub use topic::{Topic, TopicNames};
+pub use validation::{EventValidator, ValidatingEventBus};
@@ -0,0 +1,463 @@
+//! インメモリの [`EventBus`] 実装
+//!
+//! 同じプロセス内の購読者へ非同期に配信する。ローカル開発とテスト用で、
+//! メッセージは永続化しない。
+//!
+//! - 発行したメッセージはトピックのすべての購読者に届く（ファンアウト）
+//! - 購読者ごとのタスクが発行順にハンドラーを呼ぶため、発行は配信を待たず、
+//!   遅い購読者が他の購読者を待たせない（[`SubscribeOptions`] で並行に
+//!   処理する数を増やせる）
+//! - ハンドラーが失敗したメッセージは [`RetryPolicy`] に従って呼び直す
+//! - [`Deduplicator`] を設定すると、処理済みのイベントをハンドラーに渡さない
+//! - 発行時のトレースコンテキストを購読者のスパンに引き継ぐ
+//! - [`InMemoryEventBus::flush`] で発行済みのメッセージの処理を待てる
+//! - [`InMemoryEventBus::shutdown`] は未処理のメッセージを再配信できない
+//!   ため、期限まで処理を待ってから購読を止める
+
+use std::{
+    collections::HashMap,
+    pin::pin,
+    sync::{
+        Arc,
+        RwLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use async_trait::async_trait;
+use shared_kernel::{EventBus, EventError};
+use tokio::sync::{Notify, mpsc};
+use tracing::{Instrument, error, warn};
+
+use crate::{
+    Deduplicator,
+    OutgoingMessage,
+    RetryPolicy,
+    SubscribeOptions,
+    dedup,
+    drain::Drain,
+    metrics,
+    trace_context,
+};
+
+/// 購読者に渡すメッセージ
+struct Delivery {
+    data:         Arc<[u8]>,
+    attributes:   Arc<HashMap<String, String>>,
+    published_at: SystemTime,
+}
+
+type Subscriber = mpsc::UnboundedSender<Delivery>;
+
+/// 購読者がまだ処理していないメッセージの数
+#[derive(Default)]
+struct Pending {
+    count: AtomicUsize,
+    idle:  Notify,
+}
+
+impl Pending {
+    fn done(&self) {
+        if self.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.idle.notify_waiters();
+        }
+    }
+}
+
+/// インメモリイベントバス
+#[derive(Default)]
+pub struct InMemoryEventBus {
+    subscribers:  RwLock<HashMap<String, Vec<Subscriber>>>,
+    pending:      Arc<Pending>,
+    retry_policy: RetryPolicy,
+    deduplicator: Option<Arc<Deduplicator>>,
+    drain:        Drain,
+}
+
+impl InMemoryEventBus {
+    /// 新しい [`InMemoryEventBus`] インスタンスを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ハンドラーの再試行の方針を設定する
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// 処理済みのイベントを記録する重複排除を設定する
+    #[must_use]
+    pub fn with_deduplicator(mut self, deduplicator: Arc<Deduplicator>) -> Self {
+        self.deduplicator = Some(deduplicator);
+        self
+    }
+
+    /// 属性付きのメッセージを配信し、生成したメッセージ ID を返す
+    ///
+    /// 順序キーは配信に影響しない
+    pub fn publish_message(&self, message: OutgoingMessage) -> String {
+        self.deliver(&message.topic, &message.data, message.attributes);
+        uuid::Uuid::new_v4().to_string()
+    }
+
+    /// トピックの購読者数
+    pub fn subscriber_count(&self, topic: &str) -> usize {
+        self.subscribers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(topic)
+            .map_or(0, Vec::len)
+    }
+
+    /// 発行済みのメッセージをすべての購読者が処理し終えるまで待つ
+    pub async fn flush(&self) {
+        loop {
+            // 数を確認する前から通知を受け付け、確認の後の完了を取りこぼさない
+            let mut idle = pin!(self.pending.idle.notified());
+            idle.as_mut().enable();
+            if self.pending.count.load(Ordering::Acquire) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    /// 発行済みのメッセージの処理を `deadline` まで待ってから購読を止める
+    ///
+    /// 期限までに処理されなかったメッセージは捨てる
+    pub async fn shutdown(&self, deadline: Duration) {
+        let deadline = tokio::time::Instant::now() + deadline;
+        let _ = tokio::time::timeout_at(deadline, self.flush()).await;
+        self.drain
+            .shutdown(deadline.saturating_duration_since(tokio::time::Instant::now()))
+            .await;
+    }
+
+    /// 設定を指定してイベントを購読
+    ///
+    /// # Errors
+    ///
+    /// インメモリの購読は失敗しない
+    pub async fn subscribe_with_options<F>(
+        &self,
+        topic: &str,
+        options: SubscribeOptions,
+        handler: F,
+    ) -> Result<(), EventError>
+    where
+        F: Fn(&[u8]) -> Result<(), EventError> + Send + Sync + 'static,
+    {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Delivery>();
+        let handler = Arc::new(handler);
+        let pending = Arc::clone(&self.pending);
+        let retry_policy = self.retry_policy;
+        let deduplicator = self.deduplicator.clone();
+        let limit = options.in_flight_limit();
+        let topic_name = topic.to_string();
+        let drain = self.drain.clone();
+        // 停止するか、バスが破棄されて送信側がなくなると終わる
+        self.drain.spawn(async move {
+            loop {
+                // 処理中のメッセージが上限に達している間は受け取らない
+                let permit = tokio::select! {
+                    permit = Arc::clone(&limit).acquire_owned() => match permit {
+                        Ok(permit) => permit,
+                        Err(_) => break,
+                    },
+                    () = drain.stopping() => break,
+                };
+                let delivery = tokio::select! {
+                    delivery = receiver.recv() => match delivery {
+                        Some(delivery) => delivery,
+                        None => break,
+                    },
+                    () = drain.stopping() => break,
+                };
+                metrics::record_lag("memory", &topic_name, delivery.published_at);
+                metrics::record_backlog("memory", &topic_name, receiver.len());
+
+                let handler = Arc::clone(&handler);
+                let deduplicator = deduplicator.clone();
+                let pending = Arc::clone(&pending);
+                let topic_name = topic_name.clone();
+                let task_drain = drain.clone();
+                drain.spawn(async move {
+                    let span = trace_context::consumer_span(&topic_name, &delivery.attributes);
+                    let handling = retry_policy.run(handler.as_ref(), &delivery.data);
+                    let handling = dedup::handle(
+                        deduplicator.as_deref(),
+                        &topic_name,
+                        &delivery.data,
+                        handling,
+                    );
+                    let handling = metrics::timed_handling("memory", &topic_name, handling);
+                    match task_drain.run(handling.instrument(span)).await {
+                        Some(Ok(())) => {},
+                        Some(Err(e)) => {
+                            error!("Error handling event on topic {}: {}", topic_name, e);
+                        },
+                        None => warn!("Abandoned event on topic {} at shutdown", topic_name),
+                    }
+                    pending.done();
+                    drop(permit);
+                });
+            }
+
+            // 受け取っていないメッセージは永続化していないので捨てる
+            receiver.close();
+            let mut dropped = 0;
+            while receiver.try_recv().is_ok() {
+                pending.done();
+                dropped += 1;
+            }
+            if dropped > 0 {
+                warn!(
+                    "Dropped {} unprocessed events on topic {} at shutdown",
+                    dropped, topic_name
+                );
+            }
+        });
+
+        self.subscribers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(topic.to_string())
+            .or_default()
+            .push(sender);
+        Ok(())
+    }
+
+    fn deliver(&self, topic: &str, data: &[u8], mut attributes: HashMap<String, String>) {
+        let started = Instant::now();
+        let subscribers = self.subscribers.read().unwrap_or_else(|e| e.into_inner());
+
+        trace_context::inject(&mut attributes);
+        let data: Arc<[u8]> = Arc::from(data);
+        let attributes = Arc::new(attributes);
+        let published_at = SystemTime::now();
+        for subscriber in subscribers.get(topic).into_iter().flatten() {
+            self.pending.count.fetch_add(1, Ordering::AcqRel);
+            let delivery = Delivery {
+                data: Arc::clone(&data),
+                attributes: Arc::clone(&attributes),
+                published_at,
+            };
+            if subscriber.send(delivery).is_err() {
+                self.pending.done();
+            }
+        }
+        metrics::record_publish("memory", topic, 1, started.elapsed(), true);
+    }
+}
+
+#[async_trait]
+impl EventBus for InMemoryEventBus {
+    async fn publish(&self, topic: &str, event: &[u8]) -> Result<(), EventError> {
+        self.deliver(topic, event, HashMap::new());
+        Ok(())
+    }
+
+    async fn subscribe<F>(&self, topic: &str, handler: F) -> Result<(), EventError>
+    where
+        F: Fn(&[u8]) -> Result<(), EventError> + Send + Sync + 'static,
+    {
+        self.subscribe_with_options(topic, SubscribeOptions::default(), handler)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_delivers_to_topic_subscribers_only() {
+        let bus = InMemoryEventBus::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let sink = received.clone();
+        bus.subscribe("vocabulary-events", move |data| {
+            sink.lock().unwrap().push(data.to_vec());
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        bus.publish("vocabulary-events", b"created").await.unwrap();
+        bus.publish("learning-events", b"ignored").await.unwrap();
+        bus.publish_message(OutgoingMessage {
+            topic: "vocabulary-events".to_string(),
+            data: b"updated".to_vec(),
+            ..Default::default()
+        });
+        bus.flush().await;
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![b"created".to_vec(), b"updated".to_vec()]
+        );
+        assert_eq!(bus.subscriber_count("learning-events"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_publish_batch_delivers_in_order() {
+        let bus = InMemoryEventBus::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let sink = received.clone();
+        bus.subscribe("vocabulary-events", move |data| {
+            sink.lock().unwrap().push(data.to_vec());
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        bus.publish_batch("vocabulary-events", &[b"first", b"second", b"third"])
+            .await
+            .unwrap();
+        bus.flush().await;
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![b"first".to_vec(), b"second".to_vec(), b"third".to_vec()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handler_error_does_not_stop_other_subscribers() {
+        let bus = InMemoryEventBus::new();
+        let delivered = Arc::new(Mutex::new(0));
+
+        bus.subscribe("progress-events", |_| {
+            Err(EventError::Handler("boom".to_string()))
+        })
+        .await
+        .unwrap();
+        let counter = delivered.clone();
+        bus.subscribe("progress-events", move |_| {
+            *counter.lock().unwrap() += 1;
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        bus.publish("progress-events", b"{}").await.unwrap();
+        bus.flush().await;
+
+        assert_eq!(*delivered.lock().unwrap(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_max_in_flight_limits_concurrent_handlers() {
+        let bus = InMemoryEventBus::new();
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let (current, max) = (Arc::clone(&in_flight), Arc::clone(&peak));
+        bus.subscribe_with_options(
+            "vocabulary-events",
+            SubscribeOptions::max_in_flight(2),
+            move |_| {
+                let running = current.fetch_add(1, Ordering::SeqCst) + 1;
+                max.fetch_max(running, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(20));
+                current.fetch_sub(1, Ordering::SeqCst);
+                Ok(())
+            },
+        )
+        .await
+        .unwrap();
+
+        for _ in 0..8 {
+            bus.publish("vocabulary-events", b"{}").await.unwrap();
+        }
+        bus.flush().await;
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_shutdown_waits_for_in_flight_handlers_until_the_deadline() {
+        let bus = InMemoryEventBus::new().with_retry_policy(RetryPolicy::none());
+        let handled = Arc::new(Mutex::new(Vec::new()));
+
+        let sink = Arc::clone(&handled);
+        bus.subscribe("vocabulary-events", move |data| {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            sink.lock().unwrap().push(data.to_vec());
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        // 期限内に処理できるメッセージは処理してから止まる
+        bus.publish("vocabulary-events", b"created").await.unwrap();
+        bus.shutdown(Duration::from_secs(5)).await;
+        assert_eq!(*handled.lock().unwrap(), vec![b"created".to_vec()]);
+
+        // 停止した後のメッセージは処理しない
+        bus.publish("vocabulary-events", b"updated").await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), bus.flush())
+            .await
+            .unwrap();
+        assert_eq!(handled.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_publish_does_not_wait_for_slow_subscribers() {
+        let bus = InMemoryEventBus::new();
+        // 送信側を破棄するまで遅い購読者を止める
+        let (release, wait) = std::sync::mpsc::channel::<()>();
+        let wait = Arc::new(Mutex::new(wait));
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        for name in ["slow", "fast"] {
+            let wait = Arc::clone(&wait);
+            let sink = Arc::clone(&received);
+            bus.subscribe("vocabulary-events", move |data| {
+                if name == "slow" {
+                    let _ = wait.lock().unwrap().recv();
+                }
+                sink.lock().unwrap().push((name, data.to_vec()));
+                Ok(())
+            })
+            .await
+            .unwrap();
+        }
+
+        // 遅い購読者が止まっていても発行は終わり、他の購読者には届く
+        bus.publish("vocabulary-events", b"created").await.unwrap();
+        bus.publish("vocabulary-events", b"updated").await.unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while received.lock().unwrap().len() < 2 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert!(
+            received
+                .lock()
+                .unwrap()
+                .iter()
+                .all(|(name, _)| *name == "fast")
+        );
+
+        drop(release);
+        bus.flush().await;
+
+        let slow: Vec<_> = received
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| *name == "slow")
+            .map(|(_, data)| data.clone())
+            .collect();
+        assert_eq!(slow, vec![b"created".to_vec(), b"updated".to_vec()]);
+    }
+}
@@ -0,0 +1,168 @@
+//! Event Bus のメトリクス
+//!
+//! 射影の遅れを監視できるよう、各バックエンドは shared_telemetry のメーター
+//! （`shared_event_bus`）で次を記録する。属性は `backend`（pubsub / kafka /
+//! memory）と `topic`。
+//!
+//! - `event_bus.publish.duration`: 発行（Pub/Sub
+//!   のバッチは1回の操作）にかかった時間（秒）
+//! - `event_bus.publish.events`: 発行したイベント数
+//! - `event_bus.publish.errors`: 失敗した発行の回数
+//! - `event_bus.handler.duration`:
+//!   ハンドラーの処理（再試行を含む）にかかった時間（秒）
+//! - `event_bus.handler.errors`: 再試行しても失敗した処理の回数
+//! - `event_bus.consumer.lag`: 発行されてから処理を始めるまでの時間（秒）
+//! - `event_bus.subscription.backlog`: 受信済みでまだ処理していないメッセージ数
+//!   （Pub/Sub とインメモリの購読の滞留の目安。Kafka は `consumer.lag` で見る）
+
+use std::{
+    future::Future,
+    sync::LazyLock,
+    time::{Duration, Instant, SystemTime},
+};
+
+use shared_kernel::EventError;
+use shared_telemetry::metrics::{self, Counter, Gauge, Histogram, KeyValue};
+
+struct EventBusMetrics {
+    publish_duration: Histogram<f64>,
+    published_events: Counter<u64>,
+    publish_errors:   Counter<u64>,
+    handler_duration: Histogram<f64>,
+    handler_errors:   Counter<u64>,
+    consumer_lag:     Histogram<f64>,
+    backlog:          Gauge<u64>,
+}
+
+static METRICS: LazyLock<EventBusMetrics> = LazyLock::new(|| {
+    let meter = metrics::meter("shared_event_bus");
+    EventBusMetrics {
+        publish_duration: meter
+            .f64_histogram("event_bus.publish.duration")
+            .with_unit("s")
+            .with_description("Time taken to publish events to a topic")
+            .build(),
+        published_events: meter
+            .u64_counter("event_bus.publish.events")
+            .with_description("Number of events published")
+            .build(),
+        publish_errors:   meter
+            .u64_counter("event_bus.publish.errors")
+            .with_description("Publish operations that failed")
+            .build(),
+        handler_duration: meter
+            .f64_histogram("event_bus.handler.duration")
+            .with_unit("s")
+            .with_description("Time taken to handle a message, including retries")
+            .build(),
+        handler_errors:   meter
+            .u64_counter("event_bus.handler.errors")
+            .with_description("Messages whose handler failed after retries")
+            .build(),
+        consumer_lag:     meter
+            .f64_histogram("event_bus.consumer.lag")
+            .with_unit("s")
+            .with_description("Time between publishing a message and starting to handle it")
+            .build(),
+        backlog:          meter
+            .u64_gauge("event_bus.subscription.backlog")
+            .with_description("Messages received by a subscription but not yet handled")
+            .build(),
+    }
+});
+
+fn attributes(backend: &'static str, topic: &str) -> [KeyValue; 2] {
+    [
+        KeyValue::new("backend", backend),
+        KeyValue::new("topic", topic.to_string()),
+    ]
+}
+
+/// `events` 件のイベントの発行を記録する
+#[cfg_attr(
+    not(any(feature = "pubsub", feature = "kafka", feature = "memory")),
+    allow(dead_code)
+)]
+pub(crate) fn record_publish(
+    backend: &'static str,
+    topic: &str,
+    events: usize,
+    elapsed: Duration,
+    succeeded: bool,
+) {
+    let attributes = attributes(backend, topic);
+    METRICS
+        .publish_duration
+        .record(elapsed.as_secs_f64(), &attributes);
+    if succeeded {
+        METRICS.published_events.add(events as u64, &attributes);
+    } else {
+        METRICS.publish_errors.add(1, &attributes);
+    }
+}
+
+/// `events` 件のイベントの発行を計測する
+#[cfg_attr(not(any(feature = "pubsub", feature = "kafka")), allow(dead_code))]
+pub(crate) async fn timed_publish<Fut, T>(
+    backend: &'static str,
+    topic: &str,
+    events: usize,
+    publishing: Fut,
+) -> Result<T, EventError>
+where
+    Fut: Future<Output = Result<T, EventError>>,
+{
+    let started = Instant::now();
+    let result = publishing.await;
+    record_publish(backend, topic, events, started.elapsed(), result.is_ok());
+    result
+}
+
+/// メッセージの処理を計測する
+#[cfg_attr(
+    not(any(feature = "pubsub", feature = "kafka", feature = "memory")),
+    allow(dead_code)
+)]
+pub(crate) async fn timed_handling<Fut>(
+    backend: &'static str,
+    topic: &str,
+    handling: Fut,
+) -> Result<(), EventError>
+where
+    Fut: Future<Output = Result<(), EventError>>,
+{
+    let started = Instant::now();
+    let result = handling.await;
+
+    let attributes = attributes(backend, topic);
+    METRICS
+        .handler_duration
+        .record(started.elapsed().as_secs_f64(), &attributes);
+    if result.is_err() {
+        METRICS.handler_errors.add(1, &attributes);
+    }
+    result
+}
+
+/// `published_at` に発行されたメッセージの処理を始めたことを記録する
+#[cfg_attr(
+    not(any(feature = "pubsub", feature = "kafka", feature = "memory")),
+    allow(dead_code)
+)]
+pub(crate) fn record_lag(backend: &'static str, topic: &str, published_at: SystemTime) {
+    // 時計のずれで発行時刻が未来になった場合は 0 とする
+    let lag = SystemTime::now()
+        .duration_since(published_at)
+        .unwrap_or_default();
+    METRICS
+        .consumer_lag
+        .record(lag.as_secs_f64(), &attributes(backend, topic));
+}
+
+/// 購読の滞留を記録する
+#[cfg_attr(not(any(feature = "pubsub", feature = "memory")), allow(dead_code))]
+pub(crate) fn record_backlog(backend: &'static str, topic: &str, backlog: usize) {
+    METRICS
+        .backlog
+        .record(backlog as u64, &attributes(backend, topic));
+}
@@ -2,48 +2,149 @@
 //!
 //! このモジュールは [`EventBus`] トレイトの Google Pub/Sub
 //! ベースの実装を提供します。 ドメインイベントの発行と購読機能を実现します。
+//!
+//! `PUBSUB_EMULATOR_HOST` が設定されていればエミュレータに接続し、存在しない
+//! トピックを自動で作成する（[`PubSubEventBus::with_auto_provision`]）。
+//! 本番環境では自動で作成せず、トピックは [`PubSubEventBus::provision`] か
+//! インフラの定義で事前に作成する。
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use async_trait::async_trait;
 use google_cloud_googleapis::pubsub::v1::PubsubMessage;
-use google_cloud_pubsub::{client::Client, publisher::Publisher};
+use google_cloud_pubsub::{
+    client::{Client, ClientConfig},
+    publisher::{Publisher, PublisherConfig},
+    topic::Topic,
+};
 use shared_kernel::{EventBus, EventError};
 use tokio::sync::RwLock;
-use tracing::{error, info};
+use tracing::{Instrument, error, info, warn};
+
+use crate::{
+    Deduplicator,
+    OutgoingMessage,
+    RetryPolicy,
+    SubscribeOptions,
+    dedup,
+    drain::Drain,
+    factory::aggregate_id,
+    metrics,
+    trace_context,
+};
+
+/// 1回の発行 RPC にまとめるメッセージの最大数
+const BUNDLE_SIZE: usize = 100;
+
+/// メッセージをまとめるために待つ最大時間
+const FLUSH_INTERVAL: Duration = Duration::from_millis(50);
 
 /// Google Pub/Sub ベースのイベントバス実装
 pub struct PubSubEventBus {
-    client:     Client,
-    project_id: String,
-    publishers: Arc<RwLock<HashMap<String, Publisher>>>,
+    client:         Client,
+    project_id:     String,
+    publishers:     Arc<RwLock<HashMap<String, Publisher>>>,
+    /// 存在しないトピックを自動で作成するか
+    auto_provision: bool,
+    retry_policy:   RetryPolicy,
+    deduplicator:   Option<Arc<Deduplicator>>,
+    drain:          Drain,
 }
 
 impl PubSubEventBus {
     /// 新しい [`PubSubEventBus`] インスタンスを作成
     ///
+    /// `PUBSUB_EMULATOR_HOST` が設定されている場合はエミュレータに認証なしで
+    /// 接続し、存在しないトピックを自動で作成する
+    ///
     /// # Arguments
     ///
     /// * `project_id` - Google Cloud プロジェクト ID
     ///
     /// # Errors
     ///
-    /// Pub/Sub クライアントの作成に失敗した場合はエラーを返す
+    /// 認証情報の取得や Pub/Sub クライアントの作成に失敗した場合はエラーを返す
     pub async fn new(project_id: String) -> Result<Self, EventError> {
-        let client = Client::new(google_cloud_pubsub::client::ClientConfig {
+        let config = ClientConfig {
             project_id: Some(project_id.clone()),
             ..Default::default()
-        })
-        .await
-        .map_err(|e| EventError::Publish(format!("Failed to create Pub/Sub client: {e}")))?;
+        };
+        // エミュレータの接続先はクライアントが同じ環境変数から読む
+        let emulator_host = std::env::var("PUBSUB_EMULATOR_HOST").ok();
+        let config = match emulator_host {
+            Some(ref host) => {
+                info!("Using Pub/Sub emulator at {}", host);
+                config
+            },
+            None => config.with_auth().await.map_err(|e| {
+                EventError::Publish(format!("Failed to load Google Cloud credentials: {e}"))
+            })?,
+        };
+        let client = Client::new(config)
+            .await
+            .map_err(|e| EventError::Publish(format!("Failed to create Pub/Sub client: {e}")))?;
 
         Ok(Self {
             client,
             project_id,
             publishers: Arc::new(RwLock::new(HashMap::new())),
+            auto_provision: emulator_host.is_some(),
+            retry_policy: RetryPolicy::default(),
+            deduplicator: None,
+            drain: Drain::default(),
         })
     }
 
+    /// 存在しないトピックを発行・購読のときに自動で作成するかを設定する
+    ///
+    /// 本番環境のトピックは事前に作成し、これは開発環境でだけ有効にする。
+    /// 無効の場合、存在しないトピックへの発行や購読はエラーになる
+    #[must_use]
+    pub fn with_auto_provision(mut self, auto_provision: bool) -> Self {
+        self.auto_provision = auto_provision;
+        self
+    }
+
+    /// トピックを作成し、新しく作成した数を返す（既存のトピックはそのまま）
+    ///
+    /// `topics` は [`EventBus::publish`] に渡すトピック名で指定する
+    ///
+    /// # Errors
+    ///
+    /// トピックの確認や作成に失敗した場合はエラーを返す
+    pub async fn provision<I>(&self, topics: I) -> Result<usize, EventError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut created = 0;
+        for topic in topics {
+            let topic_name = Self::get_topic_name(topic.as_ref());
+            if self.create_topic_if_missing(&topic_name).await? {
+                created += 1;
+            }
+        }
+        Ok(created)
+    }
+
+    /// ハンドラーの再試行の方針を設定する
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// 処理済みのイベントを記録する重複排除を設定する
+    #[must_use]
+    pub fn with_deduplicator(mut self, deduplicator: Arc<Deduplicator>) -> Self {
+        self.deduplicator = Some(deduplicator);
+        self
+    }
+
     /// 指定されたトピック用のパブリッシャーを取得または作成
     async fn get_or_create_publisher(&self, topic_name: &str) -> Result<Publisher, EventError> {
         let mut publishers = self.publishers.write().await;
@@ -52,27 +153,184 @@ impl PubSubEventBus {
             return Ok(publisher.clone());
         }
 
-        let topic = self
-            .client
-            .topic(&format!("{}-{}", self.project_id, topic_name));
+        let topic = self.ensure_topic_exists(topic_name).await?;
+        let publisher = topic.new_publisher(Some(Self::publisher_config()));
+        publishers.insert(topic_name.to_string(), publisher.clone());
 
-        // トピックが存在しない場合は作成
-        if !topic
-            .exists(None)
-            .await
-            .map_err(|e| EventError::Publish(format!("Failed to check topic existence: {e}")))?
-        {
-            topic
-                .create(None, None)
+        Ok(publisher)
+    }
+
+    /// 属性付きのメッセージをトピック名そのままに発行し、メッセージ ID を返す
+    ///
+    /// トピックは事前に作成されている必要がある
+    ///
+    /// # Errors
+    ///
+    /// 発行に失敗した場合はエラーを返す
+    pub async fn publish_message(&self, message: OutgoingMessage) -> Result<String, EventError> {
+        let mut attributes = message.attributes;
+        trace_context::inject(&mut attributes);
+
+        let publisher = {
+            let mut publishers = self.publishers.write().await;
+            publishers
+                .entry(message.topic.clone())
+                .or_insert_with(|| {
+                    self.client
+                        .topic(&message.topic)
+                        .new_publisher(Some(Self::publisher_config()))
+                })
+                .clone()
+        };
+
+        let publishing = async {
+            let awaiter = publisher
+                .publish(PubsubMessage {
+                    data: message.data,
+                    attributes,
+                    ordering_key: message.ordering_key.unwrap_or_default(),
+                    ..Default::default()
+                })
+                .await;
+            awaiter
+                .get()
                 .await
-                .map_err(|e| EventError::Publish(format!("Failed to create topic: {e}")))?;
-            info!("Created topic: {}", topic_name);
+                .map_err(|e| EventError::Publish(format!("Failed to publish message: {e}")))
+        };
+        metrics::timed_publish("pubsub", &message.topic, 1, publishing).await
+    }
+
+    /// 購読を止めてから、すべてのパブリッシャーを停止する
+    ///
+    /// 受信をやめ、処理中のハンドラーを `deadline` まで待つ。それまでに
+    /// 終わらなかったメッセージは否定応答して再配信に回す
+    pub async fn shutdown(&self, deadline: Duration) {
+        self.drain.shutdown(deadline).await;
+
+        let mut publishers = self.publishers.write().await;
+        for (topic, mut publisher) in publishers.drain() {
+            publisher.shutdown().await;
+            info!("Publisher for topic {} shut down", topic);
         }
+    }
 
-        let publisher = topic.new_publisher(None);
-        publishers.insert(topic_name.to_string(), publisher.clone());
+    /// 設定を指定してイベントを購読
+    ///
+    /// # Errors
+    ///
+    /// サブスクリプションの作成に失敗した場合はエラーを返す
+    pub async fn subscribe_with_options<F>(
+        &self,
+        topic: &str,
+        options: SubscribeOptions,
+        handler: F,
+    ) -> Result<(), EventError>
+    where
+        F: Fn(&[u8]) -> Result<(), EventError> + Send + Sync + 'static,
+    {
+        let subscription_name = format!("effect-{}-{}", topic, uuid::Uuid::new_v4());
+        let topic_name = Self::get_topic_name(topic);
 
-        Ok(publisher)
+        // サブスクリプションの存在確認と作成
+        self.ensure_subscription_exists(&subscription_name, &topic_name)
+            .await?;
+
+        // spawn に必要な情報をクローン
+        let client = self.client.clone();
+        let handler = Arc::new(handler);
+        let retry_policy = self.retry_policy;
+        let deduplicator = self.deduplicator.clone();
+        let limit = options.in_flight_limit();
+        let subscription_name_clone = subscription_name.clone();
+        let topic_clone = topic.to_string();
+
+        let drain = self.drain.clone();
+
+        // メッセージの受信を開始（停止すると受信をやめる）
+        self.drain.spawn(async move {
+            // タスク内で subscription を新規作成
+            let subscription = client.subscription(&subscription_name_clone);
+
+            loop {
+                let pulled = tokio::select! {
+                    pulled = subscription.pull(100, None) => pulled,
+                    () = drain.stopping() => break,
+                };
+                let stream = match pulled {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        error!("Error pulling messages: {}", e);
+                        tokio::select! {
+                            () = tokio::time::sleep(Duration::from_secs(5)) => continue,
+                            () = drain.stopping() => break,
+                        }
+                    },
+                };
+
+                let mut backlog = stream.len();
+                for msg in stream {
+                    metrics::record_backlog("pubsub", &topic_clone, backlog);
+                    backlog -= 1;
+
+                    // 処理中のメッセージが上限に達している間は次を処理しない
+                    let permit = tokio::select! {
+                        permit = Arc::clone(&limit).acquire_owned() => permit.ok(),
+                        () = drain.stopping() => None,
+                    };
+                    // 停止が始まったら、まだ処理していないメッセージは再配信に回す
+                    let Some(permit) = permit.filter(|_| !drain.is_stopping()) else {
+                        let _ = msg.nack().await;
+                        continue;
+                    };
+
+                    let handler = Arc::clone(&handler);
+                    let deduplicator = deduplicator.clone();
+                    let topic_clone = topic_clone.clone();
+                    let task_drain = drain.clone();
+                    drain.spawn(async move {
+                        // イベントを発行側のトレースの中で処理（失敗したら方針に従って呼び直す）
+                        let span =
+                            trace_context::consumer_span(&topic_clone, &msg.message.attributes);
+                        let data = &msg.message.data;
+                        let handling = retry_policy.run(handler.as_ref(), data);
+                        let handling =
+                            dedup::handle(deduplicator.as_deref(), &topic_clone, data, handling);
+                        let handling = metrics::timed_handling("pubsub", &topic_clone, handling);
+                        if let Some(published_at) = publish_time(&msg.message) {
+                            metrics::record_lag("pubsub", &topic_clone, published_at);
+                        }
+                        match task_drain.run(handling.instrument(span)).await {
+                            Some(Ok(())) => {
+                                // メッセージを確認応答
+                                let _ = msg.ack().await;
+                            },
+                            Some(Err(e)) => {
+                                error!("Error handling event: {}", e);
+                                // リトライ可能にするためメッセージを否定応答
+                                let _ = msg.nack().await;
+                            },
+                            None => {
+                                warn!("Handler did not finish before shutdown, nacking message");
+                                let _ = msg.nack().await;
+                            },
+                        }
+                        drop(permit);
+                    });
+                }
+            }
+        });
+
+        info!("Started subscription: {}", subscription_name);
+        Ok(())
+    }
+
+    /// 発行をまとめるパブリッシャーの設定
+    fn publisher_config() -> PublisherConfig {
+        PublisherConfig {
+            bundle_size: BUNDLE_SIZE,
+            flush_interval: FLUSH_INTERVAL,
+            ..Default::default()
+        }
     }
 
     /// トピック名からイベントタイプを取得
@@ -80,27 +338,103 @@ impl PubSubEventBus {
         format!("effect-{topic}")
     }
 
-    /// サブスクリプションの存在確認と作成
-    async fn ensure_subscription_exists(
+    /// `effect-{topic}` に順序キーを付けて発行する
+    async fn publish_with_key(
         &self,
-        subscription_name: &str,
-        topic_name: &str,
+        topic: &str,
+        event: &[u8],
+        ordering_key: Option<String>,
     ) -> Result<(), EventError> {
-        let full_topic_name = format!("{}-{}", self.project_id, topic_name);
-        let topic = self.client.topic(&full_topic_name);
+        let topic_name = Self::get_topic_name(topic);
+        let message = Self::create_message(topic, event, ordering_key);
 
-        // トピックが存在しない場合は作成
-        if !topic
+        // メッセージを発行
+        let publishing = async {
+            let awaiter = self
+                .get_or_create_publisher(&topic_name)
+                .await?
+                .publish(message)
+                .await;
+            awaiter
+                .get()
+                .await
+                .map_err(|e| EventError::Publish(format!("Failed to publish message: {e}")))
+        };
+        metrics::timed_publish("pubsub", topic, 1, publishing).await?;
+
+        info!("Published event to topic {}", topic_name);
+        Ok(())
+    }
+
+    /// Pub/Sub メッセージを作成
+    fn create_message(topic: &str, event: &[u8], ordering_key: Option<String>) -> PubsubMessage {
+        let mut attributes = HashMap::from([
+            ("topic".to_string(), topic.to_string()),
+            ("timestamp".to_string(), chrono::Utc::now().to_rfc3339()),
+        ]);
+        trace_context::inject(&mut attributes);
+
+        PubsubMessage {
+            data: event.to_vec(),
+            attributes,
+            ordering_key: ordering_key.unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// トピックが存在することを確かめる（自動作成が有効なら作成する）
+    async fn ensure_topic_exists(&self, topic_name: &str) -> Result<Topic, EventError> {
+        if self.auto_provision {
+            self.create_topic_if_missing(topic_name).await?;
+            return Ok(self.topic(topic_name));
+        }
+
+        let topic = self.topic(topic_name);
+        let exists = topic
             .exists(None)
             .await
-            .map_err(|e| EventError::Handler(format!("Failed to check topic existence: {e}")))?
-        {
-            topic
-                .create(None, None)
-                .await
-                .map_err(|e| EventError::Handler(format!("Failed to create topic: {e}")))?;
-            info!("Created topic: {}", topic_name);
+            .map_err(|e| EventError::Bus(format!("Failed to check topic existence: {e}")))?;
+        if !exists {
+            return Err(EventError::Bus(format!(
+                "Topic {} does not exist (provision it or set PUBSUB_AUTO_PROVISION=true)",
+                topic.fully_qualified_name()
+            )));
         }
+        Ok(topic)
+    }
+
+    /// トピックが存在しなければ作成し、作成したかを返す
+    async fn create_topic_if_missing(&self, topic_name: &str) -> Result<bool, EventError> {
+        let topic = self.topic(topic_name);
+        let exists = topic
+            .exists(None)
+            .await
+            .map_err(|e| EventError::Bus(format!("Failed to check topic existence: {e}")))?;
+        if exists {
+            return Ok(false);
+        }
+
+        topic
+            .create(None, None)
+            .await
+            .map_err(|e| EventError::Bus(format!("Failed to create topic: {e}")))?;
+        info!("Created topic: {}", topic.fully_qualified_name());
+        Ok(true)
+    }
+
+    /// プロジェクト ID を付けた Pub/Sub 上のトピック
+    fn topic(&self, topic_name: &str) -> Topic {
+        self.client
+            .topic(&format!("{}-{}", self.project_id, topic_name))
+    }
+
+    /// サブスクリプションの存在確認と作成
+    async fn ensure_subscription_exists(
+        &self,
+        subscription_name: &str,
+        topic_name: &str,
+    ) -> Result<(), EventError> {
+        let topic = self.ensure_topic_exists(topic_name).await?;
 
         // サブスクリプションを作成
         let subscription = self.client.subscription(subscription_name);
@@ -110,7 +444,11 @@ impl PubSubEventBus {
             subscription
                 .create(
                     topic.fully_qualified_name(),
-                    google_cloud_pubsub::subscription::SubscriptionConfig::default(),
+                    // 同じ順序キーのメッセージを発行順に配信する
+                    google_cloud_pubsub::subscription::SubscriptionConfig {
+                        enable_message_ordering: true,
+                        ..Default::default()
+                    },
                     None,
                 )
                 .await
@@ -124,37 +462,59 @@ impl PubSubEventBus {
     }
 }
 
+/// メッセージが Pub/Sub に発行された時刻
+fn publish_time(message: &PubsubMessage) -> Option<SystemTime> {
+    let publish_time = message.publish_time.as_ref()?;
+    let seconds = u64::try_from(publish_time.seconds).ok()?;
+    let nanos = u32::try_from(publish_time.nanos).ok()?;
+    UNIX_EPOCH.checked_add(Duration::new(seconds, nanos))
+}
+
 #[async_trait]
 impl EventBus for PubSubEventBus {
-    /// イベントを適切なトピックに発行
+    /// イベントを集約 ID を順序キーにして適切なトピックに発行
     async fn publish(&self, topic: &str, event: &[u8]) -> Result<(), EventError> {
-        let topic_name = Self::get_topic_name(topic);
+        self.publish_with_key(topic, event, aggregate_id(event))
+            .await
+    }
 
-        // タイムスタンプを取得
-        let timestamp = chrono::Utc::now().to_rfc3339();
+    /// イベントを順序キーを指定して適切なトピックに発行
+    async fn publish_ordered(
+        &self,
+        topic: &str,
+        event: &[u8],
+        ordering_key: &str,
+    ) -> Result<(), EventError> {
+        self.publish_with_key(topic, event, Some(ordering_key.to_string()))
+            .await
+    }
 
-        // Pub/Sub メッセージを作成
-        let message = PubsubMessage {
-            data: event.to_vec(),
-            attributes: HashMap::from([
-                ("topic".to_string(), topic.to_string()),
-                ("timestamp".to_string(), timestamp),
-            ]),
-            ..Default::default()
-        };
+    /// 複数のイベントを1回の操作で発行し、パブリッシャーの設定に従って
+    /// RPC をまとめる
+    async fn publish_batch(&self, topic: &str, events: &[&[u8]]) -> Result<(), EventError> {
+        let topic_name = Self::get_topic_name(topic);
+        let messages = events
+            .iter()
+            .map(|event| Self::create_message(topic, event, aggregate_id(event)))
+            .collect();
 
-        // メッセージを発行
-        let awaiter = self
-            .get_or_create_publisher(&topic_name)
-            .await?
-            .publish(message)
-            .await;
-        awaiter
-            .get()
-            .await
-            .map_err(|e| EventError::Publish(format!("Failed to publish message: {e}")))?;
+        let publishing = async {
+            let awaiters = self
+                .get_or_create_publisher(&topic_name)
+                .await?
+                .publish_bulk(messages)
+                .await;
+            for awaiter in awaiters {
+                awaiter
+                    .get()
+                    .await
+                    .map_err(|e| EventError::Publish(format!("Failed to publish message: {e}")))?;
+            }
+            Ok(())
+        };
+        metrics::timed_publish("pubsub", topic, events.len(), publishing).await?;
 
-        info!("Published event to topic {}", topic_name);
+        info!("Published {} events to topic {}", events.len(), topic_name);
         Ok(())
     }
 
@@ -163,49 +523,8 @@ impl EventBus for PubSubEventBus {
     where
         F: Fn(&[u8]) -> Result<(), EventError> + Send + Sync + 'static,
     {
-        let subscription_name = format!("effect-{}-{}", topic, uuid::Uuid::new_v4());
-        let topic_name = Self::get_topic_name(topic);
-
-        // サブスクリプションの存在確認と作成
-        self.ensure_subscription_exists(&subscription_name, &topic_name)
-            .await?;
-
-        // spawn に必要な情報をクローン
-        let client = self.client.clone();
-        let handler = Arc::new(handler);
-        let subscription_name_clone = subscription_name.clone();
-
-        // メッセージの受信を開始
-        tokio::spawn(async move {
-            // タスク内で subscription を新規作成
-            let subscription = client.subscription(&subscription_name_clone);
-
-            loop {
-                let stream = match subscription.pull(100, None).await {
-                    Ok(stream) => stream,
-                    Err(e) => {
-                        error!("Error pulling messages: {}", e);
-                        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                        continue;
-                    },
-                };
-
-                for msg in stream {
-                    // イベントを処理
-                    if let Err(e) = handler(&msg.message.data) {
-                        error!("Error handling event: {}", e);
-                        // リトライ可能にするためメッセージを否定応答
-                        let _ = msg.nack().await;
-                    } else {
-                        // メッセージを確認応答
-                        let _ = msg.ack().await;
-                    }
-                }
-            }
-        });
-
-        info!("Started subscription: {}", subscription_name);
-        Ok(())
+        self.subscribe_with_options(topic, SubscribeOptions::default(), handler)
+            .await
     }
 }
 
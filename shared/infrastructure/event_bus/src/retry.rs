@@ -0,0 +1,168 @@
+//! 購読ハンドラーの再試行
+//!
+//! どのバックエンドもハンドラーが失敗したメッセージを [`RetryPolicy`] に
+//! 従って待ちながら呼び直し、それでも失敗したときだけバックエンドの失敗処理
+//! （Pub/Sub の nack など）に回す。
+//!
+//! 待ち時間は指数的に延び（上限あり）、同時に失敗した購読者が同じ時刻に
+//! 呼び直さないようジッターで縮める。デシリアライズの失敗のように何度
+//! 呼んでも結果が変わらないエラーは再試行しない（[`is_retryable`]）。
+
+use std::time::Duration;
+
+use rand::Rng;
+use shared_kernel::EventError;
+use tracing::warn;
+
+/// 再試行の方針
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// ハンドラーを呼ぶ最大回数（1 なら再試行しない）
+    pub max_attempts:    u32,
+    /// 1回目の再試行までの待ち時間
+    pub initial_backoff: Duration,
+    /// 待ち時間の上限
+    pub max_backoff:     Duration,
+    /// 再試行ごとに待ち時間に掛ける倍率
+    pub multiplier:      f64,
+    /// 待ち時間をランダムに縮める割合（0.0〜1.0）
+    pub jitter:          f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts:    5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff:     Duration::from_secs(10),
+            multiplier:      2.0,
+            jitter:          0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 再試行しない方針
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// `attempt` 回目の失敗の後に待つ時間（ジッターなし）
+    pub fn base_backoff(&self, attempt: u32) -> Duration {
+        let exponent = i32::try_from(attempt.saturating_sub(1)).unwrap_or(i32::MAX);
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        Duration::from_secs_f64(backoff.min(self.max_backoff.as_secs_f64()))
+    }
+
+    /// `attempt` 回目の失敗の後に待つ時間
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = 1.0 - rand::thread_rng().gen_range(0.0..=jitter);
+        self.base_backoff(attempt).mul_f64(factor)
+    }
+
+    /// ハンドラーを方針に従って呼び、最後の結果を返す
+    ///
+    /// # Errors
+    ///
+    /// 再試行しないエラーが返されたか、最大回数まで失敗した場合はエラーを返す
+    pub async fn run<F>(&self, handler: &F, data: &[u8]) -> Result<(), EventError>
+    where
+        F: Fn(&[u8]) -> Result<(), EventError> + ?Sized,
+    {
+        let mut attempt = 1;
+        loop {
+            match handler(data) {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= self.max_attempts || !is_retryable(&e) => return Err(e),
+                Err(e) => {
+                    let backoff = self.backoff(attempt);
+                    warn!(
+                        "Handler failed (attempt {}/{}), retrying in {:?}: {}",
+                        attempt, self.max_attempts, backoff, e
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                },
+            }
+        }
+    }
+}
+
+/// 再試行で成功しうるエラーか
+///
+/// メッセージ自体が不正なエラー（シリアライズ、デシリアライズ、不正な
+/// イベント）は再試行しない
+pub fn is_retryable(error: &EventError) -> bool {
+    !matches!(
+        error,
+        EventError::Serialization(_) | EventError::Deserialization(_) | EventError::InvalidEvent(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts:    4,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff:     Duration::from_millis(3),
+            multiplier:      2.0,
+            jitter:          0.5,
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_up_to_the_limit_with_jitter() {
+        let policy = policy();
+        assert_eq!(policy.base_backoff(1), Duration::from_millis(1));
+        assert_eq!(policy.base_backoff(2), Duration::from_millis(2));
+        assert_eq!(policy.base_backoff(3), Duration::from_millis(3));
+        assert_eq!(policy.base_backoff(30), Duration::from_millis(3));
+
+        for _ in 0..100 {
+            let backoff = policy.backoff(2);
+            assert!(backoff >= Duration::from_millis(1) && backoff <= Duration::from_millis(2));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_only_retryable_errors() {
+        let policy = policy();
+        let calls = AtomicU32::new(0);
+
+        // 一時的な失敗は成功するまで呼び直す
+        let flaky = |_: &[u8]| {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(EventError::Store("connection reset".to_string()))
+            } else {
+                Ok(())
+            }
+        };
+        policy.run(&flaky, b"{}").await.unwrap();
+        assert_eq!(calls.swap(0, Ordering::SeqCst), 3);
+
+        // 最大回数で諦める
+        let failing = |_: &[u8]| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(EventError::Handler("unavailable".to_string()))
+        };
+        assert!(policy.run(&failing, b"{}").await.is_err());
+        assert_eq!(calls.swap(0, Ordering::SeqCst), 4);
+
+        // 不正なメッセージは再試行しない
+        let malformed = |_: &[u8]| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(EventError::Deserialization("missing field".to_string()))
+        };
+        assert!(policy.run(&malformed, b"{").await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
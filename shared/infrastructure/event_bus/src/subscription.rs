@@ -0,0 +1,42 @@
+//! 購読の設定
+//!
+//! 既定では購読ごとにメッセージを1件ずつ発行順に処理する。Meilisearch の
+//! インデックス更新や AI の呼び出しのように1件の処理が重いハンドラーは、
+//! [`SubscribeOptions::max_in_flight`] で同時に処理する数の上限を決めて
+//! 並行に処理できる。上限に達している間は新しいメッセージを受信しないため、
+//! 下流のシステムに流れ込む量を購読ごとに抑えられる。
+
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+
+/// 購読の設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubscribeOptions {
+    /// 同時に処理するメッセージの最大数（1 なら発行順に1件ずつ処理する）
+    ///
+    /// 2 以上にすると、同じ順序キーのメッセージも処理の順序は保証されない
+    pub max_in_flight: usize,
+}
+
+impl Default for SubscribeOptions {
+    fn default() -> Self {
+        Self { max_in_flight: 1 }
+    }
+}
+
+impl SubscribeOptions {
+    /// 同時に処理するメッセージの最大数を指定して作る
+    pub fn max_in_flight(max_in_flight: usize) -> Self {
+        Self { max_in_flight }
+    }
+
+    /// 処理中のメッセージの数を上限までに抑えるセマフォ
+    #[cfg_attr(
+        not(any(feature = "pubsub", feature = "kafka", feature = "memory")),
+        allow(dead_code)
+    )]
+    pub(crate) fn in_flight_limit(&self) -> Arc<Semaphore> {
+        Arc::new(Semaphore::new(self.max_in_flight.max(1)))
+    }
+}
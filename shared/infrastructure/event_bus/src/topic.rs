@@ -0,0 +1,174 @@
+//! トピックのレジストリ
+//!
+//! 発行先のトピックを文字列ではなく [`Topic`] で指定し、綴りの誤りで存在
+//! しないトピックに発行することを防ぐ。バックエンド上のトピック名は
+//! [`TopicNames`] が環境ごとの接頭辞を付けて作る。
+//!
+//! ```text
+//! TopicNames::new("effect-staging").name(Topic::Vocabulary)
+//!   → "effect-staging-vocabulary-events"
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use crate::EventBusError;
+
+/// 既定のトピック名の接頭辞
+pub const DEFAULT_TOPIC_PREFIX: &str = "effect";
+
+/// Bounded Context ごとのイベントのトピック
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topic {
+    Vocabulary,
+    Learning,
+    User,
+    Algorithm,
+    Ai,
+    Progress,
+    /// どのコンテキストにも属さないイベント
+    Unknown,
+}
+
+impl Topic {
+    /// すべてのトピック
+    pub const ALL: [Self; 7] = [
+        Self::Vocabulary,
+        Self::Learning,
+        Self::User,
+        Self::Algorithm,
+        Self::Ai,
+        Self::Progress,
+        Self::Unknown,
+    ];
+
+    /// 接頭辞を付ける前のトピック名
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Vocabulary => "vocabulary-events",
+            Self::Learning => "learning-events",
+            Self::User => "user-events",
+            Self::Algorithm => "algorithm-events",
+            Self::Ai => "ai-events",
+            Self::Progress => "progress-events",
+            Self::Unknown => "unknown-events",
+        }
+    }
+
+    /// コンテキスト名（`vocabulary` など）からトピックを引く
+    pub fn from_context(context: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .filter(|topic| *topic != Self::Unknown)
+            .find(|topic| topic.name().strip_suffix("-events") == Some(context))
+    }
+
+    /// `{コンテキスト}.{イベント名}` 形式のイベントタイプからトピックを引く
+    pub fn for_event_type(event_type: &str) -> Option<Self> {
+        event_type
+            .split_once('.')
+            .and_then(|(context, _)| Self::from_context(context))
+    }
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Topic {
+    type Err = EventBusError;
+
+    /// 接頭辞を付ける前のトピック名から変換する
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|topic| topic.name() == s)
+            .ok_or_else(|| EventBusError::Configuration(format!("Unknown topic: {s}")))
+    }
+}
+
+/// 環境ごとの接頭辞を付けたトピック名
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicNames {
+    prefix: String,
+}
+
+impl Default for TopicNames {
+    fn default() -> Self {
+        Self::new(DEFAULT_TOPIC_PREFIX)
+    }
+}
+
+impl TopicNames {
+    /// 接頭辞を指定して作る（空文字列なら接頭辞を付けない）
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    /// 接頭辞
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// バックエンド上のトピック名
+    pub fn name(&self, topic: Topic) -> String {
+        if self.prefix.is_empty() {
+            topic.name().to_string()
+        } else {
+            format!("{}-{}", self.prefix, topic.name())
+        }
+    }
+
+    /// バックエンド上のトピック名から [`Topic`] を引く（他の環境の名前は None）
+    pub fn parse(&self, name: &str) -> Option<Topic> {
+        Topic::ALL
+            .into_iter()
+            .find(|topic| self.name(*topic) == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_types_are_routed_by_context() {
+        assert_eq!(
+            Topic::for_event_type("vocabulary.ItemCreated"),
+            Some(Topic::Vocabulary)
+        );
+        assert_eq!(
+            Topic::for_event_type("ai.ResponseGenerated"),
+            Some(Topic::Ai)
+        );
+        assert_eq!(Topic::for_event_type("vocabluary.ItemCreated"), None);
+        assert_eq!(Topic::for_event_type("unknown.SomeEvent"), None);
+        assert_eq!(Topic::for_event_type("ItemCreated"), None);
+
+        assert_eq!("progress-events".parse::<Topic>().unwrap(), Topic::Progress);
+        assert!("progres-events".parse::<Topic>().is_err());
+    }
+
+    #[test]
+    fn test_names_are_prefixed_per_environment() {
+        let staging = TopicNames::new("effect-staging");
+        assert_eq!(
+            staging.name(Topic::Vocabulary),
+            "effect-staging-vocabulary-events"
+        );
+        assert_eq!(
+            staging.parse("effect-staging-learning-events"),
+            Some(Topic::Learning)
+        );
+        assert_eq!(staging.parse("effect-learning-events"), None);
+
+        assert_eq!(
+            TopicNames::default().name(Topic::User),
+            "effect-user-events"
+        );
+        assert_eq!(TopicNames::new("").name(Topic::User), "user-events");
+    }
+}
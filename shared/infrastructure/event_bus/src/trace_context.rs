@@ -0,0 +1,149 @@
+//! W3C トレースコンテキストの伝播
+//!
+//! 発行時に現在のスパンのコンテキストをメッセージの属性（`traceparent` /
+//! `tracestate`）に書き込み、購読側はそれを親にしたスパンの中でハンドラーを
+//! 呼ぶ。コマンドサービスで始まったトレースが射影や検索インデックスの更新
+//! まで1本につながる。
+//!
+//! イベントストアに保存してから読むイベントは、メタデータの
+//! `trace_context` で同じようにトレースを引き継ぐ（[`inject_into_metadata`] /
+//! [`extract_from_metadata`]）。
+//!
+//! OpenTelemetry のレイヤーが登録されていない場合は何も書き込まない。
+
+use std::collections::HashMap;
+
+use opentelemetry::{Context, propagation::TextMapPropagator};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use shared_kernel::{EventMetadata, TraceContext};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// W3C トレースコンテキストのヘッダー名
+const TRACEPARENT: &str = "traceparent";
+
+/// 現在のスパンのトレースコンテキストを属性に書き込む
+pub fn inject(attributes: &mut HashMap<String, String>) {
+    let context = Span::current().context();
+    TraceContextPropagator::new().inject_context(&context, attributes);
+}
+
+/// 属性のトレースコンテキストを親にした、メッセージを処理するスパンを作る
+pub fn consumer_span(topic: &str, attributes: &HashMap<String, String>) -> Span {
+    let parent = TraceContextPropagator::new().extract(attributes);
+    let span = tracing::info_span!("event_bus.handle", topic);
+    span.set_parent(parent);
+    span
+}
+
+/// 現在のスパンのトレースコンテキストをイベントのメタデータに書き込む
+///
+/// トレースがなければ `trace_context` は None になる
+pub fn inject_into_metadata(metadata: &mut EventMetadata) {
+    let mut carrier = HashMap::new();
+    inject(&mut carrier);
+    metadata.trace_context = carrier
+        .get(TRACEPARENT)
+        .and_then(|traceparent| from_traceparent(traceparent));
+}
+
+/// イベントのメタデータのトレースコンテキストを取り出す
+///
+/// 処理するスパンの親にする（`span.set_parent(extract_from_metadata(&
+/// metadata))`）。 `trace_context` がなければ空のコンテキストを返す。
+pub fn extract_from_metadata(metadata: &EventMetadata) -> Context {
+    let carrier: HashMap<String, String> = metadata
+        .trace_context
+        .iter()
+        .map(|trace_context| {
+            // サンプリングの判定は発行側で済んでいるため、記録する前提で続ける
+            let traceparent = format!("00-{}-{}-01", trace_context.trace_id, trace_context.span_id);
+            (TRACEPARENT.to_string(), traceparent)
+        })
+        .collect();
+    TraceContextPropagator::new().extract(&carrier)
+}
+
+/// `traceparent`（`00-<trace_id>-<span_id>-<flags>`）を分解する
+fn from_traceparent(traceparent: &str) -> Option<TraceContext> {
+    let mut parts = traceparent.split('-');
+    let (_version, trace_id, span_id) = (parts.next()?, parts.next()?, parts.next()?);
+    Some(TraceContext {
+        trace_id:       trace_id.to_string(),
+        span_id:        span_id.to_string(),
+        parent_span_id: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry::trace::TracerProvider as _;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    const TRACE_ID: &str = "0af7651916cd43dd8448eb211c80319c";
+
+    #[test]
+    fn test_consumer_span_continues_the_publishers_trace() {
+        let provider = opentelemetry_sdk::trace::TracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let received = HashMap::from([(
+                "traceparent".to_string(),
+                format!("00-{TRACE_ID}-b7ad6b7169203331-01"),
+            )]);
+
+            let _entered = consumer_span("vocabulary-events", &received).entered();
+            let mut published = HashMap::new();
+            inject(&mut published);
+
+            // 同じトレースの、新しいスパンとして続く
+            let traceparent = &published["traceparent"];
+            assert!(traceparent.starts_with(&format!("00-{TRACE_ID}-")));
+            assert!(!traceparent.contains("b7ad6b7169203331"));
+        });
+    }
+
+    #[test]
+    fn test_nothing_is_injected_without_a_trace() {
+        let mut attributes = HashMap::new();
+        inject(&mut attributes);
+        assert!(attributes.is_empty());
+
+        let mut metadata = EventMetadata::new("item-1");
+        inject_into_metadata(&mut metadata);
+        assert!(metadata.trace_context.is_none());
+    }
+
+    #[test]
+    fn test_event_metadata_carries_the_trace_to_the_consumer() {
+        use opentelemetry::trace::TraceContextExt;
+
+        let provider = opentelemetry_sdk::trace::TracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let received = HashMap::from([(
+                "traceparent".to_string(),
+                format!("00-{TRACE_ID}-b7ad6b7169203331-01"),
+            )]);
+            let _entered = consumer_span("vocabulary-commands", &received).entered();
+
+            let mut metadata = EventMetadata::new("item-1");
+            inject_into_metadata(&mut metadata);
+            let trace_context = metadata.trace_context.clone().unwrap();
+            assert_eq!(trace_context.trace_id, TRACE_ID);
+
+            // 射影側はイベントを発行したスパンを親にする
+            let parent = extract_from_metadata(&metadata);
+            let span_context = parent.span().span_context().clone();
+            assert_eq!(span_context.trace_id().to_string(), TRACE_ID);
+            assert_eq!(span_context.span_id().to_string(), trace_context.span_id);
+            assert!(span_context.is_remote());
+        });
+    }
+}
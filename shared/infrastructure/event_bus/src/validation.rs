@@ -0,0 +1,167 @@
+//! 発行前のスキーマ検証
+//!
+//! [`ValidatingEventBus`] で包んだバスは、発行するイベントを
+//! [`EventValidator`]（Domain Events Service のスキーマレジストリなど）で
+//! 検証し、登録されたスキーマに合わないイベントを送信前に拒否する。
+//! 不正なイベントが購読者に届いてから射影で失敗するのを防ぐ。
+//!
+//! イベントタイプはイベントの JSON の `event_type`（トップレベルか
+//! `metadata.event_type`）から取り出す。イベントタイプのないイベントは
+//! 検証できないため拒否する。バッチは1件でも不正なら1件も発行しない。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shared_kernel::{EventBus, EventError};
+use tracing::warn;
+
+/// 発行前にイベントを検証する
+#[async_trait]
+pub trait EventValidator: Send + Sync {
+    /// イベントが `event_type` の登録されたスキーマに合うかを検証する
+    ///
+    /// # Errors
+    ///
+    /// スキーマに合わない場合は [`EventError::InvalidEvent`]、検証できな
+    /// かった場合はその他のエラーを返す（どちらも発行しない）
+    async fn validate(&self, event_type: &str, event: &[u8]) -> Result<(), EventError>;
+}
+
+/// 発行前にスキーマを検証するイベントバス
+pub struct ValidatingEventBus<B> {
+    inner:     B,
+    validator: Arc<dyn EventValidator>,
+}
+
+impl<B> ValidatingEventBus<B>
+where
+    B: EventBus,
+{
+    /// `inner` を包んだインスタンスを作成
+    pub fn new(inner: B, validator: Arc<dyn EventValidator>) -> Self {
+        Self { inner, validator }
+    }
+
+    /// 包んでいるイベントバス
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    async fn validate(&self, topic: &str, event: &[u8]) -> Result<(), EventError> {
+        let Some(event_type) = event_type(event) else {
+            return Err(EventError::InvalidEvent(format!(
+                "Event published to {topic} has no event_type to validate"
+            )));
+        };
+
+        self.validator
+            .validate(&event_type, event)
+            .await
+            .inspect_err(|e| warn!("Rejected {} event for {}: {}", event_type, topic, e))
+    }
+}
+
+#[async_trait]
+impl<B> EventBus for ValidatingEventBus<B>
+where
+    B: EventBus,
+{
+    async fn publish(&self, topic: &str, event: &[u8]) -> Result<(), EventError> {
+        self.validate(topic, event).await?;
+        self.inner.publish(topic, event).await
+    }
+
+    async fn publish_ordered(
+        &self,
+        topic: &str,
+        event: &[u8],
+        ordering_key: &str,
+    ) -> Result<(), EventError> {
+        self.validate(topic, event).await?;
+        self.inner.publish_ordered(topic, event, ordering_key).await
+    }
+
+    async fn publish_batch(&self, topic: &str, events: &[&[u8]]) -> Result<(), EventError> {
+        for event in events {
+            self.validate(topic, event).await?;
+        }
+        self.inner.publish_batch(topic, events).await
+    }
+
+    async fn subscribe<F>(&self, topic: &str, handler: F) -> Result<(), EventError>
+    where
+        F: Fn(&[u8]) -> Result<(), EventError> + Send + Sync + 'static,
+    {
+        self.inner.subscribe(topic, handler).await
+    }
+}
+
+/// イベントの JSON から `event_type`（トップレベルか
+/// `metadata.event_type`）を取り出す
+fn event_type(event: &[u8]) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_slice(event).ok()?;
+    let event_type = value
+        .get("event_type")
+        .or_else(|| value.get("metadata")?.get("event_type"))?;
+    event_type.as_str().map(ToString::to_string)
+}
+
+#[cfg(all(test, feature = "memory"))]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::InMemoryEventBus;
+
+    /// `spelling` のない語彙イベントを拒否する
+    struct SpellingRequired;
+
+    #[async_trait]
+    impl EventValidator for SpellingRequired {
+        async fn validate(&self, event_type: &str, event: &[u8]) -> Result<(), EventError> {
+            let value: serde_json::Value = serde_json::from_slice(event)
+                .map_err(|e| EventError::InvalidEvent(e.to_string()))?;
+            if event_type.starts_with("vocabulary.") && value.get("spelling").is_none() {
+                return Err(EventError::InvalidEvent("spelling is required".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_not_matching_the_schema_are_not_published() {
+        let bus = ValidatingEventBus::new(InMemoryEventBus::new(), Arc::new(SpellingRequired));
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let sink = Arc::clone(&received);
+        bus.subscribe("vocabulary-events", move |data| {
+            sink.lock().unwrap().push(data.to_vec());
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        let valid: &[u8] = br#"{"event_type":"vocabulary.ItemCreated","spelling":"apple"}"#;
+        let invalid: &[u8] = br#"{"metadata":{"event_type":"vocabulary.ItemCreated"}}"#;
+        bus.publish("vocabulary-events", valid).await.unwrap();
+        assert!(matches!(
+            bus.publish("vocabulary-events", invalid).await,
+            Err(EventError::InvalidEvent(_))
+        ));
+        // イベントタイプのないイベントは検証できない
+        assert!(matches!(
+            bus.publish("vocabulary-events", br#"{"spelling":"bank"}"#)
+                .await,
+            Err(EventError::InvalidEvent(_))
+        ));
+        // 1件でも不正なバッチは発行しない
+        assert!(
+            bus.publish_batch("vocabulary-events", &[valid, invalid])
+                .await
+                .is_err()
+        );
+        bus.inner().flush().await;
+
+        assert_eq!(*received.lock().unwrap(), vec![valid.to_vec()]);
+    }
+}
@@ -0,0 +1,16 @@
+//! メモリバックエンドのみの構成で local_stack の例が動くことを確認する
+
+#![cfg(feature = "memory")]
+
+#[path = "../examples/local_stack.rs"]
+mod local_stack;
+
+#[tokio::test]
+async fn test_local_stack_runs_with_memory_backends() {
+    let cached = local_stack::run().await.unwrap();
+
+    assert_eq!(
+        cached,
+        br#"{"event_type":"VocabularyItemCreated"}"#.to_vec()
+    );
+}
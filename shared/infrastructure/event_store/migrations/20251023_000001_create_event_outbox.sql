@@ -0,0 +1,21 @@
+-- トランザクショナル・アウトボックス
+-- PostgresEventStore::with_outbox がイベントと同じトランザクションで書き込み、
+-- shared_cqrs の OutboxRelay が outbox_id 順にイベントバスへ発行する
+
+CREATE TABLE IF NOT EXISTS event_outbox (
+    outbox_id BIGSERIAL PRIMARY KEY,
+    event_id UUID NOT NULL UNIQUE REFERENCES events (event_id),
+    aggregate_id UUID NOT NULL,
+    aggregate_type VARCHAR(255) NOT NULL,
+    event_type VARCHAR(255) NOT NULL,
+    event_version INTEGER NOT NULL,
+    payload JSONB NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    published_at TIMESTAMPTZ,               -- 発行済みの日時（未発行は NULL）
+    attempts INTEGER NOT NULL DEFAULT 0,    -- 発行に失敗した回数
+    last_error TEXT
+);
+
+CREATE INDEX idx_event_outbox_unpublished ON event_outbox (outbox_id)
+    WHERE published_at IS NULL;
@@ -0,0 +1,12 @@
+-- イベントストア全体での位置（キャッチアップ購読用）
+--
+-- position は採番順にコミットされるとは限らないため、読み出し側は
+-- 実行中のトランザクションより前にコミットされた行だけを返す
+-- （transaction_id < pg_snapshot_xmin(pg_current_snapshot())）。
+-- これにより、後から小さい position がコミットされて読み飛ばすことを防ぐ
+
+ALTER TABLE events ADD COLUMN IF NOT EXISTS position BIGSERIAL;
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS transaction_id XID8 NOT NULL DEFAULT pg_current_xact_id();
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_events_position ON events (position);
@@ -0,0 +1,15 @@
+-- イベント本体を Protobuf（BYTEA）でも保存できるようにする
+-- payload_format が 'json' なら event_data、'protobuf' なら event_payload に本体を持つ
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS payload_format VARCHAR(16) NOT NULL DEFAULT 'json',
+    ADD COLUMN IF NOT EXISTS schema_version INTEGER NOT NULL DEFAULT 1,
+    ADD COLUMN IF NOT EXISTS event_payload BYTEA;
+
+ALTER TABLE events ALTER COLUMN event_data DROP NOT NULL;
+
+ALTER TABLE events
+    ADD CONSTRAINT events_payload_format_check CHECK (
+        (payload_format = 'json' AND event_data IS NOT NULL)
+        OR (payload_format = 'protobuf' AND event_payload IS NOT NULL)
+    );
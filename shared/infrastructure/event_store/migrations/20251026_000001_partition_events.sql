@@ -0,0 +1,131 @@
+-- events を集約の種類ごとのリストパーティションに分け、さらに月ごとの
+-- レンジパーティションに分ける
+--
+-- 語彙のストリームが増えても、ユーザーや進捗のストリームの読み込みは
+-- 自分のパーティションだけを見る（load_events は aggregate_type で絞り込む）。
+--
+-- パーティションテーブルの一意制約はパーティションキーを含む必要があるため、
+-- (stream_id, event_version) の一意制約の代わりに event_streams.version を
+-- ストリームの行ロックの下で更新して楽観的ロックを行う。
+
+-- ストリームの現在のバージョン
+ALTER TABLE event_streams ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 0;
+
+UPDATE event_streams s
+SET version = v.version
+FROM (
+    SELECT stream_id, MAX(event_version) AS version
+    FROM events
+    GROUP BY stream_id
+) v
+WHERE s.stream_id = v.stream_id;
+
+ALTER TABLE events RENAME TO events_unpartitioned;
+
+CREATE SEQUENCE IF NOT EXISTS events_position_seq_partitioned;
+
+CREATE TABLE events (
+    event_id UUID NOT NULL DEFAULT gen_random_uuid(),
+    stream_id UUID NOT NULL REFERENCES event_streams (stream_id),
+    aggregate_id UUID NOT NULL,
+    aggregate_type VARCHAR(255) NOT NULL,
+    event_type VARCHAR(255) NOT NULL,
+    event_version INTEGER NOT NULL,
+    payload_format VARCHAR(16) NOT NULL DEFAULT 'json',
+    schema_version INTEGER NOT NULL DEFAULT 1,
+    event_data JSONB,
+    event_payload BYTEA,
+    metadata JSONB,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    position BIGINT NOT NULL DEFAULT nextval('events_position_seq_partitioned'),
+    transaction_id XID8 NOT NULL DEFAULT pg_current_xact_id(),
+    PRIMARY KEY (aggregate_type, created_at, event_id),
+    CONSTRAINT events_payload_format_check CHECK (
+        (payload_format = 'json' AND event_data IS NOT NULL)
+        OR (payload_format = 'protobuf' AND event_payload IS NOT NULL)
+    )
+) PARTITION BY LIST (aggregate_type);
+
+-- 集約の種類のパーティション（月ごとに分け、範囲外は既定のパーティションに入る）と
+-- p_month を含む月のパーティションを作る
+CREATE OR REPLACE FUNCTION ensure_event_partition(p_aggregate_type TEXT, p_month DATE)
+RETURNS VOID AS $$
+DECLARE
+    v_parent TEXT := 'events_' || regexp_replace(lower(p_aggregate_type), '[^a-z0-9_]', '_', 'g');
+    v_from DATE := date_trunc('month', p_month)::DATE;
+    v_child TEXT := v_parent || '_' || to_char(v_from, 'YYYYMM');
+BEGIN
+    IF to_regclass(v_parent) IS NULL THEN
+        EXECUTE format(
+            'CREATE TABLE %I PARTITION OF events FOR VALUES IN (%L) PARTITION BY RANGE (created_at)',
+            v_parent, p_aggregate_type
+        );
+        EXECUTE format('CREATE TABLE %I PARTITION OF %I DEFAULT', v_parent || '_default', v_parent);
+    END IF;
+
+    IF to_regclass(v_child) IS NULL THEN
+        EXECUTE format(
+            'CREATE TABLE %I PARTITION OF %I FOR VALUES FROM (%L) TO (%L)',
+            v_child, v_parent, v_from, (v_from + INTERVAL '1 month')::DATE
+        );
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+-- 想定外の集約の種類
+CREATE TABLE events_default PARTITION OF events DEFAULT;
+
+-- 既存の集約の種類は、移行するイベントの月と今月・来月のパーティションを作る
+-- （新しい集約の種類は PostgresEventStore::ensure_partitions で作るまで
+-- events_default に入る）
+DO $$
+DECLARE
+    r RECORD;
+BEGIN
+    FOR r IN
+        SELECT DISTINCT aggregate_type, date_trunc('month', created_at)::DATE AS month
+        FROM events_unpartitioned
+        UNION
+        SELECT t.aggregate_type, m::DATE
+        FROM (SELECT DISTINCT aggregate_type FROM events_unpartitioned) t,
+            generate_series(
+                date_trunc('month', now()),
+                date_trunc('month', now()) + INTERVAL '1 month',
+                INTERVAL '1 month'
+            ) AS m
+    LOOP
+        PERFORM ensure_event_partition(r.aggregate_type, r.month);
+    END LOOP;
+END;
+$$;
+
+INSERT INTO events (
+    event_id, stream_id, aggregate_id, aggregate_type, event_type, event_version,
+    payload_format, schema_version, event_data, event_payload, metadata,
+    occurred_at, created_at, position, transaction_id
+)
+SELECT
+    event_id, stream_id, aggregate_id, aggregate_type, event_type, event_version,
+    payload_format, schema_version, event_data, event_payload, metadata,
+    occurred_at, created_at, position, transaction_id
+FROM events_unpartitioned;
+
+SELECT setval(
+    'events_position_seq_partitioned',
+    COALESCE((SELECT MAX(position) FROM events_unpartitioned), 0) + 1,
+    false
+);
+
+-- event_outbox の外部キーも一緒に削除する（パーティションテーブルの event_id
+-- だけでは一意にならないため参照できない）
+DROP TABLE events_unpartitioned CASCADE;
+DROP FUNCTION IF EXISTS get_next_event_version(UUID);
+ALTER SEQUENCE events_position_seq_partitioned RENAME TO events_position_seq;
+ALTER SEQUENCE events_position_seq OWNED BY events.position;
+
+CREATE INDEX idx_events_aggregate ON events (aggregate_type, aggregate_id, event_version);
+CREATE INDEX idx_events_stream_id_version ON events (stream_id, event_version);
+CREATE INDEX idx_events_event_type ON events (event_type);
+CREATE INDEX idx_events_occurred_at ON events (occurred_at);
+CREATE INDEX idx_events_position ON events (position);
@@ -0,0 +1,13 @@
+-- クリプトシュレッディング用のユーザーごとの暗号鍵
+-- CryptoShreddingEventStore が個人情報のフィールドをこの鍵で暗号化して保存する。
+-- 鍵を削除すると（encryption_key を NULL にすると）過去のイベントを書き換えずに
+-- 個人情報だけを読めなくできる。削除済みの行は残し、同じユーザーの鍵を
+-- 作り直さないようにする
+
+CREATE TABLE IF NOT EXISTS encryption_keys (
+    subject_id UUID PRIMARY KEY,            -- 鍵の持ち主（ユーザー ID）
+    encryption_key BYTEA,                   -- AES-256 の鍵（削除済みは NULL）
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    destroyed_at TIMESTAMPTZ,
+    CHECK ((encryption_key IS NULL) = (destroyed_at IS NOT NULL))
+);
@@ -0,0 +1,7 @@
+-- 相関 ID でイベントを検索するためのインデックス
+-- EventStore::load_by_correlation_id が、1つの処理（例: AI 生成の依頼）から
+-- 複数のコンテキストにまたがって発生したイベントを集めるのに使う
+
+CREATE INDEX IF NOT EXISTS idx_events_correlation_id
+    ON events ((metadata ->> 'correlation_id'))
+    WHERE metadata ? 'correlation_id';
@@ -0,0 +1,5 @@
+-- ストリームの削除（墓標）
+-- EventStore::delete_stream がストリームのイベントとスナップショットを削除し、
+-- deleted_at を記録する。削除したストリームへの追記は拒否する
+
+ALTER TABLE event_streams ADD COLUMN deleted_at TIMESTAMPTZ;
@@ -0,0 +1,21 @@
+-- テナントごとのストリームの名前空間
+-- with_tenant でテナントを指定した Event Store は、そのテナントのストリームだけを
+-- 読み書きする。既存の行は既定のテナント（空文字列）になる
+
+ALTER TABLE event_streams ADD COLUMN tenant_id VARCHAR(255) NOT NULL DEFAULT '';
+ALTER TABLE event_streams DROP CONSTRAINT event_streams_aggregate_id_aggregate_type_key;
+ALTER TABLE event_streams
+    ADD CONSTRAINT event_streams_stream_unique UNIQUE (tenant_id, aggregate_id, aggregate_type);
+
+ALTER TABLE events ADD COLUMN tenant_id VARCHAR(255) NOT NULL DEFAULT '';
+DROP INDEX IF EXISTS idx_events_aggregate;
+CREATE INDEX idx_events_aggregate ON events (aggregate_type, tenant_id, aggregate_id, event_version);
+CREATE INDEX idx_events_tenant_position ON events (tenant_id, position);
+
+ALTER TABLE snapshots ADD COLUMN tenant_id VARCHAR(255) NOT NULL DEFAULT '';
+ALTER TABLE snapshots DROP CONSTRAINT snapshots_aggregate_unique;
+ALTER TABLE snapshots
+    ADD CONSTRAINT snapshots_aggregate_unique
+    UNIQUE (tenant_id, aggregate_id, aggregate_type, aggregate_version);
+
+ALTER TABLE event_outbox ADD COLUMN tenant_id VARCHAR(255) NOT NULL DEFAULT '';
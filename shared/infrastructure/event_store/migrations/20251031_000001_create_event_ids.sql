@@ -0,0 +1,13 @@
+-- 呼び出し側が指定した event_id の一意性
+-- パーティションテーブルの events には event_id だけの一意制約を作れないため、
+-- 別のテーブルで保証する。PostgresEventStore は同じストリームに保存済みの
+-- event_id を持つ追記を何もせずに成功として扱う
+
+CREATE TABLE IF NOT EXISTS event_ids (
+    event_id UUID PRIMARY KEY,
+    stream_id UUID NOT NULL REFERENCES event_streams (stream_id),
+    event_version INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_event_ids_stream_id ON event_ids (stream_id);
@@ -0,0 +1,22 @@
+-- イベント種別ごとのインデックス（$by_event_type）
+-- PostgresEventStore が追記と同じトランザクションで書き込み、
+-- load_by_event_type はこのテーブルから位置の順に読んで events と結合する。
+-- events は集約の種類でパーティションを分けているため、集約をまたいで
+-- 1つのイベント種別を読む場合でも全パーティションを走査しない
+
+CREATE TABLE IF NOT EXISTS events_by_event_type (
+    event_type VARCHAR(255) NOT NULL,
+    position BIGINT NOT NULL,
+    tenant_id VARCHAR(255) NOT NULL DEFAULT '',
+    aggregate_type VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    event_id UUID NOT NULL,
+    PRIMARY KEY (event_type, tenant_id, position)
+);
+
+INSERT INTO events_by_event_type (
+    event_type, position, tenant_id, aggregate_type, created_at, event_id
+)
+SELECT event_type, position, tenant_id, aggregate_type, created_at, event_id
+FROM events
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,94 @@
+-- 既定のパーティションに入ったイベントを、作ったパーティションに移す
+--
+-- パーティションを作る前に追記したイベントは既定のパーティションに入る。
+-- 既定のパーティションに範囲の重なる行があると PostgreSQL はパーティションを
+-- 作れないため、ensure_event_partition は次の手順で作り直す。
+--
+-- 1. 既定のパーティションを切り離す
+-- 2. パーティションを作る
+-- 3. 範囲に入る行を新しいパーティションに写し、既定のパーティションから消す
+-- 4. 既定のパーティションを付け直す
+--
+-- 切り離している間は親テーブルを排他ロックするため、移す行がある場合は
+-- 同じトランザクションの追記と読み込みを待たせる。PostgresEventStore::
+-- ensure_partitions を起動時と定期的に呼び、月が変わる前にパーティションを
+-- 作っておけば、移す行は出ない。
+
+CREATE OR REPLACE FUNCTION ensure_event_partition(p_aggregate_type TEXT, p_month DATE)
+RETURNS VOID AS $$
+DECLARE
+    v_parent TEXT := 'events_' || regexp_replace(lower(p_aggregate_type), '[^a-z0-9_]', '_', 'g');
+    v_default TEXT := v_parent || '_default';
+    v_from DATE := date_trunc('month', p_month)::DATE;
+    v_to DATE := (date_trunc('month', p_month) + INTERVAL '1 month')::DATE;
+    v_child TEXT := v_parent || '_' || to_char(v_from, 'YYYYMM');
+    v_has_rows BOOLEAN;
+BEGIN
+    IF to_regclass(v_parent) IS NULL THEN
+        -- 集約の種類のパーティションがない間のイベントは events_default にある
+        SELECT EXISTS (SELECT 1 FROM events_default WHERE aggregate_type = p_aggregate_type)
+        INTO v_has_rows;
+        IF v_has_rows THEN
+            ALTER TABLE events DETACH PARTITION events_default;
+        END IF;
+
+        EXECUTE format(
+            'CREATE TABLE %I PARTITION OF events FOR VALUES IN (%L) PARTITION BY RANGE (created_at)',
+            v_parent, p_aggregate_type
+        );
+        EXECUTE format('CREATE TABLE %I PARTITION OF %I DEFAULT', v_default, v_parent);
+
+        IF v_has_rows THEN
+            INSERT INTO events SELECT * FROM events_default WHERE aggregate_type = p_aggregate_type;
+            DELETE FROM events_default WHERE aggregate_type = p_aggregate_type;
+            ALTER TABLE events ATTACH PARTITION events_default DEFAULT;
+        END IF;
+    END IF;
+
+    IF to_regclass(v_child) IS NULL THEN
+        EXECUTE format(
+            'SELECT EXISTS (SELECT 1 FROM %I WHERE created_at >= %L AND created_at < %L)',
+            v_default, v_from, v_to
+        ) INTO v_has_rows;
+        IF v_has_rows THEN
+            EXECUTE format('ALTER TABLE %I DETACH PARTITION %I', v_parent, v_default);
+        END IF;
+
+        EXECUTE format(
+            'CREATE TABLE %I PARTITION OF %I FOR VALUES FROM (%L) TO (%L)',
+            v_child, v_parent, v_from, v_to
+        );
+
+        IF v_has_rows THEN
+            EXECUTE format(
+                'INSERT INTO %I SELECT * FROM %I WHERE created_at >= %L AND created_at < %L',
+                v_child, v_default, v_from, v_to
+            );
+            EXECUTE format(
+                'DELETE FROM %I WHERE created_at >= %L AND created_at < %L',
+                v_default, v_from, v_to
+            );
+            EXECUTE format('ALTER TABLE %I ATTACH PARTITION %I DEFAULT', v_parent, v_default);
+        END IF;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+-- マイグレーションの後に既定のパーティションに入った月のパーティションを作り、行を移す
+-- （切り離す表を読んだままにしないよう、対象の月を一時テーブルに写してから回す）
+CREATE TEMP TABLE pending_event_partitions AS
+SELECT DISTINCT aggregate_type, date_trunc('month', created_at)::DATE AS month
+FROM events
+WHERE tableoid::regclass::TEXT LIKE '%\_default';
+
+DO $$
+DECLARE
+    r RECORD;
+BEGIN
+    FOR r IN SELECT aggregate_type, month FROM pending_event_partitions ORDER BY 1, 2 LOOP
+        PERFORM ensure_event_partition(r.aggregate_type, r.month);
+    END LOOP;
+END;
+$$;
+
+DROP TABLE pending_event_partitions;
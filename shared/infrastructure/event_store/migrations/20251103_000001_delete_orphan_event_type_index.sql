@@ -0,0 +1,13 @@
+-- 削除したイベントに残ったイベント種別のインデックスを消す
+--
+-- truncate_stream・delete_stream・apply_retention は、これまで events だけを
+-- 消していた。以降はインデックスも同じ文で消す。
+
+DELETE FROM events_by_event_type i
+WHERE NOT EXISTS (
+    SELECT 1
+    FROM events e
+    WHERE e.aggregate_type = i.aggregate_type
+      AND e.created_at = i.created_at
+      AND e.event_id = i.event_id
+);
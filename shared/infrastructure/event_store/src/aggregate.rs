@@ -0,0 +1,520 @@
+//! イベントからの集約の復元と保存
+//!
+//! [`AggregateRepository`] はイベントを畳み込んで集約を復元する。
+//! [`AggregateCache`]
+//! を設定すると、保存後の状態をバージョン付きでキャッシュし、
+//! 読み込み時はキャッシュのバージョンより後のイベントだけを取得して畳み込む。
+//! [`UpcasterRegistry`] を設定すると、畳み込む前に各イベントを現在の形式に
+//! 変換する。
+
+use std::{marker::PhantomData, sync::Arc};
+
+use serde::{Serialize, de::DeserializeOwned};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::{
+    EventStore,
+    EventStoreError,
+    StoredEvent,
+    cache::{AggregateCache, CachedAggregate},
+    upcasting::UpcasterRegistry,
+};
+
+/// イベントから状態を畳み込む集約
+pub trait Aggregate: Default + Serialize + DeserializeOwned + Send + Sync {
+    /// イベントストア上の集約タイプ
+    const AGGREGATE_TYPE: &'static str;
+
+    /// イベントを1件適用する
+    fn apply(&mut self, event: &serde_json::Value) -> Result<(), EventStoreError>;
+}
+
+/// 集約の状態と、畳み込み済みの最後のイベントのバージョン
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VersionedAggregate<A> {
+    pub state:   A,
+    /// イベントがまだない集約は 0
+    pub version: u32,
+}
+
+impl<A> VersionedAggregate<A> {
+    /// イベントがまだ保存されていない集約か
+    pub fn is_new(&self) -> bool {
+        self.version == 0
+    }
+}
+
+/// 集約のリポジトリ
+pub struct AggregateRepository<A> {
+    store:      Arc<dyn EventStore>,
+    cache:      Option<Arc<AggregateCache>>,
+    upcasters:  Option<Arc<UpcasterRegistry>>,
+    _aggregate: PhantomData<fn() -> A>,
+}
+
+impl<A: Aggregate> AggregateRepository<A> {
+    pub fn new(store: Arc<dyn EventStore>) -> Self {
+        Self {
+            store,
+            cache: None,
+            upcasters: None,
+            _aggregate: PhantomData,
+        }
+    }
+
+    /// 集約の状態をキャッシュする
+    #[must_use]
+    pub fn with_cache(mut self, cache: Arc<AggregateCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// 畳み込む前にイベントを現在の形式に変換する
+    #[must_use]
+    pub fn with_upcasters(mut self, upcasters: UpcasterRegistry) -> Self {
+        self.upcasters = Some(Arc::new(upcasters));
+        self
+    }
+
+    /// 集約を読み込む（イベントがない場合は初期状態とバージョン 0）
+    pub async fn load(&self, aggregate_id: Uuid) -> Result<VersionedAggregate<A>, EventStoreError> {
+        if let Some(cache) = &self.cache {
+            if let Some(loaded) = self.load_from_cache(cache, aggregate_id).await? {
+                return Ok(loaded);
+            }
+            cache.metrics().record_miss();
+        }
+
+        self.load_after(aggregate_id, VersionedAggregate::default())
+            .await
+    }
+
+    /// 保存済みの状態（スナップショットなど）に、そのバージョンより後の
+    /// イベントを畳み込んで読み込む
+    pub async fn load_after(
+        &self,
+        aggregate_id: Uuid,
+        start: VersionedAggregate<A>,
+    ) -> Result<VersionedAggregate<A>, EventStoreError> {
+        let from_version = (!start.is_new()).then_some(start.version);
+        let events = self
+            .store
+            .load_events(aggregate_id, A::AGGREGATE_TYPE, from_version)
+            .await?;
+        let loaded = self.fold(start, events)?;
+        if !loaded.is_new() {
+            self.cache_state(aggregate_id, &loaded.state, loaded.version)
+                .await;
+        }
+        Ok(loaded)
+    }
+
+    /// 読み込んだ集約にイベントを適用して保存する
+    ///
+    /// `aggregate.version` を期待バージョンとして楽観的ロックを行い、
+    /// 保存に成功した場合だけ適用後の状態をキャッシュする
+    pub async fn save(
+        &self,
+        aggregate_id: Uuid,
+        aggregate: VersionedAggregate<A>,
+        events: Vec<serde_json::Value>,
+    ) -> Result<VersionedAggregate<A>, EventStoreError> {
+        if events.is_empty() {
+            return Ok(aggregate);
+        }
+
+        let VersionedAggregate { mut state, version } = aggregate;
+        for event in &events {
+            state.apply(event)?;
+        }
+        let version = self.persist(aggregate_id, &state, version, events).await?;
+        Ok(VersionedAggregate { state, version })
+    }
+
+    /// 適用済みの状態に対応するイベントを保存し、保存後のバージョンを返す
+    ///
+    /// `expected_version` で楽観的ロックを行い、保存に成功した場合だけ
+    /// `state` をキャッシュする
+    pub(crate) async fn persist(
+        &self,
+        aggregate_id: Uuid,
+        state: &A,
+        expected_version: u32,
+        events: Vec<serde_json::Value>,
+    ) -> Result<u32, EventStoreError> {
+        let count = u32::try_from(events.len())
+            .map_err(|_| EventStoreError::Internal("Too many events in one save".to_string()))?;
+
+        self.store
+            .save_events(
+                aggregate_id,
+                A::AGGREGATE_TYPE,
+                events,
+                Some(expected_version),
+            )
+            .await?;
+
+        let version = expected_version + count;
+        self.cache_state(aggregate_id, state, version).await;
+        Ok(version)
+    }
+
+    /// キャッシュから集約を削除する（整合性チェックなどで状態を疑う場合）
+    pub async fn evict(&self, aggregate_id: Uuid) {
+        if let Some(cache) = &self.cache {
+            cache.evict(A::AGGREGATE_TYPE, aggregate_id).await;
+        }
+    }
+
+    /// キャッシュした状態に末尾のイベントを畳み込む
+    ///
+    /// キャッシュがない、復元できない、または末尾のイベントが
+    /// キャッシュのバージョンから連続しない場合は None を返す
+    async fn load_from_cache(
+        &self,
+        cache: &AggregateCache,
+        aggregate_id: Uuid,
+    ) -> Result<Option<VersionedAggregate<A>>, EventStoreError> {
+        let Some(cached) = cache.get(A::AGGREGATE_TYPE, aggregate_id).await else {
+            return Ok(None);
+        };
+        let state = match serde_json::from_value::<A>(cached.state.clone()) {
+            Ok(state) => state,
+            Err(e) => {
+                warn!(
+                    aggregate_id = %aggregate_id,
+                    aggregate_type = A::AGGREGATE_TYPE,
+                    error = %e,
+                    "Discarding cached aggregate that no longer deserializes"
+                );
+                cache.evict(A::AGGREGATE_TYPE, aggregate_id).await;
+                return Ok(None);
+            },
+        };
+
+        let tail = self
+            .store
+            .load_events(aggregate_id, A::AGGREGATE_TYPE, Some(cached.version))
+            .await?;
+        if !is_contiguous(cached.version, &tail) {
+            warn!(
+                aggregate_id = %aggregate_id,
+                aggregate_type = A::AGGREGATE_TYPE,
+                cached_version = cached.version,
+                first_version = tail.first().map(|e| e.event_version),
+                "Cached aggregate is not contiguous with the event stream, reloading"
+            );
+            cache.metrics().record_discontinuity();
+            cache.evict(A::AGGREGATE_TYPE, aggregate_id).await;
+            return Ok(None);
+        }
+
+        let tail_events = tail.len();
+        cache.metrics().record_hit(tail_events);
+        debug!(
+            aggregate_id = %aggregate_id,
+            aggregate_type = A::AGGREGATE_TYPE,
+            cached_version = cached.version,
+            tail_events,
+            "Loaded aggregate from cache"
+        );
+
+        let loaded = self.fold(
+            VersionedAggregate {
+                state,
+                version: cached.version,
+            },
+            tail,
+        )?;
+        if tail_events > 0 {
+            self.cache_state(aggregate_id, &loaded.state, loaded.version)
+                .await;
+        }
+        Ok(Some(loaded))
+    }
+
+    /// イベントを現在の形式に変換して順に適用する
+    fn fold(
+        &self,
+        mut aggregate: VersionedAggregate<A>,
+        events: Vec<StoredEvent>,
+    ) -> Result<VersionedAggregate<A>, EventStoreError> {
+        for event in events {
+            let event = match &self.upcasters {
+                Some(upcasters) => upcasters.upcast(event)?,
+                None => event,
+            };
+            aggregate.state.apply(&event.event_data)?;
+            aggregate.version = event.event_version;
+        }
+        Ok(aggregate)
+    }
+
+    async fn cache_state(&self, aggregate_id: Uuid, state: &A, version: u32) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        match serde_json::to_value(state) {
+            Ok(state) => {
+                cache
+                    .put(
+                        A::AGGREGATE_TYPE,
+                        aggregate_id,
+                        CachedAggregate { version, state },
+                    )
+                    .await;
+            },
+            Err(e) => warn!(
+                aggregate_id = %aggregate_id,
+                aggregate_type = A::AGGREGATE_TYPE,
+                error = %e,
+                "Failed to serialize aggregate for cache"
+            ),
+        }
+    }
+}
+
+/// `version` の次から欠番なく続くイベントか
+fn is_contiguous(version: u32, events: &[StoredEvent]) -> bool {
+    events
+        .iter()
+        .zip(1..)
+        .all(|(event, offset)| event.event_version == version + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use serde::Deserialize;
+    use serde_json::json;
+    use shared_cache::{Cache, MemoryCache};
+
+    use super::*;
+    use crate::{
+        cache::DEFAULT_REMOTE_TTL_SECONDS,
+        testing::{InMemoryEventStore, stored_event},
+    };
+
+    #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+    struct Counter {
+        total:   i64,
+        applied: u32,
+    }
+
+    impl Aggregate for Counter {
+        const AGGREGATE_TYPE: &'static str = "counter";
+
+        fn apply(&mut self, event: &serde_json::Value) -> Result<(), EventStoreError> {
+            let amount = event
+                .get("amount")
+                .and_then(serde_json::Value::as_i64)
+                .ok_or_else(|| EventStoreError::Internal("Missing amount".to_string()))?;
+            self.total += amount;
+            self.applied += 1;
+            Ok(())
+        }
+    }
+
+    fn added(amount: i64) -> serde_json::Value {
+        json!({ "event_type": "Added", "amount": amount })
+    }
+
+    fn cache() -> Arc<AggregateCache> {
+        Arc::new(AggregateCache::new(NonZeroUsize::new(16).unwrap()))
+    }
+
+    #[tokio::test]
+    async fn test_cached_load_fetches_only_events_after_cached_version() {
+        let store = Arc::new(InMemoryEventStore::default());
+        let cache = cache();
+        let repository =
+            AggregateRepository::<Counter>::new(store.clone()).with_cache(cache.clone());
+        // キャッシュを持たない別のインスタンスからの書き込み
+        let other = AggregateRepository::<Counter>::new(store.clone());
+        let id = Uuid::new_v4();
+
+        let saved = repository
+            .save(
+                id,
+                VersionedAggregate::default(),
+                vec![added(1), added(2), added(3)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(saved.version, 3);
+        let current = other.load(id).await.unwrap();
+        other
+            .save(id, current, vec![added(10), added(20)])
+            .await
+            .unwrap();
+
+        let loaded = repository.load(id).await.unwrap();
+
+        assert_eq!(loaded.version, 5);
+        assert_eq!(
+            loaded.state,
+            Counter {
+                total:   36,
+                applied: 5,
+            }
+        );
+        assert_eq!(store.reads(), vec![None, Some(3)]);
+        assert_eq!(cache.metrics().hits(), 1);
+        assert_eq!(cache.metrics().tail_events(), 2);
+
+        // 末尾を畳み込んだ状態がキャッシュされる
+        repository.load(id).await.unwrap();
+        assert_eq!(store.reads().last(), Some(&Some(5)));
+        assert_eq!(cache.metrics().hits(), 2);
+        assert_eq!(cache.metrics().max_tail_events(), 2);
+        assert_eq!(cache.metrics().misses(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_discontinuous_tail_discards_cache_and_reloads() {
+        let store = Arc::new(InMemoryEventStore::default());
+        let cache = cache();
+        let repository =
+            AggregateRepository::<Counter>::new(store.clone()).with_cache(cache.clone());
+        let id = Uuid::new_v4();
+
+        store.push(stored_event(id, Counter::AGGREGATE_TYPE, 1, added(1)));
+        store.push(stored_event(id, Counter::AGGREGATE_TYPE, 2, added(2)));
+        // バージョン 3 が欠けたストリーム
+        store.push(stored_event(id, Counter::AGGREGATE_TYPE, 4, added(4)));
+        store.push(stored_event(id, Counter::AGGREGATE_TYPE, 5, added(5)));
+        cache
+            .put(
+                Counter::AGGREGATE_TYPE,
+                id,
+                CachedAggregate {
+                    version: 2,
+                    state:   json!({ "total": 100, "applied": 2 }),
+                },
+            )
+            .await;
+
+        let loaded = repository.load(id).await.unwrap();
+
+        assert_eq!(loaded.version, 5);
+        assert_eq!(
+            loaded.state,
+            Counter {
+                total:   12,
+                applied: 4,
+            }
+        );
+        assert_eq!(store.reads(), vec![Some(2), None]);
+        assert_eq!(cache.metrics().discontinuities(), 1);
+        assert_eq!(cache.metrics().misses(), 1);
+        assert_eq!(cache.metrics().hits(), 0);
+        // 読み直した状態でキャッシュを置き換える
+        assert_eq!(
+            cache
+                .get(Counter::AGGREGATE_TYPE, id)
+                .await
+                .unwrap()
+                .version,
+            5
+        );
+    }
+
+    #[tokio::test]
+    async fn test_evict_forces_full_reload() {
+        let store = Arc::new(InMemoryEventStore::default());
+        let repository = AggregateRepository::<Counter>::new(store.clone()).with_cache(cache());
+        let id = Uuid::new_v4();
+
+        repository
+            .save(id, VersionedAggregate::default(), vec![added(1)])
+            .await
+            .unwrap();
+        repository.evict(id).await;
+        repository.load(id).await.unwrap();
+
+        assert_eq!(store.reads(), vec![None]);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_save_is_not_cached() {
+        let store = Arc::new(InMemoryEventStore::default());
+        let cache = cache();
+        let repository =
+            AggregateRepository::<Counter>::new(store.clone()).with_cache(cache.clone());
+        let id = Uuid::new_v4();
+
+        let stale = repository.load(id).await.unwrap();
+        repository
+            .save(id, stale.clone(), vec![added(1)])
+            .await
+            .unwrap();
+        let result = repository.save(id, stale, vec![added(50)]).await;
+
+        assert!(matches!(
+            result,
+            Err(EventStoreError::VersionConflict {
+                expected: 0,
+                actual:   1,
+            })
+        ));
+        let loaded = repository.load(id).await.unwrap();
+        assert_eq!(loaded.state.total, 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_saves_and_loads_never_observe_stale_versions() {
+        let store = Arc::new(InMemoryEventStore::default());
+        // 2段目を共有し、1段目はインスタンスごとに持つ
+        let remote: Arc<dyn Cache> = Arc::new(MemoryCache::new());
+        let id = Uuid::new_v4();
+        let writers = 4;
+        let saves_per_writer = 10;
+
+        let handles: Vec<_> = (0..writers)
+            .map(|_| {
+                let store = store.clone();
+                let cache = Arc::new(
+                    AggregateCache::new(NonZeroUsize::new(4).unwrap())
+                        .with_remote(remote.clone(), DEFAULT_REMOTE_TTL_SECONDS),
+                );
+                tokio::spawn(async move {
+                    let repository =
+                        AggregateRepository::<Counter>::new(store.clone()).with_cache(cache);
+                    let mut saved = 0;
+                    while saved < saves_per_writer {
+                        let committed = store.current_version(id);
+                        let loaded = repository.load(id).await.unwrap();
+                        assert!(
+                            loaded.version >= committed,
+                            "loaded v{} after v{} was committed",
+                            loaded.version,
+                            committed
+                        );
+                        assert_eq!(loaded.state.applied, loaded.version);
+                        tokio::task::yield_now().await;
+
+                        match repository.save(id, loaded, vec![added(1)]).await {
+                            Ok(_) => saved += 1,
+                            Err(EventStoreError::VersionConflict { .. }) => {},
+                            Err(e) => panic!("unexpected error: {e}"),
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        let repository = AggregateRepository::<Counter>::new(store.clone()).with_cache(Arc::new(
+            AggregateCache::new(NonZeroUsize::new(4).unwrap())
+                .with_remote(remote, DEFAULT_REMOTE_TTL_SECONDS),
+        ));
+        let loaded = repository.load(id).await.unwrap();
+        let expected = writers * saves_per_writer;
+        assert_eq!(loaded.version, expected);
+        assert_eq!(loaded.state.total, i64::from(expected));
+    }
+}
@@ -0,0 +1,317 @@
+//! コマンドを処理する集約ルート
+//!
+//! 集約は [`AggregateRoot`] としてコマンドの判断（`handle`）とイベントの適用
+//! （`apply`）だけを実装する。未コミットのイベントとバージョンの管理は
+//! [`EventSourced`] が行い、[`AggregateRepository`] の
+//! [`load_root`](AggregateRepository::load_root) と
+//! [`commit`](AggregateRepository::commit) で読み込みと保存を行う。
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use uuid::Uuid;
+
+use crate::{
+    EventStoreError,
+    aggregate::{Aggregate, AggregateRepository, VersionedAggregate},
+};
+
+/// コマンドを受けてイベントを発生させる集約ルート
+///
+/// `AggregateRoot` を実装した型は [`Aggregate`] も実装し、保存した
+/// イベントをデシリアライズして `apply` する
+pub trait AggregateRoot: Default + Serialize + DeserializeOwned + Send + Sync {
+    /// イベントストア上の集約タイプ
+    const AGGREGATE_TYPE: &'static str;
+
+    type Command;
+    type Event: Serialize + DeserializeOwned + Send + Sync;
+    type Error;
+
+    /// 現在の状態でコマンドを検証し、発生させるイベントを返す（状態は変えない）
+    fn handle(&self, command: Self::Command) -> Result<Vec<Self::Event>, Self::Error>;
+
+    /// イベントを1件適用する（検証は `handle` で済んでいるため失敗しない）
+    fn apply(&mut self, event: &Self::Event);
+}
+
+impl<A: AggregateRoot> Aggregate for A {
+    const AGGREGATE_TYPE: &'static str = <A as AggregateRoot>::AGGREGATE_TYPE;
+
+    fn apply(&mut self, event: &serde_json::Value) -> Result<(), EventStoreError> {
+        let event = A::Event::deserialize(event)?;
+        AggregateRoot::apply(self, &event);
+        Ok(())
+    }
+}
+
+/// 集約ルートの状態、保存済みのバージョン、未コミットのイベント
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventSourced<A: AggregateRoot> {
+    state:             A,
+    committed_version: u32,
+    uncommitted:       Vec<A::Event>,
+}
+
+impl<A: AggregateRoot> Default for EventSourced<A> {
+    fn default() -> Self {
+        Self::restore(A::default(), 0)
+    }
+}
+
+impl<A: AggregateRoot> EventSourced<A> {
+    /// 保存済みの状態から復元する
+    pub fn restore(state: A, committed_version: u32) -> Self {
+        Self {
+            state,
+            committed_version,
+            uncommitted: Vec::new(),
+        }
+    }
+
+    /// 保存済みのイベントを順に適用して復元する
+    pub fn from_history<'a>(events: impl IntoIterator<Item = &'a A::Event>) -> Self
+    where
+        A::Event: 'a,
+    {
+        let mut restored = Self::default();
+        for event in events {
+            AggregateRoot::apply(&mut restored.state, event);
+            restored.committed_version += 1;
+        }
+        restored
+    }
+
+    pub fn state(&self) -> &A {
+        &self.state
+    }
+
+    /// 保存済みの最後のイベントのバージョン（イベントがない集約は 0）
+    pub fn committed_version(&self) -> u32 {
+        self.committed_version
+    }
+
+    /// 未コミットのイベントを含めたバージョン
+    pub fn version(&self) -> u32 {
+        self.committed_version + self.uncommitted_count()
+    }
+
+    /// イベントがまだ保存されていない集約か
+    pub fn is_new(&self) -> bool {
+        self.committed_version == 0
+    }
+
+    pub fn uncommitted_events(&self) -> &[A::Event] {
+        &self.uncommitted
+    }
+
+    pub fn has_uncommitted_events(&self) -> bool {
+        !self.uncommitted.is_empty()
+    }
+
+    /// コマンドを処理し、発生したイベントを適用して未コミットに加える
+    ///
+    /// コマンドが拒否された場合は状態を変えない。発生したイベントを返す
+    pub fn execute(&mut self, command: A::Command) -> Result<&[A::Event], A::Error> {
+        let events = self.state.handle(command)?;
+        let start = self.uncommitted.len();
+        for event in events {
+            AggregateRoot::apply(&mut self.state, &event);
+            self.uncommitted.push(event);
+        }
+        Ok(&self.uncommitted[start..])
+    }
+
+    /// 未コミットのイベントを保存済みとして取り出す
+    pub fn take_uncommitted(&mut self) -> Vec<A::Event> {
+        self.committed_version = self.version();
+        std::mem::take(&mut self.uncommitted)
+    }
+
+    fn uncommitted_count(&self) -> u32 {
+        u32::try_from(self.uncommitted.len()).unwrap_or(u32::MAX)
+    }
+}
+
+impl<A: AggregateRoot> AggregateRepository<A> {
+    /// 集約ルートを読み込む（イベントがない場合は初期状態とバージョン 0）
+    pub async fn load_root(&self, aggregate_id: Uuid) -> Result<EventSourced<A>, EventStoreError> {
+        let VersionedAggregate { state, version } = self.load(aggregate_id).await?;
+        Ok(EventSourced::restore(state, version))
+    }
+
+    /// 未コミットのイベントを保存する
+    ///
+    /// 読み込んだ時点のバージョンを期待バージョンとして楽観的ロックを行う。
+    /// 保存に失敗した場合、未コミットのイベントはそのまま残る
+    pub async fn commit(
+        &self,
+        aggregate_id: Uuid,
+        root: &mut EventSourced<A>,
+    ) -> Result<(), EventStoreError> {
+        if !root.has_uncommitted_events() {
+            return Ok(());
+        }
+
+        let events = root
+            .uncommitted_events()
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.persist(aggregate_id, root.state(), root.committed_version(), events)
+            .await?;
+        root.take_uncommitted();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::testing::InMemoryEventStore;
+
+    /// 残高を超える引き出しを拒否する口座
+    #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+    struct Account {
+        opened:  bool,
+        balance: i64,
+    }
+
+    enum AccountCommand {
+        Open,
+        Deposit(i64),
+        Withdraw(i64),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "event_type")]
+    enum AccountEvent {
+        Opened,
+        Deposited { amount: i64 },
+        Withdrawn { amount: i64 },
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum AccountError {
+        AlreadyOpened,
+        NotOpened,
+        InsufficientBalance,
+    }
+
+    impl AggregateRoot for Account {
+        const AGGREGATE_TYPE: &'static str = "account";
+
+        type Command = AccountCommand;
+        type Error = AccountError;
+        type Event = AccountEvent;
+
+        fn handle(&self, command: AccountCommand) -> Result<Vec<AccountEvent>, AccountError> {
+            match command {
+                AccountCommand::Open if self.opened => Err(AccountError::AlreadyOpened),
+                AccountCommand::Open => Ok(vec![AccountEvent::Opened]),
+                _ if !self.opened => Err(AccountError::NotOpened),
+                AccountCommand::Deposit(amount) => Ok(vec![AccountEvent::Deposited { amount }]),
+                AccountCommand::Withdraw(amount) if amount > self.balance => {
+                    Err(AccountError::InsufficientBalance)
+                },
+                AccountCommand::Withdraw(amount) => Ok(vec![AccountEvent::Withdrawn { amount }]),
+            }
+        }
+
+        fn apply(&mut self, event: &AccountEvent) {
+            match event {
+                AccountEvent::Opened => self.opened = true,
+                AccountEvent::Deposited { amount } => self.balance += amount,
+                AccountEvent::Withdrawn { amount } => self.balance -= amount,
+            }
+        }
+    }
+
+    #[test]
+    fn test_execute_tracks_uncommitted_events_and_version() {
+        let mut account = EventSourced::<Account>::default();
+        assert!(account.is_new());
+
+        let raised = account.execute(AccountCommand::Open).unwrap().to_vec();
+        assert_eq!(raised, vec![AccountEvent::Opened]);
+        account.execute(AccountCommand::Deposit(100)).unwrap();
+
+        assert_eq!(account.state().balance, 100);
+        assert_eq!(account.committed_version(), 0);
+        assert_eq!(account.version(), 2);
+        assert_eq!(account.uncommitted_events().len(), 2);
+
+        let taken = account.take_uncommitted();
+        assert_eq!(taken.len(), 2);
+        assert_eq!(account.committed_version(), 2);
+        assert_eq!(account.version(), 2);
+        assert!(!account.has_uncommitted_events());
+        assert!(!account.is_new());
+    }
+
+    #[test]
+    fn test_rejected_command_leaves_state_and_events_untouched() {
+        let mut account = EventSourced::<Account>::from_history(&[
+            AccountEvent::Opened,
+            AccountEvent::Deposited { amount: 30 },
+        ]);
+        assert_eq!(account.committed_version(), 2);
+
+        assert_eq!(
+            account.execute(AccountCommand::Withdraw(50)),
+            Err(AccountError::InsufficientBalance)
+        );
+        assert_eq!(
+            account.execute(AccountCommand::Open),
+            Err(AccountError::AlreadyOpened)
+        );
+        assert_eq!(account.state().balance, 30);
+        assert_eq!(account.version(), 2);
+        assert!(!account.has_uncommitted_events());
+    }
+
+    #[tokio::test]
+    async fn test_commit_and_load_round_trip_through_repository() {
+        let repository =
+            AggregateRepository::<Account>::new(Arc::new(InMemoryEventStore::default()));
+        let id = Uuid::new_v4();
+
+        let mut account = repository.load_root(id).await.unwrap();
+        account.execute(AccountCommand::Open).unwrap();
+        account.execute(AccountCommand::Deposit(80)).unwrap();
+        repository.commit(id, &mut account).await.unwrap();
+        assert_eq!(account.committed_version(), 2);
+
+        account.execute(AccountCommand::Withdraw(30)).unwrap();
+        repository.commit(id, &mut account).await.unwrap();
+
+        let loaded = repository.load_root(id).await.unwrap();
+        assert_eq!(loaded.state(), account.state());
+        assert_eq!(loaded.state().balance, 50);
+        assert_eq!(loaded.committed_version(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_commit_keeps_uncommitted_events() {
+        let repository =
+            AggregateRepository::<Account>::new(Arc::new(InMemoryEventStore::default()));
+        let id = Uuid::new_v4();
+        let mut first = repository.load_root(id).await.unwrap();
+        let mut second = repository.load_root(id).await.unwrap();
+
+        first.execute(AccountCommand::Open).unwrap();
+        repository.commit(id, &mut first).await.unwrap();
+        second.execute(AccountCommand::Open).unwrap();
+        let result = repository.commit(id, &mut second).await;
+
+        assert!(matches!(
+            result,
+            Err(EventStoreError::VersionConflict {
+                expected: 0,
+                actual:   1,
+            })
+        ));
+        assert_eq!(second.committed_version(), 0);
+        assert_eq!(second.uncommitted_events(), &[AccountEvent::Opened]);
+    }
+}
@@ -0,0 +1,299 @@
+//! 畳み込み済み集約のキャッシュ
+//!
+//! 集約の状態をバージョン付きで保持する。プロセス内の LRU を1段目、
+//! [`shared_cache::Cache`]（Redis など）を2段目として参照する。
+//! エントリは常にイベントストアの末尾取得で補正されるため、
+//! 古いバージョンのエントリが残っていても古い状態を返すことはない。
+
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        Arc,
+        Mutex,
+        MutexGuard,
+        PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use shared_cache::Cache;
+use tracing::warn;
+use uuid::Uuid;
+
+/// 2段目のキャッシュのデフォルトの有効期限（秒）
+pub const DEFAULT_REMOTE_TTL_SECONDS: u64 = 3600;
+
+/// キャッシュされた集約の状態
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedAggregate {
+    /// 状態に畳み込み済みの最後のイベントのバージョン
+    pub version: u32,
+    /// 集約の状態
+    pub state:   serde_json::Value,
+}
+
+/// キャッシュのメトリクス
+#[derive(Debug, Default)]
+pub struct AggregateCacheMetrics {
+    hits:            AtomicU64,
+    misses:          AtomicU64,
+    discontinuities: AtomicU64,
+    tail_events:     AtomicU64,
+    max_tail_events: AtomicU64,
+}
+
+impl AggregateCacheMetrics {
+    /// キャッシュから読み込んだ回数
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// 全イベントを読み直した回数（不連続による読み直しを含む）
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// 末尾のイベントのバージョンが連続せず、キャッシュを破棄した回数
+    pub fn discontinuities(&self) -> u64 {
+        self.discontinuities.load(Ordering::Relaxed)
+    }
+
+    /// キャッシュヒット時に末尾から取得したイベント数の合計
+    pub fn tail_events(&self) -> u64 {
+        self.tail_events.load(Ordering::Relaxed)
+    }
+
+    /// キャッシュヒット時に末尾から取得したイベント数の最大値
+    pub fn max_tail_events(&self) -> u64 {
+        self.max_tail_events.load(Ordering::Relaxed)
+    }
+
+    /// ヒット率（読み込みがまだない場合は None）
+    pub fn hit_rate(&self) -> Option<f64> {
+        let hits = self.hits();
+        let total = hits + self.misses();
+        (total > 0).then(|| hits as f64 / total as f64)
+    }
+
+    /// キャッシュヒット1回あたりに末尾から取得したイベント数の平均
+    pub fn average_tail_events(&self) -> Option<f64> {
+        let hits = self.hits();
+        (hits > 0).then(|| self.tail_events() as f64 / hits as f64)
+    }
+
+    pub(crate) fn record_hit(&self, tail_events: usize) {
+        let tail_events = tail_events as u64;
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        self.tail_events.fetch_add(tail_events, Ordering::Relaxed);
+        self.max_tail_events
+            .fetch_max(tail_events, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_discontinuity(&self) {
+        self.discontinuities.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// 2段目のキャッシュ
+struct RemoteTier {
+    cache:       Arc<dyn Cache>,
+    ttl_seconds: u64,
+}
+
+/// 集約の状態のキャッシュ
+///
+/// キーは `(集約タイプ, 集約ID)`。同じキーへの書き込みはバージョンが
+/// 新しい場合だけ1段目に反映する。2段目はバージョンを比較せずに上書きするが、
+/// 古いエントリは読み込み時の末尾取得で補正される
+pub struct AggregateCache {
+    local:   Mutex<LruCache<String, Arc<CachedAggregate>>>,
+    remote:  Option<RemoteTier>,
+    metrics: AggregateCacheMetrics,
+}
+
+impl AggregateCache {
+    /// プロセス内の LRU だけを使うキャッシュを作成
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            local:   Mutex::new(LruCache::new(capacity)),
+            remote:  None,
+            metrics: AggregateCacheMetrics::default(),
+        }
+    }
+
+    /// LRU の後ろに2段目のキャッシュを置く
+    #[must_use]
+    pub fn with_remote(mut self, cache: Arc<dyn Cache>, ttl_seconds: u64) -> Self {
+        self.remote = Some(RemoteTier { cache, ttl_seconds });
+        self
+    }
+
+    pub fn metrics(&self) -> &AggregateCacheMetrics {
+        &self.metrics
+    }
+
+    /// キャッシュされた状態を取得する
+    ///
+    /// 2段目のエラーや壊れたエントリはキャッシュミスとして扱う
+    pub async fn get(
+        &self,
+        aggregate_type: &str,
+        aggregate_id: Uuid,
+    ) -> Option<Arc<CachedAggregate>> {
+        let key = cache_key(aggregate_type, aggregate_id);
+        if let Some(cached) = self.local().get(&key) {
+            return Some(Arc::clone(cached));
+        }
+
+        let remote = self.remote.as_ref()?;
+        let bytes = match remote.cache.get(&key).await {
+            Ok(bytes) => bytes?,
+            Err(e) => {
+                warn!(key = %key, error = %e, "Failed to read cached aggregate");
+                return None;
+            },
+        };
+        match serde_json::from_slice::<CachedAggregate>(&bytes) {
+            Ok(cached) => Some(self.put_local(key, Arc::new(cached))),
+            Err(e) => {
+                warn!(key = %key, error = %e, "Discarding undecodable cached aggregate");
+                None
+            },
+        }
+    }
+
+    /// 状態を保存する
+    pub async fn put(&self, aggregate_type: &str, aggregate_id: Uuid, cached: CachedAggregate) {
+        let key = cache_key(aggregate_type, aggregate_id);
+        let cached = self.put_local(key.clone(), Arc::new(cached));
+
+        let Some(remote) = &self.remote else {
+            return;
+        };
+        let bytes = match serde_json::to_vec(cached.as_ref()) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!(key = %key, error = %e, "Failed to encode aggregate for cache");
+                return;
+            },
+        };
+        if let Err(e) = remote.cache.set(&key, bytes, remote.ttl_seconds).await {
+            warn!(key = %key, error = %e, "Failed to write cached aggregate");
+        }
+    }
+
+    /// 両方の段から状態を削除する
+    pub async fn evict(&self, aggregate_type: &str, aggregate_id: Uuid) {
+        let key = cache_key(aggregate_type, aggregate_id);
+        self.local().pop(&key);
+
+        if let Some(remote) = &self.remote
+            && let Err(e) = remote.cache.delete(&key).await
+        {
+            warn!(key = %key, error = %e, "Failed to evict cached aggregate");
+        }
+    }
+
+    /// 1段目に保存し、保存後のエントリを返す（既存のエントリの方が新しい場合は既存のまま）
+    fn put_local(&self, key: String, cached: Arc<CachedAggregate>) -> Arc<CachedAggregate> {
+        let mut local = self.local();
+        if let Some(existing) = local.get(&key)
+            && existing.version > cached.version
+        {
+            return Arc::clone(existing);
+        }
+        local.put(key, Arc::clone(&cached));
+        cached
+    }
+
+    fn local(&self) -> MutexGuard<'_, LruCache<String, Arc<CachedAggregate>>> {
+        self.local.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// キャッシュのキー
+pub fn cache_key(aggregate_type: &str, aggregate_id: Uuid) -> String {
+    format!("aggregate:{aggregate_type}:{aggregate_id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use shared_cache::MemoryCache;
+
+    use super::*;
+
+    fn capacity(n: usize) -> NonZeroUsize {
+        NonZeroUsize::new(n).unwrap()
+    }
+
+    fn cached(version: u32) -> CachedAggregate {
+        CachedAggregate {
+            version,
+            state: json!({ "version": version }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_older_write_does_not_replace_newer_entry() {
+        let cache = AggregateCache::new(capacity(8));
+        let id = Uuid::new_v4();
+
+        cache.put("collection", id, cached(5)).await;
+        cache.put("collection", id, cached(3)).await;
+
+        assert_eq!(cache.get("collection", id).await.unwrap().version, 5);
+        assert!(cache.get("learning_state", id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_remote_tier_backs_local_lru_and_evict_clears_both() {
+        let remote: Arc<dyn Cache> = Arc::new(MemoryCache::new());
+        let writer = AggregateCache::new(capacity(8))
+            .with_remote(Arc::clone(&remote), DEFAULT_REMOTE_TTL_SECONDS);
+        // 別プロセスを想定し、1段目は共有しない
+        let reader = AggregateCache::new(capacity(1))
+            .with_remote(Arc::clone(&remote), DEFAULT_REMOTE_TTL_SECONDS);
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        writer.put("collection", first, cached(2)).await;
+        writer.put("collection", second, cached(4)).await;
+
+        assert_eq!(reader.get("collection", first).await.unwrap().version, 2);
+        // 容量1の LRU から追い出されても2段目から読める
+        assert_eq!(reader.get("collection", second).await.unwrap().version, 4);
+        assert_eq!(reader.get("collection", first).await.unwrap().version, 2);
+
+        reader.evict("collection", first).await;
+        assert!(reader.get("collection", first).await.is_none());
+        assert!(
+            remote
+                .get(&cache_key("collection", first))
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_hit_rate_and_tail_sizes() {
+        let metrics = AggregateCacheMetrics::default();
+        assert_eq!(metrics.hit_rate(), None);
+
+        metrics.record_hit(0);
+        metrics.record_hit(4);
+        metrics.record_hit(2);
+        metrics.record_miss();
+
+        assert_eq!(metrics.hit_rate(), Some(0.75));
+        assert_eq!(metrics.average_tail_events(), Some(2.0));
+        assert_eq!(metrics.max_tail_events(), 4);
+    }
+}
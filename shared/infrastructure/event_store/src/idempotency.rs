@@ -0,0 +1,58 @@
+//! event_id による追記の重複排除
+//!
+//! Pub/Sub は少なくとも1回の配信なので、同じコマンドが再配信されると同じ
+//! イベントを2回追記しようとする。イベントに `event_id` を付けて保存すると
+//! （JSON の場合は `event_id` または `metadata.event_id`）、Event Store は
+//! 同じストリームにすでに保存した追記を何もせずに成功として扱う。
+//!
+//! 再配信の追記は古い `expected_version` を持つため、重複の確認は
+//! バージョンの確認より先に行う。一部のイベントだけが保存済みの追記や、
+//! 別のストリームに保存済みの `event_id` は
+//! [`EventStoreError::DuplicateEvent`] で拒否する。
+
+use uuid::Uuid;
+
+use crate::EventStoreError;
+
+/// 追記するイベントの `event_id` のうち `stored`
+/// がすでに保存済みのものを調べ、追記全体を飛ばすかを返す
+pub(crate) fn check_duplicates(
+    event_ids: &[Uuid],
+    stored: &[Uuid],
+) -> Result<bool, EventStoreError> {
+    if stored.is_empty() {
+        return Ok(false);
+    }
+    match event_ids.iter().find(|event_id| !stored.contains(event_id)) {
+        // すべて保存済み（再配信）
+        None => Ok(true),
+        Some(_) => Err(EventStoreError::DuplicateEvent(stored[0])),
+    }
+}
+
+/// 一意制約違反を重複したイベントのエラーにする
+pub(crate) fn map_unique_violation(error: sqlx::Error, event_id: Uuid) -> EventStoreError {
+    match &error {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            EventStoreError::DuplicateEvent(event_id)
+        },
+        _ => error.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skips_only_when_every_event_is_stored() {
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(!check_duplicates(&[first, second], &[]).unwrap());
+        assert!(check_duplicates(&[first, second], &[second, first]).unwrap());
+        assert!(matches!(
+            check_duplicates(&[first, second], &[first]),
+            Err(EventStoreError::DuplicateEvent(id)) if id == first
+        ));
+    }
+}
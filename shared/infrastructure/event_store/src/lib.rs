@@ -4,10 +4,39 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
 use thiserror::Error;
 use uuid::Uuid;
 
+pub mod aggregate;
+pub mod aggregate_root;
+pub mod cache;
+mod idempotency;
+mod metrics;
+pub mod payload;
 pub mod postgres;
+pub mod registry;
+pub mod retention;
+pub mod retry;
+pub mod shredding;
+pub mod snapshotting;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+mod tenant;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod upcasting;
+
+pub use aggregate::{Aggregate, AggregateRepository, VersionedAggregate};
+pub use aggregate_root::{AggregateRoot, EventSourced};
+pub use cache::{AggregateCache, AggregateCacheMetrics, CachedAggregate};
+pub use payload::{EncodedEvent, EventPayload, NewEvent, PayloadFormat};
+pub use registry::EventRegistry;
+pub use retention::RetentionPolicy;
+pub use retry::with_optimistic_retry;
+pub use shredding::{CryptoShreddingEventStore, DataKey, EncryptionKeyStore, PostgresKeyStore};
+pub use snapshotting::{AutoSnapshot, SnapshotSerializer};
+pub use upcasting::{DefaultField, RenameField, Upcaster, UpcasterRegistry, UpcastingEventStore};
 
 /// Event Store のエラー型
 #[derive(Error, Debug)]
@@ -26,12 +55,36 @@ pub enum EventStoreError {
 
     #[error("Internal error: {0}")]
     Internal(String),
+
+    #[error("No snapshot covers version {version} of aggregate {aggregate_id}")]
+    SnapshotRequired {
+        aggregate_id: Uuid,
+        version:      u32,
+    },
+
+    #[error("Stream has been deleted: {0}")]
+    StreamDeleted(Uuid),
+
+    #[error("Event already stored: {0}")]
+    DuplicateEvent(Uuid),
+
+    #[error("Encryption key of {0} has been destroyed")]
+    KeyDestroyed(Uuid),
+
+    #[error("Not supported by this event store: {0}")]
+    Unsupported(&'static str),
 }
 
+/// 全イベントの購読（位置の昇順に、追記されたイベントも待ち続けて流す）
+pub type EventSubscription = BoxStream<'static, Result<PositionedEvent, EventStoreError>>;
+
 /// Event Store trait
 #[async_trait]
 pub trait EventStore: Send + Sync {
     /// イベントを保存
+    ///
+    /// `event_id` を持つイベントがすべて同じストリームに保存済みの場合は、
+    /// 何もせずに成功する（少なくとも1回の配信で再実行されたコマンドの追記）
     async fn save_events(
         &self,
         aggregate_id: Uuid,
@@ -40,6 +93,37 @@ pub trait EventStore: Send + Sync {
         expected_version: Option<u32>,
     ) -> Result<(), EventStoreError>;
 
+    /// 複数の集約のイベントを1つのトランザクションでまとめて保存
+    ///
+    /// いずれかのストリームでバージョンが競合した場合は何も保存せず、
+    /// [`EventStoreError::VersionConflict`] を返す（一括インポートなどで、
+    /// 一部の集約だけが保存された状態を残さないため）
+    async fn save_events_batch(&self, _appends: Vec<StreamAppend>) -> Result<(), EventStoreError> {
+        Err(EventStoreError::Unsupported("save_events_batch"))
+    }
+
+    /// 形式を指定してイベントを保存（Protobuf で保存する場合に使う）
+    ///
+    /// 既定の実装は JSON のイベントだけを [`save_events`](Self::save_events)
+    /// で保存する
+    async fn save_encoded_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        events: Vec<NewEvent>,
+        expected_version: Option<u32>,
+    ) -> Result<(), EventStoreError> {
+        let events = events
+            .into_iter()
+            .map(|event| match event.payload {
+                EventPayload::Json(data) => Ok(data),
+                EventPayload::Protobuf(_) => Err(EventStoreError::Unsupported("protobuf payloads")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.save_events(aggregate_id, aggregate_type, events, expected_version)
+            .await
+    }
+
     /// 集約のイベントを読み込み
     async fn load_events(
         &self,
@@ -48,6 +132,44 @@ pub trait EventStore: Send + Sync {
         from_version: Option<u32>,
     ) -> Result<Vec<StoredEvent>, EventStoreError>;
 
+    /// 集約のイベントを新しい順に最大 `limit` 件読み込む
+    ///
+    /// `from_version` 以下（None の場合は最新）のバージョンから遡る。
+    /// 「この単語の直近 10 件の編集」のように、ストリーム全体を読まずに
+    /// 末尾だけを表示する場合に使う。既定の実装は全体を読んでから絞り込む
+    async fn load_events_backward(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        from_version: Option<u32>,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let mut events = self.load_events(aggregate_id, aggregate_type, None).await?;
+        events.reverse();
+        Ok(events
+            .into_iter()
+            .filter(|event| from_version.is_none_or(|version| event.event_version <= version))
+            .take(limit)
+            .collect())
+    }
+
+    /// 集約のイベントを形式を問わず読み込み
+    ///
+    /// 既定の実装は [`load_events`](Self::load_events) の結果を JSON として返す
+    async fn load_encoded_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        from_version: Option<u32>,
+    ) -> Result<Vec<EncodedEvent>, EventStoreError> {
+        Ok(self
+            .load_events(aggregate_id, aggregate_type, from_version)
+            .await?
+            .into_iter()
+            .map(EncodedEvent::from)
+            .collect())
+    }
+
     /// スナップショットを保存
     async fn save_snapshot(
         &self,
@@ -63,12 +185,117 @@ pub trait EventStore: Send + Sync {
         aggregate_id: Uuid,
         aggregate_type: &str,
     ) -> Result<Option<Snapshot>, EventStoreError>;
+
+    /// メタデータの `correlation_id` が一致するイベントを、集約をまたいで
+    /// 保存順に読み込む
+    ///
+    /// 1つの依頼から複数のコンテキストで発生したイベントを追うデバッグ用
+    async fn load_by_correlation_id(
+        &self,
+        _correlation_id: &str,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        Err(EventStoreError::Unsupported("load_by_correlation_id"))
+    }
+
+    /// `before_version` より前のイベントを削除し、削除した件数を返す
+    ///
+    /// 集約はスナップショットから復元するため、`before_version - 1` 以降の
+    /// スナップショットがない場合は何も消さずに
+    /// [`EventStoreError::SnapshotRequired`] を返す。ストリームのバージョンは
+    /// 変わらないので、以降の追記はこれまで通り続きのバージョンになる
+    async fn truncate_stream(
+        &self,
+        _aggregate_id: Uuid,
+        _aggregate_type: &str,
+        _before_version: u32,
+    ) -> Result<u64, EventStoreError> {
+        Err(EventStoreError::Unsupported("truncate_stream"))
+    }
+
+    /// ストリームのイベントとスナップショットを削除し、
+    /// 削除したイベントの件数を返す
+    ///
+    /// ストリームには墓標を残し、以降の追記は
+    /// [`EventStoreError::StreamDeleted`]
+    /// で拒否する（退会したユーザーのストリームを消す場合など）
+    async fn delete_stream(
+        &self,
+        _aggregate_id: Uuid,
+        _aggregate_type: &str,
+    ) -> Result<u64, EventStoreError> {
+        Err(EventStoreError::Unsupported("delete_stream"))
+    }
+
+    /// `from_position` より後のイベントを位置の昇順に最大 `limit` 件読み込む
+    ///
+    /// `filter` に合うイベントだけを返す。返した最後のイベントの位置を次の
+    /// `from_position` にすれば、集約ごとに読まずに全体を順に読み進められる
+    async fn load_all_events(
+        &self,
+        _from_position: i64,
+        _limit: usize,
+        _filter: &EventFilter,
+    ) -> Result<Vec<PositionedEvent>, EventStoreError> {
+        Err(EventStoreError::Unsupported("load_all_events"))
+    }
+
+    /// `from_position` より後の `event_type` のイベントを、集約をまたいで
+    /// 位置の昇順に最大 `limit` 件読み込む
+    ///
+    /// 検索インデックスや通知のように、一部のイベント種別だけを読む軽い
+    /// コンシューマー向け。既定の実装は
+    /// [`load_all_events`](Self::load_all_events) をイベント種別で絞り込む
+    async fn load_by_event_type(
+        &self,
+        event_type: &str,
+        from_position: i64,
+        limit: usize,
+    ) -> Result<Vec<PositionedEvent>, EventStoreError> {
+        self.load_all_events(
+            from_position,
+            limit,
+            &EventFilter::all().event_type(event_type),
+        )
+        .await
+    }
+
+    /// `from_position` より後の全イベントを位置の昇順に購読する
+    ///
+    /// 既存のイベントを読み終えた後も、新しく追記されたイベントを待って
+    /// 流し続ける。プロジェクションは処理済みの位置を保存しておき、
+    /// 再起動時にその位置から
+    /// 購読し直す（0 から購読すると最初のイベントから読む）
+    async fn subscribe_all(
+        &self,
+        _from_position: i64,
+    ) -> Result<EventSubscription, EventStoreError> {
+        Err(EventStoreError::Unsupported("subscribe_all"))
+    }
+
+    /// 読み書きするストリームのテナント（None は既定のテナント）
+    ///
+    /// すべての操作はこのテナントのストリームだけを対象にする
+    fn tenant_id(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// 1つのストリームへの追記（[`EventStore::save_events_batch`] 用）
+#[derive(Debug, Clone)]
+pub struct StreamAppend {
+    pub aggregate_id:     Uuid,
+    pub aggregate_type:   String,
+    pub events:           Vec<serde_json::Value>,
+    /// 期待するバージョン（None の場合は確認しない）
+    pub expected_version: Option<u32>,
 }
 
 /// 保存されたイベント
 #[derive(Debug, Clone)]
 pub struct StoredEvent {
     pub event_id:       Uuid,
+    /// テナント（None は既定のテナント）
+    pub tenant_id:      Option<String>,
     pub aggregate_id:   Uuid,
     pub aggregate_type: String,
     pub event_type:     String,
@@ -79,6 +306,49 @@ pub struct StoredEvent {
     pub created_at:     DateTime<Utc>,
 }
 
+/// イベントストア全体での位置を持つイベント
+#[derive(Debug, Clone)]
+pub struct PositionedEvent {
+    /// イベントストア全体での位置（単調増加）
+    pub position: i64,
+    /// イベント本体
+    pub event:    StoredEvent,
+}
+
+/// 全体を読むときの絞り込み（空の条件は絞り込まない）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    /// 集約の種類（いずれかに一致）
+    pub aggregate_types: Vec<String>,
+    /// イベント種別（いずれかに一致）
+    pub event_types:     Vec<String>,
+}
+
+impl EventFilter {
+    /// 絞り込まない
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// 集約の種類を追加する
+    pub fn aggregate_type(mut self, aggregate_type: impl Into<String>) -> Self {
+        self.aggregate_types.push(aggregate_type.into());
+        self
+    }
+
+    /// イベント種別を追加する
+    pub fn event_type(mut self, event_type: impl Into<String>) -> Self {
+        self.event_types.push(event_type.into());
+        self
+    }
+
+    /// イベントが条件に合うか
+    pub fn matches(&self, event: &StoredEvent) -> bool {
+        (self.aggregate_types.is_empty() || self.aggregate_types.contains(&event.aggregate_type))
+            && (self.event_types.is_empty() || self.event_types.contains(&event.event_type))
+    }
+}
+
 /// スナップショット
 #[derive(Debug, Clone)]
 pub struct Snapshot {
@@ -88,3 +358,73 @@ pub struct Snapshot {
     pub aggregate_data:    serde_json::Value,
     pub created_at:        DateTime<Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(aggregate_type: &str, event_type: &str) -> StoredEvent {
+        StoredEvent {
+            event_id:       Uuid::new_v4(),
+            tenant_id:      None,
+            aggregate_id:   Uuid::new_v4(),
+            aggregate_type: aggregate_type.to_string(),
+            event_type:     event_type.to_string(),
+            event_version:  1,
+            event_data:     serde_json::json!({ "event_type": event_type }),
+            metadata:       None,
+            occurred_at:    Utc::now(),
+            created_at:     Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_event_filter_matches_any_of_each_condition() {
+        let created = event("vocabulary_item", "ItemCreated");
+        let answered = event("learning_session", "QuestionAnswered");
+
+        assert!(EventFilter::all().matches(&created));
+
+        let filter = EventFilter::all()
+            .aggregate_type("vocabulary_item")
+            .aggregate_type("vocabulary_entry");
+        assert!(filter.matches(&created));
+        assert!(!filter.matches(&answered));
+
+        let filter = filter.event_type("ItemUpdated");
+        assert!(!filter.matches(&created));
+    }
+
+    #[tokio::test]
+    async fn test_load_events_backward_reads_newest_first() {
+        let versions = |events: Vec<StoredEvent>| {
+            events
+                .iter()
+                .map(|event| event.event_version)
+                .collect::<Vec<_>>()
+        };
+        let store = testing::InMemoryEventStore::new();
+        let id = Uuid::new_v4();
+        store
+            .save_events(
+                id,
+                "vocabulary_item",
+                vec![serde_json::json!({ "event_type": "ItemUpdated" }); 5],
+                None,
+            )
+            .await
+            .unwrap();
+
+        let latest = store
+            .load_events_backward(id, "vocabulary_item", None, 2)
+            .await
+            .unwrap();
+        assert_eq!(versions(latest), vec![5, 4]);
+
+        let older = store
+            .load_events_backward(id, "vocabulary_item", Some(3), 10)
+            .await
+            .unwrap();
+        assert_eq!(versions(older), vec![3, 2, 1]);
+    }
+}
@@ -0,0 +1,90 @@
+//! Event Store のメトリクス
+//!
+//! 書き込み側の状態をサービスごとに見られるよう、追記のたびに shared_telemetry
+//! のメーター（`shared_event_store`）で次を記録する。属性は `backend`
+//! （postgres / sqlite）と `aggregate_type`。
+//!
+//! - `event_store.append.duration`: 追記にかかった時間（秒）
+//! - `event_store.append.events`: 1回の追記のイベント数
+//! - `event_store.stream.length`: 追記後のストリームの長さ（バージョン）
+//! - `event_store.append.conflicts`: バージョン競合の回数
+
+use std::{sync::LazyLock, time::Duration};
+
+use shared_telemetry::metrics::{self, Counter, Gauge, Histogram, KeyValue};
+
+/// ストリームへの追記の結果
+pub(crate) struct Appended {
+    pub(crate) previous_version: u32,
+    pub(crate) events_count:     usize,
+}
+
+impl Appended {
+    /// 追記後のバージョン
+    pub(crate) fn version(&self) -> u32 {
+        self.previous_version + self.events_count as u32
+    }
+}
+
+struct EventStoreMetrics {
+    append_duration:   Histogram<f64>,
+    events_per_append: Histogram<u64>,
+    stream_length:     Gauge<u64>,
+    conflicts:         Counter<u64>,
+}
+
+static METRICS: LazyLock<EventStoreMetrics> = LazyLock::new(|| {
+    let meter = metrics::meter("shared_event_store");
+    EventStoreMetrics {
+        append_duration:   meter
+            .f64_histogram("event_store.append.duration")
+            .with_unit("s")
+            .with_description("Time taken to append events to a stream")
+            .build(),
+        events_per_append: meter
+            .u64_histogram("event_store.append.events")
+            .with_description("Number of events written by one append")
+            .build(),
+        stream_length:     meter
+            .u64_gauge("event_store.stream.length")
+            .with_description("Stream version after the latest append")
+            .build(),
+        conflicts:         meter
+            .u64_counter("event_store.append.conflicts")
+            .with_description("Appends rejected by the optimistic concurrency check")
+            .build(),
+    }
+});
+
+fn attributes(backend: &'static str, aggregate_type: &str) -> [KeyValue; 2] {
+    [
+        KeyValue::new("backend", backend),
+        KeyValue::new("aggregate_type", aggregate_type.to_string()),
+    ]
+}
+
+/// コミットした追記を記録する
+pub(crate) fn record_append(
+    backend: &'static str,
+    aggregate_type: &str,
+    elapsed: Duration,
+    appended: &Appended,
+) {
+    let attributes = attributes(backend, aggregate_type);
+    METRICS
+        .append_duration
+        .record(elapsed.as_secs_f64(), &attributes);
+    METRICS
+        .events_per_append
+        .record(appended.events_count as u64, &attributes);
+    METRICS
+        .stream_length
+        .record(u64::from(appended.version()), &attributes);
+}
+
+/// バージョン競合を記録する
+pub(crate) fn record_conflict(backend: &'static str, aggregate_type: &str) {
+    METRICS
+        .conflicts
+        .add(1, &attributes(backend, aggregate_type));
+}
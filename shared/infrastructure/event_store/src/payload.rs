@@ -0,0 +1,271 @@
+//! イベント本体の形式（JSON / Protobuf）
+//!
+//! イベントは prost の型なので、JSON を経由せずにそのままバイナリで保存できる。
+//! Protobuf で保存したイベントは `load_encoded_events` で読み込み、
+//! [`EventPayload::decode`] で元の型に戻す。JSON と違い enum の値も保たれる。
+//!
+//! Protobuf は JSON の読み込み API（`load_events` など）では読めないため、
+//! 集約の種類ごとにどちらかに揃えて使う。
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{EventStoreError, StoredEvent, upcasting};
+
+/// 保存形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadFormat {
+    Json,
+    Protobuf,
+}
+
+impl PayloadFormat {
+    /// データベースに保存する名前
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Protobuf => "protobuf",
+        }
+    }
+
+    /// データベースに保存した名前から変換する
+    pub fn parse(value: &str) -> Result<Self, EventStoreError> {
+        match value {
+            "json" => Ok(Self::Json),
+            "protobuf" => Ok(Self::Protobuf),
+            other => Err(EventStoreError::Internal(format!(
+                "Unknown payload format: {other}"
+            ))),
+        }
+    }
+}
+
+/// イベント本体
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventPayload {
+    Json(serde_json::Value),
+    Protobuf(Vec<u8>),
+}
+
+impl EventPayload {
+    /// prost のメッセージをシリアライズする
+    pub fn from_message<M: prost::Message>(message: &M) -> Self {
+        Self::Protobuf(message.encode_to_vec())
+    }
+
+    /// 保存形式
+    pub fn format(&self) -> PayloadFormat {
+        match self {
+            Self::Json(_) => PayloadFormat::Json,
+            Self::Protobuf(_) => PayloadFormat::Protobuf,
+        }
+    }
+
+    /// Protobuf のイベント本体を prost のメッセージに戻す
+    pub fn decode<M: prost::Message + Default>(&self) -> Result<M, EventStoreError> {
+        match self {
+            Self::Protobuf(bytes) => M::decode(bytes.as_slice()).map_err(|e| {
+                EventStoreError::Internal(format!("Failed to decode protobuf payload: {e}"))
+            }),
+            Self::Json(_) => Err(EventStoreError::Internal(
+                "Cannot decode a JSON payload as protobuf".to_string(),
+            )),
+        }
+    }
+}
+
+/// 保存するイベント
+#[derive(Debug, Clone)]
+pub struct NewEvent {
+    /// イベント ID（None の場合は保存時に採番する。指定すると同じ ID の
+    /// 再追記を重複として扱う）
+    pub event_id:       Option<Uuid>,
+    pub event_type:     String,
+    /// 本体の構造のバージョン
+    pub schema_version: u32,
+    pub payload:        EventPayload,
+    /// 発生日時（None の場合は保存した日時）
+    pub occurred_at:    Option<DateTime<Utc>>,
+    /// メタデータ（`correlation_id` などで検索できる）
+    pub metadata:       Option<serde_json::Value>,
+}
+
+impl NewEvent {
+    /// prost のメッセージから作成（スキーマバージョンは 1）
+    pub fn protobuf<M: prost::Message>(event_type: impl Into<String>, message: &M) -> Self {
+        Self {
+            event_id:       None,
+            event_type:     event_type.into(),
+            schema_version: upcasting::INITIAL_SCHEMA_VERSION,
+            payload:        EventPayload::from_message(message),
+            occurred_at:    None,
+            metadata:       None,
+        }
+    }
+
+    /// `event_type` を含む JSON から作成（`event_id`、`schema_version`、
+    /// `occurred_at`、`metadata` フィールドがあれば使う。`event_id` は
+    /// `metadata.event_id` からも読む）
+    pub fn json(event_data: serde_json::Value) -> Result<Self, EventStoreError> {
+        let event_type = event_data
+            .get("event_type")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| EventStoreError::Internal("Missing event_type".to_string()))?
+            .to_string();
+        let schema_version = event_data
+            .get("schema_version")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|version| u32::try_from(version).ok())
+            .unwrap_or(upcasting::INITIAL_SCHEMA_VERSION);
+        let occurred_at = event_data
+            .get("occurred_at")
+            .and_then(|v| v.as_str())
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        let metadata = event_data
+            .get("metadata")
+            .filter(|metadata| metadata.is_object())
+            .cloned();
+        let event_id = event_data
+            .get("event_id")
+            .or_else(|| metadata.as_ref()?.get("event_id"))
+            .and_then(|v| v.as_str())
+            .and_then(|s| Uuid::parse_str(s).ok());
+
+        Ok(Self {
+            event_id,
+            event_type,
+            schema_version,
+            payload: EventPayload::Json(event_data),
+            occurred_at,
+            metadata,
+        })
+    }
+
+    /// イベント ID を指定する
+    pub fn with_event_id(mut self, event_id: Uuid) -> Self {
+        self.event_id = Some(event_id);
+        self
+    }
+
+    /// スキーマバージョンを指定する
+    pub fn with_schema_version(mut self, schema_version: u32) -> Self {
+        self.schema_version = schema_version;
+        self
+    }
+
+    /// 発生日時を指定する
+    pub fn with_occurred_at(mut self, occurred_at: DateTime<Utc>) -> Self {
+        self.occurred_at = Some(occurred_at);
+        self
+    }
+
+    /// メタデータを指定する
+    pub fn with_metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+}
+
+/// `event_type` を含む JSON のイベントを保存するイベントに変換する
+pub(crate) fn json_events(
+    events: Vec<serde_json::Value>,
+) -> Result<Vec<NewEvent>, EventStoreError> {
+    events.into_iter().map(NewEvent::json).collect()
+}
+
+/// 形式を問わず読み込んだイベント
+#[derive(Debug, Clone)]
+pub struct EncodedEvent {
+    pub event_id:       Uuid,
+    /// テナント（None は既定のテナント）
+    pub tenant_id:      Option<String>,
+    pub aggregate_id:   Uuid,
+    pub aggregate_type: String,
+    pub event_type:     String,
+    pub event_version:  u32,
+    pub schema_version: u32,
+    pub payload:        EventPayload,
+    pub metadata:       Option<serde_json::Value>,
+    pub occurred_at:    DateTime<Utc>,
+    pub created_at:     DateTime<Utc>,
+}
+
+impl From<StoredEvent> for EncodedEvent {
+    fn from(event: StoredEvent) -> Self {
+        Self {
+            schema_version: upcasting::schema_version(&event),
+            event_id:       event.event_id,
+            tenant_id:      event.tenant_id,
+            aggregate_id:   event.aggregate_id,
+            aggregate_type: event.aggregate_type,
+            event_type:     event.event_type,
+            event_version:  event.event_version,
+            payload:        EventPayload::Json(event.event_data),
+            metadata:       event.metadata,
+            occurred_at:    event.occurred_at,
+            created_at:     event.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct ItemCreated {
+        #[prost(string, tag = "1")]
+        spelling:   String,
+        #[prost(int32, tag = "2")]
+        cefr_level: i32,
+    }
+
+    #[test]
+    fn test_protobuf_payload_round_trip() {
+        let created = ItemCreated {
+            spelling:   "serendipity".to_string(),
+            cefr_level: 5,
+        };
+        let event = NewEvent::protobuf("ItemCreated", &created).with_schema_version(2);
+
+        assert_eq!(event.payload.format(), PayloadFormat::Protobuf);
+        assert_eq!(event.schema_version, 2);
+        assert_eq!(event.payload.decode::<ItemCreated>().unwrap(), created);
+        assert!(
+            EventPayload::Json(json!({}))
+                .decode::<ItemCreated>()
+                .is_err()
+        );
+        assert_eq!(
+            PayloadFormat::parse(PayloadFormat::Protobuf.as_str()).unwrap(),
+            PayloadFormat::Protobuf
+        );
+    }
+
+    #[test]
+    fn test_json_event_reads_type_and_schema_version() {
+        let event = NewEvent::json(json!({
+            "event_type": "ItemCreated",
+            "schema_version": 3,
+            "occurred_at": "2025-10-01T09:00:00Z",
+            "metadata": {
+                "correlation_id": "ai-request-1",
+                "event_id": "0199c3a1-7b2e-7f00-8a00-000000000001",
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(event.event_type, "ItemCreated");
+        assert_eq!(event.schema_version, 3);
+        assert!(event.occurred_at.is_some());
+        assert_eq!(event.metadata.unwrap()["correlation_id"], "ai-request-1");
+        assert_eq!(
+            event.event_id.unwrap().to_string(),
+            "0199c3a1-7b2e-7f00-8a00-000000000001"
+        );
+        assert!(NewEvent::json(json!({ "spelling": "apple" })).is_err());
+    }
+}
@@ -0,0 +1,203 @@
+//! イベント種別の名前からの復元
+//!
+//! 射影などイベントストアの行を読む側は、`event_type` ごとの復元を
+//! [`EventRegistry`] に登録しておき、型付きのイベントに戻してから処理する。
+//! 種別ごとの巨大な match を書かずに済み、登録していない種別は None になる
+//! ため、関心のないイベントを読み飛ばせる。
+//!
+//! [`EventRegistry::with_upcasters`] を設定すると、復元の前に
+//! [`UpcasterRegistry`] で現在の形式まで変換する。
+//!
+//! ```ignore
+//! let registry = EventRegistry::new()
+//!     .register("VocabularyItemCreated", VocabularyEvent::ItemCreated)
+//!     .register("VocabularyItemPublished", VocabularyEvent::ItemPublished);
+//!
+//! if let Some(event) = registry.decode(stored)? {
+//!     projection.apply(event).await?;
+//! }
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+use serde::de::DeserializeOwned;
+
+use crate::{EventStoreError, StoredEvent, upcasting::UpcasterRegistry};
+
+type Deserializer<E> = Box<dyn Fn(serde_json::Value) -> Result<E, serde_json::Error> + Send + Sync>;
+
+/// イベント種別の名前ごとの復元
+pub struct EventRegistry<E> {
+    deserializers: HashMap<String, Deserializer<E>>,
+    upcasters:     Option<UpcasterRegistry>,
+}
+
+impl<E> Default for EventRegistry<E> {
+    fn default() -> Self {
+        Self {
+            deserializers: HashMap::new(),
+            upcasters:     None,
+        }
+    }
+}
+
+impl<E> fmt::Debug for EventRegistry<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut event_types: Vec<&String> = self.deserializers.keys().collect();
+        event_types.sort();
+        f.debug_struct("EventRegistry")
+            .field("event_types", &event_types)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E: 'static> EventRegistry<E> {
+    /// 空のレジストリを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `event_type` のイベント本体を `T` として読み、`into` で `E` にする復元を
+    /// 登録する（同じ種別の登録は置き換える）
+    ///
+    /// `into` には列挙型のバリアント（`VocabularyEvent::ItemCreated` など）を
+    /// 渡せる
+    pub fn register<T>(mut self, event_type: impl Into<String>, into: fn(T) -> E) -> Self
+    where
+        T: DeserializeOwned + 'static,
+    {
+        self.deserializers.insert(
+            event_type.into(),
+            Box::new(move |event_data| serde_json::from_value(event_data).map(into)),
+        );
+        self
+    }
+
+    /// 復元の前に `upcasters` で現在の形式まで変換する
+    pub fn with_upcasters(mut self, upcasters: UpcasterRegistry) -> Self {
+        self.upcasters = Some(upcasters);
+        self
+    }
+
+    /// `event_type` の復元が登録されているか
+    pub fn contains(&self, event_type: &str) -> bool {
+        self.deserializers.contains_key(event_type)
+    }
+
+    /// `event_type` のイベント本体を復元する（登録されていなければ None）
+    pub fn deserialize(
+        &self,
+        event_type: &str,
+        event_data: serde_json::Value,
+    ) -> Result<Option<E>, EventStoreError> {
+        self.deserializers
+            .get(event_type)
+            .map(|deserialize| deserialize(event_data))
+            .transpose()
+            .map_err(EventStoreError::from)
+    }
+
+    /// 保存済みのイベントを復元する（登録されていなければ None）
+    pub fn decode(&self, event: StoredEvent) -> Result<Option<E>, EventStoreError> {
+        if !self.contains(&event.event_type) {
+            return Ok(None);
+        }
+
+        let event = match &self.upcasters {
+            Some(upcasters) => upcasters.upcast(event)?,
+            None => event,
+        };
+        self.deserialize(&event.event_type, event.event_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::Utc;
+    use serde::Deserialize;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::RenameField;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct ItemCreated {
+        headword: String,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct ItemPublished {
+        item_id: String,
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum VocabularyEvent {
+        ItemCreated(ItemCreated),
+        ItemPublished(ItemPublished),
+    }
+
+    fn stored(event_type: &str, event_data: serde_json::Value) -> StoredEvent {
+        StoredEvent {
+            event_id: Uuid::new_v4(),
+            tenant_id: None,
+            aggregate_id: Uuid::new_v4(),
+            aggregate_type: "vocabulary_item".to_string(),
+            event_type: event_type.to_string(),
+            event_version: 1,
+            event_data,
+            metadata: None,
+            occurred_at: Utc::now(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_decodes_registered_event_types_by_name() {
+        let registry = EventRegistry::new()
+            .register("VocabularyItemCreated", VocabularyEvent::ItemCreated)
+            .register("VocabularyItemPublished", VocabularyEvent::ItemPublished)
+            .with_upcasters(UpcasterRegistry::new().register(Arc::new(RenameField::new(
+                "VocabularyItemCreated",
+                1,
+                "spelling",
+                "headword",
+            ))));
+
+        // 古い形式は現在の形式に変換してから復元する
+        assert_eq!(
+            registry
+                .decode(stored(
+                    "VocabularyItemCreated",
+                    json!({ "spelling": "apple" })
+                ))
+                .unwrap(),
+            Some(VocabularyEvent::ItemCreated(ItemCreated {
+                headword: "apple".to_string(),
+            }))
+        );
+        assert_eq!(
+            registry
+                .deserialize("VocabularyItemPublished", json!({ "item_id": "item-1" }))
+                .unwrap(),
+            Some(VocabularyEvent::ItemPublished(ItemPublished {
+                item_id: "item-1".to_string(),
+            }))
+        );
+
+        // 登録していない種別は読み飛ばし、壊れた本体はエラーにする
+        assert_eq!(
+            registry
+                .decode(stored("VocabularyItemDeleted", json!({})))
+                .unwrap(),
+            None
+        );
+        assert!(
+            registry
+                .decode(stored("VocabularyItemPublished", json!({ "item": 1 })))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! ストリームの保持期間
+//!
+//! 学習セッションのようにイベントが増え続けるストリームは、スナップショットを
+//! 取った後なら古いイベントを消しても復元できる。[`RetentionPolicy`] で
+//! 集約の種類ごとにイベントを残す期間を決め、
+//! `PostgresEventStore::apply_retention` で定期的に消す。
+//!
+//! 消すのは最新のスナップショットに含まれるバージョンまでのイベントだけで、
+//! スナップショットのないストリームや、スナップショットより後のイベントは
+//! 期間を過ぎても残す。
+
+use std::{collections::HashMap, time::Duration};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use uuid::Uuid;
+
+use crate::EventStoreError;
+
+/// 集約の種類ごとのイベントの保持期間
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    max_ages: HashMap<String, Duration>,
+}
+
+impl RetentionPolicy {
+    /// 保持期間を設定しない（何も消さない）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 集約の種類のイベントを `max_age` だけ残す
+    pub fn max_age(mut self, aggregate_type: impl Into<String>, max_age: Duration) -> Self {
+        self.max_ages.insert(aggregate_type.into(), max_age);
+        self
+    }
+
+    /// 保持期間を設定した集約の種類
+    pub fn aggregate_types(&self) -> impl Iterator<Item = &str> {
+        self.max_ages.keys().map(String::as_str)
+    }
+
+    /// `now` の時点で、これより前に保存したイベントを消してよい日時
+    ///
+    /// 保持期間を設定していない集約の種類は None
+    pub fn cutoff(&self, aggregate_type: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let max_age = self.max_ages.get(aggregate_type)?;
+        let max_age = TimeDelta::from_std(*max_age).unwrap_or(TimeDelta::MAX);
+        Some(
+            now.checked_sub_signed(max_age)
+                .unwrap_or(DateTime::<Utc>::MIN_UTC),
+        )
+    }
+}
+
+/// `before_version` より前を消すのに十分なスナップショットがあるか確認する
+///
+/// 消すイベントがない場合は false を返す
+pub(crate) fn check_truncation(
+    aggregate_id: Uuid,
+    before_version: u32,
+    snapshot_version: Option<u32>,
+) -> Result<bool, EventStoreError> {
+    let last_deleted = before_version.saturating_sub(1);
+    if last_deleted == 0 {
+        return Ok(false);
+    }
+    if snapshot_version.is_none_or(|version| version < last_deleted) {
+        return Err(EventStoreError::SnapshotRequired {
+            aggregate_id,
+            version: last_deleted,
+        });
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cutoff_only_for_configured_aggregate_types() {
+        let policy =
+            RetentionPolicy::new().max_age("learning_session", Duration::from_secs(30 * 86_400));
+        let now = Utc::now();
+
+        assert_eq!(
+            policy.cutoff("learning_session", now),
+            Some(now - TimeDelta::days(30))
+        );
+        assert_eq!(policy.cutoff("vocabulary_entry", now), None);
+        assert_eq!(
+            policy.aggregate_types().collect::<Vec<_>>(),
+            vec!["learning_session"]
+        );
+    }
+}
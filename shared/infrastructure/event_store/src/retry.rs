@@ -0,0 +1,162 @@
+//! 楽観的ロックの競合時の追記の再試行
+//!
+//! [`with_optimistic_retry`] はストリームの現在のバージョンを読み直して
+//! 処理を呼び、[`EventStoreError::VersionConflict`] で失敗した場合は
+//! 決まった回数までやり直す。処理はバージョンを受け取り、それを
+//! `expected_version` にして保存する。
+//!
+//! ```ignore
+//! with_optimistic_retry(&store, item_id, "vocabulary_item", DEFAULT_MAX_ATTEMPTS, |version| {
+//!     let store = &store;
+//!     async move {
+//!         store
+//!             .save_events(item_id, "vocabulary_item", vec![event.clone()], Some(version))
+//!             .await
+//!     }
+//! })
+//! .await?;
+//! ```
+//!
+//! コマンドハンドラー全体をやり直す場合は shared_cqrs の `RetryOnConflict`
+//! を使う。
+
+use std::future::Future;
+
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{EventStore, EventStoreError};
+
+/// 既定の最大試行回数（最初の1回を含む）
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// ストリームの現在のバージョン（イベントがなければ 0）
+///
+/// 古いイベントを削除したストリームでも、スナップショットのバージョンを使って
+/// 正しいバージョンを返す
+pub async fn current_version<S>(
+    store: &S,
+    aggregate_id: Uuid,
+    aggregate_type: &str,
+) -> Result<u32, EventStoreError>
+where
+    S: EventStore + ?Sized,
+{
+    let last_event = store
+        .load_events_backward(aggregate_id, aggregate_type, None, 1)
+        .await?
+        .first()
+        .map_or(0, |event| event.event_version);
+    let snapshot = store
+        .load_snapshot(aggregate_id, aggregate_type)
+        .await?
+        .map_or(0, |snapshot| snapshot.aggregate_version);
+    Ok(last_event.max(snapshot))
+}
+
+/// 現在のバージョンを読み直しながら、競合しなくなるまで最大 `max_attempts` 回
+/// `operation` を呼ぶ（0 は 1 として扱う）
+///
+/// 競合以外のエラーと、最後の試行の競合はそのまま返す
+pub async fn with_optimistic_retry<S, F, Fut, T>(
+    store: &S,
+    aggregate_id: Uuid,
+    aggregate_type: &str,
+    max_attempts: u32,
+    mut operation: F,
+) -> Result<T, EventStoreError>
+where
+    S: EventStore + ?Sized,
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, EventStoreError>>,
+{
+    let max_attempts = max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        let version = current_version(store, aggregate_id, aggregate_type).await?;
+        match operation(version).await {
+            Err(EventStoreError::VersionConflict { expected, actual })
+                if attempt < max_attempts =>
+            {
+                warn!(
+                    aggregate_id = %aggregate_id,
+                    aggregate_type = %aggregate_type,
+                    expected = expected,
+                    actual = actual,
+                    attempt = attempt,
+                    "Version conflict, retrying append"
+                );
+                attempt += 1;
+            },
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::InMemoryEventStore;
+
+    #[tokio::test]
+    async fn test_retries_with_the_reloaded_version() {
+        let store = InMemoryEventStore::new();
+        let item_id = Uuid::new_v4();
+        let calls = AtomicU32::new(0);
+
+        let result = with_optimistic_retry(&store, item_id, "vocabulary_item", 3, |version| {
+            let (store, calls) = (&store, &calls);
+            async move {
+                // 1回目は別の書き込みが先に追記した状態にする
+                if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    store
+                        .save_events(item_id, "vocabulary_item", vec![json!({})], None)
+                        .await?;
+                }
+                store
+                    .save_events(item_id, "vocabulary_item", vec![json!({})], Some(version))
+                    .await
+            }
+        })
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            current_version(&store, item_id, "vocabulary_item")
+                .await
+                .unwrap(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let store = InMemoryEventStore::new();
+        let item_id = Uuid::new_v4();
+
+        let result = with_optimistic_retry(&store, item_id, "vocabulary_item", 2, |version| {
+            let store = &store;
+            async move {
+                store
+                    .save_events(
+                        item_id,
+                        "vocabulary_item",
+                        vec![json!({})],
+                        Some(version + 1),
+                    )
+                    .await
+            }
+        })
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(EventStoreError::VersionConflict { .. })
+        ));
+    }
+}
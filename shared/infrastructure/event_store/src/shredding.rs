@@ -0,0 +1,769 @@
+//! 個人情報のクリプトシュレッディング
+//!
+//! イベントは書き換えないため、退会したユーザーのメールアドレスなどを
+//! 消すことができない。[`CryptoShreddingEventStore`] は指定したフィールドを
+//! ユーザーごとの鍵（AES-256-GCM）で暗号化して保存し、読み込み時に復号する。
+//! [`shred`](CryptoShreddingEventStore::shred) で鍵を削除すると、以降その
+//! ユーザーのフィールドは [`SHREDDED_VALUE`] として読まれる。
+//!
+//! 鍵の持ち主は集約 ID（ユーザー集約の ID はユーザー ID）とする。
+//! 暗号化したフィールドは `enc:v1:` で始まる文字列になり、暗号化前に
+//! 保存したフィールドはそのまま読む。
+//!
+//! ```ignore
+//! let store = CryptoShreddingEventStore::new(
+//!     Arc::new(PostgresEventStore::new(pool.clone())),
+//!     Arc::new(PostgresKeyStore::new(pool)),
+//! )
+//! .encrypt_fields("user", &["email", "display_name"]);
+//!
+//! // GDPR の削除要求を受けたとき
+//! store.shred(user_id).await?;
+//! ```
+
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use aes_gcm::{
+    Aes256Gcm,
+    Key,
+    Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::Value;
+use sqlx::{PgPool, Row};
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+use crate::{
+    EncodedEvent,
+    EventFilter,
+    EventPayload,
+    EventStore,
+    EventStoreError,
+    EventSubscription,
+    NewEvent,
+    PositionedEvent,
+    Snapshot,
+    StoredEvent,
+    StreamAppend,
+};
+
+/// 暗号化したフィールドの接頭辞
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// 鍵を削除したフィールドの読み込み結果
+pub const SHREDDED_VALUE: &str = "[shredded]";
+
+/// AES-GCM のナンスの長さ
+const NONCE_LEN: usize = 12;
+
+/// ユーザーごとの暗号鍵
+#[derive(Clone)]
+pub struct DataKey([u8; 32]);
+
+impl DataKey {
+    /// 新しい鍵を作る
+    pub fn generate() -> Self {
+        Self(Aes256Gcm::generate_key(OsRng).into())
+    }
+
+    /// 保存したバイト列から戻す
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EventStoreError> {
+        let key = bytes.try_into().map_err(|_| {
+            EventStoreError::Internal(format!("Invalid encryption key length: {}", bytes.len()))
+        })?;
+        Ok(Self(key))
+    }
+
+    /// 保存するバイト列
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.0))
+    }
+
+    /// 値を JSON にして暗号化する（持ち主とフィールド名を認証に含める）
+    fn encrypt(
+        &self,
+        subject_id: Uuid,
+        field: &str,
+        value: &Value,
+    ) -> Result<String, EventStoreError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let plaintext = serde_json::to_vec(value)?;
+        let aad = associated_data(subject_id, field);
+        let ciphertext = self
+            .cipher()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|e| EventStoreError::Internal(format!("Failed to encrypt {field}: {e}")))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend(ciphertext);
+        Ok(format!("{ENCRYPTED_PREFIX}{}", hex::encode(sealed)))
+    }
+
+    fn decrypt(
+        &self,
+        subject_id: Uuid,
+        field: &str,
+        sealed: &str,
+    ) -> Result<Value, EventStoreError> {
+        let sealed = hex::decode(sealed).map_err(|e| {
+            EventStoreError::Internal(format!("Invalid encrypted value in {field}: {e}"))
+        })?;
+        if sealed.len() < NONCE_LEN {
+            return Err(EventStoreError::Internal(format!(
+                "Invalid encrypted value in {field}"
+            )));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let aad = associated_data(subject_id, field);
+        let plaintext = self
+            .cipher()
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|e| EventStoreError::Internal(format!("Failed to decrypt {field}: {e}")))?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+}
+
+impl fmt::Debug for DataKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DataKey(..)")
+    }
+}
+
+fn associated_data(subject_id: Uuid, field: &str) -> Vec<u8> {
+    let mut aad = subject_id.as_bytes().to_vec();
+    aad.extend_from_slice(field.as_bytes());
+    aad
+}
+
+/// ユーザーごとの暗号鍵の保存先
+///
+/// 鍵をイベントと同じバックアップに含めると、バックアップから鍵を戻せてしまう。
+/// 本番では鍵を別のデータベースに置くか、KMS で暗号化して保存する
+#[async_trait]
+pub trait EncryptionKeyStore: Send + Sync {
+    /// 鍵を返し、なければ作る（削除済みの場合は None）
+    async fn get_or_create_key(&self, subject_id: Uuid)
+    -> Result<Option<DataKey>, EventStoreError>;
+
+    /// 鍵を返す（未作成または削除済みの場合は None）
+    async fn load_key(&self, subject_id: Uuid) -> Result<Option<DataKey>, EventStoreError>;
+
+    /// 鍵を削除する（削除した場合は true）
+    ///
+    /// 削除した鍵は作り直さない
+    async fn destroy_key(&self, subject_id: Uuid) -> Result<bool, EventStoreError>;
+}
+
+/// PostgreSQL の `encryption_keys` に鍵を保存する
+#[derive(Clone)]
+pub struct PostgresKeyStore {
+    pool: PgPool,
+}
+
+impl PostgresKeyStore {
+    /// 新しい鍵の保存先を作成
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EncryptionKeyStore for PostgresKeyStore {
+    #[instrument(skip(self))]
+    async fn get_or_create_key(
+        &self,
+        subject_id: Uuid,
+    ) -> Result<Option<DataKey>, EventStoreError> {
+        sqlx::query(
+            r#"
+            INSERT INTO encryption_keys (subject_id, encryption_key)
+            VALUES ($1, $2)
+            ON CONFLICT (subject_id) DO NOTHING
+            "#,
+        )
+        .bind(subject_id)
+        .bind(DataKey::generate().as_bytes())
+        .execute(&self.pool)
+        .await?;
+
+        self.load_key(subject_id).await
+    }
+
+    #[instrument(skip(self))]
+    async fn load_key(&self, subject_id: Uuid) -> Result<Option<DataKey>, EventStoreError> {
+        let row = sqlx::query("SELECT encryption_key FROM encryption_keys WHERE subject_id = $1")
+            .bind(subject_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.and_then(|row| row.get::<Option<Vec<u8>>, _>("encryption_key"))
+            .map(|key| DataKey::from_bytes(&key))
+            .transpose()
+    }
+
+    #[instrument(skip(self))]
+    async fn destroy_key(&self, subject_id: Uuid) -> Result<bool, EventStoreError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO encryption_keys (subject_id, encryption_key, destroyed_at)
+            VALUES ($1, NULL, NOW())
+            ON CONFLICT (subject_id) DO UPDATE
+            SET encryption_key = NULL, destroyed_at = NOW()
+            WHERE encryption_keys.encryption_key IS NOT NULL
+            "#,
+        )
+        .bind(subject_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// 集約の種類ごとの暗号化するフィールドと鍵
+#[derive(Clone)]
+struct PiiCipher {
+    keys:   Arc<dyn EncryptionKeyStore>,
+    fields: HashMap<String, Vec<String>>,
+}
+
+impl PiiCipher {
+    fn fields(&self, aggregate_type: &str) -> Option<&[String]> {
+        self.fields.get(aggregate_type).map(Vec::as_slice)
+    }
+
+    /// 保存するイベント（またはスナップショット）のフィールドを暗号化する
+    async fn encrypt(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        data: &mut [&mut Value],
+    ) -> Result<(), EventStoreError> {
+        let Some(fields) = self.fields(aggregate_type) else {
+            return Ok(());
+        };
+        let key = self
+            .keys
+            .get_or_create_key(aggregate_id)
+            .await?
+            .ok_or(EventStoreError::KeyDestroyed(aggregate_id))?;
+
+        for value in data.iter_mut() {
+            let Some(object) = value.as_object_mut() else {
+                continue;
+            };
+            for field in fields {
+                if let Some(value) = object.get_mut(field)
+                    && !value.is_null()
+                    && !is_encrypted(value)
+                {
+                    *value = Value::String(key.encrypt(aggregate_id, field, value)?);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 読み込んだイベントのフィールドを復号する（鍵が削除済みなら
+    /// [`SHREDDED_VALUE`] にする）
+    fn decrypt(
+        &self,
+        key: Option<&DataKey>,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        data: &mut Value,
+    ) -> Result<(), EventStoreError> {
+        let (Some(fields), Some(object)) = (self.fields(aggregate_type), data.as_object_mut())
+        else {
+            return Ok(());
+        };
+
+        for field in fields {
+            let Some(value) = object.get_mut(field) else {
+                continue;
+            };
+            let Some(sealed) = value
+                .as_str()
+                .and_then(|s| s.strip_prefix(ENCRYPTED_PREFIX))
+            else {
+                continue;
+            };
+            *value = match key {
+                Some(key) => key.decrypt(aggregate_id, field, sealed)?,
+                None => Value::String(SHREDDED_VALUE.to_string()),
+            };
+        }
+        Ok(())
+    }
+
+    /// 集約の鍵を読み込む（暗号化しない集約の種類は読まない）
+    async fn key_for(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+    ) -> Result<Option<DataKey>, EventStoreError> {
+        if self.fields(aggregate_type).is_none() {
+            return Ok(None);
+        }
+        self.keys.load_key(aggregate_id).await
+    }
+
+    async fn decrypt_event(&self, mut event: StoredEvent) -> Result<StoredEvent, EventStoreError> {
+        let key = self
+            .key_for(event.aggregate_id, &event.aggregate_type)
+            .await?;
+        self.decrypt(
+            key.as_ref(),
+            event.aggregate_id,
+            &event.aggregate_type,
+            &mut event.event_data,
+        )?;
+        Ok(event)
+    }
+}
+
+fn is_encrypted(value: &Value) -> bool {
+    value
+        .as_str()
+        .is_some_and(|s| s.starts_with(ENCRYPTED_PREFIX))
+}
+
+/// 個人情報のフィールドを暗号化して保存する Event Store
+pub struct CryptoShreddingEventStore {
+    inner:  Arc<dyn EventStore>,
+    cipher: Arc<PiiCipher>,
+}
+
+impl CryptoShreddingEventStore {
+    /// 新しい Event Store を作成（暗号化するフィールドは
+    /// [`encrypt_fields`](Self::encrypt_fields) で指定する）
+    pub fn new(inner: Arc<dyn EventStore>, keys: Arc<dyn EncryptionKeyStore>) -> Self {
+        Self {
+            inner,
+            cipher: Arc::new(PiiCipher {
+                keys,
+                fields: HashMap::new(),
+            }),
+        }
+    }
+
+    /// 集約の種類のイベントとスナップショットで暗号化するトップレベルのフィールド
+    pub fn encrypt_fields(mut self, aggregate_type: impl Into<String>, fields: &[&str]) -> Self {
+        Arc::make_mut(&mut self.cipher).fields.insert(
+            aggregate_type.into(),
+            fields.iter().map(ToString::to_string).collect(),
+        );
+        self
+    }
+
+    /// ユーザーの鍵を削除し、個人情報を読めなくする（削除した場合は true）
+    #[instrument(skip(self))]
+    pub async fn shred(&self, subject_id: Uuid) -> Result<bool, EventStoreError> {
+        let destroyed = self.cipher.keys.destroy_key(subject_id).await?;
+        if destroyed {
+            info!(subject_id = %subject_id, "Encryption key destroyed");
+        }
+        Ok(destroyed)
+    }
+
+    /// 全体の読み込みの結果を復号する（集約ごとの鍵は1回だけ読む）
+    async fn decrypt_positioned(
+        &self,
+        events: Vec<PositionedEvent>,
+    ) -> Result<Vec<PositionedEvent>, EventStoreError> {
+        let mut keys: HashMap<Uuid, Option<DataKey>> = HashMap::new();
+        let mut decrypted = Vec::with_capacity(events.len());
+        for mut positioned in events {
+            let event = &mut positioned.event;
+            if self.cipher.fields(&event.aggregate_type).is_some()
+                && !keys.contains_key(&event.aggregate_id)
+            {
+                let key = self.cipher.keys.load_key(event.aggregate_id).await?;
+                keys.insert(event.aggregate_id, key);
+            }
+            let key = keys.get(&event.aggregate_id).and_then(Option::as_ref);
+            self.cipher.decrypt(
+                key,
+                event.aggregate_id,
+                &event.aggregate_type,
+                &mut event.event_data,
+            )?;
+            decrypted.push(positioned);
+        }
+        Ok(decrypted)
+    }
+}
+
+#[async_trait]
+impl EventStore for CryptoShreddingEventStore {
+    async fn save_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        mut events: Vec<serde_json::Value>,
+        expected_version: Option<u32>,
+    ) -> Result<(), EventStoreError> {
+        self.cipher
+            .encrypt(
+                aggregate_id,
+                aggregate_type,
+                &mut events.iter_mut().collect::<Vec<_>>(),
+            )
+            .await?;
+        self.inner
+            .save_events(aggregate_id, aggregate_type, events, expected_version)
+            .await
+    }
+
+    async fn save_events_batch(
+        &self,
+        mut appends: Vec<StreamAppend>,
+    ) -> Result<(), EventStoreError> {
+        for append in &mut appends {
+            self.cipher
+                .encrypt(
+                    append.aggregate_id,
+                    &append.aggregate_type,
+                    &mut append.events.iter_mut().collect::<Vec<_>>(),
+                )
+                .await?;
+        }
+        self.inner.save_events_batch(appends).await
+    }
+
+    /// 暗号化する集約の種類では Protobuf のイベントを保存できない
+    async fn save_encoded_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        mut events: Vec<NewEvent>,
+        expected_version: Option<u32>,
+    ) -> Result<(), EventStoreError> {
+        if self.cipher.fields(aggregate_type).is_some() {
+            let mut data = Vec::with_capacity(events.len());
+            for event in &mut events {
+                match &mut event.payload {
+                    EventPayload::Json(value) => data.push(value),
+                    EventPayload::Protobuf(_) => {
+                        return Err(EventStoreError::Unsupported(
+                            "crypto shredding of protobuf payloads",
+                        ));
+                    },
+                }
+            }
+            self.cipher
+                .encrypt(aggregate_id, aggregate_type, &mut data)
+                .await?;
+        }
+        self.inner
+            .save_encoded_events(aggregate_id, aggregate_type, events, expected_version)
+            .await
+    }
+
+    async fn load_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        from_version: Option<u32>,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let key = self.cipher.key_for(aggregate_id, aggregate_type).await?;
+        self.inner
+            .load_events(aggregate_id, aggregate_type, from_version)
+            .await?
+            .into_iter()
+            .map(|mut event| {
+                self.cipher.decrypt(
+                    key.as_ref(),
+                    aggregate_id,
+                    aggregate_type,
+                    &mut event.event_data,
+                )?;
+                Ok(event)
+            })
+            .collect()
+    }
+
+    async fn load_events_backward(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        from_version: Option<u32>,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let key = self.cipher.key_for(aggregate_id, aggregate_type).await?;
+        self.inner
+            .load_events_backward(aggregate_id, aggregate_type, from_version, limit)
+            .await?
+            .into_iter()
+            .map(|mut event| {
+                self.cipher.decrypt(
+                    key.as_ref(),
+                    aggregate_id,
+                    aggregate_type,
+                    &mut event.event_data,
+                )?;
+                Ok(event)
+            })
+            .collect()
+    }
+
+    async fn load_encoded_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        from_version: Option<u32>,
+    ) -> Result<Vec<EncodedEvent>, EventStoreError> {
+        let key = self.cipher.key_for(aggregate_id, aggregate_type).await?;
+        self.inner
+            .load_encoded_events(aggregate_id, aggregate_type, from_version)
+            .await?
+            .into_iter()
+            .map(|mut event| {
+                if let EventPayload::Json(data) = &mut event.payload {
+                    self.cipher
+                        .decrypt(key.as_ref(), aggregate_id, aggregate_type, data)?;
+                }
+                Ok(event)
+            })
+            .collect()
+    }
+
+    async fn save_snapshot(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        version: u32,
+        mut data: serde_json::Value,
+    ) -> Result<(), EventStoreError> {
+        self.cipher
+            .encrypt(aggregate_id, aggregate_type, &mut [&mut data])
+            .await?;
+        self.inner
+            .save_snapshot(aggregate_id, aggregate_type, version, data)
+            .await
+    }
+
+    async fn load_snapshot(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+    ) -> Result<Option<Snapshot>, EventStoreError> {
+        let Some(mut snapshot) = self
+            .inner
+            .load_snapshot(aggregate_id, aggregate_type)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let key = self.cipher.key_for(aggregate_id, aggregate_type).await?;
+        self.cipher.decrypt(
+            key.as_ref(),
+            aggregate_id,
+            aggregate_type,
+            &mut snapshot.aggregate_data,
+        )?;
+        Ok(Some(snapshot))
+    }
+
+    async fn load_by_correlation_id(
+        &self,
+        correlation_id: &str,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let events = self.inner.load_by_correlation_id(correlation_id).await?;
+
+        let mut decrypted = Vec::with_capacity(events.len());
+        for event in events {
+            decrypted.push(self.cipher.decrypt_event(event).await?);
+        }
+        Ok(decrypted)
+    }
+
+    async fn truncate_stream(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        before_version: u32,
+    ) -> Result<u64, EventStoreError> {
+        self.inner
+            .truncate_stream(aggregate_id, aggregate_type, before_version)
+            .await
+    }
+
+    async fn delete_stream(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+    ) -> Result<u64, EventStoreError> {
+        self.inner.delete_stream(aggregate_id, aggregate_type).await
+    }
+
+    async fn load_all_events(
+        &self,
+        from_position: i64,
+        limit: usize,
+        filter: &EventFilter,
+    ) -> Result<Vec<PositionedEvent>, EventStoreError> {
+        let events = self
+            .inner
+            .load_all_events(from_position, limit, filter)
+            .await?;
+        self.decrypt_positioned(events).await
+    }
+
+    async fn load_by_event_type(
+        &self,
+        event_type: &str,
+        from_position: i64,
+        limit: usize,
+    ) -> Result<Vec<PositionedEvent>, EventStoreError> {
+        let events = self
+            .inner
+            .load_by_event_type(event_type, from_position, limit)
+            .await?;
+        self.decrypt_positioned(events).await
+    }
+
+    async fn subscribe_all(
+        &self,
+        from_position: i64,
+    ) -> Result<EventSubscription, EventStoreError> {
+        let cipher = Arc::clone(&self.cipher);
+        let subscription = self.inner.subscribe_all(from_position).await?;
+        Ok(subscription
+            .then(move |positioned| {
+                let cipher = Arc::clone(&cipher);
+                async move {
+                    let positioned = positioned?;
+                    Ok(PositionedEvent {
+                        position: positioned.position,
+                        event:    cipher.decrypt_event(positioned.event).await?,
+                    })
+                }
+            })
+            .boxed())
+    }
+
+    fn tenant_id(&self) -> Option<&str> {
+        self.inner.tenant_id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::InMemoryEventStore;
+
+    #[derive(Default)]
+    struct MemoryKeyStore(Mutex<HashMap<Uuid, Option<DataKey>>>);
+
+    #[async_trait]
+    impl EncryptionKeyStore for MemoryKeyStore {
+        async fn get_or_create_key(
+            &self,
+            subject_id: Uuid,
+        ) -> Result<Option<DataKey>, EventStoreError> {
+            Ok(self
+                .0
+                .lock()
+                .unwrap()
+                .entry(subject_id)
+                .or_insert_with(|| Some(DataKey::generate()))
+                .clone())
+        }
+
+        async fn load_key(&self, subject_id: Uuid) -> Result<Option<DataKey>, EventStoreError> {
+            Ok(self.0.lock().unwrap().get(&subject_id).cloned().flatten())
+        }
+
+        async fn destroy_key(&self, subject_id: Uuid) -> Result<bool, EventStoreError> {
+            let previous = self.0.lock().unwrap().insert(subject_id, None);
+            Ok(previous.flatten().is_some())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_encrypts_pii_and_shreds_by_destroying_the_key() {
+        let inner = Arc::new(InMemoryEventStore::new());
+        let store = CryptoShreddingEventStore::new(
+            Arc::clone(&inner) as Arc<dyn EventStore>,
+            Arc::new(MemoryKeyStore::default()),
+        )
+        .encrypt_fields("user", &["email", "display_name"]);
+        let user_id = Uuid::new_v4();
+
+        store
+            .save_events(
+                user_id,
+                "user",
+                vec![json!({
+                    "event_type": "UserSignedUp",
+                    "email": "alice@example.com",
+                    "display_name": "Alice",
+                    "initial_role": "learner",
+                })],
+                None,
+            )
+            .await
+            .unwrap();
+
+        let raw = inner.events()[0].event_data.clone();
+        assert!(is_encrypted(&raw["email"]));
+        assert!(is_encrypted(&raw["display_name"]));
+        assert_eq!(raw["initial_role"], "learner");
+
+        let events = store.load_events(user_id, "user", None).await.unwrap();
+        assert_eq!(events[0].event_data["email"], "alice@example.com");
+        assert_eq!(events[0].event_data["display_name"], "Alice");
+
+        assert!(store.shred(user_id).await.unwrap());
+        let events = store.load_events(user_id, "user", None).await.unwrap();
+        assert_eq!(events[0].event_data["email"], SHREDDED_VALUE);
+        assert_eq!(events[0].event_data["initial_role"], "learner");
+        assert!(matches!(
+            store
+                .save_events(user_id, "user", vec![json!({ "email": "a@b.c" })], None)
+                .await,
+            Err(EventStoreError::KeyDestroyed(id)) if id == user_id
+        ));
+    }
+
+    #[test]
+    fn test_ciphertext_is_bound_to_subject_and_field() {
+        let key = DataKey::generate();
+        let user_id = Uuid::new_v4();
+        let sealed = key
+            .encrypt(user_id, "email", &json!("alice@example.com"))
+            .unwrap();
+        let sealed = sealed.strip_prefix(ENCRYPTED_PREFIX).unwrap();
+
+        assert_eq!(
+            key.decrypt(user_id, "email", sealed).unwrap(),
+            json!("alice@example.com")
+        );
+        assert!(key.decrypt(user_id, "display_name", sealed).is_err());
+        assert!(key.decrypt(Uuid::new_v4(), "email", sealed).is_err());
+    }
+}
@@ -0,0 +1,1018 @@
+//! SQLite Event Store 実装
+//!
+//! PostgreSQL を用意できないローカル開発（1バイナリで動かす場合）や、
+//! 組み込みのテスト環境向け。テーブルは [`SqliteEventStore::migrate`] で作る。
+//!
+//! ```ignore
+//! let pool = SqlitePoolOptions::new().connect("sqlite://effect.db?mode=rwc").await?;
+//! let store = SqliteEventStore::new(pool);
+//! store.migrate().await?;
+//! ```
+//!
+//! 書き込みはデータベース全体で1つずつ行われるため、PostgreSQL 版のような
+//! パーティションやアウトボックスは持たない。`:memory:` を使う場合は、
+//! 接続ごとに別のデータベースになるのでプールの接続数を 1 にする。
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::{StreamExt, stream};
+use sqlx::{Row, Sqlite, SqlitePool, Transaction, sqlite::SqliteRow};
+use tracing::{info, instrument, warn};
+use uuid::Uuid;
+
+use crate::{
+    EncodedEvent,
+    EventFilter,
+    EventPayload,
+    EventStore,
+    EventStoreError,
+    EventSubscription,
+    NewEvent,
+    PayloadFormat,
+    PositionedEvent,
+    Snapshot,
+    StoredEvent,
+    StreamAppend,
+    idempotency,
+    metrics::{self, Appended},
+    payload::json_events,
+    retention,
+    tenant,
+};
+
+/// メトリクスの `backend` 属性
+const BACKEND: &str = "sqlite";
+
+/// 購読で1回に読み込むイベント数
+const SUBSCRIPTION_BATCH_SIZE: i64 = 500;
+
+/// 購読で新しいイベントがない場合に次に確認するまでの間隔
+const SUBSCRIPTION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// テーブル定義（PostgreSQL 版のマイグレーションに合わせた列を持つ）
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS event_streams (
+    tenant_id TEXT NOT NULL DEFAULT '',
+    aggregate_id BLOB NOT NULL,
+    aggregate_type TEXT NOT NULL,
+    version INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    deleted_at TEXT,
+    PRIMARY KEY (tenant_id, aggregate_id, aggregate_type)
+);
+
+CREATE TABLE IF NOT EXISTS events (
+    position INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_id BLOB NOT NULL UNIQUE,
+    tenant_id TEXT NOT NULL DEFAULT '',
+    aggregate_id BLOB NOT NULL,
+    aggregate_type TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    event_version INTEGER NOT NULL,
+    payload_format TEXT NOT NULL DEFAULT 'json',
+    schema_version INTEGER NOT NULL DEFAULT 1,
+    event_data TEXT,
+    event_payload BLOB,
+    metadata TEXT,
+    occurred_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (tenant_id, aggregate_id, aggregate_type, event_version),
+    CHECK (
+        (payload_format = 'json' AND event_data IS NOT NULL)
+        OR (payload_format = 'protobuf' AND event_payload IS NOT NULL)
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_events_event_type ON events (event_type);
+CREATE INDEX IF NOT EXISTS idx_events_correlation_id
+    ON events (json_extract(metadata, '$.correlation_id'));
+
+CREATE TABLE IF NOT EXISTS snapshots (
+    tenant_id TEXT NOT NULL DEFAULT '',
+    aggregate_id BLOB NOT NULL,
+    aggregate_type TEXT NOT NULL,
+    aggregate_version INTEGER NOT NULL,
+    aggregate_data TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (tenant_id, aggregate_id, aggregate_type, aggregate_version)
+);
+"#;
+
+/// SQLite ベースの Event Store 実装
+#[derive(Clone)]
+pub struct SqliteEventStore {
+    pool:      SqlitePool,
+    tenant_id: Option<String>,
+}
+
+impl SqliteEventStore {
+    /// 新しい Event Store を作成
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            tenant_id: None,
+        }
+    }
+
+    /// `tenant_id` のテナントのストリームだけを読み書きする
+    ///
+    /// 空文字列は既定のテナントとして扱う
+    pub fn with_tenant(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = tenant::from_column(tenant_id.into());
+        self
+    }
+
+    /// `tenant_id` 列に保存する値
+    fn tenant(&self) -> &str {
+        tenant::to_column(self.tenant_id.as_deref())
+    }
+
+    /// テーブルがなければ作る
+    #[instrument(skip(self))]
+    pub async fn migrate(&self) -> Result<(), EventStoreError> {
+        sqlx::raw_sql(SCHEMA).execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// トランザクション内でストリームにイベントを追記する
+    async fn append(
+        &self,
+        tx: &mut Transaction<'_, Sqlite>,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        events: Vec<NewEvent>,
+        expected_version: Option<u32>,
+    ) -> Result<Appended, EventStoreError> {
+        // 最初に書き込むことで、トランザクションの開始時に書き込みロックを取る
+        let stream = sqlx::query(
+            r#"
+            INSERT INTO event_streams (aggregate_id, aggregate_type, tenant_id)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (tenant_id, aggregate_id, aggregate_type) DO UPDATE SET version = version
+            RETURNING version, deleted_at IS NOT NULL AS deleted
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(aggregate_type)
+        .bind(self.tenant())
+        .fetch_one(&mut **tx)
+        .await?;
+        if stream.get::<bool, _>("deleted") {
+            return Err(EventStoreError::StreamDeleted(aggregate_id));
+        }
+        let current_version = stream.get::<i32, _>("version") as u32;
+
+        // 再配信による同じイベントの追記は、バージョンを確認する前に飛ばす
+        let event_ids: Vec<Uuid> = events.iter().filter_map(|event| event.event_id).collect();
+        if !event_ids.is_empty() {
+            let stored: Vec<Uuid> = sqlx::query_scalar(
+                r#"
+                SELECT event_id
+                FROM events
+                WHERE aggregate_id = ?1 AND aggregate_type = ?2 AND tenant_id = ?3
+                  AND lower(hex(event_id)) IN (SELECT value FROM json_each(?4))
+                "#,
+            )
+            .bind(aggregate_id)
+            .bind(aggregate_type)
+            .bind(self.tenant())
+            .bind(serde_json::to_string(
+                &event_ids
+                    .iter()
+                    .map(|event_id| event_id.simple().to_string())
+                    .collect::<Vec<_>>(),
+            )?)
+            .fetch_all(&mut **tx)
+            .await?;
+            if idempotency::check_duplicates(&event_ids, &stored)? {
+                info!(
+                    aggregate_id = %aggregate_id,
+                    aggregate_type = %aggregate_type,
+                    events_count = event_ids.len(),
+                    "Duplicate append skipped"
+                );
+                return Ok(Appended {
+                    previous_version: current_version,
+                    events_count:     0,
+                });
+            }
+        }
+
+        // 楽観的ロックのチェック
+        if let Some(expected) = expected_version
+            && current_version != expected
+        {
+            metrics::record_conflict(BACKEND, aggregate_type);
+            return Err(EventStoreError::VersionConflict {
+                expected,
+                actual: current_version,
+            });
+        }
+
+        let events_count = events.len();
+        for (next_version, event) in (current_version + 1..).zip(events) {
+            let (event_data, event_payload) = match &event.payload {
+                EventPayload::Json(data) => (Some(data), None),
+                EventPayload::Protobuf(bytes) => (None, Some(bytes.as_slice())),
+            };
+            let event_id = event.event_id.unwrap_or_else(Uuid::new_v4);
+            sqlx::query(
+                r#"
+                INSERT INTO events (
+                    event_id, aggregate_id, aggregate_type, event_type, event_version,
+                    payload_format, schema_version, event_data, event_payload, metadata,
+                    occurred_at, created_at, tenant_id
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+                "#,
+            )
+            .bind(event_id)
+            .bind(aggregate_id)
+            .bind(aggregate_type)
+            .bind(&event.event_type)
+            .bind(next_version as i32)
+            .bind(event.payload.format().as_str())
+            .bind(event.schema_version as i32)
+            .bind(event_data)
+            .bind(event_payload)
+            .bind(&event.metadata)
+            .bind(event.occurred_at.unwrap_or_else(Utc::now))
+            .bind(Utc::now())
+            .bind(self.tenant())
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| idempotency::map_unique_violation(e, event_id))?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE event_streams SET version = ?3
+            WHERE aggregate_id = ?1 AND aggregate_type = ?2 AND tenant_id = ?4
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(aggregate_type)
+        .bind((current_version as usize + events_count) as i32)
+        .bind(self.tenant())
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(Appended {
+            previous_version: current_version,
+            events_count,
+        })
+    }
+}
+
+#[async_trait]
+impl EventStore for SqliteEventStore {
+    #[instrument(skip(self, events))]
+    async fn save_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        events: Vec<serde_json::Value>,
+        expected_version: Option<u32>,
+    ) -> Result<(), EventStoreError> {
+        self.save_encoded_events(
+            aggregate_id,
+            aggregate_type,
+            json_events(events)?,
+            expected_version,
+        )
+        .await
+    }
+
+    #[instrument(skip(self, appends), fields(streams = appends.len()))]
+    async fn save_events_batch(&self, appends: Vec<StreamAppend>) -> Result<(), EventStoreError> {
+        let started = Instant::now();
+        let mut tx = self.pool.begin().await?;
+
+        let mut appended_streams = Vec::with_capacity(appends.len());
+        for append in appends {
+            let appended = self
+                .append(
+                    &mut tx,
+                    append.aggregate_id,
+                    &append.aggregate_type,
+                    json_events(append.events)?,
+                    append.expected_version,
+                )
+                .await
+                .inspect_err(|e| {
+                    warn!(
+                        aggregate_id = %append.aggregate_id,
+                        aggregate_type = %append.aggregate_type,
+                        error = %e,
+                        "Batch append rejected"
+                    );
+                })?;
+            appended_streams.push((append.aggregate_type, appended));
+        }
+
+        tx.commit().await?;
+        let mut events_count = 0;
+        for (aggregate_type, appended) in &appended_streams {
+            metrics::record_append(BACKEND, aggregate_type, started.elapsed(), appended);
+            events_count += appended.events_count;
+        }
+        info!(
+            events_count = events_count,
+            "Event batch saved successfully"
+        );
+        Ok(())
+    }
+
+    #[instrument(skip(self, events))]
+    async fn save_encoded_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        events: Vec<NewEvent>,
+        expected_version: Option<u32>,
+    ) -> Result<(), EventStoreError> {
+        let started = Instant::now();
+        let mut tx = self.pool.begin().await?;
+        let appended = self
+            .append(
+                &mut tx,
+                aggregate_id,
+                aggregate_type,
+                events,
+                expected_version,
+            )
+            .await?;
+        tx.commit().await?;
+        metrics::record_append(BACKEND, aggregate_type, started.elapsed(), &appended);
+
+        info!(
+            aggregate_id = %aggregate_id,
+            aggregate_type = %aggregate_type,
+            events_count = appended.events_count,
+            "Events saved successfully"
+        );
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn load_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        from_version: Option<u32>,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT *
+            FROM events
+            WHERE aggregate_id = ?1 AND aggregate_type = ?2 AND event_version > ?3
+              AND tenant_id = ?4
+            ORDER BY event_version
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(aggregate_type)
+        .bind(from_version.unwrap_or(0) as i32)
+        .bind(self.tenant())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(stored_event).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn load_events_backward(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        from_version: Option<u32>,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT *
+            FROM events
+            WHERE aggregate_id = ?1 AND aggregate_type = ?2 AND event_version <= ?3
+              AND tenant_id = ?5
+            ORDER BY event_version DESC
+            LIMIT ?4
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(aggregate_type)
+        .bind(from_version.map_or(i32::MAX, |version| version as i32))
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .bind(self.tenant())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(stored_event).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn load_encoded_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        from_version: Option<u32>,
+    ) -> Result<Vec<EncodedEvent>, EventStoreError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT *
+            FROM events
+            WHERE aggregate_id = ?1 AND aggregate_type = ?2 AND event_version > ?3
+              AND tenant_id = ?4
+            ORDER BY event_version
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(aggregate_type)
+        .bind(from_version.unwrap_or(0) as i32)
+        .bind(self.tenant())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(encoded_event).collect()
+    }
+
+    #[instrument(skip(self, data))]
+    async fn save_snapshot(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        version: u32,
+        data: serde_json::Value,
+    ) -> Result<(), EventStoreError> {
+        sqlx::query(
+            r#"
+            INSERT INTO snapshots (
+                aggregate_id, aggregate_type, aggregate_version, aggregate_data, created_at,
+                tenant_id
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT (tenant_id, aggregate_id, aggregate_type, aggregate_version)
+            DO UPDATE SET
+                aggregate_data = excluded.aggregate_data,
+                created_at = excluded.created_at
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(aggregate_type)
+        .bind(version as i32)
+        .bind(&data)
+        .bind(Utc::now())
+        .bind(self.tenant())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn load_snapshot(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+    ) -> Result<Option<Snapshot>, EventStoreError> {
+        let row = sqlx::query(
+            r#"
+            SELECT aggregate_id, aggregate_type, aggregate_version, aggregate_data, created_at
+            FROM snapshots
+            WHERE aggregate_id = ?1 AND aggregate_type = ?2 AND tenant_id = ?3
+            ORDER BY aggregate_version DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(aggregate_type)
+        .bind(self.tenant())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| Snapshot {
+            aggregate_id:      row.get("aggregate_id"),
+            aggregate_type:    row.get("aggregate_type"),
+            aggregate_version: row.get::<i32, _>("aggregate_version") as u32,
+            aggregate_data:    row.get("aggregate_data"),
+            created_at:        row.get("created_at"),
+        }))
+    }
+
+    #[instrument(skip(self))]
+    async fn load_by_correlation_id(
+        &self,
+        correlation_id: &str,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT *
+            FROM events
+            WHERE json_extract(metadata, '$.correlation_id') = ?1 AND tenant_id = ?2
+            ORDER BY position
+            "#,
+        )
+        .bind(correlation_id)
+        .bind(self.tenant())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(stored_event).collect()
+    }
+
+    #[instrument(skip(self))]
+    async fn truncate_stream(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        before_version: u32,
+    ) -> Result<u64, EventStoreError> {
+        let snapshot_version = self
+            .load_snapshot(aggregate_id, aggregate_type)
+            .await?
+            .map(|snapshot| snapshot.aggregate_version);
+        if !retention::check_truncation(aggregate_id, before_version, snapshot_version)? {
+            return Ok(0);
+        }
+
+        let result = sqlx::query(
+            r#"
+            DELETE FROM events
+            WHERE aggregate_id = ?1 AND aggregate_type = ?2 AND event_version < ?3
+              AND tenant_id = ?4
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(aggregate_type)
+        .bind(before_version as i32)
+        .bind(self.tenant())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_stream(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+    ) -> Result<u64, EventStoreError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO event_streams (aggregate_id, aggregate_type, deleted_at, tenant_id)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (tenant_id, aggregate_id, aggregate_type)
+            DO UPDATE SET deleted_at = COALESCE(deleted_at, excluded.deleted_at)
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(aggregate_type)
+        .bind(Utc::now())
+        .bind(self.tenant())
+        .execute(&mut *tx)
+        .await?;
+
+        let deleted = sqlx::query(
+            r#"
+            DELETE FROM events
+            WHERE aggregate_id = ?1 AND aggregate_type = ?2 AND tenant_id = ?3
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(aggregate_type)
+        .bind(self.tenant())
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        sqlx::query(
+            r#"
+            DELETE FROM snapshots
+            WHERE aggregate_id = ?1 AND aggregate_type = ?2 AND tenant_id = ?3
+            "#,
+        )
+        .bind(aggregate_id)
+        .bind(aggregate_type)
+        .bind(self.tenant())
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    #[instrument(skip(self))]
+    async fn load_all_events(
+        &self,
+        from_position: i64,
+        limit: usize,
+        filter: &EventFilter,
+    ) -> Result<Vec<PositionedEvent>, EventStoreError> {
+        read_all_after(
+            &self.pool,
+            self.tenant(),
+            from_position,
+            i64::try_from(limit).unwrap_or(i64::MAX),
+            filter,
+        )
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn subscribe_all(
+        &self,
+        from_position: i64,
+    ) -> Result<EventSubscription, EventStoreError> {
+        let state = (
+            self.pool.clone(),
+            self.tenant().to_string(),
+            from_position,
+            VecDeque::new(),
+        );
+        let subscription = stream::unfold(
+            state,
+            |(pool, tenant, mut position, mut buffered)| async move {
+                loop {
+                    if let Some(event) = buffered.pop_front() {
+                        return Some((Ok(event), (pool, tenant, position, buffered)));
+                    }
+                    match read_all_after(
+                        &pool,
+                        &tenant,
+                        position,
+                        SUBSCRIPTION_BATCH_SIZE,
+                        &EventFilter::all(),
+                    )
+                    .await
+                    {
+                        Ok(events) if events.is_empty() => {
+                            tokio::time::sleep(SUBSCRIPTION_POLL_INTERVAL).await;
+                        },
+                        Ok(events) => {
+                            position = events.last().map_or(position, |event| event.position);
+                            buffered.extend(events);
+                        },
+                        Err(e) => {
+                            warn!(position, error = %e, "Failed to read events for subscription");
+                            tokio::time::sleep(SUBSCRIPTION_POLL_INTERVAL).await;
+                            return Some((Err(e), (pool, tenant, position, buffered)));
+                        },
+                    }
+                }
+            },
+        );
+
+        Ok(subscription.boxed())
+    }
+
+    fn tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
+    }
+}
+
+/// `position` より後のテナントのイベントを読む
+///
+/// SQLite は書き込みが1つずつコミットされるため、PostgreSQL 版と違い
+/// 後から小さい位置のイベントが見えるようになることはない
+async fn read_all_after(
+    pool: &SqlitePool,
+    tenant_id: &str,
+    position: i64,
+    limit: i64,
+    filter: &EventFilter,
+) -> Result<Vec<PositionedEvent>, EventStoreError> {
+    let rows = sqlx::query(
+        r#"
+        SELECT *
+        FROM events
+        WHERE position > ?1
+          AND tenant_id = ?5
+          AND (json_array_length(?3) = 0 OR aggregate_type IN (SELECT value FROM json_each(?3)))
+          AND (json_array_length(?4) = 0 OR event_type IN (SELECT value FROM json_each(?4)))
+        ORDER BY position
+        LIMIT ?2
+        "#,
+    )
+    .bind(position)
+    .bind(limit)
+    .bind(serde_json::to_string(&filter.aggregate_types)?)
+    .bind(serde_json::to_string(&filter.event_types)?)
+    .bind(tenant_id)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(PositionedEvent {
+                position: row.get("position"),
+                event:    stored_event(row)?,
+            })
+        })
+        .collect()
+}
+
+/// JSON の読み込み API では Protobuf のイベントを読めないためエラーにする
+fn stored_event(row: &SqliteRow) -> Result<StoredEvent, EventStoreError> {
+    if PayloadFormat::parse(row.get("payload_format"))? != PayloadFormat::Json {
+        return Err(EventStoreError::Internal(format!(
+            "Event {} is stored as protobuf; use load_encoded_events",
+            row.get::<Uuid, _>("event_id")
+        )));
+    }
+
+    Ok(StoredEvent {
+        event_id:       row.get("event_id"),
+        tenant_id:      tenant::from_column(row.get("tenant_id")),
+        aggregate_id:   row.get("aggregate_id"),
+        aggregate_type: row.get("aggregate_type"),
+        event_type:     row.get("event_type"),
+        event_version:  row.get::<i32, _>("event_version") as u32,
+        event_data:     row.get("event_data"),
+        metadata:       row.get("metadata"),
+        occurred_at:    row.get("occurred_at"),
+        created_at:     row.get("created_at"),
+    })
+}
+
+fn encoded_event(row: &SqliteRow) -> Result<EncodedEvent, EventStoreError> {
+    let payload = match PayloadFormat::parse(row.get("payload_format"))? {
+        PayloadFormat::Json => EventPayload::Json(row.get("event_data")),
+        PayloadFormat::Protobuf => EventPayload::Protobuf(row.get("event_payload")),
+    };
+
+    Ok(EncodedEvent {
+        event_id: row.get("event_id"),
+        tenant_id: tenant::from_column(row.get("tenant_id")),
+        aggregate_id: row.get("aggregate_id"),
+        aggregate_type: row.get("aggregate_type"),
+        event_type: row.get("event_type"),
+        event_version: row.get::<i32, _>("event_version") as u32,
+        schema_version: row.get::<i32, _>("schema_version") as u32,
+        payload,
+        metadata: row.get("metadata"),
+        occurred_at: row.get("occurred_at"),
+        created_at: row.get("created_at"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+
+    async fn store() -> SqliteEventStore {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let store = SqliteEventStore::new(pool);
+        store.migrate().await.unwrap();
+        store
+    }
+
+    fn updated(version: u32) -> serde_json::Value {
+        json!({
+            "event_type": "ItemUpdated",
+            "revision": version,
+            "metadata": { "correlation_id": "ai-request-1" },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_appends_and_reads_a_stream() {
+        let store = store().await;
+        let item_id = Uuid::new_v4();
+
+        store
+            .save_events(
+                item_id,
+                "vocabulary_item",
+                vec![updated(1), updated(2)],
+                Some(0),
+            )
+            .await
+            .unwrap();
+        store
+            .save_events(item_id, "vocabulary_item", vec![updated(3)], Some(2))
+            .await
+            .unwrap();
+        assert!(matches!(
+            store
+                .save_events(item_id, "vocabulary_item", vec![updated(4)], Some(2))
+                .await,
+            Err(EventStoreError::VersionConflict {
+                expected: 2,
+                actual:   3,
+            })
+        ));
+
+        let events = store
+            .load_events(item_id, "vocabulary_item", Some(1))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_data["revision"], 2);
+
+        let latest = store
+            .load_events_backward(item_id, "vocabulary_item", None, 1)
+            .await
+            .unwrap();
+        assert_eq!(latest[0].event_version, 3);
+
+        let correlated = store.load_by_correlation_id("ai-request-1").await.unwrap();
+        assert_eq!(correlated.len(), 3);
+
+        let all = store
+            .load_all_events(1, 10, &EventFilter::all().event_type("ItemUpdated"))
+            .await
+            .unwrap();
+        assert_eq!(
+            all.iter().map(|event| event.position).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+
+        let published = store
+            .load_by_event_type("ItemPublished", 0, 10)
+            .await
+            .unwrap();
+        assert!(published.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_truncates_only_what_a_snapshot_covers() {
+        let store = store().await;
+        let session_id = Uuid::new_v4();
+        store
+            .save_events(
+                session_id,
+                "learning_session",
+                (1..=4).map(updated).collect(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            store
+                .truncate_stream(session_id, "learning_session", 4)
+                .await,
+            Err(EventStoreError::SnapshotRequired { version: 3, .. })
+        ));
+
+        store
+            .save_snapshot(session_id, "learning_session", 3, json!({ "answered": 3 }))
+            .await
+            .unwrap();
+        assert_eq!(
+            store
+                .truncate_stream(session_id, "learning_session", 4)
+                .await
+                .unwrap(),
+            3
+        );
+
+        // バージョンは続きから採番される
+        store
+            .save_events(session_id, "learning_session", vec![updated(5)], Some(4))
+            .await
+            .unwrap();
+        let events = store
+            .load_events(session_id, "learning_session", None)
+            .await
+            .unwrap();
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| event.event_version)
+                .collect::<Vec<_>>(),
+            vec![4, 5]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deleted_stream_rejects_appends() {
+        let store = store().await;
+        let user_id = Uuid::new_v4();
+        store
+            .save_events(user_id, "user", vec![updated(1), updated(2)], None)
+            .await
+            .unwrap();
+        store
+            .save_snapshot(user_id, "user", 2, json!({ "email": "alice@example.com" }))
+            .await
+            .unwrap();
+
+        assert_eq!(store.delete_stream(user_id, "user").await.unwrap(), 2);
+        assert!(
+            store
+                .load_events(user_id, "user", None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            store
+                .load_snapshot(user_id, "user")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(matches!(
+            store.save_events(user_id, "user", vec![updated(3)], None).await,
+            Err(EventStoreError::StreamDeleted(id)) if id == user_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_tenants_do_not_see_each_others_streams() {
+        let default = store().await;
+        let classroom = default.clone().with_tenant("classroom-42");
+        let item_id = Uuid::new_v4();
+
+        default
+            .save_events(item_id, "vocabulary_item", vec![updated(1)], Some(0))
+            .await
+            .unwrap();
+        // 同じ集約 ID でもテナントが違えば別のストリームになる
+        classroom
+            .save_events(
+                item_id,
+                "vocabulary_item",
+                vec![updated(1), updated(2)],
+                Some(0),
+            )
+            .await
+            .unwrap();
+
+        let events = classroom
+            .load_events(item_id, "vocabulary_item", None)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].tenant_id.as_deref(), Some("classroom-42"));
+        assert_eq!(
+            default
+                .load_events(item_id, "vocabulary_item", None)
+                .await
+                .unwrap()[0]
+                .tenant_id,
+            None
+        );
+
+        let all = default
+            .load_all_events(0, 10, &EventFilter::all())
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(
+            classroom
+                .load_by_correlation_id("ai-request-1")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redelivered_append_is_skipped() {
+        let store = store().await;
+        let item_id = Uuid::new_v4();
+        let event = json!({
+            "event_type": "ItemPublished",
+            "metadata": { "event_id": Uuid::new_v4().to_string() },
+        });
+
+        for _ in 0..2 {
+            store
+                .save_events(item_id, "vocabulary_item", vec![event.clone()], Some(0))
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            store
+                .load_events(item_id, "vocabulary_item", None)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // 別のストリームに同じイベントは保存できない
+        assert!(matches!(
+            store
+                .save_events(Uuid::new_v4(), "vocabulary_item", vec![event], None)
+                .await,
+            Err(EventStoreError::DuplicateEvent(_))
+        ));
+    }
+}
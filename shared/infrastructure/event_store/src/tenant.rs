@@ -0,0 +1,42 @@
+//! テナントごとのストリームの名前空間
+//!
+//! 複数の組織（教室や企業）で1つのデータベースを共有する場合に、
+//! `with_tenant` でテナントを指定した Event Store はそのテナントの
+//! ストリームだけを読み書きする。同じ集約 ID でもテナントが違えば別の
+//! ストリームになり、全体の読み込みや購読にも他のテナントのイベントは
+//! 含まれない。
+//!
+//! ```ignore
+//! let store = PostgresEventStore::new(pool.clone()).with_tenant("classroom-42");
+//! ```
+//!
+//! テナントを指定しない Event Store は既定のテナント（列の値は空文字列）を
+//! 使うため、これまでのストリームはそのまま読める。
+
+/// 既定のテナントを保存する `tenant_id` 列の値
+pub(crate) const DEFAULT_TENANT: &str = "";
+
+/// テナントを `tenant_id` 列の値にする
+pub(crate) fn to_column(tenant_id: Option<&str>) -> &str {
+    tenant_id.unwrap_or(DEFAULT_TENANT)
+}
+
+/// `tenant_id` 列の値をテナントに戻す（既定のテナントは None）
+pub(crate) fn from_column(tenant_id: String) -> Option<String> {
+    (tenant_id != DEFAULT_TENANT).then_some(tenant_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_tenant_round_trips_as_none() {
+        assert_eq!(to_column(None), "");
+        assert_eq!(from_column(to_column(None).to_string()), None);
+        assert_eq!(
+            from_column(to_column(Some("classroom-42")).to_string()),
+            Some("classroom-42".to_string())
+        );
+    }
+}
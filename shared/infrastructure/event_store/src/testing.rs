@@ -0,0 +1,247 @@
+//! テスト用のインメモリ Event Store（`testing` feature）
+//!
+//! 集約ごとのバージョン確認、スナップショット、位置順の全体読み込みを
+//! メモリ上で行う。各クレートのテストは独自の Event Store を書かずに
+//! これを使う
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{EventFilter, EventStore, EventStoreError, PositionedEvent, Snapshot, StoredEvent};
+
+/// メモリ上にイベントとスナップショットを保持する Event Store
+///
+/// `load_events` に渡された `from_version` を記録するので、キャッシュや
+/// スナップショットからの差分読み込みを確認できる
+#[derive(Default)]
+pub struct InMemoryEventStore {
+    events:    Mutex<Vec<StoredEvent>>,
+    snapshots: Mutex<HashMap<Uuid, Snapshot>>,
+    reads:     Mutex<Vec<Option<u32>>>,
+}
+
+impl InMemoryEventStore {
+    /// 空の Event Store を作成
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 保存済みのイベント（保存順）
+    ///
+    /// 返したガードを通して、保存済みのイベントを書き換えることもできる
+    pub fn events(&self) -> MutexGuard<'_, Vec<StoredEvent>> {
+        self.events.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// これまでの `load_events` に渡された `from_version`
+    pub fn reads(&self) -> Vec<Option<u32>> {
+        self.reads
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// 集約のストリームの現在のバージョン
+    pub fn current_version(&self, aggregate_id: Uuid) -> u32 {
+        version_of(&self.events(), aggregate_id)
+    }
+
+    /// バージョンを確認せずにイベントを直接追加する
+    ///
+    /// 欠番のあるストリームや旧スキーマのイベントを再現する場合に使う
+    pub fn push(&self, event: StoredEvent) {
+        self.events().push(event);
+    }
+}
+
+/// `event_data` の `event_type` を種別に持つ保存済みイベントを作成
+#[must_use]
+pub fn stored_event(
+    aggregate_id: Uuid,
+    aggregate_type: &str,
+    event_version: u32,
+    event_data: serde_json::Value,
+) -> StoredEvent {
+    StoredEvent {
+        event_id: Uuid::new_v4(),
+        tenant_id: None,
+        aggregate_id,
+        aggregate_type: aggregate_type.to_string(),
+        event_type: event_data["event_type"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        event_version,
+        event_data,
+        metadata: None,
+        occurred_at: Utc::now(),
+        created_at: Utc::now(),
+    }
+}
+
+fn version_of(events: &[StoredEvent], aggregate_id: Uuid) -> u32 {
+    events
+        .iter()
+        .filter(|event| event.aggregate_id == aggregate_id)
+        .map(|event| event.event_version)
+        .max()
+        .unwrap_or(0)
+}
+
+#[async_trait]
+impl EventStore for InMemoryEventStore {
+    async fn save_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        events: Vec<serde_json::Value>,
+        expected_version: Option<u32>,
+    ) -> Result<(), EventStoreError> {
+        // 確認と追加を同じロックの中で行う
+        let mut stored = self.events();
+        let current = version_of(&stored, aggregate_id);
+        if let Some(expected) = expected_version
+            && expected != current
+        {
+            return Err(EventStoreError::VersionConflict {
+                expected,
+                actual: current,
+            });
+        }
+        for (event_data, version) in events.into_iter().zip(current + 1..) {
+            stored.push(stored_event(
+                aggregate_id,
+                aggregate_type,
+                version,
+                event_data,
+            ));
+        }
+        drop(stored);
+        Ok(())
+    }
+
+    async fn load_events(
+        &self,
+        aggregate_id: Uuid,
+        _aggregate_type: &str,
+        from_version: Option<u32>,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        self.reads
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(from_version);
+        let from = from_version.unwrap_or(0);
+        let mut events: Vec<_> = self
+            .events()
+            .iter()
+            .filter(|event| event.aggregate_id == aggregate_id && event.event_version > from)
+            .cloned()
+            .collect();
+        events.sort_by_key(|event| event.event_version);
+        Ok(events)
+    }
+
+    async fn save_snapshot(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        version: u32,
+        data: serde_json::Value,
+    ) -> Result<(), EventStoreError> {
+        self.snapshots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                aggregate_id,
+                Snapshot {
+                    aggregate_id,
+                    aggregate_type: aggregate_type.to_string(),
+                    aggregate_version: version,
+                    aggregate_data: data,
+                    created_at: Utc::now(),
+                },
+            );
+        Ok(())
+    }
+
+    async fn load_snapshot(
+        &self,
+        aggregate_id: Uuid,
+        _aggregate_type: &str,
+    ) -> Result<Option<Snapshot>, EventStoreError> {
+        Ok(self
+            .snapshots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&aggregate_id)
+            .cloned())
+    }
+
+    /// 保存順を位置（1 始まり）として読み込む
+    async fn load_all_events(
+        &self,
+        from_position: i64,
+        limit: usize,
+        filter: &EventFilter,
+    ) -> Result<Vec<PositionedEvent>, EventStoreError> {
+        Ok(self
+            .events()
+            .iter()
+            .zip(1_i64..)
+            .filter(|(event, position)| *position > from_position && filter.matches(event))
+            .take(limit)
+            .map(|(event, position)| PositionedEvent {
+                position,
+                event: event.clone(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rejects_unexpected_version_per_stream() {
+        let store = InMemoryEventStore::new();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        store
+            .save_events(first, "item", vec![json!({ "event_type": "A" })], Some(0))
+            .await
+            .unwrap();
+        store
+            .save_events(second, "item", vec![json!({ "event_type": "B" })], Some(0))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            store
+                .save_events(first, "item", vec![json!({ "event_type": "A" })], Some(0))
+                .await,
+            Err(EventStoreError::VersionConflict {
+                expected: 0,
+                actual:   1,
+            })
+        ));
+        assert_eq!(store.current_version(first), 1);
+
+        let all = store
+            .load_all_events(1, 10, &EventFilter::all())
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].position, 2);
+        assert_eq!(all[0].event.event_type, "B");
+    }
+}
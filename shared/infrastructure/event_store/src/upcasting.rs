@@ -0,0 +1,612 @@
+//! 読み込み時のイベントの形式変換
+//!
+//! 保存済みのイベントは書き換えずに残し、読み込むときに [`UpcasterRegistry`]
+//! で現在の形式まで順に変換する。[`Upcaster`] は1つのイベント種別の
+//! `schema_version` を1つだけ上げ、v1 → v2 → v3 のように連鎖させる。
+//!
+//! `schema_version` はメタデータの `schema_version`、なければイベント本体の
+//! `schema_version` から読み、どちらもなければ 1 とみなす。
+//!
+//! フィールドの改名と、必須になったフィールドの既定値の補完はよくある変換の
+//! ため [`RenameField`] と [`DefaultField`] を用意している。
+//! [`UpcasterRegistry::deserialize`] は変換してから現在のイベント型に復元する。
+//!
+//! ```ignore
+//! let store = UpcastingEventStore::new(
+//!     Arc::new(PostgresEventStore::new(pool)),
+//!     UpcasterRegistry::new()
+//!         .register(Arc::new(SplitExampleSentences))
+//!         .register(Arc::new(RenameField::new("ItemCreated", 2, "spelling", "headword"))),
+//! );
+//! ```
+
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use crate::{
+    EncodedEvent,
+    EventFilter,
+    EventStore,
+    EventStoreError,
+    EventSubscription,
+    NewEvent,
+    PositionedEvent,
+    Snapshot,
+    StoredEvent,
+    StreamAppend,
+};
+
+/// スキーマバージョンが記録されていないイベントのバージョン
+pub const INITIAL_SCHEMA_VERSION: u32 = 1;
+
+/// 1つのイベント種別のスキーマバージョンを1つ上げる変換
+pub trait Upcaster: Send + Sync {
+    /// 対象のイベント種別
+    fn event_type(&self) -> &str;
+
+    /// 変換前のスキーマバージョン（`source_version + 1` に変換する）
+    fn source_version(&self) -> u32;
+
+    /// イベント本体を変換する
+    fn upcast(&self, event_data: serde_json::Value) -> Result<serde_json::Value, EventStoreError>;
+}
+
+/// イベント種別と変換前のバージョンごとの変換
+#[derive(Default, Clone)]
+pub struct UpcasterRegistry {
+    upcasters: HashMap<(String, u32), Arc<dyn Upcaster>>,
+}
+
+impl UpcasterRegistry {
+    /// 空のレジストリを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 変換を登録する（同じ種別とバージョンの変換は置き換える）
+    pub fn register(mut self, upcaster: Arc<dyn Upcaster>) -> Self {
+        let key = (upcaster.event_type().to_string(), upcaster.source_version());
+        self.upcasters.insert(key, upcaster);
+        self
+    }
+
+    /// イベント種別の現在のスキーマバージョン
+    pub fn current_version(&self, event_type: &str) -> u32 {
+        let mut version = INITIAL_SCHEMA_VERSION;
+        while self.find(event_type, version).is_some() {
+            version += 1;
+        }
+        version
+    }
+
+    /// イベントを現在の形式まで変換する（変換後のバージョンをメタデータに記録する）
+    pub fn upcast(&self, mut event: StoredEvent) -> Result<StoredEvent, EventStoreError> {
+        let original = schema_version(&event);
+        let mut version = original;
+        while let Some(upcaster) = self.find(&event.event_type, version) {
+            event.event_data = upcaster.upcast(event.event_data)?;
+            version += 1;
+        }
+
+        if version != original {
+            let metadata = event.metadata.get_or_insert_with(|| serde_json::json!({}));
+            if let Some(metadata) = metadata.as_object_mut() {
+                metadata.insert("schema_version".to_string(), version.into());
+            }
+        }
+        Ok(event)
+    }
+
+    /// イベントを現在の形式まで変換し、イベント型に復元する
+    pub fn deserialize<T>(&self, event: StoredEvent) -> Result<T, EventStoreError>
+    where
+        T: DeserializeOwned,
+    {
+        let event = self.upcast(event)?;
+        Ok(serde_json::from_value(event.event_data)?)
+    }
+
+    fn find(&self, event_type: &str, version: u32) -> Option<&Arc<dyn Upcaster>> {
+        self.upcasters.get(&(event_type.to_string(), version))
+    }
+}
+
+/// フィールドを改名する変換
+pub struct RenameField {
+    event_type:     String,
+    source_version: u32,
+    from:           String,
+    to:             String,
+}
+
+impl RenameField {
+    /// `source_version` の `event_type` の `from` を `to` に改名する変換を作成
+    pub fn new(
+        event_type: impl Into<String>,
+        source_version: u32,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        Self {
+            event_type: event_type.into(),
+            source_version,
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+}
+
+impl Upcaster for RenameField {
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn source_version(&self) -> u32 {
+        self.source_version
+    }
+
+    fn upcast(
+        &self,
+        mut event_data: serde_json::Value,
+    ) -> Result<serde_json::Value, EventStoreError> {
+        if let Some(data) = event_data.as_object_mut()
+            && let Some(value) = data.remove(&self.from)
+        {
+            data.insert(self.to.clone(), value);
+        }
+        Ok(event_data)
+    }
+}
+
+/// 必須になったフィールドに既定値を入れる変換
+///
+/// 値がすでにあるイベントはそのまま残す
+pub struct DefaultField {
+    event_type:     String,
+    source_version: u32,
+    field:          String,
+    default:        serde_json::Value,
+}
+
+impl DefaultField {
+    /// `source_version` の `event_type` に `field` がなければ
+    /// `default` を入れる変換を作成
+    pub fn new(
+        event_type: impl Into<String>,
+        source_version: u32,
+        field: impl Into<String>,
+        default: serde_json::Value,
+    ) -> Self {
+        Self {
+            event_type: event_type.into(),
+            source_version,
+            field: field.into(),
+            default,
+        }
+    }
+}
+
+impl Upcaster for DefaultField {
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn source_version(&self) -> u32 {
+        self.source_version
+    }
+
+    fn upcast(
+        &self,
+        mut event_data: serde_json::Value,
+    ) -> Result<serde_json::Value, EventStoreError> {
+        let data = event_data.as_object_mut().ok_or_else(|| {
+            EventStoreError::Internal(format!("{} event is not an object", self.event_type))
+        })?;
+        data.entry(self.field.as_str())
+            .or_insert_with(|| self.default.clone());
+        Ok(event_data)
+    }
+}
+
+/// 保存済みイベントのスキーマバージョン
+pub fn schema_version(event: &StoredEvent) -> u32 {
+    event
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get("schema_version"))
+        .or_else(|| event.event_data.get("schema_version"))
+        .and_then(serde_json::Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok())
+        .unwrap_or(INITIAL_SCHEMA_VERSION)
+}
+
+/// 読み込んだイベントを現在の形式に変換する Event Store
+pub struct UpcastingEventStore {
+    inner:    Arc<dyn EventStore>,
+    registry: Arc<UpcasterRegistry>,
+}
+
+impl UpcastingEventStore {
+    /// 新しい Event Store を作成
+    pub fn new(inner: Arc<dyn EventStore>, registry: UpcasterRegistry) -> Self {
+        Self {
+            inner,
+            registry: Arc::new(registry),
+        }
+    }
+}
+
+#[async_trait]
+impl EventStore for UpcastingEventStore {
+    async fn save_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        events: Vec<serde_json::Value>,
+        expected_version: Option<u32>,
+    ) -> Result<(), EventStoreError> {
+        self.inner
+            .save_events(aggregate_id, aggregate_type, events, expected_version)
+            .await
+    }
+
+    async fn save_events_batch(&self, appends: Vec<StreamAppend>) -> Result<(), EventStoreError> {
+        self.inner.save_events_batch(appends).await
+    }
+
+    async fn save_encoded_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        events: Vec<NewEvent>,
+        expected_version: Option<u32>,
+    ) -> Result<(), EventStoreError> {
+        self.inner
+            .save_encoded_events(aggregate_id, aggregate_type, events, expected_version)
+            .await
+    }
+
+    async fn load_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        from_version: Option<u32>,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        self.inner
+            .load_events(aggregate_id, aggregate_type, from_version)
+            .await?
+            .into_iter()
+            .map(|event| self.registry.upcast(event))
+            .collect()
+    }
+
+    async fn load_events_backward(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        from_version: Option<u32>,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        self.inner
+            .load_events_backward(aggregate_id, aggregate_type, from_version, limit)
+            .await?
+            .into_iter()
+            .map(|event| self.registry.upcast(event))
+            .collect()
+    }
+
+    /// Protobuf のイベントは型ごとの互換性で扱うため変換しない
+    async fn load_encoded_events(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        from_version: Option<u32>,
+    ) -> Result<Vec<EncodedEvent>, EventStoreError> {
+        self.inner
+            .load_encoded_events(aggregate_id, aggregate_type, from_version)
+            .await
+    }
+
+    async fn save_snapshot(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        version: u32,
+        data: serde_json::Value,
+    ) -> Result<(), EventStoreError> {
+        self.inner
+            .save_snapshot(aggregate_id, aggregate_type, version, data)
+            .await
+    }
+
+    async fn load_snapshot(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+    ) -> Result<Option<Snapshot>, EventStoreError> {
+        self.inner.load_snapshot(aggregate_id, aggregate_type).await
+    }
+
+    async fn load_by_correlation_id(
+        &self,
+        correlation_id: &str,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        self.inner
+            .load_by_correlation_id(correlation_id)
+            .await?
+            .into_iter()
+            .map(|event| self.registry.upcast(event))
+            .collect()
+    }
+
+    async fn truncate_stream(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+        before_version: u32,
+    ) -> Result<u64, EventStoreError> {
+        self.inner
+            .truncate_stream(aggregate_id, aggregate_type, before_version)
+            .await
+    }
+
+    async fn delete_stream(
+        &self,
+        aggregate_id: Uuid,
+        aggregate_type: &str,
+    ) -> Result<u64, EventStoreError> {
+        self.inner.delete_stream(aggregate_id, aggregate_type).await
+    }
+
+    async fn load_all_events(
+        &self,
+        from_position: i64,
+        limit: usize,
+        filter: &EventFilter,
+    ) -> Result<Vec<PositionedEvent>, EventStoreError> {
+        self.inner
+            .load_all_events(from_position, limit, filter)
+            .await?
+            .into_iter()
+            .map(|positioned| {
+                Ok(PositionedEvent {
+                    position: positioned.position,
+                    event:    self.registry.upcast(positioned.event)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn load_by_event_type(
+        &self,
+        event_type: &str,
+        from_position: i64,
+        limit: usize,
+    ) -> Result<Vec<PositionedEvent>, EventStoreError> {
+        self.inner
+            .load_by_event_type(event_type, from_position, limit)
+            .await?
+            .into_iter()
+            .map(|positioned| {
+                Ok(PositionedEvent {
+                    position: positioned.position,
+                    event:    self.registry.upcast(positioned.event)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn subscribe_all(
+        &self,
+        from_position: i64,
+    ) -> Result<EventSubscription, EventStoreError> {
+        let registry = Arc::clone(&self.registry);
+        let subscription = self.inner.subscribe_all(from_position).await?;
+        Ok(subscription
+            .map(move |positioned| {
+                let positioned = positioned?;
+                Ok(PositionedEvent {
+                    position: positioned.position,
+                    event:    registry.upcast(positioned.event)?,
+                })
+            })
+            .boxed())
+    }
+
+    fn tenant_id(&self) -> Option<&str> {
+        self.inner.tenant_id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use super::*;
+    use crate::testing::InMemoryEventStore;
+
+    /// v1: `example` に例文を1つ持つ
+    struct ExampleToExamples;
+
+    impl Upcaster for ExampleToExamples {
+        fn event_type(&self) -> &str {
+            "ItemCreated"
+        }
+
+        fn source_version(&self) -> u32 {
+            1
+        }
+
+        fn upcast(
+            &self,
+            mut event_data: serde_json::Value,
+        ) -> Result<serde_json::Value, EventStoreError> {
+            let example = event_data
+                .as_object_mut()
+                .and_then(|data| data.remove("example"))
+                .ok_or_else(|| EventStoreError::Internal("missing example".to_string()))?;
+            event_data["examples"] = json!([example]);
+            Ok(event_data)
+        }
+    }
+
+    /// v2: `spelling` を `headword` に改名
+    struct RenameSpelling;
+
+    impl Upcaster for RenameSpelling {
+        fn event_type(&self) -> &str {
+            "ItemCreated"
+        }
+
+        fn source_version(&self) -> u32 {
+            2
+        }
+
+        fn upcast(
+            &self,
+            mut event_data: serde_json::Value,
+        ) -> Result<serde_json::Value, EventStoreError> {
+            if let Some(spelling) = event_data
+                .as_object_mut()
+                .and_then(|data| data.remove("spelling"))
+            {
+                event_data["headword"] = spelling;
+            }
+            Ok(event_data)
+        }
+    }
+
+    fn registry() -> UpcasterRegistry {
+        UpcasterRegistry::new()
+            .register(Arc::new(RenameSpelling))
+            .register(Arc::new(ExampleToExamples))
+    }
+
+    fn stored(event_data: serde_json::Value, metadata: Option<serde_json::Value>) -> StoredEvent {
+        StoredEvent {
+            event_id: Uuid::new_v4(),
+            tenant_id: None,
+            aggregate_id: Uuid::new_v4(),
+            aggregate_type: "vocabulary_item".to_string(),
+            event_type: event_data["event_type"].as_str().unwrap().to_string(),
+            event_version: 1,
+            event_data,
+            metadata,
+            occurred_at: Utc::now(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_upcasts_through_every_later_version() {
+        let registry = registry();
+        assert_eq!(registry.current_version("ItemCreated"), 3);
+        assert_eq!(registry.current_version("ItemDeleted"), 1);
+
+        let v1 = stored(
+            json!({ "event_type": "ItemCreated", "spelling": "apple", "example": "An apple a day" }),
+            None,
+        );
+        let upcasted = registry.upcast(v1).unwrap();
+        assert_eq!(upcasted.event_data["headword"], "apple");
+        assert_eq!(upcasted.event_data["examples"], json!(["An apple a day"]));
+        assert_eq!(schema_version(&upcasted), 3);
+
+        let v2 = stored(
+            json!({ "event_type": "ItemCreated", "spelling": "pear", "examples": [] }),
+            Some(json!({ "schema_version": 2, "correlation_id": "import-1" })),
+        );
+        let upcasted = registry.upcast(v2).unwrap();
+        assert_eq!(upcasted.event_data["headword"], "pear");
+        assert_eq!(upcasted.metadata.unwrap()["correlation_id"], "import-1");
+
+        let current = stored(
+            json!({ "event_type": "ItemCreated", "headword": "plum", "examples": [] }),
+            Some(json!({ "schema_version": 3 })),
+        );
+        assert_eq!(
+            registry.upcast(current.clone()).unwrap().event_data,
+            current.event_data
+        );
+    }
+
+    #[test]
+    fn test_deserializes_old_events_into_the_current_shape() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct ItemPublished {
+            headword:   String,
+            cefr_level: String,
+        }
+
+        let registry = UpcasterRegistry::new()
+            .register(Arc::new(RenameField::new(
+                "ItemPublished",
+                1,
+                "spelling",
+                "headword",
+            )))
+            .register(Arc::new(DefaultField::new(
+                "ItemPublished",
+                2,
+                "cefr_level",
+                json!("unknown"),
+            )));
+
+        let v1 = stored(
+            json!({ "event_type": "ItemPublished", "spelling": "apple" }),
+            None,
+        );
+        let v2 = stored(
+            json!({ "event_type": "ItemPublished", "headword": "pear", "cefr_level": "A2" }),
+            Some(json!({ "schema_version": 2 })),
+        );
+
+        assert_eq!(
+            registry.deserialize::<ItemPublished>(v1).unwrap(),
+            ItemPublished {
+                headword:   "apple".to_string(),
+                cefr_level: "unknown".to_string(),
+            }
+        );
+        assert_eq!(
+            registry
+                .deserialize::<ItemPublished>(v2)
+                .unwrap()
+                .cefr_level,
+            "A2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_upcasts_loaded_events_and_reports_failures() {
+        let inner = Arc::new(InMemoryEventStore::new());
+        let item_id = Uuid::new_v4();
+        inner.push(StoredEvent {
+            aggregate_id: item_id,
+            ..stored(
+                json!({ "event_type": "ItemCreated", "spelling": "fig", "example": "Dried figs" }),
+                None,
+            )
+        });
+        let store = UpcastingEventStore::new(Arc::clone(&inner) as Arc<dyn EventStore>, registry());
+
+        let events = store
+            .load_events(item_id, "vocabulary_item", None)
+            .await
+            .unwrap();
+        assert_eq!(events[0].event_data["headword"], "fig");
+
+        inner.events()[0].event_data = json!({ "event_type": "ItemCreated" });
+        assert!(
+            store
+                .load_events(item_id, "vocabulary_item", None)
+                .await
+                .is_err()
+        );
+    }
+}
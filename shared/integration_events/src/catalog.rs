@@ -0,0 +1,104 @@
+//! 統合イベントのカタログ（所有コンテキスト・スキーマバージョン）
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    context::OwningContext,
+    events::{NotificationRequested, PurgeUserData, ReadModelChanged},
+};
+
+/// コンテキスト間で流れるメッセージ
+///
+/// スキーマを変更する場合は `SCHEMA_VERSION` を上げ、
+/// 旧バージョンの JSON も引き続きデシリアライズできるようにすること
+pub trait IntegrationMessage: Serialize + DeserializeOwned + Send + Sync {
+    /// イベントタイプ名
+    const EVENT_TYPE: &'static str;
+    /// スキーマを所有するコンテキスト
+    const OWNER: OwningContext;
+    /// 現在のスキーマバージョン
+    const SCHEMA_VERSION: u32;
+    /// スキーマの説明（レジストリに登録される）
+    const DESCRIPTION: &'static str;
+
+    /// JSON Schema 定義
+    fn json_schema() -> &'static str;
+
+    /// ルーティング・順序付けに使う集約 ID
+    fn aggregate_id(&self) -> String;
+
+    /// スキーマレジストリ上のキー（`<context>.<EventType>`）
+    fn registry_key() -> String {
+        format!("{}.{}", Self::OWNER.as_str(), Self::EVENT_TYPE)
+    }
+}
+
+/// スキーマレジストリに登録するための記述子
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDescriptor {
+    pub event_type:  &'static str,
+    pub owner:       OwningContext,
+    pub version:     u32,
+    pub description: &'static str,
+    pub definition:  &'static str,
+}
+
+impl SchemaDescriptor {
+    pub fn of<T: IntegrationMessage>() -> Self {
+        Self {
+            event_type:  T::EVENT_TYPE,
+            owner:       T::OWNER,
+            version:     T::SCHEMA_VERSION,
+            description: T::DESCRIPTION,
+            definition:  T::json_schema(),
+        }
+    }
+
+    /// スキーマレジストリ上のキー（`<context>.<EventType>`）
+    pub fn registry_key(&self) -> String {
+        format!("{}.{}", self.owner.as_str(), self.event_type)
+    }
+}
+
+/// すべての統合イベントのスキーマ
+pub fn all_schemas() -> Vec<SchemaDescriptor> {
+    vec![
+        SchemaDescriptor::of::<NotificationRequested>(),
+        SchemaDescriptor::of::<PurgeUserData>(),
+        SchemaDescriptor::of::<ReadModelChanged>(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_all_schemas_have_unique_keys_and_valid_definitions() {
+        let schemas = all_schemas();
+        let keys: HashSet<String> = schemas.iter().map(SchemaDescriptor::registry_key).collect();
+        assert_eq!(keys.len(), schemas.len());
+
+        for schema in &schemas {
+            assert!(schema.version >= 1);
+            let definition: serde_json::Value = serde_json::from_str(schema.definition)
+                .unwrap_or_else(|e| panic!("{} has invalid schema: {e}", schema.event_type));
+            assert_eq!(definition["title"], schema.event_type);
+        }
+    }
+
+    #[test]
+    fn test_registry_keys_include_owner() {
+        assert_eq!(
+            NotificationRequested::registry_key(),
+            "platform.NotificationRequested"
+        );
+        assert_eq!(PurgeUserData::registry_key(), "user.PurgeUserData");
+        assert_eq!(
+            ReadModelChanged::registry_key(),
+            "platform.ReadModelChanged"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! イベントの所有コンテキスト
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// 統合イベントのスキーマを所有する Bounded Context
+///
+/// スキーマの変更は所有コンテキストだけが行い、他のコンテキストは購読のみ行う
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OwningContext {
+    User,
+    Vocabulary,
+    Learning,
+    Algorithm,
+    Progress,
+    Ai,
+    /// 特定のドメインに属さない基盤的なメッセージ（通知、Read Model
+    /// の変更通知など）
+    Platform,
+}
+
+impl OwningContext {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Vocabulary => "vocabulary",
+            Self::Learning => "learning",
+            Self::Algorithm => "algorithm",
+            Self::Progress => "progress",
+            Self::Ai => "ai",
+            Self::Platform => "platform",
+        }
+    }
+}
+
+impl fmt::Display for OwningContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,201 @@
+//! EventBus で送受信するための封筒
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::{
+    catalog::IntegrationMessage,
+    context::OwningContext,
+    error::{IntegrationEventError, Result},
+};
+
+/// 統合イベントの封筒
+///
+/// イベントタイプ・所有コンテキスト・スキーマバージョンを本文と一緒に運び、
+/// 受信側でバージョンを確認してからデシリアライズできるようにする
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntegrationEnvelope {
+    pub event_type:     String,
+    pub owner:          OwningContext,
+    pub schema_version: u32,
+    pub aggregate_id:   String,
+    pub payload:        JsonValue,
+}
+
+impl IntegrationEnvelope {
+    /// メッセージを封筒に入れる
+    pub fn wrap<T: IntegrationMessage>(message: &T) -> Result<Self> {
+        Ok(Self {
+            event_type:     T::EVENT_TYPE.to_string(),
+            owner:          T::OWNER,
+            schema_version: T::SCHEMA_VERSION,
+            aggregate_id:   message.aggregate_id(),
+            payload:        serde_json::to_value(message)?,
+        })
+    }
+
+    /// 封筒からメッセージを取り出す
+    ///
+    /// 受信側が知らない新しいバージョンのスキーマは拒否する
+    pub fn open<T: IntegrationMessage>(&self) -> Result<T> {
+        if self.event_type != T::EVENT_TYPE {
+            return Err(IntegrationEventError::TypeMismatch {
+                expected: T::EVENT_TYPE.to_string(),
+                actual:   self.event_type.clone(),
+            });
+        }
+        if self.schema_version > T::SCHEMA_VERSION {
+            return Err(IntegrationEventError::UnsupportedVersion {
+                event_type: self.event_type.clone(),
+                version:    self.schema_version,
+                supported:  T::SCHEMA_VERSION,
+            });
+        }
+
+        Ok(serde_json::from_value(self.payload.clone())?)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    /// バイト列からメッセージを復元する
+    ///
+    /// 封筒形式に加え、封筒導入前に保存・アウトボックスに積まれた
+    /// 素の JSON もそのまま受け付ける
+    pub fn decode<T: IntegrationMessage>(bytes: &[u8]) -> Result<T> {
+        let value: JsonValue = serde_json::from_slice(bytes)?;
+        if value.get("event_type").is_some() && value.get("payload").is_some() {
+            let envelope: Self = serde_json::from_value(value)?;
+            envelope.open()
+        } else {
+            Ok(serde_json::from_value(value)?)
+        }
+    }
+}
+
+#[cfg(feature = "event-bus")]
+impl shared_event_bus::Event for IntegrationEnvelope {
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn aggregate_id(&self) -> &str {
+        &self.aggregate_id
+    }
+}
+
+/// 統合イベントを TopicRegistry のルーティングに従って発行する
+#[cfg(feature = "event-bus")]
+pub async fn publish<B, T>(
+    bus: &B,
+    topics: &crate::topics::TopicRegistry,
+    message: &T,
+) -> Result<()>
+where
+    B: shared_event_bus::EventBus,
+    T: IntegrationMessage,
+{
+    let envelope = IntegrationEnvelope::wrap(message)?;
+    let topic = topics.route(&envelope)?;
+    bus.publish(topic, &envelope)
+        .await
+        .map_err(|e| IntegrationEventError::Publish(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::events::{
+        NotificationCategory,
+        NotificationChannel,
+        NotificationRequested,
+        PurgeUserData,
+        ReadModelChanged,
+    };
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let message = NotificationRequested::new(
+            "0f8c1f7e-6a62-4a0e-9a53-1b2f5b8e9d01",
+            NotificationCategory::GoalReminder,
+            NotificationChannel::Email,
+            "streak_reminder",
+            OwningContext::Progress,
+        )
+        .with_param("days", "7");
+
+        let envelope = IntegrationEnvelope::wrap(&message).unwrap();
+        assert_eq!(envelope.owner, OwningContext::Platform);
+        assert_eq!(envelope.schema_version, 2);
+        assert_eq!(envelope.aggregate_id, message.user_id);
+
+        let bytes = envelope.to_bytes().unwrap();
+        let decoded: NotificationRequested = IntegrationEnvelope::decode(&bytes).unwrap();
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn test_decode_accepts_pre_envelope_json_shapes() {
+        // 封筒導入前にアウトボックスへ保存されていた形式
+        let notification = br#"{
+            "event_id": "6c1a3f5e-0000-4000-8000-000000000001",
+            "occurred_at": "2025-08-01T09:00:00Z",
+            "user_id": "0f8c1f7e-6a62-4a0e-9a53-1b2f5b8e9d01",
+            "channel": "in_app",
+            "template": "weekly_summary",
+            "source_context": "progress"
+        }"#;
+        let decoded: NotificationRequested = IntegrationEnvelope::decode(notification).unwrap();
+        assert_eq!(decoded.channel, NotificationChannel::InApp);
+        assert!(decoded.category.is_none());
+        assert!(decoded.not_before.is_none());
+        assert!(decoded.params.is_empty());
+        assert_eq!(
+            decoded.occurred_at,
+            Utc.with_ymd_and_hms(2025, 8, 1, 9, 0, 0).unwrap()
+        );
+
+        let purge = br#"{
+            "event_id": "6c1a3f5e-0000-4000-8000-000000000002",
+            "occurred_at": "2025-08-01T09:00:00Z",
+            "user_id": "0f8c1f7e-6a62-4a0e-9a53-1b2f5b8e9d01"
+        }"#;
+        let decoded: PurgeUserData = IntegrationEnvelope::decode(purge).unwrap();
+        assert!(decoded.reason.is_none());
+        assert!(decoded.purge_before.is_none());
+
+        let changed = br#"{
+            "event_id": "6c1a3f5e-0000-4000-8000-000000000003",
+            "occurred_at": "2025-08-01T09:00:00Z",
+            "source_context": "vocabulary",
+            "read_model": "vocabulary_items_read",
+            "entity_id": "item-1",
+            "version": 3
+        }"#;
+        let decoded: ReadModelChanged = IntegrationEnvelope::decode(changed).unwrap();
+        assert_eq!(decoded.version, 3);
+
+        // 現行の形式は移行前の形式と同じフィールド名で書き出される
+        let value = serde_json::to_value(&decoded).unwrap();
+        let original: JsonValue = serde_json::from_slice(changed).unwrap();
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn test_open_rejects_mismatched_type_and_newer_version() {
+        let mut envelope = IntegrationEnvelope::wrap(&PurgeUserData::new("user-1")).unwrap();
+        assert!(matches!(
+            envelope.open::<ReadModelChanged>(),
+            Err(IntegrationEventError::TypeMismatch { .. })
+        ));
+
+        envelope.schema_version = 2;
+        assert!(matches!(
+            envelope.open::<PurgeUserData>(),
+            Err(IntegrationEventError::UnsupportedVersion { version: 2, .. })
+        ));
+    }
+}
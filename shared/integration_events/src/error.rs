@@ -0,0 +1,30 @@
+//! 統合イベントのエラー型
+
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, IntegrationEventError>;
+
+#[derive(Debug, Error)]
+pub enum IntegrationEventError {
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Event type mismatch: expected {expected}, got {actual}")]
+    TypeMismatch { expected: String, actual: String },
+
+    #[error("Unsupported schema version for {event_type}: {version} (supported up to {supported})")]
+    UnsupportedVersion {
+        event_type: String,
+        version:    u32,
+        supported:  u32,
+    },
+
+    #[error("No topic registered for event type: {0}")]
+    UnknownRoute(String),
+
+    #[error("Publish error: {0}")]
+    Publish(String),
+
+    #[error("Notification preference lookup error: {0}")]
+    PreferenceLookup(String),
+}
@@ -0,0 +1,9 @@
+//! 統合イベントの定義
+
+pub mod notification;
+pub mod read_model;
+pub mod user_data;
+
+pub use notification::{NotificationCategory, NotificationChannel, NotificationRequested};
+pub use read_model::ReadModelChanged;
+pub use user_data::PurgeUserData;
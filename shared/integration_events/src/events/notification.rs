@@ -0,0 +1,142 @@
+//! 通知リクエスト
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared_kernel::IntegrationEvent;
+use uuid::Uuid;
+
+use crate::{catalog::IntegrationMessage, context::OwningContext};
+
+/// 通知チャネル
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannel {
+    Email,
+    Push,
+    InApp,
+}
+
+/// 通知カテゴリ（ユーザーが個別に有効・無効を切り替えられる単位）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    DailySummary,
+    GoalReminder,
+    ReviewReminder,
+    Editorial,
+    WeeklyReport,
+}
+
+impl NotificationCategory {
+    pub const ALL: [Self; 5] = [
+        Self::DailySummary,
+        Self::GoalReminder,
+        Self::ReviewReminder,
+        Self::Editorial,
+        Self::WeeklyReport,
+    ];
+}
+
+/// ユーザーへの通知が要求された
+///
+/// 各コンテキストはテンプレート名とパラメータだけを渡し、
+/// 配信方法は通知基盤に任せる
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationRequested {
+    pub event_id:       String,
+    pub occurred_at:    DateTime<Utc>,
+    pub user_id:        String,
+    /// 通知カテゴリ（v1 のメッセージには存在しない）
+    #[serde(default)]
+    pub category:       Option<NotificationCategory>,
+    pub channel:        NotificationChannel,
+    pub template:       String,
+    #[serde(default)]
+    pub params:         HashMap<String, String>,
+    /// 通知を要求したコンテキスト
+    pub source_context: String,
+    /// この時刻より前には配信しない（おやすみ時間による延期）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before:     Option<DateTime<Utc>>,
+}
+
+impl NotificationRequested {
+    pub fn new(
+        user_id: impl Into<String>,
+        category: NotificationCategory,
+        channel: NotificationChannel,
+        template: impl Into<String>,
+        source_context: OwningContext,
+    ) -> Self {
+        Self {
+            event_id: Uuid::new_v4().to_string(),
+            occurred_at: Utc::now(),
+            user_id: user_id.into(),
+            category: Some(category),
+            channel,
+            template: template.into(),
+            params: HashMap::new(),
+            source_context: source_context.as_str().to_string(),
+            not_before: None,
+        }
+    }
+
+    /// テンプレートパラメータを追加
+    pub fn with_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.insert(key.into(), value.into());
+        self
+    }
+}
+
+impl IntegrationMessage for NotificationRequested {
+    const EVENT_TYPE: &'static str = "NotificationRequested";
+    const OWNER: OwningContext = OwningContext::Platform;
+    const SCHEMA_VERSION: u32 = 2;
+    const DESCRIPTION: &'static str = "Notification delivery requested for a user";
+
+    fn json_schema() -> &'static str {
+        r##"{
+            "title": "NotificationRequested",
+            "type": "object",
+            "required": ["event_id", "occurred_at", "user_id", "channel", "template", "source_context"],
+            "properties": {
+                "event_id": { "type": "string", "format": "uuid" },
+                "occurred_at": { "type": "string", "format": "date-time" },
+                "user_id": { "type": "string", "format": "uuid" },
+                "category": {
+                    "type": ["string", "null"],
+                    "enum": ["daily_summary", "goal_reminder", "review_reminder", "editorial", "weekly_report", null]
+                },
+                "channel": { "type": "string", "enum": ["email", "push", "in_app"] },
+                "template": { "type": "string", "minLength": 1 },
+                "params": { "type": "object", "additionalProperties": { "type": "string" } },
+                "source_context": { "type": "string" },
+                "not_before": { "type": "string", "format": "date-time" }
+            }
+        }"##
+    }
+
+    fn aggregate_id(&self) -> String {
+        self.user_id.clone()
+    }
+}
+
+impl IntegrationEvent for NotificationRequested {
+    fn event_type(&self) -> &str {
+        Self::EVENT_TYPE
+    }
+
+    fn source_context(&self) -> &str {
+        &self.source_context
+    }
+
+    fn event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+}
@@ -0,0 +1,88 @@
+//! Read Model の変更通知
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared_kernel::IntegrationEvent;
+use uuid::Uuid;
+
+use crate::{catalog::IntegrationMessage, context::OwningContext};
+
+/// Read Model が更新された
+///
+/// プロジェクションが発行し、
+/// キャッシュの無効化や検索インデックスの更新に使われる
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadModelChanged {
+    pub event_id:       String,
+    pub occurred_at:    DateTime<Utc>,
+    /// Read Model を更新したコンテキスト
+    pub source_context: String,
+    /// Read Model 名（例: "vocabulary_items_read"）
+    pub read_model:     String,
+    pub entity_id:      String,
+    /// 更新後のバージョン
+    pub version:        i64,
+}
+
+impl ReadModelChanged {
+    pub fn new(
+        source_context: OwningContext,
+        read_model: impl Into<String>,
+        entity_id: impl Into<String>,
+        version: i64,
+    ) -> Self {
+        Self {
+            event_id: Uuid::new_v4().to_string(),
+            occurred_at: Utc::now(),
+            source_context: source_context.as_str().to_string(),
+            read_model: read_model.into(),
+            entity_id: entity_id.into(),
+            version,
+        }
+    }
+}
+
+impl IntegrationMessage for ReadModelChanged {
+    const EVENT_TYPE: &'static str = "ReadModelChanged";
+    const OWNER: OwningContext = OwningContext::Platform;
+    const SCHEMA_VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "A read model row was updated by a projection";
+
+    fn json_schema() -> &'static str {
+        r##"{
+            "title": "ReadModelChanged",
+            "type": "object",
+            "required": ["event_id", "occurred_at", "source_context", "read_model", "entity_id", "version"],
+            "properties": {
+                "event_id": { "type": "string", "format": "uuid" },
+                "occurred_at": { "type": "string", "format": "date-time" },
+                "source_context": { "type": "string" },
+                "read_model": { "type": "string", "minLength": 1 },
+                "entity_id": { "type": "string" },
+                "version": { "type": "integer" }
+            }
+        }"##
+    }
+
+    fn aggregate_id(&self) -> String {
+        self.entity_id.clone()
+    }
+}
+
+impl IntegrationEvent for ReadModelChanged {
+    fn event_type(&self) -> &str {
+        Self::EVENT_TYPE
+    }
+
+    fn source_context(&self) -> &str {
+        &self.source_context
+    }
+
+    fn event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+}
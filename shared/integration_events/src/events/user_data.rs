@@ -0,0 +1,86 @@
+//! ユーザーデータの削除要求
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared_kernel::IntegrationEvent;
+use uuid::Uuid;
+
+use crate::{catalog::IntegrationMessage, context::OwningContext};
+
+/// ユーザーの個人データの削除が要求された
+///
+/// User Context
+/// が発行し、ユーザーデータを保持するすべてのコンテキストが購読する
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PurgeUserData {
+    pub event_id:     String,
+    pub occurred_at:  DateTime<Utc>,
+    pub user_id:      String,
+    /// 削除の理由（アカウント削除、管理者による削除など）
+    #[serde(default)]
+    pub reason:       Option<String>,
+    /// この日時までに削除を完了すること
+    #[serde(default)]
+    pub purge_before: Option<DateTime<Utc>>,
+}
+
+impl PurgeUserData {
+    pub fn new(user_id: impl Into<String>) -> Self {
+        Self {
+            event_id:     Uuid::new_v4().to_string(),
+            occurred_at:  Utc::now(),
+            user_id:      user_id.into(),
+            reason:       None,
+            purge_before: None,
+        }
+    }
+
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+impl IntegrationMessage for PurgeUserData {
+    const EVENT_TYPE: &'static str = "PurgeUserData";
+    const OWNER: OwningContext = OwningContext::User;
+    const SCHEMA_VERSION: u32 = 1;
+    const DESCRIPTION: &'static str = "Request to purge all personal data of a user";
+
+    fn json_schema() -> &'static str {
+        r##"{
+            "title": "PurgeUserData",
+            "type": "object",
+            "required": ["event_id", "occurred_at", "user_id"],
+            "properties": {
+                "event_id": { "type": "string", "format": "uuid" },
+                "occurred_at": { "type": "string", "format": "date-time" },
+                "user_id": { "type": "string", "format": "uuid" },
+                "reason": { "type": ["string", "null"] },
+                "purge_before": { "type": ["string", "null"], "format": "date-time" }
+            }
+        }"##
+    }
+
+    fn aggregate_id(&self) -> String {
+        self.user_id.clone()
+    }
+}
+
+impl IntegrationEvent for PurgeUserData {
+    fn event_type(&self) -> &str {
+        Self::EVENT_TYPE
+    }
+
+    fn source_context(&self) -> &str {
+        Self::OWNER.as_str()
+    }
+
+    fn event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+}
@@ -0,0 +1,30 @@
+//! コンテキスト間の統合イベント
+//!
+//! 複数の Bounded Context にまたがって流れるメッセージを一か所で定義する。
+//! 各イベントは所有コンテキストとスキーマバージョンを明示し、
+//! スキーマレジストリへの登録とトピックのルーティングに使われる。
+
+pub mod catalog;
+pub mod context;
+pub mod envelope;
+pub mod error;
+pub mod events;
+pub mod preferences;
+pub mod topics;
+
+pub use catalog::{IntegrationMessage, SchemaDescriptor, all_schemas};
+pub use context::OwningContext;
+pub use envelope::IntegrationEnvelope;
+#[cfg(feature = "event-bus")]
+pub use envelope::publish;
+pub use error::{IntegrationEventError, Result};
+pub use events::*;
+pub use preferences::{
+    CachedPreferenceSource,
+    Delivery,
+    NotificationGate,
+    NotificationPreferenceSource,
+    NotificationPreferences,
+    QuietHours,
+};
+pub use topics::TopicRegistry;
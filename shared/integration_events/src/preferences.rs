@@ -0,0 +1,440 @@
+//! 通知設定とおやすみ時間
+//!
+//! 通知を発行するサービスはすべて [`NotificationGate`] を通す。
+//! 無効化されたカテゴリの通知は発行せず、おやすみ時間中の通知は
+//! 時間帯の終わりまで延期する（`not_before` を設定する）。
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Days, FixedOffset, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::Result,
+    events::{NotificationCategory, NotificationChannel, NotificationRequested},
+};
+
+/// おやすみ時間（ユーザーのローカル時刻、終了時刻は含まない）
+///
+/// `start > end` の場合は日付をまたぐ時間帯（例: 22:00〜07:00）として扱う
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end:   NaiveTime,
+}
+
+impl QuietHours {
+    pub fn new(start: NaiveTime, end: NaiveTime) -> Self {
+        Self { start, end }
+    }
+
+    /// 0:00 からの分で表した時間帯から作成（proto・Read Model の表現）
+    pub fn from_minutes(start_minute: u32, end_minute: u32) -> Option<Self> {
+        let time = |minute: u32| NaiveTime::from_hms_opt(minute / 60, minute % 60, 0);
+        Some(Self::new(time(start_minute)?, time(end_minute)?))
+    }
+
+    /// `now` がおやすみ時間内であれば、時間帯が終わる時刻（UTC）を返す
+    pub fn window_end(&self, now: DateTime<Utc>, offset: FixedOffset) -> Option<DateTime<Utc>> {
+        let local = now.with_timezone(&offset);
+        let time = local.time();
+        let today = local.date_naive();
+
+        let end_date = if self.start < self.end {
+            (self.start <= time && time < self.end).then_some(today)?
+        } else if self.start > self.end {
+            if time >= self.start {
+                today.checked_add_days(Days::new(1))?
+            } else if time < self.end {
+                today
+            } else {
+                return None;
+            }
+        } else {
+            // 開始と終了が同じ場合はおやすみ時間なし
+            return None;
+        };
+
+        offset
+            .from_local_datetime(&end_date.and_time(self.end))
+            .single()
+            .map(|end| end.with_timezone(&Utc))
+    }
+}
+
+/// ユーザーの通知設定
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationPreferences {
+    pub user_id:             String,
+    /// 無効化されたカテゴリ（含まれないカテゴリは有効）
+    #[serde(default)]
+    pub disabled_categories: BTreeSet<NotificationCategory>,
+    /// 希望する配信チャネル（優先順）
+    #[serde(default)]
+    pub preferred_channels:  Vec<NotificationChannel>,
+    #[serde(default)]
+    pub quiet_hours:         Option<QuietHours>,
+    /// プロフィールのタイムゾーン（UTC からのオフセット、分）
+    #[serde(default)]
+    pub utc_offset_minutes:  i32,
+}
+
+/// 通知設定を適用した結果
+#[derive(Debug, Clone, PartialEq)]
+pub enum Delivery {
+    /// すぐに配信する
+    Send(NotificationRequested),
+    /// おやすみ時間の終わりまで延期する（`not_before` 設定済み）
+    Deferred(NotificationRequested),
+    /// カテゴリが無効化されているため配信しない
+    Suppressed,
+}
+
+impl NotificationPreferences {
+    /// 設定が保存されていないユーザーの既定値（すべて有効、おやすみ時間なし）
+    pub fn default_for(user_id: impl Into<String>) -> Self {
+        Self {
+            user_id:             user_id.into(),
+            disabled_categories: BTreeSet::new(),
+            preferred_channels:  Vec::new(),
+            quiet_hours:         None,
+            utc_offset_minutes:  0,
+        }
+    }
+
+    pub fn is_enabled(&self, category: NotificationCategory) -> bool {
+        !self.disabled_categories.contains(&category)
+    }
+
+    /// プロフィールのタイムゾーン（範囲外の値は UTC として扱う）
+    pub fn offset(&self) -> FixedOffset {
+        FixedOffset::east_opt(self.utc_offset_minutes * 60)
+            .unwrap_or_else(|| FixedOffset::east_opt(0).expect("UTC offset is valid"))
+    }
+
+    /// 通知リクエストに設定を適用する
+    ///
+    /// カテゴリのない v1 のリクエストは抑止せず、おやすみ時間のみ適用する。
+    /// 希望チャネル以外のチャネルは、最優先の希望チャネルに置き換える
+    pub fn apply(&self, mut request: NotificationRequested, now: DateTime<Utc>) -> Delivery {
+        if let Some(category) = request.category
+            && !self.is_enabled(category)
+        {
+            return Delivery::Suppressed;
+        }
+
+        if let Some(&preferred) = self.preferred_channels.first()
+            && !self.preferred_channels.contains(&request.channel)
+        {
+            request.channel = preferred;
+        }
+
+        let deliver_at = request.not_before.unwrap_or(now).max(now);
+        match self
+            .quiet_hours
+            .and_then(|quiet| quiet.window_end(deliver_at, self.offset()))
+        {
+            Some(window_end) => {
+                request.not_before = Some(window_end);
+                Delivery::Deferred(request)
+            },
+            None => Delivery::Send(request),
+        }
+    }
+}
+
+/// 通知設定の取得元（User Service の内部 RPC GetNotificationPreferences）
+#[async_trait]
+pub trait NotificationPreferenceSource: Send + Sync {
+    /// 通知設定を取得（未設定のユーザーは既定値を返す）
+    async fn get_preferences(&self, user_id: &str) -> Result<NotificationPreferences>;
+}
+
+#[async_trait]
+impl<S: NotificationPreferenceSource + ?Sized> NotificationPreferenceSource for Arc<S> {
+    async fn get_preferences(&self, user_id: &str) -> Result<NotificationPreferences> {
+        (**self).get_preferences(user_id).await
+    }
+}
+
+/// 通知設定を一定時間キャッシュする取得元
+///
+/// 通知のたびに User Service へ問い合わせないようにする。
+/// 設定変更（NotificationPreferencesUpdated）を受け取ったら `invalidate` する
+pub struct CachedPreferenceSource<S> {
+    inner:   S,
+    ttl:     Duration,
+    entries: Mutex<HashMap<String, (Instant, NotificationPreferences)>>,
+}
+
+impl<S: NotificationPreferenceSource> CachedPreferenceSource<S> {
+    pub fn new(inner: S, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// ユーザーのキャッシュを破棄
+    pub fn invalidate(&self, user_id: &str) {
+        self.entries
+            .lock()
+            .expect("preference cache poisoned")
+            .remove(user_id);
+    }
+
+    fn cached(&self, user_id: &str) -> Option<NotificationPreferences> {
+        let entries = self.entries.lock().expect("preference cache poisoned");
+        entries
+            .get(user_id)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, preferences)| preferences.clone())
+    }
+}
+
+#[async_trait]
+impl<S: NotificationPreferenceSource> NotificationPreferenceSource for CachedPreferenceSource<S> {
+    async fn get_preferences(&self, user_id: &str) -> Result<NotificationPreferences> {
+        if let Some(preferences) = self.cached(user_id) {
+            return Ok(preferences);
+        }
+
+        let preferences = self.inner.get_preferences(user_id).await?;
+        self.entries
+            .lock()
+            .expect("preference cache poisoned")
+            .insert(user_id.to_string(), (Instant::now(), preferences.clone()));
+        Ok(preferences)
+    }
+}
+
+/// 通知を発行する前に通知設定を適用するゲート
+pub struct NotificationGate<S> {
+    source: S,
+}
+
+impl<S: NotificationPreferenceSource> NotificationGate<S> {
+    pub fn new(source: S) -> Self {
+        Self { source }
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// 通知リクエストにユーザーの設定を適用する
+    pub async fn prepare(
+        &self,
+        request: NotificationRequested,
+        now: DateTime<Utc>,
+    ) -> Result<Delivery> {
+        let preferences = self.source.get_preferences(&request.user_id).await?;
+        Ok(preferences.apply(request, now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::context::OwningContext;
+
+    fn at(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn request(category: NotificationCategory) -> NotificationRequested {
+        NotificationRequested::new(
+            "user-1",
+            category,
+            NotificationChannel::Push,
+            "daily_summary",
+            OwningContext::Progress,
+        )
+    }
+
+    /// JST（+09:00）で 22:00〜07:00 をおやすみ時間にした設定
+    fn night_owl() -> NotificationPreferences {
+        NotificationPreferences {
+            quiet_hours: QuietHours::from_minutes(22 * 60, 7 * 60),
+            utc_offset_minutes: 9 * 60,
+            ..NotificationPreferences::default_for("user-1")
+        }
+    }
+
+    #[test]
+    fn test_preferences_round_trip() {
+        let preferences = NotificationPreferences {
+            disabled_categories: [NotificationCategory::Editorial].into_iter().collect(),
+            preferred_channels: vec![NotificationChannel::InApp, NotificationChannel::Email],
+            ..night_owl()
+        };
+
+        let json = serde_json::to_string(&preferences).unwrap();
+        let decoded: NotificationPreferences = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, preferences);
+
+        // 保存されていない項目は既定値になる
+        let minimal: NotificationPreferences =
+            serde_json::from_str(r#"{ "user_id": "user-1" }"#).unwrap();
+        assert_eq!(minimal, NotificationPreferences::default_for("user-1"));
+    }
+
+    #[test]
+    fn test_disabled_category_is_suppressed() {
+        let preferences = NotificationPreferences {
+            disabled_categories: [NotificationCategory::DailySummary].into_iter().collect(),
+            ..NotificationPreferences::default_for("user-1")
+        };
+        let now = utc(2025, 8, 1, 3, 0);
+
+        assert_eq!(
+            preferences.apply(request(NotificationCategory::DailySummary), now),
+            Delivery::Suppressed
+        );
+        assert!(matches!(
+            preferences.apply(request(NotificationCategory::GoalReminder), now),
+            Delivery::Send(_)
+        ));
+    }
+
+    #[test]
+    fn test_quiet_hours_defer_to_end_of_window_across_midnight() {
+        let preferences = night_owl();
+
+        // JST 23:30（UTC 14:30）→ 翌日 JST 07:00（UTC 22:00）まで延期
+        match preferences.apply(
+            request(NotificationCategory::GoalReminder),
+            utc(2025, 8, 1, 14, 30),
+        ) {
+            Delivery::Deferred(deferred) => {
+                assert_eq!(deferred.not_before, Some(utc(2025, 8, 1, 22, 0)));
+            },
+            other => panic!("unexpected delivery: {other:?}"),
+        }
+
+        // JST 翌 02:00（UTC 17:00）→ 同日 JST 07:00 まで延期
+        match preferences.apply(
+            request(NotificationCategory::GoalReminder),
+            utc(2025, 8, 1, 17, 0),
+        ) {
+            Delivery::Deferred(deferred) => {
+                assert_eq!(deferred.not_before, Some(utc(2025, 8, 1, 22, 0)));
+            },
+            other => panic!("unexpected delivery: {other:?}"),
+        }
+
+        // JST 07:00 ちょうどはおやすみ時間外
+        assert!(matches!(
+            preferences.apply(
+                request(NotificationCategory::GoalReminder),
+                utc(2025, 8, 1, 22, 0)
+            ),
+            Delivery::Send(_)
+        ));
+    }
+
+    #[test]
+    fn test_quiet_hours_within_a_single_day() {
+        let quiet = QuietHours::new(at(12, 0), at(13, 30));
+        let offset = FixedOffset::east_opt(0).unwrap();
+
+        assert_eq!(
+            quiet.window_end(utc(2025, 8, 1, 12, 45), offset),
+            Some(utc(2025, 8, 1, 13, 30))
+        );
+        assert_eq!(quiet.window_end(utc(2025, 8, 1, 13, 30), offset), None);
+        assert_eq!(QuietHours::from_minutes(12 * 60, 13 * 60 + 30), Some(quiet));
+        assert_eq!(QuietHours::from_minutes(24 * 60, 0), None);
+        assert_eq!(quiet.window_end(utc(2025, 8, 1, 11, 59), offset), None);
+        assert_eq!(
+            QuietHours::new(at(9, 0), at(9, 0)).window_end(utc(2025, 8, 1, 9, 0), offset),
+            None
+        );
+    }
+
+    #[test]
+    fn test_preferred_channel_hint_replaces_unwanted_channel() {
+        let preferences = NotificationPreferences {
+            preferred_channels: vec![NotificationChannel::InApp],
+            ..NotificationPreferences::default_for("user-1")
+        };
+
+        match preferences.apply(
+            request(NotificationCategory::ReviewReminder),
+            utc(2025, 8, 1, 3, 0),
+        ) {
+            Delivery::Send(sent) => assert_eq!(sent.channel, NotificationChannel::InApp),
+            other => panic!("unexpected delivery: {other:?}"),
+        }
+    }
+
+    /// 呼び出し回数を数えるスタブの取得元
+    #[derive(Clone, Default)]
+    struct CountingSource {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl NotificationPreferenceSource for CountingSource {
+        async fn get_preferences(&self, user_id: &str) -> Result<NotificationPreferences> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(NotificationPreferences::default_for(user_id))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_source_reuses_preferences_until_invalidated() {
+        let source = CountingSource::default();
+        let cached = CachedPreferenceSource::new(source.clone(), Duration::from_secs(60));
+
+        cached.get_preferences("user-1").await.unwrap();
+        cached.get_preferences("user-1").await.unwrap();
+        assert_eq!(source.calls.load(Ordering::SeqCst), 1);
+
+        cached.get_preferences("user-2").await.unwrap();
+        assert_eq!(source.calls.load(Ordering::SeqCst), 2);
+
+        cached.invalidate("user-1");
+        cached.get_preferences("user-1").await.unwrap();
+        assert_eq!(source.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cached_source_refetches_after_ttl() {
+        let source = CountingSource::default();
+        let cached = CachedPreferenceSource::new(source.clone(), Duration::ZERO);
+
+        cached.get_preferences("user-1").await.unwrap();
+        cached.get_preferences("user-1").await.unwrap();
+        assert_eq!(source.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_gate_applies_preferences_from_source() {
+        let gate = NotificationGate::new(CachedPreferenceSource::new(
+            CountingSource::default(),
+            Duration::from_secs(60),
+        ));
+
+        let delivery = gate
+            .prepare(
+                request(NotificationCategory::Editorial),
+                utc(2025, 8, 1, 3, 0),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(delivery, Delivery::Send(_)));
+    }
+}
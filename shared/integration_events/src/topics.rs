@@ -0,0 +1,111 @@
+//! 統合イベントのトピックルーティング
+
+use std::collections::HashMap;
+
+use crate::{
+    catalog::IntegrationMessage,
+    envelope::IntegrationEnvelope,
+    error::{IntegrationEventError, Result},
+    events::{NotificationRequested, PurgeUserData, ReadModelChanged},
+};
+
+/// イベントタイプから発行先トピックを引くレジストリ
+#[derive(Debug, Clone, Default)]
+pub struct TopicRegistry {
+    routes: HashMap<String, String>,
+}
+
+impl TopicRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 統合イベントの標準のルーティングを登録したレジストリを作成
+    pub fn with_integration_events() -> Self {
+        let mut registry = Self::new();
+        registry
+            .register::<NotificationRequested>("platform-notifications")
+            .register::<PurgeUserData>("user-data-purge")
+            .register::<ReadModelChanged>("platform-read-model-changes");
+        registry
+    }
+
+    /// イベントタイプの発行先トピックを登録（既存の登録は上書きする）
+    pub fn register<T: IntegrationMessage>(&mut self, topic: impl Into<String>) -> &mut Self {
+        self.routes.insert(T::EVENT_TYPE.to_string(), topic.into());
+        self
+    }
+
+    /// イベントタイプの発行先トピックを取得
+    pub fn topic_for(&self, event_type: &str) -> Result<&str> {
+        self.routes
+            .get(event_type)
+            .map(String::as_str)
+            .ok_or_else(|| IntegrationEventError::UnknownRoute(event_type.to_string()))
+    }
+
+    /// 封筒の発行先トピックを取得
+    pub fn route(&self, envelope: &IntegrationEnvelope) -> Result<&str> {
+        self.topic_for(&envelope.event_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NotificationCategory, NotificationChannel, OwningContext, catalog::all_schemas};
+
+    #[test]
+    fn test_every_integration_event_has_a_route() {
+        let registry = TopicRegistry::with_integration_events();
+        for schema in all_schemas() {
+            assert!(
+                registry.topic_for(schema.event_type).is_ok(),
+                "{} has no topic",
+                schema.event_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_routes_each_integration_event() {
+        let registry = TopicRegistry::with_integration_events();
+
+        let notification = IntegrationEnvelope::wrap(&NotificationRequested::new(
+            "user-1",
+            NotificationCategory::ReviewReminder,
+            NotificationChannel::Push,
+            "review_due",
+            OwningContext::Learning,
+        ))
+        .unwrap();
+        assert_eq!(
+            registry.route(&notification).unwrap(),
+            "platform-notifications"
+        );
+
+        let purge = IntegrationEnvelope::wrap(&PurgeUserData::new("user-1")).unwrap();
+        assert_eq!(registry.route(&purge).unwrap(), "user-data-purge");
+
+        let changed = IntegrationEnvelope::wrap(&ReadModelChanged::new(
+            OwningContext::Vocabulary,
+            "vocabulary_items_read",
+            "item-1",
+            2,
+        ))
+        .unwrap();
+        assert_eq!(
+            registry.route(&changed).unwrap(),
+            "platform-read-model-changes"
+        );
+    }
+
+    #[test]
+    fn test_unknown_route_is_an_error() {
+        let registry = TopicRegistry::new();
+        assert!(matches!(
+            registry.topic_for("PurgeUserData"),
+            Err(IntegrationEventError::UnknownRoute(_))
+        ));
+    }
+}
@@ -0,0 +1,424 @@
+//! Proto イベントのアクセサを生成するビルドスクリプト用モジュール
+//!
+//! 各コンテキストの build.rs から `#[path]` で取り込んで使います。
+//! コンパイルした proto のディスクリプタから `oneof event` を持つイベントの
+//! メッセージを探し、すべてのバリアントについて次を OUT_DIR に書き出します。
+//!
+//! - `{package}.proto_events.rs`
+//!   - メタデータを返す `shared_kernel::HasMetadata` の実装
+//!   - 完全修飾されたイベントタイプ（`<context>.<EventType>`）を返す
+//!     `qualified_event_type()`
+//! - `{package}.builders.rs`:
+//!   テスト用のイベントのビルダー（`ItemPublishedBuilder` など）
+
+use std::{collections::HashMap, error::Error, fmt::Write as _, fs, path::Path};
+
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use prost::Message as _;
+use prost_types::{
+    DescriptorProto,
+    FieldDescriptorProto,
+    FileDescriptorSet,
+    field_descriptor_proto::{Label, Type},
+};
+
+/// イベントをまとめる oneof の名前
+const EVENT_ONEOF: &str = "event";
+
+/// 各イベントが持つべきメタデータの型
+const METADATA_TYPE: &str = ".effect.common.EventMetadata";
+
+/// prost が `r#` を付けるフィールド名
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while",
+];
+
+/// `descriptor_set` のうち `packages` のアクセサを `out_dir` に生成する
+///
+/// `extern_paths` には prost_build に渡した `extern_path` と同じ対応
+/// （`(".effect.common", "::shared_kernel::proto::effect::common")`
+/// など）を渡す。 ビルダーのフィールドの型を解決するのに使う。
+///
+/// # Errors
+///
+/// ディスクリプタを読めない場合や、`metadata` フィールドのないイベントが
+/// oneof に含まれる場合、フィールドの型を解決できない場合はエラーを返す
+pub fn generate(
+    descriptor_set: &Path,
+    out_dir: &Path,
+    packages: &[&str],
+    extern_paths: &[(&str, &str)],
+) -> Result<(), Box<dyn Error>> {
+    let descriptor_set = FileDescriptorSet::decode(fs::read(descriptor_set)?.as_slice())?;
+
+    // import されたファイルも含めて、完全修飾名でメッセージを引けるようにする
+    let mut messages: HashMap<String, &DescriptorProto> = HashMap::new();
+    for file in &descriptor_set.file {
+        collect_messages(
+            &mut messages,
+            &format!(".{}", file.package()),
+            &file.message_type,
+        );
+    }
+
+    for package in packages {
+        let types = TypeResolver {
+            package,
+            extern_paths,
+        };
+        // `effect.events.vocabulary` のコンテキストは `vocabulary`
+        let context = package.rsplit('.').next().unwrap_or(package);
+
+        let mut accessors = String::new();
+        let mut builders = String::new();
+        for file in descriptor_set
+            .file
+            .iter()
+            .filter(|file| file.package() == *package)
+        {
+            for message in &file.message_type {
+                let Some(variants) = event_variants(message, &messages)? else {
+                    continue;
+                };
+                write_accessors(&mut accessors, context, message, &variants)?;
+                for variant in &variants {
+                    let event = messages[variant.type_name()];
+                    write_builder(&mut builders, message, variant, event, &messages, &types)?;
+                }
+            }
+        }
+        fs::write(
+            out_dir.join(format!("{package}.proto_events.rs")),
+            accessors,
+        )?;
+        fs::write(out_dir.join(format!("{package}.builders.rs")), builders)?;
+    }
+
+    Ok(())
+}
+
+/// 入れ子のメッセージも含めて完全修飾名で登録する
+fn collect_messages<'a>(
+    messages: &mut HashMap<String, &'a DescriptorProto>,
+    scope: &str,
+    message_types: &'a [DescriptorProto],
+) {
+    for message in message_types {
+        let name = format!("{scope}.{}", message.name());
+        collect_messages(messages, &name, &message.nested_type);
+        messages.insert(name, message);
+    }
+}
+
+/// `oneof event` のバリアント（`oneof event` がなければ None）
+///
+/// すべてのバリアントが `metadata` フィールドを持つことを確かめる
+fn event_variants<'a>(
+    message: &'a DescriptorProto,
+    messages: &HashMap<String, &DescriptorProto>,
+) -> Result<Option<Vec<&'a FieldDescriptorProto>>, Box<dyn Error>> {
+    let Some(oneof_index) = message
+        .oneof_decl
+        .iter()
+        .position(|oneof| oneof.name() == EVENT_ONEOF)
+    else {
+        return Ok(None);
+    };
+
+    let variants: Vec<&FieldDescriptorProto> = message
+        .field
+        .iter()
+        .filter(|field| field.oneof_index == Some(oneof_index as i32))
+        .collect();
+
+    for field in &variants {
+        let has_metadata = messages.get(field.type_name()).is_some_and(|event| {
+            event
+                .field
+                .iter()
+                .any(|field| field.name() == "metadata" && field.type_name() == METADATA_TYPE)
+        });
+        if !has_metadata {
+            return Err(format!(
+                "{}.{} ({}) has no `metadata` field of type {METADATA_TYPE}",
+                message.name(),
+                field.name(),
+                field.type_name()
+            )
+            .into());
+        }
+    }
+
+    Ok(Some(variants))
+}
+
+/// oneof のバリアントのパス（`vocabulary_event::Event::ItemPublished`）
+fn variant_path(message: &DescriptorProto, variant: &FieldDescriptorProto) -> String {
+    format!(
+        "{}::{}::{}",
+        message.name().to_snake_case(),
+        EVENT_ONEOF.to_upper_camel_case(),
+        variant.name().to_upper_camel_case()
+    )
+}
+
+/// `oneof event` を持つメッセージのアクセサを書き出す
+fn write_accessors(
+    code: &mut String,
+    context: &str,
+    message: &DescriptorProto,
+    variants: &[&FieldDescriptorProto],
+) -> Result<(), Box<dyn Error>> {
+    let name = message.name().to_upper_camel_case();
+
+    writeln!(code, "impl ::shared_kernel::HasMetadata for {name} {{")?;
+    writeln!(
+        code,
+        "    fn metadata(&self) -> \
+         ::core::option::Option<&::shared_kernel::proto::ProtoEventMetadata> {{"
+    )?;
+    writeln!(code, "        match self.{EVENT_ONEOF}.as_ref()? {{")?;
+    for variant in variants {
+        writeln!(
+            code,
+            "            {}(event) => event.metadata.as_ref(),",
+            variant_path(message, variant)
+        )?;
+    }
+    writeln!(code, "        }}")?;
+    writeln!(code, "    }}")?;
+    writeln!(code, "}}")?;
+
+    writeln!(code, "impl {name} {{")?;
+    writeln!(
+        code,
+        "    /// 完全修飾されたイベントタイプ（`<context>.<EventType>`、イベントが\n    /// \
+         設定されていなければ None）"
+    )?;
+    writeln!(
+        code,
+        "    pub fn qualified_event_type(&self) -> ::core::option::Option<&'static str> {{"
+    )?;
+    writeln!(code, "        match self.{EVENT_ONEOF}.as_ref()? {{")?;
+    for variant in variants {
+        // バリアントのメッセージ名（`.effect.events.vocabulary.ItemPublished` の
+        // `ItemPublished`）
+        let event_type = variant.type_name().rsplit('.').next().unwrap_or_default();
+        writeln!(
+            code,
+            "            {}(_) => ::core::option::Option::Some(\"{context}.{event_type}\"),",
+            variant_path(message, variant)
+        )?;
+    }
+    writeln!(code, "        }}")?;
+    writeln!(code, "    }}")?;
+    writeln!(code, "}}")?;
+
+    Ok(())
+}
+
+/// イベントのビルダーを書き出す
+///
+/// メタデータは新しい集約IDのもので埋め、各フィールドに同名のセッターを作る
+fn write_builder(
+    code: &mut String,
+    message: &DescriptorProto,
+    variant: &FieldDescriptorProto,
+    event: &DescriptorProto,
+    messages: &HashMap<String, &DescriptorProto>,
+    types: &TypeResolver<'_>,
+) -> Result<(), Box<dyn Error>> {
+    let wrapper = message.name().to_upper_camel_case();
+    let name = event.name().to_upper_camel_case();
+    let builder = format!("{name}Builder");
+
+    writeln!(code, "/// [`{name}`] のビルダー")?;
+    writeln!(code, "///")?;
+    writeln!(
+        code,
+        "/// メタデータは新しい集約IDのもので埋め、その他のフィールドは既定値になる"
+    )?;
+    writeln!(code, "#[derive(Debug, Clone)]")?;
+    writeln!(code, "#[must_use]")?;
+    writeln!(code, "pub struct {builder} {{")?;
+    writeln!(code, "    event: {name},")?;
+    writeln!(code, "}}")?;
+
+    writeln!(code, "impl ::core::default::Default for {builder} {{")?;
+    writeln!(code, "    fn default() -> Self {{")?;
+    writeln!(
+        code,
+        "        let metadata = ::shared_kernel::EventMetadata::new("
+    )?;
+    writeln!(code, "            ::uuid::Uuid::new_v4().to_string(),")?;
+    writeln!(code, "        );")?;
+    writeln!(code, "        Self {{")?;
+    writeln!(
+        code,
+        "            event: ::core::default::Default::default(),"
+    )?;
+    writeln!(code, "        }}")?;
+    writeln!(code, "        .metadata(metadata.into())")?;
+    writeln!(code, "    }}")?;
+    writeln!(code, "}}")?;
+
+    writeln!(code, "impl {builder} {{")?;
+    for field in &event.field {
+        write_setter(code, field, messages, types)?;
+    }
+    writeln!(code, "    /// イベントを作る")?;
+    writeln!(code, "    pub fn build(self) -> {name} {{")?;
+    writeln!(code, "        self.event")?;
+    writeln!(code, "    }}")?;
+    writeln!(code, "    /// [`{wrapper}`] に包んで作る")?;
+    writeln!(code, "    pub fn build_event(self) -> {wrapper} {{")?;
+    writeln!(code, "        {wrapper} {{")?;
+    writeln!(
+        code,
+        "            {EVENT_ONEOF}: ::core::option::Option::Some({}(self.event)),",
+        variant_path(message, variant)
+    )?;
+    writeln!(code, "        }}")?;
+    writeln!(code, "    }}")?;
+    writeln!(code, "}}")?;
+
+    Ok(())
+}
+
+/// フィールドのセッターを書き出す（map のフィールドは対象外）
+fn write_setter(
+    code: &mut String,
+    field: &FieldDescriptorProto,
+    messages: &HashMap<String, &DescriptorProto>,
+    types: &TypeResolver<'_>,
+) -> Result<(), Box<dyn Error>> {
+    let is_map = messages
+        .get(field.type_name())
+        .and_then(|message| message.options.as_ref())
+        .is_some_and(|options| options.map_entry());
+    if is_map {
+        return Ok(());
+    }
+
+    let snake = field.name().to_snake_case();
+    let ident = if KEYWORDS.contains(&snake.as_str()) {
+        format!("r#{snake}")
+    } else {
+        snake
+    };
+
+    // (引数の型, 値を格納する形に変える式)
+    let (value_type, convert) = match field.r#type() {
+        Type::String => ("String".to_string(), "value.into()"),
+        Type::Bytes => ("::prost::alloc::vec::Vec<u8>".to_string(), "value"),
+        Type::Bool => ("bool".to_string(), "value"),
+        Type::Double => ("f64".to_string(), "value"),
+        Type::Float => ("f32".to_string(), "value"),
+        Type::Int64 | Type::Sint64 | Type::Sfixed64 => ("i64".to_string(), "value"),
+        Type::Uint64 | Type::Fixed64 => ("u64".to_string(), "value"),
+        Type::Int32 | Type::Sint32 | Type::Sfixed32 => ("i32".to_string(), "value"),
+        Type::Uint32 | Type::Fixed32 => ("u32".to_string(), "value"),
+        Type::Enum => (types.resolve(field.type_name())?, "value as i32"),
+        Type::Message | Type::Group => (types.resolve(field.type_name())?, "value"),
+    };
+    let is_string = field.r#type() == Type::String;
+
+    writeln!(code, "    /// `{}` を設定", field.name())?;
+    if field.label() == Label::Repeated {
+        if is_string {
+            writeln!(code, "    pub fn {ident}<I>(mut self, values: I) -> Self")?;
+            writeln!(code, "    where")?;
+            writeln!(code, "        I: ::core::iter::IntoIterator,")?;
+            writeln!(code, "        I::Item: ::core::convert::Into<String>,")?;
+        } else {
+            writeln!(code, "    pub fn {ident}<I>(mut self, values: I) -> Self")?;
+            writeln!(code, "    where")?;
+            writeln!(
+                code,
+                "        I: ::core::iter::IntoIterator<Item = {value_type}>,"
+            )?;
+        }
+        writeln!(code, "    {{")?;
+        if convert == "value" {
+            writeln!(
+                code,
+                "        self.event.{ident} = values.into_iter().collect();"
+            )?;
+        } else {
+            writeln!(
+                code,
+                "        self.event.{ident} = values.into_iter().map(|value| {convert}).collect();"
+            )?;
+        }
+    } else {
+        let argument = if is_string {
+            "impl ::core::convert::Into<String>".to_string()
+        } else {
+            value_type
+        };
+        let optional = field.proto3_optional() || field.r#type() == Type::Message;
+        let value = if optional {
+            format!("::core::option::Option::Some({convert})")
+        } else {
+            convert.to_string()
+        };
+        writeln!(
+            code,
+            "    pub fn {ident}(mut self, value: {argument}) -> Self {{"
+        )?;
+        writeln!(code, "        self.event.{ident} = {value};")?;
+    }
+    writeln!(code, "        self")?;
+    writeln!(code, "    }}")?;
+
+    Ok(())
+}
+
+/// proto の型名を Rust のパスにする
+struct TypeResolver<'a> {
+    package:      &'a str,
+    extern_paths: &'a [(&'a str, &'a str)],
+}
+
+impl TypeResolver<'_> {
+    fn resolve(&self, type_name: &str) -> Result<String, Box<dyn Error>> {
+        // prost は well-known types を prost_types に対応させる
+        if let Some(name) = type_name.strip_prefix(".google.protobuf.") {
+            return Ok(format!("::prost_types::{}", rust_path(name)));
+        }
+
+        // 長い（具体的な）対応を優先する
+        let mut extern_paths = self.extern_paths.to_vec();
+        extern_paths.sort_by_key(|(proto, _)| std::cmp::Reverse(proto.len()));
+        for (proto, rust) in extern_paths {
+            if type_name == proto {
+                return Ok(rust.to_string());
+            }
+            if let Some(name) = type_name
+                .strip_prefix(proto)
+                .and_then(|name| name.strip_prefix('.'))
+            {
+                return Ok(format!("{rust}::{}", rust_path(name)));
+            }
+        }
+
+        // 同じパッケージの型は生成コードと同じモジュールから参照する
+        type_name
+            .strip_prefix(&format!(".{}.", self.package))
+            .map(rust_path)
+            .ok_or_else(|| format!("cannot resolve the Rust path of {type_name}").into())
+    }
+}
+
+/// パッケージ内の名前（`Outer.Inner`）を prost の生成コードのパス
+/// （`outer::Inner`）にする
+fn rust_path(name: &str) -> String {
+    let mut segments: Vec<String> = name.split('.').map(ToSnakeCase::to_snake_case).collect();
+    if let (Some(last), Some(type_name)) = (segments.last_mut(), name.rsplit('.').next()) {
+        *last = type_name.to_upper_camel_case();
+    }
+    segments.join("::")
+}
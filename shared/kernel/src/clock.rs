@@ -0,0 +1,93 @@
+//! 時計の抽象
+//!
+//! 現在時刻に依存する処理（イベントのメタデータや復習のスケジュール）は
+//! `Utc::now()` を直接呼ばず [`Clock`] から時刻を受け取ります。本番では
+//! [`SystemClock`]、テストでは [`FixedClock`] を渡すことで、時刻を固定したり
+//! 進めたりして結果を確かめられます。
+
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::timestamp::to_proto_timestamp;
+
+/// 現在時刻を返す時計
+pub trait Clock: Send + Sync {
+    /// 現在時刻
+    fn now(&self) -> DateTime<Utc>;
+
+    /// 現在時刻（Proto の Timestamp）
+    fn now_proto(&self) -> prost_types::Timestamp {
+        to_proto_timestamp(&self.now())
+    }
+}
+
+/// システムの時刻を返す時計
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// 設定した時刻を返す時計（テスト用）
+#[derive(Debug)]
+pub struct FixedClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    /// `now` を返す時計を作成
+    #[must_use]
+    pub const fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// 返す時刻を変更
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.lock() = now;
+    }
+
+    /// 返す時刻を `duration` だけ進める
+    pub fn advance(&self, duration: Duration) {
+        *self.lock() += duration;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, DateTime<Utc>> {
+        // 時刻の読み書きでパニックすることはないため、ポイズンは無視する
+        self.now
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.lock()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_fixed_clock_can_be_set_and_advanced() {
+        let start = Utc.with_ymd_and_hms(2025, 4, 1, 9, 0, 0).unwrap();
+        let clock = FixedClock::new(start);
+        assert_eq!(clock.now(), start);
+        assert_eq!(clock.now_proto().seconds, start.timestamp());
+
+        clock.advance(Duration::days(1));
+        assert_eq!(clock.now(), start + Duration::days(1));
+
+        clock.set(start);
+        assert_eq!(clock.now(), start);
+    }
+}
@@ -0,0 +1,398 @@
+//! CloudEvents 1.0 の封筒
+//!
+//! 外部のコンシューマーや GCP Eventarc とイベントをやり取りするため、
+//! ドメインイベントを CloudEvents に変換します。属性は [`DomainEvent`] の
+//! アクセサから埋め、data の形式だけを [`CloudEventCodec`] で実装します。
+//!
+//! - 構造化モード（[`CloudEvent::to_structured`]）: 属性と data を1つの JSON
+//!   （`application/cloudevents+json`）にまとめる。バイナリの data は
+//!   `data_base64` に入れる
+//! - バイナリモード（[`CloudEvent::to_binary`]）: 属性を `ce-` で始まる
+//!   ヘッダー（Pub/Sub では属性）に、data をそのまま本文にする
+
+use std::collections::{BTreeMap, HashMap};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+
+use crate::events::{DomainEvent, EventError};
+
+/// 対応する CloudEvents の仕様バージョン
+pub const SPEC_VERSION: &str = "1.0";
+
+/// 構造化モードの Content-Type
+pub const STRUCTURED_CONTENT_TYPE: &str = "application/cloudevents+json";
+
+/// バイナリモードで属性を表すヘッダーの接頭辞
+const HEADER_PREFIX: &str = "ce-";
+
+/// イベントの発生元（`/effect/<context>`）の接頭辞
+const SOURCE_PREFIX: &str = "/effect/";
+
+/// CloudEvents の data
+#[derive(Debug, Clone, PartialEq)]
+pub enum CloudEventData {
+    /// JSON の data
+    Json(Value),
+    /// バイナリの data（Protobuf など）
+    Binary(Vec<u8>),
+}
+
+/// CloudEvents 1.0 のイベント
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloudEvent {
+    pub id:              String,
+    /// 発生元（`/effect/vocabulary` など）
+    pub source:          String,
+    /// イベントタイプ（`vocabulary.ItemPublished` など）
+    pub event_type:      String,
+    /// 集約ID
+    pub subject:         Option<String>,
+    pub time:            Option<DateTime<Utc>>,
+    pub datacontenttype: Option<String>,
+    /// 拡張属性（`correlationid` など）
+    pub extensions:      BTreeMap<String, String>,
+    pub data:            Option<CloudEventData>,
+}
+
+impl CloudEvent {
+    /// 構造化モードの JSON にする
+    pub fn to_structured(&self) -> Result<Vec<u8>, EventError> {
+        let mut object = Map::new();
+        for (name, value) in self.attributes() {
+            object.insert(name, Value::String(value));
+        }
+        match &self.data {
+            Some(CloudEventData::Json(data)) => {
+                object.insert("data".to_string(), data.clone());
+            },
+            Some(CloudEventData::Binary(data)) => {
+                object.insert("data_base64".to_string(), STANDARD.encode(data).into());
+            },
+            None => {},
+        }
+
+        serde_json::to_vec(&object).map_err(|e| EventError::Serialization(e.to_string()))
+    }
+
+    /// 構造化モードの JSON から復元する
+    pub fn from_structured(bytes: &[u8]) -> Result<Self, EventError> {
+        let object: Map<String, Value> = serde_json::from_slice(bytes)
+            .map_err(|e| EventError::Deserialization(e.to_string()))?;
+
+        let mut attributes = HashMap::new();
+        let mut data = None;
+        for (name, value) in object {
+            match (name.as_str(), value) {
+                ("data", data_value) => data = Some(CloudEventData::Json(data_value)),
+                ("data_base64", Value::String(encoded)) => {
+                    let decoded = STANDARD.decode(encoded).map_err(|e| {
+                        EventError::Deserialization(format!("Invalid data_base64: {e}"))
+                    })?;
+                    data = Some(CloudEventData::Binary(decoded));
+                },
+                (_, Value::String(value)) => {
+                    attributes.insert(name, value);
+                },
+                // 文字列以外の拡張属性（数値・真偽値）は文字列として扱う
+                (_, value) => {
+                    attributes.insert(name, value.to_string());
+                },
+            }
+        }
+
+        Self::from_attributes(attributes, data)
+    }
+
+    /// バイナリモードのヘッダーと本文にする
+    pub fn to_binary(&self) -> Result<(HashMap<String, String>, Vec<u8>), EventError> {
+        let mut headers = HashMap::new();
+        for (name, value) in self.attributes() {
+            if name == "datacontenttype" {
+                headers.insert("content-type".to_string(), value);
+            } else {
+                headers.insert(format!("{HEADER_PREFIX}{name}"), value);
+            }
+        }
+
+        let body = match &self.data {
+            Some(CloudEventData::Json(data)) => {
+                serde_json::to_vec(data).map_err(|e| EventError::Serialization(e.to_string()))?
+            },
+            Some(CloudEventData::Binary(data)) => data.clone(),
+            None => Vec::new(),
+        };
+        Ok((headers, body))
+    }
+
+    /// バイナリモードのヘッダーと本文から復元する
+    ///
+    /// ヘッダー名の大文字・小文字は区別しない。Content-Type が JSON の場合は
+    /// 本文を JSON の data として読む
+    pub fn from_binary(headers: &HashMap<String, String>, body: &[u8]) -> Result<Self, EventError> {
+        let mut attributes = HashMap::new();
+        for (name, value) in headers {
+            let name = name.to_ascii_lowercase();
+            if name == "content-type" {
+                attributes.insert("datacontenttype".to_string(), value.clone());
+            } else if let Some(attribute) = name.strip_prefix(HEADER_PREFIX) {
+                attributes.insert(attribute.to_string(), value.clone());
+            }
+        }
+
+        let is_json = attributes
+            .get("datacontenttype")
+            .is_some_and(|content_type| is_json(content_type));
+        let data = if body.is_empty() {
+            None
+        } else if is_json {
+            let data = serde_json::from_slice(body)
+                .map_err(|e| EventError::Deserialization(e.to_string()))?;
+            Some(CloudEventData::Json(data))
+        } else {
+            Some(CloudEventData::Binary(body.to_vec()))
+        };
+
+        Self::from_attributes(attributes, data)
+    }
+
+    /// data 以外の属性（名前は CloudEvents の属性名）
+    fn attributes(&self) -> Vec<(String, String)> {
+        let mut attributes = vec![
+            ("specversion".to_string(), SPEC_VERSION.to_string()),
+            ("id".to_string(), self.id.clone()),
+            ("source".to_string(), self.source.clone()),
+            ("type".to_string(), self.event_type.clone()),
+        ];
+        if let Some(subject) = &self.subject {
+            attributes.push(("subject".to_string(), subject.clone()));
+        }
+        if let Some(time) = &self.time {
+            attributes.push(("time".to_string(), time.to_rfc3339()));
+        }
+        if let Some(datacontenttype) = &self.datacontenttype {
+            attributes.push(("datacontenttype".to_string(), datacontenttype.clone()));
+        }
+        attributes.extend(
+            self.extensions
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone())),
+        );
+        attributes
+    }
+
+    fn from_attributes(
+        mut attributes: HashMap<String, String>,
+        data: Option<CloudEventData>,
+    ) -> Result<Self, EventError> {
+        let mut required = |name: &str| {
+            attributes.remove(name).ok_or_else(|| {
+                EventError::InvalidEvent(format!("CloudEvent is missing the {name} attribute"))
+            })
+        };
+
+        let specversion = required("specversion")?;
+        if specversion != SPEC_VERSION {
+            return Err(EventError::InvalidEvent(format!(
+                "Unsupported CloudEvents specversion: {specversion}"
+            )));
+        }
+        let id = required("id")?;
+        let source = required("source")?;
+        let event_type = required("type")?;
+
+        let time = attributes
+            .remove("time")
+            .map(|time| {
+                DateTime::parse_from_rfc3339(&time)
+                    .map(|time| time.with_timezone(&Utc))
+                    .map_err(|e| EventError::InvalidEvent(format!("Invalid time {time}: {e}")))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            id,
+            source,
+            event_type,
+            subject: attributes.remove("subject"),
+            time,
+            datacontenttype: attributes.remove("datacontenttype"),
+            extensions: attributes.into_iter().collect(),
+            data,
+        })
+    }
+}
+
+/// ドメインイベントと CloudEvents の変換
+///
+/// 属性は [`DomainEvent`] のアクセサから埋める（`type` は
+/// [`DomainEvent::full_event_type`]、`subject` は集約ID、拡張属性
+/// `correlationid` は相関ID）
+pub trait CloudEventCodec: DomainEvent + Sized {
+    /// data の Content-Type（`application/json`、`application/protobuf` など）
+    const DATA_CONTENT_TYPE: &'static str;
+
+    /// イベントを data にする
+    fn encode_data(&self) -> Result<CloudEventData, EventError>;
+
+    /// data からイベントを復元する
+    fn decode_data(data: &CloudEventData) -> Result<Self, EventError>;
+
+    /// CloudEvents に変換する
+    fn to_cloudevent(&self) -> Result<CloudEvent, EventError> {
+        let event_type = self.full_event_type();
+        let context = event_type.split('.').next().unwrap_or(event_type);
+
+        Ok(CloudEvent {
+            id:              self.event_id().to_string(),
+            source:          format!("{SOURCE_PREFIX}{context}"),
+            event_type:      event_type.to_string(),
+            subject:         Some(self.aggregate_id().to_string()),
+            time:            Some(self.occurred_at()),
+            datacontenttype: Some(Self::DATA_CONTENT_TYPE.to_string()),
+            extensions:      BTreeMap::from([(
+                "correlationid".to_string(),
+                self.correlation_id().to_string(),
+            )]),
+            data:            Some(self.encode_data()?),
+        })
+    }
+
+    /// CloudEvents から復元する
+    ///
+    /// data から復元したイベントのタイプが `type` と異なる場合は拒否する
+    fn from_cloudevent(event: &CloudEvent) -> Result<Self, EventError> {
+        let data = event.data.as_ref().ok_or_else(|| {
+            EventError::InvalidEvent(format!("CloudEvent {} has no data", event.id))
+        })?;
+
+        let decoded = Self::decode_data(data)?;
+        if decoded.full_event_type() != event.event_type {
+            return Err(EventError::InvalidEvent(format!(
+                "CloudEvent {} has type {} but its data is {}",
+                event.id,
+                event.event_type,
+                decoded.full_event_type()
+            )));
+        }
+        Ok(decoded)
+    }
+}
+
+/// JSON の Content-Type（`application/json`、`application/*+json`）か
+fn is_json(content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::events::EventMetadata;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct SessionStarted {
+        metadata: EventMetadata,
+        user_id:  String,
+    }
+
+    impl DomainEvent for SessionStarted {
+        fn event_type(&self) -> &str {
+            "LearningSessionStarted"
+        }
+
+        fn full_event_type(&self) -> &str {
+            "learning.SessionStarted"
+        }
+
+        fn metadata(&self) -> Option<&EventMetadata> {
+            Some(&self.metadata)
+        }
+    }
+
+    impl CloudEventCodec for SessionStarted {
+        const DATA_CONTENT_TYPE: &'static str = "application/json";
+
+        fn encode_data(&self) -> Result<CloudEventData, EventError> {
+            serde_json::to_value(self)
+                .map(CloudEventData::Json)
+                .map_err(|e| EventError::Serialization(e.to_string()))
+        }
+
+        fn decode_data(data: &CloudEventData) -> Result<Self, EventError> {
+            let CloudEventData::Json(data) = data else {
+                return Err(EventError::InvalidEvent("expected JSON data".to_string()));
+            };
+            serde_json::from_value(data.clone())
+                .map_err(|e| EventError::Deserialization(e.to_string()))
+        }
+    }
+
+    fn session_started() -> SessionStarted {
+        SessionStarted {
+            metadata: EventMetadata::new("session-1").with_correlation_id("import-7"),
+            user_id:  "user-1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_structured_mode_round_trip() {
+        let event = session_started();
+        let cloudevent = event.to_cloudevent().unwrap();
+        assert_eq!(cloudevent.source, "/effect/learning");
+        assert_eq!(cloudevent.subject.as_deref(), Some("session-1"));
+        assert_eq!(cloudevent.extensions["correlationid"], "import-7");
+
+        let json: Value = serde_json::from_slice(&cloudevent.to_structured().unwrap()).unwrap();
+        assert_eq!(json["specversion"], "1.0");
+        assert_eq!(json["type"], "learning.SessionStarted");
+        assert_eq!(json["data"]["user_id"], "user-1");
+
+        let restored = CloudEvent::from_structured(&serde_json::to_vec(&json).unwrap()).unwrap();
+        assert_eq!(restored, cloudevent);
+        assert_eq!(SessionStarted::from_cloudevent(&restored).unwrap(), event);
+    }
+
+    #[test]
+    fn test_binary_mode_round_trip() {
+        let mut cloudevent = session_started().to_cloudevent().unwrap();
+        let (headers, body) = cloudevent.to_binary().unwrap();
+        assert_eq!(headers["ce-type"], "learning.SessionStarted");
+        assert_eq!(headers["content-type"], "application/json");
+        assert_eq!(
+            CloudEvent::from_binary(&headers, &body).unwrap(),
+            cloudevent
+        );
+
+        // バイナリの data は構造化モードでは data_base64 になる
+        cloudevent.datacontenttype = Some("application/protobuf".to_string());
+        cloudevent.data = Some(CloudEventData::Binary(vec![0x0a, 0x03, 0xff]));
+        let json: Value = serde_json::from_slice(&cloudevent.to_structured().unwrap()).unwrap();
+        assert_eq!(json["data_base64"], "CgP/");
+        let (headers, body) = cloudevent.to_binary().unwrap();
+        assert_eq!(body, vec![0x0a, 0x03, 0xff]);
+        assert_eq!(
+            CloudEvent::from_binary(&headers, &body).unwrap(),
+            cloudevent
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_cloudevents() {
+        let missing_type = br#"{"specversion":"1.0","id":"1","source":"/effect/learning"}"#;
+        assert!(matches!(
+            CloudEvent::from_structured(missing_type),
+            Err(EventError::InvalidEvent(_))
+        ));
+
+        let mut cloudevent = session_started().to_cloudevent().unwrap();
+        cloudevent.event_type = "learning.SessionCompleted".to_string();
+        assert!(matches!(
+            SessionStarted::from_cloudevent(&cloudevent),
+            Err(EventError::InvalidEvent(_))
+        ));
+    }
+}
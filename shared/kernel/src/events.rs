@@ -7,7 +7,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::ids::UserId;
+use crate::{
+    clock::{Clock, SystemClock},
+    ids::UserId,
+    proto::ProtoEventMetadata,
+};
 
 /// イベントメタデータ
 ///
@@ -51,10 +55,15 @@ pub struct EventMetadata {
 impl EventMetadata {
     /// 新しいイベントメタデータを作成
     pub fn new(aggregate_id: impl Into<String>) -> Self {
+        Self::new_with_clock(aggregate_id, &SystemClock)
+    }
+
+    /// `clock` の現在時刻を発生時刻として新しいイベントメタデータを作成
+    pub fn new_with_clock(aggregate_id: impl Into<String>, clock: &dyn Clock) -> Self {
         Self {
             event_id:          Uuid::new_v4().to_string(),
             aggregate_id:      aggregate_id.into(),
-            occurred_at:       Utc::now(),
+            occurred_at:       clock.now(),
             version:           1,
             caused_by_user_id: None,
             correlation_id:    None,
@@ -89,6 +98,109 @@ impl EventMetadata {
         self.source_context = Some(source_context.into());
         self
     }
+
+    /// `cause` を原因とするイベントとして因果関係を引き継ぐ
+    ///
+    /// 相関IDをコピー（なければ `cause` が起点なのでそのイベントID）し、
+    /// 因果関係IDに `cause` のイベントIDを設定し、ユーザーIDを引き継ぐ。
+    /// サガなどが後続のイベントを発行するときに使う。
+    pub fn caused_by(mut self, cause: &EventMetadata) -> Self {
+        self.correlation_id = Some(
+            cause
+                .correlation_id
+                .clone()
+                .unwrap_or_else(|| cause.event_id.clone()),
+        );
+        self.causation_id = Some(cause.event_id.clone());
+        if cause.caused_by_user_id.is_some() {
+            self.caused_by_user_id = cause.caused_by_user_id;
+        }
+        self
+    }
+
+    /// ビルダーを作成
+    pub fn builder(aggregate_id: impl Into<String>) -> EventMetadataBuilder {
+        EventMetadataBuilder {
+            metadata: Self::new(aggregate_id),
+        }
+    }
+}
+
+/// [`EventMetadata`] のビルダー
+///
+/// 指定しなかった項目は [`EventMetadata::new`] と同じ既定値になる
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct EventMetadataBuilder {
+    metadata: EventMetadata,
+}
+
+impl EventMetadataBuilder {
+    /// イベントバージョンを設定
+    pub fn version(mut self, version: u64) -> Self {
+        self.metadata.version = version;
+        self
+    }
+
+    /// イベント発生時刻を設定
+    pub fn occurred_at(mut self, occurred_at: DateTime<Utc>) -> Self {
+        self.metadata.occurred_at = occurred_at;
+        self
+    }
+
+    /// ユーザーIDを設定
+    pub fn user(mut self, user_id: UserId) -> Self {
+        self.metadata.caused_by_user_id = Some(user_id);
+        self
+    }
+
+    /// 相関IDを設定
+    pub fn correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.metadata.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// 因果関係IDを設定
+    pub fn causation_id(mut self, causation_id: impl Into<String>) -> Self {
+        self.metadata.causation_id = Some(causation_id.into());
+        self
+    }
+
+    /// `cause` を原因とするイベントとして因果関係を引き継ぐ
+    /// （[`EventMetadata::caused_by`]）
+    pub fn caused_by(mut self, cause: &EventMetadata) -> Self {
+        self.metadata = self.metadata.caused_by(cause);
+        self
+    }
+
+    /// トレースコンテキストを設定
+    pub fn trace_context(mut self, trace_context: TraceContext) -> Self {
+        self.metadata.trace_context = Some(trace_context);
+        self
+    }
+
+    /// コマンドIDを設定
+    pub fn command_id(mut self, command_id: impl Into<String>) -> Self {
+        self.metadata.command_id = Some(command_id.into());
+        self
+    }
+
+    /// ソースコンテキストを設定
+    pub fn source_context(mut self, source_context: impl Into<String>) -> Self {
+        self.metadata.source_context = Some(source_context.into());
+        self
+    }
+
+    /// スキーマバージョンを設定
+    pub fn schema_version(mut self, schema_version: u32) -> Self {
+        self.metadata.schema_version = Some(schema_version);
+        self
+    }
+
+    /// メタデータを作成
+    pub fn build(self) -> EventMetadata {
+        self.metadata
+    }
 }
 
 /// 分散トレーシング用のコンテキスト
@@ -106,12 +218,69 @@ pub trait DomainEvent: Send + Sync {
     /// イベントタイプを取得
     fn event_type(&self) -> &str;
 
+    /// 完全修飾されたイベントタイプを取得（`learning.SessionStarted` など）
+    ///
+    /// `<context>.<EventType>` の形式で、イベントストアの event_type や
+    /// Pub/Sub の属性、スキーマレジストリのキーに使う
+    fn full_event_type(&self) -> &str;
+
     /// メタデータを取得
-    fn metadata(&self) -> &EventMetadata;
+    ///
+    /// Proto で定義したイベントは [`HasMetadata`] で Proto
+    /// のメタデータを持つため
+    /// None を返し、代わりに以下の各アクセサーを実装する
+    fn metadata(&self) -> Option<&EventMetadata> {
+        None
+    }
+
+    /// イベントIDを取得
+    fn event_id(&self) -> &str {
+        self.metadata()
+            .map_or("", |metadata| metadata.event_id.as_str())
+    }
 
     /// 集約IDを取得
     fn aggregate_id(&self) -> &str {
-        &self.metadata().aggregate_id
+        self.metadata()
+            .map_or("", |metadata| metadata.aggregate_id.as_str())
+    }
+
+    /// 相関IDを取得
+    ///
+    /// 相関IDがなければ、このイベントが一連の処理の起点としてイベントIDを返す
+    fn correlation_id(&self) -> &str {
+        self.metadata()
+            .and_then(|metadata| metadata.correlation_id.as_deref())
+            .unwrap_or_else(|| self.event_id())
+    }
+
+    /// 発生時刻を取得
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.metadata()
+            .map(|metadata| metadata.occurred_at)
+            .unwrap_or_default()
+    }
+}
+
+/// Proto で定義したイベントのメタデータへのアクセス
+///
+/// 各コンテキストのビルドスクリプトが proto のディスクリプタから
+/// `oneof event` のすべてのバリアントについて実装を生成する
+/// （`build_support/proto_events.rs`）。イベントを追加しても手で match を
+/// 直す必要はなく、`metadata` フィールドのないイベントはビルドが失敗する。
+pub trait HasMetadata {
+    /// メタデータを取得（イベントが設定されていなければ None）
+    fn metadata(&self) -> Option<&ProtoEventMetadata>;
+
+    /// 集約IDを取得
+    fn aggregate_id(&self) -> Option<&str> {
+        self.metadata()
+            .map(|metadata| metadata.aggregate_id.as_str())
+    }
+
+    /// 発生時刻を取得
+    fn occurred_at(&self) -> Option<DateTime<Utc>> {
+        crate::timestamp::from_proto_timestamp(self.metadata()?.occurred_at.as_ref()?).ok()
     }
 }
 
@@ -202,8 +371,35 @@ where
 #[async_trait::async_trait]
 pub trait EventBus: Send + Sync {
     /// イベントを発行
+    ///
+    /// 順序キーに対応するバックエンドは、イベントの `aggregate_id` を順序キー
+    /// にして同じ集約のイベントを発行順に配信する
     async fn publish(&self, topic: &str, event: &[u8]) -> Result<(), EventError>;
 
+    /// 順序キーを指定してイベントを発行
+    ///
+    /// 同じ順序キーのイベントは発行順に配信される。既定の実装は順序キーを
+    /// 使わずに [`EventBus::publish`] で発行する
+    async fn publish_ordered(
+        &self,
+        topic: &str,
+        event: &[u8],
+        ordering_key: &str,
+    ) -> Result<(), EventError> {
+        let _ = ordering_key;
+        self.publish(topic, event).await
+    }
+
+    /// 複数のイベントを同じトピックにまとめて発行
+    ///
+    /// 既定の実装は1件ずつ [`EventBus::publish`] で発行する
+    async fn publish_batch(&self, topic: &str, events: &[&[u8]]) -> Result<(), EventError> {
+        for event in events {
+            self.publish(topic, event).await?;
+        }
+        Ok(())
+    }
+
     /// イベントを購読
     async fn subscribe<F>(&self, topic: &str, handler: F) -> Result<(), EventError>
     where
@@ -250,8 +446,8 @@ pub mod serde_helpers {
         {
             match date {
                 Some(ts) => {
-                    let dt = DateTime::<Utc>::from_timestamp(ts.seconds, ts.nanos as u32)
-                        .ok_or_else(|| serde::ser::Error::custom("Invalid timestamp"))?;
+                    let dt = crate::timestamp::from_proto_timestamp(ts)
+                        .map_err(serde::ser::Error::custom)?;
                     dt.serialize(serializer)
                 },
                 None => serializer.serialize_none(),
@@ -263,10 +459,7 @@ pub mod serde_helpers {
             D: Deserializer<'de>,
         {
             let opt: Option<DateTime<Utc>> = Option::deserialize(deserializer)?;
-            Ok(opt.map(|dt| Timestamp {
-                seconds: dt.timestamp(),
-                nanos:   dt.timestamp_subsec_nanos() as i32,
-            }))
+            Ok(opt.as_ref().map(crate::timestamp::to_proto_timestamp))
         }
     }
 }
@@ -284,6 +477,48 @@ mod tests {
         assert!(metadata.caused_by_user_id.is_none());
         assert!(metadata.correlation_id.is_none());
         assert_eq!(metadata.schema_version, Some(1));
+
+        let occurred_at = chrono::TimeZone::with_ymd_and_hms(&Utc, 2025, 4, 1, 9, 0, 0).unwrap();
+        let metadata =
+            EventMetadata::new_with_clock("item-1", &crate::FixedClock::new(occurred_at));
+        assert_eq!(metadata.occurred_at, occurred_at);
+    }
+
+    struct ItemPublished {
+        metadata: EventMetadata,
+    }
+
+    impl DomainEvent for ItemPublished {
+        fn event_type(&self) -> &str {
+            "VocabularyItemPublished"
+        }
+
+        fn full_event_type(&self) -> &str {
+            "vocabulary.ItemPublished"
+        }
+
+        fn metadata(&self) -> Option<&EventMetadata> {
+            Some(&self.metadata)
+        }
+    }
+
+    #[test]
+    fn test_domain_event_accessors_delegate_to_metadata() {
+        let metadata = EventMetadata::new("item-1");
+        let event = ItemPublished {
+            metadata: metadata.clone(),
+        };
+
+        assert_eq!(event.event_id(), metadata.event_id);
+        assert_eq!(event.aggregate_id(), "item-1");
+        assert_eq!(event.occurred_at(), metadata.occurred_at);
+        // 相関IDがなければイベント自身が起点になる
+        assert_eq!(event.correlation_id(), metadata.event_id);
+
+        let event = ItemPublished {
+            metadata: metadata.with_correlation_id("import-42"),
+        };
+        assert_eq!(event.correlation_id(), "import-42");
     }
 
     #[test]
@@ -300,4 +535,26 @@ mod tests {
         assert_eq!(metadata.causation_id, Some("cause-456".to_string()));
         assert_eq!(metadata.source_context, Some("test-context".to_string()));
     }
+
+    #[test]
+    fn test_caused_by_keeps_the_causality_chain() {
+        let user_id = UserId::new();
+        let command = EventMetadata::builder("session-1")
+            .user(user_id)
+            .command_id("command-1")
+            .build();
+        // 相関IDのないイベントは自身が起点になる
+        let completed = EventMetadata::builder("session-1")
+            .version(2)
+            .caused_by(&command)
+            .build();
+        let scheduled = EventMetadata::new("schedule-1").caused_by(&completed);
+
+        assert_eq!(completed.version, 2);
+        assert_eq!(completed.correlation_id.as_ref(), Some(&command.event_id));
+        assert_eq!(completed.causation_id.as_ref(), Some(&command.event_id));
+        assert_eq!(scheduled.correlation_id.as_ref(), Some(&command.event_id));
+        assert_eq!(scheduled.causation_id.as_ref(), Some(&completed.event_id));
+        assert_eq!(scheduled.caused_by_user_id, Some(user_id));
+    }
 }
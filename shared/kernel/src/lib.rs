@@ -4,14 +4,19 @@
 //! 識別子、値オブジェクト、基本的な型定義のみを含めます。
 //! ビジネスロジックは含めず、データ構造のみを定義します。
 
+pub mod clock;
+pub mod cloudevents;
 pub mod events;
 pub mod ids;
+pub mod pagination;
 pub mod proto;
 pub mod timestamp;
 pub mod value_objects;
 
 // Re-export commonly used items
 // CefrLevel は value_objects から直接エクスポート（events からの重複を避ける）
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use cloudevents::{CloudEvent, CloudEventCodec, CloudEventData};
 pub use events::{
     CorrectnessJudgment,
     DomainEvent,
@@ -19,13 +24,16 @@ pub use events::{
     EventError,
     EventHandler,
     EventMetadata,
+    EventMetadataBuilder,
     EventStore,
+    HasMetadata,
     IntegrationEvent,
     TraceContext,
     UserRole,
     serde_helpers,
 };
 pub use ids::*;
+pub use pagination::{Cursor, CursorError, Page, PageRequest};
 pub use timestamp::*;
 pub use value_objects::*;
 
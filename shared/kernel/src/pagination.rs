@@ -0,0 +1,236 @@
+//! カーソルによるページネーション
+//!
+//! 一覧を返すクエリ・検索・進捗のサービスで共通に使う型を含みます。
+//! [`Cursor`] は最後に返した行の位置とそのときの絞り込み条件を JSON にして
+//! base64url で包んだ不透明な文字列で、クライアントは中身を解釈せずに次の
+//! リクエストへそのまま渡します。条件を変えて同じカーソルを使うと
+//! [`CursorError::FilterMismatch`] になります。
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+/// カーソルの読み書きのエラー
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CursorError {
+    #[error("Malformed cursor: {0}")]
+    Malformed(String),
+    #[error("Cursor was issued for different filters")]
+    FilterMismatch,
+}
+
+/// カーソルの中身
+#[derive(Serialize, Deserialize)]
+struct CursorPayload<P, F> {
+    #[serde(rename = "p")]
+    position: P,
+    #[serde(rename = "f")]
+    filters:  F,
+}
+
+/// 不透明なページのカーソル
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Cursor(String);
+
+impl Cursor {
+    /// 位置と絞り込み条件からカーソルを作成
+    ///
+    /// # Errors
+    ///
+    /// 位置や条件を JSON にできない場合はエラーを返します
+    pub fn encode<P, F>(position: &P, filters: &F) -> Result<Self, CursorError>
+    where
+        P: Serialize,
+        F: Serialize,
+    {
+        let json = serde_json::to_vec(&CursorPayload { position, filters })
+            .map_err(|e| CursorError::Malformed(e.to_string()))?;
+        Ok(Self(URL_SAFE_NO_PAD.encode(json)))
+    }
+
+    /// カーソルを読み、`filters` で発行されたものなら位置を返す
+    ///
+    /// # Errors
+    ///
+    /// カーソルが壊れている場合や、別の絞り込み条件で発行された場合は
+    /// エラーを返します
+    pub fn decode<P, F>(&self, filters: &F) -> Result<P, CursorError>
+    where
+        P: DeserializeOwned,
+        F: DeserializeOwned + PartialEq,
+    {
+        let json = URL_SAFE_NO_PAD
+            .decode(&self.0)
+            .map_err(|e| CursorError::Malformed(e.to_string()))?;
+        let payload: CursorPayload<P, F> =
+            serde_json::from_slice(&json).map_err(|e| CursorError::Malformed(e.to_string()))?;
+        if payload.filters != *filters {
+            return Err(CursorError::FilterMismatch);
+        }
+        Ok(payload.position)
+    }
+
+    /// クライアントから受け取った文字列をカーソルとして扱う
+    ///
+    /// 中身は [`Cursor::decode`] で検証する
+    #[must_use]
+    pub fn from_raw(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// 文字列として取得
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// ページの取得要求
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageRequest {
+    /// 前のページの [`Page::next_cursor`]（最初のページなら None）
+    pub cursor:    Option<Cursor>,
+    /// 1ページの件数（1 以上 [`PageRequest::MAX_PAGE_SIZE`] 以下）
+    pub page_size: u32,
+}
+
+impl PageRequest {
+    /// 件数を指定しなかったときの1ページの件数
+    pub const DEFAULT_PAGE_SIZE: u32 = 20;
+    /// 1ページの件数の上限
+    pub const MAX_PAGE_SIZE: u32 = 100;
+
+    /// 取得要求を作成（件数は 1 以上上限以下に丸め、0 なら既定の件数）
+    #[must_use]
+    pub fn new(page_size: u32, cursor: Option<Cursor>) -> Self {
+        let page_size = match page_size {
+            0 => Self::DEFAULT_PAGE_SIZE,
+            size => size.min(Self::MAX_PAGE_SIZE),
+        };
+        Self { cursor, page_size }
+    }
+
+    /// 最初のページの取得要求
+    #[must_use]
+    pub fn first(page_size: u32) -> Self {
+        Self::new(page_size, None)
+    }
+
+    /// リポジトリで取得する件数
+    ///
+    /// 次のページがあるかを知るため、1ページの件数より1件多く取得する
+    #[must_use]
+    pub const fn fetch_limit(&self) -> u32 {
+        self.page_size + 1
+    }
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self::first(Self::DEFAULT_PAGE_SIZE)
+    }
+}
+
+/// カーソルで続きを取得できる1ページ分の結果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items:       Vec<T>,
+    /// 次のページのカーソル（最後のページなら None）
+    pub next_cursor: Option<Cursor>,
+}
+
+impl<T> Page<T> {
+    /// [`PageRequest::fetch_limit`] 件まで取得した結果からページを作る
+    ///
+    /// 1ページの件数を超えた分を除き、続きがある場合は最後の項目から
+    /// `cursor_for` で次のカーソルを作る
+    ///
+    /// # Errors
+    ///
+    /// `cursor_for` がエラーを返した場合はそのエラーを返します
+    pub fn from_fetched(
+        mut items: Vec<T>,
+        request: &PageRequest,
+        cursor_for: impl FnOnce(&T) -> Result<Cursor, CursorError>,
+    ) -> Result<Self, CursorError> {
+        let page_size = request.page_size as usize;
+        if items.len() <= page_size {
+            return Ok(Self {
+                items,
+                next_cursor: None,
+            });
+        }
+
+        items.truncate(page_size);
+        let next_cursor = items.last().map(cursor_for).transpose()?;
+        Ok(Self { items, next_cursor })
+    }
+
+    /// 最後のページ（続きのないページ）
+    #[must_use]
+    pub fn last(items: Vec<T>) -> Self {
+        Self {
+            items,
+            next_cursor: None,
+        }
+    }
+
+    /// 続きのページがあるか
+    #[must_use]
+    pub const fn has_next_page(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+
+    /// 項目を変換する（カーソルはそのまま）
+    #[must_use]
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items:       self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Filters {
+        cefr_level: Option<String>,
+    }
+
+    #[test]
+    fn test_pages_through_fetched_rows_with_opaque_cursors() {
+        let filters = Filters {
+            cefr_level: Some("B2".to_string()),
+        };
+        let request = PageRequest::first(2);
+        assert_eq!(request.fetch_limit(), 3);
+
+        // 3件取得したら2件を返し、2件目の位置を次のカーソルにする
+        let page = Page::from_fetched(vec![1_i64, 2, 3], &request, |last| {
+            Cursor::encode(last, &filters)
+        })
+        .unwrap();
+        assert_eq!(page.items, [1, 2]);
+        let cursor = page.next_cursor.clone().unwrap();
+        assert_eq!(cursor.decode::<i64, _>(&filters), Ok(2));
+        assert_eq!(
+            cursor.decode::<i64, _>(&Filters { cefr_level: None }),
+            Err(CursorError::FilterMismatch)
+        );
+        assert!(matches!(
+            Cursor::from_raw("not a cursor").decode::<i64, _>(&filters),
+            Err(CursorError::Malformed(_))
+        ));
+
+        let last = Page::from_fetched(vec![3_i64], &PageRequest::new(2, Some(cursor)), |_| {
+            unreachable!("the last page has no next cursor")
+        })
+        .unwrap();
+        assert!(!last.has_next_page());
+        assert_eq!(PageRequest::new(0, None).page_size, 20);
+        assert_eq!(PageRequest::new(1000, None).page_size, 100);
+    }
+}
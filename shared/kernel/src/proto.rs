@@ -11,3 +11,36 @@ pub mod effect {
 
 // 共通型を再エクスポート
 pub use effect::common::{EventMetadata as ProtoEventMetadata, TraceContext as ProtoTraceContext};
+
+// ドメインのメタデータを Proto
+// のメタデータに変換する（テスト用のビルダーなどで使用）
+impl From<crate::EventMetadata> for ProtoEventMetadata {
+    fn from(metadata: crate::EventMetadata) -> Self {
+        Self {
+            event_id:          metadata.event_id,
+            aggregate_id:      metadata.aggregate_id,
+            occurred_at:       Some(crate::timestamp::to_proto_timestamp(&metadata.occurred_at)),
+            version:           metadata.version,
+            caused_by_user_id: metadata
+                .caused_by_user_id
+                .map(|user_id| user_id.to_string()),
+            correlation_id:    metadata.correlation_id,
+            causation_id:      metadata.causation_id,
+            trace_context:     metadata.trace_context.map(ProtoTraceContext::from),
+            command_id:        metadata.command_id,
+            source:            metadata.source_context,
+            schema_version:    metadata.schema_version,
+        }
+    }
+}
+
+impl From<crate::TraceContext> for ProtoTraceContext {
+    fn from(trace_context: crate::TraceContext) -> Self {
+        Self {
+            trace_id:       trace_context.trace_id,
+            span_id:        trace_context.span_id,
+            parent_span_id: trace_context.parent_span_id,
+            trace_flags:    None,
+        }
+    }
+}
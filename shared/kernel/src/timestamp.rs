@@ -1,6 +1,8 @@
 //! タイムスタンプユーティリティ
 //!
 //! このモジュールはタイムスタンプ関連の型とユーティリティを含みます。
+//! `DateTime<Utc>` と Proto の `google.protobuf.Timestamp` の変換は
+//! [`to_proto_timestamp`] と [`from_proto_timestamp`] を使います。
 
 use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
 
@@ -13,6 +15,65 @@ pub fn now() -> Timestamp {
     Utc::now()
 }
 
+/// Proto の Timestamp が表せる最小の秒（0001-01-01T00:00:00Z）
+const MIN_PROTO_SECONDS: i64 = -62_135_596_800;
+
+/// Proto の Timestamp が表せる最大の秒（9999-12-31T23:59:59Z）
+const MAX_PROTO_SECONDS: i64 = 253_402_300_799;
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+/// Proto の Timestamp の変換エラー
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TimestampError {
+    #[error("Timestamp out of range: {seconds}s {nanos}ns")]
+    OutOfRange { seconds: i64, nanos: i32 },
+}
+
+/// タイムスタンプを Proto の Timestamp に変換
+///
+/// うるう秒を表す 1 秒以上のナノ秒はその秒の最後に丸める
+#[must_use]
+pub fn to_proto_timestamp(timestamp: &Timestamp) -> prost_types::Timestamp {
+    let nanos = timestamp.timestamp_subsec_nanos().min(999_999_999);
+    prost_types::Timestamp {
+        seconds: timestamp.timestamp(),
+        // 999_999_999 以下なので i32 に収まる
+        nanos:   nanos as i32,
+    }
+}
+
+/// Proto の Timestamp をタイムスタンプに変換
+///
+/// 負のナノ秒や 1 秒以上のナノ秒は秒に繰り入れてから変換する
+/// （`-1s + 500_000_000ns` と `-2s + 1_500_000_000ns` はどちらも `-0.5s`）
+///
+/// # Errors
+///
+/// Proto の Timestamp が表せる範囲（0001 年〜9999 年）を外れる場合は
+/// エラーを返します
+pub fn from_proto_timestamp(
+    timestamp: &prost_types::Timestamp,
+) -> Result<Timestamp, TimestampError> {
+    let out_of_range = || TimestampError::OutOfRange {
+        seconds: timestamp.seconds,
+        nanos:   timestamp.nanos,
+    };
+
+    let nanos = i64::from(timestamp.nanos);
+    let seconds = timestamp
+        .seconds
+        .checked_add(nanos.div_euclid(NANOS_PER_SECOND))
+        .ok_or_else(out_of_range)?;
+    if !(MIN_PROTO_SECONDS..=MAX_PROTO_SECONDS).contains(&seconds) {
+        return Err(out_of_range());
+    }
+
+    // rem_euclid は 0 以上 1 秒未満なので u32 に収まる
+    DateTime::from_timestamp(seconds, nanos.rem_euclid(NANOS_PER_SECOND) as u32)
+        .ok_or_else(out_of_range)
+}
+
 /// タイムスタンプを JST として扱うための拡張トレイト
 pub trait JstExt {
     /// JST でフォーマットした文字列を返す
@@ -60,6 +121,33 @@ mod tests {
         assert!(timestamp <= after);
     }
 
+    #[test]
+    fn proto_timestamp_conversion_should_normalize_nanos_and_check_range() {
+        let timestamp = Utc.timestamp_opt(1_700_000_000, 123_456_789).unwrap();
+        let proto = to_proto_timestamp(&timestamp);
+        assert_eq!((proto.seconds, proto.nanos), (1_700_000_000, 123_456_789));
+        assert_eq!(from_proto_timestamp(&proto), Ok(timestamp));
+
+        // 負のナノ秒は前の秒に繰り入れる
+        let half_second_before_epoch = Utc.timestamp_opt(-1, 500_000_000).unwrap();
+        for (seconds, nanos) in [(0, -500_000_000), (-2, 1_500_000_000), (-1, 500_000_000)] {
+            let proto = prost_types::Timestamp { seconds, nanos };
+            assert_eq!(from_proto_timestamp(&proto), Ok(half_second_before_epoch));
+        }
+
+        let too_late = prost_types::Timestamp {
+            seconds: MAX_PROTO_SECONDS,
+            nanos:   1_000_000_000,
+        };
+        assert_eq!(
+            from_proto_timestamp(&too_late),
+            Err(TimestampError::OutOfRange {
+                seconds: MAX_PROTO_SECONDS,
+                nanos:   1_000_000_000,
+            })
+        );
+    }
+
     #[test]
     fn jst_conversion_should_add_9_hours() {
         let utc_time = Utc.with_ymd_and_hms(2024, 1, 15, 15, 0, 0).unwrap();
@@ -1,5 +1,9 @@
+use std::{fmt, str::FromStr};
+
 use serde::{Deserialize, Serialize};
 
+use crate::proto::effect::common::CefrLevel as ProtoCefrLevel;
+
 /// コースタイプ（全コンテキストで共通の意味）
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -23,20 +27,238 @@ pub enum CefrLevel {
     C2, // Proficient
 }
 
+impl CefrLevel {
+    /// 低い順に並べたすべてのレベル
+    pub const ALL: [Self; 6] = [Self::A1, Self::A2, Self::B1, Self::B2, Self::C1, Self::C2];
+
+    /// A1 を 0 とした段階
+    #[must_use]
+    pub const fn rank(self) -> u8 {
+        self as u8
+    }
+
+    /// 1つ上のレベル（C2 なら None）
+    #[must_use]
+    pub fn next(self) -> Option<Self> {
+        Self::ALL.get(usize::from(self.rank()) + 1).copied()
+    }
+
+    /// 1つ下のレベル（A1 なら None）
+    #[must_use]
+    pub fn previous(self) -> Option<Self> {
+        usize::from(self.rank())
+            .checked_sub(1)
+            .map(|rank| Self::ALL[rank])
+    }
+
+    /// `self` から `other` までの段階数（`other`
+    /// のほうが高ければ正、低ければ負）
+    ///
+    /// 難易度の差や推定レベルとの隔たりに使う
+    #[must_use]
+    pub const fn distance(self, other: Self) -> i8 {
+        other.rank() as i8 - self.rank() as i8
+    }
+
+    /// 文字列として取得（`"B2"` など）
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::A1 => "A1",
+            Self::A2 => "A2",
+            Self::B1 => "B1",
+            Self::B2 => "B2",
+            Self::C1 => "C1",
+            Self::C2 => "C2",
+        }
+    }
+}
+
+/// CEFR レベルの変換エラー
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CefrLevelError {
+    #[error("CEFR level is unspecified")]
+    Unspecified,
+    #[error("Unknown CEFR level value: {0}")]
+    UnknownValue(i32),
+    #[error("Invalid CEFR level: {0}")]
+    InvalidName(String),
+}
+
+impl fmt::Display for CefrLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CefrLevel {
+    type Err = CefrLevelError;
+
+    /// 大文字小文字を区別せずに読む（`"b2"` も `B2`）
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| CefrLevelError::InvalidName(s.to_string()))
+    }
+}
+
+impl From<CefrLevel> for ProtoCefrLevel {
+    fn from(level: CefrLevel) -> Self {
+        match level {
+            CefrLevel::A1 => Self::A1,
+            CefrLevel::A2 => Self::A2,
+            CefrLevel::B1 => Self::B1,
+            CefrLevel::B2 => Self::B2,
+            CefrLevel::C1 => Self::C1,
+            CefrLevel::C2 => Self::C2,
+        }
+    }
+}
+
+impl TryFrom<ProtoCefrLevel> for CefrLevel {
+    type Error = CefrLevelError;
+
+    fn try_from(level: ProtoCefrLevel) -> Result<Self, Self::Error> {
+        match level {
+            ProtoCefrLevel::Unspecified => Err(CefrLevelError::Unspecified),
+            ProtoCefrLevel::A1 => Ok(Self::A1),
+            ProtoCefrLevel::A2 => Ok(Self::A2),
+            ProtoCefrLevel::B1 => Ok(Self::B1),
+            ProtoCefrLevel::B2 => Ok(Self::B2),
+            ProtoCefrLevel::C1 => Ok(Self::C1),
+            ProtoCefrLevel::C2 => Ok(Self::C2),
+        }
+    }
+}
+
+/// Proto のメッセージのフィールド（`i32`）から変換する
+impl TryFrom<i32> for CefrLevel {
+    type Error = CefrLevelError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        ProtoCefrLevel::try_from(value)
+            .map_err(|_| CefrLevelError::UnknownValue(value))?
+            .try_into()
+    }
+}
+
+/// 言語コードの検証エラー
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum LanguageCodeError {
+    #[error("Language code must be two ASCII letters: {0}")]
+    InvalidFormat(String),
+    #[error("Unknown ISO 639-1 language code: {0}")]
+    Unknown(String),
+}
+
+/// ISO 639-1 の言語コード
+const ISO_639_1_CODES: &[&str] = &[
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bi",
+    "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da", "de",
+    "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr", "fy",
+    "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht", "hu", "hy", "hz", "ia",
+    "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj", "kk",
+    "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln", "lo",
+    "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb", "nd",
+    "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny", "oc", "oj", "om", "or", "os", "pa", "pi", "pl",
+    "ps", "pt", "qu", "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk", "sl",
+    "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti", "tk",
+    "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo", "wa",
+    "wo", "xh", "yi", "yo", "za", "zh", "zu",
+];
+
+/// 学習対象として扱う言語（語彙の言語やユーザーの学習目標に使う）
+const LEARNING_LANGUAGES: &[&str] = &["en", "ja", "zh", "ko", "fr", "de", "es"];
+
 /// 言語コード（ISO 639-1 形式）
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// 前後の空白を除いて小文字にそろえ、ISO 639-1 に定義されたコードだけを
+/// 受け付ける
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(transparent))]
 pub struct LanguageCode(String);
 
 impl LanguageCode {
-    pub fn new(code: impl Into<String>) -> Self {
-        Self(code.into())
+    /// 言語コードを検証して作成
+    ///
+    /// # Errors
+    ///
+    /// 2文字の英字でない場合や、ISO 639-1 にないコードの場合はエラーを返します
+    pub fn new(code: impl AsRef<str>) -> Result<Self, LanguageCodeError> {
+        let code = code.as_ref().trim().to_ascii_lowercase();
+        if code.len() != 2 || !code.bytes().all(|b| b.is_ascii_lowercase()) {
+            return Err(LanguageCodeError::InvalidFormat(code));
+        }
+        if !ISO_639_1_CODES.contains(&code.as_str()) {
+            return Err(LanguageCodeError::Unknown(code));
+        }
+        Ok(Self(code))
+    }
+
+    /// 英語（`en`）
+    #[must_use]
+    pub fn english() -> Self {
+        Self("en".to_string())
+    }
+
+    /// 日本語（`ja`）
+    #[must_use]
+    pub fn japanese() -> Self {
+        Self("ja".to_string())
+    }
+
+    /// 学習対象として扱う言語の一覧
+    #[must_use]
+    pub fn learning_languages() -> Vec<Self> {
+        LEARNING_LANGUAGES
+            .iter()
+            .map(|code| Self((*code).to_string()))
+            .collect()
+    }
+
+    /// 学習対象として扱う言語か
+    #[must_use]
+    pub fn is_learning_language(&self) -> bool {
+        LEARNING_LANGUAGES.contains(&self.as_str())
     }
 
+    /// 文字列として取得
+    #[must_use]
     pub fn as_str(&self) -> &str {
         &self.0
     }
 }
 
+impl fmt::Display for LanguageCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for LanguageCode {
+    type Err = LanguageCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for LanguageCode {
+    type Error = LanguageCodeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<LanguageCode> for String {
+    fn from(code: LanguageCode) -> Self {
+        code.0
+    }
+}
+
 /// 反応タイプ（学習セッションでの反応）
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -55,3 +277,427 @@ pub enum MasteryStatus {
     ShortTerm, // 短期記憶に定着
     LongTerm,  // 長期記憶に定着
 }
+
+/// メールアドレスの検証エラー
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EmailError {
+    #[error("Email address is empty")]
+    Empty,
+    #[error("Email address is too long: {0} characters (max {MAX_EMAIL_LENGTH})")]
+    TooLong(usize),
+    #[error("Invalid email address: {0}")]
+    InvalidFormat(String),
+}
+
+/// メールアドレス全体の最大長（RFC 5321 のパスの上限から山括弧を除いた長さ）
+const MAX_EMAIL_LENGTH: usize = 254;
+
+/// ローカル部の最大長（RFC 5321）
+const MAX_LOCAL_PART_LENGTH: usize = 64;
+
+/// ドメインのラベルの最大長（RFC 1035）
+const MAX_DOMAIN_LABEL_LENGTH: usize = 63;
+
+/// メールアドレス（検証・正規化済み）
+///
+/// 前後の空白を除いて小文字にそろえ、RFC 5322 の `dot-atom` 形式のローカル部と
+/// ドット区切りのホスト名のドメインだけを受け付ける。引用符付きのローカル部や
+/// IP アドレスのドメインは、
+/// ユーザーのアドレスとして使われないため受け付けない。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(transparent))]
+pub struct Email(String);
+
+impl Email {
+    /// メールアドレスを検証して作成
+    ///
+    /// # Errors
+    ///
+    /// 空の場合や長すぎる場合、形式が正しくない場合はエラーを返します
+    pub fn new(value: impl AsRef<str>) -> Result<Self, EmailError> {
+        let email = value.as_ref().trim().to_lowercase();
+        if email.is_empty() {
+            return Err(EmailError::Empty);
+        }
+        if email.len() > MAX_EMAIL_LENGTH {
+            return Err(EmailError::TooLong(email.len()));
+        }
+
+        let invalid = |reason: &str| EmailError::InvalidFormat(format!("{email}: {reason}"));
+        let Some((local_part, domain)) = email.rsplit_once('@') else {
+            return Err(invalid("missing @"));
+        };
+        if local_part.is_empty() || local_part.len() > MAX_LOCAL_PART_LENGTH {
+            return Err(invalid("local part must be 1 to 64 characters"));
+        }
+        if !is_dot_atom(local_part) {
+            return Err(invalid("local part contains invalid characters"));
+        }
+        if !is_hostname(domain) {
+            return Err(invalid("domain is not a valid hostname"));
+        }
+
+        Ok(Self(email))
+    }
+
+    /// 文字列として取得
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// ローカル部（`@` より前）
+    #[must_use]
+    pub fn local_part(&self) -> &str {
+        self.0
+            .rsplit_once('@')
+            .map_or("", |(local_part, _)| local_part)
+    }
+
+    /// ドメイン（`@` より後）
+    #[must_use]
+    pub fn domain(&self) -> &str {
+        self.0.rsplit_once('@').map_or("", |(_, domain)| domain)
+    }
+}
+
+/// RFC 5322 の `dot-atom`（atext をドットでつないだもの）か
+fn is_dot_atom(value: &str) -> bool {
+    value.split('.').all(|atom| {
+        !atom.is_empty()
+            && atom
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c))
+    })
+}
+
+/// 2つ以上のラベルからなるホスト名か
+fn is_hostname(value: &str) -> bool {
+    let labels: Vec<&str> = value.split('.').collect();
+    labels.len() >= 2
+        && labels.iter().all(|label| {
+            (1..=MAX_DOMAIN_LABEL_LENGTH).contains(&label.len())
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+impl fmt::Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Email {
+    type Err = EmailError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Email {
+    type Error = EmailError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Email> for String {
+    fn from(email: Email) -> Self {
+        email.0
+    }
+}
+
+impl AsRef<str> for Email {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// 文字列の値オブジェクトの検証エラー
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum StringValueError {
+    #[error("Value must not be empty")]
+    Empty,
+    #[error("Value is too short: {actual} characters (min {min})")]
+    TooShort { min: usize, actual: usize },
+    #[error("Value is too long: {actual} characters (max {max})")]
+    TooLong { max: usize, actual: usize },
+}
+
+/// 空でない文字列
+///
+/// 前後の空白を除いた値を保持し、空白だけの文字列も受け付けない
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type), sqlx(transparent))]
+pub struct NonEmptyString(String);
+
+impl NonEmptyString {
+    /// 文字列を検証して作成
+    ///
+    /// # Errors
+    ///
+    /// 空または空白だけの場合はエラーを返します
+    pub fn new(value: impl Into<String>) -> Result<Self, StringValueError> {
+        let value = trimmed(value.into());
+        if value.is_empty() {
+            return Err(StringValueError::Empty);
+        }
+        Ok(Self(value))
+    }
+
+    /// 文字列として取得
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 内部の文字列を取り出す
+    #[must_use]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+/// 文字数が `MIN` 以上 `MAX` 以下の文字列
+///
+/// 前後の空白を除いた値を保持し、文字数は Unicode のスカラー値で数える。
+/// 表示名なら `BoundedString<1, 100>` のように、フィールドごとの上限を型で表す
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct BoundedString<const MIN: usize, const MAX: usize>(String);
+
+impl<const MIN: usize, const MAX: usize> BoundedString<MIN, MAX> {
+    /// 文字列を検証して作成
+    ///
+    /// # Errors
+    ///
+    /// 文字数が `MIN` 未満（`MIN` が 1 以上で空なら
+    /// [`StringValueError::Empty`]）
+    /// または `MAX` を超える場合はエラーを返します
+    pub fn new(value: impl Into<String>) -> Result<Self, StringValueError> {
+        let value = trimmed(value.into());
+        let actual = value.chars().count();
+        if actual < MIN {
+            return Err(if actual == 0 {
+                StringValueError::Empty
+            } else {
+                StringValueError::TooShort { min: MIN, actual }
+            });
+        }
+        if actual > MAX {
+            return Err(StringValueError::TooLong { max: MAX, actual });
+        }
+        Ok(Self(value))
+    }
+
+    /// 文字列として取得
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 内部の文字列を取り出す
+    #[must_use]
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+/// 前後の空白を除く（除くものがなければそのまま使う）
+fn trimmed(value: String) -> String {
+    match value.trim() {
+        trimmed if trimmed.len() == value.len() => value,
+        trimmed => trimmed.to_string(),
+    }
+}
+
+impl fmt::Display for NonEmptyString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for NonEmptyString {
+    type Err = StringValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for NonEmptyString {
+    type Error = StringValueError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<NonEmptyString> for String {
+    fn from(value: NonEmptyString) -> Self {
+        value.0
+    }
+}
+
+impl AsRef<str> for NonEmptyString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> fmt::Display for BoundedString<MIN, MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> FromStr for BoundedString<MIN, MAX> {
+    type Err = StringValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> TryFrom<String> for BoundedString<MIN, MAX> {
+    type Error = StringValueError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> From<BoundedString<MIN, MAX>> for String {
+    fn from(value: BoundedString<MIN, MAX>) -> Self {
+        value.0
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> AsRef<str> for BoundedString<MIN, MAX> {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_is_normalized_and_validated() {
+        let email = Email::new("  Taro.Yamada+effect@Example.CO.jp ").unwrap();
+        assert_eq!(email.as_str(), "taro.yamada+effect@example.co.jp");
+        assert_eq!(email.local_part(), "taro.yamada+effect");
+        assert_eq!(email.domain(), "example.co.jp");
+
+        assert_eq!(Email::new(" "), Err(EmailError::Empty));
+        for invalid in [
+            "taro",
+            "@example.com",
+            "taro@",
+            "taro..yamada@example.com",
+            ".taro@example.com",
+            "taro yamada@example.com",
+            "taro@localhost",
+            "taro@-example.com",
+            "taro@example..com",
+        ] {
+            assert!(
+                matches!(Email::new(invalid), Err(EmailError::InvalidFormat(_))),
+                "{invalid} should be rejected"
+            );
+        }
+        let long = format!("{}@example.com", "a".repeat(250));
+        assert_eq!(Email::new(&long), Err(EmailError::TooLong(long.len())));
+    }
+
+    #[test]
+    fn test_cefr_level_steps_and_proto_conversion() {
+        assert!(CefrLevel::A1 < CefrLevel::C2);
+        assert_eq!(CefrLevel::B1.next(), Some(CefrLevel::B2));
+        assert_eq!(CefrLevel::C2.next(), None);
+        assert_eq!(CefrLevel::A2.previous(), Some(CefrLevel::A1));
+        assert_eq!(CefrLevel::A1.previous(), None);
+        assert_eq!(CefrLevel::A2.distance(CefrLevel::C1), 3);
+        assert_eq!(CefrLevel::C1.distance(CefrLevel::A2), -3);
+        assert_eq!("b2".parse::<CefrLevel>(), Ok(CefrLevel::B2));
+
+        for level in CefrLevel::ALL {
+            let proto = ProtoCefrLevel::from(level);
+            assert_eq!(CefrLevel::try_from(proto), Ok(level));
+            assert_eq!(CefrLevel::try_from(proto as i32), Ok(level));
+        }
+        assert_eq!(
+            CefrLevel::try_from(ProtoCefrLevel::Unspecified),
+            Err(CefrLevelError::Unspecified)
+        );
+        assert_eq!(
+            CefrLevel::try_from(42),
+            Err(CefrLevelError::UnknownValue(42))
+        );
+    }
+
+    #[test]
+    fn test_language_code_accepts_only_iso_639_1() {
+        let code = LanguageCode::new(" EN ").unwrap();
+        assert_eq!(code, LanguageCode::english());
+        assert!(code.is_learning_language());
+        assert!(!LanguageCode::new("sw").unwrap().is_learning_language());
+        assert!(LanguageCode::learning_languages().contains(&LanguageCode::japanese()));
+
+        assert_eq!(
+            LanguageCode::new("eng"),
+            Err(LanguageCodeError::InvalidFormat("eng".to_string()))
+        );
+        assert_eq!(
+            LanguageCode::new("xx"),
+            Err(LanguageCodeError::Unknown("xx".to_string()))
+        );
+        assert!(serde_json::from_str::<LanguageCode>(r#""jp""#).is_err());
+    }
+
+    #[test]
+    fn test_string_values_reject_empty_and_out_of_range_input() {
+        assert_eq!(NonEmptyString::new("  apple ").unwrap().as_str(), "apple");
+        assert_eq!(NonEmptyString::new(" \t"), Err(StringValueError::Empty));
+
+        type Spelling = BoundedString<2, 5>;
+        assert_eq!(Spelling::new("りんごです").unwrap().as_str(), "りんごです");
+        assert_eq!(Spelling::new(""), Err(StringValueError::Empty));
+        assert_eq!(
+            Spelling::new("a"),
+            Err(StringValueError::TooShort {
+                min:    2,
+                actual: 1,
+            })
+        );
+        assert_eq!(
+            Spelling::new("apples"),
+            Err(StringValueError::TooLong {
+                max:    5,
+                actual: 6,
+            })
+        );
+
+        assert!(serde_json::from_str::<NonEmptyString>(r#""""#).is_err());
+        assert!(serde_json::from_str::<Spelling>(r#""banana""#).is_err());
+        let spelling: Spelling = serde_json::from_str(r#""pear""#).unwrap();
+        assert_eq!(serde_json::to_string(&spelling).unwrap(), r#""pear""#);
+    }
+
+    #[test]
+    fn test_email_serde_validates_on_deserialize() {
+        let email: Email = serde_json::from_str(r#""User@Example.com""#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&email).unwrap(),
+            r#""user@example.com""#
+        );
+        assert!(serde_json::from_str::<Email>(r#""not-an-email""#).is_err());
+    }
+}
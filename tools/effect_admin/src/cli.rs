@@ -0,0 +1,329 @@
+//! コマンドライン引数
+
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+use crate::{
+    error::{AdminError, Result},
+    output::OutputFormat,
+};
+
+pub const USAGE: &str = "\
+usage: effect-admin aggregate fold --type <TYPE> (--id <UUID> | --file <PATH>) [options]
+       effect-admin snapshots rebuild --type <TYPE> (--dry-run | --token <TOKEN> [--yes])
+       effect-admin projection rebuild --name <NAME> (--dry-run | --token <TOKEN> [--yes])
+
+options:
+  --type <TYPE>          集約タイプ（vocabulary_item, progress）
+  --id <UUID>            Event Store から読み込む集約 ID
+  --file <PATH>          エクスポートした NDJSON ストリーム
+  --format <FORMAT>      pretty（既定）, json, diff
+  --interactive          ステップ実行モードで起動する
+  --script <PATH>        ステップ実行のコマンドをファイルから読み込む
+  --database-url <URL>   Event Store の接続先（既定: DATABASE_URL）
+
+destructive operations:
+  --name <NAME>          プロジェクション名
+  --dry-run              計画を表示し、確認トークンを発行する（何も変更しない）
+  --token <TOKEN>        ドライランで発行した確認トークン（実行時は必須）
+  --yes                  確認のプロンプトを省略する（トークンは省略できない）
+
+  確認トークンの署名鍵は ADMIN_CONFIRMATION_SECRET、操作者は
+  EFFECT_ADMIN_AUTH_TOKEN の JWT（JWT_SECRET で検証、admin ロールのみ）から取得する";
+
+/// イベントの読み込み元
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamSource {
+    EventStore { aggregate_id: Uuid },
+    File(PathBuf),
+}
+
+/// 畳み込みの実行モード
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FoldMode {
+    /// 最終状態を出力する
+    Final,
+    /// 端末からコマンドを読み込む
+    Interactive,
+    /// ファイルからコマンドを読み込む
+    Script(PathBuf),
+}
+
+/// `aggregate fold` の引数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoldArgs {
+    pub aggregate_type: String,
+    pub source:         StreamSource,
+    pub format:         OutputFormat,
+    pub mode:           FoldMode,
+    pub database_url:   Option<String>,
+}
+
+/// 破壊的な管理操作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminOp {
+    /// 集約タイプのスナップショットを削除し、次の読み込みで作り直す
+    SnapshotRebuild { aggregate_type: String },
+    /// プロジェクションのチェックポイントを先頭に戻す
+    ProjectionRebuild { projection_name: String },
+}
+
+/// 管理操作の段階
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminOpMode {
+    /// 計画を表示し、確認トークンを発行する
+    DryRun,
+    /// 確認トークンを付けて実行する（`assume_yes` はプロンプトだけを省略する）
+    Execute {
+        token:      String,
+        assume_yes: bool,
+    },
+}
+
+/// 管理操作の引数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminOpArgs {
+    pub operation:    AdminOp,
+    pub mode:         AdminOpMode,
+    pub database_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    AggregateFold(FoldArgs),
+    AdminOperation(AdminOpArgs),
+    Help,
+}
+
+/// 引数を解析する（プログラム名は含めない）
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command> {
+    let mut args = args.into_iter();
+
+    match (args.next().as_deref(), args.next().as_deref()) {
+        (None | Some("help" | "--help" | "-h"), _) => Ok(Command::Help),
+        (Some("aggregate"), Some("fold")) => parse_fold(args),
+        (Some("snapshots"), Some("rebuild")) => parse_admin_op(args, "--type"),
+        (Some("projection"), Some("rebuild")) => parse_admin_op(args, "--name"),
+        (Some(group), sub) => Err(AdminError::Usage(format!(
+            "Unknown command: {group} {}",
+            sub.unwrap_or_default()
+        ))),
+    }
+}
+
+fn parse_fold(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut aggregate_type = None;
+    let mut aggregate_id = None;
+    let mut file = None;
+    let mut format = OutputFormat::Pretty;
+    let mut interactive = false;
+    let mut script = None;
+    let mut database_url = None;
+
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| AdminError::Usage(format!("{flag} requires a value")))
+        };
+        match flag.as_str() {
+            "--type" => aggregate_type = Some(value()?),
+            "--id" => {
+                let id = value()?;
+                aggregate_id = Some(
+                    id.parse::<Uuid>()
+                        .map_err(|_| AdminError::Usage(format!("Invalid aggregate id: {id}")))?,
+                );
+            },
+            "--file" => file = Some(PathBuf::from(value()?)),
+            "--format" => format = value()?.parse()?,
+            "--interactive" | "-i" => interactive = true,
+            "--script" => script = Some(PathBuf::from(value()?)),
+            "--database-url" => database_url = Some(value()?),
+            "--help" | "-h" => return Ok(Command::Help),
+            other => return Err(AdminError::Usage(format!("Unknown option: {other}"))),
+        }
+    }
+
+    let aggregate_type =
+        aggregate_type.ok_or_else(|| AdminError::Usage("--type is required".to_string()))?;
+
+    let source = match (aggregate_id, file) {
+        (Some(aggregate_id), None) => StreamSource::EventStore { aggregate_id },
+        (None, Some(path)) => StreamSource::File(path),
+        _ => {
+            return Err(AdminError::Usage(
+                "Specify exactly one of --id or --file".to_string(),
+            ));
+        },
+    };
+
+    let mode = match (interactive, script) {
+        (false, None) => FoldMode::Final,
+        (true, None) => FoldMode::Interactive,
+        (false, Some(path)) => FoldMode::Script(path),
+        (true, Some(_)) => {
+            return Err(AdminError::Usage(
+                "--interactive and --script cannot be combined".to_string(),
+            ));
+        },
+    };
+
+    Ok(Command::AggregateFold(FoldArgs {
+        aggregate_type,
+        source,
+        format,
+        mode,
+        database_url,
+    }))
+}
+
+/// 管理操作の引数を解析する（`target_flag` は操作の対象を指定するオプション）
+fn parse_admin_op(mut args: impl Iterator<Item = String>, target_flag: &str) -> Result<Command> {
+    let mut target = None;
+    let mut dry_run = false;
+    let mut token = None;
+    let mut assume_yes = false;
+    let mut database_url = None;
+
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| AdminError::Usage(format!("{flag} requires a value")))
+        };
+        match flag.as_str() {
+            f if f == target_flag => target = Some(value()?),
+            "--dry-run" => dry_run = true,
+            "--token" => token = Some(value()?),
+            "--yes" | "-y" => assume_yes = true,
+            "--database-url" => database_url = Some(value()?),
+            "--help" | "-h" => return Ok(Command::Help),
+            other => return Err(AdminError::Usage(format!("Unknown option: {other}"))),
+        }
+    }
+
+    let target = target.ok_or_else(|| AdminError::Usage(format!("{target_flag} is required")))?;
+    let operation = match target_flag {
+        "--type" => AdminOp::SnapshotRebuild {
+            aggregate_type: target,
+        },
+        _ => AdminOp::ProjectionRebuild {
+            projection_name: target,
+        },
+    };
+
+    let mode = match (dry_run, token) {
+        (true, None) => AdminOpMode::DryRun,
+        (false, Some(token)) => AdminOpMode::Execute { token, assume_yes },
+        (true, Some(_)) => {
+            return Err(AdminError::Usage(
+                "--dry-run and --token cannot be combined".to_string(),
+            ));
+        },
+        (false, None) => {
+            return Err(AdminError::Usage(
+                "--token is required; run with --dry-run first to obtain one".to_string(),
+            ));
+        },
+    };
+
+    Ok(Command::AdminOperation(AdminOpArgs {
+        operation,
+        mode,
+        database_url,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &str) -> Result<Command> {
+        parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn test_parse_fold_from_file_with_script() {
+        let command = parse_args(
+            "aggregate fold --type vocabulary_item --file stream.ndjson --format diff --script \
+             steps.txt",
+        )
+        .unwrap();
+
+        assert_eq!(
+            command,
+            Command::AggregateFold(FoldArgs {
+                aggregate_type: "vocabulary_item".to_string(),
+                source:         StreamSource::File(PathBuf::from("stream.ndjson")),
+                format:         OutputFormat::DiffOnly,
+                mode:           FoldMode::Script(PathBuf::from("steps.txt")),
+                database_url:   None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_fold_from_event_store() {
+        let id = Uuid::new_v4();
+        let Command::AggregateFold(args) =
+            parse_args(&format!("aggregate fold --type progress --id {id} -i")).unwrap()
+        else {
+            panic!("expected fold command");
+        };
+
+        assert_eq!(args.source, StreamSource::EventStore { aggregate_id: id });
+        assert_eq!(args.mode, FoldMode::Interactive);
+        assert_eq!(args.format, OutputFormat::Pretty);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_combinations() {
+        assert!(parse_args("aggregate fold --file a.ndjson").is_err());
+        assert!(parse_args("aggregate fold --type progress").is_err());
+        assert!(parse_args("aggregate fold --type progress --file a --id x").is_err());
+        assert!(parse_args("aggregate fold --type progress --file a -i --script s").is_err());
+        assert!(parse_args("aggregate replay").is_err());
+        assert_eq!(parse_args("").unwrap(), Command::Help);
+    }
+
+    #[test]
+    fn test_parse_admin_operation_modes() {
+        assert_eq!(
+            parse_args("snapshots rebuild --type VocabularyItem --dry-run").unwrap(),
+            Command::AdminOperation(AdminOpArgs {
+                operation:    AdminOp::SnapshotRebuild {
+                    aggregate_type: "VocabularyItem".to_string(),
+                },
+                mode:         AdminOpMode::DryRun,
+                database_url: None,
+            })
+        );
+
+        let Command::AdminOperation(args) =
+            parse_args("projection rebuild --name progress_projection --token abc --yes").unwrap()
+        else {
+            panic!("expected admin operation");
+        };
+        assert_eq!(
+            args.operation,
+            AdminOp::ProjectionRebuild {
+                projection_name: "progress_projection".to_string(),
+            }
+        );
+        assert_eq!(
+            args.mode,
+            AdminOpMode::Execute {
+                token:      "abc".to_string(),
+                assume_yes: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_admin_operation_requires_token_even_with_yes() {
+        assert!(parse_args("snapshots rebuild --type Progress --yes").is_err());
+        assert!(parse_args("snapshots rebuild --type Progress --dry-run --token abc").is_err());
+        assert!(parse_args("snapshots rebuild --name Progress --dry-run").is_err());
+        assert!(parse_args("projection rebuild --dry-run").is_err());
+    }
+}
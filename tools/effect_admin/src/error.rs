@@ -0,0 +1,39 @@
+use shared_admin_ops::AdminOpsError;
+use shared_event_store::EventStoreError;
+use shared_security::SecurityError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AdminError {
+    #[error("Usage error: {0}")]
+    Usage(String),
+
+    #[error("Unknown aggregate type: {0}")]
+    UnknownAggregateType(String),
+
+    #[error("Invalid event stream at line {line}: {message}")]
+    InvalidStream { line: usize, message: String },
+
+    #[error("Version {requested} is out of range (0..={latest})")]
+    VersionOutOfRange { requested: u32, latest: u32 },
+
+    #[error("Admin operation error: {0}")]
+    AdminOperation(#[from] AdminOpsError),
+
+    #[error("Authentication error: {0}")]
+    Authentication(#[from] SecurityError),
+
+    #[error("Event store error: {0}")]
+    EventStore(#[from] EventStoreError),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, AdminError>;
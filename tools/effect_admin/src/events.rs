@@ -0,0 +1,113 @@
+//! 畳み込みの入力となるイベントストリームの読み込み
+
+use std::io::BufRead;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use shared_event_store::{EventStore, StoredEvent};
+use uuid::Uuid;
+
+use crate::error::{AdminError, Result};
+
+/// 集約のストリーム上の1イベント
+///
+/// エクスポートした NDJSON の1行もこの形式で表す
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub aggregate_id:   Uuid,
+    pub aggregate_type: String,
+    pub version:        u32,
+    pub event_type:     String,
+    pub event_data:     Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub occurred_at:    Option<DateTime<Utc>>,
+}
+
+impl From<StoredEvent> for RecordedEvent {
+    fn from(event: StoredEvent) -> Self {
+        Self {
+            aggregate_id:   event.aggregate_id,
+            aggregate_type: event.aggregate_type,
+            version:        event.event_version,
+            event_type:     event.event_type,
+            event_data:     event.event_data,
+            occurred_at:    Some(event.occurred_at),
+        }
+    }
+}
+
+/// Event Store から集約のイベントを読み込む
+pub async fn load_from_store(
+    store: &dyn EventStore,
+    aggregate_type: &str,
+    aggregate_id: Uuid,
+) -> Result<Vec<RecordedEvent>> {
+    let events = store
+        .load_events(aggregate_id, aggregate_type, None)
+        .await?;
+    Ok(sort_by_version(
+        events.into_iter().map(RecordedEvent::from).collect(),
+    ))
+}
+
+/// エクスポートした NDJSON ストリームを読み込む
+///
+/// 空行は無視し、イベントはバージョン順に並べ替える
+pub fn read_ndjson(reader: impl BufRead) -> Result<Vec<RecordedEvent>> {
+    let mut events = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event = serde_json::from_str::<RecordedEvent>(&line).map_err(|e| {
+            AdminError::InvalidStream {
+                line:    index + 1,
+                message: e.to_string(),
+            }
+        })?;
+        events.push(event);
+    }
+    Ok(sort_by_version(events))
+}
+
+fn sort_by_version(mut events: Vec<RecordedEvent>) -> Vec<RecordedEvent> {
+    events.sort_by_key(|e| e.version);
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_ndjson_skips_blank_lines_and_sorts_by_version() {
+        let id = Uuid::new_v4();
+        let input = format!(
+            r#"{{"aggregate_id":"{id}","aggregate_type":"counter","version":2,"event_type":"Added","event_data":{{"amount":2}}}}
+
+{{"aggregate_id":"{id}","aggregate_type":"counter","version":1,"event_type":"Added","event_data":{{"amount":1}},"occurred_at":"2025-08-01T00:00:00Z"}}
+"#
+        );
+
+        let events = read_ndjson(input.as_bytes()).unwrap();
+
+        assert_eq!(
+            events.iter().map(|e| e.version).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(events[0].occurred_at.is_some());
+        assert!(events[1].occurred_at.is_none());
+    }
+
+    #[test]
+    fn test_read_ndjson_reports_line_of_invalid_event() {
+        let input = "\n{\"version\": 1}\n";
+
+        match read_ndjson(input.as_bytes()) {
+            Err(AdminError::InvalidStream { line, .. }) => assert_eq!(line, 2),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! 集約の畳み込みとステップ実行
+//!
+//! 各サービスが公開する純粋な畳み込み関数を `AggregateFold` として登録し、
+//! イベントを1件ずつ適用して途中の状態を JSON で保持する。
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    error::{AdminError, Result},
+    events::RecordedEvent,
+    json_patch::{self, PatchOperation},
+};
+
+/// イベント1件を畳み込んだ結果
+#[derive(Debug, Clone, PartialEq)]
+pub enum FoldStep {
+    /// 適用後の状態
+    Applied(Value),
+    /// 畳み込み関数が扱わないイベント（状態は変えずに読み飛ばす）
+    Unrecognized(String),
+}
+
+/// 集約の畳み込み関数
+///
+/// 状態は JSON で受け渡し、集約の型に依存しない形でステップ実行や差分表示を行う
+pub trait AggregateFold: Send + Sync {
+    /// 対象の集約タイプ（`--type` で指定する名前）
+    fn aggregate_type(&self) -> &'static str;
+
+    /// イベントを適用する前の状態
+    fn initial_state(&self, aggregate_id: Uuid) -> Value;
+
+    /// イベントを1件適用する
+    fn apply(&self, state: &Value, event: &RecordedEvent) -> FoldStep;
+}
+
+/// 集約タイプごとの畳み込み関数の登録先
+#[derive(Default)]
+pub struct FoldRegistry {
+    folds: BTreeMap<&'static str, Box<dyn AggregateFold>>,
+}
+
+impl FoldRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 有効なフィーチャーのサービスが提供する畳み込み関数を登録する
+    pub fn with_service_folds() -> Self {
+        #[allow(unused_mut)]
+        let mut registry = Self::new();
+        #[cfg(feature = "vocabulary")]
+        registry.register(crate::folds::vocabulary_item::VocabularyItemFold);
+        #[cfg(feature = "progress")]
+        registry.register(crate::folds::progress::ProgressFold);
+        registry
+    }
+
+    pub fn register(&mut self, fold: impl AggregateFold + 'static) {
+        self.folds.insert(fold.aggregate_type(), Box::new(fold));
+    }
+
+    pub fn get(&self, aggregate_type: &str) -> Result<&dyn AggregateFold> {
+        self.folds
+            .get(aggregate_type)
+            .map(|fold| fold.as_ref())
+            .ok_or_else(|| AdminError::UnknownAggregateType(aggregate_type.to_string()))
+    }
+
+    pub fn aggregate_types(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.folds.keys().copied()
+    }
+}
+
+/// 適用したイベントの記録
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StepRecord {
+    pub version:    u32,
+    pub event_type: String,
+    pub applied:    bool,
+    /// 読み飛ばした理由
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped:    Option<String>,
+}
+
+/// イベントを1件ずつ適用した結果と、現在の位置
+///
+/// 位置 `n` は先頭から `n` 件のイベントを適用した状態を表す
+pub struct FoldSession {
+    aggregate_type: String,
+    aggregate_id:   Uuid,
+    states:         Vec<Value>,
+    steps:          Vec<StepRecord>,
+    position:       usize,
+}
+
+impl FoldSession {
+    /// すべてのイベントを畳み込み、各位置の状態を保持する
+    ///
+    /// 位置は先頭（イベント適用前）から始まる
+    pub fn fold(fold: &dyn AggregateFold, aggregate_id: Uuid, events: &[RecordedEvent]) -> Self {
+        let mut states = vec![fold.initial_state(aggregate_id)];
+        let mut steps = Vec::with_capacity(events.len());
+
+        for event in events {
+            let current = states.last().expect("initial state exists");
+            let (next, skipped) = match fold.apply(current, event) {
+                FoldStep::Applied(next) => (next, None),
+                FoldStep::Unrecognized(reason) => (current.clone(), Some(reason)),
+            };
+            steps.push(StepRecord {
+                version: event.version,
+                event_type: event.event_type.clone(),
+                applied: skipped.is_none(),
+                skipped,
+            });
+            states.push(next);
+        }
+
+        Self {
+            aggregate_type: fold.aggregate_type().to_string(),
+            aggregate_id,
+            states,
+            steps,
+            position: 0,
+        }
+    }
+
+    pub fn aggregate_type(&self) -> &str {
+        &self.aggregate_type
+    }
+
+    pub fn aggregate_id(&self) -> Uuid {
+        self.aggregate_id
+    }
+
+    pub fn steps(&self) -> &[StepRecord] {
+        &self.steps
+    }
+
+    /// 読み飛ばしたイベント
+    pub fn skipped(&self) -> impl Iterator<Item = &StepRecord> {
+        self.steps.iter().filter(|step| !step.applied)
+    }
+
+    /// 現在の位置までに適用した最後のイベントのバージョン（先頭では 0）
+    pub fn current_version(&self) -> u32 {
+        self.version_at(self.position)
+    }
+
+    /// ストリームの最後のバージョン
+    pub fn latest_version(&self) -> u32 {
+        self.version_at(self.steps.len())
+    }
+
+    pub fn current_state(&self) -> &Value {
+        &self.states[self.position]
+    }
+
+    pub fn final_state(&self) -> &Value {
+        self.states.last().expect("initial state exists")
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position == self.steps.len()
+    }
+
+    /// 次のイベントを適用し、その記録と状態の差分を返す
+    pub fn step_forward(&mut self) -> Option<(&StepRecord, Vec<PatchOperation>)> {
+        if self.is_finished() {
+            return None;
+        }
+        let patch = json_patch::diff(&self.states[self.position], &self.states[self.position + 1]);
+        self.position += 1;
+        Some((&self.steps[self.position - 1], patch))
+    }
+
+    /// 指定したバージョンまでを適用した位置へ移動する
+    pub fn jump_to_version(&mut self, version: u32) -> Result<()> {
+        self.position = self.position_of(version)?;
+        Ok(())
+    }
+
+    /// 指定したバージョンまでを適用した状態
+    pub fn state_at_version(&self, version: u32) -> Result<&Value> {
+        Ok(&self.states[self.position_of(version)?])
+    }
+
+    /// 2つのバージョン間の状態の差分
+    pub fn diff(&self, from_version: u32, to_version: u32) -> Result<Vec<PatchOperation>> {
+        Ok(json_patch::diff(
+            self.state_at_version(from_version)?,
+            self.state_at_version(to_version)?,
+        ))
+    }
+
+    fn version_at(&self, position: usize) -> u32 {
+        position
+            .checked_sub(1)
+            .map_or(0, |index| self.steps[index].version)
+    }
+
+    /// バージョン以下のイベントをすべて適用した位置
+    fn position_of(&self, version: u32) -> Result<usize> {
+        let latest = self.latest_version();
+        if version > latest {
+            return Err(AdminError::VersionOutOfRange {
+                requested: version,
+                latest,
+            });
+        }
+        Ok(self.steps.partition_point(|step| step.version <= version))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::events::read_ndjson;
+
+    /// テスト用の畳み込み: `Set` イベントのデータを状態にマージし、
+    /// `Cleared` でフィールドを削除する。それ以外のイベントは扱わない
+    pub(crate) struct MergeFold;
+
+    impl AggregateFold for MergeFold {
+        fn aggregate_type(&self) -> &'static str {
+            "merge"
+        }
+
+        fn initial_state(&self, aggregate_id: Uuid) -> Value {
+            json!({ "id": aggregate_id })
+        }
+
+        fn apply(&self, state: &Value, event: &RecordedEvent) -> FoldStep {
+            let mut state = state.clone();
+            match (event.event_type.as_str(), &event.event_data) {
+                ("Set", Value::Object(fields)) => {
+                    for (key, value) in fields {
+                        state[key] = value.clone();
+                    }
+                },
+                ("Cleared", Value::String(field)) => {
+                    state.as_object_mut().unwrap().remove(field);
+                },
+                (other, _) => return FoldStep::Unrecognized(format!("{other} is not handled")),
+            }
+            FoldStep::Applied(state)
+        }
+    }
+
+    pub(crate) const AGGREGATE_ID: &str = "6f1c2f4e-8a5b-4b7e-9d2a-3c4b5a6d7e8f";
+
+    /// バージョン 1, 2, 4, 5 のイベント（3 は欠番、4 は未知のイベント）
+    pub(crate) fn fixture_session() -> FoldSession {
+        let stream = format!(
+            r#"{{"aggregate_id":"{AGGREGATE_ID}","aggregate_type":"merge","version":1,"event_type":"Set","event_data":{{"status":"Draft","tags":["a"]}}}}
+{{"aggregate_id":"{AGGREGATE_ID}","aggregate_type":"merge","version":2,"event_type":"Set","event_data":{{"status":"Published"}}}}
+{{"aggregate_id":"{AGGREGATE_ID}","aggregate_type":"merge","version":5,"event_type":"Cleared","event_data":"tags"}}
+{{"aggregate_id":"{AGGREGATE_ID}","aggregate_type":"merge","version":4,"event_type":"Archived","event_data":{{}}}}
+"#
+        );
+        let events = read_ndjson(stream.as_bytes()).unwrap();
+        FoldSession::fold(&MergeFold, AGGREGATE_ID.parse().unwrap(), &events)
+    }
+
+    #[test]
+    fn test_fold_fixture_stream_to_final_state() {
+        let session = fixture_session();
+
+        assert_eq!(
+            session.final_state(),
+            &json!({ "id": AGGREGATE_ID, "status": "Published" })
+        );
+        assert_eq!(session.latest_version(), 5);
+        assert_eq!(session.current_version(), 0);
+    }
+
+    #[test]
+    fn test_unrecognized_events_are_skipped_and_reported() {
+        let session = fixture_session();
+
+        let skipped: Vec<_> = session.skipped().collect();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].version, 4);
+        assert_eq!(skipped[0].event_type, "Archived");
+        assert_eq!(
+            skipped[0].skipped.as_deref(),
+            Some("Archived is not handled")
+        );
+        assert_eq!(
+            session.state_at_version(2).unwrap(),
+            session.state_at_version(4).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_next_steps_through_events_with_diff() {
+        let mut session = fixture_session();
+
+        let (step, patch) = session.step_forward().unwrap();
+        assert_eq!(step.version, 1);
+        assert_eq!(patch.len(), 2);
+
+        let (step, patch) = session.step_forward().unwrap();
+        assert_eq!(step.version, 2);
+        assert_eq!(
+            patch,
+            vec![PatchOperation::Replace {
+                path:  "/status".to_string(),
+                value: json!("Published"),
+            }]
+        );
+
+        // 読み飛ばしたイベントは差分なし
+        let (step, patch) = session.step_forward().unwrap();
+        assert!(!step.applied);
+        assert!(patch.is_empty());
+
+        session.step_forward().unwrap();
+        assert!(session.is_finished());
+        assert!(session.step_forward().is_none());
+    }
+
+    #[test]
+    fn test_jump_and_diff_between_versions() {
+        let mut session = fixture_session();
+
+        // 欠番のバージョンは直前までを適用した位置になる
+        session.jump_to_version(3).unwrap();
+        assert_eq!(session.current_version(), 2);
+        assert_eq!(session.current_state()["status"], json!("Published"));
+
+        session.jump_to_version(0).unwrap();
+        assert_eq!(session.current_state(), &json!({ "id": AGGREGATE_ID }));
+
+        assert_eq!(
+            session.diff(2, 5).unwrap(),
+            vec![PatchOperation::Remove {
+                path: "/tags".to_string(),
+            }]
+        );
+
+        assert!(matches!(
+            session.jump_to_version(6),
+            Err(AdminError::VersionOutOfRange {
+                requested: 6,
+                latest:    5,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_registry_rejects_unknown_aggregate_type() {
+        let mut registry = FoldRegistry::new();
+        registry.register(MergeFold);
+
+        assert!(registry.get("merge").is_ok());
+        assert!(matches!(
+            registry.get("unknown"),
+            Err(AdminError::UnknownAggregateType(_))
+        ));
+        assert_eq!(
+            registry.aggregate_types().collect::<Vec<_>>(),
+            vec!["merge"]
+        );
+    }
+}
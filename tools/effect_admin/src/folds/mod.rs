@@ -0,0 +1,32 @@
+//! サービスが提供する畳み込み関数のアダプター
+//!
+//! 各サービスの集約とイベントを JSON と相互変換し、`AggregateFold` として
+//! 登録できるようにする。サービスごとにフィーチャーで有効化する。
+
+#[cfg(feature = "progress")]
+pub mod progress;
+#[cfg(feature = "vocabulary")]
+pub mod vocabulary_item;
+
+#[cfg(any(feature = "vocabulary", feature = "progress"))]
+use serde::de::DeserializeOwned;
+#[cfg(any(feature = "vocabulary", feature = "progress"))]
+use serde_json::Value;
+
+#[cfg(any(feature = "vocabulary", feature = "progress"))]
+use crate::events::RecordedEvent;
+
+/// イベントデータをサービスのイベント型に変換する
+///
+/// サービスのイベントは `type` タグ付きの列挙型のため、タグのない
+/// イベントデータには `event_type` を補ってから変換する
+#[cfg(any(feature = "vocabulary", feature = "progress"))]
+pub(crate) fn decode_event<T: DeserializeOwned>(event: &RecordedEvent) -> Result<T, String> {
+    let mut data = event.event_data.clone();
+    if let Value::Object(fields) = &mut data {
+        fields
+            .entry("type")
+            .or_insert_with(|| Value::String(event.event_type.clone()));
+    }
+    serde_json::from_value(data).map_err(|e| format!("cannot decode {}: {}", event.event_type, e))
+}
@@ -0,0 +1,42 @@
+//! Progress 集約の畳み込み
+
+use progress_command_service::domain::{Progress, ProgressEvent};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::decode_event;
+use crate::{
+    events::RecordedEvent,
+    fold::{AggregateFold, FoldStep},
+};
+
+/// `Progress::apply` による畳み込み（集約 ID はユーザー ID）
+pub struct ProgressFold;
+
+impl AggregateFold for ProgressFold {
+    fn aggregate_type(&self) -> &'static str {
+        "progress"
+    }
+
+    fn initial_state(&self, aggregate_id: Uuid) -> Value {
+        serde_json::to_value(Progress::new(aggregate_id)).unwrap_or(Value::Null)
+    }
+
+    fn apply(&self, state: &Value, event: &RecordedEvent) -> FoldStep {
+        let progress_event = match decode_event::<ProgressEvent>(event) {
+            Ok(progress_event) => progress_event,
+            Err(reason) => return FoldStep::Unrecognized(reason),
+        };
+
+        let mut progress = match serde_json::from_value::<Progress>(state.clone()) {
+            Ok(progress) => progress,
+            Err(e) => return FoldStep::Unrecognized(format!("invalid state: {e}")),
+        };
+        progress.apply(&progress_event);
+
+        match serde_json::to_value(progress) {
+            Ok(next) => FoldStep::Applied(next),
+            Err(e) => FoldStep::Unrecognized(format!("cannot encode state: {e}")),
+        }
+    }
+}
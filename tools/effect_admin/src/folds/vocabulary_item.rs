@@ -0,0 +1,81 @@
+//! VocabularyItem 集約の畳み込み
+
+use serde_json::Value;
+use uuid::Uuid;
+use vocabulary_command_service::domain::{DomainEvent, VocabularyItem};
+
+use super::decode_event;
+use crate::{
+    events::RecordedEvent,
+    fold::{AggregateFold, FoldStep},
+};
+
+/// `VocabularyItem::apply_event` による畳み込み
+///
+/// 作成イベントより前の状態は `null` で表す
+pub struct VocabularyItemFold;
+
+impl AggregateFold for VocabularyItemFold {
+    fn aggregate_type(&self) -> &'static str {
+        "vocabulary_item"
+    }
+
+    fn initial_state(&self, _aggregate_id: Uuid) -> Value {
+        Value::Null
+    }
+
+    fn apply(&self, state: &Value, event: &RecordedEvent) -> FoldStep {
+        let domain_event = match decode_event::<DomainEvent>(event) {
+            Ok(domain_event) => domain_event,
+            Err(reason) => return FoldStep::Unrecognized(reason),
+        };
+
+        let current = match state {
+            Value::Null => None,
+            state => match serde_json::from_value::<VocabularyItem>(state.clone()) {
+                Ok(item) => Some(item),
+                Err(e) => return FoldStep::Unrecognized(format!("invalid state: {e}")),
+            },
+        };
+
+        match VocabularyItem::apply_event(current.as_ref(), &domain_event) {
+            Some(item) => match serde_json::to_value(item) {
+                Ok(next) => FoldStep::Applied(next),
+                Err(e) => FoldStep::Unrecognized(format!("cannot encode state: {e}")),
+            },
+            None => FoldStep::Unrecognized(format!(
+                "{} does not apply to a VocabularyItem in this state",
+                event.event_type
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{events::read_ndjson, fold::FoldSession};
+
+    const FIXTURE: &str = include_str!("../../fixtures/vocabulary_item.ndjson");
+
+    #[test]
+    fn test_fold_vocabulary_item_fixture() {
+        let events = read_ndjson(FIXTURE.as_bytes()).unwrap();
+        let mut session = FoldSession::fold(&VocabularyItemFold, events[0].aggregate_id, &events);
+
+        let state = session.final_state();
+        assert_eq!(state["status"], json!("Published"));
+        assert_eq!(state["spelling"], json!("bank"));
+        assert_eq!(state["version"], json!(5));
+
+        // v3 はサービスが知らないイベント
+        let skipped: Vec<u32> = session.skipped().map(|step| step.version).collect();
+        assert_eq!(skipped, vec![3]);
+
+        session.jump_to_version(2).unwrap();
+        assert_eq!(session.current_state()["status"], json!("PendingAI"));
+        assert!(session.state_at_version(0).unwrap().is_null());
+    }
+}
@@ -0,0 +1,285 @@
+//! JSON Patch（RFC 6902）形式の差分
+//!
+//! 集約の状態を JSON で比較し、ある状態から別の状態への変更を
+//! add / remove / replace の操作列として表す。
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// JSON Patch の操作
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+impl PatchOperation {
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Add { path, .. } | Self::Remove { path } | Self::Replace { path, .. } => path,
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Cannot apply patch at {path}: {reason}")]
+pub struct PatchError {
+    pub path:   String,
+    pub reason: String,
+}
+
+/// `before` を `after` に変換する操作列を返す
+///
+/// 配列は先頭から要素ごとに比較し、末尾の増減は add / remove で表す。
+/// 削除は後ろの要素から順に出力するため、そのまま `apply` できる
+pub fn diff(before: &Value, after: &Value) -> Vec<PatchOperation> {
+    let mut operations = Vec::new();
+    diff_at("", before, after, &mut operations);
+    operations
+}
+
+fn diff_at(path: &str, before: &Value, after: &Value, operations: &mut Vec<PatchOperation>) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            for (key, old) in before {
+                let child = format!("{path}/{}", escape(key));
+                match after.get(key) {
+                    Some(new) => diff_at(&child, old, new, operations),
+                    None => operations.push(PatchOperation::Remove { path: child }),
+                }
+            }
+            for (key, new) in after {
+                if !before.contains_key(key) {
+                    operations.push(PatchOperation::Add {
+                        path:  format!("{path}/{}", escape(key)),
+                        value: new.clone(),
+                    });
+                }
+            }
+        },
+        (Value::Array(before), Value::Array(after)) => {
+            for (index, (old, new)) in before.iter().zip(after).enumerate() {
+                diff_at(&format!("{path}/{index}"), old, new, operations);
+            }
+            for (index, new) in after.iter().enumerate().skip(before.len()) {
+                operations.push(PatchOperation::Add {
+                    path:  format!("{path}/{index}"),
+                    value: new.clone(),
+                });
+            }
+            for index in (after.len()..before.len()).rev() {
+                operations.push(PatchOperation::Remove {
+                    path: format!("{path}/{index}"),
+                });
+            }
+        },
+        _ if before != after => operations.push(PatchOperation::Replace {
+            path:  path.to_string(),
+            value: after.clone(),
+        }),
+        _ => {},
+    }
+}
+
+/// 操作列を順に適用する
+pub fn apply(document: &mut Value, operations: &[PatchOperation]) -> Result<(), PatchError> {
+    for operation in operations {
+        apply_one(document, operation)?;
+    }
+    Ok(())
+}
+
+fn apply_one(document: &mut Value, operation: &PatchOperation) -> Result<(), PatchError> {
+    let path = operation.path();
+    let error = |reason: &str| PatchError {
+        path:   path.to_string(),
+        reason: reason.to_string(),
+    };
+
+    if path.is_empty() {
+        return match operation {
+            PatchOperation::Add { value, .. } | PatchOperation::Replace { value, .. } => {
+                *document = value.clone();
+                Ok(())
+            },
+            PatchOperation::Remove { .. } => Err(error("cannot remove the root")),
+        };
+    }
+
+    let (parent_path, last) = path.rsplit_once('/').ok_or_else(|| error("invalid path"))?;
+    let key = unescape(last);
+    let parent = document
+        .pointer_mut(parent_path)
+        .ok_or_else(|| error("parent does not exist"))?;
+
+    match (parent, operation) {
+        (Value::Object(map), PatchOperation::Add { value, .. }) => {
+            map.insert(key, value.clone());
+        },
+        (Value::Object(map), PatchOperation::Replace { value, .. }) => {
+            let slot = map
+                .get_mut(&key)
+                .ok_or_else(|| error("member does not exist"))?;
+            *slot = value.clone();
+        },
+        (Value::Object(map), PatchOperation::Remove { .. }) => {
+            map.remove(&key)
+                .ok_or_else(|| error("member does not exist"))?;
+        },
+        (Value::Array(items), operation) => {
+            let index = if key == "-" {
+                items.len()
+            } else {
+                key.parse::<usize>()
+                    .map_err(|_| error("invalid array index"))?
+            };
+            match operation {
+                PatchOperation::Add { value, .. } if index <= items.len() => {
+                    items.insert(index, value.clone());
+                },
+                PatchOperation::Replace { value, .. } if index < items.len() => {
+                    items[index] = value.clone();
+                },
+                PatchOperation::Remove { .. } if index < items.len() => {
+                    items.remove(index);
+                },
+                _ => return Err(error("array index out of bounds")),
+            }
+        },
+        _ => return Err(error("parent is not a container")),
+    }
+    Ok(())
+}
+
+/// JSON Pointer のトークンをエスケープする（`~` → `~0`、`/` → `~1`）
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn assert_round_trip(before: Value, after: Value) {
+        let operations = diff(&before, &after);
+        let mut patched = before;
+        apply(&mut patched, &operations).unwrap();
+        assert_eq!(patched, after);
+    }
+
+    #[test]
+    fn test_identical_documents_have_no_operations() {
+        let document = json!({ "status": "Draft", "tags": ["a", "b"] });
+        assert!(diff(&document, &document).is_empty());
+    }
+
+    #[test]
+    fn test_object_members_are_added_removed_and_replaced() {
+        let before = json!({ "status": "Draft", "is_primary": false, "nested": { "a": 1 } });
+        let after = json!({ "status": "Published", "nested": { "a": 1, "b": 2 } });
+
+        // メンバーの順序は serde_json の設定に依存するためパスで並べて比較する
+        let mut operations = diff(&before, &after);
+        operations.sort_by(|a, b| a.path().cmp(b.path()));
+        assert_eq!(
+            operations,
+            vec![
+                PatchOperation::Remove {
+                    path: "/is_primary".to_string(),
+                },
+                PatchOperation::Add {
+                    path:  "/nested/b".to_string(),
+                    value: json!(2),
+                },
+                PatchOperation::Replace {
+                    path:  "/status".to_string(),
+                    value: json!("Published"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arrays_are_compared_by_index() {
+        let before = json!({ "items": [1, 2, 3, 4] });
+        let after = json!({ "items": [1, 5] });
+
+        assert_eq!(
+            diff(&before, &after),
+            vec![
+                PatchOperation::Replace {
+                    path:  "/items/1".to_string(),
+                    value: json!(5),
+                },
+                PatchOperation::Remove {
+                    path: "/items/3".to_string(),
+                },
+                PatchOperation::Remove {
+                    path: "/items/2".to_string(),
+                },
+            ]
+        );
+        assert_round_trip(before.clone(), after.clone());
+        assert_round_trip(after, before);
+    }
+
+    #[test]
+    fn test_keys_are_escaped_as_json_pointer() {
+        let before = json!({});
+        let after = json!({ "a/b": { "c~d": true } });
+
+        assert_eq!(diff(&before, &after)[0].path(), "/a~1b");
+        assert_round_trip(json!({ "a/b": { "c~d": false } }), after);
+    }
+
+    #[test]
+    fn test_type_change_replaces_whole_value() {
+        assert_eq!(
+            diff(&json!({ "v": [1] }), &json!({ "v": { "x": 1 } })),
+            vec![PatchOperation::Replace {
+                path:  "/v".to_string(),
+                value: json!({ "x": 1 }),
+            }]
+        );
+        assert_round_trip(json!(null), json!({ "created": true }));
+    }
+
+    #[test]
+    fn test_operations_serialize_as_rfc6902() {
+        let operation = PatchOperation::Replace {
+            path:  "/status".to_string(),
+            value: json!("Published"),
+        };
+        assert_eq!(
+            serde_json::to_value(&operation).unwrap(),
+            json!({ "op": "replace", "path": "/status", "value": "Published" })
+        );
+    }
+
+    #[test]
+    fn test_apply_reports_missing_member() {
+        let mut document = json!({});
+        let result = apply(
+            &mut document,
+            &[PatchOperation::Remove {
+                path: "/missing".to_string(),
+            }],
+        );
+        assert_eq!(
+            result,
+            Err(PatchError {
+                path:   "/missing".to_string(),
+                reason: "member does not exist".to_string(),
+            })
+        );
+    }
+}
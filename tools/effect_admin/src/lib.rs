@@ -0,0 +1,16 @@
+//! effect-admin - 運用・調査用の管理 CLI
+//!
+//! - aggregate fold: 集約のイベントストリームを畳み込み、途中の状態や
+//!   バージョン間の差分を確認する
+//! - snapshots rebuild / projection rebuild: 破壊的な管理操作。ドライランで
+//!   発行した確認トークンを付けたときだけ実行し、監査イベントを記録する
+
+pub mod cli;
+pub mod error;
+pub mod events;
+pub mod fold;
+pub mod folds;
+pub mod json_patch;
+pub mod ops;
+pub mod output;
+pub mod repl;
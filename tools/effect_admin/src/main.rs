@@ -0,0 +1,171 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    sync::Arc,
+};
+
+use chrono::Utc;
+use effect_admin::{
+    cli::{self, AdminOp, AdminOpArgs, AdminOpMode, Command, FoldArgs, FoldMode, StreamSource},
+    error::{AdminError, Result},
+    events,
+    fold::{FoldRegistry, FoldSession},
+    ops::{
+        projection_rebuild::{PostgresProjectionCheckpoints, ProjectionRebuild},
+        snapshot_rebuild::{PostgresSnapshotCatalog, SnapshotRebuild},
+    },
+    output,
+    repl,
+};
+use shared_admin_ops::{
+    AdminOperation,
+    AdminOperationRunner,
+    ConfirmationSigner,
+    EventStoreAuditLog,
+    Operator,
+};
+use shared_event_store::postgres::PostgresEventStore;
+use sqlx::{PgPool, postgres::PgPoolOptions};
+
+#[tokio::main]
+async fn main() {
+    let command = match cli::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        },
+    };
+
+    let result = match command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        },
+        Command::AggregateFold(args) => aggregate_fold(args).await,
+        Command::AdminOperation(args) => admin_operation(args).await,
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn aggregate_fold(args: FoldArgs) -> Result<()> {
+    let registry = FoldRegistry::with_service_folds();
+    let fold = registry.get(&args.aggregate_type).map_err(|e| {
+        let known: Vec<_> = registry.aggregate_types().collect();
+        AdminError::Usage(format!("{e} (available: {})", known.join(", ")))
+    })?;
+
+    let (aggregate_id, events) = match &args.source {
+        StreamSource::File(path) => {
+            let events = events::read_ndjson(BufReader::new(File::open(path)?))?;
+            let aggregate_id = events
+                .first()
+                .map(|event| event.aggregate_id)
+                .unwrap_or_default();
+            (aggregate_id, events)
+        },
+        StreamSource::EventStore { aggregate_id } => {
+            let store = PostgresEventStore::new(connect(args.database_url.as_deref()).await?);
+            let events =
+                events::load_from_store(&store, &args.aggregate_type, *aggregate_id).await?;
+            (*aggregate_id, events)
+        },
+    };
+
+    let mut session = FoldSession::fold(fold, aggregate_id, &events);
+    let mut stdout = io::stdout().lock();
+
+    match &args.mode {
+        FoldMode::Final => output::write_final(&mut stdout, &session, args.format),
+        FoldMode::Interactive => repl::run(
+            &mut session,
+            io::stdin().lock(),
+            &mut stdout,
+            args.format,
+            true,
+        ),
+        FoldMode::Script(path) => repl::run(
+            &mut session,
+            BufReader::new(File::open(path)?),
+            &mut stdout,
+            args.format,
+            false,
+        ),
+    }
+}
+
+async fn admin_operation(args: AdminOpArgs) -> Result<()> {
+    let secret = required_env("ADMIN_CONFIRMATION_SECRET")?;
+    let pool = connect(args.database_url.as_deref()).await?;
+    let operation: Box<dyn AdminOperation> = match args.operation {
+        AdminOp::SnapshotRebuild { aggregate_type } => Box::new(SnapshotRebuild::new(
+            Arc::new(PostgresSnapshotCatalog::new(pool.clone())),
+            aggregate_type,
+        )),
+        AdminOp::ProjectionRebuild { projection_name } => Box::new(ProjectionRebuild::new(
+            Arc::new(PostgresProjectionCheckpoints::new(pool.clone())),
+            projection_name,
+        )),
+    };
+    let audit = EventStoreAuditLog::new(Arc::new(PostgresEventStore::new(pool)));
+    let runner = AdminOperationRunner::new(ConfirmationSigner::new(secret), Arc::new(audit));
+
+    match args.mode {
+        AdminOpMode::DryRun => {
+            let dry_run = runner.dry_run(operation.as_ref(), Utc::now()).await?;
+            output::write_dry_run(&mut io::stdout().lock(), &dry_run)
+        },
+        AdminOpMode::Execute { token, assume_yes } => {
+            let operator = operator_from_env()?;
+            if !assume_yes {
+                output::write_plan(&mut io::stdout().lock(), &operation.plan().await?)?;
+                if !confirm("Proceed?")? {
+                    println!("aborted");
+                    return Ok(());
+                }
+            }
+
+            // 計画の再作成とトークンの検証は --yes の有無にかかわらず実行時に行う
+            let executed = runner
+                .execute(operation.as_ref(), &token, &operator, Utc::now())
+                .await?;
+            output::write_executed(&mut io::stdout().lock(), &executed)
+        },
+    }
+}
+
+/// 管理者の JWT（EFFECT_ADMIN_AUTH_TOKEN）から操作者を取り出す
+fn operator_from_env() -> Result<Operator> {
+    let token = required_env("EFFECT_ADMIN_AUTH_TOKEN")?;
+    let claims = shared_security::validate_jwt(&token, &required_env("JWT_SECRET")?)?;
+    Ok(Operator::from_claims(&claims)?)
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{prompt} [y/N] ");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+fn required_env(name: &str) -> Result<String> {
+    std::env::var(name).map_err(|_| AdminError::Usage(format!("{name} is required")))
+}
+
+async fn connect(database_url: Option<&str>) -> Result<PgPool> {
+    let database_url = match database_url {
+        Some(url) => url.to_string(),
+        None => std::env::var("DATABASE_URL").map_err(|_| {
+            AdminError::Usage("--database-url or DATABASE_URL is required".to_string())
+        })?,
+    };
+    Ok(PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&database_url)
+        .await?)
+}
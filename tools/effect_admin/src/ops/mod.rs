@@ -0,0 +1,8 @@
+//! 破壊的な管理操作
+//!
+//! いずれも `shared_admin_ops`
+//! の2段階プロトコル（ドライランで確認トークンを発行し、
+//! トークンを付けて実行する）で実行する。
+
+pub mod projection_rebuild;
+pub mod snapshot_rebuild;
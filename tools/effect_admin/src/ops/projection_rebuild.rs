@@ -0,0 +1,248 @@
+//! プロジェクションの再構築
+//!
+//! チェックポイントを先頭に戻し、記録された失敗を削除する。プロジェクション
+//! サービスは次のポーリングで最初からイベントを処理し直す。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shared_admin_ops::{AdminOperation, AdminOpsError, OperationPlan};
+use sqlx::PgPool;
+
+/// プロジェクションのチェックポイントの状態
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CheckpointSummary {
+    /// 処理済みの位置（チェックポイントがなければ 0）
+    pub position: i64,
+    /// 記録された失敗の件数
+    pub failures: i64,
+}
+
+/// プロジェクションのチェックポイントの参照とリセット
+#[async_trait]
+pub trait ProjectionCheckpoints: Send + Sync {
+    async fn summary(&self, projection_name: &str) -> Result<CheckpointSummary, sqlx::Error>;
+
+    /// チェックポイントを先頭に戻し、失敗の記録を削除する
+    async fn reset(&self, projection_name: &str) -> Result<(), sqlx::Error>;
+}
+
+/// プロジェクションサービスの projection_states / projection_failures
+/// テーブル
+///
+/// チェックポイントは `shared_cqrs::PostgresCheckpointStore` が書き込む
+/// projection_states にある。projection_failures を持たないサービスもあるため、
+/// 失敗の記録はテーブルがある場合だけ扱う。
+pub struct PostgresProjectionCheckpoints {
+    pool: PgPool,
+}
+
+impl PostgresProjectionCheckpoints {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl PostgresProjectionCheckpoints {
+    async fn has_failures_table(&self) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar("SELECT to_regclass('projection_failures') IS NOT NULL")
+            .fetch_one(&self.pool)
+            .await
+    }
+}
+
+#[async_trait]
+impl ProjectionCheckpoints for PostgresProjectionCheckpoints {
+    async fn summary(&self, projection_name: &str) -> Result<CheckpointSummary, sqlx::Error> {
+        let position: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(
+                (SELECT last_position FROM projection_states WHERE projection_name = $1),
+                0
+            )
+            "#,
+        )
+        .bind(projection_name)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let failures: i64 = if self.has_failures_table().await? {
+            sqlx::query_scalar(
+                "SELECT COUNT(*) FROM projection_failures WHERE projection_name = $1",
+            )
+            .bind(projection_name)
+            .fetch_one(&self.pool)
+            .await?
+        } else {
+            0
+        };
+
+        Ok(CheckpointSummary { position, failures })
+    }
+
+    async fn reset(&self, projection_name: &str) -> Result<(), sqlx::Error> {
+        let has_failures_table = self.has_failures_table().await?;
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE projection_states
+            SET last_position = 0, last_event_id = NULL, updated_at = NOW()
+            WHERE projection_name = $1
+            "#,
+        )
+        .bind(projection_name)
+        .execute(&mut *tx)
+        .await?;
+
+        if has_failures_table {
+            sqlx::query("DELETE FROM projection_failures WHERE projection_name = $1")
+                .bind(projection_name)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await
+    }
+}
+
+/// `projection rebuild`
+pub struct ProjectionRebuild {
+    checkpoints:     Arc<dyn ProjectionCheckpoints>,
+    projection_name: String,
+}
+
+impl ProjectionRebuild {
+    pub const OPERATION: &'static str = "projection.rebuild";
+
+    pub fn new(
+        checkpoints: Arc<dyn ProjectionCheckpoints>,
+        projection_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            checkpoints,
+            projection_name: projection_name.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl AdminOperation for ProjectionRebuild {
+    async fn plan(&self) -> shared_admin_ops::Result<OperationPlan> {
+        let summary = self
+            .checkpoints
+            .summary(&self.projection_name)
+            .await
+            .map_err(|e| AdminOpsError::Operation(e.to_string()))?;
+
+        Ok(OperationPlan::new(Self::OPERATION, &self.projection_name)
+            .with_step(format!(
+                "reset checkpoint of {} from position {} to 0",
+                self.projection_name, summary.position
+            ))
+            .with_step(format!("delete {} recorded failures", summary.failures))
+            .with_step("the projection replays all events on its next poll")
+            .with_affected("checkpoint_position", summary.position)
+            .with_affected("failures", summary.failures))
+    }
+
+    async fn execute(&self, plan: &OperationPlan) -> shared_admin_ops::Result<String> {
+        self.checkpoints
+            .reset(&self.projection_name)
+            .await
+            .map_err(|e| AdminOpsError::Operation(e.to_string()))?;
+        Ok(format!(
+            "reset {} from position {}",
+            self.projection_name,
+            plan.affected
+                .get("checkpoint_position")
+                .copied()
+                .unwrap_or_default()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Mutex};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct InMemoryCheckpoints {
+        states: Mutex<HashMap<String, CheckpointSummary>>,
+    }
+
+    #[async_trait]
+    impl ProjectionCheckpoints for InMemoryCheckpoints {
+        async fn summary(&self, projection_name: &str) -> Result<CheckpointSummary, sqlx::Error> {
+            Ok(self
+                .states
+                .lock()
+                .unwrap()
+                .get(projection_name)
+                .copied()
+                .unwrap_or_default())
+        }
+
+        async fn reset(&self, projection_name: &str) -> Result<(), sqlx::Error> {
+            self.states
+                .lock()
+                .unwrap()
+                .insert(projection_name.to_string(), CheckpointSummary::default());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plan_reports_checkpoint_position_and_failures() {
+        let checkpoints = Arc::new(InMemoryCheckpoints::default());
+        checkpoints.states.lock().unwrap().insert(
+            "progress_projection".to_string(),
+            CheckpointSummary {
+                position: 1234,
+                failures: 2,
+            },
+        );
+        let operation = ProjectionRebuild::new(checkpoints.clone(), "progress_projection");
+
+        let plan = operation.plan().await.unwrap();
+
+        assert_eq!(plan.operation, "projection.rebuild");
+        assert_eq!(plan.target, "progress_projection");
+        assert_eq!(plan.affected["checkpoint_position"], 1234);
+        assert_eq!(plan.affected["failures"], 2);
+        assert_eq!(
+            plan.steps[0],
+            "reset checkpoint of progress_projection from position 1234 to 0"
+        );
+
+        // 処理が進むと計画のハッシュも変わる
+        checkpoints
+            .states
+            .lock()
+            .unwrap()
+            .get_mut("progress_projection")
+            .unwrap()
+            .position = 1300;
+        let advanced = operation.plan().await.unwrap();
+        assert_ne!(advanced.hash().unwrap(), plan.hash().unwrap());
+
+        let summary = operation.execute(&advanced).await.unwrap();
+        assert_eq!(summary, "reset progress_projection from position 1300");
+        assert_eq!(
+            operation.plan().await.unwrap().affected["checkpoint_position"],
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plan_for_unknown_projection_starts_at_zero() {
+        let operation = ProjectionRebuild::new(Arc::new(InMemoryCheckpoints::default()), "unknown");
+
+        let plan = operation.plan().await.unwrap();
+
+        assert_eq!(plan.affected["checkpoint_position"], 0);
+        assert_eq!(plan.affected["failures"], 0);
+    }
+}
@@ -0,0 +1,189 @@
+//! スナップショットの再構築
+//!
+//! 集約タイプのスナップショットをすべて削除する。スナップショットは次に集約を
+//! 読み込んで保存したときにイベントから作り直される。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shared_admin_ops::{AdminOperation, AdminOpsError, OperationPlan};
+use sqlx::PgPool;
+
+/// 集約タイプのスナップショットの件数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnapshotSummary {
+    pub snapshots:  i64,
+    /// スナップショットを持つ集約の数
+    pub aggregates: i64,
+}
+
+/// スナップショットの参照と削除
+#[async_trait]
+pub trait SnapshotCatalog: Send + Sync {
+    async fn summary(&self, aggregate_type: &str) -> Result<SnapshotSummary, sqlx::Error>;
+
+    /// 削除した件数を返す
+    async fn delete_all(&self, aggregate_type: &str) -> Result<u64, sqlx::Error>;
+}
+
+/// Event Store の snapshots テーブル
+pub struct PostgresSnapshotCatalog {
+    pool: PgPool,
+}
+
+impl PostgresSnapshotCatalog {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SnapshotCatalog for PostgresSnapshotCatalog {
+    async fn summary(&self, aggregate_type: &str) -> Result<SnapshotSummary, sqlx::Error> {
+        let (snapshots, aggregates): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*), COUNT(DISTINCT aggregate_id)
+            FROM snapshots
+            WHERE aggregate_type = $1
+            "#,
+        )
+        .bind(aggregate_type)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(SnapshotSummary {
+            snapshots,
+            aggregates,
+        })
+    }
+
+    async fn delete_all(&self, aggregate_type: &str) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM snapshots WHERE aggregate_type = $1")
+            .bind(aggregate_type)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// `snapshots rebuild`
+pub struct SnapshotRebuild {
+    catalog:        Arc<dyn SnapshotCatalog>,
+    aggregate_type: String,
+}
+
+impl SnapshotRebuild {
+    pub const OPERATION: &'static str = "snapshots.rebuild";
+
+    pub fn new(catalog: Arc<dyn SnapshotCatalog>, aggregate_type: impl Into<String>) -> Self {
+        Self {
+            catalog,
+            aggregate_type: aggregate_type.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl AdminOperation for SnapshotRebuild {
+    async fn plan(&self) -> shared_admin_ops::Result<OperationPlan> {
+        let summary = self
+            .catalog
+            .summary(&self.aggregate_type)
+            .await
+            .map_err(|e| AdminOpsError::Operation(e.to_string()))?;
+
+        Ok(OperationPlan::new(Self::OPERATION, &self.aggregate_type)
+            .with_step(format!(
+                "delete {} snapshots of {} {} aggregates",
+                summary.snapshots, summary.aggregates, self.aggregate_type
+            ))
+            .with_step("snapshots are rebuilt from events on the next load")
+            .with_affected("snapshots", summary.snapshots)
+            .with_affected("aggregates", summary.aggregates))
+    }
+
+    async fn execute(&self, _plan: &OperationPlan) -> shared_admin_ops::Result<String> {
+        let deleted = self
+            .catalog
+            .delete_all(&self.aggregate_type)
+            .await
+            .map_err(|e| AdminOpsError::Operation(e.to_string()))?;
+        Ok(format!(
+            "deleted {deleted} snapshots of {}",
+            self.aggregate_type
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// (集約タイプ, 集約 ID の番号) のスナップショット
+    struct InMemorySnapshotCatalog {
+        snapshots: Mutex<Vec<(String, u32)>>,
+    }
+
+    #[async_trait]
+    impl SnapshotCatalog for InMemorySnapshotCatalog {
+        async fn summary(&self, aggregate_type: &str) -> Result<SnapshotSummary, sqlx::Error> {
+            let snapshots = self.snapshots.lock().unwrap();
+            let mut aggregates: Vec<_> = snapshots
+                .iter()
+                .filter(|(t, _)| t == aggregate_type)
+                .map(|(_, id)| *id)
+                .collect();
+            let count = aggregates.len() as i64;
+            aggregates.sort_unstable();
+            aggregates.dedup();
+
+            Ok(SnapshotSummary {
+                snapshots:  count,
+                aggregates: aggregates.len() as i64,
+            })
+        }
+
+        async fn delete_all(&self, aggregate_type: &str) -> Result<u64, sqlx::Error> {
+            let mut snapshots = self.snapshots.lock().unwrap();
+            let before = snapshots.len();
+            snapshots.retain(|(t, _)| t != aggregate_type);
+            Ok((before - snapshots.len()) as u64)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plan_counts_only_the_target_type_and_matches_execution() {
+        let catalog = Arc::new(InMemorySnapshotCatalog {
+            snapshots: Mutex::new(vec![
+                ("VocabularyItem".to_string(), 1),
+                ("VocabularyItem".to_string(), 1),
+                ("VocabularyItem".to_string(), 2),
+                ("Progress".to_string(), 3),
+            ]),
+        });
+        let operation = SnapshotRebuild::new(catalog.clone(), "VocabularyItem");
+
+        let plan = operation.plan().await.unwrap();
+
+        assert_eq!(plan.operation, "snapshots.rebuild");
+        assert_eq!(plan.target, "VocabularyItem");
+        assert_eq!(plan.affected["snapshots"], 3);
+        assert_eq!(plan.affected["aggregates"], 2);
+        assert_eq!(
+            plan.steps[0],
+            "delete 3 snapshots of 2 VocabularyItem aggregates"
+        );
+        // 計画の作成では削除しない
+        assert_eq!(catalog.snapshots.lock().unwrap().len(), 4);
+
+        let summary = operation.execute(&plan).await.unwrap();
+        assert_eq!(summary, "deleted 3 snapshots of VocabularyItem");
+        assert_eq!(
+            *catalog.snapshots.lock().unwrap(),
+            vec![("Progress".to_string(), 3)]
+        );
+        assert_eq!(operation.plan().await.unwrap().affected["snapshots"], 0);
+    }
+}
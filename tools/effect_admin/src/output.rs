@@ -0,0 +1,292 @@
+//! 畳み込み結果の出力
+
+use std::{io::Write, str::FromStr};
+
+use serde_json::{Value, json};
+use shared_admin_ops::{AdminOperationExecuted, DryRun, OperationOutcome, OperationPlan};
+
+use crate::{
+    error::{AdminError, Result},
+    fold::{FoldSession, StepRecord},
+    json_patch::{self, PatchOperation},
+};
+
+/// 出力形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// 人が読むための整形済みテキスト
+    Pretty,
+    /// 1行1オブジェクトの JSON
+    Json,
+    /// 状態は出さず、JSON Patch の差分だけを出す
+    DiffOnly,
+}
+
+impl FromStr for OutputFormat {
+    type Err = AdminError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            "diff" | "diff-only" => Ok(Self::DiffOnly),
+            other => Err(AdminError::Usage(format!(
+                "Unknown format: {other} (expected pretty, json or diff)"
+            ))),
+        }
+    }
+}
+
+/// 最終状態を出力する
+pub fn write_final(
+    out: &mut impl Write,
+    session: &FoldSession,
+    format: OutputFormat,
+) -> Result<()> {
+    let skipped: Vec<&StepRecord> = session.skipped().collect();
+
+    match format {
+        OutputFormat::Pretty => {
+            writeln!(
+                out,
+                "aggregate: {} {}",
+                session.aggregate_type(),
+                session.aggregate_id()
+            )?;
+            writeln!(
+                out,
+                "version:   {} ({} events, {} skipped)",
+                session.latest_version(),
+                session.steps().len(),
+                skipped.len()
+            )?;
+            for step in &skipped {
+                writeln!(out, "skipped:   {}", describe(step))?;
+            }
+            writeln!(
+                out,
+                "{}",
+                serde_json::to_string_pretty(session.final_state())?
+            )?;
+        },
+        OutputFormat::Json => {
+            let document = json!({
+                "aggregate_type": session.aggregate_type(),
+                "aggregate_id": session.aggregate_id(),
+                "version": session.latest_version(),
+                "state": session.final_state(),
+                "skipped": skipped,
+            });
+            writeln!(out, "{}", serde_json::to_string(&document)?)?;
+        },
+        OutputFormat::DiffOnly => {
+            let mut previous = session.state_at_version(0)?;
+            for step in session.steps() {
+                let current = session.state_at_version(step.version)?;
+                write_step_diff(out, step, &json_patch::diff(previous, current))?;
+                previous = current;
+            }
+        },
+    }
+    Ok(())
+}
+
+/// ステップ実行で適用したイベントを出力する
+pub fn write_step(
+    out: &mut impl Write,
+    step: &StepRecord,
+    patch: &[PatchOperation],
+    state: &Value,
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let document = json!({ "step": step, "patch": patch, "state": state });
+            writeln!(out, "{}", serde_json::to_string(&document)?)?;
+        },
+        // 状態全体は `show` で確認する
+        OutputFormat::Pretty | OutputFormat::DiffOnly => write_step_diff(out, step, patch)?,
+    }
+    Ok(())
+}
+
+/// 指定した位置の状態を出力する
+pub fn write_state(
+    out: &mut impl Write,
+    version: u32,
+    state: &Value,
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Pretty => {
+            writeln!(out, "v{version}")?;
+            writeln!(out, "{}", serde_json::to_string_pretty(state)?)?;
+        },
+        OutputFormat::Json => {
+            let document = json!({ "version": version, "state": state });
+            writeln!(out, "{}", serde_json::to_string(&document)?)?;
+        },
+        // 差分のみの形式では状態を出さず、位置だけを示す
+        OutputFormat::DiffOnly => writeln!(out, "v{version}")?,
+    }
+    Ok(())
+}
+
+/// 2つのバージョン間の差分を出力する
+pub fn write_diff(
+    out: &mut impl Write,
+    from_version: u32,
+    to_version: u32,
+    patch: &[PatchOperation],
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let document = json!({ "from": from_version, "to": to_version, "patch": patch });
+            writeln!(out, "{}", serde_json::to_string(&document)?)?;
+        },
+        OutputFormat::Pretty | OutputFormat::DiffOnly => {
+            writeln!(out, "v{from_version} -> v{to_version}")?;
+            for operation in patch {
+                writeln!(out, "  {}", serde_json::to_string(operation)?)?;
+            }
+        },
+    }
+    Ok(())
+}
+
+/// 管理操作の計画を出力する
+pub fn write_plan(out: &mut impl Write, plan: &OperationPlan) -> Result<()> {
+    writeln!(out, "operation: {} {}", plan.operation, plan.target)?;
+    for step in &plan.steps {
+        writeln!(out, "  - {step}")?;
+    }
+    for (key, count) in &plan.affected {
+        writeln!(out, "  {key}: {count}")?;
+    }
+    writeln!(out, "plan hash: {}", plan.hash()?)?;
+    Ok(())
+}
+
+/// ドライランの結果（計画と確認トークン）を出力する
+pub fn write_dry_run(out: &mut impl Write, dry_run: &DryRun) -> Result<()> {
+    write_plan(out, &dry_run.plan)?;
+    writeln!(out, "token:     {}", dry_run.token.value)?;
+    writeln!(out, "expires:   {}", dry_run.token.expires_at.to_rfc3339())?;
+    Ok(())
+}
+
+/// 実行した管理操作の監査イベントを出力する
+pub fn write_executed(out: &mut impl Write, event: &AdminOperationExecuted) -> Result<()> {
+    match &event.outcome {
+        OperationOutcome::Succeeded { summary } => writeln!(out, "done: {summary}")?,
+        OperationOutcome::Failed { error } => writeln!(out, "failed: {error}")?,
+    }
+    writeln!(
+        out,
+        "audit: {} by {} ({})",
+        event.event_id, event.operator_id, event.operator_role
+    )?;
+    Ok(())
+}
+
+fn write_step_diff(
+    out: &mut impl Write,
+    step: &StepRecord,
+    patch: &[PatchOperation],
+) -> Result<()> {
+    writeln!(out, "{}", describe(step))?;
+    for operation in patch {
+        writeln!(out, "  {}", serde_json::to_string(operation)?)?;
+    }
+    Ok(())
+}
+
+fn describe(step: &StepRecord) -> String {
+    match &step.skipped {
+        Some(reason) => format!(
+            "v{} {} (skipped: {})",
+            step.version, step.event_type, reason
+        ),
+        None => format!("v{} {}", step.version, step.event_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fold::tests::fixture_session;
+
+    fn render(format: OutputFormat) -> String {
+        let mut out = Vec::new();
+        write_final(&mut out, &fixture_session(), format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            "pretty".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Pretty
+        );
+        assert_eq!(
+            "diff".parse::<OutputFormat>().unwrap(),
+            OutputFormat::DiffOnly
+        );
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_json_output_contains_final_state_and_skipped_events() {
+        let output: Value = serde_json::from_str(&render(OutputFormat::Json)).unwrap();
+
+        assert_eq!(output["version"], json!(5));
+        assert_eq!(output["state"]["status"], json!("Published"));
+        assert_eq!(output["skipped"][0]["event_type"], json!("Archived"));
+    }
+
+    #[test]
+    fn test_diff_only_output_lists_each_event() {
+        let output = render(OutputFormat::DiffOnly);
+        let headers: Vec<&str> = output
+            .lines()
+            .filter(|line| line.starts_with('v'))
+            .collect();
+
+        assert_eq!(
+            headers,
+            vec![
+                "v1 Set",
+                "v2 Set",
+                "v4 Archived (skipped: Archived is not handled)",
+                "v5 Cleared",
+            ]
+        );
+        assert!(output.contains(r#"  {"op":"remove","path":"/tags"}"#));
+    }
+
+    #[test]
+    fn test_dry_run_output_shows_plan_and_token() {
+        let plan = OperationPlan::new("snapshots.rebuild", "Progress")
+            .with_step("delete 3 snapshots")
+            .with_affected("snapshots", 3);
+        let token = shared_admin_ops::ConfirmationSigner::new("secret")
+            .issue(&plan.hash().unwrap(), chrono::Utc::now());
+        let mut out = Vec::new();
+
+        write_dry_run(
+            &mut out,
+            &DryRun {
+                plan:  plan.clone(),
+                token: token.clone(),
+            },
+        )
+        .unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.starts_with("operation: snapshots.rebuild Progress\n"));
+        assert!(output.contains("  - delete 3 snapshots\n  snapshots: 3\n"));
+        assert!(output.contains(&format!("plan hash: {}", plan.hash().unwrap())));
+        assert!(output.contains(&format!("token:     {}", token.value)));
+    }
+}
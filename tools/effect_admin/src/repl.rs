@@ -0,0 +1,233 @@
+//! 畳み込みのステップ実行モード
+//!
+//! 端末から対話的に、またはスクリプトファイルからコマンドを読み込み、
+//! イベントを1件ずつ適用しながら途中の状態や差分を確認する。
+
+use std::{
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+use crate::{
+    error::{AdminError, Result},
+    fold::FoldSession,
+    output::{self, OutputFormat},
+};
+
+const HELP: &str = "\
+commands:
+  next [count]        次のイベントを適用して差分を表示
+  jump <version>      指定したバージョンまで適用した位置へ移動
+  diff <from> <to>    バージョン間の差分を JSON Patch で表示
+  show                現在の状態を表示
+  help                このヘルプを表示
+  quit                終了";
+
+/// ステップ実行のコマンド
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplCommand {
+    Next(usize),
+    Jump(u32),
+    Diff(u32, u32),
+    Show,
+    Help,
+    Quit,
+}
+
+impl FromStr for ReplCommand {
+    type Err = AdminError;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+
+        let number = |value: &str| {
+            value
+                .parse::<u32>()
+                .map_err(|_| AdminError::Usage(format!("Invalid number: {value}")))
+        };
+
+        match (command, args.as_slice()) {
+            ("next" | "n", []) => Ok(Self::Next(1)),
+            ("next" | "n", [count]) => Ok(Self::Next(number(count)? as usize)),
+            ("jump" | "j", [version]) => Ok(Self::Jump(number(version)?)),
+            ("diff" | "d", [from, to]) => Ok(Self::Diff(number(from)?, number(to)?)),
+            ("show" | "s", []) => Ok(Self::Show),
+            ("help" | "h" | "?", []) => Ok(Self::Help),
+            ("quit" | "q" | "exit", []) => Ok(Self::Quit),
+            _ => Err(AdminError::Usage(format!(
+                "Unknown command: {line} (type `help` for commands)"
+            ))),
+        }
+    }
+}
+
+/// 入力からコマンドを読み込んで実行する
+///
+/// `prompt` が true のときは対話用のプロンプトを出力する。コマンドの誤りは
+/// エラーとして出力して続行し、入力の終端か `quit` で終了する
+pub fn run(
+    session: &mut FoldSession,
+    input: impl BufRead,
+    out: &mut impl Write,
+    format: OutputFormat,
+    prompt: bool,
+) -> Result<()> {
+    if prompt {
+        writeln!(
+            out,
+            "{} {}: {} events (latest v{}). type `help` for commands",
+            session.aggregate_type(),
+            session.aggregate_id(),
+            session.steps().len(),
+            session.latest_version()
+        )?;
+    }
+
+    let mut lines = input.lines();
+    loop {
+        if prompt {
+            write!(out, "fold v{}> ", session.current_version())?;
+            out.flush()?;
+        }
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match line
+            .parse::<ReplCommand>()
+            .and_then(|command| execute(session, command, out, format))
+        {
+            Ok(true) => {},
+            Ok(false) => break,
+            Err(AdminError::Io(e)) => return Err(AdminError::Io(e)),
+            Err(e) => writeln!(out, "error: {e}")?,
+        }
+    }
+    Ok(())
+}
+
+/// コマンドを実行する。終了する場合は false を返す
+fn execute(
+    session: &mut FoldSession,
+    command: ReplCommand,
+    out: &mut impl Write,
+    format: OutputFormat,
+) -> Result<bool> {
+    match command {
+        ReplCommand::Next(count) => {
+            for _ in 0..count {
+                let Some((step, patch)) = session.step_forward() else {
+                    writeln!(out, "end of stream (v{})", session.latest_version())?;
+                    break;
+                };
+                let step = step.clone();
+                output::write_step(out, &step, &patch, session.current_state(), format)?;
+            }
+        },
+        ReplCommand::Jump(version) => {
+            session.jump_to_version(version)?;
+            output::write_state(
+                out,
+                session.current_version(),
+                session.current_state(),
+                format,
+            )?;
+        },
+        ReplCommand::Diff(from, to) => {
+            let patch = session.diff(from, to)?;
+            output::write_diff(out, from, to, &patch, format)?;
+        },
+        ReplCommand::Show => output::write_state(
+            out,
+            session.current_version(),
+            session.current_state(),
+            format,
+        )?,
+        ReplCommand::Help => writeln!(out, "{HELP}")?,
+        ReplCommand::Quit => return Ok(false),
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fold::tests::fixture_session;
+
+    fn run_script(script: &str, format: OutputFormat) -> String {
+        let mut session = fixture_session();
+        let mut out = Vec::new();
+        run(&mut session, script.as_bytes(), &mut out, format, false).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!("next".parse::<ReplCommand>().unwrap(), ReplCommand::Next(1));
+        assert_eq!("n 3".parse::<ReplCommand>().unwrap(), ReplCommand::Next(3));
+        assert_eq!(
+            "jump 4".parse::<ReplCommand>().unwrap(),
+            ReplCommand::Jump(4)
+        );
+        assert_eq!(
+            "diff 1 5".parse::<ReplCommand>().unwrap(),
+            ReplCommand::Diff(1, 5)
+        );
+        assert!("jump".parse::<ReplCommand>().is_err());
+        assert!("rewind".parse::<ReplCommand>().is_err());
+    }
+
+    #[test]
+    fn test_scripted_next_and_jump() {
+        let output = run_script(
+            "next 2\n# コメントは無視する\njump 4\nshow\nnext 5\n",
+            OutputFormat::DiffOnly,
+        );
+
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec![
+                "v1 Set",
+                r#"  {"op":"add","path":"/status","value":"Draft"}"#,
+                r#"  {"op":"add","path":"/tags","value":["a"]}"#,
+                "v2 Set",
+                r#"  {"op":"replace","path":"/status","value":"Published"}"#,
+                "v4",
+                "v4",
+                "v5 Cleared",
+                r#"  {"op":"remove","path":"/tags"}"#,
+                "end of stream (v5)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scripted_diff_in_json_format() {
+        let output = run_script("diff 0 2\n", OutputFormat::Json);
+        let document: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+
+        assert_eq!(document["from"], 0);
+        assert_eq!(document["to"], 2);
+        assert_eq!(document["patch"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_errors_are_reported_and_script_continues_until_quit() {
+        let output = run_script("jump 9\nbogus\nquit\nnext\n", OutputFormat::Pretty);
+
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec![
+                "error: Version 9 is out of range (0..=5)",
+                "error: Usage error: Unknown command: bogus (type `help` for commands)",
+            ]
+        );
+    }
+}